@@ -7,6 +7,7 @@ dfx build --network=ic user_index
 dfx build --network=ic configuration
 dfx build --network=ic data_backup
 dfx build --network=ic post_cache
+dfx build --network=ic leaderboard
 
 # dfx canister install data_backup --mode reinstall --network ic --argument "(record {
 #   known_principal_ids = opt vec {
@@ -31,3 +32,12 @@ dfx build --network=ic post_cache
 #     };
 #   };
 # })"
+
+# dfx canister install leaderboard --mode reinstall --network ic --argument "(record {
+#   known_principal_ids = opt vec {
+#     record {
+#       variant { CanisterIdConfiguration };
+#       principal \"$(dfx canister id configuration --network ic)\";
+#     };
+#   };
+# })"
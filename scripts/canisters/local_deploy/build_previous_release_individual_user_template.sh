@@ -0,0 +1,38 @@
+#!/usr/bin/env bash
+set -euo pipefail
+
+usage() {
+  printf "Builds the individual_user_template wasm of the release currently deployed into\n"
+  printf "./target/wasm32-unknown-unknown/previous_release, for the upgrade tests to upgrade from\n\n"
+  printf "Usage: \n[-r Git ref of the previous release, defaults to origin/main] \n[-h Display help] \n";
+  exit 0;
+}
+
+previous_release_ref=origin/main
+
+while getopts "r:h" arg; do
+  case $arg in
+    r)
+      previous_release_ref=$OPTARG
+      ;;
+    h)
+      usage
+      ;;
+  esac
+done
+
+repo_root=$(git rev-parse --show-toplevel)
+worktree_dir=$(mktemp -d)
+target_dir=$(mktemp -d)
+
+cleanup() {
+  git -C "$repo_root" worktree remove --force "$worktree_dir"
+  rm -rf "$target_dir"
+}
+trap cleanup EXIT
+
+git -C "$repo_root" worktree add --detach "$worktree_dir" "$previous_release_ref"
+(cd "$worktree_dir" && cargo build --target wasm32-unknown-unknown --release -p individual_user_template --target-dir "$target_dir")
+
+mkdir -p "$repo_root/target/wasm32-unknown-unknown/previous_release"
+cp "$target_dir/wasm32-unknown-unknown/release/individual_user_template.wasm" "$repo_root/target/wasm32-unknown-unknown/previous_release/"
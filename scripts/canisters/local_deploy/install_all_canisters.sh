@@ -22,6 +22,7 @@ done
 dfx canister create --no-wallet configuration
 dfx canister create --no-wallet data_backup
 dfx canister create --no-wallet individual_user_template
+dfx canister create --no-wallet leaderboard
 dfx canister create --no-wallet post_cache
 dfx canister create --no-wallet user_index
 
@@ -29,6 +30,7 @@ dfx build individual_user_template
 gzip -f -1 ./target/wasm32-unknown-unknown/release/individual_user_template.wasm
 dfx build configuration
 dfx build data_backup
+dfx build leaderboard
 dfx build user_index
 dfx build post_cache
 
@@ -51,6 +53,10 @@ dfx canister install configuration --argument "(record {
       variant { CanisterIdDataBackup };
       principal \"$(dfx canister id data_backup)\";
     };
+    record {
+      variant { CanisterIdLeaderboard };
+      principal \"$(dfx canister id leaderboard)\";
+    };
     record {
       variant { CanisterIdPostCache };
       principal \"$(dfx canister id post_cache)\";
@@ -83,6 +89,10 @@ dfx canister install data_backup --argument "(record {
       variant { CanisterIdDataBackup };
       principal \"$(dfx canister id data_backup)\";
     };
+    record {
+      variant { CanisterIdLeaderboard };
+      principal \"$(dfx canister id leaderboard)\";
+    };
     record {
       variant { CanisterIdPostCache };
       principal \"$(dfx canister id post_cache)\";
@@ -100,6 +110,27 @@ dfx canister install data_backup --argument "(record {
   };
 })"
 
+dfx canister install leaderboard --argument "(record {
+  known_principal_ids = opt vec {
+    record {
+      variant { UserIdGlobalSuperAdmin };
+      principal \"$(dfx identity get-principal)\";
+    };
+    record {
+      variant { CanisterIdConfiguration };
+      principal \"$(dfx canister id configuration)\";
+    };
+    record {
+      variant { CanisterIdLeaderboard };
+      principal \"$(dfx canister id leaderboard)\";
+    };
+    record {
+      variant { CanisterIdUserIndex };
+      principal \"$(dfx canister id user_index)\";
+    };
+  }
+})"
+
 dfx canister install post_cache --argument "(record {
   known_principal_ids = opt vec {
     record {
@@ -114,6 +145,10 @@ dfx canister install post_cache --argument "(record {
       variant { CanisterIdDataBackup };
       principal \"$(dfx canister id data_backup)\";
     };
+    record {
+      variant { CanisterIdLeaderboard };
+      principal \"$(dfx canister id leaderboard)\";
+    };
     record {
       variant { CanisterIdPostCache };
       principal \"$(dfx canister id post_cache)\";
@@ -139,6 +174,10 @@ dfx canister install user_index --argument "(record {
       variant { CanisterIdDataBackup };
       principal \"$(dfx canister id data_backup)\";
     };
+    record {
+      variant { CanisterIdLeaderboard };
+      principal \"$(dfx canister id leaderboard)\";
+    };
     record {
       variant { CanisterIdPostCache };
       principal \"$(dfx canister id post_cache)\";
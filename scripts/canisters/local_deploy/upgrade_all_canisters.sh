@@ -23,6 +23,7 @@ dfx build individual_user_template
 gzip -f -1 ./target/wasm32-unknown-unknown/release/individual_user_template.wasm
 dfx build configuration
 dfx build data_backup
+dfx build leaderboard
 dfx build user_index
 dfx build post_cache
 
@@ -33,5 +34,6 @@ fi
 
 dfx canister install configuration --mode upgrade --argument "(record {})"
 dfx canister install data_backup --mode upgrade --argument "(record {})"
+dfx canister install leaderboard --mode upgrade --argument "(record {})"
 dfx canister install post_cache --mode upgrade --argument "(record {})"
 dfx canister install user_index --mode upgrade --argument "(record {})"
@@ -12,14 +12,8 @@ fn init(init_args: ConfigurationInitArgs) {
 }
 
 fn init_impl(init_args: ConfigurationInitArgs, data: &mut CanisterData) {
-    init_args
-        .known_principal_ids
-        .unwrap_or_default()
-        .iter()
-        .for_each(|(principal_belongs_to, principal_id)| {
-            data.known_principal_ids
-                .insert(*principal_belongs_to, *principal_id);
-        });
+    data.known_principal_ids
+        .extend(init_args.known_principal_ids.unwrap_or_default().iter());
 
     data.signups_enabled = init_args.signups_enabled.unwrap_or(data.signups_enabled);
 }
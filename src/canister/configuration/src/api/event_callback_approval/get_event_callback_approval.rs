@@ -0,0 +1,17 @@
+use candid::Principal;
+use shared_utils::canister_specific::configuration::types::event_callback::EventCallbackApproval;
+
+use crate::CANISTER_DATA;
+
+/// Checked by user canisters when a third party canister registers a callback on them
+#[ic_cdk::query]
+#[candid::candid_method(query)]
+fn get_event_callback_approval(canister_id: Principal) -> Option<EventCallbackApproval> {
+    CANISTER_DATA.with(|canister_data_ref_cell| {
+        canister_data_ref_cell
+            .borrow()
+            .event_callback_approvals
+            .get(&canister_id)
+            .cloned()
+    })
+}
@@ -0,0 +1,2 @@
+pub mod get_event_callback_approval;
+pub mod update_event_callback_approval;
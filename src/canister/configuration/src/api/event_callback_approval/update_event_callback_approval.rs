@@ -0,0 +1,121 @@
+use candid::Principal;
+use shared_utils::{
+    canister_specific::configuration::types::event_callback::EventCallbackApproval,
+    common::types::known_principal::KnownPrincipalType,
+};
+
+use crate::{data::CanisterData, CANISTER_DATA};
+
+/// Approves a third party canister to register event callbacks on user canisters, within the
+/// given events and quota. Pass None to withdraw the approval. Callbacks it already registered
+/// keep running until the user or the registrant revokes them.
+#[ic_cdk::update]
+#[candid::candid_method(update)]
+fn update_event_callback_approval(
+    canister_id: Principal,
+    approval: Option<EventCallbackApproval>,
+) -> Result<(), String> {
+    let api_caller = ic_cdk::caller();
+
+    CANISTER_DATA.with(|canister_data_ref_cell| {
+        let mut canister_data = canister_data_ref_cell.borrow_mut();
+
+        update_event_callback_approval_impl(api_caller, &mut canister_data, canister_id, approval)
+    })
+}
+
+fn update_event_callback_approval_impl(
+    caller: Principal,
+    canister_data: &mut CanisterData,
+    canister_id: Principal,
+    approval: Option<EventCallbackApproval>,
+) -> Result<(), String> {
+    let super_admin = canister_data
+        .known_principal_ids
+        .get(&KnownPrincipalType::UserIdGlobalSuperAdmin)
+        .ok_or("Super admin not found in internal records")?;
+
+    if caller != *super_admin {
+        return Err("Unauthorized".to_string());
+    }
+
+    match approval {
+        Some(approval) => {
+            approval.validate()?;
+            canister_data
+                .event_callback_approvals
+                .insert(canister_id, approval);
+        }
+        None => {
+            canister_data.event_callback_approvals.remove(&canister_id);
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use shared_utils::canister_specific::configuration::types::event_callback::EventCallbackKind;
+    use test_utils::setup::test_constants::{
+        get_global_super_admin_principal_id, get_mock_user_alice_canister_id,
+        get_mock_user_alice_principal_id,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_update_event_callback_approval_impl() {
+        let mut canister_data = CanisterData::default();
+        canister_data.known_principal_ids.insert(
+            KnownPrincipalType::UserIdGlobalSuperAdmin,
+            get_global_super_admin_principal_id(),
+        );
+        let approval = EventCallbackApproval {
+            allowed_events: vec![EventCallbackKind::BetWon],
+            max_callbacks_per_day: 100,
+        };
+
+        assert!(update_event_callback_approval_impl(
+            get_mock_user_alice_principal_id(),
+            &mut canister_data,
+            get_mock_user_alice_canister_id(),
+            Some(approval.clone())
+        )
+        .is_err());
+        assert!(update_event_callback_approval_impl(
+            get_global_super_admin_principal_id(),
+            &mut canister_data,
+            get_mock_user_alice_canister_id(),
+            Some(EventCallbackApproval {
+                allowed_events: vec![],
+                ..approval.clone()
+            })
+        )
+        .is_err());
+        assert!(canister_data.event_callback_approvals.is_empty());
+
+        assert!(update_event_callback_approval_impl(
+            get_global_super_admin_principal_id(),
+            &mut canister_data,
+            get_mock_user_alice_canister_id(),
+            Some(approval.clone())
+        )
+        .is_ok());
+        assert_eq!(
+            canister_data
+                .event_callback_approvals
+                .get(&get_mock_user_alice_canister_id()),
+            Some(&approval)
+        );
+
+        assert!(update_event_callback_approval_impl(
+            get_global_super_admin_principal_id(),
+            &mut canister_data,
+            get_mock_user_alice_canister_id(),
+            None
+        )
+        .is_ok());
+        assert!(canister_data.event_callback_approvals.is_empty());
+    }
+}
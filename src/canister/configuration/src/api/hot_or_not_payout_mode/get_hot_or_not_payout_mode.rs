@@ -0,0 +1,11 @@
+use shared_utils::canister_specific::individual_user_template::types::hot_or_not::HotOrNotPayoutMode;
+
+use crate::CANISTER_DATA;
+
+/// How user canisters pay out the winners of the rooms they settle
+#[ic_cdk::query]
+#[candid::candid_method(query)]
+fn get_hot_or_not_payout_mode() -> HotOrNotPayoutMode {
+    CANISTER_DATA
+        .with(|canister_data_ref_cell| canister_data_ref_cell.borrow().hot_or_not_payout_mode)
+}
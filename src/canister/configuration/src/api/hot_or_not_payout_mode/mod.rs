@@ -0,0 +1,2 @@
+pub mod get_hot_or_not_payout_mode;
+pub mod update_hot_or_not_payout_mode;
@@ -0,0 +1,85 @@
+use candid::Principal;
+use shared_utils::{
+    canister_specific::individual_user_template::types::hot_or_not::HotOrNotPayoutMode,
+    common::types::known_principal::KnownPrincipalType,
+};
+
+use crate::{data::CanisterData, CANISTER_DATA};
+
+/// Switches user canisters between the fixed multiplier and parimutuel payouts. They pick
+/// the new mode up on their next upgrade, and rooms already settled keep the mode they were
+/// paid out in.
+#[ic_cdk::update]
+#[candid::candid_method(update)]
+fn update_hot_or_not_payout_mode(payout_mode: HotOrNotPayoutMode) -> Result<(), String> {
+    let api_caller = ic_cdk::caller();
+
+    CANISTER_DATA.with(|canister_data_ref_cell| {
+        let mut canister_data = canister_data_ref_cell.borrow_mut();
+
+        update_hot_or_not_payout_mode_impl(api_caller, &mut canister_data, payout_mode)
+    })
+}
+
+fn update_hot_or_not_payout_mode_impl(
+    caller: Principal,
+    canister_data: &mut CanisterData,
+    payout_mode: HotOrNotPayoutMode,
+) -> Result<(), String> {
+    let super_admin = canister_data
+        .known_principal_ids
+        .get(&KnownPrincipalType::UserIdGlobalSuperAdmin)
+        .ok_or("Super admin not found in internal records")?;
+
+    if caller != *super_admin {
+        return Err("Unauthorized".to_string());
+    }
+
+    canister_data.hot_or_not_payout_mode = payout_mode;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use test_utils::setup::test_constants::{
+        get_global_super_admin_principal_id, get_mock_user_alice_principal_id,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_update_hot_or_not_payout_mode_impl() {
+        let mut canister_data = CanisterData::default();
+        canister_data.known_principal_ids.insert(
+            KnownPrincipalType::UserIdGlobalSuperAdmin,
+            get_global_super_admin_principal_id(),
+        );
+        assert_eq!(
+            canister_data.hot_or_not_payout_mode,
+            HotOrNotPayoutMode::Multiplier
+        );
+
+        assert!(update_hot_or_not_payout_mode_impl(
+            get_mock_user_alice_principal_id(),
+            &mut canister_data,
+            HotOrNotPayoutMode::Parimutuel,
+        )
+        .is_err());
+        assert_eq!(
+            canister_data.hot_or_not_payout_mode,
+            HotOrNotPayoutMode::Multiplier
+        );
+
+        assert!(update_hot_or_not_payout_mode_impl(
+            get_global_super_admin_principal_id(),
+            &mut canister_data,
+            HotOrNotPayoutMode::Parimutuel,
+        )
+        .is_ok());
+        assert_eq!(
+            canister_data.hot_or_not_payout_mode,
+            HotOrNotPayoutMode::Parimutuel
+        );
+    }
+}
@@ -1,3 +1,6 @@
 pub mod canister_lifecycle;
+pub mod event_callback_approval;
+pub mod hot_or_not_payout_mode;
+pub mod upgrade_window;
 pub mod user_signup;
 pub mod well_known_principal;
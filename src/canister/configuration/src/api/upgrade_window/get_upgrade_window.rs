@@ -0,0 +1,10 @@
+use shared_utils::canister_specific::configuration::types::upgrade_window::UpgradeWindow;
+
+use crate::CANISTER_DATA;
+
+/// When user canisters may be upgraded. None lets upgrades run at any time.
+#[ic_cdk::query]
+#[candid::candid_method(query)]
+fn get_upgrade_window() -> Option<UpgradeWindow> {
+    CANISTER_DATA.with(|canister_data_ref_cell| canister_data_ref_cell.borrow().upgrade_window)
+}
@@ -0,0 +1,2 @@
+pub mod get_upgrade_window;
+pub mod update_upgrade_window;
@@ -0,0 +1,100 @@
+use candid::Principal;
+use shared_utils::{
+    canister_specific::configuration::types::upgrade_window::UpgradeWindow,
+    common::types::known_principal::KnownPrincipalType,
+};
+
+use crate::{data::CanisterData, CANISTER_DATA};
+
+/// Restricts user canister upgrades to a daily window. Pass None to allow them at any time.
+/// A run already underway picks the new window up the next time it starts or resumes.
+#[ic_cdk::update]
+#[candid::candid_method(update)]
+fn update_upgrade_window(upgrade_window: Option<UpgradeWindow>) -> Result<(), String> {
+    let api_caller = ic_cdk::caller();
+
+    CANISTER_DATA.with(|canister_data_ref_cell| {
+        let mut canister_data = canister_data_ref_cell.borrow_mut();
+
+        update_upgrade_window_impl(api_caller, &mut canister_data, upgrade_window)
+    })
+}
+
+fn update_upgrade_window_impl(
+    caller: Principal,
+    canister_data: &mut CanisterData,
+    upgrade_window: Option<UpgradeWindow>,
+) -> Result<(), String> {
+    let super_admin = canister_data
+        .known_principal_ids
+        .get(&KnownPrincipalType::UserIdGlobalSuperAdmin)
+        .ok_or("Super admin not found in internal records")?;
+
+    if caller != *super_admin {
+        return Err("Unauthorized".to_string());
+    }
+
+    if let Some(upgrade_window) = &upgrade_window {
+        upgrade_window.validate()?;
+    }
+
+    canister_data.upgrade_window = upgrade_window;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use test_utils::setup::test_constants::{
+        get_global_super_admin_principal_id, get_mock_user_alice_principal_id,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_update_upgrade_window_impl() {
+        let mut canister_data = CanisterData::default();
+        canister_data.known_principal_ids.insert(
+            KnownPrincipalType::UserIdGlobalSuperAdmin,
+            get_global_super_admin_principal_id(),
+        );
+        let upgrade_window = UpgradeWindow {
+            starts_at_minute_of_day: 2 * 60,
+            ends_at_minute_of_day: 5 * 60,
+            utc_offset_in_minutes: 5 * 60 + 30,
+        };
+
+        assert!(update_upgrade_window_impl(
+            get_mock_user_alice_principal_id(),
+            &mut canister_data,
+            Some(upgrade_window)
+        )
+        .is_err());
+        assert!(update_upgrade_window_impl(
+            get_global_super_admin_principal_id(),
+            &mut canister_data,
+            Some(UpgradeWindow {
+                ends_at_minute_of_day: 2 * 60,
+                ..upgrade_window
+            })
+        )
+        .is_err());
+        assert_eq!(canister_data.upgrade_window, None);
+
+        assert!(update_upgrade_window_impl(
+            get_global_super_admin_principal_id(),
+            &mut canister_data,
+            Some(upgrade_window)
+        )
+        .is_ok());
+        assert_eq!(canister_data.upgrade_window, Some(upgrade_window));
+
+        assert!(update_upgrade_window_impl(
+            get_global_super_admin_principal_id(),
+            &mut canister_data,
+            None
+        )
+        .is_ok());
+        assert_eq!(canister_data.upgrade_window, None);
+    }
+}
@@ -0,0 +1,87 @@
+use candid::Principal;
+use shared_utils::common::types::known_principal::KnownPrincipalType;
+
+use crate::{data::CanisterData, CANISTER_DATA};
+
+use super::update_list_of_well_known_principals::validate_authorization;
+
+/// Adds another instance to a role, e.g. a new post cache shard, leaving its primary as is.
+/// `update_list_of_well_known_principals` on the other hand replaces every instance of the
+/// role.
+#[ic_cdk::update]
+#[candid::candid_method(update)]
+fn add_well_known_principal_instance(
+    principal_type: KnownPrincipalType,
+    principal_value: Principal,
+) -> Result<(), String> {
+    let api_caller = ic_cdk::caller();
+    let super_admin = CANISTER_DATA
+        .with(|canister_data_ref_cell| {
+            canister_data_ref_cell
+                .borrow()
+                .known_principal_ids
+                .get(&KnownPrincipalType::UserIdGlobalSuperAdmin)
+                .cloned()
+        })
+        .ok_or("Super admin not found in internal records")?;
+
+    validate_authorization(&super_admin, &api_caller)?;
+
+    CANISTER_DATA.with(|canister_data_ref_cell| {
+        add_well_known_principal_instance_impl(
+            &mut canister_data_ref_cell.borrow_mut(),
+            principal_type,
+            principal_value,
+        )
+    });
+
+    Ok(())
+}
+
+fn add_well_known_principal_instance_impl(
+    canister_data: &mut CanisterData,
+    principal_type: KnownPrincipalType,
+    principal_value: Principal,
+) {
+    canister_data
+        .known_principal_ids
+        .add_instance(principal_type, principal_value);
+}
+
+#[cfg(test)]
+mod test {
+    use test_utils::setup::test_constants::{
+        get_mock_canister_id_post_cache, get_mock_user_alice_canister_id,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_add_well_known_principal_instance_impl() {
+        let mut canister_data = CanisterData::default();
+        canister_data.known_principal_ids.insert(
+            KnownPrincipalType::CanisterIdPostCache,
+            get_mock_canister_id_post_cache(),
+        );
+
+        add_well_known_principal_instance_impl(
+            &mut canister_data,
+            KnownPrincipalType::CanisterIdPostCache,
+            get_mock_user_alice_canister_id(),
+        );
+
+        assert_eq!(
+            canister_data
+                .known_principal_ids
+                .get(&KnownPrincipalType::CanisterIdPostCache),
+            Some(&get_mock_canister_id_post_cache())
+        );
+        assert_eq!(
+            canister_data
+                .known_principal_ids
+                .get_all(&KnownPrincipalType::CanisterIdPostCache)
+                .len(),
+            2
+        );
+    }
+}
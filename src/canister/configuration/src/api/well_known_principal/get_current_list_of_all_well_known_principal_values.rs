@@ -15,10 +15,7 @@ fn get_current_list_of_all_well_known_principal_values() -> Vec<(KnownPrincipalT
 fn get_current_list_of_all_well_known_principal_values_impl(
     known_principal_ids: &KnownPrincipalMap,
 ) -> Vec<(KnownPrincipalType, Principal)> {
-    known_principal_ids
-        .iter()
-        .map(|(known_principal_type, principal)| (*known_principal_type, *principal))
-        .collect()
+    known_principal_ids.iter().collect()
 }
 
 #[cfg(test)]
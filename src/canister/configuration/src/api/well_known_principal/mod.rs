@@ -1,3 +1,4 @@
+pub mod add_well_known_principal_instance;
 pub mod get_current_list_of_all_well_known_principal_values;
 pub mod get_well_known_principal_value;
 pub mod update_list_of_well_known_principals;
@@ -65,7 +65,10 @@ fn update_list_of_well_known_principals(
     Ok(())
 }
 
-fn validate_authorization(super_admin: &Principal, api_caller: &Principal) -> Result<(), String> {
+pub(super) fn validate_authorization(
+    super_admin: &Principal,
+    api_caller: &Principal,
+) -> Result<(), String> {
     let is_super_admin = api_caller == super_admin;
 
     let is_canister_controller = api::is_controller(api_caller);
@@ -1,8 +1,26 @@
-use candid::{CandidType, Deserialize};
-use shared_utils::common::types::known_principal::KnownPrincipalMap;
+use std::collections::BTreeMap;
+
+use candid::{CandidType, Deserialize, Principal};
+use shared_utils::{
+    canister_specific::{
+        configuration::types::{
+            event_callback::EventCallbackApproval, upgrade_window::UpgradeWindow,
+        },
+        individual_user_template::types::hot_or_not::HotOrNotPayoutMode,
+    },
+    common::types::known_principal::KnownPrincipalMap,
+};
 
 #[derive(Default, CandidType, Deserialize)]
 pub struct CanisterData {
     pub known_principal_ids: KnownPrincipalMap,
     pub signups_enabled: bool,
+    // * None until an admin sets one, upgrades run whenever they are started
+    pub upgrade_window: Option<UpgradeWindow>,
+    // * Third party canisters allowed to register event callbacks on user canisters
+    #[serde(default)]
+    pub event_callback_approvals: BTreeMap<Principal, EventCallbackApproval>,
+    // * Handed to user canisters by the user index on their next upgrade
+    #[serde(default)]
+    pub hot_or_not_payout_mode: HotOrNotPayoutMode,
 }
@@ -3,7 +3,13 @@ use std::cell::RefCell;
 use candid::{export_service, Principal};
 use data::CanisterData;
 use shared_utils::{
-    canister_specific::configuration::types::args::ConfigurationInitArgs,
+    canister_specific::{
+        configuration::types::{
+            args::ConfigurationInitArgs, event_callback::EventCallbackApproval,
+            upgrade_window::UpgradeWindow,
+        },
+        individual_user_template::types::hot_or_not::HotOrNotPayoutMode,
+    },
     common::types::known_principal::KnownPrincipalType,
 };
 
@@ -1,6 +1,9 @@
 use shared_utils::canister_specific::data_backup::types::args::DataBackupInitArgs;
 
-use crate::{data::heap_data::HeapData, CANISTER_DATA};
+use crate::{
+    data::{heap_data::HeapData, memory_layout},
+    CANISTER_DATA,
+};
 
 #[ic_cdk::init]
 #[candid::candid_method(init)]
@@ -8,18 +11,14 @@ fn init(init_args: DataBackupInitArgs) {
     CANISTER_DATA.with(|canister_data_ref_cell| {
         let mut data = canister_data_ref_cell.borrow_mut();
         init_impl(init_args, &mut data.heap_data);
+        memory_layout::register_all_memory_regions(&mut data.heap_data.memory_id_registry)
+            .expect("Failed to register memory regions");
     });
 }
 
 fn init_impl(init_args: DataBackupInitArgs, data: &mut HeapData) {
-    init_args
-        .known_principal_ids
-        .unwrap_or_default()
-        .iter()
-        .for_each(|(principal_belongs_to, principal_id)| {
-            data.known_principal_ids
-                .insert(*principal_belongs_to, *principal_id);
-        });
+    data.known_principal_ids
+        .extend(init_args.known_principal_ids.unwrap_or_default().iter());
 
     init_args
         .access_control_map
@@ -11,6 +11,8 @@ use crate::{
 #[ic_cdk::post_upgrade]
 fn post_upgrade() {
     restore_data_from_stable_memory();
+    register_memory_regions_added_since_last_upgrade();
+    index_user_canister_ids_backed_up_before_the_index_existed();
     refetch_well_known_principals();
 }
 
@@ -35,6 +37,48 @@ fn restore_data_from_stable_memory() {
     });
 }
 
+fn register_memory_regions_added_since_last_upgrade() {
+    // * Trapping here rolls back the upgrade, which is what we want if this wasm
+    // * maps a memory id that was previously handed out to another structure.
+    CANISTER_DATA.with(|canister_data_ref_cell| {
+        memory_layout::register_all_memory_regions(
+            &mut canister_data_ref_cell
+                .borrow_mut()
+                .heap_data
+                .memory_id_registry,
+        )
+        .expect("Memory id assignments conflict with the ones persisted before the upgrade");
+    });
+}
+
+fn index_user_canister_ids_backed_up_before_the_index_existed() {
+    CANISTER_DATA.with(|canister_data_ref_cell| {
+        let mut canister_data = canister_data_ref_cell.borrow_mut();
+        if !canister_data
+            .heap_data
+            .user_canister_id_to_user_principal_id
+            .is_empty()
+        {
+            return;
+        }
+
+        let all_user_canister_ids = canister_data
+            .user_principal_id_to_all_user_data_map
+            .iter()
+            .map(|(_, all_user_data)| {
+                (
+                    all_user_data.user_canister_id,
+                    all_user_data.user_principal_id,
+                )
+            })
+            .collect::<Vec<_>>();
+        canister_data
+            .heap_data
+            .user_canister_id_to_user_principal_id
+            .extend(all_user_canister_ids);
+    });
+}
+
 fn refetch_well_known_principals() {
     ic_cdk_timers::set_timer(Duration::from_nanos(11), || {
         ic_cdk::spawn(update_locally_stored_well_known_principals::update_locally_stored_well_known_principals())
@@ -0,0 +1,147 @@
+use candid::Principal;
+use shared_utils::{
+    canister_specific::individual_user_template::types::post::PostDetailsForFrontend,
+    common::{
+        types::{
+            global_post_ref::GlobalPostRef, known_principal::KnownPrincipalType,
+            storable_principal::StorablePrincipal,
+        },
+        utils::known_caller::authorize_known_caller,
+    },
+};
+
+use crate::{data::memory_layout::CanisterData, CANISTER_DATA};
+
+/// Lets the post cache canister hydrate feed items from the last backup while the
+/// publishing canister is being migrated or can't be reached.
+#[ic_cdk::query]
+#[candid::candid_method(query)]
+fn get_backed_up_post_details_for_frontend(post: GlobalPostRef) -> Option<PostDetailsForFrontend> {
+    let caller_principal_id = ic_cdk::caller();
+
+    CANISTER_DATA.with(|canister_data_ref_cell| {
+        get_backed_up_post_details_for_frontend_impl(
+            &canister_data_ref_cell.borrow(),
+            &caller_principal_id,
+            &post,
+        )
+    })
+}
+
+fn get_backed_up_post_details_for_frontend_impl(
+    canister_data: &CanisterData,
+    caller_principal_id: &Principal,
+    post: &GlobalPostRef,
+) -> Option<PostDetailsForFrontend> {
+    authorize_known_caller(
+        caller_principal_id,
+        &canister_data.heap_data.known_principal_ids,
+        &[KnownPrincipalType::CanisterIdPostCache],
+    )
+    .ok()?;
+
+    let user_principal_id = canister_data
+        .heap_data
+        .user_canister_id_to_user_principal_id
+        .get(&post.canister_id)?;
+
+    canister_data
+        .user_principal_id_to_all_user_data_map
+        .get(&StorablePrincipal(*user_principal_id))?
+        .get_backed_up_post_details_for_frontend(post.post_id)
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::SystemTime;
+
+    use shared_utils::canister_specific::{
+        data_backup::types::all_user_data::{AllUserData, UserOwnedCanisterData},
+        individual_user_template::types::post::{Post, PostDetailsFromFrontend},
+    };
+    use test_utils::setup::test_constants::{
+        get_mock_canister_id_post_cache, get_mock_user_alice_canister_id,
+        get_mock_user_alice_principal_id, get_mock_user_bob_canister_id,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_get_backed_up_post_details_for_frontend_impl() {
+        let mut canister_data = CanisterData::default();
+        canister_data.heap_data.known_principal_ids.insert(
+            KnownPrincipalType::CanisterIdPostCache,
+            get_mock_canister_id_post_cache(),
+        );
+        canister_data
+            .heap_data
+            .user_canister_id_to_user_principal_id
+            .insert(
+                get_mock_user_alice_canister_id(),
+                get_mock_user_alice_principal_id(),
+            );
+
+        let mut alice_data = AllUserData {
+            user_principal_id: get_mock_user_alice_principal_id(),
+            user_canister_id: get_mock_user_alice_canister_id(),
+            canister_data: UserOwnedCanisterData::default(),
+        };
+        alice_data.canister_data.profile.display_name = Some("Alice".to_string());
+        alice_data.canister_data.all_created_posts.insert(
+            0,
+            Post::new(
+                0,
+                &PostDetailsFromFrontend {
+                    description: "alice post 0 - description".to_string(),
+                    hashtags: vec!["alice-tag-0".to_string()],
+                    video_uid: "alice-video-0".to_string(),
+                    creator_consent_for_inclusion_in_hot_or_not: true,
+                    collaborator_shares: None,
+                    title: None,
+                    category: None,
+                },
+                &SystemTime::now(),
+            ),
+        );
+        canister_data.user_principal_id_to_all_user_data_map.insert(
+            StorablePrincipal(get_mock_user_alice_principal_id()),
+            alice_data,
+        );
+
+        assert!(get_backed_up_post_details_for_frontend_impl(
+            &canister_data,
+            &get_mock_user_alice_principal_id(),
+            &GlobalPostRef::new(get_mock_user_alice_canister_id(), 0),
+        )
+        .is_none());
+        assert!(get_backed_up_post_details_for_frontend_impl(
+            &canister_data,
+            &get_mock_canister_id_post_cache(),
+            &GlobalPostRef::new(get_mock_user_bob_canister_id(), 0),
+        )
+        .is_none());
+        assert!(get_backed_up_post_details_for_frontend_impl(
+            &canister_data,
+            &get_mock_canister_id_post_cache(),
+            &GlobalPostRef::new(get_mock_user_alice_canister_id(), 1),
+        )
+        .is_none());
+
+        let post_details = get_backed_up_post_details_for_frontend_impl(
+            &canister_data,
+            &get_mock_canister_id_post_cache(),
+            &GlobalPostRef::new(get_mock_user_alice_canister_id(), 0),
+        )
+        .unwrap();
+        assert_eq!(post_details.video_uid, "alice-video-0");
+        assert_eq!(
+            post_details.created_by_display_name,
+            Some("Alice".to_string())
+        );
+        assert_eq!(
+            post_details.created_by_user_principal_id,
+            get_mock_user_alice_principal_id()
+        );
+        assert!(post_details.hot_or_not_betting_status.is_none());
+    }
+}
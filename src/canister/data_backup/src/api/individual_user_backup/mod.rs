@@ -1,3 +1,4 @@
+pub mod get_backed_up_post_details_for_frontend;
 pub mod receive_all_token_transactions_from_individual_user_canister;
 pub mod receive_all_user_posts_from_individual_user_canister;
 pub mod receive_current_token_balance_from_individual_user_canister;
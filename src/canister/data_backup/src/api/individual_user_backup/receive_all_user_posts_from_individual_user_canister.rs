@@ -66,7 +66,10 @@ fn receive_all_user_posts_from_individual_user_canister_impl(
 
 #[cfg(test)]
 mod test {
-    use std::{collections::HashSet, time::SystemTime};
+    use std::{
+        collections::{BTreeMap, HashSet},
+        time::SystemTime,
+    };
 
     use shared_utils::canister_specific::{
         data_backup::types::all_user_data::{AllUserData, UserOwnedCanisterData},
@@ -104,6 +107,14 @@ mod test {
                 home_feed_score: FeedScore::default(),
                 creator_consent_for_inclusion_in_hot_or_not: true,
                 hot_or_not_details: Some(HotOrNotDetails::default()),
+                minimum_bet_amount: 0,
+                collaborator_shares: vec![],
+                title: None,
+                category: None,
+                boost: None,
+                unresolved_reports: BTreeMap::new(),
+                publish_at: None,
+                is_betting_paused_by_moderator: false,
             },
             Post {
                 id: 1,
@@ -122,6 +133,14 @@ mod test {
                 home_feed_score: FeedScore::default(),
                 creator_consent_for_inclusion_in_hot_or_not: true,
                 hot_or_not_details: Some(HotOrNotDetails::default()),
+                minimum_bet_amount: 0,
+                collaborator_shares: vec![],
+                title: None,
+                category: None,
+                boost: None,
+                unresolved_reports: BTreeMap::new(),
+                publish_at: None,
+                is_betting_paused_by_moderator: false,
             },
         ];
 
@@ -60,6 +60,13 @@ fn receive_profile_details_from_individual_user_canister_impl(
 
     entry_to_insert.canister_data.profile = profile_data.clone();
 
+    canister_data
+        .heap_data
+        .user_canister_id_to_user_principal_id
+        .insert(
+            entry_to_insert.user_canister_id,
+            *canister_owner_principal_id,
+        );
     canister_data.user_principal_id_to_all_user_data_map.insert(
         StorablePrincipal(*canister_owner_principal_id),
         entry_to_insert,
@@ -122,6 +129,13 @@ mod test {
             .user_principal_id_to_all_user_data_map
             .get(&StorablePrincipal(get_mock_user_alice_principal_id()))
             .is_some());
+        assert_eq!(
+            canister_data
+                .heap_data
+                .user_canister_id_to_user_principal_id
+                .get(&get_mock_user_alice_canister_id()),
+            Some(&get_mock_user_alice_principal_id())
+        );
         assert_eq!(
             canister_data
                 .user_principal_id_to_all_user_data_map
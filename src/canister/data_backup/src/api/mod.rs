@@ -2,5 +2,6 @@ pub mod access_control;
 pub mod backup_statistics;
 pub mod canister_lifecycle;
 pub mod individual_user_backup;
+pub mod stable_memory;
 pub mod user_index_backup;
 pub mod well_known_principal;
@@ -0,0 +1,13 @@
+use shared_utils::common::types::memory_id_registry::MemoryAllocationDetail;
+
+use crate::{data::memory_layout, CANISTER_DATA};
+
+#[ic_cdk::query]
+#[candid::candid_method(query)]
+fn get_stable_memory_allocations() -> Vec<MemoryAllocationDetail> {
+    CANISTER_DATA.with(|canister_data_ref_cell| {
+        memory_layout::get_all_memory_allocations(
+            &canister_data_ref_cell.borrow().heap_data.memory_id_registry,
+        )
+    })
+}
@@ -0,0 +1 @@
+pub mod get_stable_memory_allocations;
@@ -1,6 +1,6 @@
 use candid::Principal;
 use ic_cdk::api::call;
-use shared_utils::common::types::known_principal::KnownPrincipalType;
+use shared_utils::common::types::known_principal::{KnownPrincipalMap, KnownPrincipalType};
 
 use crate::CANISTER_DATA;
 
@@ -29,6 +29,6 @@ pub async fn update_locally_stored_well_known_principals() {
         let mut canister_data = canister_data_ref_cell.borrow_mut();
         canister_data.heap_data.known_principal_ids = well_known_principals
             .into_iter()
-            .collect::<std::collections::HashMap<_, _>>();
+            .collect::<KnownPrincipalMap>();
     });
 }
@@ -1,13 +1,18 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 use candid::{CandidType, Deserialize, Principal};
 use serde::Serialize;
 use shared_utils::{
-    access_control::UserAccessRole, common::types::known_principal::KnownPrincipalMap,
+    access_control::UserAccessRole,
+    common::types::{known_principal::KnownPrincipalMap, memory_id_registry::MemoryIdRegistry},
 };
 
 #[derive(Default, CandidType, Deserialize, Serialize)]
 pub struct HeapData {
     pub known_principal_ids: KnownPrincipalMap,
     pub access_control_list: HashMap<Principal, Vec<UserAccessRole>>,
+    #[serde(default)]
+    pub memory_id_registry: MemoryIdRegistry,
+    #[serde(default)]
+    pub user_canister_id_to_user_principal_id: BTreeMap<Principal, Principal>,
 }
@@ -3,12 +3,15 @@ use std::cell::RefCell;
 use candid::Deserialize;
 use ic_stable_structures::{
     memory_manager::{MemoryId, MemoryManager, VirtualMemory},
-    DefaultMemoryImpl, StableBTreeMap,
+    DefaultMemoryImpl, Memory as _, StableBTreeMap,
 };
 use serde::Serialize;
 use shared_utils::{
     canister_specific::data_backup::types::all_user_data::AllUserData,
-    common::types::storable_principal::StorablePrincipal,
+    common::types::{
+        memory_id_registry::{MemoryAllocationDetail, MemoryIdRegistry, MemoryIdRegistryError},
+        storable_principal::StorablePrincipal,
+    },
 };
 
 use super::heap_data::HeapData;
@@ -36,26 +39,94 @@ impl Default for CanisterData {
     }
 }
 
-// * Heap data memory.
-const HEAP_DATA_MEMORY_ID: MemoryId = MemoryId::new(0);
-pub fn get_heap_data_memory() -> Memory {
+// * Every memory region handed out by the memory manager, keyed by the name it is
+// * persisted under in the memory id registry. Add new regions here before using them.
+const ALL_MEMORY_REGIONS: [(&str, u8); 2] = [
+    ("heap_data", HEAP_DATA_MEMORY_ID),
+    (
+        "user_principal_id_to_all_user_data_map",
+        USER_PRINCIPAL_ID_TO_ALL_USER_DATA_MAP_MEMORY_ID,
+    ),
+];
+
+pub fn register_all_memory_regions(
+    memory_id_registry: &mut MemoryIdRegistry,
+) -> Result<(), MemoryIdRegistryError> {
+    ALL_MEMORY_REGIONS
+        .iter()
+        .try_for_each(|(name, memory_id)| memory_id_registry.register(name, *memory_id).map(|_| ()))
+}
+
+pub fn get_all_memory_allocations(
+    memory_id_registry: &MemoryIdRegistry,
+) -> Vec<MemoryAllocationDetail> {
+    memory_id_registry
+        .iter()
+        .map(|(name, memory_id)| MemoryAllocationDetail {
+            name: name.to_string(),
+            memory_id,
+            size_in_pages: get_memory(memory_id).size(),
+        })
+        .collect()
+}
+
+fn get_memory(memory_id: u8) -> Memory {
     MEMORY_MANANGER.with(|memory_manager_ref_cell| {
         memory_manager_ref_cell
             .borrow_mut()
-            .get(HEAP_DATA_MEMORY_ID)
+            .get(MemoryId::new(memory_id))
     })
 }
 
+// * Heap data memory.
+const HEAP_DATA_MEMORY_ID: u8 = 0;
+pub fn get_heap_data_memory() -> Memory {
+    get_memory(HEAP_DATA_MEMORY_ID)
+}
+
 // * User Principal ID to all user data map memory.
-const USER_PRINCIPAL_ID_TO_ALL_USER_DATA_MAP_MEMORY_ID: MemoryId = MemoryId::new(1);
+const USER_PRINCIPAL_ID_TO_ALL_USER_DATA_MAP_MEMORY_ID: u8 = 1;
 pub fn get_user_principal_id_to_all_user_data_map_memory() -> Memory {
-    MEMORY_MANANGER.with(|memory_manager_ref_cell| {
-        memory_manager_ref_cell
-            .borrow_mut()
-            .get(USER_PRINCIPAL_ID_TO_ALL_USER_DATA_MAP_MEMORY_ID)
-    })
+    get_memory(USER_PRINCIPAL_ID_TO_ALL_USER_DATA_MAP_MEMORY_ID)
 }
 fn init_user_principal_id_to_all_user_data_map(
 ) -> StableBTreeMap<StorablePrincipal, AllUserData, Memory> {
     StableBTreeMap::init(get_user_principal_id_to_all_user_data_map_memory())
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_register_all_memory_regions() {
+        let mut memory_id_registry = MemoryIdRegistry::default();
+
+        assert_eq!(register_all_memory_regions(&mut memory_id_registry), Ok(()));
+        // * registering again after an upgrade is a no-op
+        assert_eq!(register_all_memory_regions(&mut memory_id_registry), Ok(()));
+
+        assert_eq!(
+            memory_id_registry.get_name_for_memory_id(HEAP_DATA_MEMORY_ID),
+            Some("heap_data")
+        );
+        assert_eq!(
+            memory_id_registry
+                .get_name_for_memory_id(USER_PRINCIPAL_ID_TO_ALL_USER_DATA_MAP_MEMORY_ID),
+            Some("user_principal_id_to_all_user_data_map")
+        );
+    }
+
+    #[test]
+    fn test_register_all_memory_regions_rejects_reused_memory_id() {
+        let mut memory_id_registry = MemoryIdRegistry::default();
+        memory_id_registry
+            .register(
+                "posts_archive",
+                USER_PRINCIPAL_ID_TO_ALL_USER_DATA_MAP_MEMORY_ID,
+            )
+            .unwrap();
+
+        assert!(register_all_memory_regions(&mut memory_id_registry).is_err());
+    }
+}
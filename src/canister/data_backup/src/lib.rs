@@ -9,9 +9,16 @@ use shared_utils::{
             all_user_data::AllUserData, args::DataBackupInitArgs,
             backup_statistics::BackupStatistics,
         },
-        individual_user_template::types::{post::Post, profile::UserProfile},
+        individual_user_template::types::{
+            post::{Post, PostDetailsForFrontend},
+            profile::UserProfile,
+        },
+    },
+    common::types::{
+        global_post_ref::GlobalPostRef, known_principal::KnownPrincipalType,
+        memory_id_registry::MemoryAllocationDetail,
+        utility_token::token_event::TokenEvent,
     },
-    common::types::{known_principal::KnownPrincipalType, utility_token::token_event::TokenEvent},
 };
 
 mod api;
@@ -0,0 +1,85 @@
+use candid::Principal;
+use shared_utils::{
+    canister_specific::individual_user_template::types::{
+        access_statistics::AnomalousCaller, error::GetAccessStatisticsError,
+    },
+    common::types::known_principal::KnownPrincipalType,
+};
+
+use crate::{data_model::CanisterData, CANISTER_DATA};
+
+/// Callers that went past the configured call count threshold on some method, most recently
+/// flagged first. Meant to be fed to the ban and rate limit systems as evidence.
+///
+/// # Access Control
+/// Only the global super admin can read the access statistics
+#[ic_cdk::query]
+#[candid::candid_method(query)]
+fn get_anomalous_callers() -> Result<Vec<AnomalousCaller>, GetAccessStatisticsError> {
+    let api_caller = ic_cdk::caller();
+
+    CANISTER_DATA.with(|canister_data_ref_cell| {
+        get_anomalous_callers_impl(&canister_data_ref_cell.borrow(), &api_caller)
+    })
+}
+
+fn get_anomalous_callers_impl(
+    canister_data: &CanisterData,
+    api_caller: &Principal,
+) -> Result<Vec<AnomalousCaller>, GetAccessStatisticsError> {
+    if canister_data
+        .known_principal_ids
+        .get(&KnownPrincipalType::UserIdGlobalSuperAdmin)
+        != Some(api_caller)
+    {
+        return Err(GetAccessStatisticsError::Unauthorized);
+    }
+
+    Ok(canister_data.access_statistics.get_anomalous_callers())
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::SystemTime;
+
+    use shared_utils::canister_specific::individual_user_template::types::access_statistics::AccessStatisticsConfig;
+    use test_utils::setup::test_constants::{
+        get_global_super_admin_principal_id, get_mock_user_alice_principal_id,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_get_anomalous_callers_impl() {
+        let mut canister_data = CanisterData::default();
+        canister_data.known_principal_ids.insert(
+            KnownPrincipalType::UserIdGlobalSuperAdmin,
+            get_global_super_admin_principal_id(),
+        );
+        let config = AccessStatisticsConfig {
+            anomalous_call_count_threshold: 1,
+        };
+        for _ in 0..2 {
+            canister_data.access_statistics.record_call(
+                get_mock_user_alice_principal_id(),
+                "boost_post",
+                SystemTime::UNIX_EPOCH,
+                &config,
+            );
+        }
+
+        assert_eq!(
+            get_anomalous_callers_impl(&canister_data, &get_mock_user_alice_principal_id()),
+            Err(GetAccessStatisticsError::Unauthorized)
+        );
+        assert_eq!(
+            get_anomalous_callers_impl(&canister_data, &get_global_super_admin_principal_id()),
+            Ok(vec![AnomalousCaller {
+                caller: get_mock_user_alice_principal_id(),
+                method_name: "boost_post".to_string(),
+                call_count: 2,
+                last_flagged_at: SystemTime::UNIX_EPOCH,
+            }])
+        );
+    }
+}
@@ -0,0 +1,100 @@
+use std::time::SystemTime;
+
+use candid::Principal;
+use shared_utils::{
+    canister_specific::individual_user_template::types::{
+        access_statistics::CallerAccessStatistic, error::GetAccessStatisticsError,
+    },
+    common::{types::known_principal::KnownPrincipalType, utils::system_time},
+};
+
+use crate::{data_model::CanisterData, CANISTER_DATA};
+
+/// Busiest (caller, method) pairs by decayed call count, at most `MAX_TOP_CALLERS_PAGE_SIZE`
+/// of them
+///
+/// # Access Control
+/// Only the global super admin can read the access statistics
+#[ic_cdk::query]
+#[candid::candid_method(query)]
+fn get_top_callers(limit: u64) -> Result<Vec<CallerAccessStatistic>, GetAccessStatisticsError> {
+    let api_caller = ic_cdk::caller();
+
+    CANISTER_DATA.with(|canister_data_ref_cell| {
+        get_top_callers_impl(
+            &canister_data_ref_cell.borrow(),
+            &api_caller,
+            limit,
+            system_time::get_current_system_time_from_ic(),
+        )
+    })
+}
+
+fn get_top_callers_impl(
+    canister_data: &CanisterData,
+    api_caller: &Principal,
+    limit: u64,
+    current_time: SystemTime,
+) -> Result<Vec<CallerAccessStatistic>, GetAccessStatisticsError> {
+    if canister_data
+        .known_principal_ids
+        .get(&KnownPrincipalType::UserIdGlobalSuperAdmin)
+        != Some(api_caller)
+    {
+        return Err(GetAccessStatisticsError::Unauthorized);
+    }
+
+    Ok(canister_data
+        .access_statistics
+        .get_top_callers(current_time, limit))
+}
+
+#[cfg(test)]
+mod test {
+    use shared_utils::canister_specific::individual_user_template::types::access_statistics::AccessStatisticsConfig;
+    use test_utils::setup::test_constants::{
+        get_global_super_admin_principal_id, get_mock_user_alice_principal_id,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_get_top_callers_impl() {
+        let mut canister_data = CanisterData::default();
+        canister_data.profile.principal_id = Some(get_mock_user_alice_principal_id());
+        canister_data.known_principal_ids.insert(
+            KnownPrincipalType::UserIdGlobalSuperAdmin,
+            get_global_super_admin_principal_id(),
+        );
+        canister_data.access_statistics.record_call(
+            get_mock_user_alice_principal_id(),
+            "boost_post",
+            SystemTime::UNIX_EPOCH,
+            &AccessStatisticsConfig::default(),
+        );
+
+        // * not even the profile owner can read who has been calling
+        assert_eq!(
+            get_top_callers_impl(
+                &canister_data,
+                &get_mock_user_alice_principal_id(),
+                10,
+                SystemTime::UNIX_EPOCH
+            ),
+            Err(GetAccessStatisticsError::Unauthorized)
+        );
+        assert_eq!(
+            get_top_callers_impl(
+                &canister_data,
+                &get_global_super_admin_principal_id(),
+                10,
+                SystemTime::UNIX_EPOCH
+            ),
+            Ok(vec![CallerAccessStatistic {
+                caller: get_mock_user_alice_principal_id(),
+                method_name: "boost_post".to_string(),
+                call_count: 1,
+            }])
+        );
+    }
+}
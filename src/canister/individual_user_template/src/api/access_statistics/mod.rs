@@ -0,0 +1,2 @@
+pub mod get_anomalous_callers;
+pub mod get_top_callers;
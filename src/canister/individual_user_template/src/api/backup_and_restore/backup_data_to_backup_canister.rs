@@ -2,7 +2,7 @@ use candid::Principal;
 use ic_cdk::api::call;
 use shared_utils::common::types::known_principal::KnownPrincipalType;
 
-use crate::CANISTER_DATA;
+use crate::{util::call_journal::record_call_in_journal, CANISTER_DATA};
 
 #[ic_cdk::update]
 #[candid::candid_method(update)]
@@ -10,6 +10,8 @@ async fn backup_data_to_backup_canister(
     canister_owner_principal_id: Principal,
     canister_id: Principal,
 ) {
+    record_call_in_journal("backup_data_to_backup_canister");
+
     let api_caller = ic_cdk::caller();
 
     let user_index_canister_principal_id = CANISTER_DATA.with(|canister_data_ref_cell| {
@@ -107,10 +109,19 @@ async fn send_all_created_posts(
     data_backup_canister_id: &Principal,
     canister_owner_principal_id: &Principal,
 ) {
-    let all_created_posts = CANISTER_DATA
-        .with(|canister_data_ref_cell| canister_data_ref_cell.borrow().all_created_posts.clone());
-
-    let all_created_posts_vec = all_created_posts.values().cloned().collect::<Vec<_>>();
+    let all_created_posts_vec = CANISTER_DATA.with(|canister_data_ref_cell| {
+        let canister_data = canister_data_ref_cell.borrow();
+
+        canister_data
+            .all_created_posts
+            .keys()
+            .filter_map(|post_id| {
+                canister_data
+                    .all_created_posts
+                    .get_with_archived_bets(&post_id)
+            })
+            .collect::<Vec<_>>()
+    });
 
     let all_created_posts_chunks = all_created_posts_vec.chunks(CHUNK_SIZE).collect::<Vec<_>>();
 
@@ -140,11 +151,13 @@ async fn send_all_token_data(
         .await
         .expect("Failed to call the receive_current_token_balance_from_individual_user_canister method on the data_backup canister");
 
-    let all_token_transactions = token_data
-        .utility_token_transaction_history
-        .iter()
-        .map(|(token_transaction_id, token_event)| (*token_transaction_id, (*token_event).clone()))
-        .collect::<Vec<_>>();
+    let all_token_transactions = CANISTER_DATA.with(|canister_data_ref_cell| {
+        canister_data_ref_cell
+            .borrow()
+            .token_transaction_history
+            .iter()
+            .collect::<Vec<_>>()
+    });
 
     let all_token_transactions_chunks = all_token_transactions
         .chunks(CHUNK_SIZE)
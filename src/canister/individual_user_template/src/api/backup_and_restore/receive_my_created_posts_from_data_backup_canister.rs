@@ -1,27 +1,36 @@
 use shared_utils::{
     canister_specific::individual_user_template::types::post::Post,
-    common::types::known_principal::KnownPrincipalType,
+    common::{
+        types::known_principal::KnownPrincipalType, utils::known_caller::authorize_known_caller,
+    },
 };
 
-use crate::CANISTER_DATA;
+use crate::{
+    util::{call_journal::record_call_in_journal, certified_post_details::recertify_post_details},
+    CANISTER_DATA,
+};
 
 #[ic_cdk::update]
 #[candid::candid_method(update)]
 fn receive_my_created_posts_from_data_backup_canister(all_posts_chunk_vec: Vec<Post>) {
+    record_call_in_journal("receive_my_created_posts_from_data_backup_canister");
+
     let caller = ic_cdk::caller();
-    let data_backup_canister_id = CANISTER_DATA.with(|canister_data_ref_cell| {
-        canister_data_ref_cell
-            .borrow()
-            .known_principal_ids
-            .get(&KnownPrincipalType::CanisterIdDataBackup)
-            .cloned()
-            .unwrap()
+    let is_caller_data_backup_canister = CANISTER_DATA.with(|canister_data_ref_cell| {
+        authorize_known_caller(
+            &caller,
+            &canister_data_ref_cell.borrow().known_principal_ids,
+            &[KnownPrincipalType::CanisterIdDataBackup],
+        )
+        .is_ok()
     });
 
-    if caller != data_backup_canister_id {
+    if !is_caller_data_backup_canister {
         return;
     }
 
+    let restored_post_ids: Vec<u64> = all_posts_chunk_vec.iter().map(|post| post.id).collect();
+
     CANISTER_DATA.with(|canister_data_ref_cell| {
         let mut canister_data = canister_data_ref_cell.borrow_mut();
 
@@ -29,4 +38,8 @@ fn receive_my_created_posts_from_data_backup_canister(all_posts_chunk_vec: Vec<P
             canister_data.all_created_posts.insert(post.id, post);
         }
     });
+
+    restored_post_ids
+        .into_iter()
+        .for_each(recertify_post_details);
 }
@@ -1,21 +1,25 @@
-use shared_utils::common::types::known_principal::KnownPrincipalType;
+use shared_utils::common::{
+    types::known_principal::KnownPrincipalType, utils::known_caller::authorize_known_caller,
+};
 
-use crate::CANISTER_DATA;
+use crate::{util::call_journal::record_call_in_journal, CANISTER_DATA};
 
 #[ic_cdk::update]
 #[candid::candid_method(update)]
 fn receive_my_utility_token_balance_from_data_backup_canister(token_balance: u64) {
+    record_call_in_journal("receive_my_utility_token_balance_from_data_backup_canister");
+
     let caller = ic_cdk::caller();
-    let data_backup_canister_id = CANISTER_DATA.with(|canister_data_ref_cell| {
-        canister_data_ref_cell
-            .borrow()
-            .known_principal_ids
-            .get(&KnownPrincipalType::CanisterIdDataBackup)
-            .cloned()
-            .unwrap()
+    let is_caller_data_backup_canister = CANISTER_DATA.with(|canister_data_ref_cell| {
+        authorize_known_caller(
+            &caller,
+            &canister_data_ref_cell.borrow().known_principal_ids,
+            &[KnownPrincipalType::CanisterIdDataBackup],
+        )
+        .is_ok()
     });
 
-    if caller != data_backup_canister_id {
+    if !is_caller_data_backup_canister {
         return;
     }
 
@@ -1,25 +1,30 @@
-use shared_utils::common::types::{
-    known_principal::KnownPrincipalType, utility_token::token_event::TokenEvent,
+use shared_utils::common::{
+    types::{known_principal::KnownPrincipalType, utility_token::token_event::TokenEvent},
+    utils::known_caller::authorize_known_caller,
 };
 
-use crate::CANISTER_DATA;
+use crate::{util::call_journal::record_call_in_journal, CANISTER_DATA};
 
 #[ic_cdk::update]
 #[candid::candid_method(update)]
 fn receive_my_utility_token_transaction_history_from_data_backup_canister(
     all_token_events_chunk_vec: Vec<(u64, TokenEvent)>,
 ) {
+    record_call_in_journal(
+        "receive_my_utility_token_transaction_history_from_data_backup_canister",
+    );
+
     let caller = ic_cdk::caller();
-    let data_backup_canister_id = CANISTER_DATA.with(|canister_data_ref_cell| {
-        canister_data_ref_cell
-            .borrow()
-            .known_principal_ids
-            .get(&KnownPrincipalType::CanisterIdDataBackup)
-            .cloned()
-            .unwrap()
+    let is_caller_data_backup_canister = CANISTER_DATA.with(|canister_data_ref_cell| {
+        authorize_known_caller(
+            &caller,
+            &canister_data_ref_cell.borrow().known_principal_ids,
+            &[KnownPrincipalType::CanisterIdDataBackup],
+        )
+        .is_ok()
     });
 
-    if caller != data_backup_canister_id {
+    if !is_caller_data_backup_canister {
         return;
     }
 
@@ -28,8 +33,7 @@ fn receive_my_utility_token_transaction_history_from_data_backup_canister(
 
         for (id, token_event) in all_token_events_chunk_vec {
             canister_data
-                .my_token_balance
-                .utility_token_transaction_history
+                .token_transaction_history
                 .insert(id, token_event);
         }
     });
@@ -0,0 +1,67 @@
+use candid::Principal;
+use shared_utils::common::{
+    types::known_principal::{KnownPrincipalMap, KnownPrincipalType},
+    utils::{
+        call_failure_injection::{self, CallFailureInjectionConfig},
+        known_caller::authorize_known_caller,
+    },
+};
+
+use crate::CANISTER_DATA;
+
+/// Makes the outbound calls of this canister fail or answer late, as configured, until it's
+/// configured again. Only built into canisters compiled with the `call_failure_injection`
+/// feature, for integration tests, so it isn't part of the candid interface.
+///
+/// # Access Control
+/// Only the global super admin
+#[ic_cdk::update]
+#[candid::candid_method(update)]
+fn configure_call_failure_injection(config: CallFailureInjectionConfig) {
+    let api_caller = ic_cdk::caller();
+
+    let is_authorized = CANISTER_DATA.with(|canister_data_ref_cell| {
+        is_authorized_to_configure_call_failure_injection(
+            &canister_data_ref_cell.borrow().known_principal_ids,
+            &api_caller,
+        )
+    });
+
+    if is_authorized {
+        call_failure_injection::configure_call_failure_injection(config);
+    }
+}
+
+fn is_authorized_to_configure_call_failure_injection(
+    known_principal_ids: &KnownPrincipalMap,
+    api_caller: &Principal,
+) -> bool {
+    authorize_known_caller(
+        api_caller,
+        known_principal_ids,
+        &[KnownPrincipalType::UserIdGlobalSuperAdmin],
+    )
+    .is_ok()
+}
+
+#[cfg(test)]
+mod test {
+    use test_utils::setup::spoofed_callers::{
+        assert_only_allowed_callers_are_accepted, get_mock_known_principal_map,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_is_authorized_to_configure_call_failure_injection_with_spoofed_callers() {
+        let known_principal_ids = get_mock_known_principal_map();
+
+        assert_only_allowed_callers_are_accepted(
+            &known_principal_ids,
+            &[KnownPrincipalType::UserIdGlobalSuperAdmin],
+            |caller| {
+                is_authorized_to_configure_call_failure_injection(&known_principal_ids, caller)
+            },
+        );
+    }
+}
@@ -0,0 +1,9 @@
+use shared_utils::common::utils::call_failure_injection::{self, CallFailureInjectionStatus};
+
+/// The call failure injection in place, and how many outbound calls it counted and failed
+/// since it was configured
+#[ic_cdk::query]
+#[candid::candid_method(query)]
+fn get_call_failure_injection_status() -> CallFailureInjectionStatus {
+    call_failure_injection::get_call_failure_injection_status()
+}
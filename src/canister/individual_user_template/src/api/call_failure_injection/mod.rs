@@ -0,0 +1,2 @@
+pub mod configure_call_failure_injection;
+pub mod get_call_failure_injection_status;
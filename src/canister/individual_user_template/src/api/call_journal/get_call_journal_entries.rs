@@ -0,0 +1,89 @@
+use candid::Principal;
+use shared_utils::{
+    canister_specific::individual_user_template::types::{
+        call_journal::CallJournalEntry, error::GetCallJournalEntriesError,
+    },
+    common::types::known_principal::KnownPrincipalType,
+    pagination::Page,
+};
+
+use crate::{data_model::CanisterData, CANISTER_DATA};
+
+/// Calls recorded since the journal was last switched on, oldest first. Recording stops once
+/// the journal holds `MAX_CALL_JOURNAL_ENTRIES` calls.
+///
+/// # Access Control
+/// Only the global super admin can export the journal
+#[ic_cdk::query]
+#[candid::candid_method(query)]
+fn get_call_journal_entries(
+    from_inclusive_index: u64,
+    page_size: u64,
+) -> Result<Page<CallJournalEntry>, GetCallJournalEntriesError> {
+    let api_caller = ic_cdk::caller();
+
+    CANISTER_DATA.with(|canister_data_ref_cell| {
+        get_call_journal_entries_impl(
+            &canister_data_ref_cell.borrow(),
+            &api_caller,
+            from_inclusive_index,
+            page_size,
+        )
+    })
+}
+
+fn get_call_journal_entries_impl(
+    canister_data: &CanisterData,
+    api_caller: &Principal,
+    from_inclusive_index: u64,
+    page_size: u64,
+) -> Result<Page<CallJournalEntry>, GetCallJournalEntriesError> {
+    if canister_data
+        .known_principal_ids
+        .get(&KnownPrincipalType::UserIdGlobalSuperAdmin)
+        != Some(api_caller)
+    {
+        return Err(GetCallJournalEntriesError::Unauthorized);
+    }
+
+    Ok(canister_data
+        .call_journal
+        .get_page(from_inclusive_index, page_size))
+}
+
+#[cfg(test)]
+mod test {
+    use test_utils::setup::test_constants::{
+        get_global_super_admin_principal_id, get_mock_user_alice_principal_id,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_get_call_journal_entries_impl() {
+        let mut canister_data = CanisterData::default();
+        canister_data.profile.principal_id = Some(get_mock_user_alice_principal_id());
+        canister_data.known_principal_ids.insert(
+            KnownPrincipalType::UserIdGlobalSuperAdmin,
+            get_global_super_admin_principal_id(),
+        );
+
+        // * not even the profile owner can read the journal
+        assert_eq!(
+            get_call_journal_entries_impl(
+                &canister_data,
+                &get_mock_user_alice_principal_id(),
+                0,
+                10
+            ),
+            Err(GetCallJournalEntriesError::Unauthorized)
+        );
+        assert!(get_call_journal_entries_impl(
+            &canister_data,
+            &get_global_super_admin_principal_id(),
+            0,
+            10
+        )
+        .is_ok());
+    }
+}
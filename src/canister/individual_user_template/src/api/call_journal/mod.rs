@@ -0,0 +1 @@
+pub mod get_call_journal_entries;
@@ -0,0 +1,101 @@
+use candid::Principal;
+use shared_utils::{
+    canister_specific::individual_user_template::types::{
+        error::GetAppliedMigrationsError, migration_changelog::AppliedMigration,
+    },
+    common::{
+        types::known_principal::KnownPrincipalType, utils::known_caller::authorize_known_caller,
+    },
+    pagination::Page,
+};
+
+use crate::{data_model::CanisterData, CANISTER_DATA};
+
+/// Data migrations this canister has run while upgrading, oldest first
+///
+/// # Access Control
+/// Only the global super admin and the user index, which polls it to follow migrations across
+/// the fleet, can read the changelog
+#[ic_cdk::query]
+#[candid::candid_method(query)]
+fn get_applied_migrations(
+    from_inclusive_index: u64,
+    page_size: u64,
+) -> Result<Page<AppliedMigration>, GetAppliedMigrationsError> {
+    let api_caller = ic_cdk::caller();
+
+    CANISTER_DATA.with(|canister_data_ref_cell| {
+        get_applied_migrations_impl(
+            &canister_data_ref_cell.borrow(),
+            &api_caller,
+            from_inclusive_index,
+            page_size,
+        )
+    })
+}
+
+fn get_applied_migrations_impl(
+    canister_data: &CanisterData,
+    api_caller: &Principal,
+    from_inclusive_index: u64,
+    page_size: u64,
+) -> Result<Page<AppliedMigration>, GetAppliedMigrationsError> {
+    authorize_known_caller(
+        api_caller,
+        &canister_data.known_principal_ids,
+        &[
+            KnownPrincipalType::UserIdGlobalSuperAdmin,
+            KnownPrincipalType::CanisterIdUserIndex,
+        ],
+    )
+    .map_err(|_| GetAppliedMigrationsError::Unauthorized)?;
+
+    Ok(canister_data
+        .migration_changelog
+        .get_page(from_inclusive_index, page_size))
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::SystemTime;
+
+    use test_utils::setup::{
+        spoofed_callers::{assert_only_allowed_callers_are_accepted, get_mock_known_principal_map},
+        test_constants::get_global_super_admin_principal_id,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_get_applied_migrations_impl() {
+        let mut canister_data = CanisterData {
+            known_principal_ids: get_mock_known_principal_map(),
+            ..Default::default()
+        };
+        canister_data.apply_migration(
+            "move_posts_to_stable_post_map",
+            SystemTime::UNIX_EPOCH,
+            || 0,
+            |_| Ok(2),
+        );
+
+        assert_only_allowed_callers_are_accepted(
+            &canister_data.known_principal_ids.clone(),
+            &[
+                KnownPrincipalType::UserIdGlobalSuperAdmin,
+                KnownPrincipalType::CanisterIdUserIndex,
+            ],
+            |caller| get_applied_migrations_impl(&canister_data, caller, 0, 10).is_ok(),
+        );
+
+        let page = get_applied_migrations_impl(
+            &canister_data,
+            &get_global_super_admin_principal_id(),
+            0,
+            10,
+        )
+        .unwrap();
+        assert_eq!(page.total_count, 1);
+        assert_eq!(page.items[0].entities_migrated, 2);
+    }
+}
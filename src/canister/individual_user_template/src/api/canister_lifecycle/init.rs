@@ -1,6 +1,25 @@
-use crate::{data_model::CanisterData, CANISTER_DATA};
+use crate::{
+    api::{
+        metrics::get_event_counters::get_event_counters,
+        post::update_post_add_view_details::enqueue_timer_for_flushing_post_view_buffer,
+    },
+    data_model::{memory, CanisterData},
+    util::{
+        call_journal::set_call_journal_enabled,
+        call_queue::enqueue_timer_for_retrying_queued_calls,
+        cycle_monitor::enqueue_timer_for_monitoring_cycle_balance,
+        feature_usage_push::enqueue_timer_for_pushing_feature_usage_to_user_index,
+        pseudonym_salt::generate_pseudonym_salt_if_missing,
+        replication::enqueue_timer_for_replicating_to_standby,
+        slot_compaction::enqueue_timer_for_compacting_settled_slots,
+        token_ledger_mirror::enqueue_timer_for_mirroring_token_balance_to_ledger,
+    },
+    CANISTER_DATA,
+};
 use shared_utils::{
-    canister_specific::individual_user_template::types::arg::IndividualUserTemplateInitArgs,
+    canister_specific::individual_user_template::types::{
+        arg::IndividualUserTemplateInitArgs, treasury::validate_house_rake_percentage,
+    },
     common::timer::send_metrics::enqueue_timer_for_calling_metrics_rest_api,
 };
 
@@ -12,22 +31,58 @@ fn init(init_args: IndividualUserTemplateInitArgs) {
         init_impl(init_args, &mut data);
     });
 
+    enqueue_timer_for_flushing_post_view_buffer();
+    enqueue_timer_for_pushing_feature_usage_to_user_index();
+    enqueue_timer_for_replicating_to_standby();
+    enqueue_timer_for_retrying_queued_calls();
+    enqueue_timer_for_mirroring_token_balance_to_ledger();
+    enqueue_timer_for_compacting_settled_slots();
+    enqueue_timer_for_monitoring_cycle_balance();
+    generate_pseudonym_salt_if_missing();
     send_canister_metrics();
 }
 
 fn init_impl(init_args: IndividualUserTemplateInitArgs, data: &mut CanisterData) {
-    init_args
-        .known_principal_ids
-        .unwrap_or_default()
-        .iter()
-        .for_each(|(principal_belongs_to, principal_id)| {
-            data.known_principal_ids
-                .insert(*principal_belongs_to, *principal_id);
-        });
+    data.known_principal_ids
+        .extend(init_args.known_principal_ids.unwrap_or_default().iter());
 
     data.profile.principal_id = init_args.profile_owner;
 
     data.configuration.url_to_send_canister_metrics_to = init_args.url_to_send_canister_metrics_to;
+
+    data.configuration.referrer_share_of_bet_winnings_percentage = init_args
+        .referrer_share_of_bet_winnings_percentage
+        .unwrap_or_default();
+
+    let house_rake_percentage = init_args.house_rake_percentage.unwrap_or_default();
+    validate_house_rake_percentage(house_rake_percentage)
+        .expect("Rejected the house rake percentage");
+    data.configuration.house_rake_percentage = house_rake_percentage;
+
+    data.configuration.room_sizing_config = init_args.room_sizing_config.unwrap_or_default();
+
+    data.configuration.post_category_config = init_args.post_category_config.unwrap_or_default();
+
+    data.configuration.signup_bonus_vesting_config =
+        init_args.signup_bonus_vesting_config.unwrap_or_default();
+
+    data.configuration.pot_insurance_config = init_args.pot_insurance_config.unwrap_or_default();
+
+    data.configuration.bet_cancellation_config =
+        init_args.bet_cancellation_config.unwrap_or_default();
+
+    data.configuration.access_statistics_config =
+        init_args.access_statistics_config.unwrap_or_default();
+
+    data.configuration.hot_or_not_payout_mode =
+        init_args.hot_or_not_payout_mode.unwrap_or_default();
+
+    data.binding_epoch = init_args.binding_epoch.unwrap_or_default();
+
+    set_call_journal_enabled(data, init_args.is_call_journal_enabled.unwrap_or_default());
+
+    memory::register_all_memory_regions(&mut data.memory_id_registry)
+        .expect("Memory ids assigned in this wasm conflict with each other");
 }
 
 pub fn send_canister_metrics() {
@@ -40,13 +95,23 @@ pub fn send_canister_metrics() {
     });
 
     if let Some(url_to_send_canister_metrics_to) = url_to_send_canister_metrics_to {
-        enqueue_timer_for_calling_metrics_rest_api(url_to_send_canister_metrics_to);
+        enqueue_timer_for_calling_metrics_rest_api(
+            url_to_send_canister_metrics_to,
+            get_event_counters,
+        );
     }
 }
 
 #[cfg(test)]
 mod test {
-    use shared_utils::common::types::known_principal::{KnownPrincipalMap, KnownPrincipalType};
+    use shared_utils::{
+        canister_specific::individual_user_template::types::{
+            hot_or_not::{HotOrNotPayoutMode, RoomSizingConfig},
+            post::PostCategoryConfig,
+            signup_bonus::SignupBonusVestingConfig,
+        },
+        common::types::known_principal::{KnownPrincipalMap, KnownPrincipalType},
+    };
     use test_utils::setup::test_constants::{
         get_global_super_admin_principal_id, get_mock_canister_id_configuration,
         get_mock_canister_id_user_index, get_mock_user_alice_principal_id,
@@ -79,6 +144,17 @@ mod test {
             url_to_send_canister_metrics_to: Some(
                 "http://metrics-url.com/receive-metrics".to_string(),
             ),
+            referrer_share_of_bet_winnings_percentage: Some(1),
+            house_rake_percentage: Some(1),
+            room_sizing_config: None,
+            post_category_config: None,
+            binding_epoch: Some(2),
+            is_call_journal_enabled: Some(true),
+            signup_bonus_vesting_config: None,
+            pot_insurance_config: None,
+            bet_cancellation_config: None,
+            access_statistics_config: None,
+            hot_or_not_payout_mode: Some(HotOrNotPayoutMode::Parimutuel),
         };
         let mut data = CanisterData::default();
 
@@ -114,5 +190,36 @@ mod test {
             data.configuration.url_to_send_canister_metrics_to,
             Some("http://metrics-url.com/receive-metrics".to_string())
         );
+
+        assert_eq!(
+            data.configuration.referrer_share_of_bet_winnings_percentage,
+            1
+        );
+
+        assert_eq!(data.configuration.house_rake_percentage, 1);
+
+        assert_eq!(
+            data.configuration.room_sizing_config,
+            RoomSizingConfig::default()
+        );
+
+        assert_eq!(
+            data.configuration.post_category_config,
+            PostCategoryConfig::default()
+        );
+
+        assert_eq!(
+            data.configuration.signup_bonus_vesting_config,
+            SignupBonusVestingConfig::default()
+        );
+
+        assert_eq!(
+            data.configuration.hot_or_not_payout_mode,
+            HotOrNotPayoutMode::Parimutuel
+        );
+
+        assert_eq!(data.binding_epoch, 2);
+
+        assert!(data.configuration.is_call_journal_enabled);
     }
 }
@@ -1,3 +1,5 @@
+pub mod get_applied_migrations;
 pub mod init;
 pub mod post_upgrade;
+pub mod post_upgrade_health_check;
 pub mod pre_upgrade;
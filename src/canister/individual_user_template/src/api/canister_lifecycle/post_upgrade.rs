@@ -1,16 +1,49 @@
 use std::time::Duration;
 
+use ic_stable_structures::Memory;
 use shared_utils::{
-    canister_specific::individual_user_template::types::arg::IndividualUserTemplateInitArgs,
-    common::utils::stable_memory_serializer_deserializer,
+    canister_specific::individual_user_template::types::{
+        arg::IndividualUserTemplateInitArgs, treasury::validate_house_rake_percentage,
+    },
+    common::utils::{
+        stable_memory_serializer_deserializer,
+        system_time::{self, IcTimeProvider},
+    },
 };
 
 use crate::{
     api::{
-        hot_or_not_bet::reenqueue_timers_for_pending_bet_outcomes::reenqueue_timers_for_pending_bet_outcomes,
+        hot_or_not_bet::{
+            claim_pot_insurance_from_treasury::claim_pending_pot_insurance_from_treasury,
+            reenqueue_timers_for_pending_bet_outcomes::reenqueue_timers_for_pending_bet_outcomes,
+            send_bet_outcome_notifications::send_pending_bet_outcome_notifications,
+            settle_collaborator_shares_of_commission::settle_pending_collaborator_shares_of_commission,
+            settle_house_rake_with_treasury::settle_pending_house_rake_with_treasury,
+            settle_pot_insurance_top_ups::settle_pending_pot_insurance_top_ups,
+            settle_referrer_share_of_bet_winnings::settle_pending_referrer_shares_of_bet_winnings,
+        },
+        post::{
+            boost_post::reenqueue_timers_for_active_post_boosts,
+            update_post_add_view_details::enqueue_timer_for_flushing_post_view_buffer,
+        },
+        versus::settle_versus_contest::reenqueue_timers_for_pending_versus_contests,
         well_known_principal::update_locally_stored_well_known_principals,
     },
-    data_model::CanisterData,
+    data_model::{memory, CanisterData},
+    util::{
+        call_journal::set_call_journal_enabled,
+        call_queue::enqueue_timer_for_retrying_queued_calls,
+        certified_post_details::certify_all_post_details,
+        cycle_monitor::enqueue_timer_for_monitoring_cycle_balance,
+        deferred_job_runner::enqueue_timer_for_running_deferred_jobs,
+        feature_usage_push::enqueue_timer_for_pushing_feature_usage_to_user_index,
+        legacy_post_migration::move_legacy_posts_to_stable_post_map,
+        live_betting_activity::reenqueue_timer_for_publishing_live_betting_activity,
+        pseudonym_salt::generate_pseudonym_salt_if_missing,
+        replication::enqueue_timer_for_replicating_to_standby,
+        slot_compaction::enqueue_timer_for_compacting_settled_slots,
+        token_ledger_mirror::enqueue_timer_for_mirroring_token_balance_to_ledger,
+    },
     CANISTER_DATA,
 };
 
@@ -19,16 +52,55 @@ use super::{init::send_canister_metrics, pre_upgrade::BUFFER_SIZE_BYTES};
 #[ic_cdk::post_upgrade]
 fn post_upgrade() {
     restore_data_from_stable_memory();
+    register_memory_regions_added_since_last_upgrade();
     save_upgrade_args_to_memory();
+    // * Run after the upgrade args are saved so the changelog records the version migrated to
+    move_pending_settlements_to_stable_queues();
+    move_received_settlements_to_stable_memory();
+    move_legacy_posts_to_stable_post_map();
+    move_follow_data_to_stable_follow_lists();
+    move_token_transaction_history_to_stable_memory();
+    widen_post_amounts_to_u128();
+    backfill_betting_stats();
+    // * Summed from the token transaction history, so run after it's moved to stable memory
+    backfill_referral_earnings_totals();
     refetch_well_known_principals();
-    reenqueue_timers_for_pending_bet_outcomes();
+    reenqueue_timers_for_pending_bet_outcomes(&IcTimeProvider);
+    reenqueue_timers_for_active_post_boosts();
+    reenqueue_timers_for_pending_versus_contests();
+    enqueue_timer_for_flushing_post_view_buffer();
+    enqueue_timer_for_pushing_feature_usage_to_user_index();
+    enqueue_timer_for_replicating_to_standby();
+    enqueue_timer_for_retrying_queued_calls();
+    enqueue_timer_for_mirroring_token_balance_to_ledger();
+    enqueue_timer_for_compacting_settled_slots();
+    enqueue_timer_for_monitoring_cycle_balance();
+    reenqueue_timer_for_publishing_live_betting_activity();
+    generate_pseudonym_salt_if_missing();
+    settle_pending_referrer_shares_of_bet_winnings();
+    settle_pending_house_rake_with_treasury();
+    claim_pending_pot_insurance_from_treasury();
+    settle_pending_pot_insurance_top_ups();
+    settle_pending_collaborator_shares_of_commission();
+    send_pending_bet_outcome_notifications();
+    enqueue_timer_for_running_deferred_jobs();
+    certify_all_post_details();
     send_canister_metrics();
 }
 
 fn restore_data_from_stable_memory() {
-    match stable_memory_serializer_deserializer::deserialize_from_stable_memory::<CanisterData>(
-        BUFFER_SIZE_BYTES,
-    ) {
+    let canister_data = if memory::is_stable_memory_managed_by_memory_manager() {
+        read_canister_data_from_upgrades_memory()
+    } else {
+        // * Upgrading from a wasm that serialized the heap straight to stable memory.
+        // * The memory manager takes over stable memory once this has been read.
+        stable_memory_serializer_deserializer::deserialize_from_stable_memory::<CanisterData>(
+            BUFFER_SIZE_BYTES,
+        )
+        .map_err(|e| format!("{:?}", e))
+    };
+
+    match canister_data {
         Ok(canister_data) => {
             CANISTER_DATA.with(|canister_data_ref_cell| {
                 *canister_data_ref_cell.borrow_mut() = canister_data;
@@ -40,6 +112,114 @@ fn restore_data_from_stable_memory() {
     }
 }
 
+fn read_canister_data_from_upgrades_memory() -> Result<CanisterData, String> {
+    let upgrades_memory = memory::get_upgrades_memory();
+
+    // * Read the length of the heap data state.
+    // * Since heap can be at max 4 GiB, 4 bytes are enough to store the length.
+    let mut heap_data_len_bytes = [0; 4];
+    upgrades_memory.read(0, &mut heap_data_len_bytes);
+    let heap_data_len = u32::from_le_bytes(heap_data_len_bytes) as usize;
+
+    let mut canister_data_bytes = vec![0; heap_data_len];
+    upgrades_memory.read(4, &mut canister_data_bytes);
+
+    stable_memory_serializer_deserializer::deserialize(&*canister_data_bytes)
+        .map_err(|e| format!("{:?}", e))
+}
+
+fn move_pending_settlements_to_stable_queues() {
+    CANISTER_DATA.with(|canister_data_ref_cell| {
+        canister_data_ref_cell.borrow_mut().apply_migration(
+            "move_pending_settlements_to_stable_queues",
+            system_time::get_current_system_time_from_ic(),
+            ic_cdk::api::instruction_counter,
+            |canister_data| Ok(canister_data.move_legacy_pending_settlements_to_stable_queues()),
+        );
+    });
+}
+
+fn move_received_settlements_to_stable_memory() {
+    CANISTER_DATA.with(|canister_data_ref_cell| {
+        canister_data_ref_cell.borrow_mut().apply_migration(
+            "move_received_settlements_to_stable_memory",
+            system_time::get_current_system_time_from_ic(),
+            ic_cdk::api::instruction_counter,
+            |canister_data| Ok(canister_data.move_legacy_received_settlements_to_stable_memory()),
+        );
+    });
+}
+
+fn move_follow_data_to_stable_follow_lists() {
+    CANISTER_DATA.with(|canister_data_ref_cell| {
+        canister_data_ref_cell.borrow_mut().apply_migration(
+            "move_follow_data_to_stable_follow_lists",
+            system_time::get_current_system_time_from_ic(),
+            ic_cdk::api::instruction_counter,
+            |canister_data| Ok(canister_data.move_legacy_follow_data_to_stable_follow_lists()),
+        );
+    });
+}
+
+fn move_token_transaction_history_to_stable_memory() {
+    CANISTER_DATA.with(|canister_data_ref_cell| {
+        canister_data_ref_cell.borrow_mut().apply_migration(
+            "move_token_transaction_history_to_stable_memory",
+            system_time::get_current_system_time_from_ic(),
+            ic_cdk::api::instruction_counter,
+            |canister_data| Ok(canister_data.move_legacy_token_transactions_to_stable_history()),
+        );
+    });
+}
+
+fn widen_post_amounts_to_u128() {
+    CANISTER_DATA.with(|canister_data_ref_cell| {
+        canister_data_ref_cell.borrow_mut().apply_migration(
+            "widen_post_amounts_to_u128",
+            system_time::get_current_system_time_from_ic(),
+            ic_cdk::api::instruction_counter,
+            |canister_data| {
+                Ok(canister_data
+                    .all_created_posts
+                    .reencode_legacy_hot_or_not_details())
+            },
+        );
+    });
+}
+
+fn backfill_betting_stats() {
+    CANISTER_DATA.with(|canister_data_ref_cell| {
+        canister_data_ref_cell.borrow_mut().apply_migration(
+            "backfill_betting_stats",
+            system_time::get_current_system_time_from_ic(),
+            ic_cdk::api::instruction_counter,
+            |canister_data| Ok(canister_data.backfill_betting_stats()),
+        );
+    });
+}
+
+fn backfill_referral_earnings_totals() {
+    CANISTER_DATA.with(|canister_data_ref_cell| {
+        canister_data_ref_cell.borrow_mut().apply_migration(
+            "backfill_referral_earnings_totals",
+            system_time::get_current_system_time_from_ic(),
+            ic_cdk::api::instruction_counter,
+            |canister_data| Ok(canister_data.backfill_referral_earnings_totals()),
+        );
+    });
+}
+
+fn register_memory_regions_added_since_last_upgrade() {
+    // * Trapping here rolls back the upgrade, which is what we want if this wasm
+    // * maps a memory id that was previously handed out to another structure.
+    CANISTER_DATA.with(|canister_data_ref_cell| {
+        memory::register_all_memory_regions(
+            &mut canister_data_ref_cell.borrow_mut().memory_id_registry,
+        )
+        .expect("Memory id assignments conflict with the ones persisted before the upgrade");
+    });
+}
+
 fn save_upgrade_args_to_memory() {
     let upgrade_args = ic_cdk::api::call::arg_data::<(IndividualUserTemplateInitArgs,)>().0;
 
@@ -64,6 +244,61 @@ fn save_upgrade_args_to_memory() {
                 .configuration
                 .url_to_send_canister_metrics_to = Some(url_to_send_canister_metrics_to);
         }
+
+        if let Some(referrer_share_of_bet_winnings_percentage) =
+            upgrade_args.referrer_share_of_bet_winnings_percentage
+        {
+            canister_data_ref_cell
+                .configuration
+                .referrer_share_of_bet_winnings_percentage =
+                referrer_share_of_bet_winnings_percentage;
+        }
+
+        if let Some(house_rake_percentage) = upgrade_args.house_rake_percentage {
+            validate_house_rake_percentage(house_rake_percentage)
+                .expect("Rejected the house rake percentage");
+            canister_data_ref_cell.configuration.house_rake_percentage = house_rake_percentage;
+        }
+
+        if let Some(room_sizing_config) = upgrade_args.room_sizing_config {
+            canister_data_ref_cell.configuration.room_sizing_config = room_sizing_config;
+        }
+
+        if let Some(post_category_config) = upgrade_args.post_category_config {
+            canister_data_ref_cell.configuration.post_category_config = post_category_config;
+        }
+
+        if let Some(signup_bonus_vesting_config) = upgrade_args.signup_bonus_vesting_config {
+            canister_data_ref_cell
+                .configuration
+                .signup_bonus_vesting_config = signup_bonus_vesting_config;
+        }
+
+        if let Some(pot_insurance_config) = upgrade_args.pot_insurance_config {
+            canister_data_ref_cell.configuration.pot_insurance_config = pot_insurance_config;
+        }
+
+        if let Some(bet_cancellation_config) = upgrade_args.bet_cancellation_config {
+            canister_data_ref_cell.configuration.bet_cancellation_config = bet_cancellation_config;
+        }
+
+        if let Some(access_statistics_config) = upgrade_args.access_statistics_config {
+            canister_data_ref_cell
+                .configuration
+                .access_statistics_config = access_statistics_config;
+        }
+
+        if let Some(hot_or_not_payout_mode) = upgrade_args.hot_or_not_payout_mode {
+            canister_data_ref_cell.configuration.hot_or_not_payout_mode = hot_or_not_payout_mode;
+        }
+
+        if let Some(binding_epoch) = upgrade_args.binding_epoch {
+            canister_data_ref_cell.binding_epoch = binding_epoch;
+        }
+
+        if let Some(is_call_journal_enabled) = upgrade_args.is_call_journal_enabled {
+            set_call_journal_enabled(&mut canister_data_ref_cell, is_call_journal_enabled);
+        }
     });
 }
 
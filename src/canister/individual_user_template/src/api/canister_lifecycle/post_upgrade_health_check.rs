@@ -0,0 +1,63 @@
+use shared_utils::canister_specific::individual_user_template::types::post_upgrade_health::PostUpgradeHealthReport;
+
+use crate::{data_model::CanisterData, CANISTER_DATA};
+
+/// Polled by the user index after upgrading this canister during a rolling upgrade, to decide
+/// whether to carry on with the next batch
+#[ic_cdk::query]
+#[candid::candid_method(query)]
+fn post_upgrade_health_check() -> PostUpgradeHealthReport {
+    CANISTER_DATA.with(|canister_data_ref_cell| {
+        post_upgrade_health_check_impl(&canister_data_ref_cell.borrow())
+    })
+}
+
+fn post_upgrade_health_check_impl(canister_data: &CanisterData) -> PostUpgradeHealthReport {
+    let version_number = canister_data.version_details.version_number;
+
+    PostUpgradeHealthReport {
+        version_number,
+        profile_owner: canister_data.profile.principal_id,
+        migrations_with_errors: canister_data
+            .migration_changelog
+            .get_migrations_with_errors(version_number),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::SystemTime;
+
+    use test_utils::setup::test_constants::get_mock_user_alice_principal_id;
+
+    use super::*;
+
+    #[test]
+    fn test_post_upgrade_health_check_impl() {
+        let mut canister_data = CanisterData::default();
+        canister_data.profile.principal_id = Some(get_mock_user_alice_principal_id());
+        // * errors from earlier versions were already reported when those were rolled out
+        canister_data.version_details.version_number = 3;
+        canister_data.apply_migration(
+            "move_posts_to_stable_post_map",
+            SystemTime::UNIX_EPOCH,
+            || 0,
+            |_| Err(vec!["post 2 could not be decoded".into()]),
+        );
+        canister_data.version_details.version_number = 4;
+
+        let report = post_upgrade_health_check_impl(&canister_data);
+        assert_eq!(report.check(4), Ok(()));
+
+        canister_data.apply_migration(
+            "move_token_transaction_history_to_stable_memory",
+            SystemTime::UNIX_EPOCH,
+            || 0,
+            |_| Err(vec!["token event 9 could not be decoded".into()]),
+        );
+        assert_eq!(
+            post_upgrade_health_check_impl(&canister_data).migrations_with_errors,
+            vec!["move_token_transaction_history_to_stable_memory".to_string()]
+        );
+    }
+}
@@ -1,17 +1,29 @@
+use ic_stable_structures::writer::Writer;
 use shared_utils::common::utils::stable_memory_serializer_deserializer;
 
-use crate::CANISTER_DATA;
+use crate::{data_model::memory, CANISTER_DATA};
 
 pub const BUFFER_SIZE_BYTES: usize = 2 * 1024 * 1024; // 2 MiB
 
 #[ic_cdk::pre_upgrade]
 fn pre_upgrade() {
-    CANISTER_DATA.with(|canister_data_ref_cell| {
-        let canister_data = canister_data_ref_cell.take();
-        stable_memory_serializer_deserializer::serialize_to_stable_memory(
-            canister_data,
-            BUFFER_SIZE_BYTES,
-        )
+    // * Serialize the state.
+    let mut state_bytes = vec![];
+    CANISTER_DATA
+        .with(|canister_data_ref_cell| {
+            stable_memory_serializer_deserializer::serialize(
+                &*canister_data_ref_cell.borrow(),
+                &mut state_bytes,
+            )
+            .map_err(|e| format!("{:?}", e))
+        })
         .expect("Failed to serialize canister data");
-    });
+
+    // * Write the length of the serialized bytes to memory, followed
+    // * by the bytes themselves.
+    let len = state_bytes.len() as u32;
+    let mut memory = memory::get_upgrades_memory();
+    let mut writer = Writer::new(&mut memory, 0);
+    writer.write(&len.to_le_bytes()).unwrap();
+    writer.write(&state_bytes).unwrap()
 }
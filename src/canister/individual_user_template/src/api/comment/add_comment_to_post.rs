@@ -0,0 +1,303 @@
+use std::time::SystemTime;
+
+use candid::Principal;
+use shared_utils::{
+    canister_specific::individual_user_template::types::{comment::CommentId, error::CommentError},
+    common::{types::app_primitive_type::PostId, utils::system_time},
+};
+
+use crate::{
+    data_model::CanisterData,
+    util::{call_journal::record_call_in_journal, guards::reject_updates_in_read_only_mode},
+    CANISTER_DATA,
+};
+
+/// Leaves a comment on one of this profile's posts
+///
+/// # Access Control
+/// Only the profile owner. Everyone else comments through `comment_on_post` on their own
+/// canister, which lets this one know whether they are shadow banned.
+#[ic_cdk::update(guard = "reject_updates_in_read_only_mode")]
+#[candid::candid_method(update)]
+fn add_comment_to_post(post_id: PostId, text: String) -> Result<CommentId, CommentError> {
+    record_call_in_journal("add_comment_to_post");
+
+    let api_caller = ic_cdk::caller();
+
+    CANISTER_DATA.with(|canister_data_ref_cell| {
+        add_comment_to_post_impl(
+            &mut canister_data_ref_cell.borrow_mut(),
+            &api_caller,
+            post_id,
+            text,
+            &system_time::get_current_system_time_from_ic(),
+        )
+    })
+}
+
+pub(crate) fn add_comment_to_post_impl(
+    canister_data: &mut CanisterData,
+    api_caller: &Principal,
+    post_id: PostId,
+    text: String,
+    current_time: &SystemTime,
+) -> Result<CommentId, CommentError> {
+    if *api_caller == Principal::anonymous() {
+        return Err(CommentError::UserNotLoggedIn);
+    }
+
+    if canister_data.profile.principal_id != Some(*api_caller) {
+        return Err(CommentError::MustCommentThroughOwnCanister);
+    }
+
+    let is_shadow_banned = canister_data.is_shadow_banned;
+    add_comment_from_author(
+        canister_data,
+        api_caller,
+        post_id,
+        text,
+        is_shadow_banned,
+        current_time,
+    )
+}
+
+/// Adds the comment of `author_principal_id` to one of this profile's posts. Anyone but the
+/// profile owner has to be on the post's allowlist if it has one.
+pub(crate) fn add_comment_from_author(
+    canister_data: &mut CanisterData,
+    author_principal_id: &Principal,
+    post_id: PostId,
+    text: String,
+    is_author_shadow_banned: bool,
+    current_time: &SystemTime,
+) -> Result<CommentId, CommentError> {
+    if !canister_data.all_created_posts.contains_key(&post_id) {
+        return Err(CommentError::PostNotFound);
+    }
+
+    if canister_data.profile.principal_id != Some(*author_principal_id)
+        && !canister_data
+            .delegated_moderation
+            .is_allowed_to_comment(post_id, author_principal_id)
+    {
+        return Err(CommentError::NotOnPostAllowlist);
+    }
+
+    canister_data
+        .post_comments
+        .entry(post_id)
+        .or_default()
+        .add_comment(
+            *author_principal_id,
+            text,
+            *current_time,
+            is_author_shadow_banned,
+        )
+}
+
+#[cfg(test)]
+mod test {
+    use shared_utils::canister_specific::individual_user_template::types::post::{
+        Post, PostDetailsFromFrontend,
+    };
+    use test_utils::setup::test_constants::{
+        get_mock_user_alice_principal_id, get_mock_user_bob_principal_id,
+        get_mock_user_charlie_principal_id,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_add_comment_to_post_impl() {
+        let mut canister_data = CanisterData::default();
+        let current_time = SystemTime::now();
+        canister_data.profile.principal_id = Some(get_mock_user_alice_principal_id());
+        canister_data.all_created_posts.insert(
+            0,
+            Post::new(
+                0,
+                &PostDetailsFromFrontend {
+                    description: "Doggos and puppers".into(),
+                    hashtags: vec!["doggo".into(), "pupper".into()],
+                    video_uid: "abcd#1234".into(),
+                    creator_consent_for_inclusion_in_hot_or_not: true,
+                    collaborator_shares: None,
+                    title: None,
+                    category: None,
+                },
+                &current_time,
+            ),
+        );
+
+        assert_eq!(
+            add_comment_to_post_impl(
+                &mut canister_data,
+                &Principal::anonymous(),
+                0,
+                "so cute".into(),
+                &current_time
+            ),
+            Err(CommentError::UserNotLoggedIn)
+        );
+        assert_eq!(
+            add_comment_to_post_impl(
+                &mut canister_data,
+                &get_mock_user_bob_principal_id(),
+                0,
+                "so cute".into(),
+                &current_time
+            ),
+            Err(CommentError::MustCommentThroughOwnCanister)
+        );
+        assert_eq!(
+            add_comment_to_post_impl(
+                &mut canister_data,
+                &get_mock_user_alice_principal_id(),
+                1,
+                "thanks!".into(),
+                &current_time
+            ),
+            Err(CommentError::PostNotFound)
+        );
+        assert_eq!(
+            add_comment_to_post_impl(
+                &mut canister_data,
+                &get_mock_user_alice_principal_id(),
+                0,
+                "thanks!".into(),
+                &current_time
+            ),
+            Ok(0)
+        );
+
+        // * the post owner isn't held to their own allowlist
+        canister_data
+            .delegated_moderation
+            .update_post_allowlist(0, &[get_mock_user_bob_principal_id()], &[])
+            .unwrap();
+        canister_data.is_shadow_banned = true;
+        assert_eq!(
+            add_comment_to_post_impl(
+                &mut canister_data,
+                &get_mock_user_alice_principal_id(),
+                0,
+                "thanks again!".into(),
+                &current_time
+            ),
+            Ok(1)
+        );
+
+        // * comments left while shadow banned are only shown to their author
+        let post_comments = canister_data.post_comments.get(&0).unwrap();
+        assert_eq!(
+            post_comments
+                .get_comments_visible_to(&get_mock_user_alice_principal_id())
+                .len(),
+            2
+        );
+        assert_eq!(
+            post_comments
+                .get_comments_visible_to(&get_mock_user_bob_principal_id())
+                .len(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_add_comment_from_author() {
+        let mut canister_data = CanisterData::default();
+        let current_time = SystemTime::now();
+        canister_data.profile.principal_id = Some(get_mock_user_alice_principal_id());
+        canister_data.all_created_posts.insert(
+            0,
+            Post::new(
+                0,
+                &PostDetailsFromFrontend {
+                    description: "Doggos and puppers".into(),
+                    hashtags: vec!["doggo".into(), "pupper".into()],
+                    video_uid: "abcd#1234".into(),
+                    creator_consent_for_inclusion_in_hot_or_not: true,
+                    collaborator_shares: None,
+                    title: None,
+                    category: None,
+                },
+                &current_time,
+            ),
+        );
+
+        assert_eq!(
+            add_comment_from_author(
+                &mut canister_data,
+                &get_mock_user_bob_principal_id(),
+                1,
+                "so cute".into(),
+                false,
+                &current_time
+            ),
+            Err(CommentError::PostNotFound)
+        );
+        assert_eq!(
+            add_comment_from_author(
+                &mut canister_data,
+                &get_mock_user_bob_principal_id(),
+                0,
+                "so cute".into(),
+                false,
+                &current_time
+            ),
+            Ok(0)
+        );
+
+        canister_data
+            .delegated_moderation
+            .update_post_allowlist(0, &[get_mock_user_bob_principal_id()], &[])
+            .unwrap();
+        assert_eq!(
+            add_comment_from_author(
+                &mut canister_data,
+                &get_mock_user_charlie_principal_id(),
+                0,
+                "first!".into(),
+                false,
+                &current_time
+            ),
+            Err(CommentError::NotOnPostAllowlist)
+        );
+        assert_eq!(
+            add_comment_from_author(
+                &mut canister_data,
+                &get_mock_user_bob_principal_id(),
+                0,
+                "still cute".into(),
+                true,
+                &current_time
+            ),
+            Ok(1)
+        );
+        assert_eq!(
+            add_comment_from_author(
+                &mut canister_data,
+                &get_mock_user_alice_principal_id(),
+                0,
+                "thanks!".into(),
+                false,
+                &current_time
+            ),
+            Ok(2)
+        );
+
+        let post_comments = canister_data.post_comments.get(&0).unwrap();
+        assert_eq!(
+            post_comments
+                .get_comments_visible_to(&get_mock_user_bob_principal_id())
+                .len(),
+            3
+        );
+        assert_eq!(
+            post_comments
+                .get_comments_visible_to(&get_mock_user_charlie_principal_id())
+                .len(),
+            2
+        );
+    }
+}
@@ -0,0 +1,110 @@
+use candid::Principal;
+use shared_utils::{
+    canister_specific::individual_user_template::types::{comment::CommentId, error::CommentError},
+    common::{types::app_primitive_type::PostId, utils::system_time},
+};
+
+use crate::{
+    data_model::CanisterData,
+    util::{call_journal::record_call_in_journal, guards::reject_updates_in_read_only_mode},
+    CANISTER_DATA,
+};
+
+use super::add_comment_to_post::add_comment_to_post_impl;
+
+/// Leaves a comment on a post of any profile as this profile's owner. The post's canister is
+/// told whether the owner is shadow banned, so that it only shows their comment to them.
+///
+/// # Access Control
+/// Only the profile owner
+#[ic_cdk::update(guard = "reject_updates_in_read_only_mode")]
+#[candid::candid_method(update)]
+async fn comment_on_post(
+    post_canister_id: Principal,
+    post_id: PostId,
+    text: String,
+) -> Result<CommentId, CommentError> {
+    record_call_in_journal("comment_on_post");
+
+    let api_caller = ic_cdk::caller();
+
+    if post_canister_id == ic_cdk::id() {
+        return CANISTER_DATA.with(|canister_data_ref_cell| {
+            add_comment_to_post_impl(
+                &mut canister_data_ref_cell.borrow_mut(),
+                &api_caller,
+                post_id,
+                text,
+                &system_time::get_current_system_time_from_ic(),
+            )
+        });
+    }
+
+    let is_shadow_banned = CANISTER_DATA.with(|canister_data_ref_cell| {
+        get_commenter_shadow_ban_status_impl(&canister_data_ref_cell.borrow(), &api_caller)
+    })?;
+
+    ic_cdk::call::<_, (Result<CommentId, CommentError>,)>(
+        post_canister_id,
+        "receive_comment_from_commenters_canister",
+        (post_id, text, api_caller, is_shadow_banned),
+    )
+    .await
+    .map_err(|_| CommentError::PostCreatorCanisterCallFailed)?
+    .0
+}
+
+fn get_commenter_shadow_ban_status_impl(
+    canister_data: &CanisterData,
+    api_caller: &Principal,
+) -> Result<bool, CommentError> {
+    if *api_caller == Principal::anonymous() {
+        return Err(CommentError::UserNotLoggedIn);
+    }
+
+    if canister_data.profile.principal_id != Some(*api_caller) {
+        return Err(CommentError::Unauthorized);
+    }
+
+    Ok(canister_data.is_shadow_banned)
+}
+
+#[cfg(test)]
+mod test {
+    use test_utils::setup::test_constants::{
+        get_mock_user_alice_principal_id, get_mock_user_bob_principal_id,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_get_commenter_shadow_ban_status_impl() {
+        let mut canister_data = CanisterData::default();
+        canister_data.profile.principal_id = Some(get_mock_user_alice_principal_id());
+
+        assert_eq!(
+            get_commenter_shadow_ban_status_impl(&canister_data, &Principal::anonymous()),
+            Err(CommentError::UserNotLoggedIn)
+        );
+        assert_eq!(
+            get_commenter_shadow_ban_status_impl(&canister_data, &get_mock_user_bob_principal_id()),
+            Err(CommentError::Unauthorized)
+        );
+        assert_eq!(
+            get_commenter_shadow_ban_status_impl(
+                &canister_data,
+                &get_mock_user_alice_principal_id()
+            ),
+            Ok(false)
+        );
+
+        canister_data.is_shadow_banned = true;
+        assert_eq!(
+            get_commenter_shadow_ban_status_impl(
+                &canister_data,
+                &get_mock_user_alice_principal_id()
+            ),
+            Ok(true)
+        );
+    }
+}
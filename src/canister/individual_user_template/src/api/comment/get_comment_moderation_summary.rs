@@ -0,0 +1,51 @@
+use candid::Principal;
+use shared_utils::{
+    canister_specific::individual_user_template::types::{
+        comment::CommentModerationSummary, error::CommentError,
+    },
+    common::types::{app_primitive_type::PostId, known_principal::KnownPrincipalType},
+};
+
+use crate::{data_model::CanisterData, CANISTER_DATA};
+
+/// How many of the post's comments are hidden, and every time one was hidden or restored
+///
+/// # Access Control
+/// The user whose profile details are stored in this canister, and the global super admin
+/// acting as moderator
+#[ic_cdk::query]
+#[candid::candid_method(query)]
+fn get_comment_moderation_summary(
+    post_id: PostId,
+) -> Result<CommentModerationSummary, CommentError> {
+    let api_caller = ic_cdk::caller();
+
+    CANISTER_DATA.with(|canister_data_ref_cell| {
+        get_comment_moderation_summary_impl(&canister_data_ref_cell.borrow(), &api_caller, post_id)
+    })
+}
+
+fn get_comment_moderation_summary_impl(
+    canister_data: &CanisterData,
+    api_caller: &Principal,
+    post_id: PostId,
+) -> Result<CommentModerationSummary, CommentError> {
+    let is_moderator = canister_data.profile.principal_id == Some(*api_caller)
+        || canister_data
+            .known_principal_ids
+            .get(&KnownPrincipalType::UserIdGlobalSuperAdmin)
+            == Some(api_caller);
+    if !is_moderator {
+        return Err(CommentError::Unauthorized);
+    }
+
+    if !canister_data.all_created_posts.contains_key(&post_id) {
+        return Err(CommentError::PostNotFound);
+    }
+
+    Ok(canister_data
+        .post_comments
+        .get(&post_id)
+        .map(|post_comments| post_comments.get_moderation_summary())
+        .unwrap_or_default())
+}
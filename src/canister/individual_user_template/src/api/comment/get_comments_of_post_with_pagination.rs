@@ -0,0 +1,56 @@
+use candid::Principal;
+use shared_utils::{
+    canister_specific::individual_user_template::types::{
+        comment::CommentDetailsForFrontend, error::CommentError,
+    },
+    common::types::app_primitive_type::PostId,
+    pagination::{self, Page},
+};
+
+use crate::{data_model::CanisterData, CANISTER_DATA};
+
+/// Comments on the post, oldest first. Comments the post owner hid, and comments left while
+/// their author was shadow banned, are only returned to their author.
+#[ic_cdk::query]
+#[candid::candid_method(query)]
+fn get_comments_of_post_with_pagination(
+    post_id: PostId,
+    from_inclusive_index: u64,
+    page_size: u64,
+) -> Result<Page<CommentDetailsForFrontend>, CommentError> {
+    let api_caller = ic_cdk::caller();
+
+    CANISTER_DATA.with(|canister_data_ref_cell| {
+        get_comments_of_post_with_pagination_impl(
+            &canister_data_ref_cell.borrow(),
+            &api_caller,
+            post_id,
+            from_inclusive_index,
+            page_size,
+        )
+    })
+}
+
+fn get_comments_of_post_with_pagination_impl(
+    canister_data: &CanisterData,
+    api_caller: &Principal,
+    post_id: PostId,
+    from_inclusive_index: u64,
+    page_size: u64,
+) -> Result<Page<CommentDetailsForFrontend>, CommentError> {
+    if !canister_data.all_created_posts.contains_key(&post_id) {
+        return Err(CommentError::PostNotFound);
+    }
+
+    let visible_comments = canister_data
+        .post_comments
+        .get(&post_id)
+        .map(|post_comments| post_comments.get_comments_visible_to(api_caller))
+        .unwrap_or_default();
+
+    Ok(pagination::get_page(
+        visible_comments.into_iter(),
+        from_inclusive_index,
+        page_size,
+    ))
+}
@@ -0,0 +1,6 @@
+pub mod add_comment_to_post;
+pub mod comment_on_post;
+pub mod get_comment_moderation_summary;
+pub mod get_comments_of_post_with_pagination;
+pub mod receive_comment_from_commenters_canister;
+pub mod update_comment_visibility;
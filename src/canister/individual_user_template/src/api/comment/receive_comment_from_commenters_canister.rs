@@ -0,0 +1,66 @@
+use candid::Principal;
+use shared_utils::{
+    canister_specific::individual_user_template::types::{comment::CommentId, error::CommentError},
+    common::{
+        types::{app_primitive_type::PostId, known_principal::KnownPrincipalType},
+        utils::system_time,
+    },
+};
+
+use crate::{
+    util::{call_journal::record_call_in_journal, guards::reject_updates_in_read_only_mode},
+    CANISTER_DATA,
+};
+
+use super::add_comment_to_post::add_comment_from_author;
+
+/// Leaves a comment sent by the commenter's own canister on one of this profile's posts.
+/// Comments of shadow banned commenters are only shown to them.
+///
+/// # Access Control
+/// Only the canister the user index maps the commenter to, and only for commenters on the
+/// post's allowlist if it has one
+#[ic_cdk::update(guard = "reject_updates_in_read_only_mode")]
+#[candid::candid_method(update)]
+async fn receive_comment_from_commenters_canister(
+    post_id: PostId,
+    text: String,
+    commenter_principal_id: Principal,
+    is_commenter_shadow_banned: bool,
+) -> Result<CommentId, CommentError> {
+    record_call_in_journal("receive_comment_from_commenters_canister");
+
+    let commenter_canister_id = ic_cdk::caller();
+    let user_index_canister_id = CANISTER_DATA.with(|canister_data_ref_cell| {
+        canister_data_ref_cell
+            .borrow()
+            .known_principal_ids
+            .get(&KnownPrincipalType::CanisterIdUserIndex)
+            .cloned()
+            .unwrap()
+    });
+
+    // * Trapping rejects the call, which the commenter's canister reports as retryable
+    let (registered_commenter_canister_id,) = ic_cdk::call::<_, (Option<Principal>,)>(
+        user_index_canister_id,
+        "get_user_canister_id_from_user_principal_id",
+        (commenter_principal_id,),
+    )
+    .await
+    .unwrap_or_else(|_| ic_cdk::trap("Could not verify the commenter's canister"));
+
+    if registered_commenter_canister_id != Some(commenter_canister_id) {
+        return Err(CommentError::Unauthorized);
+    }
+
+    CANISTER_DATA.with(|canister_data_ref_cell| {
+        add_comment_from_author(
+            &mut canister_data_ref_cell.borrow_mut(),
+            &commenter_principal_id,
+            post_id,
+            text,
+            is_commenter_shadow_banned,
+            &system_time::get_current_system_time_from_ic(),
+        )
+    })
+}
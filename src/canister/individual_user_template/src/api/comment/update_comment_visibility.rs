@@ -0,0 +1,244 @@
+use std::time::SystemTime;
+
+use candid::Principal;
+use shared_utils::{
+    canister_specific::individual_user_template::types::{
+        comment::CommentId,
+        error::CommentError,
+        moderation::{ModerationAction, ModeratorPermission},
+    },
+    common::{types::app_primitive_type::PostId, utils::system_time},
+};
+
+use crate::{
+    data_model::CanisterData,
+    util::{call_journal::record_call_in_journal, guards::reject_updates_in_read_only_mode},
+    CANISTER_DATA,
+};
+
+/// Hides a comment on one of this profile's posts, or restores it. A hidden comment is
+/// shadow-deleted: it stays visible to its author, who isn't told it was hidden, but
+/// nobody else sees it.
+///
+/// # Access Control
+/// The user whose profile details are stored in this canister, and moderators they allowed to
+/// hide comments
+#[ic_cdk::update(guard = "reject_updates_in_read_only_mode")]
+#[candid::candid_method(update)]
+fn update_comment_visibility(
+    post_id: PostId,
+    comment_id: CommentId,
+    is_hidden: bool,
+) -> Result<(), CommentError> {
+    record_call_in_journal("update_comment_visibility");
+
+    let api_caller = ic_cdk::caller();
+
+    CANISTER_DATA.with(|canister_data_ref_cell| {
+        update_comment_visibility_impl(
+            &mut canister_data_ref_cell.borrow_mut(),
+            &api_caller,
+            post_id,
+            comment_id,
+            is_hidden,
+            &system_time::get_current_system_time_from_ic(),
+        )
+    })
+}
+
+fn update_comment_visibility_impl(
+    canister_data: &mut CanisterData,
+    api_caller: &Principal,
+    post_id: PostId,
+    comment_id: CommentId,
+    is_hidden: bool,
+    current_time: &SystemTime,
+) -> Result<(), CommentError> {
+    if !canister_data.is_allowed_to_moderate(api_caller, ModeratorPermission::HideComments) {
+        return Err(CommentError::Unauthorized);
+    }
+
+    let is_visibility_changed = canister_data
+        .post_comments
+        .get_mut(&post_id)
+        .ok_or(CommentError::CommentNotFound)?
+        .set_comment_hidden(comment_id, is_hidden, *api_caller, *current_time)?;
+
+    if is_visibility_changed {
+        canister_data.delegated_moderation.record_action(
+            if is_hidden {
+                ModerationAction::CommentHidden {
+                    post_id,
+                    comment_id,
+                }
+            } else {
+                ModerationAction::CommentRestored {
+                    post_id,
+                    comment_id,
+                }
+            },
+            *api_caller,
+            *current_time,
+        );
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use shared_utils::canister_specific::individual_user_template::types::comment::PostComments;
+    use test_utils::setup::test_constants::{
+        get_mock_user_alice_principal_id, get_mock_user_bob_principal_id,
+        get_mock_user_charlie_principal_id,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_update_comment_visibility_impl() {
+        let mut canister_data = CanisterData::default();
+        let current_time = SystemTime::now();
+        canister_data.profile.principal_id = Some(get_mock_user_alice_principal_id());
+        let mut post_comments = PostComments::default();
+        post_comments
+            .add_comment(
+                get_mock_user_bob_principal_id(),
+                "spam spam spam".into(),
+                current_time,
+                false,
+            )
+            .unwrap();
+        canister_data.post_comments.insert(0, post_comments);
+
+        assert_eq!(
+            update_comment_visibility_impl(
+                &mut canister_data,
+                &get_mock_user_bob_principal_id(),
+                0,
+                0,
+                true,
+                &current_time
+            ),
+            Err(CommentError::Unauthorized)
+        );
+        assert_eq!(
+            update_comment_visibility_impl(
+                &mut canister_data,
+                &get_mock_user_alice_principal_id(),
+                0,
+                1,
+                true,
+                &current_time
+            ),
+            Err(CommentError::CommentNotFound)
+        );
+        assert_eq!(
+            update_comment_visibility_impl(
+                &mut canister_data,
+                &get_mock_user_alice_principal_id(),
+                0,
+                0,
+                true,
+                &current_time
+            ),
+            Ok(())
+        );
+
+        let post_comments = canister_data.post_comments.get(&0).unwrap();
+        assert_eq!(
+            post_comments
+                .get_comments_visible_to(&get_mock_user_bob_principal_id())
+                .len(),
+            1
+        );
+        assert!(post_comments
+            .get_comments_visible_to(&get_mock_user_charlie_principal_id())
+            .is_empty());
+        assert_eq!(
+            post_comments.get_moderation_summary().moderation_log[0].moderated_by,
+            get_mock_user_alice_principal_id()
+        );
+        assert_eq!(
+            canister_data
+                .delegated_moderation
+                .get_audit_log_page(0, 10)
+                .total_count,
+            1
+        );
+    }
+
+    #[test]
+    fn test_update_comment_visibility_impl_by_moderator() {
+        let mut canister_data = CanisterData::default();
+        let current_time = SystemTime::now();
+        canister_data.profile.principal_id = Some(get_mock_user_alice_principal_id());
+        let mut post_comments = PostComments::default();
+        post_comments
+            .add_comment(
+                get_mock_user_charlie_principal_id(),
+                "spam spam spam".into(),
+                current_time,
+                false,
+            )
+            .unwrap();
+        canister_data.post_comments.insert(0, post_comments);
+        canister_data
+            .delegated_moderation
+            .appoint_moderator(
+                get_mock_user_bob_principal_id(),
+                &[ModeratorPermission::PauseBetting],
+                &current_time,
+            )
+            .unwrap();
+
+        assert_eq!(
+            update_comment_visibility_impl(
+                &mut canister_data,
+                &get_mock_user_bob_principal_id(),
+                0,
+                0,
+                true,
+                &current_time
+            ),
+            Err(CommentError::Unauthorized)
+        );
+
+        canister_data
+            .delegated_moderation
+            .appoint_moderator(
+                get_mock_user_bob_principal_id(),
+                &[ModeratorPermission::HideComments],
+                &current_time,
+            )
+            .unwrap();
+        (0..2).for_each(|_| {
+            assert_eq!(
+                update_comment_visibility_impl(
+                    &mut canister_data,
+                    &get_mock_user_bob_principal_id(),
+                    0,
+                    0,
+                    true,
+                    &current_time
+                ),
+                Ok(())
+            );
+        });
+
+        // * hiding an already hidden comment isn't audited again
+        let audit_log = canister_data.delegated_moderation.get_audit_log_page(0, 10);
+        assert_eq!(audit_log.total_count, 1);
+        assert_eq!(
+            audit_log.items[0].action,
+            ModerationAction::CommentHidden {
+                post_id: 0,
+                comment_id: 0
+            }
+        );
+        assert_eq!(
+            audit_log.items[0].performed_by,
+            get_mock_user_bob_principal_id()
+        );
+    }
+}
@@ -4,11 +4,16 @@ use shared_utils::{
     constant::INDIVIDUAL_USER_CANISTER_RECHARGE_AMOUNT,
 };
 
-use crate::CANISTER_DATA;
+use crate::{
+    util::{call_journal::record_call_in_journal, guards::reject_updates_in_read_only_mode},
+    CANISTER_DATA,
+};
 
-#[ic_cdk::update]
+#[ic_cdk::update(guard = "reject_updates_in_read_only_mode")]
 #[candid::candid_method(update)]
 async fn return_cycles_to_user_index_canister(cycle_amount: Option<u128>) {
+    record_call_in_journal("return_cycles_to_user_index_canister");
+
     let api_caller = ic_cdk::caller();
 
     let user_index_canister_id = CANISTER_DATA.with(|canister_data_ref_cell| {
@@ -0,0 +1,101 @@
+use std::time::SystemTime;
+
+use candid::Principal;
+use shared_utils::{
+    canister_specific::individual_user_template::types::deferred_job::DeferredJobRequest,
+    common::{
+        types::deferred_job::{DeferredJobError, DeferredJobId},
+        utils::system_time,
+    },
+};
+
+use crate::{
+    data_model::CanisterData,
+    util::{
+        call_journal::record_call_in_journal,
+        deferred_job_runner::enqueue_timer_for_running_deferred_jobs,
+        guards::reject_updates_in_read_only_mode,
+    },
+    CANISTER_DATA,
+};
+
+/// Queues a computation that is too heavy for a single query. It runs in the background, a
+/// chunk at a time, and its results are read with `get_deferred_job_results` using the
+/// returned job id until they expire.
+///
+/// # Access Control
+/// Only the profile owner can queue jobs
+#[ic_cdk::update(guard = "reject_updates_in_read_only_mode")]
+#[candid::candid_method(update)]
+fn enqueue_deferred_job(request: DeferredJobRequest) -> Result<DeferredJobId, DeferredJobError> {
+    record_call_in_journal("enqueue_deferred_job");
+
+    let api_caller = ic_cdk::caller();
+    let current_time = system_time::get_current_system_time_from_ic();
+
+    let (job_id, is_runner_idle) = CANISTER_DATA.with(|canister_data_ref_cell| {
+        let mut canister_data = canister_data_ref_cell.borrow_mut();
+        let is_runner_idle = !canister_data.deferred_jobs.has_jobs_in_progress();
+
+        enqueue_deferred_job_impl(&mut canister_data, &api_caller, request, &current_time)
+            .map(|job_id| (job_id, is_runner_idle))
+    })?;
+
+    // * a runner that is already going picks the job up once it is done with the earlier ones
+    if is_runner_idle {
+        enqueue_timer_for_running_deferred_jobs();
+    }
+
+    Ok(job_id)
+}
+
+fn enqueue_deferred_job_impl(
+    canister_data: &mut CanisterData,
+    api_caller: &Principal,
+    request: DeferredJobRequest,
+    current_time: &SystemTime,
+) -> Result<DeferredJobId, DeferredJobError> {
+    if canister_data.profile.principal_id != Some(*api_caller) {
+        return Err(DeferredJobError::Unauthorized);
+    }
+
+    canister_data
+        .deferred_jobs
+        .enqueue(*api_caller, request.into(), current_time)
+}
+
+#[cfg(test)]
+mod test {
+    use test_utils::setup::test_constants::{
+        get_mock_user_alice_principal_id, get_mock_user_bob_principal_id,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_enqueue_deferred_job_impl() {
+        let mut canister_data = CanisterData::default();
+        canister_data.profile.principal_id = Some(get_mock_user_alice_principal_id());
+        let current_time = SystemTime::UNIX_EPOCH;
+
+        assert_eq!(
+            enqueue_deferred_job_impl(
+                &mut canister_data,
+                &get_mock_user_bob_principal_id(),
+                DeferredJobRequest::PostBetAnalyticsRollup,
+                &current_time
+            ),
+            Err(DeferredJobError::Unauthorized)
+        );
+        assert_eq!(
+            enqueue_deferred_job_impl(
+                &mut canister_data,
+                &get_mock_user_alice_principal_id(),
+                DeferredJobRequest::PostBetAnalyticsRollup,
+                &current_time
+            ),
+            Ok(0)
+        );
+        assert!(canister_data.deferred_jobs.has_jobs_in_progress());
+    }
+}
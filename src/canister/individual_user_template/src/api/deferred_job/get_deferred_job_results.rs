@@ -0,0 +1,32 @@
+use shared_utils::{
+    canister_specific::individual_user_template::types::deferred_job::DeferredJobResultItem,
+    common::{
+        types::deferred_job::{DeferredJobError, DeferredJobId, DeferredJobResults},
+        utils::system_time,
+    },
+};
+
+use crate::CANISTER_DATA;
+
+/// The status of a job queued with `enqueue_deferred_job` and a page of its results so far.
+/// Only the principal that queued the job can read them.
+#[ic_cdk::query]
+#[candid::candid_method(query)]
+fn get_deferred_job_results(
+    job_id: DeferredJobId,
+    from_inclusive_index: u64,
+    page_size: u64,
+) -> Result<DeferredJobResults<DeferredJobResultItem>, DeferredJobError> {
+    let api_caller = ic_cdk::caller();
+    let current_time = system_time::get_current_system_time_from_ic();
+
+    CANISTER_DATA.with(|canister_data_ref_cell| {
+        canister_data_ref_cell.borrow().deferred_jobs.get_results(
+            job_id,
+            &api_caller,
+            from_inclusive_index,
+            page_size,
+            &current_time,
+        )
+    })
+}
@@ -0,0 +1,2 @@
+pub mod enqueue_deferred_job;
+pub mod get_deferred_job_results;
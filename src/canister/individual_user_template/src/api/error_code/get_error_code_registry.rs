@@ -0,0 +1,100 @@
+use shared_utils::{
+    canister_specific::individual_user_template::types::error::{
+        BackfillAggregateStatsError, BetOnCurrentlyViewingPostError, BetOnMultiplePostsError,
+        BoostPostError, ClearPostReportsError, CommentError, CreateReferralCodeError,
+        DisputeSlotOutcomeError, EnrollPostInTournamentError, EventCallbackError,
+        ExportTokenTransactionsError, FollowAnotherUserProfileError, FollowGraphMigrationError,
+        GetAccessStatisticsError, GetAppliedMigrationsError, GetBetTraceError, GetBetsForPostError,
+        GetCallJournalEntriesError, GetCertifiedPostDetailsError, GetCreatorCommissionReportError,
+        GetDeadLetteredCallsError, GetFollowingFeedError, GetPostsOfFollowedCreatorsError,
+        GetPostsOfUserProfileError, GetReferralCodesError, GetReferralEarningsError,
+        ModerationError, PotInsuranceClaimError, RecalculateSlotOutcomeError, ReplicationError,
+        ReportPostError, RequestTokensFromFaucetError, RetabulateSlotOutcomeError,
+        RevokeHotOrNotConsentError, SessionDelegateError, UpdateBettingActivityPrivacyError,
+        UpdatePostMinimumBetAmountError, VersusContestError,
+    },
+    common::types::{
+        deferred_job::DeferredJobError,
+        error_code::{ErrorCodeRegistryEntry, HasErrorCode},
+    },
+    types::canister_specific::{
+        individual_user_template::error_types::{
+            GetUserUtilityTokenTransactionHistoryError, UpdateProfileSetUniqueUsernameError,
+        },
+        user_index::error_types::TournamentError,
+    },
+};
+
+use crate::api::profile::update_profile_display_details::UpdateProfileDetailsError;
+
+/// Codes of every error this canister's API can return, including errors wrapped in others.
+/// Clients can fetch this once and key translations and retry policies on the codes.
+#[ic_cdk::query]
+#[candid::candid_method(query)]
+fn get_error_code_registry() -> Vec<ErrorCodeRegistryEntry> {
+    [
+        GetPostsOfUserProfileError::error_code_registry_entries(),
+        BetOnCurrentlyViewingPostError::error_code_registry_entries(),
+        FollowAnotherUserProfileError::error_code_registry_entries(),
+        BackfillAggregateStatsError::error_code_registry_entries(),
+        EnrollPostInTournamentError::error_code_registry_entries(),
+        ExportTokenTransactionsError::error_code_registry_entries(),
+        SessionDelegateError::error_code_registry_entries(),
+        EventCallbackError::error_code_registry_entries(),
+        ReplicationError::error_code_registry_entries(),
+        RevokeHotOrNotConsentError::error_code_registry_entries(),
+        UpdatePostMinimumBetAmountError::error_code_registry_entries(),
+        BoostPostError::error_code_registry_entries(),
+        ReportPostError::error_code_registry_entries(),
+        ClearPostReportsError::error_code_registry_entries(),
+        GetCertifiedPostDetailsError::error_code_registry_entries(),
+        FollowGraphMigrationError::error_code_registry_entries(),
+        GetCreatorCommissionReportError::error_code_registry_entries(),
+        RequestTokensFromFaucetError::error_code_registry_entries(),
+        UpdateBettingActivityPrivacyError::error_code_registry_entries(),
+        DisputeSlotOutcomeError::error_code_registry_entries(),
+        RecalculateSlotOutcomeError::error_code_registry_entries(),
+        GetBetTraceError::error_code_registry_entries(),
+        GetDeadLetteredCallsError::error_code_registry_entries(),
+        GetFollowingFeedError::error_code_registry_entries(),
+        CreateReferralCodeError::error_code_registry_entries(),
+        GetReferralCodesError::error_code_registry_entries(),
+        GetReferralEarningsError::error_code_registry_entries(),
+        BetOnMultiplePostsError::error_code_registry_entries(),
+        GetCallJournalEntriesError::error_code_registry_entries(),
+        GetAccessStatisticsError::error_code_registry_entries(),
+        GetAppliedMigrationsError::error_code_registry_entries(),
+        VersusContestError::error_code_registry_entries(),
+        CommentError::error_code_registry_entries(),
+        GetBetsForPostError::error_code_registry_entries(),
+        PotInsuranceClaimError::error_code_registry_entries(),
+        GetPostsOfFollowedCreatorsError::error_code_registry_entries(),
+        ModerationError::error_code_registry_entries(),
+        RetabulateSlotOutcomeError::error_code_registry_entries(),
+        UpdateProfileSetUniqueUsernameError::error_code_registry_entries(),
+        GetUserUtilityTokenTransactionHistoryError::error_code_registry_entries(),
+        UpdateProfileDetailsError::error_code_registry_entries(),
+        TournamentError::error_code_registry_entries(),
+        DeferredJobError::error_code_registry_entries(),
+    ]
+    .concat()
+}
+
+#[cfg(test)]
+mod test {
+    use shared_utils::common::types::error_code::get_duplicate_error_codes;
+
+    use super::*;
+
+    #[test]
+    fn test_get_error_code_registry() {
+        let registry = get_error_code_registry();
+
+        assert!(get_duplicate_error_codes(&registry).is_empty());
+        assert!(registry
+            .iter()
+            .any(|entry| entry.error_type == "ReportPostError"
+                && entry.variant == "InvalidReason"
+                && entry.error_code == ReportPostError::InvalidReason.error_code()));
+    }
+}
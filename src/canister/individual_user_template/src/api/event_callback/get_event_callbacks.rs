@@ -0,0 +1,26 @@
+use candid::Principal;
+use shared_utils::canister_specific::individual_user_template::types::{
+    error::EventCallbackError, event_callback::EventCallbackRegistration,
+};
+
+use crate::CANISTER_DATA;
+
+/// # Access Control
+/// Only the user whose profile details are stored in this canister can list who is called
+/// back about their activity
+#[ic_cdk::query]
+#[candid::candid_method(query)]
+fn get_event_callbacks() -> Result<Vec<(Principal, EventCallbackRegistration)>, EventCallbackError>
+{
+    let api_caller = ic_cdk::caller();
+
+    CANISTER_DATA.with(|canister_data_ref_cell| {
+        let canister_data = canister_data_ref_cell.borrow();
+
+        if canister_data.profile.principal_id != Some(api_caller) {
+            return Err(EventCallbackError::Unauthorized);
+        }
+
+        Ok(canister_data.event_callbacks.get_registrations())
+    })
+}
@@ -0,0 +1,3 @@
+pub mod get_event_callbacks;
+pub mod register_event_callback;
+pub mod revoke_event_callback;
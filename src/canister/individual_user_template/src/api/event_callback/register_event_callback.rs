@@ -0,0 +1,149 @@
+use std::time::SystemTime;
+
+use candid::Principal;
+use ic_cdk::api::call;
+use shared_utils::{
+    canister_specific::{
+        configuration::types::event_callback::EventCallbackApproval,
+        individual_user_template::types::{
+            error::EventCallbackError, event_callback::RegisterEventCallbackArg,
+        },
+    },
+    common::{types::known_principal::KnownPrincipalType, utils::system_time},
+};
+
+use crate::{
+    data_model::CanisterData,
+    util::{call_journal::record_call_in_journal, guards::reject_updates_in_read_only_mode},
+    CANISTER_DATA,
+};
+
+/// Registers the calling canister to be called back whenever one of the events happens on
+/// this canister, within the events and daily quota the configuration canister approved it
+/// for. Registering again replaces its method and events.
+///
+/// # Access Control
+/// Only canisters approved in the configuration canister can register
+#[ic_cdk::update(guard = "reject_updates_in_read_only_mode")]
+#[candid::candid_method(update)]
+async fn register_event_callback(
+    register_event_callback_arg: RegisterEventCallbackArg,
+) -> Result<(), EventCallbackError> {
+    record_call_in_journal("register_event_callback");
+
+    let registrant = ic_cdk::caller();
+
+    let configuration_canister_id = CANISTER_DATA
+        .with(|canister_data_ref_cell| {
+            canister_data_ref_cell
+                .borrow()
+                .known_principal_ids
+                .get(&KnownPrincipalType::CanisterIdConfiguration)
+                .copied()
+        })
+        .ok_or(EventCallbackError::ConfigurationCanisterUnreachable)?;
+
+    let (approval,): (Option<EventCallbackApproval>,) = call::call(
+        configuration_canister_id,
+        "get_event_callback_approval",
+        (registrant,),
+    )
+    .await
+    .map_err(|_| EventCallbackError::ConfigurationCanisterUnreachable)?;
+
+    let current_time = system_time::get_current_system_time_from_ic();
+
+    CANISTER_DATA.with(|canister_data_ref_cell| {
+        register_event_callback_impl(
+            &mut canister_data_ref_cell.borrow_mut(),
+            registrant,
+            register_event_callback_arg,
+            approval,
+            &current_time,
+        )
+    })
+}
+
+fn register_event_callback_impl(
+    canister_data: &mut CanisterData,
+    registrant: Principal,
+    register_event_callback_arg: RegisterEventCallbackArg,
+    approval: Option<EventCallbackApproval>,
+    current_time: &SystemTime,
+) -> Result<(), EventCallbackError> {
+    if registrant == Principal::anonymous()
+        || canister_data.profile.principal_id == Some(registrant)
+    {
+        return Err(EventCallbackError::Unauthorized);
+    }
+
+    let approval = approval.ok_or(EventCallbackError::NotApproved)?;
+
+    canister_data.event_callbacks.register(
+        registrant,
+        register_event_callback_arg,
+        &approval,
+        current_time,
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use shared_utils::canister_specific::configuration::types::event_callback::EventCallbackKind;
+    use test_utils::setup::test_constants::{
+        get_mock_user_alice_principal_id, get_mock_user_bob_canister_id,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_register_event_callback_impl() {
+        let mut canister_data = CanisterData::default();
+        canister_data.profile.principal_id = Some(get_mock_user_alice_principal_id());
+        let current_time = SystemTime::now();
+        let register_event_callback_arg = RegisterEventCallbackArg {
+            method_name: "on_hot_or_not_event".to_string(),
+            events: vec![EventCallbackKind::PostCreated],
+        };
+        let approval = EventCallbackApproval {
+            allowed_events: vec![EventCallbackKind::PostCreated],
+            max_callbacks_per_day: 10,
+        };
+
+        assert_eq!(
+            register_event_callback_impl(
+                &mut canister_data,
+                get_mock_user_alice_principal_id(),
+                register_event_callback_arg.clone(),
+                Some(approval.clone()),
+                &current_time,
+            ),
+            Err(EventCallbackError::Unauthorized)
+        );
+        assert_eq!(
+            register_event_callback_impl(
+                &mut canister_data,
+                get_mock_user_bob_canister_id(),
+                register_event_callback_arg.clone(),
+                None,
+                &current_time,
+            ),
+            Err(EventCallbackError::NotApproved)
+        );
+        assert_eq!(
+            register_event_callback_impl(
+                &mut canister_data,
+                get_mock_user_bob_canister_id(),
+                register_event_callback_arg,
+                Some(approval),
+                &current_time,
+            ),
+            Ok(())
+        );
+
+        let registrations = canister_data.event_callbacks.get_registrations();
+        assert_eq!(registrations.len(), 1);
+        assert_eq!(registrations[0].0, get_mock_user_bob_canister_id());
+        assert_eq!(registrations[0].1.max_callbacks_per_day, 10);
+    }
+}
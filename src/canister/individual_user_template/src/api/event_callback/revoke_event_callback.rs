@@ -0,0 +1,111 @@
+use candid::Principal;
+use shared_utils::canister_specific::individual_user_template::types::error::EventCallbackError;
+
+use crate::{
+    data_model::CanisterData,
+    util::{call_journal::record_call_in_journal, guards::reject_updates_in_read_only_mode},
+    CANISTER_DATA,
+};
+
+/// # Access Control
+/// The profile owner can revoke any registrant. A registrant can only revoke itself
+#[ic_cdk::update(guard = "reject_updates_in_read_only_mode")]
+#[candid::candid_method(update)]
+fn revoke_event_callback(registrant: Principal) -> Result<(), EventCallbackError> {
+    record_call_in_journal("revoke_event_callback");
+
+    let api_caller = ic_cdk::caller();
+
+    CANISTER_DATA.with(|canister_data_ref_cell| {
+        revoke_event_callback_impl(
+            &mut canister_data_ref_cell.borrow_mut(),
+            &api_caller,
+            &registrant,
+        )
+    })
+}
+
+fn revoke_event_callback_impl(
+    canister_data: &mut CanisterData,
+    api_caller: &Principal,
+    registrant: &Principal,
+) -> Result<(), EventCallbackError> {
+    if canister_data.profile.principal_id != Some(*api_caller) && api_caller != registrant {
+        return Err(EventCallbackError::Unauthorized);
+    }
+
+    if !canister_data.event_callbacks.revoke(registrant) {
+        return Err(EventCallbackError::RegistrationNotFound);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::SystemTime;
+
+    use shared_utils::canister_specific::{
+        configuration::types::event_callback::{EventCallbackApproval, EventCallbackKind},
+        individual_user_template::types::event_callback::RegisterEventCallbackArg,
+    };
+    use test_utils::setup::test_constants::{
+        get_mock_user_alice_principal_id, get_mock_user_bob_canister_id,
+        get_mock_user_charlie_canister_id,
+    };
+
+    use super::*;
+
+    fn register(canister_data: &mut CanisterData, registrant: Principal) {
+        canister_data
+            .event_callbacks
+            .register(
+                registrant,
+                RegisterEventCallbackArg {
+                    method_name: "on_hot_or_not_event".to_string(),
+                    events: vec![EventCallbackKind::BetWon],
+                },
+                &EventCallbackApproval {
+                    allowed_events: vec![EventCallbackKind::BetWon],
+                    max_callbacks_per_day: 10,
+                },
+                &SystemTime::now(),
+            )
+            .unwrap();
+    }
+
+    #[test]
+    fn test_revoke_event_callback_impl() {
+        let mut canister_data = CanisterData::default();
+        canister_data.profile.principal_id = Some(get_mock_user_alice_principal_id());
+        let bob = get_mock_user_bob_canister_id();
+        let charlie = get_mock_user_charlie_canister_id();
+        register(&mut canister_data, bob);
+        register(&mut canister_data, charlie);
+
+        assert_eq!(
+            revoke_event_callback_impl(&mut canister_data, &charlie, &bob),
+            Err(EventCallbackError::Unauthorized)
+        );
+        assert_eq!(
+            revoke_event_callback_impl(&mut canister_data, &charlie, &charlie),
+            Ok(())
+        );
+        assert_eq!(
+            revoke_event_callback_impl(
+                &mut canister_data,
+                &get_mock_user_alice_principal_id(),
+                &bob
+            ),
+            Ok(())
+        );
+        assert_eq!(
+            revoke_event_callback_impl(
+                &mut canister_data,
+                &get_mock_user_alice_principal_id(),
+                &bob
+            ),
+            Err(EventCallbackError::RegistrationNotFound)
+        );
+    }
+}
@@ -0,0 +1,308 @@
+use candid::Principal;
+use shared_utils::{
+    canister_specific::individual_user_template::types::{
+        error::FollowAnotherUserProfileError, feature_usage::Feature, follow::FollowEntryDetail,
+        profile::UserProfileDetailsForFrontend,
+    },
+    common::utils::{inter_canister_call, system_time},
+};
+
+use crate::{
+    data_model::CanisterData,
+    util::{
+        call_journal::record_call_in_journal,
+        canister_binding::{
+            get_binding_epoch_of_user_canister, re_resolve_binding_epoch_of_user_canister,
+        },
+        guards::reject_updates_in_read_only_mode,
+    },
+    CANISTER_DATA,
+};
+
+use super::{
+    update_profiles_i_follow_toggle_list_with_specified_profile::MAX_USERS_IN_FOLLOWER_FOLLOWING_LIST,
+    update_profiles_that_follow_me_toggle_list_with_specified_profile::FollowerArg,
+};
+
+/// Follows the profile whose canister is `followee_canister_id`, adding this profile to its
+/// followers before adding it to the profiles this one follows. Following a profile that is
+/// already followed does nothing.
+///
+/// # Access Control
+/// Only the user whose profile details are stored in this canister can follow another user's profile.
+#[ic_cdk::update(guard = "reject_updates_in_read_only_mode")]
+#[candid::candid_method(update)]
+async fn follow_user(followee_canister_id: Principal) -> Result<(), FollowAnotherUserProfileError> {
+    record_call_in_journal("follow_user");
+
+    let current_caller = ic_cdk::caller();
+
+    let my_principal_id = CANISTER_DATA.with(|canister_data_ref_cell| {
+        validate_follow_request(
+            &canister_data_ref_cell.borrow(),
+            &current_caller,
+            &ic_cdk::id(),
+            &followee_canister_id,
+        )
+    })?;
+
+    let followee_entry_detail = FollowEntryDetail {
+        principal_id: get_followee_principal_id(followee_canister_id).await?,
+        canister_id: followee_canister_id,
+    };
+
+    let is_followee_already_followed = CANISTER_DATA.with(|canister_data_ref_cell| {
+        is_already_following(&canister_data_ref_cell.borrow(), &followee_entry_detail)
+    })?;
+    if is_followee_already_followed {
+        return Ok(());
+    }
+
+    update_follow_status_in_followees_canister(followee_canister_id, my_principal_id, true).await?;
+
+    CANISTER_DATA.with(|canister_data_ref_cell| {
+        let mut canister_data = canister_data_ref_cell.borrow_mut();
+        add_followee(&mut canister_data, followee_entry_detail);
+        canister_data.record_feature_usage(
+            Feature::ProfileFollowed,
+            &system_time::get_current_system_time_from_ic(),
+        );
+    });
+
+    Ok(())
+}
+
+/// Returns the principal of the profile owner, who is the only one allowed to change whom
+/// the profile follows
+pub(super) fn validate_follow_request(
+    canister_data: &CanisterData,
+    current_caller: &Principal,
+    my_canister_id: &Principal,
+    followee_canister_id: &Principal,
+) -> Result<Principal, FollowAnotherUserProfileError> {
+    if *current_caller == Principal::anonymous() {
+        return Err(FollowAnotherUserProfileError::Unauthenticated);
+    }
+
+    if canister_data.profile.principal_id != Some(*current_caller) {
+        return Err(FollowAnotherUserProfileError::Unauthorized);
+    }
+
+    if *followee_canister_id == *my_canister_id {
+        return Err(FollowAnotherUserProfileError::CannotFollowOwnProfile);
+    }
+
+    Ok(*current_caller)
+}
+
+fn is_already_following(
+    canister_data: &CanisterData,
+    followee_entry_detail: &FollowEntryDetail,
+) -> Result<bool, FollowAnotherUserProfileError> {
+    let following = &canister_data.follow_data.following;
+
+    if following.contains(followee_entry_detail) {
+        return Ok(true);
+    }
+
+    if following.len() as u64 >= MAX_USERS_IN_FOLLOWER_FOLLOWING_LIST {
+        return Err(FollowAnotherUserProfileError::UsersICanFollowListIsFull);
+    }
+
+    Ok(false)
+}
+
+fn add_followee(canister_data: &mut CanisterData, followee_entry_detail: FollowEntryDetail) {
+    let following = &mut canister_data.follow_data.following;
+
+    if !following.contains(&followee_entry_detail) {
+        following.add(followee_entry_detail);
+    }
+}
+
+async fn get_followee_principal_id(
+    followee_canister_id: Principal,
+) -> Result<Principal, FollowAnotherUserProfileError> {
+    let (profile_details,) = inter_canister_call::call::<_, (UserProfileDetailsForFrontend,)>(
+        followee_canister_id,
+        "get_profile_details",
+        (),
+    )
+    .await
+    .map_err(|_| FollowAnotherUserProfileError::UserITriedToFollowCrossCanisterCallFailed)?;
+
+    Ok(profile_details.principal_id)
+}
+
+/// Adds this profile to, or removes it from, the followee's followers. Resolves the binding
+/// epoch of the followee's canister again and retries once if the cached one was stale.
+pub(super) async fn update_follow_status_in_followees_canister(
+    followee_canister_id: Principal,
+    my_principal_id: Principal,
+    is_following: bool,
+) -> Result<(), FollowAnotherUserProfileError> {
+    let expected_binding_epoch = get_binding_epoch_of_user_canister(followee_canister_id).await;
+
+    match call_followees_canister(
+        followee_canister_id,
+        my_principal_id,
+        is_following,
+        expected_binding_epoch,
+    )
+    .await
+    {
+        // * the followee's canister was reinstalled since its epoch was last resolved
+        Err(FollowAnotherUserProfileError::StaleCanisterBinding) => {
+            let binding_epoch =
+                re_resolve_binding_epoch_of_user_canister(followee_canister_id).await;
+            call_followees_canister(
+                followee_canister_id,
+                my_principal_id,
+                is_following,
+                binding_epoch,
+            )
+            .await
+        }
+        result => result,
+    }
+}
+
+async fn call_followees_canister(
+    followee_canister_id: Principal,
+    my_principal_id: Principal,
+    is_following: bool,
+    expected_binding_epoch: Option<u64>,
+) -> Result<(), FollowAnotherUserProfileError> {
+    inter_canister_call::call::<_, (Result<(), FollowAnotherUserProfileError>,)>(
+        followee_canister_id,
+        "receive_follow_status_from_follower",
+        (
+            FollowerArg {
+                follower_principal_id: my_principal_id,
+                follower_canister_id: ic_cdk::id(),
+            },
+            is_following,
+            expected_binding_epoch,
+        ),
+    )
+    .await
+    .map_err(|_| FollowAnotherUserProfileError::UserITriedToFollowCrossCanisterCallFailed)?
+    .0
+}
+
+#[cfg(test)]
+mod test {
+    use test_utils::setup::test_constants::{
+        get_mock_user_alice_canister_id, get_mock_user_alice_principal_id,
+        get_mock_user_bob_canister_id, get_mock_user_bob_principal_id,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_validate_follow_request() {
+        let mut canister_data = CanisterData::default();
+        canister_data.profile.principal_id = Some(get_mock_user_alice_principal_id());
+        let my_canister_id = get_mock_user_alice_canister_id();
+        let followee_canister_id = get_mock_user_bob_canister_id();
+
+        assert_eq!(
+            validate_follow_request(
+                &canister_data,
+                &Principal::anonymous(),
+                &my_canister_id,
+                &followee_canister_id,
+            ),
+            Err(FollowAnotherUserProfileError::Unauthenticated)
+        );
+        assert_eq!(
+            validate_follow_request(
+                &canister_data,
+                &get_mock_user_bob_principal_id(),
+                &my_canister_id,
+                &followee_canister_id,
+            ),
+            Err(FollowAnotherUserProfileError::Unauthorized)
+        );
+        assert_eq!(
+            validate_follow_request(
+                &canister_data,
+                &get_mock_user_alice_principal_id(),
+                &my_canister_id,
+                &my_canister_id,
+            ),
+            Err(FollowAnotherUserProfileError::CannotFollowOwnProfile)
+        );
+        assert_eq!(
+            validate_follow_request(
+                &canister_data,
+                &get_mock_user_alice_principal_id(),
+                &my_canister_id,
+                &followee_canister_id,
+            ),
+            Ok(get_mock_user_alice_principal_id())
+        );
+    }
+
+    #[test]
+    fn test_is_already_following() {
+        let mut canister_data = CanisterData::default();
+        let followee_entry_detail = FollowEntryDetail {
+            principal_id: get_mock_user_bob_principal_id(),
+            canister_id: get_mock_user_bob_canister_id(),
+        };
+
+        assert_eq!(
+            is_already_following(&canister_data, &followee_entry_detail),
+            Ok(false)
+        );
+
+        canister_data
+            .follow_data
+            .following
+            .add(followee_entry_detail.clone());
+        assert_eq!(
+            is_already_following(&canister_data, &followee_entry_detail),
+            Ok(true)
+        );
+
+        (1..MAX_USERS_IN_FOLLOWER_FOLLOWING_LIST).for_each(|id: u64| {
+            canister_data.follow_data.following.add(FollowEntryDetail {
+                principal_id: Principal::self_authenticating(id.to_ne_bytes()),
+                canister_id: Principal::self_authenticating(id.to_ne_bytes()),
+            });
+        });
+        assert_eq!(
+            is_already_following(&canister_data, &followee_entry_detail),
+            Ok(true)
+        );
+        assert_eq!(
+            is_already_following(
+                &canister_data,
+                &FollowEntryDetail {
+                    principal_id: get_mock_user_alice_principal_id(),
+                    canister_id: get_mock_user_alice_canister_id(),
+                }
+            ),
+            Err(FollowAnotherUserProfileError::UsersICanFollowListIsFull)
+        );
+    }
+
+    #[test]
+    fn test_add_followee() {
+        let mut canister_data = CanisterData::default();
+        let followee_entry_detail = FollowEntryDetail {
+            principal_id: get_mock_user_bob_principal_id(),
+            canister_id: get_mock_user_bob_canister_id(),
+        };
+
+        add_followee(&mut canister_data, followee_entry_detail.clone());
+        add_followee(&mut canister_data, followee_entry_detail.clone());
+
+        assert_eq!(canister_data.follow_data.following.len(), 1);
+        assert!(canister_data
+            .follow_data
+            .following
+            .contains(&followee_entry_detail));
+    }
+}
@@ -0,0 +1,150 @@
+use candid::Principal;
+use shared_utils::{
+    canister_specific::individual_user_template::types::{
+        error::FollowGraphMigrationError,
+        follow::{
+            FollowEntryId, FollowListExportChunk, FollowListType,
+            MAX_FOLLOW_ENTRIES_IN_ONE_MIGRATION_CHUNK,
+        },
+    },
+    common::{
+        types::known_principal::KnownPrincipalType, utils::known_caller::authorize_known_caller,
+    },
+};
+
+use crate::{data_model::CanisterData, CANISTER_DATA};
+
+/// Exports the follower or following list in ascending index order, 500 entries at a time,
+/// for the user index to import into the canister this profile is migrating to. Start from
+/// index 0 and keep passing `next_from_inclusive_index` until it comes back empty.
+///
+/// # Access Control
+/// Only the user index canister can export the follow graph
+#[ic_cdk::query]
+#[candid::candid_method(query)]
+fn get_follow_list_export_chunk(
+    follow_list_type: FollowListType,
+    from_inclusive_index: FollowEntryId,
+) -> Result<FollowListExportChunk, FollowGraphMigrationError> {
+    let api_caller = ic_cdk::caller();
+
+    CANISTER_DATA.with(|canister_data_ref_cell| {
+        get_follow_list_export_chunk_impl(
+            &canister_data_ref_cell.borrow(),
+            &api_caller,
+            follow_list_type,
+            from_inclusive_index,
+        )
+    })
+}
+
+fn get_follow_list_export_chunk_impl(
+    canister_data: &CanisterData,
+    api_caller: &Principal,
+    follow_list_type: FollowListType,
+    from_inclusive_index: FollowEntryId,
+) -> Result<FollowListExportChunk, FollowGraphMigrationError> {
+    authorize_known_caller(
+        api_caller,
+        &canister_data.known_principal_ids,
+        &[KnownPrincipalType::CanisterIdUserIndex],
+    )
+    .map_err(|_| FollowGraphMigrationError::Unauthorized)?;
+
+    Ok(canister_data
+        .follow_data
+        .get_list(follow_list_type)
+        .get_export_chunk(
+            from_inclusive_index,
+            MAX_FOLLOW_ENTRIES_IN_ONE_MIGRATION_CHUNK,
+        ))
+}
+
+#[cfg(test)]
+mod test {
+    use shared_utils::canister_specific::individual_user_template::types::follow::FollowEntryDetail;
+    use test_utils::setup::{
+        spoofed_callers::{assert_only_allowed_callers_are_accepted, get_mock_known_principal_map},
+        test_constants::{
+            get_mock_canister_id_user_index,
+            v2::{canister, user},
+        },
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_get_follow_list_export_chunk_impl() {
+        let mut canister_data = CanisterData {
+            known_principal_ids: get_mock_known_principal_map(),
+            ..Default::default()
+        };
+        (0..MAX_FOLLOW_ENTRIES_IN_ONE_MIGRATION_CHUNK + 1).for_each(|index| {
+            canister_data.follow_data.follower.add(FollowEntryDetail {
+                principal_id: user(&format!("follower-{}", index)),
+                canister_id: canister(&format!("follower-{}", index)),
+            });
+        });
+
+        let first_chunk = get_follow_list_export_chunk_impl(
+            &canister_data,
+            &get_mock_canister_id_user_index(),
+            FollowListType::Follower,
+            0,
+        )
+        .unwrap();
+        assert_eq!(
+            first_chunk.entries.len(),
+            MAX_FOLLOW_ENTRIES_IN_ONE_MIGRATION_CHUNK
+        );
+        assert_eq!(
+            first_chunk.next_from_inclusive_index,
+            Some(MAX_FOLLOW_ENTRIES_IN_ONE_MIGRATION_CHUNK as u64)
+        );
+
+        let last_chunk = get_follow_list_export_chunk_impl(
+            &canister_data,
+            &get_mock_canister_id_user_index(),
+            FollowListType::Follower,
+            first_chunk.next_from_inclusive_index.unwrap(),
+        )
+        .unwrap();
+        assert_eq!(last_chunk.entries.len(), 1);
+        assert_eq!(last_chunk.next_from_inclusive_index, None);
+
+        assert_eq!(
+            get_follow_list_export_chunk_impl(
+                &canister_data,
+                &get_mock_canister_id_user_index(),
+                FollowListType::Following,
+                0,
+            ),
+            Ok(FollowListExportChunk {
+                entries: vec![],
+                next_from_inclusive_index: None,
+            })
+        );
+    }
+
+    #[test]
+    fn test_get_follow_list_export_chunk_impl_with_spoofed_callers() {
+        let canister_data = CanisterData {
+            known_principal_ids: get_mock_known_principal_map(),
+            ..Default::default()
+        };
+
+        assert_only_allowed_callers_are_accepted(
+            &canister_data.known_principal_ids,
+            &[KnownPrincipalType::CanisterIdUserIndex],
+            |caller| {
+                get_follow_list_export_chunk_impl(
+                    &canister_data,
+                    caller,
+                    FollowListType::Following,
+                    0,
+                )
+                .is_ok()
+            },
+        );
+    }
+}
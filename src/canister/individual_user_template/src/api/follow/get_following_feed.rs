@@ -0,0 +1,94 @@
+use shared_utils::{
+    canister_specific::individual_user_template::types::{
+        error::GetFollowingFeedError, follow::following_feed::FollowingFeedItem,
+    },
+    pagination::{self, PaginationError},
+};
+
+use crate::{data_model::CanisterData, CANISTER_DATA};
+
+/// Latest posts of the profiles this profile follows, newest first
+#[ic_cdk::query]
+#[candid::candid_method(query)]
+fn get_following_feed(
+    from_inclusive_index: u64,
+    to_exclusive_index: u64,
+) -> Result<Vec<FollowingFeedItem>, GetFollowingFeedError> {
+    CANISTER_DATA.with(|canister_data_ref_cell| {
+        get_following_feed_impl(
+            &canister_data_ref_cell.borrow(),
+            from_inclusive_index,
+            to_exclusive_index,
+        )
+    })
+}
+
+fn get_following_feed_impl(
+    canister_data: &CanisterData,
+    from_inclusive_index: u64,
+    to_exclusive_index: u64,
+) -> Result<Vec<FollowingFeedItem>, GetFollowingFeedError> {
+    let following_feed = canister_data.following_feed_cache.get_feed();
+
+    let (from_inclusive_index, to_exclusive_index) = pagination::get_pagination_bounds(
+        from_inclusive_index,
+        to_exclusive_index,
+        following_feed.len() as u64,
+    )
+    .map_err(|e| match e {
+        PaginationError::InvalidBoundsPassed => GetFollowingFeedError::InvalidBoundsPassed,
+        PaginationError::ReachedEndOfItemsList => GetFollowingFeedError::ReachedEndOfItemsList,
+        PaginationError::ExceededMaxNumberOfItemsAllowedInOneRequest => {
+            GetFollowingFeedError::ExceededMaxNumberOfItemsAllowedInOneRequest
+        }
+    })?;
+
+    Ok(following_feed[from_inclusive_index as usize..to_exclusive_index as usize].to_vec())
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::{Duration, SystemTime};
+
+    use shared_utils::canister_specific::individual_user_template::types::follow::following_feed::PostFromFollowee;
+    use test_utils::setup::test_constants::{
+        get_mock_user_alice_canister_id, get_mock_user_alice_principal_id,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_get_following_feed_impl() {
+        let mut canister_data = CanisterData::default();
+
+        assert_eq!(
+            get_following_feed_impl(&canister_data, 0, 10),
+            Err(GetFollowingFeedError::ReachedEndOfItemsList)
+        );
+
+        (0..3).for_each(|post_id| {
+            canister_data.following_feed_cache.add_post(
+                get_mock_user_alice_canister_id(),
+                PostFromFollowee {
+                    publisher_principal_id: get_mock_user_alice_principal_id(),
+                    post_id,
+                    created_at: SystemTime::UNIX_EPOCH + Duration::from_secs(post_id),
+                },
+            );
+        });
+
+        assert_eq!(
+            get_following_feed_impl(&canister_data, 1, 10).map(|following_feed| {
+                following_feed
+                    .iter()
+                    .map(|feed_item| feed_item.post_id)
+                    .collect::<Vec<_>>()
+            }),
+            Ok(vec![1, 0])
+        );
+        assert_eq!(
+            get_following_feed_impl(&canister_data, 2, 1),
+            Err(GetFollowingFeedError::InvalidBoundsPassed)
+        );
+    }
+}
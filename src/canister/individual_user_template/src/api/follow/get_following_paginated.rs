@@ -0,0 +1,70 @@
+use shared_utils::{
+    canister_specific::individual_user_template::types::follow::FollowEntryDetail, pagination::Page,
+};
+
+use crate::{data_model::CanisterData, CANISTER_DATA};
+
+/// Profiles this one follows, in the order they were followed. Indices are positions in the list.
+#[ic_cdk::query]
+#[candid::candid_method(query)]
+fn get_following_paginated(from_inclusive_index: u64, page_size: u64) -> Page<FollowEntryDetail> {
+    CANISTER_DATA.with(|canister_data_ref_cell| {
+        get_following_paginated_impl(
+            &canister_data_ref_cell.borrow(),
+            from_inclusive_index,
+            page_size,
+        )
+    })
+}
+
+fn get_following_paginated_impl(
+    canister_data: &CanisterData,
+    from_inclusive_index: u64,
+    page_size: u64,
+) -> Page<FollowEntryDetail> {
+    canister_data
+        .follow_data
+        .following
+        .get_page(from_inclusive_index, page_size)
+}
+
+#[cfg(test)]
+mod test {
+    use candid::Principal;
+
+    use super::*;
+
+    #[test]
+    fn test_get_following_paginated_impl() {
+        let mut canister_data = CanisterData::default();
+
+        assert_eq!(
+            get_following_paginated_impl(&canister_data, 0, 10).total_count,
+            0
+        );
+
+        let get_follow_entry_detail = |id: u64| FollowEntryDetail {
+            principal_id: Principal::self_authenticating(id.to_ne_bytes()),
+            canister_id: Principal::self_authenticating(id.to_ne_bytes()),
+        };
+        (0..25).for_each(|id| {
+            canister_data
+                .follow_data
+                .following
+                .add(get_follow_entry_detail(id));
+        });
+
+        let page = get_following_paginated_impl(&canister_data, 20, 10);
+
+        assert_eq!(
+            page.items,
+            (20..25).map(get_follow_entry_detail).collect::<Vec<_>>()
+        );
+        assert_eq!(page.total_count, 25);
+        assert_eq!(page.next_from_inclusive_index, None);
+        assert_eq!(
+            get_following_paginated_impl(&canister_data, 0, 10).next_from_inclusive_index,
+            Some(10)
+        );
+    }
+}
@@ -0,0 +1,283 @@
+use std::time::{Duration, SystemTime};
+
+use candid::Principal;
+use shared_utils::{
+    canister_specific::individual_user_template::types::{
+        error::GetPostsOfFollowedCreatorsError,
+        follow::followed_creators_feed::{
+            FollowedCreatorsFeedCache, MAX_FOLLOWED_CREATORS_IN_FEED,
+            MAX_POSTS_PER_FOLLOWED_CREATOR_IN_FEED,
+        },
+        post::PostDetailsForFrontend,
+    },
+    common::utils::{
+        bounded_fan_out::{self, FanOutLimits},
+        system_time,
+    },
+    pagination::{self, PaginationError},
+};
+
+use crate::{data_model::CanisterData, util::call_journal::record_call_in_journal, CANISTER_DATA};
+
+const FOLLOWED_CREATORS_FEED_FAN_OUT_LIMITS: FanOutLimits = FanOutLimits {
+    max_concurrent_calls: 10,
+    per_call_timeout: Duration::from_secs(10),
+};
+
+/// Latest posts of the creators this profile follows, newest first, fetched from their
+/// canisters. Only the `MAX_FOLLOWED_CREATORS_IN_FEED` most recently followed creators are
+/// included. The merged feed is cached for a short while, so scrolling through it only
+/// fans out once. Creators whose canister couldn't be read in time are left out until the
+/// cache is refreshed.
+///
+/// # Access Control
+/// Only the user whose profile details are stored in this canister can get their feed
+#[ic_cdk::update]
+#[candid::candid_method(update)]
+async fn get_posts_of_followed_creators_paginated(
+    from_inclusive_index: u64,
+    limit: u64,
+) -> Result<Vec<PostDetailsForFrontend>, GetPostsOfFollowedCreatorsError> {
+    record_call_in_journal("get_posts_of_followed_creators_paginated");
+
+    let current_caller = ic_cdk::caller();
+
+    let followed_creator_canister_ids = CANISTER_DATA.with(|canister_data_ref_cell| {
+        get_followed_creators_to_fan_out_to(
+            &canister_data_ref_cell.borrow(),
+            &current_caller,
+            &system_time::get_current_system_time_from_ic(),
+        )
+    })?;
+
+    if let Some(followed_creator_canister_ids) = followed_creator_canister_ids {
+        let latest_posts_calls = followed_creator_canister_ids.into_iter().map(
+            |followed_creator_canister_id| async move {
+                ic_cdk::call::<_, (Vec<PostDetailsForFrontend>,)>(
+                    followed_creator_canister_id,
+                    "get_latest_posts_of_this_user_profile",
+                    (MAX_POSTS_PER_FOLLOWED_CREATOR_IN_FEED, current_caller),
+                )
+                .await
+            },
+        );
+        let posts_by_creator = bounded_fan_out::fan_out_calls(
+            latest_posts_calls,
+            &FOLLOWED_CREATORS_FEED_FAN_OUT_LIMITS,
+        )
+        .await
+        .into_iter()
+        .filter_map(|response| response.ok()?.ok())
+        .map(|(latest_posts,)| latest_posts)
+        .collect();
+
+        CANISTER_DATA.with(|canister_data_ref_cell| {
+            canister_data_ref_cell
+                .borrow_mut()
+                .followed_creators_feed_cache = Some(FollowedCreatorsFeedCache::new(
+                posts_by_creator,
+                system_time::get_current_system_time_from_ic(),
+            ));
+        });
+    }
+
+    CANISTER_DATA.with(|canister_data_ref_cell| {
+        get_posts_of_followed_creators_page(
+            &canister_data_ref_cell.borrow(),
+            from_inclusive_index,
+            limit,
+        )
+    })
+}
+
+/// Canisters of the followed creators to fetch posts from, or `None` if the cached feed is
+/// still fresh
+fn get_followed_creators_to_fan_out_to(
+    canister_data: &CanisterData,
+    current_caller: &Principal,
+    current_time: &SystemTime,
+) -> Result<Option<Vec<Principal>>, GetPostsOfFollowedCreatorsError> {
+    if canister_data.profile.principal_id != Some(*current_caller) {
+        return Err(GetPostsOfFollowedCreatorsError::Unauthorized);
+    }
+
+    if canister_data
+        .followed_creators_feed_cache
+        .as_ref()
+        .is_some_and(|followed_creators_feed_cache| {
+            followed_creators_feed_cache.is_fresh(current_time)
+        })
+    {
+        return Ok(None);
+    }
+
+    Ok(Some(
+        canister_data
+            .follow_data
+            .following
+            .iter_rev_from(u64::MAX)
+            .take(MAX_FOLLOWED_CREATORS_IN_FEED)
+            .map(|(_, follow_entry_detail)| follow_entry_detail.canister_id)
+            .collect(),
+    ))
+}
+
+fn get_posts_of_followed_creators_page(
+    canister_data: &CanisterData,
+    from_inclusive_index: u64,
+    limit: u64,
+) -> Result<Vec<PostDetailsForFrontend>, GetPostsOfFollowedCreatorsError> {
+    let posts = canister_data
+        .followed_creators_feed_cache
+        .as_ref()
+        .map(|followed_creators_feed_cache| followed_creators_feed_cache.get_posts())
+        .unwrap_or_default();
+
+    let (from_inclusive_index, to_exclusive_index) = pagination::get_pagination_bounds(
+        from_inclusive_index,
+        from_inclusive_index.saturating_add(limit),
+        posts.len() as u64,
+    )
+    .map_err(|e| match e {
+        PaginationError::InvalidBoundsPassed => {
+            GetPostsOfFollowedCreatorsError::InvalidBoundsPassed
+        }
+        PaginationError::ReachedEndOfItemsList => {
+            GetPostsOfFollowedCreatorsError::ReachedEndOfItemsList
+        }
+        PaginationError::ExceededMaxNumberOfItemsAllowedInOneRequest => {
+            GetPostsOfFollowedCreatorsError::ExceededMaxNumberOfItemsAllowedInOneRequest
+        }
+    })?;
+
+    Ok(posts[from_inclusive_index as usize..to_exclusive_index as usize].to_vec())
+}
+
+#[cfg(test)]
+mod test {
+    use shared_utils::canister_specific::individual_user_template::types::{
+        follow::{followed_creators_feed::FOLLOWED_CREATORS_FEED_CACHE_TTL, FollowEntryDetail},
+        post::PostStatus,
+    };
+    use test_utils::setup::test_constants::{
+        get_mock_user_alice_principal_id, get_mock_user_bob_principal_id,
+    };
+
+    use super::*;
+
+    fn get_post(id: u64) -> PostDetailsForFrontend {
+        PostDetailsForFrontend {
+            id,
+            created_by_display_name: None,
+            created_by_unique_user_name: None,
+            created_by_user_principal_id: get_mock_user_bob_principal_id(),
+            created_by_profile_photo_url: None,
+            created_at: SystemTime::UNIX_EPOCH + Duration::from_secs(id),
+            description: String::new(),
+            hashtags: vec![],
+            video_uid: String::new(),
+            status: PostStatus::ReadyToView,
+            total_view_count: 0,
+            like_count: 0,
+            liked_by_me: false,
+            home_feed_ranking_score: 0,
+            hot_or_not_feed_ranking_score: None,
+            hot_or_not_betting_status: None,
+            title: None,
+            category: None,
+            active_boost: None,
+        }
+    }
+
+    #[test]
+    fn test_get_followed_creators_to_fan_out_to() {
+        let mut canister_data = CanisterData::default();
+        canister_data.profile.principal_id = Some(get_mock_user_alice_principal_id());
+        let current_time = SystemTime::now();
+
+        assert_eq!(
+            get_followed_creators_to_fan_out_to(
+                &canister_data,
+                &get_mock_user_bob_principal_id(),
+                &current_time,
+            ),
+            Err(GetPostsOfFollowedCreatorsError::Unauthorized)
+        );
+
+        let get_canister_id = |id: u64| Principal::self_authenticating(id.to_ne_bytes());
+        (0..MAX_FOLLOWED_CREATORS_IN_FEED as u64 + 5).for_each(|id| {
+            canister_data.follow_data.following.add(FollowEntryDetail {
+                principal_id: get_canister_id(id),
+                canister_id: get_canister_id(id),
+            });
+        });
+
+        let followed_creator_canister_ids = get_followed_creators_to_fan_out_to(
+            &canister_data,
+            &get_mock_user_alice_principal_id(),
+            &current_time,
+        )
+        .unwrap()
+        .unwrap();
+        assert_eq!(
+            followed_creator_canister_ids.len(),
+            MAX_FOLLOWED_CREATORS_IN_FEED
+        );
+        assert_eq!(
+            followed_creator_canister_ids[0],
+            get_canister_id(MAX_FOLLOWED_CREATORS_IN_FEED as u64 + 4)
+        );
+
+        canister_data.followed_creators_feed_cache =
+            Some(FollowedCreatorsFeedCache::new(vec![], current_time));
+        assert_eq!(
+            get_followed_creators_to_fan_out_to(
+                &canister_data,
+                &get_mock_user_alice_principal_id(),
+                &current_time,
+            ),
+            Ok(None)
+        );
+        assert!(get_followed_creators_to_fan_out_to(
+            &canister_data,
+            &get_mock_user_alice_principal_id(),
+            &(current_time + FOLLOWED_CREATORS_FEED_CACHE_TTL),
+        )
+        .unwrap()
+        .is_some());
+    }
+
+    #[test]
+    fn test_get_posts_of_followed_creators_page() {
+        let mut canister_data = CanisterData::default();
+
+        assert_eq!(
+            get_posts_of_followed_creators_page(&canister_data, 0, 10).map(|posts| posts.len()),
+            Err(GetPostsOfFollowedCreatorsError::ReachedEndOfItemsList)
+        );
+
+        canister_data.followed_creators_feed_cache = Some(FollowedCreatorsFeedCache::new(
+            vec![vec![get_post(1), get_post(3)], vec![get_post(2)]],
+            SystemTime::now(),
+        ));
+
+        assert_eq!(
+            get_posts_of_followed_creators_page(&canister_data, 1, 10).map(|posts| {
+                posts
+                    .iter()
+                    .map(|post_details| post_details.id)
+                    .collect::<Vec<_>>()
+            }),
+            Ok(vec![2, 1])
+        );
+        assert_eq!(
+            get_posts_of_followed_creators_page(&canister_data, 0, 1).map(|posts| {
+                posts
+                    .iter()
+                    .map(|post_details| post_details.id)
+                    .collect::<Vec<_>>()
+            }),
+            Ok(vec![3])
+        );
+    }
+}
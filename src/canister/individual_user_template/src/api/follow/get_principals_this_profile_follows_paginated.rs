@@ -1,5 +1,3 @@
-use std::ops::Bound::Included;
-
 use shared_utils::canister_specific::individual_user_template::types::follow::{
     FollowEntryDetail, FollowEntryId,
 };
@@ -23,21 +21,11 @@ fn get_principals_this_profile_follows_paginated_impl(
     canister_data: &CanisterData,
     last_index_received: Option<u64>,
 ) -> Vec<(FollowEntryId, FollowEntryDetail)> {
-    let following = &canister_data.follow_data.following;
-    let last_key: u64 = following
-        .sorted_index
-        .last_key_value()
-        .map_or(0, |(k, _)| *k);
-
-    following
-        .sorted_index
-        .range((
-            Included(0),
-            Included(last_index_received.unwrap_or(last_key)),
-        ))
-        .rev()
+    canister_data
+        .follow_data
+        .following
+        .iter_rev_from(last_index_received.unwrap_or(u64::MAX))
         .take(MAX_FOLLOW_ENTRIES_PER_PAGE)
-        .map(|(id, entry)| (*id, entry.clone()))
         .collect::<Vec<(u64, FollowEntryDetail)>>()
 }
 
@@ -1,5 +1,15 @@
 pub mod do_i_follow_this_user;
+pub mod follow_user;
+pub mod get_follow_list_export_chunk;
+pub mod get_followers_paginated;
+pub mod get_following_feed;
+pub mod get_following_paginated;
+pub mod get_posts_of_followed_creators_paginated;
 pub mod get_principals_that_follow_this_profile_paginated;
 pub mod get_principals_this_profile_follows_paginated;
+pub mod receive_follow_list_import_chunk;
+pub mod receive_follow_status_from_follower;
+pub mod receive_post_from_followee;
+pub mod unfollow_user;
 pub mod update_profiles_i_follow_toggle_list_with_specified_profile;
 pub mod update_profiles_that_follow_me_toggle_list_with_specified_profile;
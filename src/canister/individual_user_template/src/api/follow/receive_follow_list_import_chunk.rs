@@ -0,0 +1,162 @@
+use candid::Principal;
+use shared_utils::{
+    canister_specific::individual_user_template::types::{
+        error::FollowGraphMigrationError,
+        follow::{
+            FollowEntryDetail, FollowListImportSummary, FollowListType,
+            MAX_FOLLOW_ENTRIES_IN_ONE_MIGRATION_CHUNK,
+        },
+    },
+    common::{
+        types::known_principal::KnownPrincipalType, utils::known_caller::authorize_known_caller,
+    },
+};
+
+use crate::{data_model::CanisterData, util::call_journal::record_call_in_journal, CANISTER_DATA};
+
+use super::update_profiles_i_follow_toggle_list_with_specified_profile::MAX_USERS_IN_FOLLOWER_FOLLOWING_LIST;
+
+/// Imports a chunk exported by `get_follow_list_export_chunk` on the canister this profile is
+/// migrating from. Entries already in the list, invalid principals, the profile itself and
+/// entries past the list size limit are skipped and counted in the summary.
+///
+/// Not behind the read only guard, as the target is usually in read only mode mid migration.
+///
+/// # Access Control
+/// Only the user index canister can import the follow graph
+#[ic_cdk::update]
+#[candid::candid_method(update)]
+fn receive_follow_list_import_chunk(
+    follow_list_type: FollowListType,
+    follow_entry_details: Vec<FollowEntryDetail>,
+) -> Result<FollowListImportSummary, FollowGraphMigrationError> {
+    record_call_in_journal("receive_follow_list_import_chunk");
+
+    let api_caller = ic_cdk::caller();
+
+    CANISTER_DATA.with(|canister_data_ref_cell| {
+        receive_follow_list_import_chunk_impl(
+            &mut canister_data_ref_cell.borrow_mut(),
+            &api_caller,
+            follow_list_type,
+            follow_entry_details,
+        )
+    })
+}
+
+fn receive_follow_list_import_chunk_impl(
+    canister_data: &mut CanisterData,
+    api_caller: &Principal,
+    follow_list_type: FollowListType,
+    follow_entry_details: Vec<FollowEntryDetail>,
+) -> Result<FollowListImportSummary, FollowGraphMigrationError> {
+    authorize_known_caller(
+        api_caller,
+        &canister_data.known_principal_ids,
+        &[KnownPrincipalType::CanisterIdUserIndex],
+    )
+    .map_err(|_| FollowGraphMigrationError::Unauthorized)?;
+
+    if follow_entry_details.len() > MAX_FOLLOW_ENTRIES_IN_ONE_MIGRATION_CHUNK {
+        return Err(FollowGraphMigrationError::ExceededMaxNumberOfItemsAllowedInOneRequest);
+    }
+
+    let owner_principal_id = canister_data.profile.principal_id;
+
+    Ok(canister_data
+        .follow_data
+        .get_list_mut(follow_list_type)
+        .import(
+            follow_entry_details,
+            owner_principal_id,
+            MAX_USERS_IN_FOLLOWER_FOLLOWING_LIST,
+        ))
+}
+
+#[cfg(test)]
+mod test {
+    use test_utils::setup::{
+        spoofed_callers::{assert_only_allowed_callers_are_accepted, get_mock_known_principal_map},
+        test_constants::{
+            get_mock_canister_id_user_index, get_mock_user_alice_canister_id,
+            get_mock_user_alice_principal_id,
+            v2::{canister, user},
+        },
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_receive_follow_list_import_chunk_impl() {
+        let mut canister_data = CanisterData {
+            known_principal_ids: get_mock_known_principal_map(),
+            ..Default::default()
+        };
+        canister_data.profile.principal_id = Some(get_mock_user_alice_principal_id());
+        let followee = FollowEntryDetail {
+            principal_id: user("followee"),
+            canister_id: canister("followee"),
+        };
+
+        assert_eq!(
+            receive_follow_list_import_chunk_impl(
+                &mut canister_data,
+                &get_mock_canister_id_user_index(),
+                FollowListType::Following,
+                vec![followee.clone(); MAX_FOLLOW_ENTRIES_IN_ONE_MIGRATION_CHUNK + 1],
+            ),
+            Err(FollowGraphMigrationError::ExceededMaxNumberOfItemsAllowedInOneRequest)
+        );
+        assert!(canister_data.follow_data.following.is_empty());
+
+        assert_eq!(
+            receive_follow_list_import_chunk_impl(
+                &mut canister_data,
+                &get_mock_canister_id_user_index(),
+                FollowListType::Following,
+                vec![
+                    followee.clone(),
+                    followee.clone(),
+                    FollowEntryDetail {
+                        principal_id: get_mock_user_alice_principal_id(),
+                        canister_id: get_mock_user_alice_canister_id(),
+                    },
+                ],
+            ),
+            Ok(FollowListImportSummary {
+                imported: 1,
+                skipped_duplicates: 1,
+                skipped_invalid: 1,
+                skipped_list_full: 0,
+            })
+        );
+        assert!(canister_data.follow_data.following.contains(&followee));
+        assert!(canister_data.follow_data.follower.is_empty());
+    }
+
+    #[test]
+    fn test_receive_follow_list_import_chunk_impl_with_spoofed_callers() {
+        let known_principal_ids = get_mock_known_principal_map();
+
+        assert_only_allowed_callers_are_accepted(
+            &known_principal_ids,
+            &[KnownPrincipalType::CanisterIdUserIndex],
+            |caller| {
+                let mut canister_data = CanisterData {
+                    known_principal_ids: known_principal_ids.clone(),
+                    ..Default::default()
+                };
+                receive_follow_list_import_chunk_impl(
+                    &mut canister_data,
+                    caller,
+                    FollowListType::Follower,
+                    vec![FollowEntryDetail {
+                        principal_id: user("follower"),
+                        canister_id: canister("follower"),
+                    }],
+                )
+                .is_ok()
+            },
+        );
+    }
+}
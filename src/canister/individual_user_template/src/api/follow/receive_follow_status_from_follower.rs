@@ -0,0 +1,196 @@
+use candid::Principal;
+use shared_utils::{
+    canister_specific::individual_user_template::types::{
+        achievement::AchievementEvent, error::FollowAnotherUserProfileError,
+        follow::FollowEntryDetail,
+    },
+    common::utils::system_time,
+};
+
+use crate::{
+    data_model::CanisterData,
+    util::{
+        call_journal::record_call_in_journal, canister_binding::is_expected_binding_epoch,
+        guards::reject_updates_in_read_only_mode,
+    },
+    CANISTER_DATA,
+};
+
+use super::{
+    update_profiles_i_follow_toggle_list_with_specified_profile::MAX_USERS_IN_FOLLOWER_FOLLOWING_LIST,
+    update_profiles_that_follow_me_toggle_list_with_specified_profile::FollowerArg,
+};
+
+/// Adds the calling profile to this profile's followers, or removes it, depending on
+/// `is_following`. Unlike the toggle, calling it again with the same status does nothing.
+///
+/// # Access Control
+/// Only the follower's own canister can change its follow status
+#[ic_cdk::update(guard = "reject_updates_in_read_only_mode")]
+#[candid::candid_method(update)]
+fn receive_follow_status_from_follower(
+    arg: FollowerArg,
+    is_following: bool,
+    expected_binding_epoch: Option<u64>,
+) -> Result<(), FollowAnotherUserProfileError> {
+    record_call_in_journal("receive_follow_status_from_follower");
+
+    let calling_canister_principal = ic_cdk::caller();
+
+    CANISTER_DATA.with(|canister_data_ref_cell| {
+        let mut canister_data = canister_data_ref_cell.borrow_mut();
+        if !is_expected_binding_epoch(&canister_data, expected_binding_epoch) {
+            return Err(FollowAnotherUserProfileError::StaleCanisterBinding);
+        }
+
+        let is_follower_gained = receive_follow_status_from_follower_impl(
+            &mut canister_data,
+            &calling_canister_principal,
+            &arg,
+            is_following,
+        )?;
+
+        if is_follower_gained {
+            let follower_count = canister_data.follow_data.follower.len() as u64;
+            canister_data.handle_achievement_event(
+                &AchievementEvent::FollowerGained { follower_count },
+                &system_time::get_current_system_time_from_ic(),
+            );
+        }
+
+        Ok(())
+    })
+}
+
+/// Returns true if the caller was added to the followers
+fn receive_follow_status_from_follower_impl(
+    canister_data: &mut CanisterData,
+    calling_canister_principal: &Principal,
+    arg: &FollowerArg,
+    is_following: bool,
+) -> Result<bool, FollowAnotherUserProfileError> {
+    if *calling_canister_principal != arg.follower_canister_id {
+        return Err(FollowAnotherUserProfileError::Unauthorized);
+    }
+
+    let follow_entry_detail = FollowEntryDetail {
+        principal_id: arg.follower_principal_id,
+        canister_id: arg.follower_canister_id,
+    };
+
+    let follower = &mut canister_data.follow_data.follower;
+
+    if is_following == follower.contains(&follow_entry_detail) {
+        return Ok(false);
+    }
+
+    if !is_following {
+        follower.remove(&follow_entry_detail);
+        return Ok(false);
+    }
+
+    if follower.len() as u64 >= MAX_USERS_IN_FOLLOWER_FOLLOWING_LIST {
+        return Err(FollowAnotherUserProfileError::UserITriedToFollowHasTheirFollowersListFull);
+    }
+
+    follower.add(follow_entry_detail);
+
+    Ok(true)
+}
+
+#[cfg(test)]
+mod test {
+    use test_utils::setup::test_constants::{
+        get_mock_user_alice_canister_id, get_mock_user_alice_principal_id,
+        get_mock_user_charlie_canister_id,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_receive_follow_status_from_follower_impl() {
+        let mut canister_data = CanisterData::default();
+        let arg = FollowerArg {
+            follower_principal_id: get_mock_user_alice_principal_id(),
+            follower_canister_id: get_mock_user_alice_canister_id(),
+        };
+        let follow_entry_detail = FollowEntryDetail {
+            principal_id: arg.follower_principal_id,
+            canister_id: arg.follower_canister_id,
+        };
+
+        assert_eq!(
+            receive_follow_status_from_follower_impl(
+                &mut canister_data,
+                &get_mock_user_charlie_canister_id(),
+                &arg,
+                true,
+            ),
+            Err(FollowAnotherUserProfileError::Unauthorized)
+        );
+
+        let calling_canister_principal = get_mock_user_alice_canister_id();
+
+        assert_eq!(
+            receive_follow_status_from_follower_impl(
+                &mut canister_data,
+                &calling_canister_principal,
+                &arg,
+                true,
+            ),
+            Ok(true)
+        );
+        assert_eq!(
+            receive_follow_status_from_follower_impl(
+                &mut canister_data,
+                &calling_canister_principal,
+                &arg,
+                true,
+            ),
+            Ok(false)
+        );
+        assert_eq!(canister_data.follow_data.follower.len(), 1);
+        assert!(canister_data
+            .follow_data
+            .follower
+            .contains(&follow_entry_detail));
+
+        (0..2).for_each(|_| {
+            assert_eq!(
+                receive_follow_status_from_follower_impl(
+                    &mut canister_data,
+                    &calling_canister_principal,
+                    &arg,
+                    false,
+                ),
+                Ok(false)
+            );
+            assert!(canister_data.follow_data.follower.is_empty());
+        });
+    }
+
+    #[test]
+    fn test_receive_follow_status_from_follower_impl_when_followers_list_is_full() {
+        let mut canister_data = CanisterData::default();
+        let arg = FollowerArg {
+            follower_principal_id: get_mock_user_alice_principal_id(),
+            follower_canister_id: get_mock_user_alice_canister_id(),
+        };
+        (0..MAX_USERS_IN_FOLLOWER_FOLLOWING_LIST).for_each(|id: u64| {
+            canister_data.follow_data.follower.add(FollowEntryDetail {
+                principal_id: Principal::self_authenticating(id.to_ne_bytes()),
+                canister_id: Principal::self_authenticating(id.to_ne_bytes()),
+            });
+        });
+
+        assert_eq!(
+            receive_follow_status_from_follower_impl(
+                &mut canister_data,
+                &get_mock_user_alice_canister_id(),
+                &arg,
+                true,
+            ),
+            Err(FollowAnotherUserProfileError::UserITriedToFollowHasTheirFollowersListFull)
+        );
+    }
+}
@@ -0,0 +1,96 @@
+use candid::Principal;
+use shared_utils::canister_specific::individual_user_template::types::follow::{
+    following_feed::PostFromFollowee, FollowEntryDetail,
+};
+
+use crate::{
+    data_model::CanisterData,
+    util::{call_journal::record_call_in_journal, guards::reject_updates_in_read_only_mode},
+    CANISTER_DATA,
+};
+
+/// # Access Control
+/// Only canisters of profiles this profile follows, and only for their own posts
+#[ic_cdk::update(guard = "reject_updates_in_read_only_mode")]
+#[candid::candid_method(update)]
+fn receive_post_from_followee(post_from_followee: PostFromFollowee) {
+    record_call_in_journal("receive_post_from_followee");
+
+    let publisher_canister_id = ic_cdk::caller();
+
+    CANISTER_DATA.with(|canister_data_ref_cell| {
+        receive_post_from_followee_impl(
+            &mut canister_data_ref_cell.borrow_mut(),
+            &publisher_canister_id,
+            post_from_followee,
+        );
+    });
+}
+
+fn receive_post_from_followee_impl(
+    canister_data: &mut CanisterData,
+    publisher_canister_id: &Principal,
+    post_from_followee: PostFromFollowee,
+) {
+    let followee_entry_detail = FollowEntryDetail {
+        principal_id: post_from_followee.publisher_principal_id,
+        canister_id: *publisher_canister_id,
+    };
+    if !canister_data
+        .follow_data
+        .following
+        .contains(&followee_entry_detail)
+    {
+        return;
+    }
+
+    canister_data
+        .following_feed_cache
+        .add_post(*publisher_canister_id, post_from_followee);
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::SystemTime;
+
+    use test_utils::setup::test_constants::{
+        get_mock_user_alice_canister_id, get_mock_user_alice_principal_id,
+        get_mock_user_bob_canister_id, get_mock_user_bob_principal_id,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_receive_post_from_followee_impl() {
+        let mut canister_data = CanisterData::default();
+        canister_data.follow_data.following.add(FollowEntryDetail {
+            principal_id: get_mock_user_alice_principal_id(),
+            canister_id: get_mock_user_alice_canister_id(),
+        });
+        let get_post = |publisher_principal_id: Principal| PostFromFollowee {
+            publisher_principal_id,
+            post_id: 0,
+            created_at: SystemTime::now(),
+        };
+
+        receive_post_from_followee_impl(
+            &mut canister_data,
+            &get_mock_user_bob_canister_id(),
+            get_post(get_mock_user_bob_principal_id()),
+        );
+        // * a followee's canister can't pass off posts as someone else's
+        receive_post_from_followee_impl(
+            &mut canister_data,
+            &get_mock_user_bob_canister_id(),
+            get_post(get_mock_user_alice_principal_id()),
+        );
+        assert!(canister_data.following_feed_cache.is_empty());
+
+        receive_post_from_followee_impl(
+            &mut canister_data,
+            &get_mock_user_alice_canister_id(),
+            get_post(get_mock_user_alice_principal_id()),
+        );
+        assert_eq!(canister_data.following_feed_cache.len(), 1);
+    }
+}
@@ -0,0 +1,112 @@
+use candid::Principal;
+use shared_utils::canister_specific::individual_user_template::types::{
+    error::FollowAnotherUserProfileError, follow::FollowEntryDetail,
+};
+
+use crate::{
+    data_model::CanisterData,
+    util::{call_journal::record_call_in_journal, guards::reject_updates_in_read_only_mode},
+    CANISTER_DATA,
+};
+
+use super::follow_user::{update_follow_status_in_followees_canister, validate_follow_request};
+
+/// Stops following the profile whose canister is `followee_canister_id`, removing this
+/// profile from its followers before removing it from the profiles this one follows.
+/// Unfollowing a profile that isn't followed does nothing.
+///
+/// # Access Control
+/// Only the user whose profile details are stored in this canister can unfollow another user's profile.
+#[ic_cdk::update(guard = "reject_updates_in_read_only_mode")]
+#[candid::candid_method(update)]
+async fn unfollow_user(
+    followee_canister_id: Principal,
+) -> Result<(), FollowAnotherUserProfileError> {
+    record_call_in_journal("unfollow_user");
+
+    let current_caller = ic_cdk::caller();
+
+    let my_principal_id = CANISTER_DATA.with(|canister_data_ref_cell| {
+        validate_follow_request(
+            &canister_data_ref_cell.borrow(),
+            &current_caller,
+            &ic_cdk::id(),
+            &followee_canister_id,
+        )
+    })?;
+
+    let followee_entry_detail = CANISTER_DATA.with(|canister_data_ref_cell| {
+        canister_data_ref_cell
+            .borrow()
+            .follow_data
+            .following
+            .find_by_canister_id(&followee_canister_id)
+    });
+    let Some(followee_entry_detail) = followee_entry_detail else {
+        return Ok(());
+    };
+
+    update_follow_status_in_followees_canister(followee_canister_id, my_principal_id, false)
+        .await?;
+
+    CANISTER_DATA.with(|canister_data_ref_cell| {
+        remove_followee(
+            &mut canister_data_ref_cell.borrow_mut(),
+            &followee_entry_detail,
+        );
+    });
+
+    Ok(())
+}
+
+fn remove_followee(canister_data: &mut CanisterData, followee_entry_detail: &FollowEntryDetail) {
+    canister_data
+        .follow_data
+        .following
+        .remove(followee_entry_detail);
+    canister_data
+        .following_feed_cache
+        .remove_followee(&followee_entry_detail.canister_id);
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::SystemTime;
+
+    use shared_utils::canister_specific::individual_user_template::types::follow::following_feed::PostFromFollowee;
+    use test_utils::setup::test_constants::{
+        get_mock_user_alice_canister_id, get_mock_user_alice_principal_id,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_remove_followee() {
+        let mut canister_data = CanisterData::default();
+        let followee_entry_detail = FollowEntryDetail {
+            principal_id: get_mock_user_alice_principal_id(),
+            canister_id: get_mock_user_alice_canister_id(),
+        };
+        canister_data
+            .follow_data
+            .following
+            .add(followee_entry_detail.clone());
+        canister_data.following_feed_cache.add_post(
+            get_mock_user_alice_canister_id(),
+            PostFromFollowee {
+                publisher_principal_id: get_mock_user_alice_principal_id(),
+                post_id: 0,
+                created_at: SystemTime::now(),
+            },
+        );
+
+        remove_followee(&mut canister_data, &followee_entry_detail);
+
+        assert!(canister_data.follow_data.following.is_empty());
+        assert!(canister_data.following_feed_cache.is_empty());
+
+        remove_followee(&mut canister_data, &followee_entry_detail);
+
+        assert!(canister_data.follow_data.following.is_empty());
+    }
+}
@@ -1,8 +1,22 @@
-use crate::{data_model::CanisterData, CANISTER_DATA};
+use crate::{
+    data_model::CanisterData,
+    util::{
+        call_journal::record_call_in_journal,
+        canister_binding::{
+            get_binding_epoch_of_user_canister, re_resolve_binding_epoch_of_user_canister,
+        },
+        guards::reject_updates_in_read_only_mode,
+    },
+    CANISTER_DATA,
+};
 
 use candid::Principal;
-use shared_utils::canister_specific::individual_user_template::types::{
-    arg::FolloweeArg, error::FollowAnotherUserProfileError, follow::FollowEntryDetail,
+use shared_utils::{
+    canister_specific::individual_user_template::types::{
+        arg::FolloweeArg, error::FollowAnotherUserProfileError, feature_usage::Feature,
+        follow::FollowEntryDetail,
+    },
+    common::utils::system_time,
 };
 
 use super::update_profiles_that_follow_me_toggle_list_with_specified_profile::FollowerArg;
@@ -11,11 +25,13 @@ pub const MAX_USERS_IN_FOLLOWER_FOLLOWING_LIST: u64 = 10_000;
 
 /// # Access Control
 /// Only the user whose profile details are stored in this canister can follow another user's profile.
-#[ic_cdk::update]
+#[ic_cdk::update(guard = "reject_updates_in_read_only_mode")]
 #[candid::candid_method(update)]
 async fn update_profiles_i_follow_toggle_list_with_specified_profile(
     arg: FolloweeArg,
 ) -> Result<bool, FollowAnotherUserProfileError> {
+    record_call_in_journal("update_profiles_i_follow_toggle_list_with_specified_profile");
+
     let current_caller = ic_cdk::caller();
 
     let my_principal_id = CANISTER_DATA
@@ -28,20 +44,19 @@ async fn update_profiles_i_follow_toggle_list_with_specified_profile(
         validate_incoming_request(&canister_data, &current_caller, &my_principal_id)
     })?;
 
-    let my_canister_id = ic_cdk::id();
-
-    // * inter canister call to update the followee's list of followers
-    let follow_status = ic_cdk::call::<_, (Result<bool, FollowAnotherUserProfileError>,)>(
-        arg.followee_canister_id,
-        "update_profiles_that_follow_me_toggle_list_with_specified_profile",
-        (FollowerArg {
-            follower_principal_id: my_principal_id,
-            follower_canister_id: my_canister_id,
-        },),
-    )
-    .await
-    .map_err(|_| FollowAnotherUserProfileError::UserITriedToFollowCrossCanisterCallFailed)?
-    .0?;
+    let expected_binding_epoch = get_binding_epoch_of_user_canister(arg.followee_canister_id).await;
+    let follow_status =
+        match toggle_follower_in_followees_canister(&arg, my_principal_id, expected_binding_epoch)
+            .await
+        {
+            // * the followee's canister was reinstalled since its epoch was last resolved
+            Err(FollowAnotherUserProfileError::StaleCanisterBinding) => {
+                let binding_epoch =
+                    re_resolve_binding_epoch_of_user_canister(arg.followee_canister_id).await;
+                toggle_follower_in_followees_canister(&arg, my_principal_id, binding_epoch).await
+            }
+            follow_status => follow_status,
+        }?;
 
     let followee_entry_detail = FollowEntryDetail {
         principal_id: arg.followee_principal_id,
@@ -56,9 +71,40 @@ async fn update_profiles_i_follow_toggle_list_with_specified_profile(
         )
     })?;
 
+    if follow_status {
+        let current_time = system_time::get_current_system_time_from_ic();
+        CANISTER_DATA.with(|canister_data_ref_cell| {
+            canister_data_ref_cell
+                .borrow_mut()
+                .record_feature_usage(Feature::ProfileFollowed, &current_time);
+        });
+    }
+
     Ok(follow_status)
 }
 
+/// Inter canister call to update the followee's list of followers
+async fn toggle_follower_in_followees_canister(
+    arg: &FolloweeArg,
+    my_principal_id: Principal,
+    expected_binding_epoch: Option<u64>,
+) -> Result<bool, FollowAnotherUserProfileError> {
+    ic_cdk::call::<_, (Result<bool, FollowAnotherUserProfileError>,)>(
+        arg.followee_canister_id,
+        "update_profiles_that_follow_me_toggle_list_with_specified_profile",
+        (
+            FollowerArg {
+                follower_principal_id: my_principal_id,
+                follower_canister_id: ic_cdk::id(),
+            },
+            expected_binding_epoch,
+        ),
+    )
+    .await
+    .map_err(|_| FollowAnotherUserProfileError::UserITriedToFollowCrossCanisterCallFailed)?
+    .0
+}
+
 fn validate_incoming_request(
     canister_data: &CanisterData,
     current_caller: &Principal,
@@ -90,6 +136,9 @@ fn add_or_remove_followee_depending_on_follow_status(
         following.add(followee_entry_detail.clone());
     } else {
         following.remove(followee_entry_detail);
+        canister_data
+            .following_feed_cache
+            .remove_followee(&followee_entry_detail.canister_id);
     }
 
     Ok(())
@@ -97,6 +146,9 @@ fn add_or_remove_followee_depending_on_follow_status(
 
 #[cfg(test)]
 mod test {
+    use std::time::SystemTime;
+
+    use shared_utils::canister_specific::individual_user_template::types::follow::following_feed::PostFromFollowee;
     use test_utils::setup::test_constants::{
         get_mock_user_alice_canister_id, get_mock_user_alice_principal_id,
         get_mock_user_bob_principal_id,
@@ -170,6 +222,14 @@ mod test {
         assert!(result.is_ok());
         assert_eq!(canister_data.follow_data.following.len(), 1);
 
+        canister_data.following_feed_cache.add_post(
+            get_mock_user_alice_canister_id(),
+            PostFromFollowee {
+                publisher_principal_id: get_mock_user_alice_principal_id(),
+                post_id: 0,
+                created_at: SystemTime::now(),
+            },
+        );
         let follow_status = false;
 
         let result = add_or_remove_followee_depending_on_follow_status(
@@ -180,5 +240,6 @@ mod test {
 
         assert!(result.is_ok());
         assert_eq!(canister_data.follow_data.following.len(), 0);
+        assert!(canister_data.following_feed_cache.is_empty());
     }
 }
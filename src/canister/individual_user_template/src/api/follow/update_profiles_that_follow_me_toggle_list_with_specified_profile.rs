@@ -1,9 +1,20 @@
 use candid::{CandidType, Deserialize, Principal};
-use shared_utils::canister_specific::individual_user_template::types::{
-    error::FollowAnotherUserProfileError, follow::FollowEntryDetail,
+use shared_utils::{
+    canister_specific::individual_user_template::types::{
+        achievement::AchievementEvent, error::FollowAnotherUserProfileError,
+        follow::FollowEntryDetail,
+    },
+    common::utils::system_time,
 };
 
-use crate::{data_model::CanisterData, CANISTER_DATA};
+use crate::{
+    data_model::CanisterData,
+    util::{
+        call_journal::record_call_in_journal, canister_binding::is_expected_binding_epoch,
+        guards::reject_updates_in_read_only_mode,
+    },
+    CANISTER_DATA,
+};
 
 use super::update_profiles_i_follow_toggle_list_with_specified_profile::MAX_USERS_IN_FOLLOWER_FOLLOWING_LIST;
 
@@ -15,21 +26,37 @@ pub struct FollowerArg {
 
 /// # Access Control
 /// Only allow calls from canisters of this project
-#[ic_cdk::update]
+#[ic_cdk::update(guard = "reject_updates_in_read_only_mode")]
 #[candid::candid_method(update)]
 async fn update_profiles_that_follow_me_toggle_list_with_specified_profile(
     arg: FollowerArg,
+    expected_binding_epoch: Option<u64>,
 ) -> Result<bool, FollowAnotherUserProfileError> {
+    record_call_in_journal("update_profiles_that_follow_me_toggle_list_with_specified_profile");
+
     let calling_canister_principal = ic_cdk::caller();
 
     CANISTER_DATA.with(|canister_data_ref_cell| {
         let mut canister_data = canister_data_ref_cell.borrow_mut();
+        if !is_expected_binding_epoch(&canister_data, expected_binding_epoch) {
+            return Err(FollowAnotherUserProfileError::StaleCanisterBinding);
+        }
 
-        update_profiles_that_follow_me_toggle_list_with_specified_profile_impl(
+        let is_following = update_profiles_that_follow_me_toggle_list_with_specified_profile_impl(
             &mut canister_data,
             &calling_canister_principal,
             &arg,
-        )
+        )?;
+
+        if is_following {
+            let follower_count = canister_data.follow_data.follower.len() as u64;
+            canister_data.handle_achievement_event(
+                &AchievementEvent::FollowerGained { follower_count },
+                &system_time::get_current_system_time_from_ic(),
+            );
+        }
+
+        Ok(is_following)
     })
 }
 
@@ -64,7 +91,6 @@ fn update_profiles_that_follow_me_toggle_list_with_specified_profile_impl(
 
 #[cfg(test)]
 mod test {
-    use shared_utils::canister_specific::individual_user_template::types::follow::FollowList;
     use test_utils::setup::test_constants::{
         get_mock_user_alice_canister_id, get_mock_user_alice_principal_id,
         get_mock_user_charlie_canister_id,
@@ -127,7 +153,17 @@ mod test {
             Err(FollowAnotherUserProfileError::UserITriedToFollowHasTheirFollowersListFull)
         );
 
-        canister_data.follow_data.follower = FollowList::default();
+        let followers_added = canister_data
+            .follow_data
+            .follower
+            .get_export_chunk(0, usize::MAX)
+            .entries;
+        followers_added.iter().for_each(|(_, follow_entry_detail)| {
+            canister_data
+                .follow_data
+                .follower
+                .remove(follow_entry_detail);
+        });
         let follow_entry_detail = FollowEntryDetail {
             principal_id: arg.follower_principal_id,
             canister_id: arg.follower_canister_id,
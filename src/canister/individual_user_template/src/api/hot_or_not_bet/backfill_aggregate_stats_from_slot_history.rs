@@ -0,0 +1,321 @@
+use std::time::{Duration, SystemTime};
+
+use candid::Principal;
+use shared_utils::{
+    canister_specific::individual_user_template::types::{
+        error::BackfillAggregateStatsError,
+        hot_or_not::{AggregateStatsBackfillReport, AggregateStatsDiscrepancy},
+    },
+    common::{
+        types::known_principal::KnownPrincipalType,
+        utils::system_time::{IcTimeProvider, TimeProvider},
+    },
+};
+
+use crate::{
+    data_model::CanisterData,
+    util::{call_journal::record_call_in_journal, guards::reject_updates_in_read_only_mode},
+    CANISTER_DATA,
+};
+
+// * Number of posts processed per message so a single message stays well within the instruction limit
+const NUMBER_OF_POSTS_TO_SCAN_PER_MESSAGE: usize = 100;
+
+#[ic_cdk::update(guard = "reject_updates_in_read_only_mode")]
+#[candid::candid_method(update)]
+fn backfill_aggregate_stats_from_slot_history() -> Result<(), BackfillAggregateStatsError> {
+    record_call_in_journal("backfill_aggregate_stats_from_slot_history");
+
+    let api_caller = ic_cdk::caller();
+    let current_time = IcTimeProvider.get_current_time();
+
+    CANISTER_DATA.with(|canister_data_ref_cell| {
+        start_backfill_impl(
+            &mut canister_data_ref_cell.borrow_mut(),
+            &api_caller,
+            &current_time,
+        )
+    })?;
+
+    enqueue_next_chunk();
+
+    Ok(())
+}
+
+#[ic_cdk::query]
+#[candid::candid_method(query)]
+fn get_aggregate_stats_backfill_report() -> Option<AggregateStatsBackfillReport> {
+    CANISTER_DATA.with(|canister_data_ref_cell| {
+        canister_data_ref_cell
+            .borrow()
+            .aggregate_stats_backfill_report
+            .clone()
+    })
+}
+
+fn start_backfill_impl(
+    canister_data: &mut CanisterData,
+    api_caller: &Principal,
+    current_time: &SystemTime,
+) -> Result<(), BackfillAggregateStatsError> {
+    let global_super_admin_principal_id = canister_data
+        .known_principal_ids
+        .get(&KnownPrincipalType::UserIdGlobalSuperAdmin)
+        .cloned()
+        .unwrap_or(Principal::anonymous());
+
+    if *api_caller == Principal::anonymous() || *api_caller != global_super_admin_principal_id {
+        return Err(BackfillAggregateStatsError::Unauthorized);
+    }
+
+    if let Some(report) = &canister_data.aggregate_stats_backfill_report {
+        if report.next_post_id_to_scan.is_some() {
+            return Err(BackfillAggregateStatsError::BackfillAlreadyInProgress);
+        }
+    }
+
+    canister_data.aggregate_stats_backfill_report = Some(AggregateStatsBackfillReport {
+        started_at: *current_time,
+        number_of_posts_scanned: 0,
+        next_post_id_to_scan: Some(0),
+        discrepancies_found: vec![],
+    });
+
+    Ok(())
+}
+
+fn enqueue_next_chunk() {
+    ic_cdk_timers::set_timer(Duration::ZERO, || {
+        let is_backfill_complete = CANISTER_DATA.with(|canister_data_ref_cell| {
+            backfill_next_chunk_impl(
+                &mut canister_data_ref_cell.borrow_mut(),
+                NUMBER_OF_POSTS_TO_SCAN_PER_MESSAGE,
+            )
+        });
+
+        if !is_backfill_complete {
+            enqueue_next_chunk();
+        }
+    });
+}
+
+/// Returns true once every post has been scanned
+fn backfill_next_chunk_impl(canister_data: &mut CanisterData, chunk_size: usize) -> bool {
+    let Some(mut report) = canister_data.aggregate_stats_backfill_report.take() else {
+        return true;
+    };
+
+    let Some(start_from_post_id) = report.next_post_id_to_scan else {
+        canister_data.aggregate_stats_backfill_report = Some(report);
+        return true;
+    };
+
+    let mut last_post_id_scanned = None;
+
+    let post_ids_to_scan = canister_data
+        .all_created_posts
+        .keys_from(start_from_post_id)
+        .take(chunk_size)
+        .collect::<Vec<_>>();
+
+    for post_id in post_ids_to_scan {
+        last_post_id_scanned = Some(post_id);
+        report.number_of_posts_scanned += 1;
+
+        // * Compacted posts are recounted with their archived bets, and stay uncompacted if they
+        // * have to be written back, until the next compaction pass
+        let Some(mut post) = canister_data
+            .all_created_posts
+            .get_with_archived_bets(&post_id)
+        else {
+            continue;
+        };
+        let Some(hot_or_not_details) = post.hot_or_not_details.as_mut() else {
+            continue;
+        };
+
+        let recomputed = hot_or_not_details.recompute_aggregate_stats_from_slot_history();
+        let recomputed_histogram =
+            hot_or_not_details.recompute_bet_amount_histogram_from_slot_history();
+        let is_histogram_stale = recomputed_histogram != hot_or_not_details.bet_amount_histogram;
+
+        if recomputed != hot_or_not_details.aggregate_stats {
+            report.discrepancies_found.push(AggregateStatsDiscrepancy {
+                post_id,
+                stored: hot_or_not_details.aggregate_stats.clone(),
+                recomputed: recomputed.clone(),
+            });
+            hot_or_not_details.aggregate_stats = recomputed;
+        } else if !is_histogram_stale {
+            continue;
+        }
+
+        // * Posts from before the histogram was kept get theirs filled in here too
+        hot_or_not_details.bet_amount_histogram = recomputed_histogram;
+        canister_data.all_created_posts.insert(post_id, post);
+    }
+
+    report.next_post_id_to_scan = match last_post_id_scanned {
+        Some(last_post_id_scanned)
+            if canister_data
+                .all_created_posts
+                .keys_from(last_post_id_scanned + 1)
+                .next()
+                .is_some() =>
+        {
+            Some(last_post_id_scanned + 1)
+        }
+        _ => None,
+    };
+
+    let is_backfill_complete = report.next_post_id_to_scan.is_none();
+    canister_data.aggregate_stats_backfill_report = Some(report);
+
+    is_backfill_complete
+}
+
+#[cfg(test)]
+mod test {
+    use shared_utils::canister_specific::individual_user_template::types::{
+        hot_or_not::{AggregateStats, BetAmountHistogram, BetDirection},
+        post::{Post, PostDetailsFromFrontend},
+    };
+    use test_utils::setup::test_constants::{
+        get_global_super_admin_principal_id, get_mock_user_alice_principal_id,
+    };
+
+    use super::*;
+
+    fn get_canister_data_with_posts(number_of_posts: u64) -> CanisterData {
+        let mut canister_data = CanisterData::default();
+        canister_data.known_principal_ids.insert(
+            KnownPrincipalType::UserIdGlobalSuperAdmin,
+            get_global_super_admin_principal_id(),
+        );
+
+        (0..number_of_posts).for_each(|post_id| {
+            let mut post = Post::new(
+                post_id,
+                &PostDetailsFromFrontend {
+                    description: "Doggos and puppers".into(),
+                    hashtags: vec!["doggo".into(), "pupper".into()],
+                    video_uid: "abcd#1234".into(),
+                    creator_consent_for_inclusion_in_hot_or_not: true,
+                    collaborator_shares: None,
+                    title: None,
+                    category: None,
+                },
+                &SystemTime::now(),
+            );
+            post.place_hot_or_not_bet(
+                &Principal::from_slice(&[1]),
+                &Principal::from_slice(&[1]),
+                100,
+                &BetDirection::Hot,
+                &SystemTime::now(),
+            )
+            .unwrap();
+            canister_data.all_created_posts.insert(post_id, post);
+        });
+
+        canister_data
+    }
+
+    #[test]
+    fn test_start_backfill_impl() {
+        let mut canister_data = get_canister_data_with_posts(1);
+
+        assert_eq!(
+            start_backfill_impl(
+                &mut canister_data,
+                &get_mock_user_alice_principal_id(),
+                &SystemTime::now()
+            ),
+            Err(BackfillAggregateStatsError::Unauthorized)
+        );
+
+        assert_eq!(
+            start_backfill_impl(
+                &mut canister_data,
+                &get_global_super_admin_principal_id(),
+                &SystemTime::now()
+            ),
+            Ok(())
+        );
+
+        assert_eq!(
+            start_backfill_impl(
+                &mut canister_data,
+                &get_global_super_admin_principal_id(),
+                &SystemTime::now()
+            ),
+            Err(BackfillAggregateStatsError::BackfillAlreadyInProgress)
+        );
+    }
+
+    #[test]
+    fn test_backfill_next_chunk_impl() {
+        let mut canister_data = get_canister_data_with_posts(5);
+
+        // * introduce drift on two of the posts
+        canister_data.all_created_posts.update(&1, |post| {
+            post.hot_or_not_details
+                .as_mut()
+                .unwrap()
+                .aggregate_stats
+                .total_amount_bet = 0;
+        });
+        canister_data.all_created_posts.update(&4, |post| {
+            post.hot_or_not_details
+                .as_mut()
+                .unwrap()
+                .aggregate_stats
+                .total_number_of_not_bets = 3;
+        });
+        // * and one post from before the bet amount histogram was kept
+        canister_data.all_created_posts.update(&2, |post| {
+            post.hot_or_not_details
+                .as_mut()
+                .unwrap()
+                .bet_amount_histogram = BetAmountHistogram::default();
+        });
+
+        start_backfill_impl(
+            &mut canister_data,
+            &get_global_super_admin_principal_id(),
+            &SystemTime::now(),
+        )
+        .unwrap();
+
+        assert!(!backfill_next_chunk_impl(&mut canister_data, 2));
+        assert!(!backfill_next_chunk_impl(&mut canister_data, 2));
+        assert!(backfill_next_chunk_impl(&mut canister_data, 2));
+
+        let report = canister_data
+            .aggregate_stats_backfill_report
+            .clone()
+            .unwrap();
+        let expected_aggregate_stats = AggregateStats {
+            total_number_of_hot_bets: 1,
+            total_number_of_not_bets: 0,
+            total_amount_bet: 100,
+        };
+
+        assert_eq!(report.number_of_posts_scanned, 5);
+        assert_eq!(report.next_post_id_to_scan, None);
+        assert_eq!(report.discrepancies_found.len(), 2);
+        assert_eq!(report.discrepancies_found[0].post_id, 1);
+        assert_eq!(report.discrepancies_found[0].stored.total_amount_bet, 0);
+        assert_eq!(
+            report.discrepancies_found[0].recomputed,
+            expected_aggregate_stats
+        );
+        assert_eq!(report.discrepancies_found[1].post_id, 4);
+
+        canister_data.all_created_posts.values().for_each(|post| {
+            let hot_or_not_details = post.hot_or_not_details.as_ref().unwrap();
+            assert_eq!(hot_or_not_details.aggregate_stats, expected_aggregate_stats);
+            assert_eq!(hot_or_not_details.bet_amount_histogram.hot_bet_counts[2], 1);
+        });
+    }
+}
@@ -1,56 +1,138 @@
+use std::time::SystemTime;
+
 use candid::Principal;
 use shared_utils::{
     canister_specific::individual_user_template::types::{
+        achievement::AchievementEvent,
         arg::PlaceBetArg,
         error::BetOnCurrentlyViewingPostError,
+        feature_usage::Feature,
         hot_or_not::{BetOutcomeForBetMaker, BettingStatus, PlacedBetDetail},
     },
     common::{
-        types::utility_token::token_event::{StakeEvent, TokenEvent},
-        utils::system_time,
+        types::{
+            event_counter::EventCounterName,
+            response_envelope::ResponseEnvelope,
+            utility_token::token_event::{StakeEvent, TokenEvent},
+        },
+        utils::system_time::{IcTimeProvider, TimeProvider},
     },
 };
 
-use crate::{data_model::CanisterData, CANISTER_DATA};
+use crate::{
+    data_model::CanisterData,
+    util::{
+        call_journal::record_call_in_journal,
+        canister_binding::{
+            get_binding_epoch_of_user_canister, re_resolve_binding_epoch_of_user_canister,
+        },
+        guards::reject_bets_and_posts_in_read_only_or_low_cycles_mode,
+    },
+    CANISTER_DATA,
+};
 
-#[ic_cdk::update]
+#[ic_cdk::update(guard = "reject_bets_and_posts_in_read_only_or_low_cycles_mode")]
 #[candid::candid_method(update)]
 async fn bet_on_currently_viewing_post(
     place_bet_arg: PlaceBetArg,
+) -> Result<BettingStatus, BetOnCurrentlyViewingPostError> {
+    record_call_in_journal("bet_on_currently_viewing_post");
+
+    place_bet_and_count_rejection(place_bet_arg).await
+}
+
+async fn place_bet_and_count_rejection(
+    place_bet_arg: PlaceBetArg,
+) -> Result<BettingStatus, BetOnCurrentlyViewingPostError> {
+    let response = place_bet_on_currently_viewing_post(place_bet_arg).await;
+
+    if let Err(error) = &response {
+        CANISTER_DATA.with(|canister_data_ref_cell| {
+            record_bet_rejection(&mut canister_data_ref_cell.borrow_mut(), error);
+        });
+    }
+
+    response
+}
+
+pub fn record_bet_rejection(
+    canister_data: &mut CanisterData,
+    error: &BetOnCurrentlyViewingPostError,
+) {
+    canister_data.event_counters.increment(
+        EventCounterName::BetRejectionsTotal,
+        error.as_reason_label(),
+    );
+}
+
+async fn place_bet_on_currently_viewing_post(
+    place_bet_arg: PlaceBetArg,
 ) -> Result<BettingStatus, BetOnCurrentlyViewingPostError> {
     let bet_maker_principal_id = ic_cdk::caller();
-    let current_time = system_time::get_current_system_time_from_ic();
+    let current_time = IcTimeProvider.get_current_time();
 
     CANISTER_DATA.with(|canister_data_ref_cell| {
+        let canister_data = &mut canister_data_ref_cell.borrow_mut();
+
         validate_incoming_bet(
-            &canister_data_ref_cell.borrow(),
+            canister_data,
             &bet_maker_principal_id,
             &place_bet_arg,
-        )
+            &current_time,
+        )?;
+        reserve_session_delegate_bet_amount(
+            canister_data,
+            &bet_maker_principal_id,
+            place_bet_arg.bet_amount,
+        );
+
+        Ok(())
     })?;
 
-    let response = ic_cdk::call::<_, (Result<BettingStatus, BetOnCurrentlyViewingPostError>,)>(
-        place_bet_arg.post_canister_id,
-        "receive_bet_from_bet_makers_canister",
-        (
-            place_bet_arg.clone(),
-            CANISTER_DATA.with(|canister_data_ref_cell| {
-                canister_data_ref_cell
-                    .borrow()
-                    .profile
-                    .principal_id
-                    .unwrap()
-            }),
-        ),
-    )
-    .await
-    .map_err(|_| BetOnCurrentlyViewingPostError::PostCreatorCanisterCallFailed)?
-    .0?;
+    let bet_amount = place_bet_arg.bet_amount;
+    let response = place_validated_bet(place_bet_arg, bet_maker_principal_id, current_time).await;
+
+    CANISTER_DATA.with(|canister_data_ref_cell| {
+        release_session_delegate_bet_amount(
+            &mut canister_data_ref_cell.borrow_mut(),
+            &bet_maker_principal_id,
+            bet_amount,
+        );
+    });
+
+    response
+}
+
+/// Sends a bet that already passed validation on to the post creator's canister, and takes the
+/// stake from the balance once the post creator's canister has accepted it
+pub async fn place_validated_bet(
+    place_bet_arg: PlaceBetArg,
+    bet_maker_principal_id: Principal,
+    current_time: SystemTime,
+) -> Result<BettingStatus, BetOnCurrentlyViewingPostError> {
+    let expected_binding_epoch =
+        get_binding_epoch_of_user_canister(place_bet_arg.post_canister_id).await;
+    let response =
+        match send_bet_to_post_creators_canister(&place_bet_arg, expected_binding_epoch).await {
+            // * the post creator's canister was reinstalled since its epoch was last resolved
+            Err(BetOnCurrentlyViewingPostError::StaleCanisterBinding) => {
+                let binding_epoch =
+                    re_resolve_binding_epoch_of_user_canister(place_bet_arg.post_canister_id).await;
+                send_bet_to_post_creators_canister(&place_bet_arg, binding_epoch).await
+            }
+            response => response,
+        }?;
 
     match response {
-        BettingStatus::BettingClosed => {
+        BettingStatus::BettingClosed | BettingStatus::BettingClosedByCreator => {
             return Err(BetOnCurrentlyViewingPostError::BettingClosed);
         }
+        BettingStatus::BettingPausedForReview => {
+            return Err(BetOnCurrentlyViewingPostError::BettingPausedForReview);
+        }
+        BettingStatus::BettingNotStarted { .. } => {
+            return Err(BetOnCurrentlyViewingPostError::BettingNotStarted);
+        }
         BettingStatus::BettingOpen {
             ongoing_slot,
             ongoing_room,
@@ -59,8 +141,7 @@ async fn bet_on_currently_viewing_post(
             CANISTER_DATA.with(|canister_data_ref_cell| {
                 let canister_data = &mut canister_data_ref_cell.borrow_mut();
 
-                let my_token_balance = &mut canister_data.my_token_balance;
-                my_token_balance.handle_token_event(TokenEvent::Stake {
+                canister_data.handle_token_event(TokenEvent::Stake {
                     amount: place_bet_arg.bet_amount,
                     details: StakeEvent::BetOnHotOrNotPost {
                         post_canister_id: place_bet_arg.post_canister_id,
@@ -73,7 +154,7 @@ async fn bet_on_currently_viewing_post(
 
                 let all_hot_or_not_bets_placed = &mut canister_data.all_hot_or_not_bets_placed;
                 all_hot_or_not_bets_placed.insert(
-                    (place_bet_arg.post_canister_id, place_bet_arg.post_id),
+                    place_bet_arg.global_post_ref().into(),
                     PlacedBetDetail {
                         canister_id: place_bet_arg.post_canister_id,
                         post_id: place_bet_arg.post_id,
@@ -85,6 +166,24 @@ async fn bet_on_currently_viewing_post(
                         outcome_received: BetOutcomeForBetMaker::default(),
                     },
                 );
+                let bets_placed = all_hot_or_not_bets_placed.len() as u64;
+                canister_data
+                    .betting_stats
+                    .record_bet_placed(place_bet_arg.bet_amount);
+                canister_data.handle_achievement_event(
+                    &AchievementEvent::BetPlaced { bets_placed },
+                    &current_time,
+                );
+
+                // * bets placed through a session key count towards its daily limit
+                if let Some(session_delegate) = canister_data
+                    .session_delegates
+                    .get_mut(&bet_maker_principal_id)
+                {
+                    session_delegate.record_bet_placed(place_bet_arg.bet_amount, &current_time);
+                }
+
+                canister_data.record_feature_usage(Feature::BetPlaced, &current_time);
             });
         }
     }
@@ -92,10 +191,91 @@ async fn bet_on_currently_viewing_post(
     Ok(response)
 }
 
-fn validate_incoming_bet(
+/// Same as `bet_on_currently_viewing_post`, but clients that predate a betting status or an
+/// error get `null` in place of it rather than a decoding error
+#[ic_cdk::update(guard = "reject_bets_and_posts_in_read_only_or_low_cycles_mode")]
+#[candid::candid_method(update)]
+async fn bet_on_currently_viewing_post_v2(
+    place_bet_arg: PlaceBetArg,
+) -> ResponseEnvelope<Result<BettingStatus, BetOnCurrentlyViewingPostError>> {
+    record_call_in_journal("bet_on_currently_viewing_post_v2");
+
+    ResponseEnvelope::from_result(place_bet_and_count_rejection(place_bet_arg).await)
+}
+
+async fn send_bet_to_post_creators_canister(
+    place_bet_arg: &PlaceBetArg,
+    expected_binding_epoch: Option<u64>,
+) -> Result<BettingStatus, BetOnCurrentlyViewingPostError> {
+    // * Shadow banned profiles go through the exact same flow, only their bets are never
+    // * paid out by the post creator's canister
+    let is_shadow_banned = CANISTER_DATA
+        .with(|canister_data_ref_cell| canister_data_ref_cell.borrow().is_shadow_banned);
+
+    ic_cdk::call::<_, (Result<BettingStatus, BetOnCurrentlyViewingPostError>,)>(
+        place_bet_arg.post_canister_id,
+        if is_shadow_banned {
+            "receive_shadow_banned_bet_from_bet_makers_canister"
+        } else {
+            "receive_bet_from_bet_makers_canister"
+        },
+        (
+            place_bet_arg.clone(),
+            CANISTER_DATA.with(|canister_data_ref_cell| {
+                canister_data_ref_cell
+                    .borrow()
+                    .profile
+                    .principal_id
+                    .unwrap()
+            }),
+            Some(CANISTER_DATA.with(|canister_data_ref_cell| {
+                canister_data_ref_cell.borrow().is_betting_activity_hidden
+            })),
+            expected_binding_epoch,
+            CANISTER_DATA.with(|canister_data_ref_cell| {
+                canister_data_ref_cell
+                    .borrow()
+                    .my_referrer_details
+                    .as_ref()
+                    .map(|my_referrer_details| my_referrer_details.referrer_canister_id)
+            }),
+        ),
+    )
+    .await
+    .map_err(|_| BetOnCurrentlyViewingPostError::PostCreatorCanisterCallFailed)?
+    .0
+}
+
+pub fn validate_incoming_bet(
     canister_data: &CanisterData,
     bet_maker_principal_id: &Principal,
     place_bet_arg: &PlaceBetArg,
+    current_time: &SystemTime,
+) -> Result<(), BetOnCurrentlyViewingPostError> {
+    validate_bet_maker(
+        canister_data,
+        bet_maker_principal_id,
+        place_bet_arg.bet_amount,
+        current_time,
+    )?;
+
+    if canister_data
+        .all_hot_or_not_bets_placed
+        .contains_key(&(place_bet_arg.post_canister_id, place_bet_arg.post_id))
+    {
+        return Err(BetOnCurrentlyViewingPostError::UserAlreadyParticipatedInThisPost);
+    }
+
+    Ok(())
+}
+
+/// Checks that the caller may bet on behalf of this profile and can cover `bet_amount`. Tokens
+/// held in escrow for bets still being placed can't be bet again.
+pub fn validate_bet_maker(
+    canister_data: &CanisterData,
+    bet_maker_principal_id: &Principal,
+    bet_amount: u64,
+    current_time: &SystemTime,
 ) -> Result<(), BetOnCurrentlyViewingPostError> {
     if *bet_maker_principal_id == Principal::anonymous() {
         return Err(BetOnCurrentlyViewingPostError::UserNotLoggedIn);
@@ -107,30 +287,93 @@ fn validate_incoming_bet(
         .ok_or(BetOnCurrentlyViewingPostError::UserPrincipalNotSet)?;
 
     if *bet_maker_principal_id != profile_owner {
-        return Err(BetOnCurrentlyViewingPostError::Unauthorized);
+        let session_delegate = canister_data
+            .session_delegates
+            .get(bet_maker_principal_id)
+            .filter(|session_delegate| !session_delegate.is_expired(current_time))
+            .ok_or(BetOnCurrentlyViewingPostError::Unauthorized)?;
+
+        let amount_in_flight = canister_data
+            .session_delegate_bet_amounts_in_flight
+            .get(bet_maker_principal_id)
+            .copied()
+            .unwrap_or_default();
+
+        if session_delegate
+            .get_amount_left_to_bet_today(current_time)
+            .saturating_sub(amount_in_flight)
+            < bet_amount
+        {
+            return Err(BetOnCurrentlyViewingPostError::SessionDelegateDailyBetLimitExceeded);
+        }
     }
 
-    let utlility_token_balance = canister_data.my_token_balance.get_utility_token_balance();
+    let utlility_token_balance = canister_data
+        .my_token_balance
+        .get_utility_token_balance()
+        .saturating_sub(canister_data.escrowed_bet_amount);
 
-    if utlility_token_balance < place_bet_arg.bet_amount {
+    if utlility_token_balance < bet_amount {
         return Err(BetOnCurrentlyViewingPostError::InsufficientBalance);
     }
 
-    if canister_data
-        .all_hot_or_not_bets_placed
-        .contains_key(&(place_bet_arg.post_canister_id, place_bet_arg.post_id))
+    Ok(())
+}
+
+/// Holds `bet_amount` against the daily limit of the session delegate placing it, so that
+/// bets it places alongside can't go over the limit while this one is out. Bets placed by the
+/// profile owner aren't limited.
+pub fn reserve_session_delegate_bet_amount(
+    canister_data: &mut CanisterData,
+    bet_maker_principal_id: &Principal,
+    bet_amount: u64,
+) {
+    if !canister_data
+        .session_delegates
+        .contains_key(bet_maker_principal_id)
     {
-        return Err(BetOnCurrentlyViewingPostError::UserAlreadyParticipatedInThisPost);
+        return;
     }
 
-    Ok(())
+    let amount_in_flight = canister_data
+        .session_delegate_bet_amounts_in_flight
+        .entry(*bet_maker_principal_id)
+        .or_default();
+    *amount_in_flight = amount_in_flight.saturating_add(bet_amount);
+}
+
+/// Placed bets count towards the daily limit by now, so in either case all that is left is to
+/// stop holding the amount
+pub fn release_session_delegate_bet_amount(
+    canister_data: &mut CanisterData,
+    bet_maker_principal_id: &Principal,
+    bet_amount: u64,
+) {
+    let Some(amount_in_flight) = canister_data
+        .session_delegate_bet_amounts_in_flight
+        .get_mut(bet_maker_principal_id)
+    else {
+        return;
+    };
+
+    *amount_in_flight = amount_in_flight.saturating_sub(bet_amount);
+    if *amount_in_flight == 0 {
+        canister_data
+            .session_delegate_bet_amounts_in_flight
+            .remove(bet_maker_principal_id);
+    }
 }
 
 #[cfg(test)]
 mod test {
-    use std::time::SystemTime;
+    use std::time::Duration;
 
-    use shared_utils::canister_specific::individual_user_template::types::hot_or_not::BetDirection;
+    use shared_utils::canister_specific::individual_user_template::types::{
+        hot_or_not::BetDirection,
+        session_delegate::{
+            RegisterSessionDelegateArg, SessionDelegate, SessionDelegatePermission,
+        },
+    };
     use test_utils::setup::test_constants::{
         get_mock_user_alice_canister_id, get_mock_user_alice_principal_id,
         get_mock_user_bob_principal_id,
@@ -151,6 +394,7 @@ mod test {
                 bet_amount: 100,
                 bet_direction: BetDirection::Hot,
             },
+            &SystemTime::now(),
         );
 
         assert_eq!(result, Err(BetOnCurrentlyViewingPostError::UserNotLoggedIn));
@@ -166,6 +410,7 @@ mod test {
                 bet_amount: 100,
                 bet_direction: BetDirection::Hot,
             },
+            &SystemTime::now(),
         );
 
         assert_eq!(result, Err(BetOnCurrentlyViewingPostError::Unauthorized));
@@ -179,6 +424,7 @@ mod test {
                 bet_amount: 100,
                 bet_direction: BetDirection::Hot,
             },
+            &SystemTime::now(),
         );
 
         assert_eq!(
@@ -197,6 +443,7 @@ mod test {
                 bet_amount: 100,
                 bet_direction: BetDirection::Hot,
             },
+            &SystemTime::now(),
         );
 
         assert_eq!(result, Ok(()));
@@ -224,6 +471,7 @@ mod test {
                 bet_amount: 100,
                 bet_direction: BetDirection::Hot,
             },
+            &SystemTime::now(),
         );
 
         assert_eq!(
@@ -231,4 +479,157 @@ mod test {
             Err(BetOnCurrentlyViewingPostError::UserAlreadyParticipatedInThisPost)
         );
     }
+
+    #[test]
+    fn test_validate_incoming_bet_from_session_delegate() {
+        let mut canister_data = CanisterData::default();
+        let current_time = SystemTime::now();
+        canister_data.profile.principal_id = Some(get_mock_user_alice_principal_id());
+        canister_data.my_token_balance.utility_token_balance = 1000;
+
+        let place_bet_arg = PlaceBetArg {
+            post_canister_id: get_mock_user_alice_canister_id(),
+            post_id: 0,
+            bet_amount: 100,
+            bet_direction: BetDirection::Hot,
+        };
+
+        canister_data.session_delegates.insert(
+            get_mock_user_bob_principal_id(),
+            SessionDelegate::new(
+                &RegisterSessionDelegateArg {
+                    delegate_principal_id: get_mock_user_bob_principal_id(),
+                    permission: SessionDelegatePermission::BetOnHotOrNotPosts {
+                        max_bet_amount_per_day: 150,
+                    },
+                    expires_at: current_time
+                        .checked_add(Duration::from_secs(60 * 60))
+                        .unwrap(),
+                },
+                &current_time,
+            ),
+        );
+
+        assert_eq!(
+            validate_incoming_bet(
+                &canister_data,
+                &get_mock_user_bob_principal_id(),
+                &place_bet_arg,
+                &current_time,
+            ),
+            Ok(())
+        );
+
+        canister_data
+            .session_delegates
+            .get_mut(&get_mock_user_bob_principal_id())
+            .unwrap()
+            .record_bet_placed(100, &current_time);
+
+        assert_eq!(
+            validate_incoming_bet(
+                &canister_data,
+                &get_mock_user_bob_principal_id(),
+                &place_bet_arg,
+                &current_time,
+            ),
+            Err(BetOnCurrentlyViewingPostError::SessionDelegateDailyBetLimitExceeded)
+        );
+
+        assert_eq!(
+            validate_incoming_bet(
+                &canister_data,
+                &get_mock_user_bob_principal_id(),
+                &place_bet_arg,
+                &current_time
+                    .checked_add(Duration::from_secs(60 * 60))
+                    .unwrap(),
+            ),
+            Err(BetOnCurrentlyViewingPostError::Unauthorized)
+        );
+    }
+
+    #[test]
+    fn test_concurrent_bets_through_a_session_delegate_stay_within_its_daily_limit() {
+        let mut canister_data = CanisterData::default();
+        let current_time = SystemTime::now();
+        let session_delegate_principal_id = get_mock_user_bob_principal_id();
+        canister_data.profile.principal_id = Some(get_mock_user_alice_principal_id());
+        canister_data.my_token_balance.utility_token_balance = 1000;
+        canister_data.session_delegates.insert(
+            session_delegate_principal_id,
+            SessionDelegate::new(
+                &RegisterSessionDelegateArg {
+                    delegate_principal_id: session_delegate_principal_id,
+                    permission: SessionDelegatePermission::BetOnHotOrNotPosts {
+                        max_bet_amount_per_day: 150,
+                    },
+                    expires_at: current_time
+                        .checked_add(Duration::from_secs(60 * 60))
+                        .unwrap(),
+                },
+                &current_time,
+            ),
+        );
+        let get_place_bet_arg = |post_id| PlaceBetArg {
+            post_canister_id: get_mock_user_alice_canister_id(),
+            post_id,
+            bet_amount: 100,
+            bet_direction: BetDirection::Hot,
+        };
+
+        // * the first bet is out with the post creator's canister when the second comes in
+        assert_eq!(
+            validate_incoming_bet(
+                &canister_data,
+                &session_delegate_principal_id,
+                &get_place_bet_arg(0),
+                &current_time,
+            ),
+            Ok(())
+        );
+        reserve_session_delegate_bet_amount(
+            &mut canister_data,
+            &session_delegate_principal_id,
+            100,
+        );
+        assert_eq!(
+            validate_incoming_bet(
+                &canister_data,
+                &session_delegate_principal_id,
+                &get_place_bet_arg(1),
+                &current_time,
+            ),
+            Err(BetOnCurrentlyViewingPostError::SessionDelegateDailyBetLimitExceeded)
+        );
+
+        // * the first bet was rejected, which frees up the limit again
+        release_session_delegate_bet_amount(
+            &mut canister_data,
+            &session_delegate_principal_id,
+            100,
+        );
+        assert!(canister_data
+            .session_delegate_bet_amounts_in_flight
+            .is_empty());
+        assert_eq!(
+            validate_incoming_bet(
+                &canister_data,
+                &session_delegate_principal_id,
+                &get_place_bet_arg(1),
+                &current_time,
+            ),
+            Ok(())
+        );
+
+        // * bets of the profile owner aren't held against any limit
+        reserve_session_delegate_bet_amount(
+            &mut canister_data,
+            &get_mock_user_alice_principal_id(),
+            100,
+        );
+        assert!(canister_data
+            .session_delegate_bet_amounts_in_flight
+            .is_empty());
+    }
 }
@@ -0,0 +1,308 @@
+use std::{collections::BTreeSet, time::SystemTime};
+
+use candid::Principal;
+use shared_utils::{
+    canister_specific::individual_user_template::types::{
+        arg::{PlaceBetArg, MAX_BETS_PER_BATCH},
+        error::{BetOnCurrentlyViewingPostError, BetOnMultiplePostsError},
+        hot_or_not::BettingStatus,
+    },
+    common::utils::system_time::{IcTimeProvider, TimeProvider},
+};
+
+use crate::{
+    api::hot_or_not_bet::bet_on_currently_viewing_hot_or_not_post::{
+        place_validated_bet, record_bet_rejection, release_session_delegate_bet_amount,
+        reserve_session_delegate_bet_amount, validate_bet_maker,
+    },
+    data_model::CanisterData,
+    util::{
+        call_journal::record_call_in_journal,
+        guards::reject_bets_and_posts_in_read_only_or_low_cycles_mode,
+    },
+    CANISTER_DATA,
+};
+
+type PlaceBetResponse = Result<BettingStatus, BetOnCurrentlyViewingPostError>;
+
+/// Places a basket of bets, one per post, in a single call. The total is held in escrow up
+/// front so the bets can't be outspent while they are being placed, and each bet's share of
+/// the escrow is released once its post creator's canister has answered. Responses come back
+/// in the order the bets were passed in, and bets that fail don't undo the ones that went
+/// through.
+#[ic_cdk::update(guard = "reject_bets_and_posts_in_read_only_or_low_cycles_mode")]
+#[candid::candid_method(update)]
+async fn bet_on_multiple_posts(
+    place_bet_args: Vec<PlaceBetArg>,
+) -> Result<Vec<Result<BettingStatus, BetOnCurrentlyViewingPostError>>, BetOnMultiplePostsError> {
+    record_call_in_journal("bet_on_multiple_posts");
+
+    let bet_maker_principal_id = ic_cdk::caller();
+    let current_time = IcTimeProvider.get_current_time();
+
+    let mut responses = CANISTER_DATA.with(|canister_data_ref_cell| {
+        escrow_bet_batch_impl(
+            &mut canister_data_ref_cell.borrow_mut(),
+            &bet_maker_principal_id,
+            &place_bet_args,
+            &current_time,
+        )
+    })?;
+
+    for (place_bet_arg, response) in place_bet_args.into_iter().zip(responses.iter_mut()) {
+        if response.is_some() {
+            continue;
+        }
+
+        let bet_amount = place_bet_arg.bet_amount;
+        let placed_bet_response =
+            place_validated_bet(place_bet_arg, bet_maker_principal_id, current_time).await;
+
+        CANISTER_DATA.with(|canister_data_ref_cell| {
+            release_escrow_impl(
+                &mut canister_data_ref_cell.borrow_mut(),
+                &bet_maker_principal_id,
+                bet_amount,
+                &placed_bet_response,
+            );
+        });
+
+        *response = Some(placed_bet_response);
+    }
+
+    Ok(responses.into_iter().flatten().collect())
+}
+
+/// Checks the basket as a whole and escrows what it needs. Bets on posts this profile already
+/// bet on are answered right away and left out of the escrow, the rest are `None` until placed.
+fn escrow_bet_batch_impl(
+    canister_data: &mut CanisterData,
+    bet_maker_principal_id: &Principal,
+    place_bet_args: &[PlaceBetArg],
+    current_time: &SystemTime,
+) -> Result<Vec<Option<PlaceBetResponse>>, BetOnMultiplePostsError> {
+    if place_bet_args.is_empty() {
+        return Err(BetOnMultiplePostsError::EmptyBatch);
+    }
+
+    if place_bet_args.len() > MAX_BETS_PER_BATCH {
+        return Err(BetOnMultiplePostsError::TooManyBetsInBatch);
+    }
+
+    let mut posts_in_batch = BTreeSet::new();
+    if !place_bet_args.iter().all(|place_bet_arg| {
+        posts_in_batch.insert((place_bet_arg.post_canister_id, place_bet_arg.post_id))
+    }) {
+        return Err(BetOnMultiplePostsError::DuplicatePostInBatch);
+    }
+
+    let responses = place_bet_args
+        .iter()
+        .map(|place_bet_arg| {
+            canister_data
+                .all_hot_or_not_bets_placed
+                .contains_key(&(place_bet_arg.post_canister_id, place_bet_arg.post_id))
+                .then_some(Err(
+                    BetOnCurrentlyViewingPostError::UserAlreadyParticipatedInThisPost,
+                ))
+        })
+        .collect::<Vec<_>>();
+
+    let total_bet_amount = place_bet_args
+        .iter()
+        .zip(responses.iter())
+        .filter(|(_, response)| response.is_none())
+        .fold(0_u64, |total_bet_amount, (place_bet_arg, _)| {
+            total_bet_amount.saturating_add(place_bet_arg.bet_amount)
+        });
+
+    // * The balance and a session delegate's daily limit have to cover the whole basket
+    validate_bet_maker(
+        canister_data,
+        bet_maker_principal_id,
+        total_bet_amount,
+        current_time,
+    )
+    .map_err(BetOnMultiplePostsError::BetMakerRejected)?;
+
+    responses
+        .iter()
+        .flatten()
+        .filter_map(|response| response.as_ref().err())
+        .for_each(|error| record_bet_rejection(canister_data, error));
+    canister_data.escrowed_bet_amount += total_bet_amount;
+    reserve_session_delegate_bet_amount(canister_data, bet_maker_principal_id, total_bet_amount);
+
+    Ok(responses)
+}
+
+/// Placed bets have had their stake taken from the balance by now, so in either case all that
+/// is left is to stop holding the amount
+fn release_escrow_impl(
+    canister_data: &mut CanisterData,
+    bet_maker_principal_id: &Principal,
+    bet_amount: u64,
+    placed_bet_response: &PlaceBetResponse,
+) {
+    canister_data.escrowed_bet_amount =
+        canister_data.escrowed_bet_amount.saturating_sub(bet_amount);
+    release_session_delegate_bet_amount(canister_data, bet_maker_principal_id, bet_amount);
+
+    if let Err(error) = placed_bet_response {
+        record_bet_rejection(canister_data, error);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use shared_utils::canister_specific::individual_user_template::types::hot_or_not::{
+        BetDirection, BetOutcomeForBetMaker, PlacedBetDetail,
+    };
+    use test_utils::setup::test_constants::{
+        get_mock_user_alice_principal_id, get_mock_user_bob_canister_id,
+        get_mock_user_charlie_canister_id,
+    };
+
+    use super::*;
+
+    fn get_place_bet_arg(
+        post_canister_id: Principal,
+        post_id: u64,
+        bet_amount: u64,
+    ) -> PlaceBetArg {
+        PlaceBetArg {
+            post_canister_id,
+            post_id,
+            bet_amount,
+            bet_direction: BetDirection::Hot,
+        }
+    }
+
+    #[test]
+    fn test_escrow_bet_batch_impl_rejects_invalid_batches() {
+        let mut canister_data = CanisterData::default();
+        canister_data.profile.principal_id = Some(get_mock_user_alice_principal_id());
+        canister_data.my_token_balance.utility_token_balance = 1000;
+
+        assert_eq!(
+            escrow_bet_batch_impl(
+                &mut canister_data,
+                &get_mock_user_alice_principal_id(),
+                &[],
+                &SystemTime::now(),
+            ),
+            Err(BetOnMultiplePostsError::EmptyBatch)
+        );
+        assert_eq!(
+            escrow_bet_batch_impl(
+                &mut canister_data,
+                &get_mock_user_alice_principal_id(),
+                &(0..=MAX_BETS_PER_BATCH as u64)
+                    .map(|post_id| get_place_bet_arg(get_mock_user_bob_canister_id(), post_id, 10))
+                    .collect::<Vec<_>>(),
+                &SystemTime::now(),
+            ),
+            Err(BetOnMultiplePostsError::TooManyBetsInBatch)
+        );
+        assert_eq!(
+            escrow_bet_batch_impl(
+                &mut canister_data,
+                &get_mock_user_alice_principal_id(),
+                &[
+                    get_place_bet_arg(get_mock_user_bob_canister_id(), 0, 10),
+                    get_place_bet_arg(get_mock_user_bob_canister_id(), 0, 20),
+                ],
+                &SystemTime::now(),
+            ),
+            Err(BetOnMultiplePostsError::DuplicatePostInBatch)
+        );
+        // * each bet is affordable on its own, but not all of them together
+        assert_eq!(
+            escrow_bet_batch_impl(
+                &mut canister_data,
+                &get_mock_user_alice_principal_id(),
+                &[
+                    get_place_bet_arg(get_mock_user_bob_canister_id(), 0, 600),
+                    get_place_bet_arg(get_mock_user_charlie_canister_id(), 0, 600),
+                ],
+                &SystemTime::now(),
+            ),
+            Err(BetOnMultiplePostsError::BetMakerRejected(
+                BetOnCurrentlyViewingPostError::InsufficientBalance
+            ))
+        );
+        assert_eq!(canister_data.escrowed_bet_amount, 0);
+    }
+
+    #[test]
+    fn test_escrow_bet_batch_impl_holds_and_releases_the_escrow() {
+        let mut canister_data = CanisterData::default();
+        canister_data.profile.principal_id = Some(get_mock_user_alice_principal_id());
+        canister_data.my_token_balance.utility_token_balance = 1000;
+        canister_data.all_hot_or_not_bets_placed.insert(
+            (get_mock_user_bob_canister_id(), 1),
+            PlacedBetDetail {
+                canister_id: get_mock_user_bob_canister_id(),
+                post_id: 1,
+                slot_id: 1,
+                room_id: 1,
+                amount_bet: 100,
+                bet_direction: BetDirection::Hot,
+                bet_placed_at: SystemTime::now(),
+                outcome_received: BetOutcomeForBetMaker::default(),
+            },
+        );
+
+        let responses = escrow_bet_batch_impl(
+            &mut canister_data,
+            &get_mock_user_alice_principal_id(),
+            &[
+                get_place_bet_arg(get_mock_user_bob_canister_id(), 0, 400),
+                get_place_bet_arg(get_mock_user_bob_canister_id(), 1, 400),
+                get_place_bet_arg(get_mock_user_charlie_canister_id(), 0, 500),
+            ],
+            &SystemTime::now(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            responses,
+            vec![
+                None,
+                Some(Err(
+                    BetOnCurrentlyViewingPostError::UserAlreadyParticipatedInThisPost
+                )),
+                None,
+            ]
+        );
+        assert_eq!(canister_data.escrowed_bet_amount, 900);
+
+        // * escrowed tokens can't be bet again while the basket is being placed
+        assert_eq!(
+            validate_bet_maker(
+                &canister_data,
+                &get_mock_user_alice_principal_id(),
+                200,
+                &SystemTime::now(),
+            ),
+            Err(BetOnCurrentlyViewingPostError::InsufficientBalance)
+        );
+
+        release_escrow_impl(
+            &mut canister_data,
+            &get_mock_user_alice_principal_id(),
+            400,
+            &Err(BetOnCurrentlyViewingPostError::BettingClosed),
+        );
+        assert_eq!(canister_data.escrowed_bet_amount, 500);
+        assert_eq!(
+            validate_bet_maker(
+                &canister_data,
+                &get_mock_user_alice_principal_id(),
+                500,
+                &SystemTime::now(),
+            ),
+            Ok(())
+        );
+    }
+}
@@ -0,0 +1,75 @@
+use shared_utils::{
+    canister_specific::individual_user_template::types::{
+        arg::PlaceBetArg,
+        error::BetOnCurrentlyViewingPostError,
+        hot_or_not::{bet_eligibility::BetEligibility, BetDirection, BettingStatus},
+    },
+    common::{
+        types::global_post_ref::GlobalPostRef,
+        utils::system_time::{IcTimeProvider, TimeProvider},
+    },
+};
+
+use crate::CANISTER_DATA;
+
+use super::bet_on_currently_viewing_hot_or_not_post::validate_incoming_bet;
+
+/// Runs the checks placing the bet would run, both on this canister and on the post
+/// creator's, without placing it. Lets the frontend explain why a bet would be turned down
+/// before the user makes it.
+#[ic_cdk::query(composite = true)]
+#[candid::candid_method(composite_query)]
+async fn can_i_bet(
+    post_ref: GlobalPostRef,
+    bet_amount: u64,
+    bet_direction: BetDirection,
+) -> BetEligibility {
+    let bet_maker_principal_id = ic_cdk::caller();
+    let current_time = IcTimeProvider.get_current_time();
+    let place_bet_arg = PlaceBetArg {
+        post_canister_id: post_ref.canister_id,
+        post_id: post_ref.post_id,
+        bet_amount,
+        bet_direction,
+    };
+
+    let (bet_maker_check, profile_owner, expected_binding_epoch) =
+        CANISTER_DATA.with(|canister_data_ref_cell| {
+            let canister_data = canister_data_ref_cell.borrow();
+
+            (
+                validate_incoming_bet(
+                    &canister_data,
+                    &bet_maker_principal_id,
+                    &place_bet_arg,
+                    &current_time,
+                ),
+                canister_data
+                    .profile
+                    .principal_id
+                    .unwrap_or(bet_maker_principal_id),
+                // * a query can't cache an epoch it resolves, so only an already known one is
+                // * passed along
+                canister_data
+                    .known_binding_epochs
+                    .get(&post_ref.canister_id)
+                    .copied(),
+            )
+        });
+
+    let post_check = ic_cdk::call::<_, (Result<BettingStatus, BetOnCurrentlyViewingPostError>,)>(
+        post_ref.canister_id,
+        "validate_bet_on_post",
+        (
+            post_ref.post_id,
+            profile_owner,
+            bet_amount,
+            expected_binding_epoch,
+        ),
+    )
+    .await
+    .map_err(|_| BetOnCurrentlyViewingPostError::PostCreatorCanisterCallFailed)
+    .and_then(|(post_check,)| post_check);
+
+    BetEligibility::new(bet_maker_check, post_check)
+}
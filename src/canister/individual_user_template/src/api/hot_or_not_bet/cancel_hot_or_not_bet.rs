@@ -0,0 +1,209 @@
+use std::time::SystemTime;
+
+use candid::Principal;
+use shared_utils::{
+    canister_specific::individual_user_template::types::{
+        error::BetOnCurrentlyViewingPostError, hot_or_not::BetOutcomeForBetMaker,
+    },
+    common::{
+        types::{
+            app_primitive_type::PostId,
+            utility_token::token_event::{StakeEvent, TokenEvent},
+        },
+        utils::system_time::{IcTimeProvider, TimeProvider},
+    },
+};
+
+use crate::{
+    data_model::CanisterData,
+    util::{call_journal::record_call_in_journal, guards::reject_updates_in_read_only_mode},
+    CANISTER_DATA,
+};
+
+/// Takes back a bet this profile placed, as long as it is still within the first minutes of
+/// the slot it was placed in. Part of the stake is refunded, as set by the post's canister.
+/// Returns the amount refunded.
+///
+/// # Access Control
+/// Only the profile owner can cancel their bets
+#[ic_cdk::update(guard = "reject_updates_in_read_only_mode")]
+#[candid::candid_method(update)]
+async fn cancel_hot_or_not_bet(
+    post_canister_id: Principal,
+    post_id: PostId,
+) -> Result<u64, BetOnCurrentlyViewingPostError> {
+    record_call_in_journal("cancel_hot_or_not_bet");
+
+    let api_caller = ic_cdk::caller();
+
+    CANISTER_DATA.with(|canister_data_ref_cell| {
+        validate_bet_cancellation(
+            &canister_data_ref_cell.borrow(),
+            &api_caller,
+            &post_canister_id,
+            post_id,
+        )
+    })?;
+
+    let refund_amount = ic_cdk::call::<_, (Result<u64, BetOnCurrentlyViewingPostError>,)>(
+        post_canister_id,
+        "receive_bet_cancellation_from_bet_makers_canister",
+        (post_id, api_caller),
+    )
+    .await
+    .map_err(|_| BetOnCurrentlyViewingPostError::PostCreatorCanisterCallFailed)?
+    .0?;
+
+    let current_time = IcTimeProvider.get_current_time();
+
+    CANISTER_DATA.with(|canister_data_ref_cell| {
+        record_cancelled_bet(
+            &mut canister_data_ref_cell.borrow_mut(),
+            &post_canister_id,
+            post_id,
+            refund_amount,
+            &current_time,
+        );
+    });
+
+    Ok(refund_amount)
+}
+
+fn validate_bet_cancellation(
+    canister_data: &CanisterData,
+    api_caller: &Principal,
+    post_canister_id: &Principal,
+    post_id: PostId,
+) -> Result<(), BetOnCurrentlyViewingPostError> {
+    if *api_caller == Principal::anonymous() {
+        return Err(BetOnCurrentlyViewingPostError::UserNotLoggedIn);
+    }
+
+    if canister_data.profile.principal_id != Some(*api_caller) {
+        return Err(BetOnCurrentlyViewingPostError::Unauthorized);
+    }
+
+    match canister_data
+        .all_hot_or_not_bets_placed
+        .get(&(*post_canister_id, post_id))
+    {
+        Some(placed_bet_detail)
+            if placed_bet_detail.outcome_received == BetOutcomeForBetMaker::AwaitingResult =>
+        {
+            Ok(())
+        }
+        _ => Err(BetOnCurrentlyViewingPostError::BetNotFound),
+    }
+}
+
+fn record_cancelled_bet(
+    canister_data: &mut CanisterData,
+    post_canister_id: &Principal,
+    post_id: PostId,
+    refund_amount: u64,
+    current_time: &SystemTime,
+) {
+    let Some(placed_bet_detail) = canister_data
+        .all_hot_or_not_bets_placed
+        .remove(&(*post_canister_id, post_id))
+    else {
+        return;
+    };
+    canister_data
+        .betting_stats
+        .record_bet_cancelled(placed_bet_detail.amount_bet);
+
+    canister_data.handle_token_event(TokenEvent::Stake {
+        amount: refund_amount,
+        details: StakeEvent::BetOnHotOrNotPostCancelled {
+            post_canister_id: *post_canister_id,
+            post_id,
+            bet_amount: placed_bet_detail.amount_bet,
+            refund_amount,
+        },
+        timestamp: *current_time,
+    });
+}
+
+#[cfg(test)]
+mod test {
+    use shared_utils::canister_specific::individual_user_template::types::hot_or_not::{
+        BetDirection, PlacedBetDetail,
+    };
+    use test_utils::setup::test_constants::{
+        get_mock_user_alice_canister_id, get_mock_user_alice_principal_id,
+        get_mock_user_bob_principal_id,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_cancel_hot_or_not_bet() {
+        let mut canister_data = CanisterData::default();
+        let current_time = SystemTime::now();
+        canister_data.profile.principal_id = Some(get_mock_user_bob_principal_id());
+        canister_data.my_token_balance.utility_token_balance = 900;
+        canister_data.all_hot_or_not_bets_placed.insert(
+            (get_mock_user_alice_canister_id(), 0),
+            PlacedBetDetail {
+                canister_id: get_mock_user_alice_canister_id(),
+                post_id: 0,
+                slot_id: 1,
+                room_id: 1,
+                amount_bet: 100,
+                bet_direction: BetDirection::Hot,
+                bet_placed_at: current_time,
+                outcome_received: BetOutcomeForBetMaker::AwaitingResult,
+            },
+        );
+
+        assert_eq!(
+            validate_bet_cancellation(
+                &canister_data,
+                &get_mock_user_alice_principal_id(),
+                &get_mock_user_alice_canister_id(),
+                0,
+            ),
+            Err(BetOnCurrentlyViewingPostError::Unauthorized)
+        );
+        assert_eq!(
+            validate_bet_cancellation(
+                &canister_data,
+                &get_mock_user_bob_principal_id(),
+                &get_mock_user_alice_canister_id(),
+                1,
+            ),
+            Err(BetOnCurrentlyViewingPostError::BetNotFound)
+        );
+        assert_eq!(
+            validate_bet_cancellation(
+                &canister_data,
+                &get_mock_user_bob_principal_id(),
+                &get_mock_user_alice_canister_id(),
+                0,
+            ),
+            Ok(())
+        );
+
+        record_cancelled_bet(
+            &mut canister_data,
+            &get_mock_user_alice_canister_id(),
+            0,
+            90,
+            &current_time,
+        );
+
+        assert_eq!(canister_data.my_token_balance.utility_token_balance, 990);
+        assert!(canister_data.all_hot_or_not_bets_placed.is_empty());
+        // * nothing left to cancel
+        assert_eq!(
+            validate_bet_cancellation(
+                &canister_data,
+                &get_mock_user_bob_principal_id(),
+                &get_mock_user_alice_canister_id(),
+                0,
+            ),
+            Err(BetOnCurrentlyViewingPostError::BetNotFound)
+        );
+    }
+}
@@ -0,0 +1,149 @@
+use std::time::Duration;
+
+use shared_utils::{
+    canister_specific::individual_user_template::types::{
+        error::PotInsuranceClaimError, treasury::PotInsuranceClaim,
+    },
+    common::{
+        types::{event_counter::EventCounterName, known_principal::KnownPrincipalType},
+        utils::system_time::{IcTimeProvider, TimeProvider},
+    },
+};
+
+use crate::{data_model::CanisterData, CANISTER_DATA};
+
+/// Queues the top ups owed to winners of freshly tabulated rooms that settled below the pot
+/// floor. Returns false when there is nothing to claim.
+pub fn enqueue_pot_insurance_claims_impl(
+    canister_data: &mut CanisterData,
+    pot_insurance_claims: Vec<PotInsuranceClaim>,
+) -> bool {
+    if pot_insurance_claims.is_empty() {
+        return false;
+    }
+
+    canister_data
+        .pending_pot_insurance_claims
+        .extend(pot_insurance_claims);
+
+    true
+}
+
+/// Sends every queued claim to the treasury canister. A claim is dropped once the treasury
+/// answers, whether it paid or not, as the answer won't change on a retry of the same claim.
+/// Claims stay queued while no treasury or profile owner is set, and go to the back of the
+/// queue when the call fails.
+pub fn claim_pending_pot_insurance_from_treasury() {
+    ic_cdk_timers::set_timer(Duration::ZERO, || {
+        ic_cdk::spawn(claim_pending_pot_insurance())
+    });
+}
+
+async fn claim_pending_pot_insurance() {
+    let current_time = IcTimeProvider.get_current_time();
+    let (treasury_canister_id, post_creator_principal_id, pending_claims) =
+        CANISTER_DATA.with(|canister_data_ref_cell| {
+            let mut canister_data = canister_data_ref_cell.borrow_mut();
+
+            let treasury_canister_id = canister_data
+                .known_principal_ids
+                .get(&KnownPrincipalType::CanisterIdTreasury)
+                .cloned();
+            let post_creator_principal_id = canister_data.profile.principal_id;
+            let pending_claims =
+                if treasury_canister_id.is_some() && post_creator_principal_id.is_some() {
+                    std::mem::take(&mut canister_data.pending_pot_insurance_claims)
+                } else {
+                    Default::default()
+                };
+
+            (
+                treasury_canister_id,
+                post_creator_principal_id,
+                pending_claims,
+            )
+        });
+
+    let (Some(treasury_canister_id), Some(post_creator_principal_id)) =
+        (treasury_canister_id, post_creator_principal_id)
+    else {
+        return;
+    };
+
+    for mut claim in pending_claims {
+        // * Claims queued before they carried a tabulation time are stamped with the time
+        // * they're first sent at
+        claim.tabulated_at.get_or_insert(current_time);
+
+        let response = ic_cdk::call::<_, (Result<u64, PotInsuranceClaimError>,)>(
+            treasury_canister_id,
+            "receive_pot_insurance_claim",
+            (claim.clone(), post_creator_principal_id),
+        )
+        .await;
+
+        if response.is_ok() {
+            continue;
+        }
+
+        CANISTER_DATA.with(|canister_data_ref_cell| {
+            let canister_data = &mut canister_data_ref_cell.borrow_mut();
+
+            claim.number_of_failed_attempts += 1;
+            canister_data.pending_pot_insurance_claims.push_back(claim);
+            canister_data.event_counters.increment(
+                EventCounterName::SettlementFailuresTotal,
+                "pot_insurance_claim",
+            );
+        });
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use shared_utils::canister_specific::individual_user_template::types::treasury::PotInsuranceTopUp;
+    use test_utils::setup::test_constants::{
+        get_mock_user_alice_canister_id, get_mock_user_bob_canister_id,
+    };
+
+    use super::*;
+
+    fn get_pot_insurance_claim(room_id: u64) -> PotInsuranceClaim {
+        PotInsuranceClaim {
+            post_canister_id: get_mock_user_alice_canister_id(),
+            post_id: 0,
+            slot_id: 1,
+            room_id,
+            room_pot_total_amount: 100,
+            top_ups: vec![PotInsuranceTopUp {
+                bet_maker_canister_id: get_mock_user_bob_canister_id(),
+                amount: 720,
+            }],
+            number_of_failed_attempts: 0,
+            tabulated_at: None,
+        }
+    }
+
+    #[test]
+    fn test_enqueue_pot_insurance_claims_impl() {
+        let mut canister_data = CanisterData::default();
+
+        assert!(!enqueue_pot_insurance_claims_impl(
+            &mut canister_data,
+            vec![]
+        ));
+        assert!(enqueue_pot_insurance_claims_impl(
+            &mut canister_data,
+            vec![get_pot_insurance_claim(1), get_pot_insurance_claim(2)]
+        ));
+
+        assert_eq!(
+            canister_data
+                .pending_pot_insurance_claims
+                .iter()
+                .map(|claim| claim.room_id)
+                .collect::<Vec<_>>(),
+            vec![1, 2]
+        );
+    }
+}
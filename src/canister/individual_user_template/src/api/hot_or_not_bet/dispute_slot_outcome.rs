@@ -0,0 +1,250 @@
+use std::time::SystemTime;
+
+use candid::Principal;
+use shared_utils::{
+    canister_specific::individual_user_template::types::{
+        dispute::{SlotOutcomeDispute, MAX_SLOT_OUTCOME_DISPUTE_REASON_LENGTH},
+        error::DisputeSlotOutcomeError,
+        hot_or_not::SlotId,
+    },
+    common::{
+        types::{app_primitive_type::PostId, known_principal::KnownPrincipalType},
+        utils::system_time::{IcTimeProvider, TimeProvider},
+    },
+};
+
+use crate::{
+    data_model::CanisterData,
+    util::{
+        call_journal::record_call_in_journal, call_queue::queue_call,
+        guards::reject_updates_in_read_only_mode,
+    },
+    CANISTER_DATA,
+};
+
+/// Flags a settled slot of one of this profile's posts for review. The dispute is kept here
+/// and passed on to the user index, where admins can have the slot recalculated.
+///
+/// # Access Control
+/// Only bettors with a bet in the slot, within 24 hours of it being settled
+#[ic_cdk::update(guard = "reject_updates_in_read_only_mode")]
+#[candid::candid_method(update)]
+fn dispute_slot_outcome(
+    post_id: PostId,
+    slot_id: SlotId,
+    reason: String,
+) -> Result<(), DisputeSlotOutcomeError> {
+    record_call_in_journal("dispute_slot_outcome");
+
+    let api_caller = ic_cdk::caller();
+    let current_time = IcTimeProvider.get_current_time();
+
+    let dispute = CANISTER_DATA.with(|canister_data_ref_cell| {
+        dispute_slot_outcome_impl(
+            &mut canister_data_ref_cell.borrow_mut(),
+            &api_caller,
+            post_id,
+            slot_id,
+            reason,
+            &current_time,
+        )
+    })?;
+
+    let user_index_canister_id = CANISTER_DATA.with(|canister_data_ref_cell| {
+        canister_data_ref_cell
+            .borrow()
+            .known_principal_ids
+            .get(&KnownPrincipalType::CanisterIdUserIndex)
+            .cloned()
+    });
+
+    if let Some(user_index_canister_id) = user_index_canister_id {
+        queue_call(
+            user_index_canister_id,
+            "receive_slot_outcome_dispute",
+            (dispute,),
+        );
+    }
+
+    Ok(())
+}
+
+fn dispute_slot_outcome_impl(
+    canister_data: &mut CanisterData,
+    disputed_by: &Principal,
+    post_id: PostId,
+    slot_id: SlotId,
+    reason: String,
+    current_time: &SystemTime,
+) -> Result<SlotOutcomeDispute, DisputeSlotOutcomeError> {
+    if *disputed_by == Principal::anonymous() {
+        return Err(DisputeSlotOutcomeError::UserNotLoggedIn);
+    }
+
+    let reason = reason.trim().to_string();
+    if reason.is_empty() || reason.chars().count() > MAX_SLOT_OUTCOME_DISPUTE_REASON_LENGTH {
+        return Err(DisputeSlotOutcomeError::InvalidReason);
+    }
+
+    canister_data
+        .all_created_posts
+        .get_with_archived_bets(&post_id)
+        .ok_or(DisputeSlotOutcomeError::PostNotFound)?
+        .validate_slot_outcome_dispute(disputed_by, slot_id, current_time)?;
+
+    let dispute_key = (post_id, slot_id, *disputed_by);
+    if canister_data
+        .slot_outcome_disputes
+        .contains_key(&dispute_key)
+    {
+        return Err(DisputeSlotOutcomeError::AlreadyDisputed);
+    }
+
+    let dispute = SlotOutcomeDispute {
+        post_id,
+        slot_id,
+        disputed_by: *disputed_by,
+        reason,
+        disputed_at: *current_time,
+    };
+    canister_data
+        .slot_outcome_disputes
+        .insert(dispute_key, dispute.clone());
+
+    Ok(dispute)
+}
+
+#[cfg(test)]
+mod test {
+    use shared_utils::canister_specific::individual_user_template::types::{
+        hot_or_not::{BetDirection, HotOrNotPayoutMode},
+        post::{Post, PostDetailsFromFrontend},
+        token::TokenBalance,
+    };
+    use test_utils::setup::test_constants::{
+        get_mock_user_alice_canister_id, get_mock_user_bob_canister_id,
+        get_mock_user_bob_principal_id, get_mock_user_charlie_principal_id,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_dispute_slot_outcome_impl() {
+        let mut canister_data = CanisterData::default();
+        let created_at = SystemTime::now();
+        let mut post = Post::new(
+            0,
+            &PostDetailsFromFrontend {
+                description: "Doggos and puppers".into(),
+                hashtags: vec!["doggo".into(), "pupper".into()],
+                video_uid: "abcd#1234".into(),
+                creator_consent_for_inclusion_in_hot_or_not: true,
+                collaborator_shares: None,
+                title: None,
+                category: None,
+            },
+            &created_at,
+        );
+        post.place_hot_or_not_bet(
+            &get_mock_user_bob_principal_id(),
+            &get_mock_user_bob_canister_id(),
+            100,
+            &BetDirection::Hot,
+            &created_at,
+        )
+        .unwrap();
+        let settled_at = post.get_slot_settled_at(1);
+        post.tabulate_hot_or_not_outcome_for_slot(
+            &get_mock_user_alice_canister_id(),
+            &1,
+            &mut TokenBalance::default(),
+            0,
+            0,
+            HotOrNotPayoutMode::Multiplier,
+            &settled_at,
+        );
+        canister_data.all_created_posts.insert(0, post);
+
+        let bob = get_mock_user_bob_principal_id();
+        assert_eq!(
+            dispute_slot_outcome_impl(
+                &mut canister_data,
+                &Principal::anonymous(),
+                0,
+                1,
+                "Wrong side won".into(),
+                &settled_at,
+            ),
+            Err(DisputeSlotOutcomeError::UserNotLoggedIn)
+        );
+        assert_eq!(
+            dispute_slot_outcome_impl(&mut canister_data, &bob, 0, 1, "  ".into(), &settled_at),
+            Err(DisputeSlotOutcomeError::InvalidReason)
+        );
+        assert_eq!(
+            dispute_slot_outcome_impl(
+                &mut canister_data,
+                &bob,
+                0,
+                1,
+                "a".repeat(MAX_SLOT_OUTCOME_DISPUTE_REASON_LENGTH + 1),
+                &settled_at,
+            ),
+            Err(DisputeSlotOutcomeError::InvalidReason)
+        );
+        assert_eq!(
+            dispute_slot_outcome_impl(
+                &mut canister_data,
+                &bob,
+                1,
+                1,
+                "Wrong side won".into(),
+                &settled_at,
+            ),
+            Err(DisputeSlotOutcomeError::PostNotFound)
+        );
+        assert_eq!(
+            dispute_slot_outcome_impl(
+                &mut canister_data,
+                &get_mock_user_charlie_principal_id(),
+                0,
+                1,
+                "Wrong side won".into(),
+                &settled_at,
+            ),
+            Err(DisputeSlotOutcomeError::DidNotBetInSlot)
+        );
+        assert!(canister_data.slot_outcome_disputes.is_empty());
+
+        assert_eq!(
+            dispute_slot_outcome_impl(
+                &mut canister_data,
+                &bob,
+                0,
+                1,
+                " Wrong side won ".into(),
+                &settled_at,
+            ),
+            Ok(SlotOutcomeDispute {
+                post_id: 0,
+                slot_id: 1,
+                disputed_by: bob,
+                reason: "Wrong side won".into(),
+                disputed_at: settled_at,
+            })
+        );
+        assert_eq!(canister_data.slot_outcome_disputes.len(), 1);
+
+        assert_eq!(
+            dispute_slot_outcome_impl(
+                &mut canister_data,
+                &bob,
+                0,
+                1,
+                "Still wrong".into(),
+                &settled_at,
+            ),
+            Err(DisputeSlotOutcomeError::AlreadyDisputed)
+        );
+    }
+}
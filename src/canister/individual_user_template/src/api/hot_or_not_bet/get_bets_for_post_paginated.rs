@@ -0,0 +1,234 @@
+use candid::Principal;
+use shared_utils::{
+    canister_specific::individual_user_template::types::{
+        error::GetBetsForPostError, hot_or_not::PostBetHistoryEntry,
+    },
+    common::{
+        types::{app_primitive_type::PostId, known_principal::KnownPrincipalType},
+        utils::pseudonym::pseudonymize_principal,
+    },
+    pagination::{self, Page},
+};
+
+use crate::{data_model::CanisterData, CANISTER_DATA};
+
+/// Bets on the post a page at a time, in the order of `HotOrNotDetails::get_bet_history`.
+/// Bet makers who hide their betting activity show up under their pseudonyms.
+///
+/// # Access Control
+/// Only the user whose profile details are stored in this canister and the global super
+/// admin can list the bets on a post
+#[ic_cdk::query]
+#[candid::candid_method(query)]
+fn get_bets_for_post_paginated(
+    post_id: PostId,
+    from_inclusive_index: u64,
+    limit: u64,
+) -> Result<Page<PostBetHistoryEntry>, GetBetsForPostError> {
+    let api_caller = ic_cdk::caller();
+
+    CANISTER_DATA.with(|canister_data_ref_cell| {
+        get_bets_for_post_paginated_impl(
+            &canister_data_ref_cell.borrow(),
+            &api_caller,
+            post_id,
+            from_inclusive_index,
+            limit,
+        )
+    })
+}
+
+fn get_bets_for_post_paginated_impl(
+    canister_data: &CanisterData,
+    api_caller: &Principal,
+    post_id: PostId,
+    from_inclusive_index: u64,
+    limit: u64,
+) -> Result<Page<PostBetHistoryEntry>, GetBetsForPostError> {
+    let is_profile_owner = canister_data.profile.principal_id == Some(*api_caller);
+    let is_super_admin = canister_data
+        .known_principal_ids
+        .get(&KnownPrincipalType::UserIdGlobalSuperAdmin)
+        == Some(api_caller);
+    if !is_profile_owner && !is_super_admin {
+        return Err(GetBetsForPostError::Unauthorized);
+    }
+
+    let hot_or_not_details = canister_data
+        .all_created_posts
+        .get_with_archived_bets(&post_id)
+        .ok_or(GetBetsForPostError::PostNotFound)?
+        .hot_or_not_details
+        .ok_or(GetBetsForPostError::PostNotPartOfHotOrNot)?;
+
+    let mut page = pagination::get_page(
+        hot_or_not_details.get_bet_history().into_iter(),
+        from_inclusive_index,
+        limit,
+    );
+
+    if canister_data.bet_makers_hiding_betting_activity.is_empty() {
+        return Ok(page);
+    }
+
+    if canister_data.pseudonym_salt.is_empty() {
+        return Err(GetBetsForPostError::PseudonymSaltNotGenerated);
+    }
+
+    page.items.iter_mut().for_each(|entry| {
+        if canister_data
+            .bet_makers_hiding_betting_activity
+            .contains(&entry.bet_maker)
+        {
+            entry.bet_maker =
+                pseudonymize_principal(&entry.bet_maker, &canister_data.pseudonym_salt);
+        }
+    });
+
+    Ok(page)
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::{Duration, SystemTime};
+
+    use shared_utils::canister_specific::individual_user_template::types::{
+        hot_or_not::BetDirection,
+        post::{Post, PostDetailsFromFrontend},
+    };
+    use test_utils::setup::test_constants::{
+        get_global_super_admin_principal_id, get_mock_user_alice_principal_id,
+        get_mock_user_bob_canister_id, get_mock_user_bob_principal_id,
+        get_mock_user_charlie_canister_id, get_mock_user_charlie_principal_id,
+        get_mock_user_dan_canister_id, get_mock_user_dan_principal_id,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_get_bets_for_post_paginated_impl() {
+        let mut canister_data = CanisterData::default();
+        canister_data.profile.principal_id = Some(get_mock_user_alice_principal_id());
+        canister_data.known_principal_ids.insert(
+            KnownPrincipalType::UserIdGlobalSuperAdmin,
+            get_global_super_admin_principal_id(),
+        );
+        let created_at = SystemTime::now();
+        let mut post = Post::new(
+            0,
+            &PostDetailsFromFrontend {
+                description: "Doggos and puppers".into(),
+                hashtags: vec!["doggo".into(), "pupper".into()],
+                video_uid: "abcd#1234".into(),
+                creator_consent_for_inclusion_in_hot_or_not: true,
+                collaborator_shares: None,
+                title: None,
+                category: None,
+            },
+            &created_at,
+        );
+        let bob = get_mock_user_bob_principal_id();
+        post.place_hot_or_not_bet(
+            &bob,
+            &get_mock_user_bob_canister_id(),
+            100,
+            &BetDirection::Hot,
+            &created_at,
+        )
+        .unwrap();
+        post.place_hot_or_not_bet(
+            &get_mock_user_charlie_principal_id(),
+            &get_mock_user_charlie_canister_id(),
+            50,
+            &BetDirection::Not,
+            &created_at,
+        )
+        .unwrap();
+        // * in the second slot
+        post.place_hot_or_not_bet(
+            &get_mock_user_dan_principal_id(),
+            &get_mock_user_dan_canister_id(),
+            10,
+            &BetDirection::Hot,
+            &(created_at + Duration::from_secs(60 * 60 + 1)),
+        )
+        .unwrap();
+        canister_data.all_created_posts.insert(0, post);
+
+        assert_eq!(
+            get_bets_for_post_paginated_impl(&canister_data, &bob, 0, 0, 10).err(),
+            Some(GetBetsForPostError::Unauthorized)
+        );
+        assert_eq!(
+            get_bets_for_post_paginated_impl(
+                &canister_data,
+                &get_mock_user_alice_principal_id(),
+                1,
+                0,
+                10
+            )
+            .err(),
+            Some(GetBetsForPostError::PostNotFound)
+        );
+
+        let first_page = get_bets_for_post_paginated_impl(
+            &canister_data,
+            &get_global_super_admin_principal_id(),
+            0,
+            0,
+            2,
+        )
+        .unwrap();
+        assert_eq!(first_page.total_count, 3);
+        assert_eq!(first_page.next_from_inclusive_index, Some(2));
+        assert!(first_page
+            .items
+            .iter()
+            .all(|entry| entry.slot_id == 1 && entry.room_id == Some(1)));
+
+        let last_page = get_bets_for_post_paginated_impl(
+            &canister_data,
+            &get_mock_user_alice_principal_id(),
+            0,
+            2,
+            2,
+        )
+        .unwrap();
+        assert_eq!(last_page.next_from_inclusive_index, None);
+        assert_eq!(last_page.items.len(), 1);
+        assert_eq!(last_page.items[0].slot_id, 2);
+        assert_eq!(
+            last_page.items[0].bet_maker,
+            get_mock_user_dan_principal_id()
+        );
+
+        canister_data.record_betting_activity_privacy(&bob, Some(true));
+        assert_eq!(
+            get_bets_for_post_paginated_impl(
+                &canister_data,
+                &get_mock_user_alice_principal_id(),
+                0,
+                0,
+                10
+            )
+            .err(),
+            Some(GetBetsForPostError::PseudonymSaltNotGenerated)
+        );
+
+        canister_data.pseudonym_salt = vec![7; 32];
+        let bet_makers: Vec<Principal> = get_bets_for_post_paginated_impl(
+            &canister_data,
+            &get_mock_user_alice_principal_id(),
+            0,
+            0,
+            10,
+        )
+        .unwrap()
+        .items
+        .into_iter()
+        .map(|entry| entry.bet_maker)
+        .collect();
+        assert!(bet_makers.contains(&pseudonymize_principal(&bob, &[7; 32])));
+        assert!(!bet_makers.contains(&bob));
+    }
+}
@@ -0,0 +1,179 @@
+use candid::Principal;
+use shared_utils::{
+    canister_specific::individual_user_template::types::{
+        error::GetBetTraceError, hot_or_not::bet_trace::BettorSideBetTrace,
+    },
+    common::{
+        types::{app_primitive_type::PostId, known_principal::KnownPrincipalType},
+        utils::known_caller::authorize_known_caller,
+    },
+};
+
+use crate::{data_model::CanisterData, CANISTER_DATA};
+
+/// Where a bet this profile placed stands: the bet as recorded here, the stake, refund,
+/// winnings and top up it moved, and the referrer's share still queued up to be sent for it
+///
+/// # Access Control
+/// Only the global super admin and the user index, which stitches this together with the
+/// post's side of the bet, can trace bets
+#[ic_cdk::query]
+#[candid::candid_method(query)]
+fn get_bettor_side_bet_trace(
+    post_canister_id: Principal,
+    post_id: PostId,
+) -> Result<BettorSideBetTrace, GetBetTraceError> {
+    let api_caller = ic_cdk::caller();
+
+    CANISTER_DATA.with(|canister_data_ref_cell| {
+        get_bettor_side_bet_trace_impl(
+            &canister_data_ref_cell.borrow(),
+            &api_caller,
+            &post_canister_id,
+            post_id,
+        )
+    })
+}
+
+fn get_bettor_side_bet_trace_impl(
+    canister_data: &CanisterData,
+    api_caller: &Principal,
+    post_canister_id: &Principal,
+    post_id: PostId,
+) -> Result<BettorSideBetTrace, GetBetTraceError> {
+    authorize_known_caller(
+        api_caller,
+        &canister_data.known_principal_ids,
+        &[
+            KnownPrincipalType::UserIdGlobalSuperAdmin,
+            KnownPrincipalType::CanisterIdUserIndex,
+        ],
+    )
+    .map_err(|_| GetBetTraceError::Unauthorized)?;
+
+    let placed_bet_detail = canister_data
+        .all_hot_or_not_bets_placed
+        .get(&(*post_canister_id, post_id))
+        .cloned();
+
+    Ok(BettorSideBetTrace {
+        token_events: canister_data
+            .token_transaction_history
+            .get_token_events_for_hot_or_not_bet(
+                post_canister_id,
+                post_id,
+                placed_bet_detail.as_ref().map(|placed_bet_detail| {
+                    (placed_bet_detail.slot_id, placed_bet_detail.room_id)
+                }),
+            ),
+        pending_referrer_share_settlements: canister_data
+            .pending_referrer_share_settlements
+            .iter()
+            .map(|(_, settlement)| settlement)
+            .filter(|settlement| {
+                settlement.post_canister_id == *post_canister_id && settlement.post_id == post_id
+            })
+            .collect(),
+        has_received_pot_insurance_top_up: canister_data
+            .received_pot_insurance_top_ups
+            .contains(&(*post_canister_id, post_id)),
+        placed_bet_detail,
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::SystemTime;
+
+    use shared_utils::canister_specific::individual_user_template::types::{
+        hot_or_not::{BetDirection, BetOutcomeForBetMaker, PlacedBetDetail},
+        referral::ReferrerShareSettlement,
+    };
+    use test_utils::setup::{
+        spoofed_callers::{assert_only_allowed_callers_are_accepted, get_mock_known_principal_map},
+        test_constants::{
+            get_global_super_admin_principal_id, get_mock_user_alice_canister_id,
+            get_mock_user_bob_principal_id, get_mock_user_charlie_canister_id,
+        },
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_get_bettor_side_bet_trace_impl() {
+        let mut canister_data = CanisterData {
+            known_principal_ids: get_mock_known_principal_map(),
+            ..Default::default()
+        };
+        let alice_canister_id = get_mock_user_alice_canister_id();
+        canister_data.all_hot_or_not_bets_placed.insert(
+            (alice_canister_id, 0),
+            PlacedBetDetail {
+                canister_id: alice_canister_id,
+                post_id: 0,
+                slot_id: 1,
+                room_id: 1,
+                amount_bet: 100,
+                bet_direction: BetDirection::Hot,
+                bet_placed_at: SystemTime::UNIX_EPOCH,
+                outcome_received: BetOutcomeForBetMaker::Won(180),
+            },
+        );
+        let referrer_share_settlement = ReferrerShareSettlement {
+            referrer_canister_id: get_mock_user_charlie_canister_id(),
+            referee_principal_id: get_mock_user_bob_principal_id(),
+            referee_canister_id: None,
+            post_canister_id: alice_canister_id,
+            post_id: 0,
+            slot_id: 1,
+            room_id: 1,
+            share_amount: 2,
+            number_of_failed_attempts: 0,
+        };
+        canister_data
+            .pending_referrer_share_settlements
+            .push_back(referrer_share_settlement.clone());
+        canister_data
+            .pending_referrer_share_settlements
+            .push_back(ReferrerShareSettlement {
+                post_id: 1,
+                ..referrer_share_settlement.clone()
+            });
+        canister_data
+            .received_pot_insurance_top_ups
+            .insert((alice_canister_id, 0));
+
+        assert_only_allowed_callers_are_accepted(
+            &canister_data.known_principal_ids.clone(),
+            &[
+                KnownPrincipalType::UserIdGlobalSuperAdmin,
+                KnownPrincipalType::CanisterIdUserIndex,
+            ],
+            |caller| {
+                get_bettor_side_bet_trace_impl(&canister_data, caller, &alice_canister_id, 0)
+                    .is_ok()
+            },
+        );
+
+        let admin = get_global_super_admin_principal_id();
+        let bet_trace =
+            get_bettor_side_bet_trace_impl(&canister_data, &admin, &alice_canister_id, 0).unwrap();
+        assert_eq!(
+            bet_trace
+                .placed_bet_detail
+                .map(|placed_bet_detail| placed_bet_detail.outcome_received),
+            Some(BetOutcomeForBetMaker::Won(180))
+        );
+        assert_eq!(
+            bet_trace.pending_referrer_share_settlements,
+            vec![referrer_share_settlement]
+        );
+        assert!(bet_trace.has_received_pot_insurance_top_up);
+
+        let bet_trace =
+            get_bettor_side_bet_trace_impl(&canister_data, &admin, &alice_canister_id, 2).unwrap();
+        assert!(bet_trace.placed_bet_detail.is_none());
+        assert!(bet_trace.pending_referrer_share_settlements.is_empty());
+        assert!(!bet_trace.has_received_pot_insurance_top_up);
+    }
+}
@@ -3,7 +3,10 @@ use std::time::SystemTime;
 use candid::Principal;
 use shared_utils::{
     canister_specific::individual_user_template::types::hot_or_not::BettingStatus,
-    common::utils::system_time::{self},
+    common::{
+        types::response_envelope::ResponseEnvelope,
+        utils::system_time::{IcTimeProvider, TimeProvider},
+    },
 };
 
 use crate::{data_model::CanisterData, CANISTER_DATA};
@@ -16,13 +19,21 @@ fn get_hot_or_not_bet_details_for_this_post(post_id: u64) -> BettingStatus {
     CANISTER_DATA.with(|canister_data_ref_cell| {
         get_hot_or_not_bet_details_for_this_post_impl(
             &canister_data_ref_cell.borrow(),
-            &system_time::get_current_system_time_from_ic(),
+            &IcTimeProvider.get_current_time(),
             &request_maker,
             post_id,
         )
     })
 }
 
+/// Same as `get_hot_or_not_bet_details_for_this_post`, but clients that predate a betting
+/// status get `null` in place of it rather than a decoding error
+#[ic_cdk::query]
+#[candid::candid_method(query)]
+fn get_hot_or_not_bet_details_for_this_post_v2(post_id: u64) -> ResponseEnvelope<BettingStatus> {
+    get_hot_or_not_bet_details_for_this_post(post_id).into()
+}
+
 fn get_hot_or_not_bet_details_for_this_post_impl(
     canister_data: &CanisterData,
     current_time: &SystemTime,
@@ -39,7 +50,7 @@ fn get_hot_or_not_bet_details_for_this_post_impl(
 #[cfg(test)]
 mod test {
     use std::{
-        collections::HashSet,
+        collections::{BTreeMap, HashSet},
         time::{Duration, SystemTime},
     };
 
@@ -70,6 +81,14 @@ mod test {
                 home_feed_score: FeedScore::default(),
                 creator_consent_for_inclusion_in_hot_or_not: true,
                 hot_or_not_details: Some(HotOrNotDetails::default()),
+                minimum_bet_amount: 0,
+                collaborator_shares: vec![],
+                title: None,
+                category: None,
+                boost: None,
+                unresolved_reports: BTreeMap::new(),
+                publish_at: None,
+                is_betting_paused_by_moderator: false,
             },
         );
 
@@ -0,0 +1,15 @@
+use shared_utils::canister_specific::individual_user_template::types::live_betting_activity::LiveBettingActivityStreamStatus;
+
+use crate::CANISTER_DATA;
+
+/// How far publishing betting activity to the web socket gateway is behind
+#[ic_cdk::query]
+#[candid::candid_method(query)]
+fn get_live_betting_activity_stream_status() -> LiveBettingActivityStreamStatus {
+    CANISTER_DATA.with(|canister_data_ref_cell| {
+        canister_data_ref_cell
+            .borrow()
+            .live_betting_activity_stream
+            .get_status()
+    })
+}
@@ -0,0 +1,11 @@
+use shared_utils::canister_specific::individual_user_template::types::hot_or_not::betting_stats::BettingStatsSummary;
+
+use crate::CANISTER_DATA;
+
+/// Totals over the bets placed by this profile, kept as bets are placed and settled
+#[ic_cdk::query]
+#[candid::candid_method(query)]
+fn get_my_betting_stats() -> BettingStatsSummary {
+    CANISTER_DATA
+        .with(|canister_data_ref_cell| canister_data_ref_cell.borrow().betting_stats.get_summary())
+}
@@ -0,0 +1,22 @@
+use shared_utils::canister_specific::individual_user_template::types::hot_or_not::PostBetAnalytics;
+
+use crate::CANISTER_DATA;
+
+/// Bet counts and the spread of bet amounts on a post, read from totals kept as bets come in.
+/// None if the post doesn't exist.
+#[ic_cdk::query]
+#[candid::candid_method(query)]
+fn get_post_bet_analytics(post_id: u64) -> Option<PostBetAnalytics> {
+    CANISTER_DATA.with(|canister_data_ref_cell| {
+        let post = canister_data_ref_cell
+            .borrow()
+            .all_created_posts
+            .get(&post_id)?;
+
+        Some(
+            post.hot_or_not_details
+                .unwrap_or_default()
+                .get_bet_analytics(),
+        )
+    })
+}
@@ -0,0 +1,282 @@
+use candid::Principal;
+use shared_utils::{
+    canister_specific::individual_user_template::types::{
+        error::GetBetTraceError, hot_or_not::bet_trace::PostSideBetTrace,
+    },
+    common::{
+        types::{app_primitive_type::PostId, known_principal::KnownPrincipalType},
+        utils::known_caller::authorize_known_caller,
+    },
+};
+
+use crate::{data_model::CanisterData, CANISTER_DATA};
+
+/// Where a bet placed on a post of this profile stands: the bet and its room, the commission
+/// and rake taken from the room, and whatever is still queued up to be sent for it
+///
+/// # Access Control
+/// Only the global super admin and the user index, which stitches this together with the
+/// bettor's side of the bet, can trace bets
+#[ic_cdk::query]
+#[candid::candid_method(query)]
+fn get_post_side_bet_trace(
+    post_id: PostId,
+    bet_maker_principal_id: Principal,
+) -> Result<PostSideBetTrace, GetBetTraceError> {
+    let api_caller = ic_cdk::caller();
+
+    CANISTER_DATA.with(|canister_data_ref_cell| {
+        get_post_side_bet_trace_impl(
+            &canister_data_ref_cell.borrow(),
+            &api_caller,
+            &ic_cdk::id(),
+            post_id,
+            &bet_maker_principal_id,
+        )
+    })
+}
+
+fn get_post_side_bet_trace_impl(
+    canister_data: &CanisterData,
+    api_caller: &Principal,
+    this_canister_id: &Principal,
+    post_id: PostId,
+    bet_maker_principal_id: &Principal,
+) -> Result<PostSideBetTrace, GetBetTraceError> {
+    authorize_known_caller(
+        api_caller,
+        &canister_data.known_principal_ids,
+        &[
+            KnownPrincipalType::UserIdGlobalSuperAdmin,
+            KnownPrincipalType::CanisterIdUserIndex,
+        ],
+    )
+    .map_err(|_| GetBetTraceError::Unauthorized)?;
+
+    let post = canister_data
+        .all_created_posts
+        .get_with_archived_bets(&post_id)
+        .ok_or(GetBetTraceError::PostNotFound)?;
+    let bet = post.get_traced_bet(bet_maker_principal_id);
+
+    let Some(bet) = bet else {
+        return Ok(PostSideBetTrace {
+            bet: None,
+            token_events: vec![],
+            pending_outcome_notification: None,
+            pending_pot_insurance_claim_top_up: None,
+            pending_pot_insurance_top_up: None,
+            pending_referrer_share_settlement: None,
+        });
+    };
+    let bet_maker_canister_id = bet.bet_details.bet_maker_canister_id;
+    let is_bet_in_room = |slot_id, room_id| bet.slot_id == slot_id && bet.room_id == Some(room_id);
+
+    Ok(PostSideBetTrace {
+        token_events: canister_data
+            .token_transaction_history
+            .get_token_events_for_hot_or_not_bet(
+                this_canister_id,
+                post_id,
+                bet.room_id.map(|room_id| (bet.slot_id, room_id)),
+            ),
+        pending_outcome_notification: canister_data
+            .bet_outcome_notification_scheduler
+            .get_pending_notification(post_id, &bet_maker_canister_id)
+            .cloned(),
+        pending_pot_insurance_claim_top_up: canister_data
+            .pending_pot_insurance_claims
+            .iter()
+            .filter(|claim| {
+                claim.post_id == post_id && is_bet_in_room(claim.slot_id, claim.room_id)
+            })
+            .flat_map(|claim| claim.top_ups.iter())
+            .find(|top_up| top_up.bet_maker_canister_id == bet_maker_canister_id)
+            .cloned(),
+        pending_pot_insurance_top_up: canister_data
+            .pending_pot_insurance_top_ups
+            .iter()
+            .map(|(_, settlement)| settlement)
+            .find(|settlement| {
+                settlement.post_id == post_id
+                    && settlement.bet_maker_canister_id == bet_maker_canister_id
+                    && is_bet_in_room(settlement.slot_id, settlement.room_id)
+            }),
+        pending_referrer_share_settlement: canister_data
+            .pending_referrer_share_settlements
+            .iter()
+            .map(|(_, settlement)| settlement)
+            .find(|settlement| {
+                settlement.post_id == post_id
+                    && settlement.referee_canister_id == Some(bet_maker_canister_id)
+                    && is_bet_in_room(settlement.slot_id, settlement.room_id)
+            }),
+        bet: Some(bet),
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::SystemTime;
+
+    use shared_utils::canister_specific::individual_user_template::types::{
+        hot_or_not::{
+            outcome_notification::BetOutcomeNotification, BetDirection, BetOutcomeForBetMaker,
+        },
+        post::{Post, PostDetailsFromFrontend},
+        referral::ReferrerShareSettlement,
+        treasury::{PotInsuranceClaim, PotInsuranceTopUp},
+    };
+    use test_utils::setup::{
+        spoofed_callers::{assert_only_allowed_callers_are_accepted, get_mock_known_principal_map},
+        test_constants::{
+            get_global_super_admin_principal_id, get_mock_user_alice_canister_id,
+            get_mock_user_bob_canister_id, get_mock_user_bob_principal_id,
+            get_mock_user_charlie_canister_id, get_mock_user_charlie_principal_id,
+        },
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_get_post_side_bet_trace_impl() {
+        let mut canister_data = CanisterData {
+            known_principal_ids: get_mock_known_principal_map(),
+            ..Default::default()
+        };
+        let alice_canister_id = get_mock_user_alice_canister_id();
+        let bob_canister_id = get_mock_user_bob_canister_id();
+        let current_time = SystemTime::now();
+
+        let mut post = Post::new(
+            0,
+            &PostDetailsFromFrontend {
+                description: "Singing and dancing".to_string(),
+                hashtags: vec!["sing".to_string(), "dance".to_string()],
+                video_uid: "video#0001".to_string(),
+                creator_consent_for_inclusion_in_hot_or_not: true,
+                collaborator_shares: None,
+                title: None,
+                category: None,
+            },
+            &current_time,
+        );
+        post.place_hot_or_not_bet(
+            &get_mock_user_bob_principal_id(),
+            &bob_canister_id,
+            100,
+            &BetDirection::Hot,
+            &current_time,
+        )
+        .unwrap();
+        canister_data.all_created_posts.insert(0, post);
+        canister_data
+            .bet_outcome_notification_scheduler
+            .enqueue(BetOutcomeNotification {
+                bet_maker_canister_id: bob_canister_id,
+                post_id: 0,
+                bet_outcome: BetOutcomeForBetMaker::Won(180),
+            });
+        canister_data
+            .pending_pot_insurance_claims
+            .push_back(PotInsuranceClaim {
+                post_canister_id: alice_canister_id,
+                post_id: 0,
+                slot_id: 1,
+                room_id: 1,
+                room_pot_total_amount: 100,
+                top_ups: vec![PotInsuranceTopUp {
+                    bet_maker_canister_id: bob_canister_id,
+                    amount: 20,
+                }],
+                number_of_failed_attempts: 0,
+                tabulated_at: None,
+            });
+        let referrer_share_settlement = ReferrerShareSettlement {
+            referrer_canister_id: get_mock_user_charlie_canister_id(),
+            referee_principal_id: get_mock_user_bob_principal_id(),
+            referee_canister_id: Some(bob_canister_id),
+            post_canister_id: alice_canister_id,
+            post_id: 0,
+            slot_id: 1,
+            room_id: 1,
+            share_amount: 2,
+            number_of_failed_attempts: 0,
+        };
+        canister_data
+            .pending_referrer_share_settlements
+            .push_back(referrer_share_settlement.clone());
+
+        assert_only_allowed_callers_are_accepted(
+            &canister_data.known_principal_ids.clone(),
+            &[
+                KnownPrincipalType::UserIdGlobalSuperAdmin,
+                KnownPrincipalType::CanisterIdUserIndex,
+            ],
+            |caller| {
+                get_post_side_bet_trace_impl(
+                    &canister_data,
+                    caller,
+                    &alice_canister_id,
+                    0,
+                    &get_mock_user_bob_principal_id(),
+                )
+                .is_ok()
+            },
+        );
+
+        let admin = get_global_super_admin_principal_id();
+        assert_eq!(
+            get_post_side_bet_trace_impl(
+                &canister_data,
+                &admin,
+                &alice_canister_id,
+                1,
+                &get_mock_user_bob_principal_id(),
+            )
+            .err(),
+            Some(GetBetTraceError::PostNotFound)
+        );
+
+        let bet_trace = get_post_side_bet_trace_impl(
+            &canister_data,
+            &admin,
+            &alice_canister_id,
+            0,
+            &get_mock_user_bob_principal_id(),
+        )
+        .unwrap();
+        let bet = bet_trace.bet.unwrap();
+        assert_eq!((bet.slot_id, bet.room_id), (1, Some(1)));
+        assert_eq!(bet.bet_details.amount, 100);
+        assert_eq!(
+            bet_trace
+                .pending_outcome_notification
+                .map(|notification| notification.bet_outcome),
+            Some(BetOutcomeForBetMaker::Won(180))
+        );
+        assert_eq!(
+            bet_trace.pending_pot_insurance_claim_top_up,
+            Some(PotInsuranceTopUp {
+                bet_maker_canister_id: bob_canister_id,
+                amount: 20,
+            })
+        );
+        assert_eq!(bet_trace.pending_pot_insurance_top_up, None);
+        assert_eq!(
+            bet_trace.pending_referrer_share_settlement,
+            Some(referrer_share_settlement)
+        );
+
+        let bet_trace = get_post_side_bet_trace_impl(
+            &canister_data,
+            &admin,
+            &alice_canister_id,
+            0,
+            &get_mock_user_charlie_principal_id(),
+        )
+        .unwrap();
+        assert!(bet_trace.bet.is_none());
+        assert!(bet_trace.pending_outcome_notification.is_none());
+    }
+}
@@ -0,0 +1,94 @@
+use candid::Principal;
+use shared_utils::canister_specific::individual_user_template::types::hot_or_not::UserStatusForSpecificHotOrNotPost;
+
+use crate::{data_model::CanisterData, CANISTER_DATA};
+
+/// Everything the UI needs to render a bettor's position on a post of this profile in one
+/// call: whether they bet, on what, in which slot and room, and how it turned out.
+#[ic_cdk::query]
+#[candid::candid_method(query)]
+fn get_user_status_for_post(
+    post_id: u64,
+    bet_maker_principal_id: Principal,
+) -> UserStatusForSpecificHotOrNotPost {
+    CANISTER_DATA.with(|canister_data_ref_cell| {
+        get_user_status_for_post_impl(
+            &canister_data_ref_cell.borrow(),
+            post_id,
+            &bet_maker_principal_id,
+        )
+    })
+}
+
+fn get_user_status_for_post_impl(
+    canister_data: &CanisterData,
+    post_id: u64,
+    bet_maker_principal_id: &Principal,
+) -> UserStatusForSpecificHotOrNotPost {
+    canister_data
+        .all_created_posts
+        .get_with_archived_bets(&post_id)
+        .map(|post| post.get_user_status_for_this_post(bet_maker_principal_id))
+        .unwrap_or(UserStatusForSpecificHotOrNotPost::NotParticipatedYet)
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::SystemTime;
+
+    use shared_utils::canister_specific::individual_user_template::types::{
+        hot_or_not::{BetDetail, BetDirection},
+        post::{Post, PostDetailsFromFrontend},
+    };
+    use test_utils::setup::test_constants::{
+        get_mock_user_alice_canister_id, get_mock_user_alice_principal_id,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_get_user_status_for_post_impl() {
+        let mut canister_data = CanisterData::default();
+        let current_time = SystemTime::now();
+        let mut post = Post::new(
+            0,
+            &PostDetailsFromFrontend {
+                description: "Singing and dancing".to_string(),
+                hashtags: vec!["sing".to_string(), "dance".to_string()],
+                video_uid: "video#0001".to_string(),
+                creator_consent_for_inclusion_in_hot_or_not: true,
+                collaborator_shares: None,
+                title: None,
+                category: None,
+            },
+            &current_time,
+        );
+        post.place_hot_or_not_bet(
+            &get_mock_user_alice_principal_id(),
+            &get_mock_user_alice_canister_id(),
+            100,
+            &BetDirection::Not,
+            &current_time,
+        )
+        .unwrap();
+        canister_data.all_created_posts.insert(0, post);
+
+        assert_eq!(
+            get_user_status_for_post_impl(&canister_data, 1, &get_mock_user_alice_principal_id()),
+            UserStatusForSpecificHotOrNotPost::NotParticipatedYet
+        );
+        assert_eq!(
+            get_user_status_for_post_impl(&canister_data, 0, &Principal::anonymous()),
+            UserStatusForSpecificHotOrNotPost::NotParticipatedYet
+        );
+        assert_eq!(
+            get_user_status_for_post_impl(&canister_data, 0, &get_mock_user_alice_principal_id()),
+            UserStatusForSpecificHotOrNotPost::AwaitingResult(BetDetail {
+                amount: 100,
+                bet_direction: BetDirection::Not,
+                slot_id: 1,
+                room_id: 1,
+            })
+        );
+    }
+}
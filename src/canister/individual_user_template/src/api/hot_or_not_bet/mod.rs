@@ -1,8 +1,36 @@
+pub mod backfill_aggregate_stats_from_slot_history;
 pub mod bet_on_currently_viewing_hot_or_not_post;
+pub mod bet_on_multiple_posts;
+pub mod can_i_bet;
+pub mod cancel_hot_or_not_bet;
+pub mod claim_pot_insurance_from_treasury;
+pub mod dispute_slot_outcome;
+pub mod get_bets_for_post_paginated;
+pub mod get_bettor_side_bet_trace;
 pub mod get_hot_or_not_bet_details_for_this_post;
 pub mod get_hot_or_not_bets_placed_by_this_profile_with_pagination;
 pub mod get_individual_hot_or_not_bet_placed_by_this_profile;
+pub mod get_live_betting_activity_stream_status;
+pub mod get_my_betting_stats;
+pub mod get_post_bet_analytics;
+pub mod get_post_side_bet_trace;
+pub mod get_user_status_for_post;
+pub mod recalculate_slot_outcome;
+pub mod receive_bet_cancellation_from_bet_makers_canister;
 pub mod receive_bet_from_bet_makers_canister;
 pub mod receive_bet_winnings_when_distributed;
+pub mod receive_collaborator_share_of_commission;
+pub mod receive_house_rake_from_hot_or_not_outcome;
+pub mod receive_pot_insurance_claim;
+pub mod receive_pot_insurance_top_up;
+pub mod receive_referrer_share_of_referee_bet_winnings;
+pub mod receive_shadow_banned_bet_from_bet_makers_canister;
 pub mod reenqueue_timers_for_pending_bet_outcomes;
+pub mod retabulate_slot_outcome;
+pub mod send_bet_outcome_notifications;
+pub mod settle_collaborator_shares_of_commission;
+pub mod settle_house_rake_with_treasury;
+pub mod settle_pot_insurance_top_ups;
+pub mod settle_referrer_share_of_bet_winnings;
 pub mod tabulate_hot_or_not_outcome_for_post_slot;
+pub mod validate_bet_on_post;
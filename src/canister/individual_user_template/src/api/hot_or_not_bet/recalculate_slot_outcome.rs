@@ -0,0 +1,175 @@
+use candid::Principal;
+use ic_cdk::api::management_canister::provisional::CanisterId;
+use shared_utils::{
+    canister_specific::individual_user_template::types::{
+        dispute::SlotOutcomeRecalculation, error::RecalculateSlotOutcomeError, hot_or_not::SlotId,
+    },
+    common::types::{app_primitive_type::PostId, known_principal::KnownPrincipalType},
+};
+
+use crate::{data_model::CanisterData, CANISTER_DATA};
+
+/// Re-runs tabulation for a settled slot from the bets recorded in its rooms and reports any
+/// rooms whose outcome or payouts differ from what was settled. This is a dry run: no tokens
+/// move and the post is left as is. Uses the house rake currently configured.
+///
+/// # Access Control
+/// Only the user index, on behalf of an admin reviewing a dispute
+#[ic_cdk::query]
+#[candid::candid_method(query)]
+fn recalculate_slot_outcome(
+    post_id: PostId,
+    slot_id: SlotId,
+) -> Result<SlotOutcomeRecalculation, RecalculateSlotOutcomeError> {
+    let api_caller = ic_cdk::caller();
+    let this_canister_id = ic_cdk::id();
+
+    CANISTER_DATA.with(|canister_data_ref_cell| {
+        recalculate_slot_outcome_impl(
+            &canister_data_ref_cell.borrow(),
+            &api_caller,
+            &this_canister_id,
+            post_id,
+            slot_id,
+        )
+    })
+}
+
+fn recalculate_slot_outcome_impl(
+    canister_data: &CanisterData,
+    api_caller: &Principal,
+    this_canister_id: &CanisterId,
+    post_id: PostId,
+    slot_id: SlotId,
+) -> Result<SlotOutcomeRecalculation, RecalculateSlotOutcomeError> {
+    if canister_data
+        .known_principal_ids
+        .get(&KnownPrincipalType::CanisterIdUserIndex)
+        != Some(api_caller)
+    {
+        return Err(RecalculateSlotOutcomeError::Unauthorized);
+    }
+
+    canister_data
+        .all_created_posts
+        .get_with_archived_bets(&post_id)
+        .ok_or(RecalculateSlotOutcomeError::PostNotFound)?
+        .recalculate_slot_outcome(
+            this_canister_id,
+            slot_id,
+            canister_data.configuration.house_rake_percentage,
+            canister_data
+                .configuration
+                .referrer_share_of_bet_winnings_percentage,
+        )
+        .ok_or(RecalculateSlotOutcomeError::SlotNotSettledYet)
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::SystemTime;
+
+    use shared_utils::canister_specific::individual_user_template::types::{
+        hot_or_not::{BetDirection, HotOrNotPayoutMode},
+        post::{Post, PostDetailsFromFrontend},
+        token::TokenBalance,
+    };
+    use test_utils::setup::test_constants::{
+        get_mock_canister_id_user_index, get_mock_user_alice_canister_id,
+        get_mock_user_bob_canister_id, get_mock_user_bob_principal_id,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_recalculate_slot_outcome_impl() {
+        let mut canister_data = CanisterData::default();
+        canister_data.known_principal_ids.insert(
+            KnownPrincipalType::CanisterIdUserIndex,
+            get_mock_canister_id_user_index(),
+        );
+        let created_at = SystemTime::now();
+        let mut post = Post::new(
+            0,
+            &PostDetailsFromFrontend {
+                description: "Doggos and puppers".into(),
+                hashtags: vec!["doggo".into(), "pupper".into()],
+                video_uid: "abcd#1234".into(),
+                creator_consent_for_inclusion_in_hot_or_not: true,
+                collaborator_shares: None,
+                title: None,
+                category: None,
+            },
+            &created_at,
+        );
+        post.place_hot_or_not_bet(
+            &get_mock_user_bob_principal_id(),
+            &get_mock_user_bob_canister_id(),
+            100,
+            &BetDirection::Hot,
+            &created_at,
+        )
+        .unwrap();
+        canister_data.all_created_posts.insert(0, post);
+
+        assert_eq!(
+            recalculate_slot_outcome_impl(
+                &canister_data,
+                &get_mock_user_bob_principal_id(),
+                &get_mock_user_alice_canister_id(),
+                0,
+                1,
+            ),
+            Err(RecalculateSlotOutcomeError::Unauthorized)
+        );
+        assert_eq!(
+            recalculate_slot_outcome_impl(
+                &canister_data,
+                &get_mock_canister_id_user_index(),
+                &get_mock_user_alice_canister_id(),
+                1,
+                1,
+            ),
+            Err(RecalculateSlotOutcomeError::PostNotFound)
+        );
+        assert_eq!(
+            recalculate_slot_outcome_impl(
+                &canister_data,
+                &get_mock_canister_id_user_index(),
+                &get_mock_user_alice_canister_id(),
+                0,
+                1,
+            ),
+            Err(RecalculateSlotOutcomeError::SlotNotSettledYet)
+        );
+
+        let mut post = canister_data.all_created_posts.get(&0).unwrap();
+        let settled_at = post.get_slot_settled_at(1);
+        post.tabulate_hot_or_not_outcome_for_slot(
+            &get_mock_user_alice_canister_id(),
+            &1,
+            &mut TokenBalance::default(),
+            0,
+            0,
+            HotOrNotPayoutMode::Multiplier,
+            &settled_at,
+        );
+        canister_data.all_created_posts.insert(0, post);
+
+        assert_eq!(
+            recalculate_slot_outcome_impl(
+                &canister_data,
+                &get_mock_canister_id_user_index(),
+                &get_mock_user_alice_canister_id(),
+                0,
+                1,
+            ),
+            Ok(SlotOutcomeRecalculation {
+                post_id: 0,
+                slot_id: 1,
+                number_of_rooms_checked: 1,
+                discrepancies: vec![],
+            })
+        );
+    }
+}
@@ -0,0 +1,184 @@
+use std::time::SystemTime;
+
+use candid::Principal;
+use ic_cdk::api::management_canister::provisional::CanisterId;
+use shared_utils::{
+    canister_specific::individual_user_template::types::{
+        error::BetOnCurrentlyViewingPostError, hot_or_not::BetDirection,
+    },
+    common::{
+        types::app_primitive_type::PostId,
+        utils::system_time::{IcTimeProvider, TimeProvider},
+    },
+};
+
+use crate::{
+    api::post::update_scores_and_share_with_post_cache_if_difference_beyond_threshold::update_scores_and_share_with_post_cache_if_difference_beyond_threshold,
+    data_model::CanisterData,
+    util::{call_journal::record_call_in_journal, guards::reject_updates_in_read_only_mode},
+    CANISTER_DATA,
+};
+
+/// Takes a bet back out of its room on behalf of the bet maker's canister. Returns the amount
+/// of the stake the bet maker gets back.
+#[ic_cdk::update(guard = "reject_updates_in_read_only_mode")]
+#[candid::candid_method(update)]
+fn receive_bet_cancellation_from_bet_makers_canister(
+    post_id: PostId,
+    bet_maker_principal_id: Principal,
+) -> Result<u64, BetOnCurrentlyViewingPostError> {
+    record_call_in_journal("receive_bet_cancellation_from_bet_makers_canister");
+
+    let bet_maker_canister_id = ic_cdk::caller();
+
+    let refund_amount = CANISTER_DATA.with(|canister_data_ref_cell| {
+        receive_bet_cancellation_from_bet_makers_canister_impl(
+            &mut canister_data_ref_cell.borrow_mut(),
+            &bet_maker_principal_id,
+            &bet_maker_canister_id,
+            post_id,
+            &IcTimeProvider.get_current_time(),
+        )
+    })?;
+
+    update_scores_and_share_with_post_cache_if_difference_beyond_threshold(
+        &post_id,
+        &IcTimeProvider,
+    );
+
+    Ok(refund_amount)
+}
+
+fn receive_bet_cancellation_from_bet_makers_canister_impl(
+    canister_data: &mut CanisterData,
+    bet_maker_principal_id: &Principal,
+    bet_maker_canister_id: &CanisterId,
+    post_id: PostId,
+    current_time: &SystemTime,
+) -> Result<u64, BetOnCurrentlyViewingPostError> {
+    let mut post = canister_data
+        .all_created_posts
+        .get(&post_id)
+        .ok_or(BetOnCurrentlyViewingPostError::BetNotFound)?;
+
+    let (room_id, bet_details) =
+        post.cancel_hot_or_not_bet(bet_maker_principal_id, bet_maker_canister_id, current_time)?;
+    canister_data.all_created_posts.insert(post_id, post);
+
+    // * bets in the phantom room never counted towards stats or tournament points
+    if room_id.is_some() {
+        let profile_stats = &mut canister_data.profile.profile_stats;
+        match bet_details.bet_direction {
+            BetDirection::Hot => {
+                profile_stats.hot_bets_received = profile_stats.hot_bets_received.saturating_sub(1);
+            }
+            BetDirection::Not => {
+                profile_stats.not_bets_received = profile_stats.not_bets_received.saturating_sub(1);
+            }
+        }
+
+        if let Some(tournament_enrollment) = canister_data.tournament_enrollments.get_mut(&post_id)
+        {
+            if *current_time < tournament_enrollment.closes_at {
+                tournament_enrollment.points = tournament_enrollment
+                    .points
+                    .saturating_sub(bet_details.amount);
+            }
+        }
+    }
+
+    Ok(canister_data
+        .configuration
+        .bet_cancellation_config
+        .get_refund_amount(bet_details.amount))
+}
+
+#[cfg(test)]
+mod test {
+    use shared_utils::canister_specific::individual_user_template::types::{
+        hot_or_not::BetCancellationConfig,
+        post::{Post, PostDetailsFromFrontend},
+    };
+    use test_utils::setup::test_constants::{
+        get_mock_user_bob_canister_id, get_mock_user_bob_principal_id,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_receive_bet_cancellation_from_bet_makers_canister_impl() {
+        let mut canister_data = CanisterData::default();
+        let current_time = SystemTime::now();
+        canister_data.configuration.bet_cancellation_config = BetCancellationConfig {
+            refund_percentage: 80,
+        };
+        let mut post = Post::new(
+            0,
+            &PostDetailsFromFrontend {
+                description: "Doggos and puppers".into(),
+                hashtags: vec!["doggo".into(), "pupper".into()],
+                video_uid: "abcd#1234".into(),
+                creator_consent_for_inclusion_in_hot_or_not: true,
+                collaborator_shares: None,
+                title: None,
+                category: None,
+            },
+            &current_time,
+        );
+        post.place_hot_or_not_bet(
+            &get_mock_user_bob_principal_id(),
+            &get_mock_user_bob_canister_id(),
+            100,
+            &BetDirection::Not,
+            &current_time,
+        )
+        .unwrap();
+        canister_data.all_created_posts.insert(0, post);
+        canister_data.profile.profile_stats.not_bets_received = 1;
+
+        assert_eq!(
+            receive_bet_cancellation_from_bet_makers_canister_impl(
+                &mut canister_data,
+                &get_mock_user_bob_principal_id(),
+                &get_mock_user_bob_canister_id(),
+                1,
+                &current_time,
+            ),
+            Err(BetOnCurrentlyViewingPostError::BetNotFound)
+        );
+        assert_eq!(
+            receive_bet_cancellation_from_bet_makers_canister_impl(
+                &mut canister_data,
+                &get_mock_user_bob_principal_id(),
+                &get_mock_user_bob_canister_id(),
+                0,
+                &current_time,
+            ),
+            Ok(80)
+        );
+        assert_eq!(canister_data.profile.profile_stats.not_bets_received, 0);
+        assert_eq!(
+            canister_data
+                .all_created_posts
+                .get(&0)
+                .unwrap()
+                .hot_or_not_details
+                .unwrap()
+                .aggregate_stats
+                .total_amount_bet,
+            0
+        );
+
+        // * a bet can only be cancelled once
+        assert_eq!(
+            receive_bet_cancellation_from_bet_makers_canister_impl(
+                &mut canister_data,
+                &get_mock_user_bob_principal_id(),
+                &get_mock_user_bob_canister_id(),
+                0,
+                &current_time,
+            ),
+            Err(BetOnCurrentlyViewingPostError::BetNotFound)
+        );
+    }
+}
@@ -4,44 +4,122 @@ use candid::Principal;
 use ic_cdk::api::management_canister::provisional::CanisterId;
 use shared_utils::{
     canister_specific::individual_user_template::types::{
+        achievement::AchievementEvent,
         arg::PlaceBetArg,
         error::BetOnCurrentlyViewingPostError,
-        hot_or_not::{BetDirection, BettingStatus},
+        hot_or_not::{to_token_amount, BetDirection, BettingStatus},
+        live_betting_activity::LiveBettingActivityEvent,
+    },
+    common::{
+        types::{
+            app_primitive_type::PostId, known_principal::KnownPrincipalType,
+            top_posts::hot_or_not_bet_activity::HotOrNotBetActivityDelta,
+        },
+        utils::system_time::{IcTimeProvider, TimeProvider},
     },
-    common::utils::system_time,
 };
 
 use crate::{
-    api::post::update_scores_and_share_with_post_cache_if_difference_beyond_threshold::update_scores_and_share_with_post_cache_if_difference_beyond_threshold,
-    data_model::CanisterData, CANISTER_DATA,
+    api::{
+        hot_or_not_bet::tabulate_hot_or_not_outcome_for_post_slot::{
+            claim_slot_tabulation_timer_impl, enqueue_timer_for_slot_tabulation,
+        },
+        post::update_scores_and_share_with_post_cache_if_difference_beyond_threshold::update_scores_and_share_with_post_cache_if_difference_beyond_threshold,
+    },
+    data_model::CanisterData,
+    util::{
+        call_journal::record_call_in_journal,
+        call_queue::queue_call_impl,
+        canister_binding::is_expected_binding_epoch,
+        guards::reject_bets_and_posts_in_read_only_or_low_cycles_mode,
+        live_betting_activity::{
+            publish_pending_live_betting_activity, queue_live_betting_activity_impl,
+        },
+    },
+    CANISTER_DATA,
 };
 
-#[ic_cdk::update]
+#[ic_cdk::update(guard = "reject_bets_and_posts_in_read_only_or_low_cycles_mode")]
 #[candid::candid_method(update)]
 fn receive_bet_from_bet_makers_canister(
     place_bet_arg: PlaceBetArg,
     bet_maker_principal_id: Principal,
+    hide_betting_activity: Option<bool>,
+    expected_binding_epoch: Option<u64>,
+    bet_maker_referrer_canister_id: Option<CanisterId>,
 ) -> Result<BettingStatus, BetOnCurrentlyViewingPostError> {
+    record_call_in_journal("receive_bet_from_bet_makers_canister");
+
     let bet_maker_canister_id = ic_cdk::caller();
 
+    if !CANISTER_DATA.with(|canister_data_ref_cell| {
+        is_expected_binding_epoch(&canister_data_ref_cell.borrow(), expected_binding_epoch)
+    }) {
+        return Err(BetOnCurrentlyViewingPostError::StaleCanisterBinding);
+    }
+
+    let current_time = IcTimeProvider.get_current_time();
+
     let status = CANISTER_DATA.with(|canister_data_ref_cell| {
         receive_bet_from_bet_makers_canister_impl(
             &mut canister_data_ref_cell.borrow_mut(),
             &bet_maker_principal_id,
             &bet_maker_canister_id,
+            bet_maker_referrer_canister_id,
             place_bet_arg.clone(),
-            &system_time::get_current_system_time_from_ic(),
+            &current_time,
         )
     })?;
 
     CANISTER_DATA.with(|canister_data_ref_cell| {
-        update_profile_stats_with_bet_placed(
-            &mut canister_data_ref_cell.borrow_mut(),
-            &place_bet_arg.bet_direction,
-        );
+        let canister_data = &mut canister_data_ref_cell.borrow_mut();
+        update_profile_stats_with_bet_placed(canister_data, &place_bet_arg.bet_direction);
+        canister_data
+            .record_betting_activity_privacy(&bet_maker_principal_id, hide_betting_activity);
+
+        // * lets the post cache rank the hot or not feed by live betting activity
+        if let Some((post_cache_canister_id, hot_or_not_bet_activity_delta)) =
+            get_hot_or_not_bet_activity_delta_for_post_cache(
+                canister_data,
+                &place_bet_arg.post_canister_id,
+                place_bet_arg.post_id,
+                place_bet_arg.bet_amount,
+                &current_time,
+            )
+        {
+            queue_call_impl(
+                canister_data,
+                post_cache_canister_id,
+                "receive_hot_or_not_bet_activity_from_publishing_canister",
+                (vec![hot_or_not_bet_activity_delta],),
+                &current_time,
+            );
+        }
     });
 
-    update_scores_and_share_with_post_cache_if_difference_beyond_threshold(&place_bet_arg.post_id);
+    // * the first bet in a slot sets the timer that tabulates it once it closes
+    if let BettingStatus::BettingOpen { ongoing_slot, .. } = status {
+        if let Some(time_until_slot_closes) = CANISTER_DATA.with(|canister_data_ref_cell| {
+            claim_slot_tabulation_timer_impl(
+                &mut canister_data_ref_cell.borrow_mut(),
+                place_bet_arg.post_id,
+                ongoing_slot,
+                &current_time,
+            )
+        }) {
+            enqueue_timer_for_slot_tabulation(
+                place_bet_arg.post_id,
+                ongoing_slot,
+                time_until_slot_closes,
+            );
+        }
+    }
+
+    update_scores_and_share_with_post_cache_if_difference_beyond_threshold(
+        &place_bet_arg.post_id,
+        &IcTimeProvider,
+    );
+    publish_pending_live_betting_activity();
 
     Ok(status)
 }
@@ -50,25 +128,104 @@ fn receive_bet_from_bet_makers_canister_impl(
     canister_data: &mut CanisterData,
     bet_maker_principal_id: &Principal,
     bet_maker_canister_id: &CanisterId,
+    bet_maker_referrer_canister_id: Option<CanisterId>,
     place_bet_arg: PlaceBetArg,
     current_time: &SystemTime,
 ) -> Result<BettingStatus, BetOnCurrentlyViewingPostError> {
     let PlaceBetArg {
+        post_canister_id,
         post_id,
         bet_amount,
         bet_direction,
-        ..
     } = place_bet_arg;
 
-    let post = canister_data.all_created_posts.get_mut(&post_id).unwrap();
+    let mut post = canister_data.all_created_posts.get(&post_id).unwrap();
 
-    post.place_hot_or_not_bet(
+    let betting_status = post.place_hot_or_not_bet_with_room_sizing(
         bet_maker_principal_id,
         bet_maker_canister_id,
         bet_amount,
         &bet_direction,
         current_time,
-    )
+        &canister_data.configuration.room_sizing_config,
+    )?;
+    if let (Some(referrer_canister_id), BettingStatus::BettingOpen { ongoing_slot, .. }) =
+        (bet_maker_referrer_canister_id, &betting_status)
+    {
+        post.set_referrer_of_bet(*ongoing_slot, bet_maker_principal_id, referrer_canister_id);
+    }
+    let pot_size = post
+        .hot_or_not_details
+        .as_ref()
+        .map(|hot_or_not_details| {
+            to_token_amount(hot_or_not_details.aggregate_stats.total_amount_bet)
+        })
+        .unwrap_or_default();
+    canister_data.all_created_posts.insert(post_id, post);
+    canister_data.handle_achievement_event(
+        &AchievementEvent::BetReceivedOnPost { pot_size },
+        current_time,
+    );
+
+    if let BettingStatus::BettingOpen {
+        ongoing_slot,
+        ongoing_room,
+        number_of_participants,
+        ..
+    } = betting_status
+    {
+        queue_live_betting_activity_impl(
+            canister_data,
+            &post_canister_id,
+            post_id,
+            LiveBettingActivityEvent::BetPlaced {
+                slot_id: ongoing_slot,
+                room_id: ongoing_room,
+                bet_direction,
+                bet_amount,
+                number_of_participants_in_room: number_of_participants,
+                pot_size,
+            },
+            current_time,
+        );
+    }
+
+    // * bet volume on an enrolled post counts towards its tournament points
+    if let Some(tournament_enrollment) = canister_data.tournament_enrollments.get_mut(&post_id) {
+        if *current_time < tournament_enrollment.closes_at {
+            tournament_enrollment.points += bet_amount;
+        }
+    }
+
+    Ok(betting_status)
+}
+
+/// Post cache shard to push the bet to, along with the post's betting activity after it,
+/// or `None` if no post cache is configured
+fn get_hot_or_not_bet_activity_delta_for_post_cache(
+    canister_data: &CanisterData,
+    own_canister_id: &Principal,
+    post_id: PostId,
+    bet_amount: u64,
+    current_time: &SystemTime,
+) -> Option<(Principal, HotOrNotBetActivityDelta)> {
+    let post_cache_canister_id = canister_data
+        .known_principal_ids
+        .get_for_shard_key(&KnownPrincipalType::CanisterIdPostCache, own_canister_id)
+        .cloned()?;
+    let post = canister_data.all_created_posts.get(&post_id)?;
+    let hot_or_not_details = post.hot_or_not_details.as_ref()?;
+
+    Some((
+        post_cache_canister_id,
+        HotOrNotBetActivityDelta {
+            post_id,
+            bet_amount,
+            pot_size: to_token_amount(hot_or_not_details.aggregate_stats.total_amount_bet),
+            post_created_at: post.created_at,
+            bet_placed_at: *current_time,
+        },
+    ))
 }
 
 fn update_profile_stats_with_bet_placed(
@@ -87,12 +244,16 @@ fn update_profile_stats_with_bet_placed(
 
 #[cfg(test)]
 mod test {
+    use std::time::Duration;
+
     use shared_utils::canister_specific::individual_user_template::types::{
         hot_or_not::BetDirection,
         post::{Post, PostDetailsFromFrontend},
+        tournament::TournamentEnrollment,
     };
     use test_utils::setup::test_constants::{
         get_mock_user_alice_canister_id, get_mock_user_alice_principal_id,
+        get_mock_user_bob_canister_id,
     };
 
     use super::*;
@@ -109,6 +270,9 @@ mod test {
                     hashtags: vec!["doggo".into(), "pupper".into()],
                     video_uid: "abcd#1234".into(),
                     creator_consent_for_inclusion_in_hot_or_not: true,
+                    collaborator_shares: None,
+                    title: None,
+                    category: None,
                 },
                 &SystemTime::now(),
             ),
@@ -118,6 +282,7 @@ mod test {
             &mut canister_data,
             &get_mock_user_alice_principal_id(),
             &get_mock_user_alice_canister_id(),
+            Some(get_mock_user_bob_canister_id()),
             PlaceBetArg {
                 post_canister_id: get_mock_user_alice_canister_id(),
                 post_id: 0,
@@ -136,8 +301,153 @@ mod test {
                 number_of_participants: 1,
                 ongoing_slot: 1,
                 ongoing_room: 1,
-                has_this_user_participated_in_this_post: Some(true)
+                has_this_user_participated_in_this_post: Some(true),
+                minimum_bet_amount: 0,
             })
         );
+        assert_eq!(
+            post.hot_or_not_details.unwrap().slot_history[&1].room_details[&1].bets_made
+                [&get_mock_user_alice_principal_id()]
+                .referrer_canister_id,
+            Some(get_mock_user_bob_canister_id())
+        );
+    }
+
+    #[test]
+    fn test_receive_bet_from_bet_makers_canister_impl_accumulates_tournament_points() {
+        let mut canister_data = CanisterData::default();
+        let current_time = SystemTime::now();
+        canister_data.all_created_posts.insert(
+            0,
+            Post::new(
+                0,
+                &PostDetailsFromFrontend {
+                    description: "Doggos and puppers".into(),
+                    hashtags: vec!["doggo".into(), "pupper".into()],
+                    video_uid: "abcd#1234".into(),
+                    creator_consent_for_inclusion_in_hot_or_not: true,
+                    collaborator_shares: None,
+                    title: None,
+                    category: None,
+                },
+                &current_time,
+            ),
+        );
+        canister_data.tournament_enrollments.insert(
+            0,
+            TournamentEnrollment {
+                tournament_id: 1,
+                closes_at: current_time + Duration::from_secs(60),
+                points: 0,
+            },
+        );
+
+        [
+            (100, current_time),
+            (50, current_time + Duration::from_secs(60)),
+        ]
+        .iter()
+        .enumerate()
+        .for_each(|(index, (bet_amount, bet_time))| {
+            receive_bet_from_bet_makers_canister_impl(
+                &mut canister_data,
+                &Principal::from_slice(&[100 + index as u8]),
+                &Principal::from_slice(&[100 + index as u8]),
+                None,
+                PlaceBetArg {
+                    post_canister_id: get_mock_user_alice_canister_id(),
+                    post_id: 0,
+                    bet_amount: *bet_amount,
+                    bet_direction: BetDirection::Hot,
+                },
+                bet_time,
+            )
+            .unwrap();
+        });
+
+        assert_eq!(canister_data.tournament_enrollments[&0].points, 100);
+    }
+
+    #[test]
+    fn test_get_hot_or_not_bet_activity_delta_for_post_cache() {
+        let mut canister_data = CanisterData::default();
+        let current_time = SystemTime::now();
+        canister_data.all_created_posts.insert(
+            0,
+            Post::new(
+                0,
+                &PostDetailsFromFrontend {
+                    description: "Doggos and puppers".into(),
+                    hashtags: vec!["doggo".into(), "pupper".into()],
+                    video_uid: "abcd#1234".into(),
+                    creator_consent_for_inclusion_in_hot_or_not: true,
+                    collaborator_shares: None,
+                    title: None,
+                    category: None,
+                },
+                &current_time,
+            ),
+        );
+        receive_bet_from_bet_makers_canister_impl(
+            &mut canister_data,
+            &get_mock_user_alice_principal_id(),
+            &get_mock_user_alice_canister_id(),
+            None,
+            PlaceBetArg {
+                post_canister_id: get_mock_user_alice_canister_id(),
+                post_id: 0,
+                bet_amount: 100,
+                bet_direction: BetDirection::Hot,
+            },
+            &current_time,
+        )
+        .unwrap();
+
+        assert_eq!(
+            get_hot_or_not_bet_activity_delta_for_post_cache(
+                &canister_data,
+                &get_mock_user_alice_canister_id(),
+                0,
+                100,
+                &current_time,
+            ),
+            None
+        );
+
+        let post_cache_canister_id = Principal::from_slice(&[1]);
+        canister_data.known_principal_ids.insert(
+            KnownPrincipalType::CanisterIdPostCache,
+            post_cache_canister_id,
+        );
+
+        assert_eq!(
+            get_hot_or_not_bet_activity_delta_for_post_cache(
+                &canister_data,
+                &get_mock_user_alice_canister_id(),
+                0,
+                100,
+                &current_time,
+            ),
+            Some((
+                post_cache_canister_id,
+                HotOrNotBetActivityDelta {
+                    post_id: 0,
+                    bet_amount: 100,
+                    pot_size: 100,
+                    post_created_at: current_time,
+                    bet_placed_at: current_time,
+                }
+            ))
+        );
+        assert_eq!(
+            get_hot_or_not_bet_activity_delta_for_post_cache(
+                &canister_data,
+                &get_mock_user_alice_canister_id(),
+                1,
+                100,
+                &current_time,
+            ),
+            None
+        );
     }
 }
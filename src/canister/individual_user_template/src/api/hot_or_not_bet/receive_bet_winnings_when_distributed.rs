@@ -1,21 +1,32 @@
 use shared_utils::{
-    canister_specific::individual_user_template::types::hot_or_not::BetOutcomeForBetMaker,
+    canister_specific::individual_user_template::types::{
+        achievement::AchievementEvent, event_callback::EventCallbackPayload,
+        hot_or_not::BetOutcomeForBetMaker,
+    },
     common::{
         types::{
             app_primitive_type::PostId,
             utility_token::token_event::{HotOrNotOutcomePayoutEvent, TokenEvent},
         },
-        utils::system_time,
+        utils::system_time::{IcTimeProvider, TimeProvider},
     },
 };
 
-use crate::CANISTER_DATA;
+use crate::{
+    util::{
+        call_journal::record_call_in_journal, event_callback::send_event_callbacks,
+        guards::reject_updates_in_read_only_mode,
+    },
+    CANISTER_DATA,
+};
 
-#[ic_cdk::update]
+#[ic_cdk::update(guard = "reject_updates_in_read_only_mode")]
 #[candid::candid_method(update)]
 fn receive_bet_winnings_when_distributed(post_id: PostId, outcome: BetOutcomeForBetMaker) {
+    record_call_in_journal("receive_bet_winnings_when_distributed");
+
     let post_creator_canister_id = ic_cdk::caller();
-    let current_time = system_time::get_current_system_time_from_ic();
+    let current_time = IcTimeProvider.get_current_time();
 
     if !CANISTER_DATA.with(|canister_data_ref_cell| {
         canister_data_ref_cell
@@ -53,9 +64,9 @@ fn receive_bet_winnings_when_distributed(post_id: PostId, outcome: BetOutcomeFor
             .get(&(post_creator_canister_id, post_id))
             .cloned()
             .unwrap();
+        canister_data.betting_stats.record_bet_outcome(&outcome);
 
-        let my_token_balance = &mut canister_data.my_token_balance;
-        my_token_balance.handle_token_event(TokenEvent::HotOrNotOutcomePayout {
+        canister_data.handle_token_event(TokenEvent::HotOrNotOutcomePayout {
             amount: match outcome {
                 BetOutcomeForBetMaker::Draw(amount) => amount,
                 BetOutcomeForBetMaker::Won(amount) => amount,
@@ -71,9 +82,42 @@ fn receive_bet_winnings_when_distributed(post_id: PostId, outcome: BetOutcomeFor
                     BetOutcomeForBetMaker::Won(amount) => amount,
                     _ => 0,
                 },
-                event_outcome: outcome,
+                event_outcome: outcome.clone(),
             },
             timestamp: current_time,
         });
+
+        if let BetOutcomeForBetMaker::Won(_) = outcome {
+            let bets_won = canister_data
+                .all_hot_or_not_bets_placed
+                .values()
+                .filter(|placed_bet_detail| {
+                    matches!(
+                        placed_bet_detail.outcome_received,
+                        BetOutcomeForBetMaker::Won(_)
+                    )
+                })
+                .count() as u64;
+            canister_data
+                .handle_achievement_event(&AchievementEvent::BetWon { bets_won }, &current_time);
+        }
     });
+
+    if let BetOutcomeForBetMaker::Won(winnings_amount) = outcome {
+        // * Winnings of a profile hiding its betting activity aren't shared with third parties
+        let is_betting_activity_hidden = CANISTER_DATA.with(|canister_data_ref_cell| {
+            canister_data_ref_cell.borrow().is_betting_activity_hidden
+        });
+
+        if !is_betting_activity_hidden {
+            send_event_callbacks(
+                EventCallbackPayload::BetWon {
+                    post_canister_id: post_creator_canister_id,
+                    post_id,
+                    winnings_amount,
+                },
+                current_time,
+            );
+        }
+    }
 }
@@ -0,0 +1,180 @@
+use std::time::SystemTime;
+
+use candid::Principal;
+use shared_utils::{
+    canister_specific::individual_user_template::types::{
+        collaborator::CollaboratorCommissionSettlement,
+        received_settlements::{ReceivedSettlementKey, ReceivedSettlementType},
+    },
+    common::{
+        types::{
+            known_principal::KnownPrincipalType,
+            utility_token::token_event::{HotOrNotOutcomePayoutEvent, TokenEvent},
+        },
+        utils::system_time::{IcTimeProvider, TimeProvider},
+    },
+};
+
+use crate::{
+    data_model::CanisterData,
+    util::{call_journal::record_call_in_journal, guards::reject_updates_in_read_only_mode},
+    CANISTER_DATA,
+};
+
+/// # Access Control
+/// Only the canister the user index maps the post creator to can pay out a share, and
+/// only for its own posts
+#[ic_cdk::update(guard = "reject_updates_in_read_only_mode")]
+#[candid::candid_method(update)]
+async fn receive_collaborator_share_of_commission(
+    settlement: CollaboratorCommissionSettlement,
+    post_creator_principal_id: Principal,
+) {
+    record_call_in_journal("receive_collaborator_share_of_commission");
+
+    let post_creator_canister_id = ic_cdk::caller();
+    let user_index_canister_id = CANISTER_DATA.with(|canister_data_ref_cell| {
+        canister_data_ref_cell
+            .borrow()
+            .known_principal_ids
+            .get(&KnownPrincipalType::CanisterIdUserIndex)
+            .cloned()
+            .unwrap()
+    });
+
+    // * Trapping rejects the call so that the post creator's canister retries it later
+    let (registered_post_creator_canister_id,) = ic_cdk::call::<_, (Option<Principal>,)>(
+        user_index_canister_id,
+        "get_user_canister_id_from_user_principal_id",
+        (post_creator_principal_id,),
+    )
+    .await
+    .unwrap_or_else(|_| ic_cdk::trap("Could not verify the post creator's canister"));
+
+    if registered_post_creator_canister_id != Some(post_creator_canister_id) {
+        return;
+    }
+
+    let current_time = IcTimeProvider.get_current_time();
+
+    CANISTER_DATA.with(|canister_data_ref_cell| {
+        receive_collaborator_share_of_commission_impl(
+            &mut canister_data_ref_cell.borrow_mut(),
+            &post_creator_canister_id,
+            &post_creator_principal_id,
+            settlement,
+            &current_time,
+        );
+    });
+}
+
+fn receive_collaborator_share_of_commission_impl(
+    canister_data: &mut CanisterData,
+    post_creator_canister_id: &Principal,
+    post_creator_principal_id: &Principal,
+    settlement: CollaboratorCommissionSettlement,
+    current_time: &SystemTime,
+) {
+    if settlement.post_canister_id != *post_creator_canister_id
+        || Some(settlement.collaborator_principal_id) != canister_data.profile.principal_id
+    {
+        return;
+    }
+
+    // * The post creator's canister retries a delivery whose reply got lost
+    if !canister_data
+        .received_settlements
+        .insert(ReceivedSettlementKey {
+            settlement_type: ReceivedSettlementType::CollaboratorCommission,
+            post_canister_id: settlement.post_canister_id,
+            post_id: settlement.post_id,
+            slot_id: settlement.slot_id,
+            room_id: settlement.room_id,
+            referee_principal_id: None,
+        })
+    {
+        return;
+    }
+
+    canister_data.handle_token_event(TokenEvent::HotOrNotOutcomePayout {
+        amount: settlement.share_amount,
+        details: HotOrNotOutcomePayoutEvent::CollaboratorShareOfCommission {
+            post_creator_user_principal_id: *post_creator_principal_id,
+            post_canister_id: settlement.post_canister_id,
+            post_id: settlement.post_id,
+            slot_id: settlement.slot_id,
+            room_id: settlement.room_id,
+            room_pot_total_amount: settlement.room_pot_total_amount,
+            share_amount: settlement.share_amount,
+        },
+        timestamp: *current_time,
+    });
+}
+
+#[cfg(test)]
+mod test {
+    use test_utils::setup::test_constants::{
+        get_mock_user_alice_canister_id, get_mock_user_alice_principal_id,
+        get_mock_user_bob_principal_id, get_mock_user_charlie_canister_id,
+        get_mock_user_charlie_principal_id,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_receive_collaborator_share_of_commission_impl() {
+        let mut canister_data = CanisterData::default();
+        canister_data.profile.principal_id = Some(get_mock_user_bob_principal_id());
+
+        let settlement = CollaboratorCommissionSettlement {
+            collaborator_principal_id: get_mock_user_bob_principal_id(),
+            post_canister_id: get_mock_user_alice_canister_id(),
+            post_id: 0,
+            slot_id: 1,
+            room_id: 1,
+            room_pot_total_amount: 250,
+            share_amount: 7,
+            number_of_failed_attempts: 0,
+        };
+
+        // * paid out by a canister other than the one the post lives in
+        receive_collaborator_share_of_commission_impl(
+            &mut canister_data,
+            &get_mock_user_charlie_canister_id(),
+            &get_mock_user_charlie_principal_id(),
+            settlement.clone(),
+            &SystemTime::now(),
+        );
+        assert_eq!(canister_data.my_token_balance.utility_token_balance, 0);
+
+        // * meant for another collaborator
+        receive_collaborator_share_of_commission_impl(
+            &mut canister_data,
+            &get_mock_user_alice_canister_id(),
+            &get_mock_user_alice_principal_id(),
+            CollaboratorCommissionSettlement {
+                collaborator_principal_id: get_mock_user_charlie_principal_id(),
+                ..settlement.clone()
+            },
+            &SystemTime::now(),
+        );
+        assert_eq!(canister_data.my_token_balance.utility_token_balance, 0);
+
+        receive_collaborator_share_of_commission_impl(
+            &mut canister_data,
+            &get_mock_user_alice_canister_id(),
+            &get_mock_user_alice_principal_id(),
+            settlement.clone(),
+            &SystemTime::now(),
+        );
+        receive_collaborator_share_of_commission_impl(
+            &mut canister_data,
+            &get_mock_user_alice_canister_id(),
+            &get_mock_user_alice_principal_id(),
+            settlement,
+            &SystemTime::now(),
+        );
+        assert_eq!(canister_data.my_token_balance.utility_token_balance, 7);
+        assert_eq!(canister_data.my_token_balance.lifetime_earnings, 7);
+    }
+}
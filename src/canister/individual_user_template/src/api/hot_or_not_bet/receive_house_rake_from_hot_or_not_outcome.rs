@@ -0,0 +1,160 @@
+use std::time::SystemTime;
+
+use candid::Principal;
+use shared_utils::{
+    canister_specific::individual_user_template::types::{
+        received_settlements::{ReceivedSettlementKey, ReceivedSettlementType},
+        treasury::HouseRakeSettlement,
+    },
+    common::{
+        types::{
+            known_principal::KnownPrincipalType,
+            utility_token::token_event::{HouseRakeEvent, TokenEvent},
+        },
+        utils::system_time::{IcTimeProvider, TimeProvider},
+    },
+};
+
+use crate::{
+    data_model::CanisterData,
+    util::{call_journal::record_call_in_journal, guards::reject_updates_in_read_only_mode},
+    CANISTER_DATA,
+};
+
+/// # Access Control
+/// Only has an effect on the canister configured as the treasury, and only for rake
+/// taken from the calling canister's own posts
+#[ic_cdk::update(guard = "reject_updates_in_read_only_mode")]
+#[candid::candid_method(update)]
+fn receive_house_rake_from_hot_or_not_outcome(settlement: HouseRakeSettlement) {
+    record_call_in_journal("receive_house_rake_from_hot_or_not_outcome");
+
+    let post_canister_id = ic_cdk::caller();
+    let this_canister_id = ic_cdk::id();
+    let current_time = IcTimeProvider.get_current_time();
+
+    CANISTER_DATA.with(|canister_data_ref_cell| {
+        receive_house_rake_from_hot_or_not_outcome_impl(
+            &mut canister_data_ref_cell.borrow_mut(),
+            &this_canister_id,
+            &post_canister_id,
+            settlement,
+            &current_time,
+        );
+    });
+}
+
+fn receive_house_rake_from_hot_or_not_outcome_impl(
+    canister_data: &mut CanisterData,
+    this_canister_id: &Principal,
+    post_canister_id: &Principal,
+    settlement: HouseRakeSettlement,
+    current_time: &SystemTime,
+) {
+    if canister_data
+        .known_principal_ids
+        .get(&KnownPrincipalType::CanisterIdTreasury)
+        != Some(this_canister_id)
+    {
+        return;
+    }
+
+    if settlement.post_canister_id != *post_canister_id {
+        return;
+    }
+
+    // * The post canister retries a delivery whose reply got lost
+    if !canister_data
+        .received_settlements
+        .insert(ReceivedSettlementKey {
+            settlement_type: ReceivedSettlementType::HouseRake,
+            post_canister_id: settlement.post_canister_id,
+            post_id: settlement.post_id,
+            slot_id: settlement.slot_id,
+            room_id: settlement.room_id,
+            referee_principal_id: None,
+        })
+    {
+        return;
+    }
+
+    canister_data.handle_token_event(TokenEvent::HouseRake {
+        amount: settlement.rake_amount,
+        details: HouseRakeEvent::HouseRakeFromHotOrNotBet {
+            post_canister_id: settlement.post_canister_id,
+            post_id: settlement.post_id,
+            slot_id: settlement.slot_id,
+            room_id: settlement.room_id,
+            room_pot_total_amount: settlement.room_pot_total_amount,
+        },
+        timestamp: *current_time,
+    });
+}
+
+#[cfg(test)]
+mod test {
+    use test_utils::setup::test_constants::{
+        get_mock_user_alice_canister_id, get_mock_user_bob_canister_id,
+        get_mock_user_charlie_canister_id,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_receive_house_rake_from_hot_or_not_outcome_impl() {
+        let mut canister_data = CanisterData::default();
+        let treasury_canister_id = get_mock_user_charlie_canister_id();
+
+        let settlement = HouseRakeSettlement {
+            post_canister_id: get_mock_user_alice_canister_id(),
+            post_id: 0,
+            slot_id: 1,
+            room_id: 1,
+            room_pot_total_amount: 3550,
+            rake_amount: 35,
+            number_of_failed_attempts: 0,
+        };
+
+        // * no treasury configured
+        receive_house_rake_from_hot_or_not_outcome_impl(
+            &mut canister_data,
+            &treasury_canister_id,
+            &get_mock_user_alice_canister_id(),
+            settlement.clone(),
+            &SystemTime::now(),
+        );
+        assert_eq!(canister_data.my_token_balance.utility_token_balance, 0);
+
+        canister_data
+            .known_principal_ids
+            .insert(KnownPrincipalType::CanisterIdTreasury, treasury_canister_id);
+
+        // * caller is not the canister the rake was taken on
+        receive_house_rake_from_hot_or_not_outcome_impl(
+            &mut canister_data,
+            &treasury_canister_id,
+            &get_mock_user_bob_canister_id(),
+            settlement.clone(),
+            &SystemTime::now(),
+        );
+        assert_eq!(canister_data.my_token_balance.utility_token_balance, 0);
+
+        receive_house_rake_from_hot_or_not_outcome_impl(
+            &mut canister_data,
+            &treasury_canister_id,
+            &get_mock_user_alice_canister_id(),
+            settlement.clone(),
+            &SystemTime::now(),
+        );
+        receive_house_rake_from_hot_or_not_outcome_impl(
+            &mut canister_data,
+            &treasury_canister_id,
+            &get_mock_user_alice_canister_id(),
+            settlement,
+            &SystemTime::now(),
+        );
+        assert_eq!(canister_data.my_token_balance.utility_token_balance, 35);
+        assert_eq!(canister_data.my_token_balance.lifetime_earnings, 0);
+        assert_eq!(canister_data.token_transaction_history.len(), 1);
+    }
+}
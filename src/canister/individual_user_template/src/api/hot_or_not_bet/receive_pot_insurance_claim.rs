@@ -0,0 +1,248 @@
+use std::time::SystemTime;
+
+use candid::Principal;
+use shared_utils::{
+    canister_specific::individual_user_template::types::{
+        error::PotInsuranceClaimError,
+        treasury::{PotInsuranceClaim, PotInsuranceTopUpSettlement},
+    },
+    common::{
+        types::{
+            known_principal::KnownPrincipalType,
+            utility_token::token_event::{HouseRakeEvent, TokenEvent},
+        },
+        utils::system_time::{IcTimeProvider, TimeProvider},
+    },
+};
+
+use crate::{
+    data_model::CanisterData,
+    util::{call_journal::record_call_in_journal, guards::reject_updates_in_read_only_mode},
+    CANISTER_DATA,
+};
+
+use super::settle_pot_insurance_top_ups::settle_pending_pot_insurance_top_ups;
+
+/// Pays the top ups owed to the winners of a room that settled below the pot floor out of
+/// the treasury's balance. Returns the total paid.
+///
+/// # Access Control
+/// Only has an effect on the canister configured as the treasury, and only for rooms on the
+/// own posts of the canister the user index maps the post creator to
+#[ic_cdk::update(guard = "reject_updates_in_read_only_mode")]
+#[candid::candid_method(update)]
+async fn receive_pot_insurance_claim(
+    claim: PotInsuranceClaim,
+    post_creator_principal_id: Principal,
+) -> Result<u64, PotInsuranceClaimError> {
+    record_call_in_journal("receive_pot_insurance_claim");
+
+    let post_canister_id = ic_cdk::caller();
+    let this_canister_id = ic_cdk::id();
+    let user_index_canister_id = CANISTER_DATA.with(|canister_data_ref_cell| {
+        canister_data_ref_cell
+            .borrow()
+            .known_principal_ids
+            .get(&KnownPrincipalType::CanisterIdUserIndex)
+            .cloned()
+            .unwrap()
+    });
+
+    // * Trapping rejects the call so that the post creator's canister retries it later
+    let (registered_post_canister_id,) = ic_cdk::call::<_, (Option<Principal>,)>(
+        user_index_canister_id,
+        "get_user_canister_id_from_user_principal_id",
+        (post_creator_principal_id,),
+    )
+    .await
+    .unwrap_or_else(|_| ic_cdk::trap("Could not verify the post creator's canister"));
+
+    if registered_post_canister_id != Some(post_canister_id) {
+        return Err(PotInsuranceClaimError::Unauthorized);
+    }
+
+    let current_time = IcTimeProvider.get_current_time();
+
+    let total_top_up_amount = CANISTER_DATA.with(|canister_data_ref_cell| {
+        receive_pot_insurance_claim_impl(
+            &mut canister_data_ref_cell.borrow_mut(),
+            &this_canister_id,
+            &post_canister_id,
+            claim,
+            &current_time,
+        )
+    })?;
+
+    settle_pending_pot_insurance_top_ups();
+
+    Ok(total_top_up_amount)
+}
+
+fn receive_pot_insurance_claim_impl(
+    canister_data: &mut CanisterData,
+    this_canister_id: &Principal,
+    post_canister_id: &Principal,
+    claim: PotInsuranceClaim,
+    current_time: &SystemTime,
+) -> Result<u64, PotInsuranceClaimError> {
+    if canister_data
+        .known_principal_ids
+        .get(&KnownPrincipalType::CanisterIdTreasury)
+        != Some(this_canister_id)
+    {
+        return Err(PotInsuranceClaimError::NotTreasury);
+    }
+
+    if claim.post_canister_id != *post_canister_id {
+        return Err(PotInsuranceClaimError::Unauthorized);
+    }
+
+    let total_top_up_amount = canister_data.pot_insurance_ledger.accept_claim(
+        &claim,
+        &canister_data.configuration.pot_insurance_config,
+        canister_data.my_token_balance.utility_token_balance,
+        current_time,
+    )?;
+
+    canister_data.handle_token_event(TokenEvent::HouseRake {
+        amount: total_top_up_amount,
+        details: HouseRakeEvent::PotInsuranceTopUpsPaidOut {
+            post_canister_id: claim.post_canister_id,
+            post_id: claim.post_id,
+            slot_id: claim.slot_id,
+            room_id: claim.room_id,
+            room_pot_total_amount: claim.room_pot_total_amount,
+        },
+        timestamp: *current_time,
+    });
+
+    for top_up in claim.top_ups {
+        canister_data
+            .pending_pot_insurance_top_ups
+            .push_back(PotInsuranceTopUpSettlement {
+                post_canister_id: claim.post_canister_id,
+                post_id: claim.post_id,
+                slot_id: claim.slot_id,
+                room_id: claim.room_id,
+                bet_maker_canister_id: top_up.bet_maker_canister_id,
+                top_up_amount: top_up.amount,
+                number_of_failed_attempts: 0,
+            });
+    }
+
+    Ok(total_top_up_amount)
+}
+
+#[cfg(test)]
+mod test {
+    use shared_utils::{
+        canister_specific::individual_user_template::types::treasury::{
+            PotInsuranceConfig, PotInsuranceTopUp,
+        },
+        common::types::utility_token::token_event::MintEvent,
+    };
+    use test_utils::setup::test_constants::{
+        get_mock_user_alice_canister_id, get_mock_user_bob_canister_id,
+        get_mock_user_charlie_canister_id, get_mock_user_dan_canister_id,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_receive_pot_insurance_claim_impl() {
+        let mut canister_data = CanisterData::default();
+        let treasury_canister_id = get_mock_user_charlie_canister_id();
+        let current_time = SystemTime::now();
+
+        let claim = PotInsuranceClaim {
+            post_canister_id: get_mock_user_alice_canister_id(),
+            post_id: 0,
+            slot_id: 1,
+            room_id: 1,
+            room_pot_total_amount: 250,
+            top_ups: vec![
+                PotInsuranceTopUp {
+                    bet_maker_canister_id: get_mock_user_bob_canister_id(),
+                    amount: 90,
+                },
+                PotInsuranceTopUp {
+                    bet_maker_canister_id: get_mock_user_dan_canister_id(),
+                    amount: 180,
+                },
+            ],
+            number_of_failed_attempts: 0,
+            tabulated_at: Some(current_time),
+        };
+
+        assert_eq!(
+            receive_pot_insurance_claim_impl(
+                &mut canister_data,
+                &treasury_canister_id,
+                &get_mock_user_alice_canister_id(),
+                claim.clone(),
+                &current_time,
+            ),
+            Err(PotInsuranceClaimError::NotTreasury)
+        );
+
+        canister_data
+            .known_principal_ids
+            .insert(KnownPrincipalType::CanisterIdTreasury, treasury_canister_id);
+        canister_data.configuration.pot_insurance_config = PotInsuranceConfig {
+            room_pot_floor: 500,
+            daily_spend_cap: 10_000,
+        };
+        canister_data.handle_token_event(TokenEvent::Mint {
+            amount: 1000,
+            details: MintEvent::NewUserSignup {
+                new_user_principal_id: treasury_canister_id,
+            },
+            timestamp: current_time,
+        });
+
+        // * caller is not the canister the room is on
+        assert_eq!(
+            receive_pot_insurance_claim_impl(
+                &mut canister_data,
+                &treasury_canister_id,
+                &get_mock_user_bob_canister_id(),
+                claim.clone(),
+                &current_time,
+            ),
+            Err(PotInsuranceClaimError::Unauthorized)
+        );
+
+        assert_eq!(
+            receive_pot_insurance_claim_impl(
+                &mut canister_data,
+                &treasury_canister_id,
+                &get_mock_user_alice_canister_id(),
+                claim.clone(),
+                &current_time,
+            ),
+            Ok(270)
+        );
+        assert_eq!(canister_data.my_token_balance.utility_token_balance, 730);
+        assert_eq!(canister_data.pending_pot_insurance_top_ups.len(), 2);
+        assert_eq!(
+            canister_data
+                .pending_pot_insurance_top_ups
+                .get(1)
+                .map(|settlement| (settlement.bet_maker_canister_id, settlement.top_up_amount)),
+            Some((get_mock_user_dan_canister_id(), 180))
+        );
+
+        // * the same room is only ever paid once
+        assert_eq!(
+            receive_pot_insurance_claim_impl(
+                &mut canister_data,
+                &treasury_canister_id,
+                &get_mock_user_alice_canister_id(),
+                claim,
+                &current_time,
+            ),
+            Err(PotInsuranceClaimError::AlreadyPaid)
+        );
+        assert_eq!(canister_data.my_token_balance.utility_token_balance, 730);
+    }
+}
@@ -0,0 +1,179 @@
+use std::time::SystemTime;
+
+use candid::Principal;
+use shared_utils::{
+    canister_specific::individual_user_template::types::treasury::PotInsuranceTopUpSettlement,
+    common::{
+        types::{
+            known_principal::KnownPrincipalType,
+            utility_token::token_event::{HotOrNotOutcomePayoutEvent, TokenEvent},
+        },
+        utils::{
+            known_caller::authorize_known_caller,
+            system_time::{IcTimeProvider, TimeProvider},
+        },
+    },
+};
+
+use crate::{
+    data_model::CanisterData,
+    util::{call_journal::record_call_in_journal, guards::reject_updates_in_read_only_mode},
+    CANISTER_DATA,
+};
+
+/// # Access Control
+/// Only the treasury canister can pay out top ups, and only for a bet this profile placed
+/// in the insured room
+#[ic_cdk::update(guard = "reject_updates_in_read_only_mode")]
+#[candid::candid_method(update)]
+fn receive_pot_insurance_top_up(settlement: PotInsuranceTopUpSettlement) {
+    record_call_in_journal("receive_pot_insurance_top_up");
+
+    let treasury_canister_id = ic_cdk::caller();
+    let current_time = IcTimeProvider.get_current_time();
+
+    CANISTER_DATA.with(|canister_data_ref_cell| {
+        receive_pot_insurance_top_up_impl(
+            &mut canister_data_ref_cell.borrow_mut(),
+            &treasury_canister_id,
+            settlement,
+            &current_time,
+        );
+    });
+}
+
+fn receive_pot_insurance_top_up_impl(
+    canister_data: &mut CanisterData,
+    treasury_canister_id: &Principal,
+    settlement: PotInsuranceTopUpSettlement,
+    current_time: &SystemTime,
+) {
+    if authorize_known_caller(
+        treasury_canister_id,
+        &canister_data.known_principal_ids,
+        &[KnownPrincipalType::CanisterIdTreasury],
+    )
+    .is_err()
+    {
+        return;
+    }
+
+    let bet_key = (settlement.post_canister_id, settlement.post_id);
+    let Some(placed_bet_detail) = canister_data.all_hot_or_not_bets_placed.get(&bet_key) else {
+        return;
+    };
+    if placed_bet_detail.slot_id != settlement.slot_id
+        || placed_bet_detail.room_id != settlement.room_id
+    {
+        return;
+    }
+
+    // * The treasury retries a delivery whose reply got lost
+    if !canister_data.received_pot_insurance_top_ups.insert(bet_key) {
+        return;
+    }
+
+    canister_data.handle_token_event(TokenEvent::HotOrNotOutcomePayout {
+        amount: settlement.top_up_amount,
+        details: HotOrNotOutcomePayoutEvent::PotInsuranceTopUp {
+            post_canister_id: settlement.post_canister_id,
+            post_id: settlement.post_id,
+            slot_id: settlement.slot_id,
+            room_id: settlement.room_id,
+            top_up_amount: settlement.top_up_amount,
+        },
+        timestamp: *current_time,
+    });
+}
+
+#[cfg(test)]
+mod test {
+    use shared_utils::canister_specific::individual_user_template::types::hot_or_not::{
+        BetDirection, BetOutcomeForBetMaker, PlacedBetDetail,
+    };
+    use test_utils::setup::test_constants::{
+        get_mock_user_alice_canister_id, get_mock_user_bob_canister_id,
+        get_mock_user_charlie_canister_id,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_receive_pot_insurance_top_up_impl() {
+        let mut canister_data = CanisterData::default();
+        let treasury_canister_id = get_mock_user_charlie_canister_id();
+        let current_time = SystemTime::now();
+
+        let settlement = PotInsuranceTopUpSettlement {
+            post_canister_id: get_mock_user_alice_canister_id(),
+            post_id: 0,
+            slot_id: 1,
+            room_id: 1,
+            bet_maker_canister_id: get_mock_user_bob_canister_id(),
+            top_up_amount: 90,
+            number_of_failed_attempts: 0,
+        };
+        canister_data.all_hot_or_not_bets_placed.insert(
+            (get_mock_user_alice_canister_id(), 0),
+            PlacedBetDetail {
+                canister_id: get_mock_user_alice_canister_id(),
+                post_id: 0,
+                slot_id: 1,
+                room_id: 1,
+                amount_bet: 50,
+                bet_direction: BetDirection::Hot,
+                bet_placed_at: current_time,
+                outcome_received: BetOutcomeForBetMaker::Won(90),
+            },
+        );
+
+        // * no treasury configured
+        receive_pot_insurance_top_up_impl(
+            &mut canister_data,
+            &treasury_canister_id,
+            settlement.clone(),
+            &current_time,
+        );
+        assert_eq!(canister_data.my_token_balance.utility_token_balance, 0);
+
+        canister_data
+            .known_principal_ids
+            .insert(KnownPrincipalType::CanisterIdTreasury, treasury_canister_id);
+
+        // * caller is not the treasury
+        receive_pot_insurance_top_up_impl(
+            &mut canister_data,
+            &get_mock_user_alice_canister_id(),
+            settlement.clone(),
+            &current_time,
+        );
+        assert_eq!(canister_data.my_token_balance.utility_token_balance, 0);
+
+        // * not the room this profile bet in
+        receive_pot_insurance_top_up_impl(
+            &mut canister_data,
+            &treasury_canister_id,
+            PotInsuranceTopUpSettlement {
+                room_id: 2,
+                ..settlement.clone()
+            },
+            &current_time,
+        );
+        assert_eq!(canister_data.my_token_balance.utility_token_balance, 0);
+
+        receive_pot_insurance_top_up_impl(
+            &mut canister_data,
+            &treasury_canister_id,
+            settlement.clone(),
+            &current_time,
+        );
+        receive_pot_insurance_top_up_impl(
+            &mut canister_data,
+            &treasury_canister_id,
+            settlement,
+            &current_time,
+        );
+        assert_eq!(canister_data.my_token_balance.utility_token_balance, 90);
+        assert_eq!(canister_data.my_token_balance.lifetime_earnings, 90);
+    }
+}
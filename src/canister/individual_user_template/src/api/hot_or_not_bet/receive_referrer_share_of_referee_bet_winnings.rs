@@ -0,0 +1,225 @@
+use std::time::SystemTime;
+
+use candid::Principal;
+use shared_utils::{
+    canister_specific::individual_user_template::types::{
+        received_settlements::{ReceivedSettlementKey, ReceivedSettlementType},
+        referral::ReferrerShareSettlement,
+    },
+    common::{
+        types::{
+            known_principal::KnownPrincipalType,
+            utility_token::token_event::{ReferralCommissionEvent, TokenEvent},
+        },
+        utils::system_time::{IcTimeProvider, TimeProvider},
+    },
+};
+
+use crate::{
+    data_model::CanisterData,
+    util::{call_journal::record_call_in_journal, guards::reject_updates_in_read_only_mode},
+    CANISTER_DATA,
+};
+
+/// # Access Control
+/// Only the canister the user index maps the sender to can pay out a share, and only for
+/// bets placed on its own posts by users this profile referred. Shares queued by the
+/// referee's own canister before they were worked out when tabulating are taken from it.
+#[ic_cdk::update(guard = "reject_updates_in_read_only_mode")]
+#[candid::candid_method(update)]
+async fn receive_referrer_share_of_referee_bet_winnings(
+    settlement: ReferrerShareSettlement,
+    sender_principal_id: Principal,
+) {
+    record_call_in_journal("receive_referrer_share_of_referee_bet_winnings");
+
+    let sender_canister_id = ic_cdk::caller();
+    let user_index_canister_id = CANISTER_DATA.with(|canister_data_ref_cell| {
+        canister_data_ref_cell
+            .borrow()
+            .known_principal_ids
+            .get(&KnownPrincipalType::CanisterIdUserIndex)
+            .cloned()
+            .unwrap()
+    });
+
+    // * Trapping rejects the call so that the sender retries it later
+    let (registered_sender_canister_id,) = ic_cdk::call::<_, (Option<Principal>,)>(
+        user_index_canister_id,
+        "get_user_canister_id_from_user_principal_id",
+        (sender_principal_id,),
+    )
+    .await
+    .unwrap_or_else(|_| ic_cdk::trap("Could not verify the sender's canister"));
+
+    if registered_sender_canister_id != Some(sender_canister_id) {
+        return;
+    }
+
+    let current_time = IcTimeProvider.get_current_time();
+
+    CANISTER_DATA.with(|canister_data_ref_cell| {
+        receive_referrer_share_of_referee_bet_winnings_impl(
+            &mut canister_data_ref_cell.borrow_mut(),
+            &sender_canister_id,
+            settlement,
+            &current_time,
+        );
+    });
+}
+
+fn receive_referrer_share_of_referee_bet_winnings_impl(
+    canister_data: &mut CanisterData,
+    sender_canister_id: &Principal,
+    settlement: ReferrerShareSettlement,
+    current_time: &SystemTime,
+) {
+    let referee_canister_id = match settlement.referee_canister_id {
+        Some(referee_canister_id) if settlement.post_canister_id == *sender_canister_id => {
+            referee_canister_id
+        }
+        Some(_) => return,
+        None => *sender_canister_id,
+    };
+
+    if !canister_data
+        .my_referee_canister_ids
+        .contains(&referee_canister_id)
+    {
+        return;
+    }
+
+    // * The referee retries a delivery whose reply got lost
+    if !canister_data
+        .received_settlements
+        .insert(ReceivedSettlementKey {
+            settlement_type: ReceivedSettlementType::ReferrerShare,
+            post_canister_id: settlement.post_canister_id,
+            post_id: settlement.post_id,
+            slot_id: settlement.slot_id,
+            room_id: settlement.room_id,
+            referee_principal_id: Some(settlement.referee_principal_id),
+        })
+    {
+        return;
+    }
+
+    canister_data.handle_token_event(TokenEvent::ReferralCommission {
+        amount: settlement.share_amount,
+        details: ReferralCommissionEvent::ShareOfRefereeBetWinnings {
+            referee_user_principal_id: settlement.referee_principal_id,
+            post_canister_id: settlement.post_canister_id,
+            post_id: settlement.post_id,
+            slot_id: settlement.slot_id,
+            room_id: settlement.room_id,
+        },
+        timestamp: *current_time,
+    });
+
+    if let Some(referral_code_details) = canister_data
+        .referral_code_by_referee_canister_id
+        .get(&referee_canister_id)
+        .and_then(|referral_code| canister_data.referral_codes.get_mut(referral_code))
+    {
+        referral_code_details.referrer_share_of_winnings_earned += settlement.share_amount;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use shared_utils::canister_specific::individual_user_template::types::referral::ReferralCodeDetails;
+    use test_utils::setup::test_constants::{
+        get_mock_user_alice_canister_id, get_mock_user_bob_canister_id,
+        get_mock_user_bob_principal_id, get_mock_user_charlie_canister_id,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_receive_referrer_share_of_referee_bet_winnings_impl() {
+        let mut canister_data = CanisterData::default();
+        canister_data
+            .my_referee_canister_ids
+            .insert(get_mock_user_bob_canister_id());
+        canister_data.referral_codes.insert(
+            "AB3K9Q2Z".to_string(),
+            ReferralCodeDetails {
+                code: "AB3K9Q2Z".to_string(),
+                name: "summer".to_string(),
+                created_at: SystemTime::now(),
+                referee_count: 1,
+                signup_rewards_earned: 500,
+                referrer_share_of_winnings_earned: 0,
+            },
+        );
+        canister_data
+            .referral_code_by_referee_canister_id
+            .insert(get_mock_user_bob_canister_id(), "AB3K9Q2Z".to_string());
+
+        let settlement = ReferrerShareSettlement {
+            referrer_canister_id: get_mock_user_alice_canister_id(),
+            referee_principal_id: get_mock_user_bob_principal_id(),
+            referee_canister_id: None,
+            post_canister_id: get_mock_user_charlie_canister_id(),
+            post_id: 0,
+            slot_id: 1,
+            room_id: 1,
+            share_amount: 18,
+            number_of_failed_attempts: 0,
+        };
+
+        receive_referrer_share_of_referee_bet_winnings_impl(
+            &mut canister_data,
+            &get_mock_user_charlie_canister_id(),
+            settlement.clone(),
+            &SystemTime::now(),
+        );
+        assert_eq!(canister_data.my_token_balance.utility_token_balance, 0);
+
+        receive_referrer_share_of_referee_bet_winnings_impl(
+            &mut canister_data,
+            &get_mock_user_bob_canister_id(),
+            settlement.clone(),
+            &SystemTime::now(),
+        );
+        receive_referrer_share_of_referee_bet_winnings_impl(
+            &mut canister_data,
+            &get_mock_user_bob_canister_id(),
+            settlement.clone(),
+            &SystemTime::now(),
+        );
+        assert_eq!(canister_data.my_token_balance.utility_token_balance, 18);
+        assert_eq!(canister_data.token_transaction_history.len(), 1);
+        assert_eq!(
+            canister_data
+                .referral_codes
+                .get("AB3K9Q2Z")
+                .map(
+                    |referral_code_details| referral_code_details.referrer_share_of_winnings_earned
+                ),
+            Some(18)
+        );
+
+        // * Shares worked out when tabulating are only taken from the post's canister
+        let tabulated_settlement = ReferrerShareSettlement {
+            referee_canister_id: Some(get_mock_user_bob_canister_id()),
+            room_id: 2,
+            ..settlement
+        };
+        receive_referrer_share_of_referee_bet_winnings_impl(
+            &mut canister_data,
+            &get_mock_user_bob_canister_id(),
+            tabulated_settlement.clone(),
+            &SystemTime::now(),
+        );
+        assert_eq!(canister_data.my_token_balance.utility_token_balance, 18);
+
+        receive_referrer_share_of_referee_bet_winnings_impl(
+            &mut canister_data,
+            &get_mock_user_charlie_canister_id(),
+            tabulated_settlement,
+            &SystemTime::now(),
+        );
+        assert_eq!(canister_data.my_token_balance.utility_token_balance, 36);
+    }
+}
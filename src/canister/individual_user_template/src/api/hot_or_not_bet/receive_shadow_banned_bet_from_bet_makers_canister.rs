@@ -0,0 +1,151 @@
+use std::time::SystemTime;
+
+use candid::Principal;
+use ic_cdk::api::management_canister::provisional::CanisterId;
+use shared_utils::{
+    canister_specific::individual_user_template::types::{
+        arg::PlaceBetArg, error::BetOnCurrentlyViewingPostError, hot_or_not::BettingStatus,
+    },
+    common::utils::system_time::{IcTimeProvider, TimeProvider},
+};
+
+use crate::{
+    data_model::CanisterData,
+    util::{
+        call_journal::record_call_in_journal, canister_binding::is_expected_binding_epoch,
+        guards::reject_bets_and_posts_in_read_only_or_low_cycles_mode,
+    },
+    CANISTER_DATA,
+};
+
+/// Called by the canister of a shadow banned bet maker in place of
+/// `receive_bet_from_bet_makers_canister`. The bet is accepted into the slot's phantom room,
+/// so it does not count towards profile stats, tournament points or feed scores.
+#[ic_cdk::update(guard = "reject_bets_and_posts_in_read_only_or_low_cycles_mode")]
+#[candid::candid_method(update)]
+fn receive_shadow_banned_bet_from_bet_makers_canister(
+    place_bet_arg: PlaceBetArg,
+    bet_maker_principal_id: Principal,
+    hide_betting_activity: Option<bool>,
+    expected_binding_epoch: Option<u64>,
+) -> Result<BettingStatus, BetOnCurrentlyViewingPostError> {
+    record_call_in_journal("receive_shadow_banned_bet_from_bet_makers_canister");
+
+    let bet_maker_canister_id = ic_cdk::caller();
+
+    CANISTER_DATA.with(|canister_data_ref_cell| {
+        let canister_data = &mut canister_data_ref_cell.borrow_mut();
+        if !is_expected_binding_epoch(canister_data, expected_binding_epoch) {
+            return Err(BetOnCurrentlyViewingPostError::StaleCanisterBinding);
+        }
+
+        let status = receive_shadow_banned_bet_from_bet_makers_canister_impl(
+            canister_data,
+            &bet_maker_principal_id,
+            &bet_maker_canister_id,
+            place_bet_arg,
+            &IcTimeProvider.get_current_time(),
+        )?;
+        canister_data
+            .record_betting_activity_privacy(&bet_maker_principal_id, hide_betting_activity);
+
+        Ok(status)
+    })
+}
+
+fn receive_shadow_banned_bet_from_bet_makers_canister_impl(
+    canister_data: &mut CanisterData,
+    bet_maker_principal_id: &Principal,
+    bet_maker_canister_id: &CanisterId,
+    place_bet_arg: PlaceBetArg,
+    current_time: &SystemTime,
+) -> Result<BettingStatus, BetOnCurrentlyViewingPostError> {
+    let PlaceBetArg {
+        post_id,
+        bet_amount,
+        bet_direction,
+        ..
+    } = place_bet_arg;
+
+    let mut post = canister_data.all_created_posts.get(&post_id).unwrap();
+
+    let betting_status = post.place_hot_or_not_bet_in_phantom_room(
+        bet_maker_principal_id,
+        bet_maker_canister_id,
+        bet_amount,
+        &bet_direction,
+        current_time,
+    )?;
+    canister_data.all_created_posts.insert(post_id, post);
+
+    Ok(betting_status)
+}
+
+#[cfg(test)]
+mod test {
+    use shared_utils::canister_specific::individual_user_template::types::{
+        hot_or_not::BetDirection,
+        post::{Post, PostDetailsFromFrontend},
+    };
+    use test_utils::setup::test_constants::{
+        get_mock_user_alice_canister_id, get_mock_user_bob_canister_id,
+        get_mock_user_bob_principal_id,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_receive_shadow_banned_bet_from_bet_makers_canister_impl() {
+        let mut canister_data = CanisterData::default();
+        let current_time = SystemTime::now();
+        canister_data.all_created_posts.insert(
+            0,
+            Post::new(
+                0,
+                &PostDetailsFromFrontend {
+                    description: "Doggos and puppers".into(),
+                    hashtags: vec!["doggo".into(), "pupper".into()],
+                    video_uid: "abcd#1234".into(),
+                    creator_consent_for_inclusion_in_hot_or_not: true,
+                    collaborator_shares: None,
+                    title: None,
+                    category: None,
+                },
+                &current_time,
+            ),
+        );
+
+        let result = receive_shadow_banned_bet_from_bet_makers_canister_impl(
+            &mut canister_data,
+            &get_mock_user_bob_principal_id(),
+            &get_mock_user_bob_canister_id(),
+            PlaceBetArg {
+                post_canister_id: get_mock_user_alice_canister_id(),
+                post_id: 0,
+                bet_amount: 100,
+                bet_direction: BetDirection::Hot,
+            },
+            &current_time,
+        );
+
+        assert_eq!(
+            result,
+            Ok(BettingStatus::BettingOpen {
+                started_at: current_time,
+                number_of_participants: 1,
+                ongoing_slot: 1,
+                ongoing_room: 1,
+                has_this_user_participated_in_this_post: Some(true),
+                minimum_bet_amount: 0,
+            })
+        );
+
+        let post = canister_data.all_created_posts.get(&0).unwrap();
+        let slot_details = &post.hot_or_not_details.as_ref().unwrap().slot_history[&1];
+        assert!(slot_details.room_details.is_empty());
+        assert!(slot_details
+            .phantom_room_bets
+            .contains_key(&get_mock_user_bob_principal_id()));
+        assert_eq!(canister_data.profile.profile_stats.hot_bets_received, 0);
+    }
+}
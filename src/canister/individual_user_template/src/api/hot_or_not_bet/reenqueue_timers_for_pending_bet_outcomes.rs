@@ -1,226 +1,320 @@
-use std::time::{Duration, SystemTime};
+use std::time::SystemTime;
 
-use shared_utils::common::utils::system_time;
+use shared_utils::{
+    canister_specific::individual_user_template::types::post::{
+        Post, MAXIMUM_PUBLISH_DELAY_IN_SECONDS,
+    },
+    common::utils::system_time::TimeProvider,
+};
 
 use crate::{data_model::CanisterData, CANISTER_DATA};
 
-use super::tabulate_hot_or_not_outcome_for_post_slot::tabulate_hot_or_not_outcome_for_post_slot;
-
-pub fn reenqueue_timers_for_pending_bet_outcomes() {
-    let current_time = system_time::get_current_system_time_from_ic();
-
-    CANISTER_DATA.with(|canister_data_ref_cell| {
-        let canister_data = canister_data_ref_cell.borrow();
-
-        let posts = get_posts_that_have_pending_outcomes(&canister_data, &current_time);
-
-        reenqueue_timers_for_these_posts(&canister_data, posts, &current_time);
-    });
+use super::tabulate_hot_or_not_outcome_for_post_slot::{
+    claim_slot_tabulation_timer_impl, enqueue_timer_for_continuing_tabulation,
+    enqueue_timer_for_slot_tabulation,
+};
+
+/// Timers don't survive upgrades, so this sets them again from `post_upgrade` for every slot
+/// with bets yet to be settled. Slots that closed while the canister was being upgraded are
+/// tabulated right away.
+pub fn reenqueue_timers_for_pending_bet_outcomes(time_provider: &impl TimeProvider) {
+    let current_time = time_provider.get_current_time();
+
+    let (slot_tabulation_timers, post_slots_with_unfinished_tabulation) =
+        CANISTER_DATA.with(|canister_data_ref_cell| {
+            let canister_data = &mut canister_data_ref_cell.borrow_mut();
+
+            let slot_tabulation_timers =
+                get_post_slots_awaiting_tabulation(canister_data, &current_time)
+                    .into_iter()
+                    .filter_map(|(post_id, slot_id)| {
+                        claim_slot_tabulation_timer_impl(
+                            canister_data,
+                            post_id,
+                            slot_id,
+                            &current_time,
+                        )
+                        .map(|time_until_slot_closes| (post_id, slot_id, time_until_slot_closes))
+                    })
+                    .collect::<Vec<_>>();
+
+            (
+                slot_tabulation_timers,
+                get_post_slots_with_unfinished_tabulation(canister_data, &current_time),
+            )
+        });
+
+    slot_tabulation_timers
+        .into_iter()
+        .for_each(|(post_id, slot_id, time_until_slot_closes)| {
+            enqueue_timer_for_slot_tabulation(post_id, slot_id, time_until_slot_closes);
+        });
+    post_slots_with_unfinished_tabulation
+        .into_iter()
+        .for_each(|(post_id, slot_id)| {
+            enqueue_timer_for_continuing_tabulation(post_id, slot_id);
+        });
 }
 
-fn get_posts_that_have_pending_outcomes(
+/// Slots that have bets in them which were never tabulated, whether they are still open or
+/// closed while their timer was lost to the upgrade
+fn get_post_slots_awaiting_tabulation(
     canister_data: &CanisterData,
     current_time: &SystemTime,
-) -> Vec<u64> {
+) -> Vec<(u64, u8)> {
     canister_data
         .all_created_posts
-        .iter()
-        .rev()
+        .iter_rev()
         .take_while(|(_post_id, post)| {
-            let created_in_the_last_48_hours = current_time
-                .duration_since(post.created_at)
-                .unwrap_or(Duration::from_secs((48 * 60 + 5) * 60))
-                .as_secs()
-                < 48 * 60 * 60;
-            let is_a_hot_or_not_post = post.hot_or_not_details.is_some();
-
-            created_in_the_last_48_hours && is_a_hot_or_not_post
+            was_created_recently_enough_to_be_betting(post, current_time)
+        })
+        .filter_map(|(post_id, post)| {
+            post.hot_or_not_details
+                .map(|hot_or_not_details| (post_id, hot_or_not_details))
+        })
+        .flat_map(|(post_id, hot_or_not_details)| {
+            hot_or_not_details
+                .slot_history
+                .into_iter()
+                .filter(|(_slot_id, slot_details)| slot_details.is_awaiting_tabulation())
+                .map(move |(slot_id, _slot_details)| (post_id, slot_id))
         })
-        .map(|(post_id, _post)| *post_id)
         .collect()
 }
 
-fn reenqueue_timers_for_these_posts(
+/// Slots whose tabulation ran out of budget and was waiting on a timer lost to the upgrade.
+/// Looks an hour past the 48 hour window, as the last slot only gets tabulated at its end.
+fn get_post_slots_with_unfinished_tabulation(
     canister_data: &CanisterData,
-    post_ids: Vec<u64>,
     current_time: &SystemTime,
-) {
-    for post_id in post_ids {
-        let post = canister_data.all_created_posts.get(&post_id).unwrap();
-
-        let slot_to_enqueue_onwards = (current_time
-            .duration_since(post.created_at)
-            .unwrap()
-            .as_secs()
-            / (60 * 60)) as u8;
-
-        // * schedule hot_or_not outcome tabulation for the 48 hours after the post is created
-        (slot_to_enqueue_onwards..=48).for_each(|slot_number| {
-            ic_cdk_timers::set_timer(
-                post.created_at
-                    .checked_add(Duration::from_secs((slot_number as u64) * 60 * 60))
-                    .unwrap()
-                    .duration_since(*current_time)
-                    .unwrap_or_default(),
-                move || {
-                    CANISTER_DATA.with(|canister_data_ref_cell| {
-                        tabulate_hot_or_not_outcome_for_post_slot(
-                            &mut canister_data_ref_cell.borrow_mut(),
-                            post_id,
-                            slot_number + 1,
-                        );
-                    });
-                },
-            );
+) -> Vec<(u64, u8)> {
+    canister_data
+        .all_created_posts
+        .iter_rev()
+        .take_while(|(_post_id, post)| {
+            was_created_recently_enough_to_be_betting(post, current_time)
+        })
+        .filter(|(_post_id, post)| {
+            post.get_seconds_since_betting_started(current_time)
+                .is_some_and(|seconds_since_betting_started| {
+                    seconds_since_betting_started < 49 * 60 * 60
+                })
+        })
+        .filter_map(|(post_id, post)| {
+            post.hot_or_not_details
+                .map(|hot_or_not_details| (post_id, hot_or_not_details))
+        })
+        .flat_map(|(post_id, hot_or_not_details)| {
+            hot_or_not_details
+                .slot_history
+                .into_iter()
+                .filter(|(_slot_id, slot_details)| slot_details.tabulation_cursor.is_some())
+                .map(move |(slot_id, _slot_details)| (post_id, slot_id))
+        })
+        .collect()
+}
+
+/// Posts are iterated newest first, and a post goes live at most
+/// `MAXIMUM_PUBLISH_DELAY_IN_SECONDS` after it was created, so once a post was created longer
+/// ago than that plus the 49 hours it takes to bet on and tabulate it, so were all before it
+fn was_created_recently_enough_to_be_betting(post: &Post, current_time: &SystemTime) -> bool {
+    current_time
+        .duration_since(post.created_at)
+        .map_or(true, |time_since_creation| {
+            time_since_creation.as_secs() < 49 * 60 * 60 + MAXIMUM_PUBLISH_DELAY_IN_SECONDS
         })
-    }
 }
 
 #[cfg(test)]
 mod test {
-    use std::collections::HashSet;
+    use std::{
+        collections::{BTreeMap, HashSet},
+        time::Duration,
+    };
 
     use shared_utils::canister_specific::individual_user_template::types::{
-        hot_or_not::HotOrNotDetails,
+        hot_or_not::{
+            BetDetails, BetDirection, BetPayout, HotOrNotDetails, RoomBetPossibleOutcomes,
+            RoomDetails, SlotDetails, TabulationCursor,
+        },
         post::{FeedScore, Post, PostStatus, PostViewStatistics},
     };
+    use test_utils::setup::test_constants::{
+        get_mock_user_bob_canister_id, get_mock_user_bob_principal_id,
+    };
 
     use super::*;
 
     #[test]
-    fn test_get_posts_that_have_pending_outcomes_case_1() {
+    fn test_get_post_slots_with_unfinished_tabulation() {
         let mut canister_data = CanisterData::default();
-        let post_0_creation_time = SystemTime::now();
-
-        let post_0 = Post {
-            id: 0,
-            description: "Singing and dancing".to_string(),
-            hashtags: vec!["sing".to_string(), "dance".to_string()],
-            video_uid: "video#0001".to_string(),
-            status: PostStatus::ReadyToView,
-            created_at: post_0_creation_time,
-            likes: HashSet::new(),
-            share_count: 0,
-            view_stats: PostViewStatistics::default(),
-            home_feed_score: FeedScore::default(),
-            creator_consent_for_inclusion_in_hot_or_not: true,
-            hot_or_not_details: Some(HotOrNotDetails::default()),
-        };
-
-        canister_data
-            .all_created_posts
-            .insert(canister_data.all_created_posts.len() as u64, post_0);
-        let current_time = post_0_creation_time
-            .checked_add(Duration::from_secs((48 * 60) * 60))
+        let post_creation_time = SystemTime::now();
+
+        let mut hot_or_not_details = HotOrNotDetails::default();
+        hot_or_not_details
+            .slot_history
+            .insert(1, SlotDetails::default());
+        hot_or_not_details.slot_history.insert(
+            2,
+            SlotDetails {
+                tabulation_cursor: Some(TabulationCursor {
+                    room_id: 3,
+                    last_settled_bet_maker: None,
+                }),
+                ..Default::default()
+            },
+        );
+
+        canister_data.all_created_posts.insert(
+            0,
+            Post {
+                id: 0,
+                description: "Singing and dancing".to_string(),
+                hashtags: vec!["sing".to_string(), "dance".to_string()],
+                video_uid: "video#0001".to_string(),
+                status: PostStatus::ReadyToView,
+                created_at: post_creation_time,
+                likes: HashSet::new(),
+                share_count: 0,
+                view_stats: PostViewStatistics::default(),
+                home_feed_score: FeedScore::default(),
+                creator_consent_for_inclusion_in_hot_or_not: true,
+                hot_or_not_details: Some(hot_or_not_details),
+                minimum_bet_amount: 0,
+                collaborator_shares: vec![],
+                title: None,
+                category: None,
+                boost: None,
+                unresolved_reports: BTreeMap::new(),
+                publish_at: None,
+                is_betting_paused_by_moderator: false,
+            },
+        );
+
+        // * still picked up once the 48 hours are up, as the last slot is tabulated at its end
+        let current_time = post_creation_time
+            .checked_add(Duration::from_secs((48 * 60 + 30) * 60))
             .unwrap();
+        assert_eq!(
+            get_post_slots_with_unfinished_tabulation(&canister_data, &current_time),
+            vec![(0, 2)]
+        );
 
-        let posts_that_have_pending_outcomes =
-            get_posts_that_have_pending_outcomes(&canister_data, &current_time);
-
-        assert_eq!(posts_that_have_pending_outcomes.len(), 0);
-
-        let current_time = post_0_creation_time
-            .checked_add(Duration::from_secs(((48 * 60) - 1) * 60))
-            .unwrap();
-
-        let posts_that_have_pending_outcomes =
-            get_posts_that_have_pending_outcomes(&canister_data, &current_time);
-
-        assert_eq!(posts_that_have_pending_outcomes.len(), 1);
-        assert_eq!(posts_that_have_pending_outcomes[0], 0);
-
-        let current_time = post_0_creation_time
-            .checked_add(Duration::from_secs(((48 * 60) + 1) * 60))
-            .unwrap();
-
-        let posts_that_have_pending_outcomes =
-            get_posts_that_have_pending_outcomes(&canister_data, &current_time);
-
-        assert_eq!(posts_that_have_pending_outcomes.len(), 0);
-
-        let post_1 = Post {
-            id: 1,
-            description: "Singing and dancing".to_string(),
-            hashtags: vec!["sing".to_string(), "dance".to_string()],
-            video_uid: "video#0001".to_string(),
-            status: PostStatus::ReadyToView,
-            created_at: post_0_creation_time
-                .checked_add(Duration::from_secs(60 * 60))
-                .unwrap(),
-            likes: HashSet::new(),
-            share_count: 0,
-            view_stats: PostViewStatistics::default(),
-            home_feed_score: FeedScore::default(),
-            creator_consent_for_inclusion_in_hot_or_not: true,
-            hot_or_not_details: Some(HotOrNotDetails::default()),
-        };
-
-        canister_data
-            .all_created_posts
-            .insert(canister_data.all_created_posts.len() as u64, post_1);
-
-        let current_time = post_0_creation_time
-            .checked_add(Duration::from_secs(((48 * 60) + 1) * 60))
-            .unwrap();
-
-        let posts_that_have_pending_outcomes =
-            get_posts_that_have_pending_outcomes(&canister_data, &current_time);
-
-        assert_eq!(posts_that_have_pending_outcomes.len(), 1);
-        assert_eq!(posts_that_have_pending_outcomes[0], 1);
-
-        let current_time = post_0_creation_time
-            .checked_add(Duration::from_secs((48 * 60) * 60))
+        let current_time = post_creation_time
+            .checked_add(Duration::from_secs(49 * 60 * 60))
             .unwrap();
+        assert!(
+            get_post_slots_with_unfinished_tabulation(&canister_data, &current_time).is_empty()
+        );
+    }
 
-        let posts_that_have_pending_outcomes =
-            get_posts_that_have_pending_outcomes(&canister_data, &current_time);
-
-        assert_eq!(posts_that_have_pending_outcomes.len(), 1);
-        assert_eq!(posts_that_have_pending_outcomes[0], 1);
-
-        let current_time = post_0_creation_time
-            .checked_add(Duration::from_secs(((48 * 60) - 1) * 60))
+    #[test]
+    fn test_get_post_slots_awaiting_tabulation() {
+        let mut canister_data = CanisterData::default();
+        let post_creation_time = SystemTime::now();
+
+        let mut hot_or_not_details = HotOrNotDetails::default();
+        // * closed while its timer was lost
+        hot_or_not_details.slot_history.insert(
+            1,
+            SlotDetails {
+                room_details: BTreeMap::from([(1, RoomDetails::default())]),
+                ..Default::default()
+            },
+        );
+        // * already tabulated
+        hot_or_not_details.slot_history.insert(
+            2,
+            SlotDetails {
+                room_details: BTreeMap::from([(
+                    1,
+                    RoomDetails {
+                        bet_outcome: RoomBetPossibleOutcomes::HotWon,
+                        ..Default::default()
+                    },
+                )]),
+                ..Default::default()
+            },
+        );
+        // * only has bets from shadow banned principals
+        hot_or_not_details.slot_history.insert(
+            3,
+            SlotDetails {
+                phantom_room_bets: BTreeMap::from([(
+                    get_mock_user_bob_principal_id(),
+                    BetDetails {
+                        amount: 10,
+                        bet_direction: BetDirection::Hot,
+                        payout: BetPayout::NotCalculatedYet,
+                        bet_maker_canister_id: get_mock_user_bob_canister_id(),
+                        referrer_canister_id: None,
+                    },
+                )]),
+                ..Default::default()
+            },
+        );
+        // * ran out of budget, picked up from its cursor instead
+        hot_or_not_details.slot_history.insert(
+            4,
+            SlotDetails {
+                room_details: BTreeMap::from([(1, RoomDetails::default())]),
+                tabulation_cursor: Some(TabulationCursor {
+                    room_id: 1,
+                    last_settled_bet_maker: None,
+                }),
+                ..Default::default()
+            },
+        );
+
+        canister_data.all_created_posts.insert(
+            0,
+            Post {
+                id: 0,
+                description: "Singing and dancing".to_string(),
+                hashtags: vec!["sing".to_string(), "dance".to_string()],
+                video_uid: "video#0001".to_string(),
+                status: PostStatus::ReadyToView,
+                created_at: post_creation_time,
+                likes: HashSet::new(),
+                share_count: 0,
+                view_stats: PostViewStatistics::default(),
+                home_feed_score: FeedScore::default(),
+                creator_consent_for_inclusion_in_hot_or_not: true,
+                hot_or_not_details: Some(hot_or_not_details),
+                minimum_bet_amount: 0,
+                collaborator_shares: vec![],
+                title: None,
+                category: None,
+                boost: None,
+                unresolved_reports: BTreeMap::new(),
+                publish_at: None,
+                is_betting_paused_by_moderator: false,
+            },
+        );
+
+        let current_time = post_creation_time
+            .checked_add(Duration::from_secs(5 * 60 * 60))
             .unwrap();
-
-        let posts_that_have_pending_outcomes =
-            get_posts_that_have_pending_outcomes(&canister_data, &current_time);
-
-        assert_eq!(posts_that_have_pending_outcomes.len(), 2);
-        assert_eq!(posts_that_have_pending_outcomes[0], 1);
-        assert_eq!(posts_that_have_pending_outcomes[1], 0);
-
-        let post_2 = Post {
-            id: 2,
-            description: "Singing and dancing".to_string(),
-            hashtags: vec!["sing".to_string(), "dance".to_string()],
-            video_uid: "video#0001".to_string(),
-            status: PostStatus::ReadyToView,
-            created_at: post_0_creation_time
-                .checked_add(Duration::from_secs(((2 * 60) + 5) * 60))
-                .unwrap(),
-            likes: HashSet::new(),
-            share_count: 0,
-            view_stats: PostViewStatistics::default(),
-            home_feed_score: FeedScore::default(),
-            creator_consent_for_inclusion_in_hot_or_not: true,
-            hot_or_not_details: Some(HotOrNotDetails::default()),
-        };
-
-        canister_data
-            .all_created_posts
-            .insert(canister_data.all_created_posts.len() as u64, post_2);
-
-        let current_time = post_0_creation_time
-            .checked_add(Duration::from_secs(((48 * 60) - 1) * 60))
+        assert_eq!(
+            get_post_slots_awaiting_tabulation(&canister_data, &current_time),
+            vec![(0, 1), (0, 3)]
+        );
+
+        // * timers are claimed once, the catch up and a bet racing for the same slot included
+        assert_eq!(
+            claim_slot_tabulation_timer_impl(&mut canister_data, 0, 1, &current_time),
+            Some(Duration::ZERO)
+        );
+        assert_eq!(
+            claim_slot_tabulation_timer_impl(&mut canister_data, 0, 1, &current_time),
+            None
+        );
+
+        let current_time = post_creation_time
+            .checked_add(Duration::from_secs(
+                49 * 60 * 60 + MAXIMUM_PUBLISH_DELAY_IN_SECONDS,
+            ))
             .unwrap();
-
-        let posts_that_have_pending_outcomes =
-            get_posts_that_have_pending_outcomes(&canister_data, &current_time);
-
-        assert_eq!(posts_that_have_pending_outcomes.len(), 3);
-        assert_eq!(posts_that_have_pending_outcomes[0], 2);
-        assert_eq!(posts_that_have_pending_outcomes[1], 1);
-        assert_eq!(posts_that_have_pending_outcomes[2], 0);
+        assert!(get_post_slots_awaiting_tabulation(&canister_data, &current_time).is_empty());
     }
 }
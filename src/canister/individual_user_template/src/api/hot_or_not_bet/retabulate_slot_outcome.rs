@@ -0,0 +1,213 @@
+use std::time::SystemTime;
+
+use candid::Principal;
+use shared_utils::{
+    canister_specific::individual_user_template::types::{
+        error::RetabulateSlotOutcomeError, hot_or_not::SlotId,
+    },
+    common::{
+        types::{app_primitive_type::PostId, known_principal::KnownPrincipalType},
+        utils::{
+            known_caller::authorize_known_caller,
+            system_time::{IcTimeProvider, TimeProvider},
+        },
+    },
+};
+
+use crate::{
+    data_model::CanisterData,
+    util::{
+        call_journal::record_call_in_journal, certified_post_details::recertify_post_details,
+        guards::reject_updates_in_read_only_mode,
+    },
+    CANISTER_DATA,
+};
+
+use super::tabulate_hot_or_not_outcome_for_post_slot::tabulate_hot_or_not_outcome_for_post_slot;
+
+/// Tabulates a closed slot whose rooms are stuck with their bets ongoing, e.g. because its
+/// tabulation timer failed. Tabulation picks up from where it left off if it ran out of budget
+/// before, and rooms that already have an outcome are left alone so no payout is made twice.
+///
+/// # Access Control
+/// Only the global super admin
+#[ic_cdk::update(guard = "reject_updates_in_read_only_mode")]
+#[candid::candid_method(update)]
+fn retabulate_slot_outcome(
+    post_id: PostId,
+    slot_id: SlotId,
+) -> Result<(), RetabulateSlotOutcomeError> {
+    record_call_in_journal("retabulate_slot_outcome");
+
+    let api_caller = ic_cdk::caller();
+
+    CANISTER_DATA.with(|canister_data_ref_cell| {
+        let canister_data = &mut canister_data_ref_cell.borrow_mut();
+
+        check_slot_can_be_retabulated_impl(
+            canister_data,
+            &api_caller,
+            post_id,
+            slot_id,
+            &IcTimeProvider.get_current_time(),
+        )?;
+        tabulate_hot_or_not_outcome_for_post_slot(canister_data, post_id, slot_id, &IcTimeProvider);
+
+        Ok(())
+    })?;
+
+    recertify_post_details(post_id);
+
+    Ok(())
+}
+
+fn check_slot_can_be_retabulated_impl(
+    canister_data: &CanisterData,
+    api_caller: &Principal,
+    post_id: PostId,
+    slot_id: SlotId,
+    current_time: &SystemTime,
+) -> Result<(), RetabulateSlotOutcomeError> {
+    authorize_known_caller(
+        api_caller,
+        &canister_data.known_principal_ids,
+        &[KnownPrincipalType::UserIdGlobalSuperAdmin],
+    )
+    .map_err(|_| RetabulateSlotOutcomeError::Unauthorized)?;
+
+    let post = canister_data
+        .all_created_posts
+        .get(&post_id)
+        .ok_or(RetabulateSlotOutcomeError::PostNotFound)?;
+    let slot_details = post
+        .hot_or_not_details
+        .as_ref()
+        .and_then(|hot_or_not_details| hot_or_not_details.slot_history.get(&slot_id))
+        .ok_or(RetabulateSlotOutcomeError::SlotNotFound)?;
+
+    if *current_time < post.get_slot_closing_time(slot_id) {
+        return Err(RetabulateSlotOutcomeError::SlotStillOpen);
+    }
+
+    if slot_details.is_tabulated() {
+        return Err(RetabulateSlotOutcomeError::SlotAlreadyTabulated);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use shared_utils::canister_specific::individual_user_template::types::{
+        hot_or_not::{BetDirection, HotOrNotPayoutMode},
+        post::{Post, PostDetailsFromFrontend},
+        token::TokenBalance,
+    };
+    use test_utils::setup::{
+        spoofed_callers::{assert_only_allowed_callers_are_accepted, get_mock_known_principal_map},
+        test_constants::{
+            get_global_super_admin_principal_id, get_mock_user_alice_canister_id,
+            get_mock_user_bob_canister_id, get_mock_user_bob_principal_id,
+        },
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_check_slot_can_be_retabulated_impl() {
+        let mut canister_data = CanisterData {
+            known_principal_ids: get_mock_known_principal_map(),
+            ..Default::default()
+        };
+        let admin = get_global_super_admin_principal_id();
+        let post_creation_time = SystemTime::now();
+
+        let mut post = Post::new(
+            0,
+            &PostDetailsFromFrontend {
+                description: "Doggos and puppers".into(),
+                hashtags: vec!["doggo".into(), "pupper".into()],
+                video_uid: "abcd#1234".into(),
+                creator_consent_for_inclusion_in_hot_or_not: true,
+                collaborator_shares: None,
+                title: None,
+                category: None,
+            },
+            &post_creation_time,
+        );
+        let slot_closing_time = post.get_slot_closing_time(1);
+        post.place_hot_or_not_bet(
+            &get_mock_user_bob_principal_id(),
+            &get_mock_user_bob_canister_id(),
+            100,
+            &BetDirection::Hot,
+            &post_creation_time,
+        )
+        .unwrap();
+        canister_data.all_created_posts.insert(0, post.clone());
+
+        assert_only_allowed_callers_are_accepted(
+            &canister_data.known_principal_ids.clone(),
+            &[KnownPrincipalType::UserIdGlobalSuperAdmin],
+            |caller| {
+                check_slot_can_be_retabulated_impl(&canister_data, caller, 0, 1, &slot_closing_time)
+                    .is_ok()
+            },
+        );
+
+        assert_eq!(
+            check_slot_can_be_retabulated_impl(&canister_data, &admin, 1, 1, &slot_closing_time),
+            Err(RetabulateSlotOutcomeError::PostNotFound)
+        );
+        assert_eq!(
+            check_slot_can_be_retabulated_impl(&canister_data, &admin, 0, 2, &slot_closing_time),
+            Err(RetabulateSlotOutcomeError::SlotNotFound)
+        );
+        assert_eq!(
+            check_slot_can_be_retabulated_impl(
+                &canister_data,
+                &admin,
+                0,
+                1,
+                &(slot_closing_time - Duration::from_secs(1)),
+            ),
+            Err(RetabulateSlotOutcomeError::SlotStillOpen)
+        );
+
+        // * a tabulation that ran out of budget can be picked up again
+        let mut token_balance = TokenBalance::default();
+        let settlements = post.tabulate_hot_or_not_outcome_for_slot_within_budget(
+            &get_mock_user_alice_canister_id(),
+            &1,
+            &mut token_balance,
+            0,
+            0,
+            HotOrNotPayoutMode::Multiplier,
+            &slot_closing_time,
+            &|| false,
+        );
+        assert!(settlements.has_more_to_tabulate);
+        canister_data.all_created_posts.insert(0, post.clone());
+        assert_eq!(
+            check_slot_can_be_retabulated_impl(&canister_data, &admin, 0, 1, &slot_closing_time),
+            Ok(())
+        );
+
+        post.tabulate_hot_or_not_outcome_for_slot(
+            &get_mock_user_alice_canister_id(),
+            &1,
+            &mut token_balance,
+            0,
+            0,
+            HotOrNotPayoutMode::Multiplier,
+            &slot_closing_time,
+        );
+        canister_data.all_created_posts.insert(0, post);
+        assert_eq!(
+            check_slot_can_be_retabulated_impl(&canister_data, &admin, 0, 1, &slot_closing_time),
+            Err(RetabulateSlotOutcomeError::SlotAlreadyTabulated)
+        );
+    }
+}
@@ -0,0 +1,102 @@
+use std::time::Duration;
+
+use shared_utils::canister_specific::individual_user_template::types::hot_or_not::outcome_notification::{
+    BetOutcomeNotification, MAX_BET_OUTCOME_NOTIFICATIONS_IN_FLIGHT,
+};
+
+use crate::{data_model::CanisterData, CANISTER_DATA};
+
+/// Returns whether there is anything new to send
+pub fn enqueue_bet_outcome_notifications_impl(
+    canister_data: &mut CanisterData,
+    bet_outcome_notifications: Vec<BetOutcomeNotification>,
+) -> bool {
+    if bet_outcome_notifications.is_empty() {
+        return false;
+    }
+
+    for notification in bet_outcome_notifications {
+        canister_data
+            .bet_outcome_notification_scheduler
+            .enqueue(notification);
+    }
+
+    true
+}
+
+/// Informs bet makers of their outcomes, keeping at most
+/// `MAX_BET_OUTCOME_NOTIFICATIONS_IN_FLIGHT` calls awaiting a response at once. Each delivered
+/// notification frees up room for the next one. Bet makers whose canisters can't be reached
+/// are not retried.
+pub fn send_pending_bet_outcome_notifications() {
+    ic_cdk_timers::set_timer(Duration::ZERO, send_next_batch_of_bet_outcome_notifications);
+}
+
+fn send_next_batch_of_bet_outcome_notifications() {
+    let batch = CANISTER_DATA.with(|canister_data_ref_cell| {
+        canister_data_ref_cell
+            .borrow_mut()
+            .bet_outcome_notification_scheduler
+            .take_next_batch(MAX_BET_OUTCOME_NOTIFICATIONS_IN_FLIGHT)
+    });
+
+    batch.into_iter().for_each(|notification| {
+        ic_cdk::spawn(send_bet_outcome_notification(notification));
+    });
+}
+
+async fn send_bet_outcome_notification(notification: BetOutcomeNotification) {
+    ic_cdk::call::<_, ()>(
+        notification.bet_maker_canister_id,
+        "receive_bet_winnings_when_distributed",
+        (notification.post_id, notification.bet_outcome),
+    )
+    .await
+    .ok();
+
+    CANISTER_DATA.with(|canister_data_ref_cell| {
+        canister_data_ref_cell
+            .borrow_mut()
+            .bet_outcome_notification_scheduler
+            .mark_notification_as_delivered();
+    });
+
+    send_next_batch_of_bet_outcome_notifications();
+}
+
+#[cfg(test)]
+mod test {
+    use shared_utils::canister_specific::individual_user_template::types::hot_or_not::BetOutcomeForBetMaker;
+    use test_utils::setup::test_constants::{
+        get_mock_user_alice_canister_id, get_mock_user_bob_canister_id,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_enqueue_bet_outcome_notifications_impl() {
+        let mut canister_data = CanisterData::default();
+
+        assert!(!enqueue_bet_outcome_notifications_impl(
+            &mut canister_data,
+            vec![]
+        ));
+        assert!(enqueue_bet_outcome_notifications_impl(
+            &mut canister_data,
+            vec![
+                BetOutcomeNotification {
+                    bet_maker_canister_id: get_mock_user_alice_canister_id(),
+                    post_id: 0,
+                    bet_outcome: BetOutcomeForBetMaker::Won(180),
+                },
+                BetOutcomeNotification {
+                    bet_maker_canister_id: get_mock_user_bob_canister_id(),
+                    post_id: 1,
+                    bet_outcome: BetOutcomeForBetMaker::Lost,
+                },
+            ]
+        ));
+
+        assert_eq!(canister_data.bet_outcome_notification_scheduler.len(), 2);
+    }
+}
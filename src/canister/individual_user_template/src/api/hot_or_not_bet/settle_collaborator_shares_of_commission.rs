@@ -0,0 +1,218 @@
+use std::time::{Duration, SystemTime};
+
+use candid::Principal;
+use shared_utils::{
+    canister_specific::individual_user_template::types::collaborator::CollaboratorCommissionSettlement,
+    common::{
+        types::{
+            event_counter::EventCounterName,
+            known_principal::KnownPrincipalType,
+            stable_queue::QUEUED_ITEM_IN_FLIGHT_LEASE,
+            utility_token::token_event::{HotOrNotOutcomePayoutEvent, TokenEvent},
+        },
+        utils::{
+            inter_canister_call,
+            system_time::{IcTimeProvider, TimeProvider},
+        },
+    },
+};
+
+use crate::{data_model::CanisterData, CANISTER_DATA};
+
+/// Queues the collaborators' cuts of the commission from freshly tabulated rooms. Returns
+/// false when there is nothing to settle.
+pub fn enqueue_collaborator_commission_settlements_impl(
+    canister_data: &mut CanisterData,
+    collaborator_commission_settlements: Vec<CollaboratorCommissionSettlement>,
+) -> bool {
+    if collaborator_commission_settlements.is_empty() {
+        return false;
+    }
+
+    for settlement in collaborator_commission_settlements {
+        canister_data
+            .pending_collaborator_commission_settlements
+            .push_back(settlement);
+    }
+
+    true
+}
+
+/// Delivers every queued settlement to the collaborator's canister, looked up through the
+/// user index. Settlements whose calls fail are moved to the back of the queue and retried
+/// on the next drain or after an upgrade. Settlements still out with another drain are
+/// left to it.
+pub fn settle_pending_collaborator_shares_of_commission() {
+    ic_cdk_timers::set_timer(Duration::ZERO, || {
+        ic_cdk::spawn(settle_pending_collaborator_shares())
+    });
+}
+
+async fn settle_pending_collaborator_shares() {
+    let (Some(user_index_canister_id), Some(my_principal_id)) =
+        CANISTER_DATA.with(|canister_data_ref_cell| {
+            let canister_data = canister_data_ref_cell.borrow();
+
+            (
+                canister_data
+                    .known_principal_ids
+                    .get(&KnownPrincipalType::CanisterIdUserIndex)
+                    .cloned(),
+                canister_data.profile.principal_id,
+            )
+        })
+    else {
+        return;
+    };
+
+    let current_time = IcTimeProvider.get_current_time();
+    let pending_settlements: Vec<(u64, CollaboratorCommissionSettlement)> =
+        CANISTER_DATA.with(|canister_data_ref_cell| {
+            canister_data_ref_cell
+                .borrow_mut()
+                .pending_collaborator_commission_settlements
+                .lease(&current_time, QUEUED_ITEM_IN_FLIGHT_LEASE)
+        });
+
+    for (settlement_id, mut settlement) in pending_settlements {
+        let collaborator_canister_id = inter_canister_call::call::<_, (Option<Principal>,)>(
+            user_index_canister_id,
+            "get_user_canister_id_from_user_principal_id",
+            (settlement.collaborator_principal_id,),
+        )
+        .await;
+
+        let response = match collaborator_canister_id {
+            Ok((Some(collaborator_canister_id),)) => {
+                inter_canister_call::call::<_, ()>(
+                    collaborator_canister_id,
+                    "receive_collaborator_share_of_commission",
+                    (settlement.clone(), my_principal_id),
+                )
+                .await
+            }
+            Ok((None,)) => {
+                let current_time = IcTimeProvider.get_current_time();
+                CANISTER_DATA.with(|canister_data_ref_cell| {
+                    return_unclaimable_share_to_creator_impl(
+                        &mut canister_data_ref_cell.borrow_mut(),
+                        settlement_id,
+                        &settlement,
+                        &current_time,
+                    );
+                });
+                continue;
+            }
+            Err(error) => Err(error),
+        };
+
+        CANISTER_DATA.with(|canister_data_ref_cell| {
+            let canister_data = &mut canister_data_ref_cell.borrow_mut();
+
+            match response {
+                Ok(()) => {
+                    canister_data
+                        .pending_collaborator_commission_settlements
+                        .ack(settlement_id);
+                }
+                Err(_) => {
+                    settlement.number_of_failed_attempts += 1;
+                    canister_data
+                        .pending_collaborator_commission_settlements
+                        .requeue(settlement_id, settlement);
+                    canister_data.event_counters.increment(
+                        EventCounterName::SettlementFailuresTotal,
+                        "collaborator_commission",
+                    );
+                }
+            }
+        });
+    }
+}
+
+/// A collaborator without a canister has no way to receive their share, so it is paid out
+/// to the creator as commission instead
+fn return_unclaimable_share_to_creator_impl(
+    canister_data: &mut CanisterData,
+    settlement_id: u64,
+    settlement: &CollaboratorCommissionSettlement,
+    current_time: &SystemTime,
+) {
+    if canister_data
+        .pending_collaborator_commission_settlements
+        .ack(settlement_id)
+        .is_none()
+    {
+        return;
+    }
+
+    canister_data.handle_token_event(TokenEvent::HotOrNotOutcomePayout {
+        amount: settlement.share_amount,
+        details: HotOrNotOutcomePayoutEvent::CommissionFromHotOrNotBet {
+            post_canister_id: settlement.post_canister_id,
+            post_id: settlement.post_id,
+            slot_id: settlement.slot_id,
+            room_id: settlement.room_id,
+            room_pot_total_amount: settlement.room_pot_total_amount,
+        },
+        timestamp: *current_time,
+    });
+}
+
+#[cfg(test)]
+mod test {
+    use test_utils::setup::test_constants::{
+        get_mock_user_alice_canister_id, get_mock_user_bob_principal_id,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_return_unclaimable_share_to_creator_impl() {
+        let mut canister_data = CanisterData::default();
+        let settlement = CollaboratorCommissionSettlement {
+            collaborator_principal_id: get_mock_user_bob_principal_id(),
+            post_canister_id: get_mock_user_alice_canister_id(),
+            post_id: 0,
+            slot_id: 1,
+            room_id: 1,
+            room_pot_total_amount: 250,
+            share_amount: 7,
+            number_of_failed_attempts: 0,
+        };
+
+        assert!(!enqueue_collaborator_commission_settlements_impl(
+            &mut canister_data,
+            vec![]
+        ));
+        assert!(enqueue_collaborator_commission_settlements_impl(
+            &mut canister_data,
+            vec![settlement.clone()]
+        ));
+
+        let (settlement_id, _) = canister_data
+            .pending_collaborator_commission_settlements
+            .peek_front()
+            .unwrap();
+
+        return_unclaimable_share_to_creator_impl(
+            &mut canister_data,
+            settlement_id,
+            &settlement,
+            &SystemTime::now(),
+        );
+        assert_eq!(canister_data.my_token_balance.utility_token_balance, 7);
+        assert!(canister_data
+            .pending_collaborator_commission_settlements
+            .is_empty());
+
+        // * already returned
+        return_unclaimable_share_to_creator_impl(
+            &mut canister_data,
+            settlement_id,
+            &settlement,
+            &SystemTime::now(),
+        );
+        assert_eq!(canister_data.my_token_balance.utility_token_balance, 7);
+    }
+}
@@ -0,0 +1,145 @@
+use std::time::Duration;
+
+use shared_utils::{
+    canister_specific::individual_user_template::types::treasury::HouseRakeSettlement,
+    common::{
+        types::{
+            event_counter::EventCounterName, known_principal::KnownPrincipalType,
+            stable_queue::QUEUED_ITEM_IN_FLIGHT_LEASE,
+        },
+        utils::{inter_canister_call, system_time},
+    },
+};
+
+use crate::{data_model::CanisterData, CANISTER_DATA};
+
+/// Queues the house rake taken from freshly tabulated rooms. Returns false when there is
+/// nothing to settle.
+pub fn enqueue_house_rake_settlements_impl(
+    canister_data: &mut CanisterData,
+    house_rake_settlements: Vec<HouseRakeSettlement>,
+) -> bool {
+    if house_rake_settlements.is_empty() {
+        return false;
+    }
+
+    for settlement in house_rake_settlements {
+        canister_data
+            .pending_house_rake_settlements
+            .push_back(settlement);
+    }
+
+    true
+}
+
+/// Delivers every queued settlement to the treasury canister. Settlements stay in the
+/// queue while no treasury is configured, and are moved to the back of it when the call
+/// fails, to be retried on the next drain or after an upgrade. Settlements still out with
+/// another drain are left to it.
+pub fn settle_pending_house_rake_with_treasury() {
+    ic_cdk_timers::set_timer(
+        Duration::ZERO,
+        || ic_cdk::spawn(settle_pending_house_rake()),
+    );
+}
+
+async fn settle_pending_house_rake() {
+    let Some(treasury_canister_id) = CANISTER_DATA.with(|canister_data_ref_cell| {
+        canister_data_ref_cell
+            .borrow()
+            .known_principal_ids
+            .get(&KnownPrincipalType::CanisterIdTreasury)
+            .cloned()
+    }) else {
+        return;
+    };
+
+    let current_time = system_time::get_current_system_time_from_ic();
+    let pending_settlements: Vec<(u64, HouseRakeSettlement)> =
+        CANISTER_DATA.with(|canister_data_ref_cell| {
+            canister_data_ref_cell
+                .borrow_mut()
+                .pending_house_rake_settlements
+                .lease(&current_time, QUEUED_ITEM_IN_FLIGHT_LEASE)
+        });
+
+    for (settlement_id, mut settlement) in pending_settlements {
+        let response = inter_canister_call::call::<_, ()>(
+            treasury_canister_id,
+            "receive_house_rake_from_hot_or_not_outcome",
+            (settlement.clone(),),
+        )
+        .await;
+
+        CANISTER_DATA.with(|canister_data_ref_cell| {
+            let canister_data = &mut canister_data_ref_cell.borrow_mut();
+
+            match response {
+                Ok(()) => {
+                    canister_data
+                        .pending_house_rake_settlements
+                        .ack(settlement_id);
+                }
+                Err(_) => {
+                    settlement.number_of_failed_attempts += 1;
+                    canister_data
+                        .pending_house_rake_settlements
+                        .requeue(settlement_id, settlement);
+                    canister_data
+                        .event_counters
+                        .increment(EventCounterName::SettlementFailuresTotal, "house_rake");
+                }
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use test_utils::setup::test_constants::get_mock_user_alice_canister_id;
+
+    use super::*;
+
+    fn get_house_rake_settlement(room_id: u64, rake_amount: u64) -> HouseRakeSettlement {
+        HouseRakeSettlement {
+            post_canister_id: get_mock_user_alice_canister_id(),
+            post_id: 0,
+            slot_id: 1,
+            room_id,
+            room_pot_total_amount: rake_amount * 100,
+            rake_amount,
+            number_of_failed_attempts: 0,
+        }
+    }
+
+    #[test]
+    fn test_enqueue_house_rake_settlements_impl() {
+        let mut canister_data = CanisterData::default();
+
+        assert!(!enqueue_house_rake_settlements_impl(
+            &mut canister_data,
+            vec![]
+        ));
+        assert!(enqueue_house_rake_settlements_impl(
+            &mut canister_data,
+            vec![
+                get_house_rake_settlement(1, 35),
+                get_house_rake_settlement(2, 45)
+            ]
+        ));
+        assert!(enqueue_house_rake_settlements_impl(
+            &mut canister_data,
+            vec![get_house_rake_settlement(3, 10)]
+        ));
+
+        assert_eq!(canister_data.pending_house_rake_settlements.len(), 3);
+        assert_eq!(
+            canister_data.pending_house_rake_settlements.get(0),
+            Some(get_house_rake_settlement(1, 35))
+        );
+        assert_eq!(
+            canister_data.pending_house_rake_settlements.get(2),
+            Some(get_house_rake_settlement(3, 10))
+        );
+    }
+}
@@ -0,0 +1,63 @@
+use std::time::Duration;
+
+use shared_utils::{
+    canister_specific::individual_user_template::types::treasury::PotInsuranceTopUpSettlement,
+    common::{
+        types::{event_counter::EventCounterName, stable_queue::QUEUED_ITEM_IN_FLIGHT_LEASE},
+        utils::{
+            inter_canister_call,
+            system_time::{IcTimeProvider, TimeProvider},
+        },
+    },
+};
+
+use crate::CANISTER_DATA;
+
+/// Delivers every top up the treasury has paid out to the winner's canister. Top ups are
+/// moved to the back of the queue when the call fails, to be retried on the next drain or
+/// after an upgrade. Top ups still out with another drain are left to it.
+pub fn settle_pending_pot_insurance_top_ups() {
+    ic_cdk_timers::set_timer(Duration::ZERO, || ic_cdk::spawn(settle_pending_top_ups()));
+}
+
+async fn settle_pending_top_ups() {
+    let current_time = IcTimeProvider.get_current_time();
+    let pending_top_ups: Vec<(u64, PotInsuranceTopUpSettlement)> =
+        CANISTER_DATA.with(|canister_data_ref_cell| {
+            canister_data_ref_cell
+                .borrow_mut()
+                .pending_pot_insurance_top_ups
+                .lease(&current_time, QUEUED_ITEM_IN_FLIGHT_LEASE)
+        });
+
+    for (settlement_id, mut settlement) in pending_top_ups {
+        let response = inter_canister_call::call::<_, ()>(
+            settlement.bet_maker_canister_id,
+            "receive_pot_insurance_top_up",
+            (settlement.clone(),),
+        )
+        .await;
+
+        CANISTER_DATA.with(|canister_data_ref_cell| {
+            let canister_data = &mut canister_data_ref_cell.borrow_mut();
+
+            match response {
+                Ok(()) => {
+                    canister_data
+                        .pending_pot_insurance_top_ups
+                        .ack(settlement_id);
+                }
+                Err(_) => {
+                    settlement.number_of_failed_attempts += 1;
+                    canister_data
+                        .pending_pot_insurance_top_ups
+                        .requeue(settlement_id, settlement);
+                    canister_data.event_counters.increment(
+                        EventCounterName::SettlementFailuresTotal,
+                        "pot_insurance_top_up",
+                    );
+                }
+            }
+        });
+    }
+}
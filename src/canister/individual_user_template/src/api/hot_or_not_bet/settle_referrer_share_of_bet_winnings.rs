@@ -0,0 +1,136 @@
+use std::time::Duration;
+
+use shared_utils::{
+    canister_specific::individual_user_template::types::referral::ReferrerShareSettlement,
+    common::{
+        types::{event_counter::EventCounterName, stable_queue::QUEUED_ITEM_IN_FLIGHT_LEASE},
+        utils::{
+            inter_canister_call,
+            system_time::{IcTimeProvider, TimeProvider},
+        },
+    },
+};
+
+use crate::{data_model::CanisterData, CANISTER_DATA};
+
+/// Queues the referrers' shares of the winnings of freshly tabulated rooms. Returns false
+/// when there is nothing to settle.
+pub fn enqueue_referrer_share_settlements_impl(
+    canister_data: &mut CanisterData,
+    referrer_share_settlements: Vec<ReferrerShareSettlement>,
+) -> bool {
+    if referrer_share_settlements.is_empty() {
+        return false;
+    }
+
+    for settlement in referrer_share_settlements {
+        canister_data
+            .pending_referrer_share_settlements
+            .push_back(settlement);
+    }
+
+    true
+}
+
+/// Delivers every queued settlement to the referrer's canister. Settlements whose call
+/// fails are moved to the back of the queue and retried on the next drain or after an
+/// upgrade. Settlements still out with another drain are left to it.
+pub fn settle_pending_referrer_shares_of_bet_winnings() {
+    ic_cdk_timers::set_timer(Duration::ZERO, || {
+        ic_cdk::spawn(settle_pending_referrer_shares())
+    });
+}
+
+async fn settle_pending_referrer_shares() {
+    let Some(my_principal_id) = CANISTER_DATA
+        .with(|canister_data_ref_cell| canister_data_ref_cell.borrow().profile.principal_id)
+    else {
+        return;
+    };
+
+    let current_time = IcTimeProvider.get_current_time();
+    let pending_settlements: Vec<(u64, ReferrerShareSettlement)> =
+        CANISTER_DATA.with(|canister_data_ref_cell| {
+            canister_data_ref_cell
+                .borrow_mut()
+                .pending_referrer_share_settlements
+                .lease(&current_time, QUEUED_ITEM_IN_FLIGHT_LEASE)
+        });
+
+    for (settlement_id, mut settlement) in pending_settlements {
+        let response = inter_canister_call::call::<_, ()>(
+            settlement.referrer_canister_id,
+            "receive_referrer_share_of_referee_bet_winnings",
+            (settlement.clone(), my_principal_id),
+        )
+        .await;
+
+        CANISTER_DATA.with(|canister_data_ref_cell| {
+            let canister_data = &mut canister_data_ref_cell.borrow_mut();
+
+            match response {
+                Ok(()) => {
+                    canister_data
+                        .pending_referrer_share_settlements
+                        .ack(settlement_id);
+                }
+                Err(_) => {
+                    settlement.number_of_failed_attempts += 1;
+                    canister_data
+                        .pending_referrer_share_settlements
+                        .requeue(settlement_id, settlement);
+                    canister_data
+                        .event_counters
+                        .increment(EventCounterName::SettlementFailuresTotal, "referrer_share");
+                }
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use test_utils::setup::test_constants::{
+        get_mock_user_alice_canister_id, get_mock_user_bob_canister_id,
+        get_mock_user_bob_principal_id, get_mock_user_charlie_canister_id,
+    };
+
+    use super::*;
+
+    fn get_referrer_share_settlement(room_id: u64, share_amount: u64) -> ReferrerShareSettlement {
+        ReferrerShareSettlement {
+            referrer_canister_id: get_mock_user_alice_canister_id(),
+            referee_principal_id: get_mock_user_bob_principal_id(),
+            referee_canister_id: Some(get_mock_user_bob_canister_id()),
+            post_canister_id: get_mock_user_charlie_canister_id(),
+            post_id: 0,
+            slot_id: 1,
+            room_id,
+            share_amount,
+            number_of_failed_attempts: 0,
+        }
+    }
+
+    #[test]
+    fn test_enqueue_referrer_share_settlements_impl() {
+        let mut canister_data = CanisterData::default();
+
+        assert!(!enqueue_referrer_share_settlements_impl(
+            &mut canister_data,
+            vec![]
+        ));
+        assert!(enqueue_referrer_share_settlements_impl(
+            &mut canister_data,
+            vec![
+                get_referrer_share_settlement(1, 18),
+                get_referrer_share_settlement(2, 9)
+            ]
+        ));
+
+        assert_eq!(canister_data.pending_referrer_share_settlements.len(), 2);
+        assert_eq!(
+            canister_data.pending_referrer_share_settlements.get(0),
+            Some(get_referrer_share_settlement(1, 18))
+        );
+    }
+}
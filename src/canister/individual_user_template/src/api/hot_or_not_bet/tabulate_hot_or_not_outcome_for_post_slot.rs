@@ -1,59 +1,295 @@
-use candid::Principal;
+use std::time::{Duration, SystemTime};
+
 use shared_utils::{
-    canister_specific::individual_user_template::types::{
-        hot_or_not::{BetDirection, BetOutcomeForBetMaker, BetPayout, RoomBetPossibleOutcomes},
-        post::Post,
+    canister_specific::{
+        individual_user_template::types::{
+            hot_or_not::{
+                outcome_notification::BetOutcomeNotification, to_token_amount, BetDirection,
+                BetOutcomeForBetMaker, BetPayout, RoomBetPossibleOutcomes, SlotDetails, SlotId,
+            },
+            live_betting_activity::LiveBettingActivityEvent,
+            post::Post,
+            token::TokenBalance,
+        },
+        leaderboard::types::leaderboard::{
+            BettorLeaderboardDelta, LeaderboardBetOutcome, SlotLeaderboardDeltas,
+        },
+    },
+    common::{
+        types::{
+            app_primitive_type::PostId, known_principal::KnownPrincipalType,
+            utility_token::token_event::HOT_OR_NOT_BET_CREATOR_COMMISSION_PERCENTAGE,
+        },
+        utils::system_time::{IcTimeProvider, TimeProvider},
+    },
+};
+
+use crate::{
+    data_model::CanisterData,
+    util::{
+        call_queue::queue_call_impl,
+        certified_post_details::recertify_post_details,
+        live_betting_activity::{
+            publish_pending_live_betting_activity, queue_live_betting_activity_impl,
+        },
     },
-    common::utils::system_time,
+    CANISTER_DATA,
 };
 
-use crate::data_model::CanisterData;
+use super::{
+    claim_pot_insurance_from_treasury::{
+        claim_pending_pot_insurance_from_treasury, enqueue_pot_insurance_claims_impl,
+    },
+    send_bet_outcome_notifications::{
+        enqueue_bet_outcome_notifications_impl, send_pending_bet_outcome_notifications,
+    },
+    settle_collaborator_shares_of_commission::{
+        enqueue_collaborator_commission_settlements_impl,
+        settle_pending_collaborator_shares_of_commission,
+    },
+    settle_house_rake_with_treasury::{
+        enqueue_house_rake_settlements_impl, settle_pending_house_rake_with_treasury,
+    },
+    settle_referrer_share_of_bet_winnings::{
+        enqueue_referrer_share_settlements_impl, settle_pending_referrer_shares_of_bet_winnings,
+    },
+};
 
+// * Well under the per message instruction limit, leaving room for queueing up settlements
+// * and informing participants once the last room is done
+const TABULATION_INSTRUCTION_BUDGET: u64 = 5_000_000_000;
+
+/// Settles the rooms of the slot still awaiting an outcome. Does nothing for a slot that is
+/// already tabulated, so it is safe to call again for the same slot.
 pub fn tabulate_hot_or_not_outcome_for_post_slot(
     canister_data: &mut CanisterData,
     post_id: u64,
     slot_id: u8,
+    time_provider: &impl TimeProvider,
 ) {
-    let current_time = system_time::get_current_system_time_from_ic();
+    let current_time = time_provider.get_current_time();
     let this_canister_id = ic_cdk::id();
+    let house_rake_percentage = canister_data.configuration.house_rake_percentage;
+    let referrer_share_percentage = canister_data
+        .configuration
+        .referrer_share_of_bet_winnings_percentage;
+    let payout_mode = canister_data.configuration.hot_or_not_payout_mode;
+    let pot_insurance_config = canister_data.configuration.pot_insurance_config.clone();
+
+    let mut post_to_tabulate_results_for = canister_data.all_created_posts.get(&post_id).unwrap();
+
+    // * Settlements, notifications and leaderboard deltas are worked out for every room in
+    // * the slot, so a slot that is already tabulated must not go through this again
+    if post_to_tabulate_results_for
+        .hot_or_not_details
+        .as_ref()
+        .and_then(|hot_or_not_details| hot_or_not_details.slot_history.get(&slot_id))
+        .is_none_or(SlotDetails::is_tabulated)
+    {
+        return;
+    }
 
-    let post_to_tabulate_results_for = canister_data.all_created_posts.get_mut(&post_id).unwrap();
-    let token_balance = &mut canister_data.my_token_balance;
+    // * Commission is paid out into a scratch balance and its events replayed onto this
+    // * profile afterwards, so that they land in the stable transaction history
+    let mut commission_token_balance = TokenBalance::default();
 
-    post_to_tabulate_results_for.tabulate_hot_or_not_outcome_for_slot(
-        &this_canister_id,
-        &slot_id,
-        token_balance,
-        &current_time,
-    );
+    let settlements = post_to_tabulate_results_for
+        .tabulate_hot_or_not_outcome_for_slot_within_budget(
+            &this_canister_id,
+            &slot_id,
+            &mut commission_token_balance,
+            house_rake_percentage,
+            referrer_share_percentage,
+            payout_mode,
+            &current_time,
+            &|| ic_cdk::api::instruction_counter() < TABULATION_INSTRUCTION_BUDGET,
+        );
 
-    inform_participants_of_outcome(post_to_tabulate_results_for, &slot_id);
+    commission_token_balance
+        .utility_token_transaction_history
+        .into_values()
+        .for_each(|token_event| canister_data.handle_token_event(token_event));
+
+    let (
+        bet_outcome_notifications,
+        pot_insurance_claims,
+        slot_leaderboard_deltas,
+        slot_settled_event,
+    ) = if settlements.has_more_to_tabulate {
+        enqueue_timer_for_continuing_tabulation(post_id, slot_id);
+        (vec![], vec![], None, None)
+    } else {
+        (
+            get_bet_outcome_notifications(&post_to_tabulate_results_for, &slot_id),
+            if pot_insurance_config.is_enabled() {
+                post_to_tabulate_results_for.get_pot_insurance_claims_for_slot(
+                    &this_canister_id,
+                    &slot_id,
+                    pot_insurance_config.room_pot_floor,
+                    &current_time,
+                )
+            } else {
+                vec![]
+            },
+            get_slot_leaderboard_deltas(&post_to_tabulate_results_for, &slot_id),
+            get_slot_settled_event(&post_to_tabulate_results_for, &slot_id),
+        )
+    };
+    canister_data
+        .all_created_posts
+        .insert(post_id, post_to_tabulate_results_for);
+
+    if enqueue_bet_outcome_notifications_impl(canister_data, bet_outcome_notifications) {
+        send_pending_bet_outcome_notifications();
+    }
+
+    if enqueue_house_rake_settlements_impl(canister_data, settlements.house_rake_settlements) {
+        settle_pending_house_rake_with_treasury();
+    }
+
+    if enqueue_referrer_share_settlements_impl(
+        canister_data,
+        settlements.referrer_share_settlements,
+    ) {
+        settle_pending_referrer_shares_of_bet_winnings();
+    }
+
+    if enqueue_pot_insurance_claims_impl(canister_data, pot_insurance_claims) {
+        claim_pending_pot_insurance_from_treasury();
+    }
+
+    if enqueue_collaborator_commission_settlements_impl(
+        canister_data,
+        settlements.collaborator_commission_settlements,
+    ) {
+        settle_pending_collaborator_shares_of_commission();
+    }
+
+    if let Some(slot_settled_event) = slot_settled_event {
+        if queue_live_betting_activity_impl(
+            canister_data,
+            &this_canister_id,
+            post_id,
+            slot_settled_event,
+            &current_time,
+        ) {
+            publish_pending_live_betting_activity();
+        }
+    }
+
+    if let (Some(slot_leaderboard_deltas), Some(leaderboard_canister_id)) = (
+        slot_leaderboard_deltas,
+        canister_data
+            .known_principal_ids
+            .get(&KnownPrincipalType::CanisterIdLeaderboard)
+            .cloned(),
+    ) {
+        queue_call_impl(
+            canister_data,
+            leaderboard_canister_id,
+            "receive_slot_leaderboard_deltas",
+            (slot_leaderboard_deltas,),
+            &current_time,
+        );
+    }
 }
 
-fn inform_participants_of_outcome(post: &Post, slot_id: &u8) {
-    let hot_or_not_details = post.hot_or_not_details.as_ref();
+/// Claims the tabulation timer of a slot, which the first bet in it or the catch up after an
+/// upgrade sets. Returns how long until the slot closes, or None if the slot already has a
+/// timer set.
+pub fn claim_slot_tabulation_timer_impl(
+    canister_data: &mut CanisterData,
+    post_id: PostId,
+    slot_id: SlotId,
+    current_time: &SystemTime,
+) -> Option<Duration> {
+    let post = canister_data.all_created_posts.get(&post_id)?;
 
-    if hot_or_not_details.is_none() {
-        return;
+    if !canister_data
+        .slots_with_tabulation_timer
+        .insert((post_id, slot_id))
+    {
+        return None;
     }
 
-    let slot_details = hot_or_not_details.unwrap().slot_history.get(slot_id);
+    // * slots that closed while no timer was set are tabulated right away
+    Some(
+        post.get_slot_closing_time(slot_id)
+            .duration_since(*current_time)
+            .unwrap_or_default(),
+    )
+}
+
+/// Tabulates the slot once it closes. Pair with [`claim_slot_tabulation_timer_impl`] so that
+/// the slot is only tabulated once.
+pub fn enqueue_timer_for_slot_tabulation(post_id: PostId, slot_id: SlotId, delay: Duration) {
+    ic_cdk_timers::set_timer(delay, move || {
+        CANISTER_DATA.with(|canister_data_ref_cell| {
+            let canister_data = &mut canister_data_ref_cell.borrow_mut();
 
-    if slot_details.is_none() {
-        return;
+            canister_data
+                .slots_with_tabulation_timer
+                .remove(&(post_id, slot_id));
+            tabulate_hot_or_not_outcome_for_post_slot(
+                canister_data,
+                post_id,
+                slot_id,
+                &IcTimeProvider,
+            );
+        });
+        recertify_post_details(post_id);
+    });
+}
+
+/// Picks up a slot whose tabulation ran out of budget, from the cursor it left behind
+pub fn enqueue_timer_for_continuing_tabulation(post_id: u64, slot_id: u8) {
+    ic_cdk_timers::set_timer(Duration::ZERO, move || {
+        CANISTER_DATA.with(|canister_data_ref_cell| {
+            tabulate_hot_or_not_outcome_for_post_slot(
+                &mut canister_data_ref_cell.borrow_mut(),
+                post_id,
+                slot_id,
+                &IcTimeProvider,
+            );
+        });
+        recertify_post_details(post_id);
+    });
+}
+
+fn get_bet_outcome_notifications(post: &Post, slot_id: &u8) -> Vec<BetOutcomeNotification> {
+    let Some(slot_details) = post
+        .hot_or_not_details
+        .as_ref()
+        .and_then(|hot_or_not_details| hot_or_not_details.slot_history.get(slot_id))
+    else {
+        return vec![];
+    };
+
+    let mut bet_outcome_notifications = vec![];
+
+    for bet in slot_details.phantom_room_bets.values() {
+        if matches!(bet.payout, BetPayout::NotCalculatedYet) {
+            continue;
+        }
+
+        bet_outcome_notifications.push(BetOutcomeNotification {
+            bet_maker_canister_id: bet.bet_maker_canister_id,
+            post_id: post.id,
+            bet_outcome: BetOutcomeForBetMaker::Lost,
+        });
     }
 
-    for (_room_id, room_detail) in slot_details.unwrap().room_details.iter() {
-        for (_participant, bet) in room_detail.bets_made.iter() {
+    for room_detail in slot_details.room_details.values() {
+        for bet in room_detail.bets_made.values() {
             let bet_outcome_for_bet_maker: BetOutcomeForBetMaker = match room_detail.bet_outcome {
                 RoomBetPossibleOutcomes::BetOngoing => BetOutcomeForBetMaker::AwaitingResult,
                 RoomBetPossibleOutcomes::Draw => BetOutcomeForBetMaker::Draw(match bet.payout {
-                    BetPayout::Calculated(amount) => amount,
+                    BetPayout::Calculated(amount) => to_token_amount(amount),
                     _ => 0,
                 }),
                 RoomBetPossibleOutcomes::HotWon => match bet.bet_direction {
                     BetDirection::Hot => BetOutcomeForBetMaker::Won(match bet.payout {
-                        BetPayout::Calculated(amount) => amount,
+                        BetPayout::Calculated(amount) => to_token_amount(amount),
                         _ => 0,
                     }),
                     BetDirection::Not => BetOutcomeForBetMaker::Lost,
@@ -61,7 +297,7 @@ fn inform_participants_of_outcome(post: &Post, slot_id: &u8) {
                 RoomBetPossibleOutcomes::NotWon => match bet.bet_direction {
                     BetDirection::Hot => BetOutcomeForBetMaker::Lost,
                     BetDirection::Not => BetOutcomeForBetMaker::Won(match bet.payout {
-                        BetPayout::Calculated(amount) => amount,
+                        BetPayout::Calculated(amount) => to_token_amount(amount),
                         _ => 0,
                     }),
                 },
@@ -71,25 +307,264 @@ fn inform_participants_of_outcome(post: &Post, slot_id: &u8) {
                 continue;
             }
 
-            ic_cdk::spawn(receive_bet_winnings_when_distributed(
-                bet.bet_maker_canister_id,
-                post.id,
-                bet_outcome_for_bet_maker,
-            ));
+            bet_outcome_notifications.push(BetOutcomeNotification {
+                bet_maker_canister_id: bet.bet_maker_canister_id,
+                post_id: post.id,
+                bet_outcome: bet_outcome_for_bet_maker,
+            });
         }
     }
+
+    bet_outcome_notifications
 }
 
-async fn receive_bet_winnings_when_distributed(
-    bet_maker_canister_id: Principal,
-    post_id: u64,
-    bet_outcome_for_bet_maker: BetOutcomeForBetMaker,
-) {
-    ic_cdk::call::<_, ()>(
-        bet_maker_canister_id,
-        "receive_bet_winnings_when_distributed",
-        (post_id, bet_outcome_for_bet_maker),
-    )
-    .await
-    .ok();
+fn get_slot_settled_event(post: &Post, slot_id: &u8) -> Option<LiveBettingActivityEvent> {
+    let slot_details = post
+        .hot_or_not_details
+        .as_ref()
+        .and_then(|hot_or_not_details| hot_or_not_details.slot_history.get(slot_id))?;
+
+    Some(LiveBettingActivityEvent::SlotSettled {
+        slot_id: *slot_id,
+        room_outcomes: slot_details
+            .room_details
+            .iter()
+            .map(|(room_id, room_detail)| (*room_id, room_detail.bet_outcome.clone()))
+            .collect(),
+    })
+}
+
+/// Stakes and payouts of the settled rooms of a slot, for the leaderboard canister. Bets of
+/// shadow banned principals are left out, as they never stood a chance of winning.
+fn get_slot_leaderboard_deltas(post: &Post, slot_id: &u8) -> Option<SlotLeaderboardDeltas> {
+    let slot_details = post
+        .hot_or_not_details
+        .as_ref()
+        .and_then(|hot_or_not_details| hot_or_not_details.slot_history.get(slot_id))?;
+
+    let mut room_pots_total_amount: u128 = 0;
+    let mut bettor_deltas = vec![];
+
+    for room_detail in slot_details.room_details.values() {
+        if room_detail.bet_outcome == RoomBetPossibleOutcomes::BetOngoing {
+            continue;
+        }
+        room_pots_total_amount += room_detail.room_bets_total_pot;
+
+        for bet in room_detail.bets_made.values() {
+            let outcome = match (&room_detail.bet_outcome, &bet.bet_direction) {
+                (RoomBetPossibleOutcomes::Draw, _) => LeaderboardBetOutcome::Draw,
+                (RoomBetPossibleOutcomes::HotWon, BetDirection::Hot)
+                | (RoomBetPossibleOutcomes::NotWon, BetDirection::Not) => {
+                    LeaderboardBetOutcome::Won
+                }
+                _ => LeaderboardBetOutcome::Lost,
+            };
+
+            bettor_deltas.push(BettorLeaderboardDelta {
+                bet_maker_canister_id: bet.bet_maker_canister_id,
+                amount_staked: bet.amount,
+                amount_paid_out: match bet.payout {
+                    BetPayout::Calculated(amount) => to_token_amount(amount),
+                    BetPayout::NotCalculatedYet => 0,
+                },
+                outcome,
+            });
+        }
+    }
+
+    if bettor_deltas.is_empty() {
+        return None;
+    }
+
+    Some(SlotLeaderboardDeltas {
+        post_id: post.id,
+        slot_id: *slot_id,
+        creator_commission: to_token_amount(
+            room_pots_total_amount * HOT_OR_NOT_BET_CREATOR_COMMISSION_PERCENTAGE as u128 / 100,
+        ),
+        bettor_deltas,
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use shared_utils::canister_specific::individual_user_template::types::{
+        hot_or_not::HotOrNotPayoutMode, post::PostDetailsFromFrontend, token::TokenBalance,
+    };
+    use test_utils::setup::test_constants::{
+        get_mock_user_alice_canister_id, get_mock_user_bob_canister_id,
+        get_mock_user_bob_principal_id, get_mock_user_charlie_canister_id,
+        get_mock_user_charlie_principal_id, get_mock_user_dan_canister_id,
+        get_mock_user_dan_principal_id,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_claim_slot_tabulation_timer_impl() {
+        let mut canister_data = CanisterData::default();
+        let created_at = SystemTime::now();
+        canister_data.all_created_posts.insert(
+            0,
+            Post::new(
+                0,
+                &PostDetailsFromFrontend {
+                    description: "Doggos and puppers".into(),
+                    hashtags: vec!["doggo".into(), "pupper".into()],
+                    video_uid: "abcd#1234".into(),
+                    creator_consent_for_inclusion_in_hot_or_not: true,
+                    collaborator_shares: None,
+                    title: None,
+                    category: None,
+                },
+                &created_at,
+            ),
+        );
+        let current_time = created_at + Duration::from_secs(90 * 60);
+
+        assert_eq!(
+            claim_slot_tabulation_timer_impl(&mut canister_data, 1, 2, &current_time),
+            None
+        );
+        assert_eq!(
+            claim_slot_tabulation_timer_impl(&mut canister_data, 0, 2, &current_time),
+            Some(Duration::from_secs(30 * 60))
+        );
+        // * every slot only gets the one timer
+        assert_eq!(
+            claim_slot_tabulation_timer_impl(&mut canister_data, 0, 2, &current_time),
+            None
+        );
+        // * slots already closed are caught up on right away
+        assert_eq!(
+            claim_slot_tabulation_timer_impl(&mut canister_data, 0, 1, &current_time),
+            Some(Duration::ZERO)
+        );
+    }
+
+    #[test]
+    fn test_get_bet_outcome_notifications() {
+        let created_at = SystemTime::now();
+        let mut post = Post::new(
+            0,
+            &PostDetailsFromFrontend {
+                description: "Doggos and puppers".into(),
+                hashtags: vec!["doggo".into(), "pupper".into()],
+                video_uid: "abcd#1234".into(),
+                creator_consent_for_inclusion_in_hot_or_not: true,
+                collaborator_shares: None,
+                title: None,
+                category: None,
+            },
+            &created_at,
+        );
+        post.place_hot_or_not_bet(
+            &get_mock_user_bob_principal_id(),
+            &get_mock_user_bob_canister_id(),
+            100,
+            &BetDirection::Hot,
+            &created_at,
+        )
+        .unwrap();
+
+        // * bets still awaiting a result aren't notified
+        assert!(get_bet_outcome_notifications(&post, &1).is_empty());
+        assert!(get_bet_outcome_notifications(&post, &2).is_empty());
+
+        let settled_at = post.get_slot_settled_at(1);
+        post.tabulate_hot_or_not_outcome_for_slot(
+            &get_mock_user_alice_canister_id(),
+            &1,
+            &mut TokenBalance::default(),
+            0,
+            0,
+            HotOrNotPayoutMode::Multiplier,
+            &settled_at,
+        );
+        let bet_outcome_notifications = get_bet_outcome_notifications(&post, &1);
+
+        assert_eq!(bet_outcome_notifications.len(), 1);
+        assert_eq!(
+            bet_outcome_notifications[0].bet_maker_canister_id,
+            get_mock_user_bob_canister_id()
+        );
+        assert_eq!(bet_outcome_notifications[0].post_id, 0);
+        assert_ne!(
+            bet_outcome_notifications[0].bet_outcome,
+            BetOutcomeForBetMaker::AwaitingResult
+        );
+    }
+
+    #[test]
+    fn test_get_slot_leaderboard_deltas() {
+        let created_at = SystemTime::now();
+        let mut post = Post::new(
+            0,
+            &PostDetailsFromFrontend {
+                description: "Doggos and puppers".into(),
+                hashtags: vec!["doggo".into(), "pupper".into()],
+                video_uid: "abcd#1234".into(),
+                creator_consent_for_inclusion_in_hot_or_not: true,
+                collaborator_shares: None,
+                title: None,
+                category: None,
+            },
+            &created_at,
+        );
+        post.place_hot_or_not_bet(
+            &get_mock_user_bob_principal_id(),
+            &get_mock_user_bob_canister_id(),
+            100,
+            &BetDirection::Hot,
+            &created_at,
+        )
+        .unwrap();
+        post.place_hot_or_not_bet(
+            &get_mock_user_charlie_principal_id(),
+            &get_mock_user_charlie_canister_id(),
+            200,
+            &BetDirection::Not,
+            &created_at,
+        )
+        .unwrap();
+        post.place_hot_or_not_bet(
+            &get_mock_user_dan_principal_id(),
+            &get_mock_user_dan_canister_id(),
+            100,
+            &BetDirection::Not,
+            &created_at,
+        )
+        .unwrap();
+
+        // * nothing to report until the room is settled
+        assert_eq!(get_slot_leaderboard_deltas(&post, &1), None);
+
+        let settled_at = post.get_slot_settled_at(1);
+        post.tabulate_hot_or_not_outcome_for_slot(
+            &get_mock_user_alice_canister_id(),
+            &1,
+            &mut TokenBalance::default(),
+            0,
+            0,
+            HotOrNotPayoutMode::Multiplier,
+            &settled_at,
+        );
+        let slot_leaderboard_deltas = get_slot_leaderboard_deltas(&post, &1).unwrap();
+
+        assert_eq!(slot_leaderboard_deltas.post_id, 0);
+        assert_eq!(slot_leaderboard_deltas.slot_id, 1);
+        assert_eq!(slot_leaderboard_deltas.creator_commission, 40);
+        assert_eq!(slot_leaderboard_deltas.bettor_deltas.len(), 3);
+        let charlies_delta = slot_leaderboard_deltas
+            .bettor_deltas
+            .iter()
+            .find(|bettor_delta| {
+                bettor_delta.bet_maker_canister_id == get_mock_user_charlie_canister_id()
+            })
+            .unwrap();
+        assert_eq!(charlies_delta.amount_staked, 200);
+        assert_eq!(charlies_delta.outcome, LeaderboardBetOutcome::Won);
+        assert!(charlies_delta.amount_paid_out > 200);
+    }
 }
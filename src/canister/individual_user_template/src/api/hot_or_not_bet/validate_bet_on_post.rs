@@ -0,0 +1,140 @@
+use std::time::SystemTime;
+
+use candid::Principal;
+use shared_utils::{
+    canister_specific::individual_user_template::types::{
+        error::BetOnCurrentlyViewingPostError, hot_or_not::BettingStatus,
+    },
+    common::{
+        types::app_primitive_type::PostId,
+        utils::system_time::{IcTimeProvider, TimeProvider},
+    },
+};
+
+use crate::{
+    data_model::CanisterData, util::canister_binding::is_expected_binding_epoch, CANISTER_DATA,
+};
+
+/// Runs the checks `receive_bet_from_bet_makers_canister` would run on a bet, without placing
+/// it. A post that doesn't exist is reported as closed to betting.
+#[ic_cdk::query]
+#[candid::candid_method(query)]
+fn validate_bet_on_post(
+    post_id: PostId,
+    bet_maker_principal_id: Principal,
+    bet_amount: u64,
+    expected_binding_epoch: Option<u64>,
+) -> Result<BettingStatus, BetOnCurrentlyViewingPostError> {
+    CANISTER_DATA.with(|canister_data_ref_cell| {
+        validate_bet_on_post_impl(
+            &canister_data_ref_cell.borrow(),
+            post_id,
+            &bet_maker_principal_id,
+            bet_amount,
+            expected_binding_epoch,
+            &IcTimeProvider.get_current_time(),
+        )
+    })
+}
+
+fn validate_bet_on_post_impl(
+    canister_data: &CanisterData,
+    post_id: PostId,
+    bet_maker_principal_id: &Principal,
+    bet_amount: u64,
+    expected_binding_epoch: Option<u64>,
+    current_time: &SystemTime,
+) -> Result<BettingStatus, BetOnCurrentlyViewingPostError> {
+    if !is_expected_binding_epoch(canister_data, expected_binding_epoch) {
+        return Err(BetOnCurrentlyViewingPostError::StaleCanisterBinding);
+    }
+
+    canister_data
+        .all_created_posts
+        .get(&post_id)
+        .ok_or(BetOnCurrentlyViewingPostError::BettingClosed)?
+        .validate_hot_or_not_bet(bet_maker_principal_id, bet_amount, current_time)
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use shared_utils::{
+        canister_specific::individual_user_template::types::post::{Post, PostDetailsFromFrontend},
+        common::utils::system_time::MockTimeProvider,
+    };
+    use test_utils::setup::test_constants::get_mock_user_alice_principal_id;
+
+    use super::*;
+
+    #[test]
+    fn test_validate_bet_on_post_impl() {
+        let mut canister_data = CanisterData::default();
+        let time_provider = MockTimeProvider::new(SystemTime::now());
+        let current_time = time_provider.get_current_time();
+        canister_data.binding_epoch = 1;
+        canister_data.all_created_posts.insert(
+            0,
+            Post::new(
+                0,
+                &PostDetailsFromFrontend {
+                    description: "Doggos and puppers".into(),
+                    hashtags: vec!["doggo".into(), "pupper".into()],
+                    video_uid: "abcd#1234".into(),
+                    creator_consent_for_inclusion_in_hot_or_not: true,
+                    collaborator_shares: None,
+                    title: None,
+                    category: None,
+                },
+                &current_time,
+            ),
+        );
+
+        assert_eq!(
+            validate_bet_on_post_impl(
+                &canister_data,
+                0,
+                &get_mock_user_alice_principal_id(),
+                100,
+                Some(0),
+                &current_time
+            ),
+            Err(BetOnCurrentlyViewingPostError::StaleCanisterBinding)
+        );
+        assert_eq!(
+            validate_bet_on_post_impl(
+                &canister_data,
+                1,
+                &get_mock_user_alice_principal_id(),
+                100,
+                Some(1),
+                &current_time
+            ),
+            Err(BetOnCurrentlyViewingPostError::BettingClosed)
+        );
+        assert!(validate_bet_on_post_impl(
+            &canister_data,
+            0,
+            &get_mock_user_alice_principal_id(),
+            100,
+            Some(1),
+            &current_time
+        )
+        .is_ok());
+
+        // * betting closes once the last slot is over
+        time_provider.advance_by(Duration::from_secs(49 * 60 * 60));
+        assert_eq!(
+            validate_bet_on_post_impl(
+                &canister_data,
+                0,
+                &get_mock_user_alice_principal_id(),
+                100,
+                Some(1),
+                &time_provider.get_current_time()
+            ),
+            Err(BetOnCurrentlyViewingPostError::BettingClosed)
+        );
+    }
+}
@@ -1,9 +1,26 @@
+pub mod access_statistics;
 pub mod backup_and_restore;
+#[cfg(feature = "call_failure_injection")]
+pub mod call_failure_injection;
+pub mod call_journal;
+pub mod call_queue;
 pub mod canister_lifecycle;
+pub mod comment;
 pub mod cycle_management;
+pub mod deferred_job;
+pub mod error_code;
+pub mod event_callback;
 pub mod follow;
 pub mod hot_or_not_bet;
+pub mod metrics;
+pub mod moderation;
+pub mod onboarding;
 pub mod post;
 pub mod profile;
+pub mod read_only_mode;
+pub mod replication;
+pub mod session_delegate;
 pub mod token;
+pub mod tournament;
+pub mod versus;
 pub mod well_known_principal;
@@ -0,0 +1,151 @@
+use std::time::SystemTime;
+
+use candid::Principal;
+use shared_utils::{
+    canister_specific::individual_user_template::types::{
+        error::ModerationError,
+        moderation::{ModerationAction, ModeratorPermission},
+    },
+    common::utils::system_time,
+};
+
+use crate::{
+    data_model::CanisterData,
+    util::{call_journal::record_call_in_journal, guards::reject_updates_in_read_only_mode},
+    CANISTER_DATA,
+};
+
+/// Lets a trusted principal moderate this profile's posts, within the given permissions.
+/// Appointing an existing moderator again replaces their permissions.
+///
+/// # Access Control
+/// Only the user whose profile details are stored in this canister can appoint moderators
+#[ic_cdk::update(guard = "reject_updates_in_read_only_mode")]
+#[candid::candid_method(update)]
+fn appoint_moderator(
+    moderator_principal_id: Principal,
+    permissions: Vec<ModeratorPermission>,
+) -> Result<(), ModerationError> {
+    record_call_in_journal("appoint_moderator");
+
+    let api_caller = ic_cdk::caller();
+
+    CANISTER_DATA.with(|canister_data_ref_cell| {
+        appoint_moderator_impl(
+            &mut canister_data_ref_cell.borrow_mut(),
+            &api_caller,
+            moderator_principal_id,
+            permissions,
+            &system_time::get_current_system_time_from_ic(),
+        )
+    })
+}
+
+fn appoint_moderator_impl(
+    canister_data: &mut CanisterData,
+    api_caller: &Principal,
+    moderator_principal_id: Principal,
+    permissions: Vec<ModeratorPermission>,
+    current_time: &SystemTime,
+) -> Result<(), ModerationError> {
+    if canister_data.profile.principal_id != Some(*api_caller) {
+        return Err(ModerationError::Unauthorized);
+    }
+
+    if moderator_principal_id == *api_caller {
+        return Err(ModerationError::ModeratorCannotBeProfileOwner);
+    }
+
+    canister_data.delegated_moderation.appoint_moderator(
+        moderator_principal_id,
+        &permissions,
+        current_time,
+    )?;
+    canister_data.delegated_moderation.record_action(
+        ModerationAction::ModeratorAppointed {
+            moderator_principal_id,
+            permissions,
+        },
+        *api_caller,
+        *current_time,
+    );
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use test_utils::setup::test_constants::{
+        get_mock_user_alice_principal_id, get_mock_user_bob_principal_id,
+        get_mock_user_charlie_principal_id,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_appoint_moderator_impl() {
+        let mut canister_data = CanisterData::default();
+        let current_time = SystemTime::now();
+        canister_data.profile.principal_id = Some(get_mock_user_alice_principal_id());
+
+        assert_eq!(
+            appoint_moderator_impl(
+                &mut canister_data,
+                &get_mock_user_bob_principal_id(),
+                get_mock_user_charlie_principal_id(),
+                vec![ModeratorPermission::HideComments],
+                &current_time,
+            ),
+            Err(ModerationError::Unauthorized)
+        );
+        assert_eq!(
+            appoint_moderator_impl(
+                &mut canister_data,
+                &get_mock_user_alice_principal_id(),
+                get_mock_user_alice_principal_id(),
+                vec![ModeratorPermission::HideComments],
+                &current_time,
+            ),
+            Err(ModerationError::ModeratorCannotBeProfileOwner)
+        );
+        assert_eq!(
+            appoint_moderator_impl(
+                &mut canister_data,
+                &get_mock_user_alice_principal_id(),
+                get_mock_user_bob_principal_id(),
+                vec![],
+                &current_time,
+            ),
+            Err(ModerationError::NoPermissionsGiven)
+        );
+        assert_eq!(
+            appoint_moderator_impl(
+                &mut canister_data,
+                &get_mock_user_alice_principal_id(),
+                get_mock_user_bob_principal_id(),
+                vec![
+                    ModeratorPermission::HideComments,
+                    ModeratorPermission::PauseBetting
+                ],
+                &current_time,
+            ),
+            Ok(())
+        );
+
+        assert!(canister_data.is_allowed_to_moderate(
+            &get_mock_user_bob_principal_id(),
+            ModeratorPermission::PauseBetting
+        ));
+        assert!(!canister_data.is_allowed_to_moderate(
+            &get_mock_user_bob_principal_id(),
+            ModeratorPermission::ManagePostAllowlist
+        ));
+
+        let audit_log = canister_data.delegated_moderation.get_audit_log_page(0, 10);
+        assert_eq!(audit_log.total_count, 1);
+        assert_eq!(
+            audit_log.items[0].performed_by,
+            get_mock_user_alice_principal_id()
+        );
+    }
+}
@@ -0,0 +1,109 @@
+use candid::Principal;
+use shared_utils::{
+    canister_specific::individual_user_template::types::{
+        error::ModerationError, moderation::ModerationAuditLogEntry,
+    },
+    pagination::Page,
+};
+
+use crate::{data_model::CanisterData, CANISTER_DATA};
+
+/// Everything the profile owner and their moderators did, newest first. Only the latest
+/// `MAX_MODERATION_AUDIT_LOG_ENTRIES` actions are kept.
+///
+/// # Access Control
+/// The user whose profile details are stored in this canister, and their moderators
+#[ic_cdk::query]
+#[candid::candid_method(query)]
+fn get_moderation_audit_log(
+    from_inclusive_index: u64,
+    page_size: u64,
+) -> Result<Page<ModerationAuditLogEntry>, ModerationError> {
+    let api_caller = ic_cdk::caller();
+
+    CANISTER_DATA.with(|canister_data_ref_cell| {
+        get_moderation_audit_log_impl(
+            &canister_data_ref_cell.borrow(),
+            &api_caller,
+            from_inclusive_index,
+            page_size,
+        )
+    })
+}
+
+fn get_moderation_audit_log_impl(
+    canister_data: &CanisterData,
+    api_caller: &Principal,
+    from_inclusive_index: u64,
+    page_size: u64,
+) -> Result<Page<ModerationAuditLogEntry>, ModerationError> {
+    if canister_data.profile.principal_id != Some(*api_caller)
+        && !canister_data.delegated_moderation.is_moderator(api_caller)
+    {
+        return Err(ModerationError::Unauthorized);
+    }
+
+    Ok(canister_data
+        .delegated_moderation
+        .get_audit_log_page(from_inclusive_index, page_size))
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::SystemTime;
+
+    use shared_utils::canister_specific::individual_user_template::types::moderation::{
+        ModerationAction, ModeratorPermission,
+    };
+    use test_utils::setup::test_constants::{
+        get_mock_user_alice_principal_id, get_mock_user_bob_principal_id,
+        get_mock_user_charlie_principal_id,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_get_moderation_audit_log_impl() {
+        let mut canister_data = CanisterData::default();
+        let current_time = SystemTime::now();
+        canister_data.profile.principal_id = Some(get_mock_user_alice_principal_id());
+        canister_data
+            .delegated_moderation
+            .appoint_moderator(
+                get_mock_user_bob_principal_id(),
+                &[ModeratorPermission::PauseBetting],
+                &current_time,
+            )
+            .unwrap();
+        canister_data.delegated_moderation.record_action(
+            ModerationAction::BettingPaused { post_id: 0 },
+            get_mock_user_bob_principal_id(),
+            current_time,
+        );
+
+        assert_eq!(
+            get_moderation_audit_log_impl(
+                &canister_data,
+                &get_mock_user_charlie_principal_id(),
+                0,
+                10
+            ),
+            Err(ModerationError::Unauthorized)
+        );
+        assert_eq!(
+            get_moderation_audit_log_impl(&canister_data, &get_mock_user_bob_principal_id(), 0, 10)
+                .map(|page| page.total_count),
+            Ok(1)
+        );
+        assert_eq!(
+            get_moderation_audit_log_impl(
+                &canister_data,
+                &get_mock_user_alice_principal_id(),
+                0,
+                10
+            )
+            .map(|page| page.total_count),
+            Ok(1)
+        );
+    }
+}
@@ -0,0 +1,26 @@
+use candid::Principal;
+use shared_utils::canister_specific::individual_user_template::types::{
+    error::ModerationError, moderation::Moderator,
+};
+
+use crate::CANISTER_DATA;
+
+/// # Access Control
+/// The user whose profile details are stored in this canister, and their moderators
+#[ic_cdk::query]
+#[candid::candid_method(query)]
+fn get_moderators() -> Result<Vec<(Principal, Moderator)>, ModerationError> {
+    let api_caller = ic_cdk::caller();
+
+    CANISTER_DATA.with(|canister_data_ref_cell| {
+        let canister_data = canister_data_ref_cell.borrow();
+
+        if canister_data.profile.principal_id != Some(api_caller)
+            && !canister_data.delegated_moderation.is_moderator(&api_caller)
+        {
+            return Err(ModerationError::Unauthorized);
+        }
+
+        Ok(canister_data.delegated_moderation.get_moderators())
+    })
+}
@@ -0,0 +1,16 @@
+use candid::Principal;
+use shared_utils::common::types::app_primitive_type::PostId;
+
+use crate::CANISTER_DATA;
+
+/// Principals allowed to comment on the post. Empty if anyone can comment on it.
+#[ic_cdk::query]
+#[candid::candid_method(query)]
+fn get_post_allowlist(post_id: PostId) -> Vec<Principal> {
+    CANISTER_DATA.with(|canister_data_ref_cell| {
+        canister_data_ref_cell
+            .borrow()
+            .delegated_moderation
+            .get_post_allowlist(post_id)
+    })
+}
@@ -0,0 +1,7 @@
+pub mod appoint_moderator;
+pub mod get_moderation_audit_log;
+pub mod get_moderators;
+pub mod get_post_allowlist;
+pub mod revoke_moderator;
+pub mod update_betting_pause_of_post;
+pub mod update_post_allowlist;
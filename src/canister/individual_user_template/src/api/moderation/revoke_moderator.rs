@@ -0,0 +1,148 @@
+use std::time::SystemTime;
+
+use candid::Principal;
+use shared_utils::{
+    canister_specific::individual_user_template::types::{
+        error::ModerationError, moderation::ModerationAction,
+    },
+    common::utils::system_time,
+};
+
+use crate::{
+    data_model::CanisterData,
+    util::{call_journal::record_call_in_journal, guards::reject_updates_in_read_only_mode},
+    CANISTER_DATA,
+};
+
+/// # Access Control
+/// The profile owner can revoke any moderator. A moderator can only step down themselves
+#[ic_cdk::update(guard = "reject_updates_in_read_only_mode")]
+#[candid::candid_method(update)]
+fn revoke_moderator(moderator_principal_id: Principal) -> Result<(), ModerationError> {
+    record_call_in_journal("revoke_moderator");
+
+    let api_caller = ic_cdk::caller();
+
+    CANISTER_DATA.with(|canister_data_ref_cell| {
+        revoke_moderator_impl(
+            &mut canister_data_ref_cell.borrow_mut(),
+            &api_caller,
+            moderator_principal_id,
+            &system_time::get_current_system_time_from_ic(),
+        )
+    })
+}
+
+fn revoke_moderator_impl(
+    canister_data: &mut CanisterData,
+    api_caller: &Principal,
+    moderator_principal_id: Principal,
+    current_time: &SystemTime,
+) -> Result<(), ModerationError> {
+    if canister_data.profile.principal_id != Some(*api_caller)
+        && *api_caller != moderator_principal_id
+    {
+        return Err(ModerationError::Unauthorized);
+    }
+
+    canister_data
+        .delegated_moderation
+        .revoke_moderator(&moderator_principal_id)?;
+    canister_data.delegated_moderation.record_action(
+        ModerationAction::ModeratorRevoked {
+            moderator_principal_id,
+        },
+        *api_caller,
+        *current_time,
+    );
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use shared_utils::canister_specific::individual_user_template::types::moderation::ModeratorPermission;
+    use test_utils::setup::test_constants::{
+        get_mock_user_alice_principal_id, get_mock_user_bob_principal_id,
+        get_mock_user_charlie_principal_id,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_revoke_moderator_impl() {
+        let mut canister_data = CanisterData::default();
+        let current_time = SystemTime::now();
+        canister_data.profile.principal_id = Some(get_mock_user_alice_principal_id());
+        [
+            get_mock_user_bob_principal_id(),
+            get_mock_user_charlie_principal_id(),
+        ]
+        .into_iter()
+        .for_each(|moderator_principal_id| {
+            canister_data
+                .delegated_moderation
+                .appoint_moderator(
+                    moderator_principal_id,
+                    &[ModeratorPermission::HideComments],
+                    &current_time,
+                )
+                .unwrap();
+        });
+
+        assert_eq!(
+            revoke_moderator_impl(
+                &mut canister_data,
+                &get_mock_user_bob_principal_id(),
+                get_mock_user_charlie_principal_id(),
+                &current_time,
+            ),
+            Err(ModerationError::Unauthorized)
+        );
+        assert_eq!(
+            revoke_moderator_impl(
+                &mut canister_data,
+                &get_mock_user_bob_principal_id(),
+                get_mock_user_bob_principal_id(),
+                &current_time,
+            ),
+            Ok(())
+        );
+        assert_eq!(
+            revoke_moderator_impl(
+                &mut canister_data,
+                &get_mock_user_alice_principal_id(),
+                get_mock_user_bob_principal_id(),
+                &current_time,
+            ),
+            Err(ModerationError::ModeratorNotFound)
+        );
+        assert_eq!(
+            revoke_moderator_impl(
+                &mut canister_data,
+                &get_mock_user_alice_principal_id(),
+                get_mock_user_charlie_principal_id(),
+                &current_time,
+            ),
+            Ok(())
+        );
+
+        assert!(canister_data
+            .delegated_moderation
+            .get_moderators()
+            .is_empty());
+        assert_eq!(
+            canister_data
+                .delegated_moderation
+                .get_audit_log_page(0, 10)
+                .items
+                .iter()
+                .map(|entry| entry.performed_by)
+                .collect::<Vec<_>>(),
+            vec![
+                get_mock_user_alice_principal_id(),
+                get_mock_user_bob_principal_id()
+            ]
+        );
+    }
+}
@@ -0,0 +1,193 @@
+use std::time::SystemTime;
+
+use candid::Principal;
+use shared_utils::{
+    canister_specific::individual_user_template::types::{
+        error::ModerationError,
+        moderation::{ModerationAction, ModeratorPermission},
+    },
+    common::{types::app_primitive_type::PostId, utils::system_time},
+};
+
+use crate::{
+    data_model::CanisterData,
+    util::{
+        call_journal::record_call_in_journal, certified_post_details::recertify_post_details,
+        guards::reject_updates_in_read_only_mode,
+    },
+    CANISTER_DATA,
+};
+
+/// Holds back new bets on one of this profile's posts, or lets them in again. The pause is
+/// kept apart from the one put in place by reports, so resuming betting here doesn't lift
+/// that one. Slots that already have bets in them settle as usual.
+///
+/// # Access Control
+/// The user whose profile details are stored in this canister, and moderators they allowed to
+/// pause betting
+#[ic_cdk::update(guard = "reject_updates_in_read_only_mode")]
+#[candid::candid_method(update)]
+fn update_betting_pause_of_post(post_id: PostId, is_paused: bool) -> Result<(), ModerationError> {
+    record_call_in_journal("update_betting_pause_of_post");
+
+    let api_caller = ic_cdk::caller();
+
+    CANISTER_DATA.with(|canister_data_ref_cell| {
+        update_betting_pause_of_post_impl(
+            &mut canister_data_ref_cell.borrow_mut(),
+            &api_caller,
+            post_id,
+            is_paused,
+            &system_time::get_current_system_time_from_ic(),
+        )
+    })?;
+
+    recertify_post_details(post_id);
+
+    Ok(())
+}
+
+fn update_betting_pause_of_post_impl(
+    canister_data: &mut CanisterData,
+    api_caller: &Principal,
+    post_id: PostId,
+    is_paused: bool,
+    current_time: &SystemTime,
+) -> Result<(), ModerationError> {
+    if !canister_data.is_allowed_to_moderate(api_caller, ModeratorPermission::PauseBetting) {
+        return Err(ModerationError::Unauthorized);
+    }
+
+    let mut post = canister_data
+        .all_created_posts
+        .get(&post_id)
+        .ok_or(ModerationError::PostNotFound)?;
+
+    if post.is_betting_paused_by_moderator == is_paused {
+        return Ok(());
+    }
+
+    post.is_betting_paused_by_moderator = is_paused;
+    canister_data.all_created_posts.insert(post_id, post);
+
+    canister_data.delegated_moderation.record_action(
+        if is_paused {
+            ModerationAction::BettingPaused { post_id }
+        } else {
+            ModerationAction::BettingResumed { post_id }
+        },
+        *api_caller,
+        *current_time,
+    );
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use shared_utils::canister_specific::individual_user_template::types::post::{
+        Post, PostDetailsFromFrontend,
+    };
+    use test_utils::setup::test_constants::{
+        get_mock_user_alice_principal_id, get_mock_user_bob_principal_id,
+        get_mock_user_charlie_principal_id,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_update_betting_pause_of_post_impl() {
+        let mut canister_data = CanisterData::default();
+        let current_time = SystemTime::now();
+        canister_data.profile.principal_id = Some(get_mock_user_alice_principal_id());
+        canister_data.all_created_posts.insert(
+            0,
+            Post::new(
+                0,
+                &PostDetailsFromFrontend {
+                    description: "Doggos and puppers".into(),
+                    hashtags: vec!["doggo".into(), "pupper".into()],
+                    video_uid: "abcd#1234".into(),
+                    creator_consent_for_inclusion_in_hot_or_not: true,
+                    collaborator_shares: None,
+                    title: None,
+                    category: None,
+                },
+                &current_time,
+            ),
+        );
+        canister_data
+            .delegated_moderation
+            .appoint_moderator(
+                get_mock_user_bob_principal_id(),
+                &[ModeratorPermission::PauseBetting],
+                &current_time,
+            )
+            .unwrap();
+
+        assert_eq!(
+            update_betting_pause_of_post_impl(
+                &mut canister_data,
+                &get_mock_user_charlie_principal_id(),
+                0,
+                true,
+                &current_time,
+            ),
+            Err(ModerationError::Unauthorized)
+        );
+        assert_eq!(
+            update_betting_pause_of_post_impl(
+                &mut canister_data,
+                &get_mock_user_bob_principal_id(),
+                1,
+                true,
+                &current_time,
+            ),
+            Err(ModerationError::PostNotFound)
+        );
+        assert_eq!(
+            update_betting_pause_of_post_impl(
+                &mut canister_data,
+                &get_mock_user_bob_principal_id(),
+                0,
+                true,
+                &current_time,
+            ),
+            Ok(())
+        );
+        assert!(canister_data
+            .all_created_posts
+            .get(&0)
+            .unwrap()
+            .is_betting_paused_for_review());
+
+        assert_eq!(
+            update_betting_pause_of_post_impl(
+                &mut canister_data,
+                &get_mock_user_alice_principal_id(),
+                0,
+                false,
+                &current_time,
+            ),
+            Ok(())
+        );
+        assert!(!canister_data
+            .all_created_posts
+            .get(&0)
+            .unwrap()
+            .is_betting_paused_for_review());
+
+        let audit_log = canister_data.delegated_moderation.get_audit_log_page(0, 10);
+        assert_eq!(
+            audit_log
+                .items
+                .iter()
+                .map(|entry| entry.action.clone())
+                .collect::<Vec<_>>(),
+            vec![
+                ModerationAction::BettingResumed { post_id: 0 },
+                ModerationAction::BettingPaused { post_id: 0 },
+            ]
+        );
+    }
+}
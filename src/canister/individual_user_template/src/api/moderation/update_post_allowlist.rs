@@ -0,0 +1,184 @@
+use std::time::SystemTime;
+
+use candid::Principal;
+use shared_utils::{
+    canister_specific::individual_user_template::types::{
+        error::ModerationError,
+        moderation::{ModerationAction, ModeratorPermission},
+    },
+    common::{types::app_primitive_type::PostId, utils::system_time},
+};
+
+use crate::{
+    data_model::CanisterData,
+    util::{call_journal::record_call_in_journal, guards::reject_updates_in_read_only_mode},
+    CANISTER_DATA,
+};
+
+/// Changes who can comment on one of this profile's posts. A post without an allowlist is
+/// open to every logged in user, and removing the last principal from one opens the post up
+/// again.
+///
+/// # Access Control
+/// The user whose profile details are stored in this canister, and moderators they allowed to
+/// manage post allowlists
+#[ic_cdk::update(guard = "reject_updates_in_read_only_mode")]
+#[candid::candid_method(update)]
+fn update_post_allowlist(
+    post_id: PostId,
+    principals_to_add: Vec<Principal>,
+    principals_to_remove: Vec<Principal>,
+) -> Result<(), ModerationError> {
+    record_call_in_journal("update_post_allowlist");
+
+    let api_caller = ic_cdk::caller();
+
+    CANISTER_DATA.with(|canister_data_ref_cell| {
+        update_post_allowlist_impl(
+            &mut canister_data_ref_cell.borrow_mut(),
+            &api_caller,
+            post_id,
+            principals_to_add,
+            principals_to_remove,
+            &system_time::get_current_system_time_from_ic(),
+        )
+    })
+}
+
+fn update_post_allowlist_impl(
+    canister_data: &mut CanisterData,
+    api_caller: &Principal,
+    post_id: PostId,
+    principals_to_add: Vec<Principal>,
+    principals_to_remove: Vec<Principal>,
+    current_time: &SystemTime,
+) -> Result<(), ModerationError> {
+    if !canister_data.is_allowed_to_moderate(api_caller, ModeratorPermission::ManagePostAllowlist) {
+        return Err(ModerationError::Unauthorized);
+    }
+
+    if !canister_data.all_created_posts.contains_key(&post_id) {
+        return Err(ModerationError::PostNotFound);
+    }
+
+    canister_data.delegated_moderation.update_post_allowlist(
+        post_id,
+        &principals_to_add,
+        &principals_to_remove,
+    )?;
+    canister_data.delegated_moderation.record_action(
+        ModerationAction::PostAllowlistUpdated {
+            post_id,
+            principals_added: principals_to_add,
+            principals_removed: principals_to_remove,
+        },
+        *api_caller,
+        *current_time,
+    );
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use shared_utils::canister_specific::individual_user_template::types::post::{
+        Post, PostDetailsFromFrontend,
+    };
+    use test_utils::setup::test_constants::{
+        get_mock_user_alice_principal_id, get_mock_user_bob_principal_id,
+        get_mock_user_charlie_principal_id,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_update_post_allowlist_impl() {
+        let mut canister_data = CanisterData::default();
+        let current_time = SystemTime::now();
+        canister_data.profile.principal_id = Some(get_mock_user_alice_principal_id());
+        canister_data.all_created_posts.insert(
+            0,
+            Post::new(
+                0,
+                &PostDetailsFromFrontend {
+                    description: "Doggos and puppers".into(),
+                    hashtags: vec!["doggo".into(), "pupper".into()],
+                    video_uid: "abcd#1234".into(),
+                    creator_consent_for_inclusion_in_hot_or_not: false,
+                    collaborator_shares: None,
+                    title: None,
+                    category: None,
+                },
+                &current_time,
+            ),
+        );
+        canister_data
+            .delegated_moderation
+            .appoint_moderator(
+                get_mock_user_bob_principal_id(),
+                &[ModeratorPermission::HideComments],
+                &current_time,
+            )
+            .unwrap();
+
+        assert_eq!(
+            update_post_allowlist_impl(
+                &mut canister_data,
+                &get_mock_user_bob_principal_id(),
+                0,
+                vec![get_mock_user_charlie_principal_id()],
+                vec![],
+                &current_time,
+            ),
+            Err(ModerationError::Unauthorized)
+        );
+
+        canister_data
+            .delegated_moderation
+            .appoint_moderator(
+                get_mock_user_bob_principal_id(),
+                &[ModeratorPermission::ManagePostAllowlist],
+                &current_time,
+            )
+            .unwrap();
+        assert_eq!(
+            update_post_allowlist_impl(
+                &mut canister_data,
+                &get_mock_user_bob_principal_id(),
+                1,
+                vec![get_mock_user_charlie_principal_id()],
+                vec![],
+                &current_time,
+            ),
+            Err(ModerationError::PostNotFound)
+        );
+        assert_eq!(
+            update_post_allowlist_impl(
+                &mut canister_data,
+                &get_mock_user_bob_principal_id(),
+                0,
+                vec![get_mock_user_charlie_principal_id()],
+                vec![],
+                &current_time,
+            ),
+            Ok(())
+        );
+
+        assert_eq!(
+            canister_data.delegated_moderation.get_post_allowlist(0),
+            vec![get_mock_user_charlie_principal_id()]
+        );
+        assert_eq!(
+            canister_data
+                .delegated_moderation
+                .get_audit_log_page(0, 10)
+                .items[0]
+                .action,
+            ModerationAction::PostAllowlistUpdated {
+                post_id: 0,
+                principals_added: vec![get_mock_user_charlie_principal_id()],
+                principals_removed: vec![],
+            }
+        );
+    }
+}
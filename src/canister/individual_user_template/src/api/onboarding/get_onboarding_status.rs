@@ -0,0 +1,62 @@
+use shared_utils::canister_specific::individual_user_template::types::onboarding::OnboardingStatus;
+
+use crate::{data_model::CanisterData, CANISTER_DATA};
+
+/// Read by the user index when looking for canisters whose owners never got past onboarding
+#[ic_cdk::query]
+#[candid::candid_method(query)]
+fn get_onboarding_status() -> OnboardingStatus {
+    CANISTER_DATA
+        .with(|canister_data_ref_cell| get_onboarding_status_impl(&canister_data_ref_cell.borrow()))
+}
+
+fn get_onboarding_status_impl(canister_data: &CanisterData) -> OnboardingStatus {
+    OnboardingStatus {
+        post_count: canister_data.all_created_posts.len(),
+        bet_count: canister_data.all_hot_or_not_bets_placed.len() as u64,
+        last_active_at: canister_data
+            .token_transaction_history
+            .iter_rev()
+            .find_map(|(_, token_event)| token_event.get_timestamp()),
+        scheduled_collection_at: canister_data.scheduled_collection_at,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::{Duration, SystemTime};
+
+    use shared_utils::common::types::utility_token::token_event::{MintEvent, TokenEvent};
+    use test_utils::setup::test_constants::get_mock_user_alice_principal_id;
+
+    use super::*;
+
+    #[test]
+    fn test_get_onboarding_status_impl() {
+        let mut canister_data = CanisterData::default();
+        assert_eq!(
+            get_onboarding_status_impl(&canister_data),
+            OnboardingStatus::default()
+        );
+
+        let signed_up_at = SystemTime::UNIX_EPOCH + Duration::from_secs(1_000);
+        let token_transaction_history = &mut canister_data.token_transaction_history;
+        token_transaction_history.insert(
+            0,
+            TokenEvent::Mint {
+                amount: 1000,
+                details: MintEvent::NewUserSignup {
+                    new_user_principal_id: get_mock_user_alice_principal_id(),
+                },
+                timestamp: signed_up_at,
+            },
+        );
+        // * events without a time don't hide the latest one that has one
+        token_transaction_history.insert(1, TokenEvent::Transfer);
+
+        assert_eq!(
+            get_onboarding_status_impl(&canister_data).last_active_at,
+            Some(signed_up_at)
+        );
+    }
+}
@@ -0,0 +1,2 @@
+pub mod get_onboarding_status;
+pub mod receive_scheduled_collection_notice;
@@ -0,0 +1,82 @@
+use std::time::SystemTime;
+
+use candid::Principal;
+use shared_utils::common::{
+    types::known_principal::KnownPrincipalType, utils::known_caller::authorize_known_caller,
+};
+
+use crate::{
+    data_model::CanisterData,
+    util::{call_journal::record_call_in_journal, guards::reject_updates_in_read_only_mode},
+    CANISTER_DATA,
+};
+
+/// Lets the profile owner know when this canister is going to be collected for never getting
+/// past onboarding. None once it is no longer flagged.
+///
+/// # Access Control
+/// Only the user index canister can schedule a collection
+#[ic_cdk::update(guard = "reject_updates_in_read_only_mode")]
+#[candid::candid_method(update)]
+fn receive_scheduled_collection_notice(scheduled_collection_at: Option<SystemTime>) {
+    record_call_in_journal("receive_scheduled_collection_notice");
+
+    let api_caller = ic_cdk::caller();
+
+    CANISTER_DATA.with(|canister_data_ref_cell| {
+        receive_scheduled_collection_notice_impl(
+            &mut canister_data_ref_cell.borrow_mut(),
+            &api_caller,
+            scheduled_collection_at,
+        )
+    });
+}
+
+fn receive_scheduled_collection_notice_impl(
+    canister_data: &mut CanisterData,
+    api_caller: &Principal,
+    scheduled_collection_at: Option<SystemTime>,
+) {
+    if authorize_known_caller(
+        api_caller,
+        &canister_data.known_principal_ids,
+        &[KnownPrincipalType::CanisterIdUserIndex],
+    )
+    .is_err()
+    {
+        return;
+    }
+
+    canister_data.scheduled_collection_at = scheduled_collection_at;
+}
+
+#[cfg(test)]
+mod test {
+    use test_utils::setup::spoofed_callers::{
+        assert_only_allowed_callers_are_accepted, get_mock_known_principal_map,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_receive_scheduled_collection_notice_impl_with_spoofed_callers() {
+        let known_principal_ids = get_mock_known_principal_map();
+
+        assert_only_allowed_callers_are_accepted(
+            &known_principal_ids,
+            &[KnownPrincipalType::CanisterIdUserIndex],
+            |caller| {
+                let mut canister_data = CanisterData {
+                    known_principal_ids: known_principal_ids.clone(),
+                    ..Default::default()
+                };
+                receive_scheduled_collection_notice_impl(
+                    &mut canister_data,
+                    caller,
+                    Some(SystemTime::UNIX_EPOCH),
+                );
+                canister_data.scheduled_collection_at.is_some()
+            },
+        );
+    }
+}
@@ -1,22 +1,60 @@
-use std::time::{Duration, SystemTime};
+use std::time::SystemTime;
 
+use candid::Principal;
 use shared_utils::{
-    canister_specific::individual_user_template::types::post::{Post, PostDetailsFromFrontend},
-    common::utils::system_time,
+    canister_specific::individual_user_template::types::{
+        collaborator::validate_collaborator_shares,
+        event_callback::EventCallbackPayload,
+        feature_usage::Feature,
+        post::{validate_publish_time, Post, PostCategory, PostDetailsFromFrontend},
+    },
+    common::{
+        types::{app_primitive_type::PostId, known_principal::KnownPrincipalType},
+        utils::system_time::{self, IcTimeProvider},
+    },
 };
 
 use crate::{
-    api::hot_or_not_bet::tabulate_hot_or_not_outcome_for_post_slot::tabulate_hot_or_not_outcome_for_post_slot,
-    data_model::CanisterData, CANISTER_DATA,
+    data_model::CanisterData,
+    util::{
+        call_journal::record_call_in_journal, call_queue::queue_call,
+        event_callback::send_event_callbacks,
+        guards::reject_bets_and_posts_in_read_only_or_low_cycles_mode,
+    },
+    CANISTER_DATA,
 };
 
 use super::update_scores_and_share_with_post_cache_if_difference_beyond_threshold::update_scores_and_share_with_post_cache_if_difference_beyond_threshold;
 
 /// #### Access Control
 /// Only the user whose profile details are stored in this canister can create a post.
-#[ic_cdk::update]
+#[ic_cdk::update(guard = "reject_bets_and_posts_in_read_only_or_low_cycles_mode")]
 #[candid::candid_method(update)]
 fn add_post_v2(post_details: PostDetailsFromFrontend) -> Result<u64, String> {
+    record_call_in_journal("add_post_v2");
+
+    add_post(post_details, None)
+}
+
+/// Creates the post right away, but holds off betting on it until `publish_at`
+///
+/// #### Access Control
+/// Only the user whose profile details are stored in this canister can create a post.
+#[ic_cdk::update(guard = "reject_bets_and_posts_in_read_only_or_low_cycles_mode")]
+#[candid::candid_method(update)]
+fn add_scheduled_post(
+    post_details: PostDetailsFromFrontend,
+    publish_at: SystemTime,
+) -> Result<u64, String> {
+    record_call_in_journal("add_scheduled_post");
+
+    add_post(post_details, Some(publish_at))
+}
+
+fn add_post(
+    post_details: PostDetailsFromFrontend,
+    publish_at: Option<SystemTime>,
+) -> Result<u64, String> {
     // * access control
     let current_caller = ic_cdk::caller();
     let my_principal_id = CANISTER_DATA
@@ -28,54 +66,317 @@ fn add_post_v2(post_details: PostDetailsFromFrontend) -> Result<u64, String> {
         );
     };
 
-    let response = CANISTER_DATA.with(|canister_data_ref_cell| {
+    let current_system_time = system_time::get_current_system_time_from_ic();
+    let post_id = CANISTER_DATA.with(|canister_data_ref_cell| {
         add_post_to_memory(
             &mut canister_data_ref_cell.borrow_mut(),
             &post_details,
-            &system_time::get_current_system_time_from_ic(),
+            &current_system_time,
+            publish_at,
         )
-    });
+    })?;
 
-    let post_id = response.clone().unwrap();
+    update_scores_and_share_with_post_cache_if_difference_beyond_threshold(
+        &post_id,
+        &IcTimeProvider,
+    );
 
-    if response.is_ok() {
-        update_scores_and_share_with_post_cache_if_difference_beyond_threshold(&post_id);
+    if let Some(category) = post_details.category {
+        share_post_category_with_post_cache(post_id, category);
     }
 
-    if post_details.creator_consent_for_inclusion_in_hot_or_not {
-        // * schedule hot_or_not outcome tabulation for the 48 hours after the post is created
-        (1..=48).for_each(|slot_number: u8| {
-            ic_cdk_timers::set_timer(
-                Duration::from_secs(slot_number as u64 * 60 * 60),
-                move || {
-                    CANISTER_DATA.with(|canister_data_ref_cell| {
-                        tabulate_hot_or_not_outcome_for_post_slot(
-                            &mut canister_data_ref_cell.borrow_mut(),
-                            post_id,
-                            slot_number,
-                        );
-                    });
-                },
-            );
-        })
-    }
+    share_post_hashtags_with_post_cache(post_id);
+
+    send_event_callbacks(
+        EventCallbackPayload::PostCreated { post_id },
+        current_system_time,
+    );
 
     Ok(post_id)
 }
 
+pub fn get_post_cache_canister_id() -> Option<Principal> {
+    CANISTER_DATA.with(|canister_data_ref_cell| {
+        canister_data_ref_cell
+            .borrow()
+            .known_principal_ids
+            // * each canister sticks to one post cache shard
+            .get_for_shard_key(&KnownPrincipalType::CanisterIdPostCache, &ic_cdk::id())
+            .cloned()
+    })
+}
+
+/// Lets the post cache serve category feeds out of its home feed entries
+fn share_post_category_with_post_cache(post_id: PostId, category: PostCategory) {
+    if let Some(post_cache_canister_id) = get_post_cache_canister_id() {
+        queue_call(
+            post_cache_canister_id,
+            "receive_post_category_from_publishing_canister",
+            (post_id, category),
+        );
+    }
+}
+
+/// Lets the post cache work out trending hashtags. Sends them as they were normalized when
+/// the post was created.
+fn share_post_hashtags_with_post_cache(post_id: PostId) {
+    let hashtags = CANISTER_DATA.with(|canister_data_ref_cell| {
+        canister_data_ref_cell
+            .borrow()
+            .all_created_posts
+            .get(&post_id)
+            .map(|post| post.hashtags)
+            .unwrap_or_default()
+    });
+
+    if hashtags.is_empty() {
+        return;
+    }
+
+    if let Some(post_cache_canister_id) = get_post_cache_canister_id() {
+        queue_call(
+            post_cache_canister_id,
+            "receive_post_hashtags_from_publishing_canister",
+            (post_id, hashtags),
+        );
+    }
+}
+
 fn add_post_to_memory(
     canister_data: &mut CanisterData,
     post_details: &PostDetailsFromFrontend,
     current_system_time: &SystemTime,
+    publish_at: Option<SystemTime>,
 ) -> Result<u64, String> {
-    let new_post = Post::new(
-        canister_data.all_created_posts.len() as u64,
+    if let Some(publish_at) = &publish_at {
+        validate_publish_time(publish_at, current_system_time)?;
+    }
+
+    let collaborator_shares = post_details
+        .collaborator_shares
+        .as_deref()
+        .unwrap_or_default();
+    validate_collaborator_shares(collaborator_shares)?;
+    post_details.validate_title_and_category(&canister_data.configuration.post_category_config)?;
+
+    let mut new_post = Post::new(
+        canister_data.all_created_posts.len(),
         post_details,
         current_system_time,
     );
+    // * The creator's own share is whatever is left once the collaborators are paid
+    new_post.collaborator_shares = collaborator_shares
+        .iter()
+        .filter(|collaborator_share| {
+            Some(collaborator_share.collaborator_principal_id) != canister_data.profile.principal_id
+        })
+        .cloned()
+        .collect();
+    new_post.publish_at = publish_at;
+    new_post.hashtags = canister_data.hashtag_aliases.apply(&new_post.hashtags);
     let new_post_id = new_post.id;
     canister_data
         .all_created_posts
         .insert(new_post.id, new_post);
+    canister_data.record_feature_usage(Feature::PostCreated, current_system_time);
     Ok(new_post_id)
 }
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use shared_utils::canister_specific::individual_user_template::types::{
+        collaborator::CollaboratorShare,
+        error::BetOnCurrentlyViewingPostError,
+        feature_usage::get_day_number,
+        hot_or_not::{BetDirection, BettingStatus},
+        post::{PostCategoryConfig, MAXIMUM_POST_TITLE_LENGTH, MAXIMUM_PUBLISH_DELAY_IN_SECONDS},
+    };
+    use test_utils::setup::test_constants::{
+        get_mock_user_alice_principal_id, get_mock_user_bob_canister_id,
+        get_mock_user_bob_principal_id,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_add_post_to_memory_with_collaborators() {
+        let mut canister_data = CanisterData::default();
+        canister_data.profile.principal_id = Some(get_mock_user_alice_principal_id());
+
+        let mut post_details = PostDetailsFromFrontend {
+            description: "Doggos and puppers".into(),
+            hashtags: vec!["doggo".into(), "pupper".into()],
+            video_uid: "abcd#1234".into(),
+            creator_consent_for_inclusion_in_hot_or_not: true,
+            collaborator_shares: Some(vec![
+                CollaboratorShare {
+                    collaborator_principal_id: get_mock_user_alice_principal_id(),
+                    percentage: 70,
+                },
+                CollaboratorShare {
+                    collaborator_principal_id: get_mock_user_bob_principal_id(),
+                    percentage: 20,
+                },
+            ]),
+            title: None,
+            category: None,
+        };
+
+        assert!(
+            add_post_to_memory(&mut canister_data, &post_details, &SystemTime::now(), None)
+                .is_err()
+        );
+        assert!(canister_data.all_created_posts.is_empty());
+
+        post_details.collaborator_shares.as_mut().unwrap()[1].percentage = 30;
+
+        let post_id =
+            add_post_to_memory(&mut canister_data, &post_details, &SystemTime::now(), None)
+                .unwrap();
+
+        assert_eq!(
+            canister_data
+                .all_created_posts
+                .get(&post_id)
+                .unwrap()
+                .collaborator_shares,
+            vec![CollaboratorShare {
+                collaborator_principal_id: get_mock_user_bob_principal_id(),
+                percentage: 30,
+            }]
+        );
+    }
+
+    #[test]
+    fn test_add_post_to_memory_with_title_and_category() {
+        let mut canister_data = CanisterData::default();
+        canister_data.profile.principal_id = Some(get_mock_user_alice_principal_id());
+        canister_data.configuration.post_category_config = PostCategoryConfig {
+            allowed_categories: vec![PostCategory::Comedy, PostCategory::Music],
+        };
+
+        let mut post_details = PostDetailsFromFrontend {
+            description: "Doggos and puppers".into(),
+            hashtags: vec!["doggo".into(), "pupper".into()],
+            video_uid: "abcd#1234".into(),
+            creator_consent_for_inclusion_in_hot_or_not: true,
+            collaborator_shares: None,
+            title: Some("  Doggos  ".into()),
+            category: Some(PostCategory::Sports),
+        };
+
+        // * category not allowed
+        assert!(
+            add_post_to_memory(&mut canister_data, &post_details, &SystemTime::now(), None)
+                .is_err()
+        );
+
+        post_details.category = Some(PostCategory::Comedy);
+        post_details.title = Some("d".repeat(MAXIMUM_POST_TITLE_LENGTH + 1));
+        assert!(
+            add_post_to_memory(&mut canister_data, &post_details, &SystemTime::now(), None)
+                .is_err()
+        );
+
+        post_details.title = Some(" ".into());
+        assert!(
+            add_post_to_memory(&mut canister_data, &post_details, &SystemTime::now(), None)
+                .is_err()
+        );
+        assert!(canister_data.all_created_posts.is_empty());
+
+        post_details.title = Some("  Doggos  ".into());
+        let post_id =
+            add_post_to_memory(&mut canister_data, &post_details, &SystemTime::now(), None)
+                .unwrap();
+        let post = canister_data.all_created_posts.get(&post_id).unwrap();
+        assert_eq!(post.title, Some("Doggos".to_string()));
+        assert_eq!(post.category, Some(PostCategory::Comedy));
+        assert_eq!(
+            canister_data
+                .feature_usage_stats
+                .get_usage_count(get_day_number(&SystemTime::now()), Feature::PostCreated),
+            1
+        );
+    }
+
+    #[test]
+    fn test_add_scheduled_post_to_memory() {
+        let mut canister_data = CanisterData::default();
+        canister_data.profile.principal_id = Some(get_mock_user_alice_principal_id());
+        let post_details = PostDetailsFromFrontend {
+            description: "Doggos and puppers".into(),
+            hashtags: vec!["doggo".into(), "pupper".into()],
+            video_uid: "abcd#1234".into(),
+            creator_consent_for_inclusion_in_hot_or_not: true,
+            collaborator_shares: None,
+            title: None,
+            category: None,
+        };
+        let current_time = SystemTime::now();
+
+        // * in the past, and too far ahead
+        assert!(add_post_to_memory(
+            &mut canister_data,
+            &post_details,
+            &current_time,
+            Some(current_time - Duration::from_secs(60))
+        )
+        .is_err());
+        assert!(add_post_to_memory(
+            &mut canister_data,
+            &post_details,
+            &current_time,
+            Some(current_time + Duration::from_secs(MAXIMUM_PUBLISH_DELAY_IN_SECONDS + 1))
+        )
+        .is_err());
+        assert!(canister_data.all_created_posts.is_empty());
+
+        let publish_at = current_time + Duration::from_secs(2 * 60 * 60);
+        let post_id = add_post_to_memory(
+            &mut canister_data,
+            &post_details,
+            &current_time,
+            Some(publish_at),
+        )
+        .unwrap();
+        let mut post = canister_data.all_created_posts.get(&post_id).unwrap();
+
+        assert_eq!(
+            post.place_hot_or_not_bet(
+                &get_mock_user_bob_principal_id(),
+                &get_mock_user_bob_canister_id(),
+                10,
+                &BetDirection::Hot,
+                &current_time,
+            ),
+            Err(BetOnCurrentlyViewingPostError::BettingNotStarted)
+        );
+        assert_eq!(
+            post.get_hot_or_not_betting_status_for_this_post(
+                &current_time,
+                &get_mock_user_bob_principal_id()
+            ),
+            BettingStatus::BettingNotStarted {
+                starts_at: publish_at
+            }
+        );
+        // * slots are numbered from the publish time
+        assert_eq!(
+            post.get_hot_or_not_betting_status_for_this_post(
+                &(publish_at + Duration::from_secs(30 * 60)),
+                &get_mock_user_bob_principal_id()
+            ),
+            BettingStatus::BettingOpen {
+                started_at: publish_at,
+                number_of_participants: 0,
+                ongoing_slot: 1,
+                ongoing_room: 1,
+                has_this_user_participated_in_this_post: Some(false),
+                minimum_bet_amount: 0,
+            }
+        );
+    }
+}
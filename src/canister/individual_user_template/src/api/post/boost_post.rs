@@ -0,0 +1,300 @@
+use std::time::SystemTime;
+
+use candid::Principal;
+use shared_utils::{
+    canister_specific::individual_user_template::types::{error::BoostPostError, post::PostBoost},
+    common::{
+        types::{
+            app_primitive_type::PostId,
+            utility_token::token_event::{PostBoostEvent, TokenEvent},
+        },
+        utils::system_time::{IcTimeProvider, TimeProvider},
+    },
+};
+
+use crate::{
+    data_model::CanisterData,
+    util::{
+        call_journal::record_call_in_journal, call_queue::queue_call,
+        guards::reject_updates_in_read_only_mode,
+    },
+    CANISTER_DATA,
+};
+
+use super::{
+    add_post_v2::get_post_cache_canister_id,
+    update_scores_and_share_with_post_cache_if_difference_beyond_threshold::update_scores_and_share_with_post_cache_if_difference_beyond_threshold,
+};
+
+/// Burns `amount` utility tokens to raise the post's feed scores for the next 24 hours.
+/// Every 10 tokens add a percentage point, from 100 tokens for +10% up to 1000 tokens for +100%.
+///
+/// # Access Control
+/// Only the user whose profile details are stored in this canister can boost their posts
+#[ic_cdk::update(guard = "reject_updates_in_read_only_mode")]
+#[candid::candid_method(update)]
+fn boost_post(post_id: PostId, amount: u64) -> Result<PostBoost, BoostPostError> {
+    record_call_in_journal("boost_post");
+
+    let api_caller = ic_cdk::caller();
+    let current_time = IcTimeProvider.get_current_time();
+
+    let post_boost = CANISTER_DATA.with(|canister_data_ref_cell| {
+        boost_post_impl(
+            &mut canister_data_ref_cell.borrow_mut(),
+            &api_caller,
+            post_id,
+            amount,
+            &current_time,
+        )
+    })?;
+
+    update_scores_and_share_with_post_cache_if_difference_beyond_threshold(
+        &post_id,
+        &IcTimeProvider,
+    );
+    share_post_boost_with_post_cache(post_id, &post_boost);
+    enqueue_timer_for_boost_expiry(post_id, &post_boost, &current_time);
+
+    Ok(post_boost)
+}
+
+/// Lets the post cache mark the post as sponsored in feeds until the boost runs out
+fn share_post_boost_with_post_cache(post_id: PostId, post_boost: &PostBoost) {
+    if let Some(post_cache_canister_id) = get_post_cache_canister_id() {
+        queue_call(
+            post_cache_canister_id,
+            "receive_post_boost_from_publishing_canister",
+            (post_id, post_boost.boosted_until),
+        );
+    }
+}
+
+/// Pushes the unboosted scores once the boost runs out, as nothing else might touch the post
+pub fn enqueue_timer_for_boost_expiry(
+    post_id: PostId,
+    post_boost: &PostBoost,
+    current_time: &SystemTime,
+) {
+    ic_cdk_timers::set_timer(
+        post_boost
+            .boosted_until
+            .duration_since(*current_time)
+            .unwrap_or_default(),
+        move || {
+            update_scores_and_share_with_post_cache_if_difference_beyond_threshold(
+                &post_id,
+                &IcTimeProvider,
+            );
+        },
+    );
+}
+
+/// Expiry timers for boosts that were still running when the canister was upgraded
+pub fn reenqueue_timers_for_active_post_boosts() {
+    let current_time = IcTimeProvider.get_current_time();
+
+    let active_post_boosts = CANISTER_DATA.with(|canister_data_ref_cell| {
+        get_active_post_boosts(&canister_data_ref_cell.borrow(), &current_time)
+    });
+
+    active_post_boosts.iter().for_each(|(post_id, post_boost)| {
+        enqueue_timer_for_boost_expiry(*post_id, post_boost, &current_time);
+    });
+}
+
+fn get_active_post_boosts(
+    canister_data: &CanisterData,
+    current_time: &SystemTime,
+) -> Vec<(PostId, PostBoost)> {
+    canister_data
+        .all_created_posts
+        .iter()
+        .filter_map(|(post_id, post)| {
+            post.get_active_boost(current_time)
+                .map(|post_boost| (post_id, *post_boost))
+        })
+        .collect()
+}
+
+fn boost_post_impl(
+    canister_data: &mut CanisterData,
+    api_caller: &Principal,
+    post_id: PostId,
+    amount: u64,
+    current_time: &SystemTime,
+) -> Result<PostBoost, BoostPostError> {
+    if canister_data.profile.principal_id != Some(*api_caller) {
+        return Err(BoostPostError::Unauthorized);
+    }
+
+    let mut post = canister_data
+        .all_created_posts
+        .get(&post_id)
+        .ok_or(BoostPostError::PostNotFound)?;
+
+    if amount > canister_data.my_token_balance.get_utility_token_balance() {
+        return Err(BoostPostError::InsufficientBalance);
+    }
+
+    let post_boost = post.boost(amount, current_time)?;
+    canister_data.all_created_posts.insert(post_id, post);
+
+    canister_data.handle_token_event(TokenEvent::PostBoost {
+        amount,
+        details: PostBoostEvent::BoostedOwnPost {
+            post_id,
+            boost_percentage: post_boost.boost_percentage,
+            boosted_until: post_boost.boosted_until,
+        },
+        timestamp: *current_time,
+    });
+
+    Ok(post_boost)
+}
+
+#[cfg(test)]
+mod test {
+    use shared_utils::canister_specific::individual_user_template::types::post::{
+        Post, PostDetailsFromFrontend, POST_BOOST_DURATION,
+    };
+    use test_utils::setup::test_constants::{
+        get_mock_user_alice_principal_id, get_mock_user_bob_principal_id,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_boost_post_impl() {
+        let mut canister_data = CanisterData::default();
+        let current_time = SystemTime::now();
+        canister_data.profile.principal_id = Some(get_mock_user_alice_principal_id());
+        canister_data.my_token_balance.utility_token_balance = 1500;
+        canister_data.all_created_posts.insert(
+            0,
+            Post::new(
+                0,
+                &PostDetailsFromFrontend {
+                    description: "Doggos and puppers".into(),
+                    hashtags: vec!["doggo".into(), "pupper".into()],
+                    video_uid: "abcd#1234".into(),
+                    creator_consent_for_inclusion_in_hot_or_not: true,
+                    collaborator_shares: None,
+                    title: None,
+                    category: None,
+                },
+                &current_time,
+            ),
+        );
+
+        assert_eq!(
+            boost_post_impl(
+                &mut canister_data,
+                &get_mock_user_bob_principal_id(),
+                0,
+                200,
+                &current_time
+            ),
+            Err(BoostPostError::Unauthorized)
+        );
+        assert_eq!(
+            boost_post_impl(
+                &mut canister_data,
+                &get_mock_user_alice_principal_id(),
+                1,
+                200,
+                &current_time
+            ),
+            Err(BoostPostError::PostNotFound)
+        );
+        assert_eq!(
+            boost_post_impl(
+                &mut canister_data,
+                &get_mock_user_alice_principal_id(),
+                0,
+                2000,
+                &current_time
+            ),
+            Err(BoostPostError::InsufficientBalance)
+        );
+        assert_eq!(
+            boost_post_impl(
+                &mut canister_data,
+                &get_mock_user_alice_principal_id(),
+                0,
+                50,
+                &current_time
+            ),
+            Err(BoostPostError::BoostAmountBelowMinimum)
+        );
+        assert_eq!(
+            boost_post_impl(
+                &mut canister_data,
+                &get_mock_user_alice_principal_id(),
+                0,
+                1010,
+                &current_time
+            ),
+            Err(BoostPostError::BoostAmountAboveMaximum)
+        );
+        assert_eq!(canister_data.my_token_balance.utility_token_balance, 1500);
+        assert!(canister_data.token_transaction_history.is_empty());
+
+        let post_boost = boost_post_impl(
+            &mut canister_data,
+            &get_mock_user_alice_principal_id(),
+            0,
+            250,
+            &current_time,
+        )
+        .unwrap();
+        assert_eq!(post_boost.boost_percentage, 25);
+        assert_eq!(post_boost.boosted_until, current_time + POST_BOOST_DURATION);
+        assert_eq!(canister_data.my_token_balance.utility_token_balance, 1250);
+        assert_eq!(
+            canister_data
+                .token_transaction_history
+                .iter_rev()
+                .next()
+                .map(|(_index, token_event)| token_event),
+            Some(TokenEvent::PostBoost {
+                amount: 250,
+                details: PostBoostEvent::BoostedOwnPost {
+                    post_id: 0,
+                    boost_percentage: 25,
+                    boosted_until: post_boost.boosted_until,
+                },
+                timestamp: current_time,
+            })
+        );
+        assert_eq!(
+            get_active_post_boosts(&canister_data, &current_time),
+            vec![(0, post_boost)]
+        );
+
+        assert_eq!(
+            boost_post_impl(
+                &mut canister_data,
+                &get_mock_user_alice_principal_id(),
+                0,
+                250,
+                &current_time
+            ),
+            Err(BoostPostError::PostAlreadyBoosted)
+        );
+        assert_eq!(canister_data.my_token_balance.utility_token_balance, 1250);
+
+        // * the post can be boosted again once the boost has run out
+        let boost_expiry_time = post_boost.boosted_until;
+        assert!(get_active_post_boosts(&canister_data, &boost_expiry_time).is_empty());
+        assert!(boost_post_impl(
+            &mut canister_data,
+            &get_mock_user_alice_principal_id(),
+            0,
+            100,
+            &boost_expiry_time
+        )
+        .is_ok());
+        assert_eq!(canister_data.my_token_balance.utility_token_balance, 1150);
+    }
+}
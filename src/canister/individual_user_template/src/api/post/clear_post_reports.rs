@@ -0,0 +1,179 @@
+use candid::Principal;
+use shared_utils::{
+    canister_specific::individual_user_template::types::error::ClearPostReportsError,
+    common::{
+        types::{app_primitive_type::PostId, known_principal::KnownPrincipalType},
+        utils::known_caller::authorize_known_caller,
+    },
+};
+
+use crate::{
+    data_model::CanisterData,
+    util::{
+        call_journal::record_call_in_journal, certified_post_details::recertify_post_details,
+        guards::reject_updates_in_read_only_mode,
+    },
+    CANISTER_DATA,
+};
+
+/// Dismisses every unresolved report on the post, reopening betting if it was paused for review
+///
+/// # Access Control
+/// Only the global super admin, acting as moderator, or the user index when moderators dismiss
+/// the post's reports from its moderation queue
+#[ic_cdk::update(guard = "reject_updates_in_read_only_mode")]
+#[candid::candid_method(update)]
+fn clear_post_reports(post_id: PostId) -> Result<(), ClearPostReportsError> {
+    record_call_in_journal("clear_post_reports");
+
+    let api_caller = ic_cdk::caller();
+
+    let result = CANISTER_DATA.with(|canister_data_ref_cell| {
+        clear_post_reports_impl(
+            &mut canister_data_ref_cell.borrow_mut(),
+            &api_caller,
+            post_id,
+        )
+    });
+
+    if result.is_ok() {
+        recertify_post_details(post_id);
+    }
+
+    result
+}
+
+fn clear_post_reports_impl(
+    canister_data: &mut CanisterData,
+    api_caller: &Principal,
+    post_id: PostId,
+) -> Result<(), ClearPostReportsError> {
+    authorize_known_caller(
+        api_caller,
+        &canister_data.known_principal_ids,
+        &[
+            KnownPrincipalType::UserIdGlobalSuperAdmin,
+            KnownPrincipalType::CanisterIdUserIndex,
+        ],
+    )
+    .map_err(|_| ClearPostReportsError::Unauthorized)?;
+
+    canister_data
+        .all_created_posts
+        .update(&post_id, |post| post.clear_reports())
+        .ok_or(ClearPostReportsError::PostNotFound)?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::SystemTime;
+
+    use shared_utils::canister_specific::individual_user_template::types::{
+        hot_or_not::BettingStatus,
+        post::{Post, PostDetailsFromFrontend},
+    };
+    use test_utils::setup::{
+        spoofed_callers::{assert_only_allowed_callers_are_accepted, get_mock_known_principal_map},
+        test_constants::{
+            get_global_super_admin_principal_id, get_mock_user_alice_principal_id,
+            v2::{user, users},
+        },
+    };
+
+    use super::*;
+
+    fn get_canister_data_with_reported_post() -> CanisterData {
+        let mut canister_data = CanisterData {
+            known_principal_ids: get_mock_known_principal_map(),
+            ..Default::default()
+        };
+        let current_time = SystemTime::now();
+        canister_data.profile.principal_id = Some(get_mock_user_alice_principal_id());
+
+        let mut post = Post::new(
+            0,
+            &PostDetailsFromFrontend {
+                description: "Doggos and puppers".into(),
+                hashtags: vec!["doggo".into(), "pupper".into()],
+                video_uid: "abcd#1234".into(),
+                creator_consent_for_inclusion_in_hot_or_not: true,
+                collaborator_shares: None,
+                title: None,
+                category: None,
+            },
+            &current_time,
+        );
+        users("reporter", 6).iter().for_each(|reporter| {
+            post.report(reporter, &current_time).unwrap();
+        });
+        canister_data.all_created_posts.insert(0, post);
+
+        canister_data
+    }
+
+    #[test]
+    fn test_clear_post_reports_impl() {
+        let mut canister_data = get_canister_data_with_reported_post();
+        let current_time = canister_data.all_created_posts.get(&0).unwrap().created_at;
+
+        assert_eq!(
+            canister_data
+                .all_created_posts
+                .get(&0)
+                .unwrap()
+                .get_hot_or_not_betting_status_for_this_post(&current_time, &user("bettor")),
+            BettingStatus::BettingPausedForReview
+        );
+        assert_eq!(
+            clear_post_reports_impl(&mut canister_data, &get_mock_user_alice_principal_id(), 0),
+            Err(ClearPostReportsError::Unauthorized)
+        );
+        assert_eq!(
+            clear_post_reports_impl(
+                &mut canister_data,
+                &get_global_super_admin_principal_id(),
+                1
+            ),
+            Err(ClearPostReportsError::PostNotFound)
+        );
+        assert_eq!(
+            clear_post_reports_impl(
+                &mut canister_data,
+                &get_global_super_admin_principal_id(),
+                0
+            ),
+            Ok(())
+        );
+        assert!(canister_data
+            .all_created_posts
+            .get(&0)
+            .unwrap()
+            .unresolved_reports
+            .is_empty());
+        assert!(matches!(
+            canister_data
+                .all_created_posts
+                .get(&0)
+                .unwrap()
+                .get_hot_or_not_betting_status_for_this_post(&current_time, &user("bettor")),
+            BettingStatus::BettingOpen { .. }
+        ));
+    }
+
+    #[test]
+    fn test_clear_post_reports_impl_with_spoofed_callers() {
+        assert_only_allowed_callers_are_accepted(
+            &get_mock_known_principal_map(),
+            &[
+                KnownPrincipalType::UserIdGlobalSuperAdmin,
+                KnownPrincipalType::CanisterIdUserIndex,
+            ],
+            |caller| {
+                let mut canister_data = get_canister_data_with_reported_post();
+                clear_post_reports_impl(&mut canister_data, caller, 0).is_ok()
+            },
+        );
+    }
+}
@@ -0,0 +1,34 @@
+use shared_utils::canister_specific::individual_user_template::types::{
+    error::GetCertifiedPostDetailsError, post::CertifiedPostDetailsForFrontend,
+};
+
+use crate::CERTIFIED_POST_DETAILS;
+
+/// Certified counterpart of `get_individual_post_details_by_id`, so that boundary nodes
+/// can cache the response for hot posts. Serves the snapshot taken when the post was last
+/// recertified, which lags behind the live details in between mutations.
+#[ic_cdk::query]
+#[candid::candid_method(query)]
+fn get_certified_individual_post_details_by_id(
+    post_id: u64,
+) -> Result<CertifiedPostDetailsForFrontend, GetCertifiedPostDetailsError> {
+    let certificate = ic_cdk::api::data_certificate()
+        .ok_or(GetCertifiedPostDetailsError::CertificateUnavailable)?;
+
+    CERTIFIED_POST_DETAILS.with(|certified_post_details_ref_cell| {
+        let certified_post_details = certified_post_details_ref_cell.borrow();
+        let (version, post_details) = certified_post_details
+            .get_snapshot(post_id)
+            .cloned()
+            .ok_or(GetCertifiedPostDetailsError::PostNotFound)?;
+
+        Ok(CertifiedPostDetailsForFrontend {
+            version,
+            post_details,
+            certificate,
+            witness: certified_post_details
+                .witness_for(post_id)
+                .serialize_to_cbor(),
+        })
+    })
+}
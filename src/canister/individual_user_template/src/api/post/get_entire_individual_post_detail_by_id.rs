@@ -3,7 +3,7 @@ use shared_utils::{
     common::types::known_principal::KnownPrincipalType,
 };
 
-use crate::CANISTER_DATA;
+use crate::{data_model::CanisterData, CANISTER_DATA};
 
 #[ic_cdk::query]
 #[candid::candid_method(query)]
@@ -23,13 +23,122 @@ pub fn get_entire_individual_post_detail_by_id(post_id: u64) -> Result<Post, ()>
     }
 
     CANISTER_DATA.with(|canister_data_ref_cell| {
-        let post = canister_data_ref_cell
-            .borrow()
-            .all_created_posts
-            .get(&post_id)
-            .unwrap()
-            .clone();
-
-        Ok(post)
+        get_post_with_hidden_bet_makers_pseudonymized(&canister_data_ref_cell.borrow(), post_id)
     })
 }
+
+/// Bet makers who hide their betting activity show up under their pseudonyms. Until the
+/// pseudonym salt has been generated, no post is handed out while there are any of them.
+fn get_post_with_hidden_bet_makers_pseudonymized(
+    canister_data: &CanisterData,
+    post_id: u64,
+) -> Result<Post, ()> {
+    let mut post = canister_data
+        .all_created_posts
+        .get_with_archived_bets(&post_id)
+        .unwrap();
+
+    if canister_data.bet_makers_hiding_betting_activity.is_empty() {
+        return Ok(post);
+    }
+
+    if canister_data.pseudonym_salt.is_empty() {
+        return Err(());
+    }
+
+    if let Some(hot_or_not_details) = post.hot_or_not_details.as_mut() {
+        hot_or_not_details.pseudonymize_bet_makers(
+            |bet_maker| {
+                canister_data
+                    .bet_makers_hiding_betting_activity
+                    .contains(bet_maker)
+            },
+            &canister_data.pseudonym_salt,
+        );
+    }
+
+    Ok(post)
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::SystemTime;
+
+    use shared_utils::{
+        canister_specific::individual_user_template::types::{
+            hot_or_not::BetDirection, post::PostDetailsFromFrontend,
+        },
+        common::utils::pseudonym::pseudonymize_principal,
+    };
+    use test_utils::setup::test_constants::{
+        get_mock_user_bob_canister_id, get_mock_user_bob_principal_id,
+        get_mock_user_charlie_canister_id, get_mock_user_charlie_principal_id,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_get_post_with_hidden_bet_makers_pseudonymized() {
+        let mut canister_data = CanisterData::default();
+        let current_time = SystemTime::now();
+        let mut post = Post::new(
+            0,
+            &PostDetailsFromFrontend {
+                description: "Doggos and puppers".into(),
+                hashtags: vec!["doggo".into(), "pupper".into()],
+                video_uid: "abcd#1234".into(),
+                creator_consent_for_inclusion_in_hot_or_not: true,
+                collaborator_shares: None,
+                title: None,
+                category: None,
+            },
+            &current_time,
+        );
+        let bob = get_mock_user_bob_principal_id();
+        let charlie = get_mock_user_charlie_principal_id();
+        post.place_hot_or_not_bet(
+            &bob,
+            &get_mock_user_bob_canister_id(),
+            100,
+            &BetDirection::Hot,
+            &current_time,
+        )
+        .unwrap();
+        post.place_hot_or_not_bet(
+            &charlie,
+            &get_mock_user_charlie_canister_id(),
+            100,
+            &BetDirection::Not,
+            &current_time,
+        )
+        .unwrap();
+        canister_data.all_created_posts.insert(0, post);
+
+        let get_bet_makers = |post: Post| {
+            post.hot_or_not_details.unwrap().slot_history[&1].room_details[&1]
+                .bets_made
+                .keys()
+                .copied()
+                .collect::<Vec<_>>()
+        };
+
+        let bet_makers = get_bet_makers(
+            get_post_with_hidden_bet_makers_pseudonymized(&canister_data, 0).unwrap(),
+        );
+        assert_eq!(bet_makers.len(), 2);
+        assert!(bet_makers.contains(&bob));
+        assert!(bet_makers.contains(&charlie));
+
+        canister_data.record_betting_activity_privacy(&bob, Some(true));
+        assert!(get_post_with_hidden_bet_makers_pseudonymized(&canister_data, 0).is_err());
+
+        canister_data.pseudonym_salt = vec![7; 32];
+        let bet_makers = get_bet_makers(
+            get_post_with_hidden_bet_makers_pseudonymized(&canister_data, 0).unwrap(),
+        );
+        assert_eq!(bet_makers.len(), 2);
+        assert!(bet_makers.contains(&pseudonymize_principal(&bob, &[7; 32])));
+        assert!(bet_makers.contains(&charlie));
+        assert!(!bet_makers.contains(&bob));
+    }
+}
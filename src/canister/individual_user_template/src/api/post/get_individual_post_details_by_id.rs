@@ -13,16 +13,20 @@ pub fn get_individual_post_details_by_id(post_id: u64) -> PostDetailsForFrontend
     let api_caller = ic_cdk::caller();
 
     CANISTER_DATA.with(|canister_data_ref_cell| {
-        let post = canister_data_ref_cell
+        let mut post = canister_data_ref_cell
             .borrow()
             .all_created_posts
             .get(&post_id)
-            .unwrap()
-            .clone();
+            .unwrap();
+        post.view_stats.total_view_count += canister_data_ref_cell
+            .borrow()
+            .post_view_buffer
+            .get_pending_view_count(post_id);
         let profile = &canister_data_ref_cell.borrow().profile;
         let followers = &canister_data_ref_cell.borrow().principals_that_follow_me;
         let following = &canister_data_ref_cell.borrow().principals_i_follow;
         let token_balance = &canister_data_ref_cell.borrow().my_token_balance;
+        let achievements = &canister_data_ref_cell.borrow().achievements;
 
         post.get_post_details_for_frontend_for_this_post(
             UserProfileDetailsForFrontend {
@@ -34,6 +38,7 @@ pub fn get_individual_post_details_by_id(post_id: u64) -> PostDetailsForFrontend
                 profile_stats: profile.profile_stats,
                 unique_user_name: profile.unique_user_name.clone(),
                 lifetime_earnings: token_balance.lifetime_earnings,
+                badges: achievements.get_badges(),
             },
             api_caller,
             &system_time::get_current_system_time_from_ic(),
@@ -0,0 +1,36 @@
+use shared_utils::{
+    canister_specific::individual_user_template::types::post::projection::{
+        PostCreatorDetails, PostField, ProjectedPostDetails,
+    },
+    common::utils::system_time,
+};
+
+use crate::CANISTER_DATA;
+
+/// Like `get_individual_post_details_by_id`, with only the groups of fields in `fields`.
+/// None if there is no such post.
+#[ic_cdk::query]
+#[candid::candid_method(query)]
+fn get_individual_post_details_by_id_with_projection(
+    post_id: u64,
+    fields: Vec<PostField>,
+) -> Option<ProjectedPostDetails> {
+    let api_caller = ic_cdk::caller();
+    let current_time = system_time::get_current_system_time_from_ic();
+
+    CANISTER_DATA.with(|canister_data_ref_cell| {
+        let canister_data = canister_data_ref_cell.borrow();
+
+        let mut post = canister_data.all_created_posts.get(&post_id)?;
+        post.view_stats.total_view_count += canister_data
+            .post_view_buffer
+            .get_pending_view_count(post_id);
+
+        Some(post.get_projected_post_details(
+            &fields,
+            PostCreatorDetails::from_profile(&canister_data.profile).as_ref(),
+            api_caller,
+            &current_time,
+        ))
+    })
+}
@@ -0,0 +1,133 @@
+use std::time::SystemTime;
+
+use candid::Principal;
+use shared_utils::{
+    canister_specific::individual_user_template::types::{
+        post::{PostDetailsForFrontend, PostStatus},
+        profile::UserProfileDetailsForFrontend,
+    },
+    common::utils::system_time,
+    pagination::MAX_ITEMS_PER_PAGE,
+};
+
+use crate::{data_model::CanisterData, CANISTER_DATA};
+
+/// Up to `number_of_posts` of this profile's latest posts that are ready to view, newest
+/// first. Followers' canisters fan out to this to put together the feed of the creators
+/// their owner follows, so the details are as seen by `viewer_principal_id` rather than by
+/// the calling canister.
+#[ic_cdk::query]
+#[candid::candid_method(query)]
+fn get_latest_posts_of_this_user_profile(
+    number_of_posts: u64,
+    viewer_principal_id: Principal,
+) -> Vec<PostDetailsForFrontend> {
+    CANISTER_DATA.with(|canister_data_ref_cell| {
+        get_latest_posts_of_this_user_profile_impl(
+            &canister_data_ref_cell.borrow(),
+            number_of_posts,
+            viewer_principal_id,
+            &system_time::get_current_system_time_from_ic(),
+        )
+    })
+}
+
+fn get_latest_posts_of_this_user_profile_impl(
+    canister_data: &CanisterData,
+    number_of_posts: u64,
+    viewer_principal_id: Principal,
+    current_time: &SystemTime,
+) -> Vec<PostDetailsForFrontend> {
+    let Some(principal_id) = canister_data.profile.principal_id else {
+        return vec![];
+    };
+    let profile = &canister_data.profile;
+
+    canister_data
+        .all_created_posts
+        .iter_rev()
+        .filter(|(_, post)| matches!(post.status, PostStatus::ReadyToView))
+        .take(number_of_posts.min(MAX_ITEMS_PER_PAGE) as usize)
+        .map(|(post_id, mut post)| {
+            post.view_stats.total_view_count += canister_data
+                .post_view_buffer
+                .get_pending_view_count(post_id);
+
+            post.get_post_details_for_frontend_for_this_post(
+                UserProfileDetailsForFrontend {
+                    display_name: profile.display_name.clone(),
+                    followers_count: canister_data.follow_data.follower.len() as u64,
+                    following_count: canister_data.follow_data.following.len() as u64,
+                    principal_id,
+                    profile_picture_url: profile.profile_picture_url.clone(),
+                    profile_stats: profile.profile_stats,
+                    unique_user_name: profile.unique_user_name.clone(),
+                    lifetime_earnings: canister_data.my_token_balance.lifetime_earnings,
+                    badges: canister_data.achievements.get_badges(),
+                },
+                viewer_principal_id,
+                current_time,
+            )
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use shared_utils::canister_specific::individual_user_template::types::post::{
+        Post, PostDetailsFromFrontend,
+    };
+    use test_utils::setup::test_constants::{
+        get_mock_user_alice_principal_id, get_mock_user_bob_principal_id,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_get_latest_posts_of_this_user_profile_impl() {
+        let mut canister_data = CanisterData::default();
+        let current_time = SystemTime::now();
+
+        assert!(get_latest_posts_of_this_user_profile_impl(
+            &canister_data,
+            10,
+            get_mock_user_bob_principal_id(),
+            &current_time,
+        )
+        .is_empty());
+
+        canister_data.profile.principal_id = Some(get_mock_user_alice_principal_id());
+        (0..4).for_each(|post_id| {
+            let mut post = Post::new(
+                post_id,
+                &PostDetailsFromFrontend {
+                    description: "Doggos and puppers".into(),
+                    hashtags: vec!["doggo".into(), "pupper".into()],
+                    video_uid: "abcd#1234".into(),
+                    creator_consent_for_inclusion_in_hot_or_not: false,
+                    collaborator_shares: None,
+                    title: None,
+                    category: None,
+                },
+                &current_time,
+            );
+            if post_id != 2 {
+                post.status = PostStatus::ReadyToView;
+            }
+            canister_data.all_created_posts.insert(post_id, post);
+        });
+
+        assert_eq!(
+            get_latest_posts_of_this_user_profile_impl(
+                &canister_data,
+                2,
+                get_mock_user_bob_principal_id(),
+                &current_time,
+            )
+            .iter()
+            .map(|post_details| post_details.id)
+            .collect::<Vec<_>>(),
+            vec![3, 1]
+        );
+    }
+}
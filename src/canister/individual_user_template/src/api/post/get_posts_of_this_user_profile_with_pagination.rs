@@ -19,8 +19,7 @@ fn get_posts_of_this_user_profile_with_pagination(
         from_inclusive_id,
         to_exclusive_id,
         CANISTER_DATA
-            .with(|canister_data_ref_cell| canister_data_ref_cell.borrow().all_created_posts.len())
-            as u64,
+            .with(|canister_data_ref_cell| canister_data_ref_cell.borrow().all_created_posts.len()),
     )
     .map_err(|e| match e {
         PaginationError::InvalidBoundsPassed => GetPostsOfUserProfileError::InvalidBoundsPassed,
@@ -36,16 +35,20 @@ fn get_posts_of_this_user_profile_with_pagination(
     Ok((from_inclusive_id..to_exclusive_id)
         .map(|id| {
             CANISTER_DATA.with(|canister_data_ref_cell| {
-                let post = canister_data_ref_cell
+                let mut post = canister_data_ref_cell
                     .borrow()
                     .all_created_posts
                     .get(&id)
-                    .unwrap()
-                    .clone();
+                    .unwrap();
+                post.view_stats.total_view_count += canister_data_ref_cell
+                    .borrow()
+                    .post_view_buffer
+                    .get_pending_view_count(id);
                 let profile = &canister_data_ref_cell.borrow().profile;
                 let followers = &canister_data_ref_cell.borrow().principals_that_follow_me;
                 let following = &canister_data_ref_cell.borrow().principals_i_follow;
                 let token_balance = &canister_data_ref_cell.borrow().my_token_balance;
+                let achievements = &canister_data_ref_cell.borrow().achievements;
 
                 post.get_post_details_for_frontend_for_this_post(
                     UserProfileDetailsForFrontend {
@@ -57,6 +60,7 @@ fn get_posts_of_this_user_profile_with_pagination(
                         profile_stats: profile.profile_stats,
                         unique_user_name: profile.unique_user_name.clone(),
                         lifetime_earnings: token_balance.lifetime_earnings,
+                        badges: achievements.get_badges(),
                     },
                     api_caller,
                     &current_time,
@@ -0,0 +1,60 @@
+use shared_utils::{
+    canister_specific::individual_user_template::types::{
+        error::GetPostsOfUserProfileError,
+        post::projection::{PostCreatorDetails, PostField, ProjectedPostDetails},
+    },
+    common::utils::system_time,
+    pagination::{self, PaginationError},
+};
+
+use crate::CANISTER_DATA;
+
+/// Like `get_posts_of_this_user_profile_with_pagination`, with only the groups of fields in
+/// `fields`
+#[ic_cdk::query]
+#[candid::candid_method(query)]
+fn get_posts_of_this_user_profile_with_pagination_and_projection(
+    from_inclusive_id: u64,
+    to_exclusive_id: u64,
+    fields: Vec<PostField>,
+) -> Result<Vec<ProjectedPostDetails>, GetPostsOfUserProfileError> {
+    let (from_inclusive_id, to_exclusive_id) = pagination::get_pagination_bounds(
+        from_inclusive_id,
+        to_exclusive_id,
+        CANISTER_DATA
+            .with(|canister_data_ref_cell| canister_data_ref_cell.borrow().all_created_posts.len()),
+    )
+    .map_err(|e| match e {
+        PaginationError::InvalidBoundsPassed => GetPostsOfUserProfileError::InvalidBoundsPassed,
+        PaginationError::ReachedEndOfItemsList => GetPostsOfUserProfileError::ReachedEndOfItemsList,
+        PaginationError::ExceededMaxNumberOfItemsAllowedInOneRequest => {
+            GetPostsOfUserProfileError::ExceededMaxNumberOfItemsAllowedInOneRequest
+        }
+    })?;
+
+    let api_caller = ic_cdk::caller();
+    let current_time = system_time::get_current_system_time_from_ic();
+
+    CANISTER_DATA.with(|canister_data_ref_cell| {
+        let canister_data = canister_data_ref_cell.borrow();
+        let creator = match fields.contains(&PostField::Creator) {
+            true => PostCreatorDetails::from_profile(&canister_data.profile),
+            false => None,
+        };
+
+        Ok((from_inclusive_id..to_exclusive_id)
+            .filter_map(|id| {
+                let mut post = canister_data.all_created_posts.get(&id)?;
+                post.view_stats.total_view_count +=
+                    canister_data.post_view_buffer.get_pending_view_count(id);
+
+                Some(post.get_projected_post_details(
+                    &fields,
+                    creator.as_ref(),
+                    api_caller,
+                    &current_time,
+                ))
+            })
+            .collect())
+    })
+}
@@ -1,9 +1,20 @@
 pub mod add_post_v2;
+pub mod boost_post;
+pub mod clear_post_reports;
+pub mod get_certified_individual_post_details_by_id;
 pub mod get_entire_individual_post_detail_by_id;
 pub mod get_individual_post_details_by_id;
+pub mod get_individual_post_details_by_id_with_projection;
+pub mod get_latest_posts_of_this_user_profile;
 pub mod get_posts_of_this_user_profile_with_pagination;
+pub mod get_posts_of_this_user_profile_with_pagination_and_projection;
+pub mod receive_hashtag_merge_from_post_cache;
+pub mod receive_post_ban_from_user_index;
+pub mod report_post;
 pub mod update_post_add_view_details;
 pub mod update_post_as_ready_to_view;
 pub mod update_post_increment_share_count;
+pub mod update_post_minimum_bet_amount;
+pub mod update_post_revoke_hot_or_not_consent;
 pub mod update_post_toggle_like_status_by_caller;
 pub mod update_scores_and_share_with_post_cache_if_difference_beyond_threshold;
@@ -0,0 +1,152 @@
+use candid::Principal;
+use shared_utils::common::{
+    types::{app_primitive_type::PostId, known_principal::KnownPrincipalType},
+    utils::known_caller::authorize_known_caller,
+};
+
+use crate::{
+    data_model::CanisterData,
+    util::{
+        call_journal::record_call_in_journal, certified_post_details::recertify_post_details,
+        guards::reject_updates_in_read_only_mode,
+    },
+    CANISTER_DATA,
+};
+
+/// Hashtags moderators merged into another one on the post cache, as (merged hashtag,
+/// hashtag it was merged into), along with the posts of this profile the post cache retagged.
+/// Those posts are retagged here as well, and the merges are applied to posts created from
+/// now on.
+///
+/// # Access Control
+/// Only post cache canisters
+#[ic_cdk::update(guard = "reject_updates_in_read_only_mode")]
+#[candid::candid_method(update)]
+fn receive_hashtag_merge_from_post_cache(
+    merged_hashtags: Vec<(String, String)>,
+    post_ids: Vec<PostId>,
+) {
+    record_call_in_journal("receive_hashtag_merge_from_post_cache");
+
+    let api_caller = ic_cdk::caller();
+
+    let retagged_post_ids = CANISTER_DATA.with(|canister_data_ref_cell| {
+        receive_hashtag_merge_from_post_cache_impl(
+            &mut canister_data_ref_cell.borrow_mut(),
+            &api_caller,
+            &merged_hashtags,
+            &post_ids,
+        )
+    });
+
+    retagged_post_ids
+        .into_iter()
+        .for_each(recertify_post_details);
+}
+
+/// Returns the posts whose hashtags changed
+fn receive_hashtag_merge_from_post_cache_impl(
+    canister_data: &mut CanisterData,
+    api_caller: &Principal,
+    merged_hashtags: &[(String, String)],
+    post_ids: &[PostId],
+) -> Vec<PostId> {
+    if authorize_known_caller(
+        api_caller,
+        &canister_data.known_principal_ids,
+        &[KnownPrincipalType::CanisterIdPostCache],
+    )
+    .is_err()
+    {
+        return vec![];
+    }
+
+    merged_hashtags.iter().for_each(|(hashtag, into_hashtag)| {
+        // * Shards can disagree, e.g. on the order merges were made in. The merge that
+        // * would undo an earlier one is the one skipped.
+        let _ = canister_data.hashtag_aliases.merge(hashtag, into_hashtag);
+    });
+
+    post_ids
+        .iter()
+        .filter(|post_id| {
+            canister_data
+                .all_created_posts
+                .update(post_id, |post| {
+                    let retagged_hashtags = canister_data.hashtag_aliases.apply(&post.hashtags);
+                    let is_retagged = retagged_hashtags != post.hashtags;
+                    post.hashtags = retagged_hashtags;
+                    is_retagged
+                })
+                .unwrap_or_default()
+        })
+        .copied()
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::SystemTime;
+
+    use shared_utils::canister_specific::individual_user_template::types::post::{
+        Post, PostDetailsFromFrontend,
+    };
+    use test_utils::setup::test_constants::{
+        get_mock_canister_id_post_cache, get_mock_user_alice_principal_id,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_receive_hashtag_merge_from_post_cache_impl() {
+        let mut canister_data = CanisterData::default();
+        canister_data.known_principal_ids.insert(
+            KnownPrincipalType::CanisterIdPostCache,
+            get_mock_canister_id_post_cache(),
+        );
+        [vec!["dogo", "doggo"], vec!["cat"]]
+            .into_iter()
+            .enumerate()
+            .for_each(|(post_id, hashtags)| {
+                let post = Post::new(
+                    post_id as u64,
+                    &PostDetailsFromFrontend {
+                        description: "Doggos and puppers".into(),
+                        hashtags: hashtags.into_iter().map(String::from).collect(),
+                        video_uid: "abcd#1234".into(),
+                        creator_consent_for_inclusion_in_hot_or_not: true,
+                        collaborator_shares: None,
+                        title: None,
+                        category: None,
+                    },
+                    &SystemTime::now(),
+                );
+                canister_data.all_created_posts.insert(post.id, post);
+            });
+        let merged_hashtags = vec![("dogo".to_string(), "doggo".to_string())];
+
+        assert!(receive_hashtag_merge_from_post_cache_impl(
+            &mut canister_data,
+            &get_mock_user_alice_principal_id(),
+            &merged_hashtags,
+            &[0, 1]
+        )
+        .is_empty());
+        assert_eq!(canister_data.hashtag_aliases.resolve("dogo"), "dogo");
+
+        assert_eq!(
+            receive_hashtag_merge_from_post_cache_impl(
+                &mut canister_data,
+                &get_mock_canister_id_post_cache(),
+                &merged_hashtags,
+                &[0, 1, 2]
+            ),
+            vec![0]
+        );
+        assert_eq!(
+            canister_data.all_created_posts.get(&0).unwrap().hashtags,
+            vec!["doggo".to_string()]
+        );
+        assert_eq!(canister_data.hashtag_aliases.resolve("dogo"), "doggo");
+    }
+}
@@ -0,0 +1,140 @@
+use candid::Principal;
+use shared_utils::{
+    canister_specific::individual_user_template::types::post::PostStatus,
+    common::{
+        types::{app_primitive_type::PostId, known_principal::KnownPrincipalType},
+        utils::known_caller::authorize_known_caller,
+    },
+};
+
+use crate::{
+    data_model::CanisterData,
+    util::{
+        call_journal::record_call_in_journal, certified_post_details::recertify_post_details,
+        guards::reject_updates_in_read_only_mode,
+    },
+    CANISTER_DATA,
+};
+
+/// Moderators upheld the reports raised against the post. It is kept, but its scores stop
+/// being shared with the post cache so it drops out of feeds.
+///
+/// # Access Control
+/// Only the user index, on behalf of its moderators
+#[ic_cdk::update(guard = "reject_updates_in_read_only_mode")]
+#[candid::candid_method(update)]
+fn receive_post_ban_from_user_index(post_id: PostId) {
+    record_call_in_journal("receive_post_ban_from_user_index");
+
+    let api_caller = ic_cdk::caller();
+
+    let is_banned = CANISTER_DATA.with(|canister_data_ref_cell| {
+        receive_post_ban_from_user_index_impl(
+            &mut canister_data_ref_cell.borrow_mut(),
+            &api_caller,
+            post_id,
+        )
+    });
+
+    if is_banned {
+        recertify_post_details(post_id);
+    }
+}
+
+fn receive_post_ban_from_user_index_impl(
+    canister_data: &mut CanisterData,
+    api_caller: &Principal,
+    post_id: PostId,
+) -> bool {
+    if authorize_known_caller(
+        api_caller,
+        &canister_data.known_principal_ids,
+        &[KnownPrincipalType::CanisterIdUserIndex],
+    )
+    .is_err()
+    {
+        return false;
+    }
+
+    canister_data
+        .all_created_posts
+        .update(&post_id, |post| {
+            post.update_status(PostStatus::BannedDueToUserReporting)
+        })
+        .is_some()
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::SystemTime;
+
+    use shared_utils::canister_specific::individual_user_template::types::post::{
+        Post, PostDetailsFromFrontend,
+    };
+    use test_utils::setup::spoofed_callers::{
+        assert_only_allowed_callers_are_accepted, get_mock_known_principal_map,
+    };
+
+    use super::*;
+
+    fn get_canister_data_with_post() -> CanisterData {
+        let mut canister_data = CanisterData {
+            known_principal_ids: get_mock_known_principal_map(),
+            ..Default::default()
+        };
+        canister_data.all_created_posts.insert(
+            0,
+            Post::new(
+                0,
+                &PostDetailsFromFrontend {
+                    description: "Doggos and puppers".into(),
+                    hashtags: vec!["doggo".into(), "pupper".into()],
+                    video_uid: "abcd#1234".into(),
+                    creator_consent_for_inclusion_in_hot_or_not: true,
+                    collaborator_shares: None,
+                    title: None,
+                    category: None,
+                },
+                &SystemTime::now(),
+            ),
+        );
+
+        canister_data
+    }
+
+    #[test]
+    fn test_receive_post_ban_from_user_index_impl() {
+        let mut canister_data = get_canister_data_with_post();
+        let user_index_canister_id = *canister_data
+            .known_principal_ids
+            .get(&KnownPrincipalType::CanisterIdUserIndex)
+            .unwrap();
+
+        assert!(!receive_post_ban_from_user_index_impl(
+            &mut canister_data,
+            &user_index_canister_id,
+            1
+        ));
+        assert!(receive_post_ban_from_user_index_impl(
+            &mut canister_data,
+            &user_index_canister_id,
+            0
+        ));
+        assert!(matches!(
+            canister_data.all_created_posts.get(&0).unwrap().status,
+            PostStatus::BannedDueToUserReporting
+        ));
+    }
+
+    #[test]
+    fn test_receive_post_ban_from_user_index_impl_with_spoofed_callers() {
+        assert_only_allowed_callers_are_accepted(
+            &get_mock_known_principal_map(),
+            &[KnownPrincipalType::CanisterIdUserIndex],
+            |caller| {
+                let mut canister_data = get_canister_data_with_post();
+                receive_post_ban_from_user_index_impl(&mut canister_data, caller, 0)
+            },
+        );
+    }
+}
@@ -0,0 +1,226 @@
+use std::time::SystemTime;
+
+use candid::Principal;
+use shared_utils::{
+    canister_specific::individual_user_template::types::{
+        error::ReportPostError,
+        post::report::{PostReport, MAX_POST_REPORT_REASON_LENGTH},
+    },
+    common::{
+        types::{app_primitive_type::PostId, known_principal::KnownPrincipalType},
+        utils::system_time,
+    },
+};
+
+use crate::{
+    data_model::CanisterData,
+    util::{
+        call_journal::record_call_in_journal, call_queue::queue_call,
+        certified_post_details::recertify_post_details, guards::reject_updates_in_read_only_mode,
+    },
+    CANISTER_DATA,
+};
+
+/// Flags the post for moderators. The report is passed on to the moderation queue on the user
+/// index, where admins can ban the post. Once more than 5 users have reported it, new bets are
+/// held back until the reports are cleared
+///
+/// # Access Control
+/// Any logged in user other than the creator of the post
+#[ic_cdk::update(guard = "reject_updates_in_read_only_mode")]
+#[candid::candid_method(update)]
+fn report_post(post_id: PostId, reason: Option<String>) -> Result<(), ReportPostError> {
+    record_call_in_journal("report_post");
+
+    let api_caller = ic_cdk::caller();
+    let current_time = system_time::get_current_system_time_from_ic();
+
+    let report = CANISTER_DATA.with(|canister_data_ref_cell| {
+        report_post_impl(
+            &mut canister_data_ref_cell.borrow_mut(),
+            &api_caller,
+            post_id,
+            reason,
+            &current_time,
+        )
+    })?;
+
+    recertify_post_details(post_id);
+
+    let user_index_canister_id = CANISTER_DATA.with(|canister_data_ref_cell| {
+        canister_data_ref_cell
+            .borrow()
+            .known_principal_ids
+            .get(&KnownPrincipalType::CanisterIdUserIndex)
+            .cloned()
+    });
+
+    if let Some(user_index_canister_id) = user_index_canister_id {
+        queue_call(
+            user_index_canister_id,
+            "receive_post_report_from_individual_user_canister",
+            (report,),
+        );
+    }
+
+    Ok(())
+}
+
+fn report_post_impl(
+    canister_data: &mut CanisterData,
+    api_caller: &Principal,
+    post_id: PostId,
+    reason: Option<String>,
+    current_time: &SystemTime,
+) -> Result<PostReport, ReportPostError> {
+    if *api_caller == Principal::anonymous() {
+        return Err(ReportPostError::UserNotLoggedIn);
+    }
+
+    let reason = reason
+        .map(|reason| reason.trim().to_string())
+        .filter(|reason| !reason.is_empty());
+    if reason
+        .as_ref()
+        .is_some_and(|reason| reason.chars().count() > MAX_POST_REPORT_REASON_LENGTH)
+    {
+        return Err(ReportPostError::InvalidReason);
+    }
+
+    let mut post = canister_data
+        .all_created_posts
+        .get(&post_id)
+        .ok_or(ReportPostError::PostNotFound)?;
+
+    if canister_data.profile.principal_id == Some(*api_caller) {
+        return Err(ReportPostError::CannotReportOwnPost);
+    }
+
+    post.report(api_caller, current_time)?;
+    canister_data.all_created_posts.insert(post_id, post);
+
+    Ok(PostReport {
+        post_id,
+        reported_by: *api_caller,
+        reason,
+        reported_at: *current_time,
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use shared_utils::canister_specific::individual_user_template::types::{
+        hot_or_not::BettingStatus,
+        post::{Post, PostDetailsFromFrontend},
+    };
+    use test_utils::setup::test_constants::{
+        get_mock_user_alice_principal_id, get_mock_user_bob_principal_id,
+        v2::{user, users},
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_report_post_impl() {
+        let mut canister_data = CanisterData::default();
+        let current_time = SystemTime::now();
+        canister_data.profile.principal_id = Some(get_mock_user_alice_principal_id());
+        canister_data.all_created_posts.insert(
+            0,
+            Post::new(
+                0,
+                &PostDetailsFromFrontend {
+                    description: "Doggos and puppers".into(),
+                    hashtags: vec!["doggo".into(), "pupper".into()],
+                    video_uid: "abcd#1234".into(),
+                    creator_consent_for_inclusion_in_hot_or_not: true,
+                    collaborator_shares: None,
+                    title: None,
+                    category: None,
+                },
+                &current_time,
+            ),
+        );
+
+        assert_eq!(
+            report_post_impl(
+                &mut canister_data,
+                &Principal::anonymous(),
+                0,
+                None,
+                &current_time
+            ),
+            Err(ReportPostError::UserNotLoggedIn)
+        );
+        assert_eq!(
+            report_post_impl(
+                &mut canister_data,
+                &get_mock_user_bob_principal_id(),
+                1,
+                None,
+                &current_time
+            ),
+            Err(ReportPostError::PostNotFound)
+        );
+        assert_eq!(
+            report_post_impl(
+                &mut canister_data,
+                &get_mock_user_alice_principal_id(),
+                0,
+                None,
+                &current_time
+            ),
+            Err(ReportPostError::CannotReportOwnPost)
+        );
+        assert_eq!(
+            report_post_impl(
+                &mut canister_data,
+                &get_mock_user_bob_principal_id(),
+                0,
+                Some("a".repeat(MAX_POST_REPORT_REASON_LENGTH + 1)),
+                &current_time
+            ),
+            Err(ReportPostError::InvalidReason)
+        );
+        assert_eq!(
+            report_post_impl(
+                &mut canister_data,
+                &get_mock_user_bob_principal_id(),
+                0,
+                Some("  Spam  ".into()),
+                &current_time
+            ),
+            Ok(PostReport {
+                post_id: 0,
+                reported_by: get_mock_user_bob_principal_id(),
+                reason: Some("Spam".into()),
+                reported_at: current_time,
+            })
+        );
+        assert_eq!(
+            report_post_impl(
+                &mut canister_data,
+                &get_mock_user_bob_principal_id(),
+                0,
+                None,
+                &current_time
+            ),
+            Err(ReportPostError::AlreadyReported)
+        );
+
+        users("reporter", 5).iter().for_each(|reporter| {
+            assert_eq!(
+                report_post_impl(&mut canister_data, reporter, 0, None, &current_time).map(|_| ()),
+                Ok(())
+            );
+        });
+        assert_eq!(
+            canister_data
+                .all_created_posts
+                .get(&0)
+                .unwrap()
+                .get_hot_or_not_betting_status_for_this_post(&current_time, &user("bettor")),
+            BettingStatus::BettingPausedForReview
+        );
+    }
+}
@@ -1,27 +1,187 @@
-use shared_utils::canister_specific::individual_user_template::types::post::PostViewDetailsFromFrontend;
+use candid::Principal;
+use shared_utils::{
+    canister_specific::individual_user_template::types::post::{
+        view_buffer::POST_VIEW_BUFFER_FLUSH_INTERVAL, PostViewDetailsFromFrontend,
+    },
+    common::{types::app_primitive_type::PostId, utils::system_time::IcTimeProvider},
+};
 
-use crate::CANISTER_DATA;
+use crate::{
+    data_model::CanisterData,
+    util::{call_journal::record_call_in_journal, guards::reject_updates_in_read_only_mode},
+    CANISTER_DATA,
+};
 
 use super::update_scores_and_share_with_post_cache_if_difference_beyond_threshold::update_scores_and_share_with_post_cache_if_difference_beyond_threshold;
 
-#[ic_cdk::update]
+/// Views are buffered and applied to the post on the next flush. Until then, they are added
+/// on top of the post's view count when the post details are read.
+#[ic_cdk::update(guard = "reject_updates_in_read_only_mode")]
 #[candid::candid_method(update)]
 fn update_post_add_view_details(id: u64, details: PostViewDetailsFromFrontend) {
-    CANISTER_DATA.with(|canister_data_ref_cell| {
-        let mut post_to_update = canister_data_ref_cell
-            .borrow_mut()
-            .all_created_posts
-            .get(&id)
-            .unwrap()
-            .clone();
-
-        post_to_update.add_view_details(&details);
-
-        canister_data_ref_cell
-            .borrow_mut()
-            .all_created_posts
-            .insert(id, post_to_update);
+    record_call_in_journal("update_post_add_view_details");
+
+    let api_caller = ic_cdk::caller();
+
+    let is_post_view_buffer_full = CANISTER_DATA.with(|canister_data_ref_cell| {
+        buffer_post_view_details_impl(
+            &mut canister_data_ref_cell.borrow_mut(),
+            id,
+            &api_caller,
+            details,
+        )
     });
 
-    update_scores_and_share_with_post_cache_if_difference_beyond_threshold(&id);
+    if is_post_view_buffer_full {
+        flush_post_view_buffer();
+    }
+}
+
+pub fn enqueue_timer_for_flushing_post_view_buffer() {
+    ic_cdk_timers::set_timer_interval(POST_VIEW_BUFFER_FLUSH_INTERVAL, flush_post_view_buffer);
+}
+
+pub fn flush_post_view_buffer() {
+    let updated_post_ids = CANISTER_DATA.with(|canister_data_ref_cell| {
+        apply_buffered_post_views_impl(&mut canister_data_ref_cell.borrow_mut())
+    });
+
+    updated_post_ids.iter().for_each(|post_id| {
+        update_scores_and_share_with_post_cache_if_difference_beyond_threshold(
+            post_id,
+            &IcTimeProvider,
+        );
+    });
+}
+
+fn buffer_post_view_details_impl(
+    canister_data: &mut CanisterData,
+    post_id: PostId,
+    viewer: &Principal,
+    details: PostViewDetailsFromFrontend,
+) -> bool {
+    assert!(canister_data.all_created_posts.contains_key(&post_id));
+    assert!(details.is_valid());
+
+    canister_data
+        .post_view_buffer
+        .push(post_id, viewer, details)
+}
+
+fn apply_buffered_post_views_impl(canister_data: &mut CanisterData) -> Vec<PostId> {
+    canister_data
+        .post_view_buffer
+        .take()
+        .into_iter()
+        .filter_map(|(post_id, buffered_post_views)| {
+            canister_data.all_created_posts.update(&post_id, |post| {
+                buffered_post_views
+                    .view_details
+                    .iter()
+                    .for_each(|details| post.add_view_details(details));
+            })?;
+
+            Some(post_id)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::SystemTime;
+
+    use shared_utils::canister_specific::individual_user_template::types::post::{
+        Post, PostDetailsFromFrontend,
+    };
+    use test_utils::setup::test_constants::v2::users;
+
+    use super::*;
+
+    #[test]
+    fn test_buffered_post_views_are_applied_on_flush() {
+        let mut canister_data = CanisterData::default();
+        let current_time = SystemTime::now();
+        (0..2).for_each(|post_id| {
+            canister_data.all_created_posts.insert(
+                post_id,
+                Post::new(
+                    post_id,
+                    &PostDetailsFromFrontend {
+                        description: "Doggos and puppers".into(),
+                        hashtags: vec!["doggo".into(), "pupper".into()],
+                        video_uid: "abcd#1234".into(),
+                        creator_consent_for_inclusion_in_hot_or_not: true,
+                        collaborator_shares: None,
+                        title: None,
+                        category: None,
+                    },
+                    &current_time,
+                ),
+            );
+        });
+        let viewers = users("viewer", 3);
+
+        viewers.iter().for_each(|viewer| {
+            assert!(!buffer_post_view_details_impl(
+                &mut canister_data,
+                0,
+                viewer,
+                PostViewDetailsFromFrontend::WatchedPartially {
+                    percentage_watched: 50,
+                },
+            ));
+        });
+        assert!(!buffer_post_view_details_impl(
+            &mut canister_data,
+            1,
+            &viewers[0],
+            PostViewDetailsFromFrontend::WatchedMultipleTimes {
+                watch_count: 1,
+                percentage_watched: 10,
+            },
+        ));
+
+        // * nothing touches the posts until the buffer is flushed
+        assert_eq!(
+            canister_data
+                .all_created_posts
+                .get(&0)
+                .unwrap()
+                .view_stats
+                .total_view_count,
+            0
+        );
+        assert_eq!(canister_data.post_view_buffer.get_pending_view_count(0), 3);
+        assert_eq!(canister_data.post_view_buffer.get_pending_view_count(1), 2);
+
+        assert_eq!(
+            apply_buffered_post_views_impl(&mut canister_data),
+            vec![0, 1]
+        );
+
+        let view_stats = &canister_data.all_created_posts.get(&0).unwrap().view_stats;
+        assert_eq!(view_stats.total_view_count, 3);
+        assert_eq!(view_stats.threshold_view_count, 3);
+        assert_eq!(view_stats.average_watch_percentage, 50);
+        let view_stats = &canister_data.all_created_posts.get(&1).unwrap().view_stats;
+        assert_eq!(view_stats.total_view_count, 2);
+        assert_eq!(view_stats.threshold_view_count, 1);
+        assert!(canister_data.post_view_buffer.is_empty());
+        assert_eq!(canister_data.post_view_buffer.get_pending_view_count(0), 0);
+
+        assert!(apply_buffered_post_views_impl(&mut canister_data).is_empty());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_views_of_unknown_posts_are_rejected() {
+        buffer_post_view_details_impl(
+            &mut CanisterData::default(),
+            0,
+            &users("viewer", 1)[0],
+            PostViewDetailsFromFrontend::WatchedPartially {
+                percentage_watched: 50,
+            },
+        );
+    }
 }
@@ -3,11 +3,20 @@ use shared_utils::{
     common::types::known_principal::KnownPrincipalType,
 };
 
-use crate::CANISTER_DATA;
+use crate::{
+    util::{
+        call_journal::record_call_in_journal, certified_post_details::recertify_post_details,
+        following_feed_fan_out::fan_out_post_to_followers,
+        guards::reject_updates_in_read_only_mode,
+    },
+    CANISTER_DATA,
+};
 
-#[ic_cdk::update]
+#[ic_cdk::update(guard = "reject_updates_in_read_only_mode")]
 #[candid::candid_method(update)]
 fn update_post_as_ready_to_view(id: u64) {
+    record_call_in_journal("update_post_as_ready_to_view");
+
     let api_caller = ic_cdk::caller();
 
     let global_super_admin_principal_id = CANISTER_DATA.with(|canister_data_ref_cell| {
@@ -28,8 +37,7 @@ fn update_post_as_ready_to_view(id: u64) {
             .borrow_mut()
             .all_created_posts
             .get(&id)
-            .unwrap()
-            .clone();
+            .unwrap();
 
         post_to_update.update_status(PostStatus::ReadyToView);
 
@@ -38,4 +46,7 @@ fn update_post_as_ready_to_view(id: u64) {
             .all_created_posts
             .insert(id, post_to_update);
     });
+
+    recertify_post_details(id);
+    fan_out_post_to_followers(id);
 }
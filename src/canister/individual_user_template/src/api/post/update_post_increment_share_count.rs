@@ -1,17 +1,23 @@
-use crate::CANISTER_DATA;
+use shared_utils::common::utils::system_time::IcTimeProvider;
+
+use crate::{
+    util::{call_journal::record_call_in_journal, guards::reject_updates_in_read_only_mode},
+    CANISTER_DATA,
+};
 
 use super::update_scores_and_share_with_post_cache_if_difference_beyond_threshold::update_scores_and_share_with_post_cache_if_difference_beyond_threshold;
 
-#[ic_cdk::update]
+#[ic_cdk::update(guard = "reject_updates_in_read_only_mode")]
 #[candid::candid_method(update)]
 fn update_post_increment_share_count(id: u64) -> u64 {
+    record_call_in_journal("update_post_increment_share_count");
+
     let response = CANISTER_DATA.with(|canister_data_ref_cell| {
         let mut post_to_update = canister_data_ref_cell
             .borrow_mut()
             .all_created_posts
             .get(&id)
-            .unwrap()
-            .clone();
+            .unwrap();
 
         let updated_share_count = post_to_update.increment_share_count();
 
@@ -23,7 +29,7 @@ fn update_post_increment_share_count(id: u64) -> u64 {
         updated_share_count
     });
 
-    update_scores_and_share_with_post_cache_if_difference_beyond_threshold(&id);
+    update_scores_and_share_with_post_cache_if_difference_beyond_threshold(&id, &IcTimeProvider);
 
     response
 }
@@ -0,0 +1,197 @@
+use candid::Principal;
+use shared_utils::{
+    canister_specific::individual_user_template::types::{
+        error::UpdatePostMinimumBetAmountError, hot_or_not::MAXIMUM_MINIMUM_BET_AMOUNT_FOR_A_POST,
+    },
+    common::types::app_primitive_type::PostId,
+};
+
+use crate::{
+    data_model::CanisterData,
+    util::{
+        call_journal::record_call_in_journal, certified_post_details::recertify_post_details,
+        guards::reject_updates_in_read_only_mode,
+    },
+    CANISTER_DATA,
+};
+
+/// Bets below the minimum are rejected from now on. Passing zero removes the minimum
+///
+/// # Access Control
+/// Only the user whose profile details are stored in this canister can set the minimum
+#[ic_cdk::update(guard = "reject_updates_in_read_only_mode")]
+#[candid::candid_method(update)]
+fn update_post_minimum_bet_amount(
+    post_id: PostId,
+    minimum_bet_amount: u64,
+) -> Result<(), UpdatePostMinimumBetAmountError> {
+    record_call_in_journal("update_post_minimum_bet_amount");
+
+    let api_caller = ic_cdk::caller();
+
+    let result = CANISTER_DATA.with(|canister_data_ref_cell| {
+        update_post_minimum_bet_amount_impl(
+            &mut canister_data_ref_cell.borrow_mut(),
+            &api_caller,
+            post_id,
+            minimum_bet_amount,
+        )
+    });
+
+    if result.is_ok() {
+        recertify_post_details(post_id);
+    }
+
+    result
+}
+
+fn update_post_minimum_bet_amount_impl(
+    canister_data: &mut CanisterData,
+    api_caller: &Principal,
+    post_id: PostId,
+    minimum_bet_amount: u64,
+) -> Result<(), UpdatePostMinimumBetAmountError> {
+    if canister_data.profile.principal_id != Some(*api_caller) {
+        return Err(UpdatePostMinimumBetAmountError::Unauthorized);
+    }
+
+    if minimum_bet_amount > MAXIMUM_MINIMUM_BET_AMOUNT_FOR_A_POST {
+        return Err(UpdatePostMinimumBetAmountError::MinimumBetAmountAbovePlatformLimit);
+    }
+
+    let mut post = canister_data
+        .all_created_posts
+        .get(&post_id)
+        .ok_or(UpdatePostMinimumBetAmountError::PostNotFound)?;
+
+    if post.hot_or_not_details.is_none() {
+        return Err(UpdatePostMinimumBetAmountError::PostNotPartOfHotOrNot);
+    }
+
+    post.update_minimum_bet_amount(minimum_bet_amount);
+    canister_data.all_created_posts.insert(post_id, post);
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::SystemTime;
+
+    use shared_utils::canister_specific::individual_user_template::types::{
+        error::BetOnCurrentlyViewingPostError,
+        hot_or_not::{BetDirection, BettingStatus},
+        post::{Post, PostDetailsFromFrontend},
+    };
+    use test_utils::setup::test_constants::{
+        get_mock_user_alice_principal_id, get_mock_user_bob_canister_id,
+        get_mock_user_bob_principal_id,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_update_post_minimum_bet_amount_impl() {
+        let mut canister_data = CanisterData::default();
+        let created_at = SystemTime::now();
+        canister_data.profile.principal_id = Some(get_mock_user_alice_principal_id());
+
+        [true, false].iter().enumerate().for_each(
+            |(post_id, creator_consent_for_inclusion_in_hot_or_not)| {
+                canister_data.all_created_posts.insert(
+                    post_id as u64,
+                    Post::new(
+                        post_id as u64,
+                        &PostDetailsFromFrontend {
+                            description: "Doggos and puppers".into(),
+                            hashtags: vec!["doggo".into(), "pupper".into()],
+                            video_uid: "abcd#1234".into(),
+                            creator_consent_for_inclusion_in_hot_or_not:
+                                *creator_consent_for_inclusion_in_hot_or_not,
+                            collaborator_shares: None,
+                            title: None,
+                            category: None,
+                        },
+                        &created_at,
+                    ),
+                );
+            },
+        );
+
+        assert_eq!(
+            update_post_minimum_bet_amount_impl(
+                &mut canister_data,
+                &get_mock_user_bob_principal_id(),
+                0,
+                50
+            ),
+            Err(UpdatePostMinimumBetAmountError::Unauthorized)
+        );
+        assert_eq!(
+            update_post_minimum_bet_amount_impl(
+                &mut canister_data,
+                &get_mock_user_alice_principal_id(),
+                0,
+                MAXIMUM_MINIMUM_BET_AMOUNT_FOR_A_POST + 1
+            ),
+            Err(UpdatePostMinimumBetAmountError::MinimumBetAmountAbovePlatformLimit)
+        );
+        assert_eq!(
+            update_post_minimum_bet_amount_impl(
+                &mut canister_data,
+                &get_mock_user_alice_principal_id(),
+                2,
+                50
+            ),
+            Err(UpdatePostMinimumBetAmountError::PostNotFound)
+        );
+        assert_eq!(
+            update_post_minimum_bet_amount_impl(
+                &mut canister_data,
+                &get_mock_user_alice_principal_id(),
+                1,
+                50
+            ),
+            Err(UpdatePostMinimumBetAmountError::PostNotPartOfHotOrNot)
+        );
+        assert_eq!(
+            update_post_minimum_bet_amount_impl(
+                &mut canister_data,
+                &get_mock_user_alice_principal_id(),
+                0,
+                50
+            ),
+            Ok(())
+        );
+
+        let mut post = canister_data.all_created_posts.get(&0).unwrap();
+        match post.get_hot_or_not_betting_status_for_this_post(
+            &created_at,
+            &get_mock_user_bob_principal_id(),
+        ) {
+            BettingStatus::BettingOpen {
+                minimum_bet_amount, ..
+            } => assert_eq!(minimum_bet_amount, 50),
+            _ => panic!("Betting should be open"),
+        }
+        assert_eq!(
+            post.place_hot_or_not_bet(
+                &get_mock_user_bob_principal_id(),
+                &get_mock_user_bob_canister_id(),
+                10,
+                &BetDirection::Hot,
+                &created_at,
+            ),
+            Err(BetOnCurrentlyViewingPostError::BetAmountBelowPostMinimum)
+        );
+        assert!(post
+            .place_hot_or_not_bet(
+                &get_mock_user_bob_principal_id(),
+                &get_mock_user_bob_canister_id(),
+                50,
+                &BetDirection::Hot,
+                &created_at,
+            )
+            .is_ok());
+    }
+}
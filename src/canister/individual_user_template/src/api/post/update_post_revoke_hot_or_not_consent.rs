@@ -0,0 +1,155 @@
+use candid::Principal;
+use shared_utils::{
+    canister_specific::individual_user_template::types::error::RevokeHotOrNotConsentError,
+    common::types::app_primitive_type::PostId,
+};
+
+use crate::{
+    data_model::CanisterData,
+    util::{
+        call_journal::record_call_in_journal, certified_post_details::recertify_post_details,
+        guards::reject_updates_in_read_only_mode,
+    },
+    CANISTER_DATA,
+};
+
+/// Closes betting on the post for good. Slots that already have bets in them still get
+/// tabulated and paid out when they end
+///
+/// # Access Control
+/// Only the user whose profile details are stored in this canister can revoke consent
+#[ic_cdk::update(guard = "reject_updates_in_read_only_mode")]
+#[candid::candid_method(update)]
+fn update_post_revoke_hot_or_not_consent(
+    post_id: PostId,
+) -> Result<(), RevokeHotOrNotConsentError> {
+    record_call_in_journal("update_post_revoke_hot_or_not_consent");
+
+    let api_caller = ic_cdk::caller();
+
+    let result = CANISTER_DATA.with(|canister_data_ref_cell| {
+        update_post_revoke_hot_or_not_consent_impl(
+            &mut canister_data_ref_cell.borrow_mut(),
+            &api_caller,
+            post_id,
+        )
+    });
+
+    if result.is_ok() {
+        recertify_post_details(post_id);
+    }
+
+    result
+}
+
+fn update_post_revoke_hot_or_not_consent_impl(
+    canister_data: &mut CanisterData,
+    api_caller: &Principal,
+    post_id: PostId,
+) -> Result<(), RevokeHotOrNotConsentError> {
+    if canister_data.profile.principal_id != Some(*api_caller) {
+        return Err(RevokeHotOrNotConsentError::Unauthorized);
+    }
+
+    let mut post = canister_data
+        .all_created_posts
+        .get(&post_id)
+        .ok_or(RevokeHotOrNotConsentError::PostNotFound)?;
+
+    if post.hot_or_not_details.is_none() {
+        return Err(RevokeHotOrNotConsentError::PostNotPartOfHotOrNot);
+    }
+
+    post.revoke_hot_or_not_consent();
+    canister_data.all_created_posts.insert(post_id, post);
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::SystemTime;
+
+    use shared_utils::canister_specific::individual_user_template::types::{
+        hot_or_not::BettingStatus,
+        post::{Post, PostDetailsFromFrontend},
+    };
+    use test_utils::setup::test_constants::{
+        get_mock_user_alice_principal_id, get_mock_user_bob_principal_id,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_update_post_revoke_hot_or_not_consent_impl() {
+        let mut canister_data = CanisterData::default();
+        let created_at = SystemTime::now();
+        canister_data.profile.principal_id = Some(get_mock_user_alice_principal_id());
+
+        [true, false].iter().enumerate().for_each(
+            |(post_id, creator_consent_for_inclusion_in_hot_or_not)| {
+                canister_data.all_created_posts.insert(
+                    post_id as u64,
+                    Post::new(
+                        post_id as u64,
+                        &PostDetailsFromFrontend {
+                            description: "Doggos and puppers".into(),
+                            hashtags: vec!["doggo".into(), "pupper".into()],
+                            video_uid: "abcd#1234".into(),
+                            creator_consent_for_inclusion_in_hot_or_not:
+                                *creator_consent_for_inclusion_in_hot_or_not,
+                            collaborator_shares: None,
+                            title: None,
+                            category: None,
+                        },
+                        &created_at,
+                    ),
+                );
+            },
+        );
+
+        assert_eq!(
+            update_post_revoke_hot_or_not_consent_impl(
+                &mut canister_data,
+                &get_mock_user_bob_principal_id(),
+                0
+            ),
+            Err(RevokeHotOrNotConsentError::Unauthorized)
+        );
+        assert_eq!(
+            update_post_revoke_hot_or_not_consent_impl(
+                &mut canister_data,
+                &get_mock_user_alice_principal_id(),
+                2
+            ),
+            Err(RevokeHotOrNotConsentError::PostNotFound)
+        );
+        assert_eq!(
+            update_post_revoke_hot_or_not_consent_impl(
+                &mut canister_data,
+                &get_mock_user_alice_principal_id(),
+                1
+            ),
+            Err(RevokeHotOrNotConsentError::PostNotPartOfHotOrNot)
+        );
+        assert_eq!(
+            update_post_revoke_hot_or_not_consent_impl(
+                &mut canister_data,
+                &get_mock_user_alice_principal_id(),
+                0
+            ),
+            Ok(())
+        );
+        assert_eq!(
+            canister_data
+                .all_created_posts
+                .get(&0)
+                .unwrap()
+                .get_hot_or_not_betting_status_for_this_post(
+                    &created_at,
+                    &get_mock_user_bob_principal_id()
+                ),
+            BettingStatus::BettingClosedByCreator
+        );
+    }
+}
@@ -1,31 +1,28 @@
-use crate::CANISTER_DATA;
+use shared_utils::common::utils::system_time::IcTimeProvider;
+
+use crate::{
+    util::{call_journal::record_call_in_journal, guards::reject_updates_in_read_only_mode},
+    CANISTER_DATA,
+};
 
 use super::update_scores_and_share_with_post_cache_if_difference_beyond_threshold::update_scores_and_share_with_post_cache_if_difference_beyond_threshold;
 
-#[ic_cdk::update]
+#[ic_cdk::update(guard = "reject_updates_in_read_only_mode")]
 #[candid::candid_method(update)]
 fn update_post_toggle_like_status_by_caller(id: u64) -> bool {
+    record_call_in_journal("update_post_toggle_like_status_by_caller");
+
     let caller_id = ic_cdk::caller();
 
     let response = CANISTER_DATA.with(|canister_data_ref_cell| {
-        let mut post_to_update = canister_data_ref_cell
-            .borrow_mut()
-            .all_created_posts
-            .get(&id)
-            .unwrap()
-            .clone();
-
-        let updated_like_status = post_to_update.toggle_like_status(&caller_id);
-
         canister_data_ref_cell
             .borrow_mut()
             .all_created_posts
-            .insert(id, post_to_update);
-
-        updated_like_status
+            .update(&id, |post| post.toggle_like_status(&caller_id))
+            .unwrap()
     });
 
-    update_scores_and_share_with_post_cache_if_difference_beyond_threshold(&id);
+    update_scores_and_share_with_post_cache_if_difference_beyond_threshold(&id, &IcTimeProvider);
 
     response
 }
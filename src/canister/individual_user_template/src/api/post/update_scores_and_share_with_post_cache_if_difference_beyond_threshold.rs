@@ -1,14 +1,16 @@
 use std::time::SystemTime;
 
 use candid::Principal;
-use ic_cdk::api::call;
 use shared_utils::{
+    canister_specific::individual_user_template::types::post::PostStatus,
     common::{
         types::{
             known_principal::KnownPrincipalType,
-            top_posts::post_score_index_item::PostScoreIndexItem,
+            top_posts::{
+                post_score_index_item::PostScoreIndexItem, slot_heat_indicator::SlotHeatIndicator,
+            },
         },
-        utils::system_time,
+        utils::system_time::TimeProvider,
     },
     constant::{
         HOME_FEED_DIFFERENCE_TO_INITIATE_SYNCHRONISATION,
@@ -16,10 +18,17 @@ use shared_utils::{
     },
 };
 
-use crate::{data_model::CanisterData, CANISTER_DATA};
+use crate::{
+    data_model::CanisterData,
+    util::{call_queue::queue_call, certified_post_details::recertify_post_details},
+    CANISTER_DATA,
+};
 
-pub fn update_scores_and_share_with_post_cache_if_difference_beyond_threshold(post_id: &u64) {
-    let current_time = system_time::get_current_system_time_from_ic();
+pub fn update_scores_and_share_with_post_cache_if_difference_beyond_threshold(
+    post_id: &u64,
+    time_provider: &impl TimeProvider,
+) {
+    let current_time = time_provider.get_current_time();
     let canisters_own_principal_id = ic_cdk::id();
 
     let (home_feed_index_score_item, hot_or_not_index_score_item): (
@@ -34,6 +43,8 @@ pub fn update_scores_and_share_with_post_cache_if_difference_beyond_threshold(po
         )
     });
 
+    recertify_post_details(*post_id);
+
     if home_feed_index_score_item.is_none() && hot_or_not_index_score_item.is_none() {
         return;
     }
@@ -42,25 +53,45 @@ pub fn update_scores_and_share_with_post_cache_if_difference_beyond_threshold(po
         canister_data_ref_cell
             .borrow()
             .known_principal_ids
-            .get(&KnownPrincipalType::CanisterIdPostCache)
+            // * each canister sticks to one post cache shard
+            .get_for_shard_key(&KnownPrincipalType::CanisterIdPostCache, &ic_cdk::id())
             .cloned()
             .unwrap()
     });
 
-    if home_feed_index_score_item.is_some() {
-        let _ = call::notify(
+    if let Some(home_feed_index_score_item) = home_feed_index_score_item {
+        queue_call(
             post_cache_canister_principal_id,
             "receive_top_home_feed_posts_from_publishing_canister",
-            (vec![home_feed_index_score_item.unwrap()],),
+            (vec![home_feed_index_score_item],),
         );
     }
 
-    if hot_or_not_index_score_item.is_some() {
-        let _ = call::notify(
+    if let Some(hot_or_not_index_score_item) = hot_or_not_index_score_item {
+        queue_call(
             post_cache_canister_principal_id,
             "receive_top_hot_or_not_feed_posts_from_publishing_canister",
-            (vec![hot_or_not_index_score_item.unwrap()],),
+            (vec![hot_or_not_index_score_item],),
         );
+
+        let slot_heat_indicator: Option<SlotHeatIndicator> =
+            CANISTER_DATA.with(|canister_data_ref_cell| {
+                canister_data_ref_cell
+                    .borrow()
+                    .all_created_posts
+                    .get(post_id)
+                    .and_then(|post| {
+                        post.get_slot_heat_indicator(&canisters_own_principal_id, &current_time)
+                    })
+            });
+
+        if let Some(slot_heat_indicator) = slot_heat_indicator {
+            queue_call(
+                post_cache_canister_principal_id,
+                "receive_slot_heat_indicators_from_publishing_canister",
+                (vec![slot_heat_indicator],),
+            );
+        }
     }
 }
 
@@ -78,7 +109,15 @@ fn update_home_feed_and_hot_or_not_feed_score_and_get_post_index_item_to_send(
     let mut home_feed_index_score_item: Option<PostScoreIndexItem> = None;
     let mut hot_or_not_index_score_item: Option<PostScoreIndexItem> = None;
 
-    let mut post_to_synchronise = all_posts.get(&post_id).unwrap().clone();
+    let mut post_to_synchronise = all_posts.get(&post_id).unwrap();
+
+    // * Banned posts keep their last shared scores here, the post cache has dropped them
+    if matches!(
+        post_to_synchronise.status,
+        PostStatus::BannedDueToUserReporting
+    ) {
+        return (None, None);
+    }
 
     post_to_synchronise.recalculate_home_feed_score(&current_time);
 
@@ -97,7 +136,9 @@ fn update_home_feed_and_hot_or_not_feed_score_and_get_post_index_item_to_send(
         post_to_synchronise.home_feed_score.last_synchronized_at = current_time;
     }
 
-    if post_to_synchronise.hot_or_not_details.is_some() {
+    if post_to_synchronise.hot_or_not_details.is_some()
+        && post_to_synchronise.creator_consent_for_inclusion_in_hot_or_not
+    {
         post_to_synchronise.recalculate_hot_or_not_feed_score(&current_time);
         let last_updated_hot_or_not_feed_score = post_to_synchronise
             .hot_or_not_details
@@ -175,6 +216,9 @@ mod test {
                     hashtags: vec!["#fun".to_string(), "#post".to_string()],
                     video_uid: "abcd1234".to_string(),
                     creator_consent_for_inclusion_in_hot_or_not: true,
+                    collaborator_shares: None,
+                    title: None,
+                    category: None,
                 },
                 &post_creation_time,
             ),
@@ -198,5 +242,29 @@ mod test {
             get_mock_user_alice_canister_id(),
         );
         assert_eq!(response, (None, None));
+
+        // * a fresh post is shared straight away, unless it has been banned
+        let mut banned_post = Post::new(
+            1,
+            &PostDetailsFromFrontend {
+                description: "This is a new post".to_string(),
+                hashtags: vec!["#fun".to_string(), "#post".to_string()],
+                video_uid: "abcd1234".to_string(),
+                creator_consent_for_inclusion_in_hot_or_not: true,
+                collaborator_shares: None,
+                title: None,
+                category: None,
+            },
+            &post_creation_time,
+        );
+        banned_post.update_status(PostStatus::BannedDueToUserReporting);
+        canister_data.all_created_posts.insert(1, banned_post);
+        let response = update_home_feed_and_hot_or_not_feed_score_and_get_post_index_item_to_send(
+            &mut canister_data,
+            1,
+            post_creation_time,
+            get_mock_user_alice_canister_id(),
+        );
+        assert_eq!(response, (None, None));
     }
 }
@@ -0,0 +1,9 @@
+use crate::CANISTER_DATA;
+
+/// Whether this profile's bets show up pseudonymized in post creators' exports
+#[ic_cdk::query]
+#[candid::candid_method(query)]
+fn get_profile_betting_activity_privacy() -> bool {
+    CANISTER_DATA
+        .with(|canister_data_ref_cell| canister_data_ref_cell.borrow().is_betting_activity_hidden)
+}
@@ -1,3 +1,6 @@
+pub mod get_profile_betting_activity_privacy;
 pub mod get_profile_details;
+pub mod update_profile_betting_activity_privacy;
 pub mod update_profile_display_details;
 pub mod update_profile_set_unique_username_once;
+pub mod update_profile_shadow_ban_status;
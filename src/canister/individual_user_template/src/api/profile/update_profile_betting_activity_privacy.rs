@@ -0,0 +1,81 @@
+use candid::Principal;
+use shared_utils::canister_specific::individual_user_template::types::error::UpdateBettingActivityPrivacyError;
+
+use crate::{
+    data_model::CanisterData,
+    util::{call_journal::record_call_in_journal, guards::reject_updates_in_read_only_mode},
+    CANISTER_DATA,
+};
+
+/// Hidden betting activity still settles as usual, but the user's principal is replaced by a
+/// pseudonym in post details exported by post creators' canisters. Applies to bets placed
+/// from now on.
+///
+/// # Access Control
+/// Only the user whose profile details are stored in this canister can change their privacy
+#[ic_cdk::update(guard = "reject_updates_in_read_only_mode")]
+#[candid::candid_method(update)]
+fn update_profile_betting_activity_privacy(
+    is_betting_activity_hidden: bool,
+) -> Result<(), UpdateBettingActivityPrivacyError> {
+    record_call_in_journal("update_profile_betting_activity_privacy");
+
+    let api_caller = ic_cdk::caller();
+
+    CANISTER_DATA.with(|canister_data_ref_cell| {
+        update_profile_betting_activity_privacy_impl(
+            &mut canister_data_ref_cell.borrow_mut(),
+            &api_caller,
+            is_betting_activity_hidden,
+        )
+    })
+}
+
+fn update_profile_betting_activity_privacy_impl(
+    canister_data: &mut CanisterData,
+    api_caller: &Principal,
+    is_betting_activity_hidden: bool,
+) -> Result<(), UpdateBettingActivityPrivacyError> {
+    if canister_data.profile.principal_id != Some(*api_caller) {
+        return Err(UpdateBettingActivityPrivacyError::Unauthorized);
+    }
+
+    canister_data.is_betting_activity_hidden = is_betting_activity_hidden;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use test_utils::setup::test_constants::{
+        get_mock_user_alice_principal_id, get_mock_user_bob_principal_id,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_update_profile_betting_activity_privacy_impl() {
+        let mut canister_data = CanisterData::default();
+        canister_data.profile.principal_id = Some(get_mock_user_alice_principal_id());
+
+        assert_eq!(
+            update_profile_betting_activity_privacy_impl(
+                &mut canister_data,
+                &get_mock_user_bob_principal_id(),
+                true,
+            ),
+            Err(UpdateBettingActivityPrivacyError::Unauthorized)
+        );
+        assert!(!canister_data.is_betting_activity_hidden);
+
+        assert_eq!(
+            update_profile_betting_activity_privacy_impl(
+                &mut canister_data,
+                &get_mock_user_alice_principal_id(),
+                true,
+            ),
+            Ok(())
+        );
+        assert!(canister_data.is_betting_activity_hidden);
+    }
+}
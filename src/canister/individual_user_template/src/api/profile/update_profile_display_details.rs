@@ -1,21 +1,40 @@
-use crate::CANISTER_DATA;
+use crate::{
+    util::{
+        call_journal::record_call_in_journal, certified_post_details::recertify_all_post_details,
+        guards::reject_updates_in_read_only_mode,
+    },
+    CANISTER_DATA,
+};
 use candid::CandidType;
-use shared_utils::canister_specific::individual_user_template::types::profile::{
-    UserProfileDetailsForFrontend, UserProfileUpdateDetailsFromFrontend,
+use shared_utils::{
+    canister_specific::individual_user_template::types::profile::{
+        UserProfileDetailsForFrontend, UserProfileUpdateDetailsFromFrontend,
+    },
+    common::utils::profanity::contains_profanity,
 };
 
 #[derive(CandidType)]
 pub enum UpdateProfileDetailsError {
     NotAuthorized,
+    DisplayNameContainsProfanity,
+}
+
+shared_utils::error_codes! {
+    UpdateProfileDetailsError {
+        NotAuthorized => (4801, Unauthorized, false),
+        DisplayNameContainsProfanity => (4802, InvalidInput, false),
+    }
 }
 
 /// # Access Control
 /// Only the user whose profile details are stored in this canister can update their details.
-#[ic_cdk::update]
+#[ic_cdk::update(guard = "reject_updates_in_read_only_mode")]
 #[candid::candid_method(update)]
 fn update_profile_display_details(
     user_profile_details: UserProfileUpdateDetailsFromFrontend,
 ) -> Result<UserProfileDetailsForFrontend, UpdateProfileDetailsError> {
+    record_call_in_journal("update_profile_display_details");
+
     // * access control
     let current_caller = ic_cdk::caller();
     let my_principal_id = CANISTER_DATA
@@ -26,6 +45,14 @@ fn update_profile_display_details(
         return Err(UpdateProfileDetailsError::NotAuthorized);
     }
 
+    if user_profile_details
+        .display_name
+        .as_deref()
+        .is_some_and(contains_profanity)
+    {
+        return Err(UpdateProfileDetailsError::DisplayNameContainsProfanity);
+    }
+
     CANISTER_DATA.with(|canister_data_ref_cell| {
         let profile = &mut canister_data_ref_cell.borrow_mut().profile;
 
@@ -33,6 +60,8 @@ fn update_profile_display_details(
         profile.profile_picture_url = user_profile_details.profile_picture_url;
     });
 
+    recertify_all_post_details();
+
     Ok(CANISTER_DATA.with(|canister_data_ref_cell| {
         let canister_data = canister_data_ref_cell.borrow();
         let profile = &canister_data.profile;
@@ -50,6 +79,7 @@ fn update_profile_display_details(
                 .len() as u64,
             following_count: canister_data_ref_cell.borrow().principals_i_follow.len() as u64,
             lifetime_earnings: *lifetime_earnings,
+            badges: canister_data.achievements.get_badges(),
         }
     }))
 }
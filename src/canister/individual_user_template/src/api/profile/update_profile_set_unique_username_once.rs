@@ -1,4 +1,10 @@
-use crate::CANISTER_DATA;
+use crate::{
+    util::{
+        call_journal::record_call_in_journal, certified_post_details::recertify_all_post_details,
+        guards::reject_updates_in_read_only_mode,
+    },
+    CANISTER_DATA,
+};
 use ic_cdk::api::call;
 use shared_utils::{
     common::types::known_principal::KnownPrincipalType,
@@ -10,11 +16,13 @@ use shared_utils::{
 
 /// # Access Control
 /// Only the user whose profile details are stored in this canister can update their details.
-#[ic_cdk::update]
+#[ic_cdk::update(guard = "reject_updates_in_read_only_mode")]
 #[candid::candid_method(update)]
 async fn update_profile_set_unique_username_once(
     new_unique_username: String,
 ) -> Result<(), UpdateProfileSetUniqueUsernameError> {
+    record_call_in_journal("update_profile_set_unique_username_once");
+
     // * access control
     let current_caller = ic_cdk::caller();
     let my_principal_id = CANISTER_DATA
@@ -50,6 +58,7 @@ async fn update_profile_set_unique_username_once(
                 profile.unique_user_name = Some(new_unique_username);
                 canister_data_ref_cell.borrow_mut().profile = profile;
             });
+            recertify_all_post_details();
             Ok(())
         }
         Err(SetUniqueUsernameError::UsernameAlreadyTaken) => {
@@ -61,5 +70,11 @@ async fn update_profile_set_unique_username_once(
         Err(SetUniqueUsernameError::UserCanisterEntryDoesNotExist) => {
             Err(UpdateProfileSetUniqueUsernameError::UserCanisterEntryDoesNotExist)
         }
+        Err(SetUniqueUsernameError::UsernameReserved) => {
+            Err(UpdateProfileSetUniqueUsernameError::UsernameReserved)
+        }
+        Err(SetUniqueUsernameError::UsernameContainsProfanity) => {
+            Err(UpdateProfileSetUniqueUsernameError::UsernameContainsProfanity)
+        }
     }
 }
@@ -0,0 +1,104 @@
+use candid::Principal;
+use shared_utils::common::{
+    types::known_principal::KnownPrincipalType, utils::known_caller::authorize_known_caller,
+};
+
+use crate::{
+    data_model::CanisterData,
+    util::{call_journal::record_call_in_journal, guards::reject_updates_in_read_only_mode},
+    CANISTER_DATA,
+};
+
+/// # Access Control
+/// Only the user index canister can shadow ban a profile
+#[ic_cdk::update(guard = "reject_updates_in_read_only_mode")]
+#[candid::candid_method(update)]
+fn update_profile_shadow_ban_status(is_shadow_banned: bool) {
+    record_call_in_journal("update_profile_shadow_ban_status");
+
+    let api_caller = ic_cdk::caller();
+
+    CANISTER_DATA.with(|canister_data_ref_cell| {
+        update_profile_shadow_ban_status_impl(
+            &mut canister_data_ref_cell.borrow_mut(),
+            &api_caller,
+            is_shadow_banned,
+        )
+    });
+}
+
+fn update_profile_shadow_ban_status_impl(
+    canister_data: &mut CanisterData,
+    api_caller: &Principal,
+    is_shadow_banned: bool,
+) {
+    if authorize_known_caller(
+        api_caller,
+        &canister_data.known_principal_ids,
+        &[KnownPrincipalType::CanisterIdUserIndex],
+    )
+    .is_err()
+    {
+        return;
+    }
+
+    canister_data.is_shadow_banned = is_shadow_banned;
+}
+
+#[cfg(test)]
+mod test {
+    use test_utils::setup::{
+        spoofed_callers::{assert_only_allowed_callers_are_accepted, get_mock_known_principal_map},
+        test_constants::{get_mock_canister_id_user_index, get_mock_user_alice_principal_id},
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_update_profile_shadow_ban_status_impl() {
+        let mut canister_data = CanisterData::default();
+        canister_data.known_principal_ids.insert(
+            KnownPrincipalType::CanisterIdUserIndex,
+            get_mock_canister_id_user_index(),
+        );
+
+        update_profile_shadow_ban_status_impl(
+            &mut canister_data,
+            &get_mock_user_alice_principal_id(),
+            true,
+        );
+        assert!(!canister_data.is_shadow_banned);
+
+        update_profile_shadow_ban_status_impl(
+            &mut canister_data,
+            &get_mock_canister_id_user_index(),
+            true,
+        );
+        assert!(canister_data.is_shadow_banned);
+
+        update_profile_shadow_ban_status_impl(
+            &mut canister_data,
+            &get_mock_canister_id_user_index(),
+            false,
+        );
+        assert!(!canister_data.is_shadow_banned);
+    }
+
+    #[test]
+    fn test_update_profile_shadow_ban_status_impl_with_spoofed_callers() {
+        let known_principal_ids = get_mock_known_principal_map();
+
+        assert_only_allowed_callers_are_accepted(
+            &known_principal_ids,
+            &[KnownPrincipalType::CanisterIdUserIndex],
+            |caller| {
+                let mut canister_data = CanisterData {
+                    known_principal_ids: known_principal_ids.clone(),
+                    ..Default::default()
+                };
+                update_profile_shadow_ban_status_impl(&mut canister_data, caller, true);
+                canister_data.is_shadow_banned
+            },
+        );
+    }
+}
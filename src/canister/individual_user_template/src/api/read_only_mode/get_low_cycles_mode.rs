@@ -0,0 +1,13 @@
+use crate::CANISTER_DATA;
+
+/// Whether new bets and posts are currently being rejected with `LowCyclesMode`
+#[ic_cdk::query]
+#[candid::candid_method(query)]
+fn get_low_cycles_mode() -> bool {
+    CANISTER_DATA.with(|canister_data_ref_cell| {
+        canister_data_ref_cell
+            .borrow()
+            .cycle_monitor
+            .is_in_low_cycles_mode()
+    })
+}
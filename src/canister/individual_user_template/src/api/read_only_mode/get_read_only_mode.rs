@@ -0,0 +1,8 @@
+use crate::CANISTER_DATA;
+
+/// Whether update calls are currently being rejected with `ReadOnlyMode`
+#[ic_cdk::query]
+#[candid::candid_method(query)]
+fn get_read_only_mode() -> bool {
+    CANISTER_DATA.with(|canister_data_ref_cell| canister_data_ref_cell.borrow().is_read_only)
+}
@@ -0,0 +1,3 @@
+pub mod get_low_cycles_mode;
+pub mod get_read_only_mode;
+pub mod update_read_only_mode;
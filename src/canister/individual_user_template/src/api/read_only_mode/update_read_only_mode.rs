@@ -0,0 +1,72 @@
+use candid::Principal;
+use shared_utils::common::{
+    types::known_principal::KnownPrincipalType, utils::known_caller::authorize_known_caller,
+};
+
+use crate::{data_model::CanisterData, util::call_journal::record_call_in_journal, CANISTER_DATA};
+
+/// Puts the canister in read only mode for the duration of a migration or restore, or takes
+/// it back out. Not behind the read only guard, so that the mode can always be switched off.
+///
+/// # Access Control
+/// Only the user index canister can change the mode
+#[ic_cdk::update]
+#[candid::candid_method(update)]
+fn update_read_only_mode(is_read_only: bool) {
+    record_call_in_journal("update_read_only_mode");
+
+    let api_caller = ic_cdk::caller();
+
+    CANISTER_DATA.with(|canister_data_ref_cell| {
+        update_read_only_mode_impl(
+            &mut canister_data_ref_cell.borrow_mut(),
+            &api_caller,
+            is_read_only,
+        )
+    });
+}
+
+fn update_read_only_mode_impl(
+    canister_data: &mut CanisterData,
+    api_caller: &Principal,
+    is_read_only: bool,
+) {
+    if authorize_known_caller(
+        api_caller,
+        &canister_data.known_principal_ids,
+        &[KnownPrincipalType::CanisterIdUserIndex],
+    )
+    .is_err()
+    {
+        return;
+    }
+
+    canister_data.is_read_only = is_read_only;
+}
+
+#[cfg(test)]
+mod test {
+    use test_utils::setup::spoofed_callers::{
+        assert_only_allowed_callers_are_accepted, get_mock_known_principal_map,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_update_read_only_mode_impl_with_spoofed_callers() {
+        let known_principal_ids = get_mock_known_principal_map();
+
+        assert_only_allowed_callers_are_accepted(
+            &known_principal_ids,
+            &[KnownPrincipalType::CanisterIdUserIndex],
+            |caller| {
+                let mut canister_data = CanisterData {
+                    known_principal_ids: known_principal_ids.clone(),
+                    ..Default::default()
+                };
+                update_read_only_mode_impl(&mut canister_data, caller, true);
+                canister_data.is_read_only
+            },
+        );
+    }
+}
@@ -0,0 +1,11 @@
+use shared_utils::canister_specific::individual_user_template::types::replication::ReplicationStatus;
+
+use crate::CANISTER_DATA;
+
+/// Whether this canister is a primary, a standby or fenced, and how far replication has got
+#[ic_cdk::query]
+#[candid::candid_method(query)]
+fn get_replication_status() -> ReplicationStatus {
+    CANISTER_DATA
+        .with(|canister_data_ref_cell| canister_data_ref_cell.borrow().replication.get_status())
+}
@@ -0,0 +1,3 @@
+pub mod get_replication_status;
+pub mod receive_replication_delta;
+pub mod update_replication;
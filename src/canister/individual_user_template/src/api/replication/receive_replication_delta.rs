@@ -0,0 +1,239 @@
+use std::time::SystemTime;
+
+use candid::Principal;
+use shared_utils::{
+    canister_specific::individual_user_template::types::{
+        error::ReplicationError,
+        hot_or_not::BetOutcomeForBetMaker,
+        replication::{ReplicaRole, ReplicationDelta},
+    },
+    common::utils::system_time,
+};
+
+use crate::{data_model::CanisterData, util::call_journal::record_call_in_journal, CANISTER_DATA};
+
+/// Applies a delta streamed by the primary. A delta whose reply was lost is sent again under
+/// the same sequence number, with whatever changed since added to it, so the last delta
+/// applied is taken again. Not behind the read only guard, as standbys are read only.
+///
+/// # Access Control
+/// Only the primary of this standby can send it deltas
+#[ic_cdk::update]
+#[candid::candid_method(update)]
+fn receive_replication_delta(replication_delta: ReplicationDelta) -> Result<(), ReplicationError> {
+    record_call_in_journal("receive_replication_delta");
+
+    let api_caller = ic_cdk::caller();
+    let current_time = system_time::get_current_system_time_from_ic();
+
+    CANISTER_DATA.with(|canister_data_ref_cell| {
+        receive_replication_delta_impl(
+            &mut canister_data_ref_cell.borrow_mut(),
+            &api_caller,
+            replication_delta,
+            current_time,
+        )
+    })
+}
+
+fn receive_replication_delta_impl(
+    canister_data: &mut CanisterData,
+    api_caller: &Principal,
+    replication_delta: ReplicationDelta,
+    current_time: SystemTime,
+) -> Result<(), ReplicationError> {
+    let replication = &canister_data.replication;
+
+    let ReplicaRole::Standby {
+        primary_canister_id,
+    } = replication.role
+    else {
+        // * a primary promoted in place of the sender tells it so through the epoch
+        return Err(match replication.role {
+            ReplicaRole::Primary if replication.epoch > replication_delta.epoch => {
+                ReplicationError::StaleEpoch {
+                    current_epoch: replication.epoch,
+                }
+            }
+            _ => ReplicationError::NotAStandby,
+        });
+    };
+    if *api_caller != primary_canister_id {
+        return Err(ReplicationError::Unauthorized);
+    }
+    if replication_delta.epoch != replication.epoch {
+        return Err(ReplicationError::StaleEpoch {
+            current_epoch: replication.epoch,
+        });
+    }
+    let last_applied_sequence_number = replication.last_sequence_number;
+    if replication_delta.sequence_number != last_applied_sequence_number
+        && replication_delta.sequence_number != last_applied_sequence_number + 1
+    {
+        return Err(ReplicationError::OutOfOrder {
+            last_applied_sequence_number,
+        });
+    }
+
+    replication_delta.posts.into_iter().for_each(|post| {
+        canister_data.all_created_posts.insert(post.id, post);
+    });
+
+    if let Some(token_balance_delta) = replication_delta.token_balance {
+        let token_balance = &mut canister_data.my_token_balance;
+        token_balance.utility_token_balance = token_balance_delta.utility_token_balance;
+        token_balance.lifetime_earnings = token_balance_delta.lifetime_earnings;
+        token_balance_delta
+            .new_events
+            .into_iter()
+            .for_each(|(index, token_event)| {
+                canister_data
+                    .token_transaction_history
+                    .insert(index, token_event)
+            });
+    }
+
+    replication_delta
+        .bets_placed
+        .into_iter()
+        .for_each(|placed_bet_detail| {
+            let previous_outcome = canister_data
+                .all_hot_or_not_bets_placed
+                .get(&(placed_bet_detail.canister_id, placed_bet_detail.post_id))
+                .map(|previous| previous.outcome_received.clone());
+            let betting_stats = &mut canister_data.betting_stats;
+            match previous_outcome {
+                None => {
+                    betting_stats.record_bet_placed(placed_bet_detail.amount_bet);
+                    betting_stats.record_bet_outcome(&placed_bet_detail.outcome_received);
+                }
+                Some(BetOutcomeForBetMaker::AwaitingResult) => {
+                    betting_stats.record_bet_outcome(&placed_bet_detail.outcome_received);
+                }
+                // * settled bets are only resent with their outcome as it was
+                Some(_) => {}
+            }
+
+            canister_data.all_hot_or_not_bets_placed.insert(
+                (placed_bet_detail.canister_id, placed_bet_detail.post_id),
+                placed_bet_detail,
+            );
+        });
+
+    canister_data.replication.last_sequence_number = replication_delta.sequence_number;
+    canister_data.replication.last_replicated_at = Some(current_time);
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use shared_utils::canister_specific::individual_user_template::types::replication::TokenBalanceDelta;
+    use test_utils::setup::test_constants::{
+        get_mock_user_alice_canister_id, get_mock_user_bob_canister_id,
+    };
+
+    use super::*;
+
+    fn get_replication_delta(epoch: u64, sequence_number: u64) -> ReplicationDelta {
+        ReplicationDelta {
+            epoch,
+            sequence_number,
+            posts: vec![],
+            token_balance: None,
+            bets_placed: vec![],
+        }
+    }
+
+    #[test]
+    fn test_receive_replication_delta_impl() {
+        let mut canister_data = CanisterData::default();
+        let primary_canister_id = get_mock_user_alice_canister_id();
+        let now = SystemTime::UNIX_EPOCH;
+
+        assert_eq!(
+            receive_replication_delta_impl(
+                &mut canister_data,
+                &primary_canister_id,
+                get_replication_delta(0, 1),
+                now,
+            ),
+            Err(ReplicationError::NotAStandby)
+        );
+
+        canister_data.replication.role = ReplicaRole::Standby {
+            primary_canister_id,
+        };
+        canister_data.replication.epoch = 1;
+        assert_eq!(
+            receive_replication_delta_impl(
+                &mut canister_data,
+                &get_mock_user_bob_canister_id(),
+                get_replication_delta(1, 1),
+                now,
+            ),
+            Err(ReplicationError::Unauthorized)
+        );
+        assert_eq!(
+            receive_replication_delta_impl(
+                &mut canister_data,
+                &primary_canister_id,
+                get_replication_delta(0, 1),
+                now,
+            ),
+            Err(ReplicationError::StaleEpoch { current_epoch: 1 })
+        );
+        assert_eq!(
+            receive_replication_delta_impl(
+                &mut canister_data,
+                &primary_canister_id,
+                get_replication_delta(1, 2),
+                now,
+            ),
+            Err(ReplicationError::OutOfOrder {
+                last_applied_sequence_number: 0
+            })
+        );
+
+        let mut replication_delta = get_replication_delta(1, 1);
+        replication_delta.token_balance = Some(TokenBalanceDelta {
+            utility_token_balance: 1_000,
+            lifetime_earnings: 1_000,
+            new_events: vec![],
+        });
+        assert_eq!(
+            receive_replication_delta_impl(
+                &mut canister_data,
+                &primary_canister_id,
+                replication_delta.clone(),
+                now,
+            ),
+            Ok(())
+        );
+        // * sent again as its reply was lost
+        assert_eq!(
+            receive_replication_delta_impl(
+                &mut canister_data,
+                &primary_canister_id,
+                replication_delta,
+                now,
+            ),
+            Ok(())
+        );
+        assert_eq!(canister_data.replication.last_sequence_number, 1);
+        assert_eq!(canister_data.my_token_balance.utility_token_balance, 1_000);
+
+        // * the standby was promoted, and the old primary is told it has been replaced
+        canister_data.replication.role = ReplicaRole::Primary;
+        canister_data.replication.epoch = 2;
+        assert_eq!(
+            receive_replication_delta_impl(
+                &mut canister_data,
+                &primary_canister_id,
+                get_replication_delta(1, 2),
+                now,
+            ),
+            Err(ReplicationError::StaleEpoch { current_epoch: 2 })
+        );
+    }
+}
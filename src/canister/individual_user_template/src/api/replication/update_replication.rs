@@ -0,0 +1,214 @@
+use candid::Principal;
+use shared_utils::{
+    canister_specific::individual_user_template::types::{
+        error::ReplicationError,
+        replication::{ReplicaRole, ReplicationUpdate},
+    },
+    common::{
+        types::known_principal::KnownPrincipalType, utils::known_caller::authorize_known_caller,
+    },
+};
+
+use crate::{data_model::CanisterData, util::call_journal::record_call_in_journal, CANISTER_DATA};
+
+/// Pairs this canister with a standby, makes it the standby of another canister, promotes it
+/// or fences it off. Every update carries the epoch of the pair, and updates from an earlier
+/// epoch than the one this canister is on are turned away. Not behind the read only guard, as
+/// standbys and fenced canisters are read only.
+///
+/// # Access Control
+/// Only the user index canister can update replication
+#[ic_cdk::update]
+#[candid::candid_method(update)]
+fn update_replication(update: ReplicationUpdate, epoch: u64) -> Result<(), ReplicationError> {
+    record_call_in_journal("update_replication");
+
+    let api_caller = ic_cdk::caller();
+
+    CANISTER_DATA.with(|canister_data_ref_cell| {
+        update_replication_impl(
+            &mut canister_data_ref_cell.borrow_mut(),
+            &api_caller,
+            update,
+            epoch,
+        )
+    })
+}
+
+fn update_replication_impl(
+    canister_data: &mut CanisterData,
+    api_caller: &Principal,
+    update: ReplicationUpdate,
+    epoch: u64,
+) -> Result<(), ReplicationError> {
+    authorize_known_caller(
+        api_caller,
+        &canister_data.known_principal_ids,
+        &[KnownPrincipalType::CanisterIdUserIndex],
+    )
+    .map_err(|_| ReplicationError::Unauthorized)?;
+
+    let replication = &mut canister_data.replication;
+    let stale_epoch = ReplicationError::StaleEpoch {
+        current_epoch: replication.epoch,
+    };
+    if epoch < replication.epoch {
+        return Err(stale_epoch);
+    }
+
+    match update {
+        ReplicationUpdate::ReplicateTo {
+            standby_canister_id,
+        } => {
+            if replication.role != ReplicaRole::Primary {
+                return Err(ReplicationError::NotAPrimary);
+            }
+
+            replication.standby_canister_id = Some(standby_canister_id);
+            replication.reset_synced_state();
+        }
+        ReplicationUpdate::StopReplicating => {
+            if replication.role != ReplicaRole::Primary {
+                return Err(ReplicationError::NotAPrimary);
+            }
+
+            replication.standby_canister_id = None;
+        }
+        ReplicationUpdate::BecomeStandbyOf {
+            primary_canister_id,
+        } => {
+            replication.role = ReplicaRole::Standby {
+                primary_canister_id,
+            };
+            replication.standby_canister_id = None;
+            replication.reset_synced_state();
+            canister_data.is_read_only = true;
+        }
+        ReplicationUpdate::PromoteToPrimary => {
+            if !matches!(replication.role, ReplicaRole::Standby { .. }) {
+                return Err(ReplicationError::NotAStandby);
+            }
+            // * a promotion always starts a new epoch, so the old primary can be told apart
+            if epoch == replication.epoch {
+                return Err(stale_epoch);
+            }
+
+            replication.role = ReplicaRole::Primary;
+            replication.reset_synced_state();
+            canister_data.is_read_only = false;
+        }
+        ReplicationUpdate::Fence => {
+            if epoch == replication.epoch {
+                return Err(stale_epoch);
+            }
+
+            replication.role = ReplicaRole::Fenced;
+            replication.standby_canister_id = None;
+            canister_data.is_read_only = true;
+        }
+    }
+
+    canister_data.replication.epoch = epoch;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use test_utils::setup::{
+        spoofed_callers::get_mock_known_principal_map,
+        test_constants::{
+            get_mock_canister_id_user_index, get_mock_user_alice_canister_id,
+            get_mock_user_bob_canister_id,
+        },
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_update_replication_impl() {
+        let mut canister_data = CanisterData {
+            known_principal_ids: get_mock_known_principal_map(),
+            ..Default::default()
+        };
+        let user_index = get_mock_canister_id_user_index();
+        let primary_canister_id = get_mock_user_alice_canister_id();
+
+        assert_eq!(
+            update_replication_impl(
+                &mut canister_data,
+                &primary_canister_id,
+                ReplicationUpdate::PromoteToPrimary,
+                1,
+            ),
+            Err(ReplicationError::Unauthorized)
+        );
+        assert_eq!(
+            update_replication_impl(
+                &mut canister_data,
+                &user_index,
+                ReplicationUpdate::PromoteToPrimary,
+                1,
+            ),
+            Err(ReplicationError::NotAStandby)
+        );
+
+        assert_eq!(
+            update_replication_impl(
+                &mut canister_data,
+                &user_index,
+                ReplicationUpdate::BecomeStandbyOf {
+                    primary_canister_id
+                },
+                1,
+            ),
+            Ok(())
+        );
+        assert!(canister_data.is_read_only);
+        assert_eq!(
+            update_replication_impl(
+                &mut canister_data,
+                &user_index,
+                ReplicationUpdate::ReplicateTo {
+                    standby_canister_id: get_mock_user_bob_canister_id()
+                },
+                1,
+            ),
+            Err(ReplicationError::NotAPrimary)
+        );
+
+        // * promoting within the same epoch would leave two primaries on it
+        assert_eq!(
+            update_replication_impl(
+                &mut canister_data,
+                &user_index,
+                ReplicationUpdate::PromoteToPrimary,
+                1,
+            ),
+            Err(ReplicationError::StaleEpoch { current_epoch: 1 })
+        );
+        assert_eq!(
+            update_replication_impl(
+                &mut canister_data,
+                &user_index,
+                ReplicationUpdate::PromoteToPrimary,
+                2,
+            ),
+            Ok(())
+        );
+        assert_eq!(canister_data.replication.role, ReplicaRole::Primary);
+        assert_eq!(canister_data.replication.epoch, 2);
+        assert!(!canister_data.is_read_only);
+
+        assert_eq!(
+            update_replication_impl(&mut canister_data, &user_index, ReplicationUpdate::Fence, 1,),
+            Err(ReplicationError::StaleEpoch { current_epoch: 2 })
+        );
+        assert_eq!(
+            update_replication_impl(&mut canister_data, &user_index, ReplicationUpdate::Fence, 3,),
+            Ok(())
+        );
+        assert_eq!(canister_data.replication.role, ReplicaRole::Fenced);
+        assert!(canister_data.is_read_only);
+    }
+}
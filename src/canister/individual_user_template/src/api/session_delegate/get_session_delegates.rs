@@ -0,0 +1,30 @@
+use candid::Principal;
+use shared_utils::canister_specific::individual_user_template::types::{
+    error::SessionDelegateError, session_delegate::SessionDelegate,
+};
+
+use crate::CANISTER_DATA;
+
+/// # Access Control
+/// Only the user whose profile details are stored in this canister can list their delegates
+#[ic_cdk::query]
+#[candid::candid_method(query)]
+fn get_session_delegates() -> Result<Vec<(Principal, SessionDelegate)>, SessionDelegateError> {
+    let api_caller = ic_cdk::caller();
+
+    CANISTER_DATA.with(|canister_data_ref_cell| {
+        let canister_data = canister_data_ref_cell.borrow();
+
+        if canister_data.profile.principal_id != Some(api_caller) {
+            return Err(SessionDelegateError::Unauthorized);
+        }
+
+        Ok(canister_data
+            .session_delegates
+            .iter()
+            .map(|(delegate_principal_id, session_delegate)| {
+                (*delegate_principal_id, session_delegate.clone())
+            })
+            .collect())
+    })
+}
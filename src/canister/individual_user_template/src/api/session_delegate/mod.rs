@@ -0,0 +1,3 @@
+pub mod get_session_delegates;
+pub mod register_session_delegate;
+pub mod revoke_session_delegate;
@@ -0,0 +1,211 @@
+use std::time::SystemTime;
+
+use candid::Principal;
+use shared_utils::{
+    canister_specific::individual_user_template::types::{
+        error::SessionDelegateError,
+        session_delegate::{
+            RegisterSessionDelegateArg, SessionDelegate, MAX_SESSION_DELEGATES_PER_PROFILE,
+        },
+    },
+    common::utils::system_time,
+};
+
+use crate::{
+    data_model::CanisterData,
+    util::{call_journal::record_call_in_journal, guards::reject_updates_in_read_only_mode},
+    CANISTER_DATA,
+};
+
+/// Registering an already registered delegate replaces its permission and expiry and
+/// resets its daily usage
+///
+/// # Access Control
+/// Only the user whose profile details are stored in this canister can register delegates
+#[ic_cdk::update(guard = "reject_updates_in_read_only_mode")]
+#[candid::candid_method(update)]
+fn register_session_delegate(
+    register_session_delegate_arg: RegisterSessionDelegateArg,
+) -> Result<(), SessionDelegateError> {
+    record_call_in_journal("register_session_delegate");
+
+    let api_caller = ic_cdk::caller();
+    let current_time = system_time::get_current_system_time_from_ic();
+
+    CANISTER_DATA.with(|canister_data_ref_cell| {
+        register_session_delegate_impl(
+            &mut canister_data_ref_cell.borrow_mut(),
+            &api_caller,
+            register_session_delegate_arg,
+            &current_time,
+        )
+    })
+}
+
+fn register_session_delegate_impl(
+    canister_data: &mut CanisterData,
+    api_caller: &Principal,
+    register_session_delegate_arg: RegisterSessionDelegateArg,
+    current_time: &SystemTime,
+) -> Result<(), SessionDelegateError> {
+    if canister_data.profile.principal_id != Some(*api_caller) {
+        return Err(SessionDelegateError::Unauthorized);
+    }
+
+    let delegate_principal_id = register_session_delegate_arg.delegate_principal_id;
+
+    if delegate_principal_id == *api_caller || delegate_principal_id == Principal::anonymous() {
+        return Err(SessionDelegateError::DelegateCannotBeProfileOwner);
+    }
+
+    if register_session_delegate_arg.expires_at <= *current_time {
+        return Err(SessionDelegateError::ExpiryIsInThePast);
+    }
+
+    // * expired delegates are pruned to make room for new ones
+    canister_data
+        .session_delegates
+        .retain(|_, session_delegate| !session_delegate.is_expired(current_time));
+
+    if !canister_data
+        .session_delegates
+        .contains_key(&delegate_principal_id)
+        && canister_data.session_delegates.len() >= MAX_SESSION_DELEGATES_PER_PROFILE
+    {
+        return Err(SessionDelegateError::MaxSessionDelegatesReached);
+    }
+
+    canister_data.session_delegates.insert(
+        delegate_principal_id,
+        SessionDelegate::new(&register_session_delegate_arg, current_time),
+    );
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use shared_utils::canister_specific::individual_user_template::types::session_delegate::SessionDelegatePermission;
+    use test_utils::setup::test_constants::{
+        get_mock_user_alice_principal_id, get_mock_user_bob_principal_id,
+    };
+
+    use super::*;
+
+    fn get_register_session_delegate_arg(
+        delegate_principal_id: Principal,
+        expires_at: SystemTime,
+    ) -> RegisterSessionDelegateArg {
+        RegisterSessionDelegateArg {
+            delegate_principal_id,
+            permission: SessionDelegatePermission::BetOnHotOrNotPosts {
+                max_bet_amount_per_day: 500,
+            },
+            expires_at,
+        }
+    }
+
+    #[test]
+    fn test_register_session_delegate_impl() {
+        let mut canister_data = CanisterData::default();
+        let current_time = SystemTime::now();
+        let expires_at = current_time
+            .checked_add(Duration::from_secs(60 * 60))
+            .unwrap();
+        canister_data.profile.principal_id = Some(get_mock_user_alice_principal_id());
+
+        assert_eq!(
+            register_session_delegate_impl(
+                &mut canister_data,
+                &get_mock_user_bob_principal_id(),
+                get_register_session_delegate_arg(get_mock_user_bob_principal_id(), expires_at),
+                &current_time,
+            ),
+            Err(SessionDelegateError::Unauthorized)
+        );
+        assert_eq!(
+            register_session_delegate_impl(
+                &mut canister_data,
+                &get_mock_user_alice_principal_id(),
+                get_register_session_delegate_arg(get_mock_user_alice_principal_id(), expires_at),
+                &current_time,
+            ),
+            Err(SessionDelegateError::DelegateCannotBeProfileOwner)
+        );
+        assert_eq!(
+            register_session_delegate_impl(
+                &mut canister_data,
+                &get_mock_user_alice_principal_id(),
+                get_register_session_delegate_arg(get_mock_user_bob_principal_id(), current_time),
+                &current_time,
+            ),
+            Err(SessionDelegateError::ExpiryIsInThePast)
+        );
+        assert_eq!(
+            register_session_delegate_impl(
+                &mut canister_data,
+                &get_mock_user_alice_principal_id(),
+                get_register_session_delegate_arg(get_mock_user_bob_principal_id(), expires_at),
+                &current_time,
+            ),
+            Ok(())
+        );
+        assert_eq!(canister_data.session_delegates.len(), 1);
+
+        (0..MAX_SESSION_DELEGATES_PER_PROFILE - 1).for_each(|delegate_number| {
+            assert!(register_session_delegate_impl(
+                &mut canister_data,
+                &get_mock_user_alice_principal_id(),
+                get_register_session_delegate_arg(
+                    Principal::self_authenticating(delegate_number.to_ne_bytes()),
+                    expires_at
+                ),
+                &current_time,
+            )
+            .is_ok());
+        });
+
+        assert_eq!(
+            register_session_delegate_impl(
+                &mut canister_data,
+                &get_mock_user_alice_principal_id(),
+                get_register_session_delegate_arg(
+                    Principal::self_authenticating(MAX_SESSION_DELEGATES_PER_PROFILE.to_ne_bytes()),
+                    expires_at
+                ),
+                &current_time,
+            ),
+            Err(SessionDelegateError::MaxSessionDelegatesReached)
+        );
+
+        // * re-registering an existing delegate is always allowed
+        assert_eq!(
+            register_session_delegate_impl(
+                &mut canister_data,
+                &get_mock_user_alice_principal_id(),
+                get_register_session_delegate_arg(get_mock_user_bob_principal_id(), expires_at),
+                &current_time,
+            ),
+            Ok(())
+        );
+
+        // * once every delegate has expired there is room again
+        assert_eq!(
+            register_session_delegate_impl(
+                &mut canister_data,
+                &get_mock_user_alice_principal_id(),
+                get_register_session_delegate_arg(
+                    Principal::self_authenticating(MAX_SESSION_DELEGATES_PER_PROFILE.to_ne_bytes()),
+                    expires_at
+                        .checked_add(Duration::from_secs(60 * 60))
+                        .unwrap()
+                ),
+                &expires_at,
+            ),
+            Ok(())
+        );
+        assert_eq!(canister_data.session_delegates.len(), 1);
+    }
+}
@@ -0,0 +1,117 @@
+use candid::Principal;
+use shared_utils::canister_specific::individual_user_template::types::error::SessionDelegateError;
+
+use crate::{
+    data_model::CanisterData,
+    util::{call_journal::record_call_in_journal, guards::reject_updates_in_read_only_mode},
+    CANISTER_DATA,
+};
+
+/// # Access Control
+/// The profile owner can revoke any delegate. A delegate can only revoke itself
+#[ic_cdk::update(guard = "reject_updates_in_read_only_mode")]
+#[candid::candid_method(update)]
+fn revoke_session_delegate(delegate_principal_id: Principal) -> Result<(), SessionDelegateError> {
+    record_call_in_journal("revoke_session_delegate");
+
+    let api_caller = ic_cdk::caller();
+
+    CANISTER_DATA.with(|canister_data_ref_cell| {
+        revoke_session_delegate_impl(
+            &mut canister_data_ref_cell.borrow_mut(),
+            &api_caller,
+            &delegate_principal_id,
+        )
+    })
+}
+
+fn revoke_session_delegate_impl(
+    canister_data: &mut CanisterData,
+    api_caller: &Principal,
+    delegate_principal_id: &Principal,
+) -> Result<(), SessionDelegateError> {
+    if canister_data.profile.principal_id != Some(*api_caller)
+        && api_caller != delegate_principal_id
+    {
+        return Err(SessionDelegateError::Unauthorized);
+    }
+
+    canister_data
+        .session_delegates
+        .remove(delegate_principal_id)
+        .map(|_| ())
+        .ok_or(SessionDelegateError::DelegateNotFound)
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::SystemTime;
+
+    use shared_utils::canister_specific::individual_user_template::types::session_delegate::{
+        RegisterSessionDelegateArg, SessionDelegate, SessionDelegatePermission,
+    };
+    use test_utils::setup::test_constants::{
+        get_mock_user_alice_principal_id, get_mock_user_bob_principal_id,
+        get_mock_user_charlie_principal_id,
+    };
+
+    use super::*;
+
+    fn insert_session_delegate(canister_data: &mut CanisterData, delegate_principal_id: Principal) {
+        canister_data.session_delegates.insert(
+            delegate_principal_id,
+            SessionDelegate::new(
+                &RegisterSessionDelegateArg {
+                    delegate_principal_id,
+                    permission: SessionDelegatePermission::BetOnHotOrNotPosts {
+                        max_bet_amount_per_day: 500,
+                    },
+                    expires_at: SystemTime::now(),
+                },
+                &SystemTime::now(),
+            ),
+        );
+    }
+
+    #[test]
+    fn test_revoke_session_delegate_impl() {
+        let mut canister_data = CanisterData::default();
+        canister_data.profile.principal_id = Some(get_mock_user_alice_principal_id());
+        insert_session_delegate(&mut canister_data, get_mock_user_bob_principal_id());
+        insert_session_delegate(&mut canister_data, get_mock_user_charlie_principal_id());
+
+        assert_eq!(
+            revoke_session_delegate_impl(
+                &mut canister_data,
+                &get_mock_user_bob_principal_id(),
+                &get_mock_user_charlie_principal_id(),
+            ),
+            Err(SessionDelegateError::Unauthorized)
+        );
+        assert_eq!(
+            revoke_session_delegate_impl(
+                &mut canister_data,
+                &get_mock_user_bob_principal_id(),
+                &get_mock_user_bob_principal_id(),
+            ),
+            Ok(())
+        );
+        assert_eq!(
+            revoke_session_delegate_impl(
+                &mut canister_data,
+                &get_mock_user_alice_principal_id(),
+                &get_mock_user_bob_principal_id(),
+            ),
+            Err(SessionDelegateError::DelegateNotFound)
+        );
+        assert_eq!(
+            revoke_session_delegate_impl(
+                &mut canister_data,
+                &get_mock_user_alice_principal_id(),
+                &get_mock_user_charlie_principal_id(),
+            ),
+            Ok(())
+        );
+        assert!(canister_data.session_delegates.is_empty());
+    }
+}
@@ -0,0 +1,204 @@
+use std::time::SystemTime;
+
+use candid::Principal;
+use ic_cdk::api::call;
+use shared_utils::{
+    canister_specific::individual_user_template::types::{
+        error::CreateReferralCodeError,
+        referral::{
+            ReferralCodeDetails, MAX_REFERRAL_CODES_PER_PROFILE, MAX_REFERRAL_CODE_NAME_LENGTH,
+        },
+    },
+    common::{types::known_principal::KnownPrincipalType, utils::system_time},
+    types::canister_specific::user_index::error_types::RegisterReferralCodeError,
+};
+
+use crate::{
+    data_model::CanisterData,
+    util::{call_journal::record_call_in_journal, guards::reject_updates_in_read_only_mode},
+    CANISTER_DATA,
+};
+
+/// Registers a new referral code with the user index and keeps it here under `name`, so that
+/// signups and rewards can be told apart per campaign
+///
+/// # Access Control
+/// Only the user whose profile details are stored in this canister can create referral codes
+#[ic_cdk::update(guard = "reject_updates_in_read_only_mode")]
+#[candid::candid_method(update)]
+async fn create_referral_code(
+    name: String,
+) -> Result<ReferralCodeDetails, CreateReferralCodeError> {
+    record_call_in_journal("create_referral_code");
+
+    let api_caller = ic_cdk::caller();
+    let name = name.trim().to_string();
+
+    CANISTER_DATA.with(|canister_data_ref_cell| {
+        validate_new_referral_code_impl(&canister_data_ref_cell.borrow(), &api_caller, &name)
+    })?;
+
+    let user_index_canister_id = CANISTER_DATA
+        .with(|canister_data_ref_cell| {
+            canister_data_ref_cell
+                .borrow()
+                .known_principal_ids
+                .get(&KnownPrincipalType::CanisterIdUserIndex)
+                .cloned()
+        })
+        .ok_or(CreateReferralCodeError::UserIndexCrossCanisterCallFailed)?;
+
+    let (response,): (Result<String, RegisterReferralCodeError>,) = call::call(
+        user_index_canister_id,
+        "register_referral_code",
+        (api_caller,),
+    )
+    .await
+    .map_err(|_| CreateReferralCodeError::UserIndexCrossCanisterCallFailed)?;
+
+    let referral_code =
+        response.map_err(|_| CreateReferralCodeError::UserIndexRejectedRegistration)?;
+
+    let current_time = system_time::get_current_system_time_from_ic();
+
+    Ok(CANISTER_DATA.with(|canister_data_ref_cell| {
+        add_referral_code_impl(
+            &mut canister_data_ref_cell.borrow_mut(),
+            referral_code,
+            name,
+            current_time,
+        )
+    }))
+}
+
+fn validate_new_referral_code_impl(
+    canister_data: &CanisterData,
+    api_caller: &Principal,
+    name: &str,
+) -> Result<(), CreateReferralCodeError> {
+    if canister_data.profile.principal_id != Some(*api_caller) {
+        return Err(CreateReferralCodeError::Unauthorized);
+    }
+
+    if name.is_empty() || name.chars().count() > MAX_REFERRAL_CODE_NAME_LENGTH {
+        return Err(CreateReferralCodeError::InvalidName);
+    }
+
+    if canister_data.referral_codes.len() >= MAX_REFERRAL_CODES_PER_PROFILE {
+        return Err(CreateReferralCodeError::TooManyReferralCodes);
+    }
+
+    if canister_data
+        .referral_codes
+        .values()
+        .any(|referral_code_details| referral_code_details.name == name)
+    {
+        return Err(CreateReferralCodeError::NameAlreadyUsed);
+    }
+
+    Ok(())
+}
+
+fn add_referral_code_impl(
+    canister_data: &mut CanisterData,
+    referral_code: String,
+    name: String,
+    current_time: SystemTime,
+) -> ReferralCodeDetails {
+    let referral_code_details = ReferralCodeDetails {
+        code: referral_code.clone(),
+        name,
+        created_at: current_time,
+        referee_count: 0,
+        signup_rewards_earned: 0,
+        referrer_share_of_winnings_earned: 0,
+    };
+
+    canister_data
+        .referral_codes
+        .insert(referral_code, referral_code_details.clone());
+
+    referral_code_details
+}
+
+#[cfg(test)]
+mod test {
+    use test_utils::setup::test_constants::{
+        get_mock_user_alice_principal_id, get_mock_user_bob_principal_id,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_validate_new_referral_code_impl() {
+        let mut canister_data = CanisterData::default();
+        canister_data.profile.principal_id = Some(get_mock_user_alice_principal_id());
+
+        assert_eq!(
+            validate_new_referral_code_impl(
+                &canister_data,
+                &get_mock_user_bob_principal_id(),
+                "summer"
+            ),
+            Err(CreateReferralCodeError::Unauthorized)
+        );
+        assert_eq!(
+            validate_new_referral_code_impl(
+                &canister_data,
+                &get_mock_user_alice_principal_id(),
+                ""
+            ),
+            Err(CreateReferralCodeError::InvalidName)
+        );
+        assert_eq!(
+            validate_new_referral_code_impl(
+                &canister_data,
+                &get_mock_user_alice_principal_id(),
+                &"a".repeat(MAX_REFERRAL_CODE_NAME_LENGTH + 1)
+            ),
+            Err(CreateReferralCodeError::InvalidName)
+        );
+
+        add_referral_code_impl(
+            &mut canister_data,
+            "AB3K9Q2Z".to_string(),
+            "summer".to_string(),
+            SystemTime::now(),
+        );
+
+        assert_eq!(
+            validate_new_referral_code_impl(
+                &canister_data,
+                &get_mock_user_alice_principal_id(),
+                "summer"
+            ),
+            Err(CreateReferralCodeError::NameAlreadyUsed)
+        );
+        assert_eq!(
+            validate_new_referral_code_impl(
+                &canister_data,
+                &get_mock_user_alice_principal_id(),
+                "winter"
+            ),
+            Ok(())
+        );
+
+        (1..MAX_REFERRAL_CODES_PER_PROFILE).for_each(|index| {
+            add_referral_code_impl(
+                &mut canister_data,
+                format!("CODE{}", index),
+                format!("campaign {}", index),
+                SystemTime::now(),
+            );
+        });
+
+        assert_eq!(
+            validate_new_referral_code_impl(
+                &canister_data,
+                &get_mock_user_alice_principal_id(),
+                "winter"
+            ),
+            Err(CreateReferralCodeError::TooManyReferralCodes)
+        );
+    }
+}
@@ -0,0 +1,214 @@
+use std::time::SystemTime;
+
+use candid::Principal;
+use shared_utils::{
+    canister_specific::individual_user_template::types::{
+        error::ExportTokenTransactionsError,
+        token::{
+            compute_token_transactions_checksum, TokenTransactionsExportChunk,
+            TokenTransactionsExportManifest,
+        },
+    },
+    common::{types::known_principal::KnownPrincipalType, utils::system_time},
+};
+
+use crate::{data_model::CanisterData, CANISTER_DATA};
+
+const MAX_TOKEN_TRANSACTIONS_IN_ONE_EXPORT_CHUNK: u64 = 500;
+
+/// Returns the token events whose index lies in `[from_inclusive_index, to_exclusive_index)`
+/// in ascending index order. Fetch the manifest first and again after the last chunk; if
+/// the two checksums differ, events were added mid export and it should be rerun.
+///
+/// # Access Control
+/// Only the global super admin can export token transactions
+#[ic_cdk::query]
+#[candid::candid_method(query)]
+fn get_all_token_transactions(
+    from_inclusive_index: u64,
+    to_exclusive_index: u64,
+) -> Result<TokenTransactionsExportChunk, ExportTokenTransactionsError> {
+    let api_caller = ic_cdk::caller();
+
+    CANISTER_DATA.with(|canister_data_ref_cell| {
+        let canister_data = canister_data_ref_cell.borrow();
+
+        check_caller_is_global_super_admin(&canister_data, &api_caller)?;
+
+        get_all_token_transactions_impl(&canister_data, from_inclusive_index, to_exclusive_index)
+    })
+}
+
+/// # Access Control
+/// Only the global super admin can export token transactions
+#[ic_cdk::query]
+#[candid::candid_method(query)]
+fn get_all_token_transactions_export_manifest(
+) -> Result<TokenTransactionsExportManifest, ExportTokenTransactionsError> {
+    let api_caller = ic_cdk::caller();
+    let current_time = system_time::get_current_system_time_from_ic();
+
+    CANISTER_DATA.with(|canister_data_ref_cell| {
+        let canister_data = canister_data_ref_cell.borrow();
+
+        check_caller_is_global_super_admin(&canister_data, &api_caller)?;
+
+        Ok(get_all_token_transactions_export_manifest_impl(
+            &canister_data,
+            &current_time,
+        ))
+    })
+}
+
+fn check_caller_is_global_super_admin(
+    canister_data: &CanisterData,
+    api_caller: &Principal,
+) -> Result<(), ExportTokenTransactionsError> {
+    if canister_data
+        .known_principal_ids
+        .get(&KnownPrincipalType::UserIdGlobalSuperAdmin)
+        != Some(api_caller)
+    {
+        return Err(ExportTokenTransactionsError::Unauthorized);
+    }
+
+    Ok(())
+}
+
+fn get_all_token_transactions_impl(
+    canister_data: &CanisterData,
+    from_inclusive_index: u64,
+    to_exclusive_index: u64,
+) -> Result<TokenTransactionsExportChunk, ExportTokenTransactionsError> {
+    if from_inclusive_index >= to_exclusive_index {
+        return Err(ExportTokenTransactionsError::InvalidBoundsPassed);
+    }
+
+    if to_exclusive_index - from_inclusive_index > MAX_TOKEN_TRANSACTIONS_IN_ONE_EXPORT_CHUNK {
+        return Err(ExportTokenTransactionsError::ExceededMaxNumberOfItemsAllowedInOneRequest);
+    }
+
+    let token_transaction_history = &canister_data.token_transaction_history;
+
+    Ok(TokenTransactionsExportChunk {
+        events: token_transaction_history
+            .range(from_inclusive_index..to_exclusive_index)
+            .collect(),
+        next_from_inclusive_index: token_transaction_history
+            .range(to_exclusive_index..)
+            .next()
+            .map(|(index, _)| index),
+    })
+}
+
+fn get_all_token_transactions_export_manifest_impl(
+    canister_data: &CanisterData,
+    current_time: &SystemTime,
+) -> TokenTransactionsExportManifest {
+    let my_token_balance = &canister_data.my_token_balance;
+    let token_transaction_history = &canister_data.token_transaction_history;
+
+    TokenTransactionsExportManifest {
+        total_number_of_events: token_transaction_history.len(),
+        first_event_index: token_transaction_history.first_transaction_id(),
+        last_event_index: token_transaction_history.last_transaction_id(),
+        utility_token_balance: my_token_balance.utility_token_balance,
+        lifetime_earnings: my_token_balance.lifetime_earnings,
+        checksum: compute_token_transactions_checksum(token_transaction_history.iter()),
+        generated_at: *current_time,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use shared_utils::common::types::utility_token::token_event::{MintEvent, TokenEvent};
+    use test_utils::setup::test_constants::{
+        get_global_super_admin_principal_id, get_mock_user_alice_principal_id,
+    };
+
+    use super::*;
+
+    fn get_canister_data_with_token_events(number_of_events: u64) -> CanisterData {
+        let mut canister_data = CanisterData::default();
+        canister_data.known_principal_ids.insert(
+            KnownPrincipalType::UserIdGlobalSuperAdmin,
+            get_global_super_admin_principal_id(),
+        );
+
+        (0..number_of_events).for_each(|_| {
+            canister_data.handle_token_event(TokenEvent::Mint {
+                amount: 1000,
+                details: MintEvent::NewUserSignup {
+                    new_user_principal_id: get_mock_user_alice_principal_id(),
+                },
+                timestamp: SystemTime::UNIX_EPOCH,
+            });
+        });
+
+        canister_data
+    }
+
+    #[test]
+    fn test_check_caller_is_global_super_admin() {
+        let canister_data = get_canister_data_with_token_events(0);
+
+        assert_eq!(
+            check_caller_is_global_super_admin(&canister_data, &get_mock_user_alice_principal_id()),
+            Err(ExportTokenTransactionsError::Unauthorized)
+        );
+        assert_eq!(
+            check_caller_is_global_super_admin(
+                &canister_data,
+                &get_global_super_admin_principal_id()
+            ),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn test_get_all_token_transactions_impl() {
+        let canister_data = get_canister_data_with_token_events(7);
+
+        assert_eq!(
+            get_all_token_transactions_impl(&canister_data, 5, 5),
+            Err(ExportTokenTransactionsError::InvalidBoundsPassed)
+        );
+        assert_eq!(
+            get_all_token_transactions_impl(&canister_data, 0, 501),
+            Err(ExportTokenTransactionsError::ExceededMaxNumberOfItemsAllowedInOneRequest)
+        );
+
+        // * history indices start at 1
+        let first_chunk = get_all_token_transactions_impl(&canister_data, 0, 4).unwrap();
+        assert_eq!(
+            first_chunk
+                .events
+                .iter()
+                .map(|(index, _)| *index)
+                .collect::<Vec<_>>(),
+            vec![1, 2, 3]
+        );
+        assert_eq!(first_chunk.next_from_inclusive_index, Some(4));
+
+        let last_chunk = get_all_token_transactions_impl(&canister_data, 4, 100).unwrap();
+        assert_eq!(last_chunk.events.len(), 4);
+        assert_eq!(last_chunk.next_from_inclusive_index, None);
+
+        let manifest =
+            get_all_token_transactions_export_manifest_impl(&canister_data, &SystemTime::now());
+        assert_eq!(manifest.total_number_of_events, 7);
+        assert_eq!(manifest.first_event_index, Some(1));
+        assert_eq!(manifest.last_event_index, Some(7));
+        assert_eq!(manifest.utility_token_balance, 7000);
+        assert_eq!(
+            manifest.checksum,
+            compute_token_transactions_checksum(
+                first_chunk
+                    .events
+                    .iter()
+                    .chain(last_chunk.events.iter())
+                    .map(|(index, token_event)| (index, token_event))
+            )
+        );
+    }
+}
@@ -0,0 +1,207 @@
+use std::time::SystemTime;
+
+use candid::Principal;
+use shared_utils::{
+    canister_specific::individual_user_template::types::{
+        error::GetCreatorCommissionReportError, token::CreatorCommissionReport,
+    },
+    pagination::{self, PaginationError},
+};
+
+use crate::{data_model::CanisterData, CANISTER_DATA};
+
+/// Commission earned on each room of the creator's posts in `[from_inclusive_time,
+/// to_exclusive_time)`, ordered by post, slot and room. Paginated over rooms, 100 at a time.
+///
+/// # Access Control
+/// Only the user whose profile details are stored in this canister can see their earnings
+#[ic_cdk::query]
+#[candid::candid_method(query)]
+fn get_creator_commission_report(
+    from_inclusive_time: SystemTime,
+    to_exclusive_time: SystemTime,
+    from_inclusive_index: u64,
+    to_exclusive_index: u64,
+) -> Result<CreatorCommissionReport, GetCreatorCommissionReportError> {
+    let api_caller = ic_cdk::caller();
+
+    CANISTER_DATA.with(|canister_data_ref_cell| {
+        get_creator_commission_report_impl(
+            &canister_data_ref_cell.borrow(),
+            &api_caller,
+            &from_inclusive_time,
+            &to_exclusive_time,
+            from_inclusive_index,
+            to_exclusive_index,
+        )
+    })
+}
+
+fn get_creator_commission_report_impl(
+    canister_data: &CanisterData,
+    api_caller: &Principal,
+    from_inclusive_time: &SystemTime,
+    to_exclusive_time: &SystemTime,
+    from_inclusive_index: u64,
+    to_exclusive_index: u64,
+) -> Result<CreatorCommissionReport, GetCreatorCommissionReportError> {
+    if canister_data.profile.principal_id != Some(*api_caller) {
+        return Err(GetCreatorCommissionReportError::Unauthorized);
+    }
+
+    if from_inclusive_time >= to_exclusive_time {
+        return Err(GetCreatorCommissionReportError::InvalidDateRange);
+    }
+
+    let commission_earned_per_room = canister_data
+        .token_transaction_history
+        .get_creator_commission_earned_per_room(from_inclusive_time, to_exclusive_time);
+
+    let (from_inclusive_index, to_exclusive_index) = pagination::get_pagination_bounds(
+        from_inclusive_index,
+        to_exclusive_index,
+        commission_earned_per_room.len() as u64,
+    )
+    .map_err(|e| match e {
+        PaginationError::InvalidBoundsPassed => {
+            GetCreatorCommissionReportError::InvalidBoundsPassed
+        }
+        PaginationError::ReachedEndOfItemsList => {
+            GetCreatorCommissionReportError::ReachedEndOfItemsList
+        }
+        PaginationError::ExceededMaxNumberOfItemsAllowedInOneRequest => {
+            GetCreatorCommissionReportError::ExceededMaxNumberOfItemsAllowedInOneRequest
+        }
+    })?;
+
+    Ok(CreatorCommissionReport {
+        total_number_of_rooms: commission_earned_per_room.len() as u64,
+        total_commission_amount: commission_earned_per_room
+            .iter()
+            .map(|commission_earned_in_room| commission_earned_in_room.commission_amount)
+            .sum(),
+        rooms: commission_earned_per_room
+            [from_inclusive_index as usize..to_exclusive_index as usize]
+            .to_vec(),
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use shared_utils::common::types::utility_token::token_event::{
+        HotOrNotOutcomePayoutEvent, TokenEvent,
+    };
+    use test_utils::setup::test_constants::{
+        get_mock_user_alice_canister_id, get_mock_user_alice_principal_id,
+        get_mock_user_bob_principal_id,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_get_creator_commission_report_impl() {
+        let mut canister_data = CanisterData::default();
+        let start_time = SystemTime::now();
+        let end_time = start_time + Duration::from_secs(60 * 60 * 24 * 7);
+        canister_data.profile.principal_id = Some(get_mock_user_alice_principal_id());
+        (0..150).for_each(|post_id| {
+            canister_data.handle_token_event(TokenEvent::HotOrNotOutcomePayout {
+                amount: 10,
+                details: HotOrNotOutcomePayoutEvent::CommissionFromHotOrNotBet {
+                    post_canister_id: get_mock_user_alice_canister_id(),
+                    post_id,
+                    slot_id: 1,
+                    room_id: 1,
+                    room_pot_total_amount: 100,
+                },
+                timestamp: start_time,
+            });
+        });
+
+        assert_eq!(
+            get_creator_commission_report_impl(
+                &canister_data,
+                &get_mock_user_bob_principal_id(),
+                &start_time,
+                &end_time,
+                0,
+                100
+            ),
+            Err(GetCreatorCommissionReportError::Unauthorized)
+        );
+        assert_eq!(
+            get_creator_commission_report_impl(
+                &canister_data,
+                &get_mock_user_alice_principal_id(),
+                &end_time,
+                &start_time,
+                0,
+                100
+            ),
+            Err(GetCreatorCommissionReportError::InvalidDateRange)
+        );
+        assert_eq!(
+            get_creator_commission_report_impl(
+                &canister_data,
+                &get_mock_user_alice_principal_id(),
+                &start_time,
+                &end_time,
+                0,
+                101
+            ),
+            Err(GetCreatorCommissionReportError::ExceededMaxNumberOfItemsAllowedInOneRequest)
+        );
+        assert_eq!(
+            get_creator_commission_report_impl(
+                &canister_data,
+                &get_mock_user_alice_principal_id(),
+                &start_time,
+                &end_time,
+                150,
+                200
+            ),
+            Err(GetCreatorCommissionReportError::ReachedEndOfItemsList)
+        );
+
+        let first_page = get_creator_commission_report_impl(
+            &canister_data,
+            &get_mock_user_alice_principal_id(),
+            &start_time,
+            &end_time,
+            0,
+            100,
+        )
+        .unwrap();
+        assert_eq!(first_page.rooms.len(), 100);
+        assert_eq!(first_page.rooms[0].post_id, 0);
+        assert_eq!(first_page.total_number_of_rooms, 150);
+        assert_eq!(first_page.total_commission_amount, 1500);
+
+        let last_page = get_creator_commission_report_impl(
+            &canister_data,
+            &get_mock_user_alice_principal_id(),
+            &start_time,
+            &end_time,
+            100,
+            200,
+        )
+        .unwrap();
+        assert_eq!(last_page.rooms.len(), 50);
+        assert_eq!(last_page.rooms[0].post_id, 100);
+
+        // * nothing was paid out before the range started
+        assert_eq!(
+            get_creator_commission_report_impl(
+                &canister_data,
+                &get_mock_user_alice_principal_id(),
+                &(start_time - Duration::from_secs(60)),
+                &start_time,
+                0,
+                100
+            ),
+            Err(GetCreatorCommissionReportError::ReachedEndOfItemsList)
+        );
+    }
+}
@@ -0,0 +1,62 @@
+use candid::Principal;
+use shared_utils::common::types::known_principal::KnownPrincipalType;
+
+use crate::{data_model::CanisterData, CANISTER_DATA};
+
+/// Total house rake held by the treasury. Errors when this canister is not the treasury
+#[ic_cdk::query]
+#[candid::candid_method(query)]
+fn get_house_rake_treasury_balance() -> Result<u64, String> {
+    let this_canister_id = ic_cdk::id();
+
+    CANISTER_DATA.with(|canister_data_ref_cell| {
+        get_house_rake_treasury_balance_impl(&canister_data_ref_cell.borrow(), &this_canister_id)
+    })
+}
+
+fn get_house_rake_treasury_balance_impl(
+    canister_data: &CanisterData,
+    this_canister_id: &Principal,
+) -> Result<u64, String> {
+    if canister_data
+        .known_principal_ids
+        .get(&KnownPrincipalType::CanisterIdTreasury)
+        != Some(this_canister_id)
+    {
+        return Err("This canister is not the treasury".to_string());
+    }
+
+    Ok(canister_data.my_token_balance.utility_token_balance)
+}
+
+#[cfg(test)]
+mod test {
+    use test_utils::setup::test_constants::get_mock_user_charlie_canister_id;
+
+    use super::*;
+
+    #[test]
+    fn test_get_house_rake_treasury_balance_impl() {
+        let mut canister_data = CanisterData::default();
+        canister_data.my_token_balance.utility_token_balance = 35;
+
+        assert!(get_house_rake_treasury_balance_impl(
+            &canister_data,
+            &get_mock_user_charlie_canister_id()
+        )
+        .is_err());
+
+        canister_data.known_principal_ids.insert(
+            KnownPrincipalType::CanisterIdTreasury,
+            get_mock_user_charlie_canister_id(),
+        );
+
+        assert_eq!(
+            get_house_rake_treasury_balance_impl(
+                &canister_data,
+                &get_mock_user_charlie_canister_id()
+            ),
+            Ok(35)
+        );
+    }
+}
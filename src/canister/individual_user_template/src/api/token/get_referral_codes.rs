@@ -0,0 +1,39 @@
+use candid::Principal;
+use shared_utils::canister_specific::individual_user_template::types::{
+    error::GetReferralCodesError, referral::ReferralCodeDetails,
+};
+
+use crate::{data_model::CanisterData, CANISTER_DATA};
+
+/// Every referral code this profile has created, oldest first, with the referees each one
+/// brought in and the rewards earned through them
+///
+/// # Access Control
+/// Only the user whose profile details are stored in this canister can see their referral codes
+#[ic_cdk::query]
+#[candid::candid_method(query)]
+fn get_referral_codes() -> Result<Vec<ReferralCodeDetails>, GetReferralCodesError> {
+    let api_caller = ic_cdk::caller();
+
+    CANISTER_DATA.with(|canister_data_ref_cell| {
+        get_referral_codes_impl(&canister_data_ref_cell.borrow(), &api_caller)
+    })
+}
+
+fn get_referral_codes_impl(
+    canister_data: &CanisterData,
+    api_caller: &Principal,
+) -> Result<Vec<ReferralCodeDetails>, GetReferralCodesError> {
+    if canister_data.profile.principal_id != Some(*api_caller) {
+        return Err(GetReferralCodesError::Unauthorized);
+    }
+
+    let mut referral_codes = canister_data
+        .referral_codes
+        .values()
+        .cloned()
+        .collect::<Vec<_>>();
+    referral_codes.sort_by_key(|referral_code_details| referral_code_details.created_at);
+
+    Ok(referral_codes)
+}
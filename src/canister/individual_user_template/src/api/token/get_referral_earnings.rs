@@ -0,0 +1,178 @@
+use candid::Principal;
+use shared_utils::canister_specific::individual_user_template::types::{
+    error::GetReferralEarningsError, referral::ReferralEarnings,
+};
+
+use crate::{data_model::CanisterData, CANISTER_DATA};
+
+/// Who referred this profile, and the signup rewards and shares of referee bet winnings it
+/// has earned by referring others. Unlike `get_referral_codes`, this also counts referees
+/// that signed up without a named code.
+///
+/// # Access Control
+/// Only the user whose profile details are stored in this canister can see their earnings
+#[ic_cdk::query]
+#[candid::candid_method(query)]
+fn get_referral_earnings() -> Result<ReferralEarnings, GetReferralEarningsError> {
+    let api_caller = ic_cdk::caller();
+
+    CANISTER_DATA.with(|canister_data_ref_cell| {
+        get_referral_earnings_impl(&canister_data_ref_cell.borrow(), &api_caller)
+    })
+}
+
+fn get_referral_earnings_impl(
+    canister_data: &CanisterData,
+    api_caller: &Principal,
+) -> Result<ReferralEarnings, GetReferralEarningsError> {
+    if canister_data.profile.principal_id != Some(*api_caller) {
+        return Err(GetReferralEarningsError::Unauthorized);
+    }
+
+    let referral_earnings_totals = canister_data
+        .referral_earnings_totals
+        .clone()
+        .unwrap_or_default();
+
+    Ok(ReferralEarnings {
+        referred_by: canister_data
+            .my_referrer_details
+            .as_ref()
+            .map(|my_referrer_details| my_referrer_details.referrer_principal_id),
+        referee_count: canister_data.my_referee_canister_ids.len() as u64,
+        signup_rewards_earned: referral_earnings_totals.signup_rewards_earned,
+        referrer_share_of_winnings_earned: referral_earnings_totals
+            .referrer_share_of_winnings_earned,
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::SystemTime;
+
+    use shared_utils::{
+        canister_specific::individual_user_template::types::referral::ReferralDetails,
+        common::types::utility_token::token_event::{
+            HotOrNotOutcomePayoutEvent, MintEvent, ReferralCommissionEvent, TokenEvent,
+        },
+    };
+    use test_utils::setup::test_constants::{
+        get_mock_user_alice_canister_id, get_mock_user_alice_principal_id,
+        get_mock_user_bob_canister_id, get_mock_user_bob_principal_id,
+        get_mock_user_charlie_canister_id, get_mock_user_charlie_principal_id,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_get_referral_earnings_impl() {
+        let mut canister_data = CanisterData::default();
+        canister_data.profile.principal_id = Some(get_mock_user_bob_principal_id());
+
+        assert_eq!(
+            get_referral_earnings_impl(&canister_data, &get_mock_user_alice_principal_id()),
+            Err(GetReferralEarningsError::Unauthorized)
+        );
+        assert_eq!(
+            get_referral_earnings_impl(&canister_data, &get_mock_user_bob_principal_id()),
+            Ok(ReferralEarnings {
+                referred_by: None,
+                referee_count: 0,
+                signup_rewards_earned: 0,
+                referrer_share_of_winnings_earned: 0,
+            })
+        );
+
+        // * alice referred bob, who referred charlie
+        canister_data.my_referrer_details = Some(ReferralDetails {
+            referrer_principal_id: get_mock_user_alice_principal_id(),
+            referrer_canister_id: get_mock_user_alice_canister_id(),
+            referee_principal_id: get_mock_user_bob_principal_id(),
+            referee_canister_id: get_mock_user_bob_canister_id(),
+            referral_code: None,
+        });
+        canister_data
+            .my_referee_canister_ids
+            .insert(get_mock_user_charlie_canister_id());
+        canister_data.handle_token_event(TokenEvent::Mint {
+            amount: 500,
+            details: MintEvent::Referral {
+                referrer_user_principal_id: get_mock_user_alice_principal_id(),
+                referee_user_principal_id: get_mock_user_bob_principal_id(),
+            },
+            timestamp: SystemTime::now(),
+        });
+        canister_data.handle_token_event(TokenEvent::Mint {
+            amount: 500,
+            details: MintEvent::Referral {
+                referrer_user_principal_id: get_mock_user_bob_principal_id(),
+                referee_user_principal_id: get_mock_user_charlie_principal_id(),
+            },
+            timestamp: SystemTime::now(),
+        });
+        (0..2).for_each(|post_id| {
+            canister_data.handle_token_event(TokenEvent::ReferralCommission {
+                amount: 18,
+                details: ReferralCommissionEvent::ShareOfRefereeBetWinnings {
+                    referee_user_principal_id: get_mock_user_charlie_principal_id(),
+                    post_canister_id: get_mock_user_alice_canister_id(),
+                    post_id,
+                    slot_id: 1,
+                    room_id: 1,
+                },
+                timestamp: SystemTime::now(),
+            });
+        });
+
+        assert_eq!(
+            get_referral_earnings_impl(&canister_data, &get_mock_user_bob_principal_id()),
+            Ok(ReferralEarnings {
+                referred_by: Some(get_mock_user_alice_principal_id()),
+                referee_count: 1,
+                signup_rewards_earned: 500,
+                referrer_share_of_winnings_earned: 36,
+            })
+        );
+    }
+
+    #[test]
+    fn test_get_referral_earnings_impl_after_backfilling_the_totals() {
+        let mut canister_data = CanisterData::default();
+        canister_data.profile.principal_id = Some(get_mock_user_bob_principal_id());
+        canister_data.referral_earnings_totals = None;
+
+        // * Recorded before the totals were kept, with the legacy payout event
+        canister_data.handle_token_event(TokenEvent::Mint {
+            amount: 500,
+            details: MintEvent::Referral {
+                referrer_user_principal_id: get_mock_user_bob_principal_id(),
+                referee_user_principal_id: get_mock_user_charlie_principal_id(),
+            },
+            timestamp: SystemTime::now(),
+        });
+        canister_data.handle_token_event(TokenEvent::HotOrNotOutcomePayout {
+            amount: 18,
+            details: HotOrNotOutcomePayoutEvent::ReferrerShareOfRefereeBetWinnings {
+                referee_user_principal_id: get_mock_user_charlie_principal_id(),
+                post_canister_id: get_mock_user_alice_canister_id(),
+                post_id: 0,
+                slot_id: 1,
+                room_id: 1,
+                share_amount: 18,
+            },
+            timestamp: SystemTime::now(),
+        });
+
+        assert_eq!(canister_data.backfill_referral_earnings_totals(), 2);
+        assert_eq!(canister_data.backfill_referral_earnings_totals(), 0);
+        assert_eq!(
+            get_referral_earnings_impl(&canister_data, &get_mock_user_bob_principal_id()),
+            Ok(ReferralEarnings {
+                referred_by: None,
+                referee_count: 0,
+                signup_rewards_earned: 500,
+                referrer_share_of_winnings_earned: 18,
+            })
+        );
+    }
+}
@@ -1,35 +1,40 @@
-use crate::CANISTER_DATA;
+use crate::{
+    util::{call_journal::record_call_in_journal, guards::reject_updates_in_read_only_mode},
+    CANISTER_DATA,
+};
 use candid::Principal;
 use shared_utils::common::{
     types::{
         known_principal::KnownPrincipalType,
         utility_token::token_event::{MintEvent, TokenEvent},
     },
-    utils::system_time,
+    utils::{known_caller::authorize_known_caller, system_time},
 };
 
-#[ic_cdk::update]
+#[ic_cdk::update(guard = "reject_updates_in_read_only_mode")]
 #[candid::candid_method(update)]
 fn get_rewarded_for_referral(referrer: Principal, referree: Principal) {
+    record_call_in_journal("get_rewarded_for_referral");
+
     // * access control
     let request_maker = ic_cdk::caller();
-    let user_index_canister_principal_id = CANISTER_DATA.with(|canister_data_ref_cell| {
-        canister_data_ref_cell
-            .borrow()
-            .known_principal_ids
-            .get(&KnownPrincipalType::CanisterIdUserIndex)
-            .cloned()
-            .unwrap()
+    let is_caller_user_index_canister = CANISTER_DATA.with(|canister_data_ref_cell| {
+        authorize_known_caller(
+            &request_maker,
+            &canister_data_ref_cell.borrow().known_principal_ids,
+            &[KnownPrincipalType::CanisterIdUserIndex],
+        )
+        .is_ok()
     });
 
-    if user_index_canister_principal_id != request_maker {
+    if !is_caller_user_index_canister {
         return;
     }
 
     let current_time = system_time::get_current_system_time_from_ic();
 
     CANISTER_DATA.with(|canister_data_ref_cell| {
-        let my_token_balance = &mut canister_data_ref_cell.borrow_mut().my_token_balance;
+        let canister_data = &mut canister_data_ref_cell.borrow_mut();
 
         let referral_reward_amount =
             TokenEvent::get_token_amount_for_token_event(&TokenEvent::Mint {
@@ -41,7 +46,7 @@ fn get_rewarded_for_referral(referrer: Principal, referree: Principal) {
                 timestamp: current_time,
             });
 
-        my_token_balance.handle_token_event(TokenEvent::Mint {
+        canister_data.handle_token_event(TokenEvent::Mint {
             amount: referral_reward_amount,
             details: MintEvent::Referral {
                 referrer_user_principal_id: referrer,
@@ -1,52 +1,120 @@
-use crate::CANISTER_DATA;
-use shared_utils::common::{
-    types::{
-        known_principal::KnownPrincipalType,
-        utility_token::token_event::{MintEvent, TokenEvent},
+use std::time::SystemTime;
+
+use crate::{
+    data_model::CanisterData,
+    util::{call_journal::record_call_in_journal, guards::reject_updates_in_read_only_mode},
+    CANISTER_DATA,
+};
+use shared_utils::{
+    canister_specific::individual_user_template::types::signup_bonus::SignupBonusVesting,
+    common::{
+        types::{
+            known_principal::KnownPrincipalType,
+            utility_token::token_event::{MintEvent, TokenEvent},
+        },
+        utils::{known_caller::authorize_known_caller, system_time},
     },
-    utils::system_time,
 };
 
-#[ic_cdk::update]
+/// Credits the part of the signup bonus that unlocks right away. The rest vests over the days
+/// the user is active afterwards.
+#[ic_cdk::update(guard = "reject_updates_in_read_only_mode")]
 #[candid::candid_method(update)]
 fn get_rewarded_for_signing_up() {
+    record_call_in_journal("get_rewarded_for_signing_up");
+
     // * access control
     let request_maker = ic_cdk::caller();
-    let user_index_canister_principal_id = CANISTER_DATA.with(|canister_data_ref_cell| {
-        canister_data_ref_cell
-            .borrow()
-            .known_principal_ids
-            .get(&KnownPrincipalType::CanisterIdUserIndex)
-            .cloned()
-            .unwrap()
+    let is_caller_user_index_canister = CANISTER_DATA.with(|canister_data_ref_cell| {
+        authorize_known_caller(
+            &request_maker,
+            &canister_data_ref_cell.borrow().known_principal_ids,
+            &[KnownPrincipalType::CanisterIdUserIndex],
+        )
+        .is_ok()
     });
 
-    if user_index_canister_principal_id != request_maker {
+    if !is_caller_user_index_canister {
         return;
     }
 
     let current_time = system_time::get_current_system_time_from_ic();
 
     CANISTER_DATA.with(|canister_data_ref_cell| {
-        let mut canister_data_ref = canister_data_ref_cell.borrow_mut();
-        let my_principal_id = canister_data_ref.profile.principal_id.unwrap();
-        let my_token_balance = &mut canister_data_ref.my_token_balance;
-
-        let signup_reward_amount =
-            TokenEvent::get_token_amount_for_token_event(&TokenEvent::Mint {
-                amount: 0,
-                details: MintEvent::NewUserSignup {
-                    new_user_principal_id: my_principal_id,
-                },
-                timestamp: current_time,
-            });
-
-        my_token_balance.handle_token_event(TokenEvent::Mint {
-            amount: signup_reward_amount,
-            details: MintEvent::NewUserSignup {
-                new_user_principal_id: my_principal_id,
-            },
-            timestamp: current_time,
-        });
+        get_rewarded_for_signing_up_impl(&mut canister_data_ref_cell.borrow_mut(), &current_time);
     });
 }
+
+fn get_rewarded_for_signing_up_impl(canister_data: &mut CanisterData, current_time: &SystemTime) {
+    // * The bonus is only ever handed out once
+    if canister_data.signup_bonus_vesting.is_some() {
+        return;
+    }
+
+    let Some(my_principal_id) = canister_data.profile.principal_id else {
+        return;
+    };
+
+    let signup_bonus_vesting = SignupBonusVesting::new(
+        &canister_data.configuration.signup_bonus_vesting_config,
+        current_time,
+    );
+
+    canister_data.handle_token_event(TokenEvent::Mint {
+        amount: signup_bonus_vesting.unlocked_at_signup,
+        details: MintEvent::SignupBonusUnlockedAtSignup {
+            new_user_principal_id: my_principal_id,
+            locked_amount: signup_bonus_vesting.get_pending_amount(),
+        },
+        timestamp: *current_time,
+    });
+    canister_data.signup_bonus_vesting = Some(signup_bonus_vesting);
+}
+
+#[cfg(test)]
+mod test {
+    use shared_utils::canister_specific::individual_user_template::types::signup_bonus::SignupBonusVestingConfig;
+    use test_utils::setup::test_constants::get_mock_user_alice_principal_id;
+
+    use super::*;
+
+    #[test]
+    fn test_get_rewarded_for_signing_up_impl() {
+        let mut canister_data = CanisterData::default();
+        canister_data.profile.principal_id = Some(get_mock_user_alice_principal_id());
+        canister_data.configuration.signup_bonus_vesting_config = SignupBonusVestingConfig {
+            total_amount: 1000,
+            unlocked_at_signup_percentage: 30,
+            number_of_vesting_days: 7,
+        };
+
+        (0..2).for_each(|_| {
+            get_rewarded_for_signing_up_impl(&mut canister_data, &SystemTime::UNIX_EPOCH);
+        });
+
+        assert_eq!(canister_data.my_token_balance.utility_token_balance, 300);
+        assert_eq!(
+            canister_data
+                .token_transaction_history
+                .iter()
+                .map(|(_index, token_event)| token_event)
+                .collect::<Vec<_>>(),
+            vec![TokenEvent::Mint {
+                amount: 300,
+                details: MintEvent::SignupBonusUnlockedAtSignup {
+                    new_user_principal_id: get_mock_user_alice_principal_id(),
+                    locked_amount: 700,
+                },
+                timestamp: SystemTime::UNIX_EPOCH,
+            }]
+        );
+        assert_eq!(
+            canister_data
+                .signup_bonus_vesting
+                .as_ref()
+                .unwrap()
+                .number_of_vesting_days,
+            7
+        );
+    }
+}
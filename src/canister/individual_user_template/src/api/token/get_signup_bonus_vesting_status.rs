@@ -0,0 +1,24 @@
+use shared_utils::{
+    canister_specific::individual_user_template::types::signup_bonus::SignupBonusVestingStatus,
+    common::utils::system_time,
+};
+
+use crate::CANISTER_DATA;
+
+/// How much of the signup bonus is still locked and what the next active day unlocks. None
+/// for users who got their whole bonus at once, before it started vesting.
+#[ic_cdk::query]
+#[candid::candid_method(query)]
+fn get_signup_bonus_vesting_status() -> Option<SignupBonusVestingStatus> {
+    let current_time = system_time::get_current_system_time_from_ic();
+
+    CANISTER_DATA.with(|canister_data_ref_cell| {
+        canister_data_ref_cell
+            .borrow()
+            .signup_bonus_vesting
+            .as_ref()
+            .map(|signup_bonus_vesting| {
+                SignupBonusVestingStatus::new(signup_bonus_vesting, &current_time)
+            })
+    })
+}
@@ -0,0 +1,11 @@
+use shared_utils::canister_specific::individual_user_template::types::token_ledger::TokenLedgerMirror;
+
+use crate::CANISTER_DATA;
+
+/// How the utility token balance stands on the token ledger, as of the last sync
+#[ic_cdk::query]
+#[candid::candid_method(query)]
+fn get_token_ledger_mirror() -> TokenLedgerMirror {
+    CANISTER_DATA
+        .with(|canister_data_ref_cell| canister_data_ref_cell.borrow().token_ledger_mirror.clone())
+}
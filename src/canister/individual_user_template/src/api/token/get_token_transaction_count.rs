@@ -0,0 +1,13 @@
+use crate::CANISTER_DATA;
+
+/// Number of token events recorded for this profile
+#[ic_cdk::query]
+#[candid::candid_method(query)]
+fn get_token_transaction_count() -> u64 {
+    CANISTER_DATA.with(|canister_data_ref_cell| {
+        canister_data_ref_cell
+            .borrow()
+            .token_transaction_history
+            .len()
+    })
+}
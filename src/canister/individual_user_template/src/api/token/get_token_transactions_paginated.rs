@@ -0,0 +1,70 @@
+use shared_utils::{common::types::utility_token::token_event::TokenEvent, pagination::Page};
+
+use crate::{data_model::CanisterData, CANISTER_DATA};
+
+/// Token events of this profile oldest first, along with their transaction ids. Unlike other
+/// paginated endpoints, `from` and `next_from_inclusive_index` are transaction ids, so a page
+/// stays put as new events come in.
+#[ic_cdk::query]
+#[candid::candid_method(query)]
+fn get_token_transactions_paginated(from: u64, limit: u64) -> Page<(u64, TokenEvent)> {
+    CANISTER_DATA.with(|canister_data_ref_cell| {
+        get_token_transactions_paginated_impl(&canister_data_ref_cell.borrow(), from, limit)
+    })
+}
+
+fn get_token_transactions_paginated_impl(
+    canister_data: &CanisterData,
+    from: u64,
+    limit: u64,
+) -> Page<(u64, TokenEvent)> {
+    canister_data
+        .token_transaction_history
+        .get_page(from, limit)
+}
+
+#[cfg(test)]
+mod test {
+    use shared_utils::pagination::MAX_ITEMS_PER_PAGE;
+
+    use super::*;
+
+    #[test]
+    fn test_get_token_transactions_paginated_impl() {
+        let mut canister_data = CanisterData::default();
+
+        assert_eq!(
+            get_token_transactions_paginated_impl(&canister_data, 0, 10),
+            Page {
+                items: vec![],
+                total_count: 0,
+                next_from_inclusive_index: None,
+            }
+        );
+
+        (0..MAX_ITEMS_PER_PAGE + 5)
+            .for_each(|_| canister_data.handle_token_event(TokenEvent::Burn));
+
+        let page = get_token_transactions_paginated_impl(&canister_data, 0, 3);
+        assert_eq!(
+            page.items,
+            vec![
+                (1, TokenEvent::Burn),
+                (2, TokenEvent::Burn),
+                (3, TokenEvent::Burn)
+            ]
+        );
+        assert_eq!(page.total_count, MAX_ITEMS_PER_PAGE + 5);
+        assert_eq!(page.next_from_inclusive_index, Some(4));
+
+        // * limits above the page size are clamped
+        let page = get_token_transactions_paginated_impl(&canister_data, 4, u64::MAX);
+        assert_eq!(page.items.len() as u64, MAX_ITEMS_PER_PAGE);
+        assert_eq!(page.next_from_inclusive_index, Some(MAX_ITEMS_PER_PAGE + 4));
+        assert_eq!(
+            get_token_transactions_paginated_impl(&canister_data, MAX_ITEMS_PER_PAGE + 4, 10)
+                .next_from_inclusive_index,
+            None
+        );
+    }
+}
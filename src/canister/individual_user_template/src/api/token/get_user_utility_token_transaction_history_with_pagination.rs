@@ -17,10 +17,9 @@ fn get_user_utility_token_transaction_history_with_pagination(
         CANISTER_DATA.with(|canister_data_ref_cell| {
             canister_data_ref_cell
                 .borrow()
-                .my_token_balance
-                .utility_token_transaction_history
+                .token_transaction_history
                 .len()
-        }) as u64,
+        }),
     )
     .map_err(|e| match e {
         PaginationError::InvalidBoundsPassed => {
@@ -37,13 +36,10 @@ fn get_user_utility_token_transaction_history_with_pagination(
     Ok(CANISTER_DATA.with(|canister_data_ref_cell| {
         canister_data_ref_cell
             .borrow()
-            .my_token_balance
-            .utility_token_transaction_history
-            .iter()
-            .rev()
+            .token_transaction_history
+            .iter_rev()
             .skip(from_inclusive_id as usize)
             .take((to_exclusive_id - from_inclusive_id) as usize)
-            .map(|(time, token_event)| (*time, token_event.clone()))
             .collect()
     }))
 }
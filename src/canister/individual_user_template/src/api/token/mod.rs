@@ -1,4 +1,16 @@
+pub mod create_referral_code;
+pub mod get_all_token_transactions;
+pub mod get_creator_commission_report;
+pub mod get_house_rake_treasury_balance;
+pub mod get_referral_codes;
+pub mod get_referral_earnings;
 pub mod get_rewarded_for_referral;
 pub mod get_rewarded_for_signing_up;
+pub mod get_signup_bonus_vesting_status;
+pub mod get_token_ledger_mirror;
+pub mod get_token_transaction_count;
+pub mod get_token_transactions_paginated;
 pub mod get_user_utility_token_transaction_history_with_pagination;
 pub mod get_utility_token_balance;
+pub mod receive_referral_details;
+pub mod request_tokens_from_faucet;
@@ -0,0 +1,190 @@
+use std::time::SystemTime;
+
+use shared_utils::{
+    canister_specific::individual_user_template::types::referral::ReferralDetails,
+    common::{
+        types::{
+            known_principal::KnownPrincipalType,
+            utility_token::token_event::{MintEvent, TokenEvent},
+        },
+        utils::system_time,
+    },
+};
+
+use crate::{
+    data_model::CanisterData,
+    util::{call_journal::record_call_in_journal, guards::reject_updates_in_read_only_mode},
+    CANISTER_DATA,
+};
+
+/// Sent by the user index to both sides of a referral when the referee signs up
+///
+/// # Access Control
+/// Only the user index canister can send referral details
+#[ic_cdk::update(guard = "reject_updates_in_read_only_mode")]
+#[candid::candid_method(update)]
+fn receive_referral_details(referral_details: ReferralDetails) {
+    record_call_in_journal("receive_referral_details");
+
+    // * access control
+    let request_maker = ic_cdk::caller();
+    let user_index_canister_principal_id = CANISTER_DATA.with(|canister_data_ref_cell| {
+        canister_data_ref_cell
+            .borrow()
+            .known_principal_ids
+            .get(&KnownPrincipalType::CanisterIdUserIndex)
+            .cloned()
+            .unwrap()
+    });
+
+    if user_index_canister_principal_id != request_maker {
+        return;
+    }
+
+    let current_time = system_time::get_current_system_time_from_ic();
+
+    CANISTER_DATA.with(|canister_data_ref_cell| {
+        receive_referral_details_impl(
+            &mut canister_data_ref_cell.borrow_mut(),
+            referral_details,
+            &current_time,
+        );
+    });
+}
+
+fn receive_referral_details_impl(
+    canister_data: &mut CanisterData,
+    referral_details: ReferralDetails,
+    current_time: &SystemTime,
+) {
+    let Some(my_principal_id) = canister_data.profile.principal_id else {
+        return;
+    };
+
+    if referral_details.referee_principal_id == my_principal_id {
+        canister_data.my_referrer_details = Some(referral_details);
+    } else if referral_details.referrer_principal_id == my_principal_id {
+        let is_new_referee = canister_data
+            .my_referee_canister_ids
+            .insert(referral_details.referee_canister_id);
+        if !is_new_referee {
+            return;
+        }
+
+        // * the referral reward is minted separately, here it is only attributed to the code
+        let Some(referral_code_details) = referral_details
+            .referral_code
+            .as_ref()
+            .and_then(|referral_code| canister_data.referral_codes.get_mut(referral_code))
+        else {
+            return;
+        };
+
+        referral_code_details.referee_count += 1;
+        referral_code_details.signup_rewards_earned +=
+            TokenEvent::get_token_amount_for_token_event(&TokenEvent::Mint {
+                amount: 0,
+                details: MintEvent::Referral {
+                    referrer_user_principal_id: referral_details.referrer_principal_id,
+                    referee_user_principal_id: referral_details.referee_principal_id,
+                },
+                timestamp: *current_time,
+            });
+        canister_data.referral_code_by_referee_canister_id.insert(
+            referral_details.referee_canister_id,
+            referral_code_details.code.clone(),
+        );
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use shared_utils::canister_specific::individual_user_template::types::referral::ReferralCodeDetails;
+    use test_utils::setup::test_constants::{
+        get_mock_user_alice_canister_id, get_mock_user_alice_principal_id,
+        get_mock_user_bob_canister_id, get_mock_user_bob_principal_id,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_receive_referral_details_impl() {
+        let referral_details = ReferralDetails {
+            referrer_principal_id: get_mock_user_alice_principal_id(),
+            referrer_canister_id: get_mock_user_alice_canister_id(),
+            referee_principal_id: get_mock_user_bob_principal_id(),
+            referee_canister_id: get_mock_user_bob_canister_id(),
+            referral_code: None,
+        };
+
+        let mut referrers_canister_data = CanisterData::default();
+        referrers_canister_data.profile.principal_id = Some(get_mock_user_alice_principal_id());
+        receive_referral_details_impl(
+            &mut referrers_canister_data,
+            referral_details.clone(),
+            &SystemTime::now(),
+        );
+
+        assert!(referrers_canister_data.my_referrer_details.is_none());
+        assert!(referrers_canister_data
+            .my_referee_canister_ids
+            .contains(&get_mock_user_bob_canister_id()));
+
+        let mut referees_canister_data = CanisterData::default();
+        referees_canister_data.profile.principal_id = Some(get_mock_user_bob_principal_id());
+        receive_referral_details_impl(
+            &mut referees_canister_data,
+            referral_details.clone(),
+            &SystemTime::now(),
+        );
+
+        assert_eq!(
+            referees_canister_data.my_referrer_details,
+            Some(referral_details)
+        );
+        assert!(referees_canister_data.my_referee_canister_ids.is_empty());
+    }
+
+    #[test]
+    fn test_receive_referral_details_impl_attributes_referees_to_referral_codes() {
+        let mut canister_data = CanisterData::default();
+        canister_data.profile.principal_id = Some(get_mock_user_alice_principal_id());
+        canister_data.referral_codes.insert(
+            "AB3K9Q2Z".to_string(),
+            ReferralCodeDetails {
+                code: "AB3K9Q2Z".to_string(),
+                name: "summer".to_string(),
+                created_at: SystemTime::now(),
+                referee_count: 0,
+                signup_rewards_earned: 0,
+                referrer_share_of_winnings_earned: 0,
+            },
+        );
+
+        let referral_details = ReferralDetails {
+            referrer_principal_id: get_mock_user_alice_principal_id(),
+            referrer_canister_id: get_mock_user_alice_canister_id(),
+            referee_principal_id: get_mock_user_bob_principal_id(),
+            referee_canister_id: get_mock_user_bob_canister_id(),
+            referral_code: Some("AB3K9Q2Z".to_string()),
+        };
+
+        receive_referral_details_impl(
+            &mut canister_data,
+            referral_details.clone(),
+            &SystemTime::now(),
+        );
+        // * a redelivered referral is not counted twice
+        receive_referral_details_impl(&mut canister_data, referral_details, &SystemTime::now());
+
+        let referral_code_details = canister_data.referral_codes.get("AB3K9Q2Z").unwrap();
+        assert_eq!(referral_code_details.referee_count, 1);
+        assert_eq!(referral_code_details.signup_rewards_earned, 500);
+        assert_eq!(
+            canister_data
+                .referral_code_by_referee_canister_id
+                .get(&get_mock_user_bob_canister_id()),
+            Some(&"AB3K9Q2Z".to_string())
+        );
+    }
+}
@@ -0,0 +1,190 @@
+use std::time::SystemTime;
+
+use candid::Principal;
+use shared_utils::{
+    canister_specific::individual_user_template::types::error::RequestTokensFromFaucetError,
+    common::{
+        types::utility_token::token_event::{MintEvent, TokenEvent},
+        utils::system_time,
+    },
+    constant::{is_token_faucet_enabled, MAX_TOKEN_FAUCET_AMOUNT, TOKEN_FAUCET_COOLDOWN},
+};
+
+use crate::{
+    data_model::CanisterData,
+    util::{call_journal::record_call_in_journal, guards::reject_updates_in_read_only_mode},
+    CANISTER_DATA,
+};
+
+/// Mints up to 10,000 test tokens, at most once an hour. Only available on test and staging
+/// builds. Returns the new token balance.
+///
+/// # Access Control
+/// Only the user whose profile details are stored in this canister can request tokens
+#[ic_cdk::update(guard = "reject_updates_in_read_only_mode")]
+#[candid::candid_method(update)]
+fn request_tokens_from_faucet(amount: u64) -> Result<u64, RequestTokensFromFaucetError> {
+    record_call_in_journal("request_tokens_from_faucet");
+
+    let api_caller = ic_cdk::caller();
+    let current_time = system_time::get_current_system_time_from_ic();
+
+    CANISTER_DATA.with(|canister_data_ref_cell| {
+        request_tokens_from_faucet_impl(
+            &mut canister_data_ref_cell.borrow_mut(),
+            is_token_faucet_enabled(),
+            &api_caller,
+            amount,
+            &current_time,
+        )
+    })
+}
+
+fn request_tokens_from_faucet_impl(
+    canister_data: &mut CanisterData,
+    is_token_faucet_enabled: bool,
+    api_caller: &Principal,
+    amount: u64,
+    current_time: &SystemTime,
+) -> Result<u64, RequestTokensFromFaucetError> {
+    if !is_token_faucet_enabled {
+        return Err(RequestTokensFromFaucetError::FaucetDisabled);
+    }
+
+    if canister_data.profile.principal_id != Some(*api_caller) {
+        return Err(RequestTokensFromFaucetError::Unauthorized);
+    }
+
+    if amount == 0 || amount > MAX_TOKEN_FAUCET_AMOUNT {
+        return Err(RequestTokensFromFaucetError::InvalidAmount);
+    }
+
+    if let Some(last_token_faucet_request_at) = canister_data.last_token_faucet_request_at {
+        let next_request_allowed_at = last_token_faucet_request_at + TOKEN_FAUCET_COOLDOWN;
+        if *current_time < next_request_allowed_at {
+            return Err(RequestTokensFromFaucetError::RequestedTooSoon {
+                next_request_allowed_at,
+            });
+        }
+    }
+
+    canister_data.last_token_faucet_request_at = Some(*current_time);
+    canister_data.handle_token_event(TokenEvent::Mint {
+        amount,
+        details: MintEvent::TokenFaucet,
+        timestamp: *current_time,
+    });
+
+    Ok(canister_data.my_token_balance.get_utility_token_balance())
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use test_utils::setup::test_constants::{
+        get_mock_user_alice_principal_id, get_mock_user_bob_principal_id,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_request_tokens_from_faucet_impl() {
+        let mut canister_data = CanisterData::default();
+        canister_data.profile.principal_id = Some(get_mock_user_alice_principal_id());
+        canister_data.my_token_balance.utility_token_balance = 500;
+        let current_time = SystemTime::now();
+
+        assert_eq!(
+            request_tokens_from_faucet_impl(
+                &mut canister_data,
+                false,
+                &get_mock_user_alice_principal_id(),
+                1000,
+                &current_time,
+            ),
+            Err(RequestTokensFromFaucetError::FaucetDisabled)
+        );
+        assert_eq!(
+            request_tokens_from_faucet_impl(
+                &mut canister_data,
+                true,
+                &get_mock_user_bob_principal_id(),
+                1000,
+                &current_time,
+            ),
+            Err(RequestTokensFromFaucetError::Unauthorized)
+        );
+        assert_eq!(
+            request_tokens_from_faucet_impl(
+                &mut canister_data,
+                true,
+                &get_mock_user_alice_principal_id(),
+                MAX_TOKEN_FAUCET_AMOUNT + 1,
+                &current_time,
+            ),
+            Err(RequestTokensFromFaucetError::InvalidAmount)
+        );
+        assert_eq!(
+            request_tokens_from_faucet_impl(
+                &mut canister_data,
+                true,
+                &get_mock_user_alice_principal_id(),
+                0,
+                &current_time,
+            ),
+            Err(RequestTokensFromFaucetError::InvalidAmount)
+        );
+        assert!(canister_data.token_transaction_history.is_empty());
+
+        assert_eq!(
+            request_tokens_from_faucet_impl(
+                &mut canister_data,
+                true,
+                &get_mock_user_alice_principal_id(),
+                1000,
+                &current_time,
+            ),
+            Ok(1500)
+        );
+        assert_eq!(
+            canister_data
+                .token_transaction_history
+                .iter_rev()
+                .next()
+                .map(|(_index, token_event)| token_event),
+            Some(TokenEvent::Mint {
+                amount: 1000,
+                details: MintEvent::TokenFaucet,
+                timestamp: current_time,
+            })
+        );
+        // * test tokens aren't counted as earnings
+        assert_eq!(canister_data.my_token_balance.lifetime_earnings, 0);
+
+        let almost_an_hour_later = current_time + TOKEN_FAUCET_COOLDOWN - Duration::from_secs(1);
+        assert_eq!(
+            request_tokens_from_faucet_impl(
+                &mut canister_data,
+                true,
+                &get_mock_user_alice_principal_id(),
+                1000,
+                &almost_an_hour_later,
+            ),
+            Err(RequestTokensFromFaucetError::RequestedTooSoon {
+                next_request_allowed_at: current_time + TOKEN_FAUCET_COOLDOWN,
+            })
+        );
+
+        assert_eq!(
+            request_tokens_from_faucet_impl(
+                &mut canister_data,
+                true,
+                &get_mock_user_alice_principal_id(),
+                MAX_TOKEN_FAUCET_AMOUNT,
+                &(current_time + TOKEN_FAUCET_COOLDOWN),
+            ),
+            Ok(1500 + MAX_TOKEN_FAUCET_AMOUNT)
+        );
+    }
+}
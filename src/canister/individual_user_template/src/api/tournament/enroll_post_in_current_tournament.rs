@@ -0,0 +1,162 @@
+use std::time::SystemTime;
+
+use ic_cdk::api::call;
+use shared_utils::{
+    canister_specific::{
+        individual_user_template::types::{
+            error::EnrollPostInTournamentError, tournament::TournamentEnrollment,
+        },
+        user_index::types::tournament::TournamentDetails,
+    },
+    common::types::{app_primitive_type::PostId, known_principal::KnownPrincipalType},
+    types::canister_specific::user_index::error_types::TournamentError,
+};
+
+use crate::{
+    data_model::CanisterData,
+    util::{call_journal::record_call_in_journal, guards::reject_updates_in_read_only_mode},
+    CANISTER_DATA,
+};
+
+/// # Access Control
+/// Only the user whose profile details are stored in this canister can enroll their posts
+#[ic_cdk::update(guard = "reject_updates_in_read_only_mode")]
+#[candid::candid_method(update)]
+async fn enroll_post_in_current_tournament(
+    post_id: PostId,
+) -> Result<TournamentEnrollment, EnrollPostInTournamentError> {
+    record_call_in_journal("enroll_post_in_current_tournament");
+
+    // * access control
+    let current_caller = ic_cdk::caller();
+    let my_principal_id = CANISTER_DATA
+        .with(|canister_data_ref_cell| canister_data_ref_cell.borrow().profile.principal_id);
+
+    if Some(current_caller) != my_principal_id {
+        return Err(EnrollPostInTournamentError::Unauthorized);
+    }
+
+    let post_created_at = CANISTER_DATA.with(|canister_data_ref_cell| {
+        get_post_created_at_if_eligible(&canister_data_ref_cell.borrow(), post_id)
+    })?;
+
+    let user_index_canister_principal_id = CANISTER_DATA.with(|canister_data_ref_cell| {
+        canister_data_ref_cell
+            .borrow()
+            .known_principal_ids
+            .get(&KnownPrincipalType::CanisterIdUserIndex)
+            .cloned()
+            .unwrap()
+    });
+
+    // * cross canister call
+    let (response,): (Result<TournamentDetails, TournamentError>,) = call::call(
+        user_index_canister_principal_id,
+        "receive_post_enrollment_for_current_tournament",
+        (post_id, post_created_at),
+    )
+    .await
+    .map_err(|_| EnrollPostInTournamentError::UserIndexCrossCanisterCallFailed)?;
+
+    let tournament_details = response.map_err(EnrollPostInTournamentError::TournamentError)?;
+
+    let tournament_enrollment = TournamentEnrollment {
+        tournament_id: tournament_details.tournament_id,
+        closes_at: tournament_details.closes_at,
+        points: 0,
+    };
+
+    CANISTER_DATA.with(|canister_data_ref_cell| {
+        canister_data_ref_cell
+            .borrow_mut()
+            .tournament_enrollments
+            .insert(post_id, tournament_enrollment.clone());
+    });
+
+    Ok(tournament_enrollment)
+}
+
+/// Only posts that are part of hot or not can accumulate bet volume
+fn get_post_created_at_if_eligible(
+    canister_data: &CanisterData,
+    post_id: PostId,
+) -> Result<SystemTime, EnrollPostInTournamentError> {
+    let post = canister_data
+        .all_created_posts
+        .get(&post_id)
+        .ok_or(EnrollPostInTournamentError::PostNotFound)?;
+
+    if post.hot_or_not_details.is_none() {
+        return Err(EnrollPostInTournamentError::PostNotEligible);
+    }
+
+    if canister_data.tournament_enrollments.contains_key(&post_id) {
+        return Err(EnrollPostInTournamentError::PostAlreadyEnrolled);
+    }
+
+    Ok(post.created_at)
+}
+
+#[cfg(test)]
+mod test {
+    use shared_utils::canister_specific::individual_user_template::types::post::{
+        Post, PostDetailsFromFrontend,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_get_post_created_at_if_eligible() {
+        let mut canister_data = CanisterData::default();
+        let created_at = SystemTime::now();
+
+        [true, false].iter().enumerate().for_each(
+            |(post_id, creator_consent_for_inclusion_in_hot_or_not)| {
+                canister_data.all_created_posts.insert(
+                    post_id as u64,
+                    Post::new(
+                        post_id as u64,
+                        &PostDetailsFromFrontend {
+                            description: "Doggos and puppers".into(),
+                            hashtags: vec!["doggo".into(), "pupper".into()],
+                            video_uid: "abcd#1234".into(),
+                            creator_consent_for_inclusion_in_hot_or_not:
+                                *creator_consent_for_inclusion_in_hot_or_not,
+                            collaborator_shares: None,
+                            title: None,
+                            category: None,
+                        },
+                        &created_at,
+                    ),
+                );
+            },
+        );
+
+        assert_eq!(
+            get_post_created_at_if_eligible(&canister_data, 0),
+            Ok(created_at)
+        );
+        assert_eq!(
+            get_post_created_at_if_eligible(&canister_data, 1),
+            Err(EnrollPostInTournamentError::PostNotEligible)
+        );
+        assert_eq!(
+            get_post_created_at_if_eligible(&canister_data, 2),
+            Err(EnrollPostInTournamentError::PostNotFound)
+        );
+
+        canister_data.tournament_enrollments.insert(
+            0,
+            TournamentEnrollment {
+                tournament_id: 1,
+                closes_at: created_at,
+                points: 0,
+            },
+        );
+
+        assert_eq!(
+            get_post_created_at_if_eligible(&canister_data, 0),
+            Err(EnrollPostInTournamentError::PostAlreadyEnrolled)
+        );
+    }
+}
@@ -0,0 +1,83 @@
+use candid::Principal;
+use shared_utils::{
+    canister_specific::user_index::types::tournament::TournamentId,
+    common::types::known_principal::KnownPrincipalType,
+};
+
+use crate::{data_model::CanisterData, CANISTER_DATA};
+
+/// # Access Control
+/// Only the user index canister can collect tournament points
+#[ic_cdk::query]
+#[candid::candid_method(query)]
+fn get_tournament_points_for_enrolled_posts(tournament_id: TournamentId) -> u64 {
+    let request_maker = ic_cdk::caller();
+
+    CANISTER_DATA.with(|canister_data_ref_cell| {
+        let canister_data = canister_data_ref_cell.borrow();
+
+        if canister_data
+            .known_principal_ids
+            .get(&KnownPrincipalType::CanisterIdUserIndex)
+            .cloned()
+            .unwrap_or(Principal::anonymous())
+            != request_maker
+        {
+            return 0;
+        }
+
+        get_tournament_points_for_enrolled_posts_impl(&canister_data, tournament_id)
+    })
+}
+
+fn get_tournament_points_for_enrolled_posts_impl(
+    canister_data: &CanisterData,
+    tournament_id: TournamentId,
+) -> u64 {
+    canister_data
+        .tournament_enrollments
+        .values()
+        .filter(|enrollment| enrollment.tournament_id == tournament_id)
+        .map(|enrollment| enrollment.points)
+        .sum()
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::SystemTime;
+
+    use shared_utils::canister_specific::individual_user_template::types::tournament::TournamentEnrollment;
+
+    use super::*;
+
+    #[test]
+    fn test_get_tournament_points_for_enrolled_posts_impl() {
+        let mut canister_data = CanisterData::default();
+
+        [(0, 1, 100), (1, 1, 250), (2, 2, 400)].iter().for_each(
+            |(post_id, tournament_id, points)| {
+                canister_data.tournament_enrollments.insert(
+                    *post_id,
+                    TournamentEnrollment {
+                        tournament_id: *tournament_id,
+                        closes_at: SystemTime::now(),
+                        points: *points,
+                    },
+                );
+            },
+        );
+
+        assert_eq!(
+            get_tournament_points_for_enrolled_posts_impl(&canister_data, 1),
+            350
+        );
+        assert_eq!(
+            get_tournament_points_for_enrolled_posts_impl(&canister_data, 2),
+            400
+        );
+        assert_eq!(
+            get_tournament_points_for_enrolled_posts_impl(&canister_data, 3),
+            0
+        );
+    }
+}
@@ -0,0 +1,3 @@
+pub mod enroll_post_in_current_tournament;
+pub mod get_tournament_points_for_enrolled_posts;
+pub mod receive_tournament_prize;
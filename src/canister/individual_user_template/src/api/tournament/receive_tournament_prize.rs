@@ -0,0 +1,96 @@
+use std::time::SystemTime;
+
+use shared_utils::{
+    canister_specific::user_index::types::tournament::TournamentId,
+    common::{
+        types::{
+            known_principal::KnownPrincipalType,
+            utility_token::token_event::{MintEvent, TokenEvent},
+        },
+        utils::{known_caller::authorize_known_caller, system_time},
+    },
+};
+
+use crate::{
+    data_model::CanisterData,
+    util::{call_journal::record_call_in_journal, guards::reject_updates_in_read_only_mode},
+    CANISTER_DATA,
+};
+
+/// # Access Control
+/// Only the user index canister can award tournament prizes
+#[ic_cdk::update(guard = "reject_updates_in_read_only_mode")]
+#[candid::candid_method(update)]
+fn receive_tournament_prize(tournament_id: TournamentId, rank: u32, prize_amount: u64) {
+    record_call_in_journal("receive_tournament_prize");
+
+    // * access control
+    let request_maker = ic_cdk::caller();
+    let is_caller_user_index_canister = CANISTER_DATA.with(|canister_data_ref_cell| {
+        authorize_known_caller(
+            &request_maker,
+            &canister_data_ref_cell.borrow().known_principal_ids,
+            &[KnownPrincipalType::CanisterIdUserIndex],
+        )
+        .is_ok()
+    });
+
+    if !is_caller_user_index_canister {
+        return;
+    }
+
+    let current_time = system_time::get_current_system_time_from_ic();
+
+    CANISTER_DATA.with(|canister_data_ref_cell| {
+        receive_tournament_prize_impl(
+            &mut canister_data_ref_cell.borrow_mut(),
+            tournament_id,
+            rank,
+            prize_amount,
+            &current_time,
+        );
+    });
+}
+
+fn receive_tournament_prize_impl(
+    canister_data: &mut CanisterData,
+    tournament_id: TournamentId,
+    rank: u32,
+    prize_amount: u64,
+    current_time: &SystemTime,
+) {
+    // * The user index retries a delivery whose reply got lost
+    if !canister_data
+        .received_tournament_prizes
+        .insert((tournament_id, rank))
+    {
+        return;
+    }
+
+    canister_data.handle_token_event(TokenEvent::Mint {
+        amount: prize_amount,
+        details: MintEvent::TournamentPrize {
+            tournament_id,
+            rank,
+        },
+        timestamp: *current_time,
+    });
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_receive_tournament_prize_impl() {
+        let mut canister_data = CanisterData::default();
+
+        receive_tournament_prize_impl(&mut canister_data, 1, 1, 500, &SystemTime::now());
+        receive_tournament_prize_impl(&mut canister_data, 1, 1, 500, &SystemTime::now());
+        assert_eq!(canister_data.my_token_balance.utility_token_balance, 500);
+        assert_eq!(canister_data.token_transaction_history.len(), 1);
+
+        receive_tournament_prize_impl(&mut canister_data, 2, 1, 300, &SystemTime::now());
+        assert_eq!(canister_data.my_token_balance.utility_token_balance, 800);
+    }
+}
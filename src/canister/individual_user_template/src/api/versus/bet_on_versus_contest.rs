@@ -0,0 +1,213 @@
+use std::time::SystemTime;
+
+use candid::Principal;
+use shared_utils::{
+    canister_specific::individual_user_template::types::{
+        error::VersusContestError,
+        versus::{VersusBetPlaced, VersusContestId},
+    },
+    common::{
+        types::{
+            global_post_ref::GlobalPostRef,
+            utility_token::token_event::{StakeEvent, TokenEvent},
+        },
+        utils::system_time,
+    },
+};
+
+use crate::{
+    data_model::CanisterData,
+    util::{call_journal::record_call_in_journal, guards::reject_updates_in_read_only_mode},
+    CANISTER_DATA,
+};
+
+/// Bets `bet_amount` on `post` outperforming the other post in the contest. The stake goes
+/// into the pot held by the canister of the post bet on.
+///
+/// # Access Control
+/// Only the user whose profile details are stored in this canister can bet
+#[ic_cdk::update(guard = "reject_updates_in_read_only_mode")]
+#[candid::candid_method(update)]
+async fn bet_on_versus_contest(
+    contest_id: VersusContestId,
+    post: GlobalPostRef,
+    bet_amount: u64,
+) -> Result<(), VersusContestError> {
+    record_call_in_journal("bet_on_versus_contest");
+
+    let api_caller = ic_cdk::caller();
+
+    // * The amount is held until the post's canister accepts the bet, so it can't be bet
+    // * twice in the meantime
+    let bet_maker_principal_id = CANISTER_DATA.with(|canister_data_ref_cell| {
+        escrow_versus_bet_impl(
+            &mut canister_data_ref_cell.borrow_mut(),
+            &api_caller,
+            &contest_id,
+            bet_amount,
+        )
+    })?;
+
+    // * cross canister call
+    let response = ic_cdk::call::<_, (Result<(), VersusContestError>,)>(
+        post.canister_id,
+        "receive_versus_bet_from_bet_makers_canister",
+        (contest_id, post.post_id, bet_maker_principal_id, bet_amount),
+    )
+    .await
+    .map_err(|_| VersusContestError::OtherCanisterCallFailed)
+    .and_then(|(response,)| response);
+
+    CANISTER_DATA.with(|canister_data_ref_cell| {
+        let canister_data = &mut canister_data_ref_cell.borrow_mut();
+        canister_data.escrowed_bet_amount =
+            canister_data.escrowed_bet_amount.saturating_sub(bet_amount);
+
+        if response.is_ok() {
+            record_versus_bet_placed_impl(
+                canister_data,
+                contest_id,
+                post,
+                bet_amount,
+                &system_time::get_current_system_time_from_ic(),
+            );
+        }
+    });
+
+    response
+}
+
+/// Returns the profile owner's principal, which the bet is placed as
+fn escrow_versus_bet_impl(
+    canister_data: &mut CanisterData,
+    api_caller: &Principal,
+    contest_id: &VersusContestId,
+    bet_amount: u64,
+) -> Result<Principal, VersusContestError> {
+    let bet_maker_principal_id = canister_data
+        .profile
+        .principal_id
+        .filter(|principal_id| principal_id == api_caller)
+        .ok_or(VersusContestError::Unauthorized)?;
+
+    if bet_amount == 0 {
+        return Err(VersusContestError::InvalidBetAmount);
+    }
+
+    if canister_data.versus_bets_placed.contains_key(contest_id) {
+        return Err(VersusContestError::AlreadyBetOnContest);
+    }
+
+    let spendable_balance = canister_data
+        .my_token_balance
+        .get_utility_token_balance()
+        .saturating_sub(canister_data.escrowed_bet_amount);
+    if spendable_balance < bet_amount {
+        return Err(VersusContestError::InsufficientBalance);
+    }
+
+    canister_data.escrowed_bet_amount += bet_amount;
+
+    Ok(bet_maker_principal_id)
+}
+
+fn record_versus_bet_placed_impl(
+    canister_data: &mut CanisterData,
+    contest_id: VersusContestId,
+    post: GlobalPostRef,
+    bet_amount: u64,
+    current_time: &SystemTime,
+) {
+    canister_data.handle_token_event(TokenEvent::Stake {
+        amount: bet_amount,
+        details: StakeEvent::BetOnVersusContest {
+            contest_id,
+            post_canister_id: post.canister_id,
+            post_id: post.post_id,
+            bet_amount,
+        },
+        timestamp: *current_time,
+    });
+
+    canister_data.versus_bets_placed.insert(
+        contest_id,
+        VersusBetPlaced {
+            post,
+            amount: bet_amount,
+            placed_at: *current_time,
+            payout: None,
+        },
+    );
+}
+
+#[cfg(test)]
+mod test {
+    use test_utils::setup::test_constants::{
+        get_mock_user_alice_canister_id, get_mock_user_bob_canister_id,
+        get_mock_user_charlie_principal_id,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_escrow_versus_bet_impl_and_record_versus_bet_placed_impl() {
+        let mut canister_data = CanisterData::default();
+        canister_data.profile.principal_id = Some(get_mock_user_charlie_principal_id());
+        canister_data.my_token_balance.utility_token_balance = 1000;
+        let contest_id = VersusContestId {
+            proposer_canister_id: get_mock_user_alice_canister_id(),
+            index: 0,
+        };
+
+        assert_eq!(
+            escrow_versus_bet_impl(
+                &mut canister_data,
+                &get_mock_user_charlie_principal_id(),
+                &contest_id,
+                1001
+            ),
+            Err(VersusContestError::InsufficientBalance)
+        );
+        assert_eq!(
+            escrow_versus_bet_impl(
+                &mut canister_data,
+                &get_mock_user_charlie_principal_id(),
+                &contest_id,
+                600
+            ),
+            Ok(get_mock_user_charlie_principal_id())
+        );
+        // * escrowed tokens can't be bet again
+        assert_eq!(
+            escrow_versus_bet_impl(
+                &mut canister_data,
+                &get_mock_user_charlie_principal_id(),
+                &VersusContestId {
+                    index: 1,
+                    ..contest_id
+                },
+                600
+            ),
+            Err(VersusContestError::InsufficientBalance)
+        );
+
+        canister_data.escrowed_bet_amount = 0;
+        record_versus_bet_placed_impl(
+            &mut canister_data,
+            contest_id,
+            GlobalPostRef::new(get_mock_user_bob_canister_id(), 3),
+            600,
+            &SystemTime::UNIX_EPOCH,
+        );
+        assert_eq!(canister_data.my_token_balance.utility_token_balance, 400);
+        assert_eq!(
+            escrow_versus_bet_impl(
+                &mut canister_data,
+                &get_mock_user_charlie_principal_id(),
+                &contest_id,
+                100
+            ),
+            Err(VersusContestError::AlreadyBetOnContest)
+        );
+    }
+}
@@ -0,0 +1,18 @@
+use shared_utils::canister_specific::individual_user_template::types::versus::{
+    VersusContestDetails, VersusContestId,
+};
+
+use crate::CANISTER_DATA;
+
+/// A contest one of this profile's posts takes part in, with the bets on this profile's side
+#[ic_cdk::query]
+#[candid::candid_method(query)]
+fn get_versus_contest(contest_id: VersusContestId) -> Option<VersusContestDetails> {
+    CANISTER_DATA.with(|canister_data_ref_cell| {
+        canister_data_ref_cell
+            .borrow()
+            .versus_contests
+            .get(&contest_id)
+            .map(VersusContestDetails::from)
+    })
+}
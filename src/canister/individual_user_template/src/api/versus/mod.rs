@@ -0,0 +1,11 @@
+pub mod bet_on_versus_contest;
+pub mod get_versus_contest;
+pub mod propose_versus_contest;
+pub mod receive_versus_bet_from_bet_makers_canister;
+pub mod receive_versus_contest_payout;
+pub mod receive_versus_contest_proposal;
+pub mod receive_versus_contest_response;
+pub mod receive_versus_contest_settlement;
+pub mod report_versus_contest_side;
+pub mod respond_to_versus_contest_proposal;
+pub mod settle_versus_contest;
@@ -0,0 +1,236 @@
+use std::time::Duration;
+
+use candid::Principal;
+use shared_utils::{
+    canister_specific::individual_user_template::types::{
+        error::VersusContestError,
+        versus::{
+            VersusContest, VersusContestId, VersusContestProposal, VersusSide,
+            MAX_VERSUS_CONTEST_WINDOW, MIN_VERSUS_CONTEST_WINDOW,
+        },
+    },
+    common::types::{app_primitive_type::PostId, global_post_ref::GlobalPostRef},
+};
+
+use crate::{
+    data_model::CanisterData,
+    util::{call_journal::record_call_in_journal, guards::reject_updates_in_read_only_mode},
+    CANISTER_DATA,
+};
+
+/// Proposes pairing one of this profile's posts against `opponent_post` for a window of
+/// `window_in_seconds`. The contest only opens for bets once the opponent's creator accepts
+/// it with `respond_to_versus_contest_proposal`.
+///
+/// # Access Control
+/// Only the user whose profile details are stored in this canister can propose contests
+#[ic_cdk::update(guard = "reject_updates_in_read_only_mode")]
+#[candid::candid_method(update)]
+async fn propose_versus_contest(
+    my_post_id: PostId,
+    opponent_post: GlobalPostRef,
+    window_in_seconds: u64,
+) -> Result<VersusContestId, VersusContestError> {
+    record_call_in_journal("propose_versus_contest");
+
+    let api_caller = ic_cdk::caller();
+
+    let proposal = CANISTER_DATA.with(|canister_data_ref_cell| {
+        propose_versus_contest_impl(
+            &mut canister_data_ref_cell.borrow_mut(),
+            &api_caller,
+            &ic_cdk::id(),
+            my_post_id,
+            opponent_post,
+            window_in_seconds,
+        )
+    })?;
+
+    // * cross canister call
+    let response = ic_cdk::call::<_, (Result<(), VersusContestError>,)>(
+        opponent_post.canister_id,
+        "receive_versus_contest_proposal",
+        (proposal.clone(),),
+    )
+    .await
+    .map_err(|_| VersusContestError::OtherCanisterCallFailed)
+    .and_then(|(response,)| response);
+
+    if let Err(error) = response {
+        CANISTER_DATA.with(|canister_data_ref_cell| {
+            canister_data_ref_cell
+                .borrow_mut()
+                .versus_contests
+                .remove(&proposal.contest_id);
+        });
+
+        return Err(error);
+    }
+
+    Ok(proposal.contest_id)
+}
+
+fn propose_versus_contest_impl(
+    canister_data: &mut CanisterData,
+    api_caller: &Principal,
+    my_canister_id: &Principal,
+    my_post_id: PostId,
+    opponent_post: GlobalPostRef,
+    window_in_seconds: u64,
+) -> Result<VersusContestProposal, VersusContestError> {
+    if canister_data.profile.principal_id != Some(*api_caller) {
+        return Err(VersusContestError::Unauthorized);
+    }
+
+    let window = Duration::from_secs(window_in_seconds);
+    if !(MIN_VERSUS_CONTEST_WINDOW..=MAX_VERSUS_CONTEST_WINDOW).contains(&window) {
+        return Err(VersusContestError::InvalidWindow);
+    }
+
+    // * Both posts have to be on different profiles
+    if opponent_post.canister_id == *my_canister_id {
+        return Err(VersusContestError::InvalidOpponentPost);
+    }
+
+    validate_post_can_enter_versus_contest(canister_data, my_post_id)?;
+
+    let contest_id = VersusContestId {
+        proposer_canister_id: *my_canister_id,
+        index: canister_data.next_versus_contest_index,
+    };
+    canister_data.next_versus_contest_index += 1;
+
+    let proposal = VersusContestProposal {
+        contest_id,
+        proposer_post: GlobalPostRef::new(*my_canister_id, my_post_id),
+        opponent_post,
+        window_in_seconds,
+    };
+    canister_data.versus_contests.insert(
+        contest_id,
+        VersusContest::new(proposal.clone(), VersusSide::Proposer),
+    );
+
+    Ok(proposal)
+}
+
+/// Only posts that are part of hot or not can be paired
+pub fn validate_post_can_enter_versus_contest(
+    canister_data: &CanisterData,
+    post_id: PostId,
+) -> Result<(), VersusContestError> {
+    let post = canister_data
+        .all_created_posts
+        .get(&post_id)
+        .ok_or(VersusContestError::PostNotFound)?;
+
+    if post.hot_or_not_details.is_none() {
+        return Err(VersusContestError::PostNotEligible);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::SystemTime;
+
+    use shared_utils::canister_specific::individual_user_template::types::{
+        post::{Post, PostDetailsFromFrontend},
+        versus::VersusContestStatus,
+    };
+    use test_utils::setup::test_constants::{
+        get_mock_user_alice_canister_id, get_mock_user_alice_principal_id,
+        get_mock_user_bob_canister_id, get_mock_user_bob_principal_id,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_propose_versus_contest_impl() {
+        let mut canister_data = CanisterData::default();
+        canister_data.profile.principal_id = Some(get_mock_user_alice_principal_id());
+        canister_data.all_created_posts.insert(
+            0,
+            Post::new(
+                0,
+                &PostDetailsFromFrontend {
+                    description: "Doggos and puppers".into(),
+                    hashtags: vec!["doggo".into(), "pupper".into()],
+                    video_uid: "abcd#1234".into(),
+                    creator_consent_for_inclusion_in_hot_or_not: true,
+                    collaborator_shares: None,
+                    title: None,
+                    category: None,
+                },
+                &SystemTime::now(),
+            ),
+        );
+        let opponent_post = GlobalPostRef::new(get_mock_user_bob_canister_id(), 3);
+        let a_day = 24 * 60 * 60;
+
+        assert_eq!(
+            propose_versus_contest_impl(
+                &mut canister_data,
+                &get_mock_user_bob_principal_id(),
+                &get_mock_user_alice_canister_id(),
+                0,
+                opponent_post,
+                a_day,
+            ),
+            Err(VersusContestError::Unauthorized)
+        );
+        assert_eq!(
+            propose_versus_contest_impl(
+                &mut canister_data,
+                &get_mock_user_alice_principal_id(),
+                &get_mock_user_alice_canister_id(),
+                0,
+                opponent_post,
+                60,
+            ),
+            Err(VersusContestError::InvalidWindow)
+        );
+        assert_eq!(
+            propose_versus_contest_impl(
+                &mut canister_data,
+                &get_mock_user_alice_principal_id(),
+                &get_mock_user_alice_canister_id(),
+                0,
+                GlobalPostRef::new(get_mock_user_alice_canister_id(), 1),
+                a_day,
+            ),
+            Err(VersusContestError::InvalidOpponentPost)
+        );
+        assert_eq!(
+            propose_versus_contest_impl(
+                &mut canister_data,
+                &get_mock_user_alice_principal_id(),
+                &get_mock_user_alice_canister_id(),
+                1,
+                opponent_post,
+                a_day,
+            ),
+            Err(VersusContestError::PostNotFound)
+        );
+
+        let proposal = propose_versus_contest_impl(
+            &mut canister_data,
+            &get_mock_user_alice_principal_id(),
+            &get_mock_user_alice_canister_id(),
+            0,
+            opponent_post,
+            a_day,
+        )
+        .unwrap();
+        assert_eq!(proposal.contest_id.index, 0);
+        assert_eq!(canister_data.next_versus_contest_index, 1);
+
+        let contest = canister_data
+            .versus_contests
+            .get(&proposal.contest_id)
+            .unwrap();
+        assert_eq!(contest.my_side, VersusSide::Proposer);
+        assert_eq!(contest.status, VersusContestStatus::AwaitingOpponentConsent);
+    }
+}
@@ -0,0 +1,170 @@
+use std::time::SystemTime;
+
+use candid::Principal;
+use shared_utils::{
+    canister_specific::individual_user_template::types::{
+        error::VersusContestError,
+        versus::{VersusBetOnSide, VersusContestId},
+    },
+    common::{types::app_primitive_type::PostId, utils::system_time},
+};
+
+use crate::{
+    data_model::CanisterData,
+    util::{call_journal::record_call_in_journal, guards::reject_updates_in_read_only_mode},
+    CANISTER_DATA,
+};
+
+/// Adds a bet on this canister's post to its side of the contest pot
+#[ic_cdk::update(guard = "reject_updates_in_read_only_mode")]
+#[candid::candid_method(update)]
+fn receive_versus_bet_from_bet_makers_canister(
+    contest_id: VersusContestId,
+    post_id: PostId,
+    bet_maker_principal_id: Principal,
+    bet_amount: u64,
+) -> Result<(), VersusContestError> {
+    record_call_in_journal("receive_versus_bet_from_bet_makers_canister");
+
+    let bet_maker_canister_id = ic_cdk::caller();
+
+    CANISTER_DATA.with(|canister_data_ref_cell| {
+        receive_versus_bet_impl(
+            &mut canister_data_ref_cell.borrow_mut(),
+            &contest_id,
+            post_id,
+            bet_maker_principal_id,
+            bet_maker_canister_id,
+            bet_amount,
+            &system_time::get_current_system_time_from_ic(),
+        )
+    })
+}
+
+fn receive_versus_bet_impl(
+    canister_data: &mut CanisterData,
+    contest_id: &VersusContestId,
+    post_id: PostId,
+    bet_maker_principal_id: Principal,
+    bet_maker_canister_id: Principal,
+    bet_amount: u64,
+    current_time: &SystemTime,
+) -> Result<(), VersusContestError> {
+    let contest = canister_data
+        .versus_contests
+        .get_mut(contest_id)
+        .ok_or(VersusContestError::ContestNotFound)?;
+
+    if contest.get_my_post().post_id != post_id {
+        return Err(VersusContestError::PostNotInContest);
+    }
+
+    if !contest.is_open_for_bets(current_time) {
+        return Err(VersusContestError::ContestNotOpenForBets);
+    }
+
+    // * Bets on the other post are checked by its canister
+    if contest.bets.contains_key(&bet_maker_principal_id) {
+        return Err(VersusContestError::AlreadyBetOnContest);
+    }
+
+    contest.bets.insert(
+        bet_maker_principal_id,
+        VersusBetOnSide {
+            bet_maker_canister_id,
+            amount: bet_amount,
+            placed_at: *current_time,
+            is_payout_delivered: false,
+        },
+    );
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use shared_utils::{
+        canister_specific::individual_user_template::types::versus::{
+            VersusContest, VersusContestProposal, VersusEngagement, VersusSide,
+        },
+        common::types::global_post_ref::GlobalPostRef,
+    };
+    use test_utils::setup::test_constants::{
+        get_mock_user_alice_canister_id, get_mock_user_bob_canister_id,
+        get_mock_user_charlie_canister_id, get_mock_user_charlie_principal_id,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_receive_versus_bet_impl() {
+        let mut canister_data = CanisterData::default();
+        let contest_id = VersusContestId {
+            proposer_canister_id: get_mock_user_alice_canister_id(),
+            index: 0,
+        };
+        canister_data.versus_contests.insert(
+            contest_id,
+            VersusContest::new(
+                VersusContestProposal {
+                    contest_id,
+                    proposer_post: GlobalPostRef::new(get_mock_user_alice_canister_id(), 0),
+                    opponent_post: GlobalPostRef::new(get_mock_user_bob_canister_id(), 3),
+                    window_in_seconds: 60 * 60,
+                },
+                VersusSide::Opponent,
+            ),
+        );
+        let opens_at = SystemTime::UNIX_EPOCH;
+        let receive_bet = |canister_data: &mut CanisterData, post_id, current_time| {
+            receive_versus_bet_impl(
+                canister_data,
+                &contest_id,
+                post_id,
+                get_mock_user_charlie_principal_id(),
+                get_mock_user_charlie_canister_id(),
+                100,
+                &current_time,
+            )
+        };
+
+        assert_eq!(
+            receive_bet(&mut canister_data, 3, opens_at),
+            Err(VersusContestError::ContestNotOpenForBets)
+        );
+
+        canister_data
+            .versus_contests
+            .get_mut(&contest_id)
+            .unwrap()
+            .open(opens_at, VersusEngagement::default());
+
+        assert_eq!(
+            receive_bet(&mut canister_data, 0, opens_at),
+            Err(VersusContestError::PostNotInContest)
+        );
+        assert_eq!(
+            receive_bet(
+                &mut canister_data,
+                3,
+                opens_at + Duration::from_secs(60 * 60)
+            ),
+            Err(VersusContestError::ContestNotOpenForBets)
+        );
+        assert_eq!(receive_bet(&mut canister_data, 3, opens_at), Ok(()));
+        assert_eq!(
+            receive_bet(&mut canister_data, 3, opens_at),
+            Err(VersusContestError::AlreadyBetOnContest)
+        );
+        assert_eq!(
+            canister_data
+                .versus_contests
+                .get(&contest_id)
+                .unwrap()
+                .get_pot(),
+            100
+        );
+    }
+}
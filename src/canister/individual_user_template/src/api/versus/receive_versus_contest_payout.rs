@@ -0,0 +1,138 @@
+use std::time::SystemTime;
+
+use candid::Principal;
+use shared_utils::{
+    canister_specific::individual_user_template::types::versus::VersusContestId,
+    common::{
+        types::utility_token::token_event::{HotOrNotOutcomePayoutEvent, TokenEvent},
+        utils::system_time,
+    },
+};
+
+use crate::{
+    data_model::CanisterData,
+    util::{call_journal::record_call_in_journal, guards::reject_updates_in_read_only_mode},
+    CANISTER_DATA,
+};
+
+/// What this profile's bet on a settled contest paid out, zero if it lost
+///
+/// # Access Control
+/// Only the canister of the post that was bet on can pay the bet out
+#[ic_cdk::update(guard = "reject_updates_in_read_only_mode")]
+#[candid::candid_method(update)]
+fn receive_versus_contest_payout(contest_id: VersusContestId, payout_amount: u64) {
+    record_call_in_journal("receive_versus_contest_payout");
+
+    let post_creator_canister_id = ic_cdk::caller();
+
+    CANISTER_DATA.with(|canister_data_ref_cell| {
+        receive_versus_contest_payout_impl(
+            &mut canister_data_ref_cell.borrow_mut(),
+            &post_creator_canister_id,
+            &contest_id,
+            payout_amount,
+            &system_time::get_current_system_time_from_ic(),
+        );
+    });
+}
+
+/// Each bet is only ever paid out once
+fn receive_versus_contest_payout_impl(
+    canister_data: &mut CanisterData,
+    post_creator_canister_id: &Principal,
+    contest_id: &VersusContestId,
+    payout_amount: u64,
+    current_time: &SystemTime,
+) {
+    let Some(versus_bet_placed) =
+        canister_data
+            .versus_bets_placed
+            .get_mut(contest_id)
+            .filter(|versus_bet_placed| {
+                versus_bet_placed.post.canister_id == *post_creator_canister_id
+            })
+    else {
+        return;
+    };
+
+    if versus_bet_placed.payout.is_some() {
+        return;
+    }
+    versus_bet_placed.payout = Some(payout_amount);
+
+    let post = versus_bet_placed.post;
+    let amount_bet = versus_bet_placed.amount;
+    canister_data.handle_token_event(TokenEvent::HotOrNotOutcomePayout {
+        amount: payout_amount,
+        details: HotOrNotOutcomePayoutEvent::VersusContestPayout {
+            contest_id: *contest_id,
+            post_canister_id: post.canister_id,
+            post_id: post.post_id,
+            amount_bet,
+            payout_amount,
+        },
+        timestamp: *current_time,
+    });
+}
+
+#[cfg(test)]
+mod test {
+    use shared_utils::{
+        canister_specific::individual_user_template::types::versus::VersusBetPlaced,
+        common::types::global_post_ref::GlobalPostRef,
+    };
+    use test_utils::setup::test_constants::{
+        get_mock_user_alice_canister_id, get_mock_user_bob_canister_id,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_receive_versus_contest_payout_impl() {
+        let mut canister_data = CanisterData::default();
+        let contest_id = VersusContestId {
+            proposer_canister_id: get_mock_user_alice_canister_id(),
+            index: 0,
+        };
+        canister_data.versus_bets_placed.insert(
+            contest_id,
+            VersusBetPlaced {
+                post: GlobalPostRef::new(get_mock_user_bob_canister_id(), 3),
+                amount: 100,
+                placed_at: SystemTime::UNIX_EPOCH,
+                payout: None,
+            },
+        );
+
+        // * only the canister holding the post bet on can pay out
+        receive_versus_contest_payout_impl(
+            &mut canister_data,
+            &get_mock_user_alice_canister_id(),
+            &contest_id,
+            500,
+            &SystemTime::UNIX_EPOCH,
+        );
+        assert_eq!(canister_data.my_token_balance.utility_token_balance, 0);
+
+        (0..2).for_each(|_| {
+            receive_versus_contest_payout_impl(
+                &mut canister_data,
+                &get_mock_user_bob_canister_id(),
+                &contest_id,
+                150,
+                &SystemTime::UNIX_EPOCH,
+            );
+        });
+        assert_eq!(canister_data.my_token_balance.utility_token_balance, 150);
+        assert_eq!(canister_data.my_token_balance.lifetime_earnings, 50);
+        assert_eq!(
+            canister_data
+                .versus_bets_placed
+                .get(&contest_id)
+                .unwrap()
+                .payout,
+            Some(150)
+        );
+    }
+}
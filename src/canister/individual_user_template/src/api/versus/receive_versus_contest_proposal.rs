@@ -0,0 +1,138 @@
+use candid::Principal;
+use shared_utils::canister_specific::individual_user_template::types::{
+    error::VersusContestError,
+    versus::{VersusContest, VersusContestProposal, VersusSide},
+};
+
+use crate::{
+    data_model::CanisterData,
+    util::{call_journal::record_call_in_journal, guards::reject_updates_in_read_only_mode},
+    CANISTER_DATA,
+};
+
+use super::propose_versus_contest::validate_post_can_enter_versus_contest;
+
+/// Stores a contest proposed against one of this profile's posts until its creator responds
+///
+/// # Access Control
+/// Only the canister of the creator proposing the contest can send it
+#[ic_cdk::update(guard = "reject_updates_in_read_only_mode")]
+#[candid::candid_method(update)]
+fn receive_versus_contest_proposal(
+    proposal: VersusContestProposal,
+) -> Result<(), VersusContestError> {
+    record_call_in_journal("receive_versus_contest_proposal");
+
+    let proposer_canister_id = ic_cdk::caller();
+
+    CANISTER_DATA.with(|canister_data_ref_cell| {
+        receive_versus_contest_proposal_impl(
+            &mut canister_data_ref_cell.borrow_mut(),
+            &proposer_canister_id,
+            &ic_cdk::id(),
+            proposal,
+        )
+    })
+}
+
+fn receive_versus_contest_proposal_impl(
+    canister_data: &mut CanisterData,
+    proposer_canister_id: &Principal,
+    my_canister_id: &Principal,
+    proposal: VersusContestProposal,
+) -> Result<(), VersusContestError> {
+    if proposal.contest_id.proposer_canister_id != *proposer_canister_id
+        || proposal.proposer_post.canister_id != *proposer_canister_id
+    {
+        return Err(VersusContestError::Unauthorized);
+    }
+
+    if proposal.opponent_post.canister_id != *my_canister_id {
+        return Err(VersusContestError::InvalidOpponentPost);
+    }
+
+    validate_post_can_enter_versus_contest(canister_data, proposal.opponent_post.post_id)?;
+
+    canister_data
+        .versus_contests
+        .entry(proposal.contest_id)
+        .or_insert_with(|| VersusContest::new(proposal, VersusSide::Opponent));
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::SystemTime;
+
+    use shared_utils::{
+        canister_specific::individual_user_template::types::{
+            post::{Post, PostDetailsFromFrontend},
+            versus::VersusContestId,
+        },
+        common::types::global_post_ref::GlobalPostRef,
+    };
+    use test_utils::setup::test_constants::{
+        get_mock_user_alice_canister_id, get_mock_user_bob_canister_id,
+        get_mock_user_charlie_canister_id,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_receive_versus_contest_proposal_impl() {
+        let mut canister_data = CanisterData::default();
+        canister_data.all_created_posts.insert(
+            3,
+            Post::new(
+                3,
+                &PostDetailsFromFrontend {
+                    description: "Doggos and puppers".into(),
+                    hashtags: vec!["doggo".into(), "pupper".into()],
+                    video_uid: "abcd#1234".into(),
+                    creator_consent_for_inclusion_in_hot_or_not: true,
+                    collaborator_shares: None,
+                    title: None,
+                    category: None,
+                },
+                &SystemTime::now(),
+            ),
+        );
+        let proposal = VersusContestProposal {
+            contest_id: VersusContestId {
+                proposer_canister_id: get_mock_user_alice_canister_id(),
+                index: 0,
+            },
+            proposer_post: GlobalPostRef::new(get_mock_user_alice_canister_id(), 0),
+            opponent_post: GlobalPostRef::new(get_mock_user_bob_canister_id(), 3),
+            window_in_seconds: 24 * 60 * 60,
+        };
+
+        assert_eq!(
+            receive_versus_contest_proposal_impl(
+                &mut canister_data,
+                &get_mock_user_charlie_canister_id(),
+                &get_mock_user_bob_canister_id(),
+                proposal.clone(),
+            ),
+            Err(VersusContestError::Unauthorized)
+        );
+        assert_eq!(
+            receive_versus_contest_proposal_impl(
+                &mut canister_data,
+                &get_mock_user_alice_canister_id(),
+                &get_mock_user_bob_canister_id(),
+                proposal.clone(),
+            ),
+            Ok(())
+        );
+        assert_eq!(
+            canister_data
+                .versus_contests
+                .get(&proposal.contest_id)
+                .unwrap()
+                .my_side,
+            VersusSide::Opponent
+        );
+    }
+}
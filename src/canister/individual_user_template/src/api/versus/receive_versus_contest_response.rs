@@ -0,0 +1,171 @@
+use std::time::SystemTime;
+
+use candid::Principal;
+use shared_utils::{
+    canister_specific::individual_user_template::types::{
+        error::VersusContestError,
+        versus::{VersusContestId, VersusSide},
+    },
+    common::utils::system_time,
+};
+
+use crate::{
+    data_model::CanisterData,
+    util::{call_journal::record_call_in_journal, guards::reject_updates_in_read_only_mode},
+    CANISTER_DATA,
+};
+
+use super::{
+    respond_to_versus_contest_proposal::apply_response_to_versus_contest_impl,
+    settle_versus_contest::enqueue_timer_for_versus_contest_settlement,
+};
+
+/// The opponent creator's answer to a contest proposed from this canister. `opens_at` is
+/// None when they declined.
+///
+/// # Access Control
+/// Only the canister holding the opponent post can respond
+#[ic_cdk::update(guard = "reject_updates_in_read_only_mode")]
+#[candid::candid_method(update)]
+fn receive_versus_contest_response(
+    contest_id: VersusContestId,
+    opens_at: Option<SystemTime>,
+) -> Result<(), VersusContestError> {
+    record_call_in_journal("receive_versus_contest_response");
+
+    let opponent_canister_id = ic_cdk::caller();
+
+    let closes_at = CANISTER_DATA.with(|canister_data_ref_cell| {
+        receive_versus_contest_response_impl(
+            &mut canister_data_ref_cell.borrow_mut(),
+            &opponent_canister_id,
+            &contest_id,
+            opens_at,
+        )
+    })?;
+
+    if let Some(closes_at) = closes_at {
+        enqueue_timer_for_versus_contest_settlement(
+            contest_id,
+            &closes_at,
+            &system_time::get_current_system_time_from_ic(),
+        );
+    }
+
+    Ok(())
+}
+
+/// Returns when the contest closes if it was accepted
+fn receive_versus_contest_response_impl(
+    canister_data: &mut CanisterData,
+    opponent_canister_id: &Principal,
+    contest_id: &VersusContestId,
+    opens_at: Option<SystemTime>,
+) -> Result<Option<SystemTime>, VersusContestError> {
+    let contest = canister_data
+        .versus_contests
+        .get(contest_id)
+        .ok_or(VersusContestError::ContestNotFound)?;
+
+    if contest.my_side != VersusSide::Proposer
+        || contest.proposal.opponent_post.canister_id != *opponent_canister_id
+    {
+        return Err(VersusContestError::Unauthorized);
+    }
+
+    apply_response_to_versus_contest_impl(canister_data, contest_id, opens_at)?;
+
+    Ok(canister_data
+        .versus_contests
+        .get(contest_id)
+        .and_then(|contest| contest.get_closes_at()))
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use shared_utils::{
+        canister_specific::individual_user_template::types::versus::{
+            VersusContest, VersusContestProposal, VersusContestStatus,
+        },
+        common::types::global_post_ref::GlobalPostRef,
+    };
+    use test_utils::setup::test_constants::{
+        get_mock_user_alice_canister_id, get_mock_user_bob_canister_id,
+        get_mock_user_charlie_canister_id,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_receive_versus_contest_response_impl() {
+        let mut canister_data = CanisterData::default();
+        let contest_id = VersusContestId {
+            proposer_canister_id: get_mock_user_alice_canister_id(),
+            index: 0,
+        };
+        let proposal = VersusContestProposal {
+            contest_id,
+            proposer_post: GlobalPostRef::new(get_mock_user_alice_canister_id(), 0),
+            opponent_post: GlobalPostRef::new(get_mock_user_bob_canister_id(), 3),
+            window_in_seconds: 60 * 60,
+        };
+        canister_data.versus_contests.insert(
+            contest_id,
+            VersusContest::new(proposal.clone(), VersusSide::Proposer),
+        );
+        let opens_at = SystemTime::UNIX_EPOCH;
+
+        assert_eq!(
+            receive_versus_contest_response_impl(
+                &mut canister_data,
+                &get_mock_user_charlie_canister_id(),
+                &contest_id,
+                Some(opens_at),
+            ),
+            Err(VersusContestError::Unauthorized)
+        );
+        assert_eq!(
+            receive_versus_contest_response_impl(
+                &mut canister_data,
+                &get_mock_user_bob_canister_id(),
+                &contest_id,
+                Some(opens_at),
+            ),
+            Ok(Some(opens_at + Duration::from_secs(60 * 60)))
+        );
+
+        let declined_contest_id = VersusContestId {
+            proposer_canister_id: get_mock_user_alice_canister_id(),
+            index: 1,
+        };
+        canister_data.versus_contests.insert(
+            declined_contest_id,
+            VersusContest::new(
+                VersusContestProposal {
+                    contest_id: declined_contest_id,
+                    ..proposal
+                },
+                VersusSide::Proposer,
+            ),
+        );
+        assert_eq!(
+            receive_versus_contest_response_impl(
+                &mut canister_data,
+                &get_mock_user_bob_canister_id(),
+                &declined_contest_id,
+                None,
+            ),
+            Ok(None)
+        );
+        assert_eq!(
+            canister_data
+                .versus_contests
+                .get(&declined_contest_id)
+                .unwrap()
+                .status,
+            VersusContestStatus::Declined
+        );
+    }
+}
@@ -0,0 +1,55 @@
+use shared_utils::{
+    canister_specific::individual_user_template::types::{
+        error::VersusContestError,
+        versus::{VersusContestId, VersusContestSettlement, VersusSide},
+    },
+    common::utils::system_time,
+};
+
+use crate::{
+    util::{call_journal::record_call_in_journal, guards::reject_updates_in_read_only_mode},
+    CANISTER_DATA,
+};
+
+use super::settle_versus_contest::{
+    apply_versus_contest_settlement_impl, deliver_versus_contest_payouts,
+};
+
+/// The outcome of a contest as worked out by the proposer's canister. Bets on this
+/// canister's post are paid out from here.
+///
+/// # Access Control
+/// Only the canister of the creator that proposed the contest can settle it
+#[ic_cdk::update(guard = "reject_updates_in_read_only_mode")]
+#[candid::candid_method(update)]
+fn receive_versus_contest_settlement(
+    contest_id: VersusContestId,
+    settlement: VersusContestSettlement,
+) -> Result<(), VersusContestError> {
+    record_call_in_journal("receive_versus_contest_settlement");
+
+    let proposer_canister_id = ic_cdk::caller();
+    let current_time = system_time::get_current_system_time_from_ic();
+
+    CANISTER_DATA.with(|canister_data_ref_cell| {
+        let canister_data = &mut canister_data_ref_cell.borrow_mut();
+
+        let is_sent_by_proposer =
+            canister_data
+                .versus_contests
+                .get(&contest_id)
+                .is_some_and(|contest| {
+                    contest.my_side == VersusSide::Opponent
+                        && contest_id.proposer_canister_id == proposer_canister_id
+                });
+        if !is_sent_by_proposer {
+            return Err(VersusContestError::Unauthorized);
+        }
+
+        apply_versus_contest_settlement_impl(canister_data, &contest_id, settlement, &current_time)
+    })?;
+
+    deliver_versus_contest_payouts(contest_id);
+
+    Ok(())
+}
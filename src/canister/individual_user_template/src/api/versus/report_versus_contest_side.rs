@@ -0,0 +1,171 @@
+use std::time::SystemTime;
+
+use candid::Principal;
+use shared_utils::{
+    canister_specific::individual_user_template::types::{
+        error::VersusContestError,
+        versus::{VersusContestId, VersusSide, VersusSideReport},
+    },
+    common::utils::system_time,
+};
+
+use crate::{
+    data_model::CanisterData,
+    util::{call_journal::record_call_in_journal, guards::reject_updates_in_read_only_mode},
+    CANISTER_DATA,
+};
+
+/// How the opponent's post did over a closed contest. The report is frozen the first time it
+/// is asked for, so asking again after a failed settlement returns the same numbers.
+///
+/// # Access Control
+/// Only the canister of the creator that proposed the contest can ask for it
+#[ic_cdk::update(guard = "reject_updates_in_read_only_mode")]
+#[candid::candid_method(update)]
+fn report_versus_contest_side(
+    contest_id: VersusContestId,
+) -> Result<VersusSideReport, VersusContestError> {
+    record_call_in_journal("report_versus_contest_side");
+
+    let proposer_canister_id = ic_cdk::caller();
+
+    CANISTER_DATA.with(|canister_data_ref_cell| {
+        report_versus_contest_side_impl(
+            &mut canister_data_ref_cell.borrow_mut(),
+            &proposer_canister_id,
+            &contest_id,
+            &system_time::get_current_system_time_from_ic(),
+        )
+    })
+}
+
+fn report_versus_contest_side_impl(
+    canister_data: &mut CanisterData,
+    proposer_canister_id: &Principal,
+    contest_id: &VersusContestId,
+    current_time: &SystemTime,
+) -> Result<VersusSideReport, VersusContestError> {
+    let contest = canister_data
+        .versus_contests
+        .get_mut(contest_id)
+        .ok_or(VersusContestError::ContestNotFound)?;
+
+    if contest.my_side != VersusSide::Opponent
+        || contest.proposal.contest_id.proposer_canister_id != *proposer_canister_id
+    {
+        return Err(VersusContestError::Unauthorized);
+    }
+
+    let my_post = canister_data
+        .all_created_posts
+        .get(&contest.get_my_post().post_id)
+        .ok_or(VersusContestError::PostNotFound)?;
+
+    contest
+        .get_final_report(&my_post, current_time)
+        .ok_or(VersusContestError::ContestStillOpen)
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use shared_utils::{
+        canister_specific::individual_user_template::types::{
+            post::{Post, PostDetailsFromFrontend},
+            versus::{VersusContest, VersusContestProposal, VersusEngagement},
+        },
+        common::types::global_post_ref::GlobalPostRef,
+    };
+    use test_utils::setup::test_constants::{
+        get_mock_user_alice_canister_id, get_mock_user_bob_canister_id,
+        get_mock_user_charlie_canister_id,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_report_versus_contest_side_impl_freezes_the_report() {
+        let mut canister_data = CanisterData::default();
+        let mut post = Post::new(
+            3,
+            &PostDetailsFromFrontend {
+                description: "Doggos and puppers".into(),
+                hashtags: vec!["doggo".into(), "pupper".into()],
+                video_uid: "abcd#1234".into(),
+                creator_consent_for_inclusion_in_hot_or_not: true,
+                collaborator_shares: None,
+                title: None,
+                category: None,
+            },
+            &SystemTime::UNIX_EPOCH,
+        );
+        canister_data.all_created_posts.insert(3, post.clone());
+
+        let contest_id = VersusContestId {
+            proposer_canister_id: get_mock_user_alice_canister_id(),
+            index: 0,
+        };
+        let mut contest = VersusContest::new(
+            VersusContestProposal {
+                contest_id,
+                proposer_post: GlobalPostRef::new(get_mock_user_alice_canister_id(), 0),
+                opponent_post: GlobalPostRef::new(get_mock_user_bob_canister_id(), 3),
+                window_in_seconds: 60 * 60,
+            },
+            VersusSide::Opponent,
+        );
+        let opens_at = SystemTime::UNIX_EPOCH;
+        let closes_at = opens_at + Duration::from_secs(60 * 60);
+        contest.open(opens_at, VersusEngagement::default());
+        canister_data.versus_contests.insert(contest_id, contest);
+
+        assert_eq!(
+            report_versus_contest_side_impl(
+                &mut canister_data,
+                &get_mock_user_charlie_canister_id(),
+                &contest_id,
+                &closes_at
+            ),
+            Err(VersusContestError::Unauthorized)
+        );
+        assert_eq!(
+            report_versus_contest_side_impl(
+                &mut canister_data,
+                &get_mock_user_alice_canister_id(),
+                &contest_id,
+                &opens_at
+            ),
+            Err(VersusContestError::ContestStillOpen)
+        );
+
+        post.share_count = 2;
+        canister_data.all_created_posts.insert(3, post.clone());
+        let report = VersusSideReport {
+            pot: 0,
+            number_of_bets: 0,
+            performance_score: 20,
+        };
+        assert_eq!(
+            report_versus_contest_side_impl(
+                &mut canister_data,
+                &get_mock_user_alice_canister_id(),
+                &contest_id,
+                &closes_at
+            ),
+            Ok(report)
+        );
+
+        post.share_count = 5;
+        canister_data.all_created_posts.insert(3, post);
+        assert_eq!(
+            report_versus_contest_side_impl(
+                &mut canister_data,
+                &get_mock_user_alice_canister_id(),
+                &contest_id,
+                &closes_at
+            ),
+            Ok(report)
+        );
+    }
+}
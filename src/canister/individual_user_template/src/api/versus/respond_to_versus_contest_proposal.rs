@@ -0,0 +1,188 @@
+use std::time::SystemTime;
+
+use candid::Principal;
+use shared_utils::{
+    canister_specific::individual_user_template::types::{
+        error::VersusContestError,
+        versus::{VersusContestId, VersusContestStatus, VersusEngagement, VersusSide},
+    },
+    common::utils::system_time,
+};
+
+use crate::{
+    data_model::CanisterData,
+    util::{call_journal::record_call_in_journal, guards::reject_updates_in_read_only_mode},
+    CANISTER_DATA,
+};
+
+/// Accepts or declines a contest proposed against one of this profile's posts. Accepting
+/// opens it for bets on both posts right away, for the window the proposer asked for.
+///
+/// # Access Control
+/// Only the user whose profile details are stored in this canister can respond
+#[ic_cdk::update(guard = "reject_updates_in_read_only_mode")]
+#[candid::candid_method(update)]
+async fn respond_to_versus_contest_proposal(
+    contest_id: VersusContestId,
+    accept: bool,
+) -> Result<VersusContestStatus, VersusContestError> {
+    record_call_in_journal("respond_to_versus_contest_proposal");
+
+    let api_caller = ic_cdk::caller();
+    let current_time = system_time::get_current_system_time_from_ic();
+    let opens_at = accept.then_some(current_time);
+
+    CANISTER_DATA.with(|canister_data_ref_cell| {
+        validate_response_to_versus_contest_proposal(
+            &canister_data_ref_cell.borrow(),
+            &api_caller,
+            &contest_id,
+        )
+    })?;
+
+    // * The proposer's side goes first, as it may have been withdrawn in the meantime
+    let (response,) = ic_cdk::call::<_, (Result<(), VersusContestError>,)>(
+        contest_id.proposer_canister_id,
+        "receive_versus_contest_response",
+        (contest_id, opens_at),
+    )
+    .await
+    .map_err(|_| VersusContestError::OtherCanisterCallFailed)?;
+    response?;
+
+    CANISTER_DATA.with(|canister_data_ref_cell| {
+        apply_response_to_versus_contest_impl(
+            &mut canister_data_ref_cell.borrow_mut(),
+            &contest_id,
+            opens_at,
+        )
+    })
+}
+
+fn validate_response_to_versus_contest_proposal(
+    canister_data: &CanisterData,
+    api_caller: &Principal,
+    contest_id: &VersusContestId,
+) -> Result<(), VersusContestError> {
+    if canister_data.profile.principal_id != Some(*api_caller) {
+        return Err(VersusContestError::Unauthorized);
+    }
+
+    let contest = canister_data
+        .versus_contests
+        .get(contest_id)
+        .ok_or(VersusContestError::ContestNotFound)?;
+
+    if contest.my_side != VersusSide::Opponent
+        || contest.status != VersusContestStatus::AwaitingOpponentConsent
+    {
+        return Err(VersusContestError::ContestNotAwaitingConsent);
+    }
+
+    Ok(())
+}
+
+/// Opens the contest on this canister from `opens_at`, or declines it when there is none.
+/// Engagement on this side's post is counted from here on.
+pub fn apply_response_to_versus_contest_impl(
+    canister_data: &mut CanisterData,
+    contest_id: &VersusContestId,
+    opens_at: Option<SystemTime>,
+) -> Result<VersusContestStatus, VersusContestError> {
+    let contest = canister_data
+        .versus_contests
+        .get_mut(contest_id)
+        .ok_or(VersusContestError::ContestNotFound)?;
+
+    if contest.status != VersusContestStatus::AwaitingOpponentConsent {
+        return Err(VersusContestError::ContestNotAwaitingConsent);
+    }
+
+    match opens_at {
+        Some(opens_at) => {
+            let engagement_at_open = canister_data
+                .all_created_posts
+                .get(&contest.get_my_post().post_id)
+                .map(|post| VersusEngagement::of_post(&post))
+                .unwrap_or_default();
+            contest.open(opens_at, engagement_at_open);
+        }
+        None => contest.status = VersusContestStatus::Declined,
+    }
+
+    Ok(contest.status.clone())
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use shared_utils::{
+        canister_specific::individual_user_template::types::versus::{
+            VersusContest, VersusContestProposal,
+        },
+        common::types::global_post_ref::GlobalPostRef,
+    };
+    use test_utils::setup::test_constants::{
+        get_mock_user_alice_canister_id, get_mock_user_alice_principal_id,
+        get_mock_user_bob_canister_id, get_mock_user_bob_principal_id,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_respond_to_versus_contest_proposal() {
+        let mut canister_data = CanisterData::default();
+        canister_data.profile.principal_id = Some(get_mock_user_bob_principal_id());
+        let contest_id = VersusContestId {
+            proposer_canister_id: get_mock_user_alice_canister_id(),
+            index: 0,
+        };
+        canister_data.versus_contests.insert(
+            contest_id,
+            VersusContest::new(
+                VersusContestProposal {
+                    contest_id,
+                    proposer_post: GlobalPostRef::new(get_mock_user_alice_canister_id(), 0),
+                    opponent_post: GlobalPostRef::new(get_mock_user_bob_canister_id(), 3),
+                    window_in_seconds: 60 * 60,
+                },
+                VersusSide::Opponent,
+            ),
+        );
+
+        assert_eq!(
+            validate_response_to_versus_contest_proposal(
+                &canister_data,
+                &get_mock_user_alice_principal_id(),
+                &contest_id
+            ),
+            Err(VersusContestError::Unauthorized)
+        );
+        assert_eq!(
+            validate_response_to_versus_contest_proposal(
+                &canister_data,
+                &get_mock_user_bob_principal_id(),
+                &contest_id
+            ),
+            Ok(())
+        );
+
+        let opens_at = SystemTime::UNIX_EPOCH;
+        assert_eq!(
+            apply_response_to_versus_contest_impl(&mut canister_data, &contest_id, Some(opens_at)),
+            Ok(VersusContestStatus::Open {
+                opens_at,
+                closes_at: opens_at + Duration::from_secs(60 * 60),
+            })
+        );
+        assert_eq!(
+            validate_response_to_versus_contest_proposal(
+                &canister_data,
+                &get_mock_user_bob_principal_id(),
+                &contest_id
+            ),
+            Err(VersusContestError::ContestNotAwaitingConsent)
+        );
+    }
+}
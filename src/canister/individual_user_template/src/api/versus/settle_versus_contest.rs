@@ -0,0 +1,431 @@
+use std::time::{Duration, SystemTime};
+
+use candid::Principal;
+use shared_utils::{
+    canister_specific::individual_user_template::types::{
+        error::VersusContestError,
+        versus::{
+            VersusContestId, VersusContestSettlement, VersusContestStatus, VersusSide,
+            VersusSideReport,
+        },
+    },
+    common::{types::event_counter::EventCounterName, utils::system_time},
+};
+
+use crate::{data_model::CanisterData, CANISTER_DATA};
+
+// * How long to wait before trying again when the opponent's canister couldn't be reached
+const VERSUS_CONTEST_SETTLEMENT_RETRY_INTERVAL: Duration = Duration::from_secs(5 * 60);
+
+/// Settles a contest proposed from this canister once it closes. The proposer's canister
+/// compares both sides and hands the result to the opponent's canister, then each canister
+/// pays out the bets placed on its own post.
+pub fn enqueue_timer_for_versus_contest_settlement(
+    contest_id: VersusContestId,
+    closes_at: &SystemTime,
+    current_time: &SystemTime,
+) {
+    ic_cdk_timers::set_timer(
+        closes_at.duration_since(*current_time).unwrap_or_default(),
+        move || ic_cdk::spawn(settle_versus_contest(contest_id)),
+    );
+}
+
+/// Settlement timers and payouts that were still pending when the canister was upgraded
+pub fn reenqueue_timers_for_pending_versus_contests() {
+    let current_time = system_time::get_current_system_time_from_ic();
+
+    let (contests_to_settle, contests_to_pay_out) = CANISTER_DATA.with(|canister_data_ref_cell| {
+        get_pending_versus_contests(&canister_data_ref_cell.borrow())
+    });
+
+    contests_to_settle
+        .iter()
+        .for_each(|(contest_id, closes_at)| {
+            enqueue_timer_for_versus_contest_settlement(*contest_id, closes_at, &current_time);
+        });
+    contests_to_pay_out
+        .into_iter()
+        .for_each(deliver_versus_contest_payouts);
+}
+
+/// Open contests this canister settles along with when they close, and settled contests with
+/// payouts that haven't been delivered
+fn get_pending_versus_contests(
+    canister_data: &CanisterData,
+) -> (Vec<(VersusContestId, SystemTime)>, Vec<VersusContestId>) {
+    let contests_to_settle = canister_data
+        .versus_contests
+        .iter()
+        .filter(|(_, contest)| contest.my_side == VersusSide::Proposer)
+        .filter_map(|(contest_id, contest)| {
+            contest
+                .get_closes_at()
+                .map(|closes_at| (*contest_id, closes_at))
+        })
+        .collect();
+
+    let contests_to_pay_out = canister_data
+        .versus_contests
+        .iter()
+        .filter(|(_, contest)| matches!(contest.status, VersusContestStatus::Settled { .. }))
+        .filter(|(_, contest)| contest.bets.values().any(|bet| !bet.is_payout_delivered))
+        .map(|(contest_id, _)| *contest_id)
+        .collect();
+
+    (contests_to_settle, contests_to_pay_out)
+}
+
+async fn settle_versus_contest(contest_id: VersusContestId) {
+    let current_time = system_time::get_current_system_time_from_ic();
+
+    let my_side_report = CANISTER_DATA.with(|canister_data_ref_cell| {
+        get_final_report_of_my_side_impl(
+            &mut canister_data_ref_cell.borrow_mut(),
+            &contest_id,
+            &current_time,
+        )
+    });
+    let Some((opponent_canister_id, proposer_side)) = my_side_report else {
+        return;
+    };
+
+    match send_settlement_to_opponent(contest_id, opponent_canister_id, proposer_side).await {
+        Ok(settlement) => {
+            CANISTER_DATA
+                .with(|canister_data_ref_cell| {
+                    apply_versus_contest_settlement_impl(
+                        &mut canister_data_ref_cell.borrow_mut(),
+                        &contest_id,
+                        settlement,
+                        &current_time,
+                    )
+                })
+                .ok();
+
+            deliver_versus_contest_payouts(contest_id);
+        }
+        Err(_) => {
+            CANISTER_DATA.with(|canister_data_ref_cell| {
+                canister_data_ref_cell
+                    .borrow_mut()
+                    .event_counters
+                    .increment(EventCounterName::SettlementFailuresTotal, "versus_contest");
+            });
+
+            ic_cdk_timers::set_timer(VERSUS_CONTEST_SETTLEMENT_RETRY_INTERVAL, move || {
+                ic_cdk::spawn(settle_versus_contest(contest_id))
+            });
+        }
+    }
+}
+
+/// Returns the opponent's canister along with this side's report. None if the contest isn't
+/// one this canister settles, isn't open or hasn't closed yet.
+fn get_final_report_of_my_side_impl(
+    canister_data: &mut CanisterData,
+    contest_id: &VersusContestId,
+    current_time: &SystemTime,
+) -> Option<(Principal, VersusSideReport)> {
+    let contest = canister_data
+        .versus_contests
+        .get_mut(contest_id)
+        .filter(|contest| contest.my_side == VersusSide::Proposer)?;
+    let my_post = canister_data
+        .all_created_posts
+        .get(&contest.get_my_post().post_id)?;
+
+    contest
+        .get_final_report(&my_post, current_time)
+        .map(|report| (contest.proposal.opponent_post.canister_id, report))
+}
+
+async fn send_settlement_to_opponent(
+    contest_id: VersusContestId,
+    opponent_canister_id: Principal,
+    proposer_side: VersusSideReport,
+) -> Result<VersusContestSettlement, VersusContestError> {
+    let (opponent_side,) = ic_cdk::call::<_, (Result<VersusSideReport, VersusContestError>,)>(
+        opponent_canister_id,
+        "report_versus_contest_side",
+        (contest_id,),
+    )
+    .await
+    .map_err(|_| VersusContestError::OtherCanisterCallFailed)?;
+
+    let settlement = VersusContestSettlement::new(proposer_side, opponent_side?);
+
+    let (response,) = ic_cdk::call::<_, (Result<(), VersusContestError>,)>(
+        opponent_canister_id,
+        "receive_versus_contest_settlement",
+        (contest_id, settlement.clone()),
+    )
+    .await
+    .map_err(|_| VersusContestError::OtherCanisterCallFailed)?;
+    response?;
+
+    Ok(settlement)
+}
+
+/// Marks the contest settled. Receiving the same settlement again is not an error, so the
+/// proposer's canister can safely retry.
+pub fn apply_versus_contest_settlement_impl(
+    canister_data: &mut CanisterData,
+    contest_id: &VersusContestId,
+    settlement: VersusContestSettlement,
+    current_time: &SystemTime,
+) -> Result<(), VersusContestError> {
+    let contest = canister_data
+        .versus_contests
+        .get_mut(contest_id)
+        .ok_or(VersusContestError::ContestNotFound)?;
+
+    if let Some(closes_at) = contest.get_closes_at() {
+        if *current_time < closes_at {
+            return Err(VersusContestError::ContestStillOpen);
+        }
+
+        contest.status = VersusContestStatus::Settled {
+            settlement,
+            settled_at: *current_time,
+        };
+
+        return Ok(());
+    }
+
+    match &contest.status {
+        VersusContestStatus::Settled {
+            settlement: existing_settlement,
+            ..
+        } if *existing_settlement == settlement => Ok(()),
+        // * never opened
+        _ => Err(VersusContestError::ContestNotOpenForBets),
+    }
+}
+
+/// Sends every bettor on this canister's post what their bet paid out, zero for a losing
+/// bet. Payouts whose call fails are sent again after the next upgrade.
+pub fn deliver_versus_contest_payouts(contest_id: VersusContestId) {
+    let undelivered_payouts = CANISTER_DATA.with(|canister_data_ref_cell| {
+        get_undelivered_versus_contest_payouts_impl(&canister_data_ref_cell.borrow(), &contest_id)
+    });
+
+    undelivered_payouts.into_iter().for_each(
+        |(bet_maker_principal_id, bet_maker_canister_id, payout)| {
+            ic_cdk::spawn(async move {
+                let response = ic_cdk::call::<_, ()>(
+                    bet_maker_canister_id,
+                    "receive_versus_contest_payout",
+                    (contest_id, payout),
+                )
+                .await;
+
+                CANISTER_DATA.with(|canister_data_ref_cell| {
+                    let canister_data = &mut canister_data_ref_cell.borrow_mut();
+
+                    match response {
+                        Ok(()) => {
+                            if let Some(bet) = canister_data
+                                .versus_contests
+                                .get_mut(&contest_id)
+                                .and_then(|contest| contest.bets.get_mut(&bet_maker_principal_id))
+                            {
+                                bet.is_payout_delivered = true;
+                            }
+                        }
+                        Err(_) => {
+                            canister_data.event_counters.increment(
+                                EventCounterName::SettlementFailuresTotal,
+                                "versus_contest_payout",
+                            );
+                        }
+                    }
+                });
+            });
+        },
+    );
+}
+
+/// Bet maker principal, their canister and the payout for every bet not yet paid out
+fn get_undelivered_versus_contest_payouts_impl(
+    canister_data: &CanisterData,
+    contest_id: &VersusContestId,
+) -> Vec<(Principal, Principal, u64)> {
+    let Some(contest) = canister_data.versus_contests.get(contest_id) else {
+        return vec![];
+    };
+    let VersusContestStatus::Settled { settlement, .. } = &contest.status else {
+        return vec![];
+    };
+
+    contest
+        .bets
+        .iter()
+        .filter(|(_, bet)| !bet.is_payout_delivered)
+        .map(|(bet_maker_principal_id, bet)| {
+            (
+                *bet_maker_principal_id,
+                bet.bet_maker_canister_id,
+                settlement.get_payout(contest.my_side, bet.amount),
+            )
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use shared_utils::{
+        canister_specific::individual_user_template::types::{
+            post::{Post, PostDetailsFromFrontend},
+            versus::{
+                VersusBetOnSide, VersusContest, VersusContestOutcome, VersusContestProposal,
+                VersusEngagement,
+            },
+        },
+        common::types::global_post_ref::GlobalPostRef,
+    };
+    use test_utils::setup::test_constants::{
+        get_mock_user_alice_canister_id, get_mock_user_bob_canister_id,
+        get_mock_user_charlie_canister_id, get_mock_user_charlie_principal_id,
+        get_mock_user_dan_canister_id, get_mock_user_dan_principal_id,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_settling_a_versus_contest_on_the_proposers_canister() {
+        let mut canister_data = CanisterData::default();
+        let mut post = Post::new(
+            0,
+            &PostDetailsFromFrontend {
+                description: "Doggos and puppers".into(),
+                hashtags: vec!["doggo".into(), "pupper".into()],
+                video_uid: "abcd#1234".into(),
+                creator_consent_for_inclusion_in_hot_or_not: true,
+                collaborator_shares: None,
+                title: None,
+                category: None,
+            },
+            &SystemTime::UNIX_EPOCH,
+        );
+        post.view_stats.total_view_count = 100;
+        canister_data.all_created_posts.insert(0, post.clone());
+
+        let contest_id = VersusContestId {
+            proposer_canister_id: get_mock_user_alice_canister_id(),
+            index: 0,
+        };
+        let mut contest = VersusContest::new(
+            VersusContestProposal {
+                contest_id,
+                proposer_post: GlobalPostRef::new(get_mock_user_alice_canister_id(), 0),
+                opponent_post: GlobalPostRef::new(get_mock_user_bob_canister_id(), 3),
+                window_in_seconds: 60 * 60,
+            },
+            VersusSide::Proposer,
+        );
+        let opens_at = SystemTime::UNIX_EPOCH;
+        let closes_at = opens_at + Duration::from_secs(60 * 60);
+        contest.open(opens_at, VersusEngagement::of_post(&post));
+        [
+            (
+                get_mock_user_charlie_principal_id(),
+                get_mock_user_charlie_canister_id(),
+                100,
+            ),
+            (
+                get_mock_user_dan_principal_id(),
+                get_mock_user_dan_canister_id(),
+                300,
+            ),
+        ]
+        .into_iter()
+        .for_each(|(bet_maker_principal_id, bet_maker_canister_id, amount)| {
+            contest.bets.insert(
+                bet_maker_principal_id,
+                VersusBetOnSide {
+                    bet_maker_canister_id,
+                    amount,
+                    placed_at: opens_at,
+                    is_payout_delivered: false,
+                },
+            );
+        });
+        canister_data.versus_contests.insert(contest_id, contest);
+
+        assert_eq!(get_pending_versus_contests(&canister_data).0.len(), 1);
+        assert_eq!(
+            get_final_report_of_my_side_impl(&mut canister_data, &contest_id, &opens_at),
+            None
+        );
+
+        post.view_stats.total_view_count = 150;
+        canister_data.all_created_posts.insert(0, post);
+        let (opponent_canister_id, proposer_side) =
+            get_final_report_of_my_side_impl(&mut canister_data, &contest_id, &closes_at).unwrap();
+        assert_eq!(opponent_canister_id, get_mock_user_bob_canister_id());
+        assert_eq!(
+            proposer_side,
+            VersusSideReport {
+                pot: 400,
+                number_of_bets: 2,
+                performance_score: 450,
+            }
+        );
+
+        let settlement = VersusContestSettlement::new(
+            proposer_side,
+            VersusSideReport {
+                pot: 200,
+                number_of_bets: 1,
+                performance_score: 300,
+            },
+        );
+        assert_eq!(
+            settlement.outcome,
+            VersusContestOutcome::Won(VersusSide::Proposer)
+        );
+        assert_eq!(
+            apply_versus_contest_settlement_impl(
+                &mut canister_data,
+                &contest_id,
+                settlement.clone(),
+                &closes_at
+            ),
+            Ok(())
+        );
+        // * retried by the proposer's canister
+        assert_eq!(
+            apply_versus_contest_settlement_impl(
+                &mut canister_data,
+                &contest_id,
+                settlement,
+                &closes_at
+            ),
+            Ok(())
+        );
+
+        let (contests_to_settle, contests_to_pay_out) = get_pending_versus_contests(&canister_data);
+        assert!(contests_to_settle.is_empty());
+        assert_eq!(contests_to_pay_out, vec![contest_id]);
+
+        let mut undelivered_payouts =
+            get_undelivered_versus_contest_payouts_impl(&canister_data, &contest_id);
+        undelivered_payouts.sort_by_key(|(.., payout)| *payout);
+        assert_eq!(
+            undelivered_payouts,
+            vec![
+                (
+                    get_mock_user_charlie_principal_id(),
+                    get_mock_user_charlie_canister_id(),
+                    150
+                ),
+                (
+                    get_mock_user_dan_principal_id(),
+                    get_mock_user_dan_canister_id(),
+                    450
+                ),
+            ]
+        );
+    }
+}
@@ -0,0 +1,169 @@
+use candid::Deserialize;
+use serde::Serialize;
+use shared_utils::{
+    canister_specific::individual_user_template::types::deferred_job::{
+        DeferredJobRequest, DeferredJobResultItem,
+    },
+    common::types::{app_primitive_type::PostId, deferred_job::DeferredJob},
+};
+
+use super::CanisterData;
+
+/// A requested job along with how far it has got
+#[derive(Deserialize, Serialize)]
+pub enum IndividualUserDeferredJob {
+    PostBetAnalyticsRollup { next_post_id: PostId },
+    TokenTransactionsExport { next_transaction_id: u64 },
+}
+
+impl From<DeferredJobRequest> for IndividualUserDeferredJob {
+    fn from(request: DeferredJobRequest) -> Self {
+        match request {
+            DeferredJobRequest::PostBetAnalyticsRollup => {
+                IndividualUserDeferredJob::PostBetAnalyticsRollup { next_post_id: 0 }
+            }
+            DeferredJobRequest::TokenTransactionsExport => {
+                IndividualUserDeferredJob::TokenTransactionsExport {
+                    next_transaction_id: 0,
+                }
+            }
+        }
+    }
+}
+
+impl DeferredJob for IndividualUserDeferredJob {
+    type Context = CanisterData;
+    type ResultItem = DeferredJobResultItem;
+
+    fn run_next_chunk(
+        &mut self,
+        canister_data: &CanisterData,
+        chunk_size: usize,
+        results: &mut Vec<DeferredJobResultItem>,
+    ) -> bool {
+        match self {
+            IndividualUserDeferredJob::PostBetAnalyticsRollup { next_post_id } => {
+                let post_ids = canister_data
+                    .all_created_posts
+                    .keys_from(*next_post_id)
+                    .take(chunk_size)
+                    .collect::<Vec<_>>();
+
+                post_ids.iter().for_each(|post_id| {
+                    if let Some(post) = canister_data.all_created_posts.get(post_id) {
+                        results.push(DeferredJobResultItem::PostBetAnalytics {
+                            post_id: *post_id,
+                            analytics: post
+                                .hot_or_not_details
+                                .unwrap_or_default()
+                                .get_bet_analytics(),
+                        });
+                    }
+                });
+
+                match post_ids.last() {
+                    Some(last_post_id) if post_ids.len() == chunk_size => {
+                        *next_post_id = last_post_id + 1;
+                        false
+                    }
+                    _ => true,
+                }
+            }
+            IndividualUserDeferredJob::TokenTransactionsExport {
+                next_transaction_id,
+            } => {
+                let transactions = canister_data
+                    .token_transaction_history
+                    .range(*next_transaction_id..)
+                    .take(chunk_size)
+                    .map(
+                        |(transaction_id, token_event)| DeferredJobResultItem::TokenTransaction {
+                            transaction_id,
+                            token_event,
+                        },
+                    )
+                    .collect::<Vec<_>>();
+                let number_of_transactions = transactions.len();
+
+                if let Some(DeferredJobResultItem::TokenTransaction { transaction_id, .. }) =
+                    transactions.last()
+                {
+                    *next_transaction_id = transaction_id + 1;
+                }
+                results.extend(transactions);
+
+                number_of_transactions < chunk_size
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::SystemTime;
+
+    use shared_utils::{
+        canister_specific::individual_user_template::types::post::{Post, PostDetailsFromFrontend},
+        common::types::utility_token::token_event::{MintEvent, TokenEvent},
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_post_bet_analytics_rollup_runs_in_chunks() {
+        let mut canister_data = CanisterData::default();
+        (0..5).for_each(|post_id| {
+            canister_data.all_created_posts.insert(
+                post_id,
+                Post::new(
+                    post_id,
+                    &PostDetailsFromFrontend {
+                        description: "Doggos and puppers".into(),
+                        hashtags: vec!["doggo".into(), "pupper".into()],
+                        video_uid: "abcd#1234".into(),
+                        creator_consent_for_inclusion_in_hot_or_not: true,
+                        collaborator_shares: None,
+                        title: None,
+                        category: None,
+                    },
+                    &SystemTime::UNIX_EPOCH,
+                ),
+            );
+        });
+        let mut job = IndividualUserDeferredJob::from(DeferredJobRequest::PostBetAnalyticsRollup);
+        let mut results = vec![];
+
+        assert!(!job.run_next_chunk(&canister_data, 3, &mut results));
+        assert_eq!(results.len(), 3);
+        assert!(job.run_next_chunk(&canister_data, 3, &mut results));
+        assert_eq!(
+            results
+                .iter()
+                .map(|result| match result {
+                    DeferredJobResultItem::PostBetAnalytics { post_id, .. } => *post_id,
+                    _ => unreachable!(),
+                })
+                .collect::<Vec<_>>(),
+            vec![0, 1, 2, 3, 4]
+        );
+    }
+
+    #[test]
+    fn test_token_transactions_export_runs_in_chunks() {
+        let mut canister_data = CanisterData::default();
+        (0..4).for_each(|_| {
+            canister_data.handle_token_event(TokenEvent::Mint {
+                amount: 10,
+                details: MintEvent::TokenFaucet,
+                timestamp: SystemTime::UNIX_EPOCH,
+            });
+        });
+        let mut job = IndividualUserDeferredJob::from(DeferredJobRequest::TokenTransactionsExport);
+        let mut results = vec![];
+
+        assert!(!job.run_next_chunk(&canister_data, 2, &mut results));
+        assert!(!job.run_next_chunk(&canister_data, 2, &mut results));
+        assert!(job.run_next_chunk(&canister_data, 2, &mut results));
+        assert_eq!(results.len(), 4);
+    }
+}
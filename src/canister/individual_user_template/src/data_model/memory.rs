@@ -0,0 +1,212 @@
+use std::cell::RefCell;
+
+use ic_stable_structures::{
+    memory_manager::{MemoryId, MemoryManager, VirtualMemory},
+    DefaultMemoryImpl,
+};
+use shared_utils::common::types::memory_id_registry::{MemoryIdRegistry, MemoryIdRegistryError};
+
+thread_local! {
+    static MEMORY_MANAGER: RefCell<MemoryManager<DefaultMemoryImpl>> = RefCell::new(MemoryManager::init(DefaultMemoryImpl::default()));
+}
+
+pub type Memory = VirtualMemory<DefaultMemoryImpl>;
+
+// * Every memory region handed out by the memory manager, keyed by the name it is
+// * persisted under in the memory id registry. Add new regions here before using them.
+const ALL_MEMORY_REGIONS: [(&str, u8); 18] = [
+    ("upgrades", UPGRADES_MEMORY_ID),
+    (
+        "pending_referrer_share_settlements",
+        PENDING_REFERRER_SHARE_SETTLEMENTS_MEMORY_ID,
+    ),
+    (
+        "pending_house_rake_settlements",
+        PENDING_HOUSE_RAKE_SETTLEMENTS_MEMORY_ID,
+    ),
+    (
+        "pending_collaborator_commission_settlements",
+        PENDING_COLLABORATOR_COMMISSION_SETTLEMENTS_MEMORY_ID,
+    ),
+    ("all_created_posts", ALL_CREATED_POSTS_MEMORY_ID),
+    ("call_journal_index", CALL_JOURNAL_INDEX_MEMORY_ID),
+    ("call_journal_data", CALL_JOURNAL_DATA_MEMORY_ID),
+    (
+        "pending_pot_insurance_top_ups",
+        PENDING_POT_INSURANCE_TOP_UPS_MEMORY_ID,
+    ),
+    (
+        "migration_changelog_index",
+        MIGRATION_CHANGELOG_INDEX_MEMORY_ID,
+    ),
+    (
+        "migration_changelog_data",
+        MIGRATION_CHANGELOG_DATA_MEMORY_ID,
+    ),
+    (
+        "call_queue_pending_calls",
+        CALL_QUEUE_PENDING_CALLS_MEMORY_ID,
+    ),
+    (
+        "call_queue_dead_lettered_calls",
+        CALL_QUEUE_DEAD_LETTERED_CALLS_MEMORY_ID,
+    ),
+    ("follower_sorted_index", FOLLOWER_SORTED_INDEX_MEMORY_ID),
+    ("follower_members", FOLLOWER_MEMBERS_MEMORY_ID),
+    ("following_sorted_index", FOLLOWING_SORTED_INDEX_MEMORY_ID),
+    ("following_members", FOLLOWING_MEMBERS_MEMORY_ID),
+    (
+        "token_transaction_history",
+        TOKEN_TRANSACTION_HISTORY_MEMORY_ID,
+    ),
+    ("received_settlements", RECEIVED_SETTLEMENTS_MEMORY_ID),
+];
+
+pub fn register_all_memory_regions(
+    memory_id_registry: &mut MemoryIdRegistry,
+) -> Result<(), MemoryIdRegistryError> {
+    ALL_MEMORY_REGIONS
+        .iter()
+        .try_for_each(|(name, memory_id)| memory_id_registry.register(name, *memory_id).map(|_| ()))
+}
+
+/// Canisters created before the memory manager was introduced serialized their heap straight
+/// to the start of stable memory. Must be checked before anything touches the memory manager,
+/// which takes over stable memory on first use.
+pub fn is_stable_memory_managed_by_memory_manager() -> bool {
+    if ic_cdk::api::stable::stable64_size() == 0 {
+        return true;
+    }
+
+    let mut magic_bytes = [0; 3];
+    ic_cdk::api::stable::stable64_read(0, &mut magic_bytes);
+    &magic_bytes == b"MGR"
+}
+
+fn get_memory(memory_id: u8) -> Memory {
+    MEMORY_MANAGER.with(|memory_manager_ref_cell| {
+        memory_manager_ref_cell
+            .borrow_mut()
+            .get(MemoryId::new(memory_id))
+    })
+}
+
+// * Heap data serialized across upgrades.
+const UPGRADES_MEMORY_ID: u8 = 0;
+pub fn get_upgrades_memory() -> Memory {
+    get_memory(UPGRADES_MEMORY_ID)
+}
+
+// * Queue of referrer shares of bet winnings yet to be delivered.
+const PENDING_REFERRER_SHARE_SETTLEMENTS_MEMORY_ID: u8 = 1;
+pub fn get_pending_referrer_share_settlements_memory() -> Memory {
+    get_memory(PENDING_REFERRER_SHARE_SETTLEMENTS_MEMORY_ID)
+}
+
+// * Queue of house rake yet to be delivered to the treasury.
+const PENDING_HOUSE_RAKE_SETTLEMENTS_MEMORY_ID: u8 = 2;
+pub fn get_pending_house_rake_settlements_memory() -> Memory {
+    get_memory(PENDING_HOUSE_RAKE_SETTLEMENTS_MEMORY_ID)
+}
+
+// * Queue of collaborator shares of creator commission yet to be delivered.
+const PENDING_COLLABORATOR_COMMISSION_SETTLEMENTS_MEMORY_ID: u8 = 3;
+pub fn get_pending_collaborator_commission_settlements_memory() -> Memory {
+    get_memory(PENDING_COLLABORATOR_COMMISSION_SETTLEMENTS_MEMORY_ID)
+}
+
+// * Every post created on this profile.
+const ALL_CREATED_POSTS_MEMORY_ID: u8 = 4;
+pub fn get_all_created_posts_memory() -> Memory {
+    get_memory(ALL_CREATED_POSTS_MEMORY_ID)
+}
+
+// * Index and entries of the call journal, only written to while the journal is switched on.
+const CALL_JOURNAL_INDEX_MEMORY_ID: u8 = 5;
+pub fn get_call_journal_index_memory() -> Memory {
+    get_memory(CALL_JOURNAL_INDEX_MEMORY_ID)
+}
+
+const CALL_JOURNAL_DATA_MEMORY_ID: u8 = 6;
+pub fn get_call_journal_data_memory() -> Memory {
+    get_memory(CALL_JOURNAL_DATA_MEMORY_ID)
+}
+
+// * Queue of pot insurance top ups the treasury is yet to deliver to winners.
+const PENDING_POT_INSURANCE_TOP_UPS_MEMORY_ID: u8 = 7;
+pub fn get_pending_pot_insurance_top_ups_memory() -> Memory {
+    get_memory(PENDING_POT_INSURANCE_TOP_UPS_MEMORY_ID)
+}
+
+// * Index and entries of the data migrations run while upgrading.
+const MIGRATION_CHANGELOG_INDEX_MEMORY_ID: u8 = 8;
+pub fn get_migration_changelog_index_memory() -> Memory {
+    get_memory(MIGRATION_CHANGELOG_INDEX_MEMORY_ID)
+}
+
+const MIGRATION_CHANGELOG_DATA_MEMORY_ID: u8 = 9;
+pub fn get_migration_changelog_data_memory() -> Memory {
+    get_memory(MIGRATION_CHANGELOG_DATA_MEMORY_ID)
+}
+
+// * Fire and forget calls to other canisters yet to go through, and those that never did.
+const CALL_QUEUE_PENDING_CALLS_MEMORY_ID: u8 = 10;
+pub fn get_call_queue_pending_calls_memory() -> Memory {
+    get_memory(CALL_QUEUE_PENDING_CALLS_MEMORY_ID)
+}
+
+const CALL_QUEUE_DEAD_LETTERED_CALLS_MEMORY_ID: u8 = 11;
+pub fn get_call_queue_dead_lettered_calls_memory() -> Memory {
+    get_memory(CALL_QUEUE_DEAD_LETTERED_CALLS_MEMORY_ID)
+}
+
+// * Profiles following this one, and the profiles it follows.
+const FOLLOWER_SORTED_INDEX_MEMORY_ID: u8 = 12;
+pub fn get_follower_sorted_index_memory() -> Memory {
+    get_memory(FOLLOWER_SORTED_INDEX_MEMORY_ID)
+}
+
+const FOLLOWER_MEMBERS_MEMORY_ID: u8 = 13;
+pub fn get_follower_members_memory() -> Memory {
+    get_memory(FOLLOWER_MEMBERS_MEMORY_ID)
+}
+
+const FOLLOWING_SORTED_INDEX_MEMORY_ID: u8 = 14;
+pub fn get_following_sorted_index_memory() -> Memory {
+    get_memory(FOLLOWING_SORTED_INDEX_MEMORY_ID)
+}
+
+const FOLLOWING_MEMBERS_MEMORY_ID: u8 = 15;
+pub fn get_following_members_memory() -> Memory {
+    get_memory(FOLLOWING_MEMBERS_MEMORY_ID)
+}
+
+// * Every token event of this profile, keyed by transaction id.
+const TOKEN_TRANSACTION_HISTORY_MEMORY_ID: u8 = 16;
+pub fn get_token_transaction_history_memory() -> Memory {
+    get_memory(TOKEN_TRANSACTION_HISTORY_MEMORY_ID)
+}
+
+// * Settlements other canisters delivered that were already credited.
+const RECEIVED_SETTLEMENTS_MEMORY_ID: u8 = 17;
+pub fn get_received_settlements_memory() -> Memory {
+    get_memory(RECEIVED_SETTLEMENTS_MEMORY_ID)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_register_all_memory_regions() {
+        let mut memory_id_registry = MemoryIdRegistry::default();
+
+        assert_eq!(register_all_memory_regions(&mut memory_id_registry), Ok(()));
+        assert_eq!(register_all_memory_regions(&mut memory_id_registry), Ok(()));
+
+        assert_eq!(
+            memory_id_registry.get_name_for_memory_id(PENDING_HOUSE_RAKE_SETTLEMENTS_MEMORY_ID),
+            Some("pending_house_rake_settlements")
+        );
+    }
+}
@@ -1,36 +1,1186 @@
-use std::collections::{BTreeMap, BTreeSet};
+use std::{
+    collections::{BTreeMap, BTreeSet, VecDeque},
+    time::SystemTime,
+};
 
 use candid::{Deserialize, Principal};
 use ic_cdk::api::management_canister::provisional::CanisterId;
 use serde::Serialize;
 use shared_utils::{
-    canister_specific::individual_user_template::types::{
-        configuration::IndividualUserConfiguration, follow::FollowData,
-        hot_or_not::PlacedBetDetail, post::Post, profile::UserProfile, token::TokenBalance,
+    canister_specific::{
+        individual_user_template::types::{
+            access_statistics::AccessStatistics,
+            achievement::{AchievementEvent, Achievements},
+            call_journal::CallJournal,
+            collaborator::CollaboratorCommissionSettlement,
+            comment::PostComments,
+            configuration::IndividualUserConfiguration,
+            cycle_monitor::CycleMonitor,
+            dispute::SlotOutcomeDispute,
+            event_callback::EventCallbacks,
+            feature_usage::{Feature, FeatureUsageStats},
+            follow::{
+                followed_creators_feed::FollowedCreatorsFeedCache,
+                following_feed::FollowingFeedCache, stable_follow_list::StableFollowData,
+                stable_follow_list::StableFollowList, FollowData,
+            },
+            hot_or_not::{
+                betting_stats::BettingStats, outcome_notification::BetOutcomeNotificationScheduler,
+                AggregateStatsBackfillReport, PlacedBetDetail, RoomId, SlotId,
+            },
+            live_betting_activity::LiveBettingActivityStream,
+            migration_changelog::{AppliedMigration, MigrationChangelog},
+            moderation::{DelegatedModeration, ModeratorPermission},
+            post::{stable_post_map::StablePostMap, view_buffer::PostViewBuffer, Post},
+            profile::UserProfile,
+            received_settlements::{
+                ReceivedSettlementKey, ReceivedSettlementType, ReceivedSettlements,
+            },
+            referral::{
+                ReferralCodeDetails, ReferralDetails, ReferralEarningsTotals,
+                ReferrerShareSettlement,
+            },
+            replication::Replication,
+            session_delegate::SessionDelegate,
+            signup_bonus::SignupBonusVesting,
+            token::TokenBalance,
+            token_ledger::TokenLedgerMirror,
+            token_transaction_history::TokenTransactionHistory,
+            tournament::TournamentEnrollment,
+            treasury::{
+                HouseRakeSettlement, PotInsuranceClaim, PotInsuranceLedger,
+                PotInsuranceTopUpSettlement,
+            },
+            versus::{VersusBetPlaced, VersusContest, VersusContestId},
+        },
+        user_index::types::tournament::TournamentId,
     },
-    common::types::{
-        app_primitive_type::PostId, known_principal::KnownPrincipalMap,
-        top_posts::post_score_index::PostScoreIndex,
+    common::{
+        types::{
+            app_primitive_type::PostId,
+            deferred_job::DeferredJobs,
+            event_counter::EventCounters,
+            known_principal::KnownPrincipalMap,
+            memory_id_registry::MemoryIdRegistry,
+            stable_queue::StableQueue,
+            top_posts::post_score_index::PostScoreIndex,
+            utility_token::token_event::{MintEvent, TokenEvent},
+        },
+        utils::{call_queue::CallQueue, hashtag::HashtagAliases},
     },
 };
 
-use self::version_details::VersionDetails;
+use self::{
+    deferred_job::IndividualUserDeferredJob,
+    memory::{
+        get_all_created_posts_memory, get_call_journal_data_memory, get_call_journal_index_memory,
+        get_call_queue_dead_lettered_calls_memory, get_call_queue_pending_calls_memory,
+        get_follower_members_memory, get_follower_sorted_index_memory,
+        get_following_members_memory, get_following_sorted_index_memory,
+        get_migration_changelog_data_memory, get_migration_changelog_index_memory,
+        get_pending_collaborator_commission_settlements_memory,
+        get_pending_house_rake_settlements_memory, get_pending_pot_insurance_top_ups_memory,
+        get_pending_referrer_share_settlements_memory, get_received_settlements_memory,
+        get_token_transaction_history_memory, Memory,
+    },
+    version_details::VersionDetails,
+};
 
+pub mod deferred_job;
+pub mod memory;
 pub mod version_details;
 
-#[derive(Default, Deserialize, Serialize)]
+#[derive(Deserialize, Serialize)]
 pub struct CanisterData {
     // Key is Post ID
-    pub all_created_posts: BTreeMap<u64, Post>,
+    #[serde(skip, default = "init_all_created_posts")]
+    pub all_created_posts: StablePostMap<Memory>,
     pub all_hot_or_not_bets_placed: BTreeMap<(CanisterId, PostId), PlacedBetDetail>,
     pub configuration: IndividualUserConfiguration,
-    pub follow_data: FollowData,
+    #[serde(skip, default = "init_follow_data")]
+    pub follow_data: StableFollowData<Memory>,
     pub known_principal_ids: KnownPrincipalMap,
     pub my_token_balance: TokenBalance,
+    #[serde(skip, default = "init_token_transaction_history")]
+    pub token_transaction_history: TokenTransactionHistory<Memory>,
     pub posts_index_sorted_by_home_feed_score: PostScoreIndex,
     pub posts_index_sorted_by_hot_or_not_feed_score: PostScoreIndex,
     pub principals_i_follow: BTreeSet<Principal>,
     pub principals_that_follow_me: BTreeSet<Principal>,
     pub profile: UserProfile,
     pub version_details: VersionDetails,
+    #[serde(default)]
+    pub aggregate_stats_backfill_report: Option<AggregateStatsBackfillReport>,
+    #[serde(default)]
+    pub tournament_enrollments: BTreeMap<PostId, TournamentEnrollment>,
+    // * Prizes already received, keyed by tournament and rank
+    #[serde(default)]
+    pub received_tournament_prizes: BTreeSet<(TournamentId, u32)>,
+    #[serde(default)]
+    pub my_referrer_details: Option<ReferralDetails>,
+    #[serde(default)]
+    pub my_referee_canister_ids: BTreeSet<CanisterId>,
+    // * None on canisters that predate the totals, until they are backfilled from the
+    // * transaction history
+    #[serde(default)]
+    pub referral_earnings_totals: Option<ReferralEarningsTotals>,
+    #[serde(skip, default = "init_pending_referrer_share_settlements")]
+    pub pending_referrer_share_settlements: StableQueue<ReferrerShareSettlement, Memory>,
+    #[serde(skip, default = "init_pending_house_rake_settlements")]
+    pub pending_house_rake_settlements: StableQueue<HouseRakeSettlement, Memory>,
+    // * Settlements queued on the heap before the stable queues existed. Only ever read
+    // * back once after upgrading, to be moved into the stable queues.
+    #[serde(
+        default,
+        rename = "pending_referrer_share_settlements",
+        skip_serializing
+    )]
+    pub legacy_pending_referrer_share_settlements: BTreeMap<u64, ReferrerShareSettlement>,
+    #[serde(default, rename = "pending_house_rake_settlements", skip_serializing)]
+    pub legacy_pending_house_rake_settlements: BTreeMap<u64, HouseRakeSettlement>,
+    // * Settlements other canisters delivered that were already credited
+    #[serde(skip, default = "init_received_settlements")]
+    pub received_settlements: ReceivedSettlements<Memory>,
+    // * Settlements received before they were kept in stable memory. Only ever read back
+    // * once after upgrading, to be moved into the stable set.
+    #[serde(
+        default,
+        rename = "received_referrer_share_settlements",
+        skip_serializing
+    )]
+    pub legacy_received_referrer_share_settlements:
+        BTreeSet<(CanisterId, PostId, SlotId, RoomId, Principal)>,
+    #[serde(default, rename = "received_house_rake_settlements", skip_serializing)]
+    pub legacy_received_house_rake_settlements: BTreeSet<(CanisterId, PostId, SlotId, RoomId)>,
+    #[serde(default)]
+    pub session_delegates: BTreeMap<Principal, SessionDelegate>,
+    #[serde(default)]
+    pub memory_id_registry: MemoryIdRegistry,
+    // * Set by the user index. Bets placed by this profile end up in phantom rooms
+    #[serde(default)]
+    pub is_shadow_banned: bool,
+    #[serde(skip, default = "init_pending_collaborator_commission_settlements")]
+    pub pending_collaborator_commission_settlements:
+        StableQueue<CollaboratorCommissionSettlement, Memory>,
+    #[serde(
+        default,
+        rename = "received_collaborator_commission_settlements",
+        skip_serializing
+    )]
+    pub legacy_received_collaborator_commission_settlements:
+        BTreeSet<(CanisterId, PostId, SlotId, RoomId)>,
+    // * Bumped whenever a post is recertified, so clients can tell certified snapshots apart
+    #[serde(default)]
+    pub post_details_versions: BTreeMap<PostId, u64>,
+    // * Set by the user index while this canister is being migrated or restored. Queries are
+    // * still served, but update calls are rejected
+    #[serde(default)]
+    pub is_read_only: bool,
+    #[serde(default)]
+    pub post_view_buffer: PostViewBuffer,
+    #[serde(default)]
+    pub last_token_faucet_request_at: Option<SystemTime>,
+    // * Set by the profile owner. Passed along with each of their bets so that the post
+    // * creator's canister pseudonymizes them in listings and exports
+    #[serde(default)]
+    pub is_betting_activity_hidden: bool,
+    // * Bet makers on this profile's posts who hide their betting activity
+    #[serde(default)]
+    pub bet_makers_hiding_betting_activity: BTreeSet<Principal>,
+    // * Random salt for pseudonyms. Empty until generated after install or upgrade
+    #[serde(default)]
+    pub pseudonym_salt: Vec<u8>,
+    #[serde(default)]
+    pub slot_outcome_disputes: BTreeMap<(PostId, SlotId, Principal), SlotOutcomeDispute>,
+    // * Latest posts of the profiles this profile follows, fanned out by their canisters
+    #[serde(default)]
+    pub following_feed_cache: FollowingFeedCache,
+    // * Outcomes yet to be sent to bet makers on this profile's posts
+    #[serde(default)]
+    pub bet_outcome_notification_scheduler: BetOutcomeNotificationScheduler,
+    #[serde(default)]
+    pub feature_usage_stats: FeatureUsageStats,
+    // * Bumped by the user index every time it reinstalls this canister
+    #[serde(default)]
+    pub binding_epoch: u64,
+    // * Binding epochs of other users' canisters as last resolved through the user index
+    #[serde(default)]
+    pub known_binding_epochs: BTreeMap<Principal, u64>,
+    #[serde(default)]
+    pub event_counters: EventCounters,
+    // * Key is the normalized referral code
+    #[serde(default)]
+    pub referral_codes: BTreeMap<String, ReferralCodeDetails>,
+    // * Which of this profile's referral codes each referee signed up with
+    #[serde(default)]
+    pub referral_code_by_referee_canister_id: BTreeMap<Principal, String>,
+    // * Tokens set aside for a basket of bets that is still being placed
+    #[serde(default)]
+    pub escrowed_bet_amount: u64,
+    // * Amounts of bets still being placed through each session delegate, held against its
+    // * daily limit until the post creator's canister has answered
+    #[serde(default)]
+    pub session_delegate_bet_amounts_in_flight: BTreeMap<Principal, u64>,
+    #[serde(skip, default = "init_call_journal")]
+    pub call_journal: CallJournal<Memory>,
+    // * Set by the user index while this canister is flagged for collection because its owner
+    // * never got past onboarding
+    #[serde(default)]
+    pub scheduled_collection_at: Option<SystemTime>,
+    #[serde(default)]
+    pub achievements: Achievements,
+    #[serde(default)]
+    pub deferred_jobs: DeferredJobs<IndividualUserDeferredJob>,
+    // * Versus contests one of this profile's posts takes part in, proposed by either side
+    #[serde(default)]
+    pub versus_contests: BTreeMap<VersusContestId, VersusContest>,
+    #[serde(default)]
+    pub next_versus_contest_index: u64,
+    #[serde(default)]
+    pub versus_bets_placed: BTreeMap<VersusContestId, VersusBetPlaced>,
+    // * None for users who got their whole signup bonus at once, before it started vesting
+    #[serde(default)]
+    pub signup_bonus_vesting: Option<SignupBonusVesting>,
+    // * Comments left on this profile's posts
+    #[serde(default)]
+    pub post_comments: BTreeMap<PostId, PostComments>,
+    // * Kept on the heap as a claim holds a top up for every winner in the room
+    #[serde(default)]
+    pub pending_pot_insurance_claims: VecDeque<PotInsuranceClaim>,
+    // * Only ever filled on the treasury canister
+    #[serde(skip, default = "init_pending_pot_insurance_top_ups")]
+    pub pending_pot_insurance_top_ups: StableQueue<PotInsuranceTopUpSettlement, Memory>,
+    #[serde(default)]
+    pub pot_insurance_ledger: PotInsuranceLedger,
+    // * Bets of this profile already topped up, keyed like all_hot_or_not_bets_placed
+    #[serde(default)]
+    pub received_pot_insurance_top_ups: BTreeSet<(CanisterId, PostId)>,
+    #[serde(default)]
+    pub access_statistics: AccessStatistics,
+    #[serde(skip, default = "init_migration_changelog")]
+    pub migration_changelog: MigrationChangelog<Memory>,
+    // * Third party canisters called back about activity on this canister
+    #[serde(default)]
+    pub event_callbacks: EventCallbacks,
+    // * Standby canister this one streams its state to, or the primary it is a standby of
+    #[serde(default)]
+    pub replication: Replication,
+    #[serde(default)]
+    pub betting_stats: BettingStats,
+    #[serde(skip, default = "init_call_queue")]
+    pub call_queue: CallQueue<Memory>,
+    // * Hashtag merges passed on by the post cache, applied to posts as they are created
+    #[serde(default)]
+    pub hashtag_aliases: HashtagAliases,
+    // * Mirror of `my_token_balance` on the ICRC-1 token ledger
+    #[serde(default)]
+    pub token_ledger_mirror: TokenLedgerMirror,
+    // * Post the next slot compaction pass starts scanning from
+    #[serde(default)]
+    pub slot_compaction_cursor: PostId,
+    // * Betting activity waiting to be published to the web socket gateway
+    #[serde(default)]
+    pub live_betting_activity_stream: LiveBettingActivityStream,
+    // * Merged posts of followed creators, only kept for a short while so it isn't worth
+    // * carrying over an upgrade
+    #[serde(skip)]
+    pub followed_creators_feed_cache: Option<FollowedCreatorsFeedCache>,
+    // * Moderators appointed by the profile owner and the audit log of their actions
+    #[serde(default)]
+    pub delegated_moderation: DelegatedModeration,
+    // * Slots with a tabulation timer set, so that each slot only ever gets one. Timers don't
+    // * survive upgrades, so neither does this
+    #[serde(skip)]
+    pub slots_with_tabulation_timer: BTreeSet<(PostId, SlotId)>,
+    // * Puts the canister in low cycles mode, rejecting new bets and posts, when critically low
+    #[serde(default)]
+    pub cycle_monitor: CycleMonitor,
+    // * Posts kept on the heap before they moved to stable memory, to be moved into the stable
+    // * post map a chunk at a time after upgrading. Whatever is left of them when upgraded
+    // * again is kept for the next wasm to carry on with.
+    #[serde(
+        default,
+        rename = "all_created_posts",
+        skip_serializing_if = "BTreeMap::is_empty"
+    )]
+    pub legacy_all_created_posts: BTreeMap<u64, Post>,
+    // * Follow lists kept on the heap before they moved to stable memory. Only ever read back
+    // * once after upgrading, to be moved into the stable follow lists.
+    #[serde(default, rename = "follow_data", skip_serializing)]
+    pub legacy_follow_data: FollowData,
+}
+
+impl Default for CanisterData {
+    fn default() -> Self {
+        Self {
+            all_created_posts: init_all_created_posts(),
+            all_hot_or_not_bets_placed: BTreeMap::default(),
+            configuration: IndividualUserConfiguration::default(),
+            follow_data: init_follow_data(),
+            known_principal_ids: KnownPrincipalMap::default(),
+            my_token_balance: TokenBalance::default(),
+            token_transaction_history: init_token_transaction_history(),
+            posts_index_sorted_by_home_feed_score: PostScoreIndex::default(),
+            posts_index_sorted_by_hot_or_not_feed_score: PostScoreIndex::default(),
+            principals_i_follow: BTreeSet::default(),
+            principals_that_follow_me: BTreeSet::default(),
+            profile: UserProfile::default(),
+            version_details: VersionDetails::default(),
+            aggregate_stats_backfill_report: None,
+            tournament_enrollments: BTreeMap::default(),
+            received_tournament_prizes: BTreeSet::default(),
+            my_referrer_details: None,
+            my_referee_canister_ids: BTreeSet::default(),
+            referral_earnings_totals: Some(ReferralEarningsTotals::default()),
+            pending_referrer_share_settlements: init_pending_referrer_share_settlements(),
+            pending_house_rake_settlements: init_pending_house_rake_settlements(),
+            legacy_pending_referrer_share_settlements: BTreeMap::default(),
+            legacy_pending_house_rake_settlements: BTreeMap::default(),
+            received_settlements: init_received_settlements(),
+            legacy_received_referrer_share_settlements: BTreeSet::default(),
+            legacy_received_house_rake_settlements: BTreeSet::default(),
+            session_delegates: BTreeMap::default(),
+            memory_id_registry: MemoryIdRegistry::default(),
+            is_shadow_banned: false,
+            pending_collaborator_commission_settlements:
+                init_pending_collaborator_commission_settlements(),
+            legacy_received_collaborator_commission_settlements: BTreeSet::default(),
+            post_details_versions: BTreeMap::default(),
+            is_read_only: false,
+            post_view_buffer: PostViewBuffer::default(),
+            last_token_faucet_request_at: None,
+            is_betting_activity_hidden: false,
+            bet_makers_hiding_betting_activity: BTreeSet::default(),
+            pseudonym_salt: Vec::default(),
+            slot_outcome_disputes: BTreeMap::default(),
+            following_feed_cache: FollowingFeedCache::default(),
+            bet_outcome_notification_scheduler: BetOutcomeNotificationScheduler::default(),
+            feature_usage_stats: FeatureUsageStats::default(),
+            binding_epoch: 0,
+            known_binding_epochs: BTreeMap::default(),
+            event_counters: EventCounters::default(),
+            referral_codes: BTreeMap::default(),
+            referral_code_by_referee_canister_id: BTreeMap::default(),
+            escrowed_bet_amount: 0,
+            session_delegate_bet_amounts_in_flight: BTreeMap::default(),
+            call_journal: init_call_journal(),
+            scheduled_collection_at: None,
+            achievements: Achievements::default(),
+            deferred_jobs: DeferredJobs::default(),
+            versus_contests: BTreeMap::default(),
+            next_versus_contest_index: 0,
+            versus_bets_placed: BTreeMap::default(),
+            signup_bonus_vesting: None,
+            post_comments: BTreeMap::default(),
+            pending_pot_insurance_claims: VecDeque::default(),
+            pending_pot_insurance_top_ups: init_pending_pot_insurance_top_ups(),
+            pot_insurance_ledger: PotInsuranceLedger::default(),
+            received_pot_insurance_top_ups: BTreeSet::default(),
+            access_statistics: AccessStatistics::default(),
+            migration_changelog: init_migration_changelog(),
+            event_callbacks: EventCallbacks::default(),
+            replication: Replication::default(),
+            betting_stats: BettingStats::default(),
+            call_queue: init_call_queue(),
+            hashtag_aliases: HashtagAliases::default(),
+            token_ledger_mirror: TokenLedgerMirror::default(),
+            slot_compaction_cursor: 0,
+            live_betting_activity_stream: LiveBettingActivityStream::default(),
+            followed_creators_feed_cache: None,
+            delegated_moderation: DelegatedModeration::default(),
+            slots_with_tabulation_timer: BTreeSet::default(),
+            cycle_monitor: CycleMonitor::default(),
+            legacy_all_created_posts: BTreeMap::default(),
+            legacy_follow_data: FollowData::default(),
+        }
+    }
+}
+
+impl CanisterData {
+    /// Returns the number of settlements moved
+    pub fn move_legacy_pending_settlements_to_stable_queues(&mut self) -> u64 {
+        let mut settlements_moved = 0;
+
+        std::mem::take(&mut self.legacy_pending_referrer_share_settlements)
+            .into_values()
+            .for_each(|settlement| {
+                self.pending_referrer_share_settlements
+                    .push_back(settlement);
+                settlements_moved += 1;
+            });
+        std::mem::take(&mut self.legacy_pending_house_rake_settlements)
+            .into_values()
+            .for_each(|settlement| {
+                self.pending_house_rake_settlements.push_back(settlement);
+                settlements_moved += 1;
+            });
+
+        settlements_moved
+    }
+
+    /// Moves the settlements received before they were kept in stable memory into the stable
+    /// set. Returns the number of settlements moved.
+    pub fn move_legacy_received_settlements_to_stable_memory(&mut self) -> u64 {
+        let mut settlements_moved = 0;
+
+        std::mem::take(&mut self.legacy_received_referrer_share_settlements)
+            .into_iter()
+            .for_each(
+                |(post_canister_id, post_id, slot_id, room_id, referee_principal_id)| {
+                    self.received_settlements.insert(ReceivedSettlementKey {
+                        settlement_type: ReceivedSettlementType::ReferrerShare,
+                        post_canister_id,
+                        post_id,
+                        slot_id,
+                        room_id,
+                        referee_principal_id: Some(referee_principal_id),
+                    });
+                    settlements_moved += 1;
+                },
+            );
+        std::mem::take(&mut self.legacy_received_house_rake_settlements)
+            .into_iter()
+            .for_each(|(post_canister_id, post_id, slot_id, room_id)| {
+                self.received_settlements.insert(ReceivedSettlementKey {
+                    settlement_type: ReceivedSettlementType::HouseRake,
+                    post_canister_id,
+                    post_id,
+                    slot_id,
+                    room_id,
+                    referee_principal_id: None,
+                });
+                settlements_moved += 1;
+            });
+        std::mem::take(&mut self.legacy_received_collaborator_commission_settlements)
+            .into_iter()
+            .for_each(|(post_canister_id, post_id, slot_id, room_id)| {
+                self.received_settlements.insert(ReceivedSettlementKey {
+                    settlement_type: ReceivedSettlementType::CollaboratorCommission,
+                    post_canister_id,
+                    post_id,
+                    slot_id,
+                    room_id,
+                    referee_principal_id: None,
+                });
+                settlements_moved += 1;
+            });
+
+        settlements_moved
+    }
+
+    /// Moves posts kept on the heap into the stable post map, lowest post id first, for as
+    /// long as `is_within_budget` holds. At least one post is moved per call. Returns the
+    /// number of posts moved and, if any are left on the heap, the id of the last one moved.
+    pub fn move_legacy_posts_to_stable_post_map(
+        &mut self,
+        is_within_budget: impl Fn() -> bool,
+    ) -> (u64, Option<PostId>) {
+        let mut posts_moved = 0;
+        let mut last_post_id_moved = None;
+
+        while posts_moved == 0 || is_within_budget() {
+            let Some((post_id, post)) = self.legacy_all_created_posts.pop_first() else {
+                break;
+            };
+            self.all_created_posts.insert(post_id, post);
+            posts_moved += 1;
+            last_post_id_moved = Some(post_id);
+        }
+
+        if self.legacy_all_created_posts.is_empty() {
+            return (posts_moved, None);
+        }
+
+        (posts_moved, last_post_id_moved)
+    }
+
+    /// Returns the number of follow entries moved
+    pub fn move_legacy_follow_data_to_stable_follow_lists(&mut self) -> u64 {
+        let legacy_follow_data = std::mem::take(&mut self.legacy_follow_data);
+
+        self.follow_data
+            .follower
+            .move_legacy_entries(legacy_follow_data.follower)
+            + self
+                .follow_data
+                .following
+                .move_legacy_entries(legacy_follow_data.following)
+    }
+
+    /// Returns the number of token transactions moved. They keep their transaction ids
+    pub fn move_legacy_token_transactions_to_stable_history(&mut self) -> u64 {
+        let mut transactions_moved = 0;
+
+        std::mem::take(&mut self.my_token_balance.utility_token_transaction_history)
+            .into_iter()
+            .for_each(|(transaction_id, token_event)| {
+                self.token_transaction_history
+                    .insert(transaction_id, token_event);
+                transactions_moved += 1;
+            });
+
+        transactions_moved
+    }
+
+    /// Works out the betting stats of a canister that had bets placed before they were kept.
+    /// Returns the number of bets gone over.
+    pub fn backfill_betting_stats(&mut self) -> u64 {
+        if self.betting_stats.get_total_bets() > 0 || self.all_hot_or_not_bets_placed.is_empty() {
+            return 0;
+        }
+
+        self.betting_stats = BettingStats::from_bets(self.all_hot_or_not_bets_placed.values());
+
+        self.all_hot_or_not_bets_placed.len() as u64
+    }
+
+    /// Works out the referral earnings totals of a canister that had referral earnings
+    /// recorded before they were kept. Returns the number of token events gone over.
+    pub fn backfill_referral_earnings_totals(&mut self) -> u64 {
+        if self.referral_earnings_totals.is_some() {
+            return 0;
+        }
+
+        let mut referral_earnings_totals = ReferralEarningsTotals::default();
+        let mut token_events_gone_over = 0;
+        self.token_transaction_history
+            .iter()
+            .for_each(|(_, token_event)| {
+                referral_earnings_totals
+                    .record_token_event(&token_event, self.profile.principal_id.as_ref());
+                token_events_gone_over += 1;
+            });
+        self.referral_earnings_totals = Some(referral_earnings_totals);
+
+        token_events_gone_over
+    }
+
+    /// Runs a data migration while upgrading, recording it in the migration changelog if it
+    /// had anything to migrate
+    pub fn apply_migration(
+        &mut self,
+        migration_id: &str,
+        applied_at: SystemTime,
+        get_instruction_count: impl Fn() -> u64,
+        migration: impl FnOnce(&mut CanisterData) -> Result<u64, Vec<String>>,
+    ) {
+        let instruction_count_before = get_instruction_count();
+        let (entities_migrated, errors) = match migration(self) {
+            Ok(entities_migrated) => (entities_migrated, vec![]),
+            Err(errors) => (0, errors),
+        };
+
+        if entities_migrated == 0 && errors.is_empty() {
+            return;
+        }
+
+        self.migration_changelog.record(&AppliedMigration {
+            migration_id: migration_id.to_string(),
+            version_number: self.version_details.version_number,
+            applied_at,
+            instructions_used: get_instruction_count().saturating_sub(instruction_count_before),
+            entities_migrated,
+            errors,
+            progress_marker: None,
+        });
+    }
+
+    /// Runs a chunk of a data migration spread over several messages, recording how far it
+    /// got in the migration changelog. The chunk returns the number of entities it migrated
+    /// and the key of the last one, or None for the key once nothing is left to migrate.
+    /// Returns true once the migration is complete.
+    pub fn apply_migration_chunk(
+        &mut self,
+        migration_id: &str,
+        applied_at: SystemTime,
+        get_instruction_count: impl Fn() -> u64,
+        migration_chunk: impl FnOnce(&mut CanisterData) -> (u64, Option<u64>),
+    ) -> bool {
+        let instruction_count_before = get_instruction_count();
+        let (entities_migrated, progress_marker) = migration_chunk(self);
+
+        if entities_migrated > 0 {
+            self.migration_changelog.record(&AppliedMigration {
+                migration_id: migration_id.to_string(),
+                version_number: self.version_details.version_number,
+                applied_at,
+                instructions_used: get_instruction_count().saturating_sub(instruction_count_before),
+                entities_migrated,
+                errors: vec![],
+                progress_marker,
+            });
+        }
+
+        progress_marker.is_none()
+    }
+
+    /// Applies the event to the token balance and referral earnings totals, and records it in
+    /// the transaction history
+    pub fn handle_token_event(&mut self, token_event: TokenEvent) {
+        self.my_token_balance.apply_token_event(&token_event);
+        if let Some(referral_earnings_totals) = self.referral_earnings_totals.as_mut() {
+            referral_earnings_totals
+                .record_token_event(&token_event, self.profile.principal_id.as_ref());
+        }
+        self.token_transaction_history.append(token_event);
+    }
+
+    /// Awards the achievements `event` completes and mints their token rewards
+    pub fn handle_achievement_event(
+        &mut self,
+        event: &AchievementEvent,
+        current_time: &SystemTime,
+    ) {
+        self.achievements
+            .handle_event(event, current_time)
+            .into_iter()
+            .filter_map(|badge| {
+                badge
+                    .reward_amount
+                    .map(|reward_amount| (badge.achievement, reward_amount))
+            })
+            .for_each(|(achievement, reward_amount)| {
+                self.handle_token_event(TokenEvent::Mint {
+                    amount: reward_amount,
+                    details: MintEvent::AchievementReward { achievement },
+                    timestamp: *current_time,
+                });
+            });
+    }
+
+    /// Counts towards the daily feature usage pushed to the user index, and vests the next
+    /// tranche of the signup bonus if the profile owner hasn't been active yet today
+    pub fn record_feature_usage(&mut self, feature: Feature, current_time: &SystemTime) {
+        self.feature_usage_stats.record(feature, current_time);
+
+        let Some(signup_bonus_vesting) = self.signup_bonus_vesting.as_mut() else {
+            return;
+        };
+        let Some(vested_amount) = signup_bonus_vesting.vest_for_activity(current_time) else {
+            return;
+        };
+        let details = MintEvent::SignupBonusVested {
+            number_of_days_vested: signup_bonus_vesting.number_of_days_vested,
+            number_of_vesting_days: signup_bonus_vesting.number_of_vesting_days,
+        };
+
+        self.handle_token_event(TokenEvent::Mint {
+            amount: vested_amount,
+            details,
+            timestamp: *current_time,
+        });
+    }
+
+    /// `hide_betting_activity` is None for bets from canisters that predate the setting, which
+    /// leave the bet maker's last known preference as is
+    pub fn record_betting_activity_privacy(
+        &mut self,
+        bet_maker_principal_id: &Principal,
+        hide_betting_activity: Option<bool>,
+    ) {
+        match hide_betting_activity {
+            Some(true) => {
+                self.bet_makers_hiding_betting_activity
+                    .insert(*bet_maker_principal_id);
+            }
+            Some(false) => {
+                self.bet_makers_hiding_betting_activity
+                    .remove(bet_maker_principal_id);
+            }
+            None => {}
+        }
+    }
+
+    /// The profile owner can do everything a moderator can
+    pub fn is_allowed_to_moderate(
+        &self,
+        principal_id: &Principal,
+        permission: ModeratorPermission,
+    ) -> bool {
+        self.profile.principal_id == Some(*principal_id)
+            || self
+                .delegated_moderation
+                .has_permission(principal_id, permission)
+    }
+}
+
+fn init_all_created_posts() -> StablePostMap<Memory> {
+    StablePostMap::init(get_all_created_posts_memory())
+}
+
+fn init_token_transaction_history() -> TokenTransactionHistory<Memory> {
+    TokenTransactionHistory::init(get_token_transaction_history_memory())
+}
+
+fn init_received_settlements() -> ReceivedSettlements<Memory> {
+    ReceivedSettlements::init(get_received_settlements_memory())
+}
+
+fn init_pending_referrer_share_settlements() -> StableQueue<ReferrerShareSettlement, Memory> {
+    StableQueue::init(get_pending_referrer_share_settlements_memory())
+}
+
+fn init_pending_house_rake_settlements() -> StableQueue<HouseRakeSettlement, Memory> {
+    StableQueue::init(get_pending_house_rake_settlements_memory())
+}
+
+fn init_pending_collaborator_commission_settlements(
+) -> StableQueue<CollaboratorCommissionSettlement, Memory> {
+    StableQueue::init(get_pending_collaborator_commission_settlements_memory())
+}
+
+fn init_pending_pot_insurance_top_ups() -> StableQueue<PotInsuranceTopUpSettlement, Memory> {
+    StableQueue::init(get_pending_pot_insurance_top_ups_memory())
+}
+
+fn init_call_journal() -> CallJournal<Memory> {
+    CallJournal::init(
+        get_call_journal_index_memory(),
+        get_call_journal_data_memory(),
+    )
+}
+
+fn init_migration_changelog() -> MigrationChangelog<Memory> {
+    MigrationChangelog::init(
+        get_migration_changelog_index_memory(),
+        get_migration_changelog_data_memory(),
+    )
+}
+
+fn init_follow_data() -> StableFollowData<Memory> {
+    StableFollowData {
+        follower: StableFollowList::init(
+            get_follower_sorted_index_memory(),
+            get_follower_members_memory(),
+        ),
+        following: StableFollowList::init(
+            get_following_sorted_index_memory(),
+            get_following_members_memory(),
+        ),
+    }
+}
+
+fn init_call_queue() -> CallQueue<Memory> {
+    CallQueue::init(
+        get_call_queue_pending_calls_memory(),
+        get_call_queue_dead_lettered_calls_memory(),
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use shared_utils::canister_specific::individual_user_template::types::{
+        follow::FollowEntryDetail,
+        hot_or_not::{BetDirection, BetOutcomeForBetMaker},
+        post::PostDetailsFromFrontend,
+        signup_bonus::SignupBonusVestingConfig,
+    };
+    use test_utils::setup::test_constants::{
+        get_mock_user_alice_canister_id, get_mock_user_alice_principal_id,
+        get_mock_user_bob_canister_id, get_mock_user_bob_principal_id,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_move_legacy_pending_settlements_to_stable_queues() {
+        let mut canister_data = CanisterData::default();
+        let referrer_share_settlement = ReferrerShareSettlement {
+            referrer_canister_id: get_mock_user_alice_canister_id(),
+            referee_principal_id: get_mock_user_bob_principal_id(),
+            referee_canister_id: None,
+            post_canister_id: get_mock_user_bob_canister_id(),
+            post_id: 0,
+            slot_id: 1,
+            room_id: 1,
+            share_amount: 18,
+            number_of_failed_attempts: 2,
+        };
+        canister_data
+            .legacy_pending_referrer_share_settlements
+            .insert(4, referrer_share_settlement.clone());
+
+        assert_eq!(
+            canister_data.move_legacy_pending_settlements_to_stable_queues(),
+            1
+        );
+
+        assert!(canister_data
+            .legacy_pending_referrer_share_settlements
+            .is_empty());
+        assert_eq!(
+            canister_data
+                .pending_referrer_share_settlements
+                .iter()
+                .map(|(_, settlement)| settlement)
+                .collect::<Vec<_>>(),
+            vec![referrer_share_settlement]
+        );
+        assert!(canister_data.pending_house_rake_settlements.is_empty());
+    }
+
+    #[test]
+    fn test_move_legacy_received_settlements_to_stable_memory() {
+        let mut canister_data = CanisterData::default();
+        canister_data
+            .legacy_received_referrer_share_settlements
+            .insert((
+                get_mock_user_bob_canister_id(),
+                0,
+                1,
+                1,
+                get_mock_user_alice_principal_id(),
+            ));
+        canister_data
+            .legacy_received_house_rake_settlements
+            .insert((get_mock_user_bob_canister_id(), 0, 1, 1));
+        canister_data
+            .legacy_received_collaborator_commission_settlements
+            .insert((get_mock_user_bob_canister_id(), 0, 1, 1));
+
+        assert_eq!(
+            canister_data.move_legacy_received_settlements_to_stable_memory(),
+            3
+        );
+
+        assert!(canister_data
+            .legacy_received_referrer_share_settlements
+            .is_empty());
+        assert_eq!(canister_data.received_settlements.len(), 3);
+        assert!(canister_data
+            .received_settlements
+            .contains(&ReceivedSettlementKey {
+                settlement_type: ReceivedSettlementType::ReferrerShare,
+                post_canister_id: get_mock_user_bob_canister_id(),
+                post_id: 0,
+                slot_id: 1,
+                room_id: 1,
+                referee_principal_id: Some(get_mock_user_alice_principal_id()),
+            }));
+    }
+
+    #[test]
+    fn test_move_legacy_posts_to_stable_post_map() {
+        let mut canister_data = CanisterData::default();
+        (0..3).for_each(|post_id| {
+            canister_data.legacy_all_created_posts.insert(
+                post_id,
+                Post::new(
+                    post_id,
+                    &PostDetailsFromFrontend {
+                        description: "Doggos and puppers".into(),
+                        hashtags: vec!["doggo".into(), "pupper".into()],
+                        video_uid: "abcd#1234".into(),
+                        creator_consent_for_inclusion_in_hot_or_not: true,
+                        collaborator_shares: None,
+                        title: None,
+                        category: None,
+                    },
+                    &SystemTime::now(),
+                ),
+            );
+        });
+
+        // * the budget runs out after the second post
+        let posts_checked = std::cell::Cell::new(0);
+        let is_within_budget = || {
+            posts_checked.set(posts_checked.get() + 1);
+            posts_checked.get() < 2
+        };
+        let move_legacy_posts_chunk = |canister_data: &mut CanisterData| {
+            canister_data.move_legacy_posts_to_stable_post_map(is_within_budget)
+        };
+
+        assert!(!canister_data.apply_migration_chunk(
+            "move_posts_to_stable_post_map",
+            SystemTime::UNIX_EPOCH,
+            || 0,
+            move_legacy_posts_chunk,
+        ));
+        assert_eq!(canister_data.legacy_all_created_posts.len(), 1);
+        assert_eq!(canister_data.all_created_posts.len(), 2);
+
+        posts_checked.set(0);
+        assert!(canister_data.apply_migration_chunk(
+            "move_posts_to_stable_post_map",
+            SystemTime::UNIX_EPOCH,
+            || 0,
+            move_legacy_posts_chunk,
+        ));
+        assert_eq!(
+            canister_data
+                .migration_changelog
+                .get_page(0, 10)
+                .items
+                .into_iter()
+                .map(|applied_migration| (
+                    applied_migration.entities_migrated,
+                    applied_migration.progress_marker
+                ))
+                .collect::<Vec<_>>(),
+            vec![(2, Some(1)), (1, None)]
+        );
+
+        assert!(canister_data.legacy_all_created_posts.is_empty());
+        assert_eq!(canister_data.all_created_posts.len(), 3);
+        assert_eq!(
+            canister_data.all_created_posts.keys().collect::<Vec<_>>(),
+            vec![0, 1, 2]
+        );
+        assert!(canister_data
+            .all_created_posts
+            .get(&2)
+            .unwrap()
+            .hot_or_not_details
+            .is_some());
+    }
+
+    #[test]
+    fn test_move_legacy_follow_data_to_stable_follow_lists() {
+        let mut canister_data = CanisterData::default();
+        let follower = FollowEntryDetail {
+            principal_id: get_mock_user_bob_principal_id(),
+            canister_id: get_mock_user_bob_canister_id(),
+        };
+        canister_data
+            .legacy_follow_data
+            .follower
+            .sorted_index
+            .insert(3, follower.clone());
+        canister_data
+            .legacy_follow_data
+            .follower
+            .members
+            .insert(follower.clone(), 3);
+
+        assert_eq!(
+            canister_data.move_legacy_follow_data_to_stable_follow_lists(),
+            1
+        );
+
+        assert!(canister_data
+            .legacy_follow_data
+            .follower
+            .sorted_index
+            .is_empty());
+        assert!(canister_data.follow_data.follower.contains(&follower));
+        assert_eq!(
+            canister_data
+                .follow_data
+                .follower
+                .get_export_chunk(0, 10)
+                .entries,
+            vec![(3, follower)]
+        );
+        assert!(canister_data.follow_data.following.is_empty());
+    }
+
+    #[test]
+    fn test_move_legacy_token_transactions_to_stable_history() {
+        let mut canister_data = CanisterData::default();
+        (0..3).for_each(|_| {
+            canister_data
+                .my_token_balance
+                .handle_token_event(TokenEvent::Burn)
+        });
+
+        assert_eq!(
+            canister_data.move_legacy_token_transactions_to_stable_history(),
+            3
+        );
+
+        assert!(canister_data
+            .my_token_balance
+            .utility_token_transaction_history
+            .is_empty());
+        assert_eq!(
+            canister_data
+                .token_transaction_history
+                .iter()
+                .map(|(transaction_id, _)| transaction_id)
+                .collect::<Vec<_>>(),
+            vec![1, 2, 3]
+        );
+
+        // * later events carry on from the last moved transaction id
+        canister_data.handle_token_event(TokenEvent::Burn);
+        assert_eq!(
+            canister_data
+                .token_transaction_history
+                .last_transaction_id(),
+            Some(4)
+        );
+    }
+
+    #[test]
+    fn test_apply_migration() {
+        let mut canister_data = CanisterData::default();
+        canister_data.version_details.version_number = 7;
+        let instruction_count = std::cell::Cell::new(100);
+
+        // * nothing to migrate, nothing recorded
+        canister_data.apply_migration(
+            "move_posts_to_stable_post_map",
+            SystemTime::UNIX_EPOCH,
+            || instruction_count.get(),
+            |_| Ok(0),
+        );
+        assert!(canister_data.migration_changelog.is_empty());
+
+        canister_data.apply_migration(
+            "move_posts_to_stable_post_map",
+            SystemTime::UNIX_EPOCH,
+            || instruction_count.get(),
+            |_| {
+                instruction_count.set(350);
+                Ok(3)
+            },
+        );
+        canister_data.apply_migration(
+            "move_pending_settlements_to_stable_queues",
+            SystemTime::UNIX_EPOCH,
+            || instruction_count.get(),
+            |_| Err(vec!["Settlement 4 could not be decoded".to_string()]),
+        );
+
+        assert_eq!(
+            canister_data.migration_changelog.get_page(0, 10).items,
+            vec![
+                AppliedMigration {
+                    migration_id: "move_posts_to_stable_post_map".to_string(),
+                    version_number: 7,
+                    applied_at: SystemTime::UNIX_EPOCH,
+                    instructions_used: 250,
+                    entities_migrated: 3,
+                    errors: vec![],
+                    progress_marker: None,
+                },
+                AppliedMigration {
+                    migration_id: "move_pending_settlements_to_stable_queues".to_string(),
+                    version_number: 7,
+                    applied_at: SystemTime::UNIX_EPOCH,
+                    instructions_used: 0,
+                    entities_migrated: 0,
+                    errors: vec!["Settlement 4 could not be decoded".to_string()],
+                    progress_marker: None,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_backfill_betting_stats() {
+        let mut canister_data = CanisterData::default();
+        assert_eq!(canister_data.backfill_betting_stats(), 0);
+
+        (0..2).for_each(|post_id| {
+            canister_data.all_hot_or_not_bets_placed.insert(
+                (get_mock_user_alice_canister_id(), post_id),
+                PlacedBetDetail {
+                    canister_id: get_mock_user_alice_canister_id(),
+                    post_id,
+                    slot_id: 1,
+                    room_id: 1,
+                    amount_bet: 100,
+                    bet_direction: BetDirection::Hot,
+                    bet_placed_at: SystemTime::UNIX_EPOCH,
+                    outcome_received: BetOutcomeForBetMaker::Won(180),
+                },
+            );
+        });
+
+        assert_eq!(canister_data.backfill_betting_stats(), 2);
+        assert_eq!(canister_data.betting_stats.get_summary().total_won, 360);
+        // * stats already kept are left alone
+        assert_eq!(canister_data.backfill_betting_stats(), 0);
+    }
+
+    #[test]
+    fn test_record_betting_activity_privacy() {
+        let mut canister_data = CanisterData::default();
+        let bob = get_mock_user_bob_principal_id();
+
+        canister_data.record_betting_activity_privacy(&bob, None);
+        assert!(canister_data.bet_makers_hiding_betting_activity.is_empty());
+
+        canister_data.record_betting_activity_privacy(&bob, Some(true));
+        assert!(canister_data
+            .bet_makers_hiding_betting_activity
+            .contains(&bob));
+
+        // * bets from a canister that predates the setting keep the last known preference
+        canister_data.record_betting_activity_privacy(&bob, None);
+        assert!(canister_data
+            .bet_makers_hiding_betting_activity
+            .contains(&bob));
+
+        canister_data.record_betting_activity_privacy(&bob, Some(false));
+        assert!(canister_data.bet_makers_hiding_betting_activity.is_empty());
+    }
+
+    #[test]
+    fn test_record_feature_usage_vests_signup_bonus() {
+        let mut canister_data = CanisterData::default();
+        let signed_up_at = SystemTime::UNIX_EPOCH;
+
+        // * users from before vesting have nothing left to vest
+        canister_data.record_feature_usage(Feature::BetPlaced, &signed_up_at);
+        assert_eq!(canister_data.my_token_balance.utility_token_balance, 0);
+
+        canister_data.signup_bonus_vesting = Some(SignupBonusVesting::new(
+            &SignupBonusVestingConfig {
+                total_amount: 1000,
+                unlocked_at_signup_percentage: 20,
+                number_of_vesting_days: 8,
+            },
+            &signed_up_at,
+        ));
+        canister_data.record_feature_usage(Feature::BetPlaced, &signed_up_at);
+        canister_data.record_feature_usage(Feature::PostCreated, &signed_up_at);
+
+        assert_eq!(canister_data.my_token_balance.utility_token_balance, 100);
+        assert_eq!(
+            canister_data
+                .feature_usage_stats
+                .get_usage_count(0, Feature::BetPlaced),
+            2
+        );
+        assert_eq!(
+            canister_data
+                .signup_bonus_vesting
+                .as_ref()
+                .unwrap()
+                .get_pending_amount(),
+            700
+        );
+    }
+
+    #[test]
+    fn test_handle_achievement_event_mints_rewards_once() {
+        let mut canister_data = CanisterData::default();
+        let current_time = SystemTime::UNIX_EPOCH;
+
+        canister_data.handle_achievement_event(
+            &AchievementEvent::BetPlaced { bets_placed: 1 },
+            &current_time,
+        );
+        canister_data.handle_achievement_event(
+            &AchievementEvent::FollowerGained {
+                follower_count: 1_000,
+            },
+            &current_time,
+        );
+        canister_data.handle_achievement_event(
+            &AchievementEvent::FollowerGained {
+                follower_count: 1_001,
+            },
+            &current_time,
+        );
+
+        assert_eq!(canister_data.achievements.badges.len(), 2);
+        // * only the follower badge carries a reward
+        assert_eq!(canister_data.my_token_balance.utility_token_balance, 1_000);
+        assert_eq!(canister_data.my_token_balance.lifetime_earnings, 1_000);
+        assert_eq!(canister_data.token_transaction_history.len(), 1);
+    }
 }
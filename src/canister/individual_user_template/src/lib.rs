@@ -1,4 +1,4 @@
-use std::cell::RefCell;
+use std::{cell::RefCell, time::SystemTime};
 
 use api::{
     follow::update_profiles_that_follow_me_toggle_list_with_specified_profile::FollowerArg,
@@ -7,30 +7,98 @@ use api::{
 use candid::{export_service, Principal};
 use data_model::CanisterData;
 use ic_cdk::api::management_canister::provisional::CanisterId;
+#[cfg(feature = "call_failure_injection")]
+use shared_utils::common::utils::call_failure_injection::{
+    CallFailureInjectionConfig, CallFailureInjectionStatus,
+};
 use shared_utils::{
     canister_specific::individual_user_template::types::{
+        access_statistics::{AnomalousCaller, CallerAccessStatistic},
         arg::{FolloweeArg, IndividualUserTemplateInitArgs, PlaceBetArg},
+        call_journal::CallJournalEntry,
+        collaborator::CollaboratorCommissionSettlement,
+        comment::{CommentDetailsForFrontend, CommentId, CommentModerationSummary},
+        deferred_job::{DeferredJobRequest, DeferredJobResultItem},
+        dispute::SlotOutcomeRecalculation,
         error::{
-            BetOnCurrentlyViewingPostError, FollowAnotherUserProfileError,
-            GetPostsOfUserProfileError,
+            BackfillAggregateStatsError, BetOnCurrentlyViewingPostError, BetOnMultiplePostsError,
+            BoostPostError, ClearPostReportsError, CommentError, CreateReferralCodeError,
+            DisputeSlotOutcomeError, EnrollPostInTournamentError, EventCallbackError,
+            ExportTokenTransactionsError, FollowAnotherUserProfileError, FollowGraphMigrationError,
+            GetAccessStatisticsError, GetAppliedMigrationsError, GetBetTraceError,
+            GetBetsForPostError, GetCallJournalEntriesError, GetCertifiedPostDetailsError,
+            GetCreatorCommissionReportError, GetDeadLetteredCallsError, GetFollowingFeedError,
+            GetPostsOfFollowedCreatorsError, GetPostsOfUserProfileError, GetReferralCodesError,
+            GetReferralEarningsError, ModerationError, PotInsuranceClaimError,
+            RecalculateSlotOutcomeError, ReplicationError, ReportPostError,
+            RequestTokensFromFaucetError, RetabulateSlotOutcomeError, RevokeHotOrNotConsentError,
+            SessionDelegateError, UpdateBettingActivityPrivacyError,
+            UpdatePostMinimumBetAmountError, VersusContestError,
+        },
+        event_callback::{EventCallbackRegistration, RegisterEventCallbackArg},
+        follow::{
+            following_feed::{FollowingFeedItem, PostFromFollowee},
+            FollowEntryDetail, FollowEntryId, FollowListExportChunk, FollowListImportSummary,
+            FollowListType,
         },
-        follow::{FollowEntryDetail, FollowEntryId},
-        hot_or_not::{BetOutcomeForBetMaker, BettingStatus, PlacedBetDetail},
+        hot_or_not::{
+            bet_eligibility::BetEligibility,
+            bet_trace::{BettorSideBetTrace, PostSideBetTrace},
+            betting_stats::BettingStatsSummary,
+            AggregateStatsBackfillReport, BetDirection, BetOutcomeForBetMaker, BettingStatus,
+            PlacedBetDetail, PostBetAnalytics, PostBetHistoryEntry, SlotId,
+            UserStatusForSpecificHotOrNotPost,
+        },
+        live_betting_activity::LiveBettingActivityStreamStatus,
+        migration_changelog::AppliedMigration,
+        moderation::{ModerationAuditLogEntry, Moderator, ModeratorPermission},
+        onboarding::OnboardingStatus,
         post::{
-            Post, PostDetailsForFrontend, PostDetailsFromFrontend, PostViewDetailsFromFrontend,
+            projection::{PostField, ProjectedPostDetails},
+            CertifiedPostDetailsForFrontend, Post, PostBoost, PostDetailsForFrontend,
+            PostDetailsFromFrontend, PostViewDetailsFromFrontend,
         },
+        post_upgrade_health::PostUpgradeHealthReport,
         profile::{
             UserProfile, UserProfileDetailsForFrontend, UserProfileUpdateDetailsFromFrontend,
         },
+        referral::{
+            ReferralCodeDetails, ReferralDetails, ReferralEarnings, ReferrerShareSettlement,
+        },
+        replication::{ReplicationDelta, ReplicationStatus, ReplicationUpdate},
+        session_delegate::{RegisterSessionDelegateArg, SessionDelegate},
+        signup_bonus::SignupBonusVestingStatus,
+        token::{
+            CreatorCommissionReport, TokenTransactionsExportChunk, TokenTransactionsExportManifest,
+        },
+        token_ledger::TokenLedgerMirror,
+        tournament::TournamentEnrollment,
+        treasury::{HouseRakeSettlement, PotInsuranceClaim, PotInsuranceTopUpSettlement},
+        versus::{
+            VersusContestDetails, VersusContestId, VersusContestProposal, VersusContestSettlement,
+            VersusContestStatus, VersusSideReport,
+        },
     },
-    common::types::{
-        app_primitive_type::PostId, known_principal::KnownPrincipalType,
-        utility_token::token_event::TokenEvent,
+    canister_specific::user_index::types::tournament::TournamentId,
+    common::{
+        types::{
+            app_primitive_type::PostId,
+            deferred_job::{DeferredJobError, DeferredJobId, DeferredJobResults},
+            error_code::ErrorCodeRegistryEntry,
+            event_counter::EventCounterSample,
+            global_post_ref::GlobalPostRef,
+            known_principal::KnownPrincipalType,
+            response_envelope::ResponseEnvelope,
+            utility_token::token_event::TokenEvent,
+        },
+        utils::call_queue::QueuedCall,
     },
+    pagination::Page,
     types::canister_specific::individual_user_template::error_types::{
         GetUserUtilityTokenTransactionHistoryError, UpdateProfileSetUniqueUsernameError,
     },
 };
+use util::certified_post_details::CertifiedPostDetails;
 
 mod api;
 mod data_model;
@@ -40,6 +108,7 @@ mod util;
 
 thread_local! {
     static CANISTER_DATA: RefCell<CanisterData> = RefCell::default();
+    static CERTIFIED_POST_DETAILS: RefCell<CertifiedPostDetails> = RefCell::default();
 }
 
 #[ic_cdk::query(name = "__get_candid_interface_tmp_hack")]
@@ -0,0 +1,154 @@
+use std::time::SystemTime;
+
+use candid::Principal;
+use shared_utils::{
+    canister_specific::individual_user_template::types::call_journal::{
+        get_argument_digest, CallJournal, CallJournalEntry,
+    },
+    common::utils::system_time,
+};
+
+use crate::{
+    data_model::{
+        memory::{get_call_journal_data_memory, get_call_journal_index_memory},
+        CanisterData,
+    },
+    CANISTER_DATA,
+};
+
+/// Records the call being handled in the access statistics, and in the call journal if the
+/// journal is switched on. Called first thing in every update endpoint, since the call's
+/// arguments can no longer be read once the endpoint has awaited.
+pub fn record_call_in_journal(method_name: &str) {
+    CANISTER_DATA.with(|canister_data_ref_cell| {
+        let mut canister_data = canister_data_ref_cell.borrow_mut();
+        let caller = ic_cdk::caller();
+        let current_time = system_time::get_current_system_time_from_ic();
+
+        record_call_in_access_statistics(&mut canister_data, method_name, caller, current_time);
+
+        if !canister_data.configuration.is_call_journal_enabled {
+            return;
+        }
+
+        record_call_in_journal_impl(
+            &mut canister_data,
+            method_name,
+            caller,
+            &ic_cdk::api::call::arg_data_raw(),
+            current_time,
+        );
+    });
+}
+
+fn record_call_in_access_statistics(
+    canister_data: &mut CanisterData,
+    method_name: &str,
+    caller: Principal,
+    received_at: SystemTime,
+) {
+    let CanisterData {
+        access_statistics,
+        configuration,
+        ..
+    } = canister_data;
+
+    access_statistics.record_call(
+        caller,
+        method_name,
+        received_at,
+        &configuration.access_statistics_config,
+    );
+}
+
+fn record_call_in_journal_impl(
+    canister_data: &mut CanisterData,
+    method_name: &str,
+    caller: Principal,
+    argument_bytes: &[u8],
+    received_at: SystemTime,
+) {
+    let version_number = canister_data.version_details.version_number;
+
+    canister_data.call_journal.record(&CallJournalEntry {
+        method_name: method_name.to_string(),
+        caller,
+        argument_digest: get_argument_digest(argument_bytes),
+        received_at,
+        version_number,
+    });
+}
+
+/// Switching the journal on starts it over, so that an export only ever covers one
+/// continuous stretch of calls
+pub fn set_call_journal_enabled(canister_data: &mut CanisterData, is_call_journal_enabled: bool) {
+    if is_call_journal_enabled && !canister_data.configuration.is_call_journal_enabled {
+        canister_data.call_journal = CallJournal::new(
+            get_call_journal_index_memory(),
+            get_call_journal_data_memory(),
+        );
+    }
+
+    canister_data.configuration.is_call_journal_enabled = is_call_journal_enabled;
+}
+
+#[cfg(test)]
+mod test {
+    use test_utils::setup::test_constants::get_mock_user_alice_principal_id;
+
+    use super::*;
+
+    #[test]
+    fn test_call_journal_is_started_over_when_switched_on() {
+        let mut canister_data = CanisterData::default();
+
+        set_call_journal_enabled(&mut canister_data, true);
+        record_call_in_journal_impl(
+            &mut canister_data,
+            "boost_post",
+            get_mock_user_alice_principal_id(),
+            b"DIDL\x00\x00",
+            SystemTime::now(),
+        );
+        assert_eq!(canister_data.call_journal.len(), 1);
+
+        // * switching it on again while it's on keeps what was recorded
+        set_call_journal_enabled(&mut canister_data, true);
+        assert_eq!(canister_data.call_journal.len(), 1);
+
+        set_call_journal_enabled(&mut canister_data, false);
+        set_call_journal_enabled(&mut canister_data, true);
+        assert!(canister_data.call_journal.is_empty());
+    }
+
+    #[test]
+    fn test_calls_are_counted_even_with_the_journal_switched_off() {
+        let mut canister_data = CanisterData::default();
+        canister_data
+            .configuration
+            .access_statistics_config
+            .anomalous_call_count_threshold = 1;
+
+        for _ in 0..2 {
+            record_call_in_access_statistics(
+                &mut canister_data,
+                "boost_post",
+                get_mock_user_alice_principal_id(),
+                SystemTime::UNIX_EPOCH,
+            );
+        }
+
+        assert!(canister_data.call_journal.is_empty());
+        assert_eq!(
+            canister_data
+                .access_statistics
+                .get_top_callers(SystemTime::UNIX_EPOCH, 10)[0]
+                .call_count,
+            2
+        );
+        assert_eq!(
+            canister_data.access_statistics.get_anomalous_callers()[0].caller,
+            get_mock_user_alice_principal_id()
+        );
+    }
+}
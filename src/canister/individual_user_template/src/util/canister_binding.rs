@@ -0,0 +1,79 @@
+use candid::Principal;
+use shared_utils::common::types::known_principal::KnownPrincipalType;
+
+use crate::{data_model::CanisterData, CANISTER_DATA};
+
+/// Calls made against a reference to this canister from before it was last reinstalled are
+/// turned away, so they can't land on data that now belongs to someone else. Canisters that
+/// predate binding epochs don't pass one along and are let through.
+pub fn is_expected_binding_epoch(
+    canister_data: &CanisterData,
+    expected_binding_epoch: Option<u64>,
+) -> bool {
+    expected_binding_epoch
+        .is_none_or(|expected_binding_epoch| expected_binding_epoch == canister_data.binding_epoch)
+}
+
+/// The binding epoch of another user's canister, resolved through the user index the first
+/// time and cached after. None when the user index can't be reached, in which case calls go
+/// out unchecked.
+pub async fn get_binding_epoch_of_user_canister(user_canister_id: Principal) -> Option<u64> {
+    let cached_binding_epoch = CANISTER_DATA.with(|canister_data_ref_cell| {
+        canister_data_ref_cell
+            .borrow()
+            .known_binding_epochs
+            .get(&user_canister_id)
+            .copied()
+    });
+
+    match cached_binding_epoch {
+        Some(binding_epoch) => Some(binding_epoch),
+        None => re_resolve_binding_epoch_of_user_canister(user_canister_id).await,
+    }
+}
+
+/// Asks the user index again, for when a call was turned away because the cached epoch is
+/// stale
+pub async fn re_resolve_binding_epoch_of_user_canister(user_canister_id: Principal) -> Option<u64> {
+    let user_index_canister_id = CANISTER_DATA.with(|canister_data_ref_cell| {
+        canister_data_ref_cell
+            .borrow()
+            .known_principal_ids
+            .get(&KnownPrincipalType::CanisterIdUserIndex)
+            .cloned()
+    })?;
+
+    let (binding_epoch,) = ic_cdk::call::<_, (u64,)>(
+        user_index_canister_id,
+        "get_user_canister_binding_epoch",
+        (user_canister_id,),
+    )
+    .await
+    .ok()?;
+
+    CANISTER_DATA.with(|canister_data_ref_cell| {
+        canister_data_ref_cell
+            .borrow_mut()
+            .known_binding_epochs
+            .insert(user_canister_id, binding_epoch);
+    });
+
+    Some(binding_epoch)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_is_expected_binding_epoch() {
+        let canister_data = CanisterData {
+            binding_epoch: 1,
+            ..Default::default()
+        };
+
+        assert!(is_expected_binding_epoch(&canister_data, None));
+        assert!(is_expected_binding_epoch(&canister_data, Some(1)));
+        assert!(!is_expected_binding_epoch(&canister_data, Some(0)));
+    }
+}
@@ -0,0 +1,253 @@
+use std::{collections::BTreeMap, time::SystemTime};
+
+use candid::Principal;
+use shared_utils::{
+    canister_specific::individual_user_template::types::{
+        post::{get_post_details_certification_hash, PostDetailsForFrontend},
+        profile::UserProfileDetailsForFrontend,
+    },
+    common::{
+        types::{
+            app_primitive_type::PostId,
+            certified_map::{CertifiedMap, HashTree},
+        },
+        utils::system_time,
+    },
+};
+
+use crate::{data_model::CanisterData, CANISTER_DATA, CERTIFIED_POST_DETAILS};
+
+pub const CERTIFIED_POST_DETAILS_LABEL: &[u8] = b"post_details";
+
+/// Snapshots of the post details served through certified queries, and the hash tree
+/// certifying them. Lives on the heap only and is rebuilt after every upgrade, since the
+/// certified data is reset along with the wasm.
+pub struct CertifiedPostDetails {
+    certified_map: CertifiedMap,
+    snapshots: BTreeMap<PostId, (u64, PostDetailsForFrontend)>,
+}
+
+impl Default for CertifiedPostDetails {
+    fn default() -> Self {
+        Self {
+            certified_map: CertifiedMap::new(CERTIFIED_POST_DETAILS_LABEL),
+            snapshots: BTreeMap::default(),
+        }
+    }
+}
+
+impl CertifiedPostDetails {
+    pub fn root_hash(&self) -> [u8; 32] {
+        self.certified_map.root_hash()
+    }
+
+    pub fn get_snapshot(&self, post_id: PostId) -> Option<&(u64, PostDetailsForFrontend)> {
+        self.snapshots.get(&post_id)
+    }
+
+    pub fn witness_for(&self, post_id: PostId) -> HashTree {
+        self.certified_map.witness_for(&post_id.to_be_bytes())
+    }
+}
+
+/// To be called after anything shown in a post's details changes, so that certified reads
+/// stop serving the old snapshot
+pub fn recertify_post_details(post_id: PostId) {
+    let current_time = system_time::get_current_system_time_from_ic();
+
+    CERTIFIED_POST_DETAILS.with(|certified_post_details_ref_cell| {
+        CANISTER_DATA.with(|canister_data_ref_cell| {
+            let canister_data = &mut canister_data_ref_cell.borrow_mut();
+            let certified_post_details = &mut certified_post_details_ref_cell.borrow_mut();
+
+            bump_post_details_version_impl(canister_data, post_id);
+            certify_post_details_impl(
+                canister_data,
+                certified_post_details,
+                post_id,
+                &current_time,
+            );
+            ic_cdk::api::set_certified_data(&certified_post_details.root_hash());
+        });
+    });
+}
+
+/// For changes to the creator's profile, which show up in the details of every post
+pub fn recertify_all_post_details() {
+    certify_every_post(true);
+}
+
+/// Rebuilds the snapshots without bumping any versions, as nothing about the posts changed
+pub fn certify_all_post_details() {
+    certify_every_post(false);
+}
+
+fn certify_every_post(should_bump_versions: bool) {
+    let current_time = system_time::get_current_system_time_from_ic();
+
+    CERTIFIED_POST_DETAILS.with(|certified_post_details_ref_cell| {
+        CANISTER_DATA.with(|canister_data_ref_cell| {
+            let canister_data = &mut canister_data_ref_cell.borrow_mut();
+            let certified_post_details = &mut *certified_post_details_ref_cell.borrow_mut();
+
+            *certified_post_details = CertifiedPostDetails::default();
+            let post_ids: Vec<PostId> = canister_data.all_created_posts.keys().collect();
+            for post_id in post_ids {
+                if should_bump_versions {
+                    bump_post_details_version_impl(canister_data, post_id);
+                }
+                certify_post_details_impl(
+                    canister_data,
+                    certified_post_details,
+                    post_id,
+                    &current_time,
+                );
+            }
+            ic_cdk::api::set_certified_data(&certified_post_details.root_hash());
+        });
+    });
+}
+
+fn bump_post_details_version_impl(canister_data: &mut CanisterData, post_id: PostId) {
+    *canister_data
+        .post_details_versions
+        .entry(post_id)
+        .or_default() += 1;
+}
+
+fn certify_post_details_impl(
+    canister_data: &CanisterData,
+    certified_post_details: &mut CertifiedPostDetails,
+    post_id: PostId,
+    current_time: &SystemTime,
+) {
+    let (Some(post), Some(principal_id)) = (
+        canister_data.all_created_posts.get(&post_id),
+        canister_data.profile.principal_id,
+    ) else {
+        certified_post_details.snapshots.remove(&post_id);
+        certified_post_details
+            .certified_map
+            .remove(&post_id.to_be_bytes());
+        return;
+    };
+
+    let profile = &canister_data.profile;
+    let version = canister_data
+        .post_details_versions
+        .get(&post_id)
+        .copied()
+        .unwrap_or_default();
+    let post_details = post.get_post_details_for_frontend_for_this_post(
+        UserProfileDetailsForFrontend {
+            display_name: profile.display_name.clone(),
+            followers_count: canister_data.principals_that_follow_me.len() as u64,
+            following_count: canister_data.principals_i_follow.len() as u64,
+            principal_id,
+            profile_picture_url: profile.profile_picture_url.clone(),
+            profile_stats: profile.profile_stats,
+            unique_user_name: profile.unique_user_name.clone(),
+            lifetime_earnings: canister_data.my_token_balance.lifetime_earnings,
+            badges: canister_data.achievements.get_badges(),
+        },
+        Principal::anonymous(),
+        current_time,
+    );
+
+    certified_post_details.certified_map.insert(
+        post_id.to_be_bytes().to_vec(),
+        get_post_details_certification_hash(version, &post_details),
+    );
+    certified_post_details
+        .snapshots
+        .insert(post_id, (version, post_details));
+}
+
+#[cfg(test)]
+mod test {
+    use shared_utils::canister_specific::individual_user_template::types::post::{
+        Post, PostDetailsFromFrontend,
+    };
+    use test_utils::setup::test_constants::get_mock_user_alice_principal_id;
+
+    use super::*;
+
+    #[test]
+    fn test_certify_post_details_impl() {
+        let mut canister_data = CanisterData::default();
+        let mut certified_post_details = CertifiedPostDetails::default();
+        let current_time = SystemTime::now();
+        canister_data.profile.principal_id = Some(get_mock_user_alice_principal_id());
+        canister_data.all_created_posts.insert(
+            0,
+            Post::new(
+                0,
+                &PostDetailsFromFrontend {
+                    description: "Doggos and puppers".into(),
+                    hashtags: vec!["doggo".into(), "pupper".into()],
+                    video_uid: "abcd#1234".into(),
+                    creator_consent_for_inclusion_in_hot_or_not: true,
+                    collaborator_shares: None,
+                    title: None,
+                    category: None,
+                },
+                &current_time,
+            ),
+        );
+        let empty_root_hash = certified_post_details.root_hash();
+
+        bump_post_details_version_impl(&mut canister_data, 0);
+        certify_post_details_impl(
+            &canister_data,
+            &mut certified_post_details,
+            0,
+            &current_time,
+        );
+        let root_hash = certified_post_details.root_hash();
+        assert_ne!(root_hash, empty_root_hash);
+
+        let (version, post_details) = certified_post_details.get_snapshot(0).unwrap();
+        assert_eq!(*version, 1);
+        assert_eq!(post_details.description, "Doggos and puppers");
+        assert!(!post_details.liked_by_me);
+        assert_eq!(
+            certified_post_details
+                .certified_map
+                .get(&0_u64.to_be_bytes()),
+            Some(&get_post_details_certification_hash(1, post_details))
+        );
+        assert_eq!(
+            certified_post_details.witness_for(0).reconstruct(),
+            root_hash
+        );
+
+        // * a like only shows up once the post is recertified
+        canister_data.all_created_posts.update(&0, |post| {
+            post.likes.insert(get_mock_user_alice_principal_id());
+        });
+        assert_eq!(certified_post_details.root_hash(), root_hash);
+
+        bump_post_details_version_impl(&mut canister_data, 0);
+        certify_post_details_impl(
+            &canister_data,
+            &mut certified_post_details,
+            0,
+            &current_time,
+        );
+        let (version, post_details) = certified_post_details.get_snapshot(0).unwrap();
+        assert_eq!(*version, 2);
+        assert_eq!(post_details.like_count, 1);
+        assert_ne!(certified_post_details.root_hash(), root_hash);
+
+        // * posts that are gone are no longer certified
+        canister_data.all_created_posts.remove(&0);
+        certify_post_details_impl(
+            &canister_data,
+            &mut certified_post_details,
+            0,
+            &current_time,
+        );
+        assert!(certified_post_details.get_snapshot(0).is_none());
+        assert_eq!(certified_post_details.root_hash(), empty_root_hash);
+    }
+}
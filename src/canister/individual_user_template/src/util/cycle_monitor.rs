@@ -0,0 +1,60 @@
+use std::time::Duration;
+
+use ic_cdk::api;
+use shared_utils::common::{types::known_principal::KnownPrincipalType, utils::system_time};
+
+use crate::CANISTER_DATA;
+
+const CYCLE_BALANCE_CHECK_INTERVAL: Duration = Duration::from_secs(10 * 60);
+
+/// Checks the cycle balance every so often, asking the user index for a top up when it runs
+/// low and switching low cycles mode on or off. Timers don't survive upgrades, so this is
+/// started again from `post_upgrade`.
+pub fn enqueue_timer_for_monitoring_cycle_balance() {
+    ic_cdk_timers::set_timer_interval(CYCLE_BALANCE_CHECK_INTERVAL, || {
+        ic_cdk::spawn(check_cycle_balance())
+    });
+}
+
+async fn check_cycle_balance() {
+    let (is_top_up_due, user_index_canister_id) = CANISTER_DATA.with(|canister_data_ref_cell| {
+        let canister_data = &mut canister_data_ref_cell.borrow_mut();
+
+        (
+            canister_data.cycle_monitor.record_cycle_balance(
+                api::canister_balance128(),
+                &system_time::get_current_system_time_from_ic(),
+            ),
+            canister_data
+                .known_principal_ids
+                .get(&KnownPrincipalType::CanisterIdUserIndex)
+                .copied(),
+        )
+    });
+
+    let (true, Some(user_index_canister_id)) = (is_top_up_due, user_index_canister_id) else {
+        return;
+    };
+
+    match ic_cdk::call::<_, (Result<u128, String>,)>(
+        user_index_canister_id,
+        "request_cycle_top_up",
+        (),
+    )
+    .await
+    {
+        // * Leaves low cycles mode right away rather than on the next check
+        Ok((Ok(_),)) => CANISTER_DATA.with(|canister_data_ref_cell| {
+            canister_data_ref_cell
+                .borrow_mut()
+                .cycle_monitor
+                .record_cycle_balance(
+                    api::canister_balance128(),
+                    &system_time::get_current_system_time_from_ic(),
+                );
+        }),
+        Ok((Err(message),)) | Err((_, message)) => {
+            ic_cdk::print(format!("Failed to get a cycle top up: {}", message));
+        }
+    }
+}
@@ -0,0 +1,92 @@
+use std::time::{Duration, SystemTime};
+
+use shared_utils::common::utils::system_time;
+
+use crate::{data_model::CanisterData, CANISTER_DATA};
+
+// * Steps of a deferred job run per message, e.g. posts read out of stable memory
+const DEFERRED_JOB_STEPS_PER_MESSAGE: usize = 50;
+
+/// Runs queued deferred jobs one chunk per message, oldest job first, until none are left in
+/// progress. Timers don't survive upgrades, so this is started again from `post_upgrade`.
+pub fn enqueue_timer_for_running_deferred_jobs() {
+    ic_cdk_timers::set_timer(Duration::ZERO, || {
+        let has_jobs_in_progress = CANISTER_DATA.with(|canister_data_ref_cell| {
+            run_next_deferred_job_chunk_impl(
+                &mut canister_data_ref_cell.borrow_mut(),
+                &system_time::get_current_system_time_from_ic(),
+            )
+        });
+
+        if has_jobs_in_progress {
+            enqueue_timer_for_running_deferred_jobs();
+        }
+    });
+}
+
+/// Returns true while there are jobs left in progress
+fn run_next_deferred_job_chunk_impl(
+    canister_data: &mut CanisterData,
+    current_time: &SystemTime,
+) -> bool {
+    canister_data
+        .deferred_jobs
+        .remove_expired_jobs(current_time);
+
+    let Some((job_id, mut entry)) = canister_data.deferred_jobs.take_next_job_to_run() else {
+        return false;
+    };
+    entry.run_next_chunk(canister_data, DEFERRED_JOB_STEPS_PER_MESSAGE, current_time);
+    canister_data.deferred_jobs.put_back(job_id, entry);
+
+    canister_data.deferred_jobs.has_jobs_in_progress()
+}
+
+#[cfg(test)]
+mod test {
+    use shared_utils::{
+        canister_specific::individual_user_template::types::deferred_job::DeferredJobRequest,
+        common::types::deferred_job::DeferredJobStatus,
+    };
+    use test_utils::setup::test_constants::get_mock_user_alice_principal_id;
+
+    use super::*;
+
+    #[test]
+    fn test_run_next_deferred_job_chunk_impl() {
+        let mut canister_data = CanisterData::default();
+        let current_time = SystemTime::UNIX_EPOCH;
+        assert!(!run_next_deferred_job_chunk_impl(
+            &mut canister_data,
+            &current_time
+        ));
+
+        let job_id = canister_data
+            .deferred_jobs
+            .enqueue(
+                get_mock_user_alice_principal_id(),
+                DeferredJobRequest::TokenTransactionsExport.into(),
+                &current_time,
+            )
+            .unwrap();
+
+        assert!(!run_next_deferred_job_chunk_impl(
+            &mut canister_data,
+            &current_time
+        ));
+        assert!(matches!(
+            canister_data
+                .deferred_jobs
+                .get_results(
+                    job_id,
+                    &get_mock_user_alice_principal_id(),
+                    0,
+                    10,
+                    &current_time
+                )
+                .unwrap()
+                .status,
+            DeferredJobStatus::Completed { .. }
+        ));
+    }
+}
@@ -0,0 +1,35 @@
+use std::time::SystemTime;
+
+use shared_utils::canister_specific::individual_user_template::types::event_callback::{
+    EventCallbackNotification, EventCallbackPayload,
+};
+
+use crate::{util::call_queue::queue_call, CANISTER_DATA};
+
+/// Calls back every third party canister registered for the event that has quota left for
+/// the day. Callbacks go through the call queue, so a registrant that is briefly down still
+/// gets the event.
+pub fn send_event_callbacks(payload: EventCallbackPayload, current_time: SystemTime) {
+    let callbacks_to_send = CANISTER_DATA.with(|canister_data_ref_cell| {
+        canister_data_ref_cell
+            .borrow_mut()
+            .event_callbacks
+            .take_callbacks_to_send(payload.get_kind(), &current_time)
+    });
+
+    if callbacks_to_send.is_empty() {
+        return;
+    }
+
+    let notification = EventCallbackNotification {
+        user_canister_id: ic_cdk::id(),
+        payload,
+        occurred_at: current_time,
+    };
+
+    callbacks_to_send
+        .into_iter()
+        .for_each(|(registrant, method_name)| {
+            queue_call(registrant, &method_name, (notification.clone(),));
+        });
+}
@@ -0,0 +1,59 @@
+use std::time::Duration;
+
+use shared_utils::common::{types::known_principal::KnownPrincipalType, utils::system_time};
+
+use crate::CANISTER_DATA;
+
+const FEATURE_USAGE_PUSH_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+/// Pushes the profile owner's feature usage for every day that is over to the user index,
+/// for platform wide product analytics. Days that fail to go through are retried on the next
+/// push. Timers don't survive upgrades, so this is started again from `post_upgrade`.
+pub fn enqueue_timer_for_pushing_feature_usage_to_user_index() {
+    ic_cdk_timers::set_timer_interval(FEATURE_USAGE_PUSH_INTERVAL, || {
+        ic_cdk::spawn(push_feature_usage_to_user_index())
+    });
+}
+
+async fn push_feature_usage_to_user_index() {
+    let current_time = system_time::get_current_system_time_from_ic();
+
+    let (user_index_canister_id, days_to_push) = CANISTER_DATA.with(|canister_data_ref_cell| {
+        let canister_data = canister_data_ref_cell.borrow();
+
+        (
+            canister_data
+                .known_principal_ids
+                .get(&KnownPrincipalType::CanisterIdUserIndex)
+                .cloned(),
+            canister_data
+                .feature_usage_stats
+                .get_days_to_push(&current_time),
+        )
+    });
+
+    let (Some(user_index_canister_id), Some(last_day_to_push)) = (
+        user_index_canister_id,
+        days_to_push
+            .last()
+            .map(|feature_usage_for_day| feature_usage_for_day.day),
+    ) else {
+        return;
+    };
+
+    let response = ic_cdk::call::<_, ()>(
+        user_index_canister_id,
+        "receive_feature_usage_from_individual_user_canister",
+        (days_to_push,),
+    )
+    .await;
+
+    if response.is_ok() {
+        CANISTER_DATA.with(|canister_data_ref_cell| {
+            canister_data_ref_cell
+                .borrow_mut()
+                .feature_usage_stats
+                .mark_days_as_pushed(last_day_to_push);
+        });
+    }
+}
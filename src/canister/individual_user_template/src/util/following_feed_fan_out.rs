@@ -0,0 +1,67 @@
+use std::time::Duration;
+
+use shared_utils::{
+    canister_specific::individual_user_template::types::follow::{
+        following_feed::{PostFromFollowee, MAX_FOLLOWERS_NOTIFIED_IN_ONE_FAN_OUT_BATCH},
+        FollowEntryId,
+    },
+    common::types::app_primitive_type::PostId,
+};
+
+use crate::{data_model::CanisterData, util::call_queue::queue_call, CANISTER_DATA};
+
+/// Lets the canisters of everyone following this profile know about a new post, so it shows
+/// up in their following feed. Followers are notified in batches, one batch per message.
+pub fn fan_out_post_to_followers(post_id: PostId) {
+    let post_from_followee = CANISTER_DATA.with(|canister_data_ref_cell| {
+        get_post_from_followee(&canister_data_ref_cell.borrow(), post_id)
+    });
+
+    if let Some(post_from_followee) = post_from_followee {
+        notify_next_batch_of_followers(post_from_followee, 0);
+    }
+}
+
+fn notify_next_batch_of_followers(
+    post_from_followee: PostFromFollowee,
+    from_inclusive_index: FollowEntryId,
+) {
+    let followers_chunk = CANISTER_DATA.with(|canister_data_ref_cell| {
+        canister_data_ref_cell
+            .borrow()
+            .follow_data
+            .follower
+            .get_export_chunk(
+                from_inclusive_index,
+                MAX_FOLLOWERS_NOTIFIED_IN_ONE_FAN_OUT_BATCH,
+            )
+    });
+
+    followers_chunk
+        .entries
+        .iter()
+        .for_each(|(_follow_entry_id, follower)| {
+            queue_call(
+                follower.canister_id,
+                "receive_post_from_followee",
+                (post_from_followee.clone(),),
+            );
+        });
+
+    if let Some(next_from_inclusive_index) = followers_chunk.next_from_inclusive_index {
+        ic_cdk_timers::set_timer(Duration::ZERO, move || {
+            notify_next_batch_of_followers(post_from_followee, next_from_inclusive_index);
+        });
+    }
+}
+
+fn get_post_from_followee(
+    canister_data: &CanisterData,
+    post_id: PostId,
+) -> Option<PostFromFollowee> {
+    Some(PostFromFollowee {
+        publisher_principal_id: canister_data.profile.principal_id?,
+        post_id,
+        created_at: canister_data.all_created_posts.get(&post_id)?.created_at,
+    })
+}
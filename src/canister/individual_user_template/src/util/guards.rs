@@ -0,0 +1,124 @@
+use crate::{data_model::CanisterData, CANISTER_DATA};
+
+pub const READ_ONLY_MODE_REJECT_MESSAGE: &str = "ReadOnlyMode";
+pub const LOW_CYCLES_MODE_REJECT_MESSAGE: &str = "LowCyclesMode";
+
+/// Guard for update endpoints. While the canister is in read only mode, or still moving posts
+/// off the heap after an upgrade, the call is rejected with [`READ_ONLY_MODE_REJECT_MESSAGE`]
+/// before the endpoint runs.
+///
+/// Left off the endpoints that migrations and restores rely on, e.g. the backup and restore
+/// endpoints and the one that switches read only mode off again.
+pub fn reject_updates_in_read_only_mode() -> Result<(), String> {
+    CANISTER_DATA.with(|canister_data_ref_cell| {
+        reject_updates_in_read_only_mode_impl(&canister_data_ref_cell.borrow())
+    })
+}
+
+fn reject_updates_in_read_only_mode_impl(canister_data: &CanisterData) -> Result<(), String> {
+    if canister_data.is_read_only || !canister_data.legacy_all_created_posts.is_empty() {
+        return Err(READ_ONLY_MODE_REJECT_MESSAGE.to_string());
+    }
+
+    Ok(())
+}
+
+/// Guard for the endpoints that place bets and create posts, which commit the canister to
+/// payouts later on. On top of read only mode, they are rejected with
+/// [`LOW_CYCLES_MODE_REJECT_MESSAGE`] while the canister is critically low on cycles.
+pub fn reject_bets_and_posts_in_read_only_or_low_cycles_mode() -> Result<(), String> {
+    CANISTER_DATA.with(|canister_data_ref_cell| {
+        reject_bets_and_posts_in_read_only_or_low_cycles_mode_impl(&canister_data_ref_cell.borrow())
+    })
+}
+
+fn reject_bets_and_posts_in_read_only_or_low_cycles_mode_impl(
+    canister_data: &CanisterData,
+) -> Result<(), String> {
+    reject_updates_in_read_only_mode_impl(canister_data)?;
+
+    if canister_data.cycle_monitor.is_in_low_cycles_mode() {
+        return Err(LOW_CYCLES_MODE_REJECT_MESSAGE.to_string());
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::SystemTime;
+
+    use shared_utils::{
+        canister_specific::individual_user_template::types::post::{Post, PostDetailsFromFrontend},
+        constant::CYCLES_THRESHOLD_TO_ENTER_LOW_CYCLES_MODE,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_reject_updates_in_read_only_mode_impl() {
+        let mut canister_data = CanisterData::default();
+        assert_eq!(
+            reject_updates_in_read_only_mode_impl(&canister_data),
+            Ok(())
+        );
+
+        canister_data.is_read_only = true;
+        assert_eq!(
+            reject_updates_in_read_only_mode_impl(&canister_data),
+            Err("ReadOnlyMode".to_string())
+        );
+
+        // * as while posts are still moving off the heap after an upgrade
+        canister_data.is_read_only = false;
+        canister_data.legacy_all_created_posts.insert(
+            0,
+            Post::new(
+                0,
+                &PostDetailsFromFrontend {
+                    description: "Doggos and puppers".into(),
+                    hashtags: vec!["doggo".into(), "pupper".into()],
+                    video_uid: "abcd#1234".into(),
+                    creator_consent_for_inclusion_in_hot_or_not: true,
+                    collaborator_shares: None,
+                    title: None,
+                    category: None,
+                },
+                &SystemTime::now(),
+            ),
+        );
+        assert_eq!(
+            reject_updates_in_read_only_mode_impl(&canister_data),
+            Err("ReadOnlyMode".to_string())
+        );
+    }
+
+    #[test]
+    fn test_reject_bets_and_posts_in_read_only_or_low_cycles_mode_impl() {
+        let mut canister_data = CanisterData::default();
+        assert_eq!(
+            reject_bets_and_posts_in_read_only_or_low_cycles_mode_impl(&canister_data),
+            Ok(())
+        );
+
+        canister_data.cycle_monitor.record_cycle_balance(
+            CYCLES_THRESHOLD_TO_ENTER_LOW_CYCLES_MODE - 1,
+            &SystemTime::now(),
+        );
+        assert_eq!(
+            reject_bets_and_posts_in_read_only_or_low_cycles_mode_impl(&canister_data),
+            Err("LowCyclesMode".to_string())
+        );
+        // * other updates, e.g. the ones paying out bets, go ahead
+        assert_eq!(
+            reject_updates_in_read_only_mode_impl(&canister_data),
+            Ok(())
+        );
+
+        canister_data.is_read_only = true;
+        assert_eq!(
+            reject_bets_and_posts_in_read_only_or_low_cycles_mode_impl(&canister_data),
+            Err("ReadOnlyMode".to_string())
+        );
+    }
+}
@@ -0,0 +1,57 @@
+use std::time::Duration;
+
+use shared_utils::common::utils::system_time::{self, IcTimeProvider};
+
+use crate::{
+    api::{
+        hot_or_not_bet::reenqueue_timers_for_pending_bet_outcomes::reenqueue_timers_for_pending_bet_outcomes,
+        post::boost_post::reenqueue_timers_for_active_post_boosts,
+    },
+    util::certified_post_details::certify_all_post_details,
+    CANISTER_DATA,
+};
+
+const MOVE_LEGACY_POSTS_MIGRATION_ID: &str = "move_posts_to_stable_post_map";
+// * Leaves room in the message for what runs after a chunk, e.g. the rest of post_upgrade
+const LEGACY_POST_MIGRATION_INSTRUCTION_BUDGET: u64 = 5_000_000_000;
+
+/// Moves the posts kept on the heap before they moved to stable memory into the stable post
+/// map. As many as fit are moved from `post_upgrade`, and the rest a chunk per message on a
+/// timer, each chunk recording how far it got in the migration changelog. Posts still on the
+/// heap when the canister is upgraded again are carried over and picked up by the next wasm.
+/// Updates are rejected until every post has moved.
+pub fn move_legacy_posts_to_stable_post_map() {
+    if !move_legacy_posts_chunk() {
+        enqueue_timer_for_moving_legacy_posts();
+    }
+}
+
+fn enqueue_timer_for_moving_legacy_posts() {
+    ic_cdk_timers::set_timer(Duration::ZERO, || {
+        if !move_legacy_posts_chunk() {
+            enqueue_timer_for_moving_legacy_posts();
+            return;
+        }
+
+        // * post_upgrade only set timers for and certified the posts that had moved by then
+        reenqueue_timers_for_pending_bet_outcomes(&IcTimeProvider);
+        reenqueue_timers_for_active_post_boosts();
+        certify_all_post_details();
+    });
+}
+
+/// Returns true once no posts are left on the heap
+fn move_legacy_posts_chunk() -> bool {
+    CANISTER_DATA.with(|canister_data_ref_cell| {
+        canister_data_ref_cell.borrow_mut().apply_migration_chunk(
+            MOVE_LEGACY_POSTS_MIGRATION_ID,
+            system_time::get_current_system_time_from_ic(),
+            ic_cdk::api::instruction_counter,
+            |canister_data| {
+                canister_data.move_legacy_posts_to_stable_post_map(|| {
+                    ic_cdk::api::instruction_counter() < LEGACY_POST_MIGRATION_INSTRUCTION_BUDGET
+                })
+            },
+        )
+    })
+}
@@ -0,0 +1,253 @@
+use std::time::{Duration, SystemTime};
+
+use candid::Principal;
+use shared_utils::{
+    canister_specific::individual_user_template::types::live_betting_activity::{
+        get_post_topic_key, LiveBettingActivityEvent, LiveBettingActivityMessage,
+        LIVE_BETTING_ACTIVITY_PUBLISH_METHOD_NAME,
+    },
+    common::{
+        types::{app_primitive_type::PostId, known_principal::KnownPrincipalType},
+        utils::system_time,
+    },
+};
+
+use crate::{data_model::CanisterData, CANISTER_DATA};
+
+/// Queues betting activity on one of this canister's posts for the web socket gateway.
+/// Nothing is queued while no gateway is configured. Returns true if the event was queued.
+pub fn queue_live_betting_activity_impl(
+    canister_data: &mut CanisterData,
+    post_canister_id: &Principal,
+    post_id: PostId,
+    event: LiveBettingActivityEvent,
+    current_time: &SystemTime,
+) -> bool {
+    if canister_data
+        .known_principal_ids
+        .get(&KnownPrincipalType::CanisterIdWebSocketGateway)
+        .is_none()
+    {
+        return false;
+    }
+
+    canister_data.live_betting_activity_stream.push(
+        get_post_topic_key(post_canister_id, post_id),
+        event,
+        *current_time,
+    );
+
+    true
+}
+
+/// Publishes the queued betting activity, a batch at a time, unless a batch is already in
+/// flight or the gateway asked to back off, in which case the timer already set for it
+/// picks up the new messages
+pub fn publish_pending_live_betting_activity() {
+    let is_ready_to_publish = CANISTER_DATA.with(|canister_data_ref_cell| {
+        canister_data_ref_cell
+            .borrow()
+            .live_betting_activity_stream
+            .is_ready_to_publish(&system_time::get_current_system_time_from_ic())
+    });
+
+    if is_ready_to_publish {
+        enqueue_timer_for_publishing_live_betting_activity(Duration::ZERO);
+    }
+}
+
+/// Timers don't survive upgrades, so this picks publishing up again from `post_upgrade`,
+/// once any backoff in place before the upgrade has run out
+pub fn reenqueue_timer_for_publishing_live_betting_activity() {
+    let backoff_until = CANISTER_DATA.with(|canister_data_ref_cell| {
+        canister_data_ref_cell
+            .borrow()
+            .live_betting_activity_stream
+            .get_status()
+            .backoff_until
+    });
+    let delay = backoff_until
+        .and_then(|backoff_until| {
+            backoff_until
+                .duration_since(system_time::get_current_system_time_from_ic())
+                .ok()
+        })
+        .unwrap_or_default();
+
+    enqueue_timer_for_publishing_live_betting_activity(delay);
+}
+
+fn enqueue_timer_for_publishing_live_betting_activity(delay: Duration) {
+    ic_cdk_timers::set_timer(delay, || ic_cdk::spawn(publish_next_batch()));
+}
+
+async fn publish_next_batch() {
+    let current_time = system_time::get_current_system_time_from_ic();
+
+    let (web_socket_gateway_canister_id, batch) = CANISTER_DATA.with(|canister_data_ref_cell| {
+        let canister_data = &mut canister_data_ref_cell.borrow_mut();
+
+        let Some(web_socket_gateway_canister_id) = canister_data
+            .known_principal_ids
+            .get(&KnownPrincipalType::CanisterIdWebSocketGateway)
+            .cloned()
+        else {
+            return (None, None);
+        };
+
+        (
+            Some(web_socket_gateway_canister_id),
+            canister_data
+                .live_betting_activity_stream
+                .take_batch_to_publish(&current_time),
+        )
+    });
+
+    let (Some(web_socket_gateway_canister_id), Some(batch)) =
+        (web_socket_gateway_canister_id, batch)
+    else {
+        return;
+    };
+
+    let response = ic_cdk::call::<_, (u64,)>(
+        web_socket_gateway_canister_id,
+        LIVE_BETTING_ACTIVITY_PUBLISH_METHOD_NAME,
+        (batch.clone(),),
+    )
+    .await;
+
+    let backoff = CANISTER_DATA.with(|canister_data_ref_cell| {
+        record_publish_response_impl(
+            &mut canister_data_ref_cell.borrow_mut(),
+            &batch,
+            response.map(|(number_of_messages_accepted,)| number_of_messages_accepted),
+            &system_time::get_current_system_time_from_ic(),
+        )
+    });
+
+    match backoff {
+        Some(backoff) => enqueue_timer_for_publishing_live_betting_activity(backoff),
+        None => publish_pending_live_betting_activity(),
+    }
+}
+
+/// Returns how long to wait before publishing the next batch, if at all
+fn record_publish_response_impl<E>(
+    canister_data: &mut CanisterData,
+    batch: &[LiveBettingActivityMessage],
+    response: Result<u64, E>,
+    current_time: &SystemTime,
+) -> Option<Duration> {
+    let stream = &mut canister_data.live_betting_activity_stream;
+
+    match response {
+        Ok(number_of_messages_accepted) => {
+            stream.record_publish(batch, number_of_messages_accepted as usize, current_time)
+        }
+        Err(_) => Some(stream.record_failure(current_time)),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use shared_utils::canister_specific::individual_user_template::types::{
+        hot_or_not::RoomBetPossibleOutcomes, live_betting_activity::LiveBettingActivityStreamStatus,
+    };
+    use test_utils::setup::test_constants::get_mock_user_alice_canister_id;
+
+    use super::*;
+
+    fn get_slot_settled_event() -> LiveBettingActivityEvent {
+        LiveBettingActivityEvent::SlotSettled {
+            slot_id: 1,
+            room_outcomes: vec![(1, RoomBetPossibleOutcomes::HotWon)],
+        }
+    }
+
+    #[test]
+    fn test_queue_live_betting_activity_impl() {
+        let mut canister_data = CanisterData::default();
+        let current_time = SystemTime::now();
+
+        assert!(!queue_live_betting_activity_impl(
+            &mut canister_data,
+            &get_mock_user_alice_canister_id(),
+            0,
+            get_slot_settled_event(),
+            &current_time,
+        ));
+        assert!(!canister_data
+            .live_betting_activity_stream
+            .is_ready_to_publish(&current_time));
+
+        canister_data.known_principal_ids.insert(
+            KnownPrincipalType::CanisterIdWebSocketGateway,
+            Principal::from_slice(&[1]),
+        );
+
+        assert!(queue_live_betting_activity_impl(
+            &mut canister_data,
+            &get_mock_user_alice_canister_id(),
+            0,
+            get_slot_settled_event(),
+            &current_time,
+        ));
+
+        let batch = canister_data
+            .live_betting_activity_stream
+            .take_batch_to_publish(&current_time)
+            .unwrap();
+        assert_eq!(
+            batch[0].topic_key,
+            get_post_topic_key(&get_mock_user_alice_canister_id(), 0)
+        );
+    }
+
+    #[test]
+    fn test_record_publish_response_impl() {
+        let mut canister_data = CanisterData::default();
+        let current_time = SystemTime::now();
+        (0..2).for_each(|_| {
+            canister_data.live_betting_activity_stream.push(
+                "topic".into(),
+                get_slot_settled_event(),
+                current_time,
+            );
+        });
+
+        let batch = canister_data
+            .live_betting_activity_stream
+            .take_batch_to_publish(&current_time)
+            .unwrap();
+        assert_eq!(
+            record_publish_response_impl(&mut canister_data, &batch, Err(()), &current_time),
+            Some(Duration::from_secs(1))
+        );
+        assert_eq!(
+            canister_data
+                .live_betting_activity_stream
+                .get_status()
+                .number_of_pending_messages,
+            2
+        );
+
+        let current_time = current_time + Duration::from_secs(1);
+        let batch = canister_data
+            .live_betting_activity_stream
+            .take_batch_to_publish(&current_time)
+            .unwrap();
+        assert_eq!(
+            record_publish_response_impl::<()>(&mut canister_data, &batch, Ok(2), &current_time),
+            None
+        );
+        assert_eq!(
+            canister_data.live_betting_activity_stream.get_status(),
+            LiveBettingActivityStreamStatus {
+                number_of_pending_messages: 0,
+                number_of_dropped_messages: 0,
+                number_of_consecutive_failures: 0,
+                backoff_until: None,
+            }
+        );
+    }
+}
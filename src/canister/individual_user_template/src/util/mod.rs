@@ -1,2 +1,18 @@
+pub mod call_journal;
+pub mod call_queue;
+pub mod canister_binding;
+pub mod certified_post_details;
+pub mod cycle_monitor;
+pub mod deferred_job_runner;
+pub mod event_callback;
+pub mod feature_usage_push;
+pub mod following_feed_fan_out;
+pub mod guards;
+pub mod legacy_post_migration;
+pub mod live_betting_activity;
 pub mod periodic_update;
+pub mod pseudonym_salt;
+pub mod replication;
 pub mod score_ranking;
+pub mod slot_compaction;
+pub mod token_ledger_mirror;
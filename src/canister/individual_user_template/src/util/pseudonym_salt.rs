@@ -0,0 +1,29 @@
+use std::time::Duration;
+
+use ic_cdk::api::management_canister::main::raw_rand;
+
+use crate::CANISTER_DATA;
+
+/// Salt for the pseudonyms of bet makers who hide their betting activity. Generated once and
+/// kept across upgrades, so pseudonyms stay the same from one export to the next.
+pub fn generate_pseudonym_salt_if_missing() {
+    ic_cdk_timers::set_timer(Duration::ZERO, || {
+        ic_cdk::spawn(async {
+            let is_pseudonym_salt_missing = CANISTER_DATA.with(|canister_data_ref_cell| {
+                canister_data_ref_cell.borrow().pseudonym_salt.is_empty()
+            });
+            if !is_pseudonym_salt_missing {
+                return;
+            }
+
+            if let Ok((random_bytes,)) = raw_rand().await {
+                CANISTER_DATA.with(|canister_data_ref_cell| {
+                    let pseudonym_salt = &mut canister_data_ref_cell.borrow_mut().pseudonym_salt;
+                    if pseudonym_salt.is_empty() {
+                        *pseudonym_salt = random_bytes;
+                    }
+                });
+            }
+        })
+    });
+}
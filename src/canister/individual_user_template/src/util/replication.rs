@@ -0,0 +1,359 @@
+use std::{
+    ops::Bound::{Excluded, Unbounded},
+    time::{Duration, SystemTime},
+};
+
+use candid::Principal;
+use shared_utils::{
+    canister_specific::individual_user_template::types::{
+        error::ReplicationError,
+        replication::{
+            get_fingerprint, ReplicaRole, ReplicationDelta, SentReplicationDelta,
+            TokenBalanceDelta, MAX_BETS_PER_REPLICATION_DELTA, MAX_POSTS_PER_REPLICATION_DELTA,
+            MAX_POSTS_SCANNED_PER_REPLICATION_DELTA, MAX_TOKEN_EVENTS_PER_REPLICATION_DELTA,
+        },
+    },
+    common::utils::system_time,
+};
+
+use crate::{data_model::CanisterData, CANISTER_DATA};
+
+const REPLICATION_INTERVAL: Duration = Duration::from_secs(5 * 60);
+
+/// Streams what changed on this canister to its standby, if it has one. Timers don't survive
+/// upgrades, so this is started again from `post_upgrade`.
+pub fn enqueue_timer_for_replicating_to_standby() {
+    ic_cdk_timers::set_timer_interval(REPLICATION_INTERVAL, || {
+        ic_cdk::spawn(replicate_to_standby())
+    });
+}
+
+async fn replicate_to_standby() {
+    let Some((standby_canister_id, replication_delta, sent_replication_delta)) = CANISTER_DATA
+        .with(|canister_data_ref_cell| {
+            let mut canister_data = canister_data_ref_cell.borrow_mut();
+            let replication_delta = get_replication_delta_impl(&canister_data)?;
+            canister_data.replication.is_delta_in_flight = true;
+
+            Some(replication_delta)
+        })
+    else {
+        return;
+    };
+    let has_more_to_send = sent_replication_delta.has_more_to_send;
+
+    let response = ic_cdk::call::<_, (Result<(), ReplicationError>,)>(
+        standby_canister_id,
+        "receive_replication_delta",
+        (replication_delta,),
+    )
+    .await
+    .map(|(response,)| response);
+    let current_time = system_time::get_current_system_time_from_ic();
+
+    let is_acknowledged = CANISTER_DATA.with(|canister_data_ref_cell| {
+        let mut canister_data = canister_data_ref_cell.borrow_mut();
+        canister_data.replication.is_delta_in_flight = false;
+
+        handle_replication_response_impl(
+            &mut canister_data,
+            standby_canister_id,
+            response.ok(),
+            sent_replication_delta,
+            current_time,
+        )
+    });
+
+    if is_acknowledged && has_more_to_send {
+        ic_cdk_timers::set_timer(Duration::ZERO, || ic_cdk::spawn(replicate_to_standby()));
+    }
+}
+
+/// Posts, token balance and bets that changed since the last delta the standby acknowledged.
+/// None if this canister isn't replicating or there is nothing new to send.
+fn get_replication_delta_impl(
+    canister_data: &CanisterData,
+) -> Option<(Principal, ReplicationDelta, SentReplicationDelta)> {
+    let replication = &canister_data.replication;
+    if replication.role != ReplicaRole::Primary || replication.is_delta_in_flight {
+        return None;
+    }
+    let standby_canister_id = replication.standby_canister_id?;
+
+    let mut sent_replication_delta = SentReplicationDelta {
+        sequence_number: replication.last_sequence_number + 1,
+        ..Default::default()
+    };
+
+    let mut posts = vec![];
+    let mut posts_scanned = 0;
+    let mut post_ids = canister_data
+        .all_created_posts
+        .keys_from(replication.get_post_scan_cursor())
+        .peekable();
+    while let Some(post_id) = post_ids.next() {
+        posts_scanned += 1;
+
+        if let Some(fingerprint) = canister_data.all_created_posts.get_fingerprint(&post_id) {
+            if !replication.is_post_synced(&post_id, &fingerprint) {
+                // * The standby gets every bet, and compacts its copy of the post on its own
+                if let Some(post) = canister_data
+                    .all_created_posts
+                    .get_with_archived_bets(&post_id)
+                {
+                    posts.push(post);
+                    sent_replication_delta
+                        .post_fingerprints
+                        .push((post_id, fingerprint));
+                }
+            }
+        }
+
+        if posts.len() >= MAX_POSTS_PER_REPLICATION_DELTA
+            || posts_scanned >= MAX_POSTS_SCANNED_PER_REPLICATION_DELTA
+        {
+            // * picked up from here next time, or from the start once the end is reached
+            sent_replication_delta.next_post_scan_cursor = post_ids.peek().copied().unwrap_or(0);
+            sent_replication_delta.has_more_to_send = post_ids.peek().is_some();
+            break;
+        }
+    }
+
+    let token_balance = &canister_data.my_token_balance;
+    let new_token_events: Vec<_> = canister_data
+        .token_transaction_history
+        .range((
+            replication
+                .get_last_synced_token_event_index()
+                .map_or(Unbounded, Excluded),
+            Unbounded,
+        ))
+        .take(MAX_TOKEN_EVENTS_PER_REPLICATION_DELTA)
+        .collect();
+    let balance = (
+        token_balance.utility_token_balance,
+        token_balance.lifetime_earnings,
+    );
+    let token_balance_delta =
+        if new_token_events.is_empty() && replication.is_token_balance_synced(balance) {
+            None
+        } else {
+            sent_replication_delta.token_balance = Some(balance);
+            sent_replication_delta.last_token_event_index = new_token_events
+                .last()
+                .map(|(index, _)| *index)
+                .or(replication.get_last_synced_token_event_index());
+            sent_replication_delta.has_more_to_send |=
+                new_token_events.len() >= MAX_TOKEN_EVENTS_PER_REPLICATION_DELTA;
+
+            Some(TokenBalanceDelta {
+                utility_token_balance: balance.0,
+                lifetime_earnings: balance.1,
+                new_events: new_token_events,
+            })
+        };
+
+    let mut bets_placed = vec![];
+    for (key, placed_bet_detail) in canister_data.all_hot_or_not_bets_placed.iter() {
+        let fingerprint = get_fingerprint(placed_bet_detail);
+        if replication.is_bet_synced(key, &fingerprint) {
+            continue;
+        }
+
+        if bets_placed.len() >= MAX_BETS_PER_REPLICATION_DELTA {
+            sent_replication_delta.has_more_to_send = true;
+            break;
+        }
+        bets_placed.push(placed_bet_detail.clone());
+        sent_replication_delta
+            .bet_fingerprints
+            .push((*key, fingerprint));
+    }
+
+    if posts.is_empty() && token_balance_delta.is_none() && bets_placed.is_empty() {
+        return None;
+    }
+
+    Some((
+        standby_canister_id,
+        ReplicationDelta {
+            epoch: replication.epoch,
+            sequence_number: sent_replication_delta.sequence_number,
+            posts,
+            token_balance: token_balance_delta,
+            bets_placed,
+        },
+        sent_replication_delta,
+    ))
+}
+
+/// Records what the standby acknowledged. A standby that has moved on to a later epoch has
+/// been promoted in place of this canister, which then fences itself off from taking writes.
+/// Returns whether the delta was acknowledged.
+fn handle_replication_response_impl(
+    canister_data: &mut CanisterData,
+    standby_canister_id: Principal,
+    response: Option<Result<(), ReplicationError>>,
+    sent_replication_delta: SentReplicationDelta,
+    current_time: SystemTime,
+) -> bool {
+    let replication = &mut canister_data.replication;
+
+    // * the standby was changed while the delta was out
+    if replication.standby_canister_id != Some(standby_canister_id) {
+        return false;
+    }
+
+    match response {
+        Some(Ok(())) => {
+            replication.acknowledge(sent_replication_delta, current_time);
+            true
+        }
+        Some(Err(ReplicationError::StaleEpoch { current_epoch }))
+            if current_epoch > replication.epoch =>
+        {
+            replication.role = ReplicaRole::Fenced;
+            replication.standby_canister_id = None;
+            canister_data.is_read_only = true;
+            false
+        }
+        // * retried with the next delta
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use shared_utils::canister_specific::individual_user_template::types::{
+        hot_or_not::{BetDirection, BetOutcomeForBetMaker, PlacedBetDetail},
+        post::{Post, PostDetailsFromFrontend},
+    };
+    use test_utils::setup::test_constants::{
+        get_mock_user_alice_canister_id, get_mock_user_bob_canister_id,
+    };
+
+    use super::*;
+
+    fn get_post(post_id: u64) -> Post {
+        Post::new(
+            post_id,
+            &PostDetailsFromFrontend {
+                description: "Doggos and puppers".into(),
+                hashtags: vec!["doggo".into(), "pupper".into()],
+                video_uid: "abcd#1234".into(),
+                creator_consent_for_inclusion_in_hot_or_not: false,
+                collaborator_shares: None,
+                title: None,
+                category: None,
+            },
+            &SystemTime::UNIX_EPOCH,
+        )
+    }
+
+    #[test]
+    fn test_only_changes_since_the_last_acknowledged_delta_are_sent() {
+        let mut canister_data = CanisterData::default();
+        let standby_canister_id = get_mock_user_bob_canister_id();
+        assert!(get_replication_delta_impl(&canister_data).is_none());
+
+        canister_data.replication.standby_canister_id = Some(standby_canister_id);
+        canister_data.replication.epoch = 1;
+        assert!(get_replication_delta_impl(&canister_data).is_none());
+
+        canister_data.all_created_posts.insert(0, get_post(0));
+        canister_data.all_created_posts.insert(1, get_post(1));
+        canister_data.my_token_balance.utility_token_balance = 1_000;
+        canister_data.all_hot_or_not_bets_placed.insert(
+            (get_mock_user_alice_canister_id(), 0),
+            PlacedBetDetail {
+                canister_id: get_mock_user_alice_canister_id(),
+                post_id: 0,
+                slot_id: 1,
+                room_id: 1,
+                amount_bet: 100,
+                bet_direction: BetDirection::Hot,
+                bet_placed_at: SystemTime::UNIX_EPOCH,
+                outcome_received: BetOutcomeForBetMaker::AwaitingResult,
+            },
+        );
+
+        let (to, replication_delta, sent_replication_delta) =
+            get_replication_delta_impl(&canister_data).unwrap();
+        assert_eq!(to, standby_canister_id);
+        assert_eq!(replication_delta.epoch, 1);
+        assert_eq!(replication_delta.sequence_number, 1);
+        assert_eq!(replication_delta.posts.len(), 2);
+        assert_eq!(
+            replication_delta
+                .token_balance
+                .unwrap()
+                .utility_token_balance,
+            1_000
+        );
+        assert_eq!(replication_delta.bets_placed.len(), 1);
+
+        // * nothing is marked as synced while the delta is unacknowledged
+        assert!(!handle_replication_response_impl(
+            &mut canister_data,
+            standby_canister_id,
+            None,
+            sent_replication_delta.clone(),
+            SystemTime::UNIX_EPOCH,
+        ));
+        assert_eq!(
+            get_replication_delta_impl(&canister_data)
+                .unwrap()
+                .1
+                .posts
+                .len(),
+            2
+        );
+
+        assert!(handle_replication_response_impl(
+            &mut canister_data,
+            standby_canister_id,
+            Some(Ok(())),
+            sent_replication_delta,
+            SystemTime::UNIX_EPOCH,
+        ));
+        assert!(get_replication_delta_impl(&canister_data).is_none());
+
+        canister_data
+            .all_created_posts
+            .update(&1, |post| post.increment_share_count());
+        let (_, replication_delta, _) = get_replication_delta_impl(&canister_data).unwrap();
+        assert_eq!(replication_delta.sequence_number, 2);
+        assert_eq!(replication_delta.posts.len(), 1);
+        assert_eq!(replication_delta.posts[0].id, 1);
+        assert!(replication_delta.token_balance.is_none());
+        assert!(replication_delta.bets_placed.is_empty());
+    }
+
+    #[test]
+    fn test_primary_fences_itself_once_superseded() {
+        let mut canister_data = CanisterData::default();
+        let standby_canister_id = get_mock_user_bob_canister_id();
+        canister_data.replication.standby_canister_id = Some(standby_canister_id);
+        canister_data.replication.epoch = 1;
+
+        assert!(!handle_replication_response_impl(
+            &mut canister_data,
+            standby_canister_id,
+            Some(Err(ReplicationError::StaleEpoch { current_epoch: 1 })),
+            SentReplicationDelta::default(),
+            SystemTime::UNIX_EPOCH,
+        ));
+        assert_eq!(canister_data.replication.role, ReplicaRole::Primary);
+
+        handle_replication_response_impl(
+            &mut canister_data,
+            standby_canister_id,
+            Some(Err(ReplicationError::StaleEpoch { current_epoch: 2 })),
+            SentReplicationDelta::default(),
+            SystemTime::UNIX_EPOCH,
+        );
+        assert_eq!(canister_data.replication.role, ReplicaRole::Fenced);
+        assert!(canister_data.is_read_only);
+        assert!(get_replication_delta_impl(&canister_data).is_none());
+    }
+}
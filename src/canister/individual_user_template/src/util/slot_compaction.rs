@@ -0,0 +1,186 @@
+use std::time::{Duration, SystemTime};
+
+use shared_utils::common::utils::system_time;
+
+use crate::{data_model::CanisterData, CANISTER_DATA};
+
+const SLOT_COMPACTION_INTERVAL: Duration = Duration::from_secs(15 * 60);
+// * Posts read out of stable memory per pass
+const MAX_POSTS_SCANNED_PER_SLOT_COMPACTION_PASS: usize = 100;
+
+/// Compacts posts whose slots are all settled and past disputes, a batch of posts per pass,
+/// going round every post. Their bets are archived in stable memory and only summaries stay
+/// in their rooms, which makes the posts much cheaper to load. Timers don't survive upgrades,
+/// so this is started again from `post_upgrade`.
+pub fn enqueue_timer_for_compacting_settled_slots() {
+    ic_cdk_timers::set_timer_interval(SLOT_COMPACTION_INTERVAL, || {
+        CANISTER_DATA.with(|canister_data_ref_cell| {
+            compact_settled_slots_impl(
+                &mut canister_data_ref_cell.borrow_mut(),
+                &system_time::get_current_system_time_from_ic(),
+            );
+        });
+    });
+}
+
+/// Returns the number of posts compacted
+fn compact_settled_slots_impl(canister_data: &mut CanisterData, current_time: &SystemTime) -> u64 {
+    let post_ids_to_scan = canister_data
+        .all_created_posts
+        .keys_from(canister_data.slot_compaction_cursor)
+        .take(MAX_POSTS_SCANNED_PER_SLOT_COMPACTION_PASS)
+        .collect::<Vec<_>>();
+
+    // * Starts over from the first post once the last one has been scanned
+    canister_data.slot_compaction_cursor = match post_ids_to_scan.last() {
+        Some(last_post_id)
+            if post_ids_to_scan.len() == MAX_POSTS_SCANNED_PER_SLOT_COMPACTION_PASS =>
+        {
+            last_post_id + 1
+        }
+        _ => 0,
+    };
+
+    post_ids_to_scan
+        .into_iter()
+        .filter(|post_id| {
+            let Some(mut post) = canister_data.all_created_posts.get(post_id) else {
+                return false;
+            };
+            if !post.is_ready_for_slot_compaction(current_time) {
+                return false;
+            }
+
+            let archived_bets = post.compact_settled_slots();
+            canister_data
+                .all_created_posts
+                .archive_bets(*post_id, archived_bets);
+            canister_data.all_created_posts.insert(*post_id, post);
+
+            true
+        })
+        .count() as u64
+}
+
+#[cfg(test)]
+mod test {
+    use shared_utils::{
+        canister_specific::individual_user_template::types::{
+            dispute::SLOT_OUTCOME_DISPUTE_WINDOW,
+            hot_or_not::{
+                BetDirection, HotOrNotPayoutMode, TOTAL_DURATION_OF_ALL_SLOTS_IN_SECONDS,
+            },
+            post::{Post, PostDetailsFromFrontend},
+            token::TokenBalance,
+        },
+        common::types::app_primitive_type::PostId,
+    };
+    use test_utils::setup::test_constants::{
+        get_mock_user_alice_canister_id, get_mock_user_bob_canister_id,
+        get_mock_user_bob_principal_id,
+    };
+
+    use super::*;
+
+    fn get_post_with_settled_bet(post_id: PostId, created_at: &SystemTime) -> Post {
+        let mut post = Post::new(
+            post_id,
+            &PostDetailsFromFrontend {
+                description: "Doggos and puppers".into(),
+                hashtags: vec!["doggo".into(), "pupper".into()],
+                video_uid: "abcd#1234".into(),
+                creator_consent_for_inclusion_in_hot_or_not: true,
+                collaborator_shares: None,
+                title: None,
+                category: None,
+            },
+            created_at,
+        );
+        post.place_hot_or_not_bet(
+            &get_mock_user_bob_principal_id(),
+            &get_mock_user_bob_canister_id(),
+            100,
+            &BetDirection::Hot,
+            created_at,
+        )
+        .unwrap();
+        let settled_at = post.get_slot_settled_at(1);
+        post.tabulate_hot_or_not_outcome_for_slot(
+            &get_mock_user_alice_canister_id(),
+            &1,
+            &mut TokenBalance::default(),
+            0,
+            0,
+            HotOrNotPayoutMode::Multiplier,
+            &settled_at,
+        );
+
+        post
+    }
+
+    #[test]
+    fn test_compact_settled_slots_impl() {
+        let mut canister_data = CanisterData::default();
+        let created_at = SystemTime::UNIX_EPOCH;
+        let compactable_from = created_at
+            + Duration::from_secs(TOTAL_DURATION_OF_ALL_SLOTS_IN_SECONDS)
+            + SLOT_OUTCOME_DISPUTE_WINDOW;
+        (0..MAX_POSTS_SCANNED_PER_SLOT_COMPACTION_PASS as PostId + 1).for_each(|post_id| {
+            canister_data
+                .all_created_posts
+                .insert(post_id, get_post_with_settled_bet(post_id, &created_at));
+        });
+
+        // * a full round over every post, with nothing ready to compact yet
+        assert_eq!(
+            compact_settled_slots_impl(&mut canister_data, &created_at),
+            0
+        );
+        assert_eq!(
+            canister_data.slot_compaction_cursor,
+            MAX_POSTS_SCANNED_PER_SLOT_COMPACTION_PASS as PostId
+        );
+        assert_eq!(
+            compact_settled_slots_impl(&mut canister_data, &created_at),
+            0
+        );
+        assert_eq!(canister_data.slot_compaction_cursor, 0);
+
+        assert_eq!(
+            compact_settled_slots_impl(&mut canister_data, &compactable_from),
+            MAX_POSTS_SCANNED_PER_SLOT_COMPACTION_PASS as u64
+        );
+        assert_eq!(
+            canister_data.slot_compaction_cursor,
+            MAX_POSTS_SCANNED_PER_SLOT_COMPACTION_PASS as PostId
+        );
+        assert_eq!(
+            compact_settled_slots_impl(&mut canister_data, &compactable_from),
+            1
+        );
+        assert_eq!(canister_data.slot_compaction_cursor, 0);
+
+        // * every post is compacted by now
+        assert_eq!(
+            compact_settled_slots_impl(&mut canister_data, &compactable_from),
+            0
+        );
+
+        let compacted_post = canister_data.all_created_posts.get(&0).unwrap();
+        let room_details =
+            &compacted_post.hot_or_not_details.unwrap().slot_history[&1].room_details[&1];
+        assert!(room_details.bets_made.is_empty());
+        assert!(room_details.compacted_bets.is_some());
+        assert_eq!(
+            canister_data
+                .all_created_posts
+                .get_with_archived_bets(&0)
+                .unwrap()
+                .hot_or_not_details
+                .unwrap()
+                .get_bet_history()
+                .len(),
+            1
+        );
+    }
+}
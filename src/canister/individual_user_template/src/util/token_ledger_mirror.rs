@@ -0,0 +1,156 @@
+use std::{cell::Cell, time::Duration};
+
+use candid::{Nat, Principal};
+use shared_utils::{
+    canister_specific::individual_user_template::types::token_ledger::{
+        get_token_ledger_adjustment, TokenLedgerAdjustment,
+    },
+    common::{
+        types::{
+            event_counter::EventCounterName,
+            known_principal::KnownPrincipalType,
+            utility_token::icrc1::{nat_to_u64, Account, TransferArg, TransferResult},
+        },
+        utils::system_time,
+    },
+};
+
+use crate::CANISTER_DATA;
+
+const TOKEN_LEDGER_SYNC_INTERVAL: Duration = Duration::from_secs(5 * 60);
+
+thread_local! {
+    // * Keeps a slow ledger from having two syncs adjust the same balance at once
+    static IS_TOKEN_LEDGER_SYNC_IN_PROGRESS: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Mirrors the utility token balance to the token ledger, once one is configured, whenever it
+/// changed and otherwise every so often to reconcile the two. Syncs that fail, e.g. during a
+/// ledger outage, are simply tried again on the next tick. Timers don't survive upgrades, so
+/// this is started again from `post_upgrade`.
+pub fn enqueue_timer_for_mirroring_token_balance_to_ledger() {
+    ic_cdk_timers::set_timer_interval(TOKEN_LEDGER_SYNC_INTERVAL, || {
+        ic_cdk::spawn(sync_token_balance_with_ledger())
+    });
+}
+
+async fn sync_token_balance_with_ledger() {
+    let current_time = system_time::get_current_system_time_from_ic();
+
+    let (token_ledger_canister_id, user_index_canister_id, is_sync_due) =
+        CANISTER_DATA.with(|canister_data_ref_cell| {
+            let canister_data = canister_data_ref_cell.borrow();
+
+            (
+                canister_data
+                    .known_principal_ids
+                    .get(&KnownPrincipalType::CanisterIdTokenLedger)
+                    .cloned(),
+                canister_data
+                    .known_principal_ids
+                    .get(&KnownPrincipalType::CanisterIdUserIndex)
+                    .cloned(),
+                canister_data.token_ledger_mirror.is_sync_due(
+                    canister_data.my_token_balance.utility_token_balance,
+                    &current_time,
+                ),
+            )
+        });
+
+    let (Some(token_ledger_canister_id), Some(user_index_canister_id)) =
+        (token_ledger_canister_id, user_index_canister_id)
+    else {
+        return;
+    };
+
+    if !is_sync_due
+        || IS_TOKEN_LEDGER_SYNC_IN_PROGRESS.with(|is_in_progress| is_in_progress.replace(true))
+    {
+        return;
+    }
+
+    let response =
+        adjust_ledger_to_utility_token_balance(token_ledger_canister_id, user_index_canister_id)
+            .await;
+
+    IS_TOKEN_LEDGER_SYNC_IN_PROGRESS.with(|is_in_progress| is_in_progress.set(false));
+
+    CANISTER_DATA.with(|canister_data_ref_cell| {
+        let canister_data = &mut canister_data_ref_cell.borrow_mut();
+        let current_time = system_time::get_current_system_time_from_ic();
+
+        match response {
+            Ok(ledger_balance) => canister_data
+                .token_ledger_mirror
+                .record_sync(ledger_balance, &current_time),
+            Err(error) => {
+                canister_data.token_ledger_mirror.record_failure(error);
+                canister_data
+                    .event_counters
+                    .increment(EventCounterName::SettlementFailuresTotal, "token_ledger");
+            }
+        }
+    });
+}
+
+/// Mints or burns whatever the ledger account of this canister is off by. The ledger balance
+/// is read first, so an adjustment that went through without this canister hearing back
+/// isn't repeated. The user index is the ledger's minting account, so it does the minting
+/// and burnt tokens are sent to it. Returns the ledger balance after the adjustment.
+async fn adjust_ledger_to_utility_token_balance(
+    token_ledger_canister_id: Principal,
+    user_index_canister_id: Principal,
+) -> Result<u64, String> {
+    let (ledger_balance,) = ic_cdk::call::<_, (Nat,)>(
+        token_ledger_canister_id,
+        "icrc1_balance_of",
+        (Account::of(ic_cdk::id()),),
+    )
+    .await
+    .map_err(|(rejection_code, message)| format!("{:?}: {}", rejection_code, message))?;
+    let ledger_balance =
+        nat_to_u64(&ledger_balance).ok_or_else(|| "Ledger balance out of range".to_string())?;
+
+    // * Read once the ledger has answered, so token events applied meanwhile are included
+    let utility_token_balance = CANISTER_DATA.with(|canister_data_ref_cell| {
+        canister_data_ref_cell
+            .borrow()
+            .my_token_balance
+            .utility_token_balance
+    });
+
+    match get_token_ledger_adjustment(utility_token_balance, ledger_balance) {
+        TokenLedgerAdjustment::None => {}
+        TokenLedgerAdjustment::Mint(amount) => {
+            let (response,) = ic_cdk::call::<_, (Result<Nat, String>,)>(
+                user_index_canister_id,
+                "mint_utility_tokens_for_individual_user_canister",
+                (amount,),
+            )
+            .await
+            .map_err(|(rejection_code, message)| format!("{:?}: {}", rejection_code, message))?;
+
+            response?;
+        }
+        TokenLedgerAdjustment::Burn(amount) => {
+            let (response,) = ic_cdk::call::<_, (TransferResult,)>(
+                token_ledger_canister_id,
+                "icrc1_transfer",
+                (TransferArg {
+                    from_subaccount: None,
+                    to: Account::of(user_index_canister_id),
+                    amount: Nat::from(amount),
+                    fee: None,
+                    memo: None,
+                    created_at_time: None,
+                },),
+            )
+            .await
+            .map_err(|(rejection_code, message)| format!("{:?}: {}", rejection_code, message))?;
+
+            response.map_err(|transfer_error| format!("{:?}", transfer_error))?;
+        }
+    }
+
+    Ok(utility_token_balance)
+}
@@ -0,0 +1,59 @@
+use shared_utils::canister_specific::leaderboard::types::args::LeaderboardInitArgs;
+
+use crate::{data::CanisterData, CANISTER_DATA};
+
+#[ic_cdk::init]
+#[candid::candid_method(init)]
+fn init(init_args: LeaderboardInitArgs) {
+    CANISTER_DATA.with(|canister_data_ref_cell| {
+        let mut data = canister_data_ref_cell.borrow_mut();
+        init_impl(init_args, &mut data);
+    });
+}
+
+fn init_impl(init_args: LeaderboardInitArgs, data: &mut CanisterData) {
+    data.known_principal_ids
+        .extend(init_args.known_principal_ids.unwrap_or_default().iter());
+}
+
+#[cfg(test)]
+mod test {
+    use shared_utils::common::types::known_principal::{KnownPrincipalMap, KnownPrincipalType};
+    use test_utils::setup::test_constants::{
+        get_global_super_admin_principal_id, get_mock_canister_id_user_index,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_init_impl() {
+        let mut known_principal_ids = KnownPrincipalMap::new();
+        known_principal_ids.insert(
+            KnownPrincipalType::UserIdGlobalSuperAdmin,
+            get_global_super_admin_principal_id(),
+        );
+        known_principal_ids.insert(
+            KnownPrincipalType::CanisterIdUserIndex,
+            get_mock_canister_id_user_index(),
+        );
+        let mut data = CanisterData::default();
+
+        init_impl(
+            LeaderboardInitArgs {
+                known_principal_ids: Some(known_principal_ids),
+            },
+            &mut data,
+        );
+
+        assert_eq!(
+            data.known_principal_ids
+                .get(&KnownPrincipalType::UserIdGlobalSuperAdmin),
+            Some(&get_global_super_admin_principal_id())
+        );
+        assert_eq!(
+            data.known_principal_ids
+                .get(&KnownPrincipalType::CanisterIdUserIndex),
+            Some(&get_mock_canister_id_user_index())
+        );
+    }
+}
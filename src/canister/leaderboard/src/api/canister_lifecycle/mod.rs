@@ -0,0 +1,3 @@
+pub mod init;
+pub mod post_upgrade;
+pub mod pre_upgrade;
@@ -0,0 +1,12 @@
+use ic_cdk::storage;
+
+use crate::CANISTER_DATA;
+
+#[ic_cdk::pre_upgrade]
+fn pre_upgrade() {
+    CANISTER_DATA.with(|canister_data_ref_cell| {
+        let canister_data = canister_data_ref_cell.take();
+
+        storage::stable_save((canister_data,)).ok();
+    });
+}
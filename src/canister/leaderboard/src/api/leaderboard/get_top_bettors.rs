@@ -0,0 +1,19 @@
+use shared_utils::{
+    canister_specific::leaderboard::types::leaderboard::{LeaderboardPeriod, TopBettor},
+    common::utils::system_time::{IcTimeProvider, TimeProvider},
+};
+
+use crate::CANISTER_DATA;
+
+#[ic_cdk::query]
+#[candid::candid_method(query)]
+fn get_top_bettors(period: LeaderboardPeriod, limit: u64) -> Vec<TopBettor> {
+    let current_time = IcTimeProvider.get_current_time();
+
+    CANISTER_DATA.with(|canister_data_ref_cell| {
+        canister_data_ref_cell
+            .borrow()
+            .leaderboard
+            .get_top_bettors(period, limit, &current_time)
+    })
+}
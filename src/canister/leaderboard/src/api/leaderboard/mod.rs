@@ -0,0 +1,3 @@
+pub mod get_top_bettors;
+pub mod get_top_creators;
+pub mod receive_slot_leaderboard_deltas;
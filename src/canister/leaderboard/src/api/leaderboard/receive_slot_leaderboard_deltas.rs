@@ -0,0 +1,95 @@
+use std::time::SystemTime;
+
+use candid::Principal;
+use shared_utils::{
+    canister_specific::leaderboard::types::leaderboard::SlotLeaderboardDeltas,
+    common::utils::system_time::{IcTimeProvider, TimeProvider},
+};
+
+use crate::{data::CanisterData, CANISTER_DATA};
+
+/// # Access Control
+/// Creator stats are credited to the calling canister, so a canister can only report slots
+/// of its own posts
+#[ic_cdk::update]
+#[candid::candid_method(update)]
+fn receive_slot_leaderboard_deltas(deltas: SlotLeaderboardDeltas) {
+    let creator_canister_id = ic_cdk::caller();
+    let current_time = IcTimeProvider.get_current_time();
+
+    CANISTER_DATA.with(|canister_data_ref_cell| {
+        receive_slot_leaderboard_deltas_impl(
+            &mut canister_data_ref_cell.borrow_mut(),
+            creator_canister_id,
+            deltas,
+            &current_time,
+        );
+    });
+}
+
+fn receive_slot_leaderboard_deltas_impl(
+    canister_data: &mut CanisterData,
+    creator_canister_id: Principal,
+    deltas: SlotLeaderboardDeltas,
+    current_time: &SystemTime,
+) {
+    if creator_canister_id == Principal::anonymous() {
+        return;
+    }
+
+    canister_data
+        .leaderboard
+        .record_slot_deltas(creator_canister_id, &deltas, current_time);
+}
+
+#[cfg(test)]
+mod test {
+    use shared_utils::canister_specific::leaderboard::types::leaderboard::{
+        LeaderboardPeriod, TopCreator,
+    };
+    use test_utils::setup::test_constants::get_mock_user_alice_canister_id;
+
+    use super::*;
+
+    #[test]
+    fn test_receive_slot_leaderboard_deltas_impl() {
+        let mut canister_data = CanisterData::default();
+        let current_time = SystemTime::now();
+        let deltas = SlotLeaderboardDeltas {
+            post_id: 0,
+            slot_id: 1,
+            creator_commission: 35,
+            bettor_deltas: vec![],
+        };
+
+        receive_slot_leaderboard_deltas_impl(
+            &mut canister_data,
+            Principal::anonymous(),
+            deltas.clone(),
+            &current_time,
+        );
+        assert!(canister_data
+            .leaderboard
+            .get_top_creators(LeaderboardPeriod::AllTime, 10, &current_time)
+            .is_empty());
+
+        receive_slot_leaderboard_deltas_impl(
+            &mut canister_data,
+            get_mock_user_alice_canister_id(),
+            deltas,
+            &current_time,
+        );
+        assert_eq!(
+            canister_data.leaderboard.get_top_creators(
+                LeaderboardPeriod::AllTime,
+                10,
+                &current_time
+            ),
+            vec![TopCreator {
+                canister_id: get_mock_user_alice_canister_id(),
+                commission_earned: 35,
+                slots_settled: 1,
+            }]
+        );
+    }
+}
@@ -0,0 +1,2 @@
+pub mod canister_lifecycle;
+pub mod leaderboard;
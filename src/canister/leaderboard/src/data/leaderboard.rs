@@ -0,0 +1,338 @@
+use std::{borrow::Cow, cmp::Ordering, collections::BTreeMap, time::SystemTime};
+
+use candid::{CandidType, Deserialize, Principal};
+use shared_utils::canister_specific::{
+    individual_user_template::types::feature_usage::{get_day_number, DayNumber},
+    leaderboard::types::leaderboard::{
+        LeaderboardBetOutcome, LeaderboardPeriod, SlotLeaderboardDeltas, TopBettor, TopCreator,
+        LEADERBOARD_DAYS_IN_A_WEEK, MAX_LEADERBOARD_PAGE_SIZE,
+    },
+};
+
+#[derive(CandidType, Deserialize, Clone, Default, Debug, PartialEq, Eq)]
+struct BettorStats {
+    net_winnings: i64,
+    bets_won: u64,
+    bets_lost: u64,
+}
+
+impl BettorStats {
+    fn add(&mut self, other: &BettorStats) {
+        self.net_winnings = self.net_winnings.saturating_add(other.net_winnings);
+        self.bets_won += other.bets_won;
+        self.bets_lost += other.bets_lost;
+    }
+}
+
+#[derive(CandidType, Deserialize, Clone, Default, Debug, PartialEq, Eq)]
+struct CreatorStats {
+    commission_earned: u64,
+    slots_settled: u64,
+}
+
+impl CreatorStats {
+    fn add(&mut self, other: &CreatorStats) {
+        self.commission_earned = self
+            .commission_earned
+            .saturating_add(other.commission_earned);
+        self.slots_settled += other.slots_settled;
+    }
+}
+
+/// Stats of every bettor and creator that had a slot settled in some window of time
+#[derive(CandidType, Deserialize, Clone, Default)]
+struct PeriodStats {
+    bettors: BTreeMap<Principal, BettorStats>,
+    creators: BTreeMap<Principal, CreatorStats>,
+}
+
+impl PeriodStats {
+    fn record(&mut self, creator_canister_id: Principal, deltas: &SlotLeaderboardDeltas) {
+        deltas.bettor_deltas.iter().for_each(|bettor_delta| {
+            let net_winnings = (bettor_delta.amount_paid_out as i128
+                - bettor_delta.amount_staked as i128)
+                .clamp(i64::MIN as i128, i64::MAX as i128) as i64;
+
+            self.bettors
+                .entry(bettor_delta.bet_maker_canister_id)
+                .or_default()
+                .add(&BettorStats {
+                    net_winnings,
+                    bets_won: (bettor_delta.outcome == LeaderboardBetOutcome::Won) as u64,
+                    bets_lost: (bettor_delta.outcome == LeaderboardBetOutcome::Lost) as u64,
+                });
+        });
+
+        self.creators
+            .entry(creator_canister_id)
+            .or_default()
+            .add(&CreatorStats {
+                commission_earned: deltas.creator_commission,
+                slots_settled: 1,
+            });
+    }
+
+    fn merge(&mut self, other: &PeriodStats) {
+        other.bettors.iter().for_each(|(canister_id, stats)| {
+            self.bettors.entry(*canister_id).or_default().add(stats);
+        });
+        other.creators.iter().for_each(|(canister_id, stats)| {
+            self.creators.entry(*canister_id).or_default().add(stats);
+        });
+    }
+}
+
+/// Bettors and creators ranked across every individual user canister, by day, by the week
+/// up to today and over all time
+#[derive(CandidType, Deserialize, Default)]
+pub struct Leaderboard {
+    all_time: PeriodStats,
+    // * Only the days of the current weekly window are kept
+    days: BTreeMap<DayNumber, PeriodStats>,
+}
+
+impl Leaderboard {
+    pub fn record_slot_deltas(
+        &mut self,
+        creator_canister_id: Principal,
+        deltas: &SlotLeaderboardDeltas,
+        current_time: &SystemTime,
+    ) {
+        let today = get_day_number(current_time);
+
+        self.all_time.record(creator_canister_id, deltas);
+        self.days
+            .entry(today)
+            .or_default()
+            .record(creator_canister_id, deltas);
+
+        self.days = self.days.split_off(&get_first_day_of_week(today));
+    }
+
+    fn get_period_stats(
+        &self,
+        period: LeaderboardPeriod,
+        current_time: &SystemTime,
+    ) -> Cow<'_, PeriodStats> {
+        let today = get_day_number(current_time);
+
+        match period {
+            LeaderboardPeriod::AllTime => Cow::Borrowed(&self.all_time),
+            LeaderboardPeriod::Daily => {
+                self.days.get(&today).map(Cow::Borrowed).unwrap_or_default()
+            }
+            LeaderboardPeriod::Weekly => {
+                Cow::Owned(self.days.range(get_first_day_of_week(today)..=today).fold(
+                    PeriodStats::default(),
+                    |mut week, (_, day)| {
+                        week.merge(day);
+                        week
+                    },
+                ))
+            }
+        }
+    }
+
+    /// Highest net winnings first, ties going to whoever won more bets
+    pub fn get_top_bettors(
+        &self,
+        period: LeaderboardPeriod,
+        limit: u64,
+        current_time: &SystemTime,
+    ) -> Vec<TopBettor> {
+        let top_bettors = self
+            .get_period_stats(period, current_time)
+            .bettors
+            .iter()
+            .map(|(canister_id, stats)| TopBettor {
+                canister_id: *canister_id,
+                net_winnings: stats.net_winnings,
+                bets_won: stats.bets_won,
+                bets_lost: stats.bets_lost,
+            })
+            .collect();
+
+        get_top(top_bettors, limit, |a, b| {
+            b.net_winnings
+                .cmp(&a.net_winnings)
+                .then(b.bets_won.cmp(&a.bets_won))
+        })
+    }
+
+    /// Most commission earned first
+    pub fn get_top_creators(
+        &self,
+        period: LeaderboardPeriod,
+        limit: u64,
+        current_time: &SystemTime,
+    ) -> Vec<TopCreator> {
+        let top_creators = self
+            .get_period_stats(period, current_time)
+            .creators
+            .iter()
+            .map(|(canister_id, stats)| TopCreator {
+                canister_id: *canister_id,
+                commission_earned: stats.commission_earned,
+                slots_settled: stats.slots_settled,
+            })
+            .collect();
+
+        get_top(top_creators, limit, |a, b| {
+            b.commission_earned.cmp(&a.commission_earned)
+        })
+    }
+}
+
+fn get_first_day_of_week(today: DayNumber) -> DayNumber {
+    today.saturating_sub(LEADERBOARD_DAYS_IN_A_WEEK - 1)
+}
+
+/// Sorts only the entries that make the cut, as the all time entries run into every bettor
+fn get_top<T>(mut entries: Vec<T>, limit: u64, compare: impl Fn(&T, &T) -> Ordering) -> Vec<T> {
+    let limit = limit.min(MAX_LEADERBOARD_PAGE_SIZE) as usize;
+    if limit == 0 {
+        return vec![];
+    }
+
+    if entries.len() > limit {
+        entries.select_nth_unstable_by(limit - 1, &compare);
+        entries.truncate(limit);
+    }
+    entries.sort_by(compare);
+
+    entries
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use shared_utils::canister_specific::leaderboard::types::leaderboard::BettorLeaderboardDelta;
+    use test_utils::setup::test_constants::{
+        get_mock_user_alice_canister_id, get_mock_user_bob_canister_id,
+        get_mock_user_charlie_canister_id, get_mock_user_dan_canister_id,
+    };
+
+    use super::*;
+
+    const DAY: Duration = Duration::from_secs(24 * 60 * 60);
+
+    fn get_deltas(
+        creator_commission: u64,
+        bettor_deltas: Vec<(Principal, u64, u64, LeaderboardBetOutcome)>,
+    ) -> SlotLeaderboardDeltas {
+        SlotLeaderboardDeltas {
+            post_id: 0,
+            slot_id: 1,
+            creator_commission,
+            bettor_deltas: bettor_deltas
+                .into_iter()
+                .map(
+                    |(bet_maker_canister_id, amount_staked, amount_paid_out, outcome)| {
+                        BettorLeaderboardDelta {
+                            bet_maker_canister_id,
+                            amount_staked,
+                            amount_paid_out,
+                            outcome,
+                        }
+                    },
+                )
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_bettors_and_creators_are_ranked() {
+        let mut leaderboard = Leaderboard::default();
+        let alice = get_mock_user_alice_canister_id();
+        let bob = get_mock_user_bob_canister_id();
+        let charlie = get_mock_user_charlie_canister_id();
+        let dan = get_mock_user_dan_canister_id();
+        let now = SystemTime::UNIX_EPOCH + DAY * 100;
+
+        leaderboard.record_slot_deltas(
+            alice,
+            &get_deltas(
+                30,
+                vec![
+                    (bob, 100, 180, LeaderboardBetOutcome::Won),
+                    (charlie, 200, 0, LeaderboardBetOutcome::Lost),
+                ],
+            ),
+            &now,
+        );
+        leaderboard.record_slot_deltas(
+            dan,
+            &get_deltas(50, vec![(charlie, 100, 180, LeaderboardBetOutcome::Won)]),
+            &now,
+        );
+
+        assert_eq!(
+            leaderboard.get_top_bettors(LeaderboardPeriod::Daily, 10, &now),
+            vec![
+                TopBettor {
+                    canister_id: bob,
+                    net_winnings: 80,
+                    bets_won: 1,
+                    bets_lost: 0,
+                },
+                TopBettor {
+                    canister_id: charlie,
+                    net_winnings: -120,
+                    bets_won: 1,
+                    bets_lost: 1,
+                },
+            ]
+        );
+        assert_eq!(
+            leaderboard.get_top_creators(LeaderboardPeriod::AllTime, 1, &now),
+            vec![TopCreator {
+                canister_id: dan,
+                commission_earned: 50,
+                slots_settled: 1,
+            }]
+        );
+        assert!(leaderboard
+            .get_top_bettors(LeaderboardPeriod::Weekly, 0, &now)
+            .is_empty());
+    }
+
+    #[test]
+    fn test_periods_only_cover_their_window() {
+        let mut leaderboard = Leaderboard::default();
+        let alice = get_mock_user_alice_canister_id();
+        let bob = get_mock_user_bob_canister_id();
+        let start = SystemTime::UNIX_EPOCH + DAY * 100;
+
+        (0..10_u32).for_each(|day| {
+            leaderboard.record_slot_deltas(
+                alice,
+                &get_deltas(10, vec![(bob, 100, 180, LeaderboardBetOutcome::Won)]),
+                &(start + DAY * day),
+            );
+        });
+        let last_day = start + DAY * 9;
+
+        let get_bets_won = |period, current_time| {
+            leaderboard.get_top_bettors(period, 10, &current_time)[0].bets_won
+        };
+        assert_eq!(get_bets_won(LeaderboardPeriod::Daily, last_day), 1);
+        assert_eq!(
+            get_bets_won(LeaderboardPeriod::Weekly, last_day),
+            LEADERBOARD_DAYS_IN_A_WEEK
+        );
+        assert_eq!(get_bets_won(LeaderboardPeriod::AllTime, last_day), 10);
+        assert_eq!(leaderboard.days.len(), LEADERBOARD_DAYS_IN_A_WEEK as usize);
+
+        // * nothing settled today, and the week has moved on by two days
+        let two_days_later = last_day + DAY * 2;
+        assert!(leaderboard
+            .get_top_bettors(LeaderboardPeriod::Daily, 10, &two_days_later)
+            .is_empty());
+        assert_eq!(
+            leaderboard.get_top_creators(LeaderboardPeriod::Weekly, 10, &two_days_later)[0]
+                .slots_settled,
+            LEADERBOARD_DAYS_IN_A_WEEK - 2
+        );
+    }
+}
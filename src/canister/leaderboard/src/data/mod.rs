@@ -0,0 +1,12 @@
+use candid::{CandidType, Deserialize};
+use shared_utils::common::types::known_principal::KnownPrincipalMap;
+
+use self::leaderboard::Leaderboard;
+
+pub mod leaderboard;
+
+#[derive(Default, CandidType, Deserialize)]
+pub struct CanisterData {
+    pub known_principal_ids: KnownPrincipalMap,
+    pub leaderboard: Leaderboard,
+}
@@ -1,6 +1,15 @@
 use shared_utils::canister_specific::post_cache::types::arg::PostCacheInitArgs;
 
-use crate::CANISTER_DATA;
+use crate::{
+    api::{
+        feed::{
+            decay_feed_scores::enqueue_timer_for_decaying_feed_scores,
+            feed_response_cache::enqueue_timer_for_warming_feed_response_cache,
+        },
+        hot_or_not_feed::rescore_hot_or_not_feed_v2::enqueue_timer_for_rescoring_hot_or_not_feed_v2,
+    },
+    CANISTER_DATA,
+};
 
 #[ic_cdk::init]
 #[candid::candid_method(init)]
@@ -10,4 +19,8 @@ fn init(init_args: PostCacheInitArgs) {
 
         canister_data.known_principal_ids = init_args.known_principal_ids.unwrap_or_default();
     });
+
+    enqueue_timer_for_warming_feed_response_cache();
+    enqueue_timer_for_decaying_feed_scores();
+    enqueue_timer_for_rescoring_hot_or_not_feed_v2();
 }
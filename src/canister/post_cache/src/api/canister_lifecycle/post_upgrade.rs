@@ -3,8 +3,16 @@ use std::time::Duration;
 use shared_utils::common::utils::stable_memory_serializer_deserializer;
 
 use crate::{
-    api::well_known_principal::update_locally_stored_well_known_principals,
-    data_model::CanisterData, CANISTER_DATA,
+    api::{
+        feed::{
+            decay_feed_scores::enqueue_timer_for_decaying_feed_scores,
+            feed_response_cache::enqueue_timer_for_warming_feed_response_cache,
+        },
+        hot_or_not_feed::rescore_hot_or_not_feed_v2::enqueue_timer_for_rescoring_hot_or_not_feed_v2,
+        well_known_principal::update_locally_stored_well_known_principals,
+    },
+    data_model::CanisterData,
+    CANISTER_DATA,
 };
 
 use super::pre_upgrade::BUFFER_SIZE_BYTES;
@@ -13,6 +21,9 @@ use super::pre_upgrade::BUFFER_SIZE_BYTES;
 fn post_upgrade() {
     restore_data_from_stable_memory();
     refetch_well_known_principals();
+    enqueue_timer_for_warming_feed_response_cache();
+    enqueue_timer_for_decaying_feed_scores();
+    enqueue_timer_for_rescoring_hot_or_not_feed_v2();
 }
 
 fn restore_data_from_stable_memory() {
@@ -0,0 +1,144 @@
+use shared_utils::{
+    canister_specific::{
+        individual_user_template::types::post::PostCategory, post_cache::types::feed_item::FeedItem,
+    },
+    common::{
+        types::top_posts::post_score_index_item::PostScoreIndexItem,
+        utils::system_time::{IcTimeProvider, TimeProvider},
+    },
+    pagination::{self, PaginationError},
+    types::canister_specific::post_cache::error_types::TopPostsFetchError,
+};
+
+use crate::{api::feed::feed_item_reason::get_feed_items, data_model::CanisterData, CANISTER_DATA};
+
+/// Home feed entries filed under `category`, in home feed order
+#[ic_cdk::query]
+#[candid::candid_method(query)]
+fn get_top_posts_aggregated_from_canisters_on_this_network_for_category_feed(
+    category: PostCategory,
+    from_inclusive_index: u64,
+    to_exclusive_index: u64,
+) -> Result<Vec<FeedItem>, TopPostsFetchError> {
+    CANISTER_DATA.with(|canister_data_ref_cell| {
+        let canister_data = canister_data_ref_cell.borrow();
+        let post_score_index_items =
+            get_top_posts_aggregated_from_canisters_on_this_network_for_category_feed_impl(
+                &canister_data,
+                category,
+                from_inclusive_index,
+                to_exclusive_index,
+            )?;
+
+        Ok(get_feed_items(
+            &canister_data,
+            post_score_index_items,
+            &IcTimeProvider.get_current_time(),
+        ))
+    })
+}
+
+fn get_top_posts_aggregated_from_canisters_on_this_network_for_category_feed_impl(
+    canister_data: &CanisterData,
+    category: PostCategory,
+    from_inclusive_index: u64,
+    to_exclusive_index: u64,
+) -> Result<Vec<PostScoreIndexItem>, TopPostsFetchError> {
+    let posts_in_category: Vec<&PostScoreIndexItem> = canister_data
+        .posts_index_sorted_by_home_feed_score
+        .iter()
+        .filter(|item| {
+            canister_data
+                .post_categories
+                .get(&(item.publisher_canister_id, item.post_id))
+                == Some(&category)
+        })
+        .collect();
+
+    let (from_inclusive_index, to_exclusive_index) = pagination::get_pagination_bounds(
+        from_inclusive_index,
+        to_exclusive_index,
+        posts_in_category.len() as u64,
+    )
+    .map_err(|e| match e {
+        PaginationError::InvalidBoundsPassed => TopPostsFetchError::InvalidBoundsPassed,
+        PaginationError::ReachedEndOfItemsList => TopPostsFetchError::ReachedEndOfItemsList,
+        PaginationError::ExceededMaxNumberOfItemsAllowedInOneRequest => {
+            TopPostsFetchError::ExceededMaxNumberOfItemsAllowedInOneRequest
+        }
+    })?;
+
+    Ok(posts_in_category
+        .into_iter()
+        .skip(from_inclusive_index as usize)
+        .take((to_exclusive_index - from_inclusive_index) as usize)
+        .cloned()
+        .collect())
+}
+
+#[cfg(test)]
+mod test {
+    use test_utils::setup::test_constants::get_mock_user_alice_canister_id;
+
+    use super::*;
+
+    #[test]
+    fn test_get_top_posts_aggregated_from_canisters_on_this_network_for_category_feed_impl() {
+        let mut canister_data = CanisterData::default();
+        (0..4).for_each(|post_id| {
+            canister_data
+                .posts_index_sorted_by_home_feed_score
+                .replace(&PostScoreIndexItem {
+                    score: post_id * 100,
+                    post_id,
+                    publisher_canister_id: get_mock_user_alice_canister_id(),
+                });
+        });
+        canister_data
+            .post_categories
+            .insert((get_mock_user_alice_canister_id(), 1), PostCategory::Music);
+        canister_data
+            .post_categories
+            .insert((get_mock_user_alice_canister_id(), 2), PostCategory::Comedy);
+        canister_data
+            .post_categories
+            .insert((get_mock_user_alice_canister_id(), 3), PostCategory::Music);
+
+        let posts_in_category =
+            get_top_posts_aggregated_from_canisters_on_this_network_for_category_feed_impl(
+                &canister_data,
+                PostCategory::Music,
+                0,
+                10,
+            )
+            .unwrap();
+        assert_eq!(
+            posts_in_category
+                .iter()
+                .map(|item| item.post_id)
+                .collect::<Vec<_>>(),
+            vec![3, 1]
+        );
+
+        assert_eq!(
+            get_top_posts_aggregated_from_canisters_on_this_network_for_category_feed_impl(
+                &canister_data,
+                PostCategory::Music,
+                1,
+                10,
+            )
+            .unwrap()
+            .len(),
+            1
+        );
+        assert_eq!(
+            get_top_posts_aggregated_from_canisters_on_this_network_for_category_feed_impl(
+                &canister_data,
+                PostCategory::Food,
+                0,
+                10,
+            ),
+            Err(TopPostsFetchError::ReachedEndOfItemsList)
+        );
+    }
+}
@@ -0,0 +1,2 @@
+pub mod get_top_posts_aggregated_from_canisters_on_this_network_for_category_feed;
+pub mod receive_post_category_from_publishing_canister;
@@ -0,0 +1,104 @@
+use candid::Principal;
+use shared_utils::{
+    canister_specific::individual_user_template::types::post::PostCategory,
+    common::types::app_primitive_type::PostId,
+};
+
+use crate::{data_model::CanisterData, CANISTER_DATA};
+
+// * Categories of posts that never made it into the home feed are only dropped once there
+// * are this many of them
+const MAXIMUM_NUMBER_OF_POST_CATEGORIES: usize = 3000;
+
+/// Publishing canisters can only categorise their own posts, as entries are keyed by the
+/// calling canister
+#[ic_cdk::update]
+#[candid::candid_method(update)]
+fn receive_post_category_from_publishing_canister(post_id: PostId, category: PostCategory) {
+    let publisher_canister_id = ic_cdk::caller();
+
+    CANISTER_DATA.with(|canister_data_ref_cell| {
+        receive_post_category_from_publishing_canister_impl(
+            &mut canister_data_ref_cell.borrow_mut(),
+            publisher_canister_id,
+            post_id,
+            category,
+        );
+    });
+}
+
+fn receive_post_category_from_publishing_canister_impl(
+    canister_data: &mut CanisterData,
+    publisher_canister_id: Principal,
+    post_id: PostId,
+    category: PostCategory,
+) {
+    if canister_data.post_categories.len() >= MAXIMUM_NUMBER_OF_POST_CATEGORIES {
+        let home_feed_entries = &canister_data
+            .posts_index_sorted_by_home_feed_score
+            .item_presence_index;
+        canister_data
+            .post_categories
+            .retain(|entry, _| home_feed_entries.contains_key(entry));
+    }
+
+    canister_data
+        .post_categories
+        .insert((publisher_canister_id, post_id), category);
+}
+
+#[cfg(test)]
+mod test {
+    use shared_utils::common::types::top_posts::post_score_index_item::PostScoreIndexItem;
+    use test_utils::setup::test_constants::{
+        get_mock_user_alice_canister_id, get_mock_user_bob_canister_id,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_receive_post_category_from_publishing_canister_impl() {
+        let mut canister_data = CanisterData::default();
+        canister_data
+            .posts_index_sorted_by_home_feed_score
+            .replace(&PostScoreIndexItem {
+                score: 100,
+                post_id: 0,
+                publisher_canister_id: get_mock_user_alice_canister_id(),
+            });
+
+        (0..MAXIMUM_NUMBER_OF_POST_CATEGORIES as u64).for_each(|post_id| {
+            receive_post_category_from_publishing_canister_impl(
+                &mut canister_data,
+                get_mock_user_alice_canister_id(),
+                post_id,
+                PostCategory::Comedy,
+            );
+        });
+        assert_eq!(
+            canister_data.post_categories.len(),
+            MAXIMUM_NUMBER_OF_POST_CATEGORIES
+        );
+
+        // * only the categories of posts in the home feed survive the pruning
+        receive_post_category_from_publishing_canister_impl(
+            &mut canister_data,
+            get_mock_user_bob_canister_id(),
+            0,
+            PostCategory::Music,
+        );
+        assert_eq!(canister_data.post_categories.len(), 2);
+        assert_eq!(
+            canister_data
+                .post_categories
+                .get(&(get_mock_user_alice_canister_id(), 0)),
+            Some(&PostCategory::Comedy)
+        );
+        assert_eq!(
+            canister_data
+                .post_categories
+                .get(&(get_mock_user_bob_canister_id(), 0)),
+            Some(&PostCategory::Music)
+        );
+    }
+}
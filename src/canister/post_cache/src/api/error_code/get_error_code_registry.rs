@@ -0,0 +1,33 @@
+use shared_utils::{
+    common::types::error_code::{ErrorCodeRegistryEntry, HasErrorCode},
+    types::canister_specific::post_cache::error_types::{
+        PostDetailsHydrationError, TopPostsFetchError,
+    },
+};
+
+/// Codes of every error this canister's API can return. Clients can fetch this once and key
+/// translations and retry policies on the codes.
+#[ic_cdk::query]
+#[candid::candid_method(query)]
+fn get_error_code_registry() -> Vec<ErrorCodeRegistryEntry> {
+    [
+        TopPostsFetchError::error_code_registry_entries(),
+        PostDetailsHydrationError::error_code_registry_entries(),
+    ]
+    .concat()
+}
+
+#[cfg(test)]
+mod test {
+    use shared_utils::common::types::error_code::get_duplicate_error_codes;
+
+    use super::*;
+
+    #[test]
+    fn test_get_error_code_registry() {
+        let registry = get_error_code_registry();
+
+        assert!(get_duplicate_error_codes(&registry).is_empty());
+        assert_eq!(registry.len(), 4);
+    }
+}
@@ -0,0 +1,72 @@
+use std::time::Duration;
+
+use shared_utils::canister_specific::post_cache::types::feed_response_cache::FeedMode;
+
+use crate::{
+    data_model::score_decay::{SCORE_DECAY_CHUNK_SIZE, SCORE_DECAY_INTERVAL},
+    CANISTER_DATA,
+};
+
+use super::feed_response_cache::invalidate_feed_response_cache;
+
+/// Scores only change when publishers push them, so posts nobody pushes anymore are aged
+/// here instead of lingering at the top of the feeds. Resumes a pass that an upgrade
+/// interrupted.
+pub fn enqueue_timer_for_decaying_feed_scores() {
+    ic_cdk_timers::set_timer_interval(SCORE_DECAY_INTERVAL, start_score_decay_pass);
+
+    let is_pass_in_progress = CANISTER_DATA.with(|canister_data_ref_cell| {
+        canister_data_ref_cell
+            .borrow()
+            .score_decay_pass
+            .is_in_progress()
+    });
+    if is_pass_in_progress {
+        enqueue_next_score_decay_chunk();
+    }
+}
+
+fn start_score_decay_pass() {
+    let has_started = CANISTER_DATA.with(|canister_data_ref_cell| {
+        let canister_data = &mut *canister_data_ref_cell.borrow_mut();
+
+        // * let a pass that is running late finish before starting another
+        if canister_data.score_decay_pass.is_in_progress() {
+            return false;
+        }
+
+        canister_data.score_decay_pass.start(
+            &canister_data.posts_index_sorted_by_home_feed_score,
+            &canister_data.posts_index_sorted_by_hot_or_not_feed_score,
+        );
+        true
+    });
+
+    if has_started {
+        enqueue_next_score_decay_chunk();
+    }
+}
+
+fn enqueue_next_score_decay_chunk() {
+    ic_cdk_timers::set_timer(Duration::ZERO, decay_next_score_decay_chunk);
+}
+
+fn decay_next_score_decay_chunk() {
+    let is_pass_in_progress = CANISTER_DATA.with(|canister_data_ref_cell| {
+        let canister_data = &mut *canister_data_ref_cell.borrow_mut();
+
+        canister_data.score_decay_pass.decay_next_chunk(
+            &mut canister_data.posts_index_sorted_by_home_feed_score,
+            &mut canister_data.posts_index_sorted_by_hot_or_not_feed_score,
+            SCORE_DECAY_CHUNK_SIZE,
+        );
+        canister_data.score_decay_pass.is_in_progress()
+    });
+
+    invalidate_feed_response_cache(FeedMode::HomeFeed);
+    invalidate_feed_response_cache(FeedMode::HotOrNotFeed);
+
+    if is_pass_in_progress {
+        enqueue_next_score_decay_chunk();
+    }
+}
@@ -0,0 +1,133 @@
+use std::{collections::BTreeSet, time::SystemTime};
+
+use shared_utils::{
+    canister_specific::post_cache::types::feed_item::{FeedItem, FeedItemReason},
+    common::types::top_posts::post_score_index_item::PostScoreIndexItem,
+};
+
+use crate::{
+    api::trending_hashtags::get_trending_hashtags_per_language::get_trending_hashtags_per_language_impl,
+    data_model::CanisterData,
+};
+
+// * A hashtag only counts as a match while it's among the top few trending in its language
+const NUMBER_OF_TRENDING_HASHTAGS_TO_MATCH_PER_LANGUAGE: u64 = 5;
+
+/// Tells why each post on a feed page is there. Sponsored posts are marked as such ahead of
+/// anything else, then posts carrying a trending hashtag, and the rest are trending on their
+/// score alone.
+pub fn get_feed_items(
+    canister_data: &CanisterData,
+    post_score_index_items: Vec<PostScoreIndexItem>,
+    current_time: &SystemTime,
+) -> Vec<FeedItem> {
+    let trending_hashtags: BTreeSet<String> = get_trending_hashtags_per_language_impl(
+        canister_data,
+        NUMBER_OF_TRENDING_HASHTAGS_TO_MATCH_PER_LANGUAGE,
+    )
+    .into_iter()
+    .flat_map(|trending_hashtags_for_language| trending_hashtags_for_language.hashtags)
+    .map(|trending_hashtag| trending_hashtag.hashtag)
+    .collect();
+
+    post_score_index_items
+        .into_iter()
+        .map(|post_score_index_item| {
+            let reason = get_feed_item_reason(
+                canister_data,
+                &trending_hashtags,
+                &post_score_index_item,
+                current_time,
+            );
+            FeedItem::new(post_score_index_item, reason)
+        })
+        .collect()
+}
+
+fn get_feed_item_reason(
+    canister_data: &CanisterData,
+    trending_hashtags: &BTreeSet<String>,
+    post_score_index_item: &PostScoreIndexItem,
+    current_time: &SystemTime,
+) -> FeedItemReason {
+    let post_key = (
+        post_score_index_item.publisher_canister_id,
+        post_score_index_item.post_id,
+    );
+
+    if canister_data
+        .post_boosted_until
+        .get(&post_key)
+        .is_some_and(|boosted_until| boosted_until > current_time)
+    {
+        return FeedItemReason::Sponsored;
+    }
+
+    canister_data
+        .post_hashtags
+        .get(&post_key)
+        .and_then(|hashtags| {
+            hashtags
+                .iter()
+                .find(|hashtag| trending_hashtags.contains(*hashtag))
+        })
+        .map(|hashtag| FeedItemReason::HashtagMatch(hashtag.clone()))
+        .unwrap_or(FeedItemReason::Trending)
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use test_utils::setup::test_constants::get_mock_user_alice_canister_id;
+
+    use super::*;
+
+    #[test]
+    fn test_get_feed_items() {
+        let mut canister_data = CanisterData::default();
+        let alice = get_mock_user_alice_canister_id();
+        let current_time = SystemTime::UNIX_EPOCH + Duration::from_secs(1_000);
+
+        let post_score_index_items: Vec<PostScoreIndexItem> = (0..4)
+            .map(|post_id| PostScoreIndexItem {
+                score: 100 - post_id,
+                post_id,
+                publisher_canister_id: alice,
+            })
+            .collect();
+        post_score_index_items.iter().for_each(|item| {
+            canister_data
+                .posts_index_sorted_by_home_feed_score
+                .replace(item);
+        });
+        canister_data
+            .post_hashtags
+            .insert((alice, 0), vec!["doggo".to_string()]);
+        canister_data
+            .post_hashtags
+            .insert((alice, 1), vec!["doggo".to_string()]);
+        canister_data
+            .post_boosted_until
+            .insert((alice, 1), current_time + Duration::from_secs(10));
+        // * the boost has run out
+        canister_data
+            .post_boosted_until
+            .insert((alice, 2), current_time);
+
+        let reasons: Vec<FeedItemReason> =
+            get_feed_items(&canister_data, post_score_index_items, &current_time)
+                .into_iter()
+                .map(|feed_item| feed_item.reason)
+                .collect();
+        assert_eq!(
+            reasons,
+            vec![
+                FeedItemReason::HashtagMatch("doggo".to_string()),
+                FeedItemReason::Sponsored,
+                FeedItemReason::Trending,
+                FeedItemReason::Trending,
+            ]
+        );
+    }
+}
@@ -0,0 +1,175 @@
+use std::time::SystemTime;
+
+use shared_utils::{
+    canister_specific::post_cache::types::{feed_item::FeedItem, feed_response_cache::FeedMode},
+    common::utils::system_time::{IcTimeProvider, TimeProvider},
+    types::canister_specific::post_cache::error_types::TopPostsFetchError,
+};
+
+use crate::{
+    api::{
+        feed::feed_item_reason::get_feed_items,
+        home_feed::get_top_posts_aggregated_from_canisters_on_this_network_for_home_feed::get_top_posts_aggregated_from_canisters_on_this_network_for_home_feed_impl,
+        hot_or_not_feed::{
+            get_top_posts_aggregated_from_canisters_on_this_network_for_hot_or_not_feed::get_top_posts_aggregated_from_canisters_on_this_network_for_hot_or_not_feed_impl,
+            get_top_posts_aggregated_from_canisters_on_this_network_for_hot_or_not_feed_v2::get_top_posts_aggregated_from_canisters_on_this_network_for_hot_or_not_feed_v2_impl,
+        },
+    },
+    data_model::{
+        feed_response_cache::{FeedResponseCacheKey, FEED_RESPONSE_CACHE_TTL},
+        CanisterData,
+    },
+    CANISTER_DATA, FEED_RESPONSE_CACHE,
+};
+
+// * The pages the frontend requests most often, refreshed on every warming run
+const WARMED_PAGE_SIZE: u64 = 10;
+const NUMBER_OF_PAGES_TO_WARM: u64 = 5;
+
+pub fn get_feed_page_from_cache_or_compute(
+    cache_key: FeedResponseCacheKey,
+) -> Result<Vec<FeedItem>, TopPostsFetchError> {
+    let current_time = IcTimeProvider.get_current_time();
+
+    if let Some(cached_response) = FEED_RESPONSE_CACHE.with(|feed_response_cache_ref_cell| {
+        feed_response_cache_ref_cell
+            .borrow_mut()
+            .get(&cache_key, &current_time)
+    }) {
+        return Ok(cached_response);
+    }
+
+    let response = CANISTER_DATA.with(|canister_data_ref_cell| {
+        compute_feed_page(&canister_data_ref_cell.borrow(), &cache_key, &current_time)
+    })?;
+
+    FEED_RESPONSE_CACHE.with(|feed_response_cache_ref_cell| {
+        feed_response_cache_ref_cell.borrow_mut().insert(
+            cache_key,
+            response.clone(),
+            &current_time,
+        );
+    });
+
+    Ok(response)
+}
+
+pub fn invalidate_feed_response_cache(feed_mode: FeedMode) {
+    FEED_RESPONSE_CACHE.with(|feed_response_cache_ref_cell| {
+        feed_response_cache_ref_cell
+            .borrow_mut()
+            .invalidate_feed(feed_mode);
+    });
+}
+
+pub fn enqueue_timer_for_warming_feed_response_cache() {
+    ic_cdk_timers::set_timer_interval(FEED_RESPONSE_CACHE_TTL, warm_feed_response_cache);
+}
+
+fn warm_feed_response_cache() {
+    let current_time = IcTimeProvider.get_current_time();
+
+    FEED_RESPONSE_CACHE.with(|feed_response_cache_ref_cell| {
+        let mut feed_response_cache = feed_response_cache_ref_cell.borrow_mut();
+        feed_response_cache.remove_expired_entries(&current_time);
+
+        CANISTER_DATA.with(|canister_data_ref_cell| {
+            let canister_data = canister_data_ref_cell.borrow();
+
+            get_keys_to_warm().into_iter().for_each(|cache_key| {
+                if let Ok(response) = compute_feed_page(&canister_data, &cache_key, &current_time) {
+                    feed_response_cache.insert(cache_key, response, &current_time);
+                }
+            });
+        });
+    });
+}
+
+fn get_keys_to_warm() -> Vec<FeedResponseCacheKey> {
+    [
+        FeedMode::HomeFeed,
+        FeedMode::HotOrNotFeed,
+        FeedMode::HotOrNotFeedV2,
+    ]
+    .into_iter()
+    .flat_map(|feed_mode| {
+        (0..NUMBER_OF_PAGES_TO_WARM).map(move |page| FeedResponseCacheKey {
+            feed_mode,
+            from_inclusive_index: page * WARMED_PAGE_SIZE,
+            to_exclusive_index: (page + 1) * WARMED_PAGE_SIZE,
+        })
+    })
+    .collect()
+}
+
+fn compute_feed_page(
+    canister_data: &CanisterData,
+    cache_key: &FeedResponseCacheKey,
+    current_time: &SystemTime,
+) -> Result<Vec<FeedItem>, TopPostsFetchError> {
+    let post_score_index_items = match cache_key.feed_mode {
+        FeedMode::HomeFeed => {
+            get_top_posts_aggregated_from_canisters_on_this_network_for_home_feed_impl(
+                cache_key.from_inclusive_index,
+                cache_key.to_exclusive_index,
+                canister_data,
+            )
+        }
+        FeedMode::HotOrNotFeed => {
+            get_top_posts_aggregated_from_canisters_on_this_network_for_hot_or_not_feed_impl(
+                cache_key.from_inclusive_index,
+                cache_key.to_exclusive_index,
+                canister_data,
+            )
+        }
+        FeedMode::HotOrNotFeedV2 => {
+            get_top_posts_aggregated_from_canisters_on_this_network_for_hot_or_not_feed_v2_impl(
+                cache_key.from_inclusive_index,
+                cache_key.to_exclusive_index,
+                canister_data,
+            )
+        }
+    }?;
+
+    Ok(get_feed_items(
+        canister_data,
+        post_score_index_items,
+        current_time,
+    ))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_get_keys_to_warm() {
+        let keys_to_warm = get_keys_to_warm();
+
+        assert_eq!(keys_to_warm.len(), 15);
+        assert_eq!(
+            keys_to_warm[0],
+            FeedResponseCacheKey {
+                feed_mode: FeedMode::HomeFeed,
+                from_inclusive_index: 0,
+                to_exclusive_index: 10,
+            }
+        );
+        assert_eq!(
+            keys_to_warm[9],
+            FeedResponseCacheKey {
+                feed_mode: FeedMode::HotOrNotFeed,
+                from_inclusive_index: 40,
+                to_exclusive_index: 50,
+            }
+        );
+        assert_eq!(
+            keys_to_warm[14],
+            FeedResponseCacheKey {
+                feed_mode: FeedMode::HotOrNotFeedV2,
+                from_inclusive_index: 40,
+                to_exclusive_index: 50,
+            }
+        );
+    }
+}
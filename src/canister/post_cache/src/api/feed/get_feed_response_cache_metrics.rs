@@ -0,0 +1,10 @@
+use shared_utils::canister_specific::post_cache::types::feed_response_cache::FeedResponseCacheMetrics;
+
+use crate::FEED_RESPONSE_CACHE;
+
+#[ic_cdk::query]
+#[candid::candid_method(query)]
+fn get_feed_response_cache_metrics() -> FeedResponseCacheMetrics {
+    FEED_RESPONSE_CACHE
+        .with(|feed_response_cache_ref_cell| feed_response_cache_ref_cell.borrow().get_metrics())
+}
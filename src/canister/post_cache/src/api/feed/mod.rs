@@ -1 +1,7 @@
+pub mod decay_feed_scores;
+pub mod feed_item_reason;
+pub mod feed_response_cache;
+pub mod get_feed_response_cache_metrics;
+pub mod receive_post_ban_from_user_index;
+pub mod receive_post_boost_from_publishing_canister;
 pub mod remove_all_feed_entries;
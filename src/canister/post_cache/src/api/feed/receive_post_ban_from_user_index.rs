@@ -0,0 +1,182 @@
+use candid::Principal;
+use shared_utils::{
+    canister_specific::post_cache::types::feed_response_cache::FeedMode,
+    common::{
+        types::{
+            app_primitive_type::PostId, known_principal::KnownPrincipalType,
+            top_posts::post_score_index_item::PostScoreIndexItem,
+        },
+        utils::known_caller::authorize_known_caller,
+    },
+};
+
+use crate::{data_model::CanisterData, CANISTER_DATA};
+
+use super::feed_response_cache::invalidate_feed_response_cache;
+
+/// Moderators banned the post. Drops it from every feed, and keeps scores its publisher had
+/// already sent from bringing it back.
+///
+/// # Access Control
+/// Only the user index, on behalf of its moderators
+#[ic_cdk::update]
+#[candid::candid_method(update)]
+fn receive_post_ban_from_user_index(publisher_canister_id: Principal, post_id: PostId) {
+    let api_caller = ic_cdk::caller();
+
+    let is_banned = CANISTER_DATA.with(|canister_data_ref_cell| {
+        receive_post_ban_from_user_index_impl(
+            &mut canister_data_ref_cell.borrow_mut(),
+            &api_caller,
+            publisher_canister_id,
+            post_id,
+        )
+    });
+
+    if is_banned {
+        invalidate_feed_response_cache(FeedMode::HomeFeed);
+        invalidate_feed_response_cache(FeedMode::HotOrNotFeed);
+        invalidate_feed_response_cache(FeedMode::HotOrNotFeedV2);
+    }
+}
+
+fn receive_post_ban_from_user_index_impl(
+    canister_data: &mut CanisterData,
+    api_caller: &Principal,
+    publisher_canister_id: Principal,
+    post_id: PostId,
+) -> bool {
+    if authorize_known_caller(
+        api_caller,
+        &canister_data.known_principal_ids,
+        &[KnownPrincipalType::CanisterIdUserIndex],
+    )
+    .is_err()
+    {
+        return false;
+    }
+
+    let post_score_index_item = PostScoreIndexItem {
+        post_id,
+        publisher_canister_id,
+        score: 0,
+    };
+    canister_data
+        .posts_index_sorted_by_home_feed_score
+        .remove(&post_score_index_item);
+    canister_data
+        .posts_index_sorted_by_hot_or_not_feed_score
+        .remove(&post_score_index_item);
+    canister_data
+        .posts_index_sorted_by_hot_or_not_feed_score_v2
+        .remove(&post_score_index_item);
+
+    let entry = (publisher_canister_id, post_id);
+    canister_data.slot_heat_indicators.remove(&entry);
+    canister_data.post_categories.remove(&entry);
+    canister_data.post_hashtags.remove(&entry);
+    canister_data.post_boosted_until.remove(&entry);
+    canister_data.hot_or_not_bet_activity.remove(&entry);
+    canister_data.banned_posts.insert(entry);
+
+    true
+}
+
+#[cfg(test)]
+mod test {
+    use test_utils::setup::{
+        spoofed_callers::{assert_only_allowed_callers_are_accepted, get_mock_known_principal_map},
+        test_constants::{get_mock_user_alice_canister_id, get_mock_user_bob_canister_id},
+    };
+
+    use super::*;
+
+    fn get_canister_data_with_posts_in_feeds() -> CanisterData {
+        let mut canister_data = CanisterData {
+            known_principal_ids: get_mock_known_principal_map(),
+            ..Default::default()
+        };
+        [
+            get_mock_user_alice_canister_id(),
+            get_mock_user_bob_canister_id(),
+        ]
+        .into_iter()
+        .for_each(|publisher_canister_id| {
+            let post_score_index_item = PostScoreIndexItem {
+                post_id: 0,
+                publisher_canister_id,
+                score: 100,
+            };
+            canister_data
+                .posts_index_sorted_by_home_feed_score
+                .replace(&post_score_index_item);
+            canister_data
+                .posts_index_sorted_by_hot_or_not_feed_score
+                .replace(&post_score_index_item);
+            canister_data
+                .post_hashtags
+                .insert((publisher_canister_id, 0), vec!["doggo".into()]);
+        });
+
+        canister_data
+    }
+
+    #[test]
+    fn test_receive_post_ban_from_user_index_impl() {
+        let mut canister_data = get_canister_data_with_posts_in_feeds();
+        let user_index_canister_id = *canister_data
+            .known_principal_ids
+            .get(&KnownPrincipalType::CanisterIdUserIndex)
+            .unwrap();
+
+        assert!(receive_post_ban_from_user_index_impl(
+            &mut canister_data,
+            &user_index_canister_id,
+            get_mock_user_alice_canister_id(),
+            0
+        ));
+
+        let remaining_publishers = |canister_data: &CanisterData| {
+            canister_data
+                .posts_index_sorted_by_home_feed_score
+                .iter()
+                .chain(
+                    canister_data
+                        .posts_index_sorted_by_hot_or_not_feed_score
+                        .iter(),
+                )
+                .map(|item| item.publisher_canister_id)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            remaining_publishers(&canister_data),
+            vec![
+                get_mock_user_bob_canister_id(),
+                get_mock_user_bob_canister_id()
+            ]
+        );
+        assert!(!canister_data
+            .post_hashtags
+            .contains_key(&(get_mock_user_alice_canister_id(), 0)));
+        assert!(canister_data
+            .banned_posts
+            .contains(&(get_mock_user_alice_canister_id(), 0)));
+    }
+
+    #[test]
+    fn test_receive_post_ban_from_user_index_impl_with_spoofed_callers() {
+        assert_only_allowed_callers_are_accepted(
+            &get_mock_known_principal_map(),
+            &[KnownPrincipalType::CanisterIdUserIndex],
+            |caller| {
+                let mut canister_data = get_canister_data_with_posts_in_feeds();
+                receive_post_ban_from_user_index_impl(
+                    &mut canister_data,
+                    caller,
+                    get_mock_user_alice_canister_id(),
+                    0,
+                )
+            },
+        );
+    }
+}
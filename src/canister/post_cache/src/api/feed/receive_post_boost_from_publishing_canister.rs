@@ -0,0 +1,86 @@
+use std::time::SystemTime;
+
+use candid::Principal;
+use shared_utils::common::{
+    types::app_primitive_type::PostId,
+    utils::system_time::{IcTimeProvider, TimeProvider},
+};
+
+use crate::{data_model::CanisterData, CANISTER_DATA};
+
+/// Lets feed pages mark boosted posts as sponsored. Publishing canisters can only report
+/// boosts of their own posts, as entries are keyed by the calling canister.
+#[ic_cdk::update]
+#[candid::candid_method(update)]
+fn receive_post_boost_from_publishing_canister(post_id: PostId, boosted_until: SystemTime) {
+    let publisher_canister_id = ic_cdk::caller();
+
+    CANISTER_DATA.with(|canister_data_ref_cell| {
+        receive_post_boost_from_publishing_canister_impl(
+            &mut canister_data_ref_cell.borrow_mut(),
+            publisher_canister_id,
+            post_id,
+            boosted_until,
+            &IcTimeProvider.get_current_time(),
+        );
+    });
+}
+
+fn receive_post_boost_from_publishing_canister_impl(
+    canister_data: &mut CanisterData,
+    publisher_canister_id: Principal,
+    post_id: PostId,
+    boosted_until: SystemTime,
+    current_time: &SystemTime,
+) {
+    // * Boosts only last a day, so lapsed ones are dropped as new ones come in
+    canister_data
+        .post_boosted_until
+        .retain(|_, boosted_until| *boosted_until > *current_time);
+
+    canister_data
+        .post_boosted_until
+        .insert((publisher_canister_id, post_id), boosted_until);
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use test_utils::setup::test_constants::{
+        get_mock_user_alice_canister_id, get_mock_user_bob_canister_id,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_receive_post_boost_from_publishing_canister_impl() {
+        let mut canister_data = CanisterData::default();
+        let current_time = SystemTime::UNIX_EPOCH + Duration::from_secs(1_000);
+        let alice = get_mock_user_alice_canister_id();
+        let bob = get_mock_user_bob_canister_id();
+
+        receive_post_boost_from_publishing_canister_impl(
+            &mut canister_data,
+            alice,
+            0,
+            current_time + Duration::from_secs(10),
+            &current_time,
+        );
+        receive_post_boost_from_publishing_canister_impl(
+            &mut canister_data,
+            bob,
+            0,
+            current_time + Duration::from_secs(100),
+            &(current_time + Duration::from_secs(20)),
+        );
+
+        assert_eq!(
+            canister_data
+                .post_boosted_until
+                .into_iter()
+                .collect::<Vec<_>>(),
+            vec![((bob, 0), current_time + Duration::from_secs(100))]
+        );
+    }
+}
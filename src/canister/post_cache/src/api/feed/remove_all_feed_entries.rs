@@ -1,9 +1,14 @@
-use shared_utils::common::types::{
-    known_principal::KnownPrincipalType, top_posts::post_score_index::PostScoreIndex,
+use shared_utils::{
+    canister_specific::post_cache::types::feed_response_cache::FeedMode,
+    common::types::{
+        known_principal::KnownPrincipalType, top_posts::post_score_index::PostScoreIndex,
+    },
 };
 
 use crate::{data_model::CanisterData, CANISTER_DATA};
 
+use super::feed_response_cache::invalidate_feed_response_cache;
+
 #[ic_cdk::update]
 #[candid::candid_method(update)]
 fn remove_all_feed_entries() {
@@ -26,11 +31,19 @@ fn remove_all_feed_entries() {
 
         remove_all_feed_entries_impl(&mut canister_data);
     });
+
+    invalidate_feed_response_cache(FeedMode::HomeFeed);
+    invalidate_feed_response_cache(FeedMode::HotOrNotFeed);
+    invalidate_feed_response_cache(FeedMode::HotOrNotFeedV2);
 }
 
 fn remove_all_feed_entries_impl(canister_data: &mut CanisterData) {
     canister_data.posts_index_sorted_by_home_feed_score = PostScoreIndex::default();
     canister_data.posts_index_sorted_by_hot_or_not_feed_score = PostScoreIndex::default();
+    canister_data.posts_index_sorted_by_hot_or_not_feed_score_v2 = PostScoreIndex::default();
+    canister_data.hot_or_not_bet_activity.clear();
+    canister_data.post_categories.clear();
+    canister_data.post_hashtags.clear();
 }
 
 #[cfg(test)]
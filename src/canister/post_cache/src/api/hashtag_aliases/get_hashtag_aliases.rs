@@ -0,0 +1,9 @@
+use crate::CANISTER_DATA;
+
+/// Every hashtag merged into another one, as (merged hashtag, hashtag it was merged into)
+#[ic_cdk::query]
+#[candid::candid_method(query)]
+fn get_hashtag_aliases() -> Vec<(String, String)> {
+    CANISTER_DATA
+        .with(|canister_data_ref_cell| canister_data_ref_cell.borrow().hashtag_aliases.get_all())
+}
@@ -0,0 +1,171 @@
+use std::collections::BTreeMap;
+
+use candid::Principal;
+use ic_cdk::api::call;
+use shared_utils::{
+    canister_specific::post_cache::types::feed_response_cache::FeedMode,
+    common::types::{app_primitive_type::PostId, known_principal::KnownPrincipalType},
+};
+
+use crate::{
+    api::feed::feed_response_cache::invalidate_feed_response_cache, data_model::CanisterData,
+    CANISTER_DATA,
+};
+
+/// Merges misspelled or abusive hashtags into another one. Posts already in the tag index are
+/// retagged right away, and hashtags sent in from now on are merged as they arrive. The
+/// canisters that published the retagged posts are sent the merge so they can retag their
+/// copies and apply it to posts created from then on. Returns the number of posts retagged.
+/// Every post cache shard keeps its own aliases, so merges need to be made on each of them.
+///
+/// # Access Control
+/// Only the global super admin can merge hashtags
+#[ic_cdk::update]
+#[candid::candid_method(update)]
+fn merge_hashtags(hashtags: Vec<String>, into_hashtag: String) -> Result<u64, String> {
+    let api_caller = ic_cdk::caller();
+
+    let (merged_hashtags, retagged_posts) = CANISTER_DATA.with(|canister_data_ref_cell| {
+        merge_hashtags_impl(
+            &mut canister_data_ref_cell.borrow_mut(),
+            &api_caller,
+            &hashtags,
+            &into_hashtag,
+        )
+    })?;
+
+    invalidate_feed_response_cache(FeedMode::HomeFeed);
+    invalidate_feed_response_cache(FeedMode::HotOrNotFeed);
+    invalidate_feed_response_cache(FeedMode::HotOrNotFeedV2);
+
+    let number_of_retagged_posts = retagged_posts.values().map(Vec::len).sum::<usize>();
+    retagged_posts
+        .into_iter()
+        .for_each(|(publisher_canister_id, post_ids)| {
+            let _ = call::notify(
+                publisher_canister_id,
+                "receive_hashtag_merge_from_post_cache",
+                (merged_hashtags.clone(), post_ids),
+            );
+        });
+
+    Ok(number_of_retagged_posts as u64)
+}
+
+// * Normalized (hashtag, into_hashtag) pairs, and the posts retagged by each publisher
+type HashtagMerge = (Vec<(String, String)>, BTreeMap<Principal, Vec<PostId>>);
+
+/// Returns the normalized merges made, along with the posts retagged by each publisher
+fn merge_hashtags_impl(
+    canister_data: &mut CanisterData,
+    api_caller: &Principal,
+    hashtags: &[String],
+    into_hashtag: &str,
+) -> Result<HashtagMerge, String> {
+    if canister_data
+        .known_principal_ids
+        .get(&KnownPrincipalType::UserIdGlobalSuperAdmin)
+        != Some(api_caller)
+    {
+        return Err("Unauthorized caller".to_string());
+    }
+
+    // * Merged on a copy so that one bad hashtag leaves the rest unmerged too
+    let mut hashtag_aliases = canister_data.hashtag_aliases.clone();
+    let merged_hashtags = hashtags
+        .iter()
+        .map(|hashtag| hashtag_aliases.merge(hashtag, into_hashtag))
+        .collect::<Result<Vec<(String, String)>, String>>()?;
+    canister_data.hashtag_aliases = hashtag_aliases;
+
+    let mut retagged_posts: BTreeMap<Principal, Vec<PostId>> = BTreeMap::new();
+    canister_data.post_hashtags.iter_mut().for_each(
+        |((publisher_canister_id, post_id), post_hashtags)| {
+            let retagged_hashtags = canister_data.hashtag_aliases.apply(post_hashtags);
+            if retagged_hashtags != *post_hashtags {
+                *post_hashtags = retagged_hashtags;
+                retagged_posts
+                    .entry(*publisher_canister_id)
+                    .or_default()
+                    .push(*post_id);
+            }
+        },
+    );
+
+    Ok((merged_hashtags, retagged_posts))
+}
+
+#[cfg(test)]
+mod test {
+    use test_utils::setup::test_constants::{
+        get_global_super_admin_principal_id, get_mock_user_alice_canister_id,
+        get_mock_user_alice_principal_id, get_mock_user_bob_canister_id,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_merge_hashtags_impl() {
+        let mut canister_data = CanisterData::default();
+        canister_data.known_principal_ids.insert(
+            KnownPrincipalType::UserIdGlobalSuperAdmin,
+            get_global_super_admin_principal_id(),
+        );
+        let alice = get_mock_user_alice_canister_id();
+        let bob = get_mock_user_bob_canister_id();
+        canister_data
+            .post_hashtags
+            .insert((alice, 0), vec!["dogo".to_string(), "doggo".to_string()]);
+        canister_data
+            .post_hashtags
+            .insert((alice, 1), vec!["cat".to_string()]);
+        canister_data
+            .post_hashtags
+            .insert((bob, 0), vec!["d0ggo".to_string()]);
+
+        assert!(merge_hashtags_impl(
+            &mut canister_data,
+            &get_mock_user_alice_principal_id(),
+            &["dogo".to_string()],
+            "doggo"
+        )
+        .is_err());
+        // * nothing is merged if any of the hashtags can't be
+        assert!(merge_hashtags_impl(
+            &mut canister_data,
+            &get_global_super_admin_principal_id(),
+            &["dogo".to_string(), "#".to_string()],
+            "doggo"
+        )
+        .is_err());
+        assert_eq!(canister_data.hashtag_aliases.resolve("dogo"), "dogo");
+
+        let (merged_hashtags, retagged_posts) = merge_hashtags_impl(
+            &mut canister_data,
+            &get_global_super_admin_principal_id(),
+            &["#Dogo".to_string(), "d0ggo".to_string()],
+            "#Doggo",
+        )
+        .unwrap();
+
+        assert_eq!(
+            merged_hashtags,
+            vec![
+                ("dogo".to_string(), "doggo".to_string()),
+                ("d0ggo".to_string(), "doggo".to_string()),
+            ]
+        );
+        assert_eq!(
+            retagged_posts,
+            BTreeMap::from([(alice, vec![0]), (bob, vec![0])])
+        );
+        assert_eq!(
+            canister_data.post_hashtags[&(alice, 0)],
+            vec!["doggo".to_string()]
+        );
+        assert_eq!(
+            canister_data.post_hashtags[&(alice, 1)],
+            vec!["cat".to_string()]
+        );
+    }
+}
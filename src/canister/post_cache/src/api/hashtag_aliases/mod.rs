@@ -0,0 +1,3 @@
+pub mod get_hashtag_aliases;
+pub mod merge_hashtags;
+pub mod unmerge_hashtag;
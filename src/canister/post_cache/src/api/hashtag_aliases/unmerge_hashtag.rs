@@ -0,0 +1,86 @@
+use candid::Principal;
+use shared_utils::common::types::known_principal::KnownPrincipalType;
+
+use crate::{data_model::CanisterData, CANISTER_DATA};
+
+/// Stops merging a hashtag into another one from now on. Posts already retagged keep the
+/// hashtag they were merged into.
+///
+/// # Access Control
+/// Only the global super admin can unmerge hashtags
+#[ic_cdk::update]
+#[candid::candid_method(update)]
+fn unmerge_hashtag(hashtag: String) -> Result<(), String> {
+    let api_caller = ic_cdk::caller();
+
+    CANISTER_DATA.with(|canister_data_ref_cell| {
+        unmerge_hashtag_impl(
+            &mut canister_data_ref_cell.borrow_mut(),
+            &api_caller,
+            &hashtag,
+        )
+    })
+}
+
+fn unmerge_hashtag_impl(
+    canister_data: &mut CanisterData,
+    api_caller: &Principal,
+    hashtag: &str,
+) -> Result<(), String> {
+    if canister_data
+        .known_principal_ids
+        .get(&KnownPrincipalType::UserIdGlobalSuperAdmin)
+        != Some(api_caller)
+    {
+        return Err("Unauthorized caller".to_string());
+    }
+
+    if !canister_data.hashtag_aliases.unmerge(hashtag) {
+        return Err("Hashtag is not merged into another one".to_string());
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use test_utils::setup::test_constants::{
+        get_global_super_admin_principal_id, get_mock_user_alice_principal_id,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_unmerge_hashtag_impl() {
+        let mut canister_data = CanisterData::default();
+        canister_data.known_principal_ids.insert(
+            KnownPrincipalType::UserIdGlobalSuperAdmin,
+            get_global_super_admin_principal_id(),
+        );
+        canister_data
+            .hashtag_aliases
+            .merge("dogo", "doggo")
+            .unwrap();
+
+        assert!(unmerge_hashtag_impl(
+            &mut canister_data,
+            &get_mock_user_alice_principal_id(),
+            "dogo"
+        )
+        .is_err());
+        assert_eq!(
+            unmerge_hashtag_impl(
+                &mut canister_data,
+                &get_global_super_admin_principal_id(),
+                "#Dogo"
+            ),
+            Ok(())
+        );
+        assert!(unmerge_hashtag_impl(
+            &mut canister_data,
+            &get_global_super_admin_principal_id(),
+            "dogo"
+        )
+        .is_err());
+    }
+}
@@ -1,6 +1,12 @@
-use shared_utils::common::types::top_posts::post_score_index_item::PostScoreIndexItem;
+use shared_utils::{
+    canister_specific::post_cache::types::feed_response_cache::FeedMode,
+    common::types::top_posts::post_score_index_item::PostScoreIndexItem,
+};
 
-use crate::{data_model::CanisterData, CANISTER_DATA};
+use crate::{
+    api::feed::feed_response_cache::invalidate_feed_response_cache, data_model::CanisterData,
+    CANISTER_DATA,
+};
 
 #[ic_cdk::update]
 #[candid::candid_method(update)]
@@ -15,6 +21,8 @@ fn receive_top_home_feed_posts_from_publishing_canister(
             &mut canister_data,
         );
     });
+
+    invalidate_feed_response_cache(FeedMode::HomeFeed);
 }
 
 fn receive_top_home_feed_posts_from_publishing_canister_impl(
@@ -25,6 +33,13 @@ fn receive_top_home_feed_posts_from_publishing_canister_impl(
         &mut canister_data.posts_index_sorted_by_home_feed_score;
 
     for post_score_index_item in top_posts_from_publishing_canister {
+        if canister_data.banned_posts.contains(&(
+            post_score_index_item.publisher_canister_id,
+            post_score_index_item.post_id,
+        )) {
+            continue;
+        }
+
         posts_index_sorted_by_home_feed_score.replace(&post_score_index_item);
     }
 
@@ -99,4 +114,38 @@ mod test {
             1
         );
     }
+
+    #[test]
+    fn test_receive_top_home_feed_posts_from_publishing_canister_impl_skips_banned_posts() {
+        let mut canister_data = CanisterData::default();
+        let publisher_canister_id = Principal::from_text("aaaaa-aa").unwrap();
+        canister_data
+            .banned_posts
+            .insert((publisher_canister_id, 1));
+
+        receive_top_home_feed_posts_from_publishing_canister_impl(
+            vec![
+                PostScoreIndexItem {
+                    post_id: 1,
+                    score: 1,
+                    publisher_canister_id,
+                },
+                PostScoreIndexItem {
+                    post_id: 2,
+                    score: 2,
+                    publisher_canister_id,
+                },
+            ],
+            &mut canister_data,
+        );
+
+        assert_eq!(
+            canister_data
+                .posts_index_sorted_by_home_feed_score
+                .iter()
+                .map(|item| item.post_id)
+                .collect::<Vec<_>>(),
+            vec![2]
+        );
+    }
 }
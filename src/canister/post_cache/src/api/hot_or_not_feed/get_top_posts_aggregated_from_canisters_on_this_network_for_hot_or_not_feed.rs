@@ -1,5 +1,9 @@
-use crate::{data_model::CanisterData, CANISTER_DATA};
+use crate::{
+    api::feed::feed_response_cache::get_feed_page_from_cache_or_compute,
+    data_model::{feed_response_cache::FeedResponseCacheKey, CanisterData},
+};
 use shared_utils::{
+    canister_specific::post_cache::types::{feed_item::FeedItem, feed_response_cache::FeedMode},
     common::types::top_posts::post_score_index_item::PostScoreIndexItem,
     pagination::{self, PaginationError},
     types::canister_specific::post_cache::error_types::TopPostsFetchError,
@@ -10,19 +14,15 @@ use shared_utils::{
 fn get_top_posts_aggregated_from_canisters_on_this_network_for_hot_or_not_feed(
     from_inclusive_index: u64,
     to_exclusive_index: u64,
-) -> Result<Vec<PostScoreIndexItem>, TopPostsFetchError> {
-    CANISTER_DATA.with(|canister_data| {
-        let canister_data = canister_data.borrow();
-
-        get_top_posts_aggregated_from_canisters_on_this_network_for_hot_or_not_feed_impl(
-            from_inclusive_index,
-            to_exclusive_index,
-            &canister_data,
-        )
+) -> Result<Vec<FeedItem>, TopPostsFetchError> {
+    get_feed_page_from_cache_or_compute(FeedResponseCacheKey {
+        feed_mode: FeedMode::HotOrNotFeed,
+        from_inclusive_index,
+        to_exclusive_index,
     })
 }
 
-fn get_top_posts_aggregated_from_canisters_on_this_network_for_hot_or_not_feed_impl(
+pub fn get_top_posts_aggregated_from_canisters_on_this_network_for_hot_or_not_feed_impl(
     from_inclusive_index: u64,
     to_exclusive_index: u64,
     canister_data: &CanisterData,
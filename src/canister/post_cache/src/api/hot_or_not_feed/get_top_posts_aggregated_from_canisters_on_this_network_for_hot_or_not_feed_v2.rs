@@ -0,0 +1,97 @@
+use crate::{
+    api::feed::feed_response_cache::get_feed_page_from_cache_or_compute,
+    data_model::{feed_response_cache::FeedResponseCacheKey, CanisterData},
+};
+use shared_utils::{
+    canister_specific::post_cache::types::{feed_item::FeedItem, feed_response_cache::FeedMode},
+    common::types::top_posts::post_score_index_item::PostScoreIndexItem,
+    pagination::{self, PaginationError},
+    types::canister_specific::post_cache::error_types::TopPostsFetchError,
+};
+
+/// Same as `get_top_posts_aggregated_from_canisters_on_this_network_for_hot_or_not_feed`,
+/// but ranked by live betting activity on the posts: recent bet volume and total pot,
+/// decayed by the age of the post
+#[ic_cdk::query]
+#[candid::candid_method(query)]
+fn get_top_posts_aggregated_from_canisters_on_this_network_for_hot_or_not_feed_v2(
+    from_inclusive_index: u64,
+    to_exclusive_index: u64,
+) -> Result<Vec<FeedItem>, TopPostsFetchError> {
+    get_feed_page_from_cache_or_compute(FeedResponseCacheKey {
+        feed_mode: FeedMode::HotOrNotFeedV2,
+        from_inclusive_index,
+        to_exclusive_index,
+    })
+}
+
+pub fn get_top_posts_aggregated_from_canisters_on_this_network_for_hot_or_not_feed_v2_impl(
+    from_inclusive_index: u64,
+    to_exclusive_index: u64,
+    canister_data: &CanisterData,
+) -> Result<Vec<PostScoreIndexItem>, TopPostsFetchError> {
+    let all_posts = &canister_data.posts_index_sorted_by_hot_or_not_feed_score_v2;
+
+    let (from_inclusive_index, to_exclusive_index) = pagination::get_pagination_bounds(
+        from_inclusive_index,
+        to_exclusive_index,
+        all_posts.iter().count() as u64,
+    )
+    .map_err(|e| match e {
+        PaginationError::InvalidBoundsPassed => TopPostsFetchError::InvalidBoundsPassed,
+        PaginationError::ReachedEndOfItemsList => TopPostsFetchError::ReachedEndOfItemsList,
+        PaginationError::ExceededMaxNumberOfItemsAllowedInOneRequest => {
+            TopPostsFetchError::ExceededMaxNumberOfItemsAllowedInOneRequest
+        }
+    })?;
+
+    Ok(all_posts
+        .iter()
+        .skip(from_inclusive_index as usize)
+        .take((to_exclusive_index - from_inclusive_index) as usize)
+        .cloned()
+        .collect())
+}
+
+#[cfg(test)]
+mod test {
+    use test_utils::setup::test_constants::get_mock_user_alice_canister_id;
+
+    use super::*;
+
+    #[test]
+    fn test_get_top_posts_aggregated_from_canisters_on_this_network_for_hot_or_not_feed_v2_impl() {
+        let mut canister_data = CanisterData::default();
+
+        assert_eq!(
+            get_top_posts_aggregated_from_canisters_on_this_network_for_hot_or_not_feed_v2_impl(
+                0,
+                10,
+                &canister_data,
+            ),
+            Err(TopPostsFetchError::ReachedEndOfItemsList)
+        );
+
+        (0..3).for_each(|post_id| {
+            canister_data
+                .posts_index_sorted_by_hot_or_not_feed_score_v2
+                .replace(&PostScoreIndexItem {
+                    post_id,
+                    score: post_id * 10,
+                    publisher_canister_id: get_mock_user_alice_canister_id(),
+                });
+        });
+
+        let page =
+            get_top_posts_aggregated_from_canisters_on_this_network_for_hot_or_not_feed_v2_impl(
+                1,
+                10,
+                &canister_data,
+            )
+            .unwrap();
+        assert_eq!(
+            page.iter().map(|item| item.post_id).collect::<Vec<_>>(),
+            vec![1, 0]
+        );
+    }
+}
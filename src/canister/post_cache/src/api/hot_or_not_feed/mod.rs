@@ -1,2 +1,5 @@
 pub mod get_top_posts_aggregated_from_canisters_on_this_network_for_hot_or_not_feed;
+pub mod get_top_posts_aggregated_from_canisters_on_this_network_for_hot_or_not_feed_v2;
+pub mod receive_hot_or_not_bet_activity_from_publishing_canister;
 pub mod receive_top_hot_or_not_feed_posts_from_publishing_canister;
+pub mod rescore_hot_or_not_feed_v2;
@@ -0,0 +1,189 @@
+use std::time::SystemTime;
+
+use candid::Principal;
+use shared_utils::{
+    canister_specific::post_cache::types::feed_response_cache::FeedMode,
+    common::{
+        types::top_posts::{
+            hot_or_not_bet_activity::{HotOrNotBetActivity, HotOrNotBetActivityDelta},
+            post_score_index_item::PostScoreIndexItem,
+        },
+        utils::system_time::{IcTimeProvider, TimeProvider},
+    },
+};
+
+use crate::{
+    api::feed::feed_response_cache::invalidate_feed_response_cache, data_model::CanisterData,
+    CANISTER_DATA,
+};
+
+/// Bets placed on the calling canister's posts, ranking them in the v2 hot or not feed
+#[ic_cdk::update]
+#[candid::candid_method(update)]
+fn receive_hot_or_not_bet_activity_from_publishing_canister(
+    hot_or_not_bet_activity_deltas: Vec<HotOrNotBetActivityDelta>,
+) {
+    let publisher_canister_id = ic_cdk::caller();
+
+    CANISTER_DATA.with(|canister_data_ref_cell| {
+        receive_hot_or_not_bet_activity_from_publishing_canister_impl(
+            &mut canister_data_ref_cell.borrow_mut(),
+            publisher_canister_id,
+            hot_or_not_bet_activity_deltas,
+            &IcTimeProvider.get_current_time(),
+        );
+    });
+
+    invalidate_feed_response_cache(FeedMode::HotOrNotFeedV2);
+}
+
+fn receive_hot_or_not_bet_activity_from_publishing_canister_impl(
+    canister_data: &mut CanisterData,
+    publisher_canister_id: Principal,
+    hot_or_not_bet_activity_deltas: Vec<HotOrNotBetActivityDelta>,
+    current_time: &SystemTime,
+) {
+    for hot_or_not_bet_activity_delta in hot_or_not_bet_activity_deltas {
+        let entry = (publisher_canister_id, hot_or_not_bet_activity_delta.post_id);
+        if canister_data.banned_posts.contains(&entry) {
+            continue;
+        }
+
+        let hot_or_not_bet_activity = canister_data
+            .hot_or_not_bet_activity
+            .entry(entry)
+            .and_modify(|hot_or_not_bet_activity| {
+                hot_or_not_bet_activity.apply_delta(&hot_or_not_bet_activity_delta)
+            })
+            .or_insert_with(|| HotOrNotBetActivity::from_delta(&hot_or_not_bet_activity_delta));
+
+        canister_data
+            .posts_index_sorted_by_hot_or_not_feed_score_v2
+            .replace(&PostScoreIndexItem {
+                post_id: hot_or_not_bet_activity_delta.post_id,
+                score: hot_or_not_bet_activity.get_score(current_time),
+                publisher_canister_id,
+            });
+    }
+
+    let posts_index_sorted_by_hot_or_not_feed_score_v2 =
+        &mut canister_data.posts_index_sorted_by_hot_or_not_feed_score_v2;
+
+    if posts_index_sorted_by_hot_or_not_feed_score_v2
+        .iter()
+        .count()
+        > 1500
+    {
+        *posts_index_sorted_by_hot_or_not_feed_score_v2 =
+            posts_index_sorted_by_hot_or_not_feed_score_v2
+                .into_iter()
+                .take(1000)
+                .cloned()
+                .collect();
+
+        // * posts that fell out of the index start over if they're bet on again
+        canister_data.hot_or_not_bet_activity.retain(|entry, _| {
+            posts_index_sorted_by_hot_or_not_feed_score_v2
+                .item_presence_index
+                .contains_key(entry)
+        });
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use test_utils::setup::test_constants::{
+        get_mock_user_alice_canister_id, get_mock_user_bob_canister_id,
+    };
+
+    use super::*;
+
+    fn get_delta(post_id: u64, bet_amount: u64, pot_size: u64) -> HotOrNotBetActivityDelta {
+        HotOrNotBetActivityDelta {
+            post_id,
+            bet_amount,
+            pot_size,
+            post_created_at: SystemTime::UNIX_EPOCH,
+            bet_placed_at: SystemTime::UNIX_EPOCH,
+        }
+    }
+
+    fn get_ranking(canister_data: &CanisterData) -> Vec<(Principal, u64)> {
+        canister_data
+            .posts_index_sorted_by_hot_or_not_feed_score_v2
+            .iter()
+            .map(|item| (item.publisher_canister_id, item.post_id))
+            .collect()
+    }
+
+    #[test]
+    fn test_receive_hot_or_not_bet_activity_from_publishing_canister_impl() {
+        let mut canister_data = CanisterData::default();
+        canister_data
+            .banned_posts
+            .insert((get_mock_user_bob_canister_id(), 1));
+
+        receive_hot_or_not_bet_activity_from_publishing_canister_impl(
+            &mut canister_data,
+            get_mock_user_alice_canister_id(),
+            vec![get_delta(0, 100, 100), get_delta(1, 50, 50)],
+            &SystemTime::UNIX_EPOCH,
+        );
+        receive_hot_or_not_bet_activity_from_publishing_canister_impl(
+            &mut canister_data,
+            get_mock_user_bob_canister_id(),
+            vec![get_delta(1, 1000, 1000)],
+            &SystemTime::UNIX_EPOCH,
+        );
+        assert_eq!(
+            get_ranking(&canister_data),
+            vec![
+                (get_mock_user_alice_canister_id(), 0),
+                (get_mock_user_alice_canister_id(), 1),
+            ]
+        );
+
+        receive_hot_or_not_bet_activity_from_publishing_canister_impl(
+            &mut canister_data,
+            get_mock_user_alice_canister_id(),
+            vec![get_delta(1, 100, 150)],
+            &SystemTime::UNIX_EPOCH,
+        );
+        assert_eq!(
+            get_ranking(&canister_data),
+            vec![
+                (get_mock_user_alice_canister_id(), 1),
+                (get_mock_user_alice_canister_id(), 0),
+            ]
+        );
+        assert_eq!(canister_data.hot_or_not_bet_activity.len(), 2);
+    }
+
+    #[test]
+    fn test_receive_hot_or_not_bet_activity_from_publishing_canister_impl_trims_the_index() {
+        let mut canister_data = CanisterData::default();
+
+        receive_hot_or_not_bet_activity_from_publishing_canister_impl(
+            &mut canister_data,
+            get_mock_user_alice_canister_id(),
+            (0..1501)
+                .map(|post_id| get_delta(post_id, post_id + 1, post_id + 1))
+                .collect(),
+            &(SystemTime::UNIX_EPOCH + Duration::from_secs(1)),
+        );
+
+        assert_eq!(
+            canister_data
+                .posts_index_sorted_by_hot_or_not_feed_score_v2
+                .iter()
+                .count(),
+            1000
+        );
+        assert_eq!(canister_data.hot_or_not_bet_activity.len(), 1000);
+        assert!(!canister_data
+            .hot_or_not_bet_activity
+            .contains_key(&(get_mock_user_alice_canister_id(), 0)));
+    }
+}
@@ -1,6 +1,12 @@
-use shared_utils::common::types::top_posts::post_score_index_item::PostScoreIndexItem;
+use shared_utils::{
+    canister_specific::post_cache::types::feed_response_cache::FeedMode,
+    common::types::top_posts::post_score_index_item::PostScoreIndexItem,
+};
 
-use crate::{data_model::CanisterData, CANISTER_DATA};
+use crate::{
+    api::feed::feed_response_cache::invalidate_feed_response_cache, data_model::CanisterData,
+    CANISTER_DATA,
+};
 
 #[ic_cdk::update]
 #[candid::candid_method(update)]
@@ -15,6 +21,8 @@ fn receive_top_hot_or_not_feed_posts_from_publishing_canister(
             &mut canister_data,
         );
     });
+
+    invalidate_feed_response_cache(FeedMode::HotOrNotFeed);
 }
 
 fn receive_top_hot_or_not_feed_posts_from_publishing_canister_impl(
@@ -25,6 +33,13 @@ fn receive_top_hot_or_not_feed_posts_from_publishing_canister_impl(
         &mut canister_data.posts_index_sorted_by_hot_or_not_feed_score;
 
     for post_score_index_item in top_posts_from_publishing_canister {
+        if canister_data.banned_posts.contains(&(
+            post_score_index_item.publisher_canister_id,
+            post_score_index_item.post_id,
+        )) {
+            continue;
+        }
+
         posts_index_sorted_by_hot_or_not_feed_score.replace(&post_score_index_item);
     }
 
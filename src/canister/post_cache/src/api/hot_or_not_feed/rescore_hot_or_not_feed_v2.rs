@@ -0,0 +1,94 @@
+use std::time::{Duration, SystemTime};
+
+use shared_utils::{
+    canister_specific::post_cache::types::feed_response_cache::FeedMode,
+    common::{
+        types::top_posts::post_score_index_item::PostScoreIndexItem,
+        utils::system_time::{IcTimeProvider, TimeProvider},
+    },
+};
+
+use crate::{
+    api::feed::feed_response_cache::invalidate_feed_response_cache, data_model::CanisterData,
+    CANISTER_DATA,
+};
+
+const HOT_OR_NOT_FEED_V2_RESCORE_INTERVAL: Duration = Duration::from_secs(60 * 10);
+
+/// Scores only change when bets come in, so posts that went quiet are rescored here to let
+/// their recent bet volume and age catch up with them
+pub fn enqueue_timer_for_rescoring_hot_or_not_feed_v2() {
+    ic_cdk_timers::set_timer_interval(HOT_OR_NOT_FEED_V2_RESCORE_INTERVAL, || {
+        CANISTER_DATA.with(|canister_data_ref_cell| {
+            rescore_hot_or_not_feed_v2_impl(
+                &mut canister_data_ref_cell.borrow_mut(),
+                &IcTimeProvider.get_current_time(),
+            );
+        });
+
+        invalidate_feed_response_cache(FeedMode::HotOrNotFeedV2);
+    });
+}
+
+fn rescore_hot_or_not_feed_v2_impl(canister_data: &mut CanisterData, current_time: &SystemTime) {
+    canister_data.posts_index_sorted_by_hot_or_not_feed_score_v2 = canister_data
+        .hot_or_not_bet_activity
+        .iter()
+        .map(
+            |((publisher_canister_id, post_id), hot_or_not_bet_activity)| PostScoreIndexItem {
+                post_id: *post_id,
+                score: hot_or_not_bet_activity.get_score(current_time),
+                publisher_canister_id: *publisher_canister_id,
+            },
+        )
+        .collect();
+}
+
+#[cfg(test)]
+mod test {
+    use shared_utils::common::types::top_posts::hot_or_not_bet_activity::{
+        HotOrNotBetActivity, RECENT_BET_VOLUME_HALF_LIFE,
+    };
+    use test_utils::setup::test_constants::get_mock_user_alice_canister_id;
+
+    use super::*;
+
+    #[test]
+    fn test_rescore_hot_or_not_feed_v2_impl() {
+        let mut canister_data = CanisterData::default();
+        let current_time = SystemTime::UNIX_EPOCH + RECENT_BET_VOLUME_HALF_LIFE * 2;
+
+        // * a burst of bets that's over, and a smaller one that's still going
+        [(0, 300, SystemTime::UNIX_EPOCH), (1, 200, current_time)]
+            .into_iter()
+            .for_each(|(post_id, recent_bet_volume, last_updated_at)| {
+                canister_data.hot_or_not_bet_activity.insert(
+                    (get_mock_user_alice_canister_id(), post_id),
+                    HotOrNotBetActivity {
+                        recent_bet_volume,
+                        total_pot: recent_bet_volume,
+                        post_created_at: SystemTime::UNIX_EPOCH,
+                        last_updated_at,
+                    },
+                );
+                canister_data
+                    .posts_index_sorted_by_hot_or_not_feed_score_v2
+                    .replace(&PostScoreIndexItem {
+                        post_id,
+                        score: recent_bet_volume,
+                        publisher_canister_id: get_mock_user_alice_canister_id(),
+                    });
+            });
+
+        rescore_hot_or_not_feed_v2_impl(&mut canister_data, &current_time);
+
+        assert_eq!(
+            canister_data
+                .posts_index_sorted_by_hot_or_not_feed_score_v2
+                .iter()
+                .map(|item| item.post_id)
+                .collect::<Vec<_>>(),
+            vec![1, 0]
+        );
+    }
+}
@@ -1,5 +1,11 @@
 pub mod canister_lifecycle;
+pub mod category_feed;
+pub mod error_code;
 pub mod feed;
+pub mod hashtag_aliases;
 pub mod home_feed;
 pub mod hot_or_not_feed;
+pub mod post_details;
+pub mod slot_heat;
+pub mod trending_hashtags;
 pub mod well_known_principal;
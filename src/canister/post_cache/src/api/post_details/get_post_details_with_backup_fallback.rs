@@ -0,0 +1,180 @@
+use std::time::Duration;
+
+use candid::Principal;
+use ic_cdk::api::call;
+use shared_utils::{
+    canister_specific::{
+        individual_user_template::types::post::PostDetailsForFrontend,
+        post_cache::types::hydrated_post_details::HydratedPostDetails,
+    },
+    common::{
+        types::{global_post_ref::GlobalPostRef, known_principal::KnownPrincipalType},
+        utils::bounded_fan_out::{self, FanOutLimits},
+    },
+    types::canister_specific::post_cache::error_types::PostDetailsHydrationError,
+};
+
+use crate::CANISTER_DATA;
+
+// * Each post hydrated makes at most one call at a time, the backup only being asked once the
+// * publisher has failed
+pub const POST_DETAILS_HYDRATION_LIMITS: FanOutLimits = FanOutLimits {
+    max_concurrent_calls: 20,
+    per_call_timeout: Duration::from_secs(10),
+};
+
+/// Fetches the details of a feed item from its publishing canister. If that canister is being
+/// migrated or doesn't respond, the last backed up details are served from the data backup
+/// canister instead and flagged as stale.
+#[ic_cdk::update]
+#[candid::candid_method(update)]
+async fn get_post_details_with_backup_fallback(
+    post: GlobalPostRef,
+) -> Result<HydratedPostDetails, PostDetailsHydrationError> {
+    hydrate_post_details(post).await
+}
+
+/// A publisher that doesn't answer within the per call timeout is treated as down, so that
+/// one slow canister can't hold up a whole feed page
+pub async fn hydrate_post_details(
+    post: GlobalPostRef,
+) -> Result<HydratedPostDetails, PostDetailsHydrationError> {
+    let live_post_details: Option<PostDetailsForFrontend> = bounded_fan_out::with_timeout(
+        async move {
+            call::call::<_, (PostDetailsForFrontend,)>(
+                post.canister_id,
+                "get_individual_post_details_by_id",
+                (post.post_id,),
+            )
+            .await
+        },
+        POST_DETAILS_HYDRATION_LIMITS.per_call_timeout,
+    )
+    .await
+    .ok()
+    .and_then(Result::ok)
+    .map(|(post_details,)| post_details);
+
+    let backed_up_post_details = if live_post_details.is_none() {
+        get_backed_up_post_details(post).await
+    } else {
+        None
+    };
+
+    get_post_details_with_backup_fallback_impl(
+        post.canister_id,
+        live_post_details,
+        backed_up_post_details,
+    )
+}
+
+async fn get_backed_up_post_details(post: GlobalPostRef) -> Option<PostDetailsForFrontend> {
+    let data_backup_canister_id = CANISTER_DATA.with(|canister_data_ref_cell| {
+        canister_data_ref_cell
+            .borrow()
+            .known_principal_ids
+            .get(&KnownPrincipalType::CanisterIdDataBackup)
+            .cloned()
+    })?;
+
+    let (backed_up_post_details,): (Option<PostDetailsForFrontend>,) =
+        bounded_fan_out::with_timeout(
+            async move {
+                call::call::<_, (Option<PostDetailsForFrontend>,)>(
+                    data_backup_canister_id,
+                    "get_backed_up_post_details_for_frontend",
+                    (post,),
+                )
+                .await
+            },
+            POST_DETAILS_HYDRATION_LIMITS.per_call_timeout,
+        )
+        .await
+        .ok()?
+        .ok()?;
+
+    backed_up_post_details
+}
+
+fn get_post_details_with_backup_fallback_impl(
+    publisher_canister_id: Principal,
+    live_post_details: Option<PostDetailsForFrontend>,
+    backed_up_post_details: Option<PostDetailsForFrontend>,
+) -> Result<HydratedPostDetails, PostDetailsHydrationError> {
+    match (live_post_details, backed_up_post_details) {
+        (Some(post_details), _) => Ok(HydratedPostDetails {
+            publisher_canister_id,
+            post_details,
+            is_stale: false,
+        }),
+        (None, Some(post_details)) => Ok(HydratedPostDetails {
+            publisher_canister_id,
+            post_details,
+            is_stale: true,
+        }),
+        (None, None) => Err(PostDetailsHydrationError::PostNotFoundInPublisherOrBackup),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::SystemTime;
+
+    use shared_utils::{
+        canister_specific::individual_user_template::types::post::PostStatus,
+        common::types::app_primitive_type::PostId,
+    };
+
+    use super::*;
+
+    fn get_mock_post_details(post_id: PostId, video_uid: &str) -> PostDetailsForFrontend {
+        PostDetailsForFrontend {
+            id: post_id,
+            created_by_display_name: None,
+            created_by_unique_user_name: None,
+            created_by_user_principal_id: Principal::anonymous(),
+            created_by_profile_photo_url: None,
+            created_at: SystemTime::now(),
+            description: "description".to_string(),
+            hashtags: vec![],
+            video_uid: video_uid.to_string(),
+            status: PostStatus::ReadyToView,
+            total_view_count: 0,
+            like_count: 0,
+            liked_by_me: false,
+            home_feed_ranking_score: 0,
+            hot_or_not_feed_ranking_score: None,
+            hot_or_not_betting_status: None,
+            title: None,
+            category: None,
+            active_boost: None,
+        }
+    }
+
+    #[test]
+    fn test_get_post_details_with_backup_fallback_impl() {
+        let hydrated_post_details = get_post_details_with_backup_fallback_impl(
+            Principal::anonymous(),
+            Some(get_mock_post_details(1, "live")),
+            Some(get_mock_post_details(1, "backup")),
+        )
+        .unwrap();
+        assert!(!hydrated_post_details.is_stale);
+        assert_eq!(hydrated_post_details.post_details.video_uid, "live");
+
+        let hydrated_post_details = get_post_details_with_backup_fallback_impl(
+            Principal::anonymous(),
+            None,
+            Some(get_mock_post_details(1, "backup")),
+        )
+        .unwrap();
+        assert!(hydrated_post_details.is_stale);
+        assert_eq!(hydrated_post_details.post_details.video_uid, "backup");
+
+        assert_eq!(
+            get_post_details_with_backup_fallback_impl(Principal::anonymous(), None, None)
+                .unwrap_err(),
+            PostDetailsHydrationError::PostNotFoundInPublisherOrBackup
+        );
+    }
+}
@@ -0,0 +1,29 @@
+use shared_utils::{
+    canister_specific::post_cache::types::hydrated_post_details::HydratedPostDetails,
+    common::{types::global_post_ref::GlobalPostRef, utils::bounded_fan_out},
+    pagination::MAX_ITEMS_PER_PAGE,
+    types::canister_specific::post_cache::error_types::PostDetailsHydrationError,
+};
+
+use super::get_post_details_with_backup_fallback::{
+    hydrate_post_details, POST_DETAILS_HYDRATION_LIMITS,
+};
+
+/// Hydrates a whole feed page in one go, in the same order as `posts`. Each post falls back
+/// to its backed up details the same way as `get_post_details_with_backup_fallback`. Only the
+/// first `MAX_ITEMS_PER_PAGE` posts passed in are looked at, and only a bounded number of
+/// them are fetched at once so the page stays under the outgoing call limit.
+#[ic_cdk::update]
+#[candid::candid_method(update)]
+async fn get_post_details_with_backup_fallback_for_posts(
+    posts: Vec<GlobalPostRef>,
+) -> Vec<Result<HydratedPostDetails, PostDetailsHydrationError>> {
+    bounded_fan_out::run_with_concurrency_limit(
+        posts
+            .into_iter()
+            .take(MAX_ITEMS_PER_PAGE as usize)
+            .map(hydrate_post_details),
+        POST_DETAILS_HYDRATION_LIMITS.max_concurrent_calls,
+    )
+    .await
+}
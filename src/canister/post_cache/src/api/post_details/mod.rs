@@ -0,0 +1,2 @@
+pub mod get_post_details_with_backup_fallback;
+pub mod get_post_details_with_backup_fallback_for_posts;
@@ -0,0 +1,94 @@
+use std::time::SystemTime;
+
+use shared_utils::{
+    common::{
+        types::{
+            global_post_ref::GlobalPostRef, top_posts::slot_heat_indicator::SlotHeatIndicator,
+        },
+        utils::system_time::{IcTimeProvider, TimeProvider},
+    },
+    pagination::MAX_ITEMS_PER_PAGE,
+};
+
+use crate::{data_model::CanisterData, CANISTER_DATA};
+
+/// Takes references to posts, typically the items of a feed page, and
+/// returns the heat of those whose current slot has an indicator. Posts without one have
+/// not seen any unusual betting activity and can be rendered without a badge. Only the first
+/// `MAX_ITEMS_PER_PAGE` posts passed in are looked at.
+#[ic_cdk::query]
+#[candid::candid_method(query)]
+fn get_slot_heat_indicators_for_posts(posts: Vec<GlobalPostRef>) -> Vec<SlotHeatIndicator> {
+    let current_time = IcTimeProvider.get_current_time();
+
+    CANISTER_DATA.with(|canister_data_ref_cell| {
+        get_slot_heat_indicators_for_posts_impl(
+            &canister_data_ref_cell.borrow(),
+            &posts,
+            &current_time,
+        )
+    })
+}
+
+fn get_slot_heat_indicators_for_posts_impl(
+    canister_data: &CanisterData,
+    posts: &[GlobalPostRef],
+    current_time: &SystemTime,
+) -> Vec<SlotHeatIndicator> {
+    posts
+        .iter()
+        .take(MAX_ITEMS_PER_PAGE as usize)
+        .filter_map(|post| {
+            canister_data
+                .slot_heat_indicators
+                .get(&(post.canister_id, post.post_id))
+        })
+        .filter(|slot_heat_indicator| slot_heat_indicator.valid_until > *current_time)
+        .cloned()
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use candid::Principal;
+
+    use super::*;
+
+    #[test]
+    fn test_get_slot_heat_indicators_for_posts_impl() {
+        let mut canister_data = CanisterData::default();
+        let current_time = SystemTime::now();
+
+        [(1, 60), (2, 0)]
+            .iter()
+            .for_each(|(post_id, seconds_left_in_slot)| {
+                canister_data.slot_heat_indicators.insert(
+                    (Principal::anonymous(), *post_id),
+                    SlotHeatIndicator {
+                        publisher_canister_id: Principal::anonymous(),
+                        post_id: *post_id,
+                        slot_id: 1,
+                        bets_in_slot: 10,
+                        heat_score: 500,
+                        is_on_fire: true,
+                        valid_until: current_time + Duration::from_secs(*seconds_left_in_slot),
+                    },
+                );
+            });
+
+        let slot_heat_indicators = get_slot_heat_indicators_for_posts_impl(
+            &canister_data,
+            &[
+                GlobalPostRef::new(Principal::anonymous(), 1),
+                GlobalPostRef::new(Principal::anonymous(), 2),
+                GlobalPostRef::new(Principal::anonymous(), 3),
+            ],
+            &current_time,
+        );
+
+        assert_eq!(slot_heat_indicators.len(), 1);
+        assert_eq!(slot_heat_indicators[0].post_id, 1);
+    }
+}
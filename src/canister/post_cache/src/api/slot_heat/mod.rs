@@ -0,0 +1,2 @@
+pub mod get_slot_heat_indicators_for_posts;
+pub mod receive_slot_heat_indicators_from_publishing_canister;
@@ -0,0 +1,105 @@
+use std::time::SystemTime;
+
+use shared_utils::common::{
+    types::top_posts::slot_heat_indicator::SlotHeatIndicator,
+    utils::system_time::{IcTimeProvider, TimeProvider},
+};
+
+use crate::{data_model::CanisterData, CANISTER_DATA};
+
+#[ic_cdk::update]
+#[candid::candid_method(update)]
+fn receive_slot_heat_indicators_from_publishing_canister(
+    slot_heat_indicators_from_publishing_canister: Vec<SlotHeatIndicator>,
+) {
+    let current_time = IcTimeProvider.get_current_time();
+
+    CANISTER_DATA.with(|canister_data_ref_cell| {
+        receive_slot_heat_indicators_from_publishing_canister_impl(
+            slot_heat_indicators_from_publishing_canister,
+            &mut canister_data_ref_cell.borrow_mut(),
+            &current_time,
+        );
+    });
+}
+
+/// Replaces the heat of every post received and drops indicators whose slot has ended
+fn receive_slot_heat_indicators_from_publishing_canister_impl(
+    slot_heat_indicators_from_publishing_canister: Vec<SlotHeatIndicator>,
+    canister_data: &mut CanisterData,
+    current_time: &SystemTime,
+) {
+    let slot_heat_indicators = &mut canister_data.slot_heat_indicators;
+
+    for slot_heat_indicator in slot_heat_indicators_from_publishing_canister {
+        slot_heat_indicators.insert(
+            (
+                slot_heat_indicator.publisher_canister_id,
+                slot_heat_indicator.post_id,
+            ),
+            slot_heat_indicator,
+        );
+    }
+
+    slot_heat_indicators
+        .retain(|_, slot_heat_indicator| slot_heat_indicator.valid_until > *current_time);
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use candid::Principal;
+
+    use super::*;
+
+    fn get_slot_heat_indicator(
+        post_id: u64,
+        heat_score: u64,
+        valid_until: SystemTime,
+    ) -> SlotHeatIndicator {
+        SlotHeatIndicator {
+            publisher_canister_id: Principal::anonymous(),
+            post_id,
+            slot_id: 1,
+            bets_in_slot: 5,
+            heat_score,
+            is_on_fire: heat_score >= 200,
+            valid_until,
+        }
+    }
+
+    #[test]
+    fn test_receive_slot_heat_indicators_from_publishing_canister_impl() {
+        let mut canister_data = CanisterData::default();
+        let current_time = SystemTime::now();
+        let valid_until = current_time + Duration::from_secs(60);
+
+        receive_slot_heat_indicators_from_publishing_canister_impl(
+            vec![
+                get_slot_heat_indicator(1, 100, valid_until),
+                get_slot_heat_indicator(2, 300, valid_until),
+            ],
+            &mut canister_data,
+            &current_time,
+        );
+
+        assert_eq!(canister_data.slot_heat_indicators.len(), 2);
+
+        let next_slot_valid_until = valid_until + Duration::from_secs(60 * 60);
+
+        receive_slot_heat_indicators_from_publishing_canister_impl(
+            vec![get_slot_heat_indicator(1, 400, next_slot_valid_until)],
+            &mut canister_data,
+            &valid_until,
+        );
+
+        assert_eq!(
+            canister_data
+                .slot_heat_indicators
+                .into_values()
+                .collect::<Vec<_>>(),
+            vec![get_slot_heat_indicator(1, 400, next_slot_valid_until)]
+        );
+    }
+}
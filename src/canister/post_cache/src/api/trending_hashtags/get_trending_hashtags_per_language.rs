@@ -0,0 +1,144 @@
+use std::collections::BTreeMap;
+
+use shared_utils::{
+    canister_specific::post_cache::types::trending_hashtag::{
+        TrendingHashtag, TrendingHashtagsForLanguage,
+    },
+    common::utils::hashtag::{self, HashtagLanguageBucket},
+};
+
+use crate::{data_model::CanisterData, CANISTER_DATA};
+
+const MAXIMUM_NUMBER_OF_TRENDING_HASHTAGS_PER_LANGUAGE: u64 = 20;
+
+/// The most used hashtags among posts in the home feed, ranked separately for each script
+/// they're written in. Languages without any hashtags are left out.
+#[ic_cdk::query]
+#[candid::candid_method(query)]
+fn get_trending_hashtags_per_language(
+    number_of_hashtags_per_language: u64,
+) -> Vec<TrendingHashtagsForLanguage> {
+    CANISTER_DATA.with(|canister_data_ref_cell| {
+        get_trending_hashtags_per_language_impl(
+            &canister_data_ref_cell.borrow(),
+            number_of_hashtags_per_language.min(MAXIMUM_NUMBER_OF_TRENDING_HASHTAGS_PER_LANGUAGE),
+        )
+    })
+}
+
+pub fn get_trending_hashtags_per_language_impl(
+    canister_data: &CanisterData,
+    number_of_hashtags_per_language: u64,
+) -> Vec<TrendingHashtagsForLanguage> {
+    let mut post_counts: BTreeMap<HashtagLanguageBucket, BTreeMap<&String, u64>> = BTreeMap::new();
+
+    canister_data
+        .posts_index_sorted_by_home_feed_score
+        .iter()
+        .filter_map(|item| {
+            canister_data
+                .post_hashtags
+                .get(&(item.publisher_canister_id, item.post_id))
+        })
+        .flatten()
+        .for_each(|hashtag| {
+            *post_counts
+                .entry(hashtag::get_language_bucket(hashtag))
+                .or_default()
+                .entry(hashtag)
+                .or_default() += 1;
+        });
+
+    post_counts
+        .into_iter()
+        .map(|(language_bucket, post_counts)| {
+            let mut hashtags: Vec<TrendingHashtag> = post_counts
+                .into_iter()
+                .map(|(hashtag, post_count)| TrendingHashtag {
+                    hashtag: hashtag.clone(),
+                    post_count,
+                })
+                .collect();
+            hashtags.sort_by(|a, b| {
+                b.post_count
+                    .cmp(&a.post_count)
+                    .then_with(|| a.hashtag.cmp(&b.hashtag))
+            });
+            hashtags.truncate(number_of_hashtags_per_language as usize);
+
+            TrendingHashtagsForLanguage {
+                language_bucket,
+                hashtags,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use shared_utils::common::types::top_posts::post_score_index_item::PostScoreIndexItem;
+    use test_utils::setup::test_constants::get_mock_user_alice_canister_id;
+
+    use super::*;
+
+    #[test]
+    fn test_get_trending_hashtags_per_language_impl() {
+        let mut canister_data = CanisterData::default();
+        let hashtags_per_post: [&[&str]; 6] = [
+            &["doggo", "pupper"],
+            &["doggo"],
+            &["doggo", "music"],
+            &["music"],
+            &["музыка"],
+            // * not in the home feed, so it doesn't count
+            &["pupper"],
+        ];
+
+        hashtags_per_post
+            .iter()
+            .enumerate()
+            .for_each(|(post_id, hashtags)| {
+                let post_id = post_id as u64;
+                if post_id < 5 {
+                    canister_data.posts_index_sorted_by_home_feed_score.replace(
+                        &PostScoreIndexItem {
+                            score: post_id * 100,
+                            post_id,
+                            publisher_canister_id: get_mock_user_alice_canister_id(),
+                        },
+                    );
+                }
+                canister_data.post_hashtags.insert(
+                    (get_mock_user_alice_canister_id(), post_id),
+                    hashtags.iter().map(|hashtag| hashtag.to_string()).collect(),
+                );
+            });
+
+        assert_eq!(
+            get_trending_hashtags_per_language_impl(&canister_data, 2),
+            vec![
+                TrendingHashtagsForLanguage {
+                    language_bucket: HashtagLanguageBucket::Latin,
+                    hashtags: vec![
+                        TrendingHashtag {
+                            hashtag: "doggo".to_string(),
+                            post_count: 3,
+                        },
+                        TrendingHashtag {
+                            hashtag: "music".to_string(),
+                            post_count: 2,
+                        },
+                    ],
+                },
+                // * still trends, however few posts it's on next to the Latin ones
+                TrendingHashtagsForLanguage {
+                    language_bucket: HashtagLanguageBucket::Cyrillic,
+                    hashtags: vec![TrendingHashtag {
+                        hashtag: "музыка".to_string(),
+                        post_count: 1,
+                    }],
+                },
+            ]
+        );
+    }
+}
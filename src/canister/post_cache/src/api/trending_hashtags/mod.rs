@@ -0,0 +1,2 @@
+pub mod get_trending_hashtags_per_language;
+pub mod receive_post_hashtags_from_publishing_canister;
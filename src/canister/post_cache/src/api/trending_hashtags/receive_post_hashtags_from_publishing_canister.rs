@@ -0,0 +1,112 @@
+use candid::Principal;
+use shared_utils::common::types::app_primitive_type::PostId;
+
+use crate::{data_model::CanisterData, CANISTER_DATA};
+
+// * Hashtags of posts that never made it into the home feed are only dropped once there are
+// * this many posts with hashtags
+const MAXIMUM_NUMBER_OF_POSTS_WITH_HASHTAGS: usize = 3000;
+
+/// Publishing canisters can only tag their own posts, as entries are keyed by the calling
+/// canister
+#[ic_cdk::update]
+#[candid::candid_method(update)]
+fn receive_post_hashtags_from_publishing_canister(post_id: PostId, hashtags: Vec<String>) {
+    let publisher_canister_id = ic_cdk::caller();
+
+    CANISTER_DATA.with(|canister_data_ref_cell| {
+        receive_post_hashtags_from_publishing_canister_impl(
+            &mut canister_data_ref_cell.borrow_mut(),
+            publisher_canister_id,
+            post_id,
+            hashtags,
+        );
+    });
+}
+
+fn receive_post_hashtags_from_publishing_canister_impl(
+    canister_data: &mut CanisterData,
+    publisher_canister_id: Principal,
+    post_id: PostId,
+    hashtags: Vec<String>,
+) {
+    let hashtags = canister_data.hashtag_aliases.apply(&hashtags);
+    if hashtags.is_empty() {
+        return;
+    }
+
+    if canister_data.post_hashtags.len() >= MAXIMUM_NUMBER_OF_POSTS_WITH_HASHTAGS {
+        let home_feed_entries = &canister_data
+            .posts_index_sorted_by_home_feed_score
+            .item_presence_index;
+        canister_data
+            .post_hashtags
+            .retain(|entry, _| home_feed_entries.contains_key(entry));
+    }
+
+    canister_data
+        .post_hashtags
+        .insert((publisher_canister_id, post_id), hashtags);
+}
+
+#[cfg(test)]
+mod test {
+    use shared_utils::common::types::top_posts::post_score_index_item::PostScoreIndexItem;
+    use test_utils::setup::test_constants::{
+        get_mock_user_alice_canister_id, get_mock_user_bob_canister_id,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_receive_post_hashtags_from_publishing_canister_impl() {
+        let mut canister_data = CanisterData::default();
+        canister_data
+            .posts_index_sorted_by_home_feed_score
+            .replace(&PostScoreIndexItem {
+                score: 100,
+                post_id: 0,
+                publisher_canister_id: get_mock_user_alice_canister_id(),
+            });
+
+        (0..MAXIMUM_NUMBER_OF_POSTS_WITH_HASHTAGS as u64).for_each(|post_id| {
+            receive_post_hashtags_from_publishing_canister_impl(
+                &mut canister_data,
+                get_mock_user_alice_canister_id(),
+                post_id,
+                vec!["doggo".to_string()],
+            );
+        });
+        assert_eq!(
+            canister_data.post_hashtags.len(),
+            MAXIMUM_NUMBER_OF_POSTS_WITH_HASHTAGS
+        );
+
+        // * posts without hashtags aren't stored
+        receive_post_hashtags_from_publishing_canister_impl(
+            &mut canister_data,
+            get_mock_user_bob_canister_id(),
+            1,
+            vec![],
+        );
+        assert_eq!(
+            canister_data.post_hashtags.len(),
+            MAXIMUM_NUMBER_OF_POSTS_WITH_HASHTAGS
+        );
+
+        // * only the hashtags of posts in the home feed survive the pruning
+        receive_post_hashtags_from_publishing_canister_impl(
+            &mut canister_data,
+            get_mock_user_bob_canister_id(),
+            0,
+            vec!["pupper".to_string()],
+        );
+        assert_eq!(canister_data.post_hashtags.len(), 2);
+        assert_eq!(
+            canister_data
+                .post_hashtags
+                .get(&(get_mock_user_bob_canister_id(), 0)),
+            Some(&vec!["pupper".to_string()])
+        );
+    }
+}
@@ -0,0 +1,173 @@
+use std::{
+    collections::HashMap,
+    time::{Duration, SystemTime},
+};
+
+use shared_utils::canister_specific::post_cache::types::{
+    feed_item::FeedItem,
+    feed_response_cache::{FeedMode, FeedResponseCacheMetrics},
+};
+
+pub const FEED_RESPONSE_CACHE_TTL: Duration = Duration::from_secs(15);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct FeedResponseCacheKey {
+    pub feed_mode: FeedMode,
+    pub from_inclusive_index: u64,
+    pub to_exclusive_index: u64,
+}
+
+struct FeedResponseCacheEntry {
+    response: Vec<FeedItem>,
+    cached_at: SystemTime,
+}
+
+/// Short lived cache of feed pages. Lives only on the heap and is deliberately
+/// not carried across upgrades.
+///
+/// State changes made while serving a non-replicated query are discarded, so
+/// entries are filled by the warming timer and by replicated executions, and
+/// the hit and miss counters only reflect the latter.
+#[derive(Default)]
+pub struct FeedResponseCache {
+    entries: HashMap<FeedResponseCacheKey, FeedResponseCacheEntry>,
+    hits: u64,
+    misses: u64,
+}
+
+impl FeedResponseCache {
+    pub fn get(
+        &mut self,
+        key: &FeedResponseCacheKey,
+        current_time: &SystemTime,
+    ) -> Option<Vec<FeedItem>> {
+        let fresh_response = self
+            .entries
+            .get(key)
+            .filter(|entry| {
+                current_time
+                    .duration_since(entry.cached_at)
+                    .map(|age| age < FEED_RESPONSE_CACHE_TTL)
+                    .unwrap_or(true)
+            })
+            .map(|entry| entry.response.clone());
+
+        match fresh_response {
+            Some(_) => self.hits += 1,
+            None => self.misses += 1,
+        }
+
+        fresh_response
+    }
+
+    pub fn insert(
+        &mut self,
+        key: FeedResponseCacheKey,
+        response: Vec<FeedItem>,
+        current_time: &SystemTime,
+    ) {
+        self.entries.insert(
+            key,
+            FeedResponseCacheEntry {
+                response,
+                cached_at: *current_time,
+            },
+        );
+    }
+
+    /// Called whenever a feed's index changes so that no page outlives the data it was built from
+    pub fn invalidate_feed(&mut self, feed_mode: FeedMode) {
+        self.entries.retain(|key, _| key.feed_mode != feed_mode);
+    }
+
+    pub fn remove_expired_entries(&mut self, current_time: &SystemTime) {
+        self.entries.retain(|_, entry| {
+            current_time
+                .duration_since(entry.cached_at)
+                .map(|age| age < FEED_RESPONSE_CACHE_TTL)
+                .unwrap_or(true)
+        });
+    }
+
+    pub fn get_metrics(&self) -> FeedResponseCacheMetrics {
+        FeedResponseCacheMetrics {
+            hits: self.hits,
+            misses: self.misses,
+            number_of_entries: self.entries.len() as u64,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use candid::Principal;
+    use shared_utils::canister_specific::post_cache::types::feed_item::FeedItemReason;
+
+    use super::*;
+
+    fn get_key(feed_mode: FeedMode) -> FeedResponseCacheKey {
+        FeedResponseCacheKey {
+            feed_mode,
+            from_inclusive_index: 0,
+            to_exclusive_index: 10,
+        }
+    }
+
+    #[test]
+    fn test_feed_response_cache() {
+        let mut feed_response_cache = FeedResponseCache::default();
+        let cached_at = SystemTime::now();
+        let response = vec![FeedItem {
+            post_id: 1,
+            score: 1,
+            publisher_canister_id: Principal::anonymous(),
+            reason: FeedItemReason::Trending,
+        }];
+
+        assert_eq!(
+            feed_response_cache.get(&get_key(FeedMode::HomeFeed), &cached_at),
+            None
+        );
+
+        feed_response_cache.insert(get_key(FeedMode::HomeFeed), response.clone(), &cached_at);
+        feed_response_cache.insert(
+            get_key(FeedMode::HotOrNotFeed),
+            response.clone(),
+            &cached_at,
+        );
+
+        assert_eq!(
+            feed_response_cache.get(
+                &get_key(FeedMode::HomeFeed),
+                &(cached_at + Duration::from_secs(14))
+            ),
+            Some(response.clone())
+        );
+        assert_eq!(
+            feed_response_cache.get(
+                &get_key(FeedMode::HomeFeed),
+                &(cached_at + FEED_RESPONSE_CACHE_TTL)
+            ),
+            None
+        );
+
+        feed_response_cache.invalidate_feed(FeedMode::HotOrNotFeed);
+
+        assert_eq!(
+            feed_response_cache.get(&get_key(FeedMode::HotOrNotFeed), &cached_at),
+            None
+        );
+        assert_eq!(
+            feed_response_cache.get_metrics(),
+            FeedResponseCacheMetrics {
+                hits: 1,
+                misses: 3,
+                number_of_entries: 1,
+            }
+        );
+
+        feed_response_cache.remove_expired_entries(&(cached_at + FEED_RESPONSE_CACHE_TTL));
+
+        assert_eq!(feed_response_cache.get_metrics().number_of_entries, 0);
+    }
+}
@@ -1,12 +1,60 @@
-use candid::{CandidType, Deserialize};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    time::SystemTime,
+};
+
+use candid::{CandidType, Deserialize, Principal};
 use serde::Serialize;
-use shared_utils::common::types::{
-    known_principal::KnownPrincipalMap, top_posts::post_score_index::PostScoreIndex,
+use shared_utils::{
+    canister_specific::individual_user_template::types::post::PostCategory,
+    common::{
+        types::{
+            app_primitive_type::PostId,
+            known_principal::KnownPrincipalMap,
+            top_posts::{
+                hot_or_not_bet_activity::HotOrNotBetActivity, post_score_index::PostScoreIndex,
+                slot_heat_indicator::SlotHeatIndicator,
+            },
+        },
+        utils::hashtag::HashtagAliases,
+    },
 };
 
+use self::score_decay::ScoreDecayPass;
+
+pub mod feed_response_cache;
+pub mod score_decay;
+
 #[derive(Default, CandidType, Deserialize, Serialize)]
 pub struct CanisterData {
     pub known_principal_ids: KnownPrincipalMap,
     pub posts_index_sorted_by_home_feed_score: PostScoreIndex,
     pub posts_index_sorted_by_hot_or_not_feed_score: PostScoreIndex,
+    // * Key is (publisher canister id, post id)
+    #[serde(default)]
+    pub slot_heat_indicators: BTreeMap<(Principal, PostId), SlotHeatIndicator>,
+    #[serde(default)]
+    pub score_decay_pass: ScoreDecayPass,
+    // * Key is (publisher canister id, post id)
+    #[serde(default)]
+    pub post_categories: BTreeMap<(Principal, PostId), PostCategory>,
+    // * Key is (publisher canister id, post id). Hashtags come normalized from the publisher
+    #[serde(default)]
+    pub post_hashtags: BTreeMap<(Principal, PostId), Vec<String>>,
+    // * Key is (publisher canister id, post id), value is when the post's boost runs out
+    #[serde(default)]
+    pub post_boosted_until: BTreeMap<(Principal, PostId), SystemTime>,
+    // * Applied to hashtags as publishers send them in
+    #[serde(default)]
+    pub hashtag_aliases: HashtagAliases,
+    // * (publisher canister id, post id) of posts moderators banned. Scores sent for them are
+    // * ignored
+    #[serde(default)]
+    pub banned_posts: BTreeSet<(Principal, PostId)>,
+    // * Ranked by live betting activity, rescored from `hot_or_not_bet_activity`
+    #[serde(default)]
+    pub posts_index_sorted_by_hot_or_not_feed_score_v2: PostScoreIndex,
+    // * Key is (publisher canister id, post id). Only kept for posts in the v2 index
+    #[serde(default)]
+    pub hot_or_not_bet_activity: BTreeMap<(Principal, PostId), HotOrNotBetActivity>,
 }
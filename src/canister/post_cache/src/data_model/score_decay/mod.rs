@@ -0,0 +1,145 @@
+use std::time::Duration;
+
+use candid::{CandidType, Deserialize, Principal};
+use serde::Serialize;
+use shared_utils::common::types::{
+    app_primitive_type::PostId, top_posts::post_score_index::PostScoreIndex,
+};
+
+pub const SCORE_DECAY_INTERVAL: Duration = Duration::from_secs(60 * 60);
+pub const SCORE_DECAY_PERCENTAGE_PER_PASS: u64 = 10;
+// * Entries decayed per timer callback, so that a pass never comes close to the instruction limit
+pub const SCORE_DECAY_CHUNK_SIZE: usize = 200;
+
+/// Entries still waiting to be aged in the ongoing decay pass. Persisted so that a pass
+/// interrupted by an upgrade picks up where it left off.
+#[derive(Default, CandidType, Deserialize, Serialize, Debug, PartialEq, Eq)]
+pub struct ScoreDecayPass {
+    pub home_feed_entries_to_decay: Vec<(Principal, PostId)>,
+    pub hot_or_not_feed_entries_to_decay: Vec<(Principal, PostId)>,
+}
+
+impl ScoreDecayPass {
+    pub fn is_in_progress(&self) -> bool {
+        !self.home_feed_entries_to_decay.is_empty()
+            || !self.hot_or_not_feed_entries_to_decay.is_empty()
+    }
+
+    /// Lines up every entry currently in the feeds to be decayed
+    pub fn start(
+        &mut self,
+        posts_index_sorted_by_home_feed_score: &PostScoreIndex,
+        posts_index_sorted_by_hot_or_not_feed_score: &PostScoreIndex,
+    ) {
+        self.home_feed_entries_to_decay = posts_index_sorted_by_home_feed_score
+            .item_presence_index
+            .keys()
+            .cloned()
+            .collect();
+        self.hot_or_not_feed_entries_to_decay = posts_index_sorted_by_hot_or_not_feed_score
+            .item_presence_index
+            .keys()
+            .cloned()
+            .collect();
+    }
+
+    /// Decays up to `chunk_size` entries, home feed first. Entries that are no longer in
+    /// the feed are skipped, and fully decayed ones are evicted.
+    pub fn decay_next_chunk(
+        &mut self,
+        posts_index_sorted_by_home_feed_score: &mut PostScoreIndex,
+        posts_index_sorted_by_hot_or_not_feed_score: &mut PostScoreIndex,
+        chunk_size: usize,
+    ) {
+        let mut remaining_in_chunk = chunk_size;
+
+        for (entries_to_decay, post_score_index) in [
+            (
+                &mut self.home_feed_entries_to_decay,
+                posts_index_sorted_by_home_feed_score,
+            ),
+            (
+                &mut self.hot_or_not_feed_entries_to_decay,
+                posts_index_sorted_by_hot_or_not_feed_score,
+            ),
+        ] {
+            let number_to_decay = remaining_in_chunk.min(entries_to_decay.len());
+            entries_to_decay
+                .drain(entries_to_decay.len() - number_to_decay..)
+                .for_each(|(publisher_canister_id, post_id)| {
+                    post_score_index.decay_score(
+                        publisher_canister_id,
+                        post_id,
+                        SCORE_DECAY_PERCENTAGE_PER_PASS,
+                    );
+                });
+            remaining_in_chunk -= number_to_decay;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use shared_utils::common::types::top_posts::post_score_index_item::PostScoreIndexItem;
+    use test_utils::setup::test_constants::{
+        get_mock_user_alice_canister_id, get_mock_user_bob_canister_id,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_score_decay_pass() {
+        let mut posts_index_sorted_by_home_feed_score = PostScoreIndex::default();
+        let mut posts_index_sorted_by_hot_or_not_feed_score = PostScoreIndex::default();
+        (0..3).for_each(|post_id| {
+            posts_index_sorted_by_home_feed_score.replace(&PostScoreIndexItem {
+                score: 1000,
+                post_id,
+                publisher_canister_id: get_mock_user_alice_canister_id(),
+            });
+        });
+        posts_index_sorted_by_hot_or_not_feed_score.replace(&PostScoreIndexItem {
+            score: 1,
+            post_id: 0,
+            publisher_canister_id: get_mock_user_bob_canister_id(),
+        });
+
+        let mut score_decay_pass = ScoreDecayPass::default();
+        assert!(!score_decay_pass.is_in_progress());
+
+        score_decay_pass.start(
+            &posts_index_sorted_by_home_feed_score,
+            &posts_index_sorted_by_hot_or_not_feed_score,
+        );
+        assert!(score_decay_pass.is_in_progress());
+
+        score_decay_pass.decay_next_chunk(
+            &mut posts_index_sorted_by_home_feed_score,
+            &mut posts_index_sorted_by_hot_or_not_feed_score,
+            2,
+        );
+        assert_eq!(score_decay_pass.home_feed_entries_to_decay.len(), 1);
+        assert_eq!(
+            posts_index_sorted_by_home_feed_score
+                .iter()
+                .filter(|item| item.score == 900)
+                .count(),
+            2
+        );
+
+        score_decay_pass.decay_next_chunk(
+            &mut posts_index_sorted_by_home_feed_score,
+            &mut posts_index_sorted_by_hot_or_not_feed_score,
+            2,
+        );
+        assert!(!score_decay_pass.is_in_progress());
+        assert!(posts_index_sorted_by_home_feed_score
+            .iter()
+            .all(|item| item.score == 900));
+        // * fully decayed
+        assert_eq!(
+            posts_index_sorted_by_hot_or_not_feed_score.iter().count(),
+            0
+        );
+    }
+}
@@ -1,14 +1,31 @@
-use std::cell::RefCell;
+use std::{cell::RefCell, time::SystemTime};
 
 use candid::{export_service, Principal};
 
-use data_model::CanisterData;
+use data_model::{feed_response_cache::FeedResponseCache, CanisterData};
 use shared_utils::{
-    canister_specific::post_cache::types::arg::PostCacheInitArgs,
+    canister_specific::{
+        individual_user_template::types::post::PostCategory,
+        post_cache::types::{
+            arg::PostCacheInitArgs, feed_item::FeedItem,
+            feed_response_cache::FeedResponseCacheMetrics,
+            hydrated_post_details::HydratedPostDetails,
+            trending_hashtag::TrendingHashtagsForLanguage,
+        },
+    },
     common::types::{
-        known_principal::KnownPrincipalType, top_posts::post_score_index_item::PostScoreIndexItem,
+        app_primitive_type::PostId,
+        error_code::ErrorCodeRegistryEntry,
+        global_post_ref::GlobalPostRef,
+        known_principal::KnownPrincipalType,
+        top_posts::{
+            hot_or_not_bet_activity::HotOrNotBetActivityDelta,
+            post_score_index_item::PostScoreIndexItem, slot_heat_indicator::SlotHeatIndicator,
+        },
+    },
+    types::canister_specific::post_cache::error_types::{
+        PostDetailsHydrationError, TopPostsFetchError,
     },
-    types::canister_specific::post_cache::error_types::TopPostsFetchError,
 };
 
 mod api;
@@ -19,6 +36,7 @@ mod util;
 
 thread_local! {
     static CANISTER_DATA: RefCell<CanisterData> = RefCell::default();
+    static FEED_RESPONSE_CACHE: RefCell<FeedResponseCache> = RefCell::default();
 }
 
 #[ic_cdk::query(name = "__get_candid_interface_tmp_hack")]
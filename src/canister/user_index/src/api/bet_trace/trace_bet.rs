@@ -0,0 +1,144 @@
+use candid::Principal;
+use shared_utils::{
+    canister_specific::{
+        individual_user_template::types::{
+            error::GetBetTraceError,
+            hot_or_not::bet_trace::{BettorSideBetTrace, PostSideBetTrace},
+        },
+        user_index::types::bet_trace::BetTrace,
+    },
+    common::types::{global_post_ref::GlobalPostRef, known_principal::KnownPrincipalType},
+};
+
+use crate::{data_model::CanisterData, CANISTER_DATA};
+
+/// Follows a single bet end to end for support: the bet as both the bettor's canister and
+/// the post's canister recorded it, the room outcome, the token events it moved on either
+/// side and anything still queued up to be settled for it
+///
+/// # Access Control
+/// Only the global super admin can trace bets
+#[ic_cdk::update]
+#[candid::candid_method(update)]
+async fn trace_bet(
+    bettor_principal_id: Principal,
+    post_ref: GlobalPostRef,
+) -> Result<BetTrace, String> {
+    let api_caller = ic_cdk::caller();
+
+    let bettor_canister_id = CANISTER_DATA.with(|canister_data_ref_cell| {
+        get_bettor_canister_id_to_trace(
+            &canister_data_ref_cell.borrow(),
+            &api_caller,
+            &bettor_principal_id,
+        )
+    })?;
+
+    let bettor_side = match bettor_canister_id {
+        Some(bettor_canister_id) => {
+            ic_cdk::call::<_, (Result<BettorSideBetTrace, GetBetTraceError>,)>(
+                bettor_canister_id,
+                "get_bettor_side_bet_trace",
+                (post_ref.canister_id, post_ref.post_id),
+            )
+            .await
+            .map_err(|(_, message)| {
+                format!(
+                    "Failed to read the bet from the bettor's canister: {}",
+                    message
+                )
+            })
+            .and_then(|(bettor_side,)| bettor_side.map_err(|error| format!("{:?}", error)))
+        }
+        None => Err("Bettor has no canister".to_string()),
+    };
+
+    let post_side = ic_cdk::call::<_, (Result<PostSideBetTrace, GetBetTraceError>,)>(
+        post_ref.canister_id,
+        "get_post_side_bet_trace",
+        (post_ref.post_id, bettor_principal_id),
+    )
+    .await
+    .map_err(|(_, message)| {
+        format!(
+            "Failed to read the bet from the post's canister: {}",
+            message
+        )
+    })
+    .and_then(|(post_side,)| post_side.map_err(|error| format!("{:?}", error)));
+
+    Ok(BetTrace {
+        bettor_principal_id,
+        bettor_canister_id,
+        post_ref,
+        bettor_side,
+        post_side,
+    })
+}
+
+fn get_bettor_canister_id_to_trace(
+    canister_data: &CanisterData,
+    api_caller: &Principal,
+    bettor_principal_id: &Principal,
+) -> Result<Option<Principal>, String> {
+    if canister_data
+        .known_principal_ids
+        .get(&KnownPrincipalType::UserIdGlobalSuperAdmin)
+        != Some(api_caller)
+    {
+        return Err("Unauthorized caller".to_string());
+    }
+
+    Ok(canister_data
+        .user_principal_id_to_canister_id_map
+        .get(bettor_principal_id)
+        .copied())
+}
+
+#[cfg(test)]
+mod test {
+    use test_utils::setup::test_constants::{
+        get_global_super_admin_principal_id, get_mock_user_alice_principal_id,
+        get_mock_user_bob_canister_id, get_mock_user_bob_principal_id,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_get_bettor_canister_id_to_trace() {
+        let mut canister_data = CanisterData::default();
+        canister_data.known_principal_ids.insert(
+            KnownPrincipalType::UserIdGlobalSuperAdmin,
+            get_global_super_admin_principal_id(),
+        );
+        canister_data.user_principal_id_to_canister_id_map.insert(
+            get_mock_user_bob_principal_id(),
+            get_mock_user_bob_canister_id(),
+        );
+
+        assert_eq!(
+            get_bettor_canister_id_to_trace(
+                &canister_data,
+                &get_mock_user_alice_principal_id(),
+                &get_mock_user_bob_principal_id(),
+            ),
+            Err("Unauthorized caller".to_string())
+        );
+        assert_eq!(
+            get_bettor_canister_id_to_trace(
+                &canister_data,
+                &get_global_super_admin_principal_id(),
+                &get_mock_user_bob_principal_id(),
+            ),
+            Ok(Some(get_mock_user_bob_canister_id()))
+        );
+        assert_eq!(
+            get_bettor_canister_id_to_trace(
+                &canister_data,
+                &get_global_super_admin_principal_id(),
+                &get_mock_user_alice_principal_id(),
+            ),
+            Ok(None)
+        );
+    }
+}
@@ -0,0 +1,85 @@
+use candid::Principal;
+use shared_utils::{
+    common::{types::known_principal::KnownPrincipalType, utils::call_queue::QueuedCall},
+    pagination::Page,
+};
+
+use crate::{data_model::CanisterData, CANISTER_DATA};
+
+/// Fire and forget calls to other canisters that ran out of attempts, oldest first. Only the
+/// last `MAX_DEAD_LETTERED_CALLS` of them are kept.
+///
+/// # Access Control
+/// Only the global super admin
+#[ic_cdk::query]
+#[candid::candid_method(query)]
+fn get_dead_lettered_calls(
+    from_inclusive_index: u64,
+    page_size: u64,
+) -> Result<Page<QueuedCall>, String> {
+    let api_caller = ic_cdk::caller();
+
+    CANISTER_DATA.with(|canister_data_ref_cell| {
+        get_dead_lettered_calls_impl(
+            &canister_data_ref_cell.borrow(),
+            &api_caller,
+            from_inclusive_index,
+            page_size,
+        )
+    })
+}
+
+fn get_dead_lettered_calls_impl(
+    canister_data: &CanisterData,
+    api_caller: &Principal,
+    from_inclusive_index: u64,
+    page_size: u64,
+) -> Result<Page<QueuedCall>, String> {
+    if canister_data
+        .known_principal_ids
+        .get(&KnownPrincipalType::UserIdGlobalSuperAdmin)
+        != Some(api_caller)
+    {
+        return Err("Unauthorized caller".to_string());
+    }
+
+    Ok(canister_data
+        .call_queue
+        .get_dead_lettered_calls(from_inclusive_index, page_size))
+}
+
+#[cfg(test)]
+mod test {
+    use test_utils::setup::test_constants::{
+        get_global_super_admin_principal_id, get_mock_user_alice_principal_id,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_get_dead_lettered_calls_impl() {
+        let mut canister_data = CanisterData::default();
+        canister_data.known_principal_ids.insert(
+            KnownPrincipalType::UserIdGlobalSuperAdmin,
+            get_global_super_admin_principal_id(),
+        );
+
+        assert!(get_dead_lettered_calls_impl(
+            &canister_data,
+            &get_mock_user_alice_principal_id(),
+            0,
+            10
+        )
+        .is_err());
+        assert_eq!(
+            get_dead_lettered_calls_impl(
+                &canister_data,
+                &get_global_super_admin_principal_id(),
+                0,
+                10
+            )
+            .map(|page| page.total_count),
+            Ok(0)
+        );
+    }
+}
@@ -0,0 +1 @@
+pub mod get_dead_lettered_calls;
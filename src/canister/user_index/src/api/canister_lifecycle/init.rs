@@ -1,6 +1,11 @@
 use shared_utils::canister_specific::user_index::types::args::UserIndexInitArgs;
 
-use crate::{data_model::CanisterData, CANISTER_DATA};
+use crate::{
+    api::metrics::record_user_index_metrics::start_recording_user_index_metrics,
+    data_model::{memory, CanisterData},
+    util::call_queue::enqueue_timer_for_retrying_queued_calls,
+    CANISTER_DATA,
+};
 
 #[ic_cdk::init]
 #[candid::candid_method(init)]
@@ -9,17 +14,17 @@ fn init(init_args: UserIndexInitArgs) {
         let mut data = canister_data_ref_cell.borrow_mut();
         init_impl(init_args, &mut data);
     });
+
+    start_recording_user_index_metrics();
+    enqueue_timer_for_retrying_queued_calls();
 }
 
 fn init_impl(init_args: UserIndexInitArgs, data: &mut CanisterData) {
-    init_args
-        .known_principal_ids
-        .unwrap_or_default()
-        .iter()
-        .for_each(|(principal_belongs_to, principal_id)| {
-            data.known_principal_ids
-                .insert(*principal_belongs_to, *principal_id);
-        });
+    data.known_principal_ids
+        .extend(init_args.known_principal_ids.unwrap_or_default().iter());
+
+    memory::register_all_memory_regions(&mut data.memory_id_registry)
+        .expect("Memory ids assigned in this wasm conflict with each other");
 }
 
 #[cfg(test)]
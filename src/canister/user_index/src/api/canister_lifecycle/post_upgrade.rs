@@ -1,13 +1,17 @@
 use std::time::Duration;
 
+use ic_stable_structures::Memory;
 use shared_utils::common::utils::stable_memory_serializer_deserializer;
 
 use crate::{
     api::{
+        metrics::record_user_index_metrics::start_recording_user_index_metrics,
+        tournament::close_tournament_and_distribute_prizes::reenqueue_timers_for_unclosed_tournaments,
         upgrade_individual_user_template::update_user_index_upgrade_user_canisters_with_latest_wasm,
         well_known_principal::update_locally_stored_well_known_principals,
     },
-    data_model::{configuration::Configuration, CanisterData},
+    data_model::{memory, CanisterData},
+    util::call_queue::enqueue_timer_for_retrying_queued_calls,
     CANISTER_DATA,
 };
 
@@ -16,26 +20,39 @@ use super::pre_upgrade::BUFFER_SIZE_BYTES;
 #[ic_cdk::post_upgrade]
 fn post_upgrade() {
     restore_data_from_stable_memory();
+    register_memory_regions_added_since_last_upgrade();
     refetch_well_known_principals();
+    interrupt_rolling_upgrade();
     upgrade_all_indexed_user_canisters();
+    reenqueue_timers_for_unclosed_tournaments();
+    start_recording_user_index_metrics();
+    enqueue_timer_for_retrying_queued_calls();
 
     CANISTER_DATA.with(|canister_data_ref_cell| {
-        let well_known_principals = canister_data_ref_cell.borrow().known_principal_ids.clone();
-
-        canister_data_ref_cell.borrow_mut().configuration = Configuration {
-            known_principal_ids: well_known_principals,
-            signups_open_on_this_subnet: false,
-            url_to_send_canister_metrics_to:
-                "https://receive-canister-metrics-and-push-to-timeseries-d-74gsa5ifla-uc.a.run.app/receive-metrics"
-                    .to_string(),
-        };
+        let canister_data = &mut *canister_data_ref_cell.borrow_mut();
+
+        // * Everything else in the configuration is set through the admin endpoints and kept
+        canister_data.configuration.known_principal_ids = canister_data.known_principal_ids.clone();
+        canister_data.configuration.signups_open_on_this_subnet = false;
+        canister_data.configuration.url_to_send_canister_metrics_to =
+            "https://receive-canister-metrics-and-push-to-timeseries-d-74gsa5ifla-uc.a.run.app/receive-metrics"
+                .to_string();
     });
 }
 
 fn restore_data_from_stable_memory() {
-    match stable_memory_serializer_deserializer::deserialize_from_stable_memory::<CanisterData>(
-        BUFFER_SIZE_BYTES,
-    ) {
+    let canister_data = if memory::is_stable_memory_managed_by_memory_manager() {
+        read_canister_data_from_upgrades_memory()
+    } else {
+        // * Upgrading from a wasm that serialized the heap straight to stable memory.
+        // * The memory manager takes over stable memory once this has been read.
+        stable_memory_serializer_deserializer::deserialize_from_stable_memory::<CanisterData>(
+            BUFFER_SIZE_BYTES,
+        )
+        .map_err(|e| format!("{:?}", e))
+    };
+
+    match canister_data {
         Ok(canister_data) => {
             CANISTER_DATA.with(|canister_data_ref_cell| {
                 *canister_data_ref_cell.borrow_mut() = canister_data;
@@ -48,6 +65,33 @@ fn restore_data_from_stable_memory() {
     }
 }
 
+fn read_canister_data_from_upgrades_memory() -> Result<CanisterData, String> {
+    let upgrades_memory = memory::get_upgrades_memory();
+
+    // * Read the length of the heap data state.
+    // * Since heap can be at max 4 GiB, 4 bytes are enough to store the length.
+    let mut heap_data_len_bytes = [0; 4];
+    upgrades_memory.read(0, &mut heap_data_len_bytes);
+    let heap_data_len = u32::from_le_bytes(heap_data_len_bytes) as usize;
+
+    let mut canister_data_bytes = vec![0; heap_data_len];
+    upgrades_memory.read(4, &mut canister_data_bytes);
+
+    stable_memory_serializer_deserializer::deserialize(&*canister_data_bytes)
+        .map_err(|e| format!("{:?}", e))
+}
+
+fn register_memory_regions_added_since_last_upgrade() {
+    // * Trapping here rolls back the upgrade, which is what we want if this wasm
+    // * maps a memory id that was previously handed out to another structure.
+    CANISTER_DATA.with(|canister_data_ref_cell| {
+        memory::register_all_memory_regions(
+            &mut canister_data_ref_cell.borrow_mut().memory_id_registry,
+        )
+        .expect("Memory id assignments conflict with the ones persisted before the upgrade");
+    });
+}
+
 const DELAY_FOR_REFETCHING_WELL_KNOWN_PRINCIPALS: Duration = Duration::from_secs(1);
 fn refetch_well_known_principals() {
     ic_cdk_timers::set_timer(DELAY_FOR_REFETCHING_WELL_KNOWN_PRINCIPALS, || {
@@ -55,6 +99,17 @@ fn refetch_well_known_principals() {
     });
 }
 
+// * The rollout stopped with the upgrade. An admin resumes it if this wasm still embeds the
+// * individual user template wasm it was rolling out.
+fn interrupt_rolling_upgrade() {
+    CANISTER_DATA.with(|canister_data_ref_cell| {
+        if let Some(rolling_upgrade) = canister_data_ref_cell.borrow_mut().rolling_upgrade.as_mut()
+        {
+            rolling_upgrade.interrupt();
+        }
+    });
+}
+
 const DELAY_FOR_UPGRADING_ALL_INDEXED_USER_CANISTERS: Duration = Duration::from_secs(10);
 fn upgrade_all_indexed_user_canisters() {
     ic_cdk_timers::set_timer(DELAY_FOR_UPGRADING_ALL_INDEXED_USER_CANISTERS, || {
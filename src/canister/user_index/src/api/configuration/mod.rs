@@ -0,0 +1,8 @@
+pub mod update_bet_cancellation_config;
+pub mod update_house_rake_percentage;
+pub mod update_post_category_config;
+pub mod update_pot_insurance_config;
+pub mod update_referrer_share_of_bet_winnings_percentage;
+pub mod update_reserved_usernames;
+pub mod update_room_sizing_config;
+pub mod update_signup_bonus_vesting_config;
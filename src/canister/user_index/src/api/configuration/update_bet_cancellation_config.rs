@@ -0,0 +1,100 @@
+use candid::Principal;
+use shared_utils::{
+    canister_specific::individual_user_template::types::hot_or_not::BetCancellationConfig,
+    common::types::known_principal::KnownPrincipalType,
+};
+
+use crate::{data_model::CanisterData, CANISTER_DATA};
+
+/// Canisters created from now on get the new refund right away, existing ones the next time
+/// they are upgraded. A cancelled bet is refunded by the config of the post's canister.
+///
+/// # Access Control
+/// Only the global super admin can update the bet cancellation config
+#[ic_cdk::update]
+#[candid::candid_method(update)]
+fn update_bet_cancellation_config(
+    bet_cancellation_config: BetCancellationConfig,
+) -> Result<(), String> {
+    let api_caller = ic_cdk::caller();
+
+    CANISTER_DATA.with(|canister_data_ref_cell| {
+        update_bet_cancellation_config_impl(
+            &mut canister_data_ref_cell.borrow_mut(),
+            &api_caller,
+            bet_cancellation_config,
+        )
+    })
+}
+
+fn update_bet_cancellation_config_impl(
+    canister_data: &mut CanisterData,
+    api_caller: &Principal,
+    bet_cancellation_config: BetCancellationConfig,
+) -> Result<(), String> {
+    if canister_data
+        .known_principal_ids
+        .get(&KnownPrincipalType::UserIdGlobalSuperAdmin)
+        != Some(api_caller)
+    {
+        return Err("Unauthorized caller".to_string());
+    }
+
+    bet_cancellation_config.validate()?;
+
+    canister_data.configuration.bet_cancellation_config = bet_cancellation_config;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use test_utils::setup::test_constants::{
+        get_global_super_admin_principal_id, get_mock_user_alice_principal_id,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_update_bet_cancellation_config_impl() {
+        let mut canister_data = CanisterData::default();
+        canister_data.known_principal_ids.insert(
+            KnownPrincipalType::UserIdGlobalSuperAdmin,
+            get_global_super_admin_principal_id(),
+        );
+        let bet_cancellation_config = BetCancellationConfig {
+            refund_percentage: 75,
+        };
+
+        assert!(update_bet_cancellation_config_impl(
+            &mut canister_data,
+            &get_mock_user_alice_principal_id(),
+            bet_cancellation_config.clone()
+        )
+        .is_err());
+        assert!(update_bet_cancellation_config_impl(
+            &mut canister_data,
+            &get_global_super_admin_principal_id(),
+            BetCancellationConfig {
+                refund_percentage: 101,
+            }
+        )
+        .is_err());
+        assert_eq!(
+            canister_data.configuration.bet_cancellation_config,
+            BetCancellationConfig::default()
+        );
+        assert_eq!(
+            update_bet_cancellation_config_impl(
+                &mut canister_data,
+                &get_global_super_admin_principal_id(),
+                bet_cancellation_config.clone()
+            ),
+            Ok(())
+        );
+        assert_eq!(
+            canister_data.configuration.bet_cancellation_config,
+            bet_cancellation_config
+        );
+    }
+}
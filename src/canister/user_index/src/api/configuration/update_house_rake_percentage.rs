@@ -0,0 +1,91 @@
+use candid::Principal;
+use shared_utils::{
+    canister_specific::individual_user_template::types::treasury::MAXIMUM_HOUSE_RAKE_PERCENTAGE,
+    common::types::known_principal::KnownPrincipalType,
+};
+
+use crate::{data_model::CanisterData, CANISTER_DATA};
+
+/// Individual user canisters pick up the new percentage the next time they are upgraded
+///
+/// # Access Control
+/// Only the global super admin can update the percentage
+#[ic_cdk::update]
+#[candid::candid_method(update)]
+fn update_house_rake_percentage(percentage: u64) -> Result<(), String> {
+    let api_caller = ic_cdk::caller();
+
+    CANISTER_DATA.with(|canister_data_ref_cell| {
+        update_house_rake_percentage_impl(
+            &mut canister_data_ref_cell.borrow_mut(),
+            &api_caller,
+            percentage,
+        )
+    })
+}
+
+fn update_house_rake_percentage_impl(
+    canister_data: &mut CanisterData,
+    api_caller: &Principal,
+    percentage: u64,
+) -> Result<(), String> {
+    if canister_data
+        .known_principal_ids
+        .get(&KnownPrincipalType::UserIdGlobalSuperAdmin)
+        != Some(api_caller)
+    {
+        return Err("Unauthorized caller".to_string());
+    }
+
+    if percentage > MAXIMUM_HOUSE_RAKE_PERCENTAGE {
+        return Err(format!(
+            "Percentage cannot exceed {}",
+            MAXIMUM_HOUSE_RAKE_PERCENTAGE
+        ));
+    }
+
+    canister_data.configuration.house_rake_percentage = percentage;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use test_utils::setup::test_constants::{
+        get_global_super_admin_principal_id, get_mock_user_alice_principal_id,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_update_house_rake_percentage_impl() {
+        let mut canister_data = CanisterData::default();
+        canister_data.known_principal_ids.insert(
+            KnownPrincipalType::UserIdGlobalSuperAdmin,
+            get_global_super_admin_principal_id(),
+        );
+
+        assert_eq!(canister_data.configuration.house_rake_percentage, 0);
+        assert!(update_house_rake_percentage_impl(
+            &mut canister_data,
+            &get_mock_user_alice_principal_id(),
+            1
+        )
+        .is_err());
+        assert!(update_house_rake_percentage_impl(
+            &mut canister_data,
+            &get_global_super_admin_principal_id(),
+            MAXIMUM_HOUSE_RAKE_PERCENTAGE + 1
+        )
+        .is_err());
+        assert_eq!(
+            update_house_rake_percentage_impl(
+                &mut canister_data,
+                &get_global_super_admin_principal_id(),
+                1
+            ),
+            Ok(())
+        );
+        assert_eq!(canister_data.configuration.house_rake_percentage, 1);
+    }
+}
@@ -0,0 +1,98 @@
+use candid::Principal;
+use shared_utils::{
+    canister_specific::individual_user_template::types::post::PostCategoryConfig,
+    common::types::known_principal::KnownPrincipalType,
+};
+
+use crate::{data_model::CanisterData, CANISTER_DATA};
+
+/// Individual user canisters pick up the new categories the next time they are upgraded
+///
+/// # Access Control
+/// Only the global super admin can update the categories
+#[ic_cdk::update]
+#[candid::candid_method(update)]
+fn update_post_category_config(post_category_config: PostCategoryConfig) -> Result<(), String> {
+    let api_caller = ic_cdk::caller();
+
+    CANISTER_DATA.with(|canister_data_ref_cell| {
+        update_post_category_config_impl(
+            &mut canister_data_ref_cell.borrow_mut(),
+            &api_caller,
+            post_category_config,
+        )
+    })
+}
+
+fn update_post_category_config_impl(
+    canister_data: &mut CanisterData,
+    api_caller: &Principal,
+    post_category_config: PostCategoryConfig,
+) -> Result<(), String> {
+    if canister_data
+        .known_principal_ids
+        .get(&KnownPrincipalType::UserIdGlobalSuperAdmin)
+        != Some(api_caller)
+    {
+        return Err("Unauthorized caller".to_string());
+    }
+
+    post_category_config.validate()?;
+
+    canister_data.configuration.post_category_config = post_category_config;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use shared_utils::canister_specific::individual_user_template::types::post::PostCategory;
+    use test_utils::setup::test_constants::{
+        get_global_super_admin_principal_id, get_mock_user_alice_principal_id,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_update_post_category_config_impl() {
+        let mut canister_data = CanisterData::default();
+        canister_data.known_principal_ids.insert(
+            KnownPrincipalType::UserIdGlobalSuperAdmin,
+            get_global_super_admin_principal_id(),
+        );
+        let post_category_config = PostCategoryConfig {
+            allowed_categories: vec![PostCategory::Comedy, PostCategory::Other],
+        };
+
+        assert!(update_post_category_config_impl(
+            &mut canister_data,
+            &get_mock_user_alice_principal_id(),
+            post_category_config.clone()
+        )
+        .is_err());
+        assert!(update_post_category_config_impl(
+            &mut canister_data,
+            &get_global_super_admin_principal_id(),
+            PostCategoryConfig {
+                allowed_categories: vec![],
+            }
+        )
+        .is_err());
+        assert_eq!(
+            canister_data.configuration.post_category_config,
+            PostCategoryConfig::default()
+        );
+        assert_eq!(
+            update_post_category_config_impl(
+                &mut canister_data,
+                &get_global_super_admin_principal_id(),
+                post_category_config.clone()
+            ),
+            Ok(())
+        );
+        assert_eq!(
+            canister_data.configuration.post_category_config,
+            post_category_config
+        );
+    }
+}
@@ -0,0 +1,100 @@
+use candid::Principal;
+use shared_utils::{
+    canister_specific::individual_user_template::types::treasury::PotInsuranceConfig,
+    common::types::known_principal::KnownPrincipalType,
+};
+
+use crate::{data_model::CanisterData, CANISTER_DATA};
+
+/// Canisters created from now on get the new floor right away, existing ones the next time
+/// they are upgraded. The treasury checks claims against the config it was upgraded with.
+///
+/// # Access Control
+/// Only the global super admin can update the pot insurance config
+#[ic_cdk::update]
+#[candid::candid_method(update)]
+fn update_pot_insurance_config(pot_insurance_config: PotInsuranceConfig) -> Result<(), String> {
+    let api_caller = ic_cdk::caller();
+
+    CANISTER_DATA.with(|canister_data_ref_cell| {
+        update_pot_insurance_config_impl(
+            &mut canister_data_ref_cell.borrow_mut(),
+            &api_caller,
+            pot_insurance_config,
+        )
+    })
+}
+
+fn update_pot_insurance_config_impl(
+    canister_data: &mut CanisterData,
+    api_caller: &Principal,
+    pot_insurance_config: PotInsuranceConfig,
+) -> Result<(), String> {
+    if canister_data
+        .known_principal_ids
+        .get(&KnownPrincipalType::UserIdGlobalSuperAdmin)
+        != Some(api_caller)
+    {
+        return Err("Unauthorized caller".to_string());
+    }
+
+    pot_insurance_config.validate()?;
+
+    canister_data.configuration.pot_insurance_config = pot_insurance_config;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use test_utils::setup::test_constants::{
+        get_global_super_admin_principal_id, get_mock_user_alice_principal_id,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_update_pot_insurance_config_impl() {
+        let mut canister_data = CanisterData::default();
+        canister_data.known_principal_ids.insert(
+            KnownPrincipalType::UserIdGlobalSuperAdmin,
+            get_global_super_admin_principal_id(),
+        );
+        let pot_insurance_config = PotInsuranceConfig {
+            room_pot_floor: 500,
+            daily_spend_cap: 100_000,
+        };
+
+        assert!(update_pot_insurance_config_impl(
+            &mut canister_data,
+            &get_mock_user_alice_principal_id(),
+            pot_insurance_config.clone()
+        )
+        .is_err());
+        assert!(update_pot_insurance_config_impl(
+            &mut canister_data,
+            &get_global_super_admin_principal_id(),
+            PotInsuranceConfig {
+                daily_spend_cap: 0,
+                ..pot_insurance_config.clone()
+            }
+        )
+        .is_err());
+        assert_eq!(
+            canister_data.configuration.pot_insurance_config,
+            PotInsuranceConfig::default()
+        );
+        assert_eq!(
+            update_pot_insurance_config_impl(
+                &mut canister_data,
+                &get_global_super_admin_principal_id(),
+                pot_insurance_config.clone()
+            ),
+            Ok(())
+        );
+        assert_eq!(
+            canister_data.configuration.pot_insurance_config,
+            pot_insurance_config
+        );
+    }
+}
@@ -0,0 +1,97 @@
+use candid::Principal;
+use shared_utils::common::types::known_principal::KnownPrincipalType;
+
+use crate::{data_model::CanisterData, CANISTER_DATA};
+
+/// Individual user canisters pick up the new percentage the next time they are upgraded
+///
+/// # Access Control
+/// Only the global super admin can update the percentage
+#[ic_cdk::update]
+#[candid::candid_method(update)]
+fn update_referrer_share_of_bet_winnings_percentage(percentage: u64) -> Result<(), String> {
+    let api_caller = ic_cdk::caller();
+
+    CANISTER_DATA.with(|canister_data_ref_cell| {
+        update_referrer_share_of_bet_winnings_percentage_impl(
+            &mut canister_data_ref_cell.borrow_mut(),
+            &api_caller,
+            percentage,
+        )
+    })
+}
+
+fn update_referrer_share_of_bet_winnings_percentage_impl(
+    canister_data: &mut CanisterData,
+    api_caller: &Principal,
+    percentage: u64,
+) -> Result<(), String> {
+    if canister_data
+        .known_principal_ids
+        .get(&KnownPrincipalType::UserIdGlobalSuperAdmin)
+        != Some(api_caller)
+    {
+        return Err("Unauthorized caller".to_string());
+    }
+
+    if percentage > 100 {
+        return Err("Percentage cannot exceed 100".to_string());
+    }
+
+    canister_data
+        .configuration
+        .referrer_share_of_bet_winnings_percentage = percentage;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use test_utils::setup::test_constants::{
+        get_global_super_admin_principal_id, get_mock_user_alice_principal_id,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_update_referrer_share_of_bet_winnings_percentage_impl() {
+        let mut canister_data = CanisterData::default();
+        canister_data.known_principal_ids.insert(
+            KnownPrincipalType::UserIdGlobalSuperAdmin,
+            get_global_super_admin_principal_id(),
+        );
+
+        assert_eq!(
+            canister_data
+                .configuration
+                .referrer_share_of_bet_winnings_percentage,
+            0
+        );
+        assert!(update_referrer_share_of_bet_winnings_percentage_impl(
+            &mut canister_data,
+            &get_mock_user_alice_principal_id(),
+            1
+        )
+        .is_err());
+        assert!(update_referrer_share_of_bet_winnings_percentage_impl(
+            &mut canister_data,
+            &get_global_super_admin_principal_id(),
+            101
+        )
+        .is_err());
+        assert_eq!(
+            update_referrer_share_of_bet_winnings_percentage_impl(
+                &mut canister_data,
+                &get_global_super_admin_principal_id(),
+                1
+            ),
+            Ok(())
+        );
+        assert_eq!(
+            canister_data
+                .configuration
+                .referrer_share_of_bet_winnings_percentage,
+            1
+        );
+    }
+}
@@ -0,0 +1,129 @@
+use candid::Principal;
+use shared_utils::common::{
+    types::known_principal::KnownPrincipalType, utils::profanity::normalize_for_screening,
+};
+
+use crate::{data_model::CanisterData, CANISTER_DATA};
+
+/// Usernames already claimed stay with their owners. Reserving a name only stops it, and
+/// anything that normalizes to the same text, from being claimed from now on.
+///
+/// # Access Control
+/// Only the global super admin can update the reserved usernames
+#[ic_cdk::update]
+#[candid::candid_method(update)]
+fn update_reserved_usernames(
+    usernames_to_reserve: Vec<String>,
+    usernames_to_release: Vec<String>,
+) -> Result<(), String> {
+    let api_caller = ic_cdk::caller();
+
+    CANISTER_DATA.with(|canister_data_ref_cell| {
+        update_reserved_usernames_impl(
+            &mut canister_data_ref_cell.borrow_mut(),
+            &api_caller,
+            usernames_to_reserve,
+            usernames_to_release,
+        )
+    })
+}
+
+fn update_reserved_usernames_impl(
+    canister_data: &mut CanisterData,
+    api_caller: &Principal,
+    usernames_to_reserve: Vec<String>,
+    usernames_to_release: Vec<String>,
+) -> Result<(), String> {
+    if canister_data
+        .known_principal_ids
+        .get(&KnownPrincipalType::UserIdGlobalSuperAdmin)
+        != Some(api_caller)
+    {
+        return Err("Unauthorized caller".to_string());
+    }
+
+    if usernames_to_reserve
+        .iter()
+        .any(|username| normalize_for_screening(username).is_empty())
+    {
+        return Err("Reserved usernames need at least one letter or digit".to_string());
+    }
+
+    let reserved_usernames = &mut canister_data.configuration.reserved_usernames;
+    reserved_usernames.extend(
+        usernames_to_reserve
+            .iter()
+            .map(|username| normalize_for_screening(username)),
+    );
+    for username in usernames_to_release {
+        let normalized_username = normalize_for_screening(&username);
+        reserved_usernames.remove(&normalized_username);
+        canister_data
+            .reserved_username_grants
+            .remove(&normalized_username);
+        canister_data
+            .reserved_username_appeals
+            .retain(|(appealed_username, _), _| *appealed_username != normalized_username);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::BTreeSet;
+
+    use test_utils::setup::test_constants::{
+        get_global_super_admin_principal_id, get_mock_user_alice_principal_id,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_update_reserved_usernames_impl() {
+        let mut canister_data = CanisterData::default();
+        canister_data.known_principal_ids.insert(
+            KnownPrincipalType::UserIdGlobalSuperAdmin,
+            get_global_super_admin_principal_id(),
+        );
+
+        assert!(update_reserved_usernames_impl(
+            &mut canister_data,
+            &get_mock_user_alice_principal_id(),
+            vec!["admin".to_string()],
+            vec![],
+        )
+        .is_err());
+        assert!(update_reserved_usernames_impl(
+            &mut canister_data,
+            &get_global_super_admin_principal_id(),
+            vec!["admin".to_string(), "__".to_string()],
+            vec![],
+        )
+        .is_err());
+        assert!(canister_data.configuration.reserved_usernames.is_empty());
+
+        assert_eq!(
+            update_reserved_usernames_impl(
+                &mut canister_data,
+                &get_global_super_admin_principal_id(),
+                vec!["Admin".to_string(), "Hot_Or_Not".to_string()],
+                vec![],
+            ),
+            Ok(())
+        );
+        assert_eq!(
+            update_reserved_usernames_impl(
+                &mut canister_data,
+                &get_global_super_admin_principal_id(),
+                vec![],
+                vec!["ADMIN".to_string()],
+            ),
+            Ok(())
+        );
+        assert_eq!(
+            canister_data.configuration.reserved_usernames,
+            BTreeSet::from(["hotornot".to_string()])
+        );
+    }
+}
@@ -0,0 +1,107 @@
+use candid::Principal;
+use shared_utils::{
+    canister_specific::individual_user_template::types::hot_or_not::RoomSizingConfig,
+    common::types::known_principal::KnownPrincipalType,
+};
+
+use crate::{data_model::CanisterData, CANISTER_DATA};
+
+/// Individual user canisters pick up the new room sizes the next time they are upgraded
+///
+/// # Access Control
+/// Only the global super admin can update the room sizes
+#[ic_cdk::update]
+#[candid::candid_method(update)]
+fn update_room_sizing_config(room_sizing_config: RoomSizingConfig) -> Result<(), String> {
+    let api_caller = ic_cdk::caller();
+
+    CANISTER_DATA.with(|canister_data_ref_cell| {
+        update_room_sizing_config_impl(
+            &mut canister_data_ref_cell.borrow_mut(),
+            &api_caller,
+            room_sizing_config,
+        )
+    })
+}
+
+fn update_room_sizing_config_impl(
+    canister_data: &mut CanisterData,
+    api_caller: &Principal,
+    room_sizing_config: RoomSizingConfig,
+) -> Result<(), String> {
+    if canister_data
+        .known_principal_ids
+        .get(&KnownPrincipalType::UserIdGlobalSuperAdmin)
+        != Some(api_caller)
+    {
+        return Err("Unauthorized caller".to_string());
+    }
+
+    room_sizing_config.validate()?;
+
+    canister_data.configuration.room_sizing_config = room_sizing_config;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use shared_utils::canister_specific::individual_user_template::types::hot_or_not::RoomSizeForVelocity;
+    use test_utils::setup::test_constants::{
+        get_global_super_admin_principal_id, get_mock_user_alice_principal_id,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_update_room_sizing_config_impl() {
+        let mut canister_data = CanisterData::default();
+        canister_data.known_principal_ids.insert(
+            KnownPrincipalType::UserIdGlobalSuperAdmin,
+            get_global_super_admin_principal_id(),
+        );
+        let room_sizing_config = RoomSizingConfig {
+            default_room_size: 100,
+            high_velocity: Some(RoomSizeForVelocity {
+                projected_bets_in_slot_threshold: 1000,
+                room_size: 50,
+            }),
+            low_velocity: Some(RoomSizeForVelocity {
+                projected_bets_in_slot_threshold: 20,
+                room_size: 250,
+            }),
+        };
+
+        assert!(update_room_sizing_config_impl(
+            &mut canister_data,
+            &get_mock_user_alice_principal_id(),
+            room_sizing_config.clone()
+        )
+        .is_err());
+        assert!(update_room_sizing_config_impl(
+            &mut canister_data,
+            &get_global_super_admin_principal_id(),
+            RoomSizingConfig {
+                default_room_size: 300,
+                ..room_sizing_config.clone()
+            }
+        )
+        .is_err());
+        assert_eq!(
+            canister_data.configuration.room_sizing_config,
+            RoomSizingConfig::default()
+        );
+        assert_eq!(
+            update_room_sizing_config_impl(
+                &mut canister_data,
+                &get_global_super_admin_principal_id(),
+                room_sizing_config.clone()
+            ),
+            Ok(())
+        );
+        assert_eq!(
+            canister_data.configuration.room_sizing_config,
+            room_sizing_config
+        );
+    }
+}
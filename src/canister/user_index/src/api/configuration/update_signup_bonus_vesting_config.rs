@@ -0,0 +1,103 @@
+use candid::Principal;
+use shared_utils::{
+    canister_specific::individual_user_template::types::signup_bonus::SignupBonusVestingConfig,
+    common::types::known_principal::KnownPrincipalType,
+};
+
+use crate::{data_model::CanisterData, CANISTER_DATA};
+
+/// Canisters created from now on get the new schedule right away, existing ones the next time
+/// they are upgraded. Users who already signed up keep vesting on the schedule they got.
+///
+/// # Access Control
+/// Only the global super admin can update the schedule
+#[ic_cdk::update]
+#[candid::candid_method(update)]
+fn update_signup_bonus_vesting_config(
+    signup_bonus_vesting_config: SignupBonusVestingConfig,
+) -> Result<(), String> {
+    let api_caller = ic_cdk::caller();
+
+    CANISTER_DATA.with(|canister_data_ref_cell| {
+        update_signup_bonus_vesting_config_impl(
+            &mut canister_data_ref_cell.borrow_mut(),
+            &api_caller,
+            signup_bonus_vesting_config,
+        )
+    })
+}
+
+fn update_signup_bonus_vesting_config_impl(
+    canister_data: &mut CanisterData,
+    api_caller: &Principal,
+    signup_bonus_vesting_config: SignupBonusVestingConfig,
+) -> Result<(), String> {
+    if canister_data
+        .known_principal_ids
+        .get(&KnownPrincipalType::UserIdGlobalSuperAdmin)
+        != Some(api_caller)
+    {
+        return Err("Unauthorized caller".to_string());
+    }
+
+    signup_bonus_vesting_config.validate()?;
+
+    canister_data.configuration.signup_bonus_vesting_config = signup_bonus_vesting_config;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use test_utils::setup::test_constants::{
+        get_global_super_admin_principal_id, get_mock_user_alice_principal_id,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_update_signup_bonus_vesting_config_impl() {
+        let mut canister_data = CanisterData::default();
+        canister_data.known_principal_ids.insert(
+            KnownPrincipalType::UserIdGlobalSuperAdmin,
+            get_global_super_admin_principal_id(),
+        );
+        let signup_bonus_vesting_config = SignupBonusVestingConfig {
+            total_amount: 2000,
+            unlocked_at_signup_percentage: 50,
+            number_of_vesting_days: 14,
+        };
+
+        assert!(update_signup_bonus_vesting_config_impl(
+            &mut canister_data,
+            &get_mock_user_alice_principal_id(),
+            signup_bonus_vesting_config.clone()
+        )
+        .is_err());
+        assert!(update_signup_bonus_vesting_config_impl(
+            &mut canister_data,
+            &get_global_super_admin_principal_id(),
+            SignupBonusVestingConfig {
+                number_of_vesting_days: 0,
+                ..signup_bonus_vesting_config.clone()
+            }
+        )
+        .is_err());
+        assert_eq!(
+            canister_data.configuration.signup_bonus_vesting_config,
+            SignupBonusVestingConfig::default()
+        );
+        assert_eq!(
+            update_signup_bonus_vesting_config_impl(
+                &mut canister_data,
+                &get_global_super_admin_principal_id(),
+                signup_bonus_vesting_config.clone()
+            ),
+            Ok(())
+        );
+        assert_eq!(
+            canister_data.configuration.signup_bonus_vesting_config,
+            signup_bonus_vesting_config
+        );
+    }
+}
@@ -1 +1,2 @@
 pub mod get_user_index_canister_cycle_balance;
+pub mod request_cycle_top_up;
@@ -0,0 +1,156 @@
+use std::time::SystemTime;
+
+use candid::Principal;
+use ic_cdk::api::management_canister::{main, provisional::CanisterIdRecord};
+use shared_utils::{
+    common::utils::system_time,
+    constant::{CYCLE_TOP_UP_COOLDOWN, INDIVIDUAL_USER_CANISTER_RECHARGE_AMOUNT},
+};
+
+use crate::{data_model::CanisterData, CANISTER_DATA};
+
+/// Called by individual user canisters running low on cycles. Deposits a recharge into the
+/// calling canister and returns the number of cycles deposited.
+///
+/// # Access Control
+/// Only individual user canisters indexed by this canister, and their standbys, at most once
+/// per cooldown each
+#[ic_cdk::update]
+#[candid::candid_method(update)]
+async fn request_cycle_top_up() -> Result<u128, String> {
+    let user_canister_id = ic_cdk::caller();
+
+    CANISTER_DATA.with(|canister_data_ref_cell| {
+        reserve_cycle_top_up_impl(
+            &mut canister_data_ref_cell.borrow_mut(),
+            user_canister_id,
+            &system_time::get_current_system_time_from_ic(),
+        )
+    })?;
+
+    let deposit_response = main::deposit_cycles(
+        CanisterIdRecord {
+            canister_id: user_canister_id,
+        },
+        INDIVIDUAL_USER_CANISTER_RECHARGE_AMOUNT,
+    )
+    .await;
+
+    if let Err((_, message)) = deposit_response {
+        // * Lets the canister ask again on its next check
+        CANISTER_DATA.with(|canister_data_ref_cell| {
+            canister_data_ref_cell
+                .borrow_mut()
+                .cycle_top_ups_on_request
+                .remove(&user_canister_id);
+        });
+        return Err(message);
+    }
+
+    Ok(INDIVIDUAL_USER_CANISTER_RECHARGE_AMOUNT)
+}
+
+/// Records the top up before depositing it, so that concurrent requests from the same
+/// canister can't both go through
+fn reserve_cycle_top_up_impl(
+    canister_data: &mut CanisterData,
+    user_canister_id: Principal,
+    current_time: &SystemTime,
+) -> Result<(), String> {
+    let is_indexed_canister = canister_data
+        .user_principal_id_to_canister_id_map
+        .values()
+        .any(|indexed_canister_id| *indexed_canister_id == user_canister_id)
+        || canister_data
+            .user_canister_replications
+            .values()
+            .any(|replication| replication.standby_canister_id == Some(user_canister_id));
+
+    if !is_indexed_canister {
+        return Err("Unauthorized caller".to_string());
+    }
+
+    if let Some(last_topped_up_at) = canister_data
+        .cycle_top_ups_on_request
+        .get(&user_canister_id)
+    {
+        if current_time
+            .duration_since(*last_topped_up_at)
+            .unwrap_or_default()
+            < CYCLE_TOP_UP_COOLDOWN
+        {
+            return Err("Canister was topped up too recently".to_string());
+        }
+    }
+
+    canister_data
+        .cycle_top_ups_on_request
+        .insert(user_canister_id, *current_time);
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use test_utils::setup::test_constants::{
+        get_mock_user_alice_canister_id, get_mock_user_alice_principal_id,
+        get_mock_user_bob_canister_id,
+    };
+
+    use crate::data_model::replication::UserCanisterReplication;
+
+    use super::*;
+
+    #[test]
+    fn test_reserve_cycle_top_up_impl() {
+        let mut canister_data = CanisterData::default();
+        let current_time = SystemTime::UNIX_EPOCH;
+        let standby_canister_id = Principal::from_slice(&[7]);
+        canister_data.user_principal_id_to_canister_id_map.insert(
+            get_mock_user_alice_principal_id(),
+            get_mock_user_alice_canister_id(),
+        );
+        canister_data.user_canister_replications.insert(
+            get_mock_user_alice_principal_id(),
+            UserCanisterReplication {
+                standby_canister_id: Some(standby_canister_id),
+                epoch: 0,
+            },
+        );
+
+        assert!(reserve_cycle_top_up_impl(
+            &mut canister_data,
+            get_mock_user_bob_canister_id(),
+            &current_time
+        )
+        .is_err());
+        assert_eq!(
+            reserve_cycle_top_up_impl(
+                &mut canister_data,
+                get_mock_user_alice_canister_id(),
+                &current_time
+            ),
+            Ok(())
+        );
+        assert_eq!(
+            reserve_cycle_top_up_impl(&mut canister_data, standby_canister_id, &current_time),
+            Ok(())
+        );
+
+        // * once per cooldown
+        assert!(reserve_cycle_top_up_impl(
+            &mut canister_data,
+            get_mock_user_alice_canister_id(),
+            &(current_time + CYCLE_TOP_UP_COOLDOWN / 2)
+        )
+        .is_err());
+        assert_eq!(
+            reserve_cycle_top_up_impl(
+                &mut canister_data,
+                get_mock_user_alice_canister_id(),
+                &(current_time + CYCLE_TOP_UP_COOLDOWN)
+            ),
+            Ok(())
+        );
+    }
+}
@@ -0,0 +1,118 @@
+use candid::Principal;
+use shared_utils::{
+    canister_specific::user_index::types::dispute::DisputedSlot,
+    common::types::known_principal::KnownPrincipalType,
+    pagination::{self, Page, MAX_ITEMS_PER_PAGE},
+};
+
+use crate::{data_model::CanisterData, CANISTER_DATA};
+
+/// Only the first `MAX_ITEMS_PER_PAGE` disputes. Use `get_disputed_slots_paginated` to page
+/// through all of them.
+///
+/// # Access Control
+/// Only the global super admin can review disputes
+#[ic_cdk::query]
+#[candid::candid_method(query)]
+fn get_disputed_slots() -> Result<Vec<DisputedSlot>, String> {
+    let api_caller = ic_cdk::caller();
+
+    CANISTER_DATA.with(|canister_data_ref_cell| {
+        get_disputed_slots_impl(
+            &canister_data_ref_cell.borrow(),
+            &api_caller,
+            0,
+            MAX_ITEMS_PER_PAGE,
+        )
+        .map(|page| page.items)
+    })
+}
+
+/// # Access Control
+/// Only the global super admin can review disputes
+#[ic_cdk::query]
+#[candid::candid_method(query)]
+fn get_disputed_slots_paginated(
+    from_inclusive_index: u64,
+    page_size: u64,
+) -> Result<Page<DisputedSlot>, String> {
+    let api_caller = ic_cdk::caller();
+
+    CANISTER_DATA.with(|canister_data_ref_cell| {
+        get_disputed_slots_impl(
+            &canister_data_ref_cell.borrow(),
+            &api_caller,
+            from_inclusive_index,
+            page_size,
+        )
+    })
+}
+
+fn get_disputed_slots_impl(
+    canister_data: &CanisterData,
+    api_caller: &Principal,
+    from_inclusive_index: u64,
+    page_size: u64,
+) -> Result<Page<DisputedSlot>, String> {
+    if canister_data
+        .known_principal_ids
+        .get(&KnownPrincipalType::UserIdGlobalSuperAdmin)
+        != Some(api_caller)
+    {
+        return Err("Unauthorized caller".to_string());
+    }
+
+    Ok(pagination::get_page(
+        canister_data.slot_outcome_disputes.values().cloned(),
+        from_inclusive_index,
+        page_size,
+    ))
+}
+
+#[cfg(test)]
+mod test {
+    use test_utils::setup::test_constants::{
+        get_global_super_admin_principal_id, get_mock_user_alice_canister_id,
+        get_mock_user_alice_principal_id,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_get_disputed_slots_impl() {
+        let mut canister_data = CanisterData::default();
+        canister_data.known_principal_ids.insert(
+            KnownPrincipalType::UserIdGlobalSuperAdmin,
+            get_global_super_admin_principal_id(),
+        );
+        let disputed_slot = DisputedSlot {
+            post_canister_id: get_mock_user_alice_canister_id(),
+            post_id: 0,
+            slot_id: 1,
+            disputes: vec![],
+            last_recalculation: None,
+        };
+        canister_data.slot_outcome_disputes.insert(
+            (get_mock_user_alice_canister_id(), 0, 1),
+            disputed_slot.clone(),
+        );
+
+        assert_eq!(
+            get_disputed_slots_impl(&canister_data, &get_mock_user_alice_principal_id(), 0, 10),
+            Err("Unauthorized caller".to_string())
+        );
+        assert_eq!(
+            get_disputed_slots_impl(
+                &canister_data,
+                &get_global_super_admin_principal_id(),
+                0,
+                10
+            ),
+            Ok(Page {
+                items: vec![disputed_slot],
+                total_count: 1,
+                next_from_inclusive_index: None,
+            })
+        );
+    }
+}
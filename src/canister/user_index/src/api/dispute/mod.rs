@@ -0,0 +1,3 @@
+pub mod get_disputed_slots;
+pub mod recalculate_disputed_slot_outcome;
+pub mod receive_slot_outcome_dispute;
@@ -0,0 +1,149 @@
+use candid::Principal;
+use shared_utils::{
+    canister_specific::individual_user_template::types::{
+        dispute::SlotOutcomeRecalculation, error::RecalculateSlotOutcomeError, hot_or_not::SlotId,
+    },
+    common::types::{app_primitive_type::PostId, known_principal::KnownPrincipalType},
+};
+
+use crate::{data_model::CanisterData, CANISTER_DATA};
+
+/// Has the post creator's canister re-run tabulation for a disputed slot and keeps the
+/// result with the disputes. Discrepancies are only reported, nothing is paid out again.
+///
+/// # Access Control
+/// Only the global super admin can recalculate disputed slots
+#[ic_cdk::update]
+#[candid::candid_method(update)]
+async fn recalculate_disputed_slot_outcome(
+    post_canister_id: Principal,
+    post_id: PostId,
+    slot_id: SlotId,
+) -> Result<SlotOutcomeRecalculation, String> {
+    let api_caller = ic_cdk::caller();
+
+    CANISTER_DATA.with(|canister_data_ref_cell| {
+        validate_disputed_slot_recalculation_request(
+            &canister_data_ref_cell.borrow(),
+            &api_caller,
+            &post_canister_id,
+            post_id,
+            slot_id,
+        )
+    })?;
+
+    let (recalculation,) =
+        ic_cdk::call::<_, (Result<SlotOutcomeRecalculation, RecalculateSlotOutcomeError>,)>(
+            post_canister_id,
+            "recalculate_slot_outcome",
+            (post_id, slot_id),
+        )
+        .await
+        .map_err(|(_, message)| {
+            format!(
+                "Failed to recalculate the slot on the post creator's canister: {}",
+                message
+            )
+        })?;
+    let recalculation = recalculation.map_err(|error| format!("{:?}", error))?;
+
+    CANISTER_DATA.with(|canister_data_ref_cell| {
+        if let Some(disputed_slot) = canister_data_ref_cell
+            .borrow_mut()
+            .slot_outcome_disputes
+            .get_mut(&(post_canister_id, post_id, slot_id))
+        {
+            disputed_slot.last_recalculation = Some(recalculation.clone());
+        }
+    });
+
+    Ok(recalculation)
+}
+
+fn validate_disputed_slot_recalculation_request(
+    canister_data: &CanisterData,
+    api_caller: &Principal,
+    post_canister_id: &Principal,
+    post_id: PostId,
+    slot_id: SlotId,
+) -> Result<(), String> {
+    if canister_data
+        .known_principal_ids
+        .get(&KnownPrincipalType::UserIdGlobalSuperAdmin)
+        != Some(api_caller)
+    {
+        return Err("Unauthorized caller".to_string());
+    }
+
+    if !canister_data
+        .slot_outcome_disputes
+        .contains_key(&(*post_canister_id, post_id, slot_id))
+    {
+        return Err("Slot has not been disputed".to_string());
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use shared_utils::canister_specific::user_index::types::dispute::DisputedSlot;
+    use test_utils::setup::test_constants::{
+        get_global_super_admin_principal_id, get_mock_user_alice_canister_id,
+        get_mock_user_alice_principal_id,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_validate_disputed_slot_recalculation_request() {
+        let mut canister_data = CanisterData::default();
+        canister_data.known_principal_ids.insert(
+            KnownPrincipalType::UserIdGlobalSuperAdmin,
+            get_global_super_admin_principal_id(),
+        );
+
+        assert_eq!(
+            validate_disputed_slot_recalculation_request(
+                &canister_data,
+                &get_mock_user_alice_principal_id(),
+                &get_mock_user_alice_canister_id(),
+                0,
+                1,
+            ),
+            Err("Unauthorized caller".to_string())
+        );
+        assert_eq!(
+            validate_disputed_slot_recalculation_request(
+                &canister_data,
+                &get_global_super_admin_principal_id(),
+                &get_mock_user_alice_canister_id(),
+                0,
+                1,
+            ),
+            Err("Slot has not been disputed".to_string())
+        );
+
+        canister_data.slot_outcome_disputes.insert(
+            (get_mock_user_alice_canister_id(), 0, 1),
+            DisputedSlot {
+                post_canister_id: get_mock_user_alice_canister_id(),
+                post_id: 0,
+                slot_id: 1,
+                disputes: vec![],
+                last_recalculation: None,
+            },
+        );
+
+        assert_eq!(
+            validate_disputed_slot_recalculation_request(
+                &canister_data,
+                &get_global_super_admin_principal_id(),
+                &get_mock_user_alice_canister_id(),
+                0,
+                1,
+            ),
+            Ok(())
+        );
+    }
+}
@@ -0,0 +1,132 @@
+use candid::Principal;
+use shared_utils::canister_specific::{
+    individual_user_template::types::dispute::SlotOutcomeDispute,
+    user_index::types::dispute::DisputedSlot,
+};
+
+use crate::{data_model::CanisterData, CANISTER_DATA};
+
+/// # Access Control
+/// Only individual user canisters indexed by this canister can pass on disputes, and only
+/// for their own posts
+#[ic_cdk::update]
+#[candid::candid_method(update)]
+fn receive_slot_outcome_dispute(dispute: SlotOutcomeDispute) {
+    let post_canister_id = ic_cdk::caller();
+
+    CANISTER_DATA.with(|canister_data_ref_cell| {
+        receive_slot_outcome_dispute_impl(
+            &mut canister_data_ref_cell.borrow_mut(),
+            &post_canister_id,
+            dispute,
+        );
+    });
+}
+
+fn receive_slot_outcome_dispute_impl(
+    canister_data: &mut CanisterData,
+    post_canister_id: &Principal,
+    dispute: SlotOutcomeDispute,
+) {
+    if !canister_data
+        .user_principal_id_to_canister_id_map
+        .values()
+        .any(|user_canister_id| user_canister_id == post_canister_id)
+    {
+        return;
+    }
+
+    let disputed_slot = canister_data
+        .slot_outcome_disputes
+        .entry((*post_canister_id, dispute.post_id, dispute.slot_id))
+        .or_insert_with(|| DisputedSlot {
+            post_canister_id: *post_canister_id,
+            post_id: dispute.post_id,
+            slot_id: dispute.slot_id,
+            disputes: vec![],
+            last_recalculation: None,
+        });
+
+    if disputed_slot
+        .disputes
+        .iter()
+        .any(|existing_dispute| existing_dispute.disputed_by == dispute.disputed_by)
+    {
+        return;
+    }
+
+    disputed_slot.disputes.push(dispute);
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::SystemTime;
+
+    use test_utils::setup::test_constants::{
+        get_mock_user_alice_canister_id, get_mock_user_alice_principal_id,
+        get_mock_user_bob_canister_id, get_mock_user_bob_principal_id,
+        get_mock_user_charlie_principal_id,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_receive_slot_outcome_dispute_impl() {
+        let mut canister_data = CanisterData::default();
+        canister_data.user_principal_id_to_canister_id_map.insert(
+            get_mock_user_alice_principal_id(),
+            get_mock_user_alice_canister_id(),
+        );
+        let get_dispute = |disputed_by: Principal| SlotOutcomeDispute {
+            post_id: 0,
+            slot_id: 1,
+            disputed_by,
+            reason: "Wrong side won".into(),
+            disputed_at: SystemTime::now(),
+        };
+
+        receive_slot_outcome_dispute_impl(
+            &mut canister_data,
+            &get_mock_user_bob_canister_id(),
+            get_dispute(get_mock_user_charlie_principal_id()),
+        );
+        assert!(canister_data.slot_outcome_disputes.is_empty());
+
+        receive_slot_outcome_dispute_impl(
+            &mut canister_data,
+            &get_mock_user_alice_canister_id(),
+            get_dispute(get_mock_user_charlie_principal_id()),
+        );
+        receive_slot_outcome_dispute_impl(
+            &mut canister_data,
+            &get_mock_user_alice_canister_id(),
+            get_dispute(get_mock_user_bob_principal_id()),
+        );
+        // * repeated disputes from the same bettor are only kept once
+        receive_slot_outcome_dispute_impl(
+            &mut canister_data,
+            &get_mock_user_alice_canister_id(),
+            get_dispute(get_mock_user_bob_principal_id()),
+        );
+
+        assert_eq!(canister_data.slot_outcome_disputes.len(), 1);
+        let disputed_slot =
+            &canister_data.slot_outcome_disputes[&(get_mock_user_alice_canister_id(), 0, 1)];
+        assert_eq!(
+            disputed_slot.post_canister_id,
+            get_mock_user_alice_canister_id()
+        );
+        assert_eq!(
+            disputed_slot
+                .disputes
+                .iter()
+                .map(|dispute| dispute.disputed_by)
+                .collect::<Vec<_>>(),
+            vec![
+                get_mock_user_charlie_principal_id(),
+                get_mock_user_bob_principal_id()
+            ]
+        );
+        assert_eq!(disputed_slot.last_recalculation, None);
+    }
+}
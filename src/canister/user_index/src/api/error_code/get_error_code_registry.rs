@@ -0,0 +1,49 @@
+use shared_utils::{
+    common::types::error_code::{ErrorCodeRegistryEntry, HasErrorCode},
+    types::canister_specific::user_index::error_types::{
+        RegisterReferralCodeError, SetUniqueUsernameError, TournamentError, UserIndexShardError,
+    },
+};
+
+use crate::data_model::{
+    canister_upgrade::rolling_upgrade::RollingUpgradeError,
+    feature_usage::GetFeatureUsageFunnelError, metrics::GetUserIndexMetricsError,
+};
+
+/// Codes of every error this canister's API can return. Clients can fetch this once and key
+/// translations and retry policies on the codes.
+#[ic_cdk::query]
+#[candid::candid_method(query)]
+fn get_error_code_registry() -> Vec<ErrorCodeRegistryEntry> {
+    [
+        SetUniqueUsernameError::error_code_registry_entries(),
+        RegisterReferralCodeError::error_code_registry_entries(),
+        TournamentError::error_code_registry_entries(),
+        UserIndexShardError::error_code_registry_entries(),
+        GetUserIndexMetricsError::error_code_registry_entries(),
+        GetFeatureUsageFunnelError::error_code_registry_entries(),
+        RollingUpgradeError::error_code_registry_entries(),
+    ]
+    .concat()
+}
+
+#[cfg(test)]
+mod test {
+    use shared_utils::common::types::error_code::get_duplicate_error_codes;
+
+    use super::*;
+
+    #[test]
+    fn test_get_error_code_registry() {
+        let registry = get_error_code_registry();
+
+        assert!(get_duplicate_error_codes(&registry).is_empty());
+        assert_eq!(
+            registry
+                .iter()
+                .filter(|entry| entry.error_type == "TournamentError")
+                .count(),
+            10
+        );
+    }
+}
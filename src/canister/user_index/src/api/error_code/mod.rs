@@ -0,0 +1 @@
+pub mod get_error_code_registry;
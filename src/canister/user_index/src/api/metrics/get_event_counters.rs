@@ -0,0 +1,10 @@
+use shared_utils::common::types::event_counter::EventCounterSample;
+
+use crate::CANISTER_DATA;
+
+#[ic_cdk::query]
+#[candid::candid_method(query)]
+pub fn get_event_counters() -> Vec<EventCounterSample> {
+    CANISTER_DATA
+        .with(|canister_data_ref_cell| canister_data_ref_cell.borrow().event_counters.get_samples())
+}
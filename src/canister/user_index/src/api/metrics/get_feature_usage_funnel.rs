@@ -0,0 +1,24 @@
+use shared_utils::canister_specific::individual_user_template::types::feature_usage::DayNumber;
+
+use crate::{
+    data_model::feature_usage::{FeatureUsageFunnelForDay, GetFeatureUsageFunnelError},
+    CANISTER_DATA,
+};
+
+/// Share of active users who used each feature, per day, oldest first. Days are counted since
+/// the unix epoch in UTC, both bounds are included and at most 90 days can be asked for at
+/// once. A day only shows up once individual user canisters have pushed it, within an hour or
+/// so of it being over.
+#[ic_cdk::query]
+#[candid::candid_method(query)]
+fn get_feature_usage_funnel(
+    from_day: DayNumber,
+    to_day: DayNumber,
+) -> Result<Vec<FeatureUsageFunnelForDay>, GetFeatureUsageFunnelError> {
+    CANISTER_DATA.with(|canister_data_ref_cell| {
+        canister_data_ref_cell
+            .borrow()
+            .platform_feature_usage
+            .get_funnel(from_day, to_day)
+    })
+}
@@ -0,0 +1,26 @@
+use std::time::SystemTime;
+
+use crate::{
+    data_model::metrics::{
+        GetUserIndexMetricsError, MetricsDataPoint, MetricsResolution, UserIndexMetric,
+    },
+    CANISTER_DATA,
+};
+
+/// Data points for dashboards, oldest first. Minute data points go back a day, hour data
+/// points 30 days and day data points 2 years. At most 1500 buckets can be asked for at once.
+#[ic_cdk::query]
+#[candid::candid_method(query)]
+fn get_user_index_metrics(
+    metric: UserIndexMetric,
+    resolution: MetricsResolution,
+    from: SystemTime,
+    to: SystemTime,
+) -> Result<Vec<MetricsDataPoint>, GetUserIndexMetricsError> {
+    CANISTER_DATA.with(|canister_data_ref_cell| {
+        canister_data_ref_cell
+            .borrow()
+            .metrics_time_series
+            .get_data_points(metric, resolution, &from, &to)
+    })
+}
@@ -0,0 +1,5 @@
+pub mod get_event_counters;
+pub mod get_feature_usage_funnel;
+pub mod get_user_index_metrics;
+pub mod receive_feature_usage_from_individual_user_canister;
+pub mod record_user_index_metrics;
@@ -0,0 +1,89 @@
+use candid::Principal;
+use shared_utils::canister_specific::individual_user_template::types::feature_usage::FeatureUsageForDay;
+
+use crate::{data_model::CanisterData, CANISTER_DATA};
+
+/// # Access Control
+/// Only individual user canisters indexed by this canister can push their feature usage
+#[ic_cdk::update]
+#[candid::candid_method(update)]
+fn receive_feature_usage_from_individual_user_canister(
+    feature_usage_for_days: Vec<FeatureUsageForDay>,
+) {
+    let user_canister_id = ic_cdk::caller();
+
+    CANISTER_DATA.with(|canister_data_ref_cell| {
+        receive_feature_usage_from_individual_user_canister_impl(
+            &mut canister_data_ref_cell.borrow_mut(),
+            user_canister_id,
+            feature_usage_for_days,
+        );
+    });
+}
+
+fn receive_feature_usage_from_individual_user_canister_impl(
+    canister_data: &mut CanisterData,
+    user_canister_id: Principal,
+    feature_usage_for_days: Vec<FeatureUsageForDay>,
+) {
+    if !canister_data
+        .user_principal_id_to_canister_id_map
+        .values()
+        .any(|indexed_canister_id| *indexed_canister_id == user_canister_id)
+    {
+        return;
+    }
+
+    canister_data
+        .platform_feature_usage
+        .record_canister_usage(user_canister_id, feature_usage_for_days);
+}
+
+#[cfg(test)]
+mod test {
+    use shared_utils::canister_specific::individual_user_template::types::feature_usage::Feature;
+    use test_utils::setup::test_constants::{
+        get_mock_user_alice_canister_id, get_mock_user_alice_principal_id,
+        get_mock_user_bob_canister_id,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_receive_feature_usage_from_individual_user_canister_impl() {
+        let mut canister_data = CanisterData::default();
+        canister_data.user_principal_id_to_canister_id_map.insert(
+            get_mock_user_alice_principal_id(),
+            get_mock_user_alice_canister_id(),
+        );
+        let feature_usage_for_days = vec![FeatureUsageForDay {
+            day: 3,
+            usage_counts: vec![(Feature::BetPlaced, 2)],
+        }];
+
+        receive_feature_usage_from_individual_user_canister_impl(
+            &mut canister_data,
+            get_mock_user_bob_canister_id(),
+            feature_usage_for_days.clone(),
+        );
+        assert_eq!(
+            canister_data.platform_feature_usage.get_funnel(3, 3),
+            Ok(vec![])
+        );
+
+        receive_feature_usage_from_individual_user_canister_impl(
+            &mut canister_data,
+            get_mock_user_alice_canister_id(),
+            feature_usage_for_days,
+        );
+        let funnel = canister_data
+            .platform_feature_usage
+            .get_funnel(3, 3)
+            .unwrap();
+        assert_eq!(funnel.len(), 1);
+        assert_eq!(funnel[0].active_user_count, 1);
+        assert_eq!(funnel[0].steps[0].feature, Feature::BetPlaced);
+        assert_eq!(funnel[0].steps[0].usage_count, 2);
+        assert_eq!(funnel[0].steps[0].percentage_of_active_users, 100);
+    }
+}
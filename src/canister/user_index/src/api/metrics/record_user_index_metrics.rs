@@ -0,0 +1,125 @@
+use std::time::SystemTime;
+
+use shared_utils::common::{types::event_counter::EventCounterName, utils::system_time};
+
+use crate::{
+    data_model::{
+        metrics::{UserIndexMetric, ALL_USER_INDEX_METRICS, METRICS_SAMPLING_INTERVAL},
+        CanisterData,
+    },
+    CANISTER_DATA,
+};
+
+/// Samples every user index metric once a minute. Timers don't survive upgrades, so this
+/// is started again from `post_upgrade`.
+pub fn start_recording_user_index_metrics() {
+    ic_cdk_timers::set_timer_interval(METRICS_SAMPLING_INTERVAL, || {
+        let current_time = system_time::get_current_system_time_from_ic();
+        let cycle_balance = ic_cdk::api::canister_balance128();
+
+        CANISTER_DATA.with(|canister_data_ref_cell| {
+            record_user_index_metrics_impl(
+                &mut canister_data_ref_cell.borrow_mut(),
+                cycle_balance,
+                &current_time,
+            );
+        });
+    });
+}
+
+fn record_user_index_metrics_impl(
+    canister_data: &mut CanisterData,
+    cycle_balance: u128,
+    current_time: &SystemTime,
+) {
+    for metric in ALL_USER_INDEX_METRICS {
+        let value = match metric {
+            UserIndexMetric::UserCanisterCount => {
+                canister_data.user_principal_id_to_canister_id_map.len() as u64
+            }
+            UserIndexMetric::CanisterCreationFailureCount => {
+                canister_data.canister_creation_failures.values().sum()
+            }
+            UserIndexMetric::CycleBalance => cycle_balance.min(u64::MAX as u128) as u64,
+            UserIndexMetric::ShadowBannedUserCount => {
+                canister_data.shadow_banned_user_principal_ids.len() as u64
+            }
+            UserIndexMetric::UpgradeFailureCount => canister_data
+                .event_counters
+                .get_total(EventCounterName::UpgradeFailuresTotal),
+        };
+
+        canister_data
+            .metrics_time_series
+            .record(metric, value, current_time);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use test_utils::setup::test_constants::v2::{canister, user};
+
+    use crate::data_model::{
+        canister_creation::CanisterCreationFailureReason, metrics::MetricsResolution,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_record_user_index_metrics_impl() {
+        let mut canister_data = CanisterData::default();
+        let current_time = SystemTime::now();
+        canister_data
+            .user_principal_id_to_canister_id_map
+            .insert(user("signup-0"), canister("signup-0"));
+        canister_data
+            .user_principal_id_to_canister_id_map
+            .insert(user("signup-1"), canister("signup-1"));
+        canister_data
+            .shadow_banned_user_principal_ids
+            .insert(user("signup-1"));
+        canister_data
+            .canister_creation_failures
+            .insert(CanisterCreationFailureReason::Transient, 3);
+        canister_data
+            .canister_creation_failures
+            .insert(CanisterCreationFailureReason::OutOfCycles, 1);
+        canister_data
+            .event_counters
+            .increment(EventCounterName::UpgradeFailuresTotal, "");
+
+        record_user_index_metrics_impl(&mut canister_data, u128::MAX, &current_time);
+
+        let get_last_value = |metric: UserIndexMetric| {
+            canister_data
+                .metrics_time_series
+                .get_data_points(
+                    metric,
+                    MetricsResolution::Minute,
+                    &current_time,
+                    &current_time,
+                )
+                .unwrap()
+                .last()
+                .map(|data_point| data_point.last)
+        };
+
+        assert_eq!(get_last_value(UserIndexMetric::UserCanisterCount), Some(2));
+        assert_eq!(
+            get_last_value(UserIndexMetric::CanisterCreationFailureCount),
+            Some(4)
+        );
+        assert_eq!(
+            get_last_value(UserIndexMetric::CycleBalance),
+            Some(u64::MAX)
+        );
+        assert_eq!(
+            get_last_value(UserIndexMetric::ShadowBannedUserCount),
+            Some(1)
+        );
+        assert_eq!(
+            get_last_value(UserIndexMetric::UpgradeFailureCount),
+            Some(1)
+        );
+    }
+}
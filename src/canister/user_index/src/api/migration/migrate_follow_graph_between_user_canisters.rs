@@ -0,0 +1,162 @@
+use candid::Principal;
+use shared_utils::{
+    canister_specific::individual_user_template::types::{
+        error::FollowGraphMigrationError,
+        follow::{FollowListExportChunk, FollowListImportSummary, FollowListType},
+    },
+    common::types::known_principal::KnownPrincipalType,
+};
+
+use crate::{data_model::CanisterData, CANISTER_DATA};
+
+/// Copies the follower and following lists of a user's canister onto the canister they are
+/// being migrated to, a chunk at a time. The target skips entries it already has, so a
+/// migration that failed part way can simply be run again.
+///
+/// # Access Control
+/// Only the global super admin can migrate the follow graph
+#[ic_cdk::update]
+#[candid::candid_method(update)]
+async fn migrate_follow_graph_between_user_canisters(
+    source_canister_id: Principal,
+    target_canister_id: Principal,
+) -> Result<FollowListImportSummary, String> {
+    let api_caller = ic_cdk::caller();
+
+    CANISTER_DATA.with(|canister_data_ref_cell| {
+        validate_follow_graph_migration(
+            &canister_data_ref_cell.borrow(),
+            &api_caller,
+            &source_canister_id,
+            &target_canister_id,
+        )
+    })?;
+
+    let mut summary = FollowListImportSummary::default();
+
+    for follow_list_type in [FollowListType::Follower, FollowListType::Following] {
+        let mut next_from_inclusive_index = Some(0);
+
+        while let Some(from_inclusive_index) = next_from_inclusive_index {
+            let (export_chunk,) =
+                ic_cdk::call::<_, (Result<FollowListExportChunk, FollowGraphMigrationError>,)>(
+                    source_canister_id,
+                    "get_follow_list_export_chunk",
+                    (follow_list_type, from_inclusive_index),
+                )
+                .await
+                .map_err(|(_, message)| format!("Failed to export follow list: {}", message))?;
+            let export_chunk = export_chunk
+                .map_err(|error| format!("Failed to export follow list: {:?}", error))?;
+
+            if !export_chunk.entries.is_empty() {
+                let (chunk_summary,) = ic_cdk::call::<
+                    _,
+                    (Result<FollowListImportSummary, FollowGraphMigrationError>,),
+                >(
+                    target_canister_id,
+                    "receive_follow_list_import_chunk",
+                    (
+                        follow_list_type,
+                        export_chunk
+                            .entries
+                            .into_iter()
+                            .map(|(_, follow_entry_detail)| follow_entry_detail)
+                            .collect::<Vec<_>>(),
+                    ),
+                )
+                .await
+                .map_err(|(_, message)| format!("Failed to import follow list: {}", message))?;
+                summary += chunk_summary
+                    .map_err(|error| format!("Failed to import follow list: {:?}", error))?;
+            }
+
+            next_from_inclusive_index = export_chunk.next_from_inclusive_index;
+        }
+    }
+
+    Ok(summary)
+}
+
+fn validate_follow_graph_migration(
+    canister_data: &CanisterData,
+    api_caller: &Principal,
+    source_canister_id: &Principal,
+    target_canister_id: &Principal,
+) -> Result<(), String> {
+    if canister_data
+        .known_principal_ids
+        .get(&KnownPrincipalType::UserIdGlobalSuperAdmin)
+        != Some(api_caller)
+    {
+        return Err("Unauthorized caller".to_string());
+    }
+
+    if source_canister_id == target_canister_id {
+        return Err("Source and target canisters are the same".to_string());
+    }
+
+    if !canister_data
+        .user_principal_id_to_canister_id_map
+        .values()
+        .any(|user_canister_id| user_canister_id == source_canister_id)
+    {
+        return Err("Source canister is not indexed by this user index".to_string());
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use test_utils::setup::test_constants::{
+        get_global_super_admin_principal_id, get_mock_user_alice_canister_id,
+        get_mock_user_alice_principal_id, get_mock_user_bob_canister_id,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_validate_follow_graph_migration() {
+        let mut canister_data = CanisterData::default();
+        canister_data.known_principal_ids.insert(
+            KnownPrincipalType::UserIdGlobalSuperAdmin,
+            get_global_super_admin_principal_id(),
+        );
+        canister_data.user_principal_id_to_canister_id_map.insert(
+            get_mock_user_alice_principal_id(),
+            get_mock_user_alice_canister_id(),
+        );
+
+        assert!(validate_follow_graph_migration(
+            &canister_data,
+            &get_mock_user_alice_principal_id(),
+            &get_mock_user_alice_canister_id(),
+            &get_mock_user_bob_canister_id(),
+        )
+        .is_err());
+        assert!(validate_follow_graph_migration(
+            &canister_data,
+            &get_global_super_admin_principal_id(),
+            &get_mock_user_alice_canister_id(),
+            &get_mock_user_alice_canister_id(),
+        )
+        .is_err());
+        assert!(validate_follow_graph_migration(
+            &canister_data,
+            &get_global_super_admin_principal_id(),
+            &get_mock_user_bob_canister_id(),
+            &get_mock_user_alice_canister_id(),
+        )
+        .is_err());
+        assert_eq!(
+            validate_follow_graph_migration(
+                &canister_data,
+                &get_global_super_admin_principal_id(),
+                &get_mock_user_alice_canister_id(),
+                &get_mock_user_bob_canister_id(),
+            ),
+            Ok(())
+        );
+    }
+}
@@ -0,0 +1 @@
+pub mod migrate_follow_graph_between_user_canisters;
@@ -1,6 +1,20 @@
 pub mod backup_and_restore;
+pub mod bet_trace;
+pub mod call_queue;
 pub mod canister_lifecycle;
+pub mod configuration;
 pub mod cycle_management;
+pub mod dispute;
+pub mod error_code;
+pub mod metrics;
+pub mod migration;
+pub mod moderation;
+pub mod replication;
+pub mod stale_canister_gc;
+pub mod token_balance_audit;
+pub mod token_ledger;
+pub mod tournament;
 pub mod upgrade_individual_user_template;
+pub mod user_index_shard;
 pub mod user_record;
 pub mod well_known_principal;
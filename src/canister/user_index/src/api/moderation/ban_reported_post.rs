@@ -0,0 +1,130 @@
+use candid::Principal;
+use shared_utils::common::types::{
+    app_primitive_type::PostId, known_principal::KnownPrincipalType,
+};
+
+use crate::{data_model::CanisterData, util::call_queue::queue_call, CANISTER_DATA};
+
+/// Upholds the reports raised against the post and takes it off the moderation queue. The
+/// post creator's canister marks the post as banned due to user reporting, and the post cache
+/// shard it shares its scores with stops serving it in feeds.
+///
+/// # Access Control
+/// Only the global super admin can ban reported posts
+#[ic_cdk::update]
+#[candid::candid_method(update)]
+fn ban_reported_post(post_canister_id: Principal, post_id: PostId) -> Result<(), String> {
+    let api_caller = ic_cdk::caller();
+
+    let post_cache_canister_id = CANISTER_DATA.with(|canister_data_ref_cell| {
+        ban_reported_post_impl(
+            &mut canister_data_ref_cell.borrow_mut(),
+            &api_caller,
+            &post_canister_id,
+            post_id,
+        )
+    })?;
+
+    queue_call(
+        post_canister_id,
+        "receive_post_ban_from_user_index",
+        (post_id,),
+    );
+
+    if let Some(post_cache_canister_id) = post_cache_canister_id {
+        queue_call(
+            post_cache_canister_id,
+            "receive_post_ban_from_user_index",
+            (post_canister_id, post_id),
+        );
+    }
+
+    Ok(())
+}
+
+/// Returns the post cache shard the post creator's canister shares its scores with
+fn ban_reported_post_impl(
+    canister_data: &mut CanisterData,
+    api_caller: &Principal,
+    post_canister_id: &Principal,
+    post_id: PostId,
+) -> Result<Option<Principal>, String> {
+    if canister_data
+        .known_principal_ids
+        .get(&KnownPrincipalType::UserIdGlobalSuperAdmin)
+        != Some(api_caller)
+    {
+        return Err("Unauthorized caller".to_string());
+    }
+
+    canister_data
+        .reported_posts
+        .remove(&(*post_canister_id, post_id))
+        .ok_or_else(|| "Post has not been reported".to_string())?;
+
+    Ok(canister_data
+        .known_principal_ids
+        .get_for_shard_key(&KnownPrincipalType::CanisterIdPostCache, post_canister_id)
+        .cloned())
+}
+
+#[cfg(test)]
+mod test {
+    use shared_utils::canister_specific::user_index::types::moderation::ReportedPost;
+    use test_utils::setup::test_constants::{
+        get_global_super_admin_principal_id, get_mock_canister_id_post_cache,
+        get_mock_user_alice_canister_id, get_mock_user_alice_principal_id,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_ban_reported_post_impl() {
+        let mut canister_data = CanisterData::default();
+        canister_data.known_principal_ids.insert(
+            KnownPrincipalType::UserIdGlobalSuperAdmin,
+            get_global_super_admin_principal_id(),
+        );
+        canister_data.known_principal_ids.insert(
+            KnownPrincipalType::CanisterIdPostCache,
+            get_mock_canister_id_post_cache(),
+        );
+        canister_data.reported_posts.insert(
+            (get_mock_user_alice_canister_id(), 0),
+            ReportedPost {
+                post_canister_id: get_mock_user_alice_canister_id(),
+                post_id: 0,
+                reports: vec![],
+            },
+        );
+
+        assert_eq!(
+            ban_reported_post_impl(
+                &mut canister_data,
+                &get_mock_user_alice_principal_id(),
+                &get_mock_user_alice_canister_id(),
+                0
+            ),
+            Err("Unauthorized caller".to_string())
+        );
+        assert_eq!(
+            ban_reported_post_impl(
+                &mut canister_data,
+                &get_global_super_admin_principal_id(),
+                &get_mock_user_alice_canister_id(),
+                1
+            ),
+            Err("Post has not been reported".to_string())
+        );
+        assert_eq!(
+            ban_reported_post_impl(
+                &mut canister_data,
+                &get_global_super_admin_principal_id(),
+                &get_mock_user_alice_canister_id(),
+                0
+            ),
+            Ok(Some(get_mock_canister_id_post_cache()))
+        );
+        assert!(canister_data.reported_posts.is_empty());
+    }
+}
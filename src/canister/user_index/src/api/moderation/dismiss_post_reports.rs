@@ -0,0 +1,107 @@
+use candid::Principal;
+use shared_utils::common::types::{
+    app_primitive_type::PostId, known_principal::KnownPrincipalType,
+};
+
+use crate::{data_model::CanisterData, util::call_queue::queue_call, CANISTER_DATA};
+
+/// Moderators found nothing wrong with the post. Takes it off the moderation queue and has
+/// the post creator's canister clear its reports, which reopens betting if it was paused.
+///
+/// # Access Control
+/// Only the global super admin can dismiss reports
+#[ic_cdk::update]
+#[candid::candid_method(update)]
+fn dismiss_post_reports(post_canister_id: Principal, post_id: PostId) -> Result<(), String> {
+    let api_caller = ic_cdk::caller();
+
+    CANISTER_DATA.with(|canister_data_ref_cell| {
+        dismiss_post_reports_impl(
+            &mut canister_data_ref_cell.borrow_mut(),
+            &api_caller,
+            &post_canister_id,
+            post_id,
+        )
+    })?;
+
+    queue_call(post_canister_id, "clear_post_reports", (post_id,));
+
+    Ok(())
+}
+
+fn dismiss_post_reports_impl(
+    canister_data: &mut CanisterData,
+    api_caller: &Principal,
+    post_canister_id: &Principal,
+    post_id: PostId,
+) -> Result<(), String> {
+    if canister_data
+        .known_principal_ids
+        .get(&KnownPrincipalType::UserIdGlobalSuperAdmin)
+        != Some(api_caller)
+    {
+        return Err("Unauthorized caller".to_string());
+    }
+
+    canister_data
+        .reported_posts
+        .remove(&(*post_canister_id, post_id))
+        .map(|_| ())
+        .ok_or_else(|| "Post has not been reported".to_string())
+}
+
+#[cfg(test)]
+mod test {
+    use shared_utils::canister_specific::user_index::types::moderation::ReportedPost;
+    use test_utils::setup::test_constants::{
+        get_global_super_admin_principal_id, get_mock_user_alice_canister_id,
+        get_mock_user_alice_principal_id,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_dismiss_post_reports_impl() {
+        let mut canister_data = CanisterData::default();
+        canister_data.known_principal_ids.insert(
+            KnownPrincipalType::UserIdGlobalSuperAdmin,
+            get_global_super_admin_principal_id(),
+        );
+        canister_data.reported_posts.insert(
+            (get_mock_user_alice_canister_id(), 0),
+            ReportedPost {
+                post_canister_id: get_mock_user_alice_canister_id(),
+                post_id: 0,
+                reports: vec![],
+            },
+        );
+
+        assert_eq!(
+            dismiss_post_reports_impl(
+                &mut canister_data,
+                &get_mock_user_alice_principal_id(),
+                &get_mock_user_alice_canister_id(),
+                0
+            ),
+            Err("Unauthorized caller".to_string())
+        );
+        assert_eq!(
+            dismiss_post_reports_impl(
+                &mut canister_data,
+                &get_global_super_admin_principal_id(),
+                &get_mock_user_alice_canister_id(),
+                0
+            ),
+            Ok(())
+        );
+        assert_eq!(
+            dismiss_post_reports_impl(
+                &mut canister_data,
+                &get_global_super_admin_principal_id(),
+                &get_mock_user_alice_canister_id(),
+                0
+            ),
+            Err("Post has not been reported".to_string())
+        );
+    }
+}
@@ -0,0 +1,97 @@
+use candid::Principal;
+use shared_utils::{
+    canister_specific::user_index::types::moderation::ReportedPost,
+    common::types::known_principal::KnownPrincipalType,
+    pagination::{self, Page},
+};
+
+use crate::{data_model::CanisterData, CANISTER_DATA};
+
+/// The moderation queue, by post creator canister and post
+///
+/// # Access Control
+/// Only the global super admin can review reported posts
+#[ic_cdk::query]
+#[candid::candid_method(query)]
+fn get_reported_posts(
+    from_inclusive_index: u64,
+    page_size: u64,
+) -> Result<Page<ReportedPost>, String> {
+    let api_caller = ic_cdk::caller();
+
+    CANISTER_DATA.with(|canister_data_ref_cell| {
+        get_reported_posts_impl(
+            &canister_data_ref_cell.borrow(),
+            &api_caller,
+            from_inclusive_index,
+            page_size,
+        )
+    })
+}
+
+fn get_reported_posts_impl(
+    canister_data: &CanisterData,
+    api_caller: &Principal,
+    from_inclusive_index: u64,
+    page_size: u64,
+) -> Result<Page<ReportedPost>, String> {
+    if canister_data
+        .known_principal_ids
+        .get(&KnownPrincipalType::UserIdGlobalSuperAdmin)
+        != Some(api_caller)
+    {
+        return Err("Unauthorized caller".to_string());
+    }
+
+    Ok(pagination::get_page(
+        canister_data.reported_posts.values().cloned(),
+        from_inclusive_index,
+        page_size,
+    ))
+}
+
+#[cfg(test)]
+mod test {
+    use test_utils::setup::test_constants::{
+        get_global_super_admin_principal_id, get_mock_user_alice_canister_id,
+        get_mock_user_alice_principal_id,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_get_reported_posts_impl() {
+        let mut canister_data = CanisterData::default();
+        canister_data.known_principal_ids.insert(
+            KnownPrincipalType::UserIdGlobalSuperAdmin,
+            get_global_super_admin_principal_id(),
+        );
+        let reported_post = ReportedPost {
+            post_canister_id: get_mock_user_alice_canister_id(),
+            post_id: 0,
+            reports: vec![],
+        };
+        canister_data.reported_posts.insert(
+            (get_mock_user_alice_canister_id(), 0),
+            reported_post.clone(),
+        );
+
+        assert_eq!(
+            get_reported_posts_impl(&canister_data, &get_mock_user_alice_principal_id(), 0, 10),
+            Err("Unauthorized caller".to_string())
+        );
+        assert_eq!(
+            get_reported_posts_impl(
+                &canister_data,
+                &get_global_super_admin_principal_id(),
+                0,
+                10
+            ),
+            Ok(Page {
+                items: vec![reported_post],
+                total_count: 1,
+                next_from_inclusive_index: None,
+            })
+        );
+    }
+}
@@ -0,0 +1,4 @@
+pub mod ban_reported_post;
+pub mod dismiss_post_reports;
+pub mod get_reported_posts;
+pub mod receive_post_report_from_individual_user_canister;
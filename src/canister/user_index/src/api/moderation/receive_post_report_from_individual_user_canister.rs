@@ -0,0 +1,129 @@
+use candid::Principal;
+use shared_utils::canister_specific::{
+    individual_user_template::types::post::report::PostReport,
+    user_index::types::moderation::ReportedPost,
+};
+
+use crate::{data_model::CanisterData, CANISTER_DATA};
+
+/// Adds the report to the moderation queue
+///
+/// # Access Control
+/// Only individual user canisters indexed by this canister can pass on reports, and only
+/// for their own posts
+#[ic_cdk::update]
+#[candid::candid_method(update)]
+fn receive_post_report_from_individual_user_canister(report: PostReport) {
+    let post_canister_id = ic_cdk::caller();
+
+    CANISTER_DATA.with(|canister_data_ref_cell| {
+        receive_post_report_from_individual_user_canister_impl(
+            &mut canister_data_ref_cell.borrow_mut(),
+            &post_canister_id,
+            report,
+        );
+    });
+}
+
+fn receive_post_report_from_individual_user_canister_impl(
+    canister_data: &mut CanisterData,
+    post_canister_id: &Principal,
+    report: PostReport,
+) {
+    if !canister_data
+        .user_principal_id_to_canister_id_map
+        .values()
+        .any(|user_canister_id| user_canister_id == post_canister_id)
+    {
+        return;
+    }
+
+    let reported_post = canister_data
+        .reported_posts
+        .entry((*post_canister_id, report.post_id))
+        .or_insert_with(|| ReportedPost {
+            post_canister_id: *post_canister_id,
+            post_id: report.post_id,
+            reports: vec![],
+        });
+
+    if reported_post
+        .reports
+        .iter()
+        .any(|existing_report| existing_report.reported_by == report.reported_by)
+    {
+        return;
+    }
+
+    reported_post.reports.push(report);
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::SystemTime;
+
+    use test_utils::setup::test_constants::{
+        get_mock_user_alice_canister_id, get_mock_user_alice_principal_id,
+        get_mock_user_bob_canister_id, get_mock_user_bob_principal_id,
+        get_mock_user_charlie_principal_id,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_receive_post_report_from_individual_user_canister_impl() {
+        let mut canister_data = CanisterData::default();
+        canister_data.user_principal_id_to_canister_id_map.insert(
+            get_mock_user_alice_principal_id(),
+            get_mock_user_alice_canister_id(),
+        );
+        let get_report = |reported_by: Principal| PostReport {
+            post_id: 0,
+            reported_by,
+            reason: Some("Spam".into()),
+            reported_at: SystemTime::now(),
+        };
+
+        receive_post_report_from_individual_user_canister_impl(
+            &mut canister_data,
+            &get_mock_user_bob_canister_id(),
+            get_report(get_mock_user_charlie_principal_id()),
+        );
+        assert!(canister_data.reported_posts.is_empty());
+
+        receive_post_report_from_individual_user_canister_impl(
+            &mut canister_data,
+            &get_mock_user_alice_canister_id(),
+            get_report(get_mock_user_charlie_principal_id()),
+        );
+        receive_post_report_from_individual_user_canister_impl(
+            &mut canister_data,
+            &get_mock_user_alice_canister_id(),
+            get_report(get_mock_user_bob_principal_id()),
+        );
+        // * repeated reports from the same user are only kept once
+        receive_post_report_from_individual_user_canister_impl(
+            &mut canister_data,
+            &get_mock_user_alice_canister_id(),
+            get_report(get_mock_user_bob_principal_id()),
+        );
+
+        assert_eq!(canister_data.reported_posts.len(), 1);
+        let reported_post = &canister_data.reported_posts[&(get_mock_user_alice_canister_id(), 0)];
+        assert_eq!(
+            reported_post.post_canister_id,
+            get_mock_user_alice_canister_id()
+        );
+        assert_eq!(
+            reported_post
+                .reports
+                .iter()
+                .map(|report| report.reported_by)
+                .collect::<Vec<_>>(),
+            vec![
+                get_mock_user_charlie_principal_id(),
+                get_mock_user_bob_principal_id()
+            ]
+        );
+    }
+}
@@ -0,0 +1,2 @@
+pub mod promote_standby_user_canister;
+pub mod provision_standby_user_canister;
@@ -0,0 +1,157 @@
+use candid::Principal;
+use shared_utils::{
+    canister_specific::individual_user_template::types::replication::ReplicationUpdate,
+    common::types::known_principal::KnownPrincipalType,
+};
+
+use crate::{data_model::CanisterData, util::call_queue::queue_call, CANISTER_DATA};
+
+use super::provision_standby_user_canister::update_replication;
+
+/// Promotes a creator's standby canister to be their canister, for when the one it stands by
+/// for can't be recovered. The promotion starts a new epoch. The canister promoted from is told
+/// to fence itself off, and if it can't be reached it does so the next time it sends the
+/// standby a delta, so the two never both take writes.
+///
+/// # Access Control
+/// Only the global super admin can promote standbys
+#[ic_cdk::update]
+#[candid::candid_method(update)]
+async fn promote_standby_user_canister(user_principal_id: Principal) -> Result<Principal, String> {
+    let api_caller = ic_cdk::caller();
+
+    let (primary_canister_id, standby_canister_id, epoch) =
+        CANISTER_DATA.with(|canister_data_ref_cell| {
+            get_standby_canister_to_promote(
+                &canister_data_ref_cell.borrow(),
+                &api_caller,
+                &user_principal_id,
+            )
+        })?;
+
+    update_replication(
+        standby_canister_id,
+        ReplicationUpdate::PromoteToPrimary,
+        epoch,
+    )
+    .await?;
+
+    CANISTER_DATA.with(|canister_data_ref_cell| {
+        record_promoted_standby(
+            &mut canister_data_ref_cell.borrow_mut(),
+            &user_principal_id,
+            standby_canister_id,
+            epoch,
+        )
+    });
+
+    queue_call(
+        primary_canister_id,
+        "update_replication",
+        (ReplicationUpdate::Fence, epoch),
+    );
+
+    Ok(standby_canister_id)
+}
+
+/// The user's canister, its standby and the epoch the standby is promoted into
+fn get_standby_canister_to_promote(
+    canister_data: &CanisterData,
+    api_caller: &Principal,
+    user_principal_id: &Principal,
+) -> Result<(Principal, Principal, u64), String> {
+    if canister_data
+        .known_principal_ids
+        .get(&KnownPrincipalType::UserIdGlobalSuperAdmin)
+        != Some(api_caller)
+    {
+        return Err("Unauthorized caller".to_string());
+    }
+
+    let user_canister_id = canister_data
+        .user_principal_id_to_canister_id_map
+        .get(user_principal_id)
+        .copied()
+        .ok_or_else(|| "User is not indexed by this user index".to_string())?;
+    let standby_canister_id = canister_data
+        .get_standby_canister_id(user_principal_id)
+        .ok_or_else(|| "User's canister has no standby".to_string())?;
+    let epoch = canister_data.user_canister_replications[user_principal_id].epoch;
+
+    Ok((user_canister_id, standby_canister_id, epoch + 1))
+}
+
+fn record_promoted_standby(
+    canister_data: &mut CanisterData,
+    user_principal_id: &Principal,
+    standby_canister_id: Principal,
+    epoch: u64,
+) {
+    canister_data
+        .user_principal_id_to_canister_id_map
+        .insert(*user_principal_id, standby_canister_id);
+
+    let replication = canister_data
+        .user_canister_replications
+        .entry(*user_principal_id)
+        .or_default();
+    replication.standby_canister_id = None;
+    replication.epoch = epoch;
+}
+
+#[cfg(test)]
+mod test {
+    use test_utils::setup::test_constants::{
+        get_global_super_admin_principal_id, get_mock_user_alice_canister_id,
+        get_mock_user_alice_principal_id, get_mock_user_bob_canister_id,
+    };
+
+    use crate::data_model::replication::UserCanisterReplication;
+
+    use super::*;
+
+    #[test]
+    fn test_standby_is_promoted_into_a_new_epoch() {
+        let mut canister_data = CanisterData::default();
+        let super_admin = get_global_super_admin_principal_id();
+        let alice = get_mock_user_alice_principal_id();
+        let standby_canister_id = get_mock_user_bob_canister_id();
+        canister_data
+            .known_principal_ids
+            .insert(KnownPrincipalType::UserIdGlobalSuperAdmin, super_admin);
+        canister_data
+            .user_principal_id_to_canister_id_map
+            .insert(alice, get_mock_user_alice_canister_id());
+
+        assert!(get_standby_canister_to_promote(&canister_data, &super_admin, &alice).is_err());
+
+        canister_data.user_canister_replications.insert(
+            alice,
+            UserCanisterReplication {
+                standby_canister_id: Some(standby_canister_id),
+                epoch: 1,
+            },
+        );
+        assert!(get_standby_canister_to_promote(&canister_data, &alice, &alice).is_err());
+        assert_eq!(
+            get_standby_canister_to_promote(&canister_data, &super_admin, &alice),
+            Ok((get_mock_user_alice_canister_id(), standby_canister_id, 2))
+        );
+
+        record_promoted_standby(&mut canister_data, &alice, standby_canister_id, 2);
+        assert_eq!(
+            canister_data
+                .user_principal_id_to_canister_id_map
+                .get(&alice),
+            Some(&standby_canister_id)
+        );
+        assert_eq!(
+            canister_data.user_canister_replications.get(&alice),
+            Some(&UserCanisterReplication {
+                standby_canister_id: None,
+                epoch: 2,
+            })
+        );
+        assert!(get_standby_canister_to_promote(&canister_data, &super_admin, &alice).is_err());
+    }
+}
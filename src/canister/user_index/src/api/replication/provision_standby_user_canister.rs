@@ -0,0 +1,197 @@
+use candid::Principal;
+use shared_utils::{
+    canister_specific::individual_user_template::types::{
+        error::ReplicationError, replication::ReplicationUpdate,
+    },
+    common::types::known_principal::KnownPrincipalType,
+};
+
+use crate::{
+    data_model::{replication::UserCanisterReplication, CanisterData},
+    util::canister_management::create_users_canister,
+    CANISTER_DATA,
+};
+
+/// Provisions a standby canister for a high value creator and has their canister stream its
+/// state to it. The standby stays read only until it is promoted.
+///
+/// # Access Control
+/// Only the global super admin can provision standbys
+#[ic_cdk::update]
+#[candid::candid_method(update)]
+async fn provision_standby_user_canister(
+    user_principal_id: Principal,
+) -> Result<Principal, String> {
+    let api_caller = ic_cdk::caller();
+
+    let (primary_canister_id, epoch) = CANISTER_DATA.with(|canister_data_ref_cell| {
+        get_user_canister_to_provision_standby_for(
+            &canister_data_ref_cell.borrow(),
+            &api_caller,
+            &user_principal_id,
+        )
+    })?;
+
+    let standby_canister_id =
+        create_users_canister(user_principal_id)
+            .await
+            .map_err(|failure_reason| {
+                format!(
+                    "Failed to create a standby canister for this user: {:?}",
+                    failure_reason
+                )
+            })?;
+
+    update_replication(
+        standby_canister_id,
+        ReplicationUpdate::BecomeStandbyOf {
+            primary_canister_id,
+        },
+        epoch,
+    )
+    .await?;
+    update_replication(
+        primary_canister_id,
+        ReplicationUpdate::ReplicateTo {
+            standby_canister_id,
+        },
+        epoch,
+    )
+    .await?;
+
+    CANISTER_DATA.with(|canister_data_ref_cell| {
+        canister_data_ref_cell
+            .borrow_mut()
+            .user_canister_replications
+            .insert(
+                user_principal_id,
+                UserCanisterReplication {
+                    standby_canister_id: Some(standby_canister_id),
+                    epoch,
+                },
+            );
+    });
+
+    Ok(standby_canister_id)
+}
+
+pub(crate) async fn update_replication(
+    user_canister_id: Principal,
+    update: ReplicationUpdate,
+    epoch: u64,
+) -> Result<(), String> {
+    ic_cdk::call::<_, (Result<(), ReplicationError>,)>(
+        user_canister_id,
+        "update_replication",
+        (update, epoch),
+    )
+    .await
+    .map_err(|(_, message)| message)
+    .and_then(|(response,)| response.map_err(|err| format!("{:?}", err)))
+    .map_err(|message| {
+        format!(
+            "Failed to update replication on canister {}: {}",
+            user_canister_id.to_text(),
+            message
+        )
+    })
+}
+
+/// The user's canister and the epoch it is on
+fn get_user_canister_to_provision_standby_for(
+    canister_data: &CanisterData,
+    api_caller: &Principal,
+    user_principal_id: &Principal,
+) -> Result<(Principal, u64), String> {
+    if canister_data
+        .known_principal_ids
+        .get(&KnownPrincipalType::UserIdGlobalSuperAdmin)
+        != Some(api_caller)
+    {
+        return Err("Unauthorized caller".to_string());
+    }
+
+    let user_canister_id = canister_data
+        .user_principal_id_to_canister_id_map
+        .get(user_principal_id)
+        .copied()
+        .ok_or_else(|| "User is not indexed by this user index".to_string())?;
+
+    if canister_data
+        .get_standby_canister_id(user_principal_id)
+        .is_some()
+    {
+        return Err("User's canister already has a standby".to_string());
+    }
+
+    let epoch = canister_data
+        .user_canister_replications
+        .get(user_principal_id)
+        .map(|replication| replication.epoch)
+        .unwrap_or_default();
+
+    Ok((user_canister_id, epoch))
+}
+
+#[cfg(test)]
+mod test {
+    use test_utils::setup::test_constants::{
+        get_global_super_admin_principal_id, get_mock_user_alice_canister_id,
+        get_mock_user_alice_principal_id, get_mock_user_bob_canister_id,
+        get_mock_user_bob_principal_id,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_get_user_canister_to_provision_standby_for() {
+        let mut canister_data = CanisterData::default();
+        let super_admin = get_global_super_admin_principal_id();
+        let alice = get_mock_user_alice_principal_id();
+        canister_data
+            .known_principal_ids
+            .insert(KnownPrincipalType::UserIdGlobalSuperAdmin, super_admin);
+        canister_data
+            .user_principal_id_to_canister_id_map
+            .insert(alice, get_mock_user_alice_canister_id());
+
+        assert!(
+            get_user_canister_to_provision_standby_for(&canister_data, &alice, &alice).is_err()
+        );
+        assert!(get_user_canister_to_provision_standby_for(
+            &canister_data,
+            &super_admin,
+            &get_mock_user_bob_principal_id(),
+        )
+        .is_err());
+        assert_eq!(
+            get_user_canister_to_provision_standby_for(&canister_data, &super_admin, &alice),
+            Ok((get_mock_user_alice_canister_id(), 0))
+        );
+
+        // * a replacement standby carries on from the epoch of the last promotion
+        canister_data.user_canister_replications.insert(
+            alice,
+            UserCanisterReplication {
+                standby_canister_id: None,
+                epoch: 2,
+            },
+        );
+        assert_eq!(
+            get_user_canister_to_provision_standby_for(&canister_data, &super_admin, &alice),
+            Ok((get_mock_user_alice_canister_id(), 2))
+        );
+
+        canister_data.user_canister_replications.insert(
+            alice,
+            UserCanisterReplication {
+                standby_canister_id: Some(get_mock_user_bob_canister_id()),
+                epoch: 2,
+            },
+        );
+        assert!(
+            get_user_canister_to_provision_standby_for(&canister_data, &super_admin, &alice)
+                .is_err()
+        );
+    }
+}
@@ -0,0 +1,233 @@
+use std::time::SystemTime;
+
+use candid::Principal;
+use ic_cdk::api::management_canister::main::{self, CanisterIdRecord};
+use shared_utils::{
+    canister_specific::{
+        individual_user_template::types::onboarding::OnboardingStatus,
+        user_index::types::stale_canister_gc::StaleCanisterGcReport,
+    },
+    common::{types::known_principal::KnownPrincipalType, utils::system_time},
+};
+
+use crate::{
+    data_model::{
+        stale_canister_gc::{FlaggedCanister, StaleCanisterGcStep},
+        CanisterData,
+    },
+    util::call_queue::queue_call,
+    CANISTER_DATA,
+};
+
+/// Looks for canisters whose owners never posted or bet and have been inactive for
+/// `STALE_CANISTER_INACTIVITY_PERIOD`. Newly found ones are flagged and their owners told when
+/// the canister is going to be collected. Once the grace period is over, a canister that is
+/// still stale is snapshotted to the data backup canister, uninstalled and put in the
+/// provisioning pool. Owners who opted out are skipped.
+///
+/// # Access Control
+/// Only the global super admin can run the collection
+#[ic_cdk::update]
+#[candid::candid_method(update)]
+async fn collect_stale_individual_user_canisters() -> Result<StaleCanisterGcReport, String> {
+    let api_caller = ic_cdk::caller();
+
+    let canisters_to_check = CANISTER_DATA.with(|canister_data_ref_cell| {
+        get_canisters_to_check(&canister_data_ref_cell.borrow(), &api_caller)
+    })?;
+
+    let mut report = StaleCanisterGcReport::default();
+
+    for (user_principal_id, user_canister_id) in canisters_to_check {
+        let onboarding_status = match ic_cdk::call::<_, (OnboardingStatus,)>(
+            user_canister_id,
+            "get_onboarding_status",
+            (),
+        )
+        .await
+        {
+            Ok((onboarding_status,)) => onboarding_status,
+            Err(_) => {
+                report.failed_canister_count += 1;
+                continue;
+            }
+        };
+
+        let current_time = system_time::get_current_system_time_from_ic();
+        let next_step = CANISTER_DATA.with(|canister_data_ref_cell| {
+            canister_data_ref_cell
+                .borrow()
+                .stale_canister_gc
+                .get_next_step(&user_canister_id, &onboarding_status, &current_time)
+        });
+
+        match next_step {
+            StaleCanisterGcStep::Keep => {}
+            StaleCanisterGcStep::Flag {
+                scheduled_collection_at,
+            } => {
+                CANISTER_DATA.with(|canister_data_ref_cell| {
+                    canister_data_ref_cell
+                        .borrow_mut()
+                        .stale_canister_gc
+                        .flagged_canisters
+                        .insert(
+                            user_canister_id,
+                            FlaggedCanister {
+                                user_principal_id,
+                                scheduled_collection_at,
+                            },
+                        );
+                });
+                queue_call(
+                    user_canister_id,
+                    "receive_scheduled_collection_notice",
+                    (Some(scheduled_collection_at),),
+                );
+                report.flagged_canister_count += 1;
+            }
+            StaleCanisterGcStep::Unflag => {
+                CANISTER_DATA.with(|canister_data_ref_cell| {
+                    canister_data_ref_cell
+                        .borrow_mut()
+                        .stale_canister_gc
+                        .flagged_canisters
+                        .remove(&user_canister_id);
+                });
+                queue_call(
+                    user_canister_id,
+                    "receive_scheduled_collection_notice",
+                    (None::<SystemTime>,),
+                );
+                report.unflagged_canister_count += 1;
+            }
+            StaleCanisterGcStep::Collect => {
+                match collect_canister(user_principal_id, user_canister_id).await {
+                    Ok(true) => report.collected_canister_count += 1,
+                    Ok(false) => {}
+                    Err(message) => {
+                        ic_cdk::print(format!(
+                            "Failed to collect canister {}: {}",
+                            user_canister_id, message
+                        ));
+                        report.failed_canister_count += 1;
+                    }
+                }
+            }
+        }
+    }
+
+    report.provisioning_pool_size = CANISTER_DATA.with(|canister_data_ref_cell| {
+        canister_data_ref_cell.borrow().provisioning_pool.len() as u64
+    });
+
+    Ok(report)
+}
+
+fn get_canisters_to_check(
+    canister_data: &CanisterData,
+    api_caller: &Principal,
+) -> Result<Vec<(Principal, Principal)>, String> {
+    if canister_data
+        .known_principal_ids
+        .get(&KnownPrincipalType::UserIdGlobalSuperAdmin)
+        != Some(api_caller)
+    {
+        return Err("Unauthorized caller".to_string());
+    }
+
+    Ok(canister_data
+        .user_principal_id_to_canister_id_map
+        .iter()
+        .filter(|(user_principal_id, _)| {
+            !canister_data
+                .stale_canister_gc
+                .opted_out_user_principal_ids
+                .contains(user_principal_id)
+        })
+        .map(|(user_principal_id, user_canister_id)| (*user_principal_id, *user_canister_id))
+        .collect())
+}
+
+/// Returns false if the owner opted out while the canister was being snapshotted, in which
+/// case it is left installed
+async fn collect_canister(
+    user_principal_id: Principal,
+    user_canister_id: Principal,
+) -> Result<bool, String> {
+    ic_cdk::call::<_, ()>(
+        user_canister_id,
+        "backup_data_to_backup_canister",
+        (user_principal_id, user_canister_id),
+    )
+    .await
+    .map_err(|(_, message)| format!("Failed to snapshot the canister: {}", message))?;
+
+    let is_still_flagged = CANISTER_DATA.with(|canister_data_ref_cell| {
+        canister_data_ref_cell
+            .borrow()
+            .stale_canister_gc
+            .flagged_canisters
+            .contains_key(&user_canister_id)
+    });
+    if !is_still_flagged {
+        return Ok(false);
+    }
+
+    main::uninstall_code(CanisterIdRecord {
+        canister_id: user_canister_id,
+    })
+    .await
+    .map_err(|(_, message)| format!("Failed to uninstall the canister: {}", message))?;
+
+    CANISTER_DATA.with(|canister_data_ref_cell| {
+        canister_data_ref_cell
+            .borrow_mut()
+            .release_canister_to_provisioning_pool(&user_principal_id, user_canister_id);
+    });
+
+    Ok(true)
+}
+
+#[cfg(test)]
+mod test {
+    use test_utils::setup::test_constants::{
+        get_global_super_admin_principal_id, get_mock_user_alice_canister_id,
+        get_mock_user_alice_principal_id, get_mock_user_bob_canister_id,
+        get_mock_user_bob_principal_id,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_get_canisters_to_check() {
+        let mut canister_data = CanisterData::default();
+        canister_data.known_principal_ids.insert(
+            KnownPrincipalType::UserIdGlobalSuperAdmin,
+            get_global_super_admin_principal_id(),
+        );
+        canister_data.user_principal_id_to_canister_id_map.insert(
+            get_mock_user_alice_principal_id(),
+            get_mock_user_alice_canister_id(),
+        );
+        canister_data.user_principal_id_to_canister_id_map.insert(
+            get_mock_user_bob_principal_id(),
+            get_mock_user_bob_canister_id(),
+        );
+        canister_data
+            .stale_canister_gc
+            .opted_out_user_principal_ids
+            .insert(get_mock_user_bob_principal_id());
+
+        assert!(
+            get_canisters_to_check(&canister_data, &get_mock_user_alice_principal_id()).is_err()
+        );
+        assert_eq!(
+            get_canisters_to_check(&canister_data, &get_global_super_admin_principal_id()),
+            Ok(vec![(
+                get_mock_user_alice_principal_id(),
+                get_mock_user_alice_canister_id()
+            )])
+        );
+    }
+}
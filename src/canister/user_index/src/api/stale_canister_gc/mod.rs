@@ -0,0 +1,2 @@
+pub mod collect_stale_individual_user_canisters;
+pub mod opt_out_of_stale_canister_collection;
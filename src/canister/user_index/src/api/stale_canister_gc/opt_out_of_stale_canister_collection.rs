@@ -0,0 +1,96 @@
+use std::time::SystemTime;
+
+use candid::Principal;
+
+use crate::{data_model::CanisterData, util::call_queue::queue_call, CANISTER_DATA};
+
+/// Keeps the caller's canister from ever being collected for never getting past onboarding,
+/// calling off a collection that is already scheduled
+#[ic_cdk::update]
+#[candid::candid_method(update)]
+fn opt_out_of_stale_canister_collection() -> Result<(), String> {
+    let api_caller = ic_cdk::caller();
+
+    let user_canister_id = CANISTER_DATA.with(|canister_data_ref_cell| {
+        opt_out_of_stale_canister_collection_impl(
+            &mut canister_data_ref_cell.borrow_mut(),
+            &api_caller,
+        )
+    })?;
+
+    queue_call(
+        user_canister_id,
+        "receive_scheduled_collection_notice",
+        (None::<SystemTime>,),
+    );
+
+    Ok(())
+}
+
+fn opt_out_of_stale_canister_collection_impl(
+    canister_data: &mut CanisterData,
+    api_caller: &Principal,
+) -> Result<Principal, String> {
+    let user_canister_id = canister_data
+        .user_principal_id_to_canister_id_map
+        .get(api_caller)
+        .copied()
+        .ok_or_else(|| "User is not indexed by this user index".to_string())?;
+
+    canister_data
+        .stale_canister_gc
+        .opted_out_user_principal_ids
+        .insert(*api_caller);
+    canister_data
+        .stale_canister_gc
+        .flagged_canisters
+        .remove(&user_canister_id);
+
+    Ok(user_canister_id)
+}
+
+#[cfg(test)]
+mod test {
+    use test_utils::setup::test_constants::{
+        get_mock_user_alice_canister_id, get_mock_user_alice_principal_id,
+        get_mock_user_bob_principal_id,
+    };
+
+    use crate::data_model::stale_canister_gc::FlaggedCanister;
+
+    use super::*;
+
+    #[test]
+    fn test_opt_out_of_stale_canister_collection_impl() {
+        let mut canister_data = CanisterData::default();
+        canister_data.user_principal_id_to_canister_id_map.insert(
+            get_mock_user_alice_principal_id(),
+            get_mock_user_alice_canister_id(),
+        );
+        canister_data.stale_canister_gc.flagged_canisters.insert(
+            get_mock_user_alice_canister_id(),
+            FlaggedCanister {
+                user_principal_id: get_mock_user_alice_principal_id(),
+                scheduled_collection_at: SystemTime::UNIX_EPOCH,
+            },
+        );
+
+        assert!(opt_out_of_stale_canister_collection_impl(
+            &mut canister_data,
+            &get_mock_user_bob_principal_id()
+        )
+        .is_err());
+        assert_eq!(
+            opt_out_of_stale_canister_collection_impl(
+                &mut canister_data,
+                &get_mock_user_alice_principal_id()
+            ),
+            Ok(get_mock_user_alice_canister_id())
+        );
+        assert!(canister_data.stale_canister_gc.flagged_canisters.is_empty());
+        assert!(canister_data
+            .stale_canister_gc
+            .opted_out_user_principal_ids
+            .contains(&get_mock_user_alice_principal_id()));
+    }
+}
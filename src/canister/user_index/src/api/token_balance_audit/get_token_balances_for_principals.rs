@@ -0,0 +1,176 @@
+use std::time::Duration;
+
+use candid::Principal;
+use ic_cdk::api::call;
+use shared_utils::{
+    canister_specific::user_index::types::token_balance_audit::{
+        TokenBalanceAuditEntry, TokenBalanceAuditFailure, TokenBalanceAuditReport,
+        MAX_PRINCIPALS_PER_TOKEN_BALANCE_AUDIT,
+    },
+    common::{
+        types::known_principal::KnownPrincipalType,
+        utils::{
+            bounded_fan_out::{self, FanOutLimits},
+            system_time,
+        },
+    },
+};
+
+use crate::{data_model::CanisterData, CANISTER_DATA};
+
+const TOKEN_BALANCE_AUDIT_LIMITS: FanOutLimits = FanOutLimits {
+    max_concurrent_calls: 20,
+    per_call_timeout: Duration::from_secs(30),
+};
+
+/// Reads the utility token balances of up to `MAX_PRINCIPALS_PER_TOKEN_BALANCE_AUDIT`
+/// principals from their canisters. Principals without a canister, or whose canister
+/// couldn't be read in time, are marked as failed in the report rather than failing the
+/// whole audit.
+///
+/// # Access Control
+/// Only the global super admin can audit balances
+#[ic_cdk::update]
+#[candid::candid_method(update)]
+async fn get_token_balances_for_principals(
+    user_principal_ids: Vec<Principal>,
+) -> Result<TokenBalanceAuditReport, String> {
+    let api_caller = ic_cdk::caller();
+
+    let canisters_to_audit = CANISTER_DATA.with(|canister_data_ref_cell| {
+        get_canisters_to_audit(
+            &canister_data_ref_cell.borrow(),
+            &api_caller,
+            user_principal_ids,
+        )
+    })?;
+
+    let balance_calls = canisters_to_audit
+        .iter()
+        .map(|(_, user_canister_id)| async move {
+            let Some(user_canister_id) = *user_canister_id else {
+                return Err(TokenBalanceAuditFailure::CanisterNotFound);
+            };
+
+            call::call::<_, (u64,)>(user_canister_id, "get_utility_token_balance", ())
+                .await
+                .map(|(utility_token_balance,)| utility_token_balance)
+                .map_err(|(_, message)| TokenBalanceAuditFailure::CallFailed(message))
+        });
+    let responses =
+        bounded_fan_out::fan_out_calls(balance_calls, &TOKEN_BALANCE_AUDIT_LIMITS).await;
+
+    let entries = canisters_to_audit
+        .into_iter()
+        .zip(responses)
+        .map(
+            |((user_principal_id, user_canister_id), response)| TokenBalanceAuditEntry {
+                user_principal_id,
+                user_canister_id,
+                utility_token_balance: response.unwrap_or(Err(TokenBalanceAuditFailure::TimedOut)),
+            },
+        )
+        .collect();
+
+    Ok(TokenBalanceAuditReport::new(
+        entries,
+        system_time::get_current_system_time_from_ic(),
+    ))
+}
+
+/// The canister of each principal to audit, in the order passed in. A principal passed in
+/// more than once is only audited once.
+fn get_canisters_to_audit(
+    canister_data: &CanisterData,
+    api_caller: &Principal,
+    user_principal_ids: Vec<Principal>,
+) -> Result<Vec<(Principal, Option<Principal>)>, String> {
+    if canister_data
+        .known_principal_ids
+        .get(&KnownPrincipalType::UserIdGlobalSuperAdmin)
+        != Some(api_caller)
+    {
+        return Err("Unauthorized caller".to_string());
+    }
+
+    if user_principal_ids.len() > MAX_PRINCIPALS_PER_TOKEN_BALANCE_AUDIT {
+        return Err(format!(
+            "At most {} principals can be audited at once",
+            MAX_PRINCIPALS_PER_TOKEN_BALANCE_AUDIT
+        ));
+    }
+
+    let mut canisters_to_audit: Vec<(Principal, Option<Principal>)> = vec![];
+    for user_principal_id in user_principal_ids {
+        if canisters_to_audit
+            .iter()
+            .any(|(audited_principal_id, _)| *audited_principal_id == user_principal_id)
+        {
+            continue;
+        }
+
+        canisters_to_audit.push((
+            user_principal_id,
+            canister_data
+                .user_principal_id_to_canister_id_map
+                .get(&user_principal_id)
+                .copied(),
+        ));
+    }
+
+    Ok(canisters_to_audit)
+}
+
+#[cfg(test)]
+mod test {
+    use test_utils::setup::test_constants::{
+        get_global_super_admin_principal_id, get_mock_user_alice_canister_id,
+        get_mock_user_alice_principal_id, get_mock_user_bob_principal_id,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_get_canisters_to_audit() {
+        let mut canister_data = CanisterData::default();
+        canister_data.known_principal_ids.insert(
+            KnownPrincipalType::UserIdGlobalSuperAdmin,
+            get_global_super_admin_principal_id(),
+        );
+        canister_data.user_principal_id_to_canister_id_map.insert(
+            get_mock_user_alice_principal_id(),
+            get_mock_user_alice_canister_id(),
+        );
+
+        assert!(get_canisters_to_audit(
+            &canister_data,
+            &get_mock_user_alice_principal_id(),
+            vec![get_mock_user_alice_principal_id()]
+        )
+        .is_err());
+        assert!(get_canisters_to_audit(
+            &canister_data,
+            &get_global_super_admin_principal_id(),
+            vec![get_mock_user_alice_principal_id(); MAX_PRINCIPALS_PER_TOKEN_BALANCE_AUDIT + 1]
+        )
+        .is_err());
+        assert_eq!(
+            get_canisters_to_audit(
+                &canister_data,
+                &get_global_super_admin_principal_id(),
+                vec![
+                    get_mock_user_bob_principal_id(),
+                    get_mock_user_alice_principal_id(),
+                    get_mock_user_bob_principal_id(),
+                ]
+            ),
+            Ok(vec![
+                (get_mock_user_bob_principal_id(), None),
+                (
+                    get_mock_user_alice_principal_id(),
+                    Some(get_mock_user_alice_canister_id())
+                ),
+            ])
+        );
+    }
+}
@@ -0,0 +1 @@
+pub mod get_token_balances_for_principals;
@@ -0,0 +1,97 @@
+use candid::{Nat, Principal};
+use shared_utils::common::types::{
+    known_principal::KnownPrincipalType,
+    utility_token::icrc1::{Account, TransferArg, TransferResult},
+};
+
+use crate::{data_model::CanisterData, CANISTER_DATA};
+
+/// Mints `amount` utility tokens on the token ledger into the calling canister's ledger
+/// account, for individual user canisters mirroring their balance to the ledger. This canister
+/// has to be the ledger's minting account. Returns the index of the ledger block minting them.
+///
+/// # Access Control
+/// Only individual user canisters indexed by this canister can have tokens minted for them
+#[ic_cdk::update]
+#[candid::candid_method(update)]
+async fn mint_utility_tokens_for_individual_user_canister(amount: u64) -> Result<Nat, String> {
+    let user_canister_id = ic_cdk::caller();
+
+    let token_ledger_canister_id = CANISTER_DATA.with(|canister_data_ref_cell| {
+        get_token_ledger_to_mint_on(&canister_data_ref_cell.borrow(), &user_canister_id)
+    })?;
+
+    let (response,) = ic_cdk::call::<_, (TransferResult,)>(
+        token_ledger_canister_id,
+        "icrc1_transfer",
+        (TransferArg {
+            from_subaccount: None,
+            to: Account::of(user_canister_id),
+            amount: Nat::from(amount),
+            fee: None,
+            memo: None,
+            created_at_time: None,
+        },),
+    )
+    .await
+    .map_err(|(rejection_code, message)| format!("{:?}: {}", rejection_code, message))?;
+
+    response.map_err(|transfer_error| format!("{:?}", transfer_error))
+}
+
+fn get_token_ledger_to_mint_on(
+    canister_data: &CanisterData,
+    user_canister_id: &Principal,
+) -> Result<Principal, String> {
+    if !canister_data
+        .user_principal_id_to_canister_id_map
+        .values()
+        .any(|indexed_canister_id| indexed_canister_id == user_canister_id)
+    {
+        return Err("Unauthorized caller".to_string());
+    }
+
+    canister_data
+        .known_principal_ids
+        .get(&KnownPrincipalType::CanisterIdTokenLedger)
+        .cloned()
+        .ok_or_else(|| "No token ledger configured".to_string())
+}
+
+#[cfg(test)]
+mod test {
+    use test_utils::setup::test_constants::{
+        get_mock_user_alice_canister_id, get_mock_user_alice_principal_id,
+        get_mock_user_bob_canister_id, get_mock_user_charlie_canister_id,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_get_token_ledger_to_mint_on() {
+        let mut canister_data = CanisterData::default();
+        canister_data.user_principal_id_to_canister_id_map.insert(
+            get_mock_user_alice_principal_id(),
+            get_mock_user_alice_canister_id(),
+        );
+
+        assert_eq!(
+            get_token_ledger_to_mint_on(&canister_data, &get_mock_user_alice_canister_id()),
+            Err("No token ledger configured".to_string())
+        );
+
+        canister_data.known_principal_ids.insert(
+            KnownPrincipalType::CanisterIdTokenLedger,
+            get_mock_user_charlie_canister_id(),
+        );
+
+        assert_eq!(
+            get_token_ledger_to_mint_on(&canister_data, &get_mock_user_bob_canister_id()),
+            Err("Unauthorized caller".to_string())
+        );
+        assert_eq!(
+            get_token_ledger_to_mint_on(&canister_data, &get_mock_user_alice_canister_id()),
+            Ok(get_mock_user_charlie_canister_id())
+        );
+    }
+}
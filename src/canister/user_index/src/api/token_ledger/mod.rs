@@ -0,0 +1 @@
+pub mod mint_utility_tokens_for_individual_user_canister;
@@ -0,0 +1,333 @@
+use std::{
+    collections::BTreeMap,
+    time::{Duration, SystemTime},
+};
+
+use candid::Principal;
+use ic_cdk::api::call;
+use shared_utils::{
+    canister_specific::user_index::types::tournament::{
+        TournamentDetails, TournamentEventDetails, TournamentId, TournamentStatus,
+    },
+    common::{
+        types::known_principal::KnownPrincipalType,
+        utils::{
+            bounded_fan_out::{self, FanOutLimits},
+            system_time,
+        },
+    },
+    types::canister_specific::user_index::error_types::TournamentError,
+};
+
+use crate::{data_model::CanisterData, util::call_queue::queue_call, CANISTER_DATA};
+
+// * Every enrolled creator is asked for their points, so the close has to stay under the
+// * outgoing call limit however many creators took part
+const POINTS_COLLECTION_LIMITS: FanOutLimits = FanOutLimits {
+    max_concurrent_calls: 50,
+    per_call_timeout: Duration::from_secs(60),
+};
+
+// * Long enough for the points of every enrolled creator to be collected
+const TALLYING_LEASE: Duration = Duration::from_secs(60 * 60);
+
+/// Closes a tournament whose week has elapsed. Normally triggered by the timer set
+/// when the tournament was opened, this is exposed so that the admin can retry the
+/// close if the timer was lost, or if a close trapped while collecting points.
+///
+/// # Access Control
+/// Only the global super admin can close a tournament
+#[ic_cdk::update]
+#[candid::candid_method(update)]
+async fn close_tournament_and_distribute_prizes(
+    tournament_id: TournamentId,
+) -> Result<TournamentDetails, TournamentError> {
+    let api_caller = ic_cdk::caller();
+
+    let global_super_admin_principal_id = CANISTER_DATA.with(|canister_data_ref_cell| {
+        canister_data_ref_cell
+            .borrow()
+            .known_principal_ids
+            .get(&KnownPrincipalType::UserIdGlobalSuperAdmin)
+            .cloned()
+            .unwrap_or(Principal::anonymous())
+    });
+
+    if api_caller == Principal::anonymous() || api_caller != global_super_admin_principal_id {
+        return Err(TournamentError::Unauthorized);
+    }
+
+    close_and_distribute_prizes(tournament_id).await
+}
+
+pub fn enqueue_timer_to_close_tournament(tournament_id: TournamentId, delay: Duration) {
+    ic_cdk_timers::set_timer(delay, move || {
+        ic_cdk::spawn(async move {
+            if let Err(error) = close_and_distribute_prizes(tournament_id).await {
+                ic_cdk::print(format!(
+                    "Failed to close tournament {}: {:?}",
+                    tournament_id, error
+                ));
+            }
+        })
+    });
+}
+
+/// Timers do not survive upgrades, so the close of the open tournament is rescheduled. A
+/// tournament still tallying had its close cut off, so that close is resumed right away.
+pub fn reenqueue_timers_for_unclosed_tournaments() {
+    let current_time = system_time::get_current_system_time_from_ic();
+
+    CANISTER_DATA.with(|canister_data_ref_cell| {
+        canister_data_ref_cell
+            .borrow_mut()
+            .tournaments
+            .values_mut()
+            .filter(|tournament| tournament.details.status != TournamentStatus::Closed)
+            .for_each(|tournament| {
+                tournament.tallying_lease_expires_at = None;
+                enqueue_timer_to_close_tournament(
+                    tournament.details.tournament_id,
+                    tournament.time_left_until_close(&current_time),
+                );
+            });
+    });
+}
+
+async fn close_and_distribute_prizes(
+    tournament_id: TournamentId,
+) -> Result<TournamentDetails, TournamentError> {
+    let current_time = system_time::get_current_system_time_from_ic();
+
+    let enrolled_creators = CANISTER_DATA.with(|canister_data_ref_cell| {
+        start_tallying_impl(
+            &mut canister_data_ref_cell.borrow_mut(),
+            tournament_id,
+            &current_time,
+        )
+    })?;
+
+    let points_collection_calls = enrolled_creators
+        .keys()
+        .map(|creator_canister_id| async move {
+            call::call::<_, (u64,)>(
+                *creator_canister_id,
+                "get_tournament_points_for_enrolled_posts",
+                (tournament_id,),
+            )
+            .await
+        });
+    let responses =
+        bounded_fan_out::fan_out_calls(points_collection_calls, &POINTS_COLLECTION_LIMITS).await;
+
+    let mut points_by_creator_canister_id = BTreeMap::new();
+
+    for (creator_canister_id, response) in enrolled_creators.keys().zip(responses) {
+        let event_details = match response {
+            Ok(Ok((points,))) => {
+                points_by_creator_canister_id.insert(*creator_canister_id, points);
+                TournamentEventDetails::PointsCollected {
+                    creator_canister_id: *creator_canister_id,
+                    points,
+                }
+            }
+            // * A creator that errors out or times out is ranked without points
+            Ok(Err(_)) | Err(_) => TournamentEventDetails::PointsCollectionFailed {
+                creator_canister_id: *creator_canister_id,
+            },
+        };
+
+        record_event(tournament_id, event_details);
+    }
+
+    let tournament_details = CANISTER_DATA.with(|canister_data_ref_cell| {
+        finish_tallying_impl(
+            &mut canister_data_ref_cell.borrow_mut(),
+            tournament_id,
+            &points_by_creator_canister_id,
+            &system_time::get_current_system_time_from_ic(),
+        )
+    })?;
+
+    // * Prizes count as awarded once queued, the call queue keeps retrying until the
+    // * creator's canister takes them
+    tournament_details.rankings.iter().for_each(|ranking| {
+        queue_call(
+            ranking.creator_canister_id,
+            "receive_tournament_prize",
+            (tournament_id, ranking.rank, ranking.prize_amount),
+        );
+        record_event(
+            tournament_id,
+            TournamentEventDetails::PrizeAwarded {
+                creator_canister_id: ranking.creator_canister_id,
+                rank: ranking.rank,
+                prize_amount: ranking.prize_amount,
+            },
+        );
+    });
+
+    Ok(tournament_details)
+}
+
+fn record_event(tournament_id: TournamentId, event_details: TournamentEventDetails) {
+    let current_time = system_time::get_current_system_time_from_ic();
+
+    CANISTER_DATA.with(|canister_data_ref_cell| {
+        if let Some(tournament) = canister_data_ref_cell
+            .borrow_mut()
+            .tournaments
+            .get_mut(&tournament_id)
+        {
+            tournament.record_event(&current_time, event_details);
+        }
+    });
+}
+
+/// Marks the tournament as tallying so that it cannot be closed twice while the
+/// points are being collected. A tournament left tallying by a close that never finished
+/// is taken over once that close's lease has run out. Returns the enrolled creators keyed
+/// by canister id.
+fn start_tallying_impl(
+    canister_data: &mut CanisterData,
+    tournament_id: TournamentId,
+    current_time: &SystemTime,
+) -> Result<BTreeMap<Principal, Principal>, TournamentError> {
+    let tournament = canister_data
+        .tournaments
+        .get_mut(&tournament_id)
+        .ok_or(TournamentError::TournamentNotFound)?;
+
+    match tournament.details.status {
+        TournamentStatus::Open => {
+            if *current_time < tournament.details.closes_at {
+                return Err(TournamentError::TournamentStillRunning);
+            }
+        }
+        TournamentStatus::Tallying => {
+            if tournament
+                .tallying_lease_expires_at
+                .is_some_and(|tallying_lease_expires_at| *current_time < tallying_lease_expires_at)
+            {
+                return Err(TournamentError::TournamentBeingTallied);
+            }
+        }
+        TournamentStatus::Closed => return Err(TournamentError::TournamentAlreadyClosed),
+    }
+
+    tournament.details.status = TournamentStatus::Tallying;
+    tournament.tallying_lease_expires_at = Some(*current_time + TALLYING_LEASE);
+
+    Ok(tournament.get_enrolled_creators())
+}
+
+fn finish_tallying_impl(
+    canister_data: &mut CanisterData,
+    tournament_id: TournamentId,
+    points_by_creator_canister_id: &BTreeMap<Principal, u64>,
+    current_time: &SystemTime,
+) -> Result<TournamentDetails, TournamentError> {
+    let tournament = canister_data
+        .tournaments
+        .get_mut(&tournament_id)
+        .ok_or(TournamentError::TournamentNotFound)?;
+
+    // * A close that outlived its lease finishing after the one that took over
+    if tournament.details.status != TournamentStatus::Tallying {
+        return Err(TournamentError::TournamentAlreadyClosed);
+    }
+
+    tournament.details.rankings = tournament.compute_rankings(points_by_creator_canister_id);
+    tournament.details.status = TournamentStatus::Closed;
+    tournament.tallying_lease_expires_at = None;
+    tournament.record_event(current_time, TournamentEventDetails::Closed);
+
+    Ok(tournament.details.clone())
+}
+
+#[cfg(test)]
+mod test {
+    use shared_utils::canister_specific::user_index::types::tournament::TOURNAMENT_DURATION;
+    use test_utils::setup::test_constants::{
+        get_mock_user_alice_canister_id, get_mock_user_alice_principal_id,
+    };
+
+    use crate::data_model::tournament::Tournament;
+
+    use super::*;
+
+    #[test]
+    fn test_close_tournament_impl() {
+        let mut canister_data = CanisterData::default();
+        let opened_at = SystemTime::now();
+        let mut tournament = Tournament::new(1, 1000, &opened_at);
+        tournament
+            .enroll_post(
+                get_mock_user_alice_principal_id(),
+                get_mock_user_alice_canister_id(),
+                0,
+                &opened_at,
+                &opened_at,
+            )
+            .unwrap();
+        canister_data.tournaments.insert(1, tournament);
+
+        assert_eq!(
+            start_tallying_impl(&mut canister_data, 2, &opened_at),
+            Err(TournamentError::TournamentNotFound)
+        );
+        assert_eq!(
+            start_tallying_impl(&mut canister_data, 1, &opened_at),
+            Err(TournamentError::TournamentStillRunning)
+        );
+
+        let closes_at = opened_at + TOURNAMENT_DURATION;
+
+        assert_eq!(
+            start_tallying_impl(&mut canister_data, 1, &closes_at),
+            Ok(BTreeMap::from([(
+                get_mock_user_alice_canister_id(),
+                get_mock_user_alice_principal_id()
+            )]))
+        );
+        assert_eq!(
+            start_tallying_impl(&mut canister_data, 1, &closes_at),
+            Err(TournamentError::TournamentBeingTallied)
+        );
+
+        // * the first close never finished, so another takes over once its lease runs out
+        let lease_expired_at = closes_at + TALLYING_LEASE;
+        assert_eq!(
+            start_tallying_impl(&mut canister_data, 1, &lease_expired_at),
+            Ok(BTreeMap::from([(
+                get_mock_user_alice_canister_id(),
+                get_mock_user_alice_principal_id()
+            )]))
+        );
+
+        let tournament_details = finish_tallying_impl(
+            &mut canister_data,
+            1,
+            &BTreeMap::from([(get_mock_user_alice_canister_id(), 100)]),
+            &lease_expired_at,
+        )
+        .unwrap();
+
+        assert_eq!(tournament_details.status, TournamentStatus::Closed);
+        assert_eq!(tournament_details.rankings.len(), 1);
+        assert_eq!(tournament_details.rankings[0].prize_amount, 500);
+        assert_eq!(
+            canister_data.tournaments[&1].events.last().unwrap().details,
+            TournamentEventDetails::Closed
+        );
+
+        assert_eq!(
+            finish_tallying_impl(&mut canister_data, 1, &BTreeMap::new(), &lease_expired_at),
+            Err(TournamentError::TournamentAlreadyClosed)
+        );
+        assert_eq!(
+            start_tallying_impl(&mut canister_data, 1, &lease_expired_at),
+            Err(TournamentError::TournamentAlreadyClosed)
+        );
+    }
+}
@@ -0,0 +1,17 @@
+use shared_utils::canister_specific::user_index::types::tournament::{
+    TournamentDetails, TournamentId,
+};
+
+use crate::CANISTER_DATA;
+
+#[ic_cdk::query]
+#[candid::candid_method(query)]
+fn get_tournament_details(tournament_id: TournamentId) -> Option<TournamentDetails> {
+    CANISTER_DATA.with(|canister_data_ref_cell| {
+        canister_data_ref_cell
+            .borrow()
+            .tournaments
+            .get(&tournament_id)
+            .map(|tournament| tournament.details.clone())
+    })
+}
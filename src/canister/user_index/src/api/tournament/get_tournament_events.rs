@@ -0,0 +1,88 @@
+use shared_utils::{
+    canister_specific::user_index::types::tournament::{TournamentEvent, TournamentId},
+    pagination::{self, Page, MAX_ITEMS_PER_PAGE},
+};
+
+use crate::{data_model::CanisterData, CANISTER_DATA};
+
+/// Audit trail of everything that happened in the tournament, oldest first. Only the first
+/// `MAX_ITEMS_PER_PAGE` events, use `get_tournament_events_paginated` for the rest.
+#[ic_cdk::query]
+#[candid::candid_method(query)]
+fn get_tournament_events(tournament_id: TournamentId) -> Vec<TournamentEvent> {
+    CANISTER_DATA.with(|canister_data_ref_cell| {
+        get_tournament_events_impl(
+            &canister_data_ref_cell.borrow(),
+            tournament_id,
+            0,
+            MAX_ITEMS_PER_PAGE,
+        )
+        .items
+    })
+}
+
+#[ic_cdk::query]
+#[candid::candid_method(query)]
+fn get_tournament_events_paginated(
+    tournament_id: TournamentId,
+    from_inclusive_index: u64,
+    page_size: u64,
+) -> Page<TournamentEvent> {
+    CANISTER_DATA.with(|canister_data_ref_cell| {
+        get_tournament_events_impl(
+            &canister_data_ref_cell.borrow(),
+            tournament_id,
+            from_inclusive_index,
+            page_size,
+        )
+    })
+}
+
+fn get_tournament_events_impl(
+    canister_data: &CanisterData,
+    tournament_id: TournamentId,
+    from_inclusive_index: u64,
+    page_size: u64,
+) -> Page<TournamentEvent> {
+    let events = canister_data
+        .tournaments
+        .get(&tournament_id)
+        .map(|tournament| tournament.events.as_slice())
+        .unwrap_or_default();
+
+    pagination::get_page(events.iter().cloned(), from_inclusive_index, page_size)
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::SystemTime;
+
+    use crate::data_model::tournament::Tournament;
+
+    use super::*;
+
+    #[test]
+    fn test_get_tournament_events_impl() {
+        let mut canister_data = CanisterData::default();
+        let tournament = Tournament::new(1, 1000, &SystemTime::now());
+        let opened_event = tournament.events[0].clone();
+        canister_data.tournaments.insert(1, tournament);
+
+        assert_eq!(
+            get_tournament_events_impl(&canister_data, 1, 0, 10),
+            Page {
+                items: vec![opened_event],
+                total_count: 1,
+                next_from_inclusive_index: None,
+            }
+        );
+        assert_eq!(
+            get_tournament_events_impl(&canister_data, 2, 0, 10),
+            Page {
+                items: vec![],
+                total_count: 0,
+                next_from_inclusive_index: None,
+            }
+        );
+    }
+}
@@ -0,0 +1,5 @@
+pub mod close_tournament_and_distribute_prizes;
+pub mod get_tournament_details;
+pub mod get_tournament_events;
+pub mod open_weekly_tournament;
+pub mod receive_post_enrollment_for_current_tournament;
@@ -0,0 +1,146 @@
+use std::time::SystemTime;
+
+use candid::Principal;
+use shared_utils::{
+    canister_specific::user_index::types::tournament::{TournamentDetails, TournamentStatus},
+    common::{types::known_principal::KnownPrincipalType, utils::system_time},
+    types::canister_specific::user_index::error_types::TournamentError,
+};
+
+use crate::{data_model::tournament::Tournament, data_model::CanisterData, CANISTER_DATA};
+
+use super::close_tournament_and_distribute_prizes::enqueue_timer_to_close_tournament;
+
+/// # Access Control
+/// Only the global super admin can open a tournament
+#[ic_cdk::update]
+#[candid::candid_method(update)]
+fn open_weekly_tournament(prize_pool: u64) -> Result<TournamentDetails, TournamentError> {
+    let api_caller = ic_cdk::caller();
+    let current_time = system_time::get_current_system_time_from_ic();
+
+    let tournament_details = CANISTER_DATA.with(|canister_data_ref_cell| {
+        open_weekly_tournament_impl(
+            &mut canister_data_ref_cell.borrow_mut(),
+            &api_caller,
+            prize_pool,
+            &current_time,
+        )
+    })?;
+
+    enqueue_timer_to_close_tournament(
+        tournament_details.tournament_id,
+        tournament_details
+            .closes_at
+            .duration_since(current_time)
+            .unwrap_or_default(),
+    );
+
+    Ok(tournament_details)
+}
+
+fn open_weekly_tournament_impl(
+    canister_data: &mut CanisterData,
+    api_caller: &Principal,
+    prize_pool: u64,
+    current_time: &SystemTime,
+) -> Result<TournamentDetails, TournamentError> {
+    let global_super_admin_principal_id = canister_data
+        .known_principal_ids
+        .get(&KnownPrincipalType::UserIdGlobalSuperAdmin)
+        .cloned()
+        .unwrap_or(Principal::anonymous());
+
+    if *api_caller == Principal::anonymous() || *api_caller != global_super_admin_principal_id {
+        return Err(TournamentError::Unauthorized);
+    }
+
+    if canister_data
+        .tournaments
+        .values()
+        .any(|tournament| tournament.details.status != TournamentStatus::Closed)
+    {
+        return Err(TournamentError::TournamentAlreadyOpen);
+    }
+
+    let tournament_id = canister_data
+        .tournaments
+        .last_key_value()
+        .map(|(tournament_id, _)| tournament_id + 1)
+        .unwrap_or(1);
+
+    let tournament = Tournament::new(tournament_id, prize_pool, current_time);
+    let tournament_details = tournament.details.clone();
+
+    canister_data.tournaments.insert(tournament_id, tournament);
+
+    Ok(tournament_details)
+}
+
+#[cfg(test)]
+mod test {
+    use test_utils::setup::test_constants::{
+        get_global_super_admin_principal_id, get_mock_user_alice_principal_id,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_open_weekly_tournament_impl() {
+        let mut canister_data = CanisterData::default();
+        canister_data.known_principal_ids.insert(
+            KnownPrincipalType::UserIdGlobalSuperAdmin,
+            get_global_super_admin_principal_id(),
+        );
+
+        assert_eq!(
+            open_weekly_tournament_impl(
+                &mut canister_data,
+                &get_mock_user_alice_principal_id(),
+                1000,
+                &SystemTime::now()
+            ),
+            Err(TournamentError::Unauthorized)
+        );
+
+        let tournament_details = open_weekly_tournament_impl(
+            &mut canister_data,
+            &get_global_super_admin_principal_id(),
+            1000,
+            &SystemTime::now(),
+        )
+        .unwrap();
+
+        assert_eq!(tournament_details.tournament_id, 1);
+        assert_eq!(tournament_details.status, TournamentStatus::Open);
+
+        assert_eq!(
+            open_weekly_tournament_impl(
+                &mut canister_data,
+                &get_global_super_admin_principal_id(),
+                1000,
+                &SystemTime::now()
+            ),
+            Err(TournamentError::TournamentAlreadyOpen)
+        );
+
+        canister_data
+            .tournaments
+            .get_mut(&1)
+            .unwrap()
+            .details
+            .status = TournamentStatus::Closed;
+
+        assert_eq!(
+            open_weekly_tournament_impl(
+                &mut canister_data,
+                &get_global_super_admin_principal_id(),
+                1000,
+                &SystemTime::now()
+            )
+            .unwrap()
+            .tournament_id,
+            2
+        );
+    }
+}
@@ -0,0 +1,132 @@
+use std::time::SystemTime;
+
+use candid::Principal;
+use shared_utils::{
+    canister_specific::user_index::types::tournament::TournamentDetails,
+    common::{types::app_primitive_type::PostId, utils::system_time},
+    types::canister_specific::user_index::error_types::TournamentError,
+};
+
+use crate::{data_model::CanisterData, CANISTER_DATA};
+
+/// # Access Control
+/// Only individual user canisters indexed by this canister can enroll their posts
+#[ic_cdk::update]
+#[candid::candid_method(update)]
+fn receive_post_enrollment_for_current_tournament(
+    post_id: PostId,
+    post_created_at: SystemTime,
+) -> Result<TournamentDetails, TournamentError> {
+    let request_makers_canister_id = ic_cdk::caller();
+    let current_time = system_time::get_current_system_time_from_ic();
+
+    CANISTER_DATA.with(|canister_data_ref_cell| {
+        receive_post_enrollment_for_current_tournament_impl(
+            &mut canister_data_ref_cell.borrow_mut(),
+            &request_makers_canister_id,
+            post_id,
+            &post_created_at,
+            &current_time,
+        )
+    })
+}
+
+fn receive_post_enrollment_for_current_tournament_impl(
+    canister_data: &mut CanisterData,
+    request_makers_canister_id: &Principal,
+    post_id: PostId,
+    post_created_at: &SystemTime,
+    current_time: &SystemTime,
+) -> Result<TournamentDetails, TournamentError> {
+    let creator_principal_id = canister_data
+        .user_principal_id_to_canister_id_map
+        .iter()
+        .find(|(_, user_canister_id)| *user_canister_id == request_makers_canister_id)
+        .map(|(user_principal_id, _)| *user_principal_id)
+        .ok_or(TournamentError::UserCanisterEntryDoesNotExist)?;
+
+    let tournament = canister_data
+        .tournaments
+        .values_mut()
+        .next_back()
+        .filter(|tournament| tournament.is_accepting_enrollments(current_time))
+        .ok_or(TournamentError::NoOpenTournament)?;
+
+    tournament.enroll_post(
+        creator_principal_id,
+        *request_makers_canister_id,
+        post_id,
+        post_created_at,
+        current_time,
+    )?;
+
+    Ok(tournament.details.clone())
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use test_utils::setup::test_constants::{
+        get_mock_user_alice_canister_id, get_mock_user_alice_principal_id,
+        get_mock_user_bob_canister_id,
+    };
+
+    use crate::data_model::tournament::Tournament;
+
+    use super::*;
+
+    #[test]
+    fn test_receive_post_enrollment_for_current_tournament_impl() {
+        let mut canister_data = CanisterData::default();
+        canister_data.user_principal_id_to_canister_id_map.insert(
+            get_mock_user_alice_principal_id(),
+            get_mock_user_alice_canister_id(),
+        );
+        let opened_at = SystemTime::now();
+        let current_time = opened_at + Duration::from_secs(60);
+
+        assert_eq!(
+            receive_post_enrollment_for_current_tournament_impl(
+                &mut canister_data,
+                &get_mock_user_alice_canister_id(),
+                0,
+                &current_time,
+                &current_time,
+            ),
+            Err(TournamentError::NoOpenTournament)
+        );
+
+        canister_data
+            .tournaments
+            .insert(1, Tournament::new(1, 1000, &opened_at));
+
+        assert_eq!(
+            receive_post_enrollment_for_current_tournament_impl(
+                &mut canister_data,
+                &get_mock_user_bob_canister_id(),
+                0,
+                &current_time,
+                &current_time,
+            ),
+            Err(TournamentError::UserCanisterEntryDoesNotExist)
+        );
+
+        let tournament_details = receive_post_enrollment_for_current_tournament_impl(
+            &mut canister_data,
+            &get_mock_user_alice_canister_id(),
+            0,
+            &current_time,
+            &current_time,
+        )
+        .unwrap();
+
+        assert_eq!(tournament_details.tournament_id, 1);
+        assert_eq!(
+            canister_data.tournaments[&1]
+                .enrolled_posts
+                .get(&(get_mock_user_alice_canister_id(), 0)),
+            Some(&get_mock_user_alice_principal_id())
+        );
+    }
+}
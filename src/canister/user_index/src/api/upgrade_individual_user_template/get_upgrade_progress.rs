@@ -0,0 +1,15 @@
+use crate::{data_model::canister_upgrade::rolling_upgrade::RollingUpgradeProgress, CANISTER_DATA};
+
+/// How the latest rolling upgrade is going, None if one was never started
+#[ic_cdk::query]
+#[candid::candid_method(query)]
+fn get_upgrade_progress() -> Option<RollingUpgradeProgress> {
+    CANISTER_DATA.with(|canister_data_ref_cell| {
+        let canister_data = canister_data_ref_cell.borrow();
+
+        canister_data
+            .rolling_upgrade
+            .as_ref()
+            .map(|rolling_upgrade| rolling_upgrade.get_progress(&canister_data))
+    })
+}
@@ -0,0 +1,13 @@
+use crate::{data_model::canister_upgrade::upgrade_run::UpgradeRunReport, CANISTER_DATA};
+
+/// Reports on the most recent runs upgrading every user canister, newest first
+#[ic_cdk::query]
+#[candid::candid_method(query)]
+fn get_upgrade_run_reports() -> Vec<UpgradeRunReport> {
+    CANISTER_DATA.with(|canister_data_ref_cell| {
+        canister_data_ref_cell
+            .borrow()
+            .upgrade_run_reports
+            .get_reports()
+    })
+}
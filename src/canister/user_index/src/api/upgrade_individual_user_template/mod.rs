@@ -1,3 +1,8 @@
 pub mod get_index_details_last_upgrade_status;
+pub mod get_upgrade_progress;
+pub mod get_upgrade_run_reports;
+pub mod resume_rolling_upgrade;
+pub mod start_rolling_upgrade;
+pub mod update_individual_user_canister_upgrade_arg_override;
 pub mod update_user_index_upgrade_user_canisters_with_latest_wasm;
 pub mod upgrade_specific_individual_user_canister_with_latest_wasm;
@@ -0,0 +1,149 @@
+use candid::Principal;
+use shared_utils::common::types::known_principal::KnownPrincipalType;
+
+use crate::{
+    data_model::{
+        canister_upgrade::{
+            rolling_upgrade::{RollingUpgradeError, RollingUpgradeStatus},
+            UpgradeRunKind,
+        },
+        CanisterData,
+    },
+    util::canister_management::get_individual_user_template_wasm_hash,
+    CANISTER_DATA,
+};
+
+use super::start_rolling_upgrade::upgrade_rolling_upgrade_batches;
+
+/// Carries on with a rolling upgrade that paused on failures or was interrupted by an upgrade
+/// of this index, from the batch after the last one it finished. Canisters that failed are not
+/// retried. An interrupted rollout can only be resumed if this index still embeds the wasm it
+/// was rolling out, and not while the full run of upgrades after an upgrade of this index is
+/// still going.
+///
+/// # Access Control
+/// Only the global super admin can resume rolling upgrades
+#[ic_cdk::update]
+#[candid::candid_method(update)]
+fn resume_rolling_upgrade() -> Result<(), RollingUpgradeError> {
+    let api_caller = ic_cdk::caller();
+
+    CANISTER_DATA.with(|canister_data_ref_cell| {
+        resume_rolling_upgrade_impl(
+            &mut canister_data_ref_cell.borrow_mut(),
+            &api_caller,
+            &get_individual_user_template_wasm_hash(),
+        )
+    })?;
+
+    ic_cdk::spawn(upgrade_rolling_upgrade_batches());
+
+    Ok(())
+}
+
+fn resume_rolling_upgrade_impl(
+    canister_data: &mut CanisterData,
+    api_caller: &Principal,
+    embedded_wasm_hash: &[u8],
+) -> Result<(), RollingUpgradeError> {
+    if canister_data
+        .known_principal_ids
+        .get(&KnownPrincipalType::UserIdGlobalSuperAdmin)
+        != Some(api_caller)
+    {
+        return Err(RollingUpgradeError::Unauthorized);
+    }
+
+    if canister_data.get_upgrade_run_in_progress() == Some(UpgradeRunKind::FullRun) {
+        return Err(RollingUpgradeError::UpgradeRunInProgress);
+    }
+
+    let rolling_upgrade = canister_data
+        .rolling_upgrade
+        .as_mut()
+        .filter(|rolling_upgrade| rolling_upgrade.is_paused())
+        .ok_or(RollingUpgradeError::NoPausedRollingUpgrade)?;
+
+    if rolling_upgrade.wasm_hash != embedded_wasm_hash {
+        return Err(RollingUpgradeError::WasmHashMismatch);
+    }
+
+    rolling_upgrade.status = RollingUpgradeStatus::InProgress;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::SystemTime;
+
+    use test_utils::setup::test_constants::{
+        get_global_super_admin_principal_id, get_mock_user_alice_principal_id,
+    };
+
+    use crate::data_model::canister_upgrade::rolling_upgrade::RollingUpgrade;
+
+    use super::*;
+
+    #[test]
+    fn test_resume_rolling_upgrade_impl() {
+        let mut canister_data = CanisterData::default();
+        let super_admin = get_global_super_admin_principal_id();
+        canister_data
+            .known_principal_ids
+            .insert(KnownPrincipalType::UserIdGlobalSuperAdmin, super_admin);
+
+        assert_eq!(
+            resume_rolling_upgrade_impl(&mut canister_data, &super_admin, &[1, 2]),
+            Err(RollingUpgradeError::NoPausedRollingUpgrade)
+        );
+
+        canister_data.rolling_upgrade = Some(RollingUpgrade::new(
+            vec![1, 2],
+            1,
+            10,
+            SystemTime::UNIX_EPOCH,
+        ));
+        assert_eq!(
+            resume_rolling_upgrade_impl(&mut canister_data, &super_admin, &[1, 2]),
+            Err(RollingUpgradeError::NoPausedRollingUpgrade)
+        );
+
+        canister_data.rolling_upgrade.as_mut().unwrap().interrupt();
+        assert_eq!(
+            resume_rolling_upgrade_impl(
+                &mut canister_data,
+                &get_mock_user_alice_principal_id(),
+                &[1, 2]
+            ),
+            Err(RollingUpgradeError::Unauthorized)
+        );
+        // * the index was upgraded with another wasm since the rollout started
+        assert_eq!(
+            resume_rolling_upgrade_impl(&mut canister_data, &super_admin, &[3, 4]),
+            Err(RollingUpgradeError::WasmHashMismatch)
+        );
+
+        // * the index was upgraded and is upgrading every canister
+        canister_data
+            .upgrade_run_reports
+            .start_run(2, SystemTime::UNIX_EPOCH);
+        assert_eq!(
+            resume_rolling_upgrade_impl(&mut canister_data, &super_admin, &[1, 2]),
+            Err(RollingUpgradeError::UpgradeRunInProgress)
+        );
+        canister_data
+            .upgrade_run_reports
+            .complete_run(SystemTime::UNIX_EPOCH);
+
+        assert_eq!(
+            resume_rolling_upgrade_impl(&mut canister_data, &super_admin, &[1, 2]),
+            Ok(())
+        );
+        assert!(canister_data
+            .rolling_upgrade
+            .as_ref()
+            .unwrap()
+            .is_in_progress());
+    }
+}
@@ -0,0 +1,377 @@
+use std::time::SystemTime;
+
+use candid::Principal;
+use ic_cdk::api::call;
+use shared_utils::{
+    canister_specific::individual_user_template::types::post_upgrade_health::PostUpgradeHealthReport,
+    common::{types::known_principal::KnownPrincipalType, utils::system_time},
+};
+
+use crate::{
+    data_model::{
+        canister_upgrade::{
+            rolling_upgrade::{
+                RollingUpgrade, RollingUpgradeError, RollingUpgradeFailure,
+                MAX_ROLLING_UPGRADE_BATCH_SIZE,
+            },
+            UpgradeRunKind,
+        },
+        CanisterData,
+    },
+    util::canister_management::get_individual_user_template_wasm_hash,
+    CANISTER_DATA,
+};
+
+use super::update_user_index_upgrade_user_canisters_with_latest_wasm::{
+    get_time_until_upgrade_window_opens, get_upgrade_window, recharge_and_upgrade_user_canister,
+    record_upgrade_failure,
+};
+
+/// Upgrades every user canister to the individual user template wasm this index embeds,
+/// `batch_size` users at a time. Each upgraded canister is health checked before the next
+/// batch starts, and the rollout pauses if too many canisters in a batch fail. `wasm_hash` is
+/// the sha256 of the gzipped wasm, so that the admin confirms which wasm is being rolled out.
+/// A paused rolling upgrade is superseded by the new one. Like the full run of upgrades
+/// after this index is upgraded, batches only start while the upgrade window set in the
+/// configuration canister is open, and the two never run at the same time.
+///
+/// # Access Control
+/// Only the global super admin can start rolling upgrades
+#[ic_cdk::update]
+#[candid::candid_method(update)]
+fn start_rolling_upgrade(wasm_hash: Vec<u8>, batch_size: u64) -> Result<(), RollingUpgradeError> {
+    let api_caller = ic_cdk::caller();
+
+    CANISTER_DATA.with(|canister_data_ref_cell| {
+        start_rolling_upgrade_impl(
+            &mut canister_data_ref_cell.borrow_mut(),
+            &api_caller,
+            wasm_hash,
+            batch_size,
+            &get_individual_user_template_wasm_hash(),
+            system_time::get_current_system_time_from_ic(),
+        )
+    })?;
+
+    ic_cdk::spawn(upgrade_rolling_upgrade_batches());
+
+    Ok(())
+}
+
+fn start_rolling_upgrade_impl(
+    canister_data: &mut CanisterData,
+    api_caller: &Principal,
+    wasm_hash: Vec<u8>,
+    batch_size: u64,
+    embedded_wasm_hash: &[u8],
+    current_time: SystemTime,
+) -> Result<(), RollingUpgradeError> {
+    if canister_data
+        .known_principal_ids
+        .get(&KnownPrincipalType::UserIdGlobalSuperAdmin)
+        != Some(api_caller)
+    {
+        return Err(RollingUpgradeError::Unauthorized);
+    }
+
+    if batch_size == 0 || batch_size > MAX_ROLLING_UPGRADE_BATCH_SIZE {
+        return Err(RollingUpgradeError::InvalidBatchSize);
+    }
+
+    if wasm_hash != embedded_wasm_hash {
+        return Err(RollingUpgradeError::WasmHashMismatch);
+    }
+
+    match canister_data.get_upgrade_run_in_progress() {
+        Some(UpgradeRunKind::RollingUpgrade) => {
+            return Err(RollingUpgradeError::RollingUpgradeInProgress)
+        }
+        Some(UpgradeRunKind::FullRun) => return Err(RollingUpgradeError::UpgradeRunInProgress),
+        None => {}
+    }
+
+    canister_data.rolling_upgrade = Some(RollingUpgrade::new(
+        wasm_hash,
+        canister_data.last_run_upgrade_status.version_number + 1,
+        batch_size,
+        current_time,
+    ));
+
+    Ok(())
+}
+
+/// Works through the batches of the rolling upgrade in progress until it runs out of
+/// canisters or pauses. Waits for the upgrade window to open again once it closes.
+pub(crate) async fn upgrade_rolling_upgrade_batches() {
+    let upgrade_window = get_upgrade_window().await;
+
+    loop {
+        let Some((batch, version_number)) = CANISTER_DATA.with(|canister_data_ref_cell| {
+            let canister_data = canister_data_ref_cell.borrow();
+            let rolling_upgrade = canister_data
+                .rolling_upgrade
+                .as_ref()
+                .filter(|rolling_upgrade| rolling_upgrade.is_in_progress())?;
+
+            Some((
+                rolling_upgrade.get_next_batch(&canister_data),
+                rolling_upgrade.version_number,
+            ))
+        }) else {
+            return;
+        };
+
+        let Some((last_user_principal_id_in_batch, _)) = batch.last().copied() else {
+            CANISTER_DATA.with(|canister_data_ref_cell| {
+                let canister_data = &mut *canister_data_ref_cell.borrow_mut();
+                let current_time = system_time::get_current_system_time_from_ic();
+
+                if let Some(rolling_upgrade) = canister_data
+                    .rolling_upgrade
+                    .as_mut()
+                    .filter(|rolling_upgrade| rolling_upgrade.is_in_progress())
+                {
+                    rolling_upgrade.complete(current_time);
+                    canister_data.last_run_upgrade_status.version_number =
+                        rolling_upgrade.version_number;
+                    canister_data.last_run_upgrade_status.last_run_on = current_time;
+                }
+            });
+            return;
+        };
+
+        let current_time = system_time::get_current_system_time_from_ic();
+        let time_until_next_opening =
+            get_time_until_upgrade_window_opens(upgrade_window.as_ref(), &current_time);
+        if !time_until_next_opening.is_zero() {
+            CANISTER_DATA.with(|canister_data_ref_cell| {
+                if let Some(rolling_upgrade) =
+                    canister_data_ref_cell.borrow_mut().rolling_upgrade.as_mut()
+                {
+                    rolling_upgrade.wait_for_upgrade_window(current_time + time_until_next_opening);
+                }
+            });
+            ic_cdk_timers::set_timer(
+                time_until_next_opening,
+                resume_rolling_upgrade_when_upgrade_window_opens,
+            );
+            return;
+        }
+
+        let configuration = CANISTER_DATA
+            .with(|canister_data_ref_cell| canister_data_ref_cell.borrow().configuration.clone());
+
+        let mut failures = vec![];
+        for (user_principal_id, user_canister_id) in batch.iter() {
+            let upgrade_arg_override = CANISTER_DATA.with(|canister_data_ref_cell| {
+                canister_data_ref_cell
+                    .borrow()
+                    .upgrade_arg_overrides
+                    .get(user_canister_id)
+                    .cloned()
+            });
+
+            // * Upgrades to the version after the one it is given
+            let upgrade_result = recharge_and_upgrade_user_canister(
+                user_principal_id,
+                user_canister_id,
+                version_number - 1,
+                &configuration,
+                upgrade_arg_override.as_deref(),
+            )
+            .await;
+
+            let health_check_result = match upgrade_result {
+                Ok(()) => check_upgraded_canister_health(user_canister_id, version_number).await,
+                Err(err) => {
+                    record_upgrade_failure();
+                    Err(err)
+                }
+            };
+
+            if let Err(reason) = health_check_result {
+                failures.push(RollingUpgradeFailure {
+                    user_principal_id: *user_principal_id,
+                    user_canister_id: *user_canister_id,
+                    reason,
+                });
+            }
+        }
+
+        CANISTER_DATA.with(|canister_data_ref_cell| {
+            if let Some(rolling_upgrade) =
+                canister_data_ref_cell.borrow_mut().rolling_upgrade.as_mut()
+            {
+                rolling_upgrade.record_batch(
+                    last_user_principal_id_in_batch,
+                    batch.len(),
+                    failures,
+                );
+            }
+        });
+    }
+}
+
+fn resume_rolling_upgrade_when_upgrade_window_opens() {
+    let is_resumed = CANISTER_DATA.with(|canister_data_ref_cell| {
+        canister_data_ref_cell
+            .borrow_mut()
+            .rolling_upgrade
+            .as_mut()
+            .is_some_and(|rolling_upgrade| rolling_upgrade.stop_waiting_for_upgrade_window())
+    });
+
+    if is_resumed {
+        ic_cdk::spawn(upgrade_rolling_upgrade_batches());
+    }
+}
+
+async fn check_upgraded_canister_health(
+    user_canister_id: &Principal,
+    expected_version_number: u64,
+) -> Result<(), String> {
+    let (health_report,) = call::call::<_, (PostUpgradeHealthReport,)>(
+        *user_canister_id,
+        "post_upgrade_health_check",
+        (),
+    )
+    .await
+    .map_err(|(_, message)| format!("Health check could not be reached: {}", message))?;
+
+    health_report.check(expected_version_number)
+}
+
+#[cfg(test)]
+mod test {
+    use test_utils::setup::test_constants::{
+        get_global_super_admin_principal_id, get_mock_user_alice_principal_id,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_start_rolling_upgrade_impl() {
+        let mut canister_data = CanisterData::default();
+        let super_admin = get_global_super_admin_principal_id();
+        let embedded_wasm_hash = vec![1, 2, 3];
+        canister_data
+            .known_principal_ids
+            .insert(KnownPrincipalType::UserIdGlobalSuperAdmin, super_admin);
+        canister_data.last_run_upgrade_status.version_number = 6;
+
+        // * the full run of upgrades started after an upgrade of this index is still going
+        canister_data
+            .upgrade_run_reports
+            .start_run(6, SystemTime::UNIX_EPOCH);
+        assert_eq!(
+            start_rolling_upgrade_impl(
+                &mut canister_data,
+                &super_admin,
+                embedded_wasm_hash.clone(),
+                10,
+                &embedded_wasm_hash,
+                SystemTime::UNIX_EPOCH,
+            ),
+            Err(RollingUpgradeError::UpgradeRunInProgress)
+        );
+        canister_data
+            .upgrade_run_reports
+            .complete_run(SystemTime::UNIX_EPOCH);
+
+        assert_eq!(
+            start_rolling_upgrade_impl(
+                &mut canister_data,
+                &get_mock_user_alice_principal_id(),
+                embedded_wasm_hash.clone(),
+                10,
+                &embedded_wasm_hash,
+                SystemTime::UNIX_EPOCH,
+            ),
+            Err(RollingUpgradeError::Unauthorized)
+        );
+        assert_eq!(
+            start_rolling_upgrade_impl(
+                &mut canister_data,
+                &super_admin,
+                embedded_wasm_hash.clone(),
+                0,
+                &embedded_wasm_hash,
+                SystemTime::UNIX_EPOCH,
+            ),
+            Err(RollingUpgradeError::InvalidBatchSize)
+        );
+        assert_eq!(
+            start_rolling_upgrade_impl(
+                &mut canister_data,
+                &super_admin,
+                vec![3, 2, 1],
+                10,
+                &embedded_wasm_hash,
+                SystemTime::UNIX_EPOCH,
+            ),
+            Err(RollingUpgradeError::WasmHashMismatch)
+        );
+        assert_eq!(
+            start_rolling_upgrade_impl(
+                &mut canister_data,
+                &super_admin,
+                embedded_wasm_hash.clone(),
+                10,
+                &embedded_wasm_hash,
+                SystemTime::UNIX_EPOCH,
+            ),
+            Ok(())
+        );
+        assert_eq!(
+            canister_data
+                .rolling_upgrade
+                .as_ref()
+                .map(|rolling_upgrade| rolling_upgrade.version_number),
+            Some(7)
+        );
+
+        assert_eq!(
+            start_rolling_upgrade_impl(
+                &mut canister_data,
+                &super_admin,
+                embedded_wasm_hash.clone(),
+                10,
+                &embedded_wasm_hash,
+                SystemTime::UNIX_EPOCH,
+            ),
+            Err(RollingUpgradeError::RollingUpgradeInProgress)
+        );
+
+        // * nor while it waits for the upgrade window to open
+        canister_data
+            .rolling_upgrade
+            .as_mut()
+            .unwrap()
+            .wait_for_upgrade_window(SystemTime::UNIX_EPOCH);
+        assert_eq!(
+            start_rolling_upgrade_impl(
+                &mut canister_data,
+                &super_admin,
+                embedded_wasm_hash.clone(),
+                10,
+                &embedded_wasm_hash,
+                SystemTime::UNIX_EPOCH,
+            ),
+            Err(RollingUpgradeError::RollingUpgradeInProgress)
+        );
+
+        // * a paused rollout can be superseded, e.g. by one rolling out a fixed wasm
+        canister_data.rolling_upgrade.as_mut().unwrap().interrupt();
+        assert_eq!(
+            start_rolling_upgrade_impl(
+                &mut canister_data,
+                &super_admin,
+                embedded_wasm_hash.clone(),
+                10,
+                &embedded_wasm_hash,
+                SystemTime::UNIX_EPOCH,
+            ),
+            Ok(())
+        );
+    }
+}
@@ -0,0 +1,165 @@
+use candid::Principal;
+use shared_utils::common::types::known_principal::KnownPrincipalType;
+
+use crate::{
+    data_model::{canister_upgrade::decode_upgrade_arg_override, CanisterData},
+    CANISTER_DATA,
+};
+
+// * dfx canister call user_index update_individual_user_canister_upgrade_arg_override '(principal "", opt blob "")' --network ic
+
+/// Sets the candid encoded `IndividualUserTemplateInitArgs` a single canister is upgraded
+/// with, in place of the args every other canister gets. Passing `null` removes the override.
+///
+/// # Access Control
+/// Only the global super admin can set overrides
+#[ic_cdk::update]
+#[candid::candid_method(update)]
+fn update_individual_user_canister_upgrade_arg_override(
+    user_canister_id: Principal,
+    upgrade_arg_override: Option<Vec<u8>>,
+) -> Result<(), String> {
+    let api_caller = ic_cdk::caller();
+
+    CANISTER_DATA.with(|canister_data_ref_cell| {
+        update_individual_user_canister_upgrade_arg_override_impl(
+            &mut canister_data_ref_cell.borrow_mut(),
+            &api_caller,
+            user_canister_id,
+            upgrade_arg_override,
+        )
+    })
+}
+
+fn update_individual_user_canister_upgrade_arg_override_impl(
+    canister_data: &mut CanisterData,
+    api_caller: &Principal,
+    user_canister_id: Principal,
+    upgrade_arg_override: Option<Vec<u8>>,
+) -> Result<(), String> {
+    if canister_data
+        .known_principal_ids
+        .get(&KnownPrincipalType::UserIdGlobalSuperAdmin)
+        != Some(api_caller)
+    {
+        return Err("Unauthorized caller".to_string());
+    }
+
+    if !canister_data
+        .user_principal_id_to_canister_id_map
+        .values()
+        .any(|canister_id| *canister_id == user_canister_id)
+    {
+        return Err("Canister is not indexed by this user index".to_string());
+    }
+
+    match upgrade_arg_override {
+        Some(upgrade_arg_override) => {
+            decode_upgrade_arg_override(&upgrade_arg_override)?;
+            canister_data
+                .upgrade_arg_overrides
+                .insert(user_canister_id, upgrade_arg_override);
+        }
+        None => {
+            canister_data
+                .upgrade_arg_overrides
+                .remove(&user_canister_id);
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use candid::Encode;
+    use shared_utils::canister_specific::individual_user_template::types::arg::IndividualUserTemplateInitArgs;
+    use test_utils::setup::test_constants::{
+        get_global_super_admin_principal_id, get_mock_user_alice_canister_id,
+        get_mock_user_alice_principal_id, get_mock_user_bob_canister_id,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_update_individual_user_canister_upgrade_arg_override_impl() {
+        let mut canister_data = CanisterData::default();
+        canister_data.known_principal_ids.insert(
+            KnownPrincipalType::UserIdGlobalSuperAdmin,
+            get_global_super_admin_principal_id(),
+        );
+        canister_data.user_principal_id_to_canister_id_map.insert(
+            get_mock_user_alice_principal_id(),
+            get_mock_user_alice_canister_id(),
+        );
+
+        let upgrade_arg_override = Encode!(&IndividualUserTemplateInitArgs {
+            known_principal_ids: None,
+            profile_owner: None,
+            upgrade_version_number: None,
+            url_to_send_canister_metrics_to: None,
+            referrer_share_of_bet_winnings_percentage: None,
+            house_rake_percentage: Some(0),
+            room_sizing_config: None,
+            post_category_config: None,
+            binding_epoch: None,
+            is_call_journal_enabled: None,
+            signup_bonus_vesting_config: None,
+            pot_insurance_config: None,
+            bet_cancellation_config: None,
+            access_statistics_config: None,
+            hot_or_not_payout_mode: None,
+        })
+        .unwrap();
+
+        assert!(update_individual_user_canister_upgrade_arg_override_impl(
+            &mut canister_data,
+            &get_mock_user_alice_principal_id(),
+            get_mock_user_alice_canister_id(),
+            Some(upgrade_arg_override.clone()),
+        )
+        .is_err());
+        assert!(update_individual_user_canister_upgrade_arg_override_impl(
+            &mut canister_data,
+            &get_global_super_admin_principal_id(),
+            get_mock_user_bob_canister_id(),
+            Some(upgrade_arg_override.clone()),
+        )
+        .is_err());
+        assert!(update_individual_user_canister_upgrade_arg_override_impl(
+            &mut canister_data,
+            &get_global_super_admin_principal_id(),
+            get_mock_user_alice_canister_id(),
+            Some(vec![1, 2, 3]),
+        )
+        .is_err());
+        assert!(canister_data.upgrade_arg_overrides.is_empty());
+
+        assert_eq!(
+            update_individual_user_canister_upgrade_arg_override_impl(
+                &mut canister_data,
+                &get_global_super_admin_principal_id(),
+                get_mock_user_alice_canister_id(),
+                Some(upgrade_arg_override.clone()),
+            ),
+            Ok(())
+        );
+        assert_eq!(
+            canister_data
+                .upgrade_arg_overrides
+                .get(&get_mock_user_alice_canister_id()),
+            Some(&upgrade_arg_override)
+        );
+
+        assert_eq!(
+            update_individual_user_canister_upgrade_arg_override_impl(
+                &mut canister_data,
+                &get_global_super_admin_principal_id(),
+                get_mock_user_alice_canister_id(),
+                None,
+            ),
+            Ok(())
+        );
+        assert!(canister_data.upgrade_arg_overrides.is_empty());
+    }
+}
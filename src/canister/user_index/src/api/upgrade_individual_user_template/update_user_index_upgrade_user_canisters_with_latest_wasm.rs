@@ -1,32 +1,84 @@
-use std::time::SystemTime;
+use std::{
+    ops::Bound,
+    time::{Duration, SystemTime},
+};
 
 use candid::Principal;
-use ic_cdk::api::management_canister::{
-    main::{self, CanisterInstallMode},
-    provisional::CanisterIdRecord,
+use ic_cdk::api::{
+    call,
+    management_canister::{
+        main::{self, CanisterInstallMode},
+        provisional::CanisterIdRecord,
+    },
 };
 use shared_utils::{
-    canister_specific::individual_user_template::types::arg::IndividualUserTemplateInitArgs,
-    common::utils::system_time,
+    canister_specific::{
+        configuration::types::upgrade_window::UpgradeWindow,
+        individual_user_template::types::{
+            arg::IndividualUserTemplateInitArgs, hot_or_not::HotOrNotPayoutMode,
+        },
+    },
+    common::{
+        types::{event_counter::EventCounterName, known_principal::KnownPrincipalType},
+        utils::system_time,
+    },
     constant::{CYCLES_THRESHOLD_TO_INITIATE_RECHARGE, INDIVIDUAL_USER_CANISTER_RECHARGE_AMOUNT},
 };
 
 use crate::{
-    data_model::{configuration::Configuration, CanisterData},
+    data_model::{
+        canister_upgrade::{apply_upgrade_arg_override, UpgradeRunKind},
+        configuration::Configuration,
+        CanisterData,
+    },
     util::canister_management,
     CANISTER_DATA,
 };
 
 pub async fn upgrade_user_canisters_with_latest_wasm() {
-    let mut upgrade_count = 0;
-    let mut failed_canister_ids = Vec::new();
-
-    let user_principal_id_to_canister_id_map = CANISTER_DATA.with(|canister_data_ref_cell| {
+    let is_rolling_upgrade_in_progress = CANISTER_DATA.with(|canister_data_ref_cell| {
         canister_data_ref_cell
             .borrow()
-            .user_principal_id_to_canister_id_map
-            .clone()
+            .get_upgrade_run_in_progress()
+            == Some(UpgradeRunKind::RollingUpgrade)
     });
+    if is_rolling_upgrade_in_progress {
+        ic_cdk::print("Not upgrading user canisters, a rolling upgrade is in progress");
+        return;
+    }
+
+    CANISTER_DATA.with(|canister_data_ref_cell| {
+        let canister_data = &mut canister_data_ref_cell.borrow_mut();
+
+        let version_number = canister_data.last_run_upgrade_status.version_number + 1;
+        canister_data.upgrade_run_reports.start_run(
+            version_number,
+            system_time::get_current_system_time_from_ic(),
+        );
+        update_upgrade_status(canister_data, 0, &[], None, None);
+    });
+
+    upgrade_user_canisters_after(None).await;
+}
+
+fn resume_paused_upgrade_run() {
+    let Some(resume_after_user_principal_id) = CANISTER_DATA.with(|canister_data_ref_cell| {
+        canister_data_ref_cell
+            .borrow_mut()
+            .upgrade_run_reports
+            .resume_run()
+    }) else {
+        return;
+    };
+
+    ic_cdk::spawn(upgrade_user_canisters_after(resume_after_user_principal_id));
+}
+
+/// Upgrades canisters in user principal id order, starting after the given user. Pauses at
+/// the end of the upgrade window and picks up from the same place when it next opens.
+async fn upgrade_user_canisters_after(resume_after_user_principal_id: Option<Principal>) {
+    let upgrade_window = get_upgrade_window().await;
+    refresh_hot_or_not_payout_mode().await;
 
     let saved_upgrade_status = CANISTER_DATA.with(|canister_data_ref_cell| {
         canister_data_ref_cell
@@ -34,45 +86,80 @@ pub async fn upgrade_user_canisters_with_latest_wasm() {
             .last_run_upgrade_status
             .clone()
     });
+    // * Carried over from before the run was paused
+    let mut upgrade_count = saved_upgrade_status.successful_upgrade_count;
+    let mut failed_canister_ids = saved_upgrade_status.failed_canister_ids.clone();
+
+    let canisters_to_upgrade: Vec<(Principal, Principal)> =
+        CANISTER_DATA.with(|canister_data_ref_cell| {
+            let start_after =
+                resume_after_user_principal_id.map_or(Bound::Unbounded, Bound::Excluded);
+
+            let canister_data = canister_data_ref_cell.borrow();
+
+            canister_data
+                .user_principal_id_to_canister_id_map
+                .range((start_after, Bound::Unbounded))
+                .flat_map(|(user_principal_id, user_canister_id)| {
+                    // * Standbys go right after their primary, so the two stay on the same wasm
+                    // * and deltas keep decoding
+                    std::iter::once(*user_canister_id)
+                        .chain(canister_data.get_standby_canister_id(user_principal_id))
+                        .map(move |canister_id| (*user_principal_id, canister_id))
+                })
+                .collect()
+        });
 
     let configuration = CANISTER_DATA
         .with(|canister_data_ref_cell| canister_data_ref_cell.borrow().configuration.clone());
 
-    for (user_principal_id, user_canister_id) in user_principal_id_to_canister_id_map.iter() {
-        let is_canister_below_threshold_balance =
-            is_canister_below_threshold_balance(user_canister_id).await;
+    let upgrade_arg_overrides = CANISTER_DATA.with(|canister_data_ref_cell| {
+        canister_data_ref_cell
+            .borrow()
+            .upgrade_arg_overrides
+            .clone()
+    });
 
-        if is_canister_below_threshold_balance {
-            let recharge_result = recharge_canister(user_canister_id).await;
+    for (user_principal_id, user_canister_id) in canisters_to_upgrade.iter() {
+        let current_time = system_time::get_current_system_time_from_ic();
+        let time_until_next_opening =
+            get_time_until_upgrade_window_opens(upgrade_window.as_ref(), &current_time);
 
-            if recharge_result.is_err() {
-                let err = recharge_result.err().unwrap();
-                failed_canister_ids.push((*user_principal_id, *user_canister_id, err));
-                continue;
-            }
+        if !time_until_next_opening.is_zero() {
+            CANISTER_DATA.with(|canister_data_ref_cell| {
+                canister_data_ref_cell
+                    .borrow_mut()
+                    .upgrade_run_reports
+                    .pause_run(current_time + time_until_next_opening);
+            });
+            ic_cdk_timers::set_timer(time_until_next_opening, resume_paused_upgrade_run);
+            return;
         }
 
-        let upgrade_result = upgrade_user_canister(
+        let upgrade_result = recharge_and_upgrade_user_canister(
             user_principal_id,
             user_canister_id,
             saved_upgrade_status.version_number,
             &configuration,
+            upgrade_arg_overrides
+                .get(user_canister_id)
+                .map(|upgrade_arg_override| upgrade_arg_override.as_slice()),
         )
         .await;
 
-        if upgrade_result.is_err() {
-            let err = upgrade_result.err().unwrap();
-            ic_cdk::print(format!(
-                "Failed to upgrade canister: {:?} with error: {:?}",
-                user_canister_id.to_text(),
-                err
-            ));
-            failed_canister_ids.push((*user_principal_id, *user_canister_id, err));
-            continue;
+        match upgrade_result {
+            Ok(()) => upgrade_count += 1,
+            Err(err) => {
+                ic_cdk::print(format!(
+                    "Failed to upgrade canister: {:?} with error: {:?}",
+                    user_canister_id.to_text(),
+                    err
+                ));
+                failed_canister_ids.push((*user_principal_id, *user_canister_id, err));
+                record_upgrade_failure();
+            }
         }
 
-        upgrade_count += 1;
-
         // * Enable for data backup
         // let upgrade_response: CallResult<()> = call::call(
         //     user_canister_id.clone(),
@@ -83,27 +170,134 @@ pub async fn upgrade_user_canisters_with_latest_wasm() {
         // upgrade_response.ok();
 
         CANISTER_DATA.with(|canister_data_ref_cell| {
+            let canister_data = &mut canister_data_ref_cell.borrow_mut();
+
             update_upgrade_status(
-                &mut canister_data_ref_cell.borrow_mut(),
+                canister_data,
                 upgrade_count,
                 &failed_canister_ids,
                 None,
                 None,
             );
+            canister_data.upgrade_run_reports.record_progress(
+                *user_principal_id,
+                upgrade_count,
+                failed_canister_ids.len() as u32,
+            );
         });
     }
 
     CANISTER_DATA.with(|canister_data_ref_cell| {
+        let canister_data = &mut canister_data_ref_cell.borrow_mut();
+        let current_time = system_time::get_current_system_time_from_ic();
+
         update_upgrade_status(
-            &mut canister_data_ref_cell.borrow_mut(),
+            canister_data,
             upgrade_count,
             &failed_canister_ids,
             Some(saved_upgrade_status.version_number + 1),
-            Some(system_time::get_current_system_time_from_ic()),
+            Some(current_time),
         );
+        canister_data.upgrade_run_reports.complete_run(current_time);
     });
 }
 
+/// The window set in the configuration canister. Upgrades go ahead at any time if it can't
+/// be reached, the way they did before there were windows.
+pub(crate) async fn get_upgrade_window() -> Option<UpgradeWindow> {
+    let configuration_canister_id = CANISTER_DATA.with(|canister_data_ref_cell| {
+        canister_data_ref_cell
+            .borrow()
+            .known_principal_ids
+            .get(&KnownPrincipalType::CanisterIdConfiguration)
+            .copied()
+    })?;
+
+    match call::call::<_, (Option<UpgradeWindow>,)>(
+        configuration_canister_id,
+        "get_upgrade_window",
+        (),
+    )
+    .await
+    {
+        Ok((upgrade_window,)) => upgrade_window,
+        Err((_, message)) => {
+            ic_cdk::print(format!(
+                "Failed to fetch the upgrade window, upgrading without one: {}",
+                message
+            ));
+            None
+        }
+    }
+}
+
+/// Zero while canisters can be upgraded, either because the window is open or because there
+/// is no window
+pub(crate) fn get_time_until_upgrade_window_opens(
+    upgrade_window: Option<&UpgradeWindow>,
+    current_time: &SystemTime,
+) -> Duration {
+    upgrade_window.map_or(Duration::ZERO, |upgrade_window| {
+        upgrade_window.get_time_until_next_opening(current_time)
+    })
+}
+
+/// Copies the payout mode set in the configuration canister. Canisters keep the mode they
+/// already have if it can't be reached.
+async fn refresh_hot_or_not_payout_mode() {
+    let Some(configuration_canister_id) = CANISTER_DATA.with(|canister_data_ref_cell| {
+        canister_data_ref_cell
+            .borrow()
+            .known_principal_ids
+            .get(&KnownPrincipalType::CanisterIdConfiguration)
+            .copied()
+    }) else {
+        return;
+    };
+
+    match call::call::<_, (HotOrNotPayoutMode,)>(
+        configuration_canister_id,
+        "get_hot_or_not_payout_mode",
+        (),
+    )
+    .await
+    {
+        Ok((hot_or_not_payout_mode,)) => CANISTER_DATA.with(|canister_data_ref_cell| {
+            canister_data_ref_cell
+                .borrow_mut()
+                .configuration
+                .hot_or_not_payout_mode = hot_or_not_payout_mode;
+        }),
+        Err((_, message)) => {
+            ic_cdk::print(format!(
+                "Failed to fetch the hot or not payout mode, keeping the last one: {}",
+                message
+            ));
+        }
+    }
+}
+
+pub(crate) async fn recharge_and_upgrade_user_canister(
+    user_principal_id: &Principal,
+    user_canister_id: &Principal,
+    version_number: u64,
+    configuration: &Configuration,
+    upgrade_arg_override: Option<&[u8]>,
+) -> Result<(), String> {
+    if is_canister_below_threshold_balance(user_canister_id).await {
+        recharge_canister(user_canister_id).await?;
+    }
+
+    upgrade_user_canister(
+        user_principal_id,
+        user_canister_id,
+        version_number,
+        configuration,
+        upgrade_arg_override,
+    )
+    .await
+}
+
 async fn is_canister_below_threshold_balance(canister_id: &Principal) -> bool {
     let response: Result<(u128,), (_, _)> =
         ic_cdk::call(*canister_id, "get_user_caniser_cycle_balance", ()).await;
@@ -137,10 +331,11 @@ async fn upgrade_user_canister(
     canister_id: &Principal,
     version_number: u64,
     configuration: &Configuration,
+    upgrade_arg_override: Option<&[u8]>,
 ) -> Result<(), String> {
-    canister_management::upgrade_individual_user_canister(
-        *canister_id,
-        CanisterInstallMode::Upgrade,
+    // * An override that no longer decodes fails this canister's upgrade rather than
+    // * silently falling back to the blanket args
+    let upgrade_args = apply_upgrade_arg_override(
         IndividualUserTemplateInitArgs {
             known_principal_ids: Some(configuration.known_principal_ids.clone()),
             profile_owner: Some(*user_principal_id),
@@ -148,12 +343,41 @@ async fn upgrade_user_canister(
             url_to_send_canister_metrics_to: Some(
                 configuration.url_to_send_canister_metrics_to.clone(),
             ),
+            referrer_share_of_bet_winnings_percentage: Some(
+                configuration.referrer_share_of_bet_winnings_percentage,
+            ),
+            house_rake_percentage: Some(configuration.house_rake_percentage),
+            room_sizing_config: Some(configuration.room_sizing_config.clone()),
+            post_category_config: Some(configuration.post_category_config.clone()),
+            binding_epoch: None,
+            is_call_journal_enabled: None,
+            signup_bonus_vesting_config: Some(configuration.signup_bonus_vesting_config.clone()),
+            pot_insurance_config: Some(configuration.pot_insurance_config.clone()),
+            bet_cancellation_config: Some(configuration.bet_cancellation_config.clone()),
+            access_statistics_config: None,
+            hot_or_not_payout_mode: Some(configuration.hot_or_not_payout_mode),
         },
+        upgrade_arg_override,
+    )?;
+
+    canister_management::upgrade_individual_user_canister(
+        *canister_id,
+        CanisterInstallMode::Upgrade,
+        upgrade_args,
     )
     .await
     .map_err(|e| e.1)
 }
 
+pub(crate) fn record_upgrade_failure() {
+    CANISTER_DATA.with(|canister_data_ref_cell| {
+        canister_data_ref_cell
+            .borrow_mut()
+            .event_counters
+            .increment(EventCounterName::UpgradeFailuresTotal, "");
+    });
+}
+
 fn update_upgrade_status(
     canister_data: &mut CanisterData,
     upgrade_count: u32,
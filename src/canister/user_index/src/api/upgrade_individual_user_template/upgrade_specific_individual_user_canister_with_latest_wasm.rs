@@ -2,10 +2,13 @@ use candid::Principal;
 use ic_cdk::api::management_canister::main::CanisterInstallMode;
 use shared_utils::{
     canister_specific::individual_user_template::types::arg::IndividualUserTemplateInitArgs,
-    common::types::known_principal::KnownPrincipalType,
+    common::types::{event_counter::EventCounterName, known_principal::KnownPrincipalType},
 };
 
-use crate::{util::canister_management, CANISTER_DATA};
+use crate::{
+    data_model::canister_upgrade::apply_upgrade_arg_override, util::canister_management,
+    CANISTER_DATA,
+};
 
 // * dfx canister call user_index upgrade_specific_individual_user_canister_with_latest_wasm '(principal "", principal "", null)' --network ic
 
@@ -39,9 +42,26 @@ async fn upgrade_specific_individual_user_canister_with_latest_wasm(
     let configuration = CANISTER_DATA
         .with(|canister_data_ref_cell| canister_data_ref_cell.borrow().configuration.clone());
 
-    match canister_management::upgrade_individual_user_canister(
-        user_canister_id,
-        upgrade_mode.unwrap_or(CanisterInstallMode::Upgrade),
+    let upgrade_arg_override = CANISTER_DATA.with(|canister_data_ref_cell| {
+        canister_data_ref_cell
+            .borrow()
+            .upgrade_arg_overrides
+            .get(&user_canister_id)
+            .cloned()
+    });
+
+    // * A reinstall wipes the canister, so references to it from before are stale from then on
+    let upgrade_mode = upgrade_mode.unwrap_or(CanisterInstallMode::Upgrade);
+    let reinstalled_binding_epoch =
+        matches!(upgrade_mode, CanisterInstallMode::Reinstall).then(|| {
+            CANISTER_DATA.with(|canister_data_ref_cell| {
+                canister_data_ref_cell
+                    .borrow()
+                    .get_binding_epoch(&user_canister_id)
+            }) + 1
+        });
+
+    let upgrade_args = match apply_upgrade_arg_override(
         IndividualUserTemplateInitArgs {
             known_principal_ids: Some(CANISTER_DATA.with(|canister_data_ref_cell| {
                 canister_data_ref_cell.borrow().known_principal_ids.clone()
@@ -49,11 +69,52 @@ async fn upgrade_specific_individual_user_canister_with_latest_wasm(
             profile_owner: Some(user_principal_id),
             upgrade_version_number: Some(saved_upgrade_status.version_number + 1),
             url_to_send_canister_metrics_to: Some(configuration.url_to_send_canister_metrics_to),
+            referrer_share_of_bet_winnings_percentage: Some(
+                configuration.referrer_share_of_bet_winnings_percentage,
+            ),
+            house_rake_percentage: Some(configuration.house_rake_percentage),
+            room_sizing_config: Some(configuration.room_sizing_config),
+            post_category_config: Some(configuration.post_category_config),
+            binding_epoch: reinstalled_binding_epoch,
+            is_call_journal_enabled: None,
+            signup_bonus_vesting_config: Some(configuration.signup_bonus_vesting_config),
+            pot_insurance_config: Some(configuration.pot_insurance_config),
+            bet_cancellation_config: Some(configuration.bet_cancellation_config),
+            access_statistics_config: None,
+            hot_or_not_payout_mode: Some(configuration.hot_or_not_payout_mode),
         },
+        upgrade_arg_override.as_deref(),
+    ) {
+        Ok(upgrade_args) => upgrade_args,
+        Err(e) => return e,
+    };
+
+    match canister_management::upgrade_individual_user_canister(
+        user_canister_id,
+        upgrade_mode,
+        upgrade_args,
     )
     .await
     {
-        Ok(_) => "Success".to_string(),
-        Err(e) => e.1,
+        Ok(_) => {
+            if let Some(reinstalled_binding_epoch) = reinstalled_binding_epoch {
+                CANISTER_DATA.with(|canister_data_ref_cell| {
+                    canister_data_ref_cell
+                        .borrow_mut()
+                        .canister_binding_epochs
+                        .insert(user_canister_id, reinstalled_binding_epoch);
+                });
+            }
+            "Success".to_string()
+        }
+        Err(e) => {
+            CANISTER_DATA.with(|canister_data_ref_cell| {
+                canister_data_ref_cell
+                    .borrow_mut()
+                    .event_counters
+                    .increment(EventCounterName::UpgradeFailuresTotal, "");
+            });
+            e.1
+        }
     }
 }
@@ -0,0 +1,151 @@
+use candid::Principal;
+use shared_utils::common::{
+    types::known_principal::KnownPrincipalType, utils::known_caller::authorize_known_caller,
+};
+
+use crate::{
+    data_model::CanisterData, util::canister_management::create_users_canister, CANISTER_DATA,
+};
+
+/// Called on a shard by the primary user index when it is the shard's turn to take a signup.
+/// Creates the user's canister on this subnet and indexes it here. Signup and referral rewards
+/// are left to the primary, same as for the canisters it creates itself.
+///
+/// # Access Control
+/// Only user indexes among this canister's known principals
+#[ic_cdk::update]
+#[candid::candid_method(update)]
+async fn create_user_canister_for_routed_signup(
+    user_principal_id: Principal,
+) -> Result<Principal, String> {
+    let api_caller = ic_cdk::caller();
+
+    let existing_canister_id = CANISTER_DATA.with(|canister_data_ref_cell| {
+        get_existing_canister_for_routed_signup_impl(
+            &canister_data_ref_cell.borrow(),
+            &api_caller,
+            &user_principal_id,
+        )
+    })?;
+
+    if let Some(existing_canister_id) = existing_canister_id {
+        return Ok(existing_canister_id);
+    }
+
+    let created_canister_id =
+        create_users_canister(user_principal_id)
+            .await
+            .map_err(|failure_reason| {
+                format!(
+                    "Failed to create a canister for this user: {:?}",
+                    failure_reason
+                )
+            })?;
+
+    CANISTER_DATA.with(|canister_data_ref_cell| {
+        canister_data_ref_cell
+            .borrow_mut()
+            .user_principal_id_to_canister_id_map
+            .insert(user_principal_id, created_canister_id);
+    });
+
+    Ok(created_canister_id)
+}
+
+/// Has `shard_canister_id` create the user's canister on its subnet
+pub(crate) async fn create_user_canister_on_shard(
+    shard_canister_id: Principal,
+    user_principal_id: Principal,
+) -> Result<Principal, String> {
+    ic_cdk::call::<_, (Result<Principal, String>,)>(
+        shard_canister_id,
+        "create_user_canister_for_routed_signup",
+        (user_principal_id,),
+    )
+    .await
+    .map_err(|(_, message)| message)
+    .and_then(|(response,)| response)
+    .map_err(|message| {
+        format!(
+            "Failed to create a canister on shard {}: {}",
+            shard_canister_id.to_text(),
+            message
+        )
+    })
+}
+
+/// The canister already created for the user, if the primary retries a signup
+fn get_existing_canister_for_routed_signup_impl(
+    canister_data: &CanisterData,
+    api_caller: &Principal,
+    user_principal_id: &Principal,
+) -> Result<Option<Principal>, String> {
+    authorize_known_caller(
+        api_caller,
+        &canister_data.known_principal_ids,
+        &[KnownPrincipalType::CanisterIdUserIndex],
+    )
+    .map_err(|_| "Unauthorized caller".to_string())?;
+
+    if *user_principal_id == Principal::anonymous() {
+        return Err("Anonymous principal can't sign up".to_string());
+    }
+
+    Ok(canister_data
+        .user_principal_id_to_canister_id_map
+        .get(user_principal_id)
+        .copied())
+}
+
+#[cfg(test)]
+mod test {
+    use test_utils::setup::test_constants::{
+        get_mock_canister_id_user_index, get_mock_user_alice_canister_id,
+        get_mock_user_alice_principal_id, get_mock_user_bob_principal_id,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_get_existing_canister_for_routed_signup_impl() {
+        let mut canister_data = CanisterData::default();
+        let primary_user_index = get_mock_canister_id_user_index();
+        let alice = get_mock_user_alice_principal_id();
+        canister_data
+            .known_principal_ids
+            .insert(KnownPrincipalType::CanisterIdUserIndex, primary_user_index);
+
+        assert!(get_existing_canister_for_routed_signup_impl(
+            &canister_data,
+            &get_mock_user_bob_principal_id(),
+            &alice
+        )
+        .is_err());
+        assert!(get_existing_canister_for_routed_signup_impl(
+            &canister_data,
+            &primary_user_index,
+            &Principal::anonymous()
+        )
+        .is_err());
+        assert_eq!(
+            get_existing_canister_for_routed_signup_impl(
+                &canister_data,
+                &primary_user_index,
+                &alice
+            ),
+            Ok(None)
+        );
+
+        canister_data
+            .user_principal_id_to_canister_id_map
+            .insert(alice, get_mock_user_alice_canister_id());
+        assert_eq!(
+            get_existing_canister_for_routed_signup_impl(
+                &canister_data,
+                &primary_user_index,
+                &alice
+            ),
+            Ok(Some(get_mock_user_alice_canister_id()))
+        );
+    }
+}
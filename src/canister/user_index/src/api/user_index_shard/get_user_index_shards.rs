@@ -0,0 +1,14 @@
+use shared_utils::canister_specific::user_index::types::user_index_shard::UserIndexShard;
+
+use crate::CANISTER_DATA;
+
+#[ic_cdk::query]
+#[candid::candid_method(query)]
+fn get_user_index_shards() -> Vec<UserIndexShard> {
+    CANISTER_DATA.with(|canister_data_ref_cell| {
+        canister_data_ref_cell
+            .borrow()
+            .user_index_shards
+            .get_shards()
+    })
+}
@@ -0,0 +1,4 @@
+pub mod create_user_canister_for_routed_signup;
+pub mod get_user_index_shards;
+pub mod register_user_index_shard;
+pub mod update_user_index_shard_signup_status;
@@ -0,0 +1,130 @@
+use std::time::SystemTime;
+
+use candid::Principal;
+use shared_utils::{
+    common::{types::known_principal::KnownPrincipalType, utils::system_time},
+    types::canister_specific::user_index::error_types::UserIndexShardError,
+};
+
+use crate::{data_model::CanisterData, CANISTER_DATA};
+
+/// Starts handing new signups to a user index on another subnet, taking turns with this one and
+/// the other shards. The shard has to list this canister as an instance of
+/// `CanisterIdUserIndex` in its known principals, so that it accepts the signups and the
+/// canisters it creates accept calls from here.
+///
+/// # Access Control
+/// Only the global super admin can register shards
+#[ic_cdk::update]
+#[candid::candid_method(update)]
+fn register_user_index_shard(
+    canister_id: Principal,
+    subnet_id: Principal,
+) -> Result<(), UserIndexShardError> {
+    let api_caller = ic_cdk::caller();
+
+    CANISTER_DATA.with(|canister_data_ref_cell| {
+        register_user_index_shard_impl(
+            &mut canister_data_ref_cell.borrow_mut(),
+            &api_caller,
+            &ic_cdk::id(),
+            canister_id,
+            subnet_id,
+            &system_time::get_current_system_time_from_ic(),
+        )
+    })
+}
+
+fn register_user_index_shard_impl(
+    canister_data: &mut CanisterData,
+    api_caller: &Principal,
+    this_canister_id: &Principal,
+    canister_id: Principal,
+    subnet_id: Principal,
+    current_time: &SystemTime,
+) -> Result<(), UserIndexShardError> {
+    if canister_data
+        .known_principal_ids
+        .get(&KnownPrincipalType::UserIdGlobalSuperAdmin)
+        != Some(api_caller)
+    {
+        return Err(UserIndexShardError::Unauthorized);
+    }
+
+    if canister_id == *this_canister_id || canister_id == Principal::anonymous() {
+        return Err(UserIndexShardError::CannotRegisterThisCanister);
+    }
+
+    canister_data
+        .user_index_shards
+        .register(canister_id, subnet_id, current_time)
+}
+
+#[cfg(test)]
+mod test {
+    use test_utils::setup::test_constants::{
+        get_global_super_admin_principal_id, get_mock_canister_id_user_index,
+        get_mock_user_alice_principal_id,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_register_user_index_shard_impl() {
+        let mut canister_data = CanisterData::default();
+        let super_admin = get_global_super_admin_principal_id();
+        let this_canister_id = get_mock_canister_id_user_index();
+        let shard = Principal::from_slice(&[2]);
+        let subnet = Principal::from_slice(&[9]);
+        canister_data
+            .known_principal_ids
+            .insert(KnownPrincipalType::UserIdGlobalSuperAdmin, super_admin);
+
+        assert_eq!(
+            register_user_index_shard_impl(
+                &mut canister_data,
+                &get_mock_user_alice_principal_id(),
+                &this_canister_id,
+                shard,
+                subnet,
+                &SystemTime::UNIX_EPOCH,
+            ),
+            Err(UserIndexShardError::Unauthorized)
+        );
+        assert_eq!(
+            register_user_index_shard_impl(
+                &mut canister_data,
+                &super_admin,
+                &this_canister_id,
+                this_canister_id,
+                subnet,
+                &SystemTime::UNIX_EPOCH,
+            ),
+            Err(UserIndexShardError::CannotRegisterThisCanister)
+        );
+        assert_eq!(
+            register_user_index_shard_impl(
+                &mut canister_data,
+                &super_admin,
+                &this_canister_id,
+                shard,
+                subnet,
+                &SystemTime::UNIX_EPOCH,
+            ),
+            Ok(())
+        );
+
+        assert!(canister_data.user_index_shards.is_registered(&shard));
+        assert_eq!(
+            register_user_index_shard_impl(
+                &mut canister_data,
+                &super_admin,
+                &this_canister_id,
+                shard,
+                subnet,
+                &SystemTime::UNIX_EPOCH,
+            ),
+            Err(UserIndexShardError::ShardAlreadyRegistered)
+        );
+    }
+}
@@ -0,0 +1,105 @@
+use candid::Principal;
+use shared_utils::{
+    common::types::known_principal::KnownPrincipalType,
+    types::canister_specific::user_index::error_types::UserIndexShardError,
+};
+
+use crate::{data_model::CanisterData, CANISTER_DATA};
+
+/// Stops or resumes handing new signups to a shard, e.g. once its subnet is filling up. Users
+/// already on the shard are unaffected.
+///
+/// # Access Control
+/// Only the global super admin can change a shard's signup status
+#[ic_cdk::update]
+#[candid::candid_method(update)]
+fn update_user_index_shard_signup_status(
+    canister_id: Principal,
+    is_accepting_signups: bool,
+) -> Result<(), UserIndexShardError> {
+    let api_caller = ic_cdk::caller();
+
+    CANISTER_DATA.with(|canister_data_ref_cell| {
+        update_user_index_shard_signup_status_impl(
+            &mut canister_data_ref_cell.borrow_mut(),
+            &api_caller,
+            &canister_id,
+            is_accepting_signups,
+        )
+    })
+}
+
+fn update_user_index_shard_signup_status_impl(
+    canister_data: &mut CanisterData,
+    api_caller: &Principal,
+    canister_id: &Principal,
+    is_accepting_signups: bool,
+) -> Result<(), UserIndexShardError> {
+    if canister_data
+        .known_principal_ids
+        .get(&KnownPrincipalType::UserIdGlobalSuperAdmin)
+        != Some(api_caller)
+    {
+        return Err(UserIndexShardError::Unauthorized);
+    }
+
+    canister_data
+        .user_index_shards
+        .update_signup_status(canister_id, is_accepting_signups)
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::SystemTime;
+
+    use test_utils::setup::test_constants::{
+        get_global_super_admin_principal_id, get_mock_user_alice_principal_id,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_update_user_index_shard_signup_status_impl() {
+        let mut canister_data = CanisterData::default();
+        let super_admin = get_global_super_admin_principal_id();
+        let shard = Principal::from_slice(&[2]);
+        canister_data
+            .known_principal_ids
+            .insert(KnownPrincipalType::UserIdGlobalSuperAdmin, super_admin);
+
+        assert_eq!(
+            update_user_index_shard_signup_status_impl(
+                &mut canister_data,
+                &super_admin,
+                &shard,
+                false
+            ),
+            Err(UserIndexShardError::ShardNotFound)
+        );
+
+        canister_data
+            .user_index_shards
+            .register(shard, Principal::from_slice(&[9]), &SystemTime::UNIX_EPOCH)
+            .unwrap();
+
+        assert_eq!(
+            update_user_index_shard_signup_status_impl(
+                &mut canister_data,
+                &get_mock_user_alice_principal_id(),
+                &shard,
+                false
+            ),
+            Err(UserIndexShardError::Unauthorized)
+        );
+        assert_eq!(
+            update_user_index_shard_signup_status_impl(
+                &mut canister_data,
+                &super_admin,
+                &shard,
+                false
+            ),
+            Ok(())
+        );
+        assert!(!canister_data.user_index_shards.get_shards()[0].is_accepting_signups);
+    }
+}
@@ -0,0 +1,139 @@
+use std::time::SystemTime;
+
+use candid::Principal;
+use shared_utils::{
+    canister_specific::user_index::types::username::{
+        ReservedUsernameAppeal, MAX_RESERVED_USERNAME_APPEAL_REASON_LENGTH,
+    },
+    common::utils::{profanity::normalize_for_screening, system_time},
+};
+
+use crate::{data_model::CanisterData, CANISTER_DATA};
+
+/// Asks an admin for a username that is reserved. Appealing again replaces the earlier
+/// appeal for the same username.
+///
+/// # Access Control
+/// Only users with a canister indexed by this canister can appeal
+#[ic_cdk::update]
+#[candid::candid_method(update)]
+fn appeal_reserved_username(username: String, reason: String) -> Result<(), String> {
+    let api_caller = ic_cdk::caller();
+    let current_time = system_time::get_current_system_time_from_ic();
+
+    CANISTER_DATA.with(|canister_data_ref_cell| {
+        appeal_reserved_username_impl(
+            &mut canister_data_ref_cell.borrow_mut(),
+            api_caller,
+            username,
+            reason,
+            current_time,
+        )
+    })
+}
+
+fn appeal_reserved_username_impl(
+    canister_data: &mut CanisterData,
+    api_caller: Principal,
+    username: String,
+    reason: String,
+    current_time: SystemTime,
+) -> Result<(), String> {
+    if canister_data.get_user_canister_id(&api_caller).is_none() {
+        return Err("Unauthorized caller".to_string());
+    }
+
+    let normalized_username = normalize_for_screening(&username);
+    if !canister_data
+        .configuration
+        .reserved_usernames
+        .contains(&normalized_username)
+    {
+        return Err("Username is not reserved".to_string());
+    }
+
+    if reason.trim().is_empty()
+        || reason.chars().count() > MAX_RESERVED_USERNAME_APPEAL_REASON_LENGTH
+    {
+        return Err(format!(
+            "Reason needs to be between 1 and {} characters",
+            MAX_RESERVED_USERNAME_APPEAL_REASON_LENGTH
+        ));
+    }
+
+    canister_data.reserved_username_appeals.insert(
+        (normalized_username, api_caller),
+        ReservedUsernameAppeal {
+            username,
+            user_principal_id: api_caller,
+            reason,
+            appealed_at: current_time,
+        },
+    );
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use test_utils::setup::test_constants::{
+        get_mock_user_alice_canister_id, get_mock_user_alice_principal_id,
+        get_mock_user_bob_principal_id,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_appeal_reserved_username_impl() {
+        let alice = get_mock_user_alice_principal_id();
+        let mut canister_data = CanisterData::default();
+        canister_data
+            .user_principal_id_to_canister_id_map
+            .insert(alice, get_mock_user_alice_canister_id());
+        canister_data
+            .configuration
+            .reserved_usernames
+            .insert("alice".to_string());
+        let reason = "It's my real name".to_string();
+
+        assert!(appeal_reserved_username_impl(
+            &mut canister_data,
+            get_mock_user_bob_principal_id(),
+            "alice".to_string(),
+            reason.clone(),
+            SystemTime::now(),
+        )
+        .is_err());
+        assert!(appeal_reserved_username_impl(
+            &mut canister_data,
+            alice,
+            "not_reserved".to_string(),
+            reason.clone(),
+            SystemTime::now(),
+        )
+        .is_err());
+        assert!(appeal_reserved_username_impl(
+            &mut canister_data,
+            alice,
+            "alice".to_string(),
+            " ".to_string(),
+            SystemTime::now(),
+        )
+        .is_err());
+        assert!(canister_data.reserved_username_appeals.is_empty());
+
+        assert_eq!(
+            appeal_reserved_username_impl(
+                &mut canister_data,
+                alice,
+                "Alice".to_string(),
+                reason.clone(),
+                SystemTime::now(),
+            ),
+            Ok(())
+        );
+        let appeal = &canister_data.reserved_username_appeals[&("alice".to_string(), alice)];
+        assert_eq!(appeal.username, "Alice");
+        assert_eq!(appeal.reason, reason);
+    }
+}
@@ -0,0 +1,16 @@
+use crate::{data_model::canister_creation::CanisterCreationFailureReason, CANISTER_DATA};
+
+/// Number of failed `create_canister` and `install_code` attempts, retries included,
+/// broken down by why they failed
+#[ic_cdk::query]
+#[candid::candid_method(query)]
+fn get_index_details_canister_creation_failures() -> Vec<(CanisterCreationFailureReason, u64)> {
+    CANISTER_DATA.with(|canister_data_ref_cell| {
+        canister_data_ref_cell
+            .borrow()
+            .canister_creation_failures
+            .iter()
+            .map(|(failure_reason, count)| (*failure_reason, *count))
+            .collect()
+    })
+}
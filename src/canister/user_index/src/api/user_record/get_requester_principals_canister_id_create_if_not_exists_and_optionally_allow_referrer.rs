@@ -1,11 +1,20 @@
-use crate::{util::canister_management::create_users_canister, CANISTER_DATA};
+use crate::{
+    api::user_index_shard::create_user_canister_for_routed_signup::create_user_canister_on_shard,
+    data_model::CanisterData,
+    util::{call_queue::queue_call, canister_management::create_users_canister},
+    CANISTER_DATA,
+};
 use candid::Principal;
-use ic_cdk::api::call;
+use shared_utils::canister_specific::{
+    individual_user_template::types::referral::{normalize_referral_code, ReferralDetails},
+    user_index::types::user_index_shard::UserCanisterRoute,
+};
 
 #[ic_cdk::update]
 #[candid::candid_method(update)]
 async fn get_requester_principals_canister_id_create_if_not_exists_and_optionally_allow_referrer(
     referrer: Option<Principal>,
+    referral_code: Option<String>,
 ) -> Principal {
     let api_caller = ic_cdk::caller();
 
@@ -16,50 +25,109 @@ async fn get_requester_principals_canister_id_create_if_not_exists_and_optionall
     let canister_id_for_this_caller = CANISTER_DATA.with(|canister_data_ref_cell| {
         canister_data_ref_cell
             .borrow()
-            .user_principal_id_to_canister_id_map
-            .get(&api_caller)
-            .cloned()
+            .get_user_canister_id(&api_caller)
     });
 
     match canister_id_for_this_caller {
         // * canister already exists
         Some(canister_id) => canister_id,
         None => {
-            // * create new canister
-            let created_canister_id = create_users_canister(api_caller).await;
-
-            CANISTER_DATA.with(|canister_data_ref_cell| {
+            // * create new canister, on whichever user index's turn it is
+            let this_canister_id = ic_cdk::id();
+            let user_index_for_signup = CANISTER_DATA.with(|canister_data_ref_cell| {
                 canister_data_ref_cell
                     .borrow_mut()
-                    .user_principal_id_to_canister_id_map
-                    .insert(api_caller, created_canister_id);
+                    .user_index_shards
+                    .pick_user_index_for_next_signup(this_canister_id)
             });
 
+            let created_canister_id = if user_index_for_signup == this_canister_id {
+                let created_canister_id =
+                    create_users_canister(api_caller)
+                        .await
+                        .unwrap_or_else(|failure_reason| {
+                            panic!(
+                                "Failed to create a canister for this user: {:?}",
+                                failure_reason
+                            )
+                        });
+
+                CANISTER_DATA.with(|canister_data_ref_cell| {
+                    canister_data_ref_cell
+                        .borrow_mut()
+                        .user_principal_id_to_canister_id_map
+                        .insert(api_caller, created_canister_id);
+                });
+
+                created_canister_id
+            } else {
+                let created_canister_id =
+                    create_user_canister_on_shard(user_index_for_signup, api_caller)
+                        .await
+                        .unwrap_or_else(|message| panic!("{}", message));
+
+                CANISTER_DATA.with(|canister_data_ref_cell| {
+                    let mut canister_data = canister_data_ref_cell.borrow_mut();
+                    canister_data.user_shard_routing_table.insert(
+                        api_caller,
+                        UserCanisterRoute {
+                            user_index_canister_id: user_index_for_signup,
+                            user_canister_id: created_canister_id,
+                        },
+                    );
+                    canister_data
+                        .user_index_shards
+                        .record_user_routed(&user_index_for_signup);
+                });
+
+                created_canister_id
+            };
+
             // * reward user for signing up
-            call::notify(created_canister_id, "get_rewarded_for_signing_up", ()).ok();
+            queue_call(created_canister_id, "get_rewarded_for_signing_up", ());
+
+            let (referrer, referral_code) = CANISTER_DATA.with(|canister_data_ref_cell| {
+                resolve_referrer_impl(&canister_data_ref_cell.borrow(), referrer, referral_code)
+            });
 
             // * reward referrer for referring
             if let Some(referrer_principal_id) = referrer {
                 let referrer_canister_id = CANISTER_DATA.with(|canister_data_ref_cell| {
                     canister_data_ref_cell
                         .borrow()
-                        .user_principal_id_to_canister_id_map
-                        .get(&referrer_principal_id)
-                        .cloned()
+                        .get_user_canister_id(&referrer_principal_id)
                 });
                 if let Some(referrer_canister_id) = referrer_canister_id {
-                    call::notify(
+                    queue_call(
                         referrer_canister_id,
                         "get_rewarded_for_referral",
                         (referrer_principal_id, api_caller),
-                    )
-                    .ok();
-                    call::notify(
+                    );
+                    queue_call(
                         created_canister_id,
                         "get_rewarded_for_referral",
                         (referrer_principal_id, api_caller),
-                    )
-                    .ok();
+                    );
+
+                    // * let both canisters know about each other so that the referrer
+                    // * can be paid their share of the referee's bet winnings
+                    let referral_details = ReferralDetails {
+                        referrer_principal_id,
+                        referrer_canister_id,
+                        referee_principal_id: api_caller,
+                        referee_canister_id: created_canister_id,
+                        referral_code,
+                    };
+                    queue_call(
+                        referrer_canister_id,
+                        "receive_referral_details",
+                        (referral_details.clone(),),
+                    );
+                    queue_call(
+                        created_canister_id,
+                        "receive_referral_details",
+                        (referral_details,),
+                    );
                 }
             }
 
@@ -68,8 +136,31 @@ async fn get_requester_principals_canister_id_create_if_not_exists_and_optionall
     }
 }
 
+/// A referral code takes precedence over a referrer passed in directly. Codes that don't
+/// resolve are dropped.
+fn resolve_referrer_impl(
+    canister_data: &CanisterData,
+    referrer: Option<Principal>,
+    referral_code: Option<String>,
+) -> (Option<Principal>, Option<String>) {
+    let Some(referral_code) =
+        referral_code.map(|referral_code| normalize_referral_code(&referral_code))
+    else {
+        return (referrer, None);
+    };
+
+    match canister_data.referral_code_owners.get(&referral_code) {
+        Some(referral_code_owner) => (Some(*referral_code_owner), Some(referral_code)),
+        None => (referrer, None),
+    }
+}
+
 #[cfg(test)]
 mod test {
+    use test_utils::setup::test_constants::{
+        get_mock_user_alice_principal_id, get_mock_user_bob_principal_id,
+    };
+
     use super::*;
 
     #[test]
@@ -80,4 +171,32 @@ mod test {
             Principal::anonymous()
         );
     }
+
+    #[test]
+    fn test_resolve_referrer_impl() {
+        let mut canister_data = CanisterData::default();
+        canister_data
+            .referral_code_owners
+            .insert("AB3K9Q2Z".to_string(), get_mock_user_alice_principal_id());
+
+        assert_eq!(
+            resolve_referrer_impl(&canister_data, Some(get_mock_user_bob_principal_id()), None),
+            (Some(get_mock_user_bob_principal_id()), None)
+        );
+        assert_eq!(
+            resolve_referrer_impl(
+                &canister_data,
+                Some(get_mock_user_bob_principal_id()),
+                Some(" ab3k9q2z".to_string())
+            ),
+            (
+                Some(get_mock_user_alice_principal_id()),
+                Some("AB3K9Q2Z".to_string())
+            )
+        );
+        assert_eq!(
+            resolve_referrer_impl(&canister_data, None, Some("UNKNOWN2".to_string())),
+            (None, None)
+        );
+    }
 }
@@ -0,0 +1,123 @@
+use candid::Principal;
+use shared_utils::{
+    canister_specific::user_index::types::username::ReservedUsernameAppeal,
+    common::types::known_principal::KnownPrincipalType,
+    pagination::{self, Page, MAX_ITEMS_PER_PAGE},
+};
+
+use crate::{data_model::CanisterData, CANISTER_DATA};
+
+/// Only the first `MAX_ITEMS_PER_PAGE` appeals. Use `get_reserved_username_appeals_paginated`
+/// to page through all of them.
+///
+/// # Access Control
+/// Only the global super admin can review appeals
+#[ic_cdk::query]
+#[candid::candid_method(query)]
+fn get_reserved_username_appeals() -> Result<Vec<ReservedUsernameAppeal>, String> {
+    let api_caller = ic_cdk::caller();
+
+    CANISTER_DATA.with(|canister_data_ref_cell| {
+        get_reserved_username_appeals_impl(
+            &canister_data_ref_cell.borrow(),
+            &api_caller,
+            0,
+            MAX_ITEMS_PER_PAGE,
+        )
+        .map(|page| page.items)
+    })
+}
+
+/// # Access Control
+/// Only the global super admin can review appeals
+#[ic_cdk::query]
+#[candid::candid_method(query)]
+fn get_reserved_username_appeals_paginated(
+    from_inclusive_index: u64,
+    page_size: u64,
+) -> Result<Page<ReservedUsernameAppeal>, String> {
+    let api_caller = ic_cdk::caller();
+
+    CANISTER_DATA.with(|canister_data_ref_cell| {
+        get_reserved_username_appeals_impl(
+            &canister_data_ref_cell.borrow(),
+            &api_caller,
+            from_inclusive_index,
+            page_size,
+        )
+    })
+}
+
+fn get_reserved_username_appeals_impl(
+    canister_data: &CanisterData,
+    api_caller: &Principal,
+    from_inclusive_index: u64,
+    page_size: u64,
+) -> Result<Page<ReservedUsernameAppeal>, String> {
+    if canister_data
+        .known_principal_ids
+        .get(&KnownPrincipalType::UserIdGlobalSuperAdmin)
+        != Some(api_caller)
+    {
+        return Err("Unauthorized caller".to_string());
+    }
+
+    Ok(pagination::get_page(
+        canister_data.reserved_username_appeals.values().cloned(),
+        from_inclusive_index,
+        page_size,
+    ))
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::SystemTime;
+
+    use test_utils::setup::test_constants::{
+        get_global_super_admin_principal_id, get_mock_user_alice_principal_id,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_get_reserved_username_appeals_impl() {
+        let mut canister_data = CanisterData::default();
+        canister_data.known_principal_ids.insert(
+            KnownPrincipalType::UserIdGlobalSuperAdmin,
+            get_global_super_admin_principal_id(),
+        );
+        let appeal = ReservedUsernameAppeal {
+            username: "alice".to_string(),
+            user_principal_id: get_mock_user_alice_principal_id(),
+            reason: "It's my real name".to_string(),
+            appealed_at: SystemTime::now(),
+        };
+        canister_data.reserved_username_appeals.insert(
+            ("alice".to_string(), get_mock_user_alice_principal_id()),
+            appeal.clone(),
+        );
+
+        assert_eq!(
+            get_reserved_username_appeals_impl(
+                &canister_data,
+                &get_mock_user_alice_principal_id(),
+                0,
+                10
+            ),
+            Err("Unauthorized caller".to_string())
+        );
+        assert_eq!(
+            get_reserved_username_appeals_impl(
+                &canister_data,
+                &get_global_super_admin_principal_id(),
+                0,
+                10
+            ),
+            Ok(Page {
+                items: vec![appeal],
+                total_count: 1,
+                next_from_inclusive_index: None,
+            })
+        );
+    }
+}
@@ -0,0 +1,16 @@
+use candid::Principal;
+
+use crate::CANISTER_DATA;
+
+/// Bumped every time the canister is reinstalled. Individual user canisters pass along the
+/// epoch they last resolved for the canister they call, and re-resolve it here when the call
+/// is turned away as stale.
+#[ic_cdk::query]
+#[candid::candid_method(query)]
+fn get_user_canister_binding_epoch(user_canister_id: Principal) -> u64 {
+    CANISTER_DATA.with(|canister_data_ref_cell| {
+        canister_data_ref_cell
+            .borrow()
+            .get_binding_epoch(&user_canister_id)
+    })
+}
@@ -19,10 +19,7 @@ fn get_user_canister_id_from_unique_user_name_impl(
         .get(&user_name)
         .cloned()?;
 
-    canister_data
-        .user_principal_id_to_canister_id_map
-        .get(&profile_principal_id)
-        .cloned()
+    canister_data.get_user_canister_id(&profile_principal_id)
 }
 
 #[cfg(test)]
@@ -1,9 +1,8 @@
-use std::collections::BTreeMap;
-
 use candid::Principal;
 
-use crate::CANISTER_DATA;
+use crate::{data_model::CanisterData, CANISTER_DATA};
 
+/// Also answers for users whose canisters were created by a shard, from the routing table
 #[ic_cdk::query]
 #[candid::candid_method(query)]
 fn get_user_canister_id_from_user_principal_id(user_id: Principal) -> Option<Principal> {
@@ -12,30 +11,25 @@ fn get_user_canister_id_from_user_principal_id(user_id: Principal) -> Option<Pri
     }
 
     CANISTER_DATA.with(|canister_data_ref_cell| {
-        get_user_canister_id_from_user_principal_id_impl(
-            user_id,
-            &canister_data_ref_cell
-                .borrow()
-                .user_principal_id_to_canister_id_map,
-        )
+        get_user_canister_id_from_user_principal_id_impl(user_id, &canister_data_ref_cell.borrow())
     })
 }
 
 fn get_user_canister_id_from_user_principal_id_impl(
     user_id: Principal,
-    user_principal_id_to_canister_id_map: &BTreeMap<Principal, Principal>,
+    canister_data: &CanisterData,
 ) -> Option<Principal> {
-    user_principal_id_to_canister_id_map.get(&user_id).cloned()
+    canister_data.get_user_canister_id(&user_id)
 }
 
 #[cfg(test)]
 mod test {
+    use shared_utils::canister_specific::user_index::types::user_index_shard::UserCanisterRoute;
     use test_utils::setup::test_constants::{
         get_mock_user_alice_canister_id, get_mock_user_alice_principal_id,
+        get_mock_user_bob_canister_id, get_mock_user_bob_principal_id,
     };
 
-    use crate::data_model::CanisterData;
-
     use super::*;
 
     #[test]
@@ -45,7 +39,7 @@ mod test {
         assert_eq!(
             get_user_canister_id_from_user_principal_id_impl(
                 get_mock_user_alice_principal_id(),
-                &canister_data.user_principal_id_to_canister_id_map
+                &canister_data
             ),
             None
         );
@@ -58,9 +52,26 @@ mod test {
         assert_eq!(
             get_user_canister_id_from_user_principal_id_impl(
                 get_mock_user_alice_principal_id(),
-                &canister_data.user_principal_id_to_canister_id_map
+                &canister_data
             ),
             Some(get_mock_user_alice_canister_id())
         );
+
+        // * users that signed up through a shard are found in the routing table
+        canister_data.user_shard_routing_table.insert(
+            get_mock_user_bob_principal_id(),
+            UserCanisterRoute {
+                user_index_canister_id: Principal::from_slice(&[2]),
+                user_canister_id: get_mock_user_bob_canister_id(),
+            },
+        );
+
+        assert_eq!(
+            get_user_canister_id_from_user_principal_id_impl(
+                get_mock_user_bob_principal_id(),
+                &canister_data
+            ),
+            Some(get_mock_user_bob_canister_id())
+        );
     }
 }
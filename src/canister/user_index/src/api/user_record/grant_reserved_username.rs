@@ -0,0 +1,132 @@
+use candid::Principal;
+use shared_utils::common::{
+    types::known_principal::KnownPrincipalType, utils::profanity::normalize_for_screening,
+};
+
+use crate::{data_model::CanisterData, CANISTER_DATA};
+
+/// Lets a user claim a reserved username once an admin has verified them, e.g. after an
+/// appeal. The user still has to set it from their own canister. Granting the username to
+/// someone else takes it back from whoever had it granted before.
+///
+/// # Access Control
+/// Only the global super admin can grant reserved usernames
+#[ic_cdk::update]
+#[candid::candid_method(update)]
+fn grant_reserved_username(username: String, user_principal_id: Principal) -> Result<(), String> {
+    let api_caller = ic_cdk::caller();
+
+    CANISTER_DATA.with(|canister_data_ref_cell| {
+        grant_reserved_username_impl(
+            &mut canister_data_ref_cell.borrow_mut(),
+            &api_caller,
+            username,
+            user_principal_id,
+        )
+    })
+}
+
+fn grant_reserved_username_impl(
+    canister_data: &mut CanisterData,
+    api_caller: &Principal,
+    username: String,
+    user_principal_id: Principal,
+) -> Result<(), String> {
+    if canister_data
+        .known_principal_ids
+        .get(&KnownPrincipalType::UserIdGlobalSuperAdmin)
+        != Some(api_caller)
+    {
+        return Err("Unauthorized caller".to_string());
+    }
+
+    if canister_data
+        .get_user_canister_id(&user_principal_id)
+        .is_none()
+    {
+        return Err("User canister entry does not exist".to_string());
+    }
+
+    let normalized_username = normalize_for_screening(&username);
+    if !canister_data
+        .configuration
+        .reserved_usernames
+        .contains(&normalized_username)
+    {
+        return Err("Username is not reserved".to_string());
+    }
+
+    canister_data
+        .reserved_username_appeals
+        .remove(&(normalized_username.clone(), user_principal_id));
+    canister_data
+        .reserved_username_grants
+        .insert(normalized_username, user_principal_id);
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::SystemTime;
+
+    use shared_utils::canister_specific::user_index::types::username::ReservedUsernameAppeal;
+    use test_utils::setup::test_constants::{
+        get_global_super_admin_principal_id, get_mock_user_alice_canister_id,
+        get_mock_user_alice_principal_id, get_mock_user_bob_principal_id,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_grant_reserved_username_impl() {
+        let alice = get_mock_user_alice_principal_id();
+        let admin = get_global_super_admin_principal_id();
+        let mut canister_data = CanisterData::default();
+        canister_data
+            .known_principal_ids
+            .insert(KnownPrincipalType::UserIdGlobalSuperAdmin, admin);
+        canister_data
+            .user_principal_id_to_canister_id_map
+            .insert(alice, get_mock_user_alice_canister_id());
+        canister_data
+            .configuration
+            .reserved_usernames
+            .insert("alice".to_string());
+        canister_data.reserved_username_appeals.insert(
+            ("alice".to_string(), alice),
+            ReservedUsernameAppeal {
+                username: "alice".to_string(),
+                user_principal_id: alice,
+                reason: "It's my real name".to_string(),
+                appealed_at: SystemTime::now(),
+            },
+        );
+
+        assert!(
+            grant_reserved_username_impl(&mut canister_data, &alice, "alice".into(), alice)
+                .is_err()
+        );
+        assert!(grant_reserved_username_impl(
+            &mut canister_data,
+            &admin,
+            "alice".into(),
+            get_mock_user_bob_principal_id()
+        )
+        .is_err());
+        assert!(
+            grant_reserved_username_impl(&mut canister_data, &admin, "bob".into(), alice).is_err()
+        );
+        assert!(canister_data.reserved_username_grants.is_empty());
+
+        assert_eq!(
+            grant_reserved_username_impl(&mut canister_data, &admin, "ALICE".into(), alice),
+            Ok(())
+        );
+        assert_eq!(
+            canister_data.reserved_username_grants.get("alice"),
+            Some(&alice)
+        );
+        assert!(canister_data.reserved_username_appeals.is_empty());
+    }
+}
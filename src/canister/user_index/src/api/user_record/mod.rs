@@ -1,6 +1,14 @@
+pub mod appeal_reserved_username;
+pub mod get_index_details_canister_creation_failures;
 pub mod get_index_details_is_user_name_taken;
 pub mod get_requester_principals_canister_id_create_if_not_exists_and_optionally_allow_referrer;
+pub mod get_reserved_username_appeals;
+pub mod get_user_canister_binding_epoch;
 pub mod get_user_canister_id_from_unique_user_name;
 pub mod get_user_canister_id_from_user_principal_id;
 pub mod get_user_index_canister_count;
+pub mod grant_reserved_username;
+pub mod register_referral_code;
 pub mod update_index_with_unique_user_name_corresponding_to_user_principal_id;
+pub mod update_user_canister_read_only_mode;
+pub mod update_user_shadow_ban_status;
@@ -0,0 +1,144 @@
+use candid::Principal;
+use shared_utils::types::canister_specific::user_index::error_types::RegisterReferralCodeError;
+
+use crate::{data_model::CanisterData, CANISTER_DATA};
+
+// * Letters and digits that can't be mistaken for one another when read out or typed in
+const REFERRAL_CODE_ALPHABET: &[u8; 32] = b"23456789ABCDEFGHJKLMNPQRSTUVWXYZ";
+const REFERRAL_CODE_LENGTH: usize = 8;
+// * Odd, so multiplying by it modulo 2^40 maps every code number to a different code
+const REFERRAL_CODE_NUMBER_MULTIPLIER: u64 = 0x9E37_79B9_7F4B;
+
+/// Hands out a new referral code pointing at the user. Naming and counting what the code
+/// brings in is left to the user's own canister.
+///
+/// # Access Control
+/// Only the user's own canister can register codes for them
+#[ic_cdk::update]
+#[candid::candid_method(update)]
+fn register_referral_code(
+    user_principal_id: Principal,
+) -> Result<String, RegisterReferralCodeError> {
+    let request_makers_canister_id = ic_cdk::caller();
+
+    CANISTER_DATA.with(|canister_data_ref_cell| {
+        register_referral_code_impl(
+            &mut canister_data_ref_cell.borrow_mut(),
+            user_principal_id,
+            request_makers_canister_id,
+        )
+    })
+}
+
+fn register_referral_code_impl(
+    canister_data: &mut CanisterData,
+    user_principal_id: Principal,
+    request_makers_canister_id: Principal,
+) -> Result<String, RegisterReferralCodeError> {
+    match canister_data.get_user_canister_id(&user_principal_id) {
+        None => return Err(RegisterReferralCodeError::UserCanisterEntryDoesNotExist),
+        Some(user_canister_id) if user_canister_id != request_makers_canister_id => {
+            return Err(RegisterReferralCodeError::SendingCanisterDoesNotMatchUserCanisterId)
+        }
+        Some(_) => {}
+    }
+
+    let referral_code = get_referral_code_for_number(canister_data.next_referral_code_number);
+    canister_data.next_referral_code_number += 1;
+
+    canister_data
+        .referral_code_owners
+        .insert(referral_code.clone(), user_principal_id);
+
+    Ok(referral_code)
+}
+
+/// Consecutive code numbers come out looking unrelated, and no two numbers below 2^40 share
+/// a code
+fn get_referral_code_for_number(referral_code_number: u64) -> String {
+    let mut scrambled_number = referral_code_number.wrapping_mul(REFERRAL_CODE_NUMBER_MULTIPLIER)
+        & ((1 << (5 * REFERRAL_CODE_LENGTH)) - 1);
+
+    let mut referral_code = Vec::with_capacity(REFERRAL_CODE_LENGTH);
+    for _ in 0..REFERRAL_CODE_LENGTH {
+        referral_code.push(REFERRAL_CODE_ALPHABET[(scrambled_number & 31) as usize]);
+        scrambled_number >>= 5;
+    }
+
+    String::from_utf8(referral_code).unwrap()
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::BTreeSet;
+
+    use test_utils::setup::test_constants::{
+        get_mock_user_alice_canister_id, get_mock_user_alice_principal_id,
+        get_mock_user_bob_canister_id, get_mock_user_bob_principal_id,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_register_referral_code_impl() {
+        let mut canister_data = CanisterData::default();
+        canister_data.user_principal_id_to_canister_id_map.insert(
+            get_mock_user_alice_principal_id(),
+            get_mock_user_alice_canister_id(),
+        );
+
+        assert_eq!(
+            register_referral_code_impl(
+                &mut canister_data,
+                get_mock_user_bob_principal_id(),
+                get_mock_user_bob_canister_id(),
+            ),
+            Err(RegisterReferralCodeError::UserCanisterEntryDoesNotExist)
+        );
+        assert_eq!(
+            register_referral_code_impl(
+                &mut canister_data,
+                get_mock_user_alice_principal_id(),
+                get_mock_user_bob_canister_id(),
+            ),
+            Err(RegisterReferralCodeError::SendingCanisterDoesNotMatchUserCanisterId)
+        );
+
+        let first_referral_code = register_referral_code_impl(
+            &mut canister_data,
+            get_mock_user_alice_principal_id(),
+            get_mock_user_alice_canister_id(),
+        )
+        .unwrap();
+        let second_referral_code = register_referral_code_impl(
+            &mut canister_data,
+            get_mock_user_alice_principal_id(),
+            get_mock_user_alice_canister_id(),
+        )
+        .unwrap();
+
+        assert_ne!(first_referral_code, second_referral_code);
+        assert_eq!(canister_data.referral_code_owners.len(), 2);
+        assert_eq!(
+            canister_data
+                .referral_code_owners
+                .get(&second_referral_code),
+            Some(&get_mock_user_alice_principal_id())
+        );
+    }
+
+    #[test]
+    fn test_get_referral_code_for_number() {
+        let referral_codes = (0..10_000)
+            .map(get_referral_code_for_number)
+            .collect::<BTreeSet<_>>();
+
+        assert_eq!(referral_codes.len(), 10_000);
+        assert!(referral_codes.iter().all(|referral_code| {
+            referral_code.len() == REFERRAL_CODE_LENGTH
+                && referral_code
+                    .bytes()
+                    .all(|character| REFERRAL_CODE_ALPHABET.contains(&character))
+        }));
+    }
+}
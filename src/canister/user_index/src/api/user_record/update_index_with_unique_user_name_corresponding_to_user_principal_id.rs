@@ -1,5 +1,8 @@
 use candid::Principal;
-use shared_utils::types::canister_specific::user_index::error_types::SetUniqueUsernameError;
+use shared_utils::{
+    common::utils::profanity::{contains_profanity, normalize_for_screening},
+    types::canister_specific::user_index::error_types::SetUniqueUsernameError,
+};
 
 use crate::{data_model::CanisterData, CANISTER_DATA};
 
@@ -27,21 +30,31 @@ fn update_index_with_unique_user_name_corresponding_to_user_principal_id_impl(
     request_makers_canister_id: Principal,
     canister_data: &mut CanisterData,
 ) -> Result<(), SetUniqueUsernameError> {
-    if !canister_data
-        .user_principal_id_to_canister_id_map
-        .contains_key(&user_principal_id)
-    {
+    let Some(user_canister_id) = canister_data.get_user_canister_id(&user_principal_id) else {
         return Err(SetUniqueUsernameError::UserCanisterEntryDoesNotExist);
-    }
+    };
 
-    if canister_data
-        .user_principal_id_to_canister_id_map
-        .get(&user_principal_id)
-        != Some(&request_makers_canister_id)
-    {
+    if user_canister_id != request_makers_canister_id {
         return Err(SetUniqueUsernameError::SendingCanisterDoesNotMatchUserCanisterId);
     }
 
+    if contains_profanity(&unique_user_name) {
+        return Err(SetUniqueUsernameError::UsernameContainsProfanity);
+    }
+
+    let normalized_user_name = normalize_for_screening(&unique_user_name);
+    let is_reserved = canister_data
+        .configuration
+        .reserved_usernames
+        .contains(&normalized_user_name);
+    let is_granted_to_user = canister_data
+        .reserved_username_grants
+        .get(&normalized_user_name)
+        == Some(&user_principal_id);
+    if is_reserved && !is_granted_to_user {
+        return Err(SetUniqueUsernameError::UsernameReserved);
+    }
+
     if canister_data
         .unique_user_name_to_user_principal_id_map
         .contains_key(&unique_user_name)
@@ -53,6 +66,15 @@ fn update_index_with_unique_user_name_corresponding_to_user_principal_id_impl(
         .unique_user_name_to_user_principal_id_map
         .insert(unique_user_name.clone(), user_principal_id);
 
+    if is_granted_to_user {
+        canister_data
+            .reserved_username_grants
+            .remove(&normalized_user_name);
+        canister_data
+            .reserved_username_appeals
+            .retain(|(appealed_username, _), _| *appealed_username != normalized_user_name);
+    }
+
     Ok(())
 }
 
@@ -132,4 +154,64 @@ mod test {
             &user_principal_id
         );
     }
+
+    #[test]
+    fn test_reserved_and_profane_usernames_are_rejected() {
+        let alice = get_mock_user_alice_principal_id();
+        let bob = get_mock_user_bob_principal_id();
+        let mut canister_data = CanisterData::default();
+        canister_data
+            .user_principal_id_to_canister_id_map
+            .insert(alice, get_mock_user_alice_canister_id());
+        canister_data
+            .user_principal_id_to_canister_id_map
+            .insert(bob, get_mock_user_bob_canister_id());
+        canister_data
+            .configuration
+            .reserved_usernames
+            .insert("hotornot".to_string());
+        canister_data
+            .reserved_username_grants
+            .insert("hotornot".to_string(), bob);
+
+        assert_eq!(
+            update_index_with_unique_user_name_corresponding_to_user_principal_id_impl(
+                "sh1t_poster".to_string(),
+                alice,
+                get_mock_user_alice_canister_id(),
+                &mut canister_data,
+            ),
+            Err(SetUniqueUsernameError::UsernameContainsProfanity)
+        );
+        // * look-alikes of a reserved name are reserved too
+        assert_eq!(
+            update_index_with_unique_user_name_corresponding_to_user_principal_id_impl(
+                "Hot_Or_N0t".to_string(),
+                alice,
+                get_mock_user_alice_canister_id(),
+                &mut canister_data,
+            ),
+            Err(SetUniqueUsernameError::UsernameReserved)
+        );
+        assert!(canister_data
+            .unique_user_name_to_user_principal_id_map
+            .is_empty());
+
+        assert_eq!(
+            update_index_with_unique_user_name_corresponding_to_user_principal_id_impl(
+                "hot_or_not".to_string(),
+                bob,
+                get_mock_user_bob_canister_id(),
+                &mut canister_data,
+            ),
+            Ok(())
+        );
+        assert_eq!(
+            canister_data
+                .unique_user_name_to_user_principal_id_map
+                .get("hot_or_not"),
+            Some(&bob)
+        );
+        assert!(canister_data.reserved_username_grants.is_empty());
+    }
 }
@@ -0,0 +1,99 @@
+use candid::Principal;
+use shared_utils::common::types::known_principal::KnownPrincipalType;
+
+use crate::{data_model::CanisterData, CANISTER_DATA};
+
+/// Puts a user's canister in read only mode while it is being migrated or restored, or takes
+/// it back out. The canister keeps serving queries but rejects updates with `ReadOnlyMode`.
+///
+/// # Access Control
+/// Only the global super admin can change the mode
+#[ic_cdk::update]
+#[candid::candid_method(update)]
+async fn update_user_canister_read_only_mode(
+    user_principal_id: Principal,
+    is_read_only: bool,
+) -> Result<(), String> {
+    let api_caller = ic_cdk::caller();
+
+    let user_canister_id = CANISTER_DATA.with(|canister_data_ref_cell| {
+        get_user_canister_id_to_update_read_only_mode_of(
+            &canister_data_ref_cell.borrow(),
+            &api_caller,
+            &user_principal_id,
+        )
+    })?;
+
+    ic_cdk::call::<_, ()>(user_canister_id, "update_read_only_mode", (is_read_only,))
+        .await
+        .map_err(|(_, message)| {
+            format!(
+                "Failed to update read only mode on the user's canister: {}",
+                message
+            )
+        })
+}
+
+fn get_user_canister_id_to_update_read_only_mode_of(
+    canister_data: &CanisterData,
+    api_caller: &Principal,
+    user_principal_id: &Principal,
+) -> Result<Principal, String> {
+    if canister_data
+        .known_principal_ids
+        .get(&KnownPrincipalType::UserIdGlobalSuperAdmin)
+        != Some(api_caller)
+    {
+        return Err("Unauthorized caller".to_string());
+    }
+
+    canister_data
+        .user_principal_id_to_canister_id_map
+        .get(user_principal_id)
+        .copied()
+        .ok_or_else(|| "User is not indexed by this user index".to_string())
+}
+
+#[cfg(test)]
+mod test {
+    use test_utils::setup::test_constants::{
+        get_global_super_admin_principal_id, get_mock_user_alice_canister_id,
+        get_mock_user_alice_principal_id, get_mock_user_bob_principal_id,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_get_user_canister_id_to_update_read_only_mode_of() {
+        let mut canister_data = CanisterData::default();
+        canister_data.known_principal_ids.insert(
+            KnownPrincipalType::UserIdGlobalSuperAdmin,
+            get_global_super_admin_principal_id(),
+        );
+        canister_data.user_principal_id_to_canister_id_map.insert(
+            get_mock_user_alice_principal_id(),
+            get_mock_user_alice_canister_id(),
+        );
+
+        assert!(get_user_canister_id_to_update_read_only_mode_of(
+            &canister_data,
+            &get_mock_user_alice_principal_id(),
+            &get_mock_user_alice_principal_id(),
+        )
+        .is_err());
+        assert!(get_user_canister_id_to_update_read_only_mode_of(
+            &canister_data,
+            &get_global_super_admin_principal_id(),
+            &get_mock_user_bob_principal_id(),
+        )
+        .is_err());
+        assert_eq!(
+            get_user_canister_id_to_update_read_only_mode_of(
+                &canister_data,
+                &get_global_super_admin_principal_id(),
+                &get_mock_user_alice_principal_id(),
+            ),
+            Ok(get_mock_user_alice_canister_id())
+        );
+    }
+}
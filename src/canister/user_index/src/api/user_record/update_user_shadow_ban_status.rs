@@ -0,0 +1,138 @@
+use candid::Principal;
+use shared_utils::common::types::known_principal::KnownPrincipalType;
+
+use crate::{data_model::CanisterData, CANISTER_DATA};
+
+/// Shadow banned users can keep betting as usual, but their bets land in phantom rooms that
+/// never pay out or affect outcomes. The flag is pushed to the user's own canister, which
+/// routes their bets accordingly.
+///
+/// # Access Control
+/// Only the global super admin can shadow ban users
+#[ic_cdk::update]
+#[candid::candid_method(update)]
+async fn update_user_shadow_ban_status(
+    user_principal_id: Principal,
+    is_shadow_banned: bool,
+) -> Result<(), String> {
+    let api_caller = ic_cdk::caller();
+
+    let user_canister_id = CANISTER_DATA.with(|canister_data_ref_cell| {
+        update_user_shadow_ban_status_impl(
+            &mut canister_data_ref_cell.borrow_mut(),
+            &api_caller,
+            &user_principal_id,
+            is_shadow_banned,
+        )
+    })?;
+
+    // * The index keeps the flag even if this call fails, so calling again retries the push
+    ic_cdk::call::<_, ()>(
+        user_canister_id,
+        "update_profile_shadow_ban_status",
+        (is_shadow_banned,),
+    )
+    .await
+    .map_err(|(_, message)| {
+        format!(
+            "Failed to update shadow ban status on the user's canister: {}",
+            message
+        )
+    })
+}
+
+/// Returns the canister id of the user the flag needs to be pushed to
+fn update_user_shadow_ban_status_impl(
+    canister_data: &mut CanisterData,
+    api_caller: &Principal,
+    user_principal_id: &Principal,
+    is_shadow_banned: bool,
+) -> Result<Principal, String> {
+    if canister_data
+        .known_principal_ids
+        .get(&KnownPrincipalType::UserIdGlobalSuperAdmin)
+        != Some(api_caller)
+    {
+        return Err("Unauthorized caller".to_string());
+    }
+
+    let user_canister_id = *canister_data
+        .user_principal_id_to_canister_id_map
+        .get(user_principal_id)
+        .ok_or_else(|| "User is not indexed by this user index".to_string())?;
+
+    if is_shadow_banned {
+        canister_data
+            .shadow_banned_user_principal_ids
+            .insert(*user_principal_id);
+    } else {
+        canister_data
+            .shadow_banned_user_principal_ids
+            .remove(user_principal_id);
+    }
+
+    Ok(user_canister_id)
+}
+
+#[cfg(test)]
+mod test {
+    use test_utils::setup::test_constants::{
+        get_global_super_admin_principal_id, get_mock_user_alice_canister_id,
+        get_mock_user_alice_principal_id, get_mock_user_bob_principal_id,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_update_user_shadow_ban_status_impl() {
+        let mut canister_data = CanisterData::default();
+        canister_data.known_principal_ids.insert(
+            KnownPrincipalType::UserIdGlobalSuperAdmin,
+            get_global_super_admin_principal_id(),
+        );
+        canister_data.user_principal_id_to_canister_id_map.insert(
+            get_mock_user_alice_principal_id(),
+            get_mock_user_alice_canister_id(),
+        );
+
+        assert!(update_user_shadow_ban_status_impl(
+            &mut canister_data,
+            &get_mock_user_alice_principal_id(),
+            &get_mock_user_alice_principal_id(),
+            true
+        )
+        .is_err());
+        assert!(update_user_shadow_ban_status_impl(
+            &mut canister_data,
+            &get_global_super_admin_principal_id(),
+            &get_mock_user_bob_principal_id(),
+            true
+        )
+        .is_err());
+        assert!(canister_data.shadow_banned_user_principal_ids.is_empty());
+
+        assert_eq!(
+            update_user_shadow_ban_status_impl(
+                &mut canister_data,
+                &get_global_super_admin_principal_id(),
+                &get_mock_user_alice_principal_id(),
+                true
+            ),
+            Ok(get_mock_user_alice_canister_id())
+        );
+        assert!(canister_data
+            .shadow_banned_user_principal_ids
+            .contains(&get_mock_user_alice_principal_id()));
+
+        assert_eq!(
+            update_user_shadow_ban_status_impl(
+                &mut canister_data,
+                &get_global_super_admin_principal_id(),
+                &get_mock_user_alice_principal_id(),
+                false
+            ),
+            Ok(get_mock_user_alice_canister_id())
+        );
+        assert!(canister_data.shadow_banned_user_principal_ids.is_empty());
+    }
+}
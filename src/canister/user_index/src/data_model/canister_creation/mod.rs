@@ -0,0 +1,95 @@
+use candid::{CandidType, Deserialize};
+use ic_cdk::api::call::RejectionCode;
+use serde::Serialize;
+
+#[derive(
+    CandidType, Deserialize, Serialize, Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash,
+)]
+pub enum CanisterCreationFailureReason {
+    OutOfCycles,
+    SubnetFull,
+    InvalidWasm,
+    Transient,
+    Other,
+}
+
+impl CanisterCreationFailureReason {
+    /// Classifies a rejected `create_canister` or `install_code` call from the reject code
+    /// and message returned by the management canister
+    pub fn classify(rejection_code: RejectionCode, message: &str) -> Self {
+        let message = message.to_lowercase();
+
+        if message.contains("out of cycles")
+            || message.contains("insufficient cycles")
+            || message.contains("should have at least")
+        {
+            Self::OutOfCycles
+        } else if message.contains("oversubscribed")
+            || message.contains("memory capacity")
+            || message.contains("maximum number of canisters")
+        {
+            Self::SubnetFull
+        } else if message.contains("wasm")
+            && (message.contains("invalid")
+                || message.contains("decode")
+                || message.contains("validation"))
+        {
+            Self::InvalidWasm
+        } else if matches!(rejection_code, RejectionCode::SysTransient) {
+            Self::Transient
+        } else {
+            Self::Other
+        }
+    }
+
+    /// Retrying only helps when the failure wasn't caused by this index or the subnet it
+    /// lives on. Running out of cycles, a full subnet and a bad wasm all need an operator.
+    pub fn get_max_retries(&self) -> u8 {
+        match self {
+            Self::OutOfCycles | Self::SubnetFull | Self::InvalidWasm => 0,
+            Self::Transient => 3,
+            Self::Other => 1,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_classify() {
+        assert_eq!(
+            CanisterCreationFailureReason::classify(
+                RejectionCode::CanisterError,
+                "Canister rimrc-piaaa-aaaao-aaljq-cai is out of cycles"
+            ),
+            CanisterCreationFailureReason::OutOfCycles
+        );
+        assert_eq!(
+            CanisterCreationFailureReason::classify(
+                RejectionCode::CanisterReject,
+                "Canister installation failed with `Subnet is oversubscribed`"
+            ),
+            CanisterCreationFailureReason::SubnetFull
+        );
+        assert_eq!(
+            CanisterCreationFailureReason::classify(
+                RejectionCode::CanisterReject,
+                "Wasm module of canister is invalid"
+            ),
+            CanisterCreationFailureReason::InvalidWasm
+        );
+        assert_eq!(
+            CanisterCreationFailureReason::classify(
+                RejectionCode::SysTransient,
+                "Couldn't send message"
+            ),
+            CanisterCreationFailureReason::Transient
+        );
+        assert_eq!(
+            CanisterCreationFailureReason::classify(RejectionCode::CanisterError, "trapped"),
+            CanisterCreationFailureReason::Other
+        );
+    }
+}
@@ -3,8 +3,24 @@ use std::{
     time::{SystemTime, UNIX_EPOCH},
 };
 
-use candid::{CandidType, Deserialize, Principal};
+use candid::{CandidType, Decode, Deserialize, Principal};
 use serde::Serialize;
+use shared_utils::canister_specific::individual_user_template::types::{
+    arg::IndividualUserTemplateInitArgs, treasury::validate_house_rake_percentage,
+};
+
+pub mod rolling_upgrade;
+pub mod upgrade_run;
+
+/// The two ways user canisters are upgraded. Only one of them runs at a time, so that no
+/// canister is upgraded by both at once.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum UpgradeRunKind {
+    // * Upgrades every canister, started each time this index is upgraded
+    FullRun,
+    // * Started by an admin, upgrades canisters in health checked batches
+    RollingUpgrade,
+}
 
 #[derive(CandidType, Deserialize, Clone, Serialize, Debug)]
 pub struct UpgradeStatus {
@@ -30,3 +46,333 @@ impl Default for UpgradeStatus {
         }
     }
 }
+
+/// Decodes a candid encoded `IndividualUserTemplateInitArgs` that overrides the arguments
+/// a single canister is upgraded with. Decoding against the args type this wasm ships with
+/// is what rejects overrides written for an incompatible version of the template.
+pub fn decode_upgrade_arg_override(
+    upgrade_arg_override: &[u8],
+) -> Result<IndividualUserTemplateInitArgs, String> {
+    let decoded_upgrade_arg_override =
+        Decode!(upgrade_arg_override, IndividualUserTemplateInitArgs)
+            .map_err(|e| format!("Override does not match the expected upgrade args: {}", e))?;
+
+    if decoded_upgrade_arg_override
+        .referrer_share_of_bet_winnings_percentage
+        .is_some_and(|percentage| percentage > 100)
+    {
+        return Err("Referrer share percentage cannot exceed 100".to_string());
+    }
+
+    if let Some(house_rake_percentage) = decoded_upgrade_arg_override.house_rake_percentage {
+        validate_house_rake_percentage(house_rake_percentage)?;
+    }
+
+    if let Some(room_sizing_config) = &decoded_upgrade_arg_override.room_sizing_config {
+        room_sizing_config.validate()?;
+    }
+
+    if let Some(post_category_config) = &decoded_upgrade_arg_override.post_category_config {
+        post_category_config.validate()?;
+    }
+
+    if let Some(signup_bonus_vesting_config) =
+        &decoded_upgrade_arg_override.signup_bonus_vesting_config
+    {
+        signup_bonus_vesting_config.validate()?;
+    }
+
+    if let Some(pot_insurance_config) = &decoded_upgrade_arg_override.pot_insurance_config {
+        pot_insurance_config.validate()?;
+    }
+
+    if let Some(bet_cancellation_config) = &decoded_upgrade_arg_override.bet_cancellation_config {
+        bet_cancellation_config.validate()?;
+    }
+
+    Ok(decoded_upgrade_arg_override)
+}
+
+/// Replaces the blanket upgrade args with whatever the override sets. The profile owner
+/// and version number are always the ones the upgrade orchestrator computed.
+pub fn apply_upgrade_arg_override(
+    upgrade_args: IndividualUserTemplateInitArgs,
+    upgrade_arg_override: Option<&[u8]>,
+) -> Result<IndividualUserTemplateInitArgs, String> {
+    let Some(upgrade_arg_override) = upgrade_arg_override else {
+        return Ok(upgrade_args);
+    };
+
+    let upgrade_arg_override = decode_upgrade_arg_override(upgrade_arg_override)?;
+
+    Ok(IndividualUserTemplateInitArgs {
+        known_principal_ids: upgrade_arg_override
+            .known_principal_ids
+            .or(upgrade_args.known_principal_ids),
+        profile_owner: upgrade_args.profile_owner,
+        upgrade_version_number: upgrade_args.upgrade_version_number,
+        url_to_send_canister_metrics_to: upgrade_arg_override
+            .url_to_send_canister_metrics_to
+            .or(upgrade_args.url_to_send_canister_metrics_to),
+        referrer_share_of_bet_winnings_percentage: upgrade_arg_override
+            .referrer_share_of_bet_winnings_percentage
+            .or(upgrade_args.referrer_share_of_bet_winnings_percentage),
+        house_rake_percentage: upgrade_arg_override
+            .house_rake_percentage
+            .or(upgrade_args.house_rake_percentage),
+        room_sizing_config: upgrade_arg_override
+            .room_sizing_config
+            .or(upgrade_args.room_sizing_config),
+        post_category_config: upgrade_arg_override
+            .post_category_config
+            .or(upgrade_args.post_category_config),
+        // * Only ever set by the user index itself
+        binding_epoch: upgrade_args.binding_epoch,
+        is_call_journal_enabled: upgrade_arg_override
+            .is_call_journal_enabled
+            .or(upgrade_args.is_call_journal_enabled),
+        signup_bonus_vesting_config: upgrade_arg_override
+            .signup_bonus_vesting_config
+            .or(upgrade_args.signup_bonus_vesting_config),
+        pot_insurance_config: upgrade_arg_override
+            .pot_insurance_config
+            .or(upgrade_args.pot_insurance_config),
+        bet_cancellation_config: upgrade_arg_override
+            .bet_cancellation_config
+            .or(upgrade_args.bet_cancellation_config),
+        access_statistics_config: upgrade_arg_override
+            .access_statistics_config
+            .or(upgrade_args.access_statistics_config),
+        hot_or_not_payout_mode: upgrade_arg_override
+            .hot_or_not_payout_mode
+            .or(upgrade_args.hot_or_not_payout_mode),
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use candid::Encode;
+    use shared_utils::canister_specific::individual_user_template::types::{
+        hot_or_not::RoomSizingConfig,
+        post::{PostCategory, PostCategoryConfig},
+        signup_bonus::SignupBonusVestingConfig,
+        treasury::{PotInsuranceConfig, MAXIMUM_HOUSE_RAKE_PERCENTAGE},
+    };
+    use shared_utils::common::types::known_principal::{KnownPrincipalMap, KnownPrincipalType};
+    use test_utils::setup::test_constants::{
+        get_mock_canister_id_configuration, get_mock_user_alice_principal_id,
+        get_mock_user_bob_principal_id,
+    };
+
+    use super::*;
+
+    fn get_blanket_upgrade_args() -> IndividualUserTemplateInitArgs {
+        IndividualUserTemplateInitArgs {
+            known_principal_ids: Some(KnownPrincipalMap::default()),
+            profile_owner: Some(get_mock_user_alice_principal_id()),
+            upgrade_version_number: Some(2),
+            url_to_send_canister_metrics_to: Some("https://metrics".to_string()),
+            referrer_share_of_bet_winnings_percentage: Some(1),
+            house_rake_percentage: Some(2),
+            room_sizing_config: Some(RoomSizingConfig::default()),
+            post_category_config: Some(PostCategoryConfig::default()),
+            binding_epoch: None,
+            is_call_journal_enabled: None,
+            signup_bonus_vesting_config: None,
+            pot_insurance_config: None,
+            bet_cancellation_config: None,
+            access_statistics_config: None,
+            hot_or_not_payout_mode: None,
+        }
+    }
+
+    #[test]
+    fn test_apply_upgrade_arg_override() {
+        let upgrade_args = apply_upgrade_arg_override(get_blanket_upgrade_args(), None).unwrap();
+        assert_eq!(upgrade_args.house_rake_percentage, Some(2));
+
+        let mut overridden_known_principal_ids = KnownPrincipalMap::default();
+        overridden_known_principal_ids.insert(
+            KnownPrincipalType::CanisterIdConfiguration,
+            get_mock_canister_id_configuration(),
+        );
+        let upgrade_arg_override = Encode!(&IndividualUserTemplateInitArgs {
+            known_principal_ids: Some(overridden_known_principal_ids.clone()),
+            profile_owner: Some(get_mock_user_bob_principal_id()),
+            upgrade_version_number: Some(10),
+            url_to_send_canister_metrics_to: None,
+            referrer_share_of_bet_winnings_percentage: None,
+            house_rake_percentage: Some(0),
+            room_sizing_config: None,
+            post_category_config: Some(PostCategoryConfig {
+                allowed_categories: vec![PostCategory::Music],
+            }),
+            binding_epoch: Some(3),
+            is_call_journal_enabled: Some(true),
+            signup_bonus_vesting_config: None,
+            pot_insurance_config: None,
+            bet_cancellation_config: None,
+            access_statistics_config: None,
+            hot_or_not_payout_mode: None,
+        })
+        .unwrap();
+
+        let upgrade_args =
+            apply_upgrade_arg_override(get_blanket_upgrade_args(), Some(&upgrade_arg_override))
+                .unwrap();
+
+        assert_eq!(
+            upgrade_args.known_principal_ids,
+            Some(overridden_known_principal_ids)
+        );
+        assert_eq!(
+            upgrade_args.profile_owner,
+            Some(get_mock_user_alice_principal_id())
+        );
+        assert_eq!(upgrade_args.upgrade_version_number, Some(2));
+        assert_eq!(
+            upgrade_args.url_to_send_canister_metrics_to,
+            Some("https://metrics".to_string())
+        );
+        assert_eq!(
+            upgrade_args.referrer_share_of_bet_winnings_percentage,
+            Some(1)
+        );
+        assert_eq!(upgrade_args.house_rake_percentage, Some(0));
+        assert_eq!(
+            upgrade_args.room_sizing_config,
+            Some(RoomSizingConfig::default())
+        );
+        assert_eq!(
+            upgrade_args.post_category_config,
+            Some(PostCategoryConfig {
+                allowed_categories: vec![PostCategory::Music],
+            })
+        );
+        assert_eq!(upgrade_args.binding_epoch, None);
+        assert_eq!(upgrade_args.is_call_journal_enabled, Some(true));
+    }
+
+    #[test]
+    fn test_decode_upgrade_arg_override_rejects_invalid_overrides() {
+        assert!(decode_upgrade_arg_override(&Encode!(&"not upgrade args").unwrap()).is_err());
+        assert!(decode_upgrade_arg_override(
+            &Encode!(&IndividualUserTemplateInitArgs {
+                known_principal_ids: None,
+                profile_owner: None,
+                upgrade_version_number: None,
+                url_to_send_canister_metrics_to: None,
+                referrer_share_of_bet_winnings_percentage: None,
+                house_rake_percentage: Some(MAXIMUM_HOUSE_RAKE_PERCENTAGE + 1),
+                room_sizing_config: None,
+                post_category_config: None,
+                binding_epoch: None,
+                is_call_journal_enabled: None,
+                signup_bonus_vesting_config: None,
+                pot_insurance_config: None,
+                bet_cancellation_config: None,
+                access_statistics_config: None,
+                hot_or_not_payout_mode: None,
+            })
+            .unwrap()
+        )
+        .is_err());
+        assert!(decode_upgrade_arg_override(
+            &Encode!(&IndividualUserTemplateInitArgs {
+                known_principal_ids: None,
+                profile_owner: None,
+                upgrade_version_number: None,
+                url_to_send_canister_metrics_to: None,
+                referrer_share_of_bet_winnings_percentage: None,
+                house_rake_percentage: None,
+                room_sizing_config: Some(RoomSizingConfig {
+                    default_room_size: 0,
+                    high_velocity: None,
+                    low_velocity: None,
+                }),
+                post_category_config: None,
+                binding_epoch: None,
+                is_call_journal_enabled: None,
+                signup_bonus_vesting_config: None,
+                pot_insurance_config: None,
+                bet_cancellation_config: None,
+                access_statistics_config: None,
+                hot_or_not_payout_mode: None,
+            })
+            .unwrap()
+        )
+        .is_err());
+        assert!(decode_upgrade_arg_override(
+            &Encode!(&IndividualUserTemplateInitArgs {
+                known_principal_ids: None,
+                profile_owner: None,
+                upgrade_version_number: None,
+                url_to_send_canister_metrics_to: None,
+                referrer_share_of_bet_winnings_percentage: None,
+                house_rake_percentage: None,
+                room_sizing_config: None,
+                post_category_config: Some(PostCategoryConfig {
+                    allowed_categories: vec![],
+                }),
+                binding_epoch: None,
+                is_call_journal_enabled: None,
+                signup_bonus_vesting_config: None,
+                pot_insurance_config: None,
+                bet_cancellation_config: None,
+                access_statistics_config: None,
+                hot_or_not_payout_mode: None,
+            })
+            .unwrap()
+        )
+        .is_err());
+        assert!(decode_upgrade_arg_override(
+            &Encode!(&IndividualUserTemplateInitArgs {
+                known_principal_ids: None,
+                profile_owner: None,
+                upgrade_version_number: None,
+                url_to_send_canister_metrics_to: None,
+                referrer_share_of_bet_winnings_percentage: None,
+                house_rake_percentage: None,
+                room_sizing_config: None,
+                post_category_config: None,
+                binding_epoch: None,
+                is_call_journal_enabled: None,
+                signup_bonus_vesting_config: Some(SignupBonusVestingConfig {
+                    total_amount: 1000,
+                    unlocked_at_signup_percentage: 120,
+                    number_of_vesting_days: 5,
+                }),
+                pot_insurance_config: None,
+                bet_cancellation_config: None,
+                access_statistics_config: None,
+                hot_or_not_payout_mode: None,
+            })
+            .unwrap()
+        )
+        .is_err());
+        assert!(decode_upgrade_arg_override(
+            &Encode!(&IndividualUserTemplateInitArgs {
+                known_principal_ids: None,
+                profile_owner: None,
+                upgrade_version_number: None,
+                url_to_send_canister_metrics_to: None,
+                referrer_share_of_bet_winnings_percentage: None,
+                house_rake_percentage: None,
+                room_sizing_config: None,
+                post_category_config: None,
+                binding_epoch: None,
+                is_call_journal_enabled: None,
+                signup_bonus_vesting_config: None,
+                pot_insurance_config: Some(PotInsuranceConfig {
+                    room_pot_floor: 500,
+                    daily_spend_cap: 0,
+                }),
+                bet_cancellation_config: None,
+                access_statistics_config: None,
+                hot_or_not_payout_mode: None,
+            })
+            .unwrap()
+        )
+        .is_err());
+    }
+}
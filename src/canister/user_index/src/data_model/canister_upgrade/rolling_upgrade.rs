@@ -0,0 +1,426 @@
+use std::{collections::VecDeque, ops::Bound, time::SystemTime};
+
+use candid::{CandidType, Deserialize, Principal};
+use serde::Serialize;
+
+use crate::data_model::CanisterData;
+
+pub const MAX_ROLLING_UPGRADE_BATCH_SIZE: u64 = 500;
+// * A batch with a larger share of its canisters failing to upgrade or coming back unhealthy
+// * pauses the rollout
+pub const ROLLING_UPGRADE_FAILURE_THRESHOLD_PERCENTAGE: u64 = 10;
+// * Only the most recent failures are kept, the count covers all of them
+pub const MAX_ROLLING_UPGRADE_FAILURES_KEPT: usize = 1_000;
+
+#[derive(CandidType, Deserialize, PartialEq, Eq, Debug)]
+pub enum RollingUpgradeError {
+    Unauthorized,
+    InvalidBatchSize,
+    WasmHashMismatch,
+    RollingUpgradeInProgress,
+    NoPausedRollingUpgrade,
+    // * The run that upgrades every canister after this index is upgraded is still going
+    UpgradeRunInProgress,
+}
+
+shared_utils::error_codes! {
+    RollingUpgradeError {
+        Unauthorized => (5601, Unauthorized, false),
+        InvalidBatchSize => (5602, InvalidInput, false),
+        WasmHashMismatch => (5603, InvalidInput, false),
+        RollingUpgradeInProgress => (5604, Conflict, true),
+        NoPausedRollingUpgrade => (5605, Conflict, false),
+        UpgradeRunInProgress => (5606, Conflict, true),
+    }
+}
+
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug, PartialEq, Eq)]
+pub enum RollingUpgradeStatus {
+    InProgress,
+    // * Reached the end of the upgrade window, carries on when it next opens
+    WaitingForUpgradeWindow { resumes_at: SystemTime },
+    // * Too many canisters in the last batch failed, waits for an admin to resume it
+    PausedOnFailures,
+    // * The user index was upgraded mid rollout, possibly embedding another wasm
+    Interrupted,
+    Completed { finished_at: SystemTime },
+}
+
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug, PartialEq, Eq)]
+pub struct RollingUpgradeFailure {
+    pub user_principal_id: Principal,
+    pub user_canister_id: Principal,
+    pub reason: String,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct RollingUpgradeProgress {
+    pub wasm_hash: Vec<u8>,
+    pub version_number: u64,
+    pub batch_size: u64,
+    pub started_at: SystemTime,
+    pub status: RollingUpgradeStatus,
+    pub completed_count: u64,
+    pub failed_count: u64,
+    // * Newest first
+    pub recent_failures: Vec<RollingUpgradeFailure>,
+    pub pending_count: u64,
+}
+
+/// Upgrading every user canister to the wasm this index embeds, a batch of users at a time,
+/// checking the health of each canister before moving on to the next batch
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct RollingUpgrade {
+    pub wasm_hash: Vec<u8>,
+    // * The version canisters are upgraded to and should report in their health checks
+    pub version_number: u64,
+    pub batch_size: u64,
+    pub started_at: SystemTime,
+    pub status: RollingUpgradeStatus,
+    pub completed_count: u64,
+    pub failed_count: u64,
+    // * Oldest first
+    recent_failures: VecDeque<RollingUpgradeFailure>,
+    // * Users are taken in principal id order, the next batch starts after this one
+    last_processed_user_principal_id: Option<Principal>,
+}
+
+impl RollingUpgrade {
+    pub fn new(
+        wasm_hash: Vec<u8>,
+        version_number: u64,
+        batch_size: u64,
+        started_at: SystemTime,
+    ) -> Self {
+        Self {
+            wasm_hash,
+            version_number,
+            batch_size,
+            started_at,
+            status: RollingUpgradeStatus::InProgress,
+            completed_count: 0,
+            failed_count: 0,
+            recent_failures: VecDeque::new(),
+            last_processed_user_principal_id: None,
+        }
+    }
+
+    pub fn is_in_progress(&self) -> bool {
+        self.status == RollingUpgradeStatus::InProgress
+    }
+
+    /// In progress or waiting for the upgrade window to open
+    pub fn is_active(&self) -> bool {
+        matches!(
+            self.status,
+            RollingUpgradeStatus::InProgress | RollingUpgradeStatus::WaitingForUpgradeWindow { .. }
+        )
+    }
+
+    pub fn is_paused(&self) -> bool {
+        matches!(
+            self.status,
+            RollingUpgradeStatus::PausedOnFailures | RollingUpgradeStatus::Interrupted
+        )
+    }
+
+    fn get_remaining_user_canisters<'a>(
+        &self,
+        canister_data: &'a CanisterData,
+    ) -> impl Iterator<Item = (&'a Principal, &'a Principal)> {
+        let start_after = self
+            .last_processed_user_principal_id
+            .map_or(Bound::Unbounded, Bound::Excluded);
+
+        canister_data
+            .user_principal_id_to_canister_id_map
+            .range((start_after, Bound::Unbounded))
+    }
+
+    /// The canisters of the next `batch_size` users as (user principal id, canister id).
+    /// Standbys go right after their primary, so the two stay on the same wasm.
+    pub fn get_next_batch(&self, canister_data: &CanisterData) -> Vec<(Principal, Principal)> {
+        self.get_remaining_user_canisters(canister_data)
+            .take(self.batch_size as usize)
+            .flat_map(|(user_principal_id, user_canister_id)| {
+                std::iter::once(*user_canister_id)
+                    .chain(canister_data.get_standby_canister_id(user_principal_id))
+                    .map(move |canister_id| (*user_principal_id, canister_id))
+            })
+            .collect()
+    }
+
+    pub fn get_pending_count(&self, canister_data: &CanisterData) -> u64 {
+        self.get_remaining_user_canisters(canister_data)
+            .map(|(user_principal_id, _)| {
+                1 + canister_data
+                    .get_standby_canister_id(user_principal_id)
+                    .is_some() as u64
+            })
+            .sum()
+    }
+
+    /// Moves past a batch once every canister in it was upgraded and checked. Pauses if too
+    /// many of them failed.
+    pub fn record_batch(
+        &mut self,
+        last_user_principal_id_in_batch: Principal,
+        batch_len: usize,
+        failures: Vec<RollingUpgradeFailure>,
+    ) {
+        if self.status != RollingUpgradeStatus::InProgress {
+            return;
+        }
+
+        self.last_processed_user_principal_id = Some(last_user_principal_id_in_batch);
+        self.completed_count += (batch_len - failures.len()) as u64;
+        self.failed_count += failures.len() as u64;
+
+        if failures.len() as u64 * 100
+            > batch_len as u64 * ROLLING_UPGRADE_FAILURE_THRESHOLD_PERCENTAGE
+        {
+            self.status = RollingUpgradeStatus::PausedOnFailures;
+        }
+
+        self.recent_failures.extend(failures);
+        while self.recent_failures.len() > MAX_ROLLING_UPGRADE_FAILURES_KEPT {
+            self.recent_failures.pop_front();
+        }
+    }
+
+    pub fn complete(&mut self, finished_at: SystemTime) {
+        if self.status == RollingUpgradeStatus::InProgress {
+            self.status = RollingUpgradeStatus::Completed { finished_at };
+        }
+    }
+
+    pub fn wait_for_upgrade_window(&mut self, resumes_at: SystemTime) {
+        if self.status == RollingUpgradeStatus::InProgress {
+            self.status = RollingUpgradeStatus::WaitingForUpgradeWindow { resumes_at };
+        }
+    }
+
+    /// Returns false if the rollout stopped waiting for some other reason in the meantime
+    pub fn stop_waiting_for_upgrade_window(&mut self) -> bool {
+        if !matches!(
+            self.status,
+            RollingUpgradeStatus::WaitingForUpgradeWindow { .. }
+        ) {
+            return false;
+        }
+
+        self.status = RollingUpgradeStatus::InProgress;
+        true
+    }
+
+    pub fn interrupt(&mut self) {
+        if self.is_active() {
+            self.status = RollingUpgradeStatus::Interrupted;
+        }
+    }
+
+    pub fn get_progress(&self, canister_data: &CanisterData) -> RollingUpgradeProgress {
+        RollingUpgradeProgress {
+            wasm_hash: self.wasm_hash.clone(),
+            version_number: self.version_number,
+            batch_size: self.batch_size,
+            started_at: self.started_at,
+            status: self.status.clone(),
+            completed_count: self.completed_count,
+            failed_count: self.failed_count,
+            recent_failures: self.recent_failures.iter().rev().cloned().collect(),
+            pending_count: self.get_pending_count(canister_data),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use test_utils::setup::test_constants::{
+        get_mock_user_alice_canister_id, get_mock_user_alice_principal_id,
+        get_mock_user_bob_canister_id, get_mock_user_bob_principal_id,
+        get_mock_user_charlie_canister_id, get_mock_user_charlie_principal_id,
+    };
+
+    use crate::data_model::replication::UserCanisterReplication;
+
+    use super::*;
+
+    fn get_failure(
+        user_principal_id: Principal,
+        user_canister_id: Principal,
+    ) -> RollingUpgradeFailure {
+        RollingUpgradeFailure {
+            user_principal_id,
+            user_canister_id,
+            reason: "Canister trapped in post_upgrade".into(),
+        }
+    }
+
+    #[test]
+    fn test_batches_go_in_user_principal_id_order_with_standbys() {
+        let mut canister_data = CanisterData::default();
+        let users = [
+            (
+                get_mock_user_alice_principal_id(),
+                get_mock_user_alice_canister_id(),
+            ),
+            (
+                get_mock_user_bob_principal_id(),
+                get_mock_user_bob_canister_id(),
+            ),
+            (
+                get_mock_user_charlie_principal_id(),
+                get_mock_user_charlie_canister_id(),
+            ),
+        ];
+        users
+            .iter()
+            .for_each(|(user_principal_id, user_canister_id)| {
+                canister_data
+                    .user_principal_id_to_canister_id_map
+                    .insert(*user_principal_id, *user_canister_id);
+            });
+        let mut sorted_users = users.to_vec();
+        sorted_users.sort();
+        let standby_canister_id = Principal::from_slice(&[7]);
+        canister_data.user_canister_replications.insert(
+            sorted_users[0].0,
+            UserCanisterReplication {
+                standby_canister_id: Some(standby_canister_id),
+                epoch: 0,
+            },
+        );
+
+        let mut rolling_upgrade = RollingUpgrade::new(vec![1, 2], 1, 2, SystemTime::UNIX_EPOCH);
+        assert_eq!(rolling_upgrade.get_pending_count(&canister_data), 4);
+
+        let batch = rolling_upgrade.get_next_batch(&canister_data);
+        assert_eq!(
+            batch,
+            vec![
+                sorted_users[0],
+                (sorted_users[0].0, standby_canister_id),
+                sorted_users[1],
+            ]
+        );
+
+        rolling_upgrade.record_batch(sorted_users[1].0, batch.len(), vec![]);
+        assert_eq!(rolling_upgrade.completed_count, 3);
+        assert_eq!(rolling_upgrade.get_pending_count(&canister_data), 1);
+        assert_eq!(
+            rolling_upgrade.get_next_batch(&canister_data),
+            vec![sorted_users[2]]
+        );
+
+        rolling_upgrade.record_batch(sorted_users[2].0, 1, vec![]);
+        assert!(rolling_upgrade.get_next_batch(&canister_data).is_empty());
+        rolling_upgrade.complete(SystemTime::UNIX_EPOCH);
+        assert_eq!(
+            rolling_upgrade.get_progress(&canister_data).status,
+            RollingUpgradeStatus::Completed {
+                finished_at: SystemTime::UNIX_EPOCH
+            }
+        );
+    }
+
+    #[test]
+    fn test_record_batch_pauses_past_the_failure_threshold() {
+        let mut rolling_upgrade = RollingUpgrade::new(vec![1, 2], 1, 20, SystemTime::UNIX_EPOCH);
+        let alice_failure = get_failure(
+            get_mock_user_alice_principal_id(),
+            get_mock_user_alice_canister_id(),
+        );
+
+        // * 1 in 20 is within the threshold
+        rolling_upgrade.record_batch(
+            get_mock_user_alice_principal_id(),
+            20,
+            vec![alice_failure.clone()],
+        );
+        assert_eq!(rolling_upgrade.status, RollingUpgradeStatus::InProgress);
+
+        // * 3 in 20 is not
+        rolling_upgrade.record_batch(
+            get_mock_user_bob_principal_id(),
+            20,
+            vec![alice_failure.clone(), alice_failure.clone(), alice_failure],
+        );
+        assert_eq!(
+            rolling_upgrade.status,
+            RollingUpgradeStatus::PausedOnFailures
+        );
+        assert!(rolling_upgrade.is_paused());
+        assert_eq!(rolling_upgrade.completed_count, 36);
+        assert_eq!(rolling_upgrade.failed_count, 4);
+
+        // * nothing moves while paused
+        rolling_upgrade.record_batch(get_mock_user_charlie_principal_id(), 20, vec![]);
+        assert_eq!(rolling_upgrade.completed_count, 36);
+        assert_eq!(
+            rolling_upgrade.last_processed_user_principal_id,
+            Some(get_mock_user_bob_principal_id())
+        );
+    }
+
+    #[test]
+    fn test_waiting_for_upgrade_window() {
+        let mut rolling_upgrade = RollingUpgrade::new(vec![1, 2], 1, 20, SystemTime::UNIX_EPOCH);
+        let resumes_at = SystemTime::UNIX_EPOCH + Duration::from_secs(60 * 60);
+
+        rolling_upgrade.wait_for_upgrade_window(resumes_at);
+        assert_eq!(
+            rolling_upgrade.status,
+            RollingUpgradeStatus::WaitingForUpgradeWindow { resumes_at }
+        );
+        assert!(rolling_upgrade.is_active());
+        assert!(!rolling_upgrade.is_in_progress());
+        assert!(!rolling_upgrade.is_paused());
+
+        // * nothing moves while waiting
+        rolling_upgrade.record_batch(get_mock_user_alice_principal_id(), 20, vec![]);
+        assert_eq!(rolling_upgrade.completed_count, 0);
+
+        assert!(rolling_upgrade.stop_waiting_for_upgrade_window());
+        assert!(rolling_upgrade.is_in_progress());
+        assert!(!rolling_upgrade.stop_waiting_for_upgrade_window());
+
+        // * an upgrade of the index while waiting leaves the rollout for an admin to resume
+        rolling_upgrade.wait_for_upgrade_window(resumes_at);
+        rolling_upgrade.interrupt();
+        assert_eq!(rolling_upgrade.status, RollingUpgradeStatus::Interrupted);
+        assert!(!rolling_upgrade.stop_waiting_for_upgrade_window());
+    }
+
+    #[test]
+    fn test_only_recent_failures_are_kept() {
+        let mut rolling_upgrade = RollingUpgrade::new(vec![1, 2], 1, 1, SystemTime::UNIX_EPOCH);
+
+        (0..MAX_ROLLING_UPGRADE_FAILURES_KEPT as u64 + 1).for_each(|id| {
+            rolling_upgrade.record_batch(
+                get_mock_user_alice_principal_id(),
+                MAX_ROLLING_UPGRADE_FAILURES_KEPT + 1,
+                vec![get_failure(
+                    get_mock_user_alice_principal_id(),
+                    Principal::from_slice(&id.to_be_bytes()),
+                )],
+            );
+        });
+
+        let progress = rolling_upgrade.get_progress(&CanisterData::default());
+        assert_eq!(
+            progress.failed_count,
+            MAX_ROLLING_UPGRADE_FAILURES_KEPT as u64 + 1
+        );
+        assert_eq!(
+            progress.recent_failures.len(),
+            MAX_ROLLING_UPGRADE_FAILURES_KEPT
+        );
+        assert_eq!(
+            progress.recent_failures[0].user_canister_id,
+            Principal::from_slice(&(MAX_ROLLING_UPGRADE_FAILURES_KEPT as u64).to_be_bytes())
+        );
+    }
+}
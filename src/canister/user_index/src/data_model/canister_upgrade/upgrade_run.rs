@@ -0,0 +1,198 @@
+use std::{collections::VecDeque, time::SystemTime};
+
+use candid::{CandidType, Deserialize, Principal};
+use serde::Serialize;
+
+// * Enough to look back over a few weeks of releases
+pub const MAX_UPGRADE_RUN_REPORTS: usize = 20;
+
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug, PartialEq, Eq)]
+pub enum UpgradeRunStatus {
+    InProgress,
+    // * Reached the end of the upgrade window, carries on when it next opens
+    Paused { resumes_at: SystemTime },
+    Completed { finished_at: SystemTime },
+    // * The user index was upgraded again before this run finished, and started a new one
+    Superseded,
+}
+
+/// How one pass of upgrading every user canister to a new wasm went
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug, PartialEq, Eq)]
+pub struct UpgradeRunReport {
+    pub version_number: u64,
+    pub started_at: SystemTime,
+    pub status: UpgradeRunStatus,
+    pub successful_upgrade_count: u32,
+    pub failed_upgrade_count: u32,
+    pub number_of_pauses: u32,
+    // * Canisters are upgraded in user principal id order, a paused run resumes after this one
+    pub last_processed_user_principal_id: Option<Principal>,
+}
+
+#[derive(Deserialize, Serialize, Clone, Default, Debug)]
+pub struct UpgradeRunReports {
+    // * Oldest first. Only the newest one can still be running.
+    reports: VecDeque<UpgradeRunReport>,
+}
+
+impl UpgradeRunReports {
+    pub fn start_run(&mut self, version_number: u64, started_at: SystemTime) {
+        if let Some(unfinished_run) = self.get_unfinished_run_mut() {
+            unfinished_run.status = UpgradeRunStatus::Superseded;
+        }
+
+        self.reports.push_back(UpgradeRunReport {
+            version_number,
+            started_at,
+            status: UpgradeRunStatus::InProgress,
+            successful_upgrade_count: 0,
+            failed_upgrade_count: 0,
+            number_of_pauses: 0,
+            last_processed_user_principal_id: None,
+        });
+
+        while self.reports.len() > MAX_UPGRADE_RUN_REPORTS {
+            self.reports.pop_front();
+        }
+    }
+
+    /// A run in progress or paused until the upgrade window next opens
+    pub fn has_unfinished_run(&self) -> bool {
+        self.reports.back().is_some_and(|report| {
+            matches!(
+                report.status,
+                UpgradeRunStatus::InProgress | UpgradeRunStatus::Paused { .. }
+            )
+        })
+    }
+
+    fn get_unfinished_run_mut(&mut self) -> Option<&mut UpgradeRunReport> {
+        self.reports.back_mut().filter(|report| {
+            matches!(
+                report.status,
+                UpgradeRunStatus::InProgress | UpgradeRunStatus::Paused { .. }
+            )
+        })
+    }
+
+    fn get_run_in_progress_mut(&mut self) -> Option<&mut UpgradeRunReport> {
+        self.reports
+            .back_mut()
+            .filter(|report| report.status == UpgradeRunStatus::InProgress)
+    }
+
+    pub fn record_progress(
+        &mut self,
+        last_processed_user_principal_id: Principal,
+        successful_upgrade_count: u32,
+        failed_upgrade_count: u32,
+    ) {
+        if let Some(report) = self.get_run_in_progress_mut() {
+            report.last_processed_user_principal_id = Some(last_processed_user_principal_id);
+            report.successful_upgrade_count = successful_upgrade_count;
+            report.failed_upgrade_count = failed_upgrade_count;
+        }
+    }
+
+    pub fn pause_run(&mut self, resumes_at: SystemTime) {
+        if let Some(report) = self.get_run_in_progress_mut() {
+            report.status = UpgradeRunStatus::Paused { resumes_at };
+            report.number_of_pauses += 1;
+        }
+    }
+
+    /// Where the paused run left off, None if there is no paused run to resume
+    pub fn resume_run(&mut self) -> Option<Option<Principal>> {
+        let report = self
+            .reports
+            .back_mut()
+            .filter(|report| matches!(report.status, UpgradeRunStatus::Paused { .. }))?;
+        report.status = UpgradeRunStatus::InProgress;
+
+        Some(report.last_processed_user_principal_id)
+    }
+
+    pub fn complete_run(&mut self, finished_at: SystemTime) {
+        if let Some(report) = self.get_run_in_progress_mut() {
+            report.status = UpgradeRunStatus::Completed { finished_at };
+        }
+    }
+
+    /// Newest first
+    pub fn get_reports(&self) -> Vec<UpgradeRunReport> {
+        self.reports.iter().rev().cloned().collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use test_utils::setup::test_constants::{
+        get_mock_user_alice_principal_id, get_mock_user_bob_principal_id,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_paused_run_resumes_where_it_left_off() {
+        let mut upgrade_run_reports = UpgradeRunReports::default();
+        let started_at = SystemTime::UNIX_EPOCH;
+        let resumes_at = started_at + Duration::from_secs(60 * 60);
+
+        upgrade_run_reports.start_run(1, started_at);
+        assert_eq!(upgrade_run_reports.resume_run(), None);
+
+        upgrade_run_reports.record_progress(get_mock_user_alice_principal_id(), 1, 0);
+        upgrade_run_reports.pause_run(resumes_at);
+        // * progress can't be recorded against a paused run
+        upgrade_run_reports.record_progress(get_mock_user_bob_principal_id(), 2, 0);
+
+        assert_eq!(
+            upgrade_run_reports.resume_run(),
+            Some(Some(get_mock_user_alice_principal_id()))
+        );
+        assert_eq!(upgrade_run_reports.resume_run(), None);
+
+        upgrade_run_reports.record_progress(get_mock_user_bob_principal_id(), 1, 1);
+        upgrade_run_reports.complete_run(resumes_at);
+
+        assert_eq!(
+            upgrade_run_reports.get_reports(),
+            vec![UpgradeRunReport {
+                version_number: 1,
+                started_at,
+                status: UpgradeRunStatus::Completed {
+                    finished_at: resumes_at
+                },
+                successful_upgrade_count: 1,
+                failed_upgrade_count: 1,
+                number_of_pauses: 1,
+                last_processed_user_principal_id: Some(get_mock_user_bob_principal_id()),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_starting_a_run_supersedes_the_unfinished_one() {
+        let mut upgrade_run_reports = UpgradeRunReports::default();
+
+        upgrade_run_reports.start_run(1, SystemTime::UNIX_EPOCH);
+        upgrade_run_reports.pause_run(SystemTime::UNIX_EPOCH);
+        upgrade_run_reports.start_run(2, SystemTime::UNIX_EPOCH);
+
+        let reports = upgrade_run_reports.get_reports();
+        assert_eq!(reports[0].status, UpgradeRunStatus::InProgress);
+        assert_eq!(reports[1].status, UpgradeRunStatus::Superseded);
+        // * the superseded run's timer finds nothing to resume
+        assert_eq!(upgrade_run_reports.resume_run(), None);
+
+        (3..30).for_each(|version_number| {
+            upgrade_run_reports.start_run(version_number, SystemTime::UNIX_EPOCH)
+        });
+        assert_eq!(
+            upgrade_run_reports.get_reports().len(),
+            MAX_UPGRADE_RUN_REPORTS
+        );
+    }
+}
@@ -1,10 +1,41 @@
+use std::collections::BTreeSet;
+
 use candid::{CandidType, Deserialize};
 use serde::Serialize;
-use shared_utils::common::types::known_principal::KnownPrincipalMap;
+use shared_utils::{
+    canister_specific::individual_user_template::types::{
+        hot_or_not::{BetCancellationConfig, HotOrNotPayoutMode, RoomSizingConfig},
+        post::PostCategoryConfig,
+        signup_bonus::SignupBonusVestingConfig,
+        treasury::PotInsuranceConfig,
+    },
+    common::types::known_principal::KnownPrincipalMap,
+};
 
 #[derive(Default, Deserialize, CandidType, Serialize, Clone)]
 pub struct Configuration {
     pub known_principal_ids: KnownPrincipalMap,
     pub signups_open_on_this_subnet: bool,
     pub url_to_send_canister_metrics_to: String,
+    #[serde(default)]
+    pub referrer_share_of_bet_winnings_percentage: u64,
+    #[serde(default)]
+    pub house_rake_percentage: u64,
+    #[serde(default)]
+    pub room_sizing_config: RoomSizingConfig,
+    #[serde(default)]
+    pub post_category_config: PostCategoryConfig,
+    // * Stored normalized for screening, so look-alikes of a reserved name are reserved too
+    #[serde(default)]
+    pub reserved_usernames: BTreeSet<String>,
+    #[serde(default)]
+    pub signup_bonus_vesting_config: SignupBonusVestingConfig,
+    #[serde(default)]
+    pub pot_insurance_config: PotInsuranceConfig,
+    #[serde(default)]
+    pub bet_cancellation_config: BetCancellationConfig,
+    // * Copy of the mode set in the configuration canister, refreshed at the start of every
+    // * upgrade run so that canisters created in between get it too
+    #[serde(default)]
+    pub hot_or_not_payout_mode: HotOrNotPayoutMode,
 }
@@ -0,0 +1,212 @@
+use std::collections::BTreeMap;
+
+use candid::{CandidType, Deserialize, Principal};
+use serde::Serialize;
+use shared_utils::canister_specific::individual_user_template::types::feature_usage::{
+    DayNumber, Feature, FeatureUsageForDay,
+};
+
+pub const MAX_FEATURE_USAGE_FUNNEL_DAYS_PER_REQUEST: u64 = 90;
+
+#[derive(CandidType, Deserialize, PartialEq, Eq, Debug)]
+pub enum GetFeatureUsageFunnelError {
+    InvalidBoundsPassed,
+    ExceededMaxNumberOfItemsAllowedInOneRequest,
+}
+
+shared_utils::error_codes! {
+    GetFeatureUsageFunnelError {
+        InvalidBoundsPassed => (5401, InvalidInput, false),
+        ExceededMaxNumberOfItemsAllowedInOneRequest => (5402, LimitExceeded, false),
+    }
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct FeatureUsageFunnelStep {
+    pub feature: Feature,
+    // * Users who used the feature at least once that day
+    pub user_count: u64,
+    pub usage_count: u64,
+    pub percentage_of_active_users: u64,
+}
+
+/// Users count as active on a day if they used at least one tracked feature
+#[derive(CandidType, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct FeatureUsageFunnelForDay {
+    pub day: DayNumber,
+    pub active_user_count: u64,
+    pub steps: Vec<FeatureUsageFunnelStep>,
+}
+
+#[derive(Default, Serialize, Deserialize, Clone, Copy, Debug)]
+struct FeatureUsageTotals {
+    user_count: u64,
+    usage_count: u64,
+}
+
+/// Daily feature usage summed up across every individual user canister
+#[derive(Default, Serialize, Deserialize, Debug)]
+pub struct PlatformFeatureUsage {
+    active_user_counts: BTreeMap<DayNumber, u64>,
+    feature_totals: BTreeMap<(DayNumber, Feature), FeatureUsageTotals>,
+    // * Retried pushes don't count the same day of a canister twice
+    last_day_received_by_canister_id: BTreeMap<Principal, DayNumber>,
+}
+
+impl PlatformFeatureUsage {
+    pub fn record_canister_usage(
+        &mut self,
+        canister_id: Principal,
+        feature_usage_for_days: Vec<FeatureUsageForDay>,
+    ) {
+        for feature_usage_for_day in feature_usage_for_days {
+            let last_day_received = self.last_day_received_by_canister_id.get(&canister_id);
+            if last_day_received.is_some_and(|day| *day >= feature_usage_for_day.day) {
+                continue;
+            }
+
+            let used_features = feature_usage_for_day
+                .usage_counts
+                .into_iter()
+                .filter(|(_, usage_count)| *usage_count > 0)
+                .collect::<Vec<_>>();
+            if used_features.is_empty() {
+                continue;
+            }
+
+            *self
+                .active_user_counts
+                .entry(feature_usage_for_day.day)
+                .or_default() += 1;
+            for (feature, usage_count) in used_features {
+                let totals = self
+                    .feature_totals
+                    .entry((feature_usage_for_day.day, feature))
+                    .or_default();
+                totals.user_count += 1;
+                totals.usage_count += usage_count;
+            }
+
+            self.last_day_received_by_canister_id
+                .insert(canister_id, feature_usage_for_day.day);
+        }
+    }
+
+    /// Days with any activity between `from_day` and `to_day`, both included, oldest first
+    pub fn get_funnel(
+        &self,
+        from_day: DayNumber,
+        to_day: DayNumber,
+    ) -> Result<Vec<FeatureUsageFunnelForDay>, GetFeatureUsageFunnelError> {
+        if from_day > to_day {
+            return Err(GetFeatureUsageFunnelError::InvalidBoundsPassed);
+        }
+
+        if to_day - from_day + 1 > MAX_FEATURE_USAGE_FUNNEL_DAYS_PER_REQUEST {
+            return Err(GetFeatureUsageFunnelError::ExceededMaxNumberOfItemsAllowedInOneRequest);
+        }
+
+        Ok(self
+            .active_user_counts
+            .range(from_day..=to_day)
+            .map(|(day, active_user_count)| FeatureUsageFunnelForDay {
+                day: *day,
+                active_user_count: *active_user_count,
+                steps: self
+                    .feature_totals
+                    .range((*day, Feature::BetPlaced)..)
+                    .take_while(|((feature_day, _), _)| feature_day == day)
+                    .map(|((_, feature), totals)| FeatureUsageFunnelStep {
+                        feature: *feature,
+                        user_count: totals.user_count,
+                        usage_count: totals.usage_count,
+                        percentage_of_active_users: totals.user_count * 100 / active_user_count,
+                    })
+                    .collect(),
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use test_utils::setup::test_constants::v2::canister;
+
+    use super::*;
+
+    #[test]
+    fn test_funnel_counts_each_canister_once_per_day() {
+        let mut platform_feature_usage = PlatformFeatureUsage::default();
+
+        platform_feature_usage.record_canister_usage(
+            canister("bettor"),
+            vec![FeatureUsageForDay {
+                day: 10,
+                usage_counts: vec![(Feature::BetPlaced, 3), (Feature::PostCreated, 1)],
+            }],
+        );
+        // * a retried push of the same day is ignored
+        platform_feature_usage.record_canister_usage(
+            canister("bettor"),
+            vec![FeatureUsageForDay {
+                day: 10,
+                usage_counts: vec![(Feature::BetPlaced, 3), (Feature::PostCreated, 1)],
+            }],
+        );
+        (0..3).for_each(|index| {
+            platform_feature_usage.record_canister_usage(
+                canister(&format!("follower_{}", index)),
+                vec![FeatureUsageForDay {
+                    day: 10,
+                    usage_counts: vec![(Feature::ProfileFollowed, 1)],
+                }],
+            );
+        });
+
+        assert_eq!(
+            platform_feature_usage.get_funnel(9, 11),
+            Ok(vec![FeatureUsageFunnelForDay {
+                day: 10,
+                active_user_count: 4,
+                steps: vec![
+                    FeatureUsageFunnelStep {
+                        feature: Feature::BetPlaced,
+                        user_count: 1,
+                        usage_count: 3,
+                        percentage_of_active_users: 25,
+                    },
+                    FeatureUsageFunnelStep {
+                        feature: Feature::PostCreated,
+                        user_count: 1,
+                        usage_count: 1,
+                        percentage_of_active_users: 25,
+                    },
+                    FeatureUsageFunnelStep {
+                        feature: Feature::ProfileFollowed,
+                        user_count: 3,
+                        usage_count: 3,
+                        percentage_of_active_users: 75,
+                    },
+                ],
+            }])
+        );
+    }
+
+    #[test]
+    fn test_funnel_bounds_are_checked() {
+        let platform_feature_usage = PlatformFeatureUsage::default();
+
+        assert_eq!(
+            platform_feature_usage.get_funnel(2, 1),
+            Err(GetFeatureUsageFunnelError::InvalidBoundsPassed)
+        );
+        assert_eq!(
+            platform_feature_usage.get_funnel(0, MAX_FEATURE_USAGE_FUNNEL_DAYS_PER_REQUEST),
+            Err(GetFeatureUsageFunnelError::ExceededMaxNumberOfItemsAllowedInOneRequest)
+        );
+        assert_eq!(
+            platform_feature_usage.get_funnel(0, MAX_FEATURE_USAGE_FUNNEL_DAYS_PER_REQUEST - 1),
+            Ok(vec![])
+        );
+    }
+}
@@ -0,0 +1,108 @@
+use std::cell::RefCell;
+
+use ic_stable_structures::{
+    memory_manager::{MemoryId, MemoryManager, VirtualMemory},
+    DefaultMemoryImpl,
+};
+use shared_utils::common::types::memory_id_registry::{MemoryIdRegistry, MemoryIdRegistryError};
+
+thread_local! {
+    static MEMORY_MANAGER: RefCell<MemoryManager<DefaultMemoryImpl>> = RefCell::new(MemoryManager::init(DefaultMemoryImpl::default()));
+}
+
+pub type Memory = VirtualMemory<DefaultMemoryImpl>;
+
+// * Every memory region handed out by the memory manager, keyed by the name it is
+// * persisted under in the memory id registry. Add new regions here before using them.
+const ALL_MEMORY_REGIONS: [(&str, u8); 5] = [
+    ("upgrades", UPGRADES_MEMORY_ID),
+    ("metrics_time_series", METRICS_TIME_SERIES_MEMORY_ID),
+    (
+        "call_queue_pending_calls",
+        CALL_QUEUE_PENDING_CALLS_MEMORY_ID,
+    ),
+    (
+        "call_queue_dead_lettered_calls",
+        CALL_QUEUE_DEAD_LETTERED_CALLS_MEMORY_ID,
+    ),
+    (
+        "user_shard_routing_table",
+        USER_SHARD_ROUTING_TABLE_MEMORY_ID,
+    ),
+];
+
+pub fn register_all_memory_regions(
+    memory_id_registry: &mut MemoryIdRegistry,
+) -> Result<(), MemoryIdRegistryError> {
+    ALL_MEMORY_REGIONS
+        .iter()
+        .try_for_each(|(name, memory_id)| memory_id_registry.register(name, *memory_id).map(|_| ()))
+}
+
+/// Wasms from before the memory manager was introduced serialized the heap straight to the
+/// start of stable memory. Must be checked before anything touches the memory manager,
+/// which takes over stable memory on first use.
+pub fn is_stable_memory_managed_by_memory_manager() -> bool {
+    if ic_cdk::api::stable::stable64_size() == 0 {
+        return true;
+    }
+
+    let mut magic_bytes = [0; 3];
+    ic_cdk::api::stable::stable64_read(0, &mut magic_bytes);
+    &magic_bytes == b"MGR"
+}
+
+fn get_memory(memory_id: u8) -> Memory {
+    MEMORY_MANAGER.with(|memory_manager_ref_cell| {
+        memory_manager_ref_cell
+            .borrow_mut()
+            .get(MemoryId::new(memory_id))
+    })
+}
+
+// * Heap data serialized across upgrades.
+const UPGRADES_MEMORY_ID: u8 = 0;
+pub fn get_upgrades_memory() -> Memory {
+    get_memory(UPGRADES_MEMORY_ID)
+}
+
+// * Minute, hour and day rollups of the user index metrics.
+const METRICS_TIME_SERIES_MEMORY_ID: u8 = 1;
+pub fn get_metrics_time_series_memory() -> Memory {
+    get_memory(METRICS_TIME_SERIES_MEMORY_ID)
+}
+
+// * Fire and forget calls to other canisters yet to go through, and those that never did.
+const CALL_QUEUE_PENDING_CALLS_MEMORY_ID: u8 = 2;
+pub fn get_call_queue_pending_calls_memory() -> Memory {
+    get_memory(CALL_QUEUE_PENDING_CALLS_MEMORY_ID)
+}
+
+const CALL_QUEUE_DEAD_LETTERED_CALLS_MEMORY_ID: u8 = 3;
+pub fn get_call_queue_dead_lettered_calls_memory() -> Memory {
+    get_memory(CALL_QUEUE_DEAD_LETTERED_CALLS_MEMORY_ID)
+}
+
+// * Which shard each user that signed up through one of them ended up on.
+const USER_SHARD_ROUTING_TABLE_MEMORY_ID: u8 = 4;
+pub fn get_user_shard_routing_table_memory() -> Memory {
+    get_memory(USER_SHARD_ROUTING_TABLE_MEMORY_ID)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_register_all_memory_regions() {
+        let mut memory_id_registry = MemoryIdRegistry::default();
+
+        assert_eq!(register_all_memory_regions(&mut memory_id_registry), Ok(()));
+        assert_eq!(register_all_memory_regions(&mut memory_id_registry), Ok(()));
+
+        assert_eq!(
+            memory_id_registry.get_name_for_memory_id(METRICS_TIME_SERIES_MEMORY_ID),
+            Some("metrics_time_series")
+        );
+    }
+}
@@ -0,0 +1,506 @@
+use std::{
+    borrow::Cow,
+    time::{Duration, SystemTime},
+};
+
+use candid::{CandidType, Deserialize};
+use ic_stable_structures::{BoundedStorable, Memory, StableBTreeMap, Storable};
+use serde::Serialize;
+
+pub const METRICS_SAMPLING_INTERVAL: Duration = Duration::from_secs(60);
+pub const MAX_METRICS_DATA_POINTS_PER_REQUEST: u64 = 1500;
+
+// * Tags are persisted as part of the bucket keys. Append new metrics, never reorder them.
+#[derive(CandidType, Deserialize, Serialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum UserIndexMetric {
+    UserCanisterCount,
+    CanisterCreationFailureCount,
+    CycleBalance,
+    ShadowBannedUserCount,
+    UpgradeFailureCount,
+}
+
+pub const ALL_USER_INDEX_METRICS: [UserIndexMetric; 5] = [
+    UserIndexMetric::UserCanisterCount,
+    UserIndexMetric::CanisterCreationFailureCount,
+    UserIndexMetric::CycleBalance,
+    UserIndexMetric::ShadowBannedUserCount,
+    UserIndexMetric::UpgradeFailureCount,
+];
+
+impl UserIndexMetric {
+    fn tag(&self) -> u8 {
+        match self {
+            Self::UserCanisterCount => 0,
+            Self::CanisterCreationFailureCount => 1,
+            Self::CycleBalance => 2,
+            Self::ShadowBannedUserCount => 3,
+            Self::UpgradeFailureCount => 4,
+        }
+    }
+}
+
+/// Every sample lands in a minute, an hour and a day bucket. Finer buckets are dropped
+/// sooner, so long ranges are only available at coarser resolutions.
+#[derive(CandidType, Deserialize, Serialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MetricsResolution {
+    Minute,
+    Hour,
+    Day,
+}
+
+pub const ALL_METRICS_RESOLUTIONS: [MetricsResolution; 3] = [
+    MetricsResolution::Minute,
+    MetricsResolution::Hour,
+    MetricsResolution::Day,
+];
+
+impl MetricsResolution {
+    fn tag(&self) -> u8 {
+        match self {
+            Self::Minute => 0,
+            Self::Hour => 1,
+            Self::Day => 2,
+        }
+    }
+
+    pub fn get_bucket_duration(&self) -> Duration {
+        match self {
+            Self::Minute => Duration::from_secs(60),
+            Self::Hour => Duration::from_secs(60 * 60),
+            Self::Day => Duration::from_secs(24 * 60 * 60),
+        }
+    }
+
+    pub fn get_retention(&self) -> Duration {
+        match self {
+            Self::Minute => Duration::from_secs(24 * 60 * 60),
+            Self::Hour => Duration::from_secs(30 * 24 * 60 * 60),
+            Self::Day => Duration::from_secs(2 * 365 * 24 * 60 * 60),
+        }
+    }
+
+    fn get_bucket_start_secs(&self, time: &SystemTime) -> u64 {
+        let secs_since_epoch = time
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        let bucket_duration_secs = self.get_bucket_duration().as_secs();
+
+        secs_since_epoch - secs_since_epoch % bucket_duration_secs
+    }
+}
+
+/// Everything sampled for one metric within one bucket
+#[derive(CandidType, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MetricsDataPoint {
+    pub bucket_start: SystemTime,
+    pub sample_count: u64,
+    pub min: u64,
+    pub max: u64,
+    pub average: u64,
+    pub last: u64,
+}
+
+#[derive(CandidType, Deserialize, PartialEq, Eq, Debug)]
+pub enum GetUserIndexMetricsError {
+    InvalidBoundsPassed,
+    ExceededMaxNumberOfItemsAllowedInOneRequest,
+}
+
+shared_utils::error_codes! {
+    GetUserIndexMetricsError {
+        InvalidBoundsPassed => (5301, InvalidInput, false),
+        ExceededMaxNumberOfItemsAllowedInOneRequest => (5302, LimitExceeded, false),
+    }
+}
+
+// * Laid out as resolution, metric, then the big endian bucket start, so that the stable
+// * map keeps each series contiguous and in time order
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+struct MetricsBucketKey([u8; 10]);
+
+impl MetricsBucketKey {
+    fn new(resolution: MetricsResolution, metric: UserIndexMetric, bucket_start_secs: u64) -> Self {
+        let mut key_bytes = [0; 10];
+        key_bytes[0] = resolution.tag();
+        key_bytes[1] = metric.tag();
+        key_bytes[2..].copy_from_slice(&bucket_start_secs.to_be_bytes());
+        Self(key_bytes)
+    }
+
+    fn get_bucket_start_secs(&self) -> u64 {
+        u64::from_be_bytes(self.0[2..].try_into().unwrap())
+    }
+}
+
+impl Storable for MetricsBucketKey {
+    fn to_bytes(&self) -> Cow<'_, [u8]> {
+        Cow::Borrowed(&self.0)
+    }
+
+    fn from_bytes(bytes: Cow<[u8]>) -> Self {
+        Self(bytes.as_ref().try_into().unwrap())
+    }
+}
+
+impl BoundedStorable for MetricsBucketKey {
+    const MAX_SIZE: u32 = 10;
+    const IS_FIXED_SIZE: bool = true;
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct MetricsBucket {
+    sample_count: u64,
+    sum: u128,
+    min: u64,
+    max: u64,
+    last: u64,
+}
+
+impl MetricsBucket {
+    fn new(value: u64) -> Self {
+        Self {
+            sample_count: 1,
+            sum: value as u128,
+            min: value,
+            max: value,
+            last: value,
+        }
+    }
+
+    fn add_sample(&mut self, value: u64) {
+        self.sample_count += 1;
+        self.sum += value as u128;
+        self.min = self.min.min(value);
+        self.max = self.max.max(value);
+        self.last = value;
+    }
+
+    fn to_data_point(self, bucket_start_secs: u64) -> MetricsDataPoint {
+        MetricsDataPoint {
+            bucket_start: SystemTime::UNIX_EPOCH + Duration::from_secs(bucket_start_secs),
+            sample_count: self.sample_count,
+            min: self.min,
+            max: self.max,
+            average: (self.sum / self.sample_count as u128) as u64,
+            last: self.last,
+        }
+    }
+}
+
+impl Storable for MetricsBucket {
+    fn to_bytes(&self) -> Cow<'_, [u8]> {
+        let mut bytes = Vec::with_capacity(Self::MAX_SIZE as usize);
+        bytes.extend(self.sample_count.to_le_bytes());
+        bytes.extend(self.sum.to_le_bytes());
+        bytes.extend(self.min.to_le_bytes());
+        bytes.extend(self.max.to_le_bytes());
+        bytes.extend(self.last.to_le_bytes());
+        Cow::Owned(bytes)
+    }
+
+    fn from_bytes(bytes: Cow<[u8]>) -> Self {
+        let read_u64 =
+            |offset: usize| u64::from_le_bytes(bytes[offset..offset + 8].try_into().unwrap());
+
+        Self {
+            sample_count: read_u64(0),
+            sum: u128::from_le_bytes(bytes[8..24].try_into().unwrap()),
+            min: read_u64(24),
+            max: read_u64(32),
+            last: read_u64(40),
+        }
+    }
+}
+
+impl BoundedStorable for MetricsBucket {
+    const MAX_SIZE: u32 = 48;
+    const IS_FIXED_SIZE: bool = true;
+}
+
+/// Time series of the user index metrics, kept in their own stable memory region so that
+/// they neither weigh on the heap nor have to be serialized on upgrades. Samples are rolled
+/// up into minute, hour and day buckets as they are recorded, and buckets older than their
+/// resolution's retention are dropped.
+pub struct MetricsTimeSeries<M: Memory> {
+    buckets: StableBTreeMap<MetricsBucketKey, MetricsBucket, M>,
+}
+
+impl<M: Memory> MetricsTimeSeries<M> {
+    pub fn init(memory: M) -> Self {
+        Self {
+            buckets: StableBTreeMap::init(memory),
+        }
+    }
+
+    pub fn record(&mut self, metric: UserIndexMetric, value: u64, sampled_at: &SystemTime) {
+        for resolution in ALL_METRICS_RESOLUTIONS {
+            let key = MetricsBucketKey::new(
+                resolution,
+                metric,
+                resolution.get_bucket_start_secs(sampled_at),
+            );
+
+            let bucket = match self.buckets.get(&key) {
+                Some(mut bucket) => {
+                    bucket.add_sample(value);
+                    bucket
+                }
+                None => MetricsBucket::new(value),
+            };
+            self.buckets.insert(key, bucket);
+
+            self.drop_expired_buckets(resolution, metric, sampled_at);
+        }
+    }
+
+    /// Buckets starting anywhere from `from` to `to`, oldest first
+    pub fn get_data_points(
+        &self,
+        metric: UserIndexMetric,
+        resolution: MetricsResolution,
+        from: &SystemTime,
+        to: &SystemTime,
+    ) -> Result<Vec<MetricsDataPoint>, GetUserIndexMetricsError> {
+        if from > to {
+            return Err(GetUserIndexMetricsError::InvalidBoundsPassed);
+        }
+
+        let from_bucket_start_secs = resolution.get_bucket_start_secs(from);
+        let to_bucket_start_secs = resolution.get_bucket_start_secs(to);
+        let number_of_buckets_in_range = (to_bucket_start_secs - from_bucket_start_secs)
+            / resolution.get_bucket_duration().as_secs()
+            + 1;
+
+        if number_of_buckets_in_range > MAX_METRICS_DATA_POINTS_PER_REQUEST {
+            return Err(GetUserIndexMetricsError::ExceededMaxNumberOfItemsAllowedInOneRequest);
+        }
+
+        Ok(self
+            .buckets
+            .range(
+                MetricsBucketKey::new(resolution, metric, from_bucket_start_secs)
+                    ..=MetricsBucketKey::new(resolution, metric, to_bucket_start_secs),
+            )
+            .map(|(key, bucket)| bucket.to_data_point(key.get_bucket_start_secs()))
+            .collect())
+    }
+
+    fn drop_expired_buckets(
+        &mut self,
+        resolution: MetricsResolution,
+        metric: UserIndexMetric,
+        current_time: &SystemTime,
+    ) {
+        let Some(oldest_retained_time) = current_time.checked_sub(resolution.get_retention())
+        else {
+            return;
+        };
+
+        let expired_keys: Vec<MetricsBucketKey> = self
+            .buckets
+            .range(
+                MetricsBucketKey::new(resolution, metric, 0)
+                    ..=MetricsBucketKey::new(
+                        resolution,
+                        metric,
+                        resolution.get_bucket_start_secs(&oldest_retained_time),
+                    ),
+            )
+            .map(|(key, _bucket)| key)
+            .collect();
+
+        expired_keys.iter().for_each(|key| {
+            self.buckets.remove(key);
+        });
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ic_stable_structures::VectorMemory;
+
+    use super::*;
+
+    fn time_at(secs_since_epoch: u64) -> SystemTime {
+        SystemTime::UNIX_EPOCH + Duration::from_secs(secs_since_epoch)
+    }
+
+    #[test]
+    fn test_samples_are_rolled_up_into_every_resolution() {
+        let mut metrics_time_series = MetricsTimeSeries::init(VectorMemory::default());
+        let day_start = 1_700_006_400;
+
+        // * one sample a minute for two hours, counting up from 1
+        (0..120).for_each(|minute| {
+            metrics_time_series.record(
+                UserIndexMetric::UserCanisterCount,
+                minute + 1,
+                &time_at(day_start + minute * 60),
+            );
+        });
+        metrics_time_series.record(UserIndexMetric::CycleBalance, 5_000, &time_at(day_start));
+
+        let minute_data_points = metrics_time_series
+            .get_data_points(
+                UserIndexMetric::UserCanisterCount,
+                MetricsResolution::Minute,
+                &time_at(day_start + 10 * 60),
+                &time_at(day_start + 12 * 60 + 59),
+            )
+            .unwrap();
+        assert_eq!(minute_data_points.len(), 3);
+        assert_eq!(
+            minute_data_points[0],
+            MetricsDataPoint {
+                bucket_start: time_at(day_start + 10 * 60),
+                sample_count: 1,
+                min: 11,
+                max: 11,
+                average: 11,
+                last: 11,
+            }
+        );
+
+        let hour_data_points = metrics_time_series
+            .get_data_points(
+                UserIndexMetric::UserCanisterCount,
+                MetricsResolution::Hour,
+                &time_at(day_start),
+                &time_at(day_start + 24 * 60 * 60 - 1),
+            )
+            .unwrap();
+        assert_eq!(
+            hour_data_points,
+            vec![
+                MetricsDataPoint {
+                    bucket_start: time_at(day_start),
+                    sample_count: 60,
+                    min: 1,
+                    max: 60,
+                    average: 30,
+                    last: 60,
+                },
+                MetricsDataPoint {
+                    bucket_start: time_at(day_start + 60 * 60),
+                    sample_count: 60,
+                    min: 61,
+                    max: 120,
+                    average: 90,
+                    last: 120,
+                },
+            ]
+        );
+
+        let day_data_points = metrics_time_series
+            .get_data_points(
+                UserIndexMetric::UserCanisterCount,
+                MetricsResolution::Day,
+                &time_at(day_start),
+                &time_at(day_start),
+            )
+            .unwrap();
+        assert_eq!(day_data_points.len(), 1);
+        assert_eq!(day_data_points[0].sample_count, 120);
+        assert_eq!(day_data_points[0].last, 120);
+
+        // * series don't bleed into each other
+        assert_eq!(
+            metrics_time_series
+                .get_data_points(
+                    UserIndexMetric::CycleBalance,
+                    MetricsResolution::Day,
+                    &time_at(day_start),
+                    &time_at(day_start),
+                )
+                .unwrap()[0]
+                .sample_count,
+            1
+        );
+        assert!(metrics_time_series
+            .get_data_points(
+                UserIndexMetric::ShadowBannedUserCount,
+                MetricsResolution::Day,
+                &time_at(day_start),
+                &time_at(day_start),
+            )
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_buckets_past_their_retention_are_dropped() {
+        let memory = VectorMemory::default();
+        let mut metrics_time_series = MetricsTimeSeries::init(memory.clone());
+        let start = 1_700_006_400;
+
+        metrics_time_series.record(UserIndexMetric::UserCanisterCount, 1, &time_at(start));
+        let a_day_later = start + 24 * 60 * 60;
+        metrics_time_series.record(UserIndexMetric::UserCanisterCount, 2, &time_at(a_day_later));
+
+        let get_data_points = |metrics_time_series: &MetricsTimeSeries<VectorMemory>,
+                               resolution: MetricsResolution| {
+            metrics_time_series
+                .get_data_points(
+                    UserIndexMetric::UserCanisterCount,
+                    resolution,
+                    &time_at(start),
+                    &time_at(a_day_later),
+                )
+                .unwrap()
+        };
+
+        assert_eq!(
+            get_data_points(&metrics_time_series, MetricsResolution::Minute)
+                .iter()
+                .map(|data_point| data_point.last)
+                .collect::<Vec<_>>(),
+            vec![2]
+        );
+        assert_eq!(
+            get_data_points(&metrics_time_series, MetricsResolution::Hour).len(),
+            2
+        );
+
+        // * still there after reloading from stable memory
+        let reloaded_metrics_time_series = MetricsTimeSeries::init(memory);
+        assert_eq!(
+            get_data_points(&reloaded_metrics_time_series, MetricsResolution::Day).len(),
+            2
+        );
+    }
+
+    #[test]
+    fn test_get_data_points_bounds() {
+        let metrics_time_series = MetricsTimeSeries::init(VectorMemory::default());
+
+        assert_eq!(
+            metrics_time_series.get_data_points(
+                UserIndexMetric::UserCanisterCount,
+                MetricsResolution::Minute,
+                &time_at(120),
+                &time_at(60),
+            ),
+            Err(GetUserIndexMetricsError::InvalidBoundsPassed)
+        );
+        assert_eq!(
+            metrics_time_series.get_data_points(
+                UserIndexMetric::UserCanisterCount,
+                MetricsResolution::Minute,
+                &time_at(0),
+                &time_at(MAX_METRICS_DATA_POINTS_PER_REQUEST * 60),
+            ),
+            Err(GetUserIndexMetricsError::ExceededMaxNumberOfItemsAllowedInOneRequest)
+        );
+        assert_eq!(
+            metrics_time_series.get_data_points(
+                UserIndexMetric::UserCanisterCount,
+                MetricsResolution::Minute,
+                &time_at(0),
+                &time_at((MAX_METRICS_DATA_POINTS_PER_REQUEST - 1) * 60),
+            ),
+            Ok(vec![])
+        );
+    }
+}
@@ -1,19 +1,245 @@
-use std::collections::BTreeMap;
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    time::SystemTime,
+};
 
-use candid::{CandidType, Deserialize, Principal};
+use candid::{Deserialize, Principal};
 use serde::Serialize;
-use shared_utils::common::types::known_principal::KnownPrincipalMap;
+use shared_utils::{
+    canister_specific::{
+        individual_user_template::types::hot_or_not::SlotId,
+        user_index::types::{
+            dispute::DisputedSlot,
+            moderation::ReportedPost,
+            tournament::TournamentId,
+            user_index_shard::{UserIndexShards, UserShardRoutingTable},
+            username::ReservedUsernameAppeal,
+        },
+    },
+    common::{
+        types::{
+            app_primitive_type::PostId, event_counter::EventCounters,
+            known_principal::KnownPrincipalMap, memory_id_registry::MemoryIdRegistry,
+        },
+        utils::call_queue::CallQueue,
+    },
+};
 
-use self::{canister_upgrade::UpgradeStatus, configuration::Configuration};
+use self::{
+    canister_creation::CanisterCreationFailureReason,
+    canister_upgrade::{
+        rolling_upgrade::RollingUpgrade, upgrade_run::UpgradeRunReports, UpgradeRunKind,
+        UpgradeStatus,
+    },
+    configuration::Configuration,
+    feature_usage::PlatformFeatureUsage,
+    memory::{
+        get_call_queue_dead_lettered_calls_memory, get_call_queue_pending_calls_memory,
+        get_metrics_time_series_memory, get_user_shard_routing_table_memory, Memory,
+    },
+    metrics::MetricsTimeSeries,
+    replication::UserCanisterReplication,
+    stale_canister_gc::StaleCanisterGc,
+    tournament::Tournament,
+};
 
+pub mod canister_creation;
 pub mod canister_upgrade;
 pub mod configuration;
+pub mod feature_usage;
+pub mod memory;
+pub mod metrics;
+pub mod replication;
+pub mod stale_canister_gc;
+pub mod tournament;
 
-#[derive(Default, CandidType, Deserialize, Serialize)]
+#[derive(Deserialize, Serialize)]
 pub struct CanisterData {
     pub configuration: Configuration,
     pub last_run_upgrade_status: UpgradeStatus,
     pub known_principal_ids: KnownPrincipalMap,
     pub user_principal_id_to_canister_id_map: BTreeMap<Principal, Principal>,
     pub unique_user_name_to_user_principal_id_map: BTreeMap<String, Principal>,
+    #[serde(default)]
+    pub tournaments: BTreeMap<TournamentId, Tournament>,
+    // * Key is the individual user canister id, value is a candid encoded
+    // * IndividualUserTemplateInitArgs
+    #[serde(default)]
+    pub upgrade_arg_overrides: BTreeMap<Principal, Vec<u8>>,
+    // * Counts every failed create_canister or install_code attempt, retries included
+    #[serde(default)]
+    pub canister_creation_failures: BTreeMap<CanisterCreationFailureReason, u64>,
+    #[serde(default)]
+    pub shadow_banned_user_principal_ids: BTreeSet<Principal>,
+    #[serde(default)]
+    pub memory_id_registry: MemoryIdRegistry,
+    #[serde(skip, default = "init_metrics_time_series")]
+    pub metrics_time_series: MetricsTimeSeries<Memory>,
+    // * Key is (post creator canister id, post id, slot id)
+    #[serde(default)]
+    pub slot_outcome_disputes: BTreeMap<(Principal, PostId, SlotId), DisputedSlot>,
+    #[serde(default)]
+    pub platform_feature_usage: PlatformFeatureUsage,
+    // * Key is the normalized reserved username, value is the user it was granted to
+    #[serde(default)]
+    pub reserved_username_grants: BTreeMap<String, Principal>,
+    // * Key is (normalized reserved username, user principal id)
+    #[serde(default)]
+    pub reserved_username_appeals: BTreeMap<(String, Principal), ReservedUsernameAppeal>,
+    // * Key is the individual user canister id. Canisters that were never reinstalled are left
+    // * out and are at epoch 0.
+    #[serde(default)]
+    pub canister_binding_epochs: BTreeMap<Principal, u64>,
+    #[serde(default)]
+    pub event_counters: EventCounters,
+    // * Key is the normalized referral code, value is the principal id of the user it refers to
+    #[serde(default)]
+    pub referral_code_owners: BTreeMap<String, Principal>,
+    #[serde(default)]
+    pub next_referral_code_number: u64,
+    #[serde(default)]
+    pub stale_canister_gc: StaleCanisterGc,
+    // * Emptied canisters of collected users, handed to new users before creating any more
+    #[serde(default)]
+    pub provisioning_pool: BTreeSet<Principal>,
+    #[serde(default)]
+    pub upgrade_run_reports: UpgradeRunReports,
+    // * Key is the user principal id
+    #[serde(default)]
+    pub user_canister_replications: BTreeMap<Principal, UserCanisterReplication>,
+    // * Moderation queue. Key is (post creator canister id, post id)
+    #[serde(default)]
+    pub reported_posts: BTreeMap<(Principal, PostId), ReportedPost>,
+    #[serde(skip, default = "init_call_queue")]
+    pub call_queue: CallQueue<Memory>,
+    // * Secondary user indexes on other subnets that new signups are spread across
+    #[serde(default)]
+    pub user_index_shards: UserIndexShards,
+    #[serde(skip, default = "init_user_shard_routing_table")]
+    pub user_shard_routing_table: UserShardRoutingTable<Memory>,
+    // * The latest rolling upgrade started by an admin, kept after it finishes for its report
+    #[serde(default)]
+    pub rolling_upgrade: Option<RollingUpgrade>,
+    // * Key is the individual user canister id, value is when it was last topped up on request
+    #[serde(default)]
+    pub cycle_top_ups_on_request: BTreeMap<Principal, SystemTime>,
+}
+
+impl Default for CanisterData {
+    fn default() -> Self {
+        Self {
+            configuration: Configuration::default(),
+            last_run_upgrade_status: UpgradeStatus::default(),
+            known_principal_ids: KnownPrincipalMap::default(),
+            user_principal_id_to_canister_id_map: BTreeMap::default(),
+            unique_user_name_to_user_principal_id_map: BTreeMap::default(),
+            tournaments: BTreeMap::default(),
+            upgrade_arg_overrides: BTreeMap::default(),
+            canister_creation_failures: BTreeMap::default(),
+            shadow_banned_user_principal_ids: BTreeSet::default(),
+            memory_id_registry: MemoryIdRegistry::default(),
+            metrics_time_series: init_metrics_time_series(),
+            slot_outcome_disputes: BTreeMap::default(),
+            platform_feature_usage: PlatformFeatureUsage::default(),
+            reserved_username_grants: BTreeMap::default(),
+            reserved_username_appeals: BTreeMap::default(),
+            canister_binding_epochs: BTreeMap::default(),
+            event_counters: EventCounters::default(),
+            referral_code_owners: BTreeMap::default(),
+            next_referral_code_number: 0,
+            stale_canister_gc: StaleCanisterGc::default(),
+            provisioning_pool: BTreeSet::default(),
+            upgrade_run_reports: UpgradeRunReports::default(),
+            user_canister_replications: BTreeMap::default(),
+            reported_posts: BTreeMap::default(),
+            call_queue: init_call_queue(),
+            user_index_shards: UserIndexShards::default(),
+            user_shard_routing_table: init_user_shard_routing_table(),
+            rolling_upgrade: None,
+            cycle_top_ups_on_request: BTreeMap::default(),
+        }
+    }
+}
+
+impl CanisterData {
+    /// Looks in this index first, then in the routing table for users that signed up through
+    /// a shard
+    pub fn get_user_canister_id(&self, user_principal_id: &Principal) -> Option<Principal> {
+        self.user_principal_id_to_canister_id_map
+            .get(user_principal_id)
+            .copied()
+            .or_else(|| {
+                self.user_shard_routing_table
+                    .get(user_principal_id)
+                    .map(|route| route.user_canister_id)
+            })
+    }
+
+    pub fn get_binding_epoch(&self, user_canister_id: &Principal) -> u64 {
+        self.canister_binding_epochs
+            .get(user_canister_id)
+            .copied()
+            .unwrap_or_default()
+    }
+
+    pub fn get_standby_canister_id(&self, user_principal_id: &Principal) -> Option<Principal> {
+        self.user_canister_replications
+            .get(user_principal_id)
+            .and_then(|replication| replication.standby_canister_id)
+    }
+
+    /// The run upgrading user canisters, if any. A run that is waiting for the upgrade window
+    /// to open still counts, as it picks up again on its own.
+    pub fn get_upgrade_run_in_progress(&self) -> Option<UpgradeRunKind> {
+        if self
+            .rolling_upgrade
+            .as_ref()
+            .is_some_and(|rolling_upgrade| rolling_upgrade.is_active())
+        {
+            Some(UpgradeRunKind::RollingUpgrade)
+        } else if self.upgrade_run_reports.has_unfinished_run() {
+            Some(UpgradeRunKind::FullRun)
+        } else {
+            None
+        }
+    }
+
+    /// Drops a collected user from the index and puts their emptied canister in the
+    /// provisioning pool. The binding epoch is bumped so that other canisters can tell the
+    /// canister id now belongs to someone else once it is handed out again.
+    pub fn release_canister_to_provisioning_pool(
+        &mut self,
+        user_principal_id: &Principal,
+        user_canister_id: Principal,
+    ) {
+        self.user_principal_id_to_canister_id_map
+            .remove(user_principal_id);
+        self.unique_user_name_to_user_principal_id_map
+            .retain(|_, principal_id| principal_id != user_principal_id);
+        self.upgrade_arg_overrides.remove(&user_canister_id);
+        self.cycle_top_ups_on_request.remove(&user_canister_id);
+        self.stale_canister_gc
+            .flagged_canisters
+            .remove(&user_canister_id);
+
+        let binding_epoch = self.get_binding_epoch(&user_canister_id) + 1;
+        self.canister_binding_epochs
+            .insert(user_canister_id, binding_epoch);
+        self.provisioning_pool.insert(user_canister_id);
+    }
+}
+
+fn init_metrics_time_series() -> MetricsTimeSeries<Memory> {
+    MetricsTimeSeries::init(get_metrics_time_series_memory())
+}
+
+fn init_call_queue() -> CallQueue<Memory> {
+    CallQueue::init(
+        get_call_queue_pending_calls_memory(),
+        get_call_queue_dead_lettered_calls_memory(),
+    )
+}
+
+fn init_user_shard_routing_table() -> UserShardRoutingTable<Memory> {
+    UserShardRoutingTable::init(get_user_shard_routing_table_memory())
 }
@@ -0,0 +1,12 @@
+use candid::{Deserialize, Principal};
+use serde::Serialize;
+
+/// The standby kept for a high value creator's canister. Users without one have no entry
+/// until a standby is first provisioned for them.
+#[derive(Deserialize, Serialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct UserCanisterReplication {
+    pub standby_canister_id: Option<Principal>,
+    // * Bumped on every promotion. The canister promoted from, if it is ever reached again,
+    // * fences itself once it sees a later epoch than its own.
+    pub epoch: u64,
+}
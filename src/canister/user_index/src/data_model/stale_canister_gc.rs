@@ -0,0 +1,175 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    time::{Duration, SystemTime},
+};
+
+use candid::{Deserialize, Principal};
+use serde::Serialize;
+use shared_utils::canister_specific::individual_user_template::types::onboarding::OnboardingStatus;
+
+// * Canisters without posts or bets are stale once their owner has been inactive this long
+pub const STALE_CANISTER_INACTIVITY_PERIOD: Duration = Duration::from_secs(90 * 24 * 60 * 60);
+// * How long owners have to become active again or opt out once their canister is flagged
+pub const STALE_CANISTER_GRACE_PERIOD: Duration = Duration::from_secs(14 * 24 * 60 * 60);
+
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq, Eq)]
+pub struct FlaggedCanister {
+    pub user_principal_id: Principal,
+    pub scheduled_collection_at: SystemTime,
+}
+
+#[derive(Default, Deserialize, Serialize)]
+pub struct StaleCanisterGc {
+    // * Key is the individual user canister id
+    pub flagged_canisters: BTreeMap<Principal, FlaggedCanister>,
+    // * Users who asked to keep their canister. Never flagged again
+    pub opted_out_user_principal_ids: BTreeSet<Principal>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum StaleCanisterGcStep {
+    Keep,
+    Flag { scheduled_collection_at: SystemTime },
+    Unflag,
+    Collect,
+}
+
+impl StaleCanisterGc {
+    /// What to do with a canister given the onboarding status it just reported. A flagged
+    /// canister is only collected if it is still stale once its grace period is over.
+    pub fn get_next_step(
+        &self,
+        user_canister_id: &Principal,
+        onboarding_status: &OnboardingStatus,
+        current_time: &SystemTime,
+    ) -> StaleCanisterGcStep {
+        let flagged_canister = self.flagged_canisters.get(user_canister_id);
+
+        if !is_stale(onboarding_status, current_time) {
+            return match flagged_canister {
+                Some(_) => StaleCanisterGcStep::Unflag,
+                None => StaleCanisterGcStep::Keep,
+            };
+        }
+
+        match flagged_canister {
+            None => StaleCanisterGcStep::Flag {
+                scheduled_collection_at: *current_time + STALE_CANISTER_GRACE_PERIOD,
+            },
+            Some(flagged_canister) if flagged_canister.scheduled_collection_at <= *current_time => {
+                StaleCanisterGcStep::Collect
+            }
+            Some(_) => StaleCanisterGcStep::Keep,
+        }
+    }
+}
+
+fn is_stale(onboarding_status: &OnboardingStatus, current_time: &SystemTime) -> bool {
+    onboarding_status.post_count == 0
+        && onboarding_status.bet_count == 0
+        && onboarding_status
+            .last_active_at
+            .is_none_or(|last_active_at| {
+                current_time
+                    .duration_since(last_active_at)
+                    .unwrap_or_default()
+                    >= STALE_CANISTER_INACTIVITY_PERIOD
+            })
+}
+
+#[cfg(test)]
+mod test {
+    use test_utils::setup::test_constants::{
+        get_mock_user_alice_canister_id, get_mock_user_alice_principal_id,
+    };
+
+    use crate::data_model::CanisterData;
+
+    use super::*;
+
+    #[test]
+    fn test_get_next_step() {
+        let mut stale_canister_gc = StaleCanisterGc::default();
+        let alice_canister_id = get_mock_user_alice_canister_id();
+        let last_active_at = SystemTime::UNIX_EPOCH;
+        let onboarding_status = OnboardingStatus {
+            last_active_at: Some(last_active_at),
+            ..Default::default()
+        };
+
+        let current_time =
+            last_active_at + STALE_CANISTER_INACTIVITY_PERIOD - Duration::from_secs(1);
+        assert_eq!(
+            stale_canister_gc.get_next_step(&alice_canister_id, &onboarding_status, &current_time),
+            StaleCanisterGcStep::Keep
+        );
+
+        let current_time = last_active_at + STALE_CANISTER_INACTIVITY_PERIOD;
+        let scheduled_collection_at = current_time + STALE_CANISTER_GRACE_PERIOD;
+        assert_eq!(
+            stale_canister_gc.get_next_step(&alice_canister_id, &onboarding_status, &current_time),
+            StaleCanisterGcStep::Flag {
+                scheduled_collection_at
+            }
+        );
+        stale_canister_gc.flagged_canisters.insert(
+            alice_canister_id,
+            FlaggedCanister {
+                user_principal_id: get_mock_user_alice_principal_id(),
+                scheduled_collection_at,
+            },
+        );
+
+        // * still within the grace period
+        assert_eq!(
+            stale_canister_gc.get_next_step(&alice_canister_id, &onboarding_status, &current_time),
+            StaleCanisterGcStep::Keep
+        );
+        assert_eq!(
+            stale_canister_gc.get_next_step(
+                &alice_canister_id,
+                &onboarding_status,
+                &scheduled_collection_at
+            ),
+            StaleCanisterGcStep::Collect
+        );
+
+        // * a single post during the grace period is enough to keep the canister
+        let onboarding_status = OnboardingStatus {
+            post_count: 1,
+            ..onboarding_status
+        };
+        assert_eq!(
+            stale_canister_gc.get_next_step(
+                &alice_canister_id,
+                &onboarding_status,
+                &scheduled_collection_at
+            ),
+            StaleCanisterGcStep::Unflag
+        );
+    }
+
+    #[test]
+    fn test_release_canister_to_provisioning_pool() {
+        let mut canister_data = CanisterData::default();
+        let alice_principal_id = get_mock_user_alice_principal_id();
+        let alice_canister_id = get_mock_user_alice_canister_id();
+        canister_data
+            .user_principal_id_to_canister_id_map
+            .insert(alice_principal_id, alice_canister_id);
+        canister_data
+            .unique_user_name_to_user_principal_id_map
+            .insert("alice".to_string(), alice_principal_id);
+
+        canister_data.release_canister_to_provisioning_pool(&alice_principal_id, alice_canister_id);
+
+        assert!(canister_data
+            .user_principal_id_to_canister_id_map
+            .is_empty());
+        assert!(canister_data
+            .unique_user_name_to_user_principal_id_map
+            .is_empty());
+        assert_eq!(canister_data.get_binding_epoch(&alice_canister_id), 1);
+        assert!(canister_data.provisioning_pool.contains(&alice_canister_id));
+    }
+}
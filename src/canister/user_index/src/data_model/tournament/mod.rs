@@ -0,0 +1,306 @@
+use std::{
+    collections::BTreeMap,
+    time::{Duration, SystemTime},
+};
+
+use candid::{CandidType, Deserialize, Principal};
+use serde::Serialize;
+use shared_utils::{
+    canister_specific::user_index::types::tournament::{
+        TournamentDetails, TournamentEvent, TournamentEventDetails, TournamentId,
+        TournamentRankingEntry, TournamentStatus, TOURNAMENT_DURATION,
+        TOURNAMENT_PRIZE_SHARE_PERCENTAGES,
+    },
+    common::types::app_primitive_type::PostId,
+    types::canister_specific::user_index::error_types::TournamentError,
+};
+
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug)]
+pub struct Tournament {
+    pub details: TournamentDetails,
+    // * Key is (creator canister id, post id), value is the creator principal id
+    pub enrolled_posts: BTreeMap<(Principal, PostId), Principal>,
+    pub events: Vec<TournamentEvent>,
+    // * Set while a close is collecting points. A close that traps partway through leaves
+    // * the tournament tallying, and another close can take over once this has passed.
+    #[serde(default)]
+    pub tallying_lease_expires_at: Option<SystemTime>,
+}
+
+impl Tournament {
+    pub fn new(tournament_id: TournamentId, prize_pool: u64, current_time: &SystemTime) -> Self {
+        let closes_at = *current_time + TOURNAMENT_DURATION;
+
+        let mut tournament = Self {
+            details: TournamentDetails {
+                tournament_id,
+                opened_at: *current_time,
+                closes_at,
+                prize_pool,
+                status: TournamentStatus::Open,
+                rankings: vec![],
+            },
+            enrolled_posts: BTreeMap::new(),
+            events: vec![],
+            tallying_lease_expires_at: None,
+        };
+
+        tournament.record_event(
+            current_time,
+            TournamentEventDetails::Opened {
+                prize_pool,
+                closes_at,
+            },
+        );
+
+        tournament
+    }
+
+    pub fn is_accepting_enrollments(&self, current_time: &SystemTime) -> bool {
+        self.details.status == TournamentStatus::Open && *current_time < self.details.closes_at
+    }
+
+    pub fn time_left_until_close(&self, current_time: &SystemTime) -> Duration {
+        self.details
+            .closes_at
+            .duration_since(*current_time)
+            .unwrap_or(Duration::ZERO)
+    }
+
+    /// Only posts created while the tournament is open are eligible
+    pub fn enroll_post(
+        &mut self,
+        creator_principal_id: Principal,
+        creator_canister_id: Principal,
+        post_id: PostId,
+        post_created_at: &SystemTime,
+        current_time: &SystemTime,
+    ) -> Result<(), TournamentError> {
+        if !self.is_accepting_enrollments(current_time) {
+            return Err(TournamentError::NoOpenTournament);
+        }
+
+        if *post_created_at < self.details.opened_at || *post_created_at > *current_time {
+            return Err(TournamentError::PostNotEligible);
+        }
+
+        if self
+            .enrolled_posts
+            .contains_key(&(creator_canister_id, post_id))
+        {
+            return Err(TournamentError::PostAlreadyEnrolled);
+        }
+
+        self.enrolled_posts
+            .insert((creator_canister_id, post_id), creator_principal_id);
+
+        self.record_event(
+            current_time,
+            TournamentEventDetails::PostEnrolled {
+                creator_canister_id,
+                post_id,
+            },
+        );
+
+        Ok(())
+    }
+
+    /// Key is creator canister id, value is creator principal id
+    pub fn get_enrolled_creators(&self) -> BTreeMap<Principal, Principal> {
+        self.enrolled_posts
+            .iter()
+            .map(|((creator_canister_id, _), creator_principal_id)| {
+                (*creator_canister_id, *creator_principal_id)
+            })
+            .collect()
+    }
+
+    /// Ranks creators by points, ties broken by canister id, and splits the prize pool
+    /// among the top creators according to `TOURNAMENT_PRIZE_SHARE_PERCENTAGES`.
+    /// Creators without any points do not place.
+    pub fn compute_rankings(
+        &self,
+        points_by_creator_canister_id: &BTreeMap<Principal, u64>,
+    ) -> Vec<TournamentRankingEntry> {
+        let enrolled_creators = self.get_enrolled_creators();
+
+        let mut creators_with_points: Vec<(&Principal, &u64)> = points_by_creator_canister_id
+            .iter()
+            .filter(|(creator_canister_id, points)| {
+                **points > 0 && enrolled_creators.contains_key(creator_canister_id)
+            })
+            .collect();
+
+        creators_with_points.sort_by(|(canister_id_a, points_a), (canister_id_b, points_b)| {
+            points_b
+                .cmp(points_a)
+                .then(canister_id_a.cmp(canister_id_b))
+        });
+
+        creators_with_points
+            .into_iter()
+            .zip(TOURNAMENT_PRIZE_SHARE_PERCENTAGES.iter())
+            .enumerate()
+            .map(
+                |(index, ((creator_canister_id, points), prize_share_percentage))| {
+                    TournamentRankingEntry {
+                        rank: index as u32 + 1,
+                        creator_principal_id: enrolled_creators[creator_canister_id],
+                        creator_canister_id: *creator_canister_id,
+                        points: *points,
+                        prize_amount: self.details.prize_pool * prize_share_percentage / 100,
+                    }
+                },
+            )
+            .collect()
+    }
+
+    pub fn record_event(&mut self, current_time: &SystemTime, details: TournamentEventDetails) {
+        self.events.push(TournamentEvent {
+            timestamp: *current_time,
+            details,
+        });
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use test_utils::setup::test_constants::{
+        get_mock_user_alice_canister_id, get_mock_user_alice_principal_id,
+        get_mock_user_bob_canister_id, get_mock_user_bob_principal_id,
+        get_mock_user_charlie_canister_id, get_mock_user_charlie_principal_id,
+        get_mock_user_dan_canister_id, get_mock_user_dan_principal_id,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_enroll_post() {
+        let opened_at = SystemTime::now();
+        let mut tournament = Tournament::new(1, 1000, &opened_at);
+        let current_time = opened_at + Duration::from_secs(60);
+
+        assert_eq!(
+            tournament.enroll_post(
+                get_mock_user_alice_principal_id(),
+                get_mock_user_alice_canister_id(),
+                0,
+                &(opened_at - Duration::from_secs(1)),
+                &current_time,
+            ),
+            Err(TournamentError::PostNotEligible)
+        );
+        assert_eq!(
+            tournament.enroll_post(
+                get_mock_user_alice_principal_id(),
+                get_mock_user_alice_canister_id(),
+                0,
+                &opened_at,
+                &current_time,
+            ),
+            Ok(())
+        );
+        assert_eq!(
+            tournament.enroll_post(
+                get_mock_user_alice_principal_id(),
+                get_mock_user_alice_canister_id(),
+                0,
+                &opened_at,
+                &current_time,
+            ),
+            Err(TournamentError::PostAlreadyEnrolled)
+        );
+        assert_eq!(
+            tournament.enroll_post(
+                get_mock_user_alice_principal_id(),
+                get_mock_user_alice_canister_id(),
+                1,
+                &opened_at,
+                &(opened_at + TOURNAMENT_DURATION),
+            ),
+            Err(TournamentError::NoOpenTournament)
+        );
+
+        assert_eq!(tournament.events.len(), 2);
+        assert_eq!(
+            tournament.events[1].details,
+            TournamentEventDetails::PostEnrolled {
+                creator_canister_id: get_mock_user_alice_canister_id(),
+                post_id: 0,
+            }
+        );
+    }
+
+    #[test]
+    fn test_compute_rankings() {
+        let opened_at = SystemTime::now();
+        let mut tournament = Tournament::new(1, 1000, &opened_at);
+
+        [
+            (
+                get_mock_user_alice_principal_id(),
+                get_mock_user_alice_canister_id(),
+            ),
+            (
+                get_mock_user_bob_principal_id(),
+                get_mock_user_bob_canister_id(),
+            ),
+            (
+                get_mock_user_charlie_principal_id(),
+                get_mock_user_charlie_canister_id(),
+            ),
+            (
+                get_mock_user_dan_principal_id(),
+                get_mock_user_dan_canister_id(),
+            ),
+        ]
+        .iter()
+        .for_each(|(creator_principal_id, creator_canister_id)| {
+            tournament
+                .enroll_post(
+                    *creator_principal_id,
+                    *creator_canister_id,
+                    0,
+                    &opened_at,
+                    &opened_at,
+                )
+                .unwrap();
+        });
+
+        let points_by_creator_canister_id = BTreeMap::from([
+            (get_mock_user_alice_canister_id(), 100),
+            (get_mock_user_bob_canister_id(), 300),
+            (get_mock_user_charlie_canister_id(), 0),
+            (get_mock_user_dan_canister_id(), 200),
+        ]);
+
+        let rankings = tournament.compute_rankings(&points_by_creator_canister_id);
+
+        assert_eq!(
+            rankings,
+            vec![
+                TournamentRankingEntry {
+                    rank: 1,
+                    creator_principal_id: get_mock_user_bob_principal_id(),
+                    creator_canister_id: get_mock_user_bob_canister_id(),
+                    points: 300,
+                    prize_amount: 500,
+                },
+                TournamentRankingEntry {
+                    rank: 2,
+                    creator_principal_id: get_mock_user_dan_principal_id(),
+                    creator_canister_id: get_mock_user_dan_canister_id(),
+                    points: 200,
+                    prize_amount: 300,
+                },
+                TournamentRankingEntry {
+                    rank: 3,
+                    creator_principal_id: get_mock_user_alice_principal_id(),
+                    creator_canister_id: get_mock_user_alice_canister_id(),
+                    points: 100,
+                    prize_amount: 200,
+                },
+            ]
+        );
+    }
+}
@@ -1,12 +1,53 @@
-use std::cell::RefCell;
+use std::{cell::RefCell, time::SystemTime};
 
-use candid::{export_service, Principal};
-use data_model::{canister_upgrade::UpgradeStatus, CanisterData};
+use candid::{export_service, Nat, Principal};
+use data_model::{
+    canister_creation::CanisterCreationFailureReason,
+    canister_upgrade::{
+        rolling_upgrade::{RollingUpgradeError, RollingUpgradeProgress},
+        upgrade_run::UpgradeRunReport,
+        UpgradeStatus,
+    },
+    feature_usage::{FeatureUsageFunnelForDay, GetFeatureUsageFunnelError},
+    metrics::{GetUserIndexMetricsError, MetricsDataPoint, MetricsResolution, UserIndexMetric},
+    CanisterData,
+};
 use ic_cdk::api::management_canister::main::CanisterInstallMode;
 use shared_utils::{
-    canister_specific::user_index::types::args::UserIndexInitArgs,
-    common::types::known_principal::KnownPrincipalType,
-    types::canister_specific::user_index::error_types::SetUniqueUsernameError,
+    canister_specific::{
+        individual_user_template::types::{
+            dispute::{SlotOutcomeDispute, SlotOutcomeRecalculation},
+            feature_usage::{DayNumber, FeatureUsageForDay},
+            follow::FollowListImportSummary,
+            hot_or_not::{BetCancellationConfig, RoomSizingConfig, SlotId},
+            post::{report::PostReport, PostCategoryConfig},
+            signup_bonus::SignupBonusVestingConfig,
+            treasury::PotInsuranceConfig,
+        },
+        user_index::types::{
+            args::UserIndexInitArgs,
+            bet_trace::BetTrace,
+            dispute::DisputedSlot,
+            moderation::ReportedPost,
+            stale_canister_gc::StaleCanisterGcReport,
+            token_balance_audit::TokenBalanceAuditReport,
+            tournament::{TournamentDetails, TournamentEvent, TournamentId},
+            user_index_shard::UserIndexShard,
+            username::ReservedUsernameAppeal,
+        },
+    },
+    common::{
+        types::{
+            app_primitive_type::PostId, error_code::ErrorCodeRegistryEntry,
+            event_counter::EventCounterSample, global_post_ref::GlobalPostRef,
+            known_principal::KnownPrincipalType,
+        },
+        utils::call_queue::QueuedCall,
+    },
+    pagination::Page,
+    types::canister_specific::user_index::error_types::{
+        RegisterReferralCodeError, SetUniqueUsernameError, TournamentError, UserIndexShardError,
+    },
 };
 
 mod api;
@@ -0,0 +1,120 @@
+use std::time::{Duration, SystemTime};
+
+use candid::{utils::ArgumentEncoder, Principal};
+use ic_cdk::api::call;
+use shared_utils::common::{
+    types::event_counter::EventCounterName,
+    utils::{
+        bounded_fan_out::run_with_concurrency_limit,
+        call_queue::{send_queued_call, QueuedCall},
+        system_time,
+    },
+};
+
+use crate::{data_model::CanisterData, CANISTER_DATA};
+
+const QUEUED_CALLS_RETRY_INTERVAL: Duration = Duration::from_secs(30);
+const MAX_QUEUED_CALLS_SENT_PER_DRAIN: usize = 50;
+const MAX_CONCURRENT_QUEUED_CALLS: usize = 10;
+
+/// Sends a fire and forget call to another canister. The call is kept in the call queue until
+/// the other canister answers it, and retried with backoff if it fails.
+pub fn queue_call<T: ArgumentEncoder>(canister_id: Principal, method_name: &str, args: T) {
+    CANISTER_DATA.with(|canister_data_ref_cell| {
+        queue_call_impl(
+            &mut canister_data_ref_cell.borrow_mut(),
+            canister_id,
+            method_name,
+            args,
+            &system_time::get_current_system_time_from_ic(),
+        );
+    });
+}
+
+/// For callers already holding on to the canister data
+pub fn queue_call_impl<T: ArgumentEncoder>(
+    canister_data: &mut CanisterData,
+    canister_id: Principal,
+    method_name: &str,
+    args: T,
+    current_time: &SystemTime,
+) {
+    let Ok(args) = candid::encode_args(args) else {
+        return;
+    };
+
+    match QueuedCall::new(canister_id, method_name, args.clone(), current_time) {
+        Some(queued_call) => {
+            canister_data.call_queue.enqueue(queued_call);
+            send_due_queued_calls();
+        }
+        // * Too large to keep around, so it only gets the one shot
+        None => {
+            let _ = call::notify_raw(canister_id, method_name, &args, 0);
+        }
+    }
+}
+
+/// Retries whatever in the call queue is due on an interval. Timers don't survive upgrades,
+/// so this is started again from `post_upgrade`.
+pub fn enqueue_timer_for_retrying_queued_calls() {
+    ic_cdk_timers::set_timer_interval(QUEUED_CALLS_RETRY_INTERVAL, || {
+        ic_cdk::spawn(send_due_queued_calls_async())
+    });
+}
+
+fn send_due_queued_calls() {
+    ic_cdk_timers::set_timer(Duration::ZERO, || {
+        ic_cdk::spawn(send_due_queued_calls_async())
+    });
+}
+
+async fn send_due_queued_calls_async() {
+    let leased_calls = CANISTER_DATA.with(|canister_data_ref_cell| {
+        canister_data_ref_cell
+            .borrow_mut()
+            .call_queue
+            .lease_due_calls(
+                &system_time::get_current_system_time_from_ic(),
+                MAX_QUEUED_CALLS_SENT_PER_DRAIN,
+            )
+    });
+
+    if leased_calls.is_empty() {
+        return;
+    }
+
+    let responses = run_with_concurrency_limit(
+        leased_calls
+            .iter()
+            .map(|(_, queued_call)| async move { send_queued_call(queued_call).await }),
+        MAX_CONCURRENT_QUEUED_CALLS,
+    )
+    .await;
+
+    CANISTER_DATA.with(|canister_data_ref_cell| {
+        let canister_data = &mut canister_data_ref_cell.borrow_mut();
+        let current_time = system_time::get_current_system_time_from_ic();
+
+        leased_calls
+            .iter()
+            .zip(responses)
+            .for_each(|((id, _), response)| match response {
+                Ok(()) => canister_data.call_queue.record_success(*id),
+                Err(error) => {
+                    let is_dead_lettered =
+                        canister_data
+                            .call_queue
+                            .record_failure(*id, error, &current_time);
+                    canister_data.event_counters.increment(
+                        EventCounterName::QueuedCallFailuresTotal,
+                        if is_dead_lettered {
+                            "dead_lettered"
+                        } else {
+                            "retried"
+                        },
+                    );
+                }
+            });
+    });
+}
@@ -1,24 +1,34 @@
+use std::future::Future;
+
 use candid::Principal;
 use ic_cdk::api::{
     self,
-    call::RejectionCode,
+    call::{CallResult, RejectionCode},
     management_canister::{
         main::{self, CanisterInstallMode, CreateCanisterArgument, InstallCodeArgument},
         provisional::CanisterSettings,
     },
 };
+use sha2::{Digest, Sha256};
 use shared_utils::{
     canister_specific::individual_user_template::types::arg::IndividualUserTemplateInitArgs,
     constant::INDIVIDUAL_USER_CANISTER_RECHARGE_AMOUNT,
 };
 
-use crate::CANISTER_DATA;
+use crate::{data_model::canister_creation::CanisterCreationFailureReason, CANISTER_DATA};
 
 const INDIVIDUAL_USER_TEMPLATE_CANISTER_WASM: &[u8] = include_bytes!(
     "../../../../../target/wasm32-unknown-unknown/release/individual_user_template.wasm.gz"
 );
 
-pub async fn create_users_canister(profile_owner: Principal) -> Principal {
+/// Sha256 of the gzipped individual user template wasm this index installs and upgrades to
+pub fn get_individual_user_template_wasm_hash() -> Vec<u8> {
+    Sha256::digest(INDIVIDUAL_USER_TEMPLATE_CANISTER_WASM).to_vec()
+}
+
+pub async fn create_users_canister(
+    profile_owner: Principal,
+) -> Result<Principal, CanisterCreationFailureReason> {
     // * config for provisioning canister
     let arg = CreateCanisterArgument {
         settings: Some(CanisterSettings {
@@ -32,13 +42,26 @@ pub async fn create_users_canister(profile_owner: Principal) -> Principal {
         }),
     };
 
+    // * emptied canisters of collected users are handed out before creating any more
+    let pooled_canister_id = CANISTER_DATA.with(|canister_data_ref_cell| {
+        canister_data_ref_cell
+            .borrow_mut()
+            .provisioning_pool
+            .pop_first()
+    });
+
     // * provisioned canister
-    let canister_id: Principal =
-        main::create_canister(arg, INDIVIDUAL_USER_CANISTER_RECHARGE_AMOUNT)
-            .await
-            .unwrap()
+    let canister_id: Principal = match pooled_canister_id {
+        Some(pooled_canister_id) => pooled_canister_id,
+        None => {
+            call_with_retries(|| {
+                main::create_canister(arg.clone(), INDIVIDUAL_USER_CANISTER_RECHARGE_AMOUNT)
+            })
+            .await?
             .0
-            .canister_id;
+            .canister_id
+        }
+    };
 
     let configuration = CANISTER_DATA
         .with(|canister_data_ref_cell| canister_data_ref_cell.borrow().configuration.clone());
@@ -50,6 +73,23 @@ pub async fn create_users_canister(profile_owner: Principal) -> Principal {
         })),
         upgrade_version_number: Some(0),
         url_to_send_canister_metrics_to: Some(configuration.url_to_send_canister_metrics_to),
+        referrer_share_of_bet_winnings_percentage: Some(
+            configuration.referrer_share_of_bet_winnings_percentage,
+        ),
+        house_rake_percentage: Some(configuration.house_rake_percentage),
+        room_sizing_config: Some(configuration.room_sizing_config),
+        post_category_config: Some(configuration.post_category_config),
+        binding_epoch: Some(CANISTER_DATA.with(|canister_data_ref_cell| {
+            canister_data_ref_cell
+                .borrow()
+                .get_binding_epoch(&canister_id)
+        })),
+        is_call_journal_enabled: None,
+        signup_bonus_vesting_config: Some(configuration.signup_bonus_vesting_config),
+        pot_insurance_config: Some(configuration.pot_insurance_config),
+        bet_cancellation_config: Some(configuration.bet_cancellation_config),
+        access_statistics_config: None,
+        hot_or_not_payout_mode: Some(configuration.hot_or_not_payout_mode),
     };
 
     // * encode argument for user canister init lifecycle method
@@ -57,16 +97,64 @@ pub async fn create_users_canister(profile_owner: Principal) -> Principal {
         .expect("Failed to serialize the install argument.");
 
     // * install wasm to provisioned canister
-    main::install_code(InstallCodeArgument {
-        mode: CanisterInstallMode::Install,
-        canister_id,
-        wasm_module: INDIVIDUAL_USER_TEMPLATE_CANISTER_WASM.into(),
-        arg,
+    let install_response = call_with_retries(|| {
+        main::install_code(InstallCodeArgument {
+            mode: CanisterInstallMode::Install,
+            canister_id,
+            wasm_module: INDIVIDUAL_USER_TEMPLATE_CANISTER_WASM.into(),
+            arg: arg.clone(),
+        })
     })
-    .await
-    .unwrap();
+    .await;
+
+    if let Err(failure_reason) = install_response {
+        // * the canister is still empty, so it can go to the next user instead of leaking
+        CANISTER_DATA.with(|canister_data_ref_cell| {
+            canister_data_ref_cell
+                .borrow_mut()
+                .provisioning_pool
+                .insert(canister_id);
+        });
+        return Err(failure_reason);
+    }
+
+    Ok(canister_id)
+}
+
+/// Retries a management canister call as many times as the class of its failure allows,
+/// recording every failed attempt in the index's failure breakdown
+async fn call_with_retries<T, F, Fut>(mut call: F) -> Result<T, CanisterCreationFailureReason>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = CallResult<T>>,
+{
+    let mut number_of_retries = 0;
+
+    loop {
+        let (rejection_code, message) = match call().await {
+            Ok(response) => return Ok(response),
+            Err(error) => error,
+        };
+
+        let failure_reason = CanisterCreationFailureReason::classify(rejection_code, &message);
+        ic_cdk::print(format!(
+            "Canister creation call failed with {:?}: {}",
+            failure_reason, message
+        ));
+
+        CANISTER_DATA.with(|canister_data_ref_cell| {
+            *canister_data_ref_cell
+                .borrow_mut()
+                .canister_creation_failures
+                .entry(failure_reason)
+                .or_default() += 1;
+        });
 
-    canister_id
+        if number_of_retries >= failure_reason.get_max_retries() {
+            return Err(failure_reason);
+        }
+        number_of_retries += 1;
+    }
 }
 
 pub async fn upgrade_individual_user_canister(
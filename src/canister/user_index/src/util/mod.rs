@@ -1 +1,2 @@
+pub mod call_queue;
 pub mod canister_management;
@@ -139,8 +139,9 @@ fn when_a_new_user_signs_up_from_a_referral_then_the_new_user_is_given_a_thousan
                 }
             }
         },
-        MintEvent::NewUserSignup {
-            new_user_principal_id: alice_principal_id
+        MintEvent::SignupBonusUnlockedAtSignup {
+            new_user_principal_id: alice_principal_id,
+            locked_amount: 0,
         },
     );
 
@@ -257,8 +258,9 @@ fn when_a_new_user_signs_up_from_a_referral_then_the_new_user_is_given_a_thousan
                 }
             }
         },
-        MintEvent::NewUserSignup {
-            new_user_principal_id: alice_principal_id
+        MintEvent::SignupBonusUnlockedAtSignup {
+            new_user_principal_id: alice_principal_id,
+            locked_amount: 0,
         },
     );
 
@@ -326,8 +328,9 @@ fn when_a_new_user_signs_up_from_a_referral_then_the_new_user_is_given_a_thousan
                 }
             }
         },
-        MintEvent::NewUserSignup {
-            new_user_principal_id: bob_principal_id
+        MintEvent::SignupBonusUnlockedAtSignup {
+            new_user_principal_id: bob_principal_id,
+            locked_amount: 0,
         },
     );
 
@@ -7,7 +7,10 @@ use shared_utils::{
         arg::PlaceBetArg,
         error::BetOnCurrentlyViewingPostError,
         hot_or_not::{BetDirection, BettingStatus},
-        post::{PostDetailsFromFrontend, PostViewDetailsFromFrontend},
+        post::{
+            view_buffer::POST_VIEW_BUFFER_FLUSH_INTERVAL, PostDetailsFromFrontend,
+            PostViewDetailsFromFrontend,
+        },
     },
     common::types::{
         known_principal::KnownPrincipalType, top_posts::post_score_index_item::PostScoreIndexItem,
@@ -113,6 +116,9 @@ fn when_bob_charlie_dan_interact_with_alice_created_post_then_calculated_home_fe
                 hashtags: vec!["fun".to_string(), "video".to_string()],
                 video_uid: "abcd#1234".to_string(),
                 creator_consent_for_inclusion_in_hot_or_not: true,
+                collaborator_shares: None,
+                title: None,
+                category: None,
             })
             .unwrap(),
         )
@@ -146,7 +152,7 @@ fn when_bob_charlie_dan_interact_with_alice_created_post_then_calculated_home_fe
 
     assert_eq!(returned_posts.len(), 1);
 
-    let returned_post = returned_posts.get(0).unwrap();
+    let returned_post = returned_posts.first().unwrap();
     assert_eq!(returned_post.post_id, newly_created_post_id);
     assert_eq!(returned_post.score, 3000);
     assert_eq!(returned_post.publisher_canister_id, alice_canister_id);
@@ -169,7 +175,7 @@ fn when_bob_charlie_dan_interact_with_alice_created_post_then_calculated_home_fe
 
     assert_eq!(returned_posts.len(), 1);
 
-    let returned_post = returned_posts.get(0).unwrap();
+    let returned_post = returned_posts.first().unwrap();
     assert_eq!(returned_post.post_id, newly_created_post_id);
     assert_eq!(returned_post.score, 3000);
     assert_eq!(returned_post.publisher_canister_id, alice_canister_id);
@@ -193,6 +199,10 @@ fn when_bob_charlie_dan_interact_with_alice_created_post_then_calculated_home_fe
 
     assert!(result.is_ok());
 
+    // * views are applied to the post when the view buffer is next flushed
+    state_machine.advance_time(POST_VIEW_BUFFER_FLUSH_INTERVAL);
+    state_machine.tick();
+
     let returned_posts: Vec<PostScoreIndexItem> = state_machine
         .query_call(
             *post_cache_canister_id,
@@ -211,7 +221,7 @@ fn when_bob_charlie_dan_interact_with_alice_created_post_then_calculated_home_fe
 
     assert_eq!(returned_posts.len(), 1);
 
-    let returned_post = returned_posts.get(0).unwrap();
+    let returned_post = returned_posts.first().unwrap();
     assert_eq!(returned_post.post_id, newly_created_post_id);
     assert_eq!(returned_post.score, 4_840);
     assert_eq!(returned_post.publisher_canister_id, alice_canister_id);
@@ -234,7 +244,7 @@ fn when_bob_charlie_dan_interact_with_alice_created_post_then_calculated_home_fe
 
     assert_eq!(returned_posts.len(), 1);
 
-    let returned_post = returned_posts.get(0).unwrap();
+    let returned_post = returned_posts.first().unwrap();
     assert_eq!(returned_post.post_id, newly_created_post_id);
     assert_eq!(returned_post.score, 4_840);
     assert_eq!(returned_post.publisher_canister_id, alice_canister_id);
@@ -276,7 +286,7 @@ fn when_bob_charlie_dan_interact_with_alice_created_post_then_calculated_home_fe
 
     assert_eq!(returned_posts.len(), 1);
 
-    let returned_post = returned_posts.get(0).unwrap();
+    let returned_post = returned_posts.first().unwrap();
     assert_eq!(returned_post.post_id, newly_created_post_id);
     assert_eq!(returned_post.score, 6_840);
     assert_eq!(returned_post.publisher_canister_id, alice_canister_id);
@@ -299,7 +309,7 @@ fn when_bob_charlie_dan_interact_with_alice_created_post_then_calculated_home_fe
 
     assert_eq!(returned_posts.len(), 1);
 
-    let returned_post = returned_posts.get(0).unwrap();
+    let returned_post = returned_posts.first().unwrap();
     assert_eq!(returned_post.post_id, newly_created_post_id);
     assert_eq!(returned_post.score, 6_840);
     assert_eq!(returned_post.publisher_canister_id, alice_canister_id);
@@ -338,6 +348,7 @@ fn when_bob_charlie_dan_interact_with_alice_created_post_then_calculated_home_fe
             ongoing_slot: 3,
             ongoing_room: 1,
             has_this_user_participated_in_this_post: Some(true),
+            minimum_bet_amount: 0,
         }
     );
 
@@ -359,7 +370,7 @@ fn when_bob_charlie_dan_interact_with_alice_created_post_then_calculated_home_fe
 
     assert_eq!(returned_posts.len(), 1);
 
-    let returned_post = returned_posts.get(0).unwrap();
+    let returned_post = returned_posts.first().unwrap();
     assert_eq!(returned_post.post_id, newly_created_post_id);
     assert_eq!(returned_post.score, 7_840);
     assert_eq!(returned_post.publisher_canister_id, alice_canister_id);
@@ -382,7 +393,7 @@ fn when_bob_charlie_dan_interact_with_alice_created_post_then_calculated_home_fe
 
     assert_eq!(returned_posts.len(), 1);
 
-    let returned_post = returned_posts.get(0).unwrap();
+    let returned_post = returned_posts.first().unwrap();
     assert_eq!(returned_post.post_id, newly_created_post_id);
     assert_eq!(returned_post.score, 6_840);
     assert_eq!(returned_post.publisher_canister_id, alice_canister_id);
@@ -406,6 +417,10 @@ fn when_bob_charlie_dan_interact_with_alice_created_post_then_calculated_home_fe
 
     assert!(result.is_ok());
 
+    // * views are applied to the post when the view buffer is next flushed
+    state_machine.advance_time(POST_VIEW_BUFFER_FLUSH_INTERVAL);
+    state_machine.tick();
+
     let returned_posts: Vec<PostScoreIndexItem> = state_machine
         .query_call(
             *post_cache_canister_id,
@@ -424,7 +439,7 @@ fn when_bob_charlie_dan_interact_with_alice_created_post_then_calculated_home_fe
 
     assert_eq!(returned_posts.len(), 1);
 
-    let returned_post = returned_posts.get(0).unwrap();
+    let returned_post = returned_posts.first().unwrap();
     assert_eq!(returned_post.post_id, newly_created_post_id);
     assert_eq!(returned_post.score, 6_549);
     assert_eq!(returned_post.publisher_canister_id, alice_canister_id);
@@ -447,7 +462,7 @@ fn when_bob_charlie_dan_interact_with_alice_created_post_then_calculated_home_fe
 
     assert_eq!(returned_posts.len(), 1);
 
-    let returned_post = returned_posts.get(0).unwrap();
+    let returned_post = returned_posts.first().unwrap();
     assert_eq!(returned_post.post_id, newly_created_post_id);
     assert_eq!(returned_post.score, 5_549);
     assert_eq!(returned_post.publisher_canister_id, alice_canister_id);
@@ -485,6 +500,7 @@ fn when_bob_charlie_dan_interact_with_alice_created_post_then_calculated_home_fe
             ongoing_slot: 6,
             ongoing_room: 1,
             has_this_user_participated_in_this_post: Some(true),
+            minimum_bet_amount: 0,
         }
     );
 
@@ -506,7 +522,7 @@ fn when_bob_charlie_dan_interact_with_alice_created_post_then_calculated_home_fe
 
     assert_eq!(returned_posts.len(), 1);
 
-    let returned_post = returned_posts.get(0).unwrap();
+    let returned_post = returned_posts.first().unwrap();
     assert_eq!(returned_post.post_id, newly_created_post_id);
     assert_eq!(returned_post.score, 6_049);
     assert_eq!(returned_post.publisher_canister_id, alice_canister_id);
@@ -529,7 +545,7 @@ fn when_bob_charlie_dan_interact_with_alice_created_post_then_calculated_home_fe
 
     assert_eq!(returned_posts.len(), 1);
 
-    let returned_post = returned_posts.get(0).unwrap();
+    let returned_post = returned_posts.first().unwrap();
     assert_eq!(returned_post.post_id, newly_created_post_id);
     assert_eq!(returned_post.score, 7_549);
     assert_eq!(returned_post.publisher_canister_id, alice_canister_id);
@@ -553,6 +569,10 @@ fn when_bob_charlie_dan_interact_with_alice_created_post_then_calculated_home_fe
 
     assert!(result.is_ok());
 
+    // * views are applied to the post when the view buffer is next flushed
+    state_machine.advance_time(POST_VIEW_BUFFER_FLUSH_INTERVAL);
+    state_machine.tick();
+
     let returned_posts: Vec<PostScoreIndexItem> = state_machine
         .query_call(
             *post_cache_canister_id,
@@ -571,7 +591,7 @@ fn when_bob_charlie_dan_interact_with_alice_created_post_then_calculated_home_fe
 
     assert_eq!(returned_posts.len(), 1);
 
-    let returned_post = returned_posts.get(0).unwrap();
+    let returned_post = returned_posts.first().unwrap();
     assert_eq!(returned_post.post_id, newly_created_post_id);
     assert_eq!(returned_post.score, 5_642);
     assert_eq!(returned_post.publisher_canister_id, alice_canister_id);
@@ -594,7 +614,7 @@ fn when_bob_charlie_dan_interact_with_alice_created_post_then_calculated_home_fe
 
     assert_eq!(returned_posts.len(), 1);
 
-    let returned_post = returned_posts.get(0).unwrap();
+    let returned_post = returned_posts.first().unwrap();
     assert_eq!(returned_post.post_id, newly_created_post_id);
     assert_eq!(returned_post.score, 7_142);
     assert_eq!(returned_post.publisher_canister_id, alice_canister_id);
@@ -636,7 +656,7 @@ fn when_bob_charlie_dan_interact_with_alice_created_post_then_calculated_home_fe
 
     assert_eq!(returned_posts.len(), 1);
 
-    let returned_post = returned_posts.get(0).unwrap();
+    let returned_post = returned_posts.first().unwrap();
     assert_eq!(returned_post.post_id, newly_created_post_id);
     assert_eq!(returned_post.score, 6_267);
     assert_eq!(returned_post.publisher_canister_id, alice_canister_id);
@@ -659,7 +679,7 @@ fn when_bob_charlie_dan_interact_with_alice_created_post_then_calculated_home_fe
 
     assert_eq!(returned_posts.len(), 1);
 
-    let returned_post = returned_posts.get(0).unwrap();
+    let returned_post = returned_posts.first().unwrap();
     assert_eq!(returned_post.post_id, newly_created_post_id);
     assert_eq!(returned_post.score, 7_767);
     assert_eq!(returned_post.publisher_canister_id, alice_canister_id);
@@ -701,7 +721,7 @@ fn when_bob_charlie_dan_interact_with_alice_created_post_then_calculated_home_fe
 
     assert_eq!(returned_posts.len(), 1);
 
-    let returned_post = returned_posts.get(0).unwrap();
+    let returned_post = returned_posts.first().unwrap();
     assert_eq!(returned_post.post_id, newly_created_post_id);
     assert_eq!(returned_post.score, 12_517);
     assert_eq!(returned_post.publisher_canister_id, alice_canister_id);
@@ -724,7 +744,7 @@ fn when_bob_charlie_dan_interact_with_alice_created_post_then_calculated_home_fe
 
     assert_eq!(returned_posts.len(), 1);
 
-    let returned_post = returned_posts.get(0).unwrap();
+    let returned_post = returned_posts.first().unwrap();
     assert_eq!(returned_post.post_id, newly_created_post_id);
     assert_eq!(returned_post.score, 14_017);
     assert_eq!(returned_post.publisher_canister_id, alice_canister_id);
@@ -762,6 +782,7 @@ fn when_bob_charlie_dan_interact_with_alice_created_post_then_calculated_home_fe
             ongoing_slot: 9,
             ongoing_room: 1,
             has_this_user_participated_in_this_post: Some(true),
+            minimum_bet_amount: 0,
         }
     );
 
@@ -783,7 +804,7 @@ fn when_bob_charlie_dan_interact_with_alice_created_post_then_calculated_home_fe
 
     assert_eq!(returned_posts.len(), 1);
 
-    let returned_post = returned_posts.get(0).unwrap();
+    let returned_post = returned_posts.first().unwrap();
     assert_eq!(returned_post.post_id, newly_created_post_id);
     assert_eq!(returned_post.score, 12_683);
     assert_eq!(returned_post.publisher_canister_id, alice_canister_id);
@@ -806,7 +827,7 @@ fn when_bob_charlie_dan_interact_with_alice_created_post_then_calculated_home_fe
 
     assert_eq!(returned_posts.len(), 1);
 
-    let returned_post = returned_posts.get(0).unwrap();
+    let returned_post = returned_posts.first().unwrap();
     assert_eq!(returned_post.post_id, newly_created_post_id);
     assert_eq!(returned_post.score, 13_353);
     assert_eq!(returned_post.publisher_canister_id, alice_canister_id);
@@ -830,6 +851,10 @@ fn when_bob_charlie_dan_interact_with_alice_created_post_then_calculated_home_fe
 
     assert!(result.is_ok());
 
+    // * views are applied to the post when the view buffer is next flushed
+    state_machine.advance_time(POST_VIEW_BUFFER_FLUSH_INTERVAL);
+    state_machine.tick();
+
     let returned_posts: Vec<PostScoreIndexItem> = state_machine
         .query_call(
             *post_cache_canister_id,
@@ -848,7 +873,7 @@ fn when_bob_charlie_dan_interact_with_alice_created_post_then_calculated_home_fe
 
     assert_eq!(returned_posts.len(), 1);
 
-    let returned_post = returned_posts.get(0).unwrap();
+    let returned_post = returned_posts.first().unwrap();
     assert_eq!(returned_post.post_id, newly_created_post_id);
     assert_eq!(returned_post.score, 9_810);
     assert_eq!(returned_post.publisher_canister_id, alice_canister_id);
@@ -871,7 +896,7 @@ fn when_bob_charlie_dan_interact_with_alice_created_post_then_calculated_home_fe
 
     assert_eq!(returned_posts.len(), 1);
 
-    let returned_post = returned_posts.get(0).unwrap();
+    let returned_post = returned_posts.first().unwrap();
     assert_eq!(returned_post.post_id, newly_created_post_id);
     assert_eq!(returned_post.score, 10_480);
     assert_eq!(returned_post.publisher_canister_id, alice_canister_id);
@@ -913,7 +938,7 @@ fn when_bob_charlie_dan_interact_with_alice_created_post_then_calculated_home_fe
 
     assert_eq!(returned_posts.len(), 1);
 
-    let returned_post = returned_posts.get(0).unwrap();
+    let returned_post = returned_posts.first().unwrap();
     assert_eq!(returned_post.post_id, newly_created_post_id);
     assert_eq!(returned_post.score, 15_366);
     assert_eq!(returned_post.publisher_canister_id, alice_canister_id);
@@ -936,7 +961,7 @@ fn when_bob_charlie_dan_interact_with_alice_created_post_then_calculated_home_fe
 
     assert_eq!(returned_posts.len(), 1);
 
-    let returned_post = returned_posts.get(0).unwrap();
+    let returned_post = returned_posts.first().unwrap();
     assert_eq!(returned_post.post_id, newly_created_post_id);
     assert_eq!(returned_post.score, 16_036);
     assert_eq!(returned_post.publisher_canister_id, alice_canister_id);
@@ -121,6 +121,9 @@ fn when_bob_charlie_dan_place_bet_on_alice_created_post_then_expected_outcomes_o
                 hashtags: vec!["fun".to_string(), "video".to_string()],
                 video_uid: "abcd#1234".to_string(),
                 creator_consent_for_inclusion_in_hot_or_not: true,
+                collaborator_shares: None,
+                title: None,
+                category: None,
             },))
             .unwrap(),
         )
@@ -154,7 +157,7 @@ fn when_bob_charlie_dan_place_bet_on_alice_created_post_then_expected_outcomes_o
 
     assert_eq!(returned_posts.len(), 1);
 
-    let returned_post = returned_posts.get(0).unwrap();
+    let returned_post = returned_posts.first().unwrap();
     assert_eq!(returned_post.post_id, newly_created_post_id);
     assert_eq!(returned_post.publisher_canister_id, alice_canister_id);
 
@@ -192,6 +195,7 @@ fn when_bob_charlie_dan_place_bet_on_alice_created_post_then_expected_outcomes_o
             ongoing_slot: 1,
             ongoing_room: 1,
             has_this_user_participated_in_this_post: Some(true),
+            minimum_bet_amount: 0,
         }
     );
 
@@ -228,6 +232,7 @@ fn when_bob_charlie_dan_place_bet_on_alice_created_post_then_expected_outcomes_o
             ongoing_slot: 1,
             ongoing_room: 1,
             has_this_user_participated_in_this_post: Some(true),
+            minimum_bet_amount: 0,
         }
     );
 
@@ -264,6 +269,7 @@ fn when_bob_charlie_dan_place_bet_on_alice_created_post_then_expected_outcomes_o
             ongoing_slot: 1,
             ongoing_room: 1,
             has_this_user_participated_in_this_post: Some(true),
+            minimum_bet_amount: 0,
         }
     );
 
@@ -330,7 +336,7 @@ fn when_bob_charlie_dan_place_bet_on_alice_created_post_then_expected_outcomes_o
 
     assert_eq!(alice_token_transaction_history.len(), 2);
     assert_eq!(
-        alice_token_transaction_history.get(0).unwrap().1,
+        alice_token_transaction_history.first().unwrap().1,
         TokenEvent::HotOrNotOutcomePayout {
             amount: 16,
             details: HotOrNotOutcomePayoutEvent::CommissionFromHotOrNotBet {
@@ -341,7 +347,7 @@ fn when_bob_charlie_dan_place_bet_on_alice_created_post_then_expected_outcomes_o
                 room_pot_total_amount: 160
             },
             timestamp: if let TokenEvent::HotOrNotOutcomePayout { timestamp, .. } =
-                alice_token_transaction_history.get(0).unwrap().1.clone()
+                alice_token_transaction_history.first().unwrap().1.clone()
             {
                 timestamp
             } else {
@@ -399,7 +405,7 @@ fn when_bob_charlie_dan_place_bet_on_alice_created_post_then_expected_outcomes_o
 
     assert_eq!(bob_token_transaction_history.len(), 3);
     assert_eq!(
-        bob_token_transaction_history.get(0).unwrap().1,
+        bob_token_transaction_history.first().unwrap().1,
         TokenEvent::HotOrNotOutcomePayout {
             amount: 90,
             details: HotOrNotOutcomePayoutEvent::WinningsEarnedFromBet {
@@ -411,7 +417,7 @@ fn when_bob_charlie_dan_place_bet_on_alice_created_post_then_expected_outcomes_o
                 winnings_amount: 90
             },
             timestamp: if let TokenEvent::HotOrNotOutcomePayout { timestamp, .. } =
-                bob_token_transaction_history.get(0).unwrap().1.clone()
+                bob_token_transaction_history.first().unwrap().1.clone()
             {
                 timestamp
             } else {
@@ -488,7 +494,7 @@ fn when_bob_charlie_dan_place_bet_on_alice_created_post_then_expected_outcomes_o
 
     assert_eq!(charlie_token_transaction_history.len(), 3);
     assert_eq!(
-        charlie_token_transaction_history.get(0).unwrap().1,
+        charlie_token_transaction_history.first().unwrap().1,
         TokenEvent::HotOrNotOutcomePayout {
             amount: 0,
             details: HotOrNotOutcomePayoutEvent::WinningsEarnedFromBet {
@@ -500,7 +506,7 @@ fn when_bob_charlie_dan_place_bet_on_alice_created_post_then_expected_outcomes_o
                 winnings_amount: 0
             },
             timestamp: if let TokenEvent::HotOrNotOutcomePayout { timestamp, .. } =
-                charlie_token_transaction_history.get(0).unwrap().1.clone()
+                charlie_token_transaction_history.first().unwrap().1.clone()
             {
                 timestamp
             } else {
@@ -577,7 +583,7 @@ fn when_bob_charlie_dan_place_bet_on_alice_created_post_then_expected_outcomes_o
 
     assert_eq!(dan_token_transaction_history.len(), 3);
     assert_eq!(
-        dan_token_transaction_history.get(0).unwrap().1,
+        dan_token_transaction_history.first().unwrap().1,
         TokenEvent::HotOrNotOutcomePayout {
             amount: 18,
             details: HotOrNotOutcomePayoutEvent::WinningsEarnedFromBet {
@@ -589,7 +595,7 @@ fn when_bob_charlie_dan_place_bet_on_alice_created_post_then_expected_outcomes_o
                 winnings_amount: 18
             },
             timestamp: if let TokenEvent::HotOrNotOutcomePayout { timestamp, .. } =
-                dan_token_transaction_history.get(0).unwrap().1.clone()
+                dan_token_transaction_history.first().unwrap().1.clone()
             {
                 timestamp
             } else {
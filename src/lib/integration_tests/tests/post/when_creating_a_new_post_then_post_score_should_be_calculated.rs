@@ -43,6 +43,9 @@ fn when_creating_a_new_post_then_post_score_should_be_calculated() {
                 hashtags: vec!["fun".to_string(), "video".to_string()],
                 video_uid: "abcd#1234".to_string(),
                 creator_consent_for_inclusion_in_hot_or_not: true,
+                collaborator_shares: None,
+                title: None,
+                category: None,
             },))
             .unwrap(),
         )
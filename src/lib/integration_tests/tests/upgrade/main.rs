@@ -1 +1,2 @@
 pub mod on_every_upgrade_fetch_the_latest_list_of_well_known_principals_and_update_canisters;
+pub mod when_canisters_are_upgraded_to_a_newer_wasm_then_stable_state_and_query_replies_are_preserved;
@@ -0,0 +1,197 @@
+use candid::Principal;
+use ic_test_state_machine_client::{CanisterSettings, WasmResult};
+use shared_utils::{
+    canister_specific::individual_user_template::types::{
+        arg::IndividualUserTemplateInitArgs,
+        post::{PostDetailsForFrontend, PostDetailsFromFrontend},
+        profile::{UserProfileDetailsForFrontend, UserProfileUpdateDetailsFromFrontend},
+    },
+    common::types::known_principal::KnownPrincipalType,
+};
+use test_utils::setup::{
+    env::v1::{get_initialized_env_with_provisioned_known_canisters, get_new_state_machine},
+    snapshot::{
+        capture_canister_snapshot, upgrade_and_assert_snapshot_is_preserved, QueryToCompare,
+    },
+    test_constants::{
+        get_current_individual_user_template_wasm, get_global_super_admin_principal_id,
+        get_mock_user_alice_principal_id, get_previous_release_individual_user_template_wasm,
+        v1::CANISTER_INITIAL_CYCLES_FOR_NON_SPAWNING_CANISTERS,
+    },
+};
+
+#[test]
+fn when_canisters_are_upgraded_to_a_newer_wasm_then_stable_state_and_query_replies_are_preserved() {
+    let Some(previous_release_wasm) = get_previous_release_individual_user_template_wasm() else {
+        eprintln!(
+            "Skipping the upgrade test, the previous release's individual user template wasm \
+             isn't built. Build it with \
+             scripts/canisters/local_deploy/build_previous_release_individual_user_template.sh"
+        );
+        return;
+    };
+
+    let state_machine = get_new_state_machine();
+    let known_principal_map = get_initialized_env_with_provisioned_known_canisters(&state_machine);
+    let alice_principal_id = get_mock_user_alice_principal_id();
+
+    let init_args = IndividualUserTemplateInitArgs {
+        known_principal_ids: Some(known_principal_map.clone()),
+        profile_owner: Some(alice_principal_id),
+        upgrade_version_number: Some(0),
+        url_to_send_canister_metrics_to: None,
+        referrer_share_of_bet_winnings_percentage: None,
+        house_rake_percentage: None,
+        room_sizing_config: None,
+        post_category_config: None,
+        binding_epoch: None,
+        is_call_journal_enabled: None,
+        signup_bonus_vesting_config: None,
+        pot_insurance_config: None,
+        bet_cancellation_config: None,
+        access_statistics_config: None,
+        hot_or_not_payout_mode: None,
+    };
+
+    // * Install the release currently deployed, and have it write state of its own
+    let alice_canister_id = state_machine.create_canister_with_settings(
+        Some(CanisterSettings {
+            controllers: Some(vec![get_global_super_admin_principal_id()]),
+            ..Default::default()
+        }),
+        Some(get_global_super_admin_principal_id()),
+    );
+    state_machine.add_cycles(
+        alice_canister_id,
+        CANISTER_INITIAL_CYCLES_FOR_NON_SPAWNING_CANISTERS,
+    );
+    state_machine.install_canister(
+        alice_canister_id,
+        previous_release_wasm.clone(),
+        candid::encode_one(&init_args).unwrap(),
+        Some(get_global_super_admin_principal_id()),
+    );
+
+    let post_details = PostDetailsFromFrontend {
+        description: "This is a fun video to watch".to_string(),
+        hashtags: vec!["fun".to_string(), "video".to_string()],
+        video_uid: "abcd#1234".to_string(),
+        creator_consent_for_inclusion_in_hot_or_not: true,
+        collaborator_shares: None,
+        title: None,
+        category: None,
+    };
+    let post_id = state_machine
+        .update_call(
+            alice_canister_id,
+            alice_principal_id,
+            "add_post_v2",
+            candid::encode_one(&post_details).unwrap(),
+        )
+        .map(|reply_payload| {
+            let post_id_result: Result<u64, String> = match reply_payload {
+                WasmResult::Reply(payload) => candid::decode_one(&payload).unwrap(),
+                _ => panic!("\n🛑 add_post_v2 failed\n"),
+            };
+            post_id_result.unwrap()
+        })
+        .unwrap();
+
+    // * Only checked to have replied, the previous release's profile details lack fields the
+    // * current ones have
+    let update_profile_reply = state_machine
+        .update_call(
+            alice_canister_id,
+            alice_principal_id,
+            "update_profile_display_details",
+            candid::encode_one(UserProfileUpdateDetailsFromFrontend {
+                display_name: Some("Alice".to_string()),
+                profile_picture_url: None,
+            })
+            .unwrap(),
+        )
+        .unwrap();
+    assert!(matches!(update_profile_reply, WasmResult::Reply(_)));
+
+    let snapshot = capture_canister_snapshot(
+        &state_machine,
+        alice_canister_id,
+        previous_release_wasm,
+        candid::encode_one(&init_args).unwrap(),
+        &[
+            QueryToCompare::new("get_utility_token_balance", candid::encode_one(()).unwrap())
+                .with_sender(alice_principal_id),
+            QueryToCompare::new(
+                "get_well_known_principal_value",
+                candid::encode_one(KnownPrincipalType::CanisterIdUserIndex).unwrap(),
+            ),
+        ],
+    );
+
+    assert!(!snapshot.stable_memory.is_empty());
+
+    upgrade_and_assert_snapshot_is_preserved(
+        &state_machine,
+        &snapshot,
+        get_current_individual_user_template_wasm(),
+        candid::encode_one(IndividualUserTemplateInitArgs {
+            upgrade_version_number: Some(1),
+            ..init_args
+        })
+        .unwrap(),
+    );
+
+    // * Replies whose types changed since the previous release are checked field by field
+    let post_details_after_upgrade = state_machine
+        .query_call(
+            alice_canister_id,
+            Principal::anonymous(),
+            "get_individual_post_details_by_id",
+            candid::encode_one(post_id).unwrap(),
+        )
+        .map(|reply_payload| {
+            let post_details_after_upgrade: PostDetailsForFrontend = match reply_payload {
+                WasmResult::Reply(payload) => candid::decode_one(&payload).unwrap(),
+                _ => panic!("\n🛑 get_individual_post_details_by_id failed\n"),
+            };
+            post_details_after_upgrade
+        })
+        .unwrap();
+
+    assert_eq!(post_details_after_upgrade.id, post_id);
+    assert_eq!(
+        post_details_after_upgrade.created_by_user_principal_id,
+        alice_principal_id
+    );
+    assert_eq!(
+        post_details_after_upgrade.description,
+        post_details.description
+    );
+    assert_eq!(post_details_after_upgrade.hashtags, post_details.hashtags);
+    assert_eq!(post_details_after_upgrade.video_uid, post_details.video_uid);
+
+    let profile_details_after_upgrade = state_machine
+        .query_call(
+            alice_canister_id,
+            Principal::anonymous(),
+            "get_profile_details",
+            candid::encode_one(()).unwrap(),
+        )
+        .map(|reply_payload| {
+            let profile_details_after_upgrade: UserProfileDetailsForFrontend = match reply_payload {
+                WasmResult::Reply(payload) => candid::decode_one(&payload).unwrap(),
+                _ => panic!("\n🛑 get_profile_details failed\n"),
+            };
+            profile_details_after_upgrade
+        })
+        .unwrap();
+
+    assert_eq!(
+        profile_details_after_upgrade.principal_id,
+        alice_principal_id
+    );
+    assert_eq!(
+        profile_details_after_upgrade.display_name,
+        Some("Alice".to_string())
+    );
+}
@@ -0,0 +1,42 @@
+use candid::{CandidType, Deserialize};
+use serde::Serialize;
+
+// * Enough for a companion app that reacts to every post and bet of a busy profile, while
+// * keeping a misbehaving registrant from draining the user canister's cycles
+pub const MAX_EVENT_CALLBACKS_PER_DAY: u64 = 1_000;
+
+/// Things happening on a user canister that third party canisters can be called back about
+#[derive(
+    CandidType, Deserialize, Serialize, Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord,
+)]
+pub enum EventCallbackKind {
+    PostCreated,
+    BetWon,
+}
+
+/// What a third party canister is approved for, across every user canister it registers on
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug, PartialEq, Eq)]
+pub struct EventCallbackApproval {
+    pub allowed_events: Vec<EventCallbackKind>,
+    // * Per user canister, days counted in UTC
+    pub max_callbacks_per_day: u64,
+}
+
+impl EventCallbackApproval {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.allowed_events.is_empty() {
+            return Err("At least one event has to be allowed".to_string());
+        }
+
+        if self.max_callbacks_per_day == 0
+            || self.max_callbacks_per_day > MAX_EVENT_CALLBACKS_PER_DAY
+        {
+            return Err(format!(
+                "Callbacks per day have to be between 1 and {}",
+                MAX_EVENT_CALLBACKS_PER_DAY
+            ));
+        }
+
+        Ok(())
+    }
+}
@@ -1 +1,3 @@
 pub mod args;
+pub mod event_callback;
+pub mod upgrade_window;
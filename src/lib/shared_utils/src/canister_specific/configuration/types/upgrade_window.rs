@@ -0,0 +1,162 @@
+use std::time::{Duration, SystemTime};
+
+use candid::{CandidType, Deserialize};
+use serde::Serialize;
+
+const MINUTES_IN_A_DAY: u16 = 24 * 60;
+const SECONDS_IN_A_DAY: i64 = 24 * 60 * 60;
+// * Furthest any timezone is from UTC, e.g. Kiribati at UTC+14
+pub const MAXIMUM_UTC_OFFSET_IN_MINUTES: i16 = 14 * 60;
+
+/// A stretch of local time each day in which user canisters may be upgraded. 02:00 to 05:00
+/// IST is `starts_at_minute_of_day: 120, ends_at_minute_of_day: 300, utc_offset_in_minutes:
+/// 330`. A window that ends before it starts runs past midnight.
+#[derive(CandidType, Deserialize, Serialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct UpgradeWindow {
+    pub starts_at_minute_of_day: u16,
+    pub ends_at_minute_of_day: u16,
+    // * A fixed offset, so windows in zones with daylight saving need updating when it changes
+    pub utc_offset_in_minutes: i16,
+}
+
+impl UpgradeWindow {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.starts_at_minute_of_day >= MINUTES_IN_A_DAY
+            || self.ends_at_minute_of_day >= MINUTES_IN_A_DAY
+        {
+            return Err("Window start and end have to be minutes within a day".to_string());
+        }
+
+        if self.starts_at_minute_of_day == self.ends_at_minute_of_day {
+            return Err("Window cannot start and end at the same minute".to_string());
+        }
+
+        if self.utc_offset_in_minutes.abs() > MAXIMUM_UTC_OFFSET_IN_MINUTES {
+            return Err(format!(
+                "UTC offset cannot be more than {} minutes",
+                MAXIMUM_UTC_OFFSET_IN_MINUTES
+            ));
+        }
+
+        Ok(())
+    }
+
+    fn get_local_second_of_day(&self, current_time: &SystemTime) -> i64 {
+        let seconds_since_epoch = current_time
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs() as i64;
+
+        (seconds_since_epoch + self.utc_offset_in_minutes as i64 * 60).rem_euclid(SECONDS_IN_A_DAY)
+    }
+
+    pub fn is_open_at(&self, current_time: &SystemTime) -> bool {
+        let local_second_of_day = self.get_local_second_of_day(current_time);
+        let starts_at = self.starts_at_minute_of_day as i64 * 60;
+        let ends_at = self.ends_at_minute_of_day as i64 * 60;
+
+        if starts_at < ends_at {
+            starts_at <= local_second_of_day && local_second_of_day < ends_at
+        } else {
+            local_second_of_day >= starts_at || local_second_of_day < ends_at
+        }
+    }
+
+    /// Zero while the window is open
+    pub fn get_time_until_next_opening(&self, current_time: &SystemTime) -> Duration {
+        if self.is_open_at(current_time) {
+            return Duration::ZERO;
+        }
+
+        let starts_at = self.starts_at_minute_of_day as i64 * 60;
+        Duration::from_secs(
+            (starts_at - self.get_local_second_of_day(current_time)).rem_euclid(SECONDS_IN_A_DAY)
+                as u64,
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn at_utc(hours: u64, minutes: u64) -> SystemTime {
+        // * Some day well after the epoch, at midnight UTC
+        SystemTime::UNIX_EPOCH
+            + Duration::from_secs(19_000 * SECONDS_IN_A_DAY as u64 + (hours * 60 + minutes) * 60)
+    }
+
+    #[test]
+    fn test_upgrade_window_in_ist() {
+        // * 02:00 to 05:00 IST is 20:30 to 23:30 UTC
+        let upgrade_window = UpgradeWindow {
+            starts_at_minute_of_day: 2 * 60,
+            ends_at_minute_of_day: 5 * 60,
+            utc_offset_in_minutes: 5 * 60 + 30,
+        };
+
+        assert!(!upgrade_window.is_open_at(&at_utc(20, 29)));
+        assert!(upgrade_window.is_open_at(&at_utc(20, 30)));
+        assert!(upgrade_window.is_open_at(&at_utc(23, 29)));
+        assert!(!upgrade_window.is_open_at(&at_utc(23, 30)));
+
+        assert_eq!(
+            upgrade_window.get_time_until_next_opening(&at_utc(20, 0)),
+            Duration::from_secs(30 * 60)
+        );
+        assert_eq!(
+            upgrade_window.get_time_until_next_opening(&at_utc(21, 0)),
+            Duration::ZERO
+        );
+        assert_eq!(
+            upgrade_window.get_time_until_next_opening(&at_utc(23, 30)),
+            Duration::from_secs(21 * 60 * 60)
+        );
+    }
+
+    #[test]
+    fn test_upgrade_window_past_midnight() {
+        let upgrade_window = UpgradeWindow {
+            starts_at_minute_of_day: 23 * 60,
+            ends_at_minute_of_day: 60,
+            utc_offset_in_minutes: -60,
+        };
+
+        assert!(upgrade_window.is_open_at(&at_utc(0, 0)));
+        assert!(upgrade_window.is_open_at(&at_utc(1, 59)));
+        assert!(!upgrade_window.is_open_at(&at_utc(2, 0)));
+        assert_eq!(
+            upgrade_window.get_time_until_next_opening(&at_utc(2, 0)),
+            Duration::from_secs(22 * 60 * 60)
+        );
+    }
+
+    #[test]
+    fn test_validate_upgrade_window() {
+        let upgrade_window = UpgradeWindow {
+            starts_at_minute_of_day: 2 * 60,
+            ends_at_minute_of_day: 5 * 60,
+            utc_offset_in_minutes: 5 * 60 + 30,
+        };
+
+        assert!(upgrade_window.validate().is_ok());
+        assert!(UpgradeWindow {
+            ends_at_minute_of_day: MINUTES_IN_A_DAY,
+            ..upgrade_window
+        }
+        .validate()
+        .is_err());
+        assert!(UpgradeWindow {
+            ends_at_minute_of_day: 2 * 60,
+            ..upgrade_window
+        }
+        .validate()
+        .is_err());
+        assert!(UpgradeWindow {
+            utc_offset_in_minutes: 15 * 60,
+            ..upgrade_window
+        }
+        .validate()
+        .is_err());
+    }
+}
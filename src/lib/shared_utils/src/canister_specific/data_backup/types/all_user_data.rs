@@ -7,7 +7,9 @@ use candid::{CandidType, Decode, Deserialize, Encode, Principal};
 use ic_stable_structures::{BoundedStorable, Storable};
 
 use crate::canister_specific::individual_user_template::types::{
-    post::Post, profile::UserProfile, token::TokenBalance,
+    post::{Post, PostDetailsForFrontend},
+    profile::UserProfile,
+    token::TokenBalance,
 };
 
 #[derive(CandidType, Deserialize, Debug)]
@@ -17,8 +19,45 @@ pub struct AllUserData {
     pub canister_data: UserOwnedCanisterData,
 }
 
+impl AllUserData {
+    /// Builds the frontend view of a post from the last backed up snapshot. Viewer specific
+    /// and live betting details are left out as the snapshot can't vouch for them.
+    pub fn get_backed_up_post_details_for_frontend(
+        &self,
+        post_id: u64,
+    ) -> Option<PostDetailsForFrontend> {
+        let post = self.canister_data.all_created_posts.get(&post_id)?;
+        let profile = &self.canister_data.profile;
+
+        Some(PostDetailsForFrontend {
+            id: post.id,
+            created_by_display_name: profile.display_name.clone(),
+            created_by_unique_user_name: profile.unique_user_name.clone(),
+            created_by_user_principal_id: self.user_principal_id,
+            created_by_profile_photo_url: profile.profile_picture_url.clone(),
+            created_at: post.created_at,
+            description: post.description.clone(),
+            hashtags: post.hashtags.clone(),
+            video_uid: post.video_uid.clone(),
+            status: post.status.clone(),
+            total_view_count: post.view_stats.total_view_count,
+            like_count: post.likes.len() as u64,
+            liked_by_me: false,
+            home_feed_ranking_score: post.home_feed_score.current_score,
+            hot_or_not_feed_ranking_score: post
+                .hot_or_not_details
+                .as_ref()
+                .map(|hot_or_not_details| hot_or_not_details.hot_or_not_feed_score.current_score),
+            hot_or_not_betting_status: None,
+            title: post.title.clone(),
+            category: post.category,
+            active_boost: None,
+        })
+    }
+}
+
 impl Storable for AllUserData {
-    fn to_bytes(&self) -> Cow<[u8]> {
+    fn to_bytes(&self) -> Cow<'_, [u8]> {
         Cow::Owned(Encode!(self).unwrap())
     }
 
@@ -0,0 +1,303 @@
+use std::{
+    collections::BTreeMap,
+    time::{Duration, SystemTime},
+};
+
+use candid::{CandidType, Deserialize, Principal};
+use serde::Serialize;
+
+// * Past this many (caller, method) pairs the pair with the fewest calls is dropped to make
+// * room, so a flood of fresh principals can't grow the heap without bound
+pub const MAX_TRACKED_CALLER_METHOD_PAIRS: usize = 1_000;
+pub const MAX_ANOMALOUS_CALLERS: usize = 100;
+pub const MAX_TOP_CALLERS_PAGE_SIZE: u64 = 100;
+// * Call counts halve every time this much time passes
+pub const CALL_COUNT_HALF_LIFE_IN_SECONDS: u64 = 60 * 60;
+
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug, PartialEq, Eq)]
+pub struct AccessStatisticsConfig {
+    // * Decayed calls to a single method past which a caller is flagged. Zero disables flagging
+    pub anomalous_call_count_threshold: u64,
+}
+
+impl Default for AccessStatisticsConfig {
+    fn default() -> Self {
+        Self {
+            anomalous_call_count_threshold: 1_000,
+        }
+    }
+}
+
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug, PartialEq, Eq)]
+pub struct CallerAccessStatistic {
+    pub caller: Principal,
+    pub method_name: String,
+    pub call_count: u64,
+}
+
+/// Evidence for the ban and rate limit systems: the method a caller was hammering and how
+/// hard, as of the last time it crossed the threshold
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug, PartialEq, Eq)]
+pub struct AnomalousCaller {
+    pub caller: Principal,
+    pub method_name: String,
+    pub call_count: u64,
+    pub last_flagged_at: SystemTime,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq, Eq)]
+struct DecayedCallCount {
+    call_count: u64,
+    last_decayed_at: SystemTime,
+}
+
+impl DecayedCallCount {
+    fn get_call_count_at(&self, current_time: SystemTime) -> u64 {
+        let elapsed_half_lives = current_time
+            .duration_since(self.last_decayed_at)
+            .unwrap_or_default()
+            .as_secs()
+            / CALL_COUNT_HALF_LIFE_IN_SECONDS;
+
+        self.call_count
+            .checked_shr(elapsed_half_lives.try_into().unwrap_or(u32::MAX))
+            .unwrap_or(0)
+    }
+
+    fn decay_to(&mut self, current_time: SystemTime) {
+        let elapsed_half_lives = current_time
+            .duration_since(self.last_decayed_at)
+            .unwrap_or_default()
+            .as_secs()
+            / CALL_COUNT_HALF_LIFE_IN_SECONDS;
+
+        if elapsed_half_lives == 0 {
+            return;
+        }
+
+        self.call_count = self.get_call_count_at(current_time);
+        self.last_decayed_at +=
+            Duration::from_secs(elapsed_half_lives.saturating_mul(CALL_COUNT_HALF_LIFE_IN_SECONDS));
+    }
+}
+
+/// Calls per method per caller, decayed over time, kept on the heap since it is bounded
+#[derive(Default, Deserialize, Serialize)]
+pub struct AccessStatistics {
+    call_counts: BTreeMap<(Principal, String), DecayedCallCount>,
+    anomalous_callers: BTreeMap<Principal, AnomalousCaller>,
+}
+
+impl AccessStatistics {
+    pub fn record_call(
+        &mut self,
+        caller: Principal,
+        method_name: &str,
+        current_time: SystemTime,
+        config: &AccessStatisticsConfig,
+    ) {
+        let key = (caller, method_name.to_string());
+
+        if !self.call_counts.contains_key(&key)
+            && self.call_counts.len() >= MAX_TRACKED_CALLER_METHOD_PAIRS
+        {
+            self.evict_least_called(current_time);
+        }
+
+        let decayed_call_count = self.call_counts.entry(key).or_insert(DecayedCallCount {
+            call_count: 0,
+            last_decayed_at: current_time,
+        });
+        decayed_call_count.decay_to(current_time);
+        decayed_call_count.call_count = decayed_call_count.call_count.saturating_add(1);
+        let call_count = decayed_call_count.call_count;
+
+        if config.anomalous_call_count_threshold > 0
+            && call_count > config.anomalous_call_count_threshold
+        {
+            self.flag_anomalous_caller(AnomalousCaller {
+                caller,
+                method_name: method_name.to_string(),
+                call_count,
+                last_flagged_at: current_time,
+            });
+        }
+    }
+
+    fn evict_least_called(&mut self, current_time: SystemTime) {
+        let least_called = self
+            .call_counts
+            .iter()
+            .min_by_key(|(_, decayed_call_count)| {
+                decayed_call_count.get_call_count_at(current_time)
+            })
+            .map(|(key, _)| key.clone());
+
+        if let Some(least_called) = least_called {
+            self.call_counts.remove(&least_called);
+        }
+    }
+
+    fn flag_anomalous_caller(&mut self, anomalous_caller: AnomalousCaller) {
+        if !self
+            .anomalous_callers
+            .contains_key(&anomalous_caller.caller)
+            && self.anomalous_callers.len() >= MAX_ANOMALOUS_CALLERS
+        {
+            let longest_quiet = self
+                .anomalous_callers
+                .values()
+                .min_by_key(|flagged| flagged.last_flagged_at)
+                .map(|flagged| flagged.caller);
+
+            if let Some(longest_quiet) = longest_quiet {
+                self.anomalous_callers.remove(&longest_quiet);
+            }
+        }
+
+        self.anomalous_callers
+            .insert(anomalous_caller.caller, anomalous_caller);
+    }
+
+    /// Busiest (caller, method) pairs first
+    pub fn get_top_callers(
+        &self,
+        current_time: SystemTime,
+        limit: u64,
+    ) -> Vec<CallerAccessStatistic> {
+        let mut top_callers: Vec<CallerAccessStatistic> = self
+            .call_counts
+            .iter()
+            .map(
+                |((caller, method_name), decayed_call_count)| CallerAccessStatistic {
+                    caller: *caller,
+                    method_name: method_name.clone(),
+                    call_count: decayed_call_count.get_call_count_at(current_time),
+                },
+            )
+            .filter(|statistic| statistic.call_count > 0)
+            .collect();
+
+        top_callers.sort_by_key(|statistic| std::cmp::Reverse(statistic.call_count));
+        top_callers.truncate(limit.min(MAX_TOP_CALLERS_PAGE_SIZE) as usize);
+
+        top_callers
+    }
+
+    /// Most recently flagged first
+    pub fn get_anomalous_callers(&self) -> Vec<AnomalousCaller> {
+        let mut anomalous_callers: Vec<AnomalousCaller> =
+            self.anomalous_callers.values().cloned().collect();
+
+        anomalous_callers
+            .sort_by_key(|anomalous_caller| std::cmp::Reverse(anomalous_caller.last_flagged_at));
+
+        anomalous_callers
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn get_caller(index: u64) -> Principal {
+        Principal::self_authenticating(index.to_be_bytes())
+    }
+
+    #[test]
+    fn test_call_counts_decay_and_are_ranked() {
+        let mut access_statistics = AccessStatistics::default();
+        let config = AccessStatisticsConfig::default();
+        let start = SystemTime::UNIX_EPOCH;
+
+        for _ in 0..8 {
+            access_statistics.record_call(get_caller(1), "boost_post", start, &config);
+        }
+        access_statistics.record_call(get_caller(2), "boost_post", start, &config);
+        access_statistics.record_call(get_caller(1), "report_post", start, &config);
+
+        let top_callers = access_statistics.get_top_callers(start, 2);
+        assert_eq!(top_callers.len(), 2);
+        assert_eq!(
+            top_callers[0],
+            CallerAccessStatistic {
+                caller: get_caller(1),
+                method_name: "boost_post".to_string(),
+                call_count: 8,
+            }
+        );
+
+        // * two half lives later the busy pair has decayed to a quarter, the rest to nothing
+        let later = start + Duration::from_secs(2 * CALL_COUNT_HALF_LIFE_IN_SECONDS);
+        assert_eq!(
+            access_statistics.get_top_callers(later, 10),
+            vec![CallerAccessStatistic {
+                caller: get_caller(1),
+                method_name: "boost_post".to_string(),
+                call_count: 2,
+            }]
+        );
+
+        access_statistics.record_call(get_caller(1), "boost_post", later, &config);
+        assert_eq!(access_statistics.get_top_callers(later, 1)[0].call_count, 3);
+    }
+
+    #[test]
+    fn test_tracked_pairs_are_bounded() {
+        let mut access_statistics = AccessStatistics::default();
+        let config = AccessStatisticsConfig::default();
+        let start = SystemTime::UNIX_EPOCH;
+
+        access_statistics.record_call(get_caller(0), "boost_post", start, &config);
+        access_statistics.record_call(get_caller(0), "boost_post", start, &config);
+        for index in 1..(MAX_TRACKED_CALLER_METHOD_PAIRS as u64 + 10) {
+            access_statistics.record_call(get_caller(index), "boost_post", start, &config);
+        }
+
+        assert_eq!(
+            access_statistics.call_counts.len(),
+            MAX_TRACKED_CALLER_METHOD_PAIRS
+        );
+        // * the busiest caller is never the one evicted
+        assert_eq!(
+            access_statistics.get_top_callers(start, 1)[0].caller,
+            get_caller(0)
+        );
+    }
+
+    #[test]
+    fn test_callers_past_the_threshold_are_flagged() {
+        let mut access_statistics = AccessStatistics::default();
+        let config = AccessStatisticsConfig {
+            anomalous_call_count_threshold: 3,
+        };
+        let start = SystemTime::UNIX_EPOCH;
+
+        for _ in 0..3 {
+            access_statistics.record_call(get_caller(1), "boost_post", start, &config);
+        }
+        assert!(access_statistics.get_anomalous_callers().is_empty());
+
+        let later = start + Duration::from_secs(10);
+        access_statistics.record_call(get_caller(1), "boost_post", later, &config);
+        assert_eq!(
+            access_statistics.get_anomalous_callers(),
+            vec![AnomalousCaller {
+                caller: get_caller(1),
+                method_name: "boost_post".to_string(),
+                call_count: 4,
+                last_flagged_at: later,
+            }]
+        );
+
+        // * a zero threshold switches flagging off
+        let mut access_statistics = AccessStatistics::default();
+        let config = AccessStatisticsConfig {
+            anomalous_call_count_threshold: 0,
+        };
+        for _ in 0..10 {
+            access_statistics.record_call(get_caller(1), "boost_post", start, &config);
+        }
+        assert!(access_statistics.get_anomalous_callers().is_empty());
+    }
+}
@@ -0,0 +1,181 @@
+use std::{collections::BTreeMap, time::SystemTime};
+
+use candid::{CandidType, Deserialize};
+use serde::Serialize;
+
+#[derive(
+    CandidType, Deserialize, Serialize, Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord,
+)]
+pub enum Achievement {
+    FirstBet,
+    TenBetsWon,
+    ThousandFollowers,
+    // * One of the profile's posts drew 100k tokens in bets
+    HundredThousandPotCreator,
+}
+
+/// Something that happened on the profile that may earn it an achievement. Counts include the
+/// event itself.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AchievementEvent {
+    BetPlaced { bets_placed: u64 },
+    BetWon { bets_won: u64 },
+    FollowerGained { follower_count: u64 },
+    BetReceivedOnPost { pot_size: u64 },
+}
+
+impl Achievement {
+    pub const ALL: [Achievement; 4] = [
+        Achievement::FirstBet,
+        Achievement::TenBetsWon,
+        Achievement::ThousandFollowers,
+        Achievement::HundredThousandPotCreator,
+    ];
+
+    pub fn get_threshold(&self) -> u64 {
+        match self {
+            Achievement::FirstBet => 1,
+            Achievement::TenBetsWon => 10,
+            Achievement::ThousandFollowers => 1_000,
+            Achievement::HundredThousandPotCreator => 100_000,
+        }
+    }
+
+    /// Tokens minted once when the achievement is earned. The first bet doesn't pay out, or
+    /// fresh accounts could farm it on top of the signup reward.
+    pub fn get_reward_amount(&self) -> Option<u64> {
+        match self {
+            Achievement::FirstBet => None,
+            Achievement::TenBetsWon => Some(100),
+            Achievement::ThousandFollowers => Some(1_000),
+            Achievement::HundredThousandPotCreator => Some(1_000),
+        }
+    }
+
+    /// How far `event` takes the profile towards this achievement. None if the event has
+    /// nothing to do with it.
+    fn get_progress(&self, event: &AchievementEvent) -> Option<u64> {
+        match (self, event) {
+            (Achievement::FirstBet, AchievementEvent::BetPlaced { bets_placed }) => {
+                Some(*bets_placed)
+            }
+            (Achievement::TenBetsWon, AchievementEvent::BetWon { bets_won }) => Some(*bets_won),
+            (
+                Achievement::ThousandFollowers,
+                AchievementEvent::FollowerGained { follower_count },
+            ) => Some(*follower_count),
+            (
+                Achievement::HundredThousandPotCreator,
+                AchievementEvent::BetReceivedOnPost { pot_size },
+            ) => Some(*pot_size),
+            _ => None,
+        }
+    }
+}
+
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug, PartialEq, Eq)]
+pub struct Badge {
+    pub achievement: Achievement,
+    pub awarded_at: SystemTime,
+    pub reward_amount: Option<u64>,
+}
+
+#[derive(Default, Deserialize, Serialize, Clone, Debug)]
+pub struct Achievements {
+    pub badges: BTreeMap<Achievement, Badge>,
+}
+
+impl Achievements {
+    /// Awards the achievements `event` completes that the profile doesn't have yet. Each
+    /// achievement is only ever awarded once, so losing followers or repeating an event
+    /// doesn't take a badge away or award it again.
+    pub fn handle_event(
+        &mut self,
+        event: &AchievementEvent,
+        current_time: &SystemTime,
+    ) -> Vec<Badge> {
+        let newly_earned_achievements: Vec<Achievement> = Achievement::ALL
+            .into_iter()
+            .filter(|achievement| !self.badges.contains_key(achievement))
+            .filter(|achievement| {
+                achievement
+                    .get_progress(event)
+                    .is_some_and(|progress| progress >= achievement.get_threshold())
+            })
+            .collect();
+
+        newly_earned_achievements
+            .into_iter()
+            .map(|achievement| {
+                let badge = Badge {
+                    achievement,
+                    awarded_at: *current_time,
+                    reward_amount: achievement.get_reward_amount(),
+                };
+                self.badges.insert(achievement, badge.clone());
+                badge
+            })
+            .collect()
+    }
+
+    pub fn get_badges(&self) -> Vec<Badge> {
+        self.badges.values().cloned().collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_handle_event_awards_each_achievement_once() {
+        let mut achievements = Achievements::default();
+        let current_time = SystemTime::UNIX_EPOCH;
+
+        assert!(achievements
+            .handle_event(&AchievementEvent::BetWon { bets_won: 9 }, &current_time)
+            .is_empty());
+
+        let newly_awarded_badges =
+            achievements.handle_event(&AchievementEvent::BetWon { bets_won: 10 }, &current_time);
+        assert_eq!(
+            newly_awarded_badges,
+            vec![Badge {
+                achievement: Achievement::TenBetsWon,
+                awarded_at: current_time,
+                reward_amount: Some(100),
+            }]
+        );
+        assert!(achievements
+            .handle_event(&AchievementEvent::BetWon { bets_won: 11 }, &current_time)
+            .is_empty());
+
+        assert!(achievements
+            .handle_event(
+                &AchievementEvent::BetReceivedOnPost { pot_size: 5_000 },
+                &current_time
+            )
+            .is_empty());
+        assert_eq!(
+            achievements
+                .handle_event(
+                    &AchievementEvent::BetReceivedOnPost { pot_size: 100_000 },
+                    &current_time
+                )
+                .len(),
+            1
+        );
+
+        assert_eq!(
+            achievements
+                .get_badges()
+                .iter()
+                .map(|badge| badge.achievement)
+                .collect::<Vec<_>>(),
+            vec![
+                Achievement::TenBetsWon,
+                Achievement::HundredThousandPotCreator
+            ]
+        );
+    }
+}
@@ -1,8 +1,14 @@
 use candid::{CandidType, Deserialize, Principal};
 
-use crate::common::types::known_principal::KnownPrincipalMap;
+use crate::common::types::{global_post_ref::GlobalPostRef, known_principal::KnownPrincipalMap};
 
-use super::hot_or_not::BetDirection;
+use super::{
+    access_statistics::AccessStatisticsConfig,
+    hot_or_not::{BetCancellationConfig, BetDirection, HotOrNotPayoutMode, RoomSizingConfig},
+    post::PostCategoryConfig,
+    signup_bonus::SignupBonusVestingConfig,
+    treasury::PotInsuranceConfig,
+};
 
 #[derive(Deserialize, CandidType)]
 pub struct IndividualUserTemplateInitArgs {
@@ -10,8 +16,26 @@ pub struct IndividualUserTemplateInitArgs {
     pub profile_owner: Option<Principal>,
     pub upgrade_version_number: Option<u64>,
     pub url_to_send_canister_metrics_to: Option<String>,
+    pub referrer_share_of_bet_winnings_percentage: Option<u64>,
+    pub house_rake_percentage: Option<u64>,
+    pub room_sizing_config: Option<RoomSizingConfig>,
+    pub post_category_config: Option<PostCategoryConfig>,
+    // * Set by the user index, bumped every time it reinstalls the canister
+    pub binding_epoch: Option<u64>,
+    // * Switching the journal on starts it over empty
+    pub is_call_journal_enabled: Option<bool>,
+    // * Only applies to users who sign up after it is set
+    pub signup_bonus_vesting_config: Option<SignupBonusVestingConfig>,
+    pub pot_insurance_config: Option<PotInsuranceConfig>,
+    pub bet_cancellation_config: Option<BetCancellationConfig>,
+    // * Only ever set through an upgrade arg override, for canisters under attack
+    pub access_statistics_config: Option<AccessStatisticsConfig>,
+    // * Only applies to rooms settled after it is set
+    pub hot_or_not_payout_mode: Option<HotOrNotPayoutMode>,
 }
 
+pub const MAX_BETS_PER_BATCH: usize = 10;
+
 #[derive(Deserialize, CandidType, Clone)]
 pub struct PlaceBetArg {
     pub post_canister_id: Principal,
@@ -20,6 +44,12 @@ pub struct PlaceBetArg {
     pub bet_direction: BetDirection,
 }
 
+impl PlaceBetArg {
+    pub fn global_post_ref(&self) -> GlobalPostRef {
+        GlobalPostRef::new(self.post_canister_id, self.post_id)
+    }
+}
+
 #[derive(CandidType, Deserialize, Clone)]
 pub struct FolloweeArg {
     pub followee_principal_id: Principal,
@@ -0,0 +1,145 @@
+use std::{borrow::Cow, time::SystemTime};
+
+use candid::{CandidType, Decode, Deserialize, Encode, Principal};
+use ic_stable_structures::{Memory, StableLog, Storable};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+
+use crate::pagination::{self, Page};
+
+// * Once the journal holds this many calls it stops recording, rather than letting a
+// * forgotten journal eat into stable memory
+pub const MAX_CALL_JOURNAL_ENTRIES: u64 = 50_000;
+
+/// A state mutating call as it arrived, enough to replay the calls against the same wasm in
+/// the same order and check that the replay was fed the same arguments
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug, PartialEq, Eq)]
+pub struct CallJournalEntry {
+    pub method_name: String,
+    pub caller: Principal,
+    // * SHA-256 of the candid encoded arguments
+    pub argument_digest: Vec<u8>,
+    pub received_at: SystemTime,
+    pub version_number: u64,
+}
+
+impl Storable for CallJournalEntry {
+    fn to_bytes(&self) -> Cow<'_, [u8]> {
+        Cow::Owned(Encode!(self).unwrap())
+    }
+
+    fn from_bytes(bytes: Cow<[u8]>) -> Self {
+        Decode!(&bytes, Self).unwrap()
+    }
+}
+
+pub fn get_argument_digest(argument_bytes: &[u8]) -> Vec<u8> {
+    Sha256::digest(argument_bytes).to_vec()
+}
+
+/// Append only journal of calls living in a pair of stable memory regions, one for the index
+/// and one for the entries themselves
+pub struct CallJournal<M: Memory + Clone> {
+    entries: StableLog<CallJournalEntry, M, M>,
+}
+
+impl<M: Memory + Clone> CallJournal<M> {
+    /// Loads the journal persisted in the memories. A journal that can't be read back is
+    /// started over, since it is only ever a debugging aid.
+    pub fn init(index_memory: M, data_memory: M) -> Self {
+        let entries = StableLog::init(index_memory.clone(), data_memory.clone())
+            .unwrap_or_else(|_| StableLog::new(index_memory, data_memory));
+
+        Self { entries }
+    }
+
+    /// Starts an empty journal, dropping whatever the memories held before
+    pub fn new(index_memory: M, data_memory: M) -> Self {
+        Self {
+            entries: StableLog::new(index_memory, data_memory),
+        }
+    }
+
+    pub fn len(&self) -> u64 {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.len() == 0
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.entries.len() >= MAX_CALL_JOURNAL_ENTRIES
+    }
+
+    /// Returns false if the entry was not recorded because the journal is full or stable
+    /// memory could not grow
+    pub fn record(&mut self, entry: &CallJournalEntry) -> bool {
+        !self.is_full() && self.entries.append(entry).is_ok()
+    }
+
+    pub fn get_page(&self, from_inclusive_index: u64, page_size: u64) -> Page<CallJournalEntry> {
+        // * Paged over indices first so that only the entries on the page are read
+        let index_page = pagination::get_page(
+            0..self.entries.len() as usize,
+            from_inclusive_index,
+            page_size,
+        );
+
+        Page {
+            items: index_page
+                .items
+                .into_iter()
+                .filter_map(|index| self.entries.get(index as u64))
+                .collect(),
+            total_count: index_page.total_count,
+            next_from_inclusive_index: index_page.next_from_inclusive_index,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ic_stable_structures::VectorMemory;
+
+    use super::*;
+
+    fn get_entry(method_name: &str) -> CallJournalEntry {
+        CallJournalEntry {
+            method_name: method_name.to_string(),
+            caller: Principal::anonymous(),
+            argument_digest: get_argument_digest(method_name.as_bytes()),
+            received_at: SystemTime::UNIX_EPOCH,
+            version_number: 1,
+        }
+    }
+
+    #[test]
+    fn test_call_journal_survives_reload_and_can_be_started_over() {
+        let index_memory = VectorMemory::default();
+        let data_memory = VectorMemory::default();
+
+        let mut call_journal = CallJournal::init(index_memory.clone(), data_memory.clone());
+        assert!(call_journal.is_empty());
+        assert!(call_journal.record(&get_entry("boost_post")));
+        assert!(call_journal.record(&get_entry("report_post")));
+
+        let call_journal = CallJournal::init(index_memory.clone(), data_memory.clone());
+        let page = call_journal.get_page(1, 10);
+        assert_eq!(page.total_count, 2);
+        assert_eq!(page.items, vec![get_entry("report_post")]);
+        assert_eq!(page.next_from_inclusive_index, None);
+
+        let call_journal = CallJournal::new(index_memory, data_memory);
+        assert!(call_journal.is_empty());
+    }
+
+    #[test]
+    fn test_get_argument_digest() {
+        assert_eq!(get_argument_digest(b"DIDL\x00\x00").len(), 32);
+        assert_ne!(
+            get_argument_digest(b"DIDL\x00\x01\x7e\x00"),
+            get_argument_digest(b"DIDL\x00\x01\x7e\x01")
+        );
+    }
+}
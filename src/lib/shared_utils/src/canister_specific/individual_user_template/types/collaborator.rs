@@ -0,0 +1,170 @@
+use std::{borrow::Cow, collections::HashSet};
+
+use candid::{CandidType, Decode, Deserialize, Encode, Principal};
+use ic_stable_structures::{BoundedStorable, Storable};
+use serde::Serialize;
+
+use crate::common::types::app_primitive_type::PostId;
+
+/// A principal credited on a post along with their percentage of the creator commission
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug, PartialEq, Eq)]
+pub struct CollaboratorShare {
+    pub collaborator_principal_id: Principal,
+    pub percentage: u64,
+}
+
+/// A collaborator's cut of the creator commission from a tabulated room that is yet to be
+/// delivered to the collaborator's canister
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug, PartialEq, Eq)]
+pub struct CollaboratorCommissionSettlement {
+    pub collaborator_principal_id: Principal,
+    pub post_canister_id: Principal,
+    pub post_id: PostId,
+    pub slot_id: u8,
+    pub room_id: u64,
+    pub room_pot_total_amount: u64,
+    pub share_amount: u64,
+    pub number_of_failed_attempts: u32,
+}
+
+impl Storable for CollaboratorCommissionSettlement {
+    fn to_bytes(&self) -> Cow<'_, [u8]> {
+        Cow::Owned(Encode!(self).unwrap())
+    }
+
+    fn from_bytes(bytes: Cow<[u8]>) -> Self {
+        Decode!(&bytes, Self).unwrap()
+    }
+}
+
+impl BoundedStorable for CollaboratorCommissionSettlement {
+    const MAX_SIZE: u32 = 1_000;
+    const IS_FIXED_SIZE: bool = false;
+}
+
+pub const MAXIMUM_NUMBER_OF_COLLABORATORS_ON_A_POST: usize = 10;
+
+/// The creator lists themselves alongside their collaborators, so the splits have to add up
+/// to the entire commission
+pub fn validate_collaborator_shares(
+    collaborator_shares: &[CollaboratorShare],
+) -> Result<(), String> {
+    if collaborator_shares.is_empty() {
+        return Ok(());
+    }
+
+    if collaborator_shares.len() > MAXIMUM_NUMBER_OF_COLLABORATORS_ON_A_POST {
+        return Err(format!(
+            "A post can have at most {} collaborators",
+            MAXIMUM_NUMBER_OF_COLLABORATORS_ON_A_POST
+        ));
+    }
+
+    let mut seen_principal_ids = HashSet::new();
+    for collaborator_share in collaborator_shares {
+        if collaborator_share.collaborator_principal_id == Principal::anonymous() {
+            return Err("Collaborators cannot be anonymous".to_string());
+        }
+
+        if collaborator_share.percentage == 0 {
+            return Err("Every collaborator needs a non zero share".to_string());
+        }
+
+        if !seen_principal_ids.insert(collaborator_share.collaborator_principal_id) {
+            return Err("A collaborator can only be listed once".to_string());
+        }
+    }
+
+    let total_percentage = collaborator_shares
+        .iter()
+        .fold(0_u64, |total, collaborator_share| {
+            total.saturating_add(collaborator_share.percentage)
+        });
+
+    if total_percentage != 100 {
+        return Err("Collaborator shares need to add up to 100%".to_string());
+    }
+
+    Ok(())
+}
+
+pub fn get_collaborator_share_of_commission(commission_amount: u64, percentage: u64) -> u64 {
+    (commission_amount as u128 * percentage as u128 / 100) as u64
+}
+
+#[cfg(test)]
+mod test {
+    use test_utils::setup::test_constants::{
+        get_mock_user_alice_principal_id, get_mock_user_bob_principal_id,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_validate_collaborator_shares() {
+        assert_eq!(validate_collaborator_shares(&[]), Ok(()));
+
+        assert_eq!(
+            validate_collaborator_shares(&[
+                CollaboratorShare {
+                    collaborator_principal_id: get_mock_user_alice_principal_id(),
+                    percentage: 60,
+                },
+                CollaboratorShare {
+                    collaborator_principal_id: get_mock_user_bob_principal_id(),
+                    percentage: 40,
+                },
+            ]),
+            Ok(())
+        );
+
+        assert!(validate_collaborator_shares(&[
+            CollaboratorShare {
+                collaborator_principal_id: get_mock_user_alice_principal_id(),
+                percentage: 60,
+            },
+            CollaboratorShare {
+                collaborator_principal_id: get_mock_user_bob_principal_id(),
+                percentage: 30,
+            },
+        ])
+        .is_err());
+
+        assert!(validate_collaborator_shares(&[
+            CollaboratorShare {
+                collaborator_principal_id: get_mock_user_alice_principal_id(),
+                percentage: 50,
+            },
+            CollaboratorShare {
+                collaborator_principal_id: get_mock_user_alice_principal_id(),
+                percentage: 50,
+            },
+        ])
+        .is_err());
+
+        assert!(validate_collaborator_shares(&[
+            CollaboratorShare {
+                collaborator_principal_id: get_mock_user_alice_principal_id(),
+                percentage: 100,
+            },
+            CollaboratorShare {
+                collaborator_principal_id: get_mock_user_bob_principal_id(),
+                percentage: 0,
+            },
+        ])
+        .is_err());
+
+        assert!(validate_collaborator_shares(&[CollaboratorShare {
+            collaborator_principal_id: Principal::anonymous(),
+            percentage: 100,
+        }])
+        .is_err());
+    }
+
+    #[test]
+    fn test_get_collaborator_share_of_commission() {
+        assert_eq!(get_collaborator_share_of_commission(355, 60), 213);
+        assert_eq!(get_collaborator_share_of_commission(355, 40), 142);
+        assert_eq!(get_collaborator_share_of_commission(1, 50), 0);
+    }
+}
@@ -0,0 +1,268 @@
+use std::time::SystemTime;
+
+use candid::{CandidType, Deserialize, Principal};
+use serde::Serialize;
+
+use super::error::CommentError;
+
+pub type CommentId = u64;
+
+pub const MAX_COMMENT_LENGTH_IN_CHARACTERS: usize = 500;
+
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug, PartialEq, Eq)]
+pub struct Comment {
+    pub comment_id: CommentId,
+    pub author_principal_id: Principal,
+    pub text: String,
+    pub created_at: SystemTime,
+    // * Shadow-deleted by the post owner. The author still sees it, and isn't told
+    pub is_hidden: bool,
+    // * Left while the author was shadow banned. Only the author sees it, and isn't told
+    #[serde(default)]
+    pub is_from_shadow_banned_author: bool,
+}
+
+/// A comment as shown to readers. Leaves out whether it was hidden or left while its author
+/// was shadow banned, so that its author can't tell.
+#[derive(CandidType, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct CommentDetailsForFrontend {
+    pub comment_id: CommentId,
+    pub author_principal_id: Principal,
+    pub text: String,
+    pub created_at: SystemTime,
+}
+
+impl From<&Comment> for CommentDetailsForFrontend {
+    fn from(comment: &Comment) -> Self {
+        Self {
+            comment_id: comment.comment_id,
+            author_principal_id: comment.author_principal_id,
+            text: comment.text.clone(),
+            created_at: comment.created_at,
+        }
+    }
+}
+
+#[derive(CandidType, Deserialize, Serialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CommentModerationAction {
+    Hidden,
+    Restored,
+}
+
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug, PartialEq, Eq)]
+pub struct CommentModerationLogEntry {
+    pub comment_id: CommentId,
+    pub action: CommentModerationAction,
+    pub moderated_by: Principal,
+    pub moderated_at: SystemTime,
+}
+
+/// The comments on one post, along with every time one of them was hidden or restored
+#[derive(Deserialize, Serialize, Clone, Default, Debug)]
+pub struct PostComments {
+    // * Indexed by comment id
+    comments: Vec<Comment>,
+    moderation_log: Vec<CommentModerationLogEntry>,
+}
+
+impl PostComments {
+    pub fn add_comment(
+        &mut self,
+        author_principal_id: Principal,
+        text: String,
+        created_at: SystemTime,
+        is_from_shadow_banned_author: bool,
+    ) -> Result<CommentId, CommentError> {
+        if text.trim().is_empty() {
+            return Err(CommentError::CommentEmpty);
+        }
+
+        if text.chars().count() > MAX_COMMENT_LENGTH_IN_CHARACTERS {
+            return Err(CommentError::CommentTooLong);
+        }
+
+        let comment_id = self.comments.len() as CommentId;
+        self.comments.push(Comment {
+            comment_id,
+            author_principal_id,
+            text,
+            created_at,
+            is_hidden: false,
+            is_from_shadow_banned_author,
+        });
+
+        Ok(comment_id)
+    }
+
+    /// Oldest first. Hidden comments and comments left by shadow banned authors are left out
+    /// for everyone but their author.
+    pub fn get_comments_visible_to(&self, reader: &Principal) -> Vec<CommentDetailsForFrontend> {
+        self.comments
+            .iter()
+            .filter(|comment| {
+                !(comment.is_hidden || comment.is_from_shadow_banned_author)
+                    || comment.author_principal_id == *reader
+            })
+            .map(CommentDetailsForFrontend::from)
+            .collect()
+    }
+
+    /// Hiding a comment that is already hidden, or restoring one that isn't, changes nothing
+    /// and isn't logged. Returns whether the comment's visibility changed.
+    pub fn set_comment_hidden(
+        &mut self,
+        comment_id: CommentId,
+        is_hidden: bool,
+        moderated_by: Principal,
+        moderated_at: SystemTime,
+    ) -> Result<bool, CommentError> {
+        let comment = self
+            .comments
+            .get_mut(comment_id as usize)
+            .ok_or(CommentError::CommentNotFound)?;
+
+        if comment.is_hidden == is_hidden {
+            return Ok(false);
+        }
+        comment.is_hidden = is_hidden;
+
+        self.moderation_log.push(CommentModerationLogEntry {
+            comment_id,
+            action: if is_hidden {
+                CommentModerationAction::Hidden
+            } else {
+                CommentModerationAction::Restored
+            },
+            moderated_by,
+            moderated_at,
+        });
+
+        Ok(true)
+    }
+
+    pub fn get_moderation_summary(&self) -> CommentModerationSummary {
+        CommentModerationSummary {
+            number_of_comments: self.comments.len() as u64,
+            number_of_hidden_comments: self
+                .comments
+                .iter()
+                .filter(|comment| comment.is_hidden)
+                .count() as u64,
+            moderation_log: self.moderation_log.clone(),
+        }
+    }
+}
+
+#[derive(CandidType, Deserialize, Clone, Default, Debug, PartialEq, Eq)]
+pub struct CommentModerationSummary {
+    pub number_of_comments: u64,
+    pub number_of_hidden_comments: u64,
+    // * Oldest first
+    pub moderation_log: Vec<CommentModerationLogEntry>,
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_hidden_comments_are_only_visible_to_their_author() {
+        let author = Principal::from_slice(&[1]);
+        let reader = Principal::from_slice(&[2]);
+        let post_owner = Principal::from_slice(&[3]);
+        let mut post_comments = PostComments::default();
+
+        let comment_id = post_comments
+            .add_comment(author, "first!".to_string(), SystemTime::UNIX_EPOCH, false)
+            .unwrap();
+        post_comments
+            .add_comment(reader, "nice".to_string(), SystemTime::UNIX_EPOCH, false)
+            .unwrap();
+
+        assert_eq!(
+            post_comments.set_comment_hidden(comment_id, true, post_owner, SystemTime::UNIX_EPOCH),
+            Ok(true)
+        );
+        // * hiding twice isn't logged twice
+        assert_eq!(
+            post_comments.set_comment_hidden(comment_id, true, post_owner, SystemTime::UNIX_EPOCH),
+            Ok(false)
+        );
+
+        assert_eq!(post_comments.get_comments_visible_to(&author).len(), 2);
+        assert_eq!(post_comments.get_comments_visible_to(&reader).len(), 1);
+
+        let summary = post_comments.get_moderation_summary();
+        assert_eq!(summary.number_of_comments, 2);
+        assert_eq!(summary.number_of_hidden_comments, 1);
+        assert_eq!(summary.moderation_log.len(), 1);
+
+        post_comments
+            .set_comment_hidden(comment_id, false, post_owner, SystemTime::UNIX_EPOCH)
+            .unwrap();
+        assert_eq!(post_comments.get_comments_visible_to(&reader).len(), 2);
+        assert_eq!(
+            post_comments.get_moderation_summary().moderation_log[1].action,
+            CommentModerationAction::Restored
+        );
+    }
+
+    #[test]
+    fn test_comments_of_shadow_banned_authors_are_only_visible_to_them() {
+        let shadow_banned_author = Principal::from_slice(&[1]);
+        let reader = Principal::from_slice(&[2]);
+        let mut post_comments = PostComments::default();
+
+        post_comments
+            .add_comment(
+                shadow_banned_author,
+                "buy my tokens".to_string(),
+                SystemTime::UNIX_EPOCH,
+                true,
+            )
+            .unwrap();
+        post_comments
+            .add_comment(reader, "nice".to_string(), SystemTime::UNIX_EPOCH, false)
+            .unwrap();
+
+        assert_eq!(
+            post_comments
+                .get_comments_visible_to(&shadow_banned_author)
+                .len(),
+            2
+        );
+        assert_eq!(
+            post_comments.get_comments_visible_to(&reader),
+            vec![CommentDetailsForFrontend {
+                comment_id: 1,
+                author_principal_id: reader,
+                text: "nice".to_string(),
+                created_at: SystemTime::UNIX_EPOCH,
+            }]
+        );
+    }
+
+    #[test]
+    fn test_add_comment_validates_text() {
+        let mut post_comments = PostComments::default();
+        let author = Principal::from_slice(&[1]);
+
+        assert_eq!(
+            post_comments.add_comment(author, "  ".to_string(), SystemTime::UNIX_EPOCH, false),
+            Err(CommentError::CommentEmpty)
+        );
+        assert_eq!(
+            post_comments.add_comment(
+                author,
+                "a".repeat(MAX_COMMENT_LENGTH_IN_CHARACTERS + 1),
+                SystemTime::UNIX_EPOCH,
+                false
+            ),
+            Err(CommentError::CommentTooLong)
+        );
+        assert_eq!(
+            post_comments.set_comment_hidden(0, true, author, SystemTime::UNIX_EPOCH),
+            Err(CommentError::CommentNotFound)
+        );
+    }
+}
@@ -1,6 +1,38 @@
 use serde::{Deserialize, Serialize};
 
+use super::{
+    access_statistics::AccessStatisticsConfig,
+    hot_or_not::{BetCancellationConfig, HotOrNotPayoutMode, RoomSizingConfig},
+    post::PostCategoryConfig,
+    signup_bonus::SignupBonusVestingConfig,
+    treasury::PotInsuranceConfig,
+};
+
 #[derive(Default, Deserialize, Serialize)]
 pub struct IndividualUserConfiguration {
     pub url_to_send_canister_metrics_to: Option<String>,
+    // * Share of a bettor's winnings that goes to whoever referred them. Zero disables it
+    #[serde(default)]
+    pub referrer_share_of_bet_winnings_percentage: u64,
+    // * Share of each room's pot that goes to the treasury canister. Zero disables it
+    #[serde(default)]
+    pub house_rake_percentage: u64,
+    #[serde(default)]
+    pub room_sizing_config: RoomSizingConfig,
+    #[serde(default)]
+    pub post_category_config: PostCategoryConfig,
+    // * Off unless switched on for a canister being debugged
+    #[serde(default)]
+    pub is_call_journal_enabled: bool,
+    #[serde(default)]
+    pub signup_bonus_vesting_config: SignupBonusVestingConfig,
+    // * Post canisters claim top ups with it, and the treasury checks the claims against it
+    #[serde(default)]
+    pub pot_insurance_config: PotInsuranceConfig,
+    #[serde(default)]
+    pub bet_cancellation_config: BetCancellationConfig,
+    #[serde(default)]
+    pub access_statistics_config: AccessStatisticsConfig,
+    #[serde(default)]
+    pub hot_or_not_payout_mode: HotOrNotPayoutMode,
 }
@@ -0,0 +1,95 @@
+use std::time::SystemTime;
+
+use candid::Deserialize;
+use serde::Serialize;
+
+use crate::constant::{
+    CYCLES_THRESHOLD_TO_ENTER_LOW_CYCLES_MODE, CYCLES_THRESHOLD_TO_INITIATE_RECHARGE,
+    CYCLE_TOP_UP_COOLDOWN,
+};
+
+/// Keeps an eye on the canister's own cycle balance. Asks the user index for a top up when it
+/// runs low, and holds off new bets and posts when it runs critically low, so that the
+/// payouts already owed don't trap halfway through for lack of cycles.
+#[derive(Default, Deserialize, Serialize, Clone, Debug)]
+pub struct CycleMonitor {
+    is_in_low_cycles_mode: bool,
+    last_top_up_requested_at: Option<SystemTime>,
+}
+
+impl CycleMonitor {
+    pub fn is_in_low_cycles_mode(&self) -> bool {
+        self.is_in_low_cycles_mode
+    }
+
+    /// Enters low cycles mode below the critical threshold and only leaves it once the balance
+    /// is back above the recharge threshold, so that it doesn't flap around the critical one.
+    /// Returns whether a top up should be requested, at most once per cooldown.
+    pub fn record_cycle_balance(&mut self, cycle_balance: u128, current_time: &SystemTime) -> bool {
+        if cycle_balance < CYCLES_THRESHOLD_TO_ENTER_LOW_CYCLES_MODE {
+            self.is_in_low_cycles_mode = true;
+        }
+
+        if cycle_balance >= CYCLES_THRESHOLD_TO_INITIATE_RECHARGE {
+            self.is_in_low_cycles_mode = false;
+            return false;
+        }
+
+        let is_top_up_due = self
+            .last_top_up_requested_at
+            .is_none_or(|last_top_up_requested_at| {
+                current_time
+                    .duration_since(last_top_up_requested_at)
+                    .unwrap_or_default()
+                    >= CYCLE_TOP_UP_COOLDOWN
+            });
+
+        if is_top_up_due {
+            self.last_top_up_requested_at = Some(*current_time);
+        }
+
+        is_top_up_due
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_record_cycle_balance() {
+        let mut cycle_monitor = CycleMonitor::default();
+        let current_time = SystemTime::UNIX_EPOCH;
+
+        assert!(!cycle_monitor
+            .record_cycle_balance(CYCLES_THRESHOLD_TO_INITIATE_RECHARGE, &current_time));
+
+        // * low, but not critically
+        assert!(cycle_monitor
+            .record_cycle_balance(CYCLES_THRESHOLD_TO_INITIATE_RECHARGE - 1, &current_time));
+        assert!(!cycle_monitor.is_in_low_cycles_mode());
+
+        // * top ups are asked for once per cooldown
+        assert!(!cycle_monitor.record_cycle_balance(
+            CYCLES_THRESHOLD_TO_ENTER_LOW_CYCLES_MODE - 1,
+            &(current_time + CYCLE_TOP_UP_COOLDOWN / 2)
+        ));
+        assert!(cycle_monitor.is_in_low_cycles_mode());
+        assert!(cycle_monitor.record_cycle_balance(
+            CYCLES_THRESHOLD_TO_ENTER_LOW_CYCLES_MODE - 1,
+            &(current_time + CYCLE_TOP_UP_COOLDOWN)
+        ));
+
+        // * stays in low cycles mode until the balance is back above the recharge threshold
+        cycle_monitor.record_cycle_balance(
+            CYCLES_THRESHOLD_TO_ENTER_LOW_CYCLES_MODE,
+            &(current_time + CYCLE_TOP_UP_COOLDOWN),
+        );
+        assert!(cycle_monitor.is_in_low_cycles_mode());
+        cycle_monitor.record_cycle_balance(
+            CYCLES_THRESHOLD_TO_INITIATE_RECHARGE,
+            &(current_time + CYCLE_TOP_UP_COOLDOWN),
+        );
+        assert!(!cycle_monitor.is_in_low_cycles_mode());
+    }
+}
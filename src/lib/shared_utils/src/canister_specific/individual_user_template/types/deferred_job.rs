@@ -0,0 +1,26 @@
+use candid::{CandidType, Deserialize};
+use serde::Serialize;
+
+use crate::common::types::{app_primitive_type::PostId, utility_token::token_event::TokenEvent};
+
+use super::hot_or_not::PostBetAnalytics;
+
+#[derive(CandidType, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DeferredJobRequest {
+    // * Bet analytics of every post of the profile
+    PostBetAnalyticsRollup,
+    // * Every token transaction still kept in the history, oldest first
+    TokenTransactionsExport,
+}
+
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug, PartialEq, Eq)]
+pub enum DeferredJobResultItem {
+    PostBetAnalytics {
+        post_id: PostId,
+        analytics: PostBetAnalytics,
+    },
+    TokenTransaction {
+        transaction_id: u64,
+        token_event: TokenEvent,
+    },
+}
@@ -0,0 +1,362 @@
+use std::time::{Duration, SystemTime};
+
+use candid::{CandidType, Deserialize, Principal};
+use ic_cdk::api::management_canister::provisional::CanisterId;
+use serde::Serialize;
+
+use crate::common::types::app_primitive_type::PostId;
+
+use super::{
+    error::DisputeSlotOutcomeError,
+    hot_or_not::{
+        BetPayout, HotOrNotPayoutMode, RoomBetPossibleOutcomes, RoomId, SlotDetails, SlotId,
+        DURATION_OF_EACH_SLOT_IN_SECONDS,
+    },
+    post::Post,
+    token::TokenBalance,
+};
+
+pub const SLOT_OUTCOME_DISPUTE_WINDOW: Duration = Duration::from_secs(24 * 60 * 60);
+pub const MAX_SLOT_OUTCOME_DISPUTE_REASON_LENGTH: usize = 500;
+
+/// Raised by a bettor who thinks a slot of a post was settled wrongly. Kept by the post
+/// creator's canister and passed on to the user index for review.
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug, PartialEq, Eq)]
+pub struct SlotOutcomeDispute {
+    pub post_id: PostId,
+    pub slot_id: SlotId,
+    pub disputed_by: Principal,
+    pub reason: String,
+    pub disputed_at: SystemTime,
+}
+
+/// Outcome of re-running tabulation for a slot from the bets recorded in its rooms, next to
+/// what was settled at the time
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug, PartialEq, Eq)]
+pub struct SlotOutcomeRecalculation {
+    pub post_id: PostId,
+    pub slot_id: SlotId,
+    pub number_of_rooms_checked: u64,
+    pub discrepancies: Vec<RoomOutcomeDiscrepancy>,
+}
+
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug, PartialEq, Eq)]
+pub struct RoomOutcomeDiscrepancy {
+    pub room_id: RoomId,
+    pub recorded_outcome: RoomBetPossibleOutcomes,
+    pub recalculated_outcome: RoomBetPossibleOutcomes,
+    pub number_of_bets_with_different_payout: u64,
+}
+
+impl SlotDetails {
+    pub fn is_tabulated(&self) -> bool {
+        self.tabulation_cursor.is_none()
+            && !self.room_details.is_empty()
+            && self
+                .room_details
+                .values()
+                .all(|room_details| room_details.bet_outcome != RoomBetPossibleOutcomes::BetOngoing)
+    }
+}
+
+impl Post {
+    /// Slots are tabulated as soon as they end
+    pub fn get_slot_settled_at(&self, slot_id: SlotId) -> SystemTime {
+        self.get_betting_start_time()
+            + Duration::from_secs(slot_id as u64 * DURATION_OF_EACH_SLOT_IN_SECONDS)
+    }
+
+    /// Only bettors with a bet in one of the slot's rooms can dispute it, for 24 hours after
+    /// the slot is settled
+    pub fn validate_slot_outcome_dispute(
+        &self,
+        disputed_by: &Principal,
+        slot_id: SlotId,
+        current_time: &SystemTime,
+    ) -> Result<(), DisputeSlotOutcomeError> {
+        let slot_details = self
+            .hot_or_not_details
+            .as_ref()
+            .and_then(|hot_or_not_details| hot_or_not_details.slot_history.get(&slot_id))
+            .filter(|slot_details| {
+                slot_details
+                    .room_details
+                    .values()
+                    .any(|room_details| room_details.bets_made.contains_key(disputed_by))
+            })
+            .ok_or(DisputeSlotOutcomeError::DidNotBetInSlot)?;
+
+        if !slot_details.is_tabulated() {
+            return Err(DisputeSlotOutcomeError::SlotNotSettledYet);
+        }
+
+        if *current_time >= self.get_slot_settled_at(slot_id) + SLOT_OUTCOME_DISPUTE_WINDOW {
+            return Err(DisputeSlotOutcomeError::DisputeWindowClosed);
+        }
+
+        Ok(())
+    }
+
+    /// Re-runs tabulation for the slot on a copy of the post, with every room's outcome and
+    /// payouts cleared, and compares the result with what was settled. Nothing is paid out or
+    /// changed on the post itself. Returns None if the slot hasn't been settled yet.
+    pub fn recalculate_slot_outcome(
+        &self,
+        post_canister_id: &CanisterId,
+        slot_id: SlotId,
+        house_rake_percentage: u64,
+        referrer_share_percentage: u64,
+    ) -> Option<SlotOutcomeRecalculation> {
+        let recorded_slot_details = self
+            .hot_or_not_details
+            .as_ref()?
+            .slot_history
+            .get(&slot_id)
+            .filter(|slot_details| slot_details.is_tabulated())?;
+
+        let mut post_to_recalculate = self.clone();
+        let slot_details_to_recalculate = post_to_recalculate
+            .hot_or_not_details
+            .as_mut()?
+            .slot_history
+            .get_mut(&slot_id)?;
+        slot_details_to_recalculate
+            .room_details
+            .values_mut()
+            .for_each(|room_details| {
+                room_details.bet_outcome = RoomBetPossibleOutcomes::BetOngoing;
+                room_details
+                    .bets_made
+                    .values_mut()
+                    .for_each(|bet_details| bet_details.payout = BetPayout::NotCalculatedYet);
+            });
+
+        // * Rooms keep the payout mode they were settled in. Only those settled before there
+        // * were modes go without one, and they were all paid out with the multiplier
+        post_to_recalculate.tabulate_hot_or_not_outcome_for_slot(
+            post_canister_id,
+            &slot_id,
+            &mut TokenBalance::default(),
+            house_rake_percentage,
+            referrer_share_percentage,
+            HotOrNotPayoutMode::Multiplier,
+            &SystemTime::UNIX_EPOCH,
+        );
+        let recalculated_slot_details = &post_to_recalculate
+            .hot_or_not_details
+            .as_ref()?
+            .slot_history[&slot_id];
+
+        let discrepancies = recorded_slot_details
+            .room_details
+            .iter()
+            .filter_map(|(room_id, recorded_room_details)| {
+                let recalculated_room_details = &recalculated_slot_details.room_details[room_id];
+                let number_of_bets_with_different_payout = recorded_room_details
+                    .bets_made
+                    .iter()
+                    .filter(|(bet_maker, recorded_bet_details)| {
+                        recalculated_room_details.bets_made[*bet_maker].payout
+                            != recorded_bet_details.payout
+                    })
+                    .count() as u64;
+
+                if recorded_room_details.bet_outcome == recalculated_room_details.bet_outcome
+                    && number_of_bets_with_different_payout == 0
+                {
+                    return None;
+                }
+
+                Some(RoomOutcomeDiscrepancy {
+                    room_id: *room_id,
+                    recorded_outcome: recorded_room_details.bet_outcome.clone(),
+                    recalculated_outcome: recalculated_room_details.bet_outcome.clone(),
+                    number_of_bets_with_different_payout,
+                })
+            })
+            .collect();
+
+        Some(SlotOutcomeRecalculation {
+            post_id: self.id,
+            slot_id,
+            number_of_rooms_checked: recorded_slot_details.room_details.len() as u64,
+            discrepancies,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use test_utils::setup::test_constants::{
+        get_mock_user_alice_canister_id, get_mock_user_bob_canister_id,
+        get_mock_user_bob_principal_id, get_mock_user_charlie_canister_id,
+        get_mock_user_charlie_principal_id, get_mock_user_dan_principal_id,
+    };
+
+    use crate::canister_specific::individual_user_template::types::{
+        hot_or_not::BetDirection, post::PostDetailsFromFrontend,
+    };
+
+    use super::*;
+
+    fn get_post_with_bets_from_bob_and_charlie(created_at: &SystemTime) -> Post {
+        let mut post = Post::new(
+            0,
+            &PostDetailsFromFrontend {
+                description: "Doggos and puppers".into(),
+                hashtags: vec!["doggo".into(), "pupper".into()],
+                video_uid: "abcd#1234".into(),
+                creator_consent_for_inclusion_in_hot_or_not: true,
+                collaborator_shares: None,
+                title: None,
+                category: None,
+            },
+            created_at,
+        );
+        post.place_hot_or_not_bet(
+            &get_mock_user_bob_principal_id(),
+            &get_mock_user_bob_canister_id(),
+            100,
+            &BetDirection::Hot,
+            created_at,
+        )
+        .unwrap();
+        post.place_hot_or_not_bet(
+            &get_mock_user_charlie_principal_id(),
+            &get_mock_user_charlie_canister_id(),
+            50,
+            &BetDirection::Not,
+            created_at,
+        )
+        .unwrap();
+
+        post
+    }
+
+    #[test]
+    fn test_validate_slot_outcome_dispute() {
+        let created_at = SystemTime::now();
+        let mut post = get_post_with_bets_from_bob_and_charlie(&created_at);
+        let settled_at = post.get_slot_settled_at(1);
+        assert_eq!(settled_at, created_at + Duration::from_secs(60 * 60));
+
+        assert_eq!(
+            post.validate_slot_outcome_dispute(&get_mock_user_dan_principal_id(), 1, &settled_at),
+            Err(DisputeSlotOutcomeError::DidNotBetInSlot)
+        );
+        assert_eq!(
+            post.validate_slot_outcome_dispute(&get_mock_user_bob_principal_id(), 2, &settled_at),
+            Err(DisputeSlotOutcomeError::DidNotBetInSlot)
+        );
+        assert_eq!(
+            post.validate_slot_outcome_dispute(&get_mock_user_bob_principal_id(), 1, &created_at),
+            Err(DisputeSlotOutcomeError::SlotNotSettledYet)
+        );
+
+        post.tabulate_hot_or_not_outcome_for_slot(
+            &get_mock_user_alice_canister_id(),
+            &1,
+            &mut TokenBalance::default(),
+            0,
+            0,
+            HotOrNotPayoutMode::Multiplier,
+            &settled_at,
+        );
+
+        assert_eq!(
+            post.validate_slot_outcome_dispute(&get_mock_user_bob_principal_id(), 1, &settled_at),
+            Ok(())
+        );
+        assert_eq!(
+            post.validate_slot_outcome_dispute(
+                &get_mock_user_charlie_principal_id(),
+                1,
+                &(settled_at + SLOT_OUTCOME_DISPUTE_WINDOW - Duration::from_secs(1))
+            ),
+            Ok(())
+        );
+        assert_eq!(
+            post.validate_slot_outcome_dispute(
+                &get_mock_user_charlie_principal_id(),
+                1,
+                &(settled_at + SLOT_OUTCOME_DISPUTE_WINDOW)
+            ),
+            Err(DisputeSlotOutcomeError::DisputeWindowClosed)
+        );
+    }
+
+    #[test]
+    fn test_recalculate_slot_outcome() {
+        let created_at = SystemTime::now();
+        let mut post = get_post_with_bets_from_bob_and_charlie(&created_at);
+        let mut token_balance = TokenBalance::default();
+        let settled_at = post.get_slot_settled_at(1);
+
+        assert_eq!(
+            post.recalculate_slot_outcome(&get_mock_user_alice_canister_id(), 1, 0, 0),
+            None
+        );
+
+        post.tabulate_hot_or_not_outcome_for_slot(
+            &get_mock_user_alice_canister_id(),
+            &1,
+            &mut token_balance,
+            0,
+            0,
+            HotOrNotPayoutMode::Multiplier,
+            &settled_at,
+        );
+        let tabulated_post = post.clone();
+
+        assert_eq!(
+            post.recalculate_slot_outcome(&get_mock_user_alice_canister_id(), 1, 0, 0),
+            Some(SlotOutcomeRecalculation {
+                post_id: 0,
+                slot_id: 1,
+                number_of_rooms_checked: 1,
+                discrepancies: vec![],
+            })
+        );
+
+        // * a settlement that doesn't match the recorded bets shows up
+        let room_details = post
+            .hot_or_not_details
+            .as_mut()
+            .unwrap()
+            .slot_history
+            .get_mut(&1)
+            .unwrap()
+            .room_details
+            .get_mut(&1)
+            .unwrap();
+        room_details.bet_outcome = RoomBetPossibleOutcomes::NotWon;
+        room_details
+            .bets_made
+            .get_mut(&get_mock_user_charlie_principal_id())
+            .unwrap()
+            .payout = BetPayout::Calculated(90);
+
+        assert_eq!(
+            post.recalculate_slot_outcome(&get_mock_user_alice_canister_id(), 1, 0, 0),
+            Some(SlotOutcomeRecalculation {
+                post_id: 0,
+                slot_id: 1,
+                number_of_rooms_checked: 1,
+                discrepancies: vec![RoomOutcomeDiscrepancy {
+                    room_id: 1,
+                    recorded_outcome: RoomBetPossibleOutcomes::NotWon,
+                    recalculated_outcome: RoomBetPossibleOutcomes::Draw,
+                    number_of_bets_with_different_payout: 1,
+                }],
+            })
+        );
+
+        // * recalculating never pays anyone or touches the post
+        assert_eq!(token_balance.utility_token_transaction_history.len(), 1);
+        assert_eq!(
+            tabulated_post.hot_or_not_details.unwrap().slot_history[&1].room_details[&1]
+                .bet_outcome,
+            RoomBetPossibleOutcomes::Draw
+        );
+    }
+}
@@ -1,5 +1,9 @@
+use std::time::SystemTime;
+
 use candid::{CandidType, Deserialize};
 
+use crate::types::canister_specific::user_index::error_types::TournamentError;
+
 #[derive(CandidType, Deserialize, PartialEq, Eq, Debug)]
 pub enum GetPostsOfUserProfileError {
     InvalidBoundsPassed,
@@ -29,6 +33,37 @@ pub enum BetOnCurrentlyViewingPostError {
     UserNotLoggedIn,
     UserPrincipalNotSet,
     PostCreatorCanisterCallFailed,
+    SessionDelegateDailyBetLimitExceeded,
+    BetAmountBelowPostMinimum,
+    BettingPausedForReview,
+    StaleCanisterBinding,
+    BetNotFound,
+    BetCancellationWindowClosed,
+    BettingNotStarted,
+}
+
+impl BetOnCurrentlyViewingPostError {
+    /// Used as the `reason` label of the bet rejections counter
+    pub fn as_reason_label(&self) -> &'static str {
+        match self {
+            Self::BettingClosed => "betting_closed",
+            Self::InsufficientBalance => "insufficient_balance",
+            Self::Unauthorized => "unauthorized",
+            Self::UserAlreadyParticipatedInThisPost => "user_already_participated_in_this_post",
+            Self::UserNotLoggedIn => "user_not_logged_in",
+            Self::UserPrincipalNotSet => "user_principal_not_set",
+            Self::PostCreatorCanisterCallFailed => "post_creator_canister_call_failed",
+            Self::SessionDelegateDailyBetLimitExceeded => {
+                "session_delegate_daily_bet_limit_exceeded"
+            }
+            Self::BetAmountBelowPostMinimum => "bet_amount_below_post_minimum",
+            Self::BettingPausedForReview => "betting_paused_for_review",
+            Self::StaleCanisterBinding => "stale_canister_binding",
+            Self::BetNotFound => "bet_not_found",
+            Self::BetCancellationWindowClosed => "bet_cancellation_window_closed",
+            Self::BettingNotStarted => "betting_not_started",
+        }
+    }
 }
 
 #[derive(CandidType, Deserialize, PartialEq, Eq, Debug)]
@@ -38,4 +73,565 @@ pub enum FollowAnotherUserProfileError {
     UsersICanFollowListIsFull,
     UserITriedToFollowCrossCanisterCallFailed,
     UserITriedToFollowHasTheirFollowersListFull,
+    StaleCanisterBinding,
+    CannotFollowOwnProfile,
+}
+
+#[derive(CandidType, Deserialize, PartialEq, Eq, Debug)]
+pub enum BackfillAggregateStatsError {
+    Unauthorized,
+    BackfillAlreadyInProgress,
+}
+
+#[derive(CandidType, Deserialize, PartialEq, Eq, Debug)]
+pub enum EnrollPostInTournamentError {
+    Unauthorized,
+    PostNotFound,
+    PostNotEligible,
+    PostAlreadyEnrolled,
+    UserIndexCrossCanisterCallFailed,
+    TournamentError(TournamentError),
+}
+
+#[derive(CandidType, Deserialize, PartialEq, Eq, Debug)]
+pub enum ExportTokenTransactionsError {
+    Unauthorized,
+    InvalidBoundsPassed,
+    ExceededMaxNumberOfItemsAllowedInOneRequest,
+}
+
+#[derive(CandidType, Deserialize, PartialEq, Eq, Debug)]
+pub enum SessionDelegateError {
+    Unauthorized,
+    DelegateCannotBeProfileOwner,
+    ExpiryIsInThePast,
+    MaxSessionDelegatesReached,
+    DelegateNotFound,
+}
+
+#[derive(CandidType, Deserialize, PartialEq, Eq, Debug)]
+pub enum EventCallbackError {
+    Unauthorized,
+    // * The configuration canister has no approval on record for the calling canister
+    NotApproved,
+    EventNotApproved,
+    NoEventsRequested,
+    InvalidMethodName,
+    MaxRegistrantsReached,
+    ConfigurationCanisterUnreachable,
+    RegistrationNotFound,
+}
+
+#[derive(CandidType, Deserialize, PartialEq, Eq, Debug)]
+pub enum ReplicationError {
+    Unauthorized,
+    // * The epoch passed is behind this canister's, the sender has been superseded
+    StaleEpoch { current_epoch: u64 },
+    NotAPrimary,
+    NotAStandby,
+    OutOfOrder { last_applied_sequence_number: u64 },
+}
+
+#[derive(CandidType, Deserialize, PartialEq, Eq, Debug)]
+pub enum RevokeHotOrNotConsentError {
+    Unauthorized,
+    PostNotFound,
+    PostNotPartOfHotOrNot,
+}
+
+#[derive(CandidType, Deserialize, PartialEq, Eq, Debug)]
+pub enum UpdatePostMinimumBetAmountError {
+    Unauthorized,
+    PostNotFound,
+    PostNotPartOfHotOrNot,
+    MinimumBetAmountAbovePlatformLimit,
+}
+
+#[derive(CandidType, Deserialize, PartialEq, Eq, Debug)]
+pub enum BoostPostError {
+    Unauthorized,
+    PostNotFound,
+    PostAlreadyBoosted,
+    BoostAmountBelowMinimum,
+    BoostAmountAboveMaximum,
+    InsufficientBalance,
+}
+
+#[derive(CandidType, Deserialize, PartialEq, Eq, Debug)]
+pub enum ReportPostError {
+    UserNotLoggedIn,
+    InvalidReason,
+    PostNotFound,
+    CannotReportOwnPost,
+    AlreadyReported,
+}
+
+#[derive(CandidType, Deserialize, PartialEq, Eq, Debug)]
+pub enum ClearPostReportsError {
+    Unauthorized,
+    PostNotFound,
+}
+
+#[derive(CandidType, Deserialize, PartialEq, Eq, Debug)]
+pub enum GetCertifiedPostDetailsError {
+    PostNotFound,
+    // * Certificates are only handed to queries that aren't run in replicated mode
+    CertificateUnavailable,
+}
+
+#[derive(CandidType, Deserialize, PartialEq, Eq, Debug)]
+pub enum FollowGraphMigrationError {
+    Unauthorized,
+    ExceededMaxNumberOfItemsAllowedInOneRequest,
+}
+
+#[derive(CandidType, Deserialize, PartialEq, Eq, Debug)]
+pub enum GetCreatorCommissionReportError {
+    Unauthorized,
+    InvalidDateRange,
+    InvalidBoundsPassed,
+    ReachedEndOfItemsList,
+    ExceededMaxNumberOfItemsAllowedInOneRequest,
+}
+
+#[derive(CandidType, Deserialize, PartialEq, Eq, Debug)]
+pub enum RequestTokensFromFaucetError {
+    FaucetDisabled,
+    Unauthorized,
+    InvalidAmount,
+    RequestedTooSoon { next_request_allowed_at: SystemTime },
+}
+
+#[derive(CandidType, Deserialize, PartialEq, Eq, Debug)]
+pub enum UpdateBettingActivityPrivacyError {
+    Unauthorized,
+}
+
+#[derive(CandidType, Deserialize, PartialEq, Eq, Debug)]
+pub enum DisputeSlotOutcomeError {
+    UserNotLoggedIn,
+    InvalidReason,
+    PostNotFound,
+    DidNotBetInSlot,
+    SlotNotSettledYet,
+    DisputeWindowClosed,
+    AlreadyDisputed,
+}
+
+#[derive(CandidType, Deserialize, PartialEq, Eq, Debug)]
+pub enum RecalculateSlotOutcomeError {
+    Unauthorized,
+    PostNotFound,
+    SlotNotSettledYet,
+}
+
+#[derive(CandidType, Deserialize, PartialEq, Eq, Debug)]
+pub enum GetBetTraceError {
+    Unauthorized,
+    PostNotFound,
+}
+
+#[derive(CandidType, Deserialize, PartialEq, Eq, Debug)]
+pub enum GetDeadLetteredCallsError {
+    Unauthorized,
+}
+
+#[derive(CandidType, Deserialize, PartialEq, Eq, Debug)]
+pub enum GetFollowingFeedError {
+    InvalidBoundsPassed,
+    ReachedEndOfItemsList,
+    ExceededMaxNumberOfItemsAllowedInOneRequest,
+}
+
+#[derive(CandidType, Deserialize, PartialEq, Eq, Debug)]
+pub enum CreateReferralCodeError {
+    Unauthorized,
+    InvalidName,
+    NameAlreadyUsed,
+    TooManyReferralCodes,
+    UserIndexCrossCanisterCallFailed,
+    UserIndexRejectedRegistration,
+}
+
+#[derive(CandidType, Deserialize, PartialEq, Eq, Debug)]
+pub enum GetReferralCodesError {
+    Unauthorized,
+}
+
+#[derive(CandidType, Deserialize, PartialEq, Eq, Debug)]
+pub enum BetOnMultiplePostsError {
+    EmptyBatch,
+    TooManyBetsInBatch,
+    DuplicatePostInBatch,
+    BetMakerRejected(BetOnCurrentlyViewingPostError),
+}
+
+#[derive(CandidType, Deserialize, PartialEq, Eq, Debug)]
+pub enum GetCallJournalEntriesError {
+    Unauthorized,
+}
+
+#[derive(CandidType, Deserialize, PartialEq, Eq, Debug)]
+pub enum GetAccessStatisticsError {
+    Unauthorized,
+}
+
+#[derive(CandidType, Deserialize, PartialEq, Eq, Debug)]
+pub enum GetAppliedMigrationsError {
+    Unauthorized,
+}
+
+#[derive(CandidType, Deserialize, PartialEq, Eq, Debug)]
+pub enum VersusContestError {
+    Unauthorized,
+    PostNotFound,
+    PostNotEligible,
+    InvalidOpponentPost,
+    InvalidWindow,
+    ContestNotFound,
+    ContestNotAwaitingConsent,
+    ContestNotOpenForBets,
+    ContestStillOpen,
+    PostNotInContest,
+    InvalidBetAmount,
+    InsufficientBalance,
+    AlreadyBetOnContest,
+    OtherCanisterCallFailed,
+}
+
+#[derive(CandidType, Deserialize, PartialEq, Eq, Debug)]
+pub enum CommentError {
+    UserNotLoggedIn,
+    Unauthorized,
+    PostNotFound,
+    CommentNotFound,
+    CommentEmpty,
+    CommentTooLong,
+    // * The post owner or one of their moderators restricted who can comment on the post
+    NotOnPostAllowlist,
+    // * Only the profile owner comments straight on their own posts, everyone else comments
+    // * through their own canister
+    MustCommentThroughOwnCanister,
+    PostCreatorCanisterCallFailed,
+}
+
+#[derive(CandidType, Deserialize, PartialEq, Eq, Debug)]
+pub enum GetBetsForPostError {
+    Unauthorized,
+    PostNotFound,
+    PostNotPartOfHotOrNot,
+    // * Some bet makers hide their betting activity and there is no salt to pseudonymize them
+    // * with yet
+    PseudonymSaltNotGenerated,
+}
+
+#[derive(CandidType, Deserialize, PartialEq, Eq, Debug)]
+pub enum PotInsuranceClaimError {
+    // * The canister claimed from isn't the treasury
+    NotTreasury,
+    // * Claims can only be made by platform user canisters, for rooms on their own posts
+    Unauthorized,
+    PotInsuranceDisabled,
+    PotNotBelowFloor,
+    TopUpAboveFloor,
+    AlreadyPaid,
+    DailySpendCapReached,
+    InsufficientTreasuryBalance,
+    // * The room was tabulated too long ago to tell whether it was already paid
+    ClaimExpired,
+}
+
+#[derive(CandidType, Deserialize, PartialEq, Eq, Debug)]
+pub enum GetPostsOfFollowedCreatorsError {
+    Unauthorized,
+    InvalidBoundsPassed,
+    ReachedEndOfItemsList,
+    ExceededMaxNumberOfItemsAllowedInOneRequest,
+}
+
+#[derive(CandidType, Deserialize, PartialEq, Eq, Debug)]
+pub enum ModerationError {
+    Unauthorized,
+    ModeratorCannotBeProfileOwner,
+    NoPermissionsGiven,
+    MaxModeratorsReached,
+    ModeratorNotFound,
+    PostNotFound,
+    PostAllowlistFull,
+}
+
+#[derive(CandidType, Deserialize, PartialEq, Eq, Debug)]
+pub enum RetabulateSlotOutcomeError {
+    Unauthorized,
+    PostNotFound,
+    SlotNotFound,
+    SlotStillOpen,
+    SlotAlreadyTabulated,
+}
+
+#[derive(CandidType, Deserialize, PartialEq, Eq, Debug)]
+pub enum GetReferralEarningsError {
+    Unauthorized,
+}
+
+// * Individual user canister enums are numbered 10 to 49
+crate::error_codes! {
+    GetPostsOfUserProfileError {
+        InvalidBoundsPassed => (1001, InvalidInput, false),
+        ReachedEndOfItemsList => (1002, NotFound, false),
+        ExceededMaxNumberOfItemsAllowedInOneRequest => (1003, LimitExceeded, false),
+    }
+    GetFollowerOrFollowingError {
+        InvalidBoundsPassed => (1101, InvalidInput, false),
+        ReachedEndOfItemsList => (1102, NotFound, false),
+        ExceededMaxNumberOfItemsAllowedInOneRequest => (1103, LimitExceeded, false),
+    }
+    GetFollowerOrFollowingPageError {
+        Unauthenticated => (1201, Unauthenticated, false),
+        Unauthorized => (1202, Unauthorized, false),
+    }
+    BetOnCurrentlyViewingPostError {
+        BettingClosed => (1301, Conflict, false),
+        InsufficientBalance => (1302, InsufficientFunds, false),
+        Unauthorized => (1303, Unauthorized, false),
+        UserAlreadyParticipatedInThisPost => (1304, Conflict, false),
+        UserNotLoggedIn => (1305, Unauthenticated, false),
+        UserPrincipalNotSet => (1306, Conflict, false),
+        PostCreatorCanisterCallFailed => (1307, Unavailable, true),
+        SessionDelegateDailyBetLimitExceeded => (1308, LimitExceeded, false),
+        BetAmountBelowPostMinimum => (1309, InvalidInput, false),
+        BettingPausedForReview => (1310, Conflict, false),
+        // * Once the client has looked up the user's canister again
+        StaleCanisterBinding => (1311, Conflict, true),
+        BetNotFound => (1312, NotFound, false),
+        BetCancellationWindowClosed => (1313, Conflict, false),
+        BettingNotStarted => (1314, Conflict, true),
+    }
+    FollowAnotherUserProfileError {
+        Unauthenticated => (1401, Unauthenticated, false),
+        Unauthorized => (1402, Unauthorized, false),
+        UsersICanFollowListIsFull => (1403, LimitExceeded, false),
+        UserITriedToFollowCrossCanisterCallFailed => (1404, Unavailable, true),
+        UserITriedToFollowHasTheirFollowersListFull => (1405, LimitExceeded, false),
+        StaleCanisterBinding => (1406, Conflict, true),
+        CannotFollowOwnProfile => (1407, InvalidInput, false),
+    }
+    BackfillAggregateStatsError {
+        Unauthorized => (1501, Unauthorized, false),
+        BackfillAlreadyInProgress => (1502, Conflict, true),
+    }
+    EnrollPostInTournamentError {
+        Unauthorized => (1601, Unauthorized, false),
+        PostNotFound => (1602, NotFound, false),
+        PostNotEligible => (1603, Conflict, false),
+        PostAlreadyEnrolled => (1604, Conflict, false),
+        UserIndexCrossCanisterCallFailed => (1605, Unavailable, true),
+        // * The wrapped error has a code of its own
+        TournamentError => (1606, Conflict, false),
+    }
+    ExportTokenTransactionsError {
+        Unauthorized => (1701, Unauthorized, false),
+        InvalidBoundsPassed => (1702, InvalidInput, false),
+        ExceededMaxNumberOfItemsAllowedInOneRequest => (1703, LimitExceeded, false),
+    }
+    SessionDelegateError {
+        Unauthorized => (1801, Unauthorized, false),
+        DelegateCannotBeProfileOwner => (1802, InvalidInput, false),
+        ExpiryIsInThePast => (1803, InvalidInput, false),
+        MaxSessionDelegatesReached => (1804, LimitExceeded, false),
+        DelegateNotFound => (1805, NotFound, false),
+    }
+    EventCallbackError {
+        Unauthorized => (1901, Unauthorized, false),
+        NotApproved => (1902, Unauthorized, false),
+        EventNotApproved => (1903, Unauthorized, false),
+        NoEventsRequested => (1904, InvalidInput, false),
+        InvalidMethodName => (1905, InvalidInput, false),
+        MaxRegistrantsReached => (1906, LimitExceeded, false),
+        ConfigurationCanisterUnreachable => (1907, Unavailable, true),
+        RegistrationNotFound => (1908, NotFound, false),
+    }
+    ReplicationError {
+        Unauthorized => (2001, Unauthorized, false),
+        StaleEpoch => (2002, Conflict, false),
+        NotAPrimary => (2003, Conflict, false),
+        NotAStandby => (2004, Conflict, false),
+        OutOfOrder => (2005, Conflict, true),
+    }
+    RevokeHotOrNotConsentError {
+        Unauthorized => (2101, Unauthorized, false),
+        PostNotFound => (2102, NotFound, false),
+        PostNotPartOfHotOrNot => (2103, Conflict, false),
+    }
+    UpdatePostMinimumBetAmountError {
+        Unauthorized => (2201, Unauthorized, false),
+        PostNotFound => (2202, NotFound, false),
+        PostNotPartOfHotOrNot => (2203, Conflict, false),
+        MinimumBetAmountAbovePlatformLimit => (2204, InvalidInput, false),
+    }
+    BoostPostError {
+        Unauthorized => (2301, Unauthorized, false),
+        PostNotFound => (2302, NotFound, false),
+        PostAlreadyBoosted => (2303, Conflict, false),
+        BoostAmountBelowMinimum => (2304, InvalidInput, false),
+        BoostAmountAboveMaximum => (2305, InvalidInput, false),
+        InsufficientBalance => (2306, InsufficientFunds, false),
+    }
+    ReportPostError {
+        UserNotLoggedIn => (2401, Unauthenticated, false),
+        InvalidReason => (2402, InvalidInput, false),
+        PostNotFound => (2403, NotFound, false),
+        CannotReportOwnPost => (2404, InvalidInput, false),
+        AlreadyReported => (2405, Conflict, false),
+    }
+    ClearPostReportsError {
+        Unauthorized => (2501, Unauthorized, false),
+        PostNotFound => (2502, NotFound, false),
+    }
+    GetCertifiedPostDetailsError {
+        PostNotFound => (2601, NotFound, false),
+        CertificateUnavailable => (2602, Unavailable, true),
+    }
+    FollowGraphMigrationError {
+        Unauthorized => (2701, Unauthorized, false),
+        ExceededMaxNumberOfItemsAllowedInOneRequest => (2702, LimitExceeded, false),
+    }
+    GetCreatorCommissionReportError {
+        Unauthorized => (2801, Unauthorized, false),
+        InvalidDateRange => (2802, InvalidInput, false),
+        InvalidBoundsPassed => (2803, InvalidInput, false),
+        ReachedEndOfItemsList => (2804, NotFound, false),
+        ExceededMaxNumberOfItemsAllowedInOneRequest => (2805, LimitExceeded, false),
+    }
+    RequestTokensFromFaucetError {
+        FaucetDisabled => (2901, Conflict, false),
+        Unauthorized => (2902, Unauthorized, false),
+        InvalidAmount => (2903, InvalidInput, false),
+        RequestedTooSoon => (2904, LimitExceeded, true),
+    }
+    UpdateBettingActivityPrivacyError {
+        Unauthorized => (3001, Unauthorized, false),
+    }
+    DisputeSlotOutcomeError {
+        UserNotLoggedIn => (3101, Unauthenticated, false),
+        InvalidReason => (3102, InvalidInput, false),
+        PostNotFound => (3103, NotFound, false),
+        DidNotBetInSlot => (3104, Unauthorized, false),
+        SlotNotSettledYet => (3105, Conflict, true),
+        DisputeWindowClosed => (3106, Conflict, false),
+        AlreadyDisputed => (3107, Conflict, false),
+    }
+    RecalculateSlotOutcomeError {
+        Unauthorized => (3201, Unauthorized, false),
+        PostNotFound => (3202, NotFound, false),
+        SlotNotSettledYet => (3203, Conflict, true),
+    }
+    GetBetTraceError {
+        Unauthorized => (3301, Unauthorized, false),
+        PostNotFound => (3302, NotFound, false),
+    }
+    GetDeadLetteredCallsError {
+        Unauthorized => (3401, Unauthorized, false),
+    }
+    GetFollowingFeedError {
+        InvalidBoundsPassed => (3501, InvalidInput, false),
+        ReachedEndOfItemsList => (3502, NotFound, false),
+        ExceededMaxNumberOfItemsAllowedInOneRequest => (3503, LimitExceeded, false),
+    }
+    CreateReferralCodeError {
+        Unauthorized => (3601, Unauthorized, false),
+        InvalidName => (3602, InvalidInput, false),
+        NameAlreadyUsed => (3603, Conflict, false),
+        TooManyReferralCodes => (3604, LimitExceeded, false),
+        UserIndexCrossCanisterCallFailed => (3605, Unavailable, true),
+        UserIndexRejectedRegistration => (3606, Conflict, false),
+    }
+    GetReferralCodesError {
+        Unauthorized => (3701, Unauthorized, false),
+    }
+    BetOnMultiplePostsError {
+        EmptyBatch => (3801, InvalidInput, false),
+        TooManyBetsInBatch => (3802, LimitExceeded, false),
+        DuplicatePostInBatch => (3803, InvalidInput, false),
+        // * The wrapped error has a code of its own
+        BetMakerRejected => (3804, Conflict, false),
+    }
+    GetCallJournalEntriesError {
+        Unauthorized => (3901, Unauthorized, false),
+    }
+    GetAccessStatisticsError {
+        Unauthorized => (4001, Unauthorized, false),
+    }
+    GetAppliedMigrationsError {
+        Unauthorized => (4101, Unauthorized, false),
+    }
+    VersusContestError {
+        Unauthorized => (4201, Unauthorized, false),
+        PostNotFound => (4202, NotFound, false),
+        PostNotEligible => (4203, Conflict, false),
+        InvalidOpponentPost => (4204, InvalidInput, false),
+        InvalidWindow => (4205, InvalidInput, false),
+        ContestNotFound => (4206, NotFound, false),
+        ContestNotAwaitingConsent => (4207, Conflict, false),
+        ContestNotOpenForBets => (4208, Conflict, false),
+        ContestStillOpen => (4209, Conflict, true),
+        PostNotInContest => (4210, InvalidInput, false),
+        InvalidBetAmount => (4211, InvalidInput, false),
+        InsufficientBalance => (4212, InsufficientFunds, false),
+        AlreadyBetOnContest => (4213, Conflict, false),
+        OtherCanisterCallFailed => (4214, Unavailable, true),
+    }
+    CommentError {
+        UserNotLoggedIn => (4301, Unauthenticated, false),
+        Unauthorized => (4302, Unauthorized, false),
+        PostNotFound => (4303, NotFound, false),
+        CommentNotFound => (4304, NotFound, false),
+        CommentEmpty => (4305, InvalidInput, false),
+        CommentTooLong => (4306, InvalidInput, false),
+        NotOnPostAllowlist => (4307, Unauthorized, false),
+        MustCommentThroughOwnCanister => (4308, Unauthorized, false),
+        PostCreatorCanisterCallFailed => (4309, Unavailable, true),
+    }
+    GetBetsForPostError {
+        Unauthorized => (4401, Unauthorized, false),
+        PostNotFound => (4402, NotFound, false),
+        PostNotPartOfHotOrNot => (4403, Conflict, false),
+        PseudonymSaltNotGenerated => (4404, Unavailable, true),
+    }
+    PotInsuranceClaimError {
+        NotTreasury => (4501, Unauthorized, false),
+        Unauthorized => (4502, Unauthorized, false),
+        PotInsuranceDisabled => (4503, Conflict, false),
+        PotNotBelowFloor => (4504, Conflict, false),
+        TopUpAboveFloor => (4505, InvalidInput, false),
+        AlreadyPaid => (4506, Conflict, false),
+        DailySpendCapReached => (4507, LimitExceeded, true),
+        InsufficientTreasuryBalance => (4508, InsufficientFunds, true),
+        ClaimExpired => (4509, Conflict, false),
+    }
+    GetPostsOfFollowedCreatorsError {
+        Unauthorized => (10001, Unauthorized, false),
+        InvalidBoundsPassed => (10002, InvalidInput, false),
+        ReachedEndOfItemsList => (10003, NotFound, false),
+        ExceededMaxNumberOfItemsAllowedInOneRequest => (10004, LimitExceeded, false),
+    }
+    ModerationError {
+        Unauthorized => (10101, Unauthorized, false),
+        ModeratorCannotBeProfileOwner => (10102, InvalidInput, false),
+        NoPermissionsGiven => (10103, InvalidInput, false),
+        MaxModeratorsReached => (10104, LimitExceeded, false),
+        ModeratorNotFound => (10105, NotFound, false),
+        PostNotFound => (10106, NotFound, false),
+        PostAllowlistFull => (10107, LimitExceeded, false),
+    }
+    RetabulateSlotOutcomeError {
+        Unauthorized => (10201, Unauthorized, false),
+        PostNotFound => (10202, NotFound, false),
+        SlotNotFound => (10203, NotFound, false),
+        SlotStillOpen => (10204, Conflict, true),
+        SlotAlreadyTabulated => (10205, Conflict, false),
+    }
+    GetReferralEarningsError {
+        Unauthorized => (4901, Unauthorized, false),
+    }
 }
@@ -0,0 +1,339 @@
+use std::{collections::BTreeMap, time::SystemTime};
+
+use candid::{CandidType, Deserialize, Principal};
+use serde::Serialize;
+
+use crate::{
+    canister_specific::configuration::types::event_callback::{
+        EventCallbackApproval, EventCallbackKind,
+    },
+    common::types::app_primitive_type::PostId,
+};
+
+use super::{
+    error::EventCallbackError,
+    feature_usage::{get_day_number, DayNumber},
+};
+
+pub const MAX_EVENT_CALLBACK_REGISTRANTS_PER_PROFILE: usize = 10;
+pub const MAX_EVENT_CALLBACK_METHOD_NAME_LENGTH: usize = 64;
+
+#[derive(CandidType, Deserialize, Clone)]
+pub struct RegisterEventCallbackArg {
+    // * Method on the registrant that gets notified with an `EventCallbackNotification`
+    pub method_name: String,
+    pub events: Vec<EventCallbackKind>,
+}
+
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug, PartialEq, Eq)]
+pub enum EventCallbackPayload {
+    PostCreated {
+        post_id: PostId,
+    },
+    BetWon {
+        post_canister_id: Principal,
+        post_id: PostId,
+        winnings_amount: u64,
+    },
+}
+
+impl EventCallbackPayload {
+    pub fn get_kind(&self) -> EventCallbackKind {
+        match self {
+            Self::PostCreated { .. } => EventCallbackKind::PostCreated,
+            Self::BetWon { .. } => EventCallbackKind::BetWon,
+        }
+    }
+}
+
+/// What a registrant is called back with
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug, PartialEq, Eq)]
+pub struct EventCallbackNotification {
+    pub user_canister_id: Principal,
+    pub payload: EventCallbackPayload,
+    pub occurred_at: SystemTime,
+}
+
+/// A third party canister called back whenever one of the events it registered for happens
+/// on this canister. The scope and quota are copied from its approval in the configuration
+/// canister when it registers.
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug, PartialEq, Eq)]
+pub struct EventCallbackRegistration {
+    pub method_name: String,
+    pub events: Vec<EventCallbackKind>,
+    pub max_callbacks_per_day: u64,
+    pub registered_at: SystemTime,
+    pub day_of_last_callback: DayNumber,
+    pub callbacks_sent_on_day_of_last_callback: u64,
+}
+
+impl EventCallbackRegistration {
+    /// Counts a callback against today's quota, unless there is nothing left of it
+    fn try_use_quota(&mut self, current_time: &SystemTime) -> bool {
+        let today = get_day_number(current_time);
+
+        if today != self.day_of_last_callback {
+            self.day_of_last_callback = today;
+            self.callbacks_sent_on_day_of_last_callback = 0;
+        }
+
+        if self.callbacks_sent_on_day_of_last_callback >= self.max_callbacks_per_day {
+            return false;
+        }
+
+        self.callbacks_sent_on_day_of_last_callback += 1;
+        true
+    }
+}
+
+#[derive(Default, Deserialize, Serialize)]
+pub struct EventCallbacks {
+    registrations: BTreeMap<Principal, EventCallbackRegistration>,
+}
+
+impl EventCallbacks {
+    /// Registering again replaces the method and events, keeping today's usage
+    pub fn register(
+        &mut self,
+        registrant: Principal,
+        arg: RegisterEventCallbackArg,
+        approval: &EventCallbackApproval,
+        current_time: &SystemTime,
+    ) -> Result<(), EventCallbackError> {
+        if arg.method_name.is_empty()
+            || arg.method_name.len() > MAX_EVENT_CALLBACK_METHOD_NAME_LENGTH
+        {
+            return Err(EventCallbackError::InvalidMethodName);
+        }
+
+        if arg.events.is_empty() {
+            return Err(EventCallbackError::NoEventsRequested);
+        }
+
+        if arg
+            .events
+            .iter()
+            .any(|event| !approval.allowed_events.contains(event))
+        {
+            return Err(EventCallbackError::EventNotApproved);
+        }
+
+        if !self.registrations.contains_key(&registrant)
+            && self.registrations.len() >= MAX_EVENT_CALLBACK_REGISTRANTS_PER_PROFILE
+        {
+            return Err(EventCallbackError::MaxRegistrantsReached);
+        }
+
+        let mut events = arg.events;
+        events.sort();
+        events.dedup();
+
+        let previous_registration = self.registrations.remove(&registrant);
+        self.registrations.insert(
+            registrant,
+            EventCallbackRegistration {
+                method_name: arg.method_name,
+                events,
+                max_callbacks_per_day: approval.max_callbacks_per_day,
+                registered_at: *current_time,
+                day_of_last_callback: previous_registration
+                    .as_ref()
+                    .map_or(0, |registration| registration.day_of_last_callback),
+                callbacks_sent_on_day_of_last_callback: previous_registration
+                    .as_ref()
+                    .map_or(0, |registration| {
+                        registration.callbacks_sent_on_day_of_last_callback
+                    }),
+            },
+        );
+
+        Ok(())
+    }
+
+    pub fn revoke(&mut self, registrant: &Principal) -> bool {
+        self.registrations.remove(registrant).is_some()
+    }
+
+    pub fn get_registrations(&self) -> Vec<(Principal, EventCallbackRegistration)> {
+        self.registrations
+            .iter()
+            .map(|(registrant, registration)| (*registrant, registration.clone()))
+            .collect()
+    }
+
+    /// Registrants to call back about the event and the method to call on each, counting the
+    /// callback against their quota. Registrants out of quota for the day are skipped.
+    pub fn take_callbacks_to_send(
+        &mut self,
+        event: EventCallbackKind,
+        current_time: &SystemTime,
+    ) -> Vec<(Principal, String)> {
+        self.registrations
+            .iter_mut()
+            .filter(|(_, registration)| registration.events.contains(&event))
+            .filter_map(|(registrant, registration)| {
+                registration
+                    .try_use_quota(current_time)
+                    .then(|| (*registrant, registration.method_name.clone()))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use test_utils::setup::test_constants::{
+        get_mock_user_alice_canister_id, get_mock_user_bob_canister_id,
+    };
+
+    use super::*;
+
+    const DAY: Duration = Duration::from_secs(24 * 60 * 60);
+
+    fn get_arg(events: Vec<EventCallbackKind>) -> RegisterEventCallbackArg {
+        RegisterEventCallbackArg {
+            method_name: "on_hot_or_not_event".to_string(),
+            events,
+        }
+    }
+
+    #[test]
+    fn test_registrations_are_held_to_their_approval() {
+        let mut event_callbacks = EventCallbacks::default();
+        let approval = EventCallbackApproval {
+            allowed_events: vec![EventCallbackKind::PostCreated],
+            max_callbacks_per_day: 2,
+        };
+        let current_time = SystemTime::UNIX_EPOCH + DAY * 100;
+        let registrant = get_mock_user_alice_canister_id();
+
+        assert_eq!(
+            event_callbacks.register(
+                registrant,
+                get_arg(vec![EventCallbackKind::BetWon]),
+                &approval,
+                &current_time
+            ),
+            Err(EventCallbackError::EventNotApproved)
+        );
+        assert_eq!(
+            event_callbacks.register(registrant, get_arg(vec![]), &approval, &current_time),
+            Err(EventCallbackError::NoEventsRequested)
+        );
+        assert_eq!(
+            event_callbacks.register(
+                registrant,
+                RegisterEventCallbackArg {
+                    method_name: "".to_string(),
+                    events: vec![EventCallbackKind::PostCreated],
+                },
+                &approval,
+                &current_time
+            ),
+            Err(EventCallbackError::InvalidMethodName)
+        );
+        assert_eq!(
+            event_callbacks.register(
+                registrant,
+                get_arg(vec![
+                    EventCallbackKind::PostCreated,
+                    EventCallbackKind::PostCreated
+                ]),
+                &approval,
+                &current_time
+            ),
+            Ok(())
+        );
+        assert_eq!(
+            event_callbacks.get_registrations()[0].1.events,
+            vec![EventCallbackKind::PostCreated]
+        );
+
+        (0..MAX_EVENT_CALLBACK_REGISTRANTS_PER_PROFILE - 1).for_each(|index| {
+            assert!(event_callbacks
+                .register(
+                    Principal::self_authenticating(index.to_ne_bytes()),
+                    get_arg(vec![EventCallbackKind::PostCreated]),
+                    &approval,
+                    &current_time
+                )
+                .is_ok());
+        });
+        assert_eq!(
+            event_callbacks.register(
+                get_mock_user_bob_canister_id(),
+                get_arg(vec![EventCallbackKind::PostCreated]),
+                &approval,
+                &current_time
+            ),
+            Err(EventCallbackError::MaxRegistrantsReached)
+        );
+
+        // * re-registering an existing registrant is always allowed
+        assert!(event_callbacks
+            .register(
+                registrant,
+                get_arg(vec![EventCallbackKind::PostCreated]),
+                &approval,
+                &current_time
+            )
+            .is_ok());
+        assert!(event_callbacks.revoke(&registrant));
+        assert!(!event_callbacks.revoke(&registrant));
+    }
+
+    #[test]
+    fn test_callbacks_are_limited_to_the_daily_quota() {
+        let mut event_callbacks = EventCallbacks::default();
+        let approval = EventCallbackApproval {
+            allowed_events: vec![EventCallbackKind::PostCreated, EventCallbackKind::BetWon],
+            max_callbacks_per_day: 2,
+        };
+        let current_time = SystemTime::UNIX_EPOCH + DAY * 100;
+        let alice = get_mock_user_alice_canister_id();
+        let bob = get_mock_user_bob_canister_id();
+
+        event_callbacks
+            .register(
+                alice,
+                get_arg(vec![EventCallbackKind::PostCreated]),
+                &approval,
+                &current_time,
+            )
+            .unwrap();
+        event_callbacks
+            .register(
+                bob,
+                get_arg(vec![EventCallbackKind::BetWon]),
+                &approval,
+                &current_time,
+            )
+            .unwrap();
+
+        assert_eq!(
+            event_callbacks.take_callbacks_to_send(EventCallbackKind::BetWon, &current_time),
+            vec![(bob, "on_hot_or_not_event".to_string())]
+        );
+        (0..2).for_each(|_| {
+            assert_eq!(
+                event_callbacks
+                    .take_callbacks_to_send(EventCallbackKind::PostCreated, &current_time)
+                    .len(),
+                1
+            );
+        });
+        assert!(event_callbacks
+            .take_callbacks_to_send(EventCallbackKind::PostCreated, &current_time)
+            .is_empty());
+
+        // * the quota is back the next day
+        assert_eq!(
+            event_callbacks
+                .take_callbacks_to_send(EventCallbackKind::PostCreated, &(current_time + DAY))
+                .len(),
+            1
+        );
+    }
+}
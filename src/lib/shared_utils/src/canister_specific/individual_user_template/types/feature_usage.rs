@@ -0,0 +1,164 @@
+use std::{collections::BTreeMap, time::SystemTime};
+
+use candid::{CandidType, Deserialize};
+use serde::Serialize;
+
+// * Days since the unix epoch, in UTC
+pub type DayNumber = u64;
+
+// * Days already pushed to the user index are kept around this long
+const FEATURE_USAGE_RETENTION_IN_DAYS: u64 = 7;
+
+#[derive(
+    CandidType, Deserialize, Serialize, Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash,
+)]
+pub enum Feature {
+    BetPlaced,
+    PostCreated,
+    ProfileFollowed,
+}
+
+pub fn get_day_number(time: &SystemTime) -> DayNumber {
+    time.duration_since(SystemTime::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+        / (24 * 60 * 60)
+}
+
+/// How often the profile owner used each feature over a day. Features that weren't used are
+/// left out.
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug, PartialEq, Eq)]
+pub struct FeatureUsageForDay {
+    pub day: DayNumber,
+    pub usage_counts: Vec<(Feature, u64)>,
+}
+
+/// Daily usage counters of the profile owner. Only days that are over get pushed to the user
+/// index, each of them once.
+#[derive(Default, Serialize, Deserialize, Debug)]
+pub struct FeatureUsageStats {
+    usage_counts: BTreeMap<(DayNumber, Feature), u64>,
+    // * Every day before this one has been pushed
+    first_day_not_pushed: DayNumber,
+}
+
+impl FeatureUsageStats {
+    pub fn record(&mut self, feature: Feature, current_time: &SystemTime) {
+        *self
+            .usage_counts
+            .entry((get_day_number(current_time), feature))
+            .or_default() += 1;
+    }
+
+    pub fn get_usage_count(&self, day: DayNumber, feature: Feature) -> u64 {
+        self.usage_counts
+            .get(&(day, feature))
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Days that are over and haven't been pushed yet, oldest first
+    pub fn get_days_to_push(&self, current_time: &SystemTime) -> Vec<FeatureUsageForDay> {
+        let today = get_day_number(current_time);
+        let mut days_to_push: Vec<FeatureUsageForDay> = vec![];
+
+        for ((day, feature), usage_count) in self
+            .usage_counts
+            .range((self.first_day_not_pushed, Feature::BetPlaced)..)
+            .take_while(|((day, _), _)| *day < today)
+        {
+            match days_to_push.last_mut() {
+                Some(feature_usage_for_day) if feature_usage_for_day.day == *day => {
+                    feature_usage_for_day
+                        .usage_counts
+                        .push((*feature, *usage_count));
+                }
+                _ => days_to_push.push(FeatureUsageForDay {
+                    day: *day,
+                    usage_counts: vec![(*feature, *usage_count)],
+                }),
+            }
+        }
+
+        days_to_push
+    }
+
+    /// Drops counters of days that were pushed long enough ago
+    pub fn mark_days_as_pushed(&mut self, up_to_and_including_day: DayNumber) {
+        self.first_day_not_pushed = self.first_day_not_pushed.max(up_to_and_including_day + 1);
+
+        let first_day_to_keep = self
+            .first_day_not_pushed
+            .saturating_sub(FEATURE_USAGE_RETENTION_IN_DAYS);
+        self.usage_counts
+            .retain(|(day, _), _| *day >= first_day_to_keep);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use super::*;
+
+    const DAY: Duration = Duration::from_secs(24 * 60 * 60);
+
+    #[test]
+    fn test_only_days_that_are_over_are_pushed_once() {
+        let mut feature_usage_stats = FeatureUsageStats::default();
+        let day_10 = SystemTime::UNIX_EPOCH + DAY * 10;
+
+        feature_usage_stats.record(Feature::PostCreated, &day_10);
+        feature_usage_stats.record(Feature::BetPlaced, &day_10);
+        feature_usage_stats.record(Feature::BetPlaced, &(day_10 + Duration::from_secs(60)));
+        feature_usage_stats.record(Feature::BetPlaced, &(day_10 + DAY * 2));
+
+        assert_eq!(
+            feature_usage_stats.get_usage_count(10, Feature::BetPlaced),
+            2
+        );
+        assert!(feature_usage_stats.get_days_to_push(&day_10).is_empty());
+        assert_eq!(
+            feature_usage_stats.get_days_to_push(&(day_10 + DAY * 3)),
+            vec![
+                FeatureUsageForDay {
+                    day: 10,
+                    usage_counts: vec![(Feature::BetPlaced, 2), (Feature::PostCreated, 1)],
+                },
+                FeatureUsageForDay {
+                    day: 12,
+                    usage_counts: vec![(Feature::BetPlaced, 1)],
+                },
+            ]
+        );
+
+        feature_usage_stats.mark_days_as_pushed(10);
+
+        assert_eq!(
+            feature_usage_stats
+                .get_days_to_push(&(day_10 + DAY * 3))
+                .iter()
+                .map(|feature_usage_for_day| feature_usage_for_day.day)
+                .collect::<Vec<_>>(),
+            vec![12]
+        );
+    }
+
+    #[test]
+    fn test_pushed_days_are_dropped_after_the_retention_period() {
+        let mut feature_usage_stats = FeatureUsageStats::default();
+        feature_usage_stats.record(Feature::ProfileFollowed, &(SystemTime::UNIX_EPOCH + DAY));
+
+        feature_usage_stats.mark_days_as_pushed(1);
+        assert_eq!(
+            feature_usage_stats.get_usage_count(1, Feature::ProfileFollowed),
+            1
+        );
+
+        feature_usage_stats.mark_days_as_pushed(1 + FEATURE_USAGE_RETENTION_IN_DAYS);
+        assert_eq!(
+            feature_usage_stats.get_usage_count(1, Feature::ProfileFollowed),
+            0
+        );
+    }
+}
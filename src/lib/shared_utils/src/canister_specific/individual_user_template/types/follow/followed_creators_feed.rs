@@ -0,0 +1,116 @@
+use std::{
+    cmp::Reverse,
+    time::{Duration, SystemTime},
+};
+
+use crate::canister_specific::individual_user_template::types::post::PostDetailsForFrontend;
+
+// * Long enough for scrolling through a feed to be served from one fan out, short enough for
+// * new posts to show up on the next pull to refresh
+pub const FOLLOWED_CREATORS_FEED_CACHE_TTL: Duration = Duration::from_secs(30);
+// * Only the most recently followed creators are fanned out to
+pub const MAX_FOLLOWED_CREATORS_IN_FEED: usize = 100;
+pub const MAX_POSTS_PER_FOLLOWED_CREATOR_IN_FEED: u64 = 10;
+
+/// Posts of the creators a profile follows, as last fetched from their canisters, newest
+/// first. Posts created at the same time are ordered by their home feed score.
+#[derive(Debug)]
+pub struct FollowedCreatorsFeedCache {
+    posts: Vec<PostDetailsForFrontend>,
+    cached_at: SystemTime,
+}
+
+impl FollowedCreatorsFeedCache {
+    pub fn new(posts_by_creator: Vec<Vec<PostDetailsForFrontend>>, cached_at: SystemTime) -> Self {
+        let mut posts = posts_by_creator.into_iter().flatten().collect::<Vec<_>>();
+        posts.sort_by_key(|post| {
+            (
+                Reverse(post.created_at),
+                Reverse(post.home_feed_ranking_score),
+            )
+        });
+
+        Self { posts, cached_at }
+    }
+
+    pub fn is_fresh(&self, current_time: &SystemTime) -> bool {
+        current_time
+            .duration_since(self.cached_at)
+            .is_ok_and(|age| age < FOLLOWED_CREATORS_FEED_CACHE_TTL)
+    }
+
+    pub fn get_posts(&self) -> &[PostDetailsForFrontend] {
+        &self.posts
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use candid::Principal;
+
+    use crate::canister_specific::individual_user_template::types::post::PostStatus;
+
+    use super::*;
+
+    fn get_post(
+        id: u64,
+        created_at_secs: u64,
+        home_feed_ranking_score: u64,
+    ) -> PostDetailsForFrontend {
+        PostDetailsForFrontend {
+            id,
+            created_by_display_name: None,
+            created_by_unique_user_name: None,
+            created_by_user_principal_id: Principal::anonymous(),
+            created_by_profile_photo_url: None,
+            created_at: SystemTime::UNIX_EPOCH + Duration::from_secs(created_at_secs),
+            description: String::new(),
+            hashtags: vec![],
+            video_uid: String::new(),
+            status: PostStatus::ReadyToView,
+            total_view_count: 0,
+            like_count: 0,
+            liked_by_me: false,
+            home_feed_ranking_score,
+            hot_or_not_feed_ranking_score: None,
+            hot_or_not_betting_status: None,
+            title: None,
+            category: None,
+            active_boost: None,
+        }
+    }
+
+    #[test]
+    fn test_posts_are_merged_by_recency_then_score() {
+        let followed_creators_feed_cache = FollowedCreatorsFeedCache::new(
+            vec![
+                vec![get_post(1, 30, 0), get_post(2, 10, 0)],
+                vec![],
+                vec![get_post(3, 20, 5), get_post(4, 20, 50)],
+            ],
+            SystemTime::UNIX_EPOCH,
+        );
+
+        assert_eq!(
+            followed_creators_feed_cache
+                .get_posts()
+                .iter()
+                .map(|post| post.id)
+                .collect::<Vec<_>>(),
+            vec![1, 4, 3, 2]
+        );
+    }
+
+    #[test]
+    fn test_is_fresh() {
+        let cached_at = SystemTime::UNIX_EPOCH + Duration::from_secs(60);
+        let followed_creators_feed_cache = FollowedCreatorsFeedCache::new(vec![], cached_at);
+
+        assert!(followed_creators_feed_cache.is_fresh(&cached_at));
+        assert!(
+            !followed_creators_feed_cache.is_fresh(&(cached_at + FOLLOWED_CREATORS_FEED_CACHE_TTL))
+        );
+        // * a clock going backwards doesn't keep the cache alive
+        assert!(!followed_creators_feed_cache.is_fresh(&(cached_at - Duration::from_secs(1))));
+    }
+}
@@ -0,0 +1,160 @@
+use std::{cmp::Reverse, collections::BTreeMap, time::SystemTime};
+
+use candid::{CandidType, Deserialize, Principal};
+use serde::Serialize;
+
+use crate::common::types::app_primitive_type::PostId;
+
+pub const MAX_LATEST_POSTS_CACHED_PER_FOLLOWEE: usize = 5;
+// * Followers notified per message when fanning out a new post
+pub const MAX_FOLLOWERS_NOTIFIED_IN_ONE_FAN_OUT_BATCH: usize = 100;
+
+/// Sent by a followee's canister to each of their followers' canisters once a post of theirs
+/// is ready to view
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug, PartialEq, Eq)]
+pub struct PostFromFollowee {
+    pub publisher_principal_id: Principal,
+    pub post_id: PostId,
+    pub created_at: SystemTime,
+}
+
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug, PartialEq, Eq)]
+pub struct FollowingFeedItem {
+    pub publisher_principal_id: Principal,
+    pub publisher_canister_id: Principal,
+    pub post_id: PostId,
+    pub created_at: SystemTime,
+}
+
+/// The latest few posts of every profile this profile follows, newest first
+#[derive(Default, Serialize, Deserialize, Debug)]
+pub struct FollowingFeedCache {
+    latest_posts_by_followee_canister_id: BTreeMap<Principal, Vec<FollowingFeedItem>>,
+}
+
+impl FollowingFeedCache {
+    /// Keeps only the newest posts of each followee. Repeated posts are ignored.
+    pub fn add_post(&mut self, publisher_canister_id: Principal, post: PostFromFollowee) {
+        let latest_posts = self
+            .latest_posts_by_followee_canister_id
+            .entry(publisher_canister_id)
+            .or_default();
+
+        if latest_posts
+            .iter()
+            .any(|feed_item| feed_item.post_id == post.post_id)
+        {
+            return;
+        }
+
+        latest_posts.push(FollowingFeedItem {
+            publisher_principal_id: post.publisher_principal_id,
+            publisher_canister_id,
+            post_id: post.post_id,
+            created_at: post.created_at,
+        });
+        latest_posts.sort_by_key(|feed_item| Reverse(feed_item.created_at));
+        latest_posts.truncate(MAX_LATEST_POSTS_CACHED_PER_FOLLOWEE);
+    }
+
+    pub fn remove_followee(&mut self, publisher_canister_id: &Principal) {
+        self.latest_posts_by_followee_canister_id
+            .remove(publisher_canister_id);
+    }
+
+    pub fn len(&self) -> usize {
+        self.latest_posts_by_followee_canister_id
+            .values()
+            .map(Vec::len)
+            .sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Posts of all followees merged by recency, newest first
+    pub fn get_feed(&self) -> Vec<FollowingFeedItem> {
+        let mut feed = self
+            .latest_posts_by_followee_canister_id
+            .values()
+            .flatten()
+            .cloned()
+            .collect::<Vec<_>>();
+        feed.sort_by_key(|feed_item| Reverse(feed_item.created_at));
+
+        feed
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use test_utils::setup::test_constants::{
+        get_mock_user_alice_canister_id, get_mock_user_alice_principal_id,
+        get_mock_user_bob_canister_id, get_mock_user_bob_principal_id,
+    };
+
+    use super::*;
+
+    fn get_post(publisher_principal_id: Principal, post_id: PostId) -> PostFromFollowee {
+        PostFromFollowee {
+            publisher_principal_id,
+            post_id,
+            created_at: SystemTime::UNIX_EPOCH + Duration::from_secs(post_id * 60),
+        }
+    }
+
+    #[test]
+    fn test_only_the_latest_posts_of_each_followee_are_kept() {
+        let mut following_feed_cache = FollowingFeedCache::default();
+        let alice = get_mock_user_alice_principal_id();
+
+        (0..MAX_LATEST_POSTS_CACHED_PER_FOLLOWEE as u64 + 2)
+            .rev()
+            .for_each(|post_id| {
+                following_feed_cache
+                    .add_post(get_mock_user_alice_canister_id(), get_post(alice, post_id));
+            });
+        following_feed_cache.add_post(get_mock_user_alice_canister_id(), get_post(alice, 6));
+
+        assert_eq!(
+            following_feed_cache
+                .get_feed()
+                .iter()
+                .map(|feed_item| feed_item.post_id)
+                .collect::<Vec<_>>(),
+            vec![6, 5, 4, 3, 2]
+        );
+    }
+
+    #[test]
+    fn test_feed_merges_followees_by_recency() {
+        let mut following_feed_cache = FollowingFeedCache::default();
+        let alice = get_mock_user_alice_principal_id();
+        let bob = get_mock_user_bob_principal_id();
+
+        following_feed_cache.add_post(get_mock_user_alice_canister_id(), get_post(alice, 1));
+        following_feed_cache.add_post(get_mock_user_bob_canister_id(), get_post(bob, 2));
+        following_feed_cache.add_post(get_mock_user_alice_canister_id(), get_post(alice, 3));
+
+        assert_eq!(following_feed_cache.len(), 3);
+        assert_eq!(
+            following_feed_cache
+                .get_feed()
+                .iter()
+                .map(|feed_item| (feed_item.publisher_principal_id, feed_item.post_id))
+                .collect::<Vec<_>>(),
+            vec![(alice, 3), (bob, 2), (alice, 1)]
+        );
+        assert_eq!(
+            following_feed_cache.get_feed()[1].publisher_canister_id,
+            get_mock_user_bob_canister_id()
+        );
+
+        following_feed_cache.remove_followee(&get_mock_user_alice_canister_id());
+
+        assert_eq!(following_feed_cache.len(), 1);
+    }
+}
@@ -1,187 +1,78 @@
-use std::collections::{BTreeMap, HashMap};
+pub mod followed_creators_feed;
+pub mod following_feed;
+pub mod stable_follow_list;
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    ops::AddAssign,
+};
 
 use candid::{CandidType, Deserialize, Principal};
 use serde::Serialize;
 
+pub const MAX_FOLLOW_ENTRIES_IN_ONE_MIGRATION_CHUNK: usize = 500;
+
+/// Follow lists as they were kept on the heap, before they moved to stable memory. Only ever
+/// read back once after upgrading, to be moved into the stable follow lists.
 #[derive(Default, Serialize, Deserialize)]
 pub struct FollowData {
     pub follower: FollowList,
     pub following: FollowList,
 }
 
+#[derive(CandidType, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FollowListType {
+    Follower,
+    Following,
+}
+
 #[derive(Default, Serialize, Deserialize)]
 pub struct FollowList {
     pub sorted_index: BTreeMap<FollowEntryId, FollowEntryDetail>,
     pub members: HashMap<FollowEntryDetail, FollowEntryId>,
 }
 
-impl FollowList {
-    /// Returns the follow entry ID after the follow entry was added.
-    pub fn add(&mut self, follow_entry_detail: FollowEntryDetail) -> FollowEntryId {
-        let follow_entry_id = self.sorted_index.last_key_value().map_or(0, |(k, _)| k + 1);
-
-        self.sorted_index
-            .insert(follow_entry_id, follow_entry_detail.clone());
-        self.members.insert(follow_entry_detail, follow_entry_id);
-
-        follow_entry_id
-    }
-
-    /// Returns the follow entry ID if the follow entry was removed.
-    pub fn remove(&mut self, follow_entry_detail: &FollowEntryDetail) -> Option<FollowEntryId> {
-        let follow_entry_id = self.members.remove(follow_entry_detail);
-
-        if let Some(follow_entry_id) = follow_entry_id {
-            self.sorted_index.remove(&follow_entry_id);
-        }
-
-        follow_entry_id
-    }
-
-    /// Returns true if the follow entry exists.
-    pub fn contains(&self, follow_entry_detail: &FollowEntryDetail) -> bool {
-        self.members.contains_key(follow_entry_detail)
-    }
-
-    /// Returns the number of follow entries.
-    pub fn len(&self) -> usize {
-        self.members.len()
-    }
-
-    pub fn is_empty(&self) -> bool {
-        self.members.is_empty()
-    }
-}
-
 pub type FollowEntryId = u64;
 
-#[derive(Serialize, Deserialize, Eq, PartialEq, Hash, Clone, CandidType, Debug)]
+#[derive(
+    Serialize, Deserialize, Eq, PartialEq, Ord, PartialOrd, Hash, Clone, CandidType, Debug,
+)]
 pub struct FollowEntryDetail {
     pub principal_id: Principal,
     pub canister_id: Principal,
 }
 
-#[cfg(test)]
-mod test {
-    use super::*;
-
-    mod test_follow_list {
-        use super::*;
-
-        #[test]
-        fn test_add() {
-            let mut follow_list = FollowList::default();
-
-            let follow_entry_detail = FollowEntryDetail {
-                principal_id: Principal::self_authenticating((0u64).to_ne_bytes()),
-                canister_id: Principal::self_authenticating((0u64).to_ne_bytes()),
-            };
-
-            let follow_entry_id = follow_list.add(follow_entry_detail.clone());
-
-            assert_eq!(follow_entry_id, 0);
-            assert_eq!(follow_list.len(), 1);
-            assert!(follow_list.contains(&follow_entry_detail));
-        }
-
-        #[test]
-        fn test_remove() {
-            let mut follow_list = FollowList::default();
-
-            let follow_entry_detail = FollowEntryDetail {
-                principal_id: Principal::self_authenticating((0u64).to_ne_bytes()),
-                canister_id: Principal::self_authenticating((0u64).to_ne_bytes()),
-            };
-
-            let follow_entry_id = follow_list.add(follow_entry_detail.clone());
-
-            assert_eq!(follow_entry_id, 0);
-            assert_eq!(follow_list.len(), 1);
-            assert!(follow_list.contains(&follow_entry_detail));
-
-            let follow_entry_id = follow_list.remove(&follow_entry_detail);
-
-            assert_eq!(follow_entry_id, Some(0));
-            assert_eq!(follow_list.len(), 0);
-            assert!(!follow_list.contains(&follow_entry_detail));
-        }
-
-        #[test]
-        fn test_add_remove() {
-            let mut follow_list = FollowList::default();
-
-            let follow_entry_detail = FollowEntryDetail {
-                principal_id: Principal::self_authenticating((0u64).to_ne_bytes()),
-                canister_id: Principal::self_authenticating((0u64).to_ne_bytes()),
-            };
-
-            let follow_entry_id = follow_list.add(follow_entry_detail.clone());
-
-            assert_eq!(follow_entry_id, 0);
-            assert_eq!(follow_list.len(), 1);
-            assert!(follow_list.contains(&follow_entry_detail));
-
-            let follow_entry_id = follow_list.remove(&follow_entry_detail);
-
-            assert_eq!(follow_entry_id, Some(0));
-            assert_eq!(follow_list.len(), 0);
-            assert!(!follow_list.contains(&follow_entry_detail));
-
-            let follow_entry_id = follow_list.add(follow_entry_detail.clone());
-
-            assert_eq!(follow_entry_id, 0);
-            assert_eq!(follow_list.len(), 1);
-            assert!(follow_list.contains(&follow_entry_detail));
-        }
-
-        #[test]
-        fn test_contains() {
-            let mut follow_list = FollowList::default();
-
-            let follow_entry_detail = FollowEntryDetail {
-                principal_id: Principal::self_authenticating((0u64).to_ne_bytes()),
-                canister_id: Principal::self_authenticating((0u64).to_ne_bytes()),
-            };
-
-            let follow_entry_id = follow_list.add(follow_entry_detail.clone());
-
-            assert_eq!(follow_entry_id, 0);
-            assert_eq!(follow_list.len(), 1);
-            assert!(follow_list.contains(&follow_entry_detail));
-
-            let follow_entry_detail = FollowEntryDetail {
-                principal_id: Principal::self_authenticating((1u64).to_ne_bytes()),
-                canister_id: Principal::self_authenticating((1u64).to_ne_bytes()),
-            };
-
-            assert!(!follow_list.contains(&follow_entry_detail));
-        }
-
-        #[test]
-        fn test_len() {
-            let mut follow_list = FollowList::default();
-
-            let follow_entry_detail = FollowEntryDetail {
-                principal_id: Principal::self_authenticating((0u64).to_ne_bytes()),
-                canister_id: Principal::self_authenticating((0u64).to_ne_bytes()),
-            };
-
-            let follow_entry_id = follow_list.add(follow_entry_detail.clone());
-
-            assert_eq!(follow_entry_id, 0);
-            assert_eq!(follow_list.len(), 1);
-            assert!(follow_list.contains(&follow_entry_detail));
+impl FollowEntryDetail {
+    fn is_valid(&self) -> bool {
+        [self.principal_id, self.canister_id]
+            .iter()
+            .all(|principal| {
+                *principal != Principal::anonymous()
+                    && *principal != Principal::management_canister()
+            })
+            && self.principal_id != self.canister_id
+    }
+}
 
-            let follow_entry_detail = FollowEntryDetail {
-                principal_id: Principal::self_authenticating((1u64).to_ne_bytes()),
-                canister_id: Principal::self_authenticating((1u64).to_ne_bytes()),
-            };
+#[derive(CandidType, Deserialize, Debug, PartialEq, Eq)]
+pub struct FollowListExportChunk {
+    pub entries: Vec<(FollowEntryId, FollowEntryDetail)>,
+    pub next_from_inclusive_index: Option<FollowEntryId>,
+}
 
-            let follow_entry_id = follow_list.add(follow_entry_detail.clone());
+#[derive(CandidType, Deserialize, Default, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FollowListImportSummary {
+    pub imported: u64,
+    pub skipped_duplicates: u64,
+    pub skipped_invalid: u64,
+    pub skipped_list_full: u64,
+}
 
-            assert_eq!(follow_entry_id, 1);
-            assert_eq!(follow_list.len(), 2);
-            assert!(follow_list.contains(&follow_entry_detail));
-        }
+impl AddAssign for FollowListImportSummary {
+    fn add_assign(&mut self, other: Self) {
+        self.imported += other.imported;
+        self.skipped_duplicates += other.skipped_duplicates;
+        self.skipped_invalid += other.skipped_invalid;
+        self.skipped_list_full += other.skipped_list_full;
     }
 }
@@ -0,0 +1,615 @@
+use std::borrow::Cow;
+
+use candid::Principal;
+use ic_stable_structures::{BoundedStorable, Memory, StableBTreeMap, Storable};
+
+use crate::pagination::{self, Page};
+
+use super::{
+    FollowEntryDetail, FollowEntryId, FollowList, FollowListExportChunk, FollowListImportSummary,
+    FollowListType,
+};
+
+// * Principals are at most 29 bytes long. Each one is stored behind a byte holding its length.
+const MAX_PRINCIPAL_LENGTH: usize = 29;
+const ENCODED_PRINCIPAL_LENGTH: usize = MAX_PRINCIPAL_LENGTH + 1;
+
+fn encode_principal(principal: &Principal, bytes: &mut [u8]) {
+    let principal_bytes = principal.as_slice();
+    bytes[0] = principal_bytes.len() as u8;
+    bytes[1..=principal_bytes.len()].copy_from_slice(principal_bytes);
+}
+
+fn decode_principal(bytes: &[u8]) -> Principal {
+    Principal::from_slice(&bytes[1..=bytes[0] as usize])
+}
+
+impl Storable for FollowEntryDetail {
+    fn to_bytes(&self) -> Cow<'_, [u8]> {
+        let mut bytes = vec![0; 2 * ENCODED_PRINCIPAL_LENGTH];
+        let (principal_id_bytes, canister_id_bytes) = bytes.split_at_mut(ENCODED_PRINCIPAL_LENGTH);
+        encode_principal(&self.principal_id, principal_id_bytes);
+        encode_principal(&self.canister_id, canister_id_bytes);
+
+        Cow::Owned(bytes)
+    }
+
+    fn from_bytes(bytes: Cow<[u8]>) -> Self {
+        let (principal_id_bytes, canister_id_bytes) = bytes.split_at(ENCODED_PRINCIPAL_LENGTH);
+
+        Self {
+            principal_id: decode_principal(principal_id_bytes),
+            canister_id: decode_principal(canister_id_bytes),
+        }
+    }
+}
+
+impl BoundedStorable for FollowEntryDetail {
+    const MAX_SIZE: u32 = 2 * ENCODED_PRINCIPAL_LENGTH as u32;
+    const IS_FIXED_SIZE: bool = true;
+}
+
+/// Followers or followees of a profile, kept in a pair of stable memory regions so that the
+/// lists neither weigh on the heap nor have to be serialized on upgrades.
+///
+/// Entries are indexed by an id handed out in the order they were added, and looked up by
+/// their details in a second map so that membership checks don't have to scan the index.
+pub struct StableFollowList<M: Memory> {
+    sorted_index: StableBTreeMap<FollowEntryId, FollowEntryDetail, M>,
+    members: StableBTreeMap<FollowEntryDetail, FollowEntryId, M>,
+    next_follow_entry_id: FollowEntryId,
+}
+
+impl<M: Memory> StableFollowList<M> {
+    /// Loads the list persisted in the memories, or creates an empty one if there is none.
+    pub fn init(sorted_index_memory: M, members_memory: M) -> Self {
+        let mut follow_list = Self {
+            sorted_index: StableBTreeMap::init(sorted_index_memory),
+            members: StableBTreeMap::init(members_memory),
+            next_follow_entry_id: 0,
+        };
+        follow_list.next_follow_entry_id = follow_list.get_next_follow_entry_id();
+
+        follow_list
+    }
+
+    /// Returns the follow entry ID after the follow entry was added.
+    pub fn add(&mut self, follow_entry_detail: FollowEntryDetail) -> FollowEntryId {
+        let follow_entry_id = self.next_follow_entry_id;
+
+        self.sorted_index
+            .insert(follow_entry_id, follow_entry_detail.clone());
+        self.members.insert(follow_entry_detail, follow_entry_id);
+        self.next_follow_entry_id += 1;
+
+        follow_entry_id
+    }
+
+    /// Returns the follow entry ID if the follow entry was removed.
+    pub fn remove(&mut self, follow_entry_detail: &FollowEntryDetail) -> Option<FollowEntryId> {
+        let follow_entry_id = self.members.remove(follow_entry_detail)?;
+        self.sorted_index.remove(&follow_entry_id);
+
+        // * Same as the heap list this replaced, the id of the latest entry is handed out again
+        // * once it's removed
+        if follow_entry_id + 1 == self.next_follow_entry_id {
+            self.next_follow_entry_id = self.get_next_follow_entry_id();
+        }
+
+        Some(follow_entry_id)
+    }
+
+    /// Returns true if the follow entry exists.
+    pub fn contains(&self, follow_entry_detail: &FollowEntryDetail) -> bool {
+        self.members.contains_key(follow_entry_detail)
+    }
+
+    /// Returns the number of follow entries.
+    pub fn len(&self) -> usize {
+        self.members.len() as usize
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.members.is_empty()
+    }
+
+    /// Entry of the profile whose canister is `canister_id`. Scans the list, since entries
+    /// are looked up by both principals.
+    pub fn find_by_canister_id(&self, canister_id: &Principal) -> Option<FollowEntryDetail> {
+        self.members
+            .iter()
+            .map(|(follow_entry_detail, _)| follow_entry_detail)
+            .find(|follow_entry_detail| follow_entry_detail.canister_id == *canister_id)
+    }
+
+    /// Entries from `from_inclusive_follow_entry_id` down, latest first. The index can only
+    /// be walked in ascending order, so the ids are collected up front and the entries loaded
+    /// as they are yielded.
+    pub fn iter_rev_from(
+        &self,
+        from_inclusive_follow_entry_id: FollowEntryId,
+    ) -> impl Iterator<Item = (FollowEntryId, FollowEntryDetail)> + '_ {
+        let follow_entry_ids = self
+            .sorted_index
+            .range(..=from_inclusive_follow_entry_id)
+            .map(|(follow_entry_id, _)| follow_entry_id)
+            .collect::<Vec<_>>();
+
+        follow_entry_ids
+            .into_iter()
+            .rev()
+            .filter_map(|follow_entry_id| {
+                Some((follow_entry_id, self.sorted_index.get(&follow_entry_id)?))
+            })
+    }
+
+    /// Page of entries in the order they were added. Indices are positions in the list, not
+    /// follow entry ids.
+    pub fn get_page(&self, from_inclusive_index: u64, page_size: u64) -> Page<FollowEntryDetail> {
+        let index_page = pagination::get_page(0..self.len(), from_inclusive_index, page_size);
+
+        Page {
+            items: self
+                .sorted_index
+                .iter()
+                .skip(index_page.items.first().copied().unwrap_or_default())
+                .take(index_page.items.len())
+                .map(|(_, follow_entry_detail)| follow_entry_detail)
+                .collect(),
+            total_count: index_page.total_count,
+            next_from_inclusive_index: index_page.next_from_inclusive_index,
+        }
+    }
+
+    /// Up to `max_entries` entries starting at `from_inclusive_index`, in ascending index order
+    pub fn get_export_chunk(
+        &self,
+        from_inclusive_index: FollowEntryId,
+        max_entries: usize,
+    ) -> FollowListExportChunk {
+        let mut entries_from_index = self.sorted_index.range(from_inclusive_index..);
+
+        FollowListExportChunk {
+            entries: entries_from_index.by_ref().take(max_entries).collect(),
+            next_from_inclusive_index: entries_from_index
+                .next()
+                .map(|(follow_entry_id, _)| follow_entry_id),
+        }
+    }
+
+    /// Adds exported entries in the order given, skipping ones that are already in the list,
+    /// that aren't valid user principals or canister ids, that point back at `owner_principal_id`,
+    /// and any that would grow the list past `max_len`
+    pub fn import(
+        &mut self,
+        follow_entry_details: Vec<FollowEntryDetail>,
+        owner_principal_id: Option<Principal>,
+        max_len: u64,
+    ) -> FollowListImportSummary {
+        let mut summary = FollowListImportSummary::default();
+
+        for follow_entry_detail in follow_entry_details {
+            if !follow_entry_detail.is_valid()
+                || owner_principal_id == Some(follow_entry_detail.principal_id)
+            {
+                summary.skipped_invalid += 1;
+            } else if self.contains(&follow_entry_detail) {
+                summary.skipped_duplicates += 1;
+            } else if self.len() as u64 >= max_len {
+                summary.skipped_list_full += 1;
+            } else {
+                self.add(follow_entry_detail);
+                summary.imported += 1;
+            }
+        }
+
+        summary
+    }
+
+    /// Moves the entries of a list kept on the heap, keeping their ids. Returns the number of
+    /// entries moved.
+    pub fn move_legacy_entries(&mut self, legacy_follow_list: FollowList) -> u64 {
+        let mut entries_moved = 0;
+
+        legacy_follow_list.sorted_index.into_iter().for_each(
+            |(follow_entry_id, follow_entry_detail)| {
+                self.sorted_index
+                    .insert(follow_entry_id, follow_entry_detail.clone());
+                self.members.insert(follow_entry_detail, follow_entry_id);
+                entries_moved += 1;
+            },
+        );
+        self.next_follow_entry_id = self.get_next_follow_entry_id();
+
+        entries_moved
+    }
+
+    fn get_next_follow_entry_id(&self) -> FollowEntryId {
+        self.sorted_index
+            .iter()
+            .last()
+            .map_or(0, |(follow_entry_id, _)| follow_entry_id + 1)
+    }
+}
+
+/// Both follow lists of a profile
+pub struct StableFollowData<M: Memory> {
+    pub follower: StableFollowList<M>,
+    pub following: StableFollowList<M>,
+}
+
+impl<M: Memory> StableFollowData<M> {
+    pub fn get_list(&self, follow_list_type: FollowListType) -> &StableFollowList<M> {
+        match follow_list_type {
+            FollowListType::Follower => &self.follower,
+            FollowListType::Following => &self.following,
+        }
+    }
+
+    pub fn get_list_mut(&mut self, follow_list_type: FollowListType) -> &mut StableFollowList<M> {
+        match follow_list_type {
+            FollowListType::Follower => &mut self.follower,
+            FollowListType::Following => &mut self.following,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ic_stable_structures::VectorMemory;
+
+    use super::*;
+
+    mod test_follow_list {
+        use super::*;
+
+        fn new_follow_list() -> StableFollowList<VectorMemory> {
+            StableFollowList::init(VectorMemory::default(), VectorMemory::default())
+        }
+
+        #[test]
+        fn test_add() {
+            let mut follow_list = new_follow_list();
+
+            let follow_entry_detail = FollowEntryDetail {
+                principal_id: Principal::self_authenticating((0u64).to_ne_bytes()),
+                canister_id: Principal::self_authenticating((0u64).to_ne_bytes()),
+            };
+
+            let follow_entry_id = follow_list.add(follow_entry_detail.clone());
+
+            assert_eq!(follow_entry_id, 0);
+            assert_eq!(follow_list.len(), 1);
+            assert!(follow_list.contains(&follow_entry_detail));
+        }
+
+        #[test]
+        fn test_remove() {
+            let mut follow_list = new_follow_list();
+
+            let follow_entry_detail = FollowEntryDetail {
+                principal_id: Principal::self_authenticating((0u64).to_ne_bytes()),
+                canister_id: Principal::self_authenticating((0u64).to_ne_bytes()),
+            };
+
+            let follow_entry_id = follow_list.add(follow_entry_detail.clone());
+
+            assert_eq!(follow_entry_id, 0);
+            assert_eq!(follow_list.len(), 1);
+            assert!(follow_list.contains(&follow_entry_detail));
+
+            let follow_entry_id = follow_list.remove(&follow_entry_detail);
+
+            assert_eq!(follow_entry_id, Some(0));
+            assert_eq!(follow_list.len(), 0);
+            assert!(!follow_list.contains(&follow_entry_detail));
+        }
+
+        #[test]
+        fn test_add_remove() {
+            let mut follow_list = new_follow_list();
+
+            let follow_entry_detail = FollowEntryDetail {
+                principal_id: Principal::self_authenticating((0u64).to_ne_bytes()),
+                canister_id: Principal::self_authenticating((0u64).to_ne_bytes()),
+            };
+
+            let follow_entry_id = follow_list.add(follow_entry_detail.clone());
+
+            assert_eq!(follow_entry_id, 0);
+            assert_eq!(follow_list.len(), 1);
+            assert!(follow_list.contains(&follow_entry_detail));
+
+            let follow_entry_id = follow_list.remove(&follow_entry_detail);
+
+            assert_eq!(follow_entry_id, Some(0));
+            assert_eq!(follow_list.len(), 0);
+            assert!(!follow_list.contains(&follow_entry_detail));
+
+            let follow_entry_id = follow_list.add(follow_entry_detail.clone());
+
+            assert_eq!(follow_entry_id, 0);
+            assert_eq!(follow_list.len(), 1);
+            assert!(follow_list.contains(&follow_entry_detail));
+        }
+
+        #[test]
+        fn test_contains() {
+            let mut follow_list = new_follow_list();
+
+            let follow_entry_detail = FollowEntryDetail {
+                principal_id: Principal::self_authenticating((0u64).to_ne_bytes()),
+                canister_id: Principal::self_authenticating((0u64).to_ne_bytes()),
+            };
+
+            let follow_entry_id = follow_list.add(follow_entry_detail.clone());
+
+            assert_eq!(follow_entry_id, 0);
+            assert_eq!(follow_list.len(), 1);
+            assert!(follow_list.contains(&follow_entry_detail));
+
+            let follow_entry_detail = FollowEntryDetail {
+                principal_id: Principal::self_authenticating((1u64).to_ne_bytes()),
+                canister_id: Principal::self_authenticating((1u64).to_ne_bytes()),
+            };
+
+            assert!(!follow_list.contains(&follow_entry_detail));
+        }
+
+        #[test]
+        fn test_len() {
+            let mut follow_list = new_follow_list();
+
+            let follow_entry_detail = FollowEntryDetail {
+                principal_id: Principal::self_authenticating((0u64).to_ne_bytes()),
+                canister_id: Principal::self_authenticating((0u64).to_ne_bytes()),
+            };
+
+            let follow_entry_id = follow_list.add(follow_entry_detail.clone());
+
+            assert_eq!(follow_entry_id, 0);
+            assert_eq!(follow_list.len(), 1);
+            assert!(follow_list.contains(&follow_entry_detail));
+
+            let follow_entry_detail = FollowEntryDetail {
+                principal_id: Principal::self_authenticating((1u64).to_ne_bytes()),
+                canister_id: Principal::self_authenticating((1u64).to_ne_bytes()),
+            };
+
+            let follow_entry_id = follow_list.add(follow_entry_detail.clone());
+
+            assert_eq!(follow_entry_id, 1);
+            assert_eq!(follow_list.len(), 2);
+            assert!(follow_list.contains(&follow_entry_detail));
+        }
+
+        fn get_follow_entry_detail(label: u64) -> FollowEntryDetail {
+            FollowEntryDetail {
+                principal_id: Principal::self_authenticating(label.to_ne_bytes()),
+                canister_id: Principal::from_slice(&label.to_be_bytes()),
+            }
+        }
+
+        #[test]
+        fn test_storable_round_trip() {
+            let follow_entry_detail = get_follow_entry_detail(7);
+            let bytes = follow_entry_detail.to_bytes();
+
+            assert_eq!(bytes.len() as u32, FollowEntryDetail::MAX_SIZE);
+            assert_eq!(FollowEntryDetail::from_bytes(bytes), follow_entry_detail);
+
+            let follow_entry_detail = FollowEntryDetail {
+                principal_id: Principal::anonymous(),
+                canister_id: Principal::from_slice(&[0xff; MAX_PRINCIPAL_LENGTH]),
+            };
+
+            assert_eq!(
+                FollowEntryDetail::from_bytes(follow_entry_detail.to_bytes()),
+                follow_entry_detail
+            );
+        }
+
+        #[test]
+        fn test_init_picks_up_persisted_entries() {
+            let sorted_index_memory = VectorMemory::default();
+            let members_memory = VectorMemory::default();
+            let mut follow_list =
+                StableFollowList::init(sorted_index_memory.clone(), members_memory.clone());
+            (0..3).for_each(|label| {
+                follow_list.add(get_follow_entry_detail(label));
+            });
+
+            let mut follow_list = StableFollowList::init(sorted_index_memory, members_memory);
+
+            assert_eq!(follow_list.len(), 3);
+            assert!(follow_list.contains(&get_follow_entry_detail(1)));
+            assert_eq!(follow_list.add(get_follow_entry_detail(3)), 3);
+        }
+
+        #[test]
+        fn test_find_by_canister_id() {
+            let mut follow_list = new_follow_list();
+            (0..3).for_each(|label| {
+                follow_list.add(get_follow_entry_detail(label));
+            });
+
+            assert_eq!(
+                follow_list.find_by_canister_id(&get_follow_entry_detail(1).canister_id),
+                Some(get_follow_entry_detail(1))
+            );
+            assert_eq!(
+                follow_list.find_by_canister_id(&get_follow_entry_detail(3).canister_id),
+                None
+            );
+        }
+
+        #[test]
+        fn test_iter_rev_from() {
+            let mut follow_list = new_follow_list();
+            (0..5).for_each(|label| {
+                follow_list.add(get_follow_entry_detail(label));
+            });
+            follow_list.remove(&get_follow_entry_detail(2));
+
+            assert_eq!(
+                follow_list.iter_rev_from(3).collect::<Vec<_>>(),
+                vec![
+                    (3, get_follow_entry_detail(3)),
+                    (1, get_follow_entry_detail(1)),
+                    (0, get_follow_entry_detail(0)),
+                ]
+            );
+            assert_eq!(follow_list.iter_rev_from(u64::MAX).count(), 4);
+        }
+
+        #[test]
+        fn test_get_page() {
+            let mut follow_list = new_follow_list();
+            (0..5).for_each(|label| {
+                follow_list.add(get_follow_entry_detail(label));
+            });
+            follow_list.remove(&get_follow_entry_detail(1));
+
+            let page = follow_list.get_page(0, 3);
+            assert_eq!(
+                page,
+                Page {
+                    items: vec![
+                        get_follow_entry_detail(0),
+                        get_follow_entry_detail(2),
+                        get_follow_entry_detail(3),
+                    ],
+                    total_count: 4,
+                    next_from_inclusive_index: Some(3),
+                }
+            );
+
+            let page = follow_list.get_page(3, 3);
+            assert_eq!(page.items, vec![get_follow_entry_detail(4)]);
+            assert_eq!(page.next_from_inclusive_index, None);
+
+            assert!(follow_list.get_page(10, 3).items.is_empty());
+        }
+
+        #[test]
+        fn test_get_export_chunk() {
+            let mut follow_list = new_follow_list();
+            (0..5).for_each(|label| {
+                follow_list.add(get_follow_entry_detail(label));
+            });
+            follow_list.remove(&get_follow_entry_detail(2));
+
+            let export_chunk = follow_list.get_export_chunk(0, 2);
+            assert_eq!(
+                export_chunk,
+                FollowListExportChunk {
+                    entries: vec![
+                        (0, get_follow_entry_detail(0)),
+                        (1, get_follow_entry_detail(1)),
+                    ],
+                    next_from_inclusive_index: Some(3),
+                }
+            );
+
+            let export_chunk = follow_list.get_export_chunk(3, 2);
+            assert_eq!(
+                export_chunk,
+                FollowListExportChunk {
+                    entries: vec![
+                        (3, get_follow_entry_detail(3)),
+                        (4, get_follow_entry_detail(4)),
+                    ],
+                    next_from_inclusive_index: None,
+                }
+            );
+
+            assert!(follow_list.get_export_chunk(5, 2).entries.is_empty());
+        }
+
+        #[test]
+        fn test_import() {
+            let owner_principal_id = Principal::self_authenticating(100_u64.to_ne_bytes());
+            let mut follow_list = new_follow_list();
+            follow_list.add(get_follow_entry_detail(0));
+
+            let summary = follow_list.import(
+                vec![
+                    get_follow_entry_detail(0),
+                    get_follow_entry_detail(1),
+                    get_follow_entry_detail(1),
+                    FollowEntryDetail {
+                        principal_id: Principal::anonymous(),
+                        canister_id: Principal::from_slice(&[1]),
+                    },
+                    FollowEntryDetail {
+                        principal_id: Principal::self_authenticating([1]),
+                        canister_id: Principal::management_canister(),
+                    },
+                    FollowEntryDetail {
+                        principal_id: owner_principal_id,
+                        canister_id: Principal::from_slice(&[2]),
+                    },
+                    get_follow_entry_detail(2),
+                    get_follow_entry_detail(3),
+                ],
+                Some(owner_principal_id),
+                3,
+            );
+
+            assert_eq!(
+                summary,
+                FollowListImportSummary {
+                    imported: 2,
+                    skipped_duplicates: 2,
+                    skipped_invalid: 3,
+                    skipped_list_full: 1,
+                }
+            );
+            assert_eq!(
+                follow_list
+                    .sorted_index
+                    .iter()
+                    .map(|(_, follow_entry_detail)| follow_entry_detail)
+                    .collect::<Vec<_>>(),
+                vec![
+                    get_follow_entry_detail(0),
+                    get_follow_entry_detail(1),
+                    get_follow_entry_detail(2),
+                ]
+            );
+
+            let mut total_summary = FollowListImportSummary::default();
+            total_summary += summary;
+            total_summary += summary;
+            assert_eq!(total_summary.imported, 4);
+            assert_eq!(total_summary.skipped_list_full, 2);
+        }
+
+        #[test]
+        fn test_move_legacy_entries() {
+            let mut legacy_follow_list = FollowList::default();
+            [0, 1, 4].into_iter().for_each(|follow_entry_id| {
+                let follow_entry_detail = get_follow_entry_detail(follow_entry_id);
+                legacy_follow_list
+                    .sorted_index
+                    .insert(follow_entry_id, follow_entry_detail.clone());
+                legacy_follow_list
+                    .members
+                    .insert(follow_entry_detail, follow_entry_id);
+            });
+            let mut follow_list = new_follow_list();
+
+            assert_eq!(follow_list.move_legacy_entries(legacy_follow_list), 3);
+            assert_eq!(follow_list.len(), 3);
+            assert_eq!(
+                follow_list.get_export_chunk(0, 10).entries,
+                vec![
+                    (0, get_follow_entry_detail(0)),
+                    (1, get_follow_entry_detail(1)),
+                    (4, get_follow_entry_detail(4)),
+                ]
+            );
+            assert_eq!(follow_list.add(get_follow_entry_detail(5)), 5);
+        }
+    }
+}
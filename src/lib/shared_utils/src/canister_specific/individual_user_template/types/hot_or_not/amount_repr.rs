@@ -0,0 +1,227 @@
+use std::collections::BTreeMap;
+
+use candid::{
+    types::{Serializer, Type, TypeId},
+    CandidType, Deserialize,
+};
+use ic_cdk::api::management_canister::provisional::CanisterId;
+use serde::Serialize;
+
+use super::{
+    slot_compaction::CompactedRoomBets, to_token_amount, BetDirection, BetMaker,
+    HotOrNotPayoutMode, RoomBetPossibleOutcomes,
+};
+
+/// How aggregate stats, room details and bet details are encoded, both on the wire and in
+/// stable memory. Clients were built against `nat64` amounts, so every u128 amount is still
+/// sent as a `nat64` clamped to `u64::MAX`, with the exact amount in an optional `nat` next
+/// to it. The optional amounts are missing from posts stored while amounts were u64, whose
+/// `nat64` amounts are exact.
+#[derive(CandidType, Deserialize, Serialize)]
+pub struct AggregateStats {
+    pub total_number_of_hot_bets: u64,
+    pub total_number_of_not_bets: u64,
+    pub total_amount_bet: u64,
+    #[serde(default)]
+    pub total_amount_bet_u128: Option<u128>,
+}
+
+#[derive(CandidType, Deserialize, Serialize)]
+pub struct RoomDetails {
+    pub bets_made: BTreeMap<BetMaker, super::BetDetails>,
+    pub bet_outcome: RoomBetPossibleOutcomes,
+    pub room_bets_total_pot: u64,
+    #[serde(default)]
+    pub room_bets_total_pot_u128: Option<u128>,
+    pub total_hot_bets: u64,
+    pub total_not_bets: u64,
+    #[serde(default)]
+    pub payout_mode: Option<HotOrNotPayoutMode>,
+    #[serde(default)]
+    pub compacted_bets: Option<CompactedRoomBets>,
+}
+
+#[derive(CandidType, Deserialize, Serialize)]
+pub struct BetDetails {
+    pub amount: u64,
+    pub bet_direction: BetDirection,
+    pub payout: BetPayout,
+    #[serde(default)]
+    pub payout_u128: Option<u128>,
+    pub bet_maker_canister_id: CanisterId,
+    #[serde(default)]
+    pub referrer_canister_id: Option<CanisterId>,
+}
+
+#[derive(CandidType, Deserialize, Serialize)]
+pub enum BetPayout {
+    NotCalculatedYet,
+    Calculated(u64),
+}
+
+impl From<super::AggregateStats> for AggregateStats {
+    fn from(aggregate_stats: super::AggregateStats) -> Self {
+        Self {
+            total_number_of_hot_bets: aggregate_stats.total_number_of_hot_bets,
+            total_number_of_not_bets: aggregate_stats.total_number_of_not_bets,
+            total_amount_bet: to_token_amount(aggregate_stats.total_amount_bet),
+            total_amount_bet_u128: Some(aggregate_stats.total_amount_bet),
+        }
+    }
+}
+
+impl From<AggregateStats> for super::AggregateStats {
+    fn from(aggregate_stats: AggregateStats) -> Self {
+        Self {
+            total_number_of_hot_bets: aggregate_stats.total_number_of_hot_bets,
+            total_number_of_not_bets: aggregate_stats.total_number_of_not_bets,
+            total_amount_bet: aggregate_stats
+                .total_amount_bet_u128
+                .unwrap_or(aggregate_stats.total_amount_bet as u128),
+        }
+    }
+}
+
+impl From<super::RoomDetails> for RoomDetails {
+    fn from(room_details: super::RoomDetails) -> Self {
+        Self {
+            bets_made: room_details.bets_made,
+            bet_outcome: room_details.bet_outcome,
+            room_bets_total_pot: to_token_amount(room_details.room_bets_total_pot),
+            room_bets_total_pot_u128: Some(room_details.room_bets_total_pot),
+            total_hot_bets: room_details.total_hot_bets,
+            total_not_bets: room_details.total_not_bets,
+            payout_mode: room_details.payout_mode,
+            compacted_bets: room_details.compacted_bets,
+        }
+    }
+}
+
+impl From<RoomDetails> for super::RoomDetails {
+    fn from(room_details: RoomDetails) -> Self {
+        Self {
+            bets_made: room_details.bets_made,
+            bet_outcome: room_details.bet_outcome,
+            room_bets_total_pot: room_details
+                .room_bets_total_pot_u128
+                .unwrap_or(room_details.room_bets_total_pot as u128),
+            total_hot_bets: room_details.total_hot_bets,
+            total_not_bets: room_details.total_not_bets,
+            payout_mode: room_details.payout_mode,
+            compacted_bets: room_details.compacted_bets,
+        }
+    }
+}
+
+impl From<super::BetDetails> for BetDetails {
+    fn from(bet_details: super::BetDetails) -> Self {
+        let (payout, payout_u128) = match bet_details.payout {
+            super::BetPayout::NotCalculatedYet => (BetPayout::NotCalculatedYet, None),
+            super::BetPayout::Calculated(payout) => {
+                (BetPayout::Calculated(to_token_amount(payout)), Some(payout))
+            }
+        };
+
+        Self {
+            amount: bet_details.amount,
+            bet_direction: bet_details.bet_direction,
+            payout,
+            payout_u128,
+            bet_maker_canister_id: bet_details.bet_maker_canister_id,
+            referrer_canister_id: bet_details.referrer_canister_id,
+        }
+    }
+}
+
+impl From<BetDetails> for super::BetDetails {
+    fn from(bet_details: BetDetails) -> Self {
+        Self {
+            amount: bet_details.amount,
+            bet_direction: bet_details.bet_direction,
+            payout: match (bet_details.payout, bet_details.payout_u128) {
+                (BetPayout::NotCalculatedYet, _) => super::BetPayout::NotCalculatedYet,
+                (BetPayout::Calculated(payout), payout_u128) => {
+                    super::BetPayout::Calculated(payout_u128.unwrap_or(payout as u128))
+                }
+            },
+            bet_maker_canister_id: bet_details.bet_maker_canister_id,
+            referrer_canister_id: bet_details.referrer_canister_id,
+        }
+    }
+}
+
+// * Encoded under the same candid type as their representation, so that it's also what
+// * shows up in the candid interface
+macro_rules! encode_through_repr {
+    ($($type_name:ident),*) => {
+        $(
+            impl CandidType for super::$type_name {
+                fn id() -> TypeId {
+                    TypeId::of::<$type_name>()
+                }
+
+                fn _ty() -> Type {
+                    $type_name::_ty()
+                }
+
+                fn idl_serialize<S: Serializer>(&self, serializer: S) -> Result<(), S::Error> {
+                    $type_name::from(self.clone()).idl_serialize(serializer)
+                }
+            }
+        )*
+    };
+}
+
+encode_through_repr!(AggregateStats, RoomDetails, BetDetails);
+
+#[cfg(test)]
+mod test {
+    use candid::{Decode, Encode};
+    use test_utils::setup::test_constants::get_mock_user_alice_canister_id;
+
+    use crate::canister_specific::individual_user_template::types::hot_or_not;
+
+    use super::*;
+
+    #[test]
+    fn test_amounts_stored_as_u64_are_read_as_they_were() {
+        let bytes = Encode!(&AggregateStats {
+            total_number_of_hot_bets: 1,
+            total_number_of_not_bets: 0,
+            total_amount_bet: u64::MAX,
+            total_amount_bet_u128: None,
+        })
+        .unwrap();
+
+        assert_eq!(
+            Decode!(&bytes, hot_or_not::AggregateStats)
+                .unwrap()
+                .total_amount_bet,
+            u64::MAX as u128
+        );
+    }
+
+    #[test]
+    fn test_amounts_past_u64_are_clamped_in_their_nat64_fields() {
+        let bet_details = hot_or_not::BetDetails {
+            amount: 10,
+            bet_direction: BetDirection::Hot,
+            payout: hot_or_not::BetPayout::Calculated(u64::MAX as u128 * 2),
+            bet_maker_canister_id: get_mock_user_alice_canister_id(),
+            referrer_canister_id: None,
+        };
+        let bytes = Encode!(&bet_details).unwrap();
+
+        let bet_details_repr = Decode!(&bytes, BetDetails).unwrap();
+        assert!(matches!(
+            bet_details_repr.payout,
+            BetPayout::Calculated(u64::MAX)
+        ));
+        assert_eq!(bet_details_repr.payout_u128, Some(u64::MAX as u128 * 2));
+
+        assert_eq!(
+            Decode!(&bytes, hot_or_not::BetDetails).unwrap().payout,
+            bet_details.payout
+        );
+    }
+}
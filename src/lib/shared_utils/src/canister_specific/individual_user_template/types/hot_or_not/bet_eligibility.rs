@@ -0,0 +1,65 @@
+use candid::{CandidType, Deserialize};
+
+use crate::canister_specific::individual_user_template::types::error::BetOnCurrentlyViewingPostError;
+
+use super::BettingStatus;
+
+/// Whether a bet would go through if it were placed right now. Nothing is placed or set aside
+/// by the check, so the verdict can go stale before the bet is actually placed.
+#[derive(CandidType, Deserialize, PartialEq, Eq, Debug)]
+pub struct BetEligibility {
+    pub is_eligible: bool,
+    // * Checked by the bet maker's canister: who is betting, balance, session key limits and
+    // * posts already bet on
+    pub bet_maker_rejection: Option<BetOnCurrentlyViewingPostError>,
+    // * Checked by the post creator's canister: whether betting is open and the post minimum
+    pub post_rejection: Option<BetOnCurrentlyViewingPostError>,
+    // * What the bet maker sees on the post before betting. None if the post turned the bet
+    // * down
+    pub betting_status: Option<BettingStatus>,
+}
+
+impl BetEligibility {
+    pub fn new(
+        bet_maker_check: Result<(), BetOnCurrentlyViewingPostError>,
+        post_check: Result<BettingStatus, BetOnCurrentlyViewingPostError>,
+    ) -> Self {
+        let (betting_status, post_rejection) = match post_check {
+            Ok(betting_status) => (Some(betting_status), None),
+            Err(post_rejection) => (None, Some(post_rejection)),
+        };
+        let bet_maker_rejection = bet_maker_check.err();
+
+        Self {
+            is_eligible: bet_maker_rejection.is_none() && post_rejection.is_none(),
+            bet_maker_rejection,
+            post_rejection,
+            betting_status,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_bet_eligibility_needs_both_sides_to_accept() {
+        assert!(BetEligibility::new(Ok(()), Ok(BettingStatus::BettingClosed)).is_eligible);
+
+        let bet_eligibility = BetEligibility::new(
+            Err(BetOnCurrentlyViewingPostError::InsufficientBalance),
+            Err(BetOnCurrentlyViewingPostError::BettingClosed),
+        );
+        assert!(!bet_eligibility.is_eligible);
+        assert_eq!(
+            bet_eligibility.bet_maker_rejection,
+            Some(BetOnCurrentlyViewingPostError::InsufficientBalance)
+        );
+        assert_eq!(
+            bet_eligibility.post_rejection,
+            Some(BetOnCurrentlyViewingPostError::BettingClosed)
+        );
+        assert_eq!(bet_eligibility.betting_status, None);
+    }
+}
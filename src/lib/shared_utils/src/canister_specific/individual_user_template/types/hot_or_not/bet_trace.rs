@@ -0,0 +1,170 @@
+use candid::{CandidType, Deserialize};
+use serde::Serialize;
+
+use crate::{
+    canister_specific::individual_user_template::types::{
+        post::Post,
+        referral::ReferrerShareSettlement,
+        treasury::{PotInsuranceTopUp, PotInsuranceTopUpSettlement},
+    },
+    common::types::utility_token::token_event::TokenEvent,
+};
+
+use super::{
+    outcome_notification::BetOutcomeNotification, BetDetails, BetMaker, PlacedBetDetail,
+    RoomBetPossibleOutcomes, RoomId, SlotId,
+};
+
+// * Past this many, only the most recent token events about the bet are traced
+pub const MAX_TRACED_TOKEN_EVENTS: usize = 100;
+
+/// A bet as recorded on the post it was placed on
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug)]
+pub struct TracedBet {
+    pub slot_id: SlotId,
+    // * None for bets from shadow banned bettors, which sit in the slot's phantom room
+    pub room_id: Option<RoomId>,
+    pub bet_details: BetDetails,
+    pub room_outcome: Option<RoomBetPossibleOutcomes>,
+}
+
+/// What the canister of the post knows about a bet placed on it
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug)]
+pub struct PostSideBetTrace {
+    // * None if no bet from the bettor is on the post
+    pub bet: Option<TracedBet>,
+    // * Commission and house rake taken from the bet's room, by index in the token history
+    pub token_events: Vec<(u64, TokenEvent)>,
+    // * The outcome is settled but still to be sent to the bettor's canister
+    pub pending_outcome_notification: Option<BetOutcomeNotification>,
+    // * The bettor's top up is still to be claimed from the treasury
+    pub pending_pot_insurance_claim_top_up: Option<PotInsuranceTopUp>,
+    // * The treasury paid the top up out, but it is still to be sent to the bettor's canister
+    pub pending_pot_insurance_top_up: Option<PotInsuranceTopUpSettlement>,
+    // * The referrer's share cut from the bet's winnings, still to be sent to the referrer's
+    // * canister
+    pub pending_referrer_share_settlement: Option<ReferrerShareSettlement>,
+}
+
+/// What the bettor's canister knows about a bet it placed
+#[derive(CandidType, Deserialize, Serialize, Clone)]
+pub struct BettorSideBetTrace {
+    // * None if the bettor's canister has no record of the bet
+    pub placed_bet_detail: Option<PlacedBetDetail>,
+    // * Stake, refund, winnings and top up, by index in the token history
+    pub token_events: Vec<(u64, TokenEvent)>,
+    // * The referrer's share of the winnings, still to be sent to the referrer's canister. Only
+    // * shares queued before the post's canister took over working them out show up here
+    pub pending_referrer_share_settlements: Vec<ReferrerShareSettlement>,
+    pub has_received_pot_insurance_top_up: bool,
+}
+
+impl Post {
+    /// Where the bet of `bet_maker` sits on this post, looking through the rooms of every
+    /// slot before the phantom rooms
+    pub fn get_traced_bet(&self, bet_maker: &BetMaker) -> Option<TracedBet> {
+        let slot_history = &self.hot_or_not_details.as_ref()?.slot_history;
+
+        slot_history
+            .iter()
+            .find_map(|(slot_id, slot_details)| {
+                slot_details
+                    .room_details
+                    .iter()
+                    .find_map(|(room_id, room_details)| {
+                        let bet_details = room_details.bets_made.get(bet_maker)?;
+                        Some(TracedBet {
+                            slot_id: *slot_id,
+                            room_id: Some(*room_id),
+                            bet_details: bet_details.clone(),
+                            room_outcome: Some(room_details.bet_outcome.clone()),
+                        })
+                    })
+            })
+            .or_else(|| {
+                slot_history.iter().find_map(|(slot_id, slot_details)| {
+                    let bet_details = slot_details.phantom_room_bets.get(bet_maker)?;
+                    Some(TracedBet {
+                        slot_id: *slot_id,
+                        room_id: None,
+                        bet_details: bet_details.clone(),
+                        room_outcome: None,
+                    })
+                })
+            })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::SystemTime;
+
+    use candid::Principal;
+    use test_utils::setup::test_constants::{
+        get_mock_user_alice_canister_id, get_mock_user_alice_principal_id,
+        get_mock_user_bob_canister_id, get_mock_user_bob_principal_id,
+        get_mock_user_charlie_principal_id,
+    };
+
+    use crate::canister_specific::individual_user_template::types::{
+        hot_or_not::BetDirection, post::PostDetailsFromFrontend,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_get_traced_bet() {
+        let current_time = SystemTime::now();
+        let mut post = Post::new(
+            0,
+            &PostDetailsFromFrontend {
+                description: "Doggos and puppers".into(),
+                hashtags: vec!["doggo".into(), "pupper".into()],
+                video_uid: "abcd#1234".into(),
+                creator_consent_for_inclusion_in_hot_or_not: true,
+                collaborator_shares: None,
+                title: None,
+                category: None,
+            },
+            &current_time,
+        );
+        post.place_hot_or_not_bet(
+            &get_mock_user_alice_principal_id(),
+            &get_mock_user_alice_canister_id(),
+            100,
+            &BetDirection::Hot,
+            &current_time,
+        )
+        .unwrap();
+        post.place_hot_or_not_bet_in_phantom_room(
+            &get_mock_user_bob_principal_id(),
+            &get_mock_user_bob_canister_id(),
+            50,
+            &BetDirection::Not,
+            &current_time,
+        )
+        .unwrap();
+
+        let traced_bet = post
+            .get_traced_bet(&get_mock_user_alice_principal_id())
+            .unwrap();
+        assert_eq!(traced_bet.slot_id, 1);
+        assert_eq!(traced_bet.room_id, Some(1));
+        assert_eq!(traced_bet.bet_details.amount, 100);
+        assert_eq!(
+            traced_bet.room_outcome,
+            Some(RoomBetPossibleOutcomes::BetOngoing)
+        );
+
+        let traced_bet = post
+            .get_traced_bet(&get_mock_user_bob_principal_id())
+            .unwrap();
+        assert_eq!(traced_bet.room_id, None);
+        assert_eq!(traced_bet.bet_details.amount, 50);
+
+        assert!(post
+            .get_traced_bet(&get_mock_user_charlie_principal_id())
+            .is_none());
+        assert!(post.get_traced_bet(&Principal::anonymous()).is_none());
+    }
+}
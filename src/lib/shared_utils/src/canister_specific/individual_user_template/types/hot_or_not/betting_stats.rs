@@ -0,0 +1,167 @@
+use candid::{CandidType, Deserialize};
+use serde::Serialize;
+
+use super::{BetOutcomeForBetMaker, PlacedBetDetail};
+
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct BettingStatsSummary {
+    pub total_bets: u64,
+    pub total_wagered: u64,
+    // * Paid out on the bets that were won
+    pub total_won: u64,
+    // * Positive for a run of wins, negative for a run of losses. Draws leave it as it was
+    pub current_streak: i64,
+    // * Of the bets settled so far, draws included
+    pub win_rate_percentage: u8,
+}
+
+/// Running totals over the bets placed by this profile, kept up to date as bets are placed,
+/// cancelled and settled so that reading them doesn't mean going over every bet
+#[derive(Deserialize, Serialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct BettingStats {
+    total_bets: u64,
+    total_wagered: u64,
+    total_won: u64,
+    bets_won: u64,
+    bets_settled: u64,
+    current_streak: i64,
+}
+
+impl BettingStats {
+    /// Stats of bets placed before they were kept. The streak follows the order the bets were
+    /// placed in, as the order their outcomes arrived in wasn't recorded.
+    pub fn from_bets<'a>(bets: impl Iterator<Item = &'a PlacedBetDetail>) -> Self {
+        let mut bets: Vec<&PlacedBetDetail> = bets.collect();
+        bets.sort_by_key(|placed_bet_detail| placed_bet_detail.bet_placed_at);
+
+        bets.into_iter()
+            .fold(Self::default(), |mut betting_stats, placed_bet_detail| {
+                betting_stats.record_bet_placed(placed_bet_detail.amount_bet);
+                betting_stats.record_bet_outcome(&placed_bet_detail.outcome_received);
+                betting_stats
+            })
+    }
+
+    pub fn get_total_bets(&self) -> u64 {
+        self.total_bets
+    }
+
+    pub fn record_bet_placed(&mut self, amount_bet: u64) {
+        self.total_bets += 1;
+        self.total_wagered = self.total_wagered.saturating_add(amount_bet);
+    }
+
+    /// Only bets still awaiting their result can be cancelled
+    pub fn record_bet_cancelled(&mut self, amount_bet: u64) {
+        self.total_bets = self.total_bets.saturating_sub(1);
+        self.total_wagered = self.total_wagered.saturating_sub(amount_bet);
+    }
+
+    pub fn record_bet_outcome(&mut self, outcome: &BetOutcomeForBetMaker) {
+        match outcome {
+            BetOutcomeForBetMaker::AwaitingResult => return,
+            BetOutcomeForBetMaker::Won(winnings_amount) => {
+                self.total_won = self.total_won.saturating_add(*winnings_amount);
+                self.bets_won += 1;
+                self.current_streak = self.current_streak.max(0) + 1;
+            }
+            BetOutcomeForBetMaker::Lost => {
+                self.current_streak = self.current_streak.min(0) - 1;
+            }
+            BetOutcomeForBetMaker::Draw(_) => {}
+        }
+
+        self.bets_settled += 1;
+    }
+
+    pub fn get_summary(&self) -> BettingStatsSummary {
+        BettingStatsSummary {
+            total_bets: self.total_bets,
+            total_wagered: self.total_wagered,
+            total_won: self.total_won,
+            current_streak: self.current_streak,
+            win_rate_percentage: (self.bets_won * 100)
+                .checked_div(self.bets_settled)
+                .unwrap_or_default() as u8,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::{Duration, SystemTime};
+
+    use test_utils::setup::test_constants::get_mock_user_alice_canister_id;
+
+    use crate::canister_specific::individual_user_template::types::hot_or_not::BetDirection;
+
+    use super::*;
+
+    fn get_placed_bet_detail(
+        post_id: u64,
+        amount_bet: u64,
+        outcome_received: BetOutcomeForBetMaker,
+    ) -> PlacedBetDetail {
+        PlacedBetDetail {
+            canister_id: get_mock_user_alice_canister_id(),
+            post_id,
+            slot_id: 1,
+            room_id: 1,
+            amount_bet,
+            bet_direction: BetDirection::Hot,
+            bet_placed_at: SystemTime::UNIX_EPOCH + Duration::from_secs(post_id),
+            outcome_received,
+        }
+    }
+
+    #[test]
+    fn test_betting_stats_are_kept_as_bets_settle() {
+        let mut betting_stats = BettingStats::default();
+        assert_eq!(betting_stats.get_summary(), BettingStatsSummary::default());
+
+        (0..4).for_each(|_| betting_stats.record_bet_placed(100));
+        betting_stats.record_bet_outcome(&BetOutcomeForBetMaker::Lost);
+        betting_stats.record_bet_outcome(&BetOutcomeForBetMaker::Won(180));
+        betting_stats.record_bet_outcome(&BetOutcomeForBetMaker::Draw(100));
+        betting_stats.record_bet_outcome(&BetOutcomeForBetMaker::Won(180));
+        betting_stats.record_bet_placed(50);
+        betting_stats.record_bet_cancelled(50);
+
+        assert_eq!(
+            betting_stats.get_summary(),
+            BettingStatsSummary {
+                total_bets: 4,
+                total_wagered: 400,
+                total_won: 360,
+                current_streak: 2,
+                win_rate_percentage: 50,
+            }
+        );
+
+        betting_stats.record_bet_placed(100);
+        betting_stats.record_bet_outcome(&BetOutcomeForBetMaker::Lost);
+        assert_eq!(betting_stats.get_summary().current_streak, -1);
+        assert_eq!(betting_stats.get_summary().win_rate_percentage, 40);
+    }
+
+    #[test]
+    fn test_betting_stats_are_backfilled_from_bets_placed() {
+        let bets = [
+            get_placed_bet_detail(2, 100, BetOutcomeForBetMaker::Lost),
+            get_placed_bet_detail(0, 100, BetOutcomeForBetMaker::Won(180)),
+            get_placed_bet_detail(1, 100, BetOutcomeForBetMaker::Lost),
+            get_placed_bet_detail(3, 50, BetOutcomeForBetMaker::AwaitingResult),
+        ];
+
+        assert_eq!(
+            BettingStats::from_bets(bets.iter()).get_summary(),
+            BettingStatsSummary {
+                total_bets: 4,
+                total_wagered: 350,
+                total_won: 180,
+                current_streak: -2,
+                win_rate_percentage: 33,
+            }
+        );
+    }
+}
@@ -1,24 +1,51 @@
-use std::{cmp::Ordering, collections::BTreeMap, time::SystemTime};
+pub mod amount_repr;
+pub mod bet_eligibility;
+pub mod bet_trace;
+pub mod betting_stats;
+pub mod outcome_notification;
+pub mod slot_compaction;
+
+use std::{
+    borrow::Cow,
+    cmp::Ordering,
+    collections::BTreeMap,
+    ops::Bound::{Excluded, Unbounded},
+    time::{Duration, SystemTime},
+};
 
-use candid::{CandidType, Deserialize, Principal};
+use candid::{CandidType, Decode, Deserialize, Encode, Principal};
 use ic_cdk::api::management_canister::provisional::CanisterId;
+use ic_stable_structures::Storable;
 use serde::Serialize;
 
-use crate::common::types::{
-    app_primitive_type::PostId,
-    utility_token::token_event::{
-        HotOrNotOutcomePayoutEvent, TokenEvent, HOT_OR_NOT_BET_CREATOR_COMMISSION_PERCENTAGE,
-        HOT_OR_NOT_BET_WINNINGS_MULTIPLIER,
+use crate::common::{
+    types::{
+        app_primitive_type::PostId,
+        top_posts::slot_heat_indicator::{
+            SlotHeatIndicator, MINIMUM_BETS_IN_SLOT_FOR_ON_FIRE,
+            SLOT_HEAT_SCORE_THRESHOLD_FOR_ON_FIRE,
+        },
+        utility_token::token_event::{
+            HotOrNotOutcomePayoutEvent, TokenEvent, HOT_OR_NOT_BET_CREATOR_COMMISSION_PERCENTAGE,
+            HOT_OR_NOT_BET_WINNINGS_MULTIPLIER,
+        },
     },
+    utils::pseudonym::pseudonymize_principal,
 };
 
 use super::{
+    collaborator::{get_collaborator_share_of_commission, CollaboratorCommissionSettlement},
     error::BetOnCurrentlyViewingPostError,
     post::{FeedScore, Post},
+    referral::ReferrerShareSettlement,
     token::TokenBalance,
+    treasury::{
+        get_pot_insurance_top_up_amount, HouseRakeSettlement, PotInsuranceClaim, PotInsuranceTopUp,
+        MAXIMUM_HOUSE_RAKE_PERCENTAGE,
+    },
 };
 
-#[derive(CandidType, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[derive(CandidType, Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
 pub enum BettingStatus {
     BettingOpen {
         started_at: SystemTime,
@@ -26,34 +53,187 @@ pub enum BettingStatus {
         ongoing_slot: u8,
         ongoing_room: u64,
         has_this_user_participated_in_this_post: Option<bool>,
+        minimum_bet_amount: u64,
     },
     BettingClosed,
+    BettingClosedByCreator,
+    // * New bets wait until moderators have looked at the reports against the post
+    BettingPausedForReview,
+    // * The post is scheduled to go live later
+    BettingNotStarted {
+        starts_at: SystemTime,
+    },
 }
 
 pub const MAXIMUM_NUMBER_OF_SLOTS: u8 = 48;
 pub const DURATION_OF_EACH_SLOT_IN_SECONDS: u64 = 60 * 60;
 pub const TOTAL_DURATION_OF_ALL_SLOTS_IN_SECONDS: u64 =
     MAXIMUM_NUMBER_OF_SLOTS as u64 * DURATION_OF_EACH_SLOT_IN_SECONDS;
+// * Canister clocks drift apart slightly, so a canister can see a post created elsewhere as
+// * being from a few seconds in the future
+pub const CLOCK_SKEW_TOLERANCE_IN_SECONDS: u64 = 60;
+// * Upper bound on the minimum bet a creator can set on their post. Zero means no minimum
+pub const MAXIMUM_MINIMUM_BET_AMOUNT_FOR_A_POST: u64 = 1_000;
+pub const DEFAULT_ROOM_SIZE: u64 = 100;
+// * number_of_participants in BettingStatus is a u8
+pub const MAXIMUM_ROOM_SIZE: u64 = u8::MAX as u64;
+
+// * Bets can only be taken back this early into the slot they were placed in
+pub const BET_CANCELLATION_WINDOW_IN_SECONDS: u64 = 5 * 60;
+
+/// Share of the stake a bettor gets back for cancelling a bet. The rest is burnt, so that
+/// cancelling isn't a free way of watching where a room is heading.
+#[derive(CandidType, Clone, Deserialize, Serialize, Debug, PartialEq, Eq)]
+pub struct BetCancellationConfig {
+    pub refund_percentage: u64,
+}
+
+impl Default for BetCancellationConfig {
+    fn default() -> Self {
+        Self {
+            refund_percentage: 90,
+        }
+    }
+}
+
+impl BetCancellationConfig {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.refund_percentage > 100 {
+            return Err("Refund percentage cannot exceed 100".to_string());
+        }
+
+        Ok(())
+    }
+
+    pub fn get_refund_amount(&self, bet_amount: u64) -> u64 {
+        (bet_amount as u128 * self.refund_percentage.min(100) as u128 / 100) as u64
+    }
+}
+
+/// How the winners of a room are paid out, set per environment in the configuration canister
+#[derive(CandidType, Clone, Copy, Deserialize, Serialize, Debug, PartialEq, Eq, Default)]
+pub enum HotOrNotPayoutMode {
+    // * Winners get a fixed multiple of their stake, however lopsided the room was
+    #[default]
+    Multiplier,
+    // * Winners split the whole pot in proportion to their stakes, so a win on the side with
+    // * less riding on it pays more
+    Parimutuel,
+}
+
+/// How many bettors a room takes before the next bet starts a new room. Velocity is the
+/// number of bets the ongoing slot is projected to see at its current pace.
+#[derive(CandidType, Clone, Deserialize, Serialize, Debug, PartialEq, Eq)]
+pub struct RoomSizingConfig {
+    pub default_room_size: u64,
+    // * Fast slots start new rooms early so that no single room gets expensive to tabulate
+    pub high_velocity: Option<RoomSizeForVelocity>,
+    // * Slow slots let rooms grow so that pots are not fragmented across near empty rooms
+    pub low_velocity: Option<RoomSizeForVelocity>,
+}
+
+#[derive(CandidType, Clone, Deserialize, Serialize, Debug, PartialEq, Eq)]
+pub struct RoomSizeForVelocity {
+    pub projected_bets_in_slot_threshold: u64,
+    pub room_size: u64,
+}
+
+impl Default for RoomSizingConfig {
+    fn default() -> Self {
+        Self {
+            default_room_size: DEFAULT_ROOM_SIZE,
+            high_velocity: None,
+            low_velocity: None,
+        }
+    }
+}
+
+impl RoomSizingConfig {
+    pub fn validate(&self) -> Result<(), String> {
+        let room_sizes = [
+            Some(self.default_room_size),
+            self.high_velocity.as_ref().map(|high| high.room_size),
+            self.low_velocity.as_ref().map(|low| low.room_size),
+        ];
+        if room_sizes
+            .into_iter()
+            .flatten()
+            .any(|room_size| room_size == 0 || room_size > MAXIMUM_ROOM_SIZE)
+        {
+            return Err(format!(
+                "Room sizes must be between 1 and {}",
+                MAXIMUM_ROOM_SIZE
+            ));
+        }
 
-#[derive(CandidType)]
+        if let Some(high_velocity) = &self.high_velocity {
+            if high_velocity.room_size > self.default_room_size {
+                return Err("High velocity rooms cannot be larger than default rooms".to_string());
+            }
+        }
+
+        if let Some(low_velocity) = &self.low_velocity {
+            if low_velocity.room_size < self.default_room_size {
+                return Err("Low velocity rooms cannot be smaller than default rooms".to_string());
+            }
+        }
+
+        if let (Some(high_velocity), Some(low_velocity)) = (&self.high_velocity, &self.low_velocity)
+        {
+            if low_velocity.projected_bets_in_slot_threshold
+                >= high_velocity.projected_bets_in_slot_threshold
+            {
+                return Err(
+                    "Low velocity threshold must be below the high velocity threshold".to_string(),
+                );
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn get_room_size(&self, projected_bets_in_slot: u64) -> u64 {
+        match (&self.high_velocity, &self.low_velocity) {
+            (Some(high_velocity), _)
+                if projected_bets_in_slot >= high_velocity.projected_bets_in_slot_threshold =>
+            {
+                high_velocity.room_size
+            }
+            (_, Some(low_velocity))
+                if projected_bets_in_slot <= low_velocity.projected_bets_in_slot_threshold =>
+            {
+                low_velocity.room_size
+            }
+            _ => self.default_room_size,
+        }
+    }
+}
+
+#[derive(CandidType, Debug, PartialEq, Eq)]
 pub enum UserStatusForSpecificHotOrNotPost {
     NotParticipatedYet,
     AwaitingResult(BetDetail),
-    ResultAnnounced(BetResult),
+    ResultAnnounced {
+        bet_detail: BetDetail,
+        result: BetResult,
+        // * The exact amount of a win or draw, which `result` clamps to `u64::MAX`
+        payout_u128: Option<u128>,
+    },
 }
 
-#[derive(CandidType)]
+#[derive(CandidType, Debug, PartialEq, Eq)]
 pub enum BetResult {
     Won(u64),
     Lost,
-    Draw,
+    Draw(u64),
 }
 
-#[derive(CandidType)]
+#[derive(CandidType, Debug, PartialEq, Eq)]
 pub struct BetDetail {
-    amount: u64,
-    bet_direction: BetDirection,
-    bet_made_at: SystemTime,
+    pub amount: u64,
+    pub bet_direction: BetDirection,
+    pub slot_id: SlotId,
+    pub room_id: RoomId,
 }
 
 #[derive(CandidType, Clone, Deserialize, Serialize, Debug, PartialEq, Eq)]
@@ -73,13 +253,237 @@ pub struct HotOrNotDetails {
     pub hot_or_not_feed_score: FeedScore,
     pub aggregate_stats: AggregateStats,
     pub slot_history: BTreeMap<SlotId, SlotDetails>,
+    // * Kept up to date as bets are placed, like the aggregate stats
+    #[serde(default)]
+    pub bet_amount_histogram: BetAmountHistogram,
 }
 
-#[derive(CandidType, Clone, Deserialize, Debug, Serialize, Default)]
+impl Storable for HotOrNotDetails {
+    fn to_bytes(&self) -> Cow<'_, [u8]> {
+        Cow::Owned(Encode!(self).unwrap())
+    }
+
+    fn from_bytes(bytes: Cow<[u8]>) -> Self {
+        Decode!(&bytes, Self).unwrap()
+    }
+}
+
+// * Encoded through `amount_repr::AggregateStats`
+#[derive(Clone, Deserialize, Debug, Serialize, Default, PartialEq, Eq)]
+#[serde(
+    from = "amount_repr::AggregateStats",
+    into = "amount_repr::AggregateStats"
+)]
 pub struct AggregateStats {
     pub total_number_of_hot_bets: u64,
     pub total_number_of_not_bets: u64,
-    pub total_amount_bet: u64,
+    pub total_amount_bet: u128,
+}
+
+// * Inclusive upper bounds of the bet amount histogram buckets. Bets above the last bound all
+// * go in one more bucket.
+pub const BET_AMOUNT_HISTOGRAM_BUCKET_UPPER_BOUNDS: [u64; 5] = [10, 50, 100, 500, 1_000];
+
+/// Number of bets placed on a post per bet amount bucket, split by direction
+#[derive(CandidType, Clone, Deserialize, Debug, Serialize, Default, PartialEq, Eq)]
+pub struct BetAmountHistogram {
+    // * One count per bucket, or empty until the first bet in that direction
+    pub hot_bet_counts: Vec<u64>,
+    pub not_bet_counts: Vec<u64>,
+}
+
+#[derive(CandidType, Clone, Deserialize, Debug, Serialize, PartialEq, Eq)]
+pub struct BetAmountHistogramBucket {
+    pub min_amount: u64,
+    // * None for the last bucket, which is open ended
+    pub max_amount: Option<u64>,
+    pub hot_bet_count: u64,
+    pub not_bet_count: u64,
+}
+
+impl BetAmountHistogram {
+    pub fn record_bet(&mut self, bet_amount: u64, bet_direction: &BetDirection) {
+        let bet_counts = match bet_direction {
+            BetDirection::Hot => &mut self.hot_bet_counts,
+            BetDirection::Not => &mut self.not_bet_counts,
+        };
+        bet_counts.resize(BET_AMOUNT_HISTOGRAM_BUCKET_UPPER_BOUNDS.len() + 1, 0);
+
+        let bucket_index = BET_AMOUNT_HISTOGRAM_BUCKET_UPPER_BOUNDS
+            .partition_point(|upper_bound| *upper_bound < bet_amount);
+        bet_counts[bucket_index] += 1;
+    }
+
+    pub fn remove_bet(&mut self, bet_amount: u64, bet_direction: &BetDirection) {
+        let bet_counts = match bet_direction {
+            BetDirection::Hot => &mut self.hot_bet_counts,
+            BetDirection::Not => &mut self.not_bet_counts,
+        };
+
+        let bucket_index = BET_AMOUNT_HISTOGRAM_BUCKET_UPPER_BOUNDS
+            .partition_point(|upper_bound| *upper_bound < bet_amount);
+        if let Some(bet_count) = bet_counts.get_mut(bucket_index) {
+            *bet_count = bet_count.saturating_sub(1);
+        }
+    }
+
+    /// Every bucket, including empty ones, from the smallest amounts up
+    pub fn get_buckets(&self) -> Vec<BetAmountHistogramBucket> {
+        (0..=BET_AMOUNT_HISTOGRAM_BUCKET_UPPER_BOUNDS.len())
+            .map(|bucket_index| BetAmountHistogramBucket {
+                min_amount: match bucket_index {
+                    0 => 0,
+                    _ => BET_AMOUNT_HISTOGRAM_BUCKET_UPPER_BOUNDS[bucket_index - 1] + 1,
+                },
+                max_amount: BET_AMOUNT_HISTOGRAM_BUCKET_UPPER_BOUNDS
+                    .get(bucket_index)
+                    .copied(),
+                hot_bet_count: self
+                    .hot_bet_counts
+                    .get(bucket_index)
+                    .copied()
+                    .unwrap_or_default(),
+                not_bet_count: self
+                    .not_bet_counts
+                    .get(bucket_index)
+                    .copied()
+                    .unwrap_or_default(),
+            })
+            .collect()
+    }
+}
+
+#[derive(CandidType, Clone, Deserialize, Debug, Serialize, PartialEq, Eq)]
+pub struct PostBetAnalytics {
+    pub aggregate_stats: AggregateStats,
+    pub bet_amount_histogram: Vec<BetAmountHistogramBucket>,
+}
+
+impl HotOrNotDetails {
+    /// Derives the aggregate stats from the individual bets recorded in every room,
+    /// which are the source of truth if the running totals ever drift
+    pub fn recompute_aggregate_stats_from_slot_history(&self) -> AggregateStats {
+        self.slot_history
+            .values()
+            .flat_map(|slot_details| slot_details.room_details.values())
+            .flat_map(|room_details| room_details.bets_made.values())
+            .fold(AggregateStats::default(), |mut aggregate_stats, bet| {
+                match bet.bet_direction {
+                    BetDirection::Hot => aggregate_stats.total_number_of_hot_bets += 1,
+                    BetDirection::Not => aggregate_stats.total_number_of_not_bets += 1,
+                }
+                aggregate_stats.total_amount_bet += bet.amount as u128;
+                aggregate_stats
+            })
+    }
+
+    /// Same as `recompute_aggregate_stats_from_slot_history`, for posts whose bets were placed
+    /// before the histogram was kept
+    pub fn recompute_bet_amount_histogram_from_slot_history(&self) -> BetAmountHistogram {
+        self.slot_history
+            .values()
+            .flat_map(|slot_details| slot_details.room_details.values())
+            .flat_map(|room_details| room_details.bets_made.values())
+            .fold(BetAmountHistogram::default(), |mut histogram, bet| {
+                histogram.record_bet(bet.amount, &bet.bet_direction);
+                histogram
+            })
+    }
+
+    /// Every bet on the post, slot by slot and room by room in the order they were opened.
+    /// Bets within a room are in bet maker order, as the time of each bet isn't kept. Shadow
+    /// banned bets come after the rooms of their slot.
+    pub fn get_bet_history(&self) -> Vec<PostBetHistoryEntry> {
+        self.slot_history
+            .iter()
+            .flat_map(|(slot_id, slot_details)| {
+                let room_bets =
+                    slot_details
+                        .room_details
+                        .iter()
+                        .flat_map(move |(room_id, room_details)| {
+                            room_details
+                                .bets_made
+                                .iter()
+                                .map(move |(bet_maker, bet_details)| PostBetHistoryEntry {
+                                    slot_id: *slot_id,
+                                    room_id: Some(*room_id),
+                                    bet_maker: *bet_maker,
+                                    bet_details: bet_details.clone(),
+                                })
+                        });
+                let phantom_room_bets =
+                    slot_details
+                        .phantom_room_bets
+                        .iter()
+                        .map(move |(bet_maker, bet_details)| PostBetHistoryEntry {
+                            slot_id: *slot_id,
+                            room_id: None,
+                            bet_maker: *bet_maker,
+                            bet_details: bet_details.clone(),
+                        });
+
+                room_bets.chain(phantom_room_bets)
+            })
+            .collect()
+    }
+
+    pub fn get_bet_analytics(&self) -> PostBetAnalytics {
+        PostBetAnalytics {
+            aggregate_stats: self.aggregate_stats.clone(),
+            bet_amount_histogram: self.bet_amount_histogram.get_buckets(),
+        }
+    }
+
+    /// Replaces the bet makers picked by `should_pseudonymize` with their pseudonyms in every
+    /// room, for copies of the post that leave the canister. Amounts and payouts are untouched.
+    pub fn pseudonymize_bet_makers(
+        &mut self,
+        should_pseudonymize: impl Fn(&BetMaker) -> bool,
+        salt: &[u8],
+    ) {
+        let pseudonymize = |bet_maker: BetMaker| {
+            if should_pseudonymize(&bet_maker) {
+                pseudonymize_principal(&bet_maker, salt)
+            } else {
+                bet_maker
+            }
+        };
+        let pseudonymize_bets = |bets: &mut BTreeMap<BetMaker, BetDetails>| {
+            *bets = std::mem::take(bets)
+                .into_iter()
+                .map(|(bet_maker, bet_details)| (pseudonymize(bet_maker), bet_details))
+                .collect();
+        };
+
+        self.slot_history.values_mut().for_each(|slot_details| {
+            slot_details
+                .room_details
+                .values_mut()
+                .for_each(|room_details| pseudonymize_bets(&mut room_details.bets_made));
+            pseudonymize_bets(&mut slot_details.phantom_room_bets);
+            if let Some(tabulation_cursor) = slot_details.tabulation_cursor.as_mut() {
+                tabulation_cursor.last_settled_bet_maker =
+                    tabulation_cursor.last_settled_bet_maker.map(pseudonymize);
+            }
+        });
+    }
+}
+
+#[derive(CandidType, Clone, Deserialize, Debug, Serialize, PartialEq, Eq)]
+pub struct AggregateStatsDiscrepancy {
+    pub post_id: PostId,
+    pub stored: AggregateStats,
+    pub recomputed: AggregateStats,
+}
+
+#[derive(CandidType, Clone, Deserialize, Debug, Serialize, PartialEq, Eq)]
+pub struct AggregateStatsBackfillReport {
+    pub started_at: SystemTime,
+    pub number_of_posts_scanned: u64,
+    // * None once every post has been scanned
+    pub next_post_id_to_scan: Option<PostId>,
+    pub discrepancies_found: Vec<AggregateStatsDiscrepancy>,
 }
 
 pub type SlotId = u8;
@@ -87,34 +491,152 @@ pub type SlotId = u8;
 #[derive(CandidType, Clone, Deserialize, Default, Debug, Serialize)]
 pub struct SlotDetails {
     pub room_details: BTreeMap<RoomId, RoomDetails>,
+    // * Bets from shadow banned principals. Kept out of every room so that they never
+    // * affect an outcome, and settled as losses when the slot is tabulated
+    #[serde(default)]
+    pub phantom_room_bets: BTreeMap<BetMaker, BetDetails>,
+    // * Set when tabulation ran out of budget before getting through every room
+    #[serde(default)]
+    pub tabulation_cursor: Option<TabulationCursor>,
+    // * Id the next room opened in this slot gets. None for slots from before it was kept
+    #[serde(default)]
+    pub next_room_id: Option<RoomId>,
+}
+
+#[derive(CandidType, Clone, Deserialize, Debug, Serialize, PartialEq, Eq)]
+pub struct TabulationCursor {
+    pub room_id: RoomId,
+    // * Bets in the room are paid out in bet maker order. None if none of them have been yet
+    pub last_settled_bet_maker: Option<BetMaker>,
+}
+
+impl SlotDetails {
+    /// Has bets yet to be settled, leaving out a tabulation that ran out of budget and is
+    /// waiting to be picked up from its cursor
+    pub fn is_awaiting_tabulation(&self) -> bool {
+        self.tabulation_cursor.is_none()
+            && (self.room_details.values().any(|room_details| {
+                room_details.bet_outcome == RoomBetPossibleOutcomes::BetOngoing
+            }) || self
+                .phantom_room_bets
+                .values()
+                .any(|bet_details| bet_details.payout == BetPayout::NotCalculatedYet))
+    }
+
+    pub fn get_number_of_bets(&self) -> u64 {
+        self.room_details
+            .values()
+            .map(|room_details| room_details.bets_made.len() as u64)
+            .sum()
+    }
+
+    /// Room the next bet goes in, unless it fills up before then
+    pub fn get_ongoing_room_id(&self) -> RoomId {
+        match self.next_room_id {
+            Some(next_room_id) => next_room_id - 1,
+            None => self
+                .room_details
+                .last_key_value()
+                .map(|(room_id, _)| *room_id)
+                .unwrap_or(1),
+        }
+    }
+
+    /// Picks the room for a bet being placed right now and moves the counter along if that
+    /// means opening a new room. Works off the slot as it is at the time of placing rather
+    /// than a betting status read earlier, so bets that interleave never land in a full room
+    /// or open the same room twice.
+    fn assign_room_for_new_bet(&mut self, room_size: u64) -> RoomId {
+        let ongoing_room_id = self.get_ongoing_room_id();
+        let is_ongoing_room_full = self
+            .room_details
+            .get(&ongoing_room_id)
+            .is_some_and(|room_details| room_details.bets_made.len() as u64 >= room_size);
+
+        let room_id = if is_ongoing_room_full {
+            ongoing_room_id + 1
+        } else {
+            ongoing_room_id
+        };
+        self.next_room_id = Some(room_id + 1);
+
+        room_id
+    }
+}
+
+/// The slot betting is open in at this point of the contest, or None once it is over
+fn get_ongoing_slot(seconds_since_betting_started: u64) -> Option<u8> {
+    if seconds_since_betting_started >= TOTAL_DURATION_OF_ALL_SLOTS_IN_SECONDS {
+        return None;
+    }
+
+    Some(((seconds_since_betting_started / DURATION_OF_EACH_SLOT_IN_SECONDS) + 1) as u8)
+}
+
+/// Extrapolates the bets placed so far in the ongoing slot to the full length of the slot
+fn get_projected_bets_in_slot(bets_in_slot: u64, seconds_since_betting_started: u64) -> u64 {
+    // * Measure at least a minute into the slot so that the first bet does not look like a spike
+    let seconds_into_slot =
+        (seconds_since_betting_started % DURATION_OF_EACH_SLOT_IN_SECONDS).max(60);
+    bets_in_slot * DURATION_OF_EACH_SLOT_IN_SECONDS / seconds_into_slot
 }
 
 pub type RoomId = u64;
 
-#[derive(CandidType, Clone, Deserialize, Default, Debug, Serialize)]
+// * Encoded through `amount_repr::RoomDetails`
+#[derive(Clone, Deserialize, Default, Debug, Serialize)]
+#[serde(from = "amount_repr::RoomDetails", into = "amount_repr::RoomDetails")]
 pub struct RoomDetails {
     pub bets_made: BTreeMap<BetMaker, BetDetails>,
     pub bet_outcome: RoomBetPossibleOutcomes,
-    pub room_bets_total_pot: u64,
+    pub room_bets_total_pot: u128,
     pub total_hot_bets: u64,
     pub total_not_bets: u64,
+    // * Fixed when the outcome is decided, so resuming or recalculating the room pays the
+    // * same way even if the mode has been switched since. None for rooms settled before
+    // * there were modes, which were all paid out with the multiplier
+    pub payout_mode: Option<HotOrNotPayoutMode>,
+    // * Set once the bets of the room have been moved out to the post's archived bets, which
+    // * leaves `bets_made` empty
+    pub compacted_bets: Option<slot_compaction::CompactedRoomBets>,
 }
 
 pub type BetMaker = Principal;
 
-#[derive(CandidType, Clone, Deserialize, Debug, Serialize)]
+// * Encoded through `amount_repr::BetDetails`
+#[derive(Clone, Deserialize, Debug, Serialize)]
+#[serde(from = "amount_repr::BetDetails", into = "amount_repr::BetDetails")]
 pub struct BetDetails {
     pub amount: u64,
     pub bet_direction: BetDirection,
     pub payout: BetPayout,
     pub bet_maker_canister_id: CanisterId,
+    // * Canister of the bet maker's referrer, who is paid a share of what the bet wins
+    pub referrer_canister_id: Option<CanisterId>,
+}
+
+/// A bet on a post along with where it was placed
+#[derive(CandidType, Clone, Deserialize, Debug)]
+pub struct PostBetHistoryEntry {
+    pub slot_id: SlotId,
+    // * None for bets from shadow banned principals, which are kept out of every room
+    pub room_id: Option<RoomId>,
+    pub bet_maker: BetMaker,
+    pub bet_details: BetDetails,
 }
 
-#[derive(Clone, Deserialize, Debug, CandidType, Serialize, Default)]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
 pub enum BetPayout {
     #[default]
     NotCalculatedYet,
-    Calculated(u64),
+    Calculated(u128),
+}
+
+/// Pots and payouts are kept as u128 so that adding up bets and applying the winnings
+/// multiplier can't overflow, while token balances and the events crediting them are u64.
+/// Anything past `u64::MAX` is clamped to it rather than wrapped.
+pub fn to_token_amount(amount: u128) -> u64 {
+    u64::try_from(amount).unwrap_or(u64::MAX)
 }
 
 #[derive(CandidType, Clone, Default, Debug, Deserialize, Serialize, PartialEq, Eq)]
@@ -147,66 +669,157 @@ pub enum BetOutcomeForBetMaker {
     Draw(u64),
 }
 
+/// Payouts owed to other canisters once a slot has been tabulated
+#[derive(Default, Debug, PartialEq, Eq)]
+pub struct SlotTabulationSettlements {
+    pub house_rake_settlements: Vec<HouseRakeSettlement>,
+    pub referrer_share_settlements: Vec<ReferrerShareSettlement>,
+    pub collaborator_commission_settlements: Vec<CollaboratorCommissionSettlement>,
+    // * Tabulation stopped short of the end of the slot and has to be called again
+    pub has_more_to_tabulate: bool,
+}
+
 impl Post {
     pub fn get_hot_or_not_betting_status_for_this_post(
         &self,
         current_time_when_request_being_made: &SystemTime,
         bet_maker_principal_id: &Principal,
     ) -> BettingStatus {
-        let betting_status =
-            match current_time_when_request_being_made
-                .duration_since(self.created_at)
-                .unwrap()
-                .as_secs()
-            {
-                // * contest is still ongoing
-                0..=TOTAL_DURATION_OF_ALL_SLOTS_IN_SECONDS => {
-                    let started_at = self.created_at;
-                    let numerator = current_time_when_request_being_made
-                        .duration_since(started_at)
-                        .unwrap()
-                        .as_secs();
-
-                    let denominator = DURATION_OF_EACH_SLOT_IN_SECONDS;
-                    let currently_ongoing_slot = ((numerator / denominator) + 1) as u8;
-
-                    let temp_hot_or_not_default = &HotOrNotDetails::default();
-                    let temp_slot_details_default = &SlotDetails::default();
-                    let room_details = &self
-                        .hot_or_not_details
-                        .as_ref()
-                        .unwrap_or(temp_hot_or_not_default)
-                        .slot_history
-                        .get(&currently_ongoing_slot)
-                        .unwrap_or(temp_slot_details_default)
-                        .room_details;
-
-                    let temp_room_details_default = &RoomDetails::default();
-                    let currently_active_room = room_details
-                        .last_key_value()
-                        .unwrap_or((&1, temp_room_details_default));
-                    let number_of_participants = currently_active_room.1.bets_made.len() as u8;
-                    BettingStatus::BettingOpen {
-                        started_at,
-                        number_of_participants,
-                        ongoing_slot: currently_ongoing_slot,
-                        ongoing_room: *currently_active_room.0,
-                        has_this_user_participated_in_this_post: if *bet_maker_principal_id
-                            == Principal::anonymous()
-                        {
-                            None
-                        } else {
-                            Some(self.has_this_principal_already_bet_on_this_post(
-                                bet_maker_principal_id,
-                            ))
-                        },
-                    }
-                }
-                // * contest is over
-                _ => BettingStatus::BettingClosed,
+        // * covers posts whose creator revoked consent mid contest. Slots that already have
+        // * bets still get tabulated, but no new bets are accepted
+        if !self.creator_consent_for_inclusion_in_hot_or_not {
+            return BettingStatus::BettingClosedByCreator;
+        }
+
+        if self.is_betting_paused_for_review() {
+            return BettingStatus::BettingPausedForReview;
+        }
+
+        // * Only scheduled posts are waiting to go live. Any other post from further in the
+        // * future than skew explains is treated as closed below
+        let betting_starts_at = self.get_betting_start_time();
+        if self.publish_at.is_some()
+            && *current_time_when_request_being_made
+                + Duration::from_secs(CLOCK_SKEW_TOLERANCE_IN_SECONDS)
+                < betting_starts_at
+        {
+            return BettingStatus::BettingNotStarted {
+                starts_at: betting_starts_at,
             };
+        }
+
+        let Some(currently_ongoing_slot) = self
+            .get_seconds_since_betting_started(current_time_when_request_being_made)
+            .and_then(get_ongoing_slot)
+        else {
+            // * contest is over, or the post is from further in the future than skew explains
+            return BettingStatus::BettingClosed;
+        };
+
+        let temp_hot_or_not_default = &HotOrNotDetails::default();
+        let temp_slot_details_default = &SlotDetails::default();
+        let slot_details = self
+            .hot_or_not_details
+            .as_ref()
+            .unwrap_or(temp_hot_or_not_default)
+            .slot_history
+            .get(&currently_ongoing_slot)
+            .unwrap_or(temp_slot_details_default);
+
+        let ongoing_room = slot_details.get_ongoing_room_id();
+        let number_of_participants = slot_details
+            .room_details
+            .get(&ongoing_room)
+            .map(|room_details| room_details.bets_made.len() as u8)
+            .unwrap_or_default();
+        BettingStatus::BettingOpen {
+            started_at: self.get_betting_start_time(),
+            number_of_participants,
+            ongoing_slot: currently_ongoing_slot,
+            ongoing_room,
+            has_this_user_participated_in_this_post: if *bet_maker_principal_id
+                == Principal::anonymous()
+            {
+                None
+            } else {
+                Some(self.has_this_principal_already_bet_on_this_post(bet_maker_principal_id))
+            },
+            minimum_bet_amount: self.minimum_bet_amount,
+        }
+    }
+
+    /// Seconds elapsed since betting on this post started, which is when it was created unless
+    /// it was scheduled to go live later. A `current_time` trailing the start by no more than
+    /// [`CLOCK_SKEW_TOLERANCE_IN_SECONDS`] counts as the moment betting started, anything
+    /// further behind than that returns None.
+    pub fn get_seconds_since_betting_started(&self, current_time: &SystemTime) -> Option<u64> {
+        match current_time.duration_since(self.get_betting_start_time()) {
+            Ok(time_since_betting_started) => Some(time_since_betting_started.as_secs()),
+            Err(error)
+                if error.duration() <= Duration::from_secs(CLOCK_SKEW_TOLERANCE_IN_SECONDS) =>
+            {
+                Some(0)
+            }
+            Err(_) => None,
+        }
+    }
+
+    /// When betting in the slot ends and its outcome can be tabulated
+    pub fn get_slot_closing_time(&self, slot_id: SlotId) -> SystemTime {
+        self.get_betting_start_time()
+            + Duration::from_secs(slot_id as u64 * DURATION_OF_EACH_SLOT_IN_SECONDS)
+    }
+
+    /// Compares the betting pace of the ongoing slot against the average number of bets
+    /// per slot in the slots before it. Returns None once betting on the post has closed.
+    pub fn get_slot_heat_indicator(
+        &self,
+        publisher_canister_id: &CanisterId,
+        current_time: &SystemTime,
+    ) -> Option<SlotHeatIndicator> {
+        if !self.creator_consent_for_inclusion_in_hot_or_not || self.is_betting_paused_for_review()
+        {
+            return None;
+        }
+
+        let hot_or_not_details = self.hot_or_not_details.as_ref()?;
 
-        betting_status
+        let seconds_since_betting_started = self.get_seconds_since_betting_started(current_time)?;
+        let ongoing_slot = get_ongoing_slot(seconds_since_betting_started)?;
+
+        let bets_in_slot = hot_or_not_details
+            .slot_history
+            .get(&ongoing_slot)
+            .map(SlotDetails::get_number_of_bets)
+            .unwrap_or(0);
+        let bets_in_previous_slots: u64 = hot_or_not_details
+            .slot_history
+            .range(..ongoing_slot)
+            .map(|(_, slot_details)| slot_details.get_number_of_bets())
+            .sum();
+
+        let projected_bets_in_slot =
+            get_projected_bets_in_slot(bets_in_slot, seconds_since_betting_started);
+        let average_bets_per_previous_slot = if ongoing_slot > 1 {
+            bets_in_previous_slots / (ongoing_slot as u64 - 1)
+        } else {
+            0
+        }
+        .max(1);
+
+        let heat_score = projected_bets_in_slot * 100 / average_bets_per_previous_slot;
+
+        Some(SlotHeatIndicator {
+            publisher_canister_id: *publisher_canister_id,
+            post_id: self.id,
+            slot_id: ongoing_slot,
+            bets_in_slot,
+            heat_score,
+            is_on_fire: bets_in_slot >= MINIMUM_BETS_IN_SLOT_FOR_ON_FIRE
+                && heat_score >= SLOT_HEAT_SCORE_THRESHOLD_FOR_ON_FIRE,
+            valid_until: self.get_betting_start_time()
+                + Duration::from_secs(ongoing_slot as u64 * DURATION_OF_EACH_SLOT_IN_SECONDS),
+        })
     }
 
     pub fn has_this_principal_already_bet_on_this_post(
@@ -215,12 +828,107 @@ impl Post {
     ) -> bool {
         self.hot_or_not_details
             .as_ref()
-            .unwrap()
+            .is_some_and(|hot_or_not_details| {
+                hot_or_not_details
+                    .slot_history
+                    .values()
+                    .any(|slot_details| {
+                        slot_details
+                            .phantom_room_bets
+                            .contains_key(principal_making_bet)
+                            || slot_details.room_details.values().any(|room_details| {
+                                room_details.bets_made.contains_key(principal_making_bet)
+                            })
+                    })
+            })
+    }
+
+    /// Looks up the bettor's entry in the room they were placed in. Once the room has been
+    /// tabulated the outcome and payout come from the room and the bettor's entry in it.
+    pub fn get_user_status_for_this_post(
+        &self,
+        bet_maker_principal_id: &Principal,
+    ) -> UserStatusForSpecificHotOrNotPost {
+        let Some(hot_or_not_details) = self.hot_or_not_details.as_ref() else {
+            return UserStatusForSpecificHotOrNotPost::NotParticipatedYet;
+        };
+
+        let bet_placed = hot_or_not_details
             .slot_history
-            .values()
-            .flat_map(|slot_details| slot_details.room_details.iter())
-            .flat_map(|(_, room_details)| room_details.bets_made.iter())
-            .any(|(principal, _)| principal == principal_making_bet)
+            .iter()
+            .flat_map(|(slot_id, slot_details)| {
+                slot_details
+                    .room_details
+                    .iter()
+                    .map(move |(room_id, room_details)| (*slot_id, *room_id, room_details))
+            })
+            .find_map(|(slot_id, room_id, room_details)| {
+                room_details
+                    .bets_made
+                    .get(bet_maker_principal_id)
+                    .map(|bet_details| (slot_id, room_id, room_details, bet_details))
+            });
+
+        let Some((slot_id, room_id, room_details, bet_details)) = bet_placed else {
+            return hot_or_not_details
+                .slot_history
+                .iter()
+                .find_map(|(slot_id, slot_details)| {
+                    let bet_details = slot_details.phantom_room_bets.get(bet_maker_principal_id)?;
+                    // * Reported the same way as a bet in a room that lost
+                    let bet_detail = BetDetail {
+                        amount: bet_details.amount,
+                        bet_direction: bet_details.bet_direction.clone(),
+                        slot_id: *slot_id,
+                        room_id: slot_details.get_ongoing_room_id(),
+                    };
+                    Some(match bet_details.payout {
+                        BetPayout::NotCalculatedYet => {
+                            UserStatusForSpecificHotOrNotPost::AwaitingResult(bet_detail)
+                        }
+                        BetPayout::Calculated(_) => {
+                            UserStatusForSpecificHotOrNotPost::ResultAnnounced {
+                                bet_detail,
+                                result: BetResult::Lost,
+                                payout_u128: None,
+                            }
+                        }
+                    })
+                })
+                .unwrap_or(UserStatusForSpecificHotOrNotPost::NotParticipatedYet);
+        };
+
+        let bet_detail = BetDetail {
+            amount: bet_details.amount,
+            bet_direction: bet_details.bet_direction.clone(),
+            slot_id,
+            room_id,
+        };
+        let payout = match bet_details.payout {
+            BetPayout::Calculated(payout) => payout,
+            BetPayout::NotCalculatedYet => 0,
+        };
+
+        let (result, payout_u128) = match (&room_details.bet_outcome, &bet_details.bet_direction) {
+            (RoomBetPossibleOutcomes::BetOngoing, _) => {
+                return UserStatusForSpecificHotOrNotPost::AwaitingResult(bet_detail);
+            }
+            (RoomBetPossibleOutcomes::Draw, _) => {
+                (BetResult::Draw(to_token_amount(payout)), Some(payout))
+            }
+            (RoomBetPossibleOutcomes::HotWon, BetDirection::Hot)
+            | (RoomBetPossibleOutcomes::NotWon, BetDirection::Not) => {
+                (BetResult::Won(to_token_amount(payout)), Some(payout))
+            }
+            (RoomBetPossibleOutcomes::HotWon, BetDirection::Not)
+            | (RoomBetPossibleOutcomes::NotWon, BetDirection::Hot) => (BetResult::Lost, None),
+        };
+
+        UserStatusForSpecificHotOrNotPost::ResultAnnounced {
+            bet_detail,
+            result,
+            payout_u128,
+        }
     }
 
     pub fn place_hot_or_not_bet(
@@ -230,6 +938,24 @@ impl Post {
         bet_amount: u64,
         bet_direction: &BetDirection,
         current_time_when_request_being_made: &SystemTime,
+    ) -> Result<BettingStatus, BetOnCurrentlyViewingPostError> {
+        self.place_hot_or_not_bet_with_room_sizing(
+            bet_maker_principal_id,
+            bet_maker_canister_id,
+            bet_amount,
+            bet_direction,
+            current_time_when_request_being_made,
+            &RoomSizingConfig::default(),
+        )
+    }
+
+    /// Checks whether the post would take the bet right now without placing it. Returns the
+    /// betting status the bet maker would see before betting.
+    pub fn validate_hot_or_not_bet(
+        &self,
+        bet_maker_principal_id: &Principal,
+        bet_amount: u64,
+        current_time_when_request_being_made: &SystemTime,
     ) -> Result<BettingStatus, BetOnCurrentlyViewingPostError> {
         if *bet_maker_principal_id == Principal::anonymous() {
             return Err(BetOnCurrentlyViewingPostError::UserNotLoggedIn);
@@ -241,10 +967,16 @@ impl Post {
         );
 
         match betting_status {
-            BettingStatus::BettingClosed => Err(BetOnCurrentlyViewingPostError::BettingClosed),
+            BettingStatus::BettingClosed | BettingStatus::BettingClosedByCreator => {
+                Err(BetOnCurrentlyViewingPostError::BettingClosed)
+            }
+            BettingStatus::BettingPausedForReview => {
+                Err(BetOnCurrentlyViewingPostError::BettingPausedForReview)
+            }
+            BettingStatus::BettingNotStarted { .. } => {
+                Err(BetOnCurrentlyViewingPostError::BettingNotStarted)
+            }
             BettingStatus::BettingOpen {
-                ongoing_slot,
-                ongoing_room,
                 has_this_user_participated_in_this_post,
                 ..
             } => {
@@ -252,194 +984,668 @@ impl Post {
                     return Err(BetOnCurrentlyViewingPostError::UserAlreadyParticipatedInThisPost);
                 }
 
-                let mut hot_or_not_details = self
-                    .hot_or_not_details
-                    .take()
-                    .unwrap_or(HotOrNotDetails::default());
-                let slot_history = hot_or_not_details
-                    .slot_history
-                    .entry(ongoing_slot)
-                    .or_default();
-                let room_detail = slot_history.room_details.entry(ongoing_room).or_default();
-                let bets_made_currently = &mut room_detail.bets_made;
-
-                // * Update bets_made currently
-                if bets_made_currently.len() < 100 {
-                    bets_made_currently.insert(
-                        *bet_maker_principal_id,
-                        BetDetails {
-                            amount: bet_amount,
-                            bet_direction: bet_direction.clone(),
-                            payout: BetPayout::default(),
-                            bet_maker_canister_id: *bet_maker_canister_id,
-                        },
-                    );
-                    room_detail.room_bets_total_pot += bet_amount;
-                } else {
-                    let new_room_number = ongoing_room + 1;
-                    let mut bets_made = BTreeMap::default();
-                    bets_made.insert(
-                        *bet_maker_principal_id,
-                        BetDetails {
-                            amount: bet_amount,
-                            bet_direction: bet_direction.clone(),
-                            payout: BetPayout::default(),
-                            bet_maker_canister_id: *bet_maker_canister_id,
-                        },
-                    );
-                    slot_history.room_details.insert(
-                        new_room_number,
-                        RoomDetails {
-                            bets_made,
-                            room_bets_total_pot: bet_amount,
-                            ..Default::default()
-                        },
-                    );
-                }
-
-                // * Update aggregate stats
-                hot_or_not_details.aggregate_stats.total_amount_bet += bet_amount;
-                let mut last_room_entry = slot_history.room_details.last_entry().unwrap();
-                match bet_direction {
-                    BetDirection::Hot => {
-                        hot_or_not_details.aggregate_stats.total_number_of_hot_bets += 1;
-                        last_room_entry.get_mut().total_hot_bets += 1;
-                    }
-                    BetDirection::Not => {
-                        hot_or_not_details.aggregate_stats.total_number_of_not_bets += 1;
-                        last_room_entry.get_mut().total_not_bets += 1;
-                    }
+                if bet_amount < self.minimum_bet_amount {
+                    return Err(BetOnCurrentlyViewingPostError::BetAmountBelowPostMinimum);
                 }
 
-                self.hot_or_not_details = Some(hot_or_not_details);
-
-                let slot_history = &self.hot_or_not_details.as_ref().unwrap().slot_history;
-                let started_at = self.created_at;
-                let number_of_participants = slot_history
-                    .last_key_value()
-                    .unwrap()
-                    .1
-                    .room_details
-                    .last_key_value()
-                    .unwrap()
-                    .1
-                    .bets_made
-                    .len() as u8;
-                let ongoing_slot = *slot_history.last_key_value().unwrap().0;
-                let ongoing_room = *slot_history
-                    .last_key_value()
-                    .unwrap()
-                    .1
-                    .room_details
-                    .last_key_value()
-                    .unwrap()
-                    .0;
-                Ok(BettingStatus::BettingOpen {
-                    started_at,
-                    number_of_participants,
-                    ongoing_slot,
-                    ongoing_room,
-                    has_this_user_participated_in_this_post: Some(true),
-                })
+                Ok(betting_status)
             }
         }
     }
 
-    pub fn tabulate_hot_or_not_outcome_for_slot(
+    /// Places the bet in the ongoing room, or starts a new room if the ongoing one has
+    /// reached the size the slot's betting velocity calls for
+    pub fn place_hot_or_not_bet_with_room_sizing(
         &mut self,
-        post_canister_id: &CanisterId,
-        slot_id: &u8,
-        token_balance: &mut TokenBalance,
-        current_time: &SystemTime,
-    ) {
-        let hot_or_not_details = self.hot_or_not_details.as_mut();
-
-        if hot_or_not_details.is_none() {
-            return;
-        }
-
-        let slot_history = hot_or_not_details.unwrap().slot_history.get_mut(slot_id);
-
+        bet_maker_principal_id: &Principal,
+        bet_maker_canister_id: &CanisterId,
+        bet_amount: u64,
+        bet_direction: &BetDirection,
+        current_time_when_request_being_made: &SystemTime,
+        room_sizing_config: &RoomSizingConfig,
+    ) -> Result<BettingStatus, BetOnCurrentlyViewingPostError> {
+        // * validation only lets open betting through
+        let BettingStatus::BettingOpen { ongoing_slot, .. } = self.validate_hot_or_not_bet(
+            bet_maker_principal_id,
+            bet_amount,
+            current_time_when_request_being_made,
+        )?
+        else {
+            return Err(BetOnCurrentlyViewingPostError::BettingClosed);
+        };
+
+        let seconds_since_betting_started = self
+            .get_seconds_since_betting_started(current_time_when_request_being_made)
+            .unwrap_or_default();
+        let betting_started_at = self.get_betting_start_time();
+        let hot_or_not_details = self
+            .hot_or_not_details
+            .get_or_insert_with(HotOrNotDetails::default);
+        let slot_history = hot_or_not_details
+            .slot_history
+            .entry(ongoing_slot)
+            .or_default();
+        let room_size = room_sizing_config.get_room_size(get_projected_bets_in_slot(
+            slot_history.get_number_of_bets(),
+            seconds_since_betting_started,
+        ));
+        let room_id = slot_history.assign_room_for_new_bet(room_size);
+        let room_detail = slot_history.room_details.entry(room_id).or_default();
+
+        room_detail.bets_made.insert(
+            *bet_maker_principal_id,
+            BetDetails {
+                amount: bet_amount,
+                bet_direction: bet_direction.clone(),
+                payout: BetPayout::default(),
+                bet_maker_canister_id: *bet_maker_canister_id,
+                referrer_canister_id: None,
+            },
+        );
+        room_detail.room_bets_total_pot += bet_amount as u128;
+
+        // * Update aggregate stats
+        hot_or_not_details.aggregate_stats.total_amount_bet += bet_amount as u128;
+        hot_or_not_details
+            .bet_amount_histogram
+            .record_bet(bet_amount, bet_direction);
+        match bet_direction {
+            BetDirection::Hot => {
+                hot_or_not_details.aggregate_stats.total_number_of_hot_bets += 1;
+                room_detail.total_hot_bets += 1;
+            }
+            BetDirection::Not => {
+                hot_or_not_details.aggregate_stats.total_number_of_not_bets += 1;
+                room_detail.total_not_bets += 1;
+            }
+        }
+
+        Ok(BettingStatus::BettingOpen {
+            started_at: betting_started_at,
+            number_of_participants: room_detail.bets_made.len() as u8,
+            ongoing_slot,
+            ongoing_room: room_id,
+            has_this_user_participated_in_this_post: Some(true),
+            minimum_bet_amount: self.minimum_bet_amount,
+        })
+    }
+
+    /// Takes the bet back out of the room it was placed in, as long as that room's slot is
+    /// still ongoing and no more than [`BET_CANCELLATION_WINDOW_IN_SECONDS`] in. Bets from
+    /// shadow banned principals are taken out of the phantom room the same way. Returns the
+    /// bet that was taken back along with its room, None for the phantom room.
+    pub fn cancel_hot_or_not_bet(
+        &mut self,
+        bet_maker_principal_id: &Principal,
+        bet_maker_canister_id: &CanisterId,
+        current_time: &SystemTime,
+    ) -> Result<(Option<RoomId>, BetDetails), BetOnCurrentlyViewingPostError> {
+        let seconds_since_betting_started = self
+            .get_seconds_since_betting_started(current_time)
+            .ok_or(BetOnCurrentlyViewingPostError::BetCancellationWindowClosed)?;
+        let ongoing_slot = get_ongoing_slot(seconds_since_betting_started);
+
+        let hot_or_not_details = self
+            .hot_or_not_details
+            .as_mut()
+            .ok_or(BetOnCurrentlyViewingPostError::BetNotFound)?;
+
+        let (slot_id, room_id) = hot_or_not_details
+            .slot_history
+            .iter()
+            .find_map(|(slot_id, slot_details)| {
+                if let Some(bet_details) =
+                    slot_details.phantom_room_bets.get(bet_maker_principal_id)
+                {
+                    return Some((*slot_id, None, bet_details));
+                }
+
+                slot_details
+                    .room_details
+                    .iter()
+                    .find_map(|(room_id, room_details)| {
+                        room_details
+                            .bets_made
+                            .get(bet_maker_principal_id)
+                            .map(|bet_details| (*slot_id, Some(*room_id), bet_details))
+                    })
+            })
+            .filter(|(_, _, bet_details)| {
+                bet_details.bet_maker_canister_id == *bet_maker_canister_id
+            })
+            .map(|(slot_id, room_id, _)| (slot_id, room_id))
+            .ok_or(BetOnCurrentlyViewingPostError::BetNotFound)?;
+
+        if ongoing_slot != Some(slot_id)
+            || seconds_since_betting_started % DURATION_OF_EACH_SLOT_IN_SECONDS
+                >= BET_CANCELLATION_WINDOW_IN_SECONDS
+        {
+            return Err(BetOnCurrentlyViewingPostError::BetCancellationWindowClosed);
+        }
+
+        let slot_details = hot_or_not_details.slot_history.get_mut(&slot_id).unwrap();
+
+        let Some(room_id) = room_id else {
+            let bet_details = slot_details
+                .phantom_room_bets
+                .remove(bet_maker_principal_id)
+                .unwrap();
+            return Ok((None, bet_details));
+        };
+
+        let room_details = slot_details.room_details.get_mut(&room_id).unwrap();
+        let bet_details = room_details
+            .bets_made
+            .remove(bet_maker_principal_id)
+            .unwrap();
+        room_details.room_bets_total_pot -= bet_details.amount as u128;
+
+        let aggregate_stats = &mut hot_or_not_details.aggregate_stats;
+        aggregate_stats.total_amount_bet -= bet_details.amount as u128;
+        match bet_details.bet_direction {
+            BetDirection::Hot => {
+                aggregate_stats.total_number_of_hot_bets -= 1;
+                room_details.total_hot_bets -= 1;
+            }
+            BetDirection::Not => {
+                aggregate_stats.total_number_of_not_bets -= 1;
+                room_details.total_not_bets -= 1;
+            }
+        }
+        hot_or_not_details
+            .bet_amount_histogram
+            .remove_bet(bet_details.amount, &bet_details.bet_direction);
+
+        Ok((Some(room_id), bet_details))
+    }
+
+    /// Records the referrer of the bet `bet_maker_principal_id` placed in `slot_id`, so that
+    /// tabulation pays them their share of what it wins
+    pub fn set_referrer_of_bet(
+        &mut self,
+        slot_id: SlotId,
+        bet_maker_principal_id: &Principal,
+        referrer_canister_id: CanisterId,
+    ) {
+        let bet_details = self
+            .hot_or_not_details
+            .as_mut()
+            .and_then(|hot_or_not_details| hot_or_not_details.slot_history.get_mut(&slot_id))
+            .and_then(|slot_details| {
+                slot_details
+                    .room_details
+                    .values_mut()
+                    .find_map(|room_detail| room_detail.bets_made.get_mut(bet_maker_principal_id))
+            });
+
+        if let Some(bet_details) = bet_details {
+            bet_details.referrer_canister_id = Some(referrer_canister_id);
+        }
+    }
+
+    /// Accepts a bet from a shadow banned principal exactly like a real bet is accepted, but
+    /// puts it in the slot's phantom room instead of the ongoing room
+    pub fn place_hot_or_not_bet_in_phantom_room(
+        &mut self,
+        bet_maker_principal_id: &Principal,
+        bet_maker_canister_id: &CanisterId,
+        bet_amount: u64,
+        bet_direction: &BetDirection,
+        current_time_when_request_being_made: &SystemTime,
+    ) -> Result<BettingStatus, BetOnCurrentlyViewingPostError> {
+        if *bet_maker_principal_id == Principal::anonymous() {
+            return Err(BetOnCurrentlyViewingPostError::UserNotLoggedIn);
+        }
+
+        let BettingStatus::BettingOpen {
+            started_at,
+            number_of_participants,
+            ongoing_slot,
+            ongoing_room,
+            has_this_user_participated_in_this_post,
+            minimum_bet_amount,
+        } = self.get_hot_or_not_betting_status_for_this_post(
+            current_time_when_request_being_made,
+            bet_maker_principal_id,
+        )
+        else {
+            return Err(BetOnCurrentlyViewingPostError::BettingClosed);
+        };
+
+        if has_this_user_participated_in_this_post.unwrap() {
+            return Err(BetOnCurrentlyViewingPostError::UserAlreadyParticipatedInThisPost);
+        }
+
+        if bet_amount < minimum_bet_amount {
+            return Err(BetOnCurrentlyViewingPostError::BetAmountBelowPostMinimum);
+        }
+
+        self.hot_or_not_details
+            .get_or_insert_with(HotOrNotDetails::default)
+            .slot_history
+            .entry(ongoing_slot)
+            .or_default()
+            .phantom_room_bets
+            .insert(
+                *bet_maker_principal_id,
+                BetDetails {
+                    amount: bet_amount,
+                    bet_direction: bet_direction.clone(),
+                    payout: BetPayout::default(),
+                    bet_maker_canister_id: *bet_maker_canister_id,
+                    referrer_canister_id: None,
+                },
+            );
+
+        // * Looks the same as the status returned for a bet placed in the ongoing room
+        Ok(BettingStatus::BettingOpen {
+            started_at,
+            number_of_participants: number_of_participants.saturating_add(1),
+            ongoing_slot,
+            ongoing_room,
+            has_this_user_participated_in_this_post: Some(true),
+            minimum_bet_amount,
+        })
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn tabulate_hot_or_not_outcome_for_slot(
+        &mut self,
+        post_canister_id: &CanisterId,
+        slot_id: &u8,
+        token_balance: &mut TokenBalance,
+        house_rake_percentage: u64,
+        referrer_share_percentage: u64,
+        payout_mode: HotOrNotPayoutMode,
+        current_time: &SystemTime,
+    ) -> SlotTabulationSettlements {
+        self.tabulate_hot_or_not_outcome_for_slot_within_budget(
+            post_canister_id,
+            slot_id,
+            token_balance,
+            house_rake_percentage,
+            referrer_share_percentage,
+            payout_mode,
+            current_time,
+            &|| true,
+        )
+    }
+
+    /// Tabulates rooms in order until `is_within_budget` says otherwise. Where it stopped
+    /// is saved in the slot's tabulation cursor, so that the next call picks up from there,
+    /// even if it stopped halfway through paying out a room.
+    #[allow(clippy::too_many_arguments)]
+    pub fn tabulate_hot_or_not_outcome_for_slot_within_budget(
+        &mut self,
+        post_canister_id: &CanisterId,
+        slot_id: &u8,
+        token_balance: &mut TokenBalance,
+        house_rake_percentage: u64,
+        referrer_share_percentage: u64,
+        payout_mode: HotOrNotPayoutMode,
+        current_time: &SystemTime,
+        is_within_budget: &dyn Fn() -> bool,
+    ) -> SlotTabulationSettlements {
+        let mut settlements = SlotTabulationSettlements::default();
+        let hot_or_not_details = self.hot_or_not_details.as_mut();
+
+        if hot_or_not_details.is_none() {
+            return settlements;
+        }
+
+        let slot_history = hot_or_not_details.unwrap().slot_history.get_mut(slot_id);
+
         if slot_history.is_none() {
-            return;
+            return settlements;
         }
 
+        let slot_history = slot_history.unwrap();
+
+        // * Rakes out of range are rejected when installing, but even so the rake must never
+        // * leave winners with less than the platform allows
+        let house_rake_percentage = house_rake_percentage.min(MAXIMUM_HOUSE_RAKE_PERCENTAGE);
+        let percentage_of_bet_paid_out = (100 - HOT_OR_NOT_BET_CREATOR_COMMISSION_PERCENTAGE)
+            .saturating_sub(house_rake_percentage);
+        let post_id = self.id;
+        let collaborator_shares = &self.collaborator_shares;
+
+        // * Phantom room bets always lose and never add to any pot
         slot_history
-            .unwrap()
-            .room_details
-            .iter_mut()
-            .for_each(|(room_id, room_detail)| {
-                if room_detail.bet_outcome == RoomBetPossibleOutcomes::BetOngoing {
-                    // * Figure out which side won
-                    match room_detail.total_hot_bets.cmp(&room_detail.total_not_bets) {
-                        Ordering::Greater => {
-                            room_detail.bet_outcome = RoomBetPossibleOutcomes::HotWon;
-                        }
-                        Ordering::Less => {
-                            room_detail.bet_outcome = RoomBetPossibleOutcomes::NotWon;
-                        }
-                        Ordering::Equal => room_detail.bet_outcome = RoomBetPossibleOutcomes::Draw,
+            .phantom_room_bets
+            .values_mut()
+            .for_each(|bet_details| bet_details.payout = BetPayout::Calculated(0));
+
+        let tabulation_cursor = &mut slot_history.tabulation_cursor;
+        let (first_room_id, mut last_settled_bet_maker) = match tabulation_cursor.take() {
+            Some(TabulationCursor {
+                room_id,
+                last_settled_bet_maker,
+            }) => (room_id, last_settled_bet_maker),
+            None => (RoomId::MIN, None),
+        };
+
+        for (room_id, room_detail) in slot_history.room_details.range_mut(first_room_id..) {
+            if !is_within_budget() {
+                *tabulation_cursor = Some(TabulationCursor {
+                    room_id: *room_id,
+                    last_settled_bet_maker,
+                });
+                settlements.has_more_to_tabulate = true;
+                return settlements;
+            }
+
+            if room_detail.bet_outcome == RoomBetPossibleOutcomes::BetOngoing {
+                // * Figure out which side won
+                match room_detail.total_hot_bets.cmp(&room_detail.total_not_bets) {
+                    Ordering::Greater => {
+                        room_detail.bet_outcome = RoomBetPossibleOutcomes::HotWon;
+                    }
+                    Ordering::Less => {
+                        room_detail.bet_outcome = RoomBetPossibleOutcomes::NotWon;
+                    }
+                    Ordering::Equal => room_detail.bet_outcome = RoomBetPossibleOutcomes::Draw,
+                }
+                room_detail.payout_mode.get_or_insert(payout_mode);
+
+                let room_pot_total_amount = to_token_amount(room_detail.room_bets_total_pot);
+
+                // * Commission is 10% of total pot, split with the post's collaborators
+                let commission_amount = to_token_amount(
+                    room_detail.room_bets_total_pot
+                        * HOT_OR_NOT_BET_CREATOR_COMMISSION_PERCENTAGE as u128
+                        / 100,
+                );
+                let mut creator_commission_amount = commission_amount;
+
+                collaborator_shares.iter().for_each(|collaborator_share| {
+                    let share_amount = get_collaborator_share_of_commission(
+                        commission_amount,
+                        collaborator_share.percentage,
+                    );
+                    if share_amount == 0 {
+                        return;
                     }
 
-                    // * Reward creator with commission. Commission is 10% of total pot
-                    token_balance.handle_token_event(TokenEvent::HotOrNotOutcomePayout {
-                        amount: room_detail.room_bets_total_pot
-                            * HOT_OR_NOT_BET_CREATOR_COMMISSION_PERCENTAGE
-                            / 100,
-                        details: HotOrNotOutcomePayoutEvent::CommissionFromHotOrNotBet {
+                    creator_commission_amount -= share_amount;
+                    settlements.collaborator_commission_settlements.push(
+                        CollaboratorCommissionSettlement {
+                            collaborator_principal_id: collaborator_share.collaborator_principal_id,
                             post_canister_id: *post_canister_id,
-                            post_id: self.id,
+                            post_id,
                             slot_id: *slot_id,
                             room_id: *room_id,
-                            room_pot_total_amount: room_detail.room_bets_total_pot,
+                            room_pot_total_amount,
+                            share_amount,
+                            number_of_failed_attempts: 0,
                         },
-                        timestamp: *current_time,
+                    );
+                });
+
+                // * Reward creator with whatever is left of the commission
+                token_balance.handle_token_event(TokenEvent::HotOrNotOutcomePayout {
+                    amount: creator_commission_amount,
+                    details: HotOrNotOutcomePayoutEvent::CommissionFromHotOrNotBet {
+                        post_canister_id: *post_canister_id,
+                        post_id,
+                        slot_id: *slot_id,
+                        room_id: *room_id,
+                        room_pot_total_amount,
+                    },
+                    timestamp: *current_time,
+                });
+
+                // * Set aside the house rake for the treasury canister. It is worked out from
+                // * the same winnings the payouts are cut from, so the treasury gets exactly
+                // * what the bettors give up.
+                let winnings_ratio = get_winnings_ratio(room_detail);
+                let rake_amount = room_detail
+                    .bets_made
+                    .values()
+                    .filter_map(|bet_details| {
+                        get_percentage_of_bet_winnings(
+                            &room_detail.bet_outcome,
+                            bet_details,
+                            house_rake_percentage,
+                            winnings_ratio,
+                        )
+                    })
+                    .fold(0_u64, |rake_amount, bet_rake_amount| {
+                        rake_amount.saturating_add(to_token_amount(bet_rake_amount))
                     });
-
-                    // * Reward individual participants
-                    room_detail
-                        .bets_made
-                        .iter_mut()
-                        .for_each(|(_user_id, bet_details)| {
-                            match &room_detail.bet_outcome {
-                                RoomBetPossibleOutcomes::HotWon => {
-                                    if bet_details.bet_direction == BetDirection::Hot {
-                                        bet_details.payout = BetPayout::Calculated(
-                                            bet_details.amount
-                                                * HOT_OR_NOT_BET_WINNINGS_MULTIPLIER
-                                                * (100
-                                                    - HOT_OR_NOT_BET_CREATOR_COMMISSION_PERCENTAGE)
-                                                / 100,
-                                        );
-                                    } else {
-                                        bet_details.payout = BetPayout::Calculated(0);
-                                    }
-                                }
-                                RoomBetPossibleOutcomes::NotWon => {
-                                    if bet_details.bet_direction == BetDirection::Not {
-                                        bet_details.payout = BetPayout::Calculated(
-                                            bet_details.amount
-                                                * HOT_OR_NOT_BET_WINNINGS_MULTIPLIER
-                                                * (100
-                                                    - HOT_OR_NOT_BET_CREATOR_COMMISSION_PERCENTAGE)
-                                                / 100,
-                                        );
-                                    } else {
-                                        bet_details.payout = BetPayout::Calculated(0);
-                                    }
-                                }
-                                RoomBetPossibleOutcomes::Draw => {
-                                    bet_details.payout = BetPayout::Calculated(
-                                        bet_details.amount
-                                            * (100 - HOT_OR_NOT_BET_CREATOR_COMMISSION_PERCENTAGE)
-                                            / 100,
-                                    );
-                                }
-                                RoomBetPossibleOutcomes::BetOngoing => {}
-                            };
+                if rake_amount > 0 {
+                    settlements
+                        .house_rake_settlements
+                        .push(HouseRakeSettlement {
+                            post_canister_id: *post_canister_id,
+                            post_id,
+                            slot_id: *slot_id,
+                            room_id: *room_id,
+                            room_pot_total_amount,
+                            rake_amount,
+                            number_of_failed_attempts: 0,
                         });
                 }
+            }
+
+            let winnings_ratio = get_winnings_ratio(room_detail);
+
+            // * Reward individual participants, resuming after the last one paid out if the
+            // * budget ran out partway through this room
+            let bets_to_settle = match last_settled_bet_maker.take() {
+                Some(last_settled_bet_maker) => room_detail
+                    .bets_made
+                    .range_mut((Excluded(last_settled_bet_maker), Unbounded)),
+                None => room_detail.bets_made.range_mut(..),
+            };
+
+            for (bet_maker, bet_details) in bets_to_settle {
+                if !is_within_budget() {
+                    *tabulation_cursor = Some(TabulationCursor {
+                        room_id: *room_id,
+                        last_settled_bet_maker,
+                    });
+                    settlements.has_more_to_tabulate = true;
+                    return settlements;
+                }
+
+                if matches!(bet_details.payout, BetPayout::NotCalculatedYet) {
+                    // * The bet maker's referrer is paid their share out of what the bet wins
+                    let referrer_share_percentage =
+                        match (bet_details.referrer_canister_id, &room_detail.bet_outcome) {
+                            (
+                                Some(_),
+                                RoomBetPossibleOutcomes::HotWon | RoomBetPossibleOutcomes::NotWon,
+                            ) => referrer_share_percentage.min(percentage_of_bet_paid_out),
+                            _ => 0,
+                        };
+                    bet_details.payout = get_bet_payout(
+                        &room_detail.bet_outcome,
+                        bet_details,
+                        percentage_of_bet_paid_out - referrer_share_percentage,
+                        winnings_ratio,
+                    );
+
+                    let share_amount = get_percentage_of_bet_winnings(
+                        &room_detail.bet_outcome,
+                        bet_details,
+                        referrer_share_percentage,
+                        winnings_ratio,
+                    )
+                    .map_or(0, to_token_amount);
+                    if let (Some(referrer_canister_id), true) =
+                        (bet_details.referrer_canister_id, share_amount > 0)
+                    {
+                        settlements
+                            .referrer_share_settlements
+                            .push(ReferrerShareSettlement {
+                                referrer_canister_id,
+                                referee_principal_id: *bet_maker,
+                                referee_canister_id: Some(bet_details.bet_maker_canister_id),
+                                post_canister_id: *post_canister_id,
+                                post_id,
+                                slot_id: *slot_id,
+                                room_id: *room_id,
+                                share_amount,
+                                number_of_failed_attempts: 0,
+                            });
+                    }
+                }
+                last_settled_bet_maker = Some(*bet_maker);
+            }
+            last_settled_bet_maker = None;
+        }
+
+        settlements
+    }
+
+    /// Top ups owed to the winners of every room in the slot that settled below
+    /// `room_pot_floor`. Rooms that ended in a draw have no winners to top up.
+    pub fn get_pot_insurance_claims_for_slot(
+        &self,
+        post_canister_id: &CanisterId,
+        slot_id: &SlotId,
+        room_pot_floor: u64,
+        current_time: &SystemTime,
+    ) -> Vec<PotInsuranceClaim> {
+        let Some(slot_details) = self
+            .hot_or_not_details
+            .as_ref()
+            .and_then(|hot_or_not_details| hot_or_not_details.slot_history.get(slot_id))
+        else {
+            return vec![];
+        };
+
+        slot_details
+            .room_details
+            .iter()
+            .filter(|(_, room_detail)| {
+                matches!(
+                    room_detail.bet_outcome,
+                    RoomBetPossibleOutcomes::HotWon | RoomBetPossibleOutcomes::NotWon
+                )
+            })
+            .filter_map(|(room_id, room_detail)| {
+                let top_ups: Vec<PotInsuranceTopUp> = room_detail
+                    .bets_made
+                    .values()
+                    .filter_map(|bet_details| {
+                        let BetPayout::Calculated(payout) = bet_details.payout else {
+                            return None;
+                        };
+                        let amount = get_pot_insurance_top_up_amount(
+                            to_token_amount(payout),
+                            to_token_amount(room_detail.room_bets_total_pot),
+                            room_pot_floor,
+                        );
+
+                        (amount > 0).then_some(PotInsuranceTopUp {
+                            bet_maker_canister_id: bet_details.bet_maker_canister_id,
+                            amount,
+                        })
+                    })
+                    .collect();
+
+                (!top_ups.is_empty()).then(|| PotInsuranceClaim {
+                    post_canister_id: *post_canister_id,
+                    post_id: self.id,
+                    slot_id: *slot_id,
+                    room_id: *room_id,
+                    room_pot_total_amount: to_token_amount(room_detail.room_bets_total_pot),
+                    top_ups,
+                    number_of_failed_attempts: 0,
+                    tabulated_at: Some(*current_time),
+                })
+            })
+            .collect()
+    }
+}
+
+/// What a winning stake is multiplied by before the commission and rake come off, as a
+/// numerator and denominator
+fn get_winnings_ratio(room_detail: &RoomDetails) -> (u128, u128) {
+    let winning_direction = match room_detail.bet_outcome {
+        RoomBetPossibleOutcomes::HotWon => BetDirection::Hot,
+        RoomBetPossibleOutcomes::NotWon => BetDirection::Not,
+        RoomBetPossibleOutcomes::Draw | RoomBetPossibleOutcomes::BetOngoing => return (1, 1),
+    };
+
+    match room_detail.payout_mode.unwrap_or_default() {
+        HotOrNotPayoutMode::Multiplier => (HOT_OR_NOT_BET_WINNINGS_MULTIPLIER as u128, 1),
+        HotOrNotPayoutMode::Parimutuel => {
+            // * Summed from the bets rather than taken from the pot, so that the winners never
+            // * split more than was staked in the room
+            let (winning_side_pot, room_pot) = room_detail.bets_made.values().fold(
+                (0_u128, 0_u128),
+                |(winning_side_pot, room_pot), bet_details| {
+                    let amount = bet_details.amount as u128;
+                    if bet_details.bet_direction == winning_direction {
+                        (winning_side_pot + amount, room_pot + amount)
+                    } else {
+                        (winning_side_pot, room_pot + amount)
+                    }
+                },
+            );
+
+            if winning_side_pot == 0 {
+                return (1, 1);
+            }
+
+            (room_pot, winning_side_pot)
+        }
+    }
+}
+
+/// `percentage` of what a bet wins before the commission and rake are cut from it. None
+/// while the room is still ongoing.
+fn get_percentage_of_bet_winnings(
+    room_outcome: &RoomBetPossibleOutcomes,
+    bet_details: &BetDetails,
+    percentage: u64,
+    (winnings_numerator, winnings_denominator): (u128, u128),
+) -> Option<u128> {
+    let winnings = || {
+        (bet_details.amount as u128)
+            .saturating_mul(winnings_numerator)
+            .saturating_mul(percentage as u128)
+            / (winnings_denominator * 100)
+    };
+
+    match room_outcome {
+        RoomBetPossibleOutcomes::HotWon => {
+            Some(if bet_details.bet_direction == BetDirection::Hot {
+                winnings()
+            } else {
+                0
             })
+        }
+        RoomBetPossibleOutcomes::NotWon => {
+            Some(if bet_details.bet_direction == BetDirection::Not {
+                winnings()
+            } else {
+                0
+            })
+        }
+        RoomBetPossibleOutcomes::Draw => {
+            Some(bet_details.amount as u128 * percentage as u128 / 100)
+        }
+        RoomBetPossibleOutcomes::BetOngoing => None,
+    }
+}
+
+fn get_bet_payout(
+    room_outcome: &RoomBetPossibleOutcomes,
+    bet_details: &BetDetails,
+    percentage_of_bet_paid_out: u64,
+    winnings_ratio: (u128, u128),
+) -> BetPayout {
+    match get_percentage_of_bet_winnings(
+        room_outcome,
+        bet_details,
+        percentage_of_bet_paid_out,
+        winnings_ratio,
+    ) {
+        Some(payout) => BetPayout::Calculated(payout),
+        None => BetPayout::NotCalculatedYet,
     }
 }
 
@@ -449,9 +1655,14 @@ mod test {
 
     use test_utils::setup::test_constants::{
         get_mock_user_alice_canister_id, get_mock_user_alice_principal_id,
+        get_mock_user_bob_principal_id, get_mock_user_charlie_principal_id,
     };
 
-    use crate::canister_specific::individual_user_template::types::post::PostDetailsFromFrontend;
+    use crate::canister_specific::individual_user_template::types::{
+        collaborator::CollaboratorShare,
+        error::ReportPostError,
+        post::{PostDetailsFromFrontend, MAXIMUM_UNRESOLVED_REPORTS_BEFORE_BETTING_IS_PAUSED},
+    };
 
     use super::*;
 
@@ -464,6 +1675,9 @@ mod test {
                 hashtags: vec!["doggo".into(), "pupper".into()],
                 video_uid: "abcd#1234".into(),
                 creator_consent_for_inclusion_in_hot_or_not: true,
+                collaborator_shares: None,
+                title: None,
+                category: None,
             },
             &SystemTime::now(),
         );
@@ -492,6 +1706,7 @@ mod test {
                 ongoing_slot: 1,
                 ongoing_room: 1,
                 has_this_user_participated_in_this_post: None,
+                minimum_bet_amount: 0,
             }
         );
 
@@ -512,6 +1727,7 @@ mod test {
                 ongoing_slot: 3,
                 ongoing_room: 1,
                 has_this_user_participated_in_this_post: None,
+                minimum_bet_amount: 0,
             }
         );
 
@@ -546,6 +1762,7 @@ mod test {
                 ongoing_slot: 3,
                 ongoing_room: 1,
                 has_this_user_participated_in_this_post: Some(true),
+                minimum_bet_amount: 0,
             }
         );
 
@@ -596,6 +1813,7 @@ mod test {
                 ongoing_slot: 3,
                 ongoing_room: 2,
                 has_this_user_participated_in_this_post: Some(true),
+                minimum_bet_amount: 0,
             }
         );
 
@@ -630,6 +1848,7 @@ mod test {
                 ongoing_slot: 3,
                 ongoing_room: 2,
                 has_this_user_participated_in_this_post: Some(true),
+                minimum_bet_amount: 0,
             }
         );
 
@@ -664,10 +1883,119 @@ mod test {
                 ongoing_slot: 5,
                 ongoing_room: 1,
                 has_this_user_participated_in_this_post: Some(true),
+                minimum_bet_amount: 0,
             }
         );
     }
 
+    #[test]
+    fn test_get_hot_or_not_betting_status_for_this_post_at_slot_edges() {
+        let created_at = SystemTime::now();
+        let post = Post::new(
+            0,
+            &PostDetailsFromFrontend {
+                description: "Doggos and puppers".into(),
+                hashtags: vec!["doggo".into(), "pupper".into()],
+                video_uid: "abcd#1234".into(),
+                creator_consent_for_inclusion_in_hot_or_not: true,
+                collaborator_shares: None,
+                title: None,
+                category: None,
+            },
+            &created_at,
+        );
+
+        let get_ongoing_slot_at = |current_time: SystemTime| match post
+            .get_hot_or_not_betting_status_for_this_post(&current_time, &Principal::anonymous())
+        {
+            BettingStatus::BettingOpen { ongoing_slot, .. } => Some(ongoing_slot),
+            _ => None,
+        };
+
+        // * clock of the canister asking trails the one the post was created on
+        assert_eq!(
+            get_ongoing_slot_at(created_at - Duration::from_secs(CLOCK_SKEW_TOLERANCE_IN_SECONDS)),
+            Some(1)
+        );
+        assert_eq!(
+            get_ongoing_slot_at(
+                created_at - Duration::from_secs(CLOCK_SKEW_TOLERANCE_IN_SECONDS + 1)
+            ),
+            None
+        );
+
+        assert_eq!(get_ongoing_slot_at(created_at), Some(1));
+        assert_eq!(
+            get_ongoing_slot_at(
+                created_at + Duration::from_secs(DURATION_OF_EACH_SLOT_IN_SECONDS - 1)
+            ),
+            Some(1)
+        );
+        assert_eq!(
+            get_ongoing_slot_at(created_at + Duration::from_secs(DURATION_OF_EACH_SLOT_IN_SECONDS)),
+            Some(2)
+        );
+        assert_eq!(
+            get_ongoing_slot_at(
+                created_at + Duration::from_secs(TOTAL_DURATION_OF_ALL_SLOTS_IN_SECONDS - 1)
+            ),
+            Some(MAXIMUM_NUMBER_OF_SLOTS)
+        );
+        assert_eq!(
+            get_ongoing_slot_at(
+                created_at + Duration::from_secs(TOTAL_DURATION_OF_ALL_SLOTS_IN_SECONDS)
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn test_place_hot_or_not_bet_with_clock_skew() {
+        let created_at = SystemTime::now();
+        let mut post = Post::new(
+            0,
+            &PostDetailsFromFrontend {
+                description: "Doggos and puppers".into(),
+                hashtags: vec!["doggo".into(), "pupper".into()],
+                video_uid: "abcd#1234".into(),
+                creator_consent_for_inclusion_in_hot_or_not: true,
+                collaborator_shares: None,
+                title: None,
+                category: None,
+            },
+            &created_at,
+        );
+
+        assert_eq!(
+            post.place_hot_or_not_bet(
+                &get_mock_user_alice_principal_id(),
+                &get_mock_user_alice_canister_id(),
+                100,
+                &BetDirection::Hot,
+                &(created_at - Duration::from_secs(5)),
+            ),
+            Ok(BettingStatus::BettingOpen {
+                started_at: created_at,
+                number_of_participants: 1,
+                ongoing_slot: 1,
+                ongoing_room: 1,
+                has_this_user_participated_in_this_post: Some(true),
+                minimum_bet_amount: 0,
+            })
+        );
+
+        assert_eq!(
+            post.place_hot_or_not_bet(
+                &get_mock_user_bob_principal_id(),
+                &get_mock_user_alice_canister_id(),
+                100,
+                &BetDirection::Hot,
+                &(created_at - Duration::from_secs(CLOCK_SKEW_TOLERANCE_IN_SECONDS + 1)),
+            ),
+            Err(BetOnCurrentlyViewingPostError::BettingClosed)
+        );
+    }
+
     #[test]
     fn test_has_this_principal_already_bet_on_this_post() {
         let mut post = Post::new(
@@ -677,6 +2005,9 @@ mod test {
                 hashtags: vec!["doggo".into(), "pupper".into()],
                 video_uid: "abcd#1234".into(),
                 creator_consent_for_inclusion_in_hot_or_not: true,
+                collaborator_shares: None,
+                title: None,
+                category: None,
             },
             &SystemTime::now(),
         );
@@ -702,7 +2033,8 @@ mod test {
     }
 
     #[test]
-    fn test_place_hot_or_not_bet() {
+    fn test_get_user_status_for_this_post() {
+        let post_creation_time = SystemTime::now();
         let mut post = Post::new(
             0,
             &PostDetailsFromFrontend {
@@ -710,28 +2042,321 @@ mod test {
                 hashtags: vec!["doggo".into(), "pupper".into()],
                 video_uid: "abcd#1234".into(),
                 creator_consent_for_inclusion_in_hot_or_not: true,
+                collaborator_shares: None,
+                title: None,
+                category: None,
             },
-            &SystemTime::now(),
+            &post_creation_time,
         );
+        let hot_bettor = Principal::self_authenticating(1_u64.to_ne_bytes());
+        let not_bettor = Principal::self_authenticating(2_u64.to_ne_bytes());
 
-        assert!(post.hot_or_not_details.is_some());
+        assert_eq!(
+            post.get_user_status_for_this_post(&hot_bettor),
+            UserStatusForSpecificHotOrNotPost::NotParticipatedYet
+        );
 
-        let result = post.place_hot_or_not_bet(
+        [
+            (hot_bettor, BetDirection::Hot),
+            (not_bettor, BetDirection::Not),
+        ]
+        .iter()
+        .for_each(|(bettor, bet_direction)| {
+            post.place_hot_or_not_bet(bettor, bettor, 100, bet_direction, &post_creation_time)
+                .unwrap();
+        });
+        post.place_hot_or_not_bet(
             &get_mock_user_alice_principal_id(),
             &get_mock_user_alice_canister_id(),
-            100,
+            50,
             &BetDirection::Hot,
-            &SystemTime::now()
-                .checked_add(Duration::from_secs(
-                    TOTAL_DURATION_OF_ALL_SLOTS_IN_SECONDS + 1,
-                ))
-                .unwrap(),
-        );
+            &post_creation_time,
+        )
+        .unwrap();
 
-        assert_eq!(result, Err(BetOnCurrentlyViewingPostError::BettingClosed));
+        assert_eq!(
+            post.get_user_status_for_this_post(&hot_bettor),
+            UserStatusForSpecificHotOrNotPost::AwaitingResult(BetDetail {
+                amount: 100,
+                bet_direction: BetDirection::Hot,
+                slot_id: 1,
+                room_id: 1,
+            })
+        );
 
-        let result = post.place_hot_or_not_bet(
-            &get_mock_user_alice_principal_id(),
+        post.tabulate_hot_or_not_outcome_for_slot(
+            &get_mock_user_alice_canister_id(),
+            &1,
+            &mut TokenBalance::default(),
+            0,
+            0,
+            HotOrNotPayoutMode::Multiplier,
+            &post_creation_time
+                .checked_add(Duration::from_secs(60 * 5))
+                .unwrap(),
+        );
+
+        assert_eq!(
+            post.get_user_status_for_this_post(&hot_bettor),
+            UserStatusForSpecificHotOrNotPost::ResultAnnounced {
+                bet_detail: BetDetail {
+                    amount: 100,
+                    bet_direction: BetDirection::Hot,
+                    slot_id: 1,
+                    room_id: 1,
+                },
+                result: BetResult::Won(180),
+                payout_u128: Some(180),
+            }
+        );
+        assert_eq!(
+            post.get_user_status_for_this_post(&not_bettor),
+            UserStatusForSpecificHotOrNotPost::ResultAnnounced {
+                bet_detail: BetDetail {
+                    amount: 100,
+                    bet_direction: BetDirection::Not,
+                    slot_id: 1,
+                    room_id: 1,
+                },
+                result: BetResult::Lost,
+                payout_u128: None,
+            }
+        );
+    }
+
+    #[test]
+    fn test_betting_after_creator_revokes_hot_or_not_consent() {
+        let post_creation_time = SystemTime::now();
+        let mut post = Post::new(
+            0,
+            &PostDetailsFromFrontend {
+                description: "Doggos and puppers".into(),
+                hashtags: vec!["doggo".into(), "pupper".into()],
+                video_uid: "abcd#1234".into(),
+                creator_consent_for_inclusion_in_hot_or_not: true,
+                collaborator_shares: None,
+                title: None,
+                category: None,
+            },
+            &post_creation_time,
+        );
+        let mut token_balance = TokenBalance::default();
+
+        assert!(post
+            .place_hot_or_not_bet(
+                &get_mock_user_alice_principal_id(),
+                &get_mock_user_alice_canister_id(),
+                100,
+                &BetDirection::Hot,
+                &post_creation_time,
+            )
+            .is_ok());
+
+        post.revoke_hot_or_not_consent();
+
+        let status_check_time = post_creation_time
+            .checked_add(Duration::from_secs(60 * 5))
+            .unwrap();
+        assert_eq!(
+            post.get_hot_or_not_betting_status_for_this_post(
+                &status_check_time,
+                &get_mock_user_alice_principal_id()
+            ),
+            BettingStatus::BettingClosedByCreator
+        );
+        assert_eq!(
+            post.place_hot_or_not_bet(
+                &Principal::self_authenticating(1_u64.to_ne_bytes()),
+                &Principal::self_authenticating(1_u64.to_ne_bytes()),
+                100,
+                &BetDirection::Not,
+                &status_check_time,
+            ),
+            Err(BetOnCurrentlyViewingPostError::BettingClosed)
+        );
+        assert!(post
+            .get_slot_heat_indicator(&get_mock_user_alice_canister_id(), &status_check_time)
+            .is_none());
+
+        // * the slot that already had bets in it still settles
+        post.tabulate_hot_or_not_outcome_for_slot(
+            &get_mock_user_alice_canister_id(),
+            &1,
+            &mut token_balance,
+            0,
+            0,
+            HotOrNotPayoutMode::Multiplier,
+            &status_check_time,
+        );
+
+        assert_eq!(token_balance.utility_token_balance, 10);
+        assert_eq!(
+            post.hot_or_not_details.as_ref().unwrap().slot_history[&1].room_details[&1].bet_outcome,
+            RoomBetPossibleOutcomes::HotWon
+        );
+
+        // * posts that never opted in have no bets to look up
+        let post_without_consent = Post::new(
+            1,
+            &PostDetailsFromFrontend {
+                description: "Doggos and puppers".into(),
+                hashtags: vec!["doggo".into(), "pupper".into()],
+                video_uid: "abcd#1234".into(),
+                creator_consent_for_inclusion_in_hot_or_not: false,
+                collaborator_shares: None,
+                title: None,
+                category: None,
+            },
+            &post_creation_time,
+        );
+        assert!(!post_without_consent
+            .has_this_principal_already_bet_on_this_post(&get_mock_user_alice_principal_id()));
+    }
+
+    #[test]
+    fn test_betting_paused_while_post_has_too_many_unresolved_reports() {
+        let post_creation_time = SystemTime::now();
+        let mut post = Post::new(
+            0,
+            &PostDetailsFromFrontend {
+                description: "Doggos and puppers".into(),
+                hashtags: vec!["doggo".into(), "pupper".into()],
+                video_uid: "abcd#1234".into(),
+                creator_consent_for_inclusion_in_hot_or_not: true,
+                collaborator_shares: None,
+                title: None,
+                category: None,
+            },
+            &post_creation_time,
+        );
+        let mut token_balance = TokenBalance::default();
+
+        assert!(post
+            .place_hot_or_not_bet(
+                &get_mock_user_alice_principal_id(),
+                &get_mock_user_alice_canister_id(),
+                100,
+                &BetDirection::Hot,
+                &post_creation_time,
+            )
+            .is_ok());
+
+        (0..=MAXIMUM_UNRESOLVED_REPORTS_BEFORE_BETTING_IS_PAUSED as u64).for_each(|reporter| {
+            assert!(matches!(
+                post.get_hot_or_not_betting_status_for_this_post(
+                    &post_creation_time,
+                    &get_mock_user_bob_principal_id()
+                ),
+                BettingStatus::BettingOpen { .. }
+            ));
+            assert!(post
+                .report(
+                    &Principal::self_authenticating(reporter.to_ne_bytes()),
+                    &post_creation_time
+                )
+                .is_ok());
+        });
+        assert_eq!(
+            post.report(
+                &Principal::self_authenticating(0_u64.to_ne_bytes()),
+                &post_creation_time
+            ),
+            Err(ReportPostError::AlreadyReported)
+        );
+
+        let status_check_time = post_creation_time
+            .checked_add(Duration::from_secs(60 * 5))
+            .unwrap();
+        assert_eq!(
+            post.get_hot_or_not_betting_status_for_this_post(
+                &status_check_time,
+                &get_mock_user_bob_principal_id()
+            ),
+            BettingStatus::BettingPausedForReview
+        );
+        assert_eq!(
+            post.place_hot_or_not_bet(
+                &get_mock_user_bob_principal_id(),
+                &get_mock_user_bob_principal_id(),
+                100,
+                &BetDirection::Not,
+                &status_check_time,
+            ),
+            Err(BetOnCurrentlyViewingPostError::BettingPausedForReview)
+        );
+        assert!(post
+            .get_slot_heat_indicator(&get_mock_user_alice_canister_id(), &status_check_time)
+            .is_none());
+
+        // * settlement isn't held back by the review
+        post.tabulate_hot_or_not_outcome_for_slot(
+            &get_mock_user_alice_canister_id(),
+            &1,
+            &mut token_balance,
+            0,
+            0,
+            HotOrNotPayoutMode::Multiplier,
+            &status_check_time,
+        );
+        assert_eq!(
+            post.hot_or_not_details.as_ref().unwrap().slot_history[&1].room_details[&1].bet_outcome,
+            RoomBetPossibleOutcomes::HotWon
+        );
+
+        post.clear_reports();
+
+        assert!(matches!(
+            post.get_hot_or_not_betting_status_for_this_post(
+                &status_check_time,
+                &get_mock_user_bob_principal_id()
+            ),
+            BettingStatus::BettingOpen { .. }
+        ));
+        assert!(post
+            .place_hot_or_not_bet(
+                &get_mock_user_bob_principal_id(),
+                &get_mock_user_bob_principal_id(),
+                100,
+                &BetDirection::Not,
+                &status_check_time,
+            )
+            .is_ok());
+    }
+
+    #[test]
+    fn test_place_hot_or_not_bet() {
+        let mut post = Post::new(
+            0,
+            &PostDetailsFromFrontend {
+                description: "Doggos and puppers".into(),
+                hashtags: vec!["doggo".into(), "pupper".into()],
+                video_uid: "abcd#1234".into(),
+                creator_consent_for_inclusion_in_hot_or_not: true,
+                collaborator_shares: None,
+                title: None,
+                category: None,
+            },
+            &SystemTime::now(),
+        );
+
+        assert!(post.hot_or_not_details.is_some());
+
+        let result = post.place_hot_or_not_bet(
+            &get_mock_user_alice_principal_id(),
+            &get_mock_user_alice_canister_id(),
+            100,
+            &BetDirection::Hot,
+            &SystemTime::now()
+                .checked_add(Duration::from_secs(
+                    TOTAL_DURATION_OF_ALL_SLOTS_IN_SECONDS + 1,
+                ))
+                .unwrap(),
+        );
+
+        assert_eq!(result, Err(BetOnCurrentlyViewingPostError::BettingClosed));
+
+        let result = post.place_hot_or_not_bet(
+            &get_mock_user_alice_principal_id(),
             &get_mock_user_alice_canister_id(),
             100,
             &BetDirection::Hot,
@@ -745,7 +2370,8 @@ mod test {
                 number_of_participants: 1,
                 ongoing_slot: 1,
                 ongoing_room: 1,
-                has_this_user_participated_in_this_post: Some(true)
+                has_this_user_participated_in_this_post: Some(true),
+                minimum_bet_amount: 0,
             })
         );
         let hot_or_not_details = post.hot_or_not_details.clone().unwrap();
@@ -797,8 +2423,73 @@ mod test {
     }
 
     #[test]
-    fn test_tabulate_hot_or_not_outcome_for_slot_case_1() {
-        let post_creation_time = SystemTime::now();
+    fn test_room_sizing_config() {
+        let room_sizing_config = RoomSizingConfig {
+            default_room_size: 100,
+            high_velocity: Some(RoomSizeForVelocity {
+                projected_bets_in_slot_threshold: 60,
+                room_size: 3,
+            }),
+            low_velocity: Some(RoomSizeForVelocity {
+                projected_bets_in_slot_threshold: 10,
+                room_size: 200,
+            }),
+        };
+
+        assert_eq!(room_sizing_config.validate(), Ok(()));
+        assert_eq!(RoomSizingConfig::default().validate(), Ok(()));
+        assert_eq!(room_sizing_config.get_room_size(0), 200);
+        assert_eq!(room_sizing_config.get_room_size(10), 200);
+        assert_eq!(room_sizing_config.get_room_size(11), 100);
+        assert_eq!(room_sizing_config.get_room_size(60), 3);
+        assert_eq!(
+            RoomSizingConfig::default().get_room_size(u64::MAX),
+            DEFAULT_ROOM_SIZE
+        );
+
+        assert!(RoomSizingConfig {
+            default_room_size: MAXIMUM_ROOM_SIZE + 1,
+            ..room_sizing_config.clone()
+        }
+        .validate()
+        .is_err());
+        assert!(RoomSizingConfig {
+            high_velocity: Some(RoomSizeForVelocity {
+                projected_bets_in_slot_threshold: 60,
+                room_size: 0,
+            }),
+            ..room_sizing_config.clone()
+        }
+        .validate()
+        .is_err());
+        assert!(RoomSizingConfig {
+            default_room_size: 2,
+            ..room_sizing_config.clone()
+        }
+        .validate()
+        .is_err());
+        assert!(RoomSizingConfig {
+            low_velocity: Some(RoomSizeForVelocity {
+                projected_bets_in_slot_threshold: 60,
+                room_size: 200,
+            }),
+            ..room_sizing_config
+        }
+        .validate()
+        .is_err());
+    }
+
+    #[test]
+    fn test_place_hot_or_not_bet_with_room_sizing() {
+        let created_at = SystemTime::now();
+        let room_sizing_config = RoomSizingConfig {
+            default_room_size: 100,
+            high_velocity: Some(RoomSizeForVelocity {
+                projected_bets_in_slot_threshold: 60,
+                room_size: 3,
+            }),
+            low_velocity: None,
+        };
         let mut post = Post::new(
             0,
             &PostDetailsFromFrontend {
@@ -806,114 +2497,589 @@ mod test {
                 hashtags: vec!["doggo".into(), "pupper".into()],
                 video_uid: "abcd#1234".into(),
                 creator_consent_for_inclusion_in_hot_or_not: true,
+                collaborator_shares: None,
+                title: None,
+                category: None,
             },
-            &post_creation_time,
+            &created_at,
         );
-        let mut token_balance = TokenBalance::default();
-        let tabulation_canister_id = get_mock_user_alice_canister_id();
-
-        assert!(post.hot_or_not_details.is_some());
-
-        let data_set: Vec<(u64, BetDirection, u64, u64)> = vec![
-            (1, BetDirection::Not, 10, 18),
-            (2, BetDirection::Hot, 100, 0),
-            (3, BetDirection::Hot, 100, 0),
-            (4, BetDirection::Not, 100, 180),
-            (5, BetDirection::Hot, 10, 0),
-            (6, BetDirection::Not, 100, 180),
-            (7, BetDirection::Not, 50, 90),
-            (8, BetDirection::Not, 100, 180),
-            (9, BetDirection::Hot, 50, 0),
-            (10, BetDirection::Not, 50, 90),
-            (11, BetDirection::Not, 100, 180),
-            (12, BetDirection::Not, 10, 18),
-            (13, BetDirection::Hot, 100, 0),
-            (14, BetDirection::Not, 10, 18),
-            (15, BetDirection::Hot, 50, 0),
-            (16, BetDirection::Hot, 10, 0),
-            (17, BetDirection::Hot, 10, 0),
-            (18, BetDirection::Hot, 100, 0),
-            (19, BetDirection::Not, 10, 18),
-            (20, BetDirection::Hot, 50, 0),
-            (21, BetDirection::Hot, 10, 0),
-            (22, BetDirection::Not, 50, 90),
-            (23, BetDirection::Not, 50, 90),
-            (24, BetDirection::Hot, 100, 0),
-            (25, BetDirection::Not, 50, 90),
-            (26, BetDirection::Not, 10, 18),
-            (27, BetDirection::Not, 10, 18),
-            (28, BetDirection::Not, 50, 90),
-            (29, BetDirection::Hot, 50, 0),
-            (30, BetDirection::Not, 100, 180),
-            (31, BetDirection::Not, 50, 90),
-            (32, BetDirection::Not, 50, 90),
-            (33, BetDirection::Hot, 100, 0),
-            (34, BetDirection::Not, 10, 18),
-            (35, BetDirection::Not, 10, 18),
-            (36, BetDirection::Not, 100, 180),
-            (37, BetDirection::Hot, 10, 0),
-            (38, BetDirection::Not, 100, 180),
-            (39, BetDirection::Not, 50, 90),
-            (40, BetDirection::Hot, 100, 0),
-            (41, BetDirection::Hot, 50, 0),
-            (42, BetDirection::Not, 10, 18),
-            (43, BetDirection::Hot, 50, 0),
-            (44, BetDirection::Not, 10, 18),
-            (45, BetDirection::Not, 10, 18),
-            (46, BetDirection::Hot, 100, 0),
-            (47, BetDirection::Hot, 50, 0),
-            (48, BetDirection::Hot, 50, 0),
-            (49, BetDirection::Not, 100, 180),
-            (50, BetDirection::Hot, 10, 0),
-            (51, BetDirection::Not, 50, 90),
-            (52, BetDirection::Hot, 10, 0),
-            (53, BetDirection::Not, 50, 90),
-            (54, BetDirection::Not, 10, 18),
-            (55, BetDirection::Hot, 100, 0),
-            (56, BetDirection::Hot, 50, 0),
-            (57, BetDirection::Not, 50, 90),
-            (58, BetDirection::Not, 10, 18),
-            (59, BetDirection::Not, 50, 90),
-            (60, BetDirection::Hot, 10, 0),
-            (61, BetDirection::Not, 10, 18),
-            (62, BetDirection::Not, 50, 90),
-            (63, BetDirection::Not, 50, 90),
-            (64, BetDirection::Not, 10, 18),
-            (65, BetDirection::Not, 10, 18),
-            (66, BetDirection::Not, 100, 180),
-            (67, BetDirection::Hot, 100, 0),
-            (68, BetDirection::Not, 10, 18),
-            (69, BetDirection::Not, 10, 18),
-            (70, BetDirection::Not, 50, 90),
-            (71, BetDirection::Not, 100, 180),
-            (72, BetDirection::Not, 10, 18),
-            (73, BetDirection::Not, 10, 18),
-            (74, BetDirection::Hot, 10, 0),
-            (75, BetDirection::Not, 10, 18),
-        ];
-
-        data_set
-            .iter()
-            .for_each(|(user_id, bet_direction, bet_amount, _)| {
-                let result = post.place_hot_or_not_bet(
-                    &Principal::self_authenticating(user_id.to_ne_bytes()),
-                    &Principal::self_authenticating(user_id.to_ne_bytes()),
-                    *bet_amount,
-                    bet_direction,
-                    &post_creation_time,
-                );
-                assert!(result.is_ok());
-            });
 
-        let score_tabulation_time = post_creation_time
-            .checked_add(Duration::from_secs(60 * 5))
-            .unwrap();
+        // * 10 bets in the first 10 minutes puts the slot on pace for 60 bets
+        let ten_minutes_in = created_at + Duration::from_secs(10 * 60);
+        let ongoing_rooms = (1..=14)
+            .map(|num| {
+                match post
+                    .place_hot_or_not_bet_with_room_sizing(
+                        &Principal::from_slice(&[100 + num]),
+                        &Principal::from_slice(&[100 + num]),
+                        10,
+                        &BetDirection::Hot,
+                        &ten_minutes_in,
+                        &room_sizing_config,
+                    )
+                    .unwrap()
+                {
+                    BettingStatus::BettingOpen { ongoing_room, .. } => ongoing_room,
+                    _ => panic!("Betting should be open"),
+                }
+            })
+            .collect::<Vec<_>>();
 
-        post.tabulate_hot_or_not_outcome_for_slot(
-            &tabulation_canister_id,
-            &1,
-            &mut token_balance,
-            &score_tabulation_time,
+        assert_eq!(
+            ongoing_rooms,
+            vec![1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 2, 2, 2, 3]
+        );
+
+        let slot_details = &post.hot_or_not_details.as_ref().unwrap().slot_history[&1];
+        assert_eq!(slot_details.get_number_of_bets(), 14);
+        assert_eq!(slot_details.room_details[&2].room_bets_total_pot, 30);
+        assert_eq!(slot_details.room_details[&3].total_hot_bets, 1);
+
+        // * A slow slot lets rooms grow past the default size
+        let room_sizing_config = RoomSizingConfig {
+            default_room_size: 2,
+            high_velocity: None,
+            low_velocity: Some(RoomSizeForVelocity {
+                projected_bets_in_slot_threshold: 10,
+                room_size: 5,
+            }),
+        };
+        let mut post = Post::new(
+            1,
+            &PostDetailsFromFrontend {
+                description: "Doggos and puppers".into(),
+                hashtags: vec!["doggo".into(), "pupper".into()],
+                video_uid: "abcd#1234".into(),
+                creator_consent_for_inclusion_in_hot_or_not: true,
+                collaborator_shares: None,
+                title: None,
+                category: None,
+            },
+            &created_at,
+        );
+        let end_of_first_slot =
+            created_at + Duration::from_secs(DURATION_OF_EACH_SLOT_IN_SECONDS - 60);
+        (1..=4).for_each(|num| {
+            post.place_hot_or_not_bet_with_room_sizing(
+                &Principal::from_slice(&[100 + num]),
+                &Principal::from_slice(&[100 + num]),
+                10,
+                &BetDirection::Not,
+                &end_of_first_slot,
+                &room_sizing_config,
+            )
+            .unwrap();
+        });
+
+        let room_details = &post.hot_or_not_details.as_ref().unwrap().slot_history[&1].room_details;
+        assert_eq!(room_details.len(), 1);
+        assert_eq!(room_details[&1].bets_made.len(), 4);
+    }
+
+    #[test]
+    fn test_recompute_aggregate_stats_from_slot_history() {
+        let mut post = Post::new(
+            0,
+            &PostDetailsFromFrontend {
+                description: "Doggos and puppers".into(),
+                hashtags: vec!["doggo".into(), "pupper".into()],
+                video_uid: "abcd#1234".into(),
+                creator_consent_for_inclusion_in_hot_or_not: true,
+                collaborator_shares: None,
+                title: None,
+                category: None,
+            },
+            &SystemTime::now(),
+        );
+
+        (1..=5).for_each(|num| {
+            post.place_hot_or_not_bet(
+                &Principal::from_slice(&[100 + num]),
+                &Principal::from_slice(&[100 + num]),
+                num as u64 * 10,
+                if num % 2 == 0 {
+                    &BetDirection::Not
+                } else {
+                    &BetDirection::Hot
+                },
+                &SystemTime::now(),
+            )
+            .unwrap();
+        });
+
+        let hot_or_not_details = post.hot_or_not_details.as_mut().unwrap();
+        let expected_aggregate_stats = AggregateStats {
+            total_number_of_hot_bets: 3,
+            total_number_of_not_bets: 2,
+            total_amount_bet: 150,
+        };
+
+        assert_eq!(
+            hot_or_not_details.recompute_aggregate_stats_from_slot_history(),
+            expected_aggregate_stats
+        );
+
+        // * drift in the running totals does not affect the recomputed value
+        hot_or_not_details.aggregate_stats.total_amount_bet = 0;
+        hot_or_not_details.aggregate_stats.total_number_of_hot_bets = 7;
+
+        assert_eq!(
+            hot_or_not_details.recompute_aggregate_stats_from_slot_history(),
+            expected_aggregate_stats
+        );
+    }
+
+    #[test]
+    fn test_validate_hot_or_not_bet() {
+        let mut post = Post::new(
+            0,
+            &PostDetailsFromFrontend {
+                description: "Doggos and puppers".into(),
+                hashtags: vec!["doggo".into(), "pupper".into()],
+                video_uid: "abcd#1234".into(),
+                creator_consent_for_inclusion_in_hot_or_not: true,
+                collaborator_shares: None,
+                title: None,
+                category: None,
+            },
+            &SystemTime::now(),
+        );
+        post.minimum_bet_amount = 50;
+        let bet_maker_principal_id = Principal::from_slice(&[100]);
+
+        assert_eq!(
+            post.validate_hot_or_not_bet(&bet_maker_principal_id, 10, &SystemTime::now()),
+            Err(BetOnCurrentlyViewingPostError::BetAmountBelowPostMinimum)
+        );
+        assert!(matches!(
+            post.validate_hot_or_not_bet(&bet_maker_principal_id, 50, &SystemTime::now()),
+            Ok(BettingStatus::BettingOpen {
+                has_this_user_participated_in_this_post: Some(false),
+                ..
+            })
+        ));
+        // * nothing was placed by validating
+        assert_eq!(
+            post.hot_or_not_details
+                .as_ref()
+                .unwrap()
+                .aggregate_stats
+                .total_amount_bet,
+            0
+        );
+
+        post.place_hot_or_not_bet(
+            &bet_maker_principal_id,
+            &bet_maker_principal_id,
+            50,
+            &BetDirection::Hot,
+            &SystemTime::now(),
+        )
+        .unwrap();
+        assert_eq!(
+            post.validate_hot_or_not_bet(&bet_maker_principal_id, 50, &SystemTime::now()),
+            Err(BetOnCurrentlyViewingPostError::UserAlreadyParticipatedInThisPost)
+        );
+    }
+
+    #[test]
+    fn test_cancel_hot_or_not_bet() {
+        let created_at = SystemTime::now();
+        let mut post = Post::new(
+            0,
+            &PostDetailsFromFrontend {
+                description: "Doggos and puppers".into(),
+                hashtags: vec!["doggo".into(), "pupper".into()],
+                video_uid: "abcd#1234".into(),
+                creator_consent_for_inclusion_in_hot_or_not: true,
+                collaborator_shares: None,
+                title: None,
+                category: None,
+            },
+            &created_at,
+        );
+        let bet_maker_principal_ids: Vec<Principal> =
+            (100..103).map(|id| Principal::from_slice(&[id])).collect();
+
+        bet_maker_principal_ids
+            .iter()
+            .enumerate()
+            .for_each(|(index, bet_maker_principal_id)| {
+                post.place_hot_or_not_bet(
+                    bet_maker_principal_id,
+                    bet_maker_principal_id,
+                    50 * (index as u64 + 1),
+                    if index == 2 {
+                        &BetDirection::Not
+                    } else {
+                        &BetDirection::Hot
+                    },
+                    &created_at,
+                )
+                .unwrap();
+            });
+
+        // * only the canister that placed the bet can take it back
+        assert_eq!(
+            post.cancel_hot_or_not_bet(
+                &bet_maker_principal_ids[1],
+                &bet_maker_principal_ids[0],
+                &created_at,
+            )
+            .err(),
+            Some(BetOnCurrentlyViewingPostError::BetNotFound)
+        );
+        assert_eq!(
+            post.cancel_hot_or_not_bet(
+                &bet_maker_principal_ids[1],
+                &bet_maker_principal_ids[1],
+                &(created_at + Duration::from_secs(BET_CANCELLATION_WINDOW_IN_SECONDS)),
+            )
+            .err(),
+            Some(BetOnCurrentlyViewingPostError::BetCancellationWindowClosed)
+        );
+        assert_eq!(
+            post.cancel_hot_or_not_bet(
+                &bet_maker_principal_ids[1],
+                &bet_maker_principal_ids[1],
+                &(created_at + Duration::from_secs(BET_CANCELLATION_WINDOW_IN_SECONDS - 1)),
+            )
+            .map(|(room_id, bet_details)| (room_id, bet_details.amount)),
+            Ok((Some(1), 100))
+        );
+
+        let hot_or_not_details = post.hot_or_not_details.as_ref().unwrap();
+        assert_eq!(
+            hot_or_not_details.aggregate_stats,
+            AggregateStats {
+                total_number_of_hot_bets: 1,
+                total_number_of_not_bets: 1,
+                total_amount_bet: 200,
+            }
+        );
+        assert_eq!(
+            hot_or_not_details.aggregate_stats,
+            hot_or_not_details.recompute_aggregate_stats_from_slot_history()
+        );
+        assert_eq!(
+            hot_or_not_details.bet_amount_histogram,
+            hot_or_not_details.recompute_bet_amount_histogram_from_slot_history()
+        );
+        let room_details = &hot_or_not_details.slot_history[&1].room_details[&1];
+        assert_eq!(room_details.room_bets_total_pot, 200);
+        assert_eq!(room_details.total_hot_bets, 1);
+        assert!(!post.has_this_principal_already_bet_on_this_post(&bet_maker_principal_ids[1]));
+
+        assert_eq!(
+            post.cancel_hot_or_not_bet(
+                &bet_maker_principal_ids[1],
+                &bet_maker_principal_ids[1],
+                &created_at,
+            )
+            .err(),
+            Some(BetOnCurrentlyViewingPostError::BetNotFound)
+        );
+        // * the window is counted from the start of the slot the bet was placed in
+        assert_eq!(
+            post.cancel_hot_or_not_bet(
+                &bet_maker_principal_ids[0],
+                &bet_maker_principal_ids[0],
+                &(created_at + Duration::from_secs(DURATION_OF_EACH_SLOT_IN_SECONDS)),
+            )
+            .err(),
+            Some(BetOnCurrentlyViewingPostError::BetCancellationWindowClosed)
+        );
+    }
+
+    #[test]
+    fn test_get_bet_cancellation_refund_amount() {
+        assert_eq!(BetCancellationConfig::default().get_refund_amount(105), 94);
+        assert_eq!(
+            BetCancellationConfig {
+                refund_percentage: 0
+            }
+            .get_refund_amount(105),
+            0
+        );
+        assert!(BetCancellationConfig {
+            refund_percentage: 101
+        }
+        .validate()
+        .is_err());
+    }
+
+    #[test]
+    fn test_bet_amount_histogram() {
+        let mut post = Post::new(
+            0,
+            &PostDetailsFromFrontend {
+                description: "Doggos and puppers".into(),
+                hashtags: vec!["doggo".into(), "pupper".into()],
+                video_uid: "abcd#1234".into(),
+                creator_consent_for_inclusion_in_hot_or_not: true,
+                collaborator_shares: None,
+                title: None,
+                category: None,
+            },
+            &SystemTime::now(),
+        );
+
+        [
+            (10, BetDirection::Hot),
+            (11, BetDirection::Hot),
+            (50, BetDirection::Not),
+            (2_000, BetDirection::Not),
+        ]
+        .iter()
+        .enumerate()
+        .for_each(|(index, (bet_amount, bet_direction))| {
+            post.place_hot_or_not_bet(
+                &Principal::from_slice(&[100 + index as u8]),
+                &Principal::from_slice(&[100 + index as u8]),
+                *bet_amount,
+                bet_direction,
+                &SystemTime::now(),
+            )
+            .unwrap();
+        });
+
+        let hot_or_not_details = post.hot_or_not_details.as_ref().unwrap();
+        let bucket_counts = hot_or_not_details
+            .get_bet_analytics()
+            .bet_amount_histogram
+            .iter()
+            .map(|bucket| {
+                (
+                    bucket.min_amount,
+                    bucket.max_amount,
+                    bucket.hot_bet_count,
+                    bucket.not_bet_count,
+                )
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            bucket_counts,
+            vec![
+                (0, Some(10), 1, 0),
+                (11, Some(50), 1, 1),
+                (51, Some(100), 0, 0),
+                (101, Some(500), 0, 0),
+                (501, Some(1_000), 0, 0),
+                (1_001, None, 0, 1),
+            ]
+        );
+        assert_eq!(
+            hot_or_not_details.recompute_bet_amount_histogram_from_slot_history(),
+            hot_or_not_details.bet_amount_histogram
+        );
+    }
+
+    #[test]
+    fn test_get_slot_heat_indicator() {
+        let created_at = SystemTime::now();
+        let mut post = Post::new(
+            0,
+            &PostDetailsFromFrontend {
+                description: "Doggos and puppers".into(),
+                hashtags: vec!["doggo".into(), "pupper".into()],
+                video_uid: "abcd#1234".into(),
+                creator_consent_for_inclusion_in_hot_or_not: true,
+                collaborator_shares: None,
+                title: None,
+                category: None,
+            },
+            &created_at,
+        );
+
+        // * 2 bets in the first slot, 6 bets in the first 10 minutes of the second slot
+        let first_slot_time = created_at + Duration::from_secs(30 * 60);
+        let second_slot_time =
+            created_at + Duration::from_secs(DURATION_OF_EACH_SLOT_IN_SECONDS + 10 * 60);
+        (1..=8).for_each(|num| {
+            post.place_hot_or_not_bet(
+                &Principal::from_slice(&[100 + num]),
+                &Principal::from_slice(&[100 + num]),
+                10,
+                &BetDirection::Hot,
+                if num <= 2 {
+                    &first_slot_time
+                } else {
+                    &second_slot_time
+                },
+            )
+            .unwrap();
+        });
+
+        let slot_heat_indicator = post
+            .get_slot_heat_indicator(&get_mock_user_alice_canister_id(), &second_slot_time)
+            .unwrap();
+
+        assert_eq!(
+            slot_heat_indicator,
+            SlotHeatIndicator {
+                publisher_canister_id: get_mock_user_alice_canister_id(),
+                post_id: 0,
+                slot_id: 2,
+                bets_in_slot: 6,
+                heat_score: 1800,
+                is_on_fire: true,
+                valid_until: created_at + Duration::from_secs(2 * DURATION_OF_EACH_SLOT_IN_SECONDS),
+            }
+        );
+
+        // * The projected pace drops as the slot goes on without new bets
+        let end_of_second_slot =
+            created_at + Duration::from_secs(2 * DURATION_OF_EACH_SLOT_IN_SECONDS - 1);
+        let slot_heat_indicator = post
+            .get_slot_heat_indicator(&get_mock_user_alice_canister_id(), &end_of_second_slot)
+            .unwrap();
+        assert_eq!(slot_heat_indicator.heat_score, 300);
+        assert!(slot_heat_indicator.is_on_fire);
+
+        let slot_heat_indicator = post
+            .get_slot_heat_indicator(
+                &get_mock_user_alice_canister_id(),
+                &(created_at + Duration::from_secs(2 * DURATION_OF_EACH_SLOT_IN_SECONDS)),
+            )
+            .unwrap();
+        assert_eq!(slot_heat_indicator.slot_id, 3);
+        assert_eq!(slot_heat_indicator.bets_in_slot, 0);
+        assert!(!slot_heat_indicator.is_on_fire);
+
+        assert_eq!(
+            post.get_slot_heat_indicator(
+                &get_mock_user_alice_canister_id(),
+                &(created_at + Duration::from_secs(TOTAL_DURATION_OF_ALL_SLOTS_IN_SECONDS)),
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn test_tabulate_hot_or_not_outcome_for_slot_case_1() {
+        let post_creation_time = SystemTime::now();
+        let mut post = Post::new(
+            0,
+            &PostDetailsFromFrontend {
+                description: "Doggos and puppers".into(),
+                hashtags: vec!["doggo".into(), "pupper".into()],
+                video_uid: "abcd#1234".into(),
+                creator_consent_for_inclusion_in_hot_or_not: true,
+                collaborator_shares: None,
+                title: None,
+                category: None,
+            },
+            &post_creation_time,
+        );
+        let mut token_balance = TokenBalance::default();
+        let tabulation_canister_id = get_mock_user_alice_canister_id();
+
+        assert!(post.hot_or_not_details.is_some());
+
+        let data_set: Vec<(u64, BetDirection, u64, u128)> = vec![
+            (1, BetDirection::Not, 10, 18),
+            (2, BetDirection::Hot, 100, 0),
+            (3, BetDirection::Hot, 100, 0),
+            (4, BetDirection::Not, 100, 180),
+            (5, BetDirection::Hot, 10, 0),
+            (6, BetDirection::Not, 100, 180),
+            (7, BetDirection::Not, 50, 90),
+            (8, BetDirection::Not, 100, 180),
+            (9, BetDirection::Hot, 50, 0),
+            (10, BetDirection::Not, 50, 90),
+            (11, BetDirection::Not, 100, 180),
+            (12, BetDirection::Not, 10, 18),
+            (13, BetDirection::Hot, 100, 0),
+            (14, BetDirection::Not, 10, 18),
+            (15, BetDirection::Hot, 50, 0),
+            (16, BetDirection::Hot, 10, 0),
+            (17, BetDirection::Hot, 10, 0),
+            (18, BetDirection::Hot, 100, 0),
+            (19, BetDirection::Not, 10, 18),
+            (20, BetDirection::Hot, 50, 0),
+            (21, BetDirection::Hot, 10, 0),
+            (22, BetDirection::Not, 50, 90),
+            (23, BetDirection::Not, 50, 90),
+            (24, BetDirection::Hot, 100, 0),
+            (25, BetDirection::Not, 50, 90),
+            (26, BetDirection::Not, 10, 18),
+            (27, BetDirection::Not, 10, 18),
+            (28, BetDirection::Not, 50, 90),
+            (29, BetDirection::Hot, 50, 0),
+            (30, BetDirection::Not, 100, 180),
+            (31, BetDirection::Not, 50, 90),
+            (32, BetDirection::Not, 50, 90),
+            (33, BetDirection::Hot, 100, 0),
+            (34, BetDirection::Not, 10, 18),
+            (35, BetDirection::Not, 10, 18),
+            (36, BetDirection::Not, 100, 180),
+            (37, BetDirection::Hot, 10, 0),
+            (38, BetDirection::Not, 100, 180),
+            (39, BetDirection::Not, 50, 90),
+            (40, BetDirection::Hot, 100, 0),
+            (41, BetDirection::Hot, 50, 0),
+            (42, BetDirection::Not, 10, 18),
+            (43, BetDirection::Hot, 50, 0),
+            (44, BetDirection::Not, 10, 18),
+            (45, BetDirection::Not, 10, 18),
+            (46, BetDirection::Hot, 100, 0),
+            (47, BetDirection::Hot, 50, 0),
+            (48, BetDirection::Hot, 50, 0),
+            (49, BetDirection::Not, 100, 180),
+            (50, BetDirection::Hot, 10, 0),
+            (51, BetDirection::Not, 50, 90),
+            (52, BetDirection::Hot, 10, 0),
+            (53, BetDirection::Not, 50, 90),
+            (54, BetDirection::Not, 10, 18),
+            (55, BetDirection::Hot, 100, 0),
+            (56, BetDirection::Hot, 50, 0),
+            (57, BetDirection::Not, 50, 90),
+            (58, BetDirection::Not, 10, 18),
+            (59, BetDirection::Not, 50, 90),
+            (60, BetDirection::Hot, 10, 0),
+            (61, BetDirection::Not, 10, 18),
+            (62, BetDirection::Not, 50, 90),
+            (63, BetDirection::Not, 50, 90),
+            (64, BetDirection::Not, 10, 18),
+            (65, BetDirection::Not, 10, 18),
+            (66, BetDirection::Not, 100, 180),
+            (67, BetDirection::Hot, 100, 0),
+            (68, BetDirection::Not, 10, 18),
+            (69, BetDirection::Not, 10, 18),
+            (70, BetDirection::Not, 50, 90),
+            (71, BetDirection::Not, 100, 180),
+            (72, BetDirection::Not, 10, 18),
+            (73, BetDirection::Not, 10, 18),
+            (74, BetDirection::Hot, 10, 0),
+            (75, BetDirection::Not, 10, 18),
+        ];
+
+        data_set
+            .iter()
+            .for_each(|(user_id, bet_direction, bet_amount, _)| {
+                let result = post.place_hot_or_not_bet(
+                    &Principal::self_authenticating(user_id.to_ne_bytes()),
+                    &Principal::self_authenticating(user_id.to_ne_bytes()),
+                    *bet_amount,
+                    bet_direction,
+                    &post_creation_time,
+                );
+                assert!(result.is_ok());
+            });
+
+        let score_tabulation_time = post_creation_time
+            .checked_add(Duration::from_secs(60 * 5))
+            .unwrap();
+
+        post.tabulate_hot_or_not_outcome_for_slot(
+            &tabulation_canister_id,
+            &1,
+            &mut token_balance,
+            0,
+            0,
+            HotOrNotPayoutMode::Multiplier,
+            &score_tabulation_time,
         );
 
         assert_eq!(token_balance.utility_token_transaction_history.len(), 1);
@@ -927,15 +3093,453 @@ mod test {
             .get(&1)
             .unwrap()
             .room_details
-            .get(&1)
+            .get(&1)
+            .unwrap();
+
+        assert_eq!(room_detail.bet_outcome, RoomBetPossibleOutcomes::NotWon);
+        assert_eq!(room_detail.room_bets_total_pot, 3550);
+        assert_eq!(room_detail.total_hot_bets, 28);
+        assert_eq!(room_detail.total_not_bets, 47);
+
+        data_set
+            .iter()
+            .for_each(|(user_id, bet_direction, bet_amount, amount_won)| {
+                let bet_detail = room_detail
+                    .bets_made
+                    .get(&Principal::self_authenticating(user_id.to_ne_bytes()))
+                    .unwrap();
+
+                assert_eq!(bet_detail.bet_direction, *bet_direction);
+                assert_eq!(bet_detail.amount, *bet_amount);
+                assert_eq!(
+                    match bet_detail.payout {
+                        BetPayout::Calculated(n) => {
+                            n
+                        }
+                        _ => {
+                            0
+                        }
+                    },
+                    *amount_won
+                );
+            });
+
+        let data_set: Vec<(u64, BetDirection, u64, u128)> = vec![
+            (1, BetDirection::Hot, 10, 18),
+            (2, BetDirection::Hot, 50, 90),
+            (3, BetDirection::Hot, 10, 18),
+            (4, BetDirection::Not, 100, 0),
+            (5, BetDirection::Hot, 100, 180),
+            (6, BetDirection::Not, 100, 0),
+            (7, BetDirection::Hot, 50, 90),
+            (8, BetDirection::Hot, 100, 180),
+            (9, BetDirection::Hot, 100, 180),
+            (10, BetDirection::Not, 50, 0),
+            (11, BetDirection::Not, 50, 0),
+            (12, BetDirection::Hot, 50, 90),
+            (13, BetDirection::Hot, 100, 180),
+            (14, BetDirection::Hot, 100, 180),
+            (15, BetDirection::Not, 50, 0),
+            (16, BetDirection::Not, 50, 0),
+            (17, BetDirection::Not, 100, 0),
+            (18, BetDirection::Not, 100, 0),
+            (19, BetDirection::Hot, 100, 180),
+            (20, BetDirection::Not, 10, 0),
+            (21, BetDirection::Hot, 100, 180),
+            (22, BetDirection::Hot, 10, 18),
+            (23, BetDirection::Hot, 10, 18),
+            (24, BetDirection::Hot, 50, 90),
+            (25, BetDirection::Not, 100, 0),
+            (26, BetDirection::Hot, 10, 18),
+            (27, BetDirection::Hot, 100, 180),
+            (28, BetDirection::Hot, 50, 90),
+            (29, BetDirection::Hot, 50, 90),
+            (30, BetDirection::Hot, 10, 18),
+            (31, BetDirection::Hot, 10, 18),
+            (32, BetDirection::Hot, 100, 180),
+            (33, BetDirection::Not, 100, 0),
+            (34, BetDirection::Hot, 50, 90),
+            (35, BetDirection::Hot, 100, 180),
+            (36, BetDirection::Hot, 100, 180),
+            (37, BetDirection::Hot, 50, 90),
+            (38, BetDirection::Not, 10, 0),
+            (39, BetDirection::Hot, 50, 90),
+            (40, BetDirection::Not, 10, 0),
+            (41, BetDirection::Hot, 50, 90),
+            (42, BetDirection::Not, 10, 0),
+            (43, BetDirection::Not, 100, 0),
+            (44, BetDirection::Not, 100, 0),
+            (45, BetDirection::Not, 100, 0),
+            (46, BetDirection::Hot, 100, 180),
+            (47, BetDirection::Not, 50, 0),
+            (48, BetDirection::Hot, 100, 180),
+            (49, BetDirection::Not, 100, 0),
+            (50, BetDirection::Not, 50, 0),
+            (51, BetDirection::Not, 10, 0),
+            (52, BetDirection::Not, 100, 0),
+            (53, BetDirection::Hot, 100, 180),
+            (54, BetDirection::Hot, 10, 18),
+            (55, BetDirection::Not, 100, 0),
+            (56, BetDirection::Not, 100, 0),
+            (57, BetDirection::Hot, 50, 90),
+            (58, BetDirection::Not, 100, 0),
+            (59, BetDirection::Not, 10, 0),
+            (60, BetDirection::Hot, 10, 18),
+            (61, BetDirection::Not, 10, 0),
+            (62, BetDirection::Hot, 50, 90),
+            (63, BetDirection::Hot, 10, 18),
+            (64, BetDirection::Hot, 50, 90),
+            (65, BetDirection::Not, 100, 0),
+            (66, BetDirection::Not, 50, 0),
+            (67, BetDirection::Not, 100, 0),
+            (68, BetDirection::Hot, 10, 18),
+            (69, BetDirection::Hot, 50, 90),
+            (70, BetDirection::Not, 100, 0),
+            (71, BetDirection::Hot, 50, 90),
+            (72, BetDirection::Hot, 50, 90),
+            (73, BetDirection::Not, 50, 0),
+            (74, BetDirection::Not, 50, 0),
+            (75, BetDirection::Not, 50, 0),
+        ];
+
+        // * 1 min into the 2nd hour/2nd slot
+        let post_creation_time = post_creation_time
+            .checked_add(Duration::from_secs(60 * (60 + 1)))
+            .unwrap();
+
+        data_set
+            .iter()
+            .for_each(|(user_id, bet_direction, bet_amount, _)| {
+                let result = post.place_hot_or_not_bet(
+                    &Principal::self_authenticating((user_id + 75).to_ne_bytes()),
+                    &Principal::self_authenticating((user_id + 75).to_ne_bytes()),
+                    *bet_amount,
+                    bet_direction,
+                    &post_creation_time,
+                );
+                assert!(result.is_ok());
+            });
+
+        let score_tabulation_time = post_creation_time
+            .checked_add(Duration::from_secs(60 * 5))
+            .unwrap();
+
+        post.tabulate_hot_or_not_outcome_for_slot(
+            &get_mock_user_alice_canister_id(),
+            &2,
+            &mut token_balance,
+            0,
+            0,
+            HotOrNotPayoutMode::Multiplier,
+            &score_tabulation_time,
+        );
+
+        assert_eq!(token_balance.utility_token_transaction_history.len(), 2);
+        assert_eq!(token_balance.utility_token_balance, 355 + 458);
+
+        let room_detail = post
+            .hot_or_not_details
+            .as_ref()
+            .unwrap()
+            .slot_history
+            .get(&2)
+            .unwrap()
+            .room_details
+            .get(&1)
+            .unwrap();
+
+        assert_eq!(room_detail.bet_outcome, RoomBetPossibleOutcomes::HotWon);
+        assert_eq!(room_detail.room_bets_total_pot, 4580);
+        assert_eq!(room_detail.total_hot_bets, 41);
+        assert_eq!(room_detail.total_not_bets, 34);
+
+        data_set
+            .iter()
+            .for_each(|(user_id, bet_direction, bet_amount, amount_won)| {
+                let bet_detail = room_detail
+                    .bets_made
+                    .get(&Principal::self_authenticating(
+                        (user_id + 75).to_ne_bytes(),
+                    ))
+                    .unwrap();
+
+                assert_eq!(bet_detail.bet_direction, *bet_direction);
+                assert_eq!(bet_detail.amount, *bet_amount);
+                assert_eq!(
+                    match bet_detail.payout {
+                        BetPayout::Calculated(n) => {
+                            n
+                        }
+                        _ => {
+                            0
+                        }
+                    },
+                    *amount_won
+                );
+            });
+    }
+
+    #[test]
+    fn test_tabulate_hot_or_not_outcome_for_slot_case_2() {
+        let post_creation_time = SystemTime::now();
+        let mut post = Post::new(
+            0,
+            &PostDetailsFromFrontend {
+                description: "Doggos and puppers".into(),
+                hashtags: vec!["doggo".into(), "pupper".into()],
+                video_uid: "abcd#1234".into(),
+                creator_consent_for_inclusion_in_hot_or_not: true,
+                collaborator_shares: None,
+                title: None,
+                category: None,
+            },
+            &post_creation_time,
+        );
+        let mut token_balance = TokenBalance::default();
+
+        assert!(post.hot_or_not_details.is_some());
+
+        let data_set: Vec<(u64, BetDirection, u64, u128)> = vec![
+            (1, BetDirection::Not, 10, 18),
+            (2, BetDirection::Hot, 100, 0),
+            (3, BetDirection::Hot, 100, 0),
+            (4, BetDirection::Not, 100, 180),
+            (5, BetDirection::Hot, 10, 0),
+            (6, BetDirection::Not, 100, 180),
+            (7, BetDirection::Not, 50, 90),
+            (8, BetDirection::Not, 100, 180),
+            (9, BetDirection::Hot, 50, 0),
+            (10, BetDirection::Not, 50, 90),
+            (11, BetDirection::Not, 100, 180),
+            (12, BetDirection::Not, 10, 18),
+            (13, BetDirection::Hot, 100, 0),
+            (14, BetDirection::Not, 10, 18),
+            (15, BetDirection::Hot, 50, 0),
+            (16, BetDirection::Hot, 10, 0),
+            (17, BetDirection::Hot, 10, 0),
+            (18, BetDirection::Hot, 100, 0),
+            (19, BetDirection::Not, 10, 18),
+            (20, BetDirection::Hot, 50, 0),
+            (21, BetDirection::Hot, 10, 0),
+            (22, BetDirection::Not, 50, 90),
+            (23, BetDirection::Not, 50, 90),
+            (24, BetDirection::Hot, 100, 0),
+            (25, BetDirection::Not, 50, 90),
+            (26, BetDirection::Not, 10, 18),
+            (27, BetDirection::Not, 10, 18),
+            (28, BetDirection::Not, 50, 90),
+            (29, BetDirection::Hot, 50, 0),
+            (30, BetDirection::Not, 100, 180),
+            (31, BetDirection::Not, 50, 90),
+            (32, BetDirection::Not, 50, 90),
+            (33, BetDirection::Hot, 100, 0),
+            (34, BetDirection::Not, 10, 18),
+            (35, BetDirection::Not, 10, 18),
+            (36, BetDirection::Not, 100, 180),
+            (37, BetDirection::Hot, 10, 0),
+            (38, BetDirection::Not, 100, 180),
+            (39, BetDirection::Not, 50, 90),
+            (40, BetDirection::Hot, 100, 0),
+            (41, BetDirection::Hot, 50, 0),
+            (42, BetDirection::Not, 10, 18),
+            (43, BetDirection::Hot, 50, 0),
+            (44, BetDirection::Not, 10, 18),
+            (45, BetDirection::Not, 10, 18),
+            (46, BetDirection::Hot, 100, 0),
+            (47, BetDirection::Hot, 50, 0),
+            (48, BetDirection::Hot, 50, 0),
+            (49, BetDirection::Not, 100, 180),
+            (50, BetDirection::Hot, 10, 0),
+            (51, BetDirection::Not, 50, 90),
+            (52, BetDirection::Hot, 10, 0),
+            (53, BetDirection::Not, 50, 90),
+            (54, BetDirection::Not, 10, 18),
+            (55, BetDirection::Hot, 100, 0),
+            (56, BetDirection::Hot, 50, 0),
+            (57, BetDirection::Not, 50, 90),
+            (58, BetDirection::Not, 10, 18),
+            (59, BetDirection::Not, 50, 90),
+            (60, BetDirection::Hot, 10, 0),
+            (61, BetDirection::Not, 10, 18),
+            (62, BetDirection::Not, 50, 90),
+            (63, BetDirection::Not, 50, 90),
+            (64, BetDirection::Not, 10, 18),
+            (65, BetDirection::Not, 10, 18),
+            (66, BetDirection::Not, 100, 180),
+            (67, BetDirection::Hot, 100, 0),
+            (68, BetDirection::Not, 10, 18),
+            (69, BetDirection::Not, 10, 18),
+            (70, BetDirection::Not, 50, 90),
+            (71, BetDirection::Not, 100, 180),
+            (72, BetDirection::Not, 10, 18),
+            (73, BetDirection::Not, 10, 18),
+            (74, BetDirection::Hot, 10, 0),
+            (75, BetDirection::Not, 10, 18),
+            (76, BetDirection::Hot, 50, 0),
+            (77, BetDirection::Hot, 50, 0),
+            (78, BetDirection::Not, 100, 180),
+            (79, BetDirection::Not, 100, 180),
+            (80, BetDirection::Hot, 50, 0),
+            (81, BetDirection::Hot, 10, 0),
+            (82, BetDirection::Hot, 50, 0),
+            (83, BetDirection::Not, 10, 18),
+            (84, BetDirection::Not, 50, 90),
+            (85, BetDirection::Not, 10, 18),
+            (86, BetDirection::Not, 10, 18),
+            (87, BetDirection::Hot, 100, 0),
+            (88, BetDirection::Not, 10, 18),
+            (89, BetDirection::Not, 50, 90),
+            (90, BetDirection::Hot, 100, 0),
+            (91, BetDirection::Hot, 100, 0),
+            (92, BetDirection::Hot, 10, 0),
+            (93, BetDirection::Hot, 10, 0),
+            (94, BetDirection::Hot, 100, 0),
+            (95, BetDirection::Hot, 50, 0),
+            (96, BetDirection::Hot, 100, 0),
+            (97, BetDirection::Hot, 50, 0),
+            (98, BetDirection::Hot, 50, 0),
+            (99, BetDirection::Hot, 50, 0),
+            (100, BetDirection::Hot, 50, 0),
+            (101, BetDirection::Not, 10, 18),
+            (102, BetDirection::Not, 50, 90),
+            (103, BetDirection::Not, 10, 18),
+            (104, BetDirection::Hot, 100, 0),
+            (105, BetDirection::Not, 100, 180),
+            (106, BetDirection::Hot, 100, 0),
+            (107, BetDirection::Not, 50, 90),
+            (108, BetDirection::Not, 100, 180),
+            (109, BetDirection::Not, 100, 180),
+            (110, BetDirection::Hot, 50, 0),
+            (111, BetDirection::Hot, 50, 0),
+            (112, BetDirection::Not, 50, 90),
+            (113, BetDirection::Not, 100, 180),
+            (114, BetDirection::Not, 100, 180),
+            (115, BetDirection::Hot, 50, 0),
+            (116, BetDirection::Hot, 50, 0),
+            (117, BetDirection::Hot, 100, 0),
+            (118, BetDirection::Hot, 100, 0),
+            (119, BetDirection::Not, 100, 180),
+            (120, BetDirection::Hot, 10, 0),
+            (121, BetDirection::Not, 100, 180),
+            (122, BetDirection::Not, 10, 18),
+            (123, BetDirection::Not, 10, 18),
+            (124, BetDirection::Not, 50, 90),
+            (125, BetDirection::Hot, 100, 0),
+            (126, BetDirection::Not, 10, 18),
+            (127, BetDirection::Not, 100, 180),
+            (128, BetDirection::Not, 50, 90),
+            (129, BetDirection::Not, 50, 90),
+            (130, BetDirection::Not, 10, 18),
+            (131, BetDirection::Not, 10, 18),
+            (132, BetDirection::Not, 100, 180),
+            (133, BetDirection::Hot, 100, 0),
+            (134, BetDirection::Not, 50, 90),
+            (135, BetDirection::Not, 100, 180),
+            (136, BetDirection::Not, 100, 180),
+            (137, BetDirection::Not, 50, 90),
+            (138, BetDirection::Hot, 10, 0),
+            (139, BetDirection::Not, 50, 90),
+            (140, BetDirection::Hot, 10, 0),
+            (141, BetDirection::Not, 50, 90),
+            (142, BetDirection::Hot, 10, 0),
+            (143, BetDirection::Hot, 100, 0),
+            (144, BetDirection::Hot, 100, 0),
+            (145, BetDirection::Hot, 100, 0),
+            (146, BetDirection::Not, 100, 180),
+            (147, BetDirection::Hot, 50, 0),
+            (148, BetDirection::Not, 100, 180),
+            (149, BetDirection::Hot, 100, 0),
+            (150, BetDirection::Hot, 50, 0),
+        ];
+
+        data_set
+            .iter()
+            .for_each(|(user_id, bet_direction, bet_amount, _)| {
+                let result = post.place_hot_or_not_bet(
+                    &Principal::self_authenticating(user_id.to_ne_bytes()),
+                    &Principal::self_authenticating(user_id.to_ne_bytes()),
+                    *bet_amount,
+                    bet_direction,
+                    &post_creation_time,
+                );
+                assert!(result.is_ok());
+            });
+
+        let score_tabulation_time = post_creation_time
+            .checked_add(Duration::from_secs(60 * 5))
+            .unwrap();
+
+        post.tabulate_hot_or_not_outcome_for_slot(
+            &get_mock_user_alice_canister_id(),
+            &1,
+            &mut token_balance,
+            0,
+            0,
+            HotOrNotPayoutMode::Multiplier,
+            &score_tabulation_time,
+        );
+
+        assert_eq!(token_balance.utility_token_transaction_history.len(), 2);
+        assert_eq!(token_balance.utility_token_balance, 487 + 321);
+
+        // * Room 1
+        let room_detail = post
+            .hot_or_not_details
+            .as_ref()
+            .unwrap()
+            .slot_history
+            .get(&1)
+            .unwrap()
+            .room_details
+            .get(&1)
+            .unwrap();
+
+        assert_eq!(room_detail.bet_outcome, RoomBetPossibleOutcomes::NotWon);
+        assert_eq!(room_detail.room_bets_total_pot, 4870);
+        assert_eq!(room_detail.total_hot_bets, 45);
+        assert_eq!(room_detail.total_not_bets, 55);
+
+        data_set[0..100]
+            .iter()
+            .for_each(|(user_id, bet_direction, bet_amount, amount_won)| {
+                let bet_detail = room_detail
+                    .bets_made
+                    .get(&Principal::self_authenticating(user_id.to_ne_bytes()))
+                    .unwrap();
+
+                assert_eq!(bet_detail.bet_direction, *bet_direction);
+                assert_eq!(bet_detail.amount, *bet_amount);
+                assert_eq!(
+                    match bet_detail.payout {
+                        BetPayout::Calculated(n) => {
+                            n
+                        }
+                        _ => {
+                            0
+                        }
+                    },
+                    *amount_won
+                );
+            });
+
+        // * Room 2
+        let room_detail = post
+            .hot_or_not_details
+            .as_ref()
+            .unwrap()
+            .slot_history
+            .get(&1)
+            .unwrap()
+            .room_details
+            .get(&2)
             .unwrap();
 
         assert_eq!(room_detail.bet_outcome, RoomBetPossibleOutcomes::NotWon);
-        assert_eq!(room_detail.room_bets_total_pot, 3550);
-        assert_eq!(room_detail.total_hot_bets, 28);
-        assert_eq!(room_detail.total_not_bets, 47);
+        assert_eq!(room_detail.room_bets_total_pot, 3210);
+        assert_eq!(room_detail.total_hot_bets, 20);
+        assert_eq!(room_detail.total_not_bets, 30);
 
-        data_set
+        data_set[100..]
             .iter()
             .for_each(|(user_id, bet_direction, bet_amount, amount_won)| {
                 let bet_detail = room_detail
@@ -957,96 +3561,211 @@ mod test {
                     *amount_won
                 );
             });
+    }
 
-        let data_set: Vec<(u64, BetDirection, u64, u64)> = vec![
-            (1, BetDirection::Hot, 10, 18),
-            (2, BetDirection::Hot, 50, 90),
-            (3, BetDirection::Hot, 10, 18),
-            (4, BetDirection::Not, 100, 0),
-            (5, BetDirection::Hot, 100, 180),
-            (6, BetDirection::Not, 100, 0),
-            (7, BetDirection::Hot, 50, 90),
-            (8, BetDirection::Hot, 100, 180),
-            (9, BetDirection::Hot, 100, 180),
-            (10, BetDirection::Not, 50, 0),
-            (11, BetDirection::Not, 50, 0),
-            (12, BetDirection::Hot, 50, 90),
-            (13, BetDirection::Hot, 100, 180),
-            (14, BetDirection::Hot, 100, 180),
-            (15, BetDirection::Not, 50, 0),
-            (16, BetDirection::Not, 50, 0),
-            (17, BetDirection::Not, 100, 0),
-            (18, BetDirection::Not, 100, 0),
-            (19, BetDirection::Hot, 100, 180),
-            (20, BetDirection::Not, 10, 0),
-            (21, BetDirection::Hot, 100, 180),
-            (22, BetDirection::Hot, 10, 18),
-            (23, BetDirection::Hot, 10, 18),
-            (24, BetDirection::Hot, 50, 90),
-            (25, BetDirection::Not, 100, 0),
-            (26, BetDirection::Hot, 10, 18),
-            (27, BetDirection::Hot, 100, 180),
-            (28, BetDirection::Hot, 50, 90),
-            (29, BetDirection::Hot, 50, 90),
-            (30, BetDirection::Hot, 10, 18),
-            (31, BetDirection::Hot, 10, 18),
-            (32, BetDirection::Hot, 100, 180),
-            (33, BetDirection::Not, 100, 0),
-            (34, BetDirection::Hot, 50, 90),
-            (35, BetDirection::Hot, 100, 180),
-            (36, BetDirection::Hot, 100, 180),
-            (37, BetDirection::Hot, 50, 90),
-            (38, BetDirection::Not, 10, 0),
-            (39, BetDirection::Hot, 50, 90),
-            (40, BetDirection::Not, 10, 0),
-            (41, BetDirection::Hot, 50, 90),
-            (42, BetDirection::Not, 10, 0),
-            (43, BetDirection::Not, 100, 0),
-            (44, BetDirection::Not, 100, 0),
-            (45, BetDirection::Not, 100, 0),
-            (46, BetDirection::Hot, 100, 180),
-            (47, BetDirection::Not, 50, 0),
-            (48, BetDirection::Hot, 100, 180),
-            (49, BetDirection::Not, 100, 0),
-            (50, BetDirection::Not, 50, 0),
-            (51, BetDirection::Not, 10, 0),
-            (52, BetDirection::Not, 100, 0),
-            (53, BetDirection::Hot, 100, 180),
-            (54, BetDirection::Hot, 10, 18),
-            (55, BetDirection::Not, 100, 0),
-            (56, BetDirection::Not, 100, 0),
-            (57, BetDirection::Hot, 50, 90),
-            (58, BetDirection::Not, 100, 0),
-            (59, BetDirection::Not, 10, 0),
-            (60, BetDirection::Hot, 10, 18),
-            (61, BetDirection::Not, 10, 0),
-            (62, BetDirection::Hot, 50, 90),
-            (63, BetDirection::Hot, 10, 18),
-            (64, BetDirection::Hot, 50, 90),
-            (65, BetDirection::Not, 100, 0),
-            (66, BetDirection::Not, 50, 0),
-            (67, BetDirection::Not, 100, 0),
-            (68, BetDirection::Hot, 10, 18),
-            (69, BetDirection::Hot, 50, 90),
-            (70, BetDirection::Not, 100, 0),
-            (71, BetDirection::Hot, 50, 90),
-            (72, BetDirection::Hot, 50, 90),
-            (73, BetDirection::Not, 50, 0),
-            (74, BetDirection::Not, 50, 0),
-            (75, BetDirection::Not, 50, 0),
+    #[test]
+    fn test_tabulate_hot_or_not_outcome_for_slot_case_3() {
+        let post_creation_time = SystemTime::now();
+        let mut post = Post::new(
+            0,
+            &PostDetailsFromFrontend {
+                description: "Doggos and puppers".into(),
+                hashtags: vec!["doggo".into(), "pupper".into()],
+                video_uid: "abcd#1234".into(),
+                creator_consent_for_inclusion_in_hot_or_not: true,
+                collaborator_shares: None,
+                title: None,
+                category: None,
+            },
+            &post_creation_time,
+        );
+        let mut token_balance = TokenBalance::default();
+
+        assert!(post.hot_or_not_details.is_some());
+
+        let data_set: Vec<(u64, BetDirection, u64, u128)> = vec![
+            (1, BetDirection::Not, 10, 9),
+            (2, BetDirection::Hot, 100, 90),
+            (3, BetDirection::Hot, 100, 90),
+            (4, BetDirection::Hot, 100, 90),
+            (5, BetDirection::Hot, 10, 9),
+            (6, BetDirection::Hot, 100, 90),
+            (7, BetDirection::Hot, 50, 45),
+            (8, BetDirection::Not, 100, 90),
+            (9, BetDirection::Hot, 50, 45),
+            (10, BetDirection::Not, 50, 45),
+            (11, BetDirection::Not, 100, 90),
+            (12, BetDirection::Hot, 10, 9),
+            (13, BetDirection::Hot, 100, 90),
+            (14, BetDirection::Not, 10, 9),
+            (15, BetDirection::Hot, 50, 45),
+            (16, BetDirection::Hot, 10, 9),
+            (17, BetDirection::Hot, 10, 9),
+            (18, BetDirection::Hot, 100, 90),
+            (19, BetDirection::Not, 10, 9),
+            (20, BetDirection::Hot, 50, 45),
+            (21, BetDirection::Hot, 10, 9),
+            (22, BetDirection::Hot, 50, 45),
+            (23, BetDirection::Not, 50, 45),
+            (24, BetDirection::Hot, 100, 90),
+            (25, BetDirection::Not, 50, 45),
+            (26, BetDirection::Not, 10, 9),
+            (27, BetDirection::Hot, 10, 9),
+            (28, BetDirection::Hot, 50, 45),
+            (29, BetDirection::Hot, 50, 45),
+            (30, BetDirection::Not, 100, 90),
+            (31, BetDirection::Hot, 50, 45),
+            (32, BetDirection::Not, 50, 45),
+            (33, BetDirection::Hot, 100, 90),
+            (34, BetDirection::Hot, 10, 9),
+            (35, BetDirection::Not, 10, 9),
+            (36, BetDirection::Not, 100, 90),
+            (37, BetDirection::Hot, 10, 9),
+            (38, BetDirection::Not, 100, 90),
+            (39, BetDirection::Not, 50, 45),
+            (40, BetDirection::Hot, 100, 90),
+            (41, BetDirection::Hot, 50, 45),
+            (42, BetDirection::Not, 10, 9),
+            (43, BetDirection::Hot, 50, 45),
+            (44, BetDirection::Not, 10, 9),
+            (45, BetDirection::Not, 10, 9),
+            (46, BetDirection::Hot, 100, 90),
+            (47, BetDirection::Hot, 50, 45),
+            (48, BetDirection::Hot, 50, 45),
+            (49, BetDirection::Not, 100, 90),
+            (50, BetDirection::Hot, 10, 9),
+            (51, BetDirection::Not, 50, 45),
+            (52, BetDirection::Hot, 10, 9),
+            (53, BetDirection::Not, 50, 45),
+            (54, BetDirection::Not, 10, 9),
+            (55, BetDirection::Hot, 100, 90),
+            (56, BetDirection::Hot, 50, 45),
+            (57, BetDirection::Not, 50, 45),
+            (58, BetDirection::Not, 10, 9),
+            (59, BetDirection::Not, 50, 45),
+            (60, BetDirection::Hot, 10, 9),
+            (61, BetDirection::Not, 10, 9),
+            (62, BetDirection::Not, 50, 45),
+            (63, BetDirection::Not, 50, 45),
+            (64, BetDirection::Not, 10, 9),
+            (65, BetDirection::Not, 10, 9),
+            (66, BetDirection::Not, 100, 90),
+            (67, BetDirection::Hot, 100, 90),
+            (68, BetDirection::Not, 10, 9),
+            (69, BetDirection::Not, 10, 9),
+            (70, BetDirection::Not, 50, 45),
+            (71, BetDirection::Not, 100, 90),
+            (72, BetDirection::Not, 10, 9),
+            (73, BetDirection::Not, 10, 9),
+            (74, BetDirection::Hot, 10, 9),
+            (75, BetDirection::Not, 10, 9),
+            (76, BetDirection::Hot, 50, 45),
+            (77, BetDirection::Hot, 50, 45),
+            (78, BetDirection::Not, 100, 90),
+            (79, BetDirection::Not, 100, 90),
+            (80, BetDirection::Hot, 50, 45),
         ];
 
-        // * 1 min into the 2nd hour/2nd slot
-        let post_creation_time = post_creation_time
-            .checked_add(Duration::from_secs(60 * (60 + 1)))
-            .unwrap();
+        data_set
+            .iter()
+            .for_each(|(user_id, bet_direction, bet_amount, _)| {
+                let result = post.place_hot_or_not_bet(
+                    &Principal::self_authenticating(user_id.to_ne_bytes()),
+                    &Principal::self_authenticating(user_id.to_ne_bytes()),
+                    *bet_amount,
+                    bet_direction,
+                    &post_creation_time,
+                );
+                assert!(result.is_ok());
+            });
+
+        let score_tabulation_time = post_creation_time
+            .checked_add(Duration::from_secs(60 * 5))
+            .unwrap();
+
+        post.tabulate_hot_or_not_outcome_for_slot(
+            &get_mock_user_alice_canister_id(),
+            &1,
+            &mut token_balance,
+            0,
+            0,
+            HotOrNotPayoutMode::Multiplier,
+            &score_tabulation_time,
+        );
+
+        assert_eq!(token_balance.utility_token_transaction_history.len(), 1);
+        assert_eq!(token_balance.utility_token_balance, 390);
+
+        let room_detail = post
+            .hot_or_not_details
+            .as_ref()
+            .unwrap()
+            .slot_history
+            .get(&1)
+            .unwrap()
+            .room_details
+            .get(&1)
+            .unwrap();
+
+        assert_eq!(room_detail.bet_outcome, RoomBetPossibleOutcomes::Draw);
+        assert_eq!(room_detail.room_bets_total_pot, 3900);
+        assert_eq!(room_detail.total_hot_bets, 40);
+        assert_eq!(room_detail.total_not_bets, 40);
+
+        data_set
+            .iter()
+            .for_each(|(user_id, bet_direction, bet_amount, amount_won)| {
+                let bet_detail = room_detail
+                    .bets_made
+                    .get(&Principal::self_authenticating(user_id.to_ne_bytes()))
+                    .unwrap();
+
+                assert_eq!(bet_detail.bet_direction, *bet_direction);
+                assert_eq!(bet_detail.amount, *bet_amount);
+                assert_eq!(
+                    match bet_detail.payout {
+                        BetPayout::Calculated(n) => {
+                            n
+                        }
+                        _ => {
+                            0
+                        }
+                    },
+                    *amount_won
+                );
+            });
+    }
+
+    #[test]
+    fn test_tabulate_hot_or_not_outcome_for_slot_with_house_rake() {
+        let post_creation_time = SystemTime::now();
+        let mut post = Post::new(
+            0,
+            &PostDetailsFromFrontend {
+                description: "Doggos and puppers".into(),
+                hashtags: vec!["doggo".into(), "pupper".into()],
+                video_uid: "abcd#1234".into(),
+                creator_consent_for_inclusion_in_hot_or_not: true,
+                collaborator_shares: None,
+                title: None,
+                category: None,
+            },
+            &post_creation_time,
+        );
+        let mut token_balance = TokenBalance::default();
+
+        let data_set: Vec<(u64, BetDirection, u64, u128)> = vec![
+            (1, BetDirection::Hot, 100, 178),
+            (2, BetDirection::Hot, 50, 89),
+            (3, BetDirection::Not, 100, 0),
+        ];
 
         data_set
             .iter()
             .for_each(|(user_id, bet_direction, bet_amount, _)| {
                 let result = post.place_hot_or_not_bet(
-                    &Principal::self_authenticating((user_id + 75).to_ne_bytes()),
-                    &Principal::self_authenticating((user_id + 75).to_ne_bytes()),
+                    &Principal::self_authenticating(user_id.to_ne_bytes()),
+                    &Principal::self_authenticating(user_id.to_ne_bytes()),
                     *bet_amount,
                     bet_direction,
                     &post_creation_time,
@@ -1058,60 +3777,75 @@ mod test {
             .checked_add(Duration::from_secs(60 * 5))
             .unwrap();
 
-        post.tabulate_hot_or_not_outcome_for_slot(
+        let settlements = post.tabulate_hot_or_not_outcome_for_slot(
             &get_mock_user_alice_canister_id(),
-            &2,
+            &1,
             &mut token_balance,
+            1,
+            0,
+            HotOrNotPayoutMode::Multiplier,
             &score_tabulation_time,
         );
 
-        assert_eq!(token_balance.utility_token_transaction_history.len(), 2);
-        assert_eq!(token_balance.utility_token_balance, 355 + 458);
+        assert_eq!(
+            settlements.house_rake_settlements,
+            vec![HouseRakeSettlement {
+                post_canister_id: get_mock_user_alice_canister_id(),
+                post_id: 0,
+                slot_id: 1,
+                room_id: 1,
+                room_pot_total_amount: 250,
+                rake_amount: 3,
+                number_of_failed_attempts: 0,
+            }]
+        );
+        assert_eq!(token_balance.utility_token_balance, 25);
 
         let room_detail = post
             .hot_or_not_details
             .as_ref()
             .unwrap()
             .slot_history
-            .get(&2)
+            .get(&1)
             .unwrap()
             .room_details
             .get(&1)
             .unwrap();
 
         assert_eq!(room_detail.bet_outcome, RoomBetPossibleOutcomes::HotWon);
-        assert_eq!(room_detail.room_bets_total_pot, 4580);
-        assert_eq!(room_detail.total_hot_bets, 41);
-        assert_eq!(room_detail.total_not_bets, 34);
 
-        data_set
-            .iter()
-            .for_each(|(user_id, bet_direction, bet_amount, amount_won)| {
-                let bet_detail = room_detail
-                    .bets_made
-                    .get(&Principal::self_authenticating(
-                        (user_id + 75).to_ne_bytes(),
-                    ))
-                    .unwrap();
+        data_set.iter().for_each(|(user_id, _, _, amount_won)| {
+            let bet_detail = room_detail
+                .bets_made
+                .get(&Principal::self_authenticating(user_id.to_ne_bytes()))
+                .unwrap();
+
+            assert_eq!(
+                match bet_detail.payout {
+                    BetPayout::Calculated(n) => n,
+                    _ => 0,
+                },
+                *amount_won
+            );
+        });
 
-                assert_eq!(bet_detail.bet_direction, *bet_direction);
-                assert_eq!(bet_detail.amount, *bet_amount);
-                assert_eq!(
-                    match bet_detail.payout {
-                        BetPayout::Calculated(n) => {
-                            n
-                        }
-                        _ => {
-                            0
-                        }
-                    },
-                    *amount_won
-                );
-            });
+        // * rooms already tabulated are not raked again
+        assert_eq!(
+            post.tabulate_hot_or_not_outcome_for_slot(
+                &get_mock_user_alice_canister_id(),
+                &1,
+                &mut token_balance,
+                1,
+                0,
+                HotOrNotPayoutMode::Multiplier,
+                &score_tabulation_time,
+            ),
+            SlotTabulationSettlements::default()
+        );
     }
 
     #[test]
-    fn test_tabulate_hot_or_not_outcome_for_slot_case_2() {
+    fn test_tabulate_hot_or_not_outcome_for_slot_with_referrer_shares() {
         let post_creation_time = SystemTime::now();
         let mut post = Post::new(
             0,
@@ -1120,164 +3854,241 @@ mod test {
                 hashtags: vec!["doggo".into(), "pupper".into()],
                 video_uid: "abcd#1234".into(),
                 creator_consent_for_inclusion_in_hot_or_not: true,
+                collaborator_shares: None,
+                title: None,
+                category: None,
             },
             &post_creation_time,
         );
         let mut token_balance = TokenBalance::default();
+        let referrer_canister_id = get_mock_user_alice_canister_id();
 
-        assert!(post.hot_or_not_details.is_some());
+        // * Bettors 1 and 3 were referred, only bettor 1 wins
+        let data_set: Vec<(u64, BetDirection, u64, bool, u128)> = vec![
+            (1, BetDirection::Hot, 100, true, 168),
+            (2, BetDirection::Hot, 50, false, 89),
+            (3, BetDirection::Not, 100, true, 0),
+        ];
 
-        let data_set: Vec<(u64, BetDirection, u64, u64)> = vec![
-            (1, BetDirection::Not, 10, 18),
-            (2, BetDirection::Hot, 100, 0),
-            (3, BetDirection::Hot, 100, 0),
-            (4, BetDirection::Not, 100, 180),
-            (5, BetDirection::Hot, 10, 0),
-            (6, BetDirection::Not, 100, 180),
-            (7, BetDirection::Not, 50, 90),
-            (8, BetDirection::Not, 100, 180),
-            (9, BetDirection::Hot, 50, 0),
-            (10, BetDirection::Not, 50, 90),
-            (11, BetDirection::Not, 100, 180),
-            (12, BetDirection::Not, 10, 18),
-            (13, BetDirection::Hot, 100, 0),
-            (14, BetDirection::Not, 10, 18),
-            (15, BetDirection::Hot, 50, 0),
-            (16, BetDirection::Hot, 10, 0),
-            (17, BetDirection::Hot, 10, 0),
-            (18, BetDirection::Hot, 100, 0),
-            (19, BetDirection::Not, 10, 18),
-            (20, BetDirection::Hot, 50, 0),
-            (21, BetDirection::Hot, 10, 0),
-            (22, BetDirection::Not, 50, 90),
-            (23, BetDirection::Not, 50, 90),
-            (24, BetDirection::Hot, 100, 0),
-            (25, BetDirection::Not, 50, 90),
-            (26, BetDirection::Not, 10, 18),
-            (27, BetDirection::Not, 10, 18),
-            (28, BetDirection::Not, 50, 90),
-            (29, BetDirection::Hot, 50, 0),
-            (30, BetDirection::Not, 100, 180),
-            (31, BetDirection::Not, 50, 90),
-            (32, BetDirection::Not, 50, 90),
-            (33, BetDirection::Hot, 100, 0),
-            (34, BetDirection::Not, 10, 18),
-            (35, BetDirection::Not, 10, 18),
-            (36, BetDirection::Not, 100, 180),
-            (37, BetDirection::Hot, 10, 0),
-            (38, BetDirection::Not, 100, 180),
-            (39, BetDirection::Not, 50, 90),
-            (40, BetDirection::Hot, 100, 0),
-            (41, BetDirection::Hot, 50, 0),
-            (42, BetDirection::Not, 10, 18),
-            (43, BetDirection::Hot, 50, 0),
-            (44, BetDirection::Not, 10, 18),
-            (45, BetDirection::Not, 10, 18),
-            (46, BetDirection::Hot, 100, 0),
-            (47, BetDirection::Hot, 50, 0),
-            (48, BetDirection::Hot, 50, 0),
-            (49, BetDirection::Not, 100, 180),
-            (50, BetDirection::Hot, 10, 0),
-            (51, BetDirection::Not, 50, 90),
-            (52, BetDirection::Hot, 10, 0),
-            (53, BetDirection::Not, 50, 90),
-            (54, BetDirection::Not, 10, 18),
-            (55, BetDirection::Hot, 100, 0),
-            (56, BetDirection::Hot, 50, 0),
-            (57, BetDirection::Not, 50, 90),
-            (58, BetDirection::Not, 10, 18),
-            (59, BetDirection::Not, 50, 90),
-            (60, BetDirection::Hot, 10, 0),
-            (61, BetDirection::Not, 10, 18),
-            (62, BetDirection::Not, 50, 90),
-            (63, BetDirection::Not, 50, 90),
-            (64, BetDirection::Not, 10, 18),
-            (65, BetDirection::Not, 10, 18),
-            (66, BetDirection::Not, 100, 180),
-            (67, BetDirection::Hot, 100, 0),
-            (68, BetDirection::Not, 10, 18),
-            (69, BetDirection::Not, 10, 18),
-            (70, BetDirection::Not, 50, 90),
-            (71, BetDirection::Not, 100, 180),
-            (72, BetDirection::Not, 10, 18),
-            (73, BetDirection::Not, 10, 18),
-            (74, BetDirection::Hot, 10, 0),
-            (75, BetDirection::Not, 10, 18),
-            (76, BetDirection::Hot, 50, 0),
-            (77, BetDirection::Hot, 50, 0),
-            (78, BetDirection::Not, 100, 180),
-            (79, BetDirection::Not, 100, 180),
-            (80, BetDirection::Hot, 50, 0),
-            (81, BetDirection::Hot, 10, 0),
-            (82, BetDirection::Hot, 50, 0),
-            (83, BetDirection::Not, 10, 18),
-            (84, BetDirection::Not, 50, 90),
-            (85, BetDirection::Not, 10, 18),
-            (86, BetDirection::Not, 10, 18),
-            (87, BetDirection::Hot, 100, 0),
-            (88, BetDirection::Not, 10, 18),
-            (89, BetDirection::Not, 50, 90),
-            (90, BetDirection::Hot, 100, 0),
-            (91, BetDirection::Hot, 100, 0),
-            (92, BetDirection::Hot, 10, 0),
-            (93, BetDirection::Hot, 10, 0),
-            (94, BetDirection::Hot, 100, 0),
-            (95, BetDirection::Hot, 50, 0),
-            (96, BetDirection::Hot, 100, 0),
-            (97, BetDirection::Hot, 50, 0),
-            (98, BetDirection::Hot, 50, 0),
-            (99, BetDirection::Hot, 50, 0),
-            (100, BetDirection::Hot, 50, 0),
-            (101, BetDirection::Not, 10, 18),
-            (102, BetDirection::Not, 50, 90),
-            (103, BetDirection::Not, 10, 18),
-            (104, BetDirection::Hot, 100, 0),
-            (105, BetDirection::Not, 100, 180),
-            (106, BetDirection::Hot, 100, 0),
-            (107, BetDirection::Not, 50, 90),
-            (108, BetDirection::Not, 100, 180),
-            (109, BetDirection::Not, 100, 180),
-            (110, BetDirection::Hot, 50, 0),
-            (111, BetDirection::Hot, 50, 0),
-            (112, BetDirection::Not, 50, 90),
-            (113, BetDirection::Not, 100, 180),
-            (114, BetDirection::Not, 100, 180),
-            (115, BetDirection::Hot, 50, 0),
-            (116, BetDirection::Hot, 50, 0),
-            (117, BetDirection::Hot, 100, 0),
-            (118, BetDirection::Hot, 100, 0),
-            (119, BetDirection::Not, 100, 180),
-            (120, BetDirection::Hot, 10, 0),
-            (121, BetDirection::Not, 100, 180),
-            (122, BetDirection::Not, 10, 18),
-            (123, BetDirection::Not, 10, 18),
-            (124, BetDirection::Not, 50, 90),
-            (125, BetDirection::Hot, 100, 0),
-            (126, BetDirection::Not, 10, 18),
-            (127, BetDirection::Not, 100, 180),
-            (128, BetDirection::Not, 50, 90),
-            (129, BetDirection::Not, 50, 90),
-            (130, BetDirection::Not, 10, 18),
-            (131, BetDirection::Not, 10, 18),
-            (132, BetDirection::Not, 100, 180),
-            (133, BetDirection::Hot, 100, 0),
-            (134, BetDirection::Not, 50, 90),
-            (135, BetDirection::Not, 100, 180),
-            (136, BetDirection::Not, 100, 180),
-            (137, BetDirection::Not, 50, 90),
-            (138, BetDirection::Hot, 10, 0),
-            (139, BetDirection::Not, 50, 90),
-            (140, BetDirection::Hot, 10, 0),
-            (141, BetDirection::Not, 50, 90),
-            (142, BetDirection::Hot, 10, 0),
-            (143, BetDirection::Hot, 100, 0),
-            (144, BetDirection::Hot, 100, 0),
-            (145, BetDirection::Hot, 100, 0),
-            (146, BetDirection::Not, 100, 180),
-            (147, BetDirection::Hot, 50, 0),
-            (148, BetDirection::Not, 100, 180),
-            (149, BetDirection::Hot, 100, 0),
-            (150, BetDirection::Hot, 50, 0),
+        data_set
+            .iter()
+            .for_each(|(user_id, bet_direction, bet_amount, is_referred, _)| {
+                let bet_maker = Principal::self_authenticating(user_id.to_ne_bytes());
+                let result = post.place_hot_or_not_bet(
+                    &bet_maker,
+                    &bet_maker,
+                    *bet_amount,
+                    bet_direction,
+                    &post_creation_time,
+                );
+                assert!(result.is_ok());
+                if *is_referred {
+                    post.set_referrer_of_bet(1, &bet_maker, referrer_canister_id);
+                }
+            });
+
+        let settlements = post.tabulate_hot_or_not_outcome_for_slot(
+            &get_mock_user_alice_canister_id(),
+            &1,
+            &mut token_balance,
+            1,
+            5,
+            HotOrNotPayoutMode::Multiplier,
+            &(post_creation_time + Duration::from_secs(60 * 5)),
+        );
+
+        // * The referrer's share is cut from the winnings, not minted on top of them
+        let referee = Principal::self_authenticating(1_u64.to_ne_bytes());
+        assert_eq!(
+            settlements.referrer_share_settlements,
+            vec![ReferrerShareSettlement {
+                referrer_canister_id,
+                referee_principal_id: referee,
+                referee_canister_id: Some(referee),
+                post_canister_id: get_mock_user_alice_canister_id(),
+                post_id: 0,
+                slot_id: 1,
+                room_id: 1,
+                share_amount: 10,
+                number_of_failed_attempts: 0,
+            }]
+        );
+        assert_eq!(settlements.house_rake_settlements[0].rake_amount, 3);
+
+        let room_detail = post
+            .hot_or_not_details
+            .as_ref()
+            .unwrap()
+            .slot_history
+            .get(&1)
+            .unwrap()
+            .room_details
+            .get(&1)
+            .unwrap();
+
+        data_set.iter().for_each(|(user_id, _, _, _, amount_won)| {
+            let bet_detail = room_detail
+                .bets_made
+                .get(&Principal::self_authenticating(user_id.to_ne_bytes()))
+                .unwrap();
+
+            assert_eq!(bet_detail.payout, BetPayout::Calculated(*amount_won));
+        });
+    }
+
+    #[test]
+    fn test_tabulate_hot_or_not_outcome_for_slot_pays_out_the_whole_pot_in_both_payout_modes() {
+        [
+            HotOrNotPayoutMode::Multiplier,
+            HotOrNotPayoutMode::Parimutuel,
+        ]
+        .into_iter()
+        .for_each(|payout_mode| {
+            let post_creation_time = SystemTime::now();
+            let mut post = Post::new(
+                0,
+                &PostDetailsFromFrontend {
+                    description: "Doggos and puppers".into(),
+                    hashtags: vec!["doggo".into(), "pupper".into()],
+                    video_uid: "abcd#1234".into(),
+                    creator_consent_for_inclusion_in_hot_or_not: true,
+                    collaborator_shares: None,
+                    title: None,
+                    category: None,
+                },
+                &post_creation_time,
+            );
+            let mut token_balance = TokenBalance::default();
+
+            // * Both sides stake the same, so both modes pay winners twice their stake
+            // * before the cuts
+            [
+                (1_u64, BetDirection::Hot, 100),
+                (2, BetDirection::Hot, 100),
+                (3, BetDirection::Not, 200),
+            ]
+            .iter()
+            .for_each(|(user_id, bet_direction, bet_amount)| {
+                post.place_hot_or_not_bet(
+                    &Principal::self_authenticating(user_id.to_ne_bytes()),
+                    &Principal::self_authenticating(user_id.to_ne_bytes()),
+                    *bet_amount,
+                    bet_direction,
+                    &post_creation_time,
+                )
+                .unwrap();
+            });
+
+            let settlements = post.tabulate_hot_or_not_outcome_for_slot(
+                &get_mock_user_alice_canister_id(),
+                &1,
+                &mut token_balance,
+                5,
+                0,
+                payout_mode,
+                &(post_creation_time + Duration::from_secs(60 * 5)),
+            );
+
+            let payouts_total: u128 = post.hot_or_not_details.as_ref().unwrap().slot_history[&1]
+                .room_details[&1]
+                .bets_made
+                .values()
+                .map(|bet_details| match bet_details.payout {
+                    BetPayout::Calculated(payout) => payout,
+                    BetPayout::NotCalculatedYet => 0,
+                })
+                .sum();
+            let rake_total: u64 = settlements
+                .house_rake_settlements
+                .iter()
+                .map(|house_rake_settlement| house_rake_settlement.rake_amount)
+                .sum();
+
+            assert_eq!(payouts_total, 340, "{:?}", payout_mode);
+            assert_eq!(token_balance.utility_token_balance, 40, "{:?}", payout_mode);
+            assert_eq!(rake_total, 20, "{:?}", payout_mode);
+            assert_eq!(
+                payouts_total + token_balance.utility_token_balance as u128 + rake_total as u128,
+                400,
+                "{:?}",
+                payout_mode
+            );
+        });
+    }
+
+    #[test]
+    fn test_slot_is_awaiting_tabulation_until_tabulated() {
+        let post_creation_time = SystemTime::now();
+        let mut post = Post::new(
+            0,
+            &PostDetailsFromFrontend {
+                description: "Doggos and puppers".into(),
+                hashtags: vec!["doggo".into(), "pupper".into()],
+                video_uid: "abcd#1234".into(),
+                creator_consent_for_inclusion_in_hot_or_not: true,
+                collaborator_shares: None,
+                title: None,
+                category: None,
+            },
+            &post_creation_time,
+        );
+        let mut token_balance = TokenBalance::default();
+
+        assert_eq!(
+            post.get_slot_closing_time(1),
+            post_creation_time + Duration::from_secs(DURATION_OF_EACH_SLOT_IN_SECONDS)
+        );
+
+        post.place_hot_or_not_bet(
+            &get_mock_user_bob_principal_id(),
+            &get_mock_user_bob_principal_id(),
+            100,
+            &BetDirection::Hot,
+            &post_creation_time,
+        )
+        .unwrap();
+        let is_slot_awaiting_tabulation = |post: &Post| {
+            post.hot_or_not_details.as_ref().unwrap().slot_history[&1].is_awaiting_tabulation()
+        };
+        assert!(is_slot_awaiting_tabulation(&post));
+
+        post.tabulate_hot_or_not_outcome_for_slot(
+            &get_mock_user_alice_canister_id(),
+            &1,
+            &mut token_balance,
+            0,
+            0,
+            HotOrNotPayoutMode::Multiplier,
+            &post.get_slot_closing_time(1),
+        );
+        assert!(!is_slot_awaiting_tabulation(&post));
+        assert!(post.hot_or_not_details.as_ref().unwrap().slot_history[&1].is_tabulated());
+    }
+
+    #[test]
+    fn test_tabulate_hot_or_not_outcome_for_slot_with_parimutuel_payouts() {
+        let post_creation_time = SystemTime::now();
+        let mut post = Post::new(
+            0,
+            &PostDetailsFromFrontend {
+                description: "Doggos and puppers".into(),
+                hashtags: vec!["doggo".into(), "pupper".into()],
+                video_uid: "abcd#1234".into(),
+                creator_consent_for_inclusion_in_hot_or_not: true,
+                collaborator_shares: None,
+                title: None,
+                category: None,
+            },
+            &post_creation_time,
+        );
+        let mut token_balance = TokenBalance::default();
+
+        // * hot wins on the number of bets while staking less, so its winners split the
+        // * bigger losing pot
+        let data_set: Vec<(u64, BetDirection, u64, u128)> = vec![
+            (1, BetDirection::Hot, 100, 300),
+            (2, BetDirection::Hot, 50, 150),
+            (3, BetDirection::Not, 350, 0),
         ];
 
         data_set
@@ -1301,95 +4112,74 @@ mod test {
             &get_mock_user_alice_canister_id(),
             &1,
             &mut token_balance,
+            0,
+            0,
+            HotOrNotPayoutMode::Parimutuel,
             &score_tabulation_time,
         );
 
-        assert_eq!(token_balance.utility_token_transaction_history.len(), 2);
-        assert_eq!(token_balance.utility_token_balance, 487 + 321);
-
-        // * Room 1
-        let room_detail = post
-            .hot_or_not_details
-            .as_ref()
-            .unwrap()
-            .slot_history
-            .get(&1)
-            .unwrap()
-            .room_details
-            .get(&1)
-            .unwrap();
-
-        assert_eq!(room_detail.bet_outcome, RoomBetPossibleOutcomes::NotWon);
-        assert_eq!(room_detail.room_bets_total_pot, 4870);
-        assert_eq!(room_detail.total_hot_bets, 45);
-        assert_eq!(room_detail.total_not_bets, 55);
-
-        data_set[0..100]
-            .iter()
-            .for_each(|(user_id, bet_direction, bet_amount, amount_won)| {
-                let bet_detail = room_detail
-                    .bets_made
-                    .get(&Principal::self_authenticating(user_id.to_ne_bytes()))
-                    .unwrap();
+        let get_payouts = |post: &Post| -> Vec<u128> {
+            let room_detail =
+                &post.hot_or_not_details.as_ref().unwrap().slot_history[&1].room_details[&1];
+            data_set
+                .iter()
+                .map(|(user_id, _, _, _)| {
+                    match room_detail.bets_made
+                        [&Principal::self_authenticating(user_id.to_ne_bytes())]
+                        .payout
+                    {
+                        BetPayout::Calculated(payout) => payout,
+                        BetPayout::NotCalculatedYet => 0,
+                    }
+                })
+                .collect()
+        };
 
-                assert_eq!(bet_detail.bet_direction, *bet_direction);
-                assert_eq!(bet_detail.amount, *bet_amount);
-                assert_eq!(
-                    match bet_detail.payout {
-                        BetPayout::Calculated(n) => {
-                            n
-                        }
-                        _ => {
-                            0
-                        }
-                    },
-                    *amount_won
-                );
-            });
+        let room_detail =
+            &post.hot_or_not_details.as_ref().unwrap().slot_history[&1].room_details[&1];
+        assert_eq!(room_detail.bet_outcome, RoomBetPossibleOutcomes::HotWon);
+        assert_eq!(
+            room_detail.payout_mode,
+            Some(HotOrNotPayoutMode::Parimutuel)
+        );
+        assert_eq!(
+            get_payouts(&post),
+            data_set
+                .iter()
+                .map(|(_, _, _, amount_won)| *amount_won)
+                .collect::<Vec<_>>()
+        );
+        // * the winners take the whole pot less the commission
+        assert_eq!(get_payouts(&post).iter().sum::<u128>(), 450);
+        assert_eq!(token_balance.utility_token_balance, 50);
 
-        // * Room 2
-        let room_detail = post
-            .hot_or_not_details
-            .as_ref()
+        // * payouts worked out again keep the mode the room was settled in
+        post.hot_or_not_details
+            .as_mut()
             .unwrap()
             .slot_history
-            .get(&1)
+            .get_mut(&1)
             .unwrap()
             .room_details
-            .get(&2)
-            .unwrap();
-
-        assert_eq!(room_detail.bet_outcome, RoomBetPossibleOutcomes::NotWon);
-        assert_eq!(room_detail.room_bets_total_pot, 3210);
-        assert_eq!(room_detail.total_hot_bets, 20);
-        assert_eq!(room_detail.total_not_bets, 30);
-
-        data_set[100..]
-            .iter()
-            .for_each(|(user_id, bet_direction, bet_amount, amount_won)| {
-                let bet_detail = room_detail
-                    .bets_made
-                    .get(&Principal::self_authenticating(user_id.to_ne_bytes()))
-                    .unwrap();
-
-                assert_eq!(bet_detail.bet_direction, *bet_direction);
-                assert_eq!(bet_detail.amount, *bet_amount);
-                assert_eq!(
-                    match bet_detail.payout {
-                        BetPayout::Calculated(n) => {
-                            n
-                        }
-                        _ => {
-                            0
-                        }
-                    },
-                    *amount_won
-                );
-            });
+            .get_mut(&1)
+            .unwrap()
+            .bets_made
+            .values_mut()
+            .for_each(|bet_details| bet_details.payout = BetPayout::NotCalculatedYet);
+        post.tabulate_hot_or_not_outcome_for_slot(
+            &get_mock_user_alice_canister_id(),
+            &1,
+            &mut token_balance,
+            0,
+            0,
+            HotOrNotPayoutMode::Multiplier,
+            &score_tabulation_time,
+        );
+        assert_eq!(get_payouts(&post), vec![300, 150, 0]);
     }
 
     #[test]
-    fn test_tabulate_hot_or_not_outcome_for_slot_case_3() {
+    fn test_get_pot_insurance_claims_for_slot() {
         let post_creation_time = SystemTime::now();
         let mut post = Post::new(
             0,
@@ -1398,94 +4188,101 @@ mod test {
                 hashtags: vec!["doggo".into(), "pupper".into()],
                 video_uid: "abcd#1234".into(),
                 creator_consent_for_inclusion_in_hot_or_not: true,
+                collaborator_shares: None,
+                title: None,
+                category: None,
             },
             &post_creation_time,
         );
         let mut token_balance = TokenBalance::default();
 
-        assert!(post.hot_or_not_details.is_some());
+        [
+            (1_u64, BetDirection::Hot, 100),
+            (2, BetDirection::Hot, 50),
+            (3, BetDirection::Not, 100),
+        ]
+        .iter()
+        .for_each(|(user_id, bet_direction, bet_amount)| {
+            post.place_hot_or_not_bet(
+                &Principal::self_authenticating(user_id.to_ne_bytes()),
+                &Principal::self_authenticating(user_id.to_ne_bytes()),
+                *bet_amount,
+                bet_direction,
+                &post_creation_time,
+            )
+            .unwrap();
+        });
 
-        let data_set: Vec<(u64, BetDirection, u64, u64)> = vec![
-            (1, BetDirection::Not, 10, 9),
-            (2, BetDirection::Hot, 100, 90),
-            (3, BetDirection::Hot, 100, 90),
-            (4, BetDirection::Hot, 100, 90),
-            (5, BetDirection::Hot, 10, 9),
-            (6, BetDirection::Hot, 100, 90),
-            (7, BetDirection::Hot, 50, 45),
-            (8, BetDirection::Not, 100, 90),
-            (9, BetDirection::Hot, 50, 45),
-            (10, BetDirection::Not, 50, 45),
-            (11, BetDirection::Not, 100, 90),
-            (12, BetDirection::Hot, 10, 9),
-            (13, BetDirection::Hot, 100, 90),
-            (14, BetDirection::Not, 10, 9),
-            (15, BetDirection::Hot, 50, 45),
-            (16, BetDirection::Hot, 10, 9),
-            (17, BetDirection::Hot, 10, 9),
-            (18, BetDirection::Hot, 100, 90),
-            (19, BetDirection::Not, 10, 9),
-            (20, BetDirection::Hot, 50, 45),
-            (21, BetDirection::Hot, 10, 9),
-            (22, BetDirection::Hot, 50, 45),
-            (23, BetDirection::Not, 50, 45),
-            (24, BetDirection::Hot, 100, 90),
-            (25, BetDirection::Not, 50, 45),
-            (26, BetDirection::Not, 10, 9),
-            (27, BetDirection::Hot, 10, 9),
-            (28, BetDirection::Hot, 50, 45),
-            (29, BetDirection::Hot, 50, 45),
-            (30, BetDirection::Not, 100, 90),
-            (31, BetDirection::Hot, 50, 45),
-            (32, BetDirection::Not, 50, 45),
-            (33, BetDirection::Hot, 100, 90),
-            (34, BetDirection::Hot, 10, 9),
-            (35, BetDirection::Not, 10, 9),
-            (36, BetDirection::Not, 100, 90),
-            (37, BetDirection::Hot, 10, 9),
-            (38, BetDirection::Not, 100, 90),
-            (39, BetDirection::Not, 50, 45),
-            (40, BetDirection::Hot, 100, 90),
-            (41, BetDirection::Hot, 50, 45),
-            (42, BetDirection::Not, 10, 9),
-            (43, BetDirection::Hot, 50, 45),
-            (44, BetDirection::Not, 10, 9),
-            (45, BetDirection::Not, 10, 9),
-            (46, BetDirection::Hot, 100, 90),
-            (47, BetDirection::Hot, 50, 45),
-            (48, BetDirection::Hot, 50, 45),
-            (49, BetDirection::Not, 100, 90),
-            (50, BetDirection::Hot, 10, 9),
-            (51, BetDirection::Not, 50, 45),
-            (52, BetDirection::Hot, 10, 9),
-            (53, BetDirection::Not, 50, 45),
-            (54, BetDirection::Not, 10, 9),
-            (55, BetDirection::Hot, 100, 90),
-            (56, BetDirection::Hot, 50, 45),
-            (57, BetDirection::Not, 50, 45),
-            (58, BetDirection::Not, 10, 9),
-            (59, BetDirection::Not, 50, 45),
-            (60, BetDirection::Hot, 10, 9),
-            (61, BetDirection::Not, 10, 9),
-            (62, BetDirection::Not, 50, 45),
-            (63, BetDirection::Not, 50, 45),
-            (64, BetDirection::Not, 10, 9),
-            (65, BetDirection::Not, 10, 9),
-            (66, BetDirection::Not, 100, 90),
-            (67, BetDirection::Hot, 100, 90),
-            (68, BetDirection::Not, 10, 9),
-            (69, BetDirection::Not, 10, 9),
-            (70, BetDirection::Not, 50, 45),
-            (71, BetDirection::Not, 100, 90),
-            (72, BetDirection::Not, 10, 9),
-            (73, BetDirection::Not, 10, 9),
-            (74, BetDirection::Hot, 10, 9),
-            (75, BetDirection::Not, 10, 9),
-            (76, BetDirection::Hot, 50, 45),
-            (77, BetDirection::Hot, 50, 45),
-            (78, BetDirection::Not, 100, 90),
-            (79, BetDirection::Not, 100, 90),
-            (80, BetDirection::Hot, 50, 45),
+        // * nothing is owed before the room is tabulated
+        assert!(post
+            .get_pot_insurance_claims_for_slot(
+                &get_mock_user_alice_canister_id(),
+                &1,
+                500,
+                &post_creation_time
+            )
+            .is_empty());
+
+        post.tabulate_hot_or_not_outcome_for_slot(
+            &get_mock_user_alice_canister_id(),
+            &1,
+            &mut token_balance,
+            0,
+            0,
+            HotOrNotPayoutMode::Multiplier,
+            &post_creation_time,
+        );
+
+        let claims = post.get_pot_insurance_claims_for_slot(
+            &get_mock_user_alice_canister_id(),
+            &1,
+            500,
+            &post_creation_time,
+        );
+        assert_eq!(claims.len(), 1);
+        assert_eq!(claims[0].tabulated_at, Some(post_creation_time));
+        assert_eq!(claims[0].room_pot_total_amount, 250);
+        // * the 180 and 90 payouts out of a pot of 250, doubled for a pot of 500
+        let mut top_up_amounts: Vec<u64> = claims[0]
+            .top_ups
+            .iter()
+            .map(|top_up| top_up.amount)
+            .collect();
+        top_up_amounts.sort();
+        assert_eq!(top_up_amounts, vec![90, 180]);
+
+        assert!(post
+            .get_pot_insurance_claims_for_slot(
+                &get_mock_user_alice_canister_id(),
+                &1,
+                250,
+                &post_creation_time
+            )
+            .is_empty());
+    }
+
+    #[test]
+    fn test_tabulate_hot_or_not_outcome_for_slot_within_budget() {
+        let post_creation_time = SystemTime::now();
+        let mut post = Post::new(
+            0,
+            &PostDetailsFromFrontend {
+                description: "Doggos and puppers".into(),
+                hashtags: vec!["doggo".into(), "pupper".into()],
+                video_uid: "abcd#1234".into(),
+                creator_consent_for_inclusion_in_hot_or_not: true,
+                collaborator_shares: None,
+                title: None,
+                category: None,
+            },
+            &post_creation_time,
+        );
+        let mut token_balance = TokenBalance::default();
+
+        let data_set: Vec<(u64, BetDirection, u64, u128)> = vec![
+            (1, BetDirection::Hot, 100, 180),
+            (2, BetDirection::Hot, 50, 90),
+            (3, BetDirection::Not, 100, 0),
         ];
 
         data_set
@@ -1504,54 +4301,506 @@ mod test {
         let score_tabulation_time = post_creation_time
             .checked_add(Duration::from_secs(60 * 5))
             .unwrap();
+        let mut bet_makers: Vec<BetMaker> = data_set
+            .iter()
+            .map(|(user_id, _, _, _)| Principal::self_authenticating(user_id.to_ne_bytes()))
+            .collect();
+        bet_makers.sort();
 
-        post.tabulate_hot_or_not_outcome_for_slot(
+        // * budget runs out after the room's outcome and its first bet are settled
+        let budget_checks_left = std::cell::Cell::new(2_u32);
+        let settlements = post.tabulate_hot_or_not_outcome_for_slot_within_budget(
             &get_mock_user_alice_canister_id(),
             &1,
             &mut token_balance,
+            0,
+            0,
+            HotOrNotPayoutMode::Multiplier,
             &score_tabulation_time,
+            &|| {
+                let checks_left = budget_checks_left.get();
+                budget_checks_left.set(checks_left.saturating_sub(1));
+                checks_left > 0
+            },
         );
 
-        assert_eq!(token_balance.utility_token_transaction_history.len(), 1);
-        assert_eq!(token_balance.utility_token_balance, 390);
+        assert!(settlements.has_more_to_tabulate);
+        assert_eq!(token_balance.utility_token_balance, 25);
 
-        let room_detail = post
+        let slot_details = post
             .hot_or_not_details
             .as_ref()
             .unwrap()
             .slot_history
             .get(&1)
+            .unwrap();
+        assert_eq!(
+            slot_details.tabulation_cursor,
+            Some(TabulationCursor {
+                room_id: 1,
+                last_settled_bet_maker: Some(bet_makers[0]),
+            })
+        );
+        let room_detail = slot_details.room_details.get(&1).unwrap();
+        assert_eq!(room_detail.bet_outcome, RoomBetPossibleOutcomes::HotWon);
+        assert!(!matches!(
+            room_detail.bets_made.get(&bet_makers[0]).unwrap().payout,
+            BetPayout::NotCalculatedYet
+        ));
+        assert!(bet_makers[1..].iter().all(|bet_maker| matches!(
+            room_detail.bets_made.get(bet_maker).unwrap().payout,
+            BetPayout::NotCalculatedYet
+        )));
+
+        // * resuming pays out the rest without paying the creator commission again
+        let settlements = post.tabulate_hot_or_not_outcome_for_slot(
+            &get_mock_user_alice_canister_id(),
+            &1,
+            &mut token_balance,
+            0,
+            0,
+            HotOrNotPayoutMode::Multiplier,
+            &score_tabulation_time,
+        );
+
+        assert_eq!(settlements, SlotTabulationSettlements::default());
+        assert_eq!(token_balance.utility_token_balance, 25);
+
+        let slot_details = post
+            .hot_or_not_details
+            .as_ref()
             .unwrap()
-            .room_details
+            .slot_history
             .get(&1)
             .unwrap();
+        assert_eq!(slot_details.tabulation_cursor, None);
 
-        assert_eq!(room_detail.bet_outcome, RoomBetPossibleOutcomes::Draw);
-        assert_eq!(room_detail.room_bets_total_pot, 3900);
-        assert_eq!(room_detail.total_hot_bets, 40);
-        assert_eq!(room_detail.total_not_bets, 40);
+        data_set.iter().for_each(|(user_id, _, _, amount_won)| {
+            let bet_detail = slot_details
+                .room_details
+                .get(&1)
+                .unwrap()
+                .bets_made
+                .get(&Principal::self_authenticating(user_id.to_ne_bytes()))
+                .unwrap();
+
+            assert!(matches!(
+                bet_detail.payout,
+                BetPayout::Calculated(amount) if amount == *amount_won
+            ));
+        });
+    }
 
-        data_set
-            .iter()
-            .for_each(|(user_id, bet_direction, bet_amount, amount_won)| {
-                let bet_detail = room_detail
-                    .bets_made
-                    .get(&Principal::self_authenticating(user_id.to_ne_bytes()))
-                    .unwrap();
+    #[test]
+    fn test_tabulate_hot_or_not_outcome_for_slot_with_collaborators() {
+        let post_creation_time = SystemTime::now();
+        let mut post = Post::new(
+            0,
+            &PostDetailsFromFrontend {
+                description: "Doggos and puppers".into(),
+                hashtags: vec!["doggo".into(), "pupper".into()],
+                video_uid: "abcd#1234".into(),
+                creator_consent_for_inclusion_in_hot_or_not: true,
+                collaborator_shares: None,
+                title: None,
+                category: None,
+            },
+            &post_creation_time,
+        );
+        post.collaborator_shares = vec![
+            CollaboratorShare {
+                collaborator_principal_id: get_mock_user_bob_principal_id(),
+                percentage: 30,
+            },
+            CollaboratorShare {
+                collaborator_principal_id: get_mock_user_charlie_principal_id(),
+                percentage: 25,
+            },
+        ];
+        let mut token_balance = TokenBalance::default();
 
-                assert_eq!(bet_detail.bet_direction, *bet_direction);
-                assert_eq!(bet_detail.amount, *bet_amount);
-                assert_eq!(
-                    match bet_detail.payout {
-                        BetPayout::Calculated(n) => {
-                            n
-                        }
-                        _ => {
-                            0
-                        }
-                    },
-                    *amount_won
-                );
-            });
+        [
+            (1_u64, BetDirection::Hot, 100),
+            (2, BetDirection::Hot, 50),
+            (3, BetDirection::Not, 100),
+        ]
+        .iter()
+        .for_each(|(user_id, bet_direction, bet_amount)| {
+            assert!(post
+                .place_hot_or_not_bet(
+                    &Principal::self_authenticating(user_id.to_ne_bytes()),
+                    &Principal::self_authenticating(user_id.to_ne_bytes()),
+                    *bet_amount,
+                    bet_direction,
+                    &post_creation_time,
+                )
+                .is_ok());
+        });
+
+        let settlements = post.tabulate_hot_or_not_outcome_for_slot(
+            &get_mock_user_alice_canister_id(),
+            &1,
+            &mut token_balance,
+            0,
+            0,
+            HotOrNotPayoutMode::Multiplier,
+            &post_creation_time
+                .checked_add(Duration::from_secs(60 * 5))
+                .unwrap(),
+        );
+
+        // * 25 in commission, 7 and 6 of which go to the collaborators
+        assert_eq!(
+            settlements.collaborator_commission_settlements,
+            vec![
+                CollaboratorCommissionSettlement {
+                    collaborator_principal_id: get_mock_user_bob_principal_id(),
+                    post_canister_id: get_mock_user_alice_canister_id(),
+                    post_id: 0,
+                    slot_id: 1,
+                    room_id: 1,
+                    room_pot_total_amount: 250,
+                    share_amount: 7,
+                    number_of_failed_attempts: 0,
+                },
+                CollaboratorCommissionSettlement {
+                    collaborator_principal_id: get_mock_user_charlie_principal_id(),
+                    post_canister_id: get_mock_user_alice_canister_id(),
+                    post_id: 0,
+                    slot_id: 1,
+                    room_id: 1,
+                    room_pot_total_amount: 250,
+                    share_amount: 6,
+                    number_of_failed_attempts: 0,
+                },
+            ]
+        );
+        assert!(settlements.house_rake_settlements.is_empty());
+        assert_eq!(token_balance.utility_token_balance, 12);
+        assert_eq!(token_balance.lifetime_earnings, 12);
+    }
+
+    #[test]
+    fn test_place_hot_or_not_bet_rolls_rooms_over_from_the_counter() {
+        let post_creation_time = SystemTime::now();
+        let mut post = Post::new(
+            0,
+            &PostDetailsFromFrontend {
+                description: "Doggos and puppers".into(),
+                hashtags: vec!["doggo".into(), "pupper".into()],
+                video_uid: "abcd#1234".into(),
+                creator_consent_for_inclusion_in_hot_or_not: true,
+                collaborator_shares: None,
+                title: None,
+                category: None,
+            },
+            &post_creation_time,
+        );
+        let room_sizing_config = RoomSizingConfig {
+            default_room_size: 2,
+            ..Default::default()
+        };
+
+        // * status read before any of the bets land, as it would be across an await
+        let stale_betting_status = post.get_hot_or_not_betting_status_for_this_post(
+            &post_creation_time,
+            &Principal::self_authenticating(0_u64.to_ne_bytes()),
+        );
+        assert!(matches!(
+            stale_betting_status,
+            BettingStatus::BettingOpen {
+                ongoing_room: 1,
+                ..
+            }
+        ));
+
+        let rooms_assigned: Vec<RoomId> = (1_u64..=5)
+            .map(|user_id| {
+                match post
+                    .place_hot_or_not_bet_with_room_sizing(
+                        &Principal::self_authenticating(user_id.to_ne_bytes()),
+                        &Principal::self_authenticating(user_id.to_ne_bytes()),
+                        10,
+                        &BetDirection::Hot,
+                        &post_creation_time,
+                        &room_sizing_config,
+                    )
+                    .unwrap()
+                {
+                    BettingStatus::BettingOpen { ongoing_room, .. } => ongoing_room,
+                    _ => panic!("betting should be open"),
+                }
+            })
+            .collect();
+        assert_eq!(rooms_assigned, vec![1, 1, 2, 2, 3]);
+
+        let slot_details = post
+            .hot_or_not_details
+            .as_mut()
+            .unwrap()
+            .slot_history
+            .get_mut(&1)
+            .unwrap();
+        assert_eq!(slot_details.next_room_id, Some(4));
+        assert_eq!(slot_details.get_number_of_bets(), 5);
+        assert_eq!(slot_details.room_details.get(&2).unwrap().total_hot_bets, 2);
+
+        // * slots from before the counter was kept carry on from their last room
+        slot_details.next_room_id = None;
+        assert_eq!(slot_details.get_ongoing_room_id(), 3);
+        assert_eq!(slot_details.assign_room_for_new_bet(2), 3);
+        assert_eq!(slot_details.next_room_id, Some(4));
+    }
+
+    #[test]
+    fn test_place_hot_or_not_bet_in_phantom_room() {
+        let post_creation_time = SystemTime::now();
+        let mut post = Post::new(
+            0,
+            &PostDetailsFromFrontend {
+                description: "Doggos and puppers".into(),
+                hashtags: vec!["doggo".into(), "pupper".into()],
+                video_uid: "abcd#1234".into(),
+                creator_consent_for_inclusion_in_hot_or_not: true,
+                collaborator_shares: None,
+                title: None,
+                category: None,
+            },
+            &post_creation_time,
+        );
+        let mut token_balance = TokenBalance::default();
+        let shadow_banned_principal_id = Principal::self_authenticating(3_u64.to_ne_bytes());
+        let other_shadow_banned_principal_id = Principal::self_authenticating(4_u64.to_ne_bytes());
+
+        post.place_hot_or_not_bet(
+            &Principal::self_authenticating(1_u64.to_ne_bytes()),
+            &Principal::self_authenticating(1_u64.to_ne_bytes()),
+            100,
+            &BetDirection::Hot,
+            &post_creation_time,
+        )
+        .unwrap();
+
+        // * Shadow banned bets neither grow the pot nor count towards the outcome
+        post.place_hot_or_not_bet_in_phantom_room(
+            &other_shadow_banned_principal_id,
+            &other_shadow_banned_principal_id,
+            10,
+            &BetDirection::Not,
+            &post_creation_time,
+        )
+        .unwrap();
+        let result = post.place_hot_or_not_bet_in_phantom_room(
+            &shadow_banned_principal_id,
+            &shadow_banned_principal_id,
+            1_000,
+            &BetDirection::Not,
+            &post_creation_time,
+        );
+        assert_eq!(
+            result,
+            Ok(BettingStatus::BettingOpen {
+                started_at: post_creation_time,
+                number_of_participants: 2,
+                ongoing_slot: 1,
+                ongoing_room: 1,
+                has_this_user_participated_in_this_post: Some(true),
+                minimum_bet_amount: 0,
+            })
+        );
+        assert_eq!(
+            post.place_hot_or_not_bet_in_phantom_room(
+                &shadow_banned_principal_id,
+                &shadow_banned_principal_id,
+                1_000,
+                &BetDirection::Not,
+                &post_creation_time,
+            ),
+            Err(BetOnCurrentlyViewingPostError::UserAlreadyParticipatedInThisPost)
+        );
+        assert!(post.has_this_principal_already_bet_on_this_post(&shadow_banned_principal_id));
+
+        let hot_or_not_details = post.hot_or_not_details.as_ref().unwrap();
+        assert_eq!(hot_or_not_details.aggregate_stats.total_amount_bet, 100);
+        assert_eq!(
+            hot_or_not_details.slot_history[&1].room_details[&1].room_bets_total_pot,
+            100
+        );
+        assert!(matches!(
+            post.get_user_status_for_this_post(&shadow_banned_principal_id),
+            UserStatusForSpecificHotOrNotPost::AwaitingResult(_)
+        ));
+
+        post.tabulate_hot_or_not_outcome_for_slot(
+            &get_mock_user_alice_canister_id(),
+            &1,
+            &mut token_balance,
+            0,
+            0,
+            HotOrNotPayoutMode::Multiplier,
+            &post_creation_time,
+        );
+
+        let slot_details = &post.hot_or_not_details.as_ref().unwrap().slot_history[&1];
+        assert_eq!(
+            slot_details.room_details[&1].bet_outcome,
+            RoomBetPossibleOutcomes::HotWon
+        );
+        assert!(matches!(
+            slot_details.phantom_room_bets[&shadow_banned_principal_id].payout,
+            BetPayout::Calculated(0)
+        ));
+        // * Creator commission is only taken from the real pot
+        assert_eq!(token_balance.utility_token_balance, 10);
+        assert_eq!(
+            post.get_user_status_for_this_post(&shadow_banned_principal_id),
+            UserStatusForSpecificHotOrNotPost::ResultAnnounced {
+                bet_detail: BetDetail {
+                    amount: 1_000,
+                    bet_direction: BetDirection::Not,
+                    slot_id: 1,
+                    room_id: 1,
+                },
+                result: BetResult::Lost,
+                payout_u128: None,
+            }
+        );
+    }
+
+    #[test]
+    fn test_pseudonymize_bet_makers() {
+        let post_creation_time = SystemTime::now();
+        let mut post = Post::new(
+            0,
+            &PostDetailsFromFrontend {
+                description: "Doggos and puppers".into(),
+                hashtags: vec!["doggo".into(), "pupper".into()],
+                video_uid: "abcd#1234".into(),
+                creator_consent_for_inclusion_in_hot_or_not: true,
+                collaborator_shares: None,
+                title: None,
+                category: None,
+            },
+            &post_creation_time,
+        );
+        let alice = get_mock_user_alice_principal_id();
+        let bob = get_mock_user_bob_principal_id();
+        let charlie = get_mock_user_charlie_principal_id();
+        let salt = [7; 32];
+
+        post.place_hot_or_not_bet(
+            &alice,
+            &get_mock_user_alice_canister_id(),
+            100,
+            &BetDirection::Hot,
+            &post_creation_time,
+        )
+        .unwrap();
+        post.place_hot_or_not_bet(
+            &bob,
+            &get_mock_user_alice_canister_id(),
+            50,
+            &BetDirection::Not,
+            &post_creation_time,
+        )
+        .unwrap();
+        post.place_hot_or_not_bet_in_phantom_room(
+            &charlie,
+            &get_mock_user_alice_canister_id(),
+            10,
+            &BetDirection::Not,
+            &post_creation_time,
+        )
+        .unwrap();
+
+        let mut hot_or_not_details = post.hot_or_not_details.clone().unwrap();
+        hot_or_not_details.pseudonymize_bet_makers(|bet_maker| *bet_maker != bob, &salt);
+
+        let slot_details = &hot_or_not_details.slot_history[&1];
+        let bets_made = &slot_details.room_details[&1].bets_made;
+        assert!(!bets_made.contains_key(&alice));
+        assert_eq!(
+            bets_made[&pseudonymize_principal(&alice, &salt)].amount,
+            100
+        );
+        assert_eq!(bets_made[&bob].amount, 50);
+        assert!(!slot_details.phantom_room_bets.contains_key(&charlie));
+        assert!(slot_details
+            .phantom_room_bets
+            .contains_key(&pseudonymize_principal(&charlie, &salt)));
+
+        // * the post itself is left as is for settlement
+        assert!(
+            post.hot_or_not_details.unwrap().slot_history[&1].room_details[&1]
+                .bets_made
+                .contains_key(&alice)
+        );
+    }
+
+    #[test]
+    fn test_get_bet_payout_for_bets_too_large_for_u64_math() {
+        let bet_details = BetDetails {
+            amount: u64::MAX,
+            bet_direction: BetDirection::Hot,
+            payout: BetPayout::NotCalculatedYet,
+            bet_maker_canister_id: get_mock_user_alice_canister_id(),
+            referrer_canister_id: None,
+        };
+
+        // * u64::MAX * 2 * 80 would have overflowed well before the division
+        assert_eq!(
+            get_bet_payout(&RoomBetPossibleOutcomes::HotWon, &bet_details, 80, (2, 1)),
+            BetPayout::Calculated(u64::MAX as u128 * 160 / 100)
+        );
+        assert_eq!(
+            get_bet_payout(&RoomBetPossibleOutcomes::Draw, &bet_details, 80, (2, 1)),
+            BetPayout::Calculated(u64::MAX as u128 * 80 / 100)
+        );
+        assert_eq!(
+            get_bet_payout(&RoomBetPossibleOutcomes::NotWon, &bet_details, 80, (2, 1)),
+            BetPayout::Calculated(0)
+        );
+
+        assert_eq!(to_token_amount(u64::MAX as u128 * 160 / 100), u64::MAX);
+        assert_eq!(to_token_amount(180), 180);
+    }
+
+    #[test]
+    fn test_room_pot_holds_more_than_u64() {
+        let post_creation_time = SystemTime::now();
+        let mut post = Post::new(
+            0,
+            &PostDetailsFromFrontend {
+                description: "Doggos and puppers".into(),
+                hashtags: vec!["doggo".into(), "pupper".into()],
+                video_uid: "abcd#1234".into(),
+                creator_consent_for_inclusion_in_hot_or_not: true,
+                collaborator_shares: None,
+                title: None,
+                category: None,
+            },
+            &post_creation_time,
+        );
+
+        (0..3_u64).for_each(|bet_maker| {
+            post.place_hot_or_not_bet(
+                &Principal::self_authenticating(bet_maker.to_ne_bytes()),
+                &get_mock_user_alice_canister_id(),
+                u64::MAX / 2,
+                &BetDirection::Hot,
+                &post_creation_time,
+            )
+            .unwrap();
+        });
+
+        let hot_or_not_details = post.hot_or_not_details.as_ref().unwrap();
+        assert_eq!(
+            hot_or_not_details.slot_history[&1].room_details[&1].room_bets_total_pot,
+            3 * (u64::MAX / 2) as u128
+        );
+        assert_eq!(
+            hot_or_not_details.aggregate_stats.total_amount_bet,
+            3 * (u64::MAX / 2) as u128
+        );
     }
 }
@@ -0,0 +1,181 @@
+use std::collections::{BTreeMap, VecDeque};
+
+use candid::{CandidType, Deserialize, Principal};
+use serde::Serialize;
+
+use crate::common::types::app_primitive_type::PostId;
+
+use super::BetOutcomeForBetMaker;
+
+// * Most calls informing bet makers of outcomes that a canister has awaiting a response at once
+pub const MAX_BET_OUTCOME_NOTIFICATIONS_IN_FLIGHT: u64 = 50;
+
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug, PartialEq, Eq)]
+pub struct BetOutcomeNotification {
+    pub bet_maker_canister_id: Principal,
+    pub post_id: PostId,
+    pub bet_outcome: BetOutcomeForBetMaker,
+}
+
+/// Outcomes yet to be sent to bet makers, queued per post. Batches take turns across posts,
+/// so a post with many bets settling doesn't hold up the bet makers of every other post.
+#[derive(Default, Serialize, Deserialize, Debug)]
+pub struct BetOutcomeNotificationScheduler {
+    pending_notifications_by_post: BTreeMap<PostId, VecDeque<BetOutcomeNotification>>,
+    // * The post whose turn is next
+    next_post_id: PostId,
+    // * No calls are left awaiting a response across upgrades
+    #[serde(skip)]
+    notifications_in_flight: u64,
+}
+
+impl BetOutcomeNotificationScheduler {
+    pub fn enqueue(&mut self, notification: BetOutcomeNotification) {
+        self.pending_notifications_by_post
+            .entry(notification.post_id)
+            .or_default()
+            .push_back(notification);
+    }
+
+    pub fn len(&self) -> usize {
+        self.pending_notifications_by_post
+            .values()
+            .map(VecDeque::len)
+            .sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pending_notifications_by_post.is_empty()
+    }
+
+    pub fn get_notifications_in_flight(&self) -> u64 {
+        self.notifications_in_flight
+    }
+
+    /// The outcome of a post still waiting to be sent to a bet maker, if any
+    pub fn get_pending_notification(
+        &self,
+        post_id: PostId,
+        bet_maker_canister_id: &Principal,
+    ) -> Option<&BetOutcomeNotification> {
+        self.pending_notifications_by_post
+            .get(&post_id)?
+            .iter()
+            .find(|notification| notification.bet_maker_canister_id == *bet_maker_canister_id)
+    }
+
+    /// Hands out as many notifications as fit in what's left of `max_notifications_in_flight`,
+    /// one post at a time in ascending order of post ids, carrying on from the post whose turn
+    /// is next and wrapping around. Handed out notifications count as in flight until
+    /// [`Self::mark_notification_as_delivered`] is called for them.
+    pub fn take_next_batch(
+        &mut self,
+        max_notifications_in_flight: u64,
+    ) -> Vec<BetOutcomeNotification> {
+        let mut batch = vec![];
+
+        while self.notifications_in_flight < max_notifications_in_flight {
+            let Some(post_id) = self
+                .pending_notifications_by_post
+                .range(self.next_post_id..)
+                .next()
+                .or_else(|| self.pending_notifications_by_post.iter().next())
+                .map(|(post_id, _)| *post_id)
+            else {
+                break;
+            };
+
+            let pending_notifications = self
+                .pending_notifications_by_post
+                .get_mut(&post_id)
+                .unwrap();
+            batch.push(pending_notifications.pop_front().unwrap());
+            if pending_notifications.is_empty() {
+                self.pending_notifications_by_post.remove(&post_id);
+            }
+
+            self.next_post_id = post_id.saturating_add(1);
+            self.notifications_in_flight += 1;
+        }
+
+        batch
+    }
+
+    pub fn mark_notification_as_delivered(&mut self) {
+        self.notifications_in_flight = self.notifications_in_flight.saturating_sub(1);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use test_utils::setup::test_constants::v2::canister;
+
+    use super::*;
+
+    fn get_notification(post_id: PostId, label: &str) -> BetOutcomeNotification {
+        BetOutcomeNotification {
+            bet_maker_canister_id: canister(label),
+            post_id,
+            bet_outcome: BetOutcomeForBetMaker::Lost,
+        }
+    }
+
+    fn get_post_ids(batch: &[BetOutcomeNotification]) -> Vec<PostId> {
+        batch
+            .iter()
+            .map(|notification| notification.post_id)
+            .collect()
+    }
+
+    #[test]
+    fn test_batches_take_turns_across_posts() {
+        let mut scheduler = BetOutcomeNotificationScheduler::default();
+        (0..5).for_each(|bet_index| {
+            scheduler.enqueue(get_notification(0, &format!("post_0_bettor_{}", bet_index)));
+        });
+        scheduler.enqueue(get_notification(1, "post_1_bettor"));
+        scheduler.enqueue(get_notification(2, "post_2_bettor"));
+
+        assert_eq!(scheduler.len(), 7);
+        assert!(scheduler
+            .get_pending_notification(1, &canister("post_1_bettor"))
+            .is_some());
+        assert!(scheduler
+            .get_pending_notification(1, &canister("post_2_bettor"))
+            .is_none());
+        assert_eq!(
+            get_post_ids(&scheduler.take_next_batch(4)),
+            vec![0, 1, 2, 0]
+        );
+        // * nothing more is handed out until some of the notifications are delivered
+        assert!(scheduler.take_next_batch(4).is_empty());
+
+        scheduler.enqueue(get_notification(3, "post_3_bettor"));
+        scheduler.mark_notification_as_delivered();
+        scheduler.mark_notification_as_delivered();
+
+        assert_eq!(get_post_ids(&scheduler.take_next_batch(4)), vec![3, 0]);
+        assert_eq!(scheduler.get_notifications_in_flight(), 4);
+
+        (0..4).for_each(|_| scheduler.mark_notification_as_delivered());
+
+        assert_eq!(get_post_ids(&scheduler.take_next_batch(4)), vec![0, 0]);
+        assert!(scheduler.is_empty());
+    }
+
+    #[test]
+    fn test_notifications_of_a_post_are_handed_out_in_order() {
+        let mut scheduler = BetOutcomeNotificationScheduler::default();
+        scheduler.enqueue(get_notification(7, "first_bettor"));
+        scheduler.enqueue(get_notification(7, "second_bettor"));
+
+        assert_eq!(
+            scheduler.take_next_batch(MAX_BET_OUTCOME_NOTIFICATIONS_IN_FLIGHT),
+            vec![
+                get_notification(7, "first_bettor"),
+                get_notification(7, "second_bettor")
+            ]
+        );
+        assert_eq!(scheduler.get_notifications_in_flight(), 2);
+    }
+}
@@ -0,0 +1,292 @@
+use std::{
+    collections::BTreeMap,
+    time::{Duration, SystemTime},
+};
+
+use candid::{CandidType, Deserialize, Encode};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+
+use crate::canister_specific::individual_user_template::types::{
+    dispute::SLOT_OUTCOME_DISPUTE_WINDOW, post::Post,
+};
+
+use super::{
+    BetDetails, BetMaker, BetPayout, RoomId, SlotId, TOTAL_DURATION_OF_ALL_SLOTS_IN_SECONDS,
+};
+
+/// Bets moved out of the rooms of a post when it was compacted, by slot and room
+pub type ArchivedBets = BTreeMap<SlotId, BTreeMap<RoomId, BTreeMap<BetMaker, BetDetails>>>;
+
+/// What a settled room keeps of its bets once they have been archived
+#[derive(CandidType, Clone, Deserialize, Debug, Serialize, PartialEq, Eq)]
+pub struct CompactedRoomBets {
+    pub number_of_bets: u64,
+    pub total_amount_bet: u128,
+    pub total_payout: u128,
+    // * Sha256 of the archived bets of the room, candid encoded
+    pub bets_made_hash: Vec<u8>,
+}
+
+impl CompactedRoomBets {
+    fn new(bets_made: &BTreeMap<BetMaker, BetDetails>) -> Self {
+        Self {
+            number_of_bets: bets_made.len() as u64,
+            total_amount_bet: bets_made
+                .values()
+                .map(|bet_details| bet_details.amount as u128)
+                .sum(),
+            total_payout: bets_made
+                .values()
+                .map(|bet_details| match bet_details.payout {
+                    BetPayout::Calculated(payout) => payout,
+                    BetPayout::NotCalculatedYet => 0,
+                })
+                .sum(),
+            bets_made_hash: get_bets_made_hash(bets_made),
+        }
+    }
+}
+
+fn get_bets_made_hash(bets_made: &BTreeMap<BetMaker, BetDetails>) -> Vec<u8> {
+    Sha256::digest(Encode!(bets_made).unwrap()).to_vec()
+}
+
+impl Post {
+    /// Betting is over, every slot has been settled and the window to dispute the last of them
+    /// has closed, so nothing on the way to an outcome reads the individual bets anymore
+    pub fn is_ready_for_slot_compaction(&self, current_time: &SystemTime) -> bool {
+        let Some(hot_or_not_details) = self.hot_or_not_details.as_ref() else {
+            return false;
+        };
+
+        let compactable_from = self.get_betting_start_time()
+            + Duration::from_secs(TOTAL_DURATION_OF_ALL_SLOTS_IN_SECONDS)
+            + SLOT_OUTCOME_DISPUTE_WINDOW;
+        if *current_time < compactable_from {
+            return false;
+        }
+
+        let mut slots = hot_or_not_details.slot_history.values();
+
+        slots
+            .clone()
+            .all(|slot_details| slot_details.room_details.is_empty() || slot_details.is_tabulated())
+            && slots.any(|slot_details| {
+                slot_details
+                    .room_details
+                    .values()
+                    .any(|room_details| !room_details.bets_made.is_empty())
+            })
+    }
+
+    /// Moves the bets out of every room of the post, leaving a summary and a hash of them in
+    /// their place. Returns the bets moved out, for them to be archived.
+    pub fn compact_settled_slots(&mut self) -> ArchivedBets {
+        let Some(hot_or_not_details) = self.hot_or_not_details.as_mut() else {
+            return ArchivedBets::default();
+        };
+
+        hot_or_not_details
+            .slot_history
+            .iter_mut()
+            .filter_map(|(slot_id, slot_details)| {
+                let archived_rooms: BTreeMap<RoomId, BTreeMap<BetMaker, BetDetails>> = slot_details
+                    .room_details
+                    .iter_mut()
+                    .filter(|(_, room_details)| !room_details.bets_made.is_empty())
+                    .map(|(room_id, room_details)| {
+                        let bets_made = std::mem::take(&mut room_details.bets_made);
+                        room_details.compacted_bets = Some(CompactedRoomBets::new(&bets_made));
+
+                        (*room_id, bets_made)
+                    })
+                    .collect();
+
+                (!archived_rooms.is_empty()).then_some((*slot_id, archived_rooms))
+            })
+            .collect()
+    }
+
+    /// Puts archived bets back in the rooms they were compacted out of. Bets that don't match
+    /// the hash kept for their room are left out rather than trusted.
+    pub fn restore_archived_bets(&mut self, mut archived_bets: ArchivedBets) {
+        let Some(hot_or_not_details) = self.hot_or_not_details.as_mut() else {
+            return;
+        };
+
+        hot_or_not_details
+            .slot_history
+            .iter_mut()
+            .for_each(|(slot_id, slot_details)| {
+                let Some(mut archived_rooms) = archived_bets.remove(slot_id) else {
+                    return;
+                };
+
+                slot_details
+                    .room_details
+                    .iter_mut()
+                    .for_each(|(room_id, room_details)| {
+                        let (Some(compacted_bets), Some(bets_made)) = (
+                            room_details.compacted_bets.as_ref(),
+                            archived_rooms.remove(room_id),
+                        ) else {
+                            return;
+                        };
+
+                        if compacted_bets.bets_made_hash == get_bets_made_hash(&bets_made) {
+                            room_details.bets_made = bets_made;
+                            room_details.compacted_bets = None;
+                        }
+                    });
+            });
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use test_utils::setup::test_constants::{
+        get_mock_user_alice_canister_id, get_mock_user_bob_canister_id,
+        get_mock_user_bob_principal_id, get_mock_user_charlie_canister_id,
+        get_mock_user_charlie_principal_id,
+    };
+
+    use crate::canister_specific::individual_user_template::types::{
+        hot_or_not::{BetDirection, HotOrNotPayoutMode},
+        post::PostDetailsFromFrontend,
+        token::TokenBalance,
+    };
+
+    use super::*;
+
+    fn get_post_with_bets(created_at: &SystemTime) -> Post {
+        let mut post = Post::new(
+            0,
+            &PostDetailsFromFrontend {
+                description: "Doggos and puppers".into(),
+                hashtags: vec!["doggo".into(), "pupper".into()],
+                video_uid: "abcd#1234".into(),
+                creator_consent_for_inclusion_in_hot_or_not: true,
+                collaborator_shares: None,
+                title: None,
+                category: None,
+            },
+            created_at,
+        );
+        post.place_hot_or_not_bet(
+            &get_mock_user_bob_principal_id(),
+            &get_mock_user_bob_canister_id(),
+            100,
+            &BetDirection::Hot,
+            created_at,
+        )
+        .unwrap();
+        post.place_hot_or_not_bet(
+            &get_mock_user_charlie_principal_id(),
+            &get_mock_user_charlie_canister_id(),
+            50,
+            &BetDirection::Not,
+            created_at,
+        )
+        .unwrap();
+
+        post
+    }
+
+    #[test]
+    fn test_is_ready_for_slot_compaction() {
+        let created_at = SystemTime::UNIX_EPOCH;
+        let mut post = get_post_with_bets(&created_at);
+        let compactable_from = created_at
+            + Duration::from_secs(TOTAL_DURATION_OF_ALL_SLOTS_IN_SECONDS)
+            + SLOT_OUTCOME_DISPUTE_WINDOW;
+
+        // * the slot hasn't been settled yet
+        assert!(!post.is_ready_for_slot_compaction(&compactable_from));
+
+        post.tabulate_hot_or_not_outcome_for_slot(
+            &get_mock_user_alice_canister_id(),
+            &1,
+            &mut TokenBalance::default(),
+            0,
+            0,
+            HotOrNotPayoutMode::Multiplier,
+            &post.get_slot_settled_at(1),
+        );
+
+        // * disputes can still be raised on the last slot
+        assert!(!post.is_ready_for_slot_compaction(&(compactable_from - Duration::from_secs(1))));
+        assert!(post.is_ready_for_slot_compaction(&compactable_from));
+
+        // * nothing left to compact
+        post.compact_settled_slots();
+        assert!(!post.is_ready_for_slot_compaction(&compactable_from));
+    }
+
+    #[test]
+    fn test_compact_and_restore_settled_slots() {
+        let created_at = SystemTime::UNIX_EPOCH;
+        let mut post = get_post_with_bets(&created_at);
+        post.tabulate_hot_or_not_outcome_for_slot(
+            &get_mock_user_alice_canister_id(),
+            &1,
+            &mut TokenBalance::default(),
+            0,
+            0,
+            HotOrNotPayoutMode::Multiplier,
+            &post.get_slot_settled_at(1),
+        );
+        let bet_history = post.hot_or_not_details.as_ref().unwrap().get_bet_history();
+        let aggregate_stats = post
+            .hot_or_not_details
+            .as_ref()
+            .unwrap()
+            .aggregate_stats
+            .clone();
+
+        let archived_bets = post.compact_settled_slots();
+
+        let hot_or_not_details = post.hot_or_not_details.as_ref().unwrap();
+        let room_details = &hot_or_not_details.slot_history[&1].room_details[&1];
+        assert!(room_details.bets_made.is_empty());
+        let compacted_bets = room_details.compacted_bets.as_ref().unwrap();
+        assert_eq!(compacted_bets.number_of_bets, 2);
+        assert_eq!(compacted_bets.total_amount_bet, 150);
+        assert_eq!(
+            compacted_bets.bets_made_hash,
+            get_bets_made_hash(&archived_bets[&1][&1])
+        );
+        assert!(hot_or_not_details.get_bet_history().is_empty());
+        // * aggregates stay as they were
+        assert_eq!(hot_or_not_details.aggregate_stats, aggregate_stats);
+        assert_eq!(archived_bets[&1][&1].len(), 2);
+
+        // * bets that don't match the hash aren't put back
+        let mut tampered_bets = archived_bets.clone();
+        tampered_bets
+            .get_mut(&1)
+            .unwrap()
+            .get_mut(&1)
+            .unwrap()
+            .values_mut()
+            .for_each(|bet_details| bet_details.amount += 1);
+        let mut tampered_post = post.clone();
+        tampered_post.restore_archived_bets(tampered_bets);
+        assert!(tampered_post
+            .hot_or_not_details
+            .unwrap()
+            .get_bet_history()
+            .is_empty());
+
+        post.restore_archived_bets(archived_bets);
+        let hot_or_not_details = post.hot_or_not_details.as_ref().unwrap();
+        assert_eq!(
+            hot_or_not_details.slot_history[&1].room_details[&1].compacted_bets,
+            None
+        );
+        assert_eq!(
+            hot_or_not_details.get_bet_history().len(),
+            bet_history.len()
+        );
+    }
+}
@@ -0,0 +1,321 @@
+use std::{
+    collections::VecDeque,
+    time::{Duration, SystemTime},
+};
+
+use candid::{CandidType, Deserialize, Principal};
+use serde::Serialize;
+
+use crate::common::types::app_primitive_type::PostId;
+
+use super::hot_or_not::{BetDirection, RoomBetPossibleOutcomes, RoomId, SlotId};
+
+// * Once this many messages are waiting for the gateway, the oldest ones are dropped. A live
+// * stream is only worth as much as it is current, so old pot updates are the first to go.
+pub const MAX_PENDING_LIVE_BETTING_ACTIVITY_MESSAGES: usize = 1_000;
+pub const MAX_LIVE_BETTING_ACTIVITY_MESSAGES_PER_PUBLISH: usize = 100;
+// * Method on the web socket gateway taking a batch of messages and returning how many of them,
+// * from the front, it accepted
+pub const LIVE_BETTING_ACTIVITY_PUBLISH_METHOD_NAME: &str = "publish_live_betting_activity";
+const INITIAL_PUBLISH_BACKOFF: Duration = Duration::from_secs(1);
+const MAX_PUBLISH_BACKOFF: Duration = Duration::from_secs(60);
+
+/// Topic clients subscribe to on the gateway for live updates of one post
+pub fn get_post_topic_key(post_canister_id: &Principal, post_id: PostId) -> String {
+    format!("hot_or_not/{}/{}", post_canister_id, post_id)
+}
+
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug, PartialEq, Eq)]
+pub enum LiveBettingActivityEvent {
+    BetPlaced {
+        slot_id: SlotId,
+        room_id: RoomId,
+        bet_direction: BetDirection,
+        bet_amount: u64,
+        number_of_participants_in_room: u8,
+        // * Total bet on the post so far
+        pot_size: u64,
+    },
+    SlotSettled {
+        slot_id: SlotId,
+        room_outcomes: Vec<(RoomId, RoomBetPossibleOutcomes)>,
+    },
+}
+
+/// What the gateway is sent for every event
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug, PartialEq, Eq)]
+pub struct LiveBettingActivityMessage {
+    pub topic_key: String,
+    // * Increases by one with every message of this canister, so subscribers can tell when
+    // * messages were dropped
+    pub sequence_number: u64,
+    pub event: LiveBettingActivityEvent,
+    pub occurred_at: SystemTime,
+}
+
+/// Betting activity of this canister's posts waiting to be published to the web socket
+/// gateway.
+///
+/// Only one batch is in flight at a time. The gateway may accept only part of a batch when
+/// it's falling behind, in which case the rest is kept and publishing backs off, the same
+/// as when the call fails. Backoff doubles with every consecutive push back or failure.
+#[derive(Default, Deserialize, Serialize)]
+pub struct LiveBettingActivityStream {
+    pending_messages: VecDeque<LiveBettingActivityMessage>,
+    next_sequence_number: u64,
+    number_of_dropped_messages: u64,
+    number_of_consecutive_failures: u32,
+    backoff_until: Option<SystemTime>,
+    #[serde(skip)]
+    is_publish_in_flight: bool,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct LiveBettingActivityStreamStatus {
+    pub number_of_pending_messages: u64,
+    pub number_of_dropped_messages: u64,
+    pub number_of_consecutive_failures: u32,
+    pub backoff_until: Option<SystemTime>,
+}
+
+impl LiveBettingActivityStream {
+    pub fn push(
+        &mut self,
+        topic_key: String,
+        event: LiveBettingActivityEvent,
+        occurred_at: SystemTime,
+    ) {
+        if self.pending_messages.len() >= MAX_PENDING_LIVE_BETTING_ACTIVITY_MESSAGES {
+            self.pending_messages.pop_front();
+            self.number_of_dropped_messages += 1;
+        }
+
+        self.pending_messages.push_back(LiveBettingActivityMessage {
+            topic_key,
+            sequence_number: self.next_sequence_number,
+            event,
+            occurred_at,
+        });
+        self.next_sequence_number += 1;
+    }
+
+    /// False while a batch is in flight, publishing is backing off or there is nothing to
+    /// publish
+    pub fn is_ready_to_publish(&self, current_time: &SystemTime) -> bool {
+        !self.is_publish_in_flight
+            && !self.pending_messages.is_empty()
+            && !self
+                .backoff_until
+                .is_some_and(|backoff_until| *current_time < backoff_until)
+    }
+
+    /// Marks a batch as in flight and returns it, if the stream is ready to publish
+    pub fn take_batch_to_publish(
+        &mut self,
+        current_time: &SystemTime,
+    ) -> Option<Vec<LiveBettingActivityMessage>> {
+        if !self.is_ready_to_publish(current_time) {
+            return None;
+        }
+
+        self.is_publish_in_flight = true;
+
+        Some(
+            self.pending_messages
+                .iter()
+                .take(MAX_LIVE_BETTING_ACTIVITY_MESSAGES_PER_PUBLISH)
+                .cloned()
+                .collect(),
+        )
+    }
+
+    /// Drops the messages the gateway accepted out of `batch`. Returns how long to wait
+    /// before publishing again if it pushed back, or `None` to carry on right away.
+    pub fn record_publish(
+        &mut self,
+        batch: &[LiveBettingActivityMessage],
+        number_of_messages_accepted: usize,
+        current_time: &SystemTime,
+    ) -> Option<Duration> {
+        self.is_publish_in_flight = false;
+
+        // * Matched by sequence number, since messages may have been dropped from the front
+        // * while the batch was in flight
+        if let Some(last_accepted_message) = batch
+            .get(..number_of_messages_accepted.min(batch.len()))
+            .and_then(|accepted_messages| accepted_messages.last())
+        {
+            while self.pending_messages.front().is_some_and(|message| {
+                message.sequence_number <= last_accepted_message.sequence_number
+            }) {
+                self.pending_messages.pop_front();
+            }
+        }
+
+        if number_of_messages_accepted < batch.len() {
+            return Some(self.back_off(current_time));
+        }
+
+        self.number_of_consecutive_failures = 0;
+        self.backoff_until = None;
+
+        None
+    }
+
+    /// Returns how long to wait before publishing again
+    pub fn record_failure(&mut self, current_time: &SystemTime) -> Duration {
+        self.is_publish_in_flight = false;
+
+        self.back_off(current_time)
+    }
+
+    pub fn get_status(&self) -> LiveBettingActivityStreamStatus {
+        LiveBettingActivityStreamStatus {
+            number_of_pending_messages: self.pending_messages.len() as u64,
+            number_of_dropped_messages: self.number_of_dropped_messages,
+            number_of_consecutive_failures: self.number_of_consecutive_failures,
+            backoff_until: self.backoff_until,
+        }
+    }
+
+    fn back_off(&mut self, current_time: &SystemTime) -> Duration {
+        let backoff = INITIAL_PUBLISH_BACKOFF
+            .saturating_mul(2_u32.saturating_pow(self.number_of_consecutive_failures))
+            .min(MAX_PUBLISH_BACKOFF);
+        self.number_of_consecutive_failures = self.number_of_consecutive_failures.saturating_add(1);
+        self.backoff_until = Some(*current_time + backoff);
+
+        backoff
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn get_bet_placed_event(bet_amount: u64) -> LiveBettingActivityEvent {
+        LiveBettingActivityEvent::BetPlaced {
+            slot_id: 1,
+            room_id: 1,
+            bet_direction: BetDirection::Hot,
+            bet_amount,
+            number_of_participants_in_room: 1,
+            pot_size: bet_amount,
+        }
+    }
+
+    #[test]
+    fn test_push_drops_oldest_messages_once_full() {
+        let mut stream = LiveBettingActivityStream::default();
+        let current_time = SystemTime::UNIX_EPOCH;
+
+        (0..MAX_PENDING_LIVE_BETTING_ACTIVITY_MESSAGES as u64 + 2).for_each(|bet_amount| {
+            stream.push(
+                "topic".into(),
+                get_bet_placed_event(bet_amount),
+                current_time,
+            );
+        });
+
+        let status = stream.get_status();
+        assert_eq!(
+            status.number_of_pending_messages,
+            MAX_PENDING_LIVE_BETTING_ACTIVITY_MESSAGES as u64
+        );
+        assert_eq!(status.number_of_dropped_messages, 2);
+
+        let batch = stream.take_batch_to_publish(&current_time).unwrap();
+        assert_eq!(batch.len(), MAX_LIVE_BETTING_ACTIVITY_MESSAGES_PER_PUBLISH);
+        assert_eq!(batch[0].sequence_number, 2);
+    }
+
+    #[test]
+    fn test_only_one_batch_is_in_flight() {
+        let mut stream = LiveBettingActivityStream::default();
+        let current_time = SystemTime::UNIX_EPOCH;
+
+        assert_eq!(stream.take_batch_to_publish(&current_time), None);
+
+        stream.push("topic".into(), get_bet_placed_event(10), current_time);
+        let batch = stream.take_batch_to_publish(&current_time).unwrap();
+        assert_eq!(stream.take_batch_to_publish(&current_time), None);
+
+        assert_eq!(
+            stream.record_publish(&batch, batch.len(), &current_time),
+            None
+        );
+        assert_eq!(stream.get_status().number_of_pending_messages, 0);
+        assert_eq!(stream.take_batch_to_publish(&current_time), None);
+    }
+
+    #[test]
+    fn test_record_publish_keeps_what_the_gateway_pushed_back() {
+        let mut stream = LiveBettingActivityStream::default();
+        let current_time = SystemTime::UNIX_EPOCH;
+        (0..3).for_each(|bet_amount| {
+            stream.push(
+                "topic".into(),
+                get_bet_placed_event(bet_amount),
+                current_time,
+            );
+        });
+
+        let batch = stream.take_batch_to_publish(&current_time).unwrap();
+        // * pushed while the batch was in flight
+        stream.push("topic".into(), get_bet_placed_event(3), current_time);
+
+        assert_eq!(
+            stream.record_publish(&batch, 2, &current_time),
+            Some(INITIAL_PUBLISH_BACKOFF)
+        );
+        assert_eq!(stream.get_status().number_of_pending_messages, 2);
+        assert!(!stream.is_ready_to_publish(&current_time));
+        assert_eq!(stream.take_batch_to_publish(&current_time), None);
+
+        let current_time = current_time + INITIAL_PUBLISH_BACKOFF;
+        let batch = stream.take_batch_to_publish(&current_time).unwrap();
+        assert_eq!(
+            batch
+                .iter()
+                .map(|message| message.sequence_number)
+                .collect::<Vec<_>>(),
+            vec![2, 3]
+        );
+
+        assert_eq!(stream.record_publish(&batch, 2, &current_time), None);
+        assert_eq!(stream.get_status().number_of_consecutive_failures, 0);
+        assert_eq!(stream.get_status().backoff_until, None);
+    }
+
+    #[test]
+    fn test_record_failure_backs_off_exponentially() {
+        let mut stream = LiveBettingActivityStream::default();
+        let current_time = SystemTime::UNIX_EPOCH;
+        stream.push("topic".into(), get_bet_placed_event(10), current_time);
+
+        let backoffs = (0..8)
+            .map(|_| stream.record_failure(&current_time))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            backoffs,
+            [1, 2, 4, 8, 16, 32, 60, 60]
+                .into_iter()
+                .map(Duration::from_secs)
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(stream.get_status().number_of_pending_messages, 1);
+        assert_eq!(
+            stream.get_status().backoff_until,
+            Some(current_time + MAX_PUBLISH_BACKOFF)
+        );
+    }
+
+    #[test]
+    fn test_get_post_topic_key() {
+        assert_eq!(
+            get_post_topic_key(&Principal::anonymous(), 7),
+            "hot_or_not/2vxsx-fae/7"
+        );
+    }
+}
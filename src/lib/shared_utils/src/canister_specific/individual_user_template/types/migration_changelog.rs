@@ -0,0 +1,159 @@
+use std::{borrow::Cow, time::SystemTime};
+
+use candid::{CandidType, Decode, Deserialize, Encode};
+use ic_stable_structures::{Memory, StableLog, Storable};
+use serde::Serialize;
+
+use crate::pagination::{self, Page};
+
+/// A data migration run while upgrading, e.g. moving a heap structure into stable memory
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug, PartialEq, Eq)]
+pub struct AppliedMigration {
+    pub migration_id: String,
+    // * Version of the wasm that ran the migration
+    pub version_number: u64,
+    pub applied_at: SystemTime,
+    // * Time stands still within a single message, so a migration is measured in instructions
+    pub instructions_used: u64,
+    pub entities_migrated: u64,
+    pub errors: Vec<String>,
+    // * For migrations run a chunk per message, the key of the last entity the chunk got to.
+    // * None once the last chunk has run, and for migrations run in one go.
+    #[serde(default)]
+    pub progress_marker: Option<u64>,
+}
+
+impl Storable for AppliedMigration {
+    fn to_bytes(&self) -> Cow<'_, [u8]> {
+        Cow::Owned(Encode!(self).unwrap())
+    }
+
+    fn from_bytes(bytes: Cow<[u8]>) -> Self {
+        Decode!(&bytes, Self).unwrap()
+    }
+}
+
+/// Append only log of the data migrations this canister has been through, oldest first,
+/// living in a pair of stable memory regions, one for the index and one for the entries
+pub struct MigrationChangelog<M: Memory + Clone> {
+    entries: StableLog<AppliedMigration, M, M>,
+}
+
+impl<M: Memory + Clone> MigrationChangelog<M> {
+    /// Loads the changelog persisted in the memories. A changelog that can't be read back is
+    /// started over rather than failing the upgrade that is loading it.
+    pub fn init(index_memory: M, data_memory: M) -> Self {
+        let entries = StableLog::init(index_memory.clone(), data_memory.clone())
+            .unwrap_or_else(|_| StableLog::new(index_memory, data_memory));
+
+        Self { entries }
+    }
+
+    pub fn len(&self) -> u64 {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.len() == 0
+    }
+
+    /// Returns false if stable memory could not grow to hold the entry
+    pub fn record(&mut self, applied_migration: &AppliedMigration) -> bool {
+        self.entries.append(applied_migration).is_ok()
+    }
+
+    /// Ids of the migrations run by the given version that hit errors
+    pub fn get_migrations_with_errors(&self, version_number: u64) -> Vec<String> {
+        (0..self.entries.len())
+            .filter_map(|index| self.entries.get(index))
+            .filter(|applied_migration| {
+                applied_migration.version_number == version_number
+                    && !applied_migration.errors.is_empty()
+            })
+            .map(|applied_migration| applied_migration.migration_id)
+            .collect()
+    }
+
+    pub fn get_page(&self, from_inclusive_index: u64, page_size: u64) -> Page<AppliedMigration> {
+        // * Paged over indices first so that only the entries on the page are read
+        let index_page = pagination::get_page(
+            0..self.entries.len() as usize,
+            from_inclusive_index,
+            page_size,
+        );
+
+        Page {
+            items: index_page
+                .items
+                .into_iter()
+                .filter_map(|index| self.entries.get(index as u64))
+                .collect(),
+            total_count: index_page.total_count,
+            next_from_inclusive_index: index_page.next_from_inclusive_index,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ic_stable_structures::VectorMemory;
+
+    use super::*;
+
+    fn get_applied_migration(migration_id: &str, entities_migrated: u64) -> AppliedMigration {
+        AppliedMigration {
+            migration_id: migration_id.to_string(),
+            version_number: 1,
+            applied_at: SystemTime::UNIX_EPOCH,
+            instructions_used: 1_000,
+            entities_migrated,
+            errors: vec![],
+            progress_marker: None,
+        }
+    }
+
+    #[test]
+    fn test_migration_changelog_survives_reload() {
+        let index_memory = VectorMemory::default();
+        let data_memory = VectorMemory::default();
+
+        let mut migration_changelog =
+            MigrationChangelog::init(index_memory.clone(), data_memory.clone());
+        assert!(migration_changelog.is_empty());
+        assert!(migration_changelog.record(&get_applied_migration("move_posts", 12)));
+        assert!(migration_changelog.record(&get_applied_migration("move_settlements", 3)));
+
+        let migration_changelog = MigrationChangelog::init(index_memory, data_memory);
+        assert_eq!(migration_changelog.len(), 2);
+
+        let page = migration_changelog.get_page(1, 10);
+        assert_eq!(page.total_count, 2);
+        assert_eq!(
+            page.items,
+            vec![get_applied_migration("move_settlements", 3)]
+        );
+        assert_eq!(page.next_from_inclusive_index, None);
+    }
+
+    #[test]
+    fn test_get_migrations_with_errors() {
+        let mut migration_changelog =
+            MigrationChangelog::init(VectorMemory::default(), VectorMemory::default());
+        migration_changelog.record(&get_applied_migration("move_posts", 12));
+        migration_changelog.record(&AppliedMigration {
+            errors: vec!["post 3 could not be decoded".into()],
+            ..get_applied_migration("move_settlements", 3)
+        });
+        migration_changelog.record(&AppliedMigration {
+            version_number: 2,
+            errors: vec!["bet 7 could not be decoded".into()],
+            ..get_applied_migration("backfill_betting_stats", 0)
+        });
+
+        assert_eq!(
+            migration_changelog.get_migrations_with_errors(1),
+            vec!["move_settlements".to_string()]
+        );
+        assert!(migration_changelog.get_migrations_with_errors(3).is_empty());
+    }
+}
@@ -1,8 +1,33 @@
+pub mod access_statistics;
+pub mod achievement;
 pub mod arg;
+pub mod call_journal;
+pub mod collaborator;
+pub mod comment;
 pub mod configuration;
+pub mod cycle_monitor;
+pub mod deferred_job;
+pub mod dispute;
 pub mod error;
+pub mod event_callback;
+pub mod feature_usage;
 pub mod follow;
 pub mod hot_or_not;
+pub mod live_betting_activity;
+pub mod migration_changelog;
+pub mod moderation;
+pub mod onboarding;
 pub mod post;
+pub mod post_upgrade_health;
 pub mod profile;
+pub mod received_settlements;
+pub mod referral;
+pub mod replication;
+pub mod session_delegate;
+pub mod signup_bonus;
 pub mod token;
+pub mod token_ledger;
+pub mod token_transaction_history;
+pub mod tournament;
+pub mod treasury;
+pub mod versus;
@@ -0,0 +1,360 @@
+use std::{
+    collections::{BTreeMap, BTreeSet, VecDeque},
+    time::SystemTime,
+};
+
+use candid::{CandidType, Deserialize, Principal};
+use serde::Serialize;
+
+use crate::{
+    common::types::app_primitive_type::PostId,
+    pagination::{self, Page},
+};
+
+use super::{comment::CommentId, error::ModerationError};
+
+pub const MAX_MODERATORS_PER_PROFILE: usize = 20;
+pub const MAX_PRINCIPALS_IN_POST_ALLOWLIST: usize = 1_000;
+// * Once the audit log holds this many entries, the oldest ones are dropped
+pub const MAX_MODERATION_AUDIT_LOG_ENTRIES: usize = 1_000;
+
+#[derive(
+    CandidType, Deserialize, Serialize, Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord,
+)]
+pub enum ModeratorPermission {
+    HideComments,
+    PauseBetting,
+    ManagePostAllowlist,
+}
+
+/// A principal the profile owner trusts to moderate their posts, within the permissions
+/// they were given
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug, PartialEq, Eq)]
+pub struct Moderator {
+    pub permissions: BTreeSet<ModeratorPermission>,
+    pub appointed_at: SystemTime,
+}
+
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug, PartialEq, Eq)]
+pub enum ModerationAction {
+    ModeratorAppointed {
+        moderator_principal_id: Principal,
+        permissions: Vec<ModeratorPermission>,
+    },
+    ModeratorRevoked {
+        moderator_principal_id: Principal,
+    },
+    CommentHidden {
+        post_id: PostId,
+        comment_id: CommentId,
+    },
+    CommentRestored {
+        post_id: PostId,
+        comment_id: CommentId,
+    },
+    BettingPaused {
+        post_id: PostId,
+    },
+    BettingResumed {
+        post_id: PostId,
+    },
+    PostAllowlistUpdated {
+        post_id: PostId,
+        principals_added: Vec<Principal>,
+        principals_removed: Vec<Principal>,
+    },
+}
+
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug, PartialEq, Eq)]
+pub struct ModerationAuditLogEntry {
+    pub action: ModerationAction,
+    pub performed_by: Principal,
+    pub performed_at: SystemTime,
+}
+
+/// Moderators appointed by the profile owner, the per post allowlists they look after and
+/// the audit log of everything done by the owner or the moderators
+#[derive(Default, Deserialize, Serialize)]
+pub struct DelegatedModeration {
+    moderators: BTreeMap<Principal, Moderator>,
+    // * Principals allowed to comment on a post. Posts without an allowlist are open to all
+    post_allowlists: BTreeMap<PostId, BTreeSet<Principal>>,
+    // * Oldest first
+    audit_log: VecDeque<ModerationAuditLogEntry>,
+}
+
+impl DelegatedModeration {
+    /// Appointing an existing moderator again replaces their permissions
+    pub fn appoint_moderator(
+        &mut self,
+        moderator_principal_id: Principal,
+        permissions: &[ModeratorPermission],
+        current_time: &SystemTime,
+    ) -> Result<(), ModerationError> {
+        if permissions.is_empty() {
+            return Err(ModerationError::NoPermissionsGiven);
+        }
+
+        if !self.moderators.contains_key(&moderator_principal_id)
+            && self.moderators.len() >= MAX_MODERATORS_PER_PROFILE
+        {
+            return Err(ModerationError::MaxModeratorsReached);
+        }
+
+        self.moderators.insert(
+            moderator_principal_id,
+            Moderator {
+                permissions: permissions.iter().copied().collect(),
+                appointed_at: *current_time,
+            },
+        );
+
+        Ok(())
+    }
+
+    pub fn revoke_moderator(
+        &mut self,
+        moderator_principal_id: &Principal,
+    ) -> Result<(), ModerationError> {
+        self.moderators
+            .remove(moderator_principal_id)
+            .map(|_| ())
+            .ok_or(ModerationError::ModeratorNotFound)
+    }
+
+    pub fn is_moderator(&self, principal_id: &Principal) -> bool {
+        self.moderators.contains_key(principal_id)
+    }
+
+    pub fn has_permission(
+        &self,
+        principal_id: &Principal,
+        permission: ModeratorPermission,
+    ) -> bool {
+        self.moderators
+            .get(principal_id)
+            .is_some_and(|moderator| moderator.permissions.contains(&permission))
+    }
+
+    pub fn get_moderators(&self) -> Vec<(Principal, Moderator)> {
+        self.moderators
+            .iter()
+            .map(|(moderator_principal_id, moderator)| (*moderator_principal_id, moderator.clone()))
+            .collect()
+    }
+
+    /// Removing the last principal from an allowlist opens the post to everyone again
+    pub fn update_post_allowlist(
+        &mut self,
+        post_id: PostId,
+        principals_to_add: &[Principal],
+        principals_to_remove: &[Principal],
+    ) -> Result<(), ModerationError> {
+        let mut post_allowlist = self
+            .post_allowlists
+            .get(&post_id)
+            .cloned()
+            .unwrap_or_default();
+        post_allowlist.extend(principals_to_add.iter().copied());
+        principals_to_remove.iter().for_each(|principal_id| {
+            post_allowlist.remove(principal_id);
+        });
+
+        if post_allowlist.len() > MAX_PRINCIPALS_IN_POST_ALLOWLIST {
+            return Err(ModerationError::PostAllowlistFull);
+        }
+
+        if post_allowlist.is_empty() {
+            self.post_allowlists.remove(&post_id);
+        } else {
+            self.post_allowlists.insert(post_id, post_allowlist);
+        }
+
+        Ok(())
+    }
+
+    pub fn get_post_allowlist(&self, post_id: PostId) -> Vec<Principal> {
+        self.post_allowlists
+            .get(&post_id)
+            .map(|post_allowlist| post_allowlist.iter().copied().collect())
+            .unwrap_or_default()
+    }
+
+    pub fn is_allowed_to_comment(&self, post_id: PostId, principal_id: &Principal) -> bool {
+        self.post_allowlists
+            .get(&post_id)
+            .is_none_or(|post_allowlist| post_allowlist.contains(principal_id))
+    }
+
+    pub fn record_action(
+        &mut self,
+        action: ModerationAction,
+        performed_by: Principal,
+        performed_at: SystemTime,
+    ) {
+        if self.audit_log.len() >= MAX_MODERATION_AUDIT_LOG_ENTRIES {
+            self.audit_log.pop_front();
+        }
+
+        self.audit_log.push_back(ModerationAuditLogEntry {
+            action,
+            performed_by,
+            performed_at,
+        });
+    }
+
+    /// Newest first
+    pub fn get_audit_log_page(
+        &self,
+        from_inclusive_index: u64,
+        page_size: u64,
+    ) -> Page<ModerationAuditLogEntry> {
+        pagination::get_page(
+            self.audit_log.iter().rev().cloned(),
+            from_inclusive_index,
+            page_size,
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_appoint_and_revoke_moderator() {
+        let mut delegated_moderation = DelegatedModeration::default();
+        let moderator = Principal::from_slice(&[1]);
+        let current_time = SystemTime::UNIX_EPOCH;
+
+        assert_eq!(
+            delegated_moderation.appoint_moderator(moderator, &[], &current_time),
+            Err(ModerationError::NoPermissionsGiven)
+        );
+
+        delegated_moderation
+            .appoint_moderator(
+                moderator,
+                &[ModeratorPermission::HideComments],
+                &current_time,
+            )
+            .unwrap();
+        assert!(delegated_moderation.has_permission(&moderator, ModeratorPermission::HideComments));
+        assert!(!delegated_moderation.has_permission(&moderator, ModeratorPermission::PauseBetting));
+
+        // * appointing again replaces the permissions
+        delegated_moderation
+            .appoint_moderator(
+                moderator,
+                &[ModeratorPermission::PauseBetting],
+                &current_time,
+            )
+            .unwrap();
+        assert!(!delegated_moderation.has_permission(&moderator, ModeratorPermission::HideComments));
+        assert!(delegated_moderation.has_permission(&moderator, ModeratorPermission::PauseBetting));
+        assert_eq!(delegated_moderation.get_moderators().len(), 1);
+
+        delegated_moderation.revoke_moderator(&moderator).unwrap();
+        assert!(!delegated_moderation.is_moderator(&moderator));
+        assert_eq!(
+            delegated_moderation.revoke_moderator(&moderator),
+            Err(ModerationError::ModeratorNotFound)
+        );
+    }
+
+    #[test]
+    fn test_appoint_moderator_is_capped() {
+        let mut delegated_moderation = DelegatedModeration::default();
+        let current_time = SystemTime::UNIX_EPOCH;
+
+        (0..MAX_MODERATORS_PER_PROFILE as u8).for_each(|id| {
+            delegated_moderation
+                .appoint_moderator(
+                    Principal::from_slice(&[id]),
+                    &[ModeratorPermission::HideComments],
+                    &current_time,
+                )
+                .unwrap();
+        });
+
+        assert_eq!(
+            delegated_moderation.appoint_moderator(
+                Principal::from_slice(&[u8::MAX]),
+                &[ModeratorPermission::HideComments],
+                &current_time,
+            ),
+            Err(ModerationError::MaxModeratorsReached)
+        );
+        // * existing moderators can still have their permissions changed
+        assert!(delegated_moderation
+            .appoint_moderator(
+                Principal::from_slice(&[0]),
+                &[ModeratorPermission::PauseBetting],
+                &current_time,
+            )
+            .is_ok());
+    }
+
+    #[test]
+    fn test_post_allowlist() {
+        let mut delegated_moderation = DelegatedModeration::default();
+        let alice = Principal::from_slice(&[1]);
+        let bob = Principal::from_slice(&[2]);
+
+        assert!(delegated_moderation.is_allowed_to_comment(0, &bob));
+
+        delegated_moderation
+            .update_post_allowlist(0, &[alice], &[])
+            .unwrap();
+        assert!(delegated_moderation.is_allowed_to_comment(0, &alice));
+        assert!(!delegated_moderation.is_allowed_to_comment(0, &bob));
+        // * other posts stay open
+        assert!(delegated_moderation.is_allowed_to_comment(1, &bob));
+
+        delegated_moderation
+            .update_post_allowlist(0, &[], &[alice])
+            .unwrap();
+        assert!(delegated_moderation.get_post_allowlist(0).is_empty());
+        assert!(delegated_moderation.is_allowed_to_comment(0, &bob));
+
+        let too_many_principals = (0..=MAX_PRINCIPALS_IN_POST_ALLOWLIST as u64)
+            .map(|id| Principal::from_slice(&id.to_ne_bytes()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            delegated_moderation.update_post_allowlist(0, &too_many_principals, &[]),
+            Err(ModerationError::PostAllowlistFull)
+        );
+        assert!(delegated_moderation.get_post_allowlist(0).is_empty());
+    }
+
+    #[test]
+    fn test_audit_log_is_capped_and_paged_newest_first() {
+        let mut delegated_moderation = DelegatedModeration::default();
+        let owner = Principal::from_slice(&[1]);
+
+        (0..MAX_MODERATION_AUDIT_LOG_ENTRIES as u64 + 2).for_each(|post_id| {
+            delegated_moderation.record_action(
+                ModerationAction::BettingPaused { post_id },
+                owner,
+                SystemTime::UNIX_EPOCH,
+            );
+        });
+
+        let page = delegated_moderation.get_audit_log_page(0, 2);
+        assert_eq!(page.total_count, MAX_MODERATION_AUDIT_LOG_ENTRIES as u64);
+        assert_eq!(
+            page.items[0].action,
+            ModerationAction::BettingPaused {
+                post_id: MAX_MODERATION_AUDIT_LOG_ENTRIES as u64 + 1
+            }
+        );
+        assert_eq!(page.next_from_inclusive_index, Some(2));
+        assert_eq!(
+            delegated_moderation
+                .get_audit_log_page(MAX_MODERATION_AUDIT_LOG_ENTRIES as u64 - 1, 2)
+                .items[0]
+                .action,
+            ModerationAction::BettingPaused { post_id: 2 }
+        );
+    }
+}
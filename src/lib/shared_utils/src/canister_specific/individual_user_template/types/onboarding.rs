@@ -0,0 +1,17 @@
+use std::time::SystemTime;
+
+use candid::{CandidType, Deserialize};
+use serde::Serialize;
+
+/// Enough for the user index to tell whether the profile owner ever got past onboarding
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug, PartialEq, Eq, Default)]
+pub struct OnboardingStatus {
+    pub post_count: u64,
+    pub bet_count: u64,
+    // * Time of the latest token transaction. Every canister is minted a sign up reward, so
+    // * this is only None on canisters that predate it
+    pub last_active_at: Option<SystemTime>,
+    // * Set while the canister is flagged for collection, unless the owner becomes active or
+    // * opts out before then
+    pub scheduled_collection_at: Option<SystemTime>,
+}
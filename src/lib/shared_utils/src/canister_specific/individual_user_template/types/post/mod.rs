@@ -1,13 +1,28 @@
-use candid::{CandidType, Deserialize, Principal};
+use candid::{CandidType, Decode, Deserialize, Encode, Principal};
+use ic_stable_structures::Storable;
 use serde::Serialize;
+use sha2::{Digest, Sha256};
 use std::{
-    collections::HashSet,
+    borrow::Cow,
+    collections::{BTreeMap, HashSet},
     time::{Duration, SystemTime},
 };
 
-use crate::canister_specific::individual_user_template::types::profile::UserProfileDetailsForFrontend;
+use crate::{
+    canister_specific::individual_user_template::types::profile::UserProfileDetailsForFrontend,
+    common::utils::hashtag::normalize_hashtags,
+};
+
+use super::{
+    collaborator::CollaboratorShare,
+    error::{BoostPostError, ReportPostError},
+    hot_or_not::{BettingStatus, HotOrNotDetails},
+};
 
-use super::hot_or_not::{BettingStatus, HotOrNotDetails};
+pub mod projection;
+pub mod report;
+pub mod stable_post_map;
+pub mod view_buffer;
 
 #[derive(CandidType, Clone, Deserialize, Debug, Serialize)]
 pub struct Post {
@@ -23,6 +38,148 @@ pub struct Post {
     pub home_feed_score: FeedScore,
     pub creator_consent_for_inclusion_in_hot_or_not: bool,
     pub hot_or_not_details: Option<HotOrNotDetails>,
+    // * Smallest bet accepted on this post. Zero means any amount is accepted
+    #[serde(default)]
+    pub minimum_bet_amount: u64,
+    // * Everyone other than the creator that the creator commission is split with. Whatever
+    // * isn't shared stays with the creator
+    #[serde(default)]
+    pub collaborator_shares: Vec<CollaboratorShare>,
+    #[serde(default)]
+    pub title: Option<String>,
+    #[serde(default)]
+    pub category: Option<PostCategory>,
+    #[serde(default)]
+    pub boost: Option<PostBoost>,
+    // * Reporter and when they reported the post, since the post was last reviewed
+    #[serde(default)]
+    pub unresolved_reports: BTreeMap<Principal, SystemTime>,
+    // * Set for posts scheduled to go live after they were created. Betting, and slot
+    // * numbering, start from it instead of from created_at
+    #[serde(default)]
+    pub publish_at: Option<SystemTime>,
+    // * Set by the creator, or one of their moderators, independently of reports
+    #[serde(default)]
+    pub is_betting_paused_by_moderator: bool,
+}
+
+impl Storable for Post {
+    fn to_bytes(&self) -> Cow<'_, [u8]> {
+        Cow::Owned(Encode!(self).unwrap())
+    }
+
+    fn from_bytes(bytes: Cow<[u8]>) -> Self {
+        Decode!(&bytes, Self).unwrap()
+    }
+}
+
+#[derive(
+    CandidType, Clone, Copy, Deserialize, Debug, Serialize, PartialEq, Eq, PartialOrd, Ord,
+)]
+pub enum PostCategory {
+    Comedy,
+    Dance,
+    Education,
+    Fashion,
+    Food,
+    Gaming,
+    Music,
+    News,
+    Sports,
+    Technology,
+    Travel,
+    Other,
+}
+
+pub const ALL_POST_CATEGORIES: [PostCategory; 12] = [
+    PostCategory::Comedy,
+    PostCategory::Dance,
+    PostCategory::Education,
+    PostCategory::Fashion,
+    PostCategory::Food,
+    PostCategory::Gaming,
+    PostCategory::Music,
+    PostCategory::News,
+    PostCategory::Sports,
+    PostCategory::Technology,
+    PostCategory::Travel,
+    PostCategory::Other,
+];
+
+pub const MAXIMUM_POST_TITLE_LENGTH: usize = 100;
+// * Betting is paused once a post has more unresolved reports than this
+pub const MAXIMUM_UNRESOLVED_REPORTS_BEFORE_BETTING_IS_PAUSED: usize = 5;
+// * How far past its creation a post can be scheduled to go live
+pub const MAXIMUM_PUBLISH_DELAY_IN_SECONDS: u64 = 7 * 24 * 60 * 60;
+
+/// Categories creators can currently file new posts under. Managed from the user index so
+/// that categories can be retired without shipping a new wasm. Posts filed under a category
+/// that is later retired keep it.
+#[derive(CandidType, Clone, Deserialize, Serialize, Debug, PartialEq, Eq)]
+pub struct PostCategoryConfig {
+    pub allowed_categories: Vec<PostCategory>,
+}
+
+impl Default for PostCategoryConfig {
+    fn default() -> Self {
+        Self {
+            allowed_categories: ALL_POST_CATEGORIES.to_vec(),
+        }
+    }
+}
+
+impl PostCategoryConfig {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.allowed_categories.is_empty() {
+            return Err("At least one category needs to be allowed".to_string());
+        }
+
+        Ok(())
+    }
+}
+
+pub const POST_BOOST_DURATION: Duration = Duration::from_secs(24 * 60 * 60);
+pub const POST_BOOST_TOKENS_PER_PERCENTAGE_POINT: u64 = 10;
+pub const MINIMUM_POST_BOOST_AMOUNT: u64 = 100;
+// * Scores at most double while a post is boosted
+pub const MAXIMUM_POST_BOOST_PERCENTAGE: u64 = 100;
+
+/// Feed score bump a creator bought for one of their posts with utility tokens. The tokens
+/// are burnt, and the post's feed scores are raised by `boost_percentage` until
+/// `boosted_until`.
+#[derive(CandidType, Clone, Copy, Deserialize, Debug, Serialize, PartialEq, Eq)]
+pub struct PostBoost {
+    pub amount: u64,
+    pub boost_percentage: u64,
+    pub boosted_at: SystemTime,
+    pub boosted_until: SystemTime,
+}
+
+impl PostBoost {
+    pub fn new(amount: u64, current_time: &SystemTime) -> Result<Self, BoostPostError> {
+        if amount < MINIMUM_POST_BOOST_AMOUNT {
+            return Err(BoostPostError::BoostAmountBelowMinimum);
+        }
+
+        if amount > MAXIMUM_POST_BOOST_PERCENTAGE * POST_BOOST_TOKENS_PER_PERCENTAGE_POINT {
+            return Err(BoostPostError::BoostAmountAboveMaximum);
+        }
+
+        Ok(Self {
+            amount,
+            boost_percentage: amount / POST_BOOST_TOKENS_PER_PERCENTAGE_POINT,
+            boosted_at: *current_time,
+            boosted_until: *current_time + POST_BOOST_DURATION,
+        })
+    }
+
+    pub fn is_active(&self, current_time: &SystemTime) -> bool {
+        *current_time < self.boosted_until
+    }
+
+    fn apply_to_score(&self, score: u64) -> u64 {
+        score + (score * self.boost_percentage) / 100
+    }
 }
 
 #[derive(CandidType, Clone, Deserialize, Debug, Serialize)]
@@ -42,7 +199,7 @@ impl Default for FeedScore {
     }
 }
 
-#[derive(Deserialize, CandidType)]
+#[derive(Deserialize, CandidType, Clone, Debug, Serialize, PartialEq, Eq)]
 pub enum PostViewDetailsFromFrontend {
     WatchedPartially {
         percentage_watched: u8,
@@ -54,6 +211,29 @@ pub enum PostViewDetailsFromFrontend {
     },
 }
 
+impl PostViewDetailsFromFrontend {
+    pub fn is_valid(&self) -> bool {
+        let percentage_watched = match self {
+            PostViewDetailsFromFrontend::WatchedPartially { percentage_watched }
+            | PostViewDetailsFromFrontend::WatchedMultipleTimes {
+                percentage_watched, ..
+            } => *percentage_watched,
+        };
+
+        percentage_watched <= 100 && percentage_watched > 0
+    }
+
+    /// Views this adds to the post's total view count
+    pub fn get_view_count(&self) -> u64 {
+        match self {
+            PostViewDetailsFromFrontend::WatchedPartially { .. } => 1,
+            PostViewDetailsFromFrontend::WatchedMultipleTimes { watch_count, .. } => {
+                *watch_count as u64 + 1
+            }
+        }
+    }
+}
+
 #[derive(CandidType, Clone, Deserialize, Debug, Serialize, Default)]
 pub struct PostViewStatistics {
     pub total_view_count: u64,
@@ -73,7 +253,7 @@ pub enum PostStatus {
     Deleted,
 }
 
-#[derive(Serialize, CandidType, Deserialize, Debug)]
+#[derive(Serialize, CandidType, Clone, Deserialize, Debug)]
 pub struct PostDetailsForFrontend {
     pub id: u64,
     pub created_by_display_name: Option<String>,
@@ -91,6 +271,36 @@ pub struct PostDetailsForFrontend {
     pub home_feed_ranking_score: u64,
     pub hot_or_not_feed_ranking_score: Option<u64>,
     pub hot_or_not_betting_status: Option<BettingStatus>,
+    pub title: Option<String>,
+    pub category: Option<PostCategory>,
+    pub active_boost: Option<PostBoost>,
+}
+
+/// Post details as they were when the post was last certified, along with the proof that
+/// boundary nodes and agents check them against
+#[derive(CandidType, Deserialize, Debug)]
+pub struct CertifiedPostDetailsForFrontend {
+    /// Bumped every time the post is recertified
+    pub version: u64,
+    /// Snapshot taken for an anonymous caller, so `liked_by_me` is always false and
+    /// `has_this_user_participated_in_this_post` is never set
+    pub post_details: PostDetailsForFrontend,
+    /// CBOR encoded certificate over the canister's certified data
+    pub certificate: Vec<u8>,
+    /// CBOR encoded hash tree revealing `["post_details", big endian post id]`, whose leaf
+    /// is [`get_post_details_certification_hash`] of `version` and `post_details`
+    pub witness: Vec<u8>,
+}
+
+pub fn get_post_details_certification_hash(
+    version: u64,
+    post_details: &PostDetailsForFrontend,
+) -> [u8; 32] {
+    Sha256::digest(
+        candid::encode_args((version, post_details))
+            .expect("Post details are always candid encodable"),
+    )
+    .into()
 }
 
 #[derive(Serialize, CandidType, Deserialize)]
@@ -99,6 +309,57 @@ pub struct PostDetailsFromFrontend {
     pub hashtags: Vec<String>,
     pub video_uid: String,
     pub creator_consent_for_inclusion_in_hot_or_not: bool,
+    pub collaborator_shares: Option<Vec<CollaboratorShare>>,
+    pub title: Option<String>,
+    pub category: Option<PostCategory>,
+}
+
+/// Scheduled posts have to go live some time after they are created, but not so long after
+/// that they hold up finding the posts still being bet on
+pub fn validate_publish_time(
+    publish_at: &SystemTime,
+    current_time: &SystemTime,
+) -> Result<(), String> {
+    let Ok(publish_delay) = publish_at.duration_since(*current_time) else {
+        return Err("Posts can only be scheduled to go live in the future".to_string());
+    };
+
+    if publish_delay.as_secs() > MAXIMUM_PUBLISH_DELAY_IN_SECONDS {
+        return Err(format!(
+            "Posts cannot be scheduled to go live more than {} days ahead",
+            MAXIMUM_PUBLISH_DELAY_IN_SECONDS / (24 * 60 * 60)
+        ));
+    }
+
+    Ok(())
+}
+
+impl PostDetailsFromFrontend {
+    pub fn validate_title_and_category(
+        &self,
+        post_category_config: &PostCategoryConfig,
+    ) -> Result<(), String> {
+        if let Some(title) = &self.title {
+            if title.trim().is_empty() {
+                return Err("Post title cannot be blank".to_string());
+            }
+
+            if title.chars().count() > MAXIMUM_POST_TITLE_LENGTH {
+                return Err(format!(
+                    "Post title cannot be longer than {} characters",
+                    MAXIMUM_POST_TITLE_LENGTH
+                ));
+            }
+        }
+
+        if let Some(category) = self.category {
+            if !post_category_config.allowed_categories.contains(&category) {
+                return Err(format!("Posts can no longer be filed under {:?}", category));
+            }
+        }
+
+        Ok(())
+    }
 }
 
 impl Post {
@@ -150,23 +411,86 @@ impl Post {
             like_count: self.likes.len() as u64,
             liked_by_me: self.likes.contains(&caller),
             home_feed_ranking_score: self.home_feed_score.current_score,
-            hot_or_not_feed_ranking_score: if self.hot_or_not_details.is_some() {
-                Some(
-                    self.hot_or_not_details
-                        .as_ref()
-                        .unwrap()
-                        .hot_or_not_feed_score
-                        .current_score,
-                )
-            } else {
-                None
-            },
-            hot_or_not_betting_status: if self.creator_consent_for_inclusion_in_hot_or_not {
+            hot_or_not_feed_ranking_score: self
+                .hot_or_not_details
+                .as_ref()
+                .map(|hot_or_not_details| hot_or_not_details.hot_or_not_feed_score.current_score),
+            hot_or_not_betting_status: if self.hot_or_not_details.is_some() {
                 Some(self.get_hot_or_not_betting_status_for_this_post(current_time, &caller))
             } else {
                 None
             },
+            title: self.title.clone(),
+            category: self.category,
+            active_boost: self.get_active_boost(current_time).copied(),
+        }
+    }
+
+    pub fn get_active_boost(&self, current_time: &SystemTime) -> Option<&PostBoost> {
+        self.boost
+            .as_ref()
+            .filter(|boost| boost.is_active(current_time))
+    }
+
+    /// Only one boost can run on a post at a time. Once it lapses the post can be boosted again
+    pub fn boost(
+        &mut self,
+        amount: u64,
+        current_time: &SystemTime,
+    ) -> Result<PostBoost, BoostPostError> {
+        if self.get_active_boost(current_time).is_some() {
+            return Err(BoostPostError::PostAlreadyBoosted);
+        }
+
+        let boost = PostBoost::new(amount, current_time)?;
+        self.boost = Some(boost);
+
+        Ok(boost)
+    }
+
+    fn apply_active_boost(&self, score: u64, current_time: &SystemTime) -> u64 {
+        match self.get_active_boost(current_time) {
+            Some(boost) => boost.apply_to_score(score),
+            None => score,
+        }
+    }
+
+    /// Keeps the hot or not details around so that slots with bets in them still settle
+    pub fn revoke_hot_or_not_consent(&mut self) {
+        self.creator_consent_for_inclusion_in_hot_or_not = false;
+    }
+
+    /// Each user can report a post once until moderators next review it
+    pub fn report(
+        &mut self,
+        reporter_principal_id: &Principal,
+        current_time: &SystemTime,
+    ) -> Result<(), ReportPostError> {
+        if self.unresolved_reports.contains_key(reporter_principal_id) {
+            return Err(ReportPostError::AlreadyReported);
         }
+
+        self.unresolved_reports
+            .insert(*reporter_principal_id, *current_time);
+
+        Ok(())
+    }
+
+    /// Moderators found nothing wrong with the post, which lifts the pause on betting put in
+    /// place by reports
+    pub fn clear_reports(&mut self) {
+        self.unresolved_reports.clear();
+    }
+
+    /// Only new bets are held back. Slots that already have bets in them settle as usual
+    pub fn is_betting_paused_for_review(&self) -> bool {
+        self.is_betting_paused_by_moderator
+            || self.unresolved_reports.len() > MAXIMUM_UNRESOLVED_REPORTS_BEFORE_BETTING_IS_PAUSED
+    }
+
+    /// Only applies to bets placed from now on
+    pub fn update_minimum_bet_amount(&mut self, minimum_bet_amount: u64) {
+        self.minimum_bet_amount = minimum_bet_amount;
     }
 
     pub fn increment_share_count(&mut self) -> u64 {
@@ -182,7 +506,7 @@ impl Post {
         Post {
             id,
             description: (*post_details_from_frontend.description).to_string(),
-            hashtags: (*post_details_from_frontend.hashtags).to_vec(),
+            hashtags: normalize_hashtags(&post_details_from_frontend.hashtags),
             video_uid: (*post_details_from_frontend.video_uid).to_string(),
             status: PostStatus::Uploaded,
             created_at: *current_time,
@@ -203,9 +527,25 @@ impl Post {
             } else {
                 None
             },
+            minimum_bet_amount: 0,
+            collaborator_shares: vec![],
+            title: post_details_from_frontend
+                .title
+                .as_ref()
+                .map(|title| title.trim().to_string()),
+            category: post_details_from_frontend.category,
+            boost: None,
+            unresolved_reports: BTreeMap::new(),
+            publish_at: None,
+            is_betting_paused_by_moderator: false,
         }
     }
 
+    /// When the post goes live and its first slot starts
+    pub fn get_betting_start_time(&self) -> SystemTime {
+        self.publish_at.unwrap_or(self.created_at)
+    }
+
     fn recalculate_average_watched(&self, percentage_watched: u8, full_view_count: u8) -> u8 {
         let earlier_sum_component =
             self.view_stats.average_watch_percentage as u64 * self.view_stats.total_view_count;
@@ -286,12 +626,15 @@ impl Post {
         //     hot_or_not_participation_component
         // );
 
-        self.home_feed_score.current_score = likes_component
-            + threshold_views_component
-            + average_percent_viewed_component
-            + post_share_component
-            + age_of_video_component
-            + hot_or_not_participation_component;
+        self.home_feed_score.current_score = self.apply_active_boost(
+            likes_component
+                + threshold_views_component
+                + average_percent_viewed_component
+                + post_share_component
+                + age_of_video_component
+                + hot_or_not_participation_component,
+            current_time,
+        );
     }
 
     pub fn recalculate_hot_or_not_feed_score(&mut self, current_time: &SystemTime) {
@@ -361,16 +704,19 @@ impl Post {
             //     hot_or_not_score_component
             // );
 
-            self.hot_or_not_details
-                .as_mut()
-                .unwrap()
-                .hot_or_not_feed_score
-                .current_score = likes_component
-                + threshold_views_component
-                + average_percent_viewed_component
-                + post_share_component
-                + age_of_video_component
-                + hot_or_not_score_component;
+            let hot_or_not_feed_score = self.apply_active_boost(
+                likes_component
+                    + threshold_views_component
+                    + average_percent_viewed_component
+                    + post_share_component
+                    + age_of_video_component
+                    + hot_or_not_score_component,
+                current_time,
+            );
+
+            if let Some(ref mut details) = self.hot_or_not_details {
+                details.hot_or_not_feed_score.current_score = hot_or_not_feed_score;
+            }
         }
     }
 
@@ -405,11 +751,15 @@ mod test {
                 hashtags: vec!["#fun".to_string(), "#post".to_string()],
                 video_uid: "abcd1234".to_string(),
                 creator_consent_for_inclusion_in_hot_or_not: false,
+                collaborator_shares: None,
+                title: None,
+                category: None,
             },
             &SystemTime::now(),
         );
 
         assert!(post.hot_or_not_details.is_none());
+        assert_eq!(post.hashtags, vec!["fun".to_string(), "post".to_string()]);
 
         let post = Post::new(
             0,
@@ -418,6 +768,9 @@ mod test {
                 hashtags: vec!["#fun".to_string(), "#post".to_string()],
                 video_uid: "abcd1234".to_string(),
                 creator_consent_for_inclusion_in_hot_or_not: true,
+                collaborator_shares: None,
+                title: None,
+                category: None,
             },
             &SystemTime::now(),
         );
@@ -443,6 +796,9 @@ mod test {
                 hashtags: vec!["doggo".into(), "pupper".into()],
                 video_uid: "abcd#1234".into(),
                 creator_consent_for_inclusion_in_hot_or_not: true,
+                collaborator_shares: None,
+                title: None,
+                category: None,
             },
             &post_created_at,
         );
@@ -518,6 +874,9 @@ mod test {
                 hashtags: vec!["doggo".into(), "pupper".into()],
                 video_uid: "abcd#1234".into(),
                 creator_consent_for_inclusion_in_hot_or_not: true,
+                collaborator_shares: None,
+                title: None,
+                category: None,
             },
             &post_created_at,
         );
@@ -593,6 +952,9 @@ mod test {
                 hashtags: vec!["doggo".into(), "pupper".into()],
                 video_uid: "abcd#1234".into(),
                 creator_consent_for_inclusion_in_hot_or_not: true,
+                collaborator_shares: None,
+                title: None,
+                category: None,
             },
             &post_created_at,
         );
@@ -671,6 +1033,9 @@ mod test {
                 hashtags: vec!["doggo".into(), "pupper".into()],
                 video_uid: "abcd#1234".into(),
                 creator_consent_for_inclusion_in_hot_or_not: true,
+                collaborator_shares: None,
+                title: None,
+                category: None,
             },
             &post_created_at,
         );
@@ -751,6 +1116,9 @@ mod test {
                 hashtags: vec!["doggo".into(), "pupper".into()],
                 video_uid: "abcd#1234".into(),
                 creator_consent_for_inclusion_in_hot_or_not: true,
+                collaborator_shares: None,
+                title: None,
+                category: None,
             },
             &post_created_at,
         );
@@ -831,6 +1199,9 @@ mod test {
                 hashtags: vec!["doggo".into(), "pupper".into()],
                 video_uid: "abcd#1234".into(),
                 creator_consent_for_inclusion_in_hot_or_not: true,
+                collaborator_shares: None,
+                title: None,
+                category: None,
             },
             &post_created_at,
         );
@@ -911,6 +1282,9 @@ mod test {
                 hashtags: vec!["doggo".into(), "pupper".into()],
                 video_uid: "abcd#1234".into(),
                 creator_consent_for_inclusion_in_hot_or_not: true,
+                collaborator_shares: None,
+                title: None,
+                category: None,
             },
             &post_created_at,
         );
@@ -991,6 +1365,9 @@ mod test {
                 hashtags: vec!["doggo".into(), "pupper".into()],
                 video_uid: "abcd#1234".into(),
                 creator_consent_for_inclusion_in_hot_or_not: true,
+                collaborator_shares: None,
+                title: None,
+                category: None,
             },
             &post_created_at,
         );
@@ -1071,6 +1448,9 @@ mod test {
                 hashtags: vec!["doggo".into(), "pupper".into()],
                 video_uid: "abcd#1234".into(),
                 creator_consent_for_inclusion_in_hot_or_not: true,
+                collaborator_shares: None,
+                title: None,
+                category: None,
             },
             &post_created_at,
         );
@@ -1151,6 +1531,9 @@ mod test {
                 hashtags: vec!["doggo".into(), "pupper".into()],
                 video_uid: "abcd#1234".into(),
                 creator_consent_for_inclusion_in_hot_or_not: true,
+                collaborator_shares: None,
+                title: None,
+                category: None,
             },
             &post_created_at,
         );
@@ -1231,6 +1614,9 @@ mod test {
                 hashtags: vec!["doggo".into(), "pupper".into()],
                 video_uid: "abcd#1234".into(),
                 creator_consent_for_inclusion_in_hot_or_not: true,
+                collaborator_shares: None,
+                title: None,
+                category: None,
             },
             &post_created_at,
         );
@@ -1320,6 +1706,9 @@ mod test {
                 hashtags: vec!["doggo".into(), "pupper".into()],
                 video_uid: "abcd#1234".into(),
                 creator_consent_for_inclusion_in_hot_or_not: true,
+                collaborator_shares: None,
+                title: None,
+                category: None,
             },
             &post_created_at,
         );
@@ -1409,6 +1798,9 @@ mod test {
                 hashtags: vec!["doggo".into(), "pupper".into()],
                 video_uid: "abcd#1234".into(),
                 creator_consent_for_inclusion_in_hot_or_not: true,
+                collaborator_shares: None,
+                title: None,
+                category: None,
             },
             &post_created_at,
         );
@@ -1501,6 +1893,9 @@ mod test {
                 hashtags: vec!["doggo".into(), "pupper".into()],
                 video_uid: "abcd#1234".into(),
                 creator_consent_for_inclusion_in_hot_or_not: true,
+                collaborator_shares: None,
+                title: None,
+                category: None,
             },
             &post_created_at,
         );
@@ -1595,6 +1990,9 @@ mod test {
                 hashtags: vec!["doggo".into(), "pupper".into()],
                 video_uid: "abcd#1234".into(),
                 creator_consent_for_inclusion_in_hot_or_not: true,
+                collaborator_shares: None,
+                title: None,
+                category: None,
             },
             &post_created_at,
         );
@@ -1689,6 +2087,9 @@ mod test {
                 hashtags: vec!["doggo".into(), "pupper".into()],
                 video_uid: "abcd#1234".into(),
                 creator_consent_for_inclusion_in_hot_or_not: true,
+                collaborator_shares: None,
+                title: None,
+                category: None,
             },
             &post_created_at,
         );
@@ -1783,6 +2184,9 @@ mod test {
                 hashtags: vec!["doggo".into(), "pupper".into()],
                 video_uid: "abcd#1234".into(),
                 creator_consent_for_inclusion_in_hot_or_not: true,
+                collaborator_shares: None,
+                title: None,
+                category: None,
             },
             &post_created_at,
         );
@@ -1877,6 +2281,9 @@ mod test {
                 hashtags: vec!["doggo".into(), "pupper".into()],
                 video_uid: "abcd#1234".into(),
                 creator_consent_for_inclusion_in_hot_or_not: true,
+                collaborator_shares: None,
+                title: None,
+                category: None,
             },
             &post_created_at,
         );
@@ -1971,6 +2378,9 @@ mod test {
                 hashtags: vec!["doggo".into(), "pupper".into()],
                 video_uid: "abcd#1234".into(),
                 creator_consent_for_inclusion_in_hot_or_not: true,
+                collaborator_shares: None,
+                title: None,
+                category: None,
             },
             &post_created_at,
         );
@@ -2065,6 +2475,9 @@ mod test {
                 hashtags: vec!["doggo".into(), "pupper".into()],
                 video_uid: "abcd#1234".into(),
                 creator_consent_for_inclusion_in_hot_or_not: true,
+                collaborator_shares: None,
+                title: None,
+                category: None,
             },
             &post_created_at,
         );
@@ -2153,6 +2566,9 @@ mod test {
                 hashtags: vec!["doggo".into(), "pupper".into()],
                 video_uid: "abcd#1234".into(),
                 creator_consent_for_inclusion_in_hot_or_not: true,
+                collaborator_shares: None,
+                title: None,
+                category: None,
             },
             &post_created_at,
         );
@@ -2252,4 +2668,96 @@ mod test {
 
         assert_eq!(post.view_stats.average_watch_percentage, 77);
     }
+
+    #[test]
+    fn test_boost_raises_feed_scores_while_active() {
+        let post_created_at = SystemTime::now();
+        let mut post = Post::new(
+            0,
+            &PostDetailsFromFrontend {
+                description: "Doggos and puppers".into(),
+                hashtags: vec!["doggo".into(), "pupper".into()],
+                video_uid: "abcd#1234".into(),
+                creator_consent_for_inclusion_in_hot_or_not: true,
+                collaborator_shares: None,
+                title: None,
+                category: None,
+            },
+            &post_created_at,
+        );
+        post.view_stats.total_view_count = 10;
+        post.view_stats.threshold_view_count = 5;
+        let boosted_at = post_created_at + Duration::from_secs(60 * 60);
+
+        post.recalculate_home_feed_score(&boosted_at);
+        post.recalculate_hot_or_not_feed_score(&boosted_at);
+        let home_feed_score = post.home_feed_score.current_score;
+        let hot_or_not_feed_score = post
+            .hot_or_not_details
+            .as_ref()
+            .unwrap()
+            .hot_or_not_feed_score
+            .current_score;
+
+        assert_eq!(
+            post.boost(50, &boosted_at),
+            Err(BoostPostError::BoostAmountBelowMinimum)
+        );
+        assert_eq!(
+            post.boost(
+                (MAXIMUM_POST_BOOST_PERCENTAGE + 1) * POST_BOOST_TOKENS_PER_PERCENTAGE_POINT,
+                &boosted_at
+            ),
+            Err(BoostPostError::BoostAmountAboveMaximum)
+        );
+        assert!(post.get_active_boost(&boosted_at).is_none());
+
+        // * Scores decay with age, so compare against the unboosted scores once the boost ends
+        let mut unboosted_post = post.clone();
+        let boost = post.boost(505, &boosted_at).unwrap();
+        assert_eq!(boost.boost_percentage, 50);
+        unboosted_post.recalculate_home_feed_score(&boost.boosted_until);
+        unboosted_post.recalculate_hot_or_not_feed_score(&boost.boosted_until);
+        assert_eq!(
+            post.boost(100, &boosted_at),
+            Err(BoostPostError::PostAlreadyBoosted)
+        );
+
+        post.recalculate_home_feed_score(&boosted_at);
+        post.recalculate_hot_or_not_feed_score(&boosted_at);
+        assert_eq!(
+            post.home_feed_score.current_score,
+            home_feed_score + home_feed_score / 2
+        );
+        assert_eq!(
+            post.hot_or_not_details
+                .as_ref()
+                .unwrap()
+                .hot_or_not_feed_score
+                .current_score,
+            hot_or_not_feed_score + hot_or_not_feed_score / 2
+        );
+        assert_eq!(post.get_active_boost(&boosted_at), Some(&boost));
+
+        post.recalculate_home_feed_score(&boost.boosted_until);
+        post.recalculate_hot_or_not_feed_score(&boost.boosted_until);
+        assert_eq!(
+            post.home_feed_score.current_score,
+            unboosted_post.home_feed_score.current_score
+        );
+        assert_eq!(
+            post.hot_or_not_details
+                .as_ref()
+                .unwrap()
+                .hot_or_not_feed_score
+                .current_score,
+            unboosted_post
+                .hot_or_not_details
+                .as_ref()
+                .unwrap()
+                .hot_or_not_feed_score
+                .current_score
+        );
+        assert!(post.get_active_boost(&boost.boosted_until).is_none());
+    }
 }
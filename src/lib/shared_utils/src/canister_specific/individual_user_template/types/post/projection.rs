@@ -0,0 +1,201 @@
+use std::time::SystemTime;
+
+use candid::{CandidType, Deserialize, Principal};
+use serde::Serialize;
+
+use crate::canister_specific::individual_user_template::types::{
+    hot_or_not::BettingStatus, profile::UserProfile,
+};
+
+use super::{Post, PostBoost, PostCategory, PostStatus};
+
+/// Groups of post details a caller can ask for, so that feeds needing only scores and counts
+/// don't pay for encoding and decoding the rest
+#[derive(
+    CandidType, Deserialize, Serialize, Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord,
+)]
+pub enum PostField {
+    Creator,
+    Content,
+    Counts,
+    Scores,
+    // * Works out the ongoing slot and room, so costs more than the other groups
+    BettingStatus,
+}
+
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug)]
+pub struct PostCreatorDetails {
+    pub display_name: Option<String>,
+    pub unique_user_name: Option<String>,
+    pub user_principal_id: Principal,
+    pub profile_photo_url: Option<String>,
+}
+
+impl PostCreatorDetails {
+    /// None until the canister has been handed to its owner
+    pub fn from_profile(profile: &UserProfile) -> Option<Self> {
+        Some(Self {
+            display_name: profile.display_name.clone(),
+            unique_user_name: profile.unique_user_name.clone(),
+            user_principal_id: profile.principal_id?,
+            profile_photo_url: profile.profile_picture_url.clone(),
+        })
+    }
+}
+
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug)]
+pub struct PostContentDetails {
+    pub created_at: SystemTime,
+    pub description: String,
+    pub hashtags: Vec<String>,
+    pub video_uid: String,
+    pub status: PostStatus,
+    pub title: Option<String>,
+    pub category: Option<PostCategory>,
+}
+
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug, PartialEq, Eq)]
+pub struct PostCounts {
+    pub total_view_count: u64,
+    pub like_count: u64,
+    pub liked_by_me: bool,
+}
+
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug, PartialEq, Eq)]
+pub struct PostScores {
+    pub home_feed_ranking_score: u64,
+    pub hot_or_not_feed_ranking_score: Option<u64>,
+    pub active_boost: Option<PostBoost>,
+}
+
+/// The fields of [`super::PostDetailsForFrontend`] in the groups that were asked for. Groups
+/// that weren't are left out.
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug)]
+pub struct ProjectedPostDetails {
+    pub id: u64,
+    pub creator: Option<PostCreatorDetails>,
+    pub content: Option<PostContentDetails>,
+    pub counts: Option<PostCounts>,
+    pub scores: Option<PostScores>,
+    // * Also left out for posts that aren't on hot or not
+    pub hot_or_not_betting_status: Option<BettingStatus>,
+}
+
+impl Post {
+    /// `creator` is only read if asked for, and is the same for every post in the canister
+    pub fn get_projected_post_details(
+        &self,
+        fields: &[PostField],
+        creator: Option<&PostCreatorDetails>,
+        caller: Principal,
+        current_time: &SystemTime,
+    ) -> ProjectedPostDetails {
+        ProjectedPostDetails {
+            id: self.id,
+            creator: creator
+                .filter(|_| fields.contains(&PostField::Creator))
+                .cloned(),
+            content: fields
+                .contains(&PostField::Content)
+                .then(|| PostContentDetails {
+                    created_at: self.created_at,
+                    description: self.description.clone(),
+                    hashtags: self.hashtags.clone(),
+                    video_uid: self.video_uid.clone(),
+                    status: self.status.clone(),
+                    title: self.title.clone(),
+                    category: self.category,
+                }),
+            counts: fields.contains(&PostField::Counts).then(|| PostCounts {
+                total_view_count: self.view_stats.total_view_count,
+                like_count: self.likes.len() as u64,
+                liked_by_me: self.likes.contains(&caller),
+            }),
+            scores: fields.contains(&PostField::Scores).then(|| PostScores {
+                home_feed_ranking_score: self.home_feed_score.current_score,
+                hot_or_not_feed_ranking_score: self.hot_or_not_details.as_ref().map(
+                    |hot_or_not_details| hot_or_not_details.hot_or_not_feed_score.current_score,
+                ),
+                active_boost: self.get_active_boost(current_time).copied(),
+            }),
+            hot_or_not_betting_status: (fields.contains(&PostField::BettingStatus)
+                && self.hot_or_not_details.is_some())
+            .then(|| self.get_hot_or_not_betting_status_for_this_post(current_time, &caller)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use test_utils::setup::test_constants::{
+        get_mock_user_alice_principal_id, get_mock_user_bob_principal_id,
+    };
+
+    use crate::canister_specific::individual_user_template::types::post::PostDetailsFromFrontend;
+
+    use super::*;
+
+    #[test]
+    fn test_only_the_fields_asked_for_are_projected() {
+        let mut post = Post::new(
+            0,
+            &PostDetailsFromFrontend {
+                description: "Doggos and puppers".into(),
+                hashtags: vec!["doggo".into(), "pupper".into()],
+                video_uid: "abcd#1234".into(),
+                creator_consent_for_inclusion_in_hot_or_not: true,
+                collaborator_shares: None,
+                title: None,
+                category: None,
+            },
+            &SystemTime::UNIX_EPOCH,
+        );
+        post.likes.insert(get_mock_user_bob_principal_id());
+        let creator = PostCreatorDetails {
+            display_name: Some("Alice".into()),
+            unique_user_name: None,
+            user_principal_id: get_mock_user_alice_principal_id(),
+            profile_photo_url: None,
+        };
+
+        let projected_post_details = post.get_projected_post_details(
+            &[PostField::Counts, PostField::Scores],
+            Some(&creator),
+            get_mock_user_bob_principal_id(),
+            &SystemTime::UNIX_EPOCH,
+        );
+        assert_eq!(
+            projected_post_details.counts,
+            Some(PostCounts {
+                total_view_count: 0,
+                like_count: 1,
+                liked_by_me: true,
+            })
+        );
+        assert!(projected_post_details.scores.is_some());
+        assert!(projected_post_details.creator.is_none());
+        assert!(projected_post_details.content.is_none());
+        assert!(projected_post_details.hot_or_not_betting_status.is_none());
+
+        let projected_post_details = post.get_projected_post_details(
+            &[
+                PostField::Creator,
+                PostField::Content,
+                PostField::BettingStatus,
+            ],
+            Some(&creator),
+            get_mock_user_bob_principal_id(),
+            &SystemTime::UNIX_EPOCH,
+        );
+        assert_eq!(
+            projected_post_details.creator.unwrap().display_name,
+            Some("Alice".to_string())
+        );
+        assert_eq!(
+            projected_post_details.content.unwrap().video_uid,
+            "abcd#1234"
+        );
+        assert!(projected_post_details.hot_or_not_betting_status.is_some());
+        assert!(projected_post_details.counts.is_none());
+    }
+}
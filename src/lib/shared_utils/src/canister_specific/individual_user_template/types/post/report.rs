@@ -0,0 +1,19 @@
+use std::time::SystemTime;
+
+use candid::{CandidType, Deserialize, Principal};
+use serde::Serialize;
+
+use crate::common::types::app_primitive_type::PostId;
+
+pub const MAX_POST_REPORT_REASON_LENGTH: usize = 500;
+
+/// Raised by a user against a post they think should not be served. Counted towards pausing
+/// betting on the post by its creator's canister and passed on to the user index, where
+/// moderators review it.
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug, PartialEq, Eq)]
+pub struct PostReport {
+    pub post_id: PostId,
+    pub reported_by: Principal,
+    pub reason: Option<String>,
+    pub reported_at: SystemTime,
+}
@@ -0,0 +1,555 @@
+use std::{borrow::Cow, ops::RangeInclusive};
+
+use candid::{Decode, Encode};
+use ic_stable_structures::{BoundedStorable, Memory, StableBTreeMap, Storable};
+use sha2::{Digest, Sha256};
+
+use crate::{
+    canister_specific::individual_user_template::types::hot_or_not::{
+        slot_compaction::ArchivedBets, HotOrNotDetails,
+    },
+    common::types::app_primitive_type::PostId,
+};
+
+use super::Post;
+
+// * Encoded posts are split into chunks of at most this many bytes
+const POST_CHUNK_SIZE: u32 = 2048;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum PostSection {
+    Metadata = 0,
+    HotOrNotDetails = 1,
+    ArchivedBets = 2,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+struct PostChunkKey([u8; 13]);
+
+impl PostChunkKey {
+    fn new(post_id: PostId, section: PostSection, chunk_index: u32) -> Self {
+        let mut key_bytes = [0; 13];
+        key_bytes[..8].copy_from_slice(&post_id.to_be_bytes());
+        key_bytes[8] = section as u8;
+        key_bytes[9..].copy_from_slice(&chunk_index.to_be_bytes());
+        Self(key_bytes)
+    }
+
+    fn get_post_id(&self) -> PostId {
+        u64::from_be_bytes(self.0[..8].try_into().unwrap())
+    }
+
+    fn range_for_section(post_id: PostId, section: PostSection) -> RangeInclusive<Self> {
+        Self::new(post_id, section, 0)..=Self::new(post_id, section, u32::MAX)
+    }
+}
+
+impl Storable for PostChunkKey {
+    fn to_bytes(&self) -> Cow<'_, [u8]> {
+        Cow::Borrowed(&self.0)
+    }
+
+    fn from_bytes(bytes: Cow<[u8]>) -> Self {
+        Self(bytes.as_ref().try_into().unwrap())
+    }
+}
+
+impl BoundedStorable for PostChunkKey {
+    const MAX_SIZE: u32 = 13;
+    const IS_FIXED_SIZE: bool = true;
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct PostChunk(Vec<u8>);
+
+impl Storable for PostChunk {
+    fn to_bytes(&self) -> Cow<'_, [u8]> {
+        Cow::Borrowed(&self.0)
+    }
+
+    fn from_bytes(bytes: Cow<[u8]>) -> Self {
+        Self(bytes.into_owned())
+    }
+}
+
+impl BoundedStorable for PostChunk {
+    const MAX_SIZE: u32 = POST_CHUNK_SIZE;
+    const IS_FIXED_SIZE: bool = false;
+}
+
+/// Every post created by a profile, kept in its own stable memory region so that posts
+/// neither weigh on the heap nor have to be serialized on upgrades.
+///
+/// A post's hot or not details grow with every bet placed on it, so they are stored apart
+/// from the rest of the post. Both parts are encoded and split into bounded chunks, keyed by
+/// post id, part and chunk index, which keeps all chunks of a post next to each other.
+///
+/// Bets compacted out of settled rooms make up a third part, which is only read by the few
+/// callers that ask for them.
+pub struct StablePostMap<M: Memory> {
+    chunks: StableBTreeMap<PostChunkKey, PostChunk, M>,
+    len: u64,
+}
+
+impl<M: Memory> StablePostMap<M> {
+    /// Loads the posts persisted in `memory`, or creates an empty map if there are none.
+    pub fn init(memory: M) -> Self {
+        let mut post_map = Self {
+            chunks: StableBTreeMap::init(memory),
+            len: 0,
+        };
+        post_map.len = post_map.keys().count() as u64;
+
+        post_map
+    }
+
+    pub fn len(&self) -> u64 {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn contains_key(&self, post_id: &PostId) -> bool {
+        self.chunks
+            .contains_key(&PostChunkKey::new(*post_id, PostSection::Metadata, 0))
+    }
+
+    pub fn get(&self, post_id: &PostId) -> Option<Post> {
+        let mut post = Post::from_bytes(Cow::Owned(
+            self.read_section(*post_id, PostSection::Metadata)?,
+        ));
+        post.hot_or_not_details = self
+            .read_section(*post_id, PostSection::HotOrNotDetails)
+            .map(|bytes| HotOrNotDetails::from_bytes(Cow::Owned(bytes)));
+
+        Some(post)
+    }
+
+    /// Same as `get`, with the bets of compacted rooms read back in from the archived bets.
+    /// Costs another read out of stable memory, so only for callers that need every bet.
+    pub fn get_with_archived_bets(&self, post_id: &PostId) -> Option<Post> {
+        let mut post = self.get(post_id)?;
+        if let Some(bytes) = self.read_section(*post_id, PostSection::ArchivedBets) {
+            post.restore_archived_bets(Decode!(&bytes, ArchivedBets).unwrap());
+        }
+
+        Some(post)
+    }
+
+    /// Adds bets compacted out of the post's rooms to its archived bets
+    pub fn archive_bets(&mut self, post_id: PostId, bets_to_archive: ArchivedBets) {
+        let mut archived_bets = self
+            .read_section(post_id, PostSection::ArchivedBets)
+            .map(|bytes| Decode!(&bytes, ArchivedBets).unwrap())
+            .unwrap_or_default();
+        bets_to_archive
+            .into_iter()
+            .for_each(|(slot_id, archived_rooms)| {
+                archived_bets
+                    .entry(slot_id)
+                    .or_default()
+                    .extend(archived_rooms);
+            });
+
+        self.remove_section(post_id, PostSection::ArchivedBets);
+        self.write_section(
+            post_id,
+            PostSection::ArchivedBets,
+            &Encode!(&archived_bets).unwrap(),
+        );
+    }
+
+    pub fn insert(&mut self, post_id: PostId, mut post: Post) {
+        if !self.remove_section(post_id, PostSection::Metadata) {
+            self.len += 1;
+        }
+        self.remove_section(post_id, PostSection::HotOrNotDetails);
+
+        if let Some(hot_or_not_details) = post.hot_or_not_details.take() {
+            self.write_section(
+                post_id,
+                PostSection::HotOrNotDetails,
+                &hot_or_not_details.to_bytes(),
+            );
+        }
+        self.write_section(post_id, PostSection::Metadata, &post.to_bytes());
+    }
+
+    pub fn remove(&mut self, post_id: &PostId) -> Option<Post> {
+        let post = self.get(post_id)?;
+        self.remove_section(*post_id, PostSection::Metadata);
+        self.remove_section(*post_id, PostSection::HotOrNotDetails);
+        self.remove_section(*post_id, PostSection::ArchivedBets);
+        self.len -= 1;
+
+        Some(post)
+    }
+
+    /// Loads the post, hands it to `update` and stores it back. Returns `None` without
+    /// calling `update` if there is no such post.
+    pub fn update<R>(
+        &mut self,
+        post_id: &PostId,
+        update: impl FnOnce(&mut Post) -> R,
+    ) -> Option<R> {
+        let mut post = self.get(post_id)?;
+        let result = update(&mut post);
+        self.insert(*post_id, post);
+
+        Some(result)
+    }
+
+    /// Digest of the post as stored, that changes whenever any part of it does. Hashes the
+    /// stored bytes without decoding them, so it is cheap enough to check every post with.
+    pub fn get_fingerprint(&self, post_id: &PostId) -> Option<[u8; 32]> {
+        let mut hasher = Sha256::new();
+        hasher.update(self.read_section(*post_id, PostSection::Metadata)?);
+        if let Some(bytes) = self.read_section(*post_id, PostSection::HotOrNotDetails) {
+            hasher.update(bytes);
+        }
+
+        Some(hasher.finalize().into())
+    }
+
+    /// Re-encodes the hot or not details of posts stored while pots and payouts were u64, so
+    /// that they carry their u128 amounts too. Returns the number of posts re-encoded.
+    pub fn reencode_legacy_hot_or_not_details(&mut self) -> u64 {
+        let post_ids = self.keys().collect::<Vec<_>>();
+        let mut posts_reencoded = 0;
+
+        post_ids.into_iter().for_each(|post_id| {
+            let Some(bytes) = self.read_section(post_id, PostSection::HotOrNotDetails) else {
+                return;
+            };
+            let reencoded_bytes = HotOrNotDetails::from_bytes(Cow::Borrowed(&bytes))
+                .to_bytes()
+                .into_owned();
+            if reencoded_bytes == bytes {
+                return;
+            }
+
+            self.remove_section(post_id, PostSection::HotOrNotDetails);
+            self.write_section(post_id, PostSection::HotOrNotDetails, &reencoded_bytes);
+            posts_reencoded += 1;
+        });
+
+        posts_reencoded
+    }
+
+    /// Yields post ids in ascending order
+    pub fn keys(&self) -> impl Iterator<Item = PostId> + '_ {
+        self.keys_from(PostId::MIN)
+    }
+
+    /// Yields post ids from `start_from_post_id` onwards, in ascending order
+    pub fn keys_from(&self, start_from_post_id: PostId) -> impl Iterator<Item = PostId> + '_ {
+        let mut next_post_id = Some(start_from_post_id);
+
+        std::iter::from_fn(move || {
+            // * Every post has a first metadata chunk, which sorts before its other chunks
+            let (key, _) = self
+                .chunks
+                .range(PostChunkKey::new(next_post_id?, PostSection::Metadata, 0)..)
+                .next()?;
+            let post_id = key.get_post_id();
+            next_post_id = post_id.checked_add(1);
+
+            Some(post_id)
+        })
+    }
+
+    /// Yields posts in ascending order of their ids
+    pub fn iter(&self) -> impl Iterator<Item = (PostId, Post)> + '_ {
+        self.keys()
+            .filter_map(|post_id| Some((post_id, self.get(&post_id)?)))
+    }
+
+    /// Yields posts in descending order of their ids. Only the ids are collected up front,
+    /// posts are loaded as they are yielded.
+    pub fn iter_rev(&self) -> impl Iterator<Item = (PostId, Post)> + '_ {
+        let post_ids = self.keys().collect::<Vec<_>>();
+
+        post_ids
+            .into_iter()
+            .rev()
+            .filter_map(|post_id| Some((post_id, self.get(&post_id)?)))
+    }
+
+    /// Yields posts in ascending order of their ids
+    pub fn values(&self) -> impl Iterator<Item = Post> + '_ {
+        self.keys().filter_map(|post_id| self.get(&post_id))
+    }
+
+    fn read_section(&self, post_id: PostId, section: PostSection) -> Option<Vec<u8>> {
+        let mut chunks = self
+            .chunks
+            .range(PostChunkKey::range_for_section(post_id, section))
+            .peekable();
+        chunks.peek()?;
+
+        Some(chunks.flat_map(|(_, chunk)| chunk.0).collect())
+    }
+
+    fn write_section(&mut self, post_id: PostId, section: PostSection, bytes: &[u8]) {
+        bytes
+            .chunks(POST_CHUNK_SIZE as usize)
+            .enumerate()
+            .for_each(|(chunk_index, chunk)| {
+                self.chunks.insert(
+                    PostChunkKey::new(post_id, section, chunk_index as u32),
+                    PostChunk(chunk.to_vec()),
+                );
+            });
+    }
+
+    /// Returns whether there was anything to remove
+    fn remove_section(&mut self, post_id: PostId, section: PostSection) -> bool {
+        let keys_to_remove = self
+            .chunks
+            .range(PostChunkKey::range_for_section(post_id, section))
+            .map(|(key, _)| key)
+            .collect::<Vec<_>>();
+        keys_to_remove.iter().for_each(|key| {
+            self.chunks.remove(key);
+        });
+
+        !keys_to_remove.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{collections::BTreeMap, time::SystemTime};
+
+    use candid::{CandidType, Encode};
+    use ic_stable_structures::VectorMemory;
+    use test_utils::setup::test_constants::v2::{canister, users};
+
+    use crate::canister_specific::individual_user_template::types::{
+        hot_or_not::{
+            amount_repr::AggregateStats, BetAmountHistogram, BetDirection, SlotDetails, SlotId,
+        },
+        post::{FeedScore, PostDetailsFromFrontend},
+    };
+
+    use super::*;
+
+    fn get_post(post_id: PostId, number_of_bets: usize) -> Post {
+        let current_time = SystemTime::now();
+        let mut post = Post::new(
+            post_id,
+            &PostDetailsFromFrontend {
+                description: "Doggos and puppers".into(),
+                hashtags: vec!["doggo".into(), "pupper".into()],
+                video_uid: "abcd#1234".into(),
+                creator_consent_for_inclusion_in_hot_or_not: true,
+                collaborator_shares: None,
+                title: None,
+                category: None,
+            },
+            &current_time,
+        );
+        users("bettor", number_of_bets)
+            .iter()
+            .for_each(|bet_maker_principal_id| {
+                post.place_hot_or_not_bet(
+                    bet_maker_principal_id,
+                    &canister("bettor"),
+                    10,
+                    &BetDirection::Hot,
+                    &current_time,
+                )
+                .unwrap();
+            });
+
+        post
+    }
+
+    #[test]
+    fn test_posts_larger_than_a_chunk_are_stored_and_read_back() {
+        let mut post_map = StablePostMap::init(VectorMemory::default());
+        assert!(post_map.is_empty());
+        assert!(post_map.get(&0).is_none());
+
+        let post = get_post(0, 100);
+        assert!(
+            post.hot_or_not_details.as_ref().unwrap().to_bytes().len() > POST_CHUNK_SIZE as usize
+        );
+        let expected_bytes = post.to_bytes().into_owned();
+        post_map.insert(0, post);
+
+        assert_eq!(post_map.len(), 1);
+        assert!(post_map.contains_key(&0));
+        assert_eq!(
+            post_map.get(&0).unwrap().to_bytes().into_owned(),
+            expected_bytes
+        );
+    }
+
+    #[test]
+    fn test_overwriting_a_post_drops_its_stale_chunks() {
+        let mut post_map = StablePostMap::init(VectorMemory::default());
+        post_map.insert(0, get_post(0, 100));
+        post_map.insert(1, get_post(1, 1));
+        let number_of_chunks_of_post_1 = post_map
+            .chunks
+            .iter()
+            .filter(|(key, _)| key.get_post_id() == 1)
+            .count();
+
+        let mut smaller_post = get_post(0, 1);
+        smaller_post.description = "Just doggos".into();
+        post_map.insert(0, smaller_post);
+
+        assert_eq!(post_map.len(), 2);
+        assert_eq!(post_map.get(&0).unwrap().description, "Just doggos");
+        assert_eq!(
+            post_map
+                .chunks
+                .iter()
+                .filter(|(key, _)| key.get_post_id() == 0)
+                .count(),
+            number_of_chunks_of_post_1
+        );
+
+        let mut post_without_hot_or_not_details = get_post(0, 0);
+        post_without_hot_or_not_details.hot_or_not_details = None;
+        post_map.insert(0, post_without_hot_or_not_details);
+
+        assert!(post_map.get(&0).unwrap().hot_or_not_details.is_none());
+    }
+
+    #[test]
+    fn test_update_and_remove() {
+        let mut post_map = StablePostMap::init(VectorMemory::default());
+        post_map.insert(0, get_post(0, 1));
+
+        assert_eq!(
+            post_map.update(&0, |post| post.increment_share_count()),
+            Some(1)
+        );
+        assert_eq!(
+            post_map.update(&1, |post| post.increment_share_count()),
+            None
+        );
+        assert_eq!(post_map.get(&0).unwrap().share_count, 1);
+
+        // * the fingerprint follows every change to the post
+        let fingerprint = post_map.get_fingerprint(&0).unwrap();
+        assert_eq!(post_map.get_fingerprint(&0), Some(fingerprint));
+        post_map.update(&0, |post| post.increment_share_count());
+        assert_ne!(post_map.get_fingerprint(&0), Some(fingerprint));
+        assert_eq!(post_map.get_fingerprint(&1), None);
+
+        assert_eq!(post_map.remove(&0).map(|post| post.share_count), Some(2));
+        assert!(post_map.remove(&0).is_none());
+        assert!(post_map.is_empty());
+        assert_eq!(post_map.chunks.len(), 0);
+    }
+
+    #[test]
+    fn test_archived_bets_are_only_read_back_on_request() {
+        let mut post_map = StablePostMap::init(VectorMemory::default());
+        let mut post = get_post(0, 20);
+        let archived_bets = post.compact_settled_slots();
+        post_map.archive_bets(0, archived_bets);
+        post_map.insert(0, post);
+
+        let get_number_of_bets = |post: Post| {
+            post.hot_or_not_details
+                .unwrap()
+                .slot_history
+                .values()
+                .flat_map(|slot_details| slot_details.room_details.values())
+                .map(|room_details| room_details.bets_made.len())
+                .sum::<usize>()
+        };
+        assert_eq!(get_number_of_bets(post_map.get(&0).unwrap()), 0);
+        assert_eq!(
+            get_number_of_bets(post_map.get_with_archived_bets(&0).unwrap()),
+            20
+        );
+        assert_eq!(post_map.keys().collect::<Vec<_>>(), vec![0]);
+
+        post_map.remove(&0);
+        assert_eq!(post_map.chunks.len(), 0);
+    }
+
+    #[test]
+    fn test_posts_are_iterated_in_order_and_reloaded_from_memory() {
+        let memory = VectorMemory::default();
+        let mut post_map = StablePostMap::init(memory.clone());
+        [3, 0, 256, 1].iter().for_each(|post_id| {
+            post_map.insert(*post_id, get_post(*post_id, 20));
+        });
+
+        assert_eq!(post_map.keys().collect::<Vec<_>>(), vec![0, 1, 3, 256]);
+        assert_eq!(post_map.keys_from(2).collect::<Vec<_>>(), vec![3, 256]);
+        assert_eq!(
+            post_map.values().map(|post| post.id).collect::<Vec<_>>(),
+            vec![0, 1, 3, 256]
+        );
+        assert!(post_map.iter().all(|(post_id, post)| post_id == post.id));
+        assert_eq!(
+            post_map
+                .iter_rev()
+                .map(|(post_id, _post)| post_id)
+                .collect::<Vec<_>>(),
+            vec![256, 3, 1, 0]
+        );
+
+        let reloaded_post_map = StablePostMap::init(memory);
+
+        assert_eq!(reloaded_post_map.len(), 4);
+        assert_eq!(
+            reloaded_post_map.keys().collect::<Vec<_>>(),
+            vec![0, 1, 3, 256]
+        );
+    }
+
+    #[test]
+    fn test_hot_or_not_details_with_u64_amounts_are_reencoded() {
+        let mut post_map = StablePostMap::init(VectorMemory::default());
+        post_map.insert(0, get_post(0, 1));
+        post_map.insert(1, get_post(1, 1));
+        // * As encoded before aggregate stats carried the u128 total amount bet
+        #[derive(CandidType)]
+        struct LegacyHotOrNotDetails {
+            hot_or_not_feed_score: FeedScore,
+            aggregate_stats: AggregateStats,
+            slot_history: BTreeMap<SlotId, SlotDetails>,
+            bet_amount_histogram: BetAmountHistogram,
+        }
+        let legacy_bytes = Encode!(&LegacyHotOrNotDetails {
+            hot_or_not_feed_score: FeedScore::default(),
+            aggregate_stats: AggregateStats {
+                total_number_of_hot_bets: 1,
+                total_number_of_not_bets: 0,
+                total_amount_bet: 10,
+                total_amount_bet_u128: None,
+            },
+            slot_history: BTreeMap::new(),
+            bet_amount_histogram: BetAmountHistogram::default(),
+        })
+        .unwrap();
+        post_map.remove_section(1, PostSection::HotOrNotDetails);
+        post_map.write_section(1, PostSection::HotOrNotDetails, &legacy_bytes);
+
+        assert_eq!(
+            post_map
+                .get(&1)
+                .unwrap()
+                .hot_or_not_details
+                .unwrap()
+                .aggregate_stats
+                .total_amount_bet,
+            10
+        );
+
+        assert_eq!(post_map.reencode_legacy_hot_or_not_details(), 1);
+        assert_eq!(post_map.reencode_legacy_hot_or_not_details(), 0);
+        assert_eq!(post_map.len(), 2);
+    }
+}
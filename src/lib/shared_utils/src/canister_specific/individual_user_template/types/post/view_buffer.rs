@@ -0,0 +1,129 @@
+use std::{collections::BTreeMap, time::Duration};
+
+use candid::{Deserialize, Principal};
+use serde::Serialize;
+
+use crate::common::types::{app_primitive_type::PostId, sharded_counter::ShardedCounter};
+
+use super::PostViewDetailsFromFrontend;
+
+pub const MAX_BUFFERED_POST_VIEWS: usize = 500;
+pub const POST_VIEW_BUFFER_FLUSH_INTERVAL: Duration = Duration::from_secs(30);
+
+#[derive(Clone, Deserialize, Serialize, Default, Debug, PartialEq, Eq)]
+pub struct BufferedPostViews {
+    pub view_counter: ShardedCounter,
+    pub view_details: Vec<PostViewDetailsFromFrontend>,
+}
+
+/// Views reported since the last flush. Recording a view only appends here, and the views are
+/// applied to the posts in one go on a timer, so a viral post's scores get recalculated and
+/// pushed once per flush instead of on every view.
+#[derive(Clone, Deserialize, Serialize, Default, Debug)]
+pub struct PostViewBuffer {
+    buffered_views: BTreeMap<PostId, BufferedPostViews>,
+    number_of_buffered_views: usize,
+}
+
+impl PostViewBuffer {
+    /// Returns whether the buffer is full and should be flushed right away
+    pub fn push(
+        &mut self,
+        post_id: PostId,
+        viewer: &Principal,
+        details: PostViewDetailsFromFrontend,
+    ) -> bool {
+        let buffered_post_views = self.buffered_views.entry(post_id).or_default();
+        buffered_post_views
+            .view_counter
+            .increment(viewer, details.get_view_count());
+        buffered_post_views.view_details.push(details);
+        self.number_of_buffered_views += 1;
+
+        self.number_of_buffered_views >= MAX_BUFFERED_POST_VIEWS
+    }
+
+    /// Views of this post that haven't been applied to its view statistics yet
+    pub fn get_pending_view_count(&self, post_id: PostId) -> u64 {
+        self.buffered_views
+            .get(&post_id)
+            .map(|buffered_post_views| buffered_post_views.view_counter.get())
+            .unwrap_or_default()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.buffered_views.is_empty()
+    }
+
+    pub fn take(&mut self) -> BTreeMap<PostId, BufferedPostViews> {
+        self.number_of_buffered_views = 0;
+        std::mem::take(&mut self.buffered_views)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use test_utils::setup::test_constants::v2::users;
+
+    use super::*;
+
+    #[test]
+    fn test_post_view_buffer() {
+        let mut post_view_buffer = PostViewBuffer::default();
+        let viewers = users("viewer", MAX_BUFFERED_POST_VIEWS);
+
+        assert!(!post_view_buffer.push(
+            0,
+            &viewers[0],
+            PostViewDetailsFromFrontend::WatchedMultipleTimes {
+                watch_count: 2,
+                percentage_watched: 40,
+            },
+        ));
+        assert!(!post_view_buffer.push(
+            1,
+            &viewers[1],
+            PostViewDetailsFromFrontend::WatchedPartially {
+                percentage_watched: 10,
+            },
+        ));
+        assert_eq!(post_view_buffer.get_pending_view_count(0), 3);
+        assert_eq!(post_view_buffer.get_pending_view_count(1), 1);
+        assert_eq!(post_view_buffer.get_pending_view_count(2), 0);
+
+        let is_full = viewers[2..]
+            .iter()
+            .map(|viewer| {
+                post_view_buffer.push(
+                    0,
+                    viewer,
+                    PostViewDetailsFromFrontend::WatchedPartially {
+                        percentage_watched: 50,
+                    },
+                )
+            })
+            .last()
+            .unwrap();
+        assert!(is_full);
+        assert_eq!(
+            post_view_buffer.get_pending_view_count(0),
+            3 + (MAX_BUFFERED_POST_VIEWS as u64 - 2)
+        );
+
+        let buffered_views = post_view_buffer.take();
+        assert_eq!(buffered_views.len(), 2);
+        assert_eq!(
+            buffered_views[&0].view_details.len(),
+            MAX_BUFFERED_POST_VIEWS - 1
+        );
+        assert!(post_view_buffer.is_empty());
+        assert_eq!(post_view_buffer.get_pending_view_count(0), 0);
+        assert!(!post_view_buffer.push(
+            0,
+            &viewers[0],
+            PostViewDetailsFromFrontend::WatchedPartially {
+                percentage_watched: 50,
+            },
+        ));
+    }
+}
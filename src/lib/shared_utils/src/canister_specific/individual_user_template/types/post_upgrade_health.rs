@@ -0,0 +1,66 @@
+use candid::{CandidType, Deserialize, Principal};
+use serde::Serialize;
+
+/// What an individual user canister reports about itself right after being upgraded
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug, PartialEq, Eq)]
+pub struct PostUpgradeHealthReport {
+    pub version_number: u64,
+    pub profile_owner: Option<Principal>,
+    // * Ids of the migrations this version ran that hit errors
+    pub migrations_with_errors: Vec<String>,
+}
+
+impl PostUpgradeHealthReport {
+    /// Checks the canister came back on the version it was upgraded to, still knows who owns
+    /// it, and migrated its data cleanly
+    pub fn check(&self, expected_version_number: u64) -> Result<(), String> {
+        if self.version_number != expected_version_number {
+            return Err(format!(
+                "Canister is on version {} instead of {}",
+                self.version_number, expected_version_number
+            ));
+        }
+
+        if self.profile_owner.is_none() {
+            return Err("Canister lost its profile owner".to_string());
+        }
+
+        if !self.migrations_with_errors.is_empty() {
+            return Err(format!(
+                "Migrations hit errors: {}",
+                self.migrations_with_errors.join(", ")
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_check() {
+        let healthy_report = PostUpgradeHealthReport {
+            version_number: 3,
+            profile_owner: Some(Principal::from_slice(&[1])),
+            migrations_with_errors: vec![],
+        };
+        assert_eq!(healthy_report.check(3), Ok(()));
+        assert!(healthy_report.check(4).is_err());
+
+        assert!(PostUpgradeHealthReport {
+            profile_owner: None,
+            ..healthy_report.clone()
+        }
+        .check(3)
+        .is_err());
+        assert!(PostUpgradeHealthReport {
+            migrations_with_errors: vec!["move_posts_to_stable_post_map".into()],
+            ..healthy_report
+        }
+        .check(3)
+        .is_err());
+    }
+}
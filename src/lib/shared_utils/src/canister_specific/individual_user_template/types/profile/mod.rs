@@ -1,6 +1,8 @@
 use candid::{CandidType, Deserialize, Principal};
 use serde::Serialize;
 
+use super::achievement::Badge;
+
 #[derive(Default, Clone, CandidType, Deserialize, Debug, Serialize)]
 pub struct UserProfile {
     pub display_name: Option<String>,
@@ -20,6 +22,7 @@ pub struct UserProfileDetailsForFrontend {
     pub profile_stats: UserProfileGlobalStats,
     pub lifetime_earnings: u64,
     pub unique_user_name: Option<String>,
+    pub badges: Vec<Badge>,
 }
 
 #[derive(CandidType, Deserialize, Clone, Copy, Debug, Default, Serialize)]
@@ -0,0 +1,172 @@
+use std::borrow::Cow;
+
+use candid::Principal;
+use ic_stable_structures::{BoundedStorable, Memory, StableBTreeMap, Storable};
+
+use crate::common::types::app_primitive_type::PostId;
+
+use super::hot_or_not::{RoomId, SlotId};
+
+// * Principals are at most 29 bytes long. Each one is stored behind a byte holding its length,
+// * which is zero for a missing one.
+const MAX_PRINCIPAL_LENGTH: usize = 29;
+const ENCODED_PRINCIPAL_LENGTH: usize = MAX_PRINCIPAL_LENGTH + 1;
+const ENCODED_KEY_LENGTH: usize =
+    1 + ENCODED_PRINCIPAL_LENGTH + 8 + 1 + 8 + ENCODED_PRINCIPAL_LENGTH;
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub enum ReceivedSettlementType {
+    ReferrerShare,
+    HouseRake,
+    CollaboratorCommission,
+}
+
+/// A settlement another canister delivered for a room of a post. Referrer shares are
+/// delivered once per referee betting in the room, so they also carry the referee.
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub struct ReceivedSettlementKey {
+    pub settlement_type: ReceivedSettlementType,
+    pub post_canister_id: Principal,
+    pub post_id: PostId,
+    pub slot_id: SlotId,
+    pub room_id: RoomId,
+    pub referee_principal_id: Option<Principal>,
+}
+
+fn encode_principal(principal: Option<&Principal>, bytes: &mut [u8]) {
+    if let Some(principal) = principal {
+        let principal_bytes = principal.as_slice();
+        bytes[0] = principal_bytes.len() as u8;
+        bytes[1..=principal_bytes.len()].copy_from_slice(principal_bytes);
+    }
+}
+
+fn decode_principal(bytes: &[u8]) -> Option<Principal> {
+    match bytes[0] {
+        0 => None,
+        length => Some(Principal::from_slice(&bytes[1..=length as usize])),
+    }
+}
+
+impl Storable for ReceivedSettlementKey {
+    fn to_bytes(&self) -> Cow<'_, [u8]> {
+        let mut bytes = vec![0; ENCODED_KEY_LENGTH];
+        bytes[0] = self.settlement_type as u8;
+        encode_principal(Some(&self.post_canister_id), &mut bytes[1..]);
+        let mut offset = 1 + ENCODED_PRINCIPAL_LENGTH;
+        bytes[offset..offset + 8].copy_from_slice(&self.post_id.to_be_bytes());
+        offset += 8;
+        bytes[offset] = self.slot_id;
+        offset += 1;
+        bytes[offset..offset + 8].copy_from_slice(&self.room_id.to_be_bytes());
+        offset += 8;
+        encode_principal(self.referee_principal_id.as_ref(), &mut bytes[offset..]);
+
+        Cow::Owned(bytes)
+    }
+
+    fn from_bytes(bytes: Cow<[u8]>) -> Self {
+        let settlement_type = match bytes[0] {
+            0 => ReceivedSettlementType::ReferrerShare,
+            1 => ReceivedSettlementType::HouseRake,
+            _ => ReceivedSettlementType::CollaboratorCommission,
+        };
+        let mut offset = 1 + ENCODED_PRINCIPAL_LENGTH;
+        let post_id = u64::from_be_bytes(bytes[offset..offset + 8].try_into().unwrap());
+        offset += 8;
+        let slot_id = bytes[offset];
+        offset += 1;
+        let room_id = u64::from_be_bytes(bytes[offset..offset + 8].try_into().unwrap());
+        offset += 8;
+
+        Self {
+            settlement_type,
+            post_canister_id: decode_principal(&bytes[1..]).unwrap(),
+            post_id,
+            slot_id,
+            room_id,
+            referee_principal_id: decode_principal(&bytes[offset..]),
+        }
+    }
+}
+
+impl BoundedStorable for ReceivedSettlementKey {
+    const MAX_SIZE: u32 = ENCODED_KEY_LENGTH as u32;
+    const IS_FIXED_SIZE: bool = true;
+}
+
+/// Settlements delivered by other canisters that were already credited, kept in their own
+/// stable memory region so that they neither weigh on the heap nor have to be serialized on
+/// upgrades. Senders retry a delivery whose reply got lost for as long as it takes, so
+/// entries are never pruned.
+pub struct ReceivedSettlements<M: Memory> {
+    settlements: StableBTreeMap<ReceivedSettlementKey, (), M>,
+}
+
+impl<M: Memory> ReceivedSettlements<M> {
+    /// Loads the settlements persisted in `memory`, or creates an empty set if there are none.
+    pub fn init(memory: M) -> Self {
+        Self {
+            settlements: StableBTreeMap::init(memory),
+        }
+    }
+
+    /// Returns false if the settlement was already received
+    pub fn insert(&mut self, received_settlement_key: ReceivedSettlementKey) -> bool {
+        self.settlements
+            .insert(received_settlement_key, ())
+            .is_none()
+    }
+
+    pub fn contains(&self, received_settlement_key: &ReceivedSettlementKey) -> bool {
+        self.settlements.contains_key(received_settlement_key)
+    }
+
+    pub fn len(&self) -> u64 {
+        self.settlements.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.settlements.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ic_stable_structures::VectorMemory;
+
+    use super::*;
+
+    #[test]
+    fn test_received_settlements() {
+        let mut received_settlements = ReceivedSettlements::init(VectorMemory::default());
+        let house_rake_key = ReceivedSettlementKey {
+            settlement_type: ReceivedSettlementType::HouseRake,
+            post_canister_id: Principal::from_slice(&[1; 10]),
+            post_id: 7,
+            slot_id: 3,
+            room_id: 2,
+            referee_principal_id: None,
+        };
+        let referrer_share_key = ReceivedSettlementKey {
+            settlement_type: ReceivedSettlementType::ReferrerShare,
+            referee_principal_id: Some(Principal::from_slice(&[2; 29])),
+            ..house_rake_key.clone()
+        };
+
+        assert_eq!(
+            ReceivedSettlementKey::from_bytes(referrer_share_key.to_bytes()),
+            referrer_share_key
+        );
+        assert_eq!(
+            ReceivedSettlementKey::from_bytes(house_rake_key.to_bytes()),
+            house_rake_key
+        );
+
+        assert!(received_settlements.insert(house_rake_key.clone()));
+        assert!(!received_settlements.insert(house_rake_key.clone()));
+        assert!(!received_settlements.contains(&referrer_share_key));
+        assert!(received_settlements.insert(referrer_share_key));
+        assert_eq!(received_settlements.len(), 2);
+    }
+}
@@ -0,0 +1,141 @@
+use std::{borrow::Cow, time::SystemTime};
+
+use candid::{CandidType, Decode, Deserialize, Encode, Principal};
+use ic_stable_structures::{BoundedStorable, Storable};
+use serde::Serialize;
+
+use crate::common::types::{
+    app_primitive_type::PostId,
+    utility_token::token_event::{
+        HotOrNotOutcomePayoutEvent, MintEvent, ReferralCommissionEvent, TokenEvent,
+    },
+};
+
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug, PartialEq, Eq)]
+pub struct ReferralDetails {
+    pub referrer_principal_id: Principal,
+    pub referrer_canister_id: Principal,
+    pub referee_principal_id: Principal,
+    pub referee_canister_id: Principal,
+    // * The named code the referee signed up with, if any
+    #[serde(default)]
+    pub referral_code: Option<String>,
+}
+
+pub const MAX_REFERRAL_CODES_PER_PROFILE: usize = 20;
+pub const MAX_REFERRAL_CODE_NAME_LENGTH: usize = 50;
+
+/// A named referral code handed out by this profile, e.g. one per campaign, along with
+/// the signups and rewards it has brought in
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug, PartialEq, Eq)]
+pub struct ReferralCodeDetails {
+    pub code: String,
+    pub name: String,
+    pub created_at: SystemTime,
+    pub referee_count: u64,
+    pub signup_rewards_earned: u64,
+    pub referrer_share_of_winnings_earned: u64,
+}
+
+/// Everything this profile has earned through referrals, whether or not the referees signed
+/// up with a named code
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug, PartialEq, Eq)]
+pub struct ReferralEarnings {
+    // * The profile that referred this one, if any
+    pub referred_by: Option<Principal>,
+    pub referee_count: u64,
+    pub signup_rewards_earned: u64,
+    pub referrer_share_of_winnings_earned: u64,
+}
+
+/// Running totals of what this profile has earned by referring others, kept up to date as
+/// token events are recorded so they don't have to be summed from the transaction history
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct ReferralEarningsTotals {
+    pub signup_rewards_earned: u64,
+    pub referrer_share_of_winnings_earned: u64,
+}
+
+impl ReferralEarningsTotals {
+    pub fn record_token_event(
+        &mut self,
+        token_event: &TokenEvent,
+        my_principal_id: Option<&Principal>,
+    ) {
+        match token_event {
+            // * The referee is rewarded with the same event, only count the referrer's side
+            TokenEvent::Mint {
+                amount,
+                details:
+                    MintEvent::Referral {
+                        referrer_user_principal_id,
+                        ..
+                    },
+                ..
+            } if Some(referrer_user_principal_id) == my_principal_id => {
+                self.signup_rewards_earned += amount;
+            }
+            TokenEvent::ReferralCommission {
+                amount,
+                details: ReferralCommissionEvent::ShareOfRefereeBetWinnings { .. },
+                ..
+            }
+            | TokenEvent::HotOrNotOutcomePayout {
+                amount,
+                details: HotOrNotOutcomePayoutEvent::ReferrerShareOfRefereeBetWinnings { .. },
+                ..
+            } => {
+                self.referrer_share_of_winnings_earned += amount;
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Codes are matched regardless of case and surrounding whitespace
+pub fn normalize_referral_code(referral_code: &str) -> String {
+    referral_code.trim().to_uppercase()
+}
+
+/// A referrer's cut of one of their referee's bet winnings that is yet to be
+/// delivered to the referrer's canister
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug, PartialEq, Eq)]
+pub struct ReferrerShareSettlement {
+    pub referrer_canister_id: Principal,
+    pub referee_principal_id: Principal,
+    // * None for shares queued by the referee's own canister, before shares were worked out
+    // * when tabulating the slot
+    #[serde(default)]
+    pub referee_canister_id: Option<Principal>,
+    pub post_canister_id: Principal,
+    pub post_id: PostId,
+    pub slot_id: u8,
+    pub room_id: u64,
+    pub share_amount: u64,
+    pub number_of_failed_attempts: u32,
+}
+
+impl Storable for ReferrerShareSettlement {
+    fn to_bytes(&self) -> Cow<'_, [u8]> {
+        Cow::Owned(Encode!(self).unwrap())
+    }
+
+    fn from_bytes(bytes: Cow<[u8]>) -> Self {
+        Decode!(&bytes, Self).unwrap()
+    }
+}
+
+impl BoundedStorable for ReferrerShareSettlement {
+    const MAX_SIZE: u32 = 1_000;
+    const IS_FIXED_SIZE: bool = false;
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_normalize_referral_code() {
+        assert_eq!(normalize_referral_code(" ab3k9q2z \n"), "AB3K9Q2Z");
+    }
+}
@@ -0,0 +1,215 @@
+use std::{collections::BTreeMap, time::SystemTime};
+
+use candid::{CandidType, Deserialize, Principal};
+use ic_cdk::api::management_canister::provisional::CanisterId;
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+
+use crate::common::types::{app_primitive_type::PostId, utility_token::token_event::TokenEvent};
+
+use super::{hot_or_not::PlacedBetDetail, post::Post};
+
+// * Keep a delta well inside the message size limit. Whatever doesn't fit goes out with the
+// * next one.
+pub const MAX_POSTS_PER_REPLICATION_DELTA: usize = 20;
+pub const MAX_TOKEN_EVENTS_PER_REPLICATION_DELTA: usize = 500;
+pub const MAX_BETS_PER_REPLICATION_DELTA: usize = 500;
+// * Posts are checked for changes a stretch at a time, picking up where the last delta left off
+pub const MAX_POSTS_SCANNED_PER_REPLICATION_DELTA: usize = 200;
+
+pub type Fingerprint = [u8; 32];
+
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug, Default, PartialEq, Eq)]
+pub enum ReplicaRole {
+    #[default]
+    Primary,
+    // * Read only, kept up to date by its primary
+    Standby {
+        primary_canister_id: Principal,
+    },
+    // * A primary that a standby was promoted in place of. Stays read only so the two can't
+    // * both take writes.
+    Fenced,
+}
+
+/// Sent by the user index, along with the epoch of the primary and standby pair
+#[derive(CandidType, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub enum ReplicationUpdate {
+    ReplicateTo { standby_canister_id: Principal },
+    StopReplicating,
+    BecomeStandbyOf { primary_canister_id: Principal },
+    PromoteToPrimary,
+    Fence,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct TokenBalanceDelta {
+    pub utility_token_balance: u64,
+    pub lifetime_earnings: u64,
+    pub new_events: Vec<(u64, TokenEvent)>,
+}
+
+/// What changed on a primary since the last delta its standby acknowledged. Applying a delta
+/// twice leaves the standby as applying it once did.
+#[derive(CandidType, Deserialize, Clone)]
+pub struct ReplicationDelta {
+    pub epoch: u64,
+    pub sequence_number: u64,
+    pub posts: Vec<Post>,
+    pub token_balance: Option<TokenBalanceDelta>,
+    pub bets_placed: Vec<PlacedBetDetail>,
+}
+
+/// The fingerprints of what went out with a delta, recorded as synced once it is acknowledged
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SentReplicationDelta {
+    pub sequence_number: u64,
+    pub post_fingerprints: Vec<(PostId, Fingerprint)>,
+    pub bet_fingerprints: Vec<((CanisterId, PostId), Fingerprint)>,
+    pub token_balance: Option<(u64, u64)>,
+    pub last_token_event_index: Option<u64>,
+    pub next_post_scan_cursor: PostId,
+    // * Some changes didn't fit, so the next delta should follow right away
+    pub has_more_to_send: bool,
+}
+
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug, PartialEq, Eq)]
+pub struct ReplicationStatus {
+    pub role: ReplicaRole,
+    pub epoch: u64,
+    pub standby_canister_id: Option<Principal>,
+    pub last_sequence_number: u64,
+    pub last_replicated_at: Option<SystemTime>,
+}
+
+#[derive(Default, Deserialize, Serialize)]
+pub struct Replication {
+    pub role: ReplicaRole,
+    // * Bumped by the user index on every promotion, so a primary that was replaced can tell
+    pub epoch: u64,
+    pub standby_canister_id: Option<Principal>,
+    // * Last delta acknowledged by the standby on a primary, last one applied on a standby
+    pub last_sequence_number: u64,
+    pub last_replicated_at: Option<SystemTime>,
+    // * Timers don't survive upgrades, so neither does a delta that was out at the time
+    #[serde(skip)]
+    pub is_delta_in_flight: bool,
+    synced_post_fingerprints: BTreeMap<PostId, Fingerprint>,
+    synced_bet_fingerprints: BTreeMap<(CanisterId, PostId), Fingerprint>,
+    synced_token_balance: Option<(u64, u64)>,
+    last_synced_token_event_index: Option<u64>,
+    post_scan_cursor: PostId,
+}
+
+impl Replication {
+    pub fn get_status(&self) -> ReplicationStatus {
+        ReplicationStatus {
+            role: self.role.clone(),
+            epoch: self.epoch,
+            standby_canister_id: self.standby_canister_id,
+            last_sequence_number: self.last_sequence_number,
+            last_replicated_at: self.last_replicated_at,
+        }
+    }
+
+    /// Forgets what was synced, so that a new standby gets sent everything
+    pub fn reset_synced_state(&mut self) {
+        self.last_sequence_number = 0;
+        self.last_replicated_at = None;
+        self.synced_post_fingerprints.clear();
+        self.synced_bet_fingerprints.clear();
+        self.synced_token_balance = None;
+        self.last_synced_token_event_index = None;
+        self.post_scan_cursor = 0;
+    }
+
+    pub fn is_post_synced(&self, post_id: &PostId, fingerprint: &Fingerprint) -> bool {
+        self.synced_post_fingerprints.get(post_id) == Some(fingerprint)
+    }
+
+    pub fn is_bet_synced(&self, key: &(CanisterId, PostId), fingerprint: &Fingerprint) -> bool {
+        self.synced_bet_fingerprints.get(key) == Some(fingerprint)
+    }
+
+    pub fn is_token_balance_synced(&self, token_balance: (u64, u64)) -> bool {
+        // * A new standby starts out with an empty balance
+        self.synced_token_balance.unwrap_or_default() == token_balance
+    }
+
+    pub fn get_last_synced_token_event_index(&self) -> Option<u64> {
+        self.last_synced_token_event_index
+    }
+
+    pub fn get_post_scan_cursor(&self) -> PostId {
+        self.post_scan_cursor
+    }
+
+    pub fn acknowledge(&mut self, sent: SentReplicationDelta, current_time: SystemTime) {
+        self.last_sequence_number = sent.sequence_number;
+        self.last_replicated_at = Some(current_time);
+        self.synced_post_fingerprints.extend(sent.post_fingerprints);
+        self.synced_bet_fingerprints.extend(sent.bet_fingerprints);
+        if sent.token_balance.is_some() {
+            self.synced_token_balance = sent.token_balance;
+        }
+        if sent.last_token_event_index.is_some() {
+            self.last_synced_token_event_index = sent.last_token_event_index;
+        }
+        self.post_scan_cursor = sent.next_post_scan_cursor;
+    }
+}
+
+pub fn get_fingerprint<T: CandidType>(value: &T) -> Fingerprint {
+    Sha256::digest(candid::encode_one(value).unwrap()).into()
+}
+
+#[cfg(test)]
+mod test {
+    use test_utils::setup::test_constants::get_mock_user_alice_canister_id;
+
+    use super::*;
+
+    #[test]
+    fn test_acknowledged_deltas_are_synced() {
+        let mut replication = Replication::default();
+        let fingerprint = get_fingerprint(&"doggos".to_string());
+        let bet_key = (get_mock_user_alice_canister_id(), 3);
+
+        assert!(!replication.is_post_synced(&0, &fingerprint));
+        assert!(!replication.is_token_balance_synced((100, 0)));
+
+        replication.acknowledge(
+            SentReplicationDelta {
+                sequence_number: 1,
+                post_fingerprints: vec![(0, fingerprint)],
+                bet_fingerprints: vec![(bet_key, fingerprint)],
+                token_balance: Some((100, 0)),
+                last_token_event_index: Some(4),
+                next_post_scan_cursor: 1,
+                has_more_to_send: false,
+            },
+            SystemTime::UNIX_EPOCH,
+        );
+        assert!(replication.is_post_synced(&0, &fingerprint));
+        assert!(!replication.is_post_synced(&0, &get_fingerprint(&"puppers".to_string())));
+        assert!(replication.is_bet_synced(&bet_key, &fingerprint));
+        assert!(replication.is_token_balance_synced((100, 0)));
+        assert_eq!(replication.get_last_synced_token_event_index(), Some(4));
+        assert_eq!(replication.get_post_scan_cursor(), 1);
+
+        // * a delta without token changes keeps what was synced before
+        replication.acknowledge(
+            SentReplicationDelta {
+                sequence_number: 2,
+                ..Default::default()
+            },
+            SystemTime::UNIX_EPOCH,
+        );
+        assert_eq!(replication.last_sequence_number, 2);
+        assert_eq!(replication.get_last_synced_token_event_index(), Some(4));
+
+        replication.reset_synced_state();
+        assert!(!replication.is_post_synced(&0, &fingerprint));
+        assert_eq!(replication.last_sequence_number, 0);
+    }
+}
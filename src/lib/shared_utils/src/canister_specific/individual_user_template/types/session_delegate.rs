@@ -0,0 +1,145 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use candid::{CandidType, Deserialize, Principal};
+use serde::Serialize;
+
+pub const MAX_SESSION_DELEGATES_PER_PROFILE: usize = 10;
+const SECONDS_IN_A_DAY: u64 = 24 * 60 * 60;
+
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug, PartialEq, Eq)]
+pub enum SessionDelegatePermission {
+    BetOnHotOrNotPosts { max_bet_amount_per_day: u64 },
+}
+
+#[derive(CandidType, Deserialize, Clone)]
+pub struct RegisterSessionDelegateArg {
+    pub delegate_principal_id: Principal,
+    pub permission: SessionDelegatePermission,
+    pub expires_at: SystemTime,
+}
+
+/// A principal, typically a session key held by the mobile app, that may act on behalf of
+/// the profile owner within the bounds of its permission
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug, PartialEq, Eq)]
+pub struct SessionDelegate {
+    pub permission: SessionDelegatePermission,
+    pub registered_at: SystemTime,
+    pub expires_at: SystemTime,
+    // * Days are counted from the unix epoch in UTC
+    pub day_of_last_bet: u64,
+    pub amount_bet_on_day_of_last_bet: u64,
+}
+
+impl SessionDelegate {
+    pub fn new(arg: &RegisterSessionDelegateArg, current_time: &SystemTime) -> Self {
+        Self {
+            permission: arg.permission.clone(),
+            registered_at: *current_time,
+            expires_at: arg.expires_at,
+            day_of_last_bet: 0,
+            amount_bet_on_day_of_last_bet: 0,
+        }
+    }
+
+    pub fn is_expired(&self, current_time: &SystemTime) -> bool {
+        *current_time >= self.expires_at
+    }
+
+    pub fn get_amount_left_to_bet_today(&self, current_time: &SystemTime) -> u64 {
+        let SessionDelegatePermission::BetOnHotOrNotPosts {
+            max_bet_amount_per_day,
+        } = self.permission;
+
+        if get_day_number(current_time) != self.day_of_last_bet {
+            return max_bet_amount_per_day;
+        }
+
+        max_bet_amount_per_day.saturating_sub(self.amount_bet_on_day_of_last_bet)
+    }
+
+    pub fn record_bet_placed(&mut self, bet_amount: u64, current_time: &SystemTime) {
+        let today = get_day_number(current_time);
+
+        if today != self.day_of_last_bet {
+            self.day_of_last_bet = today;
+            self.amount_bet_on_day_of_last_bet = 0;
+        }
+
+        self.amount_bet_on_day_of_last_bet += bet_amount;
+    }
+}
+
+fn get_day_number(time: &SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+        / SECONDS_IN_A_DAY
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use test_utils::setup::test_constants::get_mock_user_bob_principal_id;
+
+    use super::*;
+
+    #[test]
+    fn test_session_delegate_daily_bet_limit() {
+        let start_of_day = UNIX_EPOCH
+            .checked_add(Duration::from_secs(SECONDS_IN_A_DAY * 19_000))
+            .unwrap();
+        let mut session_delegate = SessionDelegate::new(
+            &RegisterSessionDelegateArg {
+                delegate_principal_id: get_mock_user_bob_principal_id(),
+                permission: SessionDelegatePermission::BetOnHotOrNotPosts {
+                    max_bet_amount_per_day: 150,
+                },
+                expires_at: start_of_day
+                    .checked_add(Duration::from_secs(SECONDS_IN_A_DAY * 7))
+                    .unwrap(),
+            },
+            &start_of_day,
+        );
+
+        assert!(!session_delegate.is_expired(&start_of_day));
+        assert_eq!(
+            session_delegate.get_amount_left_to_bet_today(&start_of_day),
+            150
+        );
+
+        session_delegate.record_bet_placed(100, &start_of_day);
+        session_delegate.record_bet_placed(
+            50,
+            &start_of_day.checked_add(Duration::from_secs(60)).unwrap(),
+        );
+
+        let end_of_day = start_of_day
+            .checked_add(Duration::from_secs(SECONDS_IN_A_DAY - 1))
+            .unwrap();
+        assert_eq!(
+            session_delegate.get_amount_left_to_bet_today(&end_of_day),
+            0
+        );
+
+        let next_day = start_of_day
+            .checked_add(Duration::from_secs(SECONDS_IN_A_DAY))
+            .unwrap();
+        assert_eq!(
+            session_delegate.get_amount_left_to_bet_today(&next_day),
+            150
+        );
+
+        session_delegate.record_bet_placed(10, &next_day);
+        assert_eq!(
+            session_delegate.get_amount_left_to_bet_today(&next_day),
+            140
+        );
+
+        assert!(session_delegate.is_expired(
+            &start_of_day
+                .checked_add(Duration::from_secs(SECONDS_IN_A_DAY * 7))
+                .unwrap()
+        ));
+    }
+}
@@ -0,0 +1,233 @@
+use std::time::SystemTime;
+
+use candid::{CandidType, Deserialize};
+use serde::Serialize;
+
+use super::feature_usage::{get_day_number, DayNumber};
+
+pub const MAXIMUM_SIGNUP_BONUS_VESTING_DAYS: u64 = 365;
+
+/// How the tokens a new user gets for signing up are handed out. Part of them unlocks right
+/// away, the rest in equal tranches, one for each day the user is active.
+#[derive(CandidType, Clone, Deserialize, Serialize, Debug, PartialEq, Eq)]
+pub struct SignupBonusVestingConfig {
+    pub total_amount: u64,
+    pub unlocked_at_signup_percentage: u64,
+    // * Number of active days it takes for the rest of the bonus to unlock
+    pub number_of_vesting_days: u64,
+}
+
+// * Hands the whole bonus out at signup, the way it was before vesting, until the admin sets
+// * a schedule
+impl Default for SignupBonusVestingConfig {
+    fn default() -> Self {
+        Self {
+            total_amount: 1000,
+            unlocked_at_signup_percentage: 100,
+            number_of_vesting_days: 0,
+        }
+    }
+}
+
+impl SignupBonusVestingConfig {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.unlocked_at_signup_percentage > 100 {
+            return Err("Share unlocked at signup cannot be above 100%".to_string());
+        }
+
+        if self.unlocked_at_signup_percentage < 100 && self.number_of_vesting_days == 0 {
+            return Err("The locked part of the bonus needs at least one day to vest".to_string());
+        }
+
+        if self.number_of_vesting_days > MAXIMUM_SIGNUP_BONUS_VESTING_DAYS {
+            return Err(format!(
+                "Vesting cannot take more than {} days",
+                MAXIMUM_SIGNUP_BONUS_VESTING_DAYS
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+/// A user's signup bonus, fixed from the config in place when they signed up
+#[derive(CandidType, Clone, Deserialize, Serialize, Debug, PartialEq, Eq)]
+pub struct SignupBonusVesting {
+    pub total_amount: u64,
+    pub unlocked_at_signup: u64,
+    pub number_of_vesting_days: u64,
+    pub signed_up_at: SystemTime,
+    pub number_of_days_vested: u64,
+    // * Only one tranche vests per day, however active the user is
+    pub last_vested_day: Option<DayNumber>,
+}
+
+impl SignupBonusVesting {
+    pub fn new(config: &SignupBonusVestingConfig, signed_up_at: &SystemTime) -> Self {
+        let unlocked_at_signup = (config.total_amount as u128
+            * config.unlocked_at_signup_percentage.min(100) as u128
+            / 100) as u64;
+        let number_of_vesting_days = if unlocked_at_signup == config.total_amount {
+            0
+        } else {
+            config.number_of_vesting_days.max(1)
+        };
+
+        Self {
+            total_amount: config.total_amount,
+            unlocked_at_signup,
+            number_of_vesting_days,
+            signed_up_at: *signed_up_at,
+            number_of_days_vested: 0,
+            last_vested_day: None,
+        }
+    }
+
+    fn get_amount_vested_after_days(&self, number_of_days: u64) -> u64 {
+        if self.number_of_vesting_days == 0 {
+            return 0;
+        }
+
+        // * Spread so that the tranches always add up to the locked amount exactly
+        ((self.total_amount - self.unlocked_at_signup) as u128 * number_of_days as u128
+            / self.number_of_vesting_days as u128) as u64
+    }
+
+    pub fn get_vested_amount(&self) -> u64 {
+        self.get_amount_vested_after_days(self.number_of_days_vested)
+    }
+
+    pub fn get_pending_amount(&self) -> u64 {
+        self.total_amount - self.unlocked_at_signup - self.get_vested_amount()
+    }
+
+    pub fn get_next_tranche_amount(&self) -> u64 {
+        if self.number_of_days_vested >= self.number_of_vesting_days {
+            return 0;
+        }
+
+        self.get_amount_vested_after_days(self.number_of_days_vested + 1) - self.get_vested_amount()
+    }
+
+    /// Vests the next tranche if the user hasn't been active yet today. Returns the amount
+    /// vested, None if nothing did.
+    pub fn vest_for_activity(&mut self, current_time: &SystemTime) -> Option<u64> {
+        let today = get_day_number(current_time);
+
+        if self.number_of_days_vested >= self.number_of_vesting_days
+            || self.last_vested_day == Some(today)
+        {
+            return None;
+        }
+
+        let tranche_amount = self.get_next_tranche_amount();
+        self.number_of_days_vested += 1;
+        self.last_vested_day = Some(today);
+
+        Some(tranche_amount)
+    }
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct SignupBonusVestingStatus {
+    pub total_amount: u64,
+    pub unlocked_at_signup: u64,
+    pub vested_amount: u64,
+    // * Still locked, waiting on future active days
+    pub pending_amount: u64,
+    pub next_tranche_amount: u64,
+    pub number_of_days_vested: u64,
+    pub number_of_vesting_days: u64,
+    pub signed_up_at: SystemTime,
+    // * Whether activity today would vest a tranche
+    pub can_vest_today: bool,
+}
+
+impl SignupBonusVestingStatus {
+    pub fn new(vesting: &SignupBonusVesting, current_time: &SystemTime) -> Self {
+        Self {
+            total_amount: vesting.total_amount,
+            unlocked_at_signup: vesting.unlocked_at_signup,
+            vested_amount: vesting.get_vested_amount(),
+            pending_amount: vesting.get_pending_amount(),
+            next_tranche_amount: vesting.get_next_tranche_amount(),
+            number_of_days_vested: vesting.number_of_days_vested,
+            number_of_vesting_days: vesting.number_of_vesting_days,
+            signed_up_at: vesting.signed_up_at,
+            can_vest_today: vesting.number_of_days_vested < vesting.number_of_vesting_days
+                && vesting.last_vested_day != Some(get_day_number(current_time)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use super::*;
+
+    const A_DAY: Duration = Duration::from_secs(24 * 60 * 60);
+
+    #[test]
+    fn test_vest_for_activity() {
+        let signed_up_at = SystemTime::UNIX_EPOCH;
+        let mut vesting = SignupBonusVesting::new(
+            &SignupBonusVestingConfig {
+                total_amount: 1000,
+                unlocked_at_signup_percentage: 10,
+                number_of_vesting_days: 7,
+            },
+            &signed_up_at,
+        );
+
+        assert_eq!(vesting.unlocked_at_signup, 100);
+        assert_eq!(vesting.get_pending_amount(), 900);
+
+        assert_eq!(vesting.vest_for_activity(&signed_up_at), Some(128));
+        // * a second action on the same day doesn't vest anything more
+        assert_eq!(vesting.vest_for_activity(&(signed_up_at + A_DAY / 2)), None);
+
+        let vested_amounts: Vec<u64> = (1..10)
+            .filter_map(|day| vesting.vest_for_activity(&(signed_up_at + A_DAY * day)))
+            .collect();
+
+        assert_eq!(vested_amounts.len(), 6);
+        assert_eq!(vested_amounts.iter().sum::<u64>() + 128, 900);
+        assert_eq!(vesting.get_pending_amount(), 0);
+        assert_eq!(vesting.get_next_tranche_amount(), 0);
+    }
+
+    #[test]
+    fn test_everything_unlocked_at_signup() {
+        let vesting = SignupBonusVesting::new(
+            &SignupBonusVestingConfig {
+                total_amount: 1000,
+                unlocked_at_signup_percentage: 100,
+                number_of_vesting_days: 0,
+            },
+            &SystemTime::UNIX_EPOCH,
+        );
+
+        assert_eq!(vesting.unlocked_at_signup, 1000);
+        assert_eq!(vesting.get_pending_amount(), 0);
+        assert!(!SignupBonusVestingStatus::new(&vesting, &SystemTime::UNIX_EPOCH).can_vest_today);
+    }
+
+    #[test]
+    fn test_validate_signup_bonus_vesting_config() {
+        assert!(SignupBonusVestingConfig::default().validate().is_ok());
+        assert!(SignupBonusVestingConfig {
+            unlocked_at_signup_percentage: 101,
+            ..SignupBonusVestingConfig::default()
+        }
+        .validate()
+        .is_err());
+        assert!(SignupBonusVestingConfig {
+            total_amount: 1000,
+            unlocked_at_signup_percentage: 20,
+            number_of_vesting_days: 0,
+        }
+        .validate()
+        .is_err());
+    }
+}
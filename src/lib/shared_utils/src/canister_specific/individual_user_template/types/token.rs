@@ -1,16 +1,24 @@
-use std::collections::BTreeMap;
+use std::{borrow::Borrow, collections::BTreeMap, time::SystemTime};
 
 use candid::{CandidType, Deserialize};
 use serde::Serialize;
-
-use crate::common::types::utility_token::token_event::{
-    HotOrNotOutcomePayoutEvent, MintEvent, StakeEvent, TokenEvent,
-    HOT_OR_NOT_BET_CREATOR_COMMISSION_PERCENTAGE, HOT_OR_NOT_BET_WINNINGS_MULTIPLIER,
+use sha2::{Digest, Sha256};
+
+use crate::common::types::{
+    app_primitive_type::PostId,
+    utility_token::token_event::{
+        HotOrNotOutcomePayoutEvent, HouseRakeEvent, MintEvent, PostBoostEvent,
+        ReferralCommissionEvent, StakeEvent, TokenEvent,
+        HOT_OR_NOT_BET_CREATOR_COMMISSION_PERCENTAGE, HOT_OR_NOT_BET_WINNINGS_MULTIPLIER,
+    },
 };
 
 #[derive(Default, Clone, Deserialize, CandidType, Debug, Serialize)]
 pub struct TokenBalance {
     pub utility_token_balance: u64,
+    // * Individual user canisters keep their history in a `TokenTransactionHistory` in stable
+    // * memory. This only holds events of backups, of profiles saved before that, and of the
+    // * scratch balances outcomes are tabulated into
     pub utility_token_transaction_history: BTreeMap<u64, TokenEvent>,
     pub lifetime_earnings: u64,
 }
@@ -24,9 +32,31 @@ impl TokenBalance {
         &self.utility_token_transaction_history
     }
 
+    /// Applies the event to the balance and records it in the history kept alongside it
     pub fn handle_token_event(&mut self, token_event: TokenEvent) {
-        match &token_event {
-            TokenEvent::Mint { details, .. } => match details {
+        self.apply_token_event(&token_event);
+
+        let utility_token_transaction_history = &mut self.utility_token_transaction_history;
+
+        let last_key = *utility_token_transaction_history
+            .last_key_value()
+            .unwrap_or((&0, &TokenEvent::Burn))
+            .0;
+
+        if utility_token_transaction_history.len() > 1500 {
+            utility_token_transaction_history.retain(|key, _| *key > last_key - 1000)
+        }
+
+        self.utility_token_transaction_history
+            .insert(last_key + 1, token_event);
+    }
+
+    /// Applies the event to the balance, leaving recording it to the caller
+    pub fn apply_token_event(&mut self, token_event: &TokenEvent) {
+        match token_event {
+            TokenEvent::Mint {
+                amount, details, ..
+            } => match details {
                 MintEvent::NewUserSignup { .. } => {
                     self.utility_token_balance += token_event.get_token_amount_for_token_event();
                     self.lifetime_earnings += token_event.get_token_amount_for_token_event();
@@ -35,6 +65,23 @@ impl TokenBalance {
                     self.utility_token_balance += token_event.get_token_amount_for_token_event();
                     self.lifetime_earnings += token_event.get_token_amount_for_token_event();
                 }
+                MintEvent::TournamentPrize { .. } => {
+                    self.utility_token_balance += amount;
+                    self.lifetime_earnings += amount;
+                }
+                MintEvent::AchievementReward { .. } => {
+                    self.utility_token_balance += amount;
+                    self.lifetime_earnings += amount;
+                }
+                MintEvent::SignupBonusUnlockedAtSignup { .. }
+                | MintEvent::SignupBonusVested { .. } => {
+                    self.utility_token_balance += amount;
+                    self.lifetime_earnings += amount;
+                }
+                // * Test tokens aren't earnings
+                MintEvent::TokenFaucet => {
+                    self.utility_token_balance += amount;
+                }
             },
             TokenEvent::Burn => {}
             TokenEvent::Transfer => {}
@@ -42,16 +89,20 @@ impl TokenBalance {
                 StakeEvent::BetOnHotOrNotPost { bet_amount, .. } => {
                     self.utility_token_balance -= bet_amount;
                 }
+                StakeEvent::BetOnHotOrNotPostCancelled { refund_amount, .. } => {
+                    self.utility_token_balance += refund_amount;
+                }
+                StakeEvent::BetOnVersusContest { bet_amount, .. } => {
+                    self.utility_token_balance -= bet_amount;
+                }
             },
-            TokenEvent::HotOrNotOutcomePayout { details, .. } => match details {
-                HotOrNotOutcomePayoutEvent::CommissionFromHotOrNotBet {
-                    room_pot_total_amount,
-                    ..
-                } => {
-                    self.utility_token_balance +=
-                        room_pot_total_amount * HOT_OR_NOT_BET_CREATOR_COMMISSION_PERCENTAGE / 100;
-                    self.lifetime_earnings +=
-                        room_pot_total_amount * HOT_OR_NOT_BET_CREATOR_COMMISSION_PERCENTAGE / 100;
+            TokenEvent::HotOrNotOutcomePayout {
+                amount, details, ..
+            } => match details {
+                // * Less than 10% of the pot when the commission is split with collaborators
+                HotOrNotOutcomePayoutEvent::CommissionFromHotOrNotBet { .. } => {
+                    self.utility_token_balance += amount;
+                    self.lifetime_earnings += amount;
                 }
                 HotOrNotOutcomePayoutEvent::WinningsEarnedFromBet {
                     winnings_amount, ..
@@ -60,23 +111,114 @@ impl TokenBalance {
                     self.lifetime_earnings +=
                         get_earnings_amount_from_winnings_amount(winnings_amount);
                 }
+                HotOrNotOutcomePayoutEvent::ReferrerShareOfRefereeBetWinnings {
+                    share_amount,
+                    ..
+                } => {
+                    self.utility_token_balance += share_amount;
+                    self.lifetime_earnings += share_amount;
+                }
+                HotOrNotOutcomePayoutEvent::CollaboratorShareOfCommission {
+                    share_amount, ..
+                } => {
+                    self.utility_token_balance += share_amount;
+                    self.lifetime_earnings += share_amount;
+                }
+                HotOrNotOutcomePayoutEvent::PotInsuranceTopUp { top_up_amount, .. } => {
+                    self.utility_token_balance += top_up_amount;
+                    self.lifetime_earnings += top_up_amount;
+                }
+                // * Only what comes on top of the stake is earned
+                HotOrNotOutcomePayoutEvent::VersusContestPayout {
+                    amount_bet,
+                    payout_amount,
+                    ..
+                } => {
+                    self.utility_token_balance += payout_amount;
+                    self.lifetime_earnings += payout_amount.saturating_sub(*amount_bet);
+                }
+            },
+            // * Only ever recorded on the treasury canister. Not counted as earnings
+            TokenEvent::HouseRake {
+                amount, details, ..
+            } => match details {
+                HouseRakeEvent::HouseRakeFromHotOrNotBet { .. } => {
+                    self.utility_token_balance += amount;
+                }
+                HouseRakeEvent::PotInsuranceTopUpsPaidOut { .. } => {
+                    self.utility_token_balance -= amount;
+                }
+            },
+            TokenEvent::PostBoost {
+                amount, details, ..
+            } => match details {
+                PostBoostEvent::BoostedOwnPost { .. } => {
+                    self.utility_token_balance -= amount;
+                }
+            },
+            TokenEvent::ReferralCommission {
+                amount, details, ..
+            } => match details {
+                ReferralCommissionEvent::ShareOfRefereeBetWinnings { .. } => {
+                    self.utility_token_balance += amount;
+                    self.lifetime_earnings += amount;
+                }
             },
         }
+    }
+}
 
-        let utility_token_transaction_history = &mut self.utility_token_transaction_history;
+#[derive(CandidType, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct CreatorCommissionEarnedInRoom {
+    pub post_id: PostId,
+    pub slot_id: u8,
+    pub room_id: u64,
+    pub room_pot_total_amount: u64,
+    pub commission_amount: u64,
+    pub last_paid_at: SystemTime,
+}
 
-        let last_key = *utility_token_transaction_history
-            .last_key_value()
-            .unwrap_or((&0, &TokenEvent::Burn))
-            .0;
+#[derive(CandidType, Deserialize, Debug, PartialEq, Eq)]
+pub struct CreatorCommissionReport {
+    pub rooms: Vec<CreatorCommissionEarnedInRoom>,
+    /// Across every room in the date range, not just this page
+    pub total_number_of_rooms: u64,
+    pub total_commission_amount: u64,
+}
 
-        if utility_token_transaction_history.len() > 1500 {
-            utility_token_transaction_history.retain(|key, _| *key > last_key - 1000)
-        }
+#[derive(CandidType, Deserialize, Debug, PartialEq, Eq)]
+pub struct TokenTransactionsExportChunk {
+    pub events: Vec<(u64, TokenEvent)>,
+    pub next_from_inclusive_index: Option<u64>,
+}
 
-        self.utility_token_transaction_history
-            .insert(last_key + 1, token_event);
-    }
+#[derive(CandidType, Deserialize, Debug, PartialEq, Eq)]
+pub struct TokenTransactionsExportManifest {
+    pub total_number_of_events: u64,
+    pub first_event_index: Option<u64>,
+    pub last_event_index: Option<u64>,
+    pub utility_token_balance: u64,
+    pub lifetime_earnings: u64,
+    /// Hex encoded SHA-256 over the candid encoding of every `(index, event)` pair in
+    /// ascending index order. Recompute it over the concatenated chunks with
+    /// [`compute_token_transactions_checksum`] to verify the extraction is complete.
+    pub checksum: String,
+    pub generated_at: SystemTime,
+}
+
+pub fn compute_token_transactions_checksum(
+    events: impl Iterator<Item = (impl Borrow<u64>, impl Borrow<TokenEvent>)>,
+) -> String {
+    let mut hasher = Sha256::new();
+
+    events.for_each(|(index, token_event)| {
+        hasher.update(
+            candid::encode_one((index.borrow(), token_event.borrow()))
+                .expect("Token events are always candid encodable"),
+        );
+    });
+
+    hex::encode(hasher.finalize())
 }
 
 fn get_earnings_amount_from_winnings_amount(winnings_amount: &u64) -> u64 {
@@ -207,9 +349,90 @@ mod test {
             });
 
             assert_eq!(token_balance.utility_token_balance, 1400);
+
+            token_balance.handle_token_event(TokenEvent::Mint {
+                amount: 250,
+                details: MintEvent::TournamentPrize {
+                    tournament_id: 1,
+                    rank: 2,
+                },
+                timestamp: SystemTime::now(),
+            });
+
+            assert_eq!(token_balance.utility_token_balance, 1650);
+            assert_eq!(token_balance.lifetime_earnings, 1750);
+
+            token_balance.handle_token_event(TokenEvent::HotOrNotOutcomePayout {
+                amount: 18,
+                details: HotOrNotOutcomePayoutEvent::ReferrerShareOfRefereeBetWinnings {
+                    referee_user_principal_id: get_mock_user_bob_principal_id(),
+                    post_canister_id: get_mock_user_alice_canister_id(),
+                    post_id: 1,
+                    slot_id: 1,
+                    room_id: 1,
+                    share_amount: 18,
+                },
+                timestamp: SystemTime::now(),
+            });
+
+            assert_eq!(token_balance.utility_token_balance, 1668);
+            assert_eq!(token_balance.lifetime_earnings, 1768);
+
+            token_balance.handle_token_event(TokenEvent::HouseRake {
+                amount: 3,
+                details: HouseRakeEvent::HouseRakeFromHotOrNotBet {
+                    post_canister_id: get_mock_user_alice_canister_id(),
+                    post_id: 1,
+                    slot_id: 1,
+                    room_id: 1,
+                    room_pot_total_amount: 300,
+                },
+                timestamp: SystemTime::now(),
+            });
+
+            assert_eq!(token_balance.utility_token_balance, 1671);
+            assert_eq!(token_balance.lifetime_earnings, 1768);
+
+            token_balance.handle_token_event(TokenEvent::PostBoost {
+                amount: 200,
+                details: PostBoostEvent::BoostedOwnPost {
+                    post_id: 1,
+                    boost_percentage: 20,
+                    boosted_until: SystemTime::now(),
+                },
+                timestamp: SystemTime::now(),
+            });
+
+            assert_eq!(token_balance.utility_token_balance, 1471);
+            assert_eq!(token_balance.lifetime_earnings, 1768);
         }
     }
 
+    #[test]
+    fn test_compute_token_transactions_checksum() {
+        let mut token_balance = TokenBalance::default();
+        (0..10).for_each(|_| token_balance.handle_token_event(TokenEvent::Burn));
+
+        let full_checksum = compute_token_transactions_checksum(
+            token_balance.utility_token_transaction_history.iter(),
+        );
+        let checksum_from_chunks = compute_token_transactions_checksum(
+            token_balance
+                .utility_token_transaction_history
+                .range(..5)
+                .chain(token_balance.utility_token_transaction_history.range(5..)),
+        );
+
+        assert_eq!(full_checksum, checksum_from_chunks);
+        assert_eq!(full_checksum.len(), 64);
+        assert_ne!(
+            full_checksum,
+            compute_token_transactions_checksum(
+                token_balance.utility_token_transaction_history.range(2..)
+            )
+        );
+    }
+
     mod test_get_earnings_amount_from_winnings_amount {
         use super::*;
 
@@ -0,0 +1,121 @@
+use std::time::{Duration, SystemTime};
+
+use candid::{CandidType, Deserialize};
+use serde::Serialize;
+
+// * Even when nothing changed locally, the ledger is checked this often to catch drift
+pub const TOKEN_LEDGER_FULL_RECONCILIATION_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+/// How this canister's utility token balance is mirrored to the ICRC-1 token ledger.
+///
+/// `TokenBalance` stays the source of truth and token events keep being applied to it
+/// synchronously. The ledger account of this canister is then brought in line with it on a
+/// timer, by minting or burning the difference. The ledger balance is read before every
+/// adjustment, so adjustments that failed or were cut short by a ledger outage are picked up
+/// by the next sync instead of being applied twice.
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct TokenLedgerMirror {
+    // * What the ledger held for this canister after the last successful sync
+    pub mirrored_balance: Option<u64>,
+    pub last_synced_at: Option<SystemTime>,
+    pub number_of_failed_syncs: u32,
+    pub last_error: Option<String>,
+}
+
+impl TokenLedgerMirror {
+    pub fn is_sync_due(&self, utility_token_balance: u64, current_time: &SystemTime) -> bool {
+        if self.mirrored_balance != Some(utility_token_balance) {
+            return true;
+        }
+
+        self.last_synced_at.is_none_or(|last_synced_at| {
+            current_time
+                .duration_since(last_synced_at)
+                .unwrap_or_default()
+                >= TOKEN_LEDGER_FULL_RECONCILIATION_INTERVAL
+        })
+    }
+
+    pub fn record_sync(&mut self, ledger_balance: u64, current_time: &SystemTime) {
+        self.mirrored_balance = Some(ledger_balance);
+        self.last_synced_at = Some(*current_time);
+        self.number_of_failed_syncs = 0;
+        self.last_error = None;
+    }
+
+    pub fn record_failure(&mut self, error: String) {
+        self.number_of_failed_syncs += 1;
+        self.last_error = Some(error);
+    }
+}
+
+/// What the ledger needs to match the local balance
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TokenLedgerAdjustment {
+    None,
+    Mint(u64),
+    Burn(u64),
+}
+
+pub fn get_token_ledger_adjustment(
+    utility_token_balance: u64,
+    ledger_balance: u64,
+) -> TokenLedgerAdjustment {
+    match utility_token_balance.cmp(&ledger_balance) {
+        std::cmp::Ordering::Equal => TokenLedgerAdjustment::None,
+        std::cmp::Ordering::Greater => {
+            TokenLedgerAdjustment::Mint(utility_token_balance - ledger_balance)
+        }
+        std::cmp::Ordering::Less => {
+            TokenLedgerAdjustment::Burn(ledger_balance - utility_token_balance)
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_get_token_ledger_adjustment() {
+        assert_eq!(
+            get_token_ledger_adjustment(1_000, 1_000),
+            TokenLedgerAdjustment::None
+        );
+        assert_eq!(
+            get_token_ledger_adjustment(1_250, 1_000),
+            TokenLedgerAdjustment::Mint(250)
+        );
+        assert_eq!(
+            get_token_ledger_adjustment(900, 1_000),
+            TokenLedgerAdjustment::Burn(100)
+        );
+    }
+
+    #[test]
+    fn test_is_sync_due() {
+        let mut token_ledger_mirror = TokenLedgerMirror::default();
+        let current_time = SystemTime::UNIX_EPOCH;
+
+        // * never synced
+        assert!(token_ledger_mirror.is_sync_due(0, &current_time));
+
+        token_ledger_mirror.record_failure("TemporarilyUnavailable".to_string());
+        assert_eq!(token_ledger_mirror.number_of_failed_syncs, 1);
+        assert!(token_ledger_mirror.is_sync_due(0, &current_time));
+
+        token_ledger_mirror.record_sync(1_000, &current_time);
+        assert_eq!(token_ledger_mirror.number_of_failed_syncs, 0);
+        assert_eq!(token_ledger_mirror.last_error, None);
+        assert!(!token_ledger_mirror.is_sync_due(1_000, &current_time));
+
+        // * the local balance moved
+        assert!(token_ledger_mirror.is_sync_due(1_100, &current_time));
+
+        // * nothing moved, but a full reconciliation is due
+        assert!(token_ledger_mirror.is_sync_due(
+            1_000,
+            &(current_time + TOKEN_LEDGER_FULL_RECONCILIATION_INTERVAL)
+        ));
+    }
+}
@@ -0,0 +1,460 @@
+use std::{borrow::Cow, collections::BTreeMap, ops::RangeBounds, time::SystemTime};
+
+use candid::{Decode, Encode, Principal};
+use ic_stable_structures::{BoundedStorable, Memory, StableBTreeMap, Storable};
+
+use crate::{
+    common::types::{
+        app_primitive_type::PostId,
+        utility_token::token_event::{HotOrNotOutcomePayoutEvent, TokenEvent},
+    },
+    pagination::{Page, MAX_ITEMS_PER_PAGE},
+};
+
+use super::{
+    hot_or_not::{bet_trace::MAX_TRACED_TOKEN_EVENTS, RoomId, SlotId},
+    token::CreatorCommissionEarnedInRoom,
+};
+
+impl Storable for TokenEvent {
+    fn to_bytes(&self) -> Cow<'_, [u8]> {
+        Cow::Owned(Encode!(self).unwrap())
+    }
+
+    fn from_bytes(bytes: Cow<[u8]>) -> Self {
+        Decode!(&bytes, Self).unwrap()
+    }
+}
+
+impl BoundedStorable for TokenEvent {
+    // * Mostly the candid type table, which every variant carries in full
+    const MAX_SIZE: u32 = 2_048;
+    const IS_FIXED_SIZE: bool = false;
+}
+
+/// Every token event of a profile, keyed by transaction id, kept in its own stable memory
+/// region so that the history neither weighs on the heap nor has to be serialized on
+/// upgrades. Ids increase in the order events happened, starting from 1.
+pub struct TokenTransactionHistory<M: Memory> {
+    transactions: StableBTreeMap<u64, TokenEvent, M>,
+    last_transaction_id: Option<u64>,
+}
+
+impl<M: Memory> TokenTransactionHistory<M> {
+    /// Loads the history persisted in `memory`, or creates an empty one if there is none.
+    pub fn init(memory: M) -> Self {
+        let transactions: StableBTreeMap<u64, TokenEvent, M> = StableBTreeMap::init(memory);
+        let last_transaction_id = transactions.iter().last().map(|(id, _)| id);
+
+        Self {
+            transactions,
+            last_transaction_id,
+        }
+    }
+
+    pub fn len(&self) -> u64 {
+        self.transactions.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.transactions.is_empty()
+    }
+
+    pub fn first_transaction_id(&self) -> Option<u64> {
+        self.transactions.iter().next().map(|(id, _)| id)
+    }
+
+    pub fn last_transaction_id(&self) -> Option<u64> {
+        self.last_transaction_id
+    }
+
+    /// Records the event under the next transaction id and returns it
+    pub fn append(&mut self, token_event: TokenEvent) -> u64 {
+        let transaction_id = self.last_transaction_id.map_or(1, |id| id + 1);
+        self.insert(transaction_id, token_event);
+
+        transaction_id
+    }
+
+    /// Records the event under an id it was given elsewhere, e.g. in a backup or on the
+    /// replica it was copied from
+    pub fn insert(&mut self, transaction_id: u64, token_event: TokenEvent) {
+        self.transactions.insert(transaction_id, token_event);
+        self.last_transaction_id = self.last_transaction_id.max(Some(transaction_id));
+    }
+
+    pub fn get(&self, transaction_id: u64) -> Option<TokenEvent> {
+        self.transactions.get(&transaction_id)
+    }
+
+    /// Yields the events oldest first along with their transaction ids
+    pub fn iter(&self) -> impl Iterator<Item = (u64, TokenEvent)> + '_ {
+        self.transactions.iter()
+    }
+
+    pub fn range(
+        &self,
+        transaction_id_range: impl RangeBounds<u64>,
+    ) -> impl Iterator<Item = (u64, TokenEvent)> + '_ {
+        self.transactions.range(transaction_id_range)
+    }
+
+    /// Events newest first. The map can only be walked in ascending order, so the ids are
+    /// collected up front and the events loaded as they are yielded.
+    pub fn iter_rev(&self) -> impl Iterator<Item = (u64, TokenEvent)> + '_ {
+        let transaction_ids = self.iter().map(|(id, _)| id).collect::<Vec<_>>();
+
+        transaction_ids
+            .into_iter()
+            .rev()
+            .filter_map(|transaction_id| Some((transaction_id, self.get(transaction_id)?)))
+    }
+
+    /// Up to `limit` events from `from_inclusive_transaction_id` on, oldest first. The next
+    /// page starts from `next_from_inclusive_index`, which is a transaction id as well.
+    pub fn get_page(
+        &self,
+        from_inclusive_transaction_id: u64,
+        limit: u64,
+    ) -> Page<(u64, TokenEvent)> {
+        let limit = limit.clamp(1, MAX_ITEMS_PER_PAGE) as usize;
+        let mut transactions = self.range(from_inclusive_transaction_id..);
+
+        Page {
+            items: transactions.by_ref().take(limit).collect(),
+            total_count: self.len(),
+            next_from_inclusive_index: transactions.next().map(|(id, _)| id),
+        }
+    }
+
+    /// Commission paid out in `[from_inclusive_time, to_exclusive_time)`, one entry per room
+    /// ordered by post, slot and room. Shares returned by collaborators who couldn't be paid
+    /// are paid out as commission again, so they are added to the room's entry.
+    pub fn get_creator_commission_earned_per_room(
+        &self,
+        from_inclusive_time: &SystemTime,
+        to_exclusive_time: &SystemTime,
+    ) -> Vec<CreatorCommissionEarnedInRoom> {
+        let mut commission_earned_per_room: BTreeMap<
+            (PostId, u8, u64),
+            CreatorCommissionEarnedInRoom,
+        > = BTreeMap::new();
+
+        self.iter().for_each(|(_, token_event)| {
+            let TokenEvent::HotOrNotOutcomePayout {
+                amount,
+                details:
+                    HotOrNotOutcomePayoutEvent::CommissionFromHotOrNotBet {
+                        post_id,
+                        slot_id,
+                        room_id,
+                        room_pot_total_amount,
+                        ..
+                    },
+                timestamp,
+            } = token_event
+            else {
+                return;
+            };
+
+            if timestamp < *from_inclusive_time || timestamp >= *to_exclusive_time {
+                return;
+            }
+
+            let commission_earned_in_room = commission_earned_per_room
+                .entry((post_id, slot_id, room_id))
+                .or_insert(CreatorCommissionEarnedInRoom {
+                    post_id,
+                    slot_id,
+                    room_id,
+                    room_pot_total_amount,
+                    commission_amount: 0,
+                    last_paid_at: timestamp,
+                });
+            commission_earned_in_room.commission_amount += amount;
+            commission_earned_in_room.last_paid_at =
+                commission_earned_in_room.last_paid_at.max(timestamp);
+        });
+
+        commission_earned_per_room.into_values().collect()
+    }
+
+    /// Events about a hot or not post, in transaction id order, keeping the most recent
+    /// `MAX_TRACED_TOKEN_EVENTS`. Given the slot and room of a bet, events about the post's
+    /// other rooms are left out.
+    pub fn get_token_events_for_hot_or_not_bet(
+        &self,
+        post_canister_id: &Principal,
+        post_id: PostId,
+        slot_and_room: Option<(SlotId, RoomId)>,
+    ) -> Vec<(u64, TokenEvent)> {
+        let token_events: Vec<(u64, TokenEvent)> = self
+            .iter()
+            .filter(|(_, token_event)| {
+                token_event.get_hot_or_not_post() == Some((*post_canister_id, post_id))
+                    && match (slot_and_room, token_event.get_hot_or_not_slot_and_room()) {
+                        (Some(slot_and_room), Some(event_slot_and_room)) => {
+                            slot_and_room == event_slot_and_room
+                        }
+                        _ => true,
+                    }
+            })
+            .collect();
+        let number_of_older_token_events =
+            token_events.len().saturating_sub(MAX_TRACED_TOKEN_EVENTS);
+
+        token_events
+            .into_iter()
+            .skip(number_of_older_token_events)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ic_stable_structures::VectorMemory;
+
+    use super::*;
+
+    #[test]
+    fn test_token_transaction_history_survives_reload() {
+        let memory = VectorMemory::default();
+
+        let mut token_transaction_history = TokenTransactionHistory::init(memory.clone());
+        assert!(token_transaction_history.is_empty());
+        assert_eq!(token_transaction_history.append(TokenEvent::Burn), 1);
+        assert_eq!(token_transaction_history.append(TokenEvent::Transfer), 2);
+
+        let mut token_transaction_history = TokenTransactionHistory::init(memory);
+        assert_eq!(token_transaction_history.len(), 2);
+        assert_eq!(token_transaction_history.last_transaction_id(), Some(2));
+        assert_eq!(token_transaction_history.append(TokenEvent::Burn), 3);
+        assert_eq!(token_transaction_history.get(2), Some(TokenEvent::Transfer));
+    }
+
+    #[test]
+    fn test_insert_keeps_ids_given_elsewhere() {
+        let mut token_transaction_history = TokenTransactionHistory::init(VectorMemory::default());
+
+        token_transaction_history.insert(501, TokenEvent::Burn);
+        token_transaction_history.insert(500, TokenEvent::Transfer);
+
+        assert_eq!(token_transaction_history.first_transaction_id(), Some(500));
+        assert_eq!(token_transaction_history.last_transaction_id(), Some(501));
+        assert_eq!(token_transaction_history.append(TokenEvent::Burn), 502);
+        assert_eq!(
+            token_transaction_history
+                .iter_rev()
+                .map(|(id, _)| id)
+                .collect::<Vec<_>>(),
+            vec![502, 501, 500]
+        );
+    }
+
+    #[test]
+    fn test_get_page() {
+        let mut token_transaction_history = TokenTransactionHistory::init(VectorMemory::default());
+        (0..5).for_each(|_| {
+            token_transaction_history.append(TokenEvent::Burn);
+        });
+
+        let page = token_transaction_history.get_page(2, 2);
+        assert_eq!(
+            page.items.iter().map(|(id, _)| *id).collect::<Vec<_>>(),
+            vec![2, 3]
+        );
+        assert_eq!(page.total_count, 5);
+        assert_eq!(page.next_from_inclusive_index, Some(4));
+
+        let page = token_transaction_history.get_page(4, 10);
+        assert_eq!(page.items.len(), 2);
+        assert_eq!(page.next_from_inclusive_index, None);
+
+        assert!(token_transaction_history.get_page(6, 10).items.is_empty());
+    }
+
+    #[test]
+    fn test_largest_token_event_fits_within_max_size() {
+        let token_event = TokenEvent::HotOrNotOutcomePayout {
+            amount: u64::MAX,
+            details: HotOrNotOutcomePayoutEvent::CollaboratorShareOfCommission {
+                post_creator_user_principal_id: Principal::from_slice(&[u8::MAX; 29]),
+                post_canister_id: Principal::from_slice(&[u8::MAX; 29]),
+                post_id: u64::MAX,
+                slot_id: u8::MAX,
+                room_id: u64::MAX,
+                room_pot_total_amount: u64::MAX,
+                share_amount: u64::MAX,
+            },
+            timestamp: SystemTime::now(),
+        };
+
+        assert!(token_event.to_bytes().len() <= TokenEvent::MAX_SIZE as usize);
+    }
+
+    mod test_get_creator_commission_earned_per_room {
+        use std::time::Duration;
+
+        use test_utils::setup::test_constants::get_mock_user_alice_canister_id;
+
+        use crate::common::types::utility_token::token_event::MintEvent;
+
+        use super::*;
+
+        fn get_commission_event(
+            post_id: PostId,
+            slot_id: u8,
+            room_id: u64,
+            amount: u64,
+            timestamp: SystemTime,
+        ) -> TokenEvent {
+            TokenEvent::HotOrNotOutcomePayout {
+                amount,
+                details: HotOrNotOutcomePayoutEvent::CommissionFromHotOrNotBet {
+                    post_canister_id: get_mock_user_alice_canister_id(),
+                    post_id,
+                    slot_id,
+                    room_id,
+                    room_pot_total_amount: amount * 10,
+                },
+                timestamp,
+            }
+        }
+
+        #[test]
+        fn test_get_creator_commission_earned_per_room() {
+            let start_time = SystemTime::now();
+            let an_hour_later = start_time + Duration::from_secs(60 * 60);
+            let a_day_later = start_time + Duration::from_secs(60 * 60 * 24);
+            let mut token_transaction_history =
+                TokenTransactionHistory::init(VectorMemory::default());
+
+            token_transaction_history.append(get_commission_event(1, 2, 1, 10, start_time));
+            token_transaction_history.append(get_commission_event(0, 1, 1, 20, start_time));
+            // * a collaborator's unclaimable share paid back out for the same room
+            token_transaction_history.append(get_commission_event(1, 2, 1, 3, an_hour_later));
+            token_transaction_history.append(get_commission_event(0, 1, 2, 30, a_day_later));
+            token_transaction_history.append(TokenEvent::Mint {
+                amount: 1000,
+                details: MintEvent::NewUserSignup {
+                    new_user_principal_id: get_mock_user_alice_canister_id(),
+                },
+                timestamp: start_time,
+            });
+
+            assert_eq!(
+                token_transaction_history
+                    .get_creator_commission_earned_per_room(&start_time, &a_day_later),
+                vec![
+                    CreatorCommissionEarnedInRoom {
+                        post_id: 0,
+                        slot_id: 1,
+                        room_id: 1,
+                        room_pot_total_amount: 200,
+                        commission_amount: 20,
+                        last_paid_at: start_time,
+                    },
+                    CreatorCommissionEarnedInRoom {
+                        post_id: 1,
+                        slot_id: 2,
+                        room_id: 1,
+                        room_pot_total_amount: 100,
+                        commission_amount: 13,
+                        last_paid_at: an_hour_later,
+                    },
+                ]
+            );
+            assert_eq!(
+                token_transaction_history.get_creator_commission_earned_per_room(
+                    &an_hour_later,
+                    &SystemTime::UNIX_EPOCH
+                ),
+                vec![]
+            );
+            assert_eq!(
+                token_transaction_history
+                    .get_creator_commission_earned_per_room(
+                        &a_day_later,
+                        &(a_day_later + Duration::from_secs(1))
+                    )
+                    .len(),
+                1
+            );
+        }
+    }
+
+    mod test_get_token_events_for_hot_or_not_bet {
+        use test_utils::setup::test_constants::{
+            get_mock_user_alice_canister_id, get_mock_user_bob_canister_id,
+        };
+
+        use crate::{
+            canister_specific::individual_user_template::types::hot_or_not::BetDirection,
+            common::types::utility_token::token_event::StakeEvent,
+        };
+
+        use super::*;
+
+        fn get_stake_event(post_canister_id: Principal, post_id: PostId) -> TokenEvent {
+            TokenEvent::Stake {
+                amount: 10,
+                details: StakeEvent::BetOnHotOrNotPost {
+                    post_canister_id,
+                    post_id,
+                    bet_amount: 10,
+                    bet_direction: BetDirection::Hot,
+                },
+                timestamp: SystemTime::UNIX_EPOCH,
+            }
+        }
+
+        fn get_commission_event(slot_id: u8, room_id: u64) -> TokenEvent {
+            TokenEvent::HotOrNotOutcomePayout {
+                amount: 1,
+                details: HotOrNotOutcomePayoutEvent::CommissionFromHotOrNotBet {
+                    post_canister_id: get_mock_user_alice_canister_id(),
+                    post_id: 0,
+                    slot_id,
+                    room_id,
+                    room_pot_total_amount: 10,
+                },
+                timestamp: SystemTime::UNIX_EPOCH,
+            }
+        }
+
+        #[test]
+        fn test_get_token_events_for_hot_or_not_bet() {
+            let alice = get_mock_user_alice_canister_id();
+            let mut token_transaction_history =
+                TokenTransactionHistory::init(VectorMemory::default());
+
+            token_transaction_history.append(get_stake_event(alice, 0));
+            token_transaction_history.append(get_stake_event(alice, 1));
+            token_transaction_history.append(get_stake_event(get_mock_user_bob_canister_id(), 0));
+            token_transaction_history.append(get_commission_event(1, 1));
+            token_transaction_history.append(get_commission_event(1, 2));
+
+            let get_transaction_ids = |slot_and_room| {
+                token_transaction_history
+                    .get_token_events_for_hot_or_not_bet(&alice, 0, slot_and_room)
+                    .into_iter()
+                    .map(|(transaction_id, _)| transaction_id)
+                    .collect::<Vec<u64>>()
+            };
+            assert_eq!(get_transaction_ids(None), vec![1, 4, 5]);
+            // * stakes carry no room, so they are kept whatever the room
+            assert_eq!(get_transaction_ids(Some((1, 2))), vec![1, 5]);
+
+            (0..MAX_TRACED_TOKEN_EVENTS).for_each(|_| {
+                token_transaction_history.append(get_commission_event(1, 2));
+            });
+            let token_events =
+                token_transaction_history.get_token_events_for_hot_or_not_bet(&alice, 0, None);
+            assert_eq!(token_events.len(), MAX_TRACED_TOKEN_EVENTS);
+            assert_eq!(
+                token_events.last().unwrap().0,
+                5 + MAX_TRACED_TOKEN_EVENTS as u64
+            );
+        }
+    }
+}
@@ -0,0 +1,14 @@
+use std::time::SystemTime;
+
+use candid::{CandidType, Deserialize};
+use serde::Serialize;
+
+use crate::canister_specific::user_index::types::tournament::TournamentId;
+
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug, PartialEq, Eq)]
+pub struct TournamentEnrollment {
+    pub tournament_id: TournamentId,
+    pub closes_at: SystemTime,
+    // * Total amount bet on the post while the tournament was open
+    pub points: u64,
+}
@@ -0,0 +1,396 @@
+use std::{
+    borrow::Cow,
+    collections::{BTreeMap, BTreeSet},
+    time::SystemTime,
+};
+
+use candid::{CandidType, Decode, Deserialize, Encode, Principal};
+use ic_stable_structures::{BoundedStorable, Storable};
+use serde::Serialize;
+
+use crate::common::types::{
+    app_primitive_type::PostId, utility_token::token_event::HOT_OR_NOT_BET_WINNINGS_MULTIPLIER,
+};
+
+use super::{
+    error::PotInsuranceClaimError,
+    feature_usage::{get_day_number, DayNumber},
+    hot_or_not::{RoomId, SlotId},
+};
+
+/// The platform's cut of a tabulated room's winnings that is yet to be delivered to the
+/// treasury canister
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug, PartialEq, Eq)]
+pub struct HouseRakeSettlement {
+    pub post_canister_id: Principal,
+    pub post_id: PostId,
+    pub slot_id: u8,
+    pub room_id: u64,
+    pub room_pot_total_amount: u64,
+    pub rake_amount: u64,
+    pub number_of_failed_attempts: u32,
+}
+
+impl Storable for HouseRakeSettlement {
+    fn to_bytes(&self) -> Cow<'_, [u8]> {
+        Cow::Owned(Encode!(self).unwrap())
+    }
+
+    fn from_bytes(bytes: Cow<[u8]>) -> Self {
+        Decode!(&bytes, Self).unwrap()
+    }
+}
+
+impl BoundedStorable for HouseRakeSettlement {
+    const MAX_SIZE: u32 = 1_000;
+    const IS_FIXED_SIZE: bool = false;
+}
+
+/// Highest rake the platform takes on top of the creator commission, so that winners always
+/// keep most of what they won
+pub const MAXIMUM_HOUSE_RAKE_PERCENTAGE: u64 = 10;
+
+pub fn validate_house_rake_percentage(house_rake_percentage: u64) -> Result<(), String> {
+    if house_rake_percentage > MAXIMUM_HOUSE_RAKE_PERCENTAGE {
+        return Err(format!(
+            "House rake percentage cannot exceed {}",
+            MAXIMUM_HOUSE_RAKE_PERCENTAGE
+        ));
+    }
+
+    Ok(())
+}
+
+/// Minimum pot the treasury guarantees winners of a room, so that rooms with only a few bets
+/// in them still pay out like busier ones
+#[derive(CandidType, Clone, Deserialize, Serialize, Debug, Default, PartialEq, Eq)]
+pub struct PotInsuranceConfig {
+    // * Zero switches pot insurance off
+    pub room_pot_floor: u64,
+    // * Most the treasury pays out in top ups in a day
+    pub daily_spend_cap: u64,
+}
+
+impl PotInsuranceConfig {
+    pub fn is_enabled(&self) -> bool {
+        self.room_pot_floor > 0 && self.daily_spend_cap > 0
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        if self.room_pot_floor > 0 && self.daily_spend_cap == 0 {
+            return Err("A room pot floor needs a daily spend cap above zero".to_string());
+        }
+
+        Ok(())
+    }
+}
+
+/// What a winner would have been paid on top of `payout` had the room's pot been at the
+/// floor. Zero for rooms whose pot already reaches it.
+pub fn get_pot_insurance_top_up_amount(
+    payout: u64,
+    room_pot_total_amount: u64,
+    room_pot_floor: u64,
+) -> u64 {
+    if room_pot_total_amount == 0 || room_pot_total_amount >= room_pot_floor {
+        return 0;
+    }
+
+    (payout as u128 * (room_pot_floor - room_pot_total_amount) as u128
+        / room_pot_total_amount as u128) as u64
+}
+
+/// Most the treasury pays out for a single room. Winners can't have staked more than the
+/// whole pot, so their top ups together never come to more than this.
+pub fn get_maximum_pot_insurance_payout_for_room(
+    room_pot_total_amount: u64,
+    room_pot_floor: u64,
+) -> u64 {
+    room_pot_floor.saturating_sub(room_pot_total_amount) * HOT_OR_NOT_BET_WINNINGS_MULTIPLIER
+}
+
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug, PartialEq, Eq)]
+pub struct PotInsuranceTopUp {
+    pub bet_maker_canister_id: Principal,
+    pub amount: u64,
+}
+
+/// Top ups owed to the winners of a room that settled below the pot floor, yet to be
+/// claimed from the treasury canister
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug, PartialEq, Eq)]
+pub struct PotInsuranceClaim {
+    pub post_canister_id: Principal,
+    pub post_id: PostId,
+    pub slot_id: SlotId,
+    pub room_id: RoomId,
+    pub room_pot_total_amount: u64,
+    pub top_ups: Vec<PotInsuranceTopUp>,
+    pub number_of_failed_attempts: u32,
+    // * Only missing on claims queued before it was added, which are stamped when sent
+    #[serde(default)]
+    pub tabulated_at: Option<SystemTime>,
+}
+
+impl PotInsuranceClaim {
+    pub fn get_total_top_up_amount(&self) -> u64 {
+        self.top_ups.iter().map(|top_up| top_up.amount).sum()
+    }
+}
+
+/// A top up the treasury accepted and is yet to deliver to the winner's canister
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug, PartialEq, Eq)]
+pub struct PotInsuranceTopUpSettlement {
+    pub post_canister_id: Principal,
+    pub post_id: PostId,
+    pub slot_id: SlotId,
+    pub room_id: RoomId,
+    pub bet_maker_canister_id: Principal,
+    pub top_up_amount: u64,
+    pub number_of_failed_attempts: u32,
+}
+
+impl Storable for PotInsuranceTopUpSettlement {
+    fn to_bytes(&self) -> Cow<'_, [u8]> {
+        Cow::Owned(Encode!(self).unwrap())
+    }
+
+    fn from_bytes(bytes: Cow<[u8]>) -> Self {
+        Decode!(&bytes, Self).unwrap()
+    }
+}
+
+impl BoundedStorable for PotInsuranceTopUpSettlement {
+    const MAX_SIZE: u32 = 1_000;
+    const IS_FIXED_SIZE: bool = false;
+}
+
+// * Claims for rooms tabulated longer ago than this are turned down, so that the claims
+// * paid before then can be forgotten
+const POT_INSURANCE_CLAIM_RETENTION_IN_DAYS: u64 = 7;
+
+// * (post canister id, post id, slot id, room id)
+type PaidClaimKey = (Principal, PostId, SlotId, RoomId);
+
+/// The treasury's record of the claims it paid, so that no room is insured twice and the
+/// daily spend stays under the cap
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug, Default)]
+pub struct PotInsuranceLedger {
+    pub day: DayNumber,
+    pub spent_on_day: u64,
+    // * Claims paid before they were kept by day. Moved under the day they're next looked
+    // * at on, so that they get pruned along with it.
+    #[serde(default)]
+    paid_claims: BTreeSet<PaidClaimKey>,
+    // * Keyed by the day the claim's room was tabulated on
+    #[serde(default)]
+    paid_claims_by_day: BTreeMap<DayNumber, BTreeSet<PaidClaimKey>>,
+}
+
+impl PotInsuranceLedger {
+    /// Checks the claim against the config and what has already been paid out, and records it
+    /// if it passes. Returns the amount to pay out.
+    pub fn accept_claim(
+        &mut self,
+        claim: &PotInsuranceClaim,
+        config: &PotInsuranceConfig,
+        treasury_balance: u64,
+        current_time: &SystemTime,
+    ) -> Result<u64, PotInsuranceClaimError> {
+        if !config.is_enabled() {
+            return Err(PotInsuranceClaimError::PotInsuranceDisabled);
+        }
+
+        if claim.room_pot_total_amount >= config.room_pot_floor {
+            return Err(PotInsuranceClaimError::PotNotBelowFloor);
+        }
+
+        let total_top_up_amount = claim.get_total_top_up_amount();
+        if total_top_up_amount
+            > get_maximum_pot_insurance_payout_for_room(
+                claim.room_pot_total_amount,
+                config.room_pot_floor,
+            )
+        {
+            return Err(PotInsuranceClaimError::TopUpAboveFloor);
+        }
+
+        let today = get_day_number(current_time);
+        self.prune_paid_claims(today);
+
+        let first_day_kept = today.saturating_sub(POT_INSURANCE_CLAIM_RETENTION_IN_DAYS);
+        let tabulated_on_day = claim
+            .tabulated_at
+            .map(|tabulated_at| get_day_number(&tabulated_at))
+            .unwrap_or_default();
+        if tabulated_on_day < first_day_kept {
+            return Err(PotInsuranceClaimError::ClaimExpired);
+        }
+
+        let claim_key = (
+            claim.post_canister_id,
+            claim.post_id,
+            claim.slot_id,
+            claim.room_id,
+        );
+        if self
+            .paid_claims_by_day
+            .values()
+            .any(|paid_claims| paid_claims.contains(&claim_key))
+        {
+            return Err(PotInsuranceClaimError::AlreadyPaid);
+        }
+
+        let spent_today = if self.day == today {
+            self.spent_on_day
+        } else {
+            0
+        };
+        if spent_today + total_top_up_amount > config.daily_spend_cap {
+            return Err(PotInsuranceClaimError::DailySpendCapReached);
+        }
+
+        if total_top_up_amount > treasury_balance {
+            return Err(PotInsuranceClaimError::InsufficientTreasuryBalance);
+        }
+
+        self.day = today;
+        self.spent_on_day = spent_today + total_top_up_amount;
+        self.paid_claims_by_day
+            .entry(tabulated_on_day)
+            .or_default()
+            .insert(claim_key);
+
+        Ok(total_top_up_amount)
+    }
+
+    fn prune_paid_claims(&mut self, today: DayNumber) {
+        if !self.paid_claims.is_empty() {
+            let paid_claims = std::mem::take(&mut self.paid_claims);
+            self.paid_claims_by_day
+                .entry(today)
+                .or_default()
+                .extend(paid_claims);
+        }
+
+        let first_day_kept = today.saturating_sub(POT_INSURANCE_CLAIM_RETENTION_IN_DAYS);
+        self.paid_claims_by_day
+            .retain(|day, _| *day >= first_day_kept);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use test_utils::setup::test_constants::{
+        get_mock_user_alice_canister_id, get_mock_user_bob_canister_id,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_validate_house_rake_percentage() {
+        assert!(validate_house_rake_percentage(0).is_ok());
+        assert!(validate_house_rake_percentage(MAXIMUM_HOUSE_RAKE_PERCENTAGE).is_ok());
+        assert!(validate_house_rake_percentage(MAXIMUM_HOUSE_RAKE_PERCENTAGE + 1).is_err());
+    }
+
+    #[test]
+    fn test_get_pot_insurance_top_up_amount() {
+        // * a 180 payout out of a 100 pot, with the floor at 500
+        assert_eq!(get_pot_insurance_top_up_amount(180, 100, 500), 720);
+        assert_eq!(get_pot_insurance_top_up_amount(180, 500, 500), 0);
+        assert_eq!(get_pot_insurance_top_up_amount(180, 100, 0), 0);
+        assert_eq!(get_pot_insurance_top_up_amount(0, 100, 500), 0);
+        assert!(
+            get_pot_insurance_top_up_amount(180, 100, 500)
+                <= get_maximum_pot_insurance_payout_for_room(100, 500)
+        );
+    }
+
+    #[test]
+    fn test_accept_pot_insurance_claim() {
+        let config = PotInsuranceConfig {
+            room_pot_floor: 500,
+            daily_spend_cap: 1000,
+        };
+        let get_claim = |room_id: RoomId, amount: u64| PotInsuranceClaim {
+            post_canister_id: get_mock_user_alice_canister_id(),
+            post_id: 0,
+            slot_id: 1,
+            room_id,
+            room_pot_total_amount: 100,
+            top_ups: vec![PotInsuranceTopUp {
+                bet_maker_canister_id: get_mock_user_bob_canister_id(),
+                amount,
+            }],
+            number_of_failed_attempts: 0,
+            tabulated_at: Some(SystemTime::UNIX_EPOCH),
+        };
+        let current_time = SystemTime::UNIX_EPOCH;
+        let mut ledger = PotInsuranceLedger::default();
+
+        assert_eq!(
+            ledger.accept_claim(
+                &get_claim(1, 720),
+                &PotInsuranceConfig::default(),
+                10_000,
+                &current_time
+            ),
+            Err(PotInsuranceClaimError::PotInsuranceDisabled)
+        );
+        assert_eq!(
+            ledger.accept_claim(&get_claim(1, 801), &config, 10_000, &current_time),
+            Err(PotInsuranceClaimError::TopUpAboveFloor)
+        );
+        assert_eq!(
+            ledger.accept_claim(&get_claim(1, 720), &config, 700, &current_time),
+            Err(PotInsuranceClaimError::InsufficientTreasuryBalance)
+        );
+        assert_eq!(
+            ledger.accept_claim(&get_claim(1, 720), &config, 10_000, &current_time),
+            Ok(720)
+        );
+        assert_eq!(
+            ledger.accept_claim(&get_claim(1, 720), &config, 10_000, &current_time),
+            Err(PotInsuranceClaimError::AlreadyPaid)
+        );
+        assert_eq!(
+            ledger.accept_claim(&get_claim(2, 720), &config, 10_000, &current_time),
+            Err(PotInsuranceClaimError::DailySpendCapReached)
+        );
+
+        // * the cap starts over the next day
+        assert_eq!(
+            ledger.accept_claim(
+                &get_claim(2, 720),
+                &config,
+                10_000,
+                &(current_time + Duration::from_secs(24 * 60 * 60))
+            ),
+            Ok(720)
+        );
+        assert_eq!(ledger.spent_on_day, 720);
+
+        // * paid claims are forgotten once their rooms are too old to be claimed for
+        let after_retention = current_time
+            + Duration::from_secs((POT_INSURANCE_CLAIM_RETENTION_IN_DAYS + 1) * 24 * 60 * 60);
+        assert_eq!(
+            ledger.accept_claim(&get_claim(3, 720), &config, 10_000, &after_retention),
+            Err(PotInsuranceClaimError::ClaimExpired)
+        );
+        assert!(ledger.paid_claims_by_day.is_empty());
+        assert_eq!(
+            ledger.accept_claim(
+                &PotInsuranceClaim {
+                    tabulated_at: Some(after_retention),
+                    ..get_claim(3, 720)
+                },
+                &config,
+                10_000,
+                &after_retention
+            ),
+            Ok(720)
+        );
+    }
+}
@@ -0,0 +1,339 @@
+use std::{
+    collections::BTreeMap,
+    time::{Duration, SystemTime},
+};
+
+use candid::{CandidType, Deserialize, Principal};
+use serde::Serialize;
+
+use crate::common::types::global_post_ref::GlobalPostRef;
+
+use super::post::Post;
+
+pub const MIN_VERSUS_CONTEST_WINDOW: Duration = Duration::from_secs(60 * 60);
+pub const MAX_VERSUS_CONTEST_WINDOW: Duration = Duration::from_secs(7 * 24 * 60 * 60);
+
+// * How much engagement gained during the window counts for, in tokens bet on the post
+pub const VERSUS_SCORE_PER_VIEW: u64 = 1;
+pub const VERSUS_SCORE_PER_LIKE: u64 = 10;
+pub const VERSUS_SCORE_PER_SHARE: u64 = 10;
+
+/// Identifies a contest on both canisters taking part. Indices are handed out by the
+/// canister of the creator that proposed the contest.
+#[derive(
+    CandidType, Deserialize, Serialize, Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord,
+)]
+pub struct VersusContestId {
+    pub proposer_canister_id: Principal,
+    pub index: u64,
+}
+
+#[derive(CandidType, Deserialize, Serialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum VersusSide {
+    Proposer,
+    Opponent,
+}
+
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug, PartialEq, Eq)]
+pub struct VersusContestProposal {
+    pub contest_id: VersusContestId,
+    pub proposer_post: GlobalPostRef,
+    pub opponent_post: GlobalPostRef,
+    pub window_in_seconds: u64,
+}
+
+impl VersusContestProposal {
+    pub fn get_post(&self, side: VersusSide) -> GlobalPostRef {
+        match side {
+            VersusSide::Proposer => self.proposer_post,
+            VersusSide::Opponent => self.opponent_post,
+        }
+    }
+
+    pub fn get_side_of_post(&self, post: &GlobalPostRef) -> Option<VersusSide> {
+        if *post == self.proposer_post {
+            Some(VersusSide::Proposer)
+        } else if *post == self.opponent_post {
+            Some(VersusSide::Opponent)
+        } else {
+            None
+        }
+    }
+}
+
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug, PartialEq, Eq)]
+pub enum VersusContestStatus {
+    AwaitingOpponentConsent,
+    Declined,
+    Open {
+        opens_at: SystemTime,
+        closes_at: SystemTime,
+    },
+    Settled {
+        settlement: VersusContestSettlement,
+        settled_at: SystemTime,
+    },
+}
+
+#[derive(CandidType, Deserialize, Serialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum VersusContestOutcome {
+    Won(VersusSide),
+    Tie,
+}
+
+/// How one side of a contest did over the window
+#[derive(CandidType, Deserialize, Serialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct VersusSideReport {
+    // * Total amount bet on this side's post in the contest
+    pub pot: u64,
+    pub number_of_bets: u64,
+    // * The pot plus the weighted engagement the post gained while the contest was open
+    pub performance_score: u64,
+}
+
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug, PartialEq, Eq)]
+pub struct VersusContestSettlement {
+    pub outcome: VersusContestOutcome,
+    pub proposer_side: VersusSideReport,
+    pub opponent_side: VersusSideReport,
+}
+
+impl VersusContestSettlement {
+    pub fn new(proposer_side: VersusSideReport, opponent_side: VersusSideReport) -> Self {
+        let outcome = match proposer_side
+            .performance_score
+            .cmp(&opponent_side.performance_score)
+        {
+            std::cmp::Ordering::Greater => VersusContestOutcome::Won(VersusSide::Proposer),
+            std::cmp::Ordering::Less => VersusContestOutcome::Won(VersusSide::Opponent),
+            std::cmp::Ordering::Equal => VersusContestOutcome::Tie,
+        };
+
+        Self {
+            outcome,
+            proposer_side,
+            opponent_side,
+        }
+    }
+
+    fn get_report(&self, side: VersusSide) -> &VersusSideReport {
+        match side {
+            VersusSide::Proposer => &self.proposer_side,
+            VersusSide::Opponent => &self.opponent_side,
+        }
+    }
+
+    /// What a bet of `amount_bet` on `side` pays out. Winners get their stake back along with
+    /// a share of the losing side's pot in proportion to their stake, losers get nothing and
+    /// a tie refunds everyone.
+    pub fn get_payout(&self, side: VersusSide, amount_bet: u64) -> u64 {
+        match self.outcome {
+            VersusContestOutcome::Tie => amount_bet,
+            VersusContestOutcome::Won(winning_side) if winning_side == side => {
+                let winning_pot = self.get_report(side).pot;
+                let losing_pot = match side {
+                    VersusSide::Proposer => self.opponent_side.pot,
+                    VersusSide::Opponent => self.proposer_side.pot,
+                };
+
+                if winning_pot == 0 {
+                    return amount_bet;
+                }
+
+                amount_bet + (amount_bet as u128 * losing_pot as u128 / winning_pot as u128) as u64
+            }
+            VersusContestOutcome::Won(_) => 0,
+        }
+    }
+}
+
+/// A post's engagement counters at one point in time
+#[derive(CandidType, Deserialize, Serialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct VersusEngagement {
+    pub view_count: u64,
+    pub like_count: u64,
+    pub share_count: u64,
+}
+
+impl VersusEngagement {
+    pub fn of_post(post: &Post) -> Self {
+        Self {
+            view_count: post.view_stats.total_view_count,
+            like_count: post.likes.len() as u64,
+            share_count: post.share_count,
+        }
+    }
+
+    /// Weighted engagement gained since `earlier`. Unlikes only take away from what was
+    /// gained during the window.
+    pub fn get_score_gained_since(&self, earlier: &VersusEngagement) -> u64 {
+        self.view_count.saturating_sub(earlier.view_count) * VERSUS_SCORE_PER_VIEW
+            + self.like_count.saturating_sub(earlier.like_count) * VERSUS_SCORE_PER_LIKE
+            + self.share_count.saturating_sub(earlier.share_count) * VERSUS_SCORE_PER_SHARE
+    }
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq, Eq)]
+pub struct VersusBetOnSide {
+    pub bet_maker_canister_id: Principal,
+    pub amount: u64,
+    pub placed_at: SystemTime,
+    pub is_payout_delivered: bool,
+}
+
+/// A contest as stored by either canister taking part, along with the bets placed on this
+/// canister's post. The two canisters only share the pot sizes and scores at settlement.
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct VersusContest {
+    pub proposal: VersusContestProposal,
+    pub my_side: VersusSide,
+    pub status: VersusContestStatus,
+    // * Keyed by the principal of the bet maker
+    pub bets: BTreeMap<Principal, VersusBetOnSide>,
+    pub engagement_at_open: VersusEngagement,
+    // * Frozen the first time this side is reported once the contest closes, so engagement
+    // * coming in later doesn't change it
+    pub final_report: Option<VersusSideReport>,
+}
+
+impl VersusContest {
+    pub fn new(proposal: VersusContestProposal, my_side: VersusSide) -> Self {
+        Self {
+            proposal,
+            my_side,
+            status: VersusContestStatus::AwaitingOpponentConsent,
+            bets: BTreeMap::new(),
+            engagement_at_open: VersusEngagement::default(),
+            final_report: None,
+        }
+    }
+
+    pub fn get_my_post(&self) -> GlobalPostRef {
+        self.proposal.get_post(self.my_side)
+    }
+
+    pub fn get_pot(&self) -> u64 {
+        self.bets.values().map(|bet| bet.amount).sum()
+    }
+
+    pub fn open(&mut self, opens_at: SystemTime, engagement_at_open: VersusEngagement) {
+        self.status = VersusContestStatus::Open {
+            opens_at,
+            closes_at: opens_at + Duration::from_secs(self.proposal.window_in_seconds),
+        };
+        self.engagement_at_open = engagement_at_open;
+    }
+
+    pub fn get_closes_at(&self) -> Option<SystemTime> {
+        match self.status {
+            VersusContestStatus::Open { closes_at, .. } => Some(closes_at),
+            _ => None,
+        }
+    }
+
+    pub fn is_open_for_bets(&self, current_time: &SystemTime) -> bool {
+        self.get_closes_at()
+            .is_some_and(|closes_at| *current_time < closes_at)
+    }
+
+    /// This side's report, once the contest has closed. None while it is still running.
+    pub fn get_final_report(
+        &mut self,
+        my_post: &Post,
+        current_time: &SystemTime,
+    ) -> Option<VersusSideReport> {
+        if let Some(final_report) = self.final_report {
+            return Some(final_report);
+        }
+
+        if self.get_closes_at()? > *current_time {
+            return None;
+        }
+
+        let pot = self.get_pot();
+        let final_report = VersusSideReport {
+            pot,
+            number_of_bets: self.bets.len() as u64,
+            performance_score: pot
+                + VersusEngagement::of_post(my_post)
+                    .get_score_gained_since(&self.engagement_at_open),
+        };
+        self.final_report = Some(final_report);
+
+        Some(final_report)
+    }
+}
+
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug, PartialEq, Eq)]
+pub struct VersusBetPlaced {
+    pub post: GlobalPostRef,
+    pub amount: u64,
+    pub placed_at: SystemTime,
+    // * None until the contest is settled. Zero if the bet lost
+    pub payout: Option<u64>,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct VersusContestDetails {
+    pub proposal: VersusContestProposal,
+    pub my_side: VersusSide,
+    pub status: VersusContestStatus,
+    pub pot_on_this_side: u64,
+    pub number_of_bets_on_this_side: u64,
+}
+
+impl From<&VersusContest> for VersusContestDetails {
+    fn from(contest: &VersusContest) -> Self {
+        Self {
+            proposal: contest.proposal.clone(),
+            my_side: contest.my_side,
+            status: contest.status.clone(),
+            pot_on_this_side: contest.get_pot(),
+            number_of_bets_on_this_side: contest.bets.len() as u64,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn get_report(pot: u64, performance_score: u64) -> VersusSideReport {
+        VersusSideReport {
+            pot,
+            number_of_bets: 1,
+            performance_score,
+        }
+    }
+
+    #[test]
+    fn test_settlement_outcome_and_payouts() {
+        let settlement = VersusContestSettlement::new(get_report(300, 500), get_report(100, 450));
+        assert_eq!(
+            settlement.outcome,
+            VersusContestOutcome::Won(VersusSide::Proposer)
+        );
+        assert_eq!(settlement.get_payout(VersusSide::Proposer, 150), 200);
+        assert_eq!(settlement.get_payout(VersusSide::Opponent, 100), 0);
+
+        let settlement = VersusContestSettlement::new(get_report(300, 500), get_report(100, 500));
+        assert_eq!(settlement.outcome, VersusContestOutcome::Tie);
+        assert_eq!(settlement.get_payout(VersusSide::Opponent, 100), 100);
+    }
+
+    #[test]
+    fn test_engagement_score_gained_since() {
+        let at_open = VersusEngagement {
+            view_count: 100,
+            like_count: 5,
+            share_count: 1,
+        };
+        let now = VersusEngagement {
+            view_count: 150,
+            like_count: 4,
+            share_count: 3,
+        };
+
+        assert_eq!(now.get_score_gained_since(&at_open), 50 + 20);
+    }
+}
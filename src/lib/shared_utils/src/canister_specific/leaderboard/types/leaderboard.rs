@@ -0,0 +1,59 @@
+use candid::{CandidType, Deserialize, Principal};
+use serde::Serialize;
+
+use crate::common::types::app_primitive_type::PostId;
+
+pub const MAX_LEADERBOARD_PAGE_SIZE: u64 = 100;
+// * The weekly window is today and the six days before it
+pub const LEADERBOARD_DAYS_IN_A_WEEK: u64 = 7;
+
+#[derive(CandidType, Deserialize, Serialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LeaderboardPeriod {
+    // * The current UTC day
+    Daily,
+    Weekly,
+    AllTime,
+}
+
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug, PartialEq, Eq)]
+pub enum LeaderboardBetOutcome {
+    Won,
+    Lost,
+    Draw,
+}
+
+/// How a single bet in a tabulated room turned out for its bettor
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug, PartialEq, Eq)]
+pub struct BettorLeaderboardDelta {
+    pub bet_maker_canister_id: Principal,
+    pub amount_staked: u64,
+    pub amount_paid_out: u64,
+    pub outcome: LeaderboardBetOutcome,
+}
+
+/// What tabulating a slot of a post changed on the leaderboards. Pushed by the canister of the
+/// post's creator once every room in the slot has been settled.
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug, PartialEq, Eq)]
+pub struct SlotLeaderboardDeltas {
+    pub post_id: PostId,
+    pub slot_id: u8,
+    // * Commission taken from every room in the slot, before collaborators get their share
+    pub creator_commission: u64,
+    pub bettor_deltas: Vec<BettorLeaderboardDelta>,
+}
+
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug, PartialEq, Eq)]
+pub struct TopBettor {
+    pub canister_id: Principal,
+    // * Paid out less staked, over every bet in the period
+    pub net_winnings: i64,
+    pub bets_won: u64,
+    pub bets_lost: u64,
+}
+
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug, PartialEq, Eq)]
+pub struct TopCreator {
+    pub canister_id: Principal,
+    pub commission_earned: u64,
+    pub slots_settled: u64,
+}
@@ -1,5 +1,6 @@
 pub mod configuration;
 pub mod data_backup;
 pub mod individual_user_template;
+pub mod leaderboard;
 pub mod post_cache;
 pub mod user_index;
@@ -0,0 +1,42 @@
+use candid::{CandidType, Deserialize, Principal};
+
+use crate::common::types::{
+    app_primitive_type::PostId, top_posts::post_score_index_item::PostScoreIndexItem,
+};
+
+/// Why a post made it into a feed, for the UI to explain and for analytics to tell ranking
+/// sources apart
+#[derive(CandidType, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub enum FeedItemReason {
+    // * Ranked on its feed score alone
+    Trending,
+    // * Not given out by the post cache, which doesn't know who is viewing the feed
+    FromFollowedCreator,
+    // * The creator paid for a boost that is still running
+    Sponsored,
+    // * Not given out by the post cache, as new creators get no ranking boost yet
+    NewCreatorBoost,
+    // * Carries a hashtag that is trending in its language
+    HashtagMatch(String),
+}
+
+/// A feed entry along with why it is in the feed. Has the fields of `PostScoreIndexItem`, so
+/// clients decoding feed pages into that still can.
+#[derive(CandidType, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct FeedItem {
+    pub score: u64,
+    pub post_id: PostId,
+    pub publisher_canister_id: Principal,
+    pub reason: FeedItemReason,
+}
+
+impl FeedItem {
+    pub fn new(post_score_index_item: PostScoreIndexItem, reason: FeedItemReason) -> Self {
+        Self {
+            score: post_score_index_item.score,
+            post_id: post_score_index_item.post_id,
+            publisher_canister_id: post_score_index_item.publisher_canister_id,
+            reason,
+        }
+    }
+}
@@ -0,0 +1,15 @@
+use candid::{CandidType, Deserialize};
+
+#[derive(CandidType, Deserialize, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum FeedMode {
+    HomeFeed,
+    HotOrNotFeed,
+    HotOrNotFeedV2,
+}
+
+#[derive(CandidType, Deserialize, Debug, PartialEq, Eq)]
+pub struct FeedResponseCacheMetrics {
+    pub hits: u64,
+    pub misses: u64,
+    pub number_of_entries: u64,
+}
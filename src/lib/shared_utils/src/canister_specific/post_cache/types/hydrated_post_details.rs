@@ -0,0 +1,12 @@
+use candid::{CandidType, Deserialize, Principal};
+
+use crate::canister_specific::individual_user_template::types::post::PostDetailsForFrontend;
+
+#[derive(CandidType, Deserialize, Debug)]
+pub struct HydratedPostDetails {
+    pub publisher_canister_id: Principal,
+    pub post_details: PostDetailsForFrontend,
+    /// Set when the publishing canister could not be reached and the details were read from
+    /// the last backup instead. Stale posts should be rendered as non-interactive cards.
+    pub is_stale: bool,
+}
@@ -1 +1,5 @@
 pub mod arg;
+pub mod feed_item;
+pub mod feed_response_cache;
+pub mod hydrated_post_details;
+pub mod trending_hashtag;
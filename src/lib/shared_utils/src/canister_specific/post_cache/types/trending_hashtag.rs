@@ -0,0 +1,16 @@
+use candid::{CandidType, Deserialize};
+
+use crate::common::utils::hashtag::HashtagLanguageBucket;
+
+#[derive(CandidType, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct TrendingHashtag {
+    pub hashtag: String,
+    // * Posts in the home feed right now that carry this hashtag
+    pub post_count: u64,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct TrendingHashtagsForLanguage {
+    pub language_bucket: HashtagLanguageBucket,
+    pub hashtags: Vec<TrendingHashtag>,
+}
@@ -0,0 +1,21 @@
+use candid::{CandidType, Deserialize, Principal};
+use serde::Serialize;
+
+use crate::{
+    canister_specific::individual_user_template::types::hot_or_not::bet_trace::{
+        BettorSideBetTrace, PostSideBetTrace,
+    },
+    common::types::global_post_ref::GlobalPostRef,
+};
+
+/// A single bet followed from the bettor's canister to the canister of the post it was
+/// placed on. Either side may fail to be read without failing the other.
+#[derive(CandidType, Deserialize, Serialize, Clone)]
+pub struct BetTrace {
+    pub bettor_principal_id: Principal,
+    // * None if the bettor has no canister registered with this index
+    pub bettor_canister_id: Option<Principal>,
+    pub post_ref: GlobalPostRef,
+    pub bettor_side: Result<BettorSideBetTrace, String>,
+    pub post_side: Result<PostSideBetTrace, String>,
+}
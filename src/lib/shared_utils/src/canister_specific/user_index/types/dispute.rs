@@ -0,0 +1,21 @@
+use candid::{CandidType, Deserialize, Principal};
+use serde::Serialize;
+
+use crate::{
+    canister_specific::individual_user_template::types::{
+        dispute::{SlotOutcomeDispute, SlotOutcomeRecalculation},
+        hot_or_not::SlotId,
+    },
+    common::types::app_primitive_type::PostId,
+};
+
+/// All disputes raised against a slot of a post, along with the latest recalculation an
+/// admin asked for
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug, PartialEq, Eq)]
+pub struct DisputedSlot {
+    pub post_canister_id: Principal,
+    pub post_id: PostId,
+    pub slot_id: SlotId,
+    pub disputes: Vec<SlotOutcomeDispute>,
+    pub last_recalculation: Option<SlotOutcomeRecalculation>,
+}
@@ -1 +1,9 @@
 pub mod args;
+pub mod bet_trace;
+pub mod dispute;
+pub mod moderation;
+pub mod stale_canister_gc;
+pub mod token_balance_audit;
+pub mod tournament;
+pub mod user_index_shard;
+pub mod username;
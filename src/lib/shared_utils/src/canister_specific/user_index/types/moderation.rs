@@ -0,0 +1,16 @@
+use candid::{CandidType, Deserialize, Principal};
+use serde::Serialize;
+
+use crate::{
+    canister_specific::individual_user_template::types::post::report::PostReport,
+    common::types::app_primitive_type::PostId,
+};
+
+/// A post in the moderation queue, with every report raised against it since it was last
+/// reviewed
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug, PartialEq, Eq)]
+pub struct ReportedPost {
+    pub post_canister_id: Principal,
+    pub post_id: PostId,
+    pub reports: Vec<PostReport>,
+}
@@ -0,0 +1,15 @@
+use candid::{CandidType, Deserialize};
+use serde::Serialize;
+
+/// What a single run of the stale canister collection did
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug, PartialEq, Eq, Default)]
+pub struct StaleCanisterGcReport {
+    pub flagged_canister_count: u64,
+    // * Flagged canisters whose owners became active again
+    pub unflagged_canister_count: u64,
+    pub collected_canister_count: u64,
+    // * Canisters that could not be reached, snapshotted or uninstalled. Retried on the
+    // * next run
+    pub failed_canister_count: u64,
+    pub provisioning_pool_size: u64,
+}
@@ -0,0 +1,79 @@
+use std::time::SystemTime;
+
+use candid::{CandidType, Deserialize, Principal};
+
+pub const MAX_PRINCIPALS_PER_TOKEN_BALANCE_AUDIT: usize = 100;
+
+#[derive(CandidType, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub enum TokenBalanceAuditFailure {
+    // * The principal never signed up on this subnet
+    CanisterNotFound,
+    CallFailed(String),
+    TimedOut,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct TokenBalanceAuditEntry {
+    pub user_principal_id: Principal,
+    pub user_canister_id: Option<Principal>,
+    pub utility_token_balance: Result<u64, TokenBalanceAuditFailure>,
+}
+
+/// Balances of a set of principals as read from their canisters, in the order the
+/// principals were passed in
+#[derive(CandidType, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct TokenBalanceAuditReport {
+    pub entries: Vec<TokenBalanceAuditEntry>,
+    // * Only covers the balances that could be read
+    pub total_utility_token_balance: u64,
+    pub failed_count: u64,
+    pub generated_at: SystemTime,
+}
+
+impl TokenBalanceAuditReport {
+    pub fn new(entries: Vec<TokenBalanceAuditEntry>, generated_at: SystemTime) -> Self {
+        let total_utility_token_balance = entries
+            .iter()
+            .filter_map(|entry| entry.utility_token_balance.as_ref().ok())
+            .sum();
+        let failed_count = entries
+            .iter()
+            .filter(|entry| entry.utility_token_balance.is_err())
+            .count() as u64;
+
+        Self {
+            entries,
+            total_utility_token_balance,
+            failed_count,
+            generated_at,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_token_balance_audit_report_totals() {
+        let entry = |utility_token_balance| TokenBalanceAuditEntry {
+            user_principal_id: Principal::anonymous(),
+            user_canister_id: None,
+            utility_token_balance,
+        };
+
+        let report = TokenBalanceAuditReport::new(
+            vec![
+                entry(Ok(1_000)),
+                entry(Err(TokenBalanceAuditFailure::TimedOut)),
+                entry(Ok(250)),
+                entry(Err(TokenBalanceAuditFailure::CanisterNotFound)),
+            ],
+            SystemTime::UNIX_EPOCH,
+        );
+
+        assert_eq!(report.total_utility_token_balance, 1_250);
+        assert_eq!(report.failed_count, 2);
+        assert_eq!(report.entries.len(), 4);
+    }
+}
@@ -0,0 +1,70 @@
+use std::time::{Duration, SystemTime};
+
+use candid::{CandidType, Deserialize, Principal};
+use serde::Serialize;
+
+use crate::common::types::app_primitive_type::PostId;
+
+pub type TournamentId = u64;
+
+pub const TOURNAMENT_DURATION: Duration = Duration::from_secs(7 * 24 * 60 * 60);
+// * Share of the prize pool awarded to the creators ranked 1st, 2nd and 3rd
+pub const TOURNAMENT_PRIZE_SHARE_PERCENTAGES: [u64; 3] = [50, 30, 20];
+
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug, PartialEq, Eq)]
+pub enum TournamentStatus {
+    Open,
+    // * Points are being collected from the enrolled creators' canisters
+    Tallying,
+    Closed,
+}
+
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug, PartialEq, Eq)]
+pub struct TournamentDetails {
+    pub tournament_id: TournamentId,
+    pub opened_at: SystemTime,
+    pub closes_at: SystemTime,
+    pub prize_pool: u64,
+    pub status: TournamentStatus,
+    pub rankings: Vec<TournamentRankingEntry>,
+}
+
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug, PartialEq, Eq)]
+pub struct TournamentRankingEntry {
+    pub rank: u32,
+    pub creator_principal_id: Principal,
+    pub creator_canister_id: Principal,
+    pub points: u64,
+    pub prize_amount: u64,
+}
+
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug, PartialEq, Eq)]
+pub struct TournamentEvent {
+    pub timestamp: SystemTime,
+    pub details: TournamentEventDetails,
+}
+
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug, PartialEq, Eq)]
+pub enum TournamentEventDetails {
+    Opened {
+        prize_pool: u64,
+        closes_at: SystemTime,
+    },
+    PostEnrolled {
+        creator_canister_id: Principal,
+        post_id: PostId,
+    },
+    PointsCollected {
+        creator_canister_id: Principal,
+        points: u64,
+    },
+    PointsCollectionFailed {
+        creator_canister_id: Principal,
+    },
+    PrizeAwarded {
+        creator_canister_id: Principal,
+        rank: u32,
+        prize_amount: u64,
+    },
+    Closed,
+}
@@ -0,0 +1,309 @@
+use std::{borrow::Cow, collections::BTreeMap, time::SystemTime};
+
+use candid::{CandidType, Deserialize, Principal};
+use ic_stable_structures::{BoundedStorable, Memory, StableBTreeMap, Storable};
+use serde::Serialize;
+
+use crate::types::canister_specific::user_index::error_types::UserIndexShardError;
+
+// * Principals are at most 29 bytes long. Each one is stored behind a byte holding its length.
+const MAX_PRINCIPAL_LENGTH: usize = 29;
+const ENCODED_PRINCIPAL_LENGTH: usize = MAX_PRINCIPAL_LENGTH + 1;
+
+fn encode_principal(principal: &Principal, bytes: &mut [u8]) {
+    let principal_bytes = principal.as_slice();
+    bytes[0] = principal_bytes.len() as u8;
+    bytes[1..=principal_bytes.len()].copy_from_slice(principal_bytes);
+}
+
+fn decode_principal(bytes: &[u8]) -> Principal {
+    Principal::from_slice(&bytes[1..=bytes[0] as usize])
+}
+
+/// A secondary user index on another subnet that the primary user index hands new signups to
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug, PartialEq, Eq)]
+pub struct UserIndexShard {
+    pub canister_id: Principal,
+    pub subnet_id: Principal,
+    pub registered_at: SystemTime,
+    // * Shards that are close to their subnet's limits stop getting new users but keep
+    // * serving the ones they already have
+    pub is_accepting_signups: bool,
+    pub number_of_users_routed: u64,
+}
+
+/// The secondary user indexes registered with the primary, and whose turn it is to take the
+/// next signup
+#[derive(Default, Deserialize, Serialize)]
+pub struct UserIndexShards {
+    shards: BTreeMap<Principal, UserIndexShard>,
+    next_signup_turn: u64,
+}
+
+impl UserIndexShards {
+    pub fn register(
+        &mut self,
+        canister_id: Principal,
+        subnet_id: Principal,
+        current_time: &SystemTime,
+    ) -> Result<(), UserIndexShardError> {
+        if self.shards.contains_key(&canister_id) {
+            return Err(UserIndexShardError::ShardAlreadyRegistered);
+        }
+
+        self.shards.insert(
+            canister_id,
+            UserIndexShard {
+                canister_id,
+                subnet_id,
+                registered_at: *current_time,
+                is_accepting_signups: true,
+                number_of_users_routed: 0,
+            },
+        );
+
+        Ok(())
+    }
+
+    pub fn update_signup_status(
+        &mut self,
+        canister_id: &Principal,
+        is_accepting_signups: bool,
+    ) -> Result<(), UserIndexShardError> {
+        let shard = self
+            .shards
+            .get_mut(canister_id)
+            .ok_or(UserIndexShardError::ShardNotFound)?;
+        shard.is_accepting_signups = is_accepting_signups;
+
+        Ok(())
+    }
+
+    pub fn is_registered(&self, canister_id: &Principal) -> bool {
+        self.shards.contains_key(canister_id)
+    }
+
+    pub fn get_shards(&self) -> Vec<UserIndexShard> {
+        self.shards.values().cloned().collect()
+    }
+
+    /// Takes turns between this user index and every shard accepting signups, so that new
+    /// canisters are spread evenly across subnets
+    pub fn pick_user_index_for_next_signup(
+        &mut self,
+        this_user_index_canister_id: Principal,
+    ) -> Principal {
+        let user_indexes = std::iter::once(this_user_index_canister_id)
+            .chain(
+                self.shards
+                    .values()
+                    .filter(|shard| shard.is_accepting_signups)
+                    .map(|shard| shard.canister_id),
+            )
+            .collect::<Vec<_>>();
+
+        let user_index_canister_id =
+            user_indexes[(self.next_signup_turn % user_indexes.len() as u64) as usize];
+        self.next_signup_turn = self.next_signup_turn.wrapping_add(1);
+
+        user_index_canister_id
+    }
+
+    pub fn record_user_routed(&mut self, canister_id: &Principal) {
+        if let Some(shard) = self.shards.get_mut(canister_id) {
+            shard.number_of_users_routed += 1;
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct RoutedUserPrincipalId(pub Principal);
+
+impl Storable for RoutedUserPrincipalId {
+    fn to_bytes(&self) -> Cow<'_, [u8]> {
+        let mut bytes = vec![0; ENCODED_PRINCIPAL_LENGTH];
+        encode_principal(&self.0, &mut bytes);
+
+        Cow::Owned(bytes)
+    }
+
+    fn from_bytes(bytes: Cow<[u8]>) -> Self {
+        Self(decode_principal(&bytes))
+    }
+}
+
+impl BoundedStorable for RoutedUserPrincipalId {
+    const MAX_SIZE: u32 = ENCODED_PRINCIPAL_LENGTH as u32;
+    const IS_FIXED_SIZE: bool = true;
+}
+
+/// Where a user that signed up through a shard ended up
+#[derive(CandidType, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct UserCanisterRoute {
+    pub user_index_canister_id: Principal,
+    pub user_canister_id: Principal,
+}
+
+impl Storable for UserCanisterRoute {
+    fn to_bytes(&self) -> Cow<'_, [u8]> {
+        let mut bytes = vec![0; 2 * ENCODED_PRINCIPAL_LENGTH];
+        let (user_index_canister_id_bytes, user_canister_id_bytes) =
+            bytes.split_at_mut(ENCODED_PRINCIPAL_LENGTH);
+        encode_principal(&self.user_index_canister_id, user_index_canister_id_bytes);
+        encode_principal(&self.user_canister_id, user_canister_id_bytes);
+
+        Cow::Owned(bytes)
+    }
+
+    fn from_bytes(bytes: Cow<[u8]>) -> Self {
+        let (user_index_canister_id_bytes, user_canister_id_bytes) =
+            bytes.split_at(ENCODED_PRINCIPAL_LENGTH);
+
+        Self {
+            user_index_canister_id: decode_principal(user_index_canister_id_bytes),
+            user_canister_id: decode_principal(user_canister_id_bytes),
+        }
+    }
+}
+
+impl BoundedStorable for UserCanisterRoute {
+    const MAX_SIZE: u32 = 2 * ENCODED_PRINCIPAL_LENGTH as u32;
+    const IS_FIXED_SIZE: bool = true;
+}
+
+/// Users whose canisters were created by a shard, kept in stable memory on the primary so
+/// that lookups can be answered without calling the shards and the table doesn't have to be
+/// serialized on upgrades. Users created by the primary itself aren't in here.
+pub struct UserShardRoutingTable<M: Memory> {
+    routes: StableBTreeMap<RoutedUserPrincipalId, UserCanisterRoute, M>,
+}
+
+impl<M: Memory> UserShardRoutingTable<M> {
+    /// Loads the table persisted in `memory`, or creates an empty one if there is none.
+    pub fn init(memory: M) -> Self {
+        Self {
+            routes: StableBTreeMap::init(memory),
+        }
+    }
+
+    pub fn insert(&mut self, user_principal_id: Principal, route: UserCanisterRoute) {
+        self.routes
+            .insert(RoutedUserPrincipalId(user_principal_id), route);
+    }
+
+    pub fn get(&self, user_principal_id: &Principal) -> Option<UserCanisterRoute> {
+        self.routes.get(&RoutedUserPrincipalId(*user_principal_id))
+    }
+
+    pub fn remove(&mut self, user_principal_id: &Principal) -> Option<UserCanisterRoute> {
+        self.routes
+            .remove(&RoutedUserPrincipalId(*user_principal_id))
+    }
+
+    pub fn len(&self) -> u64 {
+        self.routes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.routes.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ic_stable_structures::VectorMemory;
+    use test_utils::setup::test_constants::{
+        get_mock_user_alice_canister_id, get_mock_user_alice_principal_id,
+        get_mock_user_bob_canister_id, get_mock_user_bob_principal_id,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_pick_user_index_for_next_signup_takes_turns() {
+        let mut user_index_shards = UserIndexShards::default();
+        let this_user_index = Principal::from_slice(&[1]);
+        let shard_a = Principal::from_slice(&[2]);
+        let shard_b = Principal::from_slice(&[3]);
+        let subnet = Principal::from_slice(&[9]);
+
+        assert_eq!(
+            user_index_shards.pick_user_index_for_next_signup(this_user_index),
+            this_user_index
+        );
+
+        user_index_shards
+            .register(shard_a, subnet, &SystemTime::UNIX_EPOCH)
+            .unwrap();
+        user_index_shards
+            .register(shard_b, subnet, &SystemTime::UNIX_EPOCH)
+            .unwrap();
+        assert_eq!(
+            user_index_shards.register(shard_a, subnet, &SystemTime::UNIX_EPOCH),
+            Err(UserIndexShardError::ShardAlreadyRegistered)
+        );
+
+        let picked = (0..6)
+            .map(|_| user_index_shards.pick_user_index_for_next_signup(this_user_index))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            picked.iter().filter(|id| **id == this_user_index).count(),
+            2
+        );
+        assert_eq!(picked.iter().filter(|id| **id == shard_a).count(), 2);
+        assert_eq!(picked.iter().filter(|id| **id == shard_b).count(), 2);
+
+        // * shards that stopped accepting signups are skipped
+        user_index_shards
+            .update_signup_status(&shard_a, false)
+            .unwrap();
+        assert!((0..4).all(
+            |_| user_index_shards.pick_user_index_for_next_signup(this_user_index) != shard_a
+        ));
+        assert_eq!(
+            user_index_shards.update_signup_status(&this_user_index, false),
+            Err(UserIndexShardError::ShardNotFound)
+        );
+    }
+
+    #[test]
+    fn test_user_shard_routing_table() {
+        let memory = VectorMemory::default();
+        let mut routing_table = UserShardRoutingTable::init(memory.clone());
+        let shard = Principal::from_slice(&[2]);
+        let route = UserCanisterRoute {
+            user_index_canister_id: shard,
+            user_canister_id: get_mock_user_alice_canister_id(),
+        };
+
+        routing_table.insert(get_mock_user_alice_principal_id(), route);
+        assert_eq!(
+            routing_table.get(&get_mock_user_alice_principal_id()),
+            Some(route)
+        );
+        assert_eq!(routing_table.get(&get_mock_user_bob_principal_id()), None);
+
+        // * survives being loaded again, as after an upgrade
+        let mut routing_table = UserShardRoutingTable::init(memory);
+        assert_eq!(routing_table.len(), 1);
+        assert_eq!(
+            routing_table.remove(&get_mock_user_alice_principal_id()),
+            Some(route)
+        );
+        assert!(routing_table.is_empty());
+
+        routing_table.insert(
+            get_mock_user_bob_principal_id(),
+            UserCanisterRoute {
+                user_index_canister_id: shard,
+                user_canister_id: get_mock_user_bob_canister_id(),
+            },
+        );
+        assert_eq!(
+            routing_table
+                .get(&get_mock_user_bob_principal_id())
+                .map(|route| route.user_canister_id),
+            Some(get_mock_user_bob_canister_id())
+        );
+    }
+}
@@ -0,0 +1,16 @@
+use std::time::SystemTime;
+
+use candid::{CandidType, Deserialize, Principal};
+use serde::Serialize;
+
+pub const MAX_RESERVED_USERNAME_APPEAL_REASON_LENGTH: usize = 500;
+
+/// A user asking for a reserved username, e.g. a creator whose brand or real name is on the
+/// reserved list. An admin verifies the user off-chain before granting it to them.
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug, PartialEq, Eq)]
+pub struct ReservedUsernameAppeal {
+    pub username: String,
+    pub user_principal_id: Principal,
+    pub reason: String,
+    pub appealed_at: SystemTime,
+}
@@ -9,22 +9,38 @@ use ic_cdk::api::management_canister::{
 use rmp_serde::encode;
 use serde::Serialize;
 
-use crate::common::utils::system_time;
+use crate::common::{types::event_counter::EventCounterSample, utils::system_time};
 
 // Send metrics every hour
 const PING_INTERVAL_FOR_CALLING_METRICS_REST_API: Duration = Duration::from_secs(60 * 60);
 const CYCLES_TO_SEND_ALONG_WITH_EVERY_REQUEST: u128 = 1_000_000_000;
 
-pub fn enqueue_timer_for_calling_metrics_rest_api(url_to_ping: String) {
+/// `get_event_counters` is read on every ping so that the latest counts go out alongside the
+/// cycle balance
+pub fn enqueue_timer_for_calling_metrics_rest_api(
+    url_to_ping: String,
+    get_event_counters: fn() -> Vec<EventCounterSample>,
+) {
     ic_cdk_timers::set_timer_interval(PING_INTERVAL_FOR_CALLING_METRICS_REST_API, move || {
-        ic_cdk::spawn(ping_metrics_rest_api(url_to_ping.clone()))
+        ic_cdk::spawn(ping_metrics_rest_api(
+            url_to_ping.clone(),
+            get_event_counters,
+        ))
     });
 }
 
-async fn ping_metrics_rest_api(url_to_ping: String) {
+async fn ping_metrics_rest_api(
+    url_to_ping: String,
+    get_event_counters: fn() -> Vec<EventCounterSample>,
+) {
     let current_time = system_time::get_current_system_time_from_ic();
 
-    let status = get_my_canister_cycle_balance_and_memory_usage(current_time).await;
+    let status = get_my_canister_cycle_balance_and_memory_usage(current_time)
+        .await
+        .map(|mut status| {
+            status.event_counters = get_event_counters();
+            status
+        });
 
     let request_arg = CanisterHttpRequestArgument {
         url: url_to_ping,
@@ -46,6 +62,7 @@ pub struct CanisterStatus {
     pub idle_cycles_burned_per_day: i64,
     pub memory_size: i64,
     pub timestamp: SystemTime,
+    pub event_counters: Vec<EventCounterSample>,
 }
 
 #[derive(Serialize)]
@@ -77,5 +94,6 @@ pub async fn get_my_canister_cycle_balance_and_memory_usage(
             .try_into()
             .unwrap(),
         timestamp: current_time,
+        event_counters: vec![],
     })
 }
@@ -0,0 +1,312 @@
+use std::collections::BTreeMap;
+
+use sha2::{Digest, Sha256};
+
+pub type Hash = [u8; 32];
+
+/// Minimal version of the IC's hash tree, enough to certify a single labeled map of
+/// value hashes and to hand out a witness for one of its keys.
+///
+/// See https://internetcomputer.org/docs/current/references/ic-interface-spec#certification-encoding
+#[derive(Debug, PartialEq, Eq)]
+pub enum HashTree {
+    Empty,
+    Fork(Box<HashTree>, Box<HashTree>),
+    Labeled(Vec<u8>, Box<HashTree>),
+    Leaf(Vec<u8>),
+    Pruned(Hash),
+}
+
+impl HashTree {
+    pub fn reconstruct(&self) -> Hash {
+        match self {
+            HashTree::Empty => hash_with_domain_separator(b"ic-hashtree-empty", &[]),
+            HashTree::Fork(left, right) => hash_with_domain_separator(
+                b"ic-hashtree-fork",
+                &[&left.reconstruct(), &right.reconstruct()],
+            ),
+            HashTree::Labeled(label, subtree) => {
+                hash_with_domain_separator(b"ic-hashtree-labeled", &[label, &subtree.reconstruct()])
+            }
+            HashTree::Leaf(value) => hash_with_domain_separator(b"ic-hashtree-leaf", &[value]),
+            HashTree::Pruned(hash) => *hash,
+        }
+    }
+
+    /// Self describing CBOR, the encoding boundary nodes and agents expect witnesses in
+    pub fn serialize_to_cbor(&self) -> Vec<u8> {
+        // * self describe tag 55799
+        let mut cbor = vec![0xd9, 0xd9, 0xf7];
+        self.write_cbor(&mut cbor);
+        cbor
+    }
+
+    fn write_cbor(&self, cbor: &mut Vec<u8>) {
+        match self {
+            HashTree::Empty => {
+                write_cbor_header(cbor, CBOR_MAJOR_TYPE_ARRAY, 1);
+                write_cbor_header(cbor, CBOR_MAJOR_TYPE_UNSIGNED_INTEGER, 0);
+            }
+            HashTree::Fork(left, right) => {
+                write_cbor_header(cbor, CBOR_MAJOR_TYPE_ARRAY, 3);
+                write_cbor_header(cbor, CBOR_MAJOR_TYPE_UNSIGNED_INTEGER, 1);
+                left.write_cbor(cbor);
+                right.write_cbor(cbor);
+            }
+            HashTree::Labeled(label, subtree) => {
+                write_cbor_header(cbor, CBOR_MAJOR_TYPE_ARRAY, 3);
+                write_cbor_header(cbor, CBOR_MAJOR_TYPE_UNSIGNED_INTEGER, 2);
+                write_cbor_bytes(cbor, label);
+                subtree.write_cbor(cbor);
+            }
+            HashTree::Leaf(value) => {
+                write_cbor_header(cbor, CBOR_MAJOR_TYPE_ARRAY, 2);
+                write_cbor_header(cbor, CBOR_MAJOR_TYPE_UNSIGNED_INTEGER, 3);
+                write_cbor_bytes(cbor, value);
+            }
+            HashTree::Pruned(hash) => {
+                write_cbor_header(cbor, CBOR_MAJOR_TYPE_ARRAY, 2);
+                write_cbor_header(cbor, CBOR_MAJOR_TYPE_UNSIGNED_INTEGER, 4);
+                write_cbor_bytes(cbor, hash);
+            }
+        }
+    }
+}
+
+/// Certifies `key -> value hash` entries under a single top level label. The root hash is
+/// what goes into the canister's certified data.
+///
+/// Entries are laid out as a balanced tree of forks over the keys in sorted order, so that
+/// the witness for any one key only carries a logarithmic number of pruned hashes.
+pub struct CertifiedMap {
+    label: Vec<u8>,
+    entries: BTreeMap<Vec<u8>, Hash>,
+}
+
+impl CertifiedMap {
+    pub fn new(label: &[u8]) -> Self {
+        Self {
+            label: label.to_vec(),
+            entries: BTreeMap::new(),
+        }
+    }
+
+    pub fn insert(&mut self, key: Vec<u8>, value_hash: Hash) {
+        self.entries.insert(key, value_hash);
+    }
+
+    pub fn remove(&mut self, key: &[u8]) -> Option<Hash> {
+        self.entries.remove(key)
+    }
+
+    pub fn get(&self, key: &[u8]) -> Option<&Hash> {
+        self.entries.get(key)
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    pub fn root_hash(&self) -> Hash {
+        self.witness(None).reconstruct()
+    }
+
+    /// Tree with every entry other than `key` pruned. Proves absence when `key` isn't in
+    /// the map, since its neighbours are pruned all the same.
+    pub fn witness_for(&self, key: &[u8]) -> HashTree {
+        self.witness(Some(key))
+    }
+
+    fn witness(&self, key: Option<&[u8]>) -> HashTree {
+        let entries: Vec<(&Vec<u8>, &Hash)> = self.entries.iter().collect();
+
+        HashTree::Labeled(
+            self.label.clone(),
+            Box::new(build_subtree(&entries, key, true)),
+        )
+    }
+}
+
+fn build_subtree(entries: &[(&Vec<u8>, &Hash)], key: Option<&[u8]>, reveal: bool) -> HashTree {
+    let subtree = match entries {
+        [] => HashTree::Empty,
+        [(entry_key, value_hash)] => HashTree::Labeled(
+            entry_key.to_vec(),
+            Box::new(HashTree::Leaf(value_hash.to_vec())),
+        ),
+        _ => {
+            let (left, right) = entries.split_at(entries.len() / 2);
+            let key_is_on_the_left = key.is_some_and(|key| key < right[0].0.as_slice());
+
+            HashTree::Fork(
+                Box::new(build_subtree(left, key, key_is_on_the_left)),
+                Box::new(build_subtree(
+                    right,
+                    key,
+                    key.is_some() && !key_is_on_the_left,
+                )),
+            )
+        }
+    };
+
+    if reveal || key.is_none() {
+        subtree
+    } else {
+        HashTree::Pruned(subtree.reconstruct())
+    }
+}
+
+fn hash_with_domain_separator(domain_separator: &[u8], parts: &[&[u8]]) -> Hash {
+    let mut hasher = Sha256::new();
+    hasher.update([domain_separator.len() as u8]);
+    hasher.update(domain_separator);
+    parts.iter().for_each(|part| hasher.update(part));
+    hasher.finalize().into()
+}
+
+const CBOR_MAJOR_TYPE_UNSIGNED_INTEGER: u8 = 0;
+const CBOR_MAJOR_TYPE_BYTE_STRING: u8 = 2;
+const CBOR_MAJOR_TYPE_ARRAY: u8 = 4;
+
+fn write_cbor_header(cbor: &mut Vec<u8>, major_type: u8, value: u64) {
+    let major_type = major_type << 5;
+    match value {
+        0..=23 => cbor.push(major_type | value as u8),
+        24..=0xff => cbor.extend([major_type | 24, value as u8]),
+        0x100..=0xffff => {
+            cbor.push(major_type | 25);
+            cbor.extend((value as u16).to_be_bytes());
+        }
+        0x1_0000..=0xffff_ffff => {
+            cbor.push(major_type | 26);
+            cbor.extend((value as u32).to_be_bytes());
+        }
+        _ => {
+            cbor.push(major_type | 27);
+            cbor.extend(value.to_be_bytes());
+        }
+    }
+}
+
+fn write_cbor_bytes(cbor: &mut Vec<u8>, bytes: &[u8]) {
+    write_cbor_header(cbor, CBOR_MAJOR_TYPE_BYTE_STRING, bytes.len() as u64);
+    cbor.extend_from_slice(bytes);
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn lookup<'a>(hash_tree: &'a HashTree, path: &[&[u8]]) -> Option<&'a [u8]> {
+        match (hash_tree, path) {
+            (HashTree::Leaf(value), []) => Some(value.as_slice()),
+            (HashTree::Fork(left, right), _) => lookup(left, path).or_else(|| lookup(right, path)),
+            (HashTree::Labeled(label, subtree), [first, rest @ ..]) if label == first => {
+                lookup(subtree, rest)
+            }
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn test_certified_map_witness() {
+        let mut certified_map = CertifiedMap::new(b"post_details");
+        let empty_root_hash = certified_map.root_hash();
+
+        (0_u64..7).for_each(|post_id| {
+            certified_map.insert(post_id.to_be_bytes().to_vec(), [post_id as u8; 32]);
+        });
+        let root_hash = certified_map.root_hash();
+        assert_ne!(root_hash, empty_root_hash);
+
+        (0_u64..7).for_each(|post_id| {
+            let witness = certified_map.witness_for(&post_id.to_be_bytes());
+
+            assert_eq!(witness.reconstruct(), root_hash);
+            assert_eq!(
+                lookup(&witness, &[b"post_details", &post_id.to_be_bytes()]),
+                Some([post_id as u8; 32].as_slice())
+            );
+            // * every other entry is pruned
+            assert_eq!(
+                lookup(
+                    &witness,
+                    &[b"post_details", &((post_id + 1) % 7).to_be_bytes()]
+                ),
+                None
+            );
+        });
+
+        let witness = certified_map.witness_for(&10_u64.to_be_bytes());
+        assert_eq!(witness.reconstruct(), root_hash);
+        assert_eq!(
+            lookup(&witness, &[b"post_details", &10_u64.to_be_bytes()]),
+            None
+        );
+
+        certified_map.insert(3_u64.to_be_bytes().to_vec(), [42; 32]);
+        assert_ne!(certified_map.root_hash(), root_hash);
+        certified_map.remove(&3_u64.to_be_bytes());
+        certified_map.insert(3_u64.to_be_bytes().to_vec(), [3; 32]);
+        assert_eq!(certified_map.root_hash(), root_hash);
+    }
+
+    #[test]
+    fn test_hash_tree_reconstruct() {
+        // * example from the interface spec
+        let hash_tree = HashTree::Fork(
+            Box::new(HashTree::Fork(
+                Box::new(HashTree::Labeled(
+                    b"a".to_vec(),
+                    Box::new(HashTree::Fork(
+                        Box::new(HashTree::Fork(
+                            Box::new(HashTree::Labeled(
+                                b"x".to_vec(),
+                                Box::new(HashTree::Leaf(b"hello".to_vec())),
+                            )),
+                            Box::new(HashTree::Empty),
+                        )),
+                        Box::new(HashTree::Labeled(
+                            b"y".to_vec(),
+                            Box::new(HashTree::Leaf(b"world".to_vec())),
+                        )),
+                    )),
+                )),
+                Box::new(HashTree::Labeled(
+                    b"b".to_vec(),
+                    Box::new(HashTree::Leaf(b"good".to_vec())),
+                )),
+            )),
+            Box::new(HashTree::Fork(
+                Box::new(HashTree::Labeled(b"c".to_vec(), Box::new(HashTree::Empty))),
+                Box::new(HashTree::Labeled(
+                    b"d".to_vec(),
+                    Box::new(HashTree::Leaf(b"morning".to_vec())),
+                )),
+            )),
+        );
+
+        assert_eq!(
+            hex::encode(hash_tree.reconstruct()),
+            "eb5c5b2195e62d996b84c9bcc8259d19a83786a2f59e0878cec84c811f669aa0"
+        );
+    }
+
+    #[test]
+    fn test_hash_tree_serialize_to_cbor() {
+        let hash_tree = HashTree::Labeled(
+            b"a".to_vec(),
+            Box::new(HashTree::Fork(
+                Box::new(HashTree::Pruned([0; 32])),
+                Box::new(HashTree::Leaf(b"b".to_vec())),
+            )),
+        );
+
+        let mut expected = vec![0xd9, 0xd9, 0xf7, 0x83, 0x02, 0x41, b'a', 0x83, 0x01];
+        expected.extend([0x82, 0x04, 0x58, 0x20]);
+        expected.extend([0; 32]);
+        expected.extend([0x82, 0x03, 0x41, b'b']);
+
+        assert_eq!(hash_tree.serialize_to_cbor(), expected);
+    }
+}
@@ -0,0 +1,346 @@
+use std::{
+    collections::BTreeMap,
+    time::{Duration, SystemTime},
+};
+
+use candid::{CandidType, Deserialize, Principal};
+use serde::Serialize;
+
+use crate::pagination::{get_page, Page};
+
+pub type DeferredJobId = u64;
+
+// * Results are dropped this long after the job completes, whether they were fetched or not
+pub const DEFERRED_JOB_RESULTS_EXPIRY: Duration = Duration::from_secs(24 * 60 * 60);
+pub const MAX_DEFERRED_JOBS_IN_PROGRESS_PER_REQUESTER: usize = 2;
+
+/// Work that is too heavy for a single message, e.g. a rollup over every post. It is run one
+/// chunk per message by a timer until it reports that it is done.
+pub trait DeferredJob {
+    type Context;
+    type ResultItem;
+
+    /// Runs the next `chunk_size` steps of the job, appending whatever they produce to
+    /// `results`. Returns true once the job is done.
+    fn run_next_chunk(
+        &mut self,
+        context: &Self::Context,
+        chunk_size: usize,
+        results: &mut Vec<Self::ResultItem>,
+    ) -> bool;
+}
+
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug, PartialEq, Eq)]
+pub enum DeferredJobStatus {
+    Queued,
+    Running,
+    Completed { expires_at: SystemTime },
+}
+
+#[derive(CandidType, Deserialize, PartialEq, Eq, Debug)]
+pub enum DeferredJobError {
+    Unauthorized,
+    TooManyJobsInProgress,
+    // * Also returned once a job's results have expired
+    JobNotFound,
+}
+
+// * Enums shared by every canister are numbered 90 to 99
+crate::error_codes! {
+    DeferredJobError {
+        Unauthorized => (9001, Unauthorized, false),
+        TooManyJobsInProgress => (9002, LimitExceeded, true),
+        JobNotFound => (9003, NotFound, false),
+    }
+}
+
+/// The status of a job and a page of what it produced so far. Results only grow while the
+/// job runs, so pages fetched before it completes stay valid.
+#[derive(CandidType, Deserialize, Debug, PartialEq, Eq)]
+pub struct DeferredJobResults<T> {
+    pub status: DeferredJobStatus,
+    pub results: Page<T>,
+}
+
+#[derive(Deserialize, Serialize)]
+#[serde(bound(
+    serialize = "J: Serialize, J::ResultItem: Serialize",
+    deserialize = "J: Deserialize<'de>, J::ResultItem: Deserialize<'de>"
+))]
+pub struct DeferredJobEntry<J: DeferredJob> {
+    pub requested_by: Principal,
+    pub enqueued_at: SystemTime,
+    pub status: DeferredJobStatus,
+    pub job: J,
+    pub results: Vec<J::ResultItem>,
+}
+
+impl<J: DeferredJob> DeferredJobEntry<J> {
+    /// Returns true once the job is done
+    pub fn run_next_chunk(
+        &mut self,
+        context: &J::Context,
+        chunk_size: usize,
+        current_time: &SystemTime,
+    ) -> bool {
+        let is_done = self
+            .job
+            .run_next_chunk(context, chunk_size, &mut self.results);
+
+        self.status = if is_done {
+            DeferredJobStatus::Completed {
+                expires_at: *current_time + DEFERRED_JOB_RESULTS_EXPIRY,
+            }
+        } else {
+            DeferredJobStatus::Running
+        };
+
+        is_done
+    }
+
+    fn is_in_progress(&self) -> bool {
+        !matches!(self.status, DeferredJobStatus::Completed { .. })
+    }
+
+    fn has_expired(&self, current_time: &SystemTime) -> bool {
+        match self.status {
+            DeferredJobStatus::Completed { expires_at } => expires_at <= *current_time,
+            _ => false,
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize)]
+#[serde(bound(
+    serialize = "J: Serialize, J::ResultItem: Serialize",
+    deserialize = "J: Deserialize<'de>, J::ResultItem: Deserialize<'de>"
+))]
+pub struct DeferredJobs<J: DeferredJob> {
+    next_job_id: DeferredJobId,
+    jobs: BTreeMap<DeferredJobId, DeferredJobEntry<J>>,
+}
+
+impl<J: DeferredJob> Default for DeferredJobs<J> {
+    fn default() -> Self {
+        Self {
+            next_job_id: 0,
+            jobs: BTreeMap::default(),
+        }
+    }
+}
+
+impl<J: DeferredJob> DeferredJobs<J> {
+    pub fn enqueue(
+        &mut self,
+        requested_by: Principal,
+        job: J,
+        current_time: &SystemTime,
+    ) -> Result<DeferredJobId, DeferredJobError> {
+        self.remove_expired_jobs(current_time);
+
+        let number_of_jobs_in_progress = self
+            .jobs
+            .values()
+            .filter(|entry| entry.requested_by == requested_by && entry.is_in_progress())
+            .count();
+        if number_of_jobs_in_progress >= MAX_DEFERRED_JOBS_IN_PROGRESS_PER_REQUESTER {
+            return Err(DeferredJobError::TooManyJobsInProgress);
+        }
+
+        let job_id = self.next_job_id;
+        self.next_job_id += 1;
+        self.jobs.insert(
+            job_id,
+            DeferredJobEntry {
+                requested_by,
+                enqueued_at: *current_time,
+                status: DeferredJobStatus::Queued,
+                job,
+                results: vec![],
+            },
+        );
+
+        Ok(job_id)
+    }
+
+    pub fn has_jobs_in_progress(&self) -> bool {
+        self.jobs.values().any(DeferredJobEntry::is_in_progress)
+    }
+
+    /// Takes out the oldest job that isn't done yet, so that it can run against state that
+    /// holds this queue. Hand it back with `put_back`.
+    pub fn take_next_job_to_run(&mut self) -> Option<(DeferredJobId, DeferredJobEntry<J>)> {
+        let job_id = self
+            .jobs
+            .iter()
+            .find(|(_, entry)| entry.is_in_progress())
+            .map(|(job_id, _)| *job_id)?;
+
+        self.jobs.remove(&job_id).map(|entry| (job_id, entry))
+    }
+
+    pub fn put_back(&mut self, job_id: DeferredJobId, entry: DeferredJobEntry<J>) {
+        self.jobs.insert(job_id, entry);
+    }
+
+    pub fn remove_expired_jobs(&mut self, current_time: &SystemTime) {
+        self.jobs
+            .retain(|_, entry| !entry.has_expired(current_time));
+    }
+
+    /// Only the principal that enqueued a job can read its results
+    pub fn get_results(
+        &self,
+        job_id: DeferredJobId,
+        api_caller: &Principal,
+        from_inclusive_index: u64,
+        page_size: u64,
+        current_time: &SystemTime,
+    ) -> Result<DeferredJobResults<J::ResultItem>, DeferredJobError>
+    where
+        J::ResultItem: Clone,
+    {
+        let entry = self
+            .jobs
+            .get(&job_id)
+            .filter(|entry| !entry.has_expired(current_time))
+            .ok_or(DeferredJobError::JobNotFound)?;
+
+        if entry.requested_by != *api_caller {
+            return Err(DeferredJobError::Unauthorized);
+        }
+
+        Ok(DeferredJobResults {
+            status: entry.status.clone(),
+            results: get_page(
+                entry.results.iter().cloned(),
+                from_inclusive_index,
+                page_size,
+            ),
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use test_utils::setup::test_constants::{
+        get_mock_user_alice_principal_id, get_mock_user_bob_principal_id,
+    };
+
+    use super::*;
+
+    // * Counts up to `up_to`, one number per step
+    struct CountingJob {
+        next: u64,
+        up_to: u64,
+    }
+
+    impl DeferredJob for CountingJob {
+        type Context = ();
+        type ResultItem = u64;
+
+        fn run_next_chunk(
+            &mut self,
+            _context: &(),
+            chunk_size: usize,
+            results: &mut Vec<u64>,
+        ) -> bool {
+            let chunk_end = (self.next + chunk_size as u64).min(self.up_to);
+            results.extend(self.next..chunk_end);
+            self.next = chunk_end;
+
+            self.next == self.up_to
+        }
+    }
+
+    fn run_next_chunk(jobs: &mut DeferredJobs<CountingJob>, current_time: &SystemTime) -> bool {
+        let Some((job_id, mut entry)) = jobs.take_next_job_to_run() else {
+            return false;
+        };
+        entry.run_next_chunk(&(), 3, current_time);
+        jobs.put_back(job_id, entry);
+
+        true
+    }
+
+    #[test]
+    fn test_deferred_jobs_run_in_chunks_and_expire() {
+        let mut jobs = DeferredJobs::default();
+        let alice = get_mock_user_alice_principal_id();
+        let current_time = SystemTime::UNIX_EPOCH;
+
+        let job_id = jobs
+            .enqueue(alice, CountingJob { next: 0, up_to: 5 }, &current_time)
+            .unwrap();
+
+        assert!(run_next_chunk(&mut jobs, &current_time));
+        let results = jobs
+            .get_results(job_id, &alice, 0, 10, &current_time)
+            .unwrap();
+        assert_eq!(results.status, DeferredJobStatus::Running);
+        assert_eq!(results.results.items, vec![0, 1, 2]);
+
+        assert!(run_next_chunk(&mut jobs, &current_time));
+        assert!(!jobs.has_jobs_in_progress());
+        assert!(!run_next_chunk(&mut jobs, &current_time));
+
+        let results = jobs
+            .get_results(job_id, &alice, 3, 10, &current_time)
+            .unwrap();
+        assert_eq!(
+            results.status,
+            DeferredJobStatus::Completed {
+                expires_at: current_time + DEFERRED_JOB_RESULTS_EXPIRY
+            }
+        );
+        assert_eq!(results.results.items, vec![3, 4]);
+        assert_eq!(results.results.total_count, 5);
+
+        assert_eq!(
+            jobs.get_results(
+                job_id,
+                &get_mock_user_bob_principal_id(),
+                0,
+                10,
+                &current_time
+            ),
+            Err(DeferredJobError::Unauthorized)
+        );
+
+        let expired_at = current_time + DEFERRED_JOB_RESULTS_EXPIRY;
+        assert_eq!(
+            jobs.get_results(job_id, &alice, 0, 10, &expired_at),
+            Err(DeferredJobError::JobNotFound)
+        );
+    }
+
+    #[test]
+    fn test_enqueue_limits_jobs_in_progress_per_requester() {
+        let mut jobs = DeferredJobs::default();
+        let alice = get_mock_user_alice_principal_id();
+        let current_time = SystemTime::UNIX_EPOCH;
+
+        (0..MAX_DEFERRED_JOBS_IN_PROGRESS_PER_REQUESTER).for_each(|_| {
+            assert!(jobs
+                .enqueue(alice, CountingJob { next: 0, up_to: 1 }, &current_time)
+                .is_ok());
+        });
+        assert_eq!(
+            jobs.enqueue(alice, CountingJob { next: 0, up_to: 1 }, &current_time),
+            Err(DeferredJobError::TooManyJobsInProgress)
+        );
+        assert!(jobs
+            .enqueue(
+                get_mock_user_bob_principal_id(),
+                CountingJob { next: 0, up_to: 1 },
+                &current_time
+            )
+            .is_ok());
+
+        // * finished jobs no longer count towards the limit
+        assert!(run_next_chunk(&mut jobs, &current_time));
+        assert!(jobs
+            .enqueue(alice, CountingJob { next: 0, up_to: 1 }, &current_time)
+            .is_ok());
+    }
+}
@@ -0,0 +1,194 @@
+use candid::{CandidType, Deserialize};
+use serde::Serialize;
+
+/// Broad kind of failure, for clients that handle whole groups of errors the same way
+#[derive(CandidType, Deserialize, Serialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ErrorCategory {
+    Unauthenticated,
+    Unauthorized,
+    InvalidInput,
+    NotFound,
+    // * The request is fine, but what it acts on is in a state that doesn't allow it
+    Conflict,
+    LimitExceeded,
+    InsufficientFunds,
+    // * Another canister, or this one, couldn't be reached or couldn't answer at the time
+    Unavailable,
+}
+
+/// Stable, machine readable identity of an error variant. `code` never changes or gets reused
+/// once it has shipped, so clients can key translations and retry policies on it.
+#[derive(CandidType, Deserialize, Serialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ErrorCode {
+    pub code: u32,
+    pub category: ErrorCategory,
+    // * Sending the same request again later may succeed
+    pub is_retryable: bool,
+}
+
+/// One variant of an error enum returned by a canister's API, and its code
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug, PartialEq, Eq)]
+pub struct ErrorCodeRegistryEntry {
+    pub error_type: String,
+    pub variant: String,
+    pub error_code: ErrorCode,
+}
+
+/// Implemented through [`error_codes!`](crate::error_codes) for every error enum returned by
+/// a public API
+pub trait HasErrorCode {
+    fn error_code(&self) -> ErrorCode;
+
+    /// Every variant of the error and its code, in declaration order
+    fn error_code_registry_entries() -> Vec<ErrorCodeRegistryEntry>;
+}
+
+/// Assigns codes to the variants of error enums, as
+/// `ErrorType { Variant => (code, Category, is_retryable), .. }`. The generated match is
+/// exhaustive, so a variant added without a code fails to compile.
+///
+/// Codes are the number of the enum times 100 plus the number of the variant. New enums take
+/// the next free number in their canister's range, new variants the next free one in their
+/// enum, whatever their position in the enum. The individual canister's first range, 10 to
+/// 49, is full, so its newer enums number on from 100.
+#[macro_export]
+macro_rules! error_codes {
+    ($(
+        $error_type:ident {
+            $($variant:ident => ($code:literal, $category:ident, $is_retryable:literal)),* $(,)?
+        }
+    )*) => {
+        $(
+            impl $crate::common::types::error_code::HasErrorCode for $error_type {
+                fn error_code(&self) -> $crate::common::types::error_code::ErrorCode {
+                    match self {
+                        $(
+                            Self::$variant { .. } => $crate::common::types::error_code::ErrorCode {
+                                code: $code,
+                                category: $crate::common::types::error_code::ErrorCategory::$category,
+                                is_retryable: $is_retryable,
+                            },
+                        )*
+                    }
+                }
+
+                fn error_code_registry_entries(
+                ) -> Vec<$crate::common::types::error_code::ErrorCodeRegistryEntry> {
+                    vec![
+                        $(
+                            $crate::common::types::error_code::ErrorCodeRegistryEntry {
+                                error_type: stringify!($error_type).to_string(),
+                                variant: stringify!($variant).to_string(),
+                                error_code: $crate::common::types::error_code::ErrorCode {
+                                    code: $code,
+                                    category:
+                                        $crate::common::types::error_code::ErrorCategory::$category,
+                                    is_retryable: $is_retryable,
+                                },
+                            },
+                        )*
+                    ]
+                }
+            }
+        )*
+    };
+}
+
+/// Codes that more than one entry claims. A canister's registry is expected to have none.
+pub fn get_duplicate_error_codes(registry: &[ErrorCodeRegistryEntry]) -> Vec<u32> {
+    let mut codes: Vec<u32> = registry.iter().map(|entry| entry.error_code.code).collect();
+    codes.sort_unstable();
+
+    let mut duplicate_codes: Vec<u32> = codes
+        .windows(2)
+        .filter(|pair| pair[0] == pair[1])
+        .map(|pair| pair[0])
+        .collect();
+    duplicate_codes.dedup();
+
+    duplicate_codes
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[allow(dead_code)]
+    enum ExampleError {
+        Unauthorized,
+        RequestedTooSoon { retry_after_seconds: u64 },
+        Nested(ErrorCategory),
+    }
+
+    error_codes! {
+        ExampleError {
+            Unauthorized => (9901, Unauthorized, false),
+            RequestedTooSoon => (9902, LimitExceeded, true),
+            Nested => (9903, Conflict, false),
+        }
+    }
+
+    #[test]
+    fn test_error_codes() {
+        assert_eq!(
+            ExampleError::RequestedTooSoon {
+                retry_after_seconds: 10
+            }
+            .error_code(),
+            ErrorCode {
+                code: 9902,
+                category: ErrorCategory::LimitExceeded,
+                is_retryable: true,
+            }
+        );
+        assert_eq!(
+            ExampleError::Nested(ErrorCategory::NotFound)
+                .error_code()
+                .code,
+            9903
+        );
+
+        let registry = ExampleError::error_code_registry_entries();
+        assert_eq!(
+            registry
+                .iter()
+                .map(|entry| (entry.error_type.as_str(), entry.variant.as_str()))
+                .collect::<Vec<_>>(),
+            vec![
+                ("ExampleError", "Unauthorized"),
+                ("ExampleError", "RequestedTooSoon"),
+                ("ExampleError", "Nested"),
+            ]
+        );
+        assert_eq!(
+            registry[0].error_code,
+            ExampleError::Unauthorized.error_code()
+        );
+        assert!(get_duplicate_error_codes(&registry).is_empty());
+    }
+
+    #[test]
+    fn test_get_duplicate_error_codes() {
+        let get_entry = |code: u32| ErrorCodeRegistryEntry {
+            error_type: "ExampleError".into(),
+            variant: "Unauthorized".into(),
+            error_code: ErrorCode {
+                code,
+                category: ErrorCategory::Unauthorized,
+                is_retryable: false,
+            },
+        };
+
+        assert_eq!(
+            get_duplicate_error_codes(&[
+                get_entry(3),
+                get_entry(1),
+                get_entry(3),
+                get_entry(2),
+                get_entry(3),
+                get_entry(1),
+            ]),
+            vec![1, 3]
+        );
+    }
+}
@@ -0,0 +1,145 @@
+use std::collections::BTreeMap;
+
+use candid::{CandidType, Deserialize};
+use serde::Serialize;
+
+/// Counters that alert rules are defined against. They only ever go up, and each is split by
+/// at most one label whose values come from a small fixed set, so the number of series stays
+/// low.
+#[derive(
+    CandidType, Deserialize, Serialize, Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord,
+)]
+pub enum EventCounterName {
+    BetRejectionsTotal,
+    SettlementFailuresTotal,
+    UpgradeFailuresTotal,
+    QueuedCallFailuresTotal,
+}
+
+impl EventCounterName {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::BetRejectionsTotal => "bet_rejections_total",
+            Self::SettlementFailuresTotal => "settlement_failures_total",
+            Self::UpgradeFailuresTotal => "upgrade_failures_total",
+            Self::QueuedCallFailuresTotal => "queued_call_failures_total",
+        }
+    }
+
+    pub fn get_label_name(&self) -> Option<&'static str> {
+        match self {
+            Self::BetRejectionsTotal => Some("reason"),
+            Self::SettlementFailuresTotal => Some("settlement"),
+            Self::UpgradeFailuresTotal => None,
+            // * "retried" or "dead_lettered"
+            Self::QueuedCallFailuresTotal => Some("outcome"),
+        }
+    }
+}
+
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug, PartialEq, Eq)]
+pub struct EventCounterSample {
+    pub name: String,
+    pub labels: Vec<(String, String)>,
+    pub value: u64,
+}
+
+#[derive(Default, Serialize, Deserialize, Debug)]
+pub struct EventCounters {
+    // * Key is (counter, label value). Counters without a label use an empty label value.
+    counts: BTreeMap<(EventCounterName, String), u64>,
+}
+
+impl EventCounters {
+    /// Label values are static so that they can't be built from ids or user input
+    pub fn increment(&mut self, counter: EventCounterName, label_value: &'static str) {
+        let label_value = match counter.get_label_name() {
+            Some(_) => label_value,
+            None => "",
+        };
+
+        let count = self
+            .counts
+            .entry((counter, label_value.to_string()))
+            .or_default();
+        *count = count.saturating_add(1);
+    }
+
+    pub fn get(&self, counter: EventCounterName, label_value: &str) -> u64 {
+        self.counts
+            .get(&(counter, label_value.to_string()))
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Summed across label values
+    pub fn get_total(&self, counter: EventCounterName) -> u64 {
+        self.counts
+            .iter()
+            .filter(|((name, _), _)| *name == counter)
+            .fold(0, |total, (_, count)| total.saturating_add(*count))
+    }
+
+    pub fn get_samples(&self) -> Vec<EventCounterSample> {
+        self.counts
+            .iter()
+            .map(|((counter, label_value), count)| EventCounterSample {
+                name: counter.as_str().to_string(),
+                labels: counter
+                    .get_label_name()
+                    .map(|label_name| vec![(label_name.to_string(), label_value.clone())])
+                    .unwrap_or_default(),
+                value: *count,
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_event_counters() {
+        let mut event_counters = EventCounters::default();
+
+        event_counters.increment(EventCounterName::BetRejectionsTotal, "insufficient_balance");
+        event_counters.increment(EventCounterName::BetRejectionsTotal, "insufficient_balance");
+        event_counters.increment(EventCounterName::BetRejectionsTotal, "betting_closed");
+        // * label values passed for unlabelled counters are dropped
+        event_counters.increment(EventCounterName::UpgradeFailuresTotal, "ignored");
+
+        assert_eq!(
+            event_counters.get(EventCounterName::BetRejectionsTotal, "insufficient_balance"),
+            2
+        );
+        assert_eq!(
+            event_counters.get_total(EventCounterName::BetRejectionsTotal),
+            3
+        );
+        assert_eq!(
+            event_counters.get_total(EventCounterName::SettlementFailuresTotal),
+            0
+        );
+        assert_eq!(
+            event_counters.get_samples(),
+            vec![
+                EventCounterSample {
+                    name: "bet_rejections_total".to_string(),
+                    labels: vec![("reason".to_string(), "betting_closed".to_string())],
+                    value: 1,
+                },
+                EventCounterSample {
+                    name: "bet_rejections_total".to_string(),
+                    labels: vec![("reason".to_string(), "insufficient_balance".to_string())],
+                    value: 2,
+                },
+                EventCounterSample {
+                    name: "upgrade_failures_total".to_string(),
+                    labels: vec![],
+                    value: 1,
+                },
+            ]
+        );
+    }
+}
@@ -0,0 +1,134 @@
+use std::{borrow::Cow, fmt, str::FromStr};
+
+use candid::{CandidType, Decode, Deserialize, Encode, Principal};
+use ic_stable_structures::{BoundedStorable, Storable};
+use serde::Serialize;
+
+use super::app_primitive_type::PostId;
+
+/// Identifies a post anywhere on the platform. Post ids are only unique within the
+/// individual user canister that published the post, so the canister id goes along with it.
+#[derive(
+    CandidType, Deserialize, Serialize, Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash,
+)]
+pub struct GlobalPostRef {
+    pub canister_id: Principal,
+    pub post_id: PostId,
+}
+
+impl GlobalPostRef {
+    pub fn new(canister_id: Principal, post_id: PostId) -> Self {
+        Self {
+            canister_id,
+            post_id,
+        }
+    }
+}
+
+impl From<(Principal, PostId)> for GlobalPostRef {
+    fn from((canister_id, post_id): (Principal, PostId)) -> Self {
+        Self::new(canister_id, post_id)
+    }
+}
+
+impl From<GlobalPostRef> for (Principal, PostId) {
+    fn from(global_post_ref: GlobalPostRef) -> Self {
+        (global_post_ref.canister_id, global_post_ref.post_id)
+    }
+}
+
+/// `<canister id>/<post id>`, the form posts take in URLs
+impl fmt::Display for GlobalPostRef {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", self.canister_id.to_text(), self.post_id)
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum GlobalPostRefParseError {
+    MissingSeparator,
+    InvalidCanisterId,
+    InvalidPostId,
+}
+
+impl FromStr for GlobalPostRef {
+    type Err = GlobalPostRefParseError;
+
+    fn from_str(global_post_ref: &str) -> Result<Self, Self::Err> {
+        let (canister_id, post_id) = global_post_ref
+            .split_once('/')
+            .ok_or(GlobalPostRefParseError::MissingSeparator)?;
+
+        let canister_id = Principal::from_text(canister_id)
+            .map_err(|_| GlobalPostRefParseError::InvalidCanisterId)?;
+        // * u64's parser lets a leading + through, which would give the same post two forms
+        if post_id.starts_with('+') {
+            return Err(GlobalPostRefParseError::InvalidPostId);
+        }
+        let post_id = post_id
+            .parse::<PostId>()
+            .map_err(|_| GlobalPostRefParseError::InvalidPostId)?;
+
+        Ok(Self::new(canister_id, post_id))
+    }
+}
+
+impl Storable for GlobalPostRef {
+    fn to_bytes(&self) -> Cow<'_, [u8]> {
+        Cow::Owned(Encode!(self).unwrap())
+    }
+
+    fn from_bytes(bytes: Cow<[u8]>) -> Self {
+        Decode!(bytes.as_ref(), Self).unwrap()
+    }
+}
+
+impl BoundedStorable for GlobalPostRef {
+    const MAX_SIZE: u32 = 100;
+    const IS_FIXED_SIZE: bool = false;
+}
+
+#[cfg(test)]
+mod test {
+    use test_utils::setup::test_constants::get_mock_user_alice_canister_id;
+
+    use super::*;
+
+    #[test]
+    fn test_global_post_ref_string_form() {
+        let global_post_ref = GlobalPostRef::new(get_mock_user_alice_canister_id(), 42);
+        let string_form = global_post_ref.to_string();
+
+        assert_eq!(
+            string_form,
+            format!("{}/42", get_mock_user_alice_canister_id().to_text())
+        );
+        assert_eq!(string_form.parse(), Ok(global_post_ref));
+
+        assert_eq!(
+            "42".parse::<GlobalPostRef>(),
+            Err(GlobalPostRefParseError::MissingSeparator)
+        );
+        assert_eq!(
+            "not a canister/42".parse::<GlobalPostRef>(),
+            Err(GlobalPostRefParseError::InvalidCanisterId)
+        );
+        assert_eq!(
+            format!("{}/-1", get_mock_user_alice_canister_id().to_text()).parse::<GlobalPostRef>(),
+            Err(GlobalPostRefParseError::InvalidPostId)
+        );
+        assert_eq!(
+            format!("{}/+42", get_mock_user_alice_canister_id().to_text()).parse::<GlobalPostRef>(),
+            Err(GlobalPostRefParseError::InvalidPostId)
+        );
+    }
+
+    #[test]
+    fn test_global_post_ref_storable() {
+        let global_post_ref = GlobalPostRef::new(Principal::self_authenticating([1]), u64::MAX);
+        let bytes = global_post_ref.to_bytes();
+
+        assert!(bytes.len() <= GlobalPostRef::MAX_SIZE as usize);
+        assert_eq!(GlobalPostRef::from_bytes(bytes), global_post_ref);
+    }
+}
@@ -1,19 +1,368 @@
-use std::collections::HashMap;
+use std::{collections::HashMap, fmt};
 
-use candid::{CandidType, Deserialize, Principal};
-use serde::Serialize;
+use candid::{
+    types::{Serializer, Type},
+    CandidType, Deserialize, Principal,
+};
+use serde::{
+    de::{MapAccess, SeqAccess, Visitor},
+    ser::SerializeSeq,
+    Deserializer, Serialize,
+};
+use sha2::{Digest, Sha256};
 
-#[derive(CandidType, Deserialize, PartialEq, Eq, Hash, Serialize, Copy, Clone)]
+#[derive(CandidType, Deserialize, PartialEq, Eq, Hash, Serialize, Copy, Clone, Debug)]
 pub enum KnownPrincipalType {
     UserIdGlobalSuperAdmin,
     CanisterIdConfiguration,
     CanisterIdDataBackup,
+    CanisterIdLeaderboard,
     CanisterIdPostCache,
     CanisterIdProjectMemberIndex,
     CanisterIdRootCanister,
     CanisterIdSNSController,
+    CanisterIdTokenLedger,
     CanisterIdTopicCacheIndex,
+    CanisterIdTreasury,
     CanisterIdUserIndex,
+    CanisterIdWebSocketGateway,
 }
 
-pub type KnownPrincipalMap = HashMap<KnownPrincipalType, Principal>;
+/// The principals filling each known role. A role can be filled by several instances, e.g.
+/// post cache shards or user indexes on different subnets. The last instance of a role is its
+/// primary, which is what `get` returns to callers that only talk to one instance.
+///
+/// On the wire this is the same `vec record { KnownPrincipalType; principal }` a plain map
+/// used to be, with one record per instance and the primary listed last. Decoders that predate
+/// multiple instances keep the last record of a role, so they still end up with the primary.
+#[derive(Default, Clone, PartialEq, Eq, Debug)]
+pub struct KnownPrincipalMap {
+    instances: HashMap<KnownPrincipalType, Vec<Principal>>,
+}
+
+impl KnownPrincipalMap {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The primary instance of a role
+    pub fn get(&self, principal_type: &KnownPrincipalType) -> Option<&Principal> {
+        self.get_all(principal_type).last()
+    }
+
+    /// Every instance of a role, primary last
+    pub fn get_all(&self, principal_type: &KnownPrincipalType) -> &[Principal] {
+        self.instances
+            .get(principal_type)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    pub fn contains(&self, principal_type: &KnownPrincipalType, principal_id: &Principal) -> bool {
+        self.get_all(principal_type).contains(principal_id)
+    }
+
+    /// Picks the instance of a role that is responsible for `shard_key`. The same key keeps
+    /// landing on the same instance for as long as the role's instances don't change.
+    pub fn get_for_shard_key(
+        &self,
+        principal_type: &KnownPrincipalType,
+        shard_key: &Principal,
+    ) -> Option<&Principal> {
+        let instances = self.get_all(principal_type);
+        if instances.is_empty() {
+            return None;
+        }
+
+        let digest = Sha256::digest(shard_key.as_slice());
+        let mut hash_prefix = [0u8; 8];
+        hash_prefix.copy_from_slice(&digest[..8]);
+
+        instances.get((u64::from_be_bytes(hash_prefix) % instances.len() as u64) as usize)
+    }
+
+    /// Makes `principal_id` the one and only instance of a role
+    pub fn insert(&mut self, principal_type: KnownPrincipalType, principal_id: Principal) {
+        self.instances.insert(principal_type, vec![principal_id]);
+    }
+
+    /// Adds another instance to a role without changing its primary. The first instance added
+    /// to a role becomes its primary.
+    pub fn add_instance(&mut self, principal_type: KnownPrincipalType, principal_id: Principal) {
+        let instances = self.instances.entry(principal_type).or_default();
+        if instances.contains(&principal_id) {
+            return;
+        }
+
+        let primary_index = instances.len().saturating_sub(1);
+        instances.insert(primary_index, principal_id);
+    }
+
+    /// Returns false if `principal_id` wasn't an instance of the role. Removing the primary
+    /// promotes the instance listed before it.
+    pub fn remove_instance(
+        &mut self,
+        principal_type: &KnownPrincipalType,
+        principal_id: &Principal,
+    ) -> bool {
+        let Some(instances) = self.instances.get_mut(principal_type) else {
+            return false;
+        };
+        let Some(index) = instances
+            .iter()
+            .position(|instance| instance == principal_id)
+        else {
+            return false;
+        };
+
+        instances.remove(index);
+        if instances.is_empty() {
+            self.instances.remove(principal_type);
+        }
+
+        true
+    }
+
+    /// One pair per instance, in wire order
+    pub fn iter(&self) -> impl Iterator<Item = (KnownPrincipalType, Principal)> + '_ {
+        self.instances
+            .iter()
+            .flat_map(|(principal_type, instances)| {
+                instances
+                    .iter()
+                    .map(move |principal_id| (*principal_type, *principal_id))
+            })
+    }
+
+    /// Every instance of every role
+    pub fn values(&self) -> impl Iterator<Item = &Principal> {
+        self.instances.values().flatten()
+    }
+}
+
+/// A later pair for a role becomes its primary, earlier ones stay on as instances. Extending
+/// with the pairs of a `KnownPrincipalMap` rebuilds the same roles.
+impl Extend<(KnownPrincipalType, Principal)> for KnownPrincipalMap {
+    fn extend<T: IntoIterator<Item = (KnownPrincipalType, Principal)>>(&mut self, iter: T) {
+        iter.into_iter().for_each(|(principal_type, principal_id)| {
+            let instances = self.instances.entry(principal_type).or_default();
+            instances.retain(|instance| *instance != principal_id);
+            instances.push(principal_id);
+        });
+    }
+}
+
+impl FromIterator<(KnownPrincipalType, Principal)> for KnownPrincipalMap {
+    fn from_iter<T: IntoIterator<Item = (KnownPrincipalType, Principal)>>(iter: T) -> Self {
+        let mut known_principal_ids = Self::default();
+        known_principal_ids.extend(iter);
+        known_principal_ids
+    }
+}
+
+impl<const N: usize> From<[(KnownPrincipalType, Principal); N]> for KnownPrincipalMap {
+    fn from(pairs: [(KnownPrincipalType, Principal); N]) -> Self {
+        pairs.into_iter().collect()
+    }
+}
+
+impl CandidType for KnownPrincipalMap {
+    fn _ty() -> Type {
+        <Vec<(KnownPrincipalType, Principal)>>::ty()
+    }
+
+    fn idl_serialize<S: Serializer>(&self, serializer: S) -> Result<(), S::Error> {
+        self.iter().collect::<Vec<_>>().idl_serialize(serializer)
+    }
+}
+
+impl Serialize for KnownPrincipalMap {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut seq = serializer.serialize_seq(Some(self.values().count()))?;
+        for pair in self.iter() {
+            seq.serialize_element(&pair)?;
+        }
+        seq.end()
+    }
+}
+
+struct KnownPrincipalMapVisitor;
+
+impl<'de> Visitor<'de> for KnownPrincipalMapVisitor {
+    type Value = KnownPrincipalMap;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a list of known principal type and principal pairs")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let mut known_principal_ids = KnownPrincipalMap::default();
+        while let Some(pair) = seq.next_element::<(KnownPrincipalType, Principal)>()? {
+            known_principal_ids.extend([pair]);
+        }
+        Ok(known_principal_ids)
+    }
+
+    // * Stable memory written before roles could have several instances holds a plain map
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        let mut known_principal_ids = KnownPrincipalMap::default();
+        while let Some(pair) = map.next_entry::<KnownPrincipalType, Principal>()? {
+            known_principal_ids.extend([pair]);
+        }
+        Ok(known_principal_ids)
+    }
+}
+
+impl<'de> Deserialize<'de> for KnownPrincipalMap {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_any(KnownPrincipalMapVisitor)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use candid::{Decode, Encode};
+    use test_utils::setup::test_constants::{
+        get_global_super_admin_principal_id, get_mock_canister_id_post_cache,
+        get_mock_canister_id_user_index, get_mock_user_alice_canister_id,
+        get_mock_user_bob_canister_id,
+    };
+
+    use super::*;
+
+    fn get_map_with_two_post_caches() -> (KnownPrincipalMap, Principal, Principal) {
+        let primary_post_cache = get_mock_canister_id_post_cache();
+        let other_post_cache = get_mock_user_bob_canister_id();
+        let mut known_principal_ids = KnownPrincipalMap::new();
+        known_principal_ids.insert(
+            KnownPrincipalType::UserIdGlobalSuperAdmin,
+            get_global_super_admin_principal_id(),
+        );
+        known_principal_ids
+            .add_instance(KnownPrincipalType::CanisterIdPostCache, primary_post_cache);
+        known_principal_ids.add_instance(KnownPrincipalType::CanisterIdPostCache, other_post_cache);
+
+        (known_principal_ids, primary_post_cache, other_post_cache)
+    }
+
+    #[test]
+    fn test_instances_keep_their_primary() {
+        let (mut known_principal_ids, primary_post_cache, other_post_cache) =
+            get_map_with_two_post_caches();
+
+        assert_eq!(
+            known_principal_ids.get(&KnownPrincipalType::CanisterIdPostCache),
+            Some(&primary_post_cache)
+        );
+        assert_eq!(
+            known_principal_ids.get_all(&KnownPrincipalType::CanisterIdPostCache),
+            &[other_post_cache, primary_post_cache]
+        );
+        assert!(known_principal_ids
+            .contains(&KnownPrincipalType::CanisterIdPostCache, &other_post_cache));
+        assert!(known_principal_ids
+            .get_all(&KnownPrincipalType::CanisterIdUserIndex)
+            .is_empty());
+
+        assert!(known_principal_ids.remove_instance(
+            &KnownPrincipalType::CanisterIdPostCache,
+            &primary_post_cache
+        ));
+        assert_eq!(
+            known_principal_ids.get(&KnownPrincipalType::CanisterIdPostCache),
+            Some(&other_post_cache)
+        );
+
+        known_principal_ids.insert(
+            KnownPrincipalType::CanisterIdPostCache,
+            get_mock_canister_id_post_cache(),
+        );
+        assert_eq!(
+            known_principal_ids.get_all(&KnownPrincipalType::CanisterIdPostCache),
+            &[get_mock_canister_id_post_cache()]
+        );
+    }
+
+    #[test]
+    fn test_get_for_shard_key_is_stable() {
+        let (known_principal_ids, primary_post_cache, other_post_cache) =
+            get_map_with_two_post_caches();
+        let shard_key = get_mock_user_alice_canister_id();
+
+        let selected_post_cache = known_principal_ids
+            .get_for_shard_key(&KnownPrincipalType::CanisterIdPostCache, &shard_key)
+            .copied();
+        assert!(
+            selected_post_cache == Some(primary_post_cache)
+                || selected_post_cache == Some(other_post_cache)
+        );
+        assert_eq!(
+            known_principal_ids
+                .clone()
+                .get_for_shard_key(&KnownPrincipalType::CanisterIdPostCache, &shard_key)
+                .copied(),
+            selected_post_cache
+        );
+        assert_eq!(
+            known_principal_ids
+                .get_for_shard_key(&KnownPrincipalType::CanisterIdUserIndex, &shard_key),
+            None
+        );
+    }
+
+    #[test]
+    fn test_candid_representation_is_backward_compatible() {
+        let (known_principal_ids, primary_post_cache, _) = get_map_with_two_post_caches();
+
+        let encoded = Encode!(&known_principal_ids).unwrap();
+        let decoded_as_plain_map =
+            Decode!(&encoded, HashMap<KnownPrincipalType, Principal>).unwrap();
+        assert_eq!(
+            decoded_as_plain_map.get(&KnownPrincipalType::CanisterIdPostCache),
+            Some(&primary_post_cache)
+        );
+        assert_eq!(
+            Decode!(&encoded, KnownPrincipalMap).unwrap(),
+            known_principal_ids
+        );
+
+        let plain_map = HashMap::from([(
+            KnownPrincipalType::CanisterIdUserIndex,
+            get_mock_canister_id_user_index(),
+        )]);
+        assert_eq!(
+            Decode!(&Encode!(&plain_map).unwrap(), KnownPrincipalMap).unwrap(),
+            KnownPrincipalMap::from([(
+                KnownPrincipalType::CanisterIdUserIndex,
+                get_mock_canister_id_user_index()
+            )])
+        );
+    }
+
+    #[test]
+    fn test_stable_memory_written_as_plain_map_can_be_read() {
+        let plain_map = HashMap::from([(
+            KnownPrincipalType::CanisterIdUserIndex,
+            get_mock_canister_id_user_index(),
+        )]);
+        let mut bytes = vec![];
+        plain_map
+            .serialize(&mut rmp_serde::Serializer::new(&mut bytes).with_struct_map())
+            .unwrap();
+        let known_principal_ids: KnownPrincipalMap = rmp_serde::from_slice(&bytes).unwrap();
+        assert_eq!(
+            known_principal_ids.get(&KnownPrincipalType::CanisterIdUserIndex),
+            Some(&get_mock_canister_id_user_index())
+        );
+
+        let (known_principal_ids, _, _) = get_map_with_two_post_caches();
+        let mut bytes = vec![];
+        known_principal_ids
+            .serialize(&mut rmp_serde::Serializer::new(&mut bytes).with_struct_map())
+            .unwrap();
+        assert_eq!(
+            rmp_serde::from_slice::<KnownPrincipalMap>(&bytes).unwrap(),
+            known_principal_ids
+        );
+    }
+}
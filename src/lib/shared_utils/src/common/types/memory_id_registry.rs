@@ -0,0 +1,182 @@
+use std::collections::BTreeMap;
+
+use candid::{CandidType, Deserialize};
+use ic_stable_structures::memory_manager::MemoryId;
+use serde::Serialize;
+
+/// The memory manager reserves the last id for its own bookkeeping
+pub const MAX_ALLOCATABLE_MEMORY_ID: u8 = 254;
+
+/// Persisted record of which stable structure owns which `MemoryId`.
+/// Lives in heap data so that the assignments survive upgrades and a
+/// wasm that tries to reuse an id for a different structure is rejected
+/// before it can overwrite existing data.
+#[derive(CandidType, Deserialize, Serialize, Default, Clone, Debug)]
+pub struct MemoryIdRegistry {
+    allocations: BTreeMap<String, u8>,
+}
+
+#[derive(CandidType, Deserialize, PartialEq, Eq, Debug)]
+pub enum MemoryIdRegistryError {
+    NameAlreadyAllocatedToAnotherMemoryId {
+        name: String,
+        allocated_memory_id: u8,
+    },
+    MemoryIdAlreadyAllocatedToAnotherName {
+        memory_id: u8,
+        allocated_to: String,
+    },
+    MemoryIdOutOfRange,
+    NoFreeMemoryIdsLeft,
+}
+
+#[derive(CandidType, Deserialize, PartialEq, Eq, Debug)]
+pub struct MemoryAllocationDetail {
+    pub name: String,
+    pub memory_id: u8,
+    pub size_in_pages: u64,
+}
+
+impl MemoryIdRegistry {
+    /// Pins `name` to a specific `memory_id`. Re-registering the same pair is a no-op.
+    pub fn register(
+        &mut self,
+        name: &str,
+        memory_id: u8,
+    ) -> Result<MemoryId, MemoryIdRegistryError> {
+        if memory_id > MAX_ALLOCATABLE_MEMORY_ID {
+            return Err(MemoryIdRegistryError::MemoryIdOutOfRange);
+        }
+
+        if let Some(allocated_memory_id) = self.allocations.get(name) {
+            return if *allocated_memory_id == memory_id {
+                Ok(MemoryId::new(memory_id))
+            } else {
+                Err(
+                    MemoryIdRegistryError::NameAlreadyAllocatedToAnotherMemoryId {
+                        name: name.to_string(),
+                        allocated_memory_id: *allocated_memory_id,
+                    },
+                )
+            };
+        }
+
+        if let Some(allocated_to) = self.get_name_for_memory_id(memory_id) {
+            return Err(
+                MemoryIdRegistryError::MemoryIdAlreadyAllocatedToAnotherName {
+                    memory_id,
+                    allocated_to: allocated_to.to_string(),
+                },
+            );
+        }
+
+        self.allocations.insert(name.to_string(), memory_id);
+        Ok(MemoryId::new(memory_id))
+    }
+
+    /// Returns the id already assigned to `name`, or assigns the lowest free one
+    pub fn allocate(&mut self, name: &str) -> Result<MemoryId, MemoryIdRegistryError> {
+        if let Some(allocated_memory_id) = self.allocations.get(name) {
+            return Ok(MemoryId::new(*allocated_memory_id));
+        }
+
+        let free_memory_id = (0..=MAX_ALLOCATABLE_MEMORY_ID)
+            .find(|memory_id| self.get_name_for_memory_id(*memory_id).is_none())
+            .ok_or(MemoryIdRegistryError::NoFreeMemoryIdsLeft)?;
+
+        self.register(name, free_memory_id)
+    }
+
+    pub fn get(&self, name: &str) -> Option<MemoryId> {
+        self.allocations
+            .get(name)
+            .map(|memory_id| MemoryId::new(*memory_id))
+    }
+
+    pub fn get_name_for_memory_id(&self, memory_id: u8) -> Option<&str> {
+        self.allocations
+            .iter()
+            .find(|(_, allocated_memory_id)| **allocated_memory_id == memory_id)
+            .map(|(name, _)| name.as_str())
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&str, u8)> {
+        self.allocations
+            .iter()
+            .map(|(name, memory_id)| (name.as_str(), *memory_id))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_register() {
+        let mut registry = MemoryIdRegistry::default();
+
+        assert_eq!(registry.register("heap_data", 0), Ok(MemoryId::new(0)));
+        assert_eq!(registry.register("heap_data", 0), Ok(MemoryId::new(0)));
+
+        assert_eq!(
+            registry.register("heap_data", 1),
+            Err(
+                MemoryIdRegistryError::NameAlreadyAllocatedToAnotherMemoryId {
+                    name: "heap_data".to_string(),
+                    allocated_memory_id: 0,
+                }
+            )
+        );
+        assert_eq!(
+            registry.register("posts_archive", 0),
+            Err(
+                MemoryIdRegistryError::MemoryIdAlreadyAllocatedToAnotherName {
+                    memory_id: 0,
+                    allocated_to: "heap_data".to_string(),
+                }
+            )
+        );
+        assert_eq!(
+            registry.register("posts_archive", 255),
+            Err(MemoryIdRegistryError::MemoryIdOutOfRange)
+        );
+    }
+
+    #[test]
+    fn test_allocate() {
+        let mut registry = MemoryIdRegistry::default();
+
+        registry.register("heap_data", 0).unwrap();
+        registry.register("logs", 2).unwrap();
+
+        assert_eq!(registry.allocate("posts_archive"), Ok(MemoryId::new(1)));
+        assert_eq!(registry.allocate("follower_graph"), Ok(MemoryId::new(3)));
+        assert_eq!(registry.allocate("posts_archive"), Ok(MemoryId::new(1)));
+
+        assert_eq!(
+            registry.iter().collect::<Vec<_>>(),
+            vec![
+                ("follower_graph", 3),
+                ("heap_data", 0),
+                ("logs", 2),
+                ("posts_archive", 1)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_allocate_when_all_ids_are_taken() {
+        let mut registry = MemoryIdRegistry::default();
+
+        (0..=MAX_ALLOCATABLE_MEMORY_ID).for_each(|memory_id| {
+            registry
+                .register(&format!("memory_{}", memory_id), memory_id)
+                .unwrap();
+        });
+
+        assert_eq!(
+            registry.allocate("one_too_many"),
+            Err(MemoryIdRegistryError::NoFreeMemoryIdsLeft)
+        );
+    }
+}
@@ -1,5 +1,14 @@
 pub mod app_primitive_type;
+pub mod certified_map;
+pub mod deferred_job;
+pub mod error_code;
+pub mod event_counter;
+pub mod global_post_ref;
 pub mod known_principal;
+pub mod memory_id_registry;
+pub mod response_envelope;
+pub mod sharded_counter;
+pub mod stable_queue;
 pub mod storable_principal;
 pub mod top_posts;
 pub mod utility_token;
@@ -0,0 +1,245 @@
+use candid::{CandidType, Deserialize};
+
+use super::error_code::{ErrorCode, HasErrorCode};
+
+/// Bumped whenever a variant is added to an enum that is returned inside a [`ResponseEnvelope`]
+pub const RESPONSE_ENVELOPE_INTERFACE_VERSION: u32 = 3;
+
+/// Wraps responses that carry enums which keep growing variants, like `BettingStatus` and
+/// `BetOnCurrentlyViewingPostError`.
+///
+/// Candid decodes a value that doesn't fit an `opt` as null, so a client built against an
+/// older interface gets `value = null` for a variant it doesn't know yet, instead of failing
+/// to decode the whole response. `interface_version` tells it that it should update.
+///
+/// `error_code` is set for errors, so that even a client that got `value = null` knows what
+/// went wrong and whether to retry.
+#[derive(CandidType, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct ResponseEnvelope<T> {
+    pub interface_version: u32,
+    pub value: Option<T>,
+    pub error_code: Option<ErrorCode>,
+}
+
+impl<T> From<T> for ResponseEnvelope<T> {
+    fn from(value: T) -> Self {
+        Self {
+            interface_version: RESPONSE_ENVELOPE_INTERFACE_VERSION,
+            value: Some(value),
+            error_code: None,
+        }
+    }
+}
+
+impl<T, E: HasErrorCode> ResponseEnvelope<Result<T, E>> {
+    pub fn from_result(result: Result<T, E>) -> Self {
+        Self {
+            interface_version: RESPONSE_ENVELOPE_INTERFACE_VERSION,
+            error_code: result.as_ref().err().map(HasErrorCode::error_code),
+            value: Some(result),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::SystemTime;
+
+    use candid::{Decode, Encode};
+
+    use crate::canister_specific::individual_user_template::types::{
+        error::BetOnCurrentlyViewingPostError, hot_or_not::BettingStatus,
+    };
+
+    use super::*;
+
+    /// The enums as they were at interface version 1. Leave these alone when adding variants;
+    /// every value below has to keep decoding into the latest types.
+    mod v1 {
+        use std::time::SystemTime;
+
+        use candid::{CandidType, Deserialize};
+
+        #[derive(CandidType, Deserialize, Clone, Debug, PartialEq, Eq)]
+        #[allow(clippy::enum_variant_names)]
+        pub enum BettingStatus {
+            BettingOpen {
+                started_at: SystemTime,
+                number_of_participants: u8,
+                ongoing_slot: u8,
+                ongoing_room: u64,
+                has_this_user_participated_in_this_post: Option<bool>,
+                minimum_bet_amount: u64,
+            },
+            BettingClosed,
+            BettingClosedByCreator,
+            BettingPausedForReview,
+        }
+
+        #[derive(CandidType, Deserialize, Clone, Debug, PartialEq, Eq)]
+        pub enum BetOnCurrentlyViewingPostError {
+            BettingClosed,
+            InsufficientBalance,
+            Unauthorized,
+            UserAlreadyParticipatedInThisPost,
+            UserNotLoggedIn,
+            UserPrincipalNotSet,
+            PostCreatorCanisterCallFailed,
+            SessionDelegateDailyBetLimitExceeded,
+            BetAmountBelowPostMinimum,
+            BettingPausedForReview,
+        }
+    }
+
+    #[test]
+    fn test_v1_fixtures_decode_into_latest_types() {
+        let started_at = SystemTime::UNIX_EPOCH;
+        let betting_status_fixtures = vec![
+            v1::BettingStatus::BettingOpen {
+                started_at,
+                number_of_participants: 3,
+                ongoing_slot: 1,
+                ongoing_room: 2,
+                has_this_user_participated_in_this_post: Some(false),
+                minimum_bet_amount: 10,
+            },
+            v1::BettingStatus::BettingClosed,
+            v1::BettingStatus::BettingClosedByCreator,
+            v1::BettingStatus::BettingPausedForReview,
+        ];
+        let latest_betting_statuses = vec![
+            BettingStatus::BettingOpen {
+                started_at,
+                number_of_participants: 3,
+                ongoing_slot: 1,
+                ongoing_room: 2,
+                has_this_user_participated_in_this_post: Some(false),
+                minimum_bet_amount: 10,
+            },
+            BettingStatus::BettingClosed,
+            BettingStatus::BettingClosedByCreator,
+            BettingStatus::BettingPausedForReview,
+        ];
+
+        betting_status_fixtures
+            .into_iter()
+            .zip(latest_betting_statuses)
+            .for_each(|(fixture, latest)| {
+                let bytes = Encode!(&ResponseEnvelope::from(fixture)).unwrap();
+                assert_eq!(
+                    Decode!(&bytes, ResponseEnvelope<BettingStatus>).unwrap(),
+                    ResponseEnvelope::from(latest)
+                );
+            });
+
+        let error_fixtures = vec![
+            v1::BetOnCurrentlyViewingPostError::BettingClosed,
+            v1::BetOnCurrentlyViewingPostError::InsufficientBalance,
+            v1::BetOnCurrentlyViewingPostError::Unauthorized,
+            v1::BetOnCurrentlyViewingPostError::UserAlreadyParticipatedInThisPost,
+            v1::BetOnCurrentlyViewingPostError::UserNotLoggedIn,
+            v1::BetOnCurrentlyViewingPostError::UserPrincipalNotSet,
+            v1::BetOnCurrentlyViewingPostError::PostCreatorCanisterCallFailed,
+            v1::BetOnCurrentlyViewingPostError::SessionDelegateDailyBetLimitExceeded,
+            v1::BetOnCurrentlyViewingPostError::BetAmountBelowPostMinimum,
+            v1::BetOnCurrentlyViewingPostError::BettingPausedForReview,
+        ];
+        let latest_errors = vec![
+            BetOnCurrentlyViewingPostError::BettingClosed,
+            BetOnCurrentlyViewingPostError::InsufficientBalance,
+            BetOnCurrentlyViewingPostError::Unauthorized,
+            BetOnCurrentlyViewingPostError::UserAlreadyParticipatedInThisPost,
+            BetOnCurrentlyViewingPostError::UserNotLoggedIn,
+            BetOnCurrentlyViewingPostError::UserPrincipalNotSet,
+            BetOnCurrentlyViewingPostError::PostCreatorCanisterCallFailed,
+            BetOnCurrentlyViewingPostError::SessionDelegateDailyBetLimitExceeded,
+            BetOnCurrentlyViewingPostError::BetAmountBelowPostMinimum,
+            BetOnCurrentlyViewingPostError::BettingPausedForReview,
+        ];
+
+        error_fixtures
+            .into_iter()
+            .zip(latest_errors)
+            .for_each(|(fixture, latest)| {
+                let bytes = Encode!(&ResponseEnvelope::from(Err::<v1::BettingStatus, _>(
+                    fixture
+                )))
+                .unwrap();
+                assert_eq!(
+                    Decode!(
+                        &bytes,
+                        ResponseEnvelope<Result<BettingStatus, BetOnCurrentlyViewingPostError>>
+                    )
+                    .unwrap(),
+                    ResponseEnvelope::from(Err(latest))
+                );
+            });
+    }
+
+    #[test]
+    fn test_variants_unknown_to_older_clients_decode_as_null() {
+        #[derive(CandidType, Deserialize, Debug, PartialEq, Eq)]
+        enum OlderClientBettingStatus {
+            BettingOpen {
+                started_at: SystemTime,
+                number_of_participants: u8,
+                ongoing_slot: u8,
+                ongoing_room: u64,
+                has_this_user_participated_in_this_post: Option<bool>,
+                minimum_bet_amount: u64,
+            },
+            BettingClosed,
+        }
+
+        let bytes = Encode!(&ResponseEnvelope::from(
+            BettingStatus::BettingPausedForReview
+        ))
+        .unwrap();
+        assert_eq!(
+            Decode!(&bytes, ResponseEnvelope<OlderClientBettingStatus>).unwrap(),
+            ResponseEnvelope {
+                interface_version: RESPONSE_ENVELOPE_INTERFACE_VERSION,
+                value: None,
+                error_code: None,
+            }
+        );
+
+        // * without the envelope the whole response fails to decode
+        let bytes = Encode!(&BettingStatus::BettingPausedForReview).unwrap();
+        assert!(Decode!(&bytes, OlderClientBettingStatus).is_err());
+
+        let bytes = Encode!(&ResponseEnvelope::from(BettingStatus::BettingClosed)).unwrap();
+        assert_eq!(
+            Decode!(&bytes, ResponseEnvelope<OlderClientBettingStatus>).unwrap(),
+            ResponseEnvelope::from(OlderClientBettingStatus::BettingClosed)
+        );
+    }
+
+    #[test]
+    fn test_error_codes_reach_clients_that_predate_the_error() {
+        #[derive(CandidType, Deserialize, Debug, PartialEq, Eq)]
+        enum OlderClientBetOnCurrentlyViewingPostError {
+            BettingClosed,
+        }
+
+        let bytes = Encode!(&ResponseEnvelope::from_result(Err::<BettingStatus, _>(
+            BetOnCurrentlyViewingPostError::BettingNotStarted
+        )))
+        .unwrap();
+        let response = Decode!(
+            &bytes,
+            ResponseEnvelope<Result<BettingStatus, OlderClientBetOnCurrentlyViewingPostError>>
+        )
+        .unwrap();
+        assert_eq!(response.value, None);
+        assert_eq!(
+            response.error_code,
+            Some(BetOnCurrentlyViewingPostError::BettingNotStarted.error_code())
+        );
+
+        let response = ResponseEnvelope::from_result(Ok::<_, BetOnCurrentlyViewingPostError>(
+            BettingStatus::BettingClosed,
+        ));
+        assert_eq!(response.error_code, None);
+    }
+}
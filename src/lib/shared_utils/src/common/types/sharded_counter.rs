@@ -0,0 +1,62 @@
+use std::collections::BTreeMap;
+
+use candid::{CandidType, Deserialize, Principal};
+use serde::Serialize;
+
+pub const NUMBER_OF_COUNTER_SHARDS: u8 = 16;
+
+/// Counter split into sub-counters so that writers don't all land on the same entry.
+/// Each writer always lands on the same shard, picked from their principal, and the total is
+/// summed on read.
+#[derive(CandidType, Clone, Deserialize, Serialize, Default, Debug, PartialEq, Eq)]
+pub struct ShardedCounter {
+    shards: BTreeMap<u8, u64>,
+}
+
+impl ShardedCounter {
+    pub fn increment(&mut self, writer: &Principal, by: u64) {
+        let shard = self.shards.entry(get_shard_for_writer(writer)).or_default();
+        *shard = shard.saturating_add(by);
+    }
+
+    pub fn get(&self) -> u64 {
+        self.shards
+            .values()
+            .fold(0, |total, shard| total.saturating_add(*shard))
+    }
+}
+
+fn get_shard_for_writer(writer: &Principal) -> u8 {
+    writer
+        .as_slice()
+        .iter()
+        .fold(0_u8, |shard, byte| shard.wrapping_add(*byte))
+        % NUMBER_OF_COUNTER_SHARDS
+}
+
+#[cfg(test)]
+mod test {
+    use test_utils::setup::test_constants::v2::users;
+
+    use super::*;
+
+    #[test]
+    fn test_sharded_counter() {
+        let mut counter = ShardedCounter::default();
+        assert_eq!(counter.get(), 0);
+
+        let viewers = users("viewer", 40);
+        viewers
+            .iter()
+            .for_each(|viewer| counter.increment(viewer, 1));
+        counter.increment(&viewers[0], 3);
+
+        assert_eq!(counter.get(), 43);
+        assert!(counter.shards.len() > 1);
+        assert!(counter.shards.len() <= NUMBER_OF_COUNTER_SHARDS as usize);
+
+        // * the same writer keeps landing on the same shard
+        let shard_of_first_viewer = get_shard_for_writer(&viewers[0]);
+        assert!(counter.shards[&shard_of_first_viewer] >= 4);
+    }
+}
@@ -0,0 +1,282 @@
+use std::{
+    collections::BTreeMap,
+    time::{Duration, SystemTime},
+};
+
+use ic_stable_structures::{BoundedStorable, Memory, StableBTreeMap};
+
+// * A leased item neither acked nor requeued this long after, e.g. because the call made
+// * for it never returned, is handed out again
+pub const QUEUED_ITEM_IN_FLIGHT_LEASE: Duration = Duration::from_secs(10 * 60);
+
+/// Durable FIFO queue living in its own stable memory region, so that whatever is queued
+/// survives upgrades without having to pass through the heap.
+///
+/// Every item is handed an id when it's pushed. Ids increase in the order items are queued,
+/// so iterating the underlying map in key order yields the queue in FIFO order. Consumers
+/// `ack` an item once it has been processed, or `requeue` it to the back of the queue to
+/// retry it after everything that's already waiting.
+///
+/// Consumers that process items across an await `lease` them first, so that a consumer
+/// running alongside doesn't pick up the same items before they are acked or requeued.
+pub struct StableQueue<T: BoundedStorable, M: Memory> {
+    items: StableBTreeMap<u64, T, M>,
+    next_id: u64,
+    // * Not kept across upgrades, an item leased at the time is only sent again
+    leased_until: BTreeMap<u64, SystemTime>,
+}
+
+impl<T: BoundedStorable, M: Memory> StableQueue<T, M> {
+    /// Loads the queue persisted in `memory`, or creates an empty one if there is none.
+    pub fn init(memory: M) -> Self {
+        let items: StableBTreeMap<u64, T, M> = StableBTreeMap::init(memory);
+        let next_id = items.iter().last().map(|(id, _)| id + 1).unwrap_or(0);
+
+        Self {
+            items,
+            next_id,
+            leased_until: BTreeMap::new(),
+        }
+    }
+
+    pub fn len(&self) -> u64 {
+        self.items.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Adds an item to the back of the queue and returns the id it was queued under
+    pub fn push_back(&mut self, item: T) -> u64 {
+        let id = self.next_id;
+        self.items.insert(id, item);
+        self.next_id += 1;
+        id
+    }
+
+    pub fn peek_front(&self) -> Option<(u64, T)> {
+        self.items.iter().next()
+    }
+
+    pub fn get(&self, id: u64) -> Option<T> {
+        self.items.get(&id)
+    }
+
+    /// Yields the queued items front to back along with their ids
+    pub fn iter(&self) -> impl Iterator<Item = (u64, T)> + '_ {
+        self.items.iter()
+    }
+
+    /// Hands out the queued items that aren't leased to another consumer, front to back, and
+    /// leases them until `current_time + lease_duration`. They stay queued until acked or
+    /// requeued, and are handed out again if the lease runs out before that.
+    pub fn lease(&mut self, current_time: &SystemTime, lease_duration: Duration) -> Vec<(u64, T)> {
+        self.leased_until
+            .retain(|_, lease_expires_at| *lease_expires_at > *current_time);
+
+        let unleased_items: Vec<(u64, T)> = self
+            .items
+            .iter()
+            .filter(|(id, _)| !self.leased_until.contains_key(id))
+            .collect();
+        unleased_items.iter().for_each(|(id, _)| {
+            self.leased_until
+                .insert(*id, *current_time + lease_duration);
+        });
+
+        unleased_items
+    }
+
+    /// Removes an item that has been processed. Returns `None` if it was already acked.
+    pub fn ack(&mut self, id: u64) -> Option<T> {
+        self.leased_until.remove(&id);
+        self.items.remove(&id)
+    }
+
+    /// Moves an item that could not be processed to the back of the queue, replacing it
+    /// with `item` so that consumers can record the failed attempt. Returns the new id, or
+    /// `None` if the item is no longer queued.
+    pub fn requeue(&mut self, id: u64, item: T) -> Option<u64> {
+        self.ack(id)?;
+        Some(self.push_back(item))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::VecDeque;
+
+    use ic_stable_structures::VectorMemory;
+    use proptest::prelude::*;
+
+    use super::*;
+
+    fn new_queue() -> StableQueue<u64, VectorMemory> {
+        StableQueue::init(VectorMemory::default())
+    }
+
+    #[test]
+    fn test_queue_is_first_in_first_out() {
+        let mut queue = new_queue();
+        assert!(queue.is_empty());
+        assert_eq!(queue.peek_front(), None);
+
+        let first_id = queue.push_back(10);
+        let second_id = queue.push_back(20);
+        queue.push_back(30);
+
+        assert_eq!(queue.len(), 3);
+        assert_eq!(queue.peek_front(), Some((first_id, 10)));
+        assert_eq!(
+            queue.iter().map(|(_, item)| item).collect::<Vec<_>>(),
+            vec![10, 20, 30]
+        );
+
+        assert_eq!(queue.ack(first_id), Some(10));
+        assert_eq!(queue.ack(first_id), None);
+        assert_eq!(queue.peek_front(), Some((second_id, 20)));
+    }
+
+    #[test]
+    fn test_requeue_moves_item_to_the_back() {
+        let mut queue = new_queue();
+        let first_id = queue.push_back(10);
+        queue.push_back(20);
+
+        let requeued_id = queue.requeue(first_id, 11).unwrap();
+
+        assert_eq!(queue.get(first_id), None);
+        assert_eq!(queue.get(requeued_id), Some(11));
+        assert_eq!(
+            queue.iter().map(|(_, item)| item).collect::<Vec<_>>(),
+            vec![20, 11]
+        );
+        assert_eq!(queue.requeue(first_id, 12), None);
+        assert_eq!(queue.len(), 2);
+    }
+
+    #[test]
+    fn test_overlapping_drains_dont_lease_the_same_items() {
+        let mut queue = new_queue();
+        let current_time = SystemTime::UNIX_EPOCH;
+        let lease_duration = Duration::from_secs(60);
+        let first_id = queue.push_back(10);
+        let second_id = queue.push_back(20);
+
+        let first_drain = queue.lease(&current_time, lease_duration);
+        queue.push_back(30);
+        let second_drain = queue.lease(&current_time, lease_duration);
+
+        assert_eq!(first_drain, vec![(first_id, 10), (second_id, 20)]);
+        assert_eq!(
+            second_drain
+                .iter()
+                .map(|(_, item)| *item)
+                .collect::<Vec<_>>(),
+            vec![30]
+        );
+
+        // * a failed item is up for grabs again once requeued
+        let requeued_id = queue.requeue(first_id, 11).unwrap();
+        queue.ack(second_id);
+        assert_eq!(
+            queue.lease(&current_time, lease_duration),
+            vec![(requeued_id, 11)]
+        );
+        assert!(queue.lease(&current_time, lease_duration).is_empty());
+
+        // * items whose drain never reported back are handed out once the lease runs out
+        assert_eq!(
+            queue
+                .lease(&(current_time + lease_duration), lease_duration)
+                .iter()
+                .map(|(_, item)| *item)
+                .collect::<Vec<_>>(),
+            vec![30, 11]
+        );
+        assert_eq!(queue.len(), 2);
+    }
+
+    #[test]
+    fn test_queue_is_reloaded_from_memory() {
+        let memory = VectorMemory::default();
+        let mut queue = StableQueue::<u64, _>::init(memory.clone());
+        queue.push_back(10);
+        queue.push_back(20);
+
+        let reloaded_queue = StableQueue::<u64, _>::init(memory);
+
+        assert_eq!(
+            reloaded_queue
+                .iter()
+                .map(|(_, item)| item)
+                .collect::<Vec<_>>(),
+            vec![10, 20]
+        );
+    }
+
+    #[derive(Debug, Clone)]
+    enum QueueOperation {
+        PushBack(u64),
+        AckFront,
+        RequeueFront,
+    }
+
+    fn queue_operation_strategy() -> impl Strategy<Value = QueueOperation> {
+        prop_oneof![
+            any::<u64>().prop_map(QueueOperation::PushBack),
+            Just(QueueOperation::AckFront),
+            Just(QueueOperation::RequeueFront),
+        ]
+    }
+
+    proptest! {
+        #[test]
+        fn test_queue_behaves_like_a_vec_deque(
+            operations in prop::collection::vec(queue_operation_strategy(), 0..100)
+        ) {
+            let mut queue = new_queue();
+            let mut model = VecDeque::new();
+
+            for operation in operations {
+                match operation {
+                    QueueOperation::PushBack(item) => {
+                        queue.push_back(item);
+                        model.push_back(item);
+                    }
+                    QueueOperation::AckFront => {
+                        let front = queue.peek_front();
+                        prop_assert_eq!(front.as_ref().map(|(_, item)| *item), model.pop_front());
+                        if let Some((id, _)) = front {
+                            prop_assert!(queue.ack(id).is_some());
+                        }
+                    }
+                    QueueOperation::RequeueFront => {
+                        if let Some((id, item)) = queue.peek_front() {
+                            prop_assert!(queue.requeue(id, item).is_some());
+                            model.rotate_left(1);
+                        }
+                    }
+                }
+
+                prop_assert_eq!(queue.len(), model.len() as u64);
+                prop_assert_eq!(
+                    queue.iter().map(|(_, item)| item).collect::<Vec<_>>(),
+                    model.iter().copied().collect::<Vec<_>>()
+                );
+            }
+        }
+
+        #[test]
+        fn test_ids_increase_front_to_back(items in prop::collection::vec(any::<u64>(), 0..50)) {
+            let mut queue = new_queue();
+            items.iter().for_each(|item| {
+                queue.push_back(*item);
+            });
+
+            let ids = queue.iter().map(|(id, _)| id).collect::<Vec<_>>();
+            prop_assert!(ids.windows(2).all(|pair| pair[0] < pair[1]));
+        }
+    }
+}
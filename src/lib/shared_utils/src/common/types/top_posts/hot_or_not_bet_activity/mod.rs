@@ -0,0 +1,182 @@
+use std::time::{Duration, SystemTime};
+
+use candid::{CandidType, Deserialize};
+use serde::Serialize;
+
+use crate::common::types::app_primitive_type::PostId;
+
+// * Recent bet volume halves after this long without new bets, so a post stops ranking on a
+// * burst of bets once the burst is over
+pub const RECENT_BET_VOLUME_HALF_LIFE: Duration = Duration::from_secs(60 * 60);
+// * The whole score halves once a post is this old, so older posts need more activity to
+// * hold on to their spot
+pub const POST_RECENCY_HALF_LIFE: Duration = Duration::from_secs(60 * 60 * 24);
+pub const RECENT_BET_VOLUME_WEIGHT: u64 = 3;
+pub const TOTAL_POT_WEIGHT: u64 = 1;
+
+/// Sent by the canister a post lives in to the post cache whenever a bet is placed on it
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug, PartialEq, Eq)]
+pub struct HotOrNotBetActivityDelta {
+    pub post_id: PostId,
+    pub bet_amount: u64,
+    // * Total bet on the post so far, including this bet
+    pub pot_size: u64,
+    pub post_created_at: SystemTime,
+    pub bet_placed_at: SystemTime,
+}
+
+/// Betting activity on a post as seen by the post cache, used to rank the hot or not feed
+/// by live betting activity
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug, PartialEq, Eq)]
+pub struct HotOrNotBetActivity {
+    // * Decayed as of `last_updated_at`
+    pub recent_bet_volume: u64,
+    pub total_pot: u64,
+    pub post_created_at: SystemTime,
+    pub last_updated_at: SystemTime,
+}
+
+impl HotOrNotBetActivity {
+    pub fn from_delta(delta: &HotOrNotBetActivityDelta) -> Self {
+        Self {
+            recent_bet_volume: delta.bet_amount,
+            total_pot: delta.pot_size,
+            post_created_at: delta.post_created_at,
+            last_updated_at: delta.bet_placed_at,
+        }
+    }
+
+    pub fn apply_delta(&mut self, delta: &HotOrNotBetActivityDelta) {
+        // * Deltas can arrive out of order, as they're retried independently
+        let bet_placed_at = delta.bet_placed_at.max(self.last_updated_at);
+
+        self.recent_bet_volume = decay(
+            self.recent_bet_volume,
+            elapsed_between(&self.last_updated_at, &bet_placed_at),
+            RECENT_BET_VOLUME_HALF_LIFE,
+        )
+        .saturating_add(delta.bet_amount);
+        self.total_pot = self.total_pot.max(delta.pot_size);
+        self.last_updated_at = bet_placed_at;
+    }
+
+    /// Recent bet volume and total pot, weighted and decayed by the age of the post
+    pub fn get_score(&self, current_time: &SystemTime) -> u64 {
+        let recent_bet_volume = decay(
+            self.recent_bet_volume,
+            elapsed_between(&self.last_updated_at, current_time),
+            RECENT_BET_VOLUME_HALF_LIFE,
+        );
+        let activity_score = recent_bet_volume
+            .saturating_mul(RECENT_BET_VOLUME_WEIGHT)
+            .saturating_add(self.total_pot.saturating_mul(TOTAL_POT_WEIGHT));
+
+        decay(
+            activity_score,
+            elapsed_between(&self.post_created_at, current_time),
+            POST_RECENCY_HALF_LIFE,
+        )
+    }
+}
+
+fn elapsed_between(earlier: &SystemTime, later: &SystemTime) -> Duration {
+    later.duration_since(*earlier).unwrap_or_default()
+}
+
+// * Hyperbolic rather than exponential, so it stays in integer math. Halves after
+// * `half_life`, thirds after twice that and so on.
+fn decay(value: u64, elapsed: Duration, half_life: Duration) -> u64 {
+    let half_life = half_life.as_secs() as u128;
+
+    (value as u128 * half_life / (half_life + elapsed.as_secs() as u128)) as u64
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn get_delta(
+        bet_amount: u64,
+        pot_size: u64,
+        bet_placed_after: Duration,
+    ) -> HotOrNotBetActivityDelta {
+        HotOrNotBetActivityDelta {
+            post_id: 0,
+            bet_amount,
+            pot_size,
+            post_created_at: SystemTime::UNIX_EPOCH,
+            bet_placed_at: SystemTime::UNIX_EPOCH + bet_placed_after,
+        }
+    }
+
+    #[test]
+    fn test_decay() {
+        assert_eq!(decay(100, Duration::ZERO, RECENT_BET_VOLUME_HALF_LIFE), 100);
+        assert_eq!(
+            decay(
+                100,
+                RECENT_BET_VOLUME_HALF_LIFE,
+                RECENT_BET_VOLUME_HALF_LIFE
+            ),
+            50
+        );
+        assert_eq!(
+            decay(
+                90,
+                RECENT_BET_VOLUME_HALF_LIFE * 2,
+                RECENT_BET_VOLUME_HALF_LIFE
+            ),
+            30
+        );
+        assert_eq!(
+            decay(u64::MAX, Duration::ZERO, POST_RECENCY_HALF_LIFE),
+            u64::MAX
+        );
+    }
+
+    #[test]
+    fn test_apply_delta() {
+        let mut hot_or_not_bet_activity =
+            HotOrNotBetActivity::from_delta(&get_delta(100, 100, Duration::ZERO));
+
+        hot_or_not_bet_activity.apply_delta(&get_delta(50, 150, RECENT_BET_VOLUME_HALF_LIFE));
+        assert_eq!(hot_or_not_bet_activity.recent_bet_volume, 100);
+        assert_eq!(hot_or_not_bet_activity.total_pot, 150);
+
+        // * a delta retried late doesn't roll back the pot or the clock
+        hot_or_not_bet_activity.apply_delta(&get_delta(10, 120, Duration::ZERO));
+        assert_eq!(hot_or_not_bet_activity.recent_bet_volume, 110);
+        assert_eq!(hot_or_not_bet_activity.total_pot, 150);
+        assert_eq!(
+            hot_or_not_bet_activity.last_updated_at,
+            SystemTime::UNIX_EPOCH + RECENT_BET_VOLUME_HALF_LIFE
+        );
+    }
+
+    #[test]
+    fn test_get_score() {
+        let hot_or_not_bet_activity =
+            HotOrNotBetActivity::from_delta(&get_delta(100, 200, Duration::ZERO));
+
+        assert_eq!(
+            hot_or_not_bet_activity.get_score(&SystemTime::UNIX_EPOCH),
+            100 * RECENT_BET_VOLUME_WEIGHT + 200 * TOTAL_POT_WEIGHT
+        );
+        assert_eq!(
+            hot_or_not_bet_activity.get_score(&(SystemTime::UNIX_EPOCH + POST_RECENCY_HALF_LIFE)),
+            (100 / 25 * RECENT_BET_VOLUME_WEIGHT + 200 * TOTAL_POT_WEIGHT) / 2
+        );
+    }
+
+    #[test]
+    fn test_recent_bets_outrank_a_bigger_pot_that_went_quiet() {
+        let current_time = SystemTime::UNIX_EPOCH + Duration::from_secs(60 * 60 * 3);
+
+        let mut busy_post =
+            HotOrNotBetActivity::from_delta(&get_delta(100, 100, Duration::from_secs(60 * 60 * 2)));
+        busy_post.apply_delta(&get_delta(100, 200, Duration::from_secs(60 * 60 * 3)));
+        let quiet_post = HotOrNotBetActivity::from_delta(&get_delta(300, 300, Duration::ZERO));
+
+        assert!(busy_post.get_score(&current_time) > quiet_post.get_score(&current_time));
+    }
+}
@@ -1,2 +1,4 @@
+pub mod hot_or_not_bet_activity;
 pub mod post_score_index;
 pub mod post_score_index_item;
+pub mod slot_heat_indicator;
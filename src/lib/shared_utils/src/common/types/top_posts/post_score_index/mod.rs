@@ -71,7 +71,47 @@ impl PostScoreIndex {
         }
     }
 
-    pub fn iter(&self) -> PostScoreIndexIterator {
+    /// Scales an item's score down by `decay_percentage`, evicting it once it decays all
+    /// the way to zero. Returns the decayed score, or None if the item was evicted or is
+    /// not in the index.
+    pub fn decay_score(
+        &mut self,
+        publisher_canister_id: PublisherCanisterId,
+        post_id: PostId,
+        decay_percentage: u64,
+    ) -> Option<Score> {
+        let old_score = *self
+            .item_presence_index
+            .get(&(publisher_canister_id, post_id))?;
+
+        self.remove(&PostScoreIndexItem {
+            score: old_score,
+            post_id,
+            publisher_canister_id,
+        });
+        if self
+            .items_sorted_by_score
+            .get(&old_score)
+            .is_some_and(|items| items.is_empty())
+        {
+            self.items_sorted_by_score.remove(&old_score);
+        }
+
+        let decayed_score = old_score * (100 - decay_percentage.min(100)) / 100;
+        if decayed_score == 0 {
+            return None;
+        }
+
+        self.replace(&PostScoreIndexItem {
+            score: decayed_score,
+            post_id,
+            publisher_canister_id,
+        });
+
+        Some(decayed_score)
+    }
+
+    pub fn iter(&self) -> PostScoreIndexIterator<'_> {
         PostScoreIndexIterator {
             inner: self.items_sorted_by_score.iter().rev(),
             current_vec: None,
@@ -563,4 +603,53 @@ mod test {
         );
         assert_eq!(post_score_index_iter.next(), None);
     }
+    #[test]
+    fn if_decaying_scores_then_items_reordered_and_fully_decayed_items_evicted() {
+        let mut post_score_index = PostScoreIndex::default();
+        let publisher_canister_id = Principal::from_text("w4nuc-waaaa-aaaao-aal2a-cai").unwrap();
+
+        post_score_index.replace(&PostScoreIndexItem {
+            score: 1000,
+            post_id: 1,
+            publisher_canister_id,
+        });
+        post_score_index.replace(&PostScoreIndexItem {
+            score: 950,
+            post_id: 2,
+            publisher_canister_id,
+        });
+        post_score_index.replace(&PostScoreIndexItem {
+            score: 1,
+            post_id: 3,
+            publisher_canister_id,
+        });
+
+        assert_eq!(
+            post_score_index.decay_score(publisher_canister_id, 1, 10),
+            Some(900)
+        );
+        assert_eq!(
+            post_score_index
+                .iter()
+                .map(|item| (item.post_id, item.score))
+                .collect::<Vec<_>>(),
+            vec![(2, 950), (1, 900), (3, 1)]
+        );
+
+        assert_eq!(
+            post_score_index.decay_score(publisher_canister_id, 3, 10),
+            None
+        );
+        assert_eq!(post_score_index.iter().count(), 2);
+        assert!(!post_score_index
+            .item_presence_index
+            .contains_key(&(publisher_canister_id, 3)));
+        assert!(!post_score_index.items_sorted_by_score.contains_key(&1));
+        assert!(!post_score_index.items_sorted_by_score.contains_key(&1000));
+
+        assert_eq!(
+            post_score_index.decay_score(publisher_canister_id, 4, 10),
+            None
+        );
+    }
 }
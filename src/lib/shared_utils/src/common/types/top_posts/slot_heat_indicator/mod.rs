@@ -0,0 +1,25 @@
+use std::time::SystemTime;
+
+use candid::{CandidType, Deserialize, Principal};
+use serde::Serialize;
+
+use crate::common::types::app_primitive_type::PostId;
+
+/// Heat score at and above which the current slot's betting pace counts as unusually high.
+/// The score is the projected number of bets in the current slot as a percentage of the
+/// average number of bets per slot seen so far on the post.
+pub const SLOT_HEAT_SCORE_THRESHOLD_FOR_ON_FIRE: u64 = 200;
+/// Keeps a couple of early bets on a quiet post from lighting it up
+pub const MINIMUM_BETS_IN_SLOT_FOR_ON_FIRE: u64 = 5;
+
+#[derive(CandidType, Clone, Deserialize, Debug, Serialize, PartialEq, Eq)]
+pub struct SlotHeatIndicator {
+    pub publisher_canister_id: Principal,
+    pub post_id: PostId,
+    pub slot_id: u8,
+    pub bets_in_slot: u64,
+    pub heat_score: u64,
+    pub is_on_fire: bool,
+    // * End of the slot the heat was computed for, after which it no longer applies
+    pub valid_until: SystemTime,
+}
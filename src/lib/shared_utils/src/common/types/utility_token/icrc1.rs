@@ -0,0 +1,69 @@
+//! The subset of the ICRC-1 ledger interface used to mirror utility token balances
+
+use candid::{CandidType, Deserialize, Nat, Principal};
+use serde::Serialize;
+
+pub type Subaccount = Vec<u8>;
+
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug, PartialEq, Eq)]
+pub struct Account {
+    pub owner: Principal,
+    pub subaccount: Option<Subaccount>,
+}
+
+impl Account {
+    /// The default account of a principal
+    pub fn of(owner: Principal) -> Self {
+        Self {
+            owner,
+            subaccount: None,
+        }
+    }
+}
+
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug, PartialEq, Eq)]
+pub struct TransferArg {
+    pub from_subaccount: Option<Subaccount>,
+    pub to: Account,
+    pub amount: Nat,
+    pub fee: Option<Nat>,
+    pub memo: Option<Vec<u8>>,
+    pub created_at_time: Option<u64>,
+}
+
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug, PartialEq, Eq)]
+pub enum TransferError {
+    BadFee { expected_fee: Nat },
+    BadBurn { min_burn_amount: Nat },
+    InsufficientFunds { balance: Nat },
+    TooOld,
+    CreatedInFuture { ledger_time: u64 },
+    Duplicate { duplicate_of: Nat },
+    TemporarilyUnavailable,
+    GenericError { error_code: Nat, message: String },
+}
+
+pub type TransferResult = Result<Nat, TransferError>;
+
+/// Ledger amounts are unbounded, utility token balances are `u64`. Returns `None` for
+/// amounts that don't fit.
+pub fn nat_to_u64(amount: &Nat) -> Option<u64> {
+    match amount.0.to_u64_digits().as_slice() {
+        [] => Some(0),
+        [amount] => Some(*amount),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_nat_to_u64() {
+        assert_eq!(nat_to_u64(&Nat::from(0_u64)), Some(0));
+        assert_eq!(nat_to_u64(&Nat::from(1_250_u64)), Some(1_250));
+        assert_eq!(nat_to_u64(&Nat::from(u64::MAX)), Some(u64::MAX));
+        assert_eq!(nat_to_u64(&Nat::from(u64::MAX as u128 + 1)), None);
+    }
+}
@@ -1 +1,2 @@
+pub mod icrc1;
 pub mod token_event;
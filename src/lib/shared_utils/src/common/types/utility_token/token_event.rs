@@ -3,8 +3,13 @@ use std::time::SystemTime;
 use candid::{CandidType, Deserialize, Principal};
 use serde::Serialize;
 
-use crate::canister_specific::individual_user_template::types::hot_or_not::{
-    BetDirection, BetOutcomeForBetMaker,
+use crate::canister_specific::{
+    individual_user_template::types::{
+        achievement::Achievement,
+        hot_or_not::{BetDirection, BetOutcomeForBetMaker},
+        versus::VersusContestId,
+    },
+    user_index::types::tournament::TournamentId,
 };
 
 #[derive(Clone, CandidType, Deserialize, Debug, PartialEq, Eq, Serialize)]
@@ -26,18 +31,172 @@ pub enum TokenEvent {
         details: HotOrNotOutcomePayoutEvent,
         timestamp: SystemTime,
     },
+    HouseRake {
+        amount: u64,
+        details: HouseRakeEvent,
+        timestamp: SystemTime,
+    },
+    PostBoost {
+        amount: u64,
+        details: PostBoostEvent,
+        timestamp: SystemTime,
+    },
+    ReferralCommission {
+        amount: u64,
+        details: ReferralCommissionEvent,
+        timestamp: SystemTime,
+    },
 }
 
 impl TokenEvent {
     pub fn get_token_amount_for_token_event(&self) -> u64 {
         match self {
-            TokenEvent::Mint { details, .. } => match details {
+            TokenEvent::Mint {
+                amount, details, ..
+            } => match details {
                 MintEvent::NewUserSignup { .. } => 1000,
                 MintEvent::Referral { .. } => 500,
+                MintEvent::TournamentPrize { .. }
+                | MintEvent::TokenFaucet
+                | MintEvent::AchievementReward { .. }
+                | MintEvent::SignupBonusUnlockedAtSignup { .. }
+                | MintEvent::SignupBonusVested { .. } => *amount,
             },
             _ => 0,
         }
     }
+
+    /// None for the variants that were never recorded with a time
+    pub fn get_timestamp(&self) -> Option<SystemTime> {
+        match self {
+            TokenEvent::Mint { timestamp, .. }
+            | TokenEvent::Stake { timestamp, .. }
+            | TokenEvent::HotOrNotOutcomePayout { timestamp, .. }
+            | TokenEvent::HouseRake { timestamp, .. }
+            | TokenEvent::PostBoost { timestamp, .. }
+            | TokenEvent::ReferralCommission { timestamp, .. } => Some(*timestamp),
+            TokenEvent::Burn | TokenEvent::Transfer => None,
+        }
+    }
+
+    /// The hot or not post an event is about, as the post's canister id and post id
+    pub fn get_hot_or_not_post(&self) -> Option<(Principal, u64)> {
+        match self {
+            TokenEvent::Stake {
+                details:
+                    StakeEvent::BetOnHotOrNotPost {
+                        post_canister_id,
+                        post_id,
+                        ..
+                    }
+                    | StakeEvent::BetOnHotOrNotPostCancelled {
+                        post_canister_id,
+                        post_id,
+                        ..
+                    },
+                ..
+            }
+            | TokenEvent::HotOrNotOutcomePayout {
+                details:
+                    HotOrNotOutcomePayoutEvent::CommissionFromHotOrNotBet {
+                        post_canister_id,
+                        post_id,
+                        ..
+                    }
+                    | HotOrNotOutcomePayoutEvent::WinningsEarnedFromBet {
+                        post_canister_id,
+                        post_id,
+                        ..
+                    }
+                    | HotOrNotOutcomePayoutEvent::ReferrerShareOfRefereeBetWinnings {
+                        post_canister_id,
+                        post_id,
+                        ..
+                    }
+                    | HotOrNotOutcomePayoutEvent::CollaboratorShareOfCommission {
+                        post_canister_id,
+                        post_id,
+                        ..
+                    }
+                    | HotOrNotOutcomePayoutEvent::PotInsuranceTopUp {
+                        post_canister_id,
+                        post_id,
+                        ..
+                    },
+                ..
+            }
+            | TokenEvent::HouseRake {
+                details:
+                    HouseRakeEvent::HouseRakeFromHotOrNotBet {
+                        post_canister_id,
+                        post_id,
+                        ..
+                    }
+                    | HouseRakeEvent::PotInsuranceTopUpsPaidOut {
+                        post_canister_id,
+                        post_id,
+                        ..
+                    },
+                ..
+            }
+            | TokenEvent::ReferralCommission {
+                details:
+                    ReferralCommissionEvent::ShareOfRefereeBetWinnings {
+                        post_canister_id,
+                        post_id,
+                        ..
+                    },
+                ..
+            } => Some((*post_canister_id, *post_id)),
+            _ => None,
+        }
+    }
+
+    /// The slot and room of the hot or not post an event is about. None for stakes, which
+    /// are recorded before the bet lands in a room
+    pub fn get_hot_or_not_slot_and_room(&self) -> Option<(u8, u64)> {
+        match self {
+            TokenEvent::HotOrNotOutcomePayout {
+                details:
+                    HotOrNotOutcomePayoutEvent::CommissionFromHotOrNotBet {
+                        slot_id, room_id, ..
+                    }
+                    | HotOrNotOutcomePayoutEvent::WinningsEarnedFromBet {
+                        slot_id, room_id, ..
+                    }
+                    | HotOrNotOutcomePayoutEvent::ReferrerShareOfRefereeBetWinnings {
+                        slot_id,
+                        room_id,
+                        ..
+                    }
+                    | HotOrNotOutcomePayoutEvent::CollaboratorShareOfCommission {
+                        slot_id, room_id, ..
+                    }
+                    | HotOrNotOutcomePayoutEvent::PotInsuranceTopUp {
+                        slot_id, room_id, ..
+                    },
+                ..
+            }
+            | TokenEvent::HouseRake {
+                details:
+                    HouseRakeEvent::HouseRakeFromHotOrNotBet {
+                        slot_id, room_id, ..
+                    }
+                    | HouseRakeEvent::PotInsuranceTopUpsPaidOut {
+                        slot_id, room_id, ..
+                    },
+                ..
+            }
+            | TokenEvent::ReferralCommission {
+                details:
+                    ReferralCommissionEvent::ShareOfRefereeBetWinnings {
+                        slot_id, room_id, ..
+                    },
+                ..
+            } => Some((*slot_id, *room_id)),
+            _ => None,
+        }
+    }
 }
 
 #[derive(Clone, CandidType, Deserialize, Debug, PartialEq, Eq, Serialize)]
@@ -49,6 +208,25 @@ pub enum MintEvent {
         referee_user_principal_id: Principal,
         referrer_user_principal_id: Principal,
     },
+    TournamentPrize {
+        tournament_id: TournamentId,
+        rank: u32,
+    },
+    // * Test tokens handed out by the faucet on staging deployments
+    TokenFaucet,
+    AchievementReward {
+        achievement: Achievement,
+    },
+    // * Replaces NewUserSignup for users who signed up once the bonus started vesting
+    SignupBonusUnlockedAtSignup {
+        new_user_principal_id: Principal,
+        locked_amount: u64,
+    },
+    // * One tranche of the locked part of the signup bonus, for a day the user was active
+    SignupBonusVested {
+        number_of_days_vested: u64,
+        number_of_vesting_days: u64,
+    },
 }
 
 #[derive(Clone, CandidType, Deserialize, Serialize, Debug, PartialEq, Eq)]
@@ -59,6 +237,19 @@ pub enum StakeEvent {
         bet_amount: u64,
         bet_direction: BetDirection,
     },
+    // * Part of the stake handed back for a bet taken back early in its slot
+    BetOnHotOrNotPostCancelled {
+        post_canister_id: Principal,
+        post_id: u64,
+        bet_amount: u64,
+        refund_amount: u64,
+    },
+    BetOnVersusContest {
+        contest_id: VersusContestId,
+        post_canister_id: Principal,
+        post_id: u64,
+        bet_amount: u64,
+    },
 }
 
 #[derive(Clone, CandidType, Deserialize, Serialize, Debug, PartialEq, Eq)]
@@ -78,6 +269,82 @@ pub enum HotOrNotOutcomePayoutEvent {
         event_outcome: BetOutcomeForBetMaker,
         winnings_amount: u64,
     },
+    // * Recorded before referral commissions got an event of their own
+    ReferrerShareOfRefereeBetWinnings {
+        referee_user_principal_id: Principal,
+        post_canister_id: Principal,
+        post_id: u64,
+        slot_id: u8,
+        room_id: u64,
+        share_amount: u64,
+    },
+    CollaboratorShareOfCommission {
+        post_creator_user_principal_id: Principal,
+        post_canister_id: Principal,
+        post_id: u64,
+        slot_id: u8,
+        room_id: u64,
+        room_pot_total_amount: u64,
+        share_amount: u64,
+    },
+    // * Paid by the treasury on top of the winnings from a room that settled below the pot
+    // * floor
+    PotInsuranceTopUp {
+        post_canister_id: Principal,
+        post_id: u64,
+        slot_id: u8,
+        room_id: u64,
+        top_up_amount: u64,
+    },
+    // * Zero when the bet lost
+    VersusContestPayout {
+        contest_id: VersusContestId,
+        post_canister_id: Principal,
+        post_id: u64,
+        amount_bet: u64,
+        payout_amount: u64,
+    },
+}
+
+#[derive(Clone, CandidType, Deserialize, Serialize, Debug, PartialEq, Eq)]
+pub enum HouseRakeEvent {
+    HouseRakeFromHotOrNotBet {
+        post_canister_id: Principal,
+        post_id: u64,
+        slot_id: u8,
+        room_id: u64,
+        room_pot_total_amount: u64,
+    },
+    // * Paid out of the treasury to the winners of a room that settled below the pot floor
+    PotInsuranceTopUpsPaidOut {
+        post_canister_id: Principal,
+        post_id: u64,
+        slot_id: u8,
+        room_id: u64,
+        room_pot_total_amount: u64,
+    },
+}
+
+// * Tokens spent on a boost are burnt
+#[derive(Clone, CandidType, Deserialize, Serialize, Debug, PartialEq, Eq)]
+pub enum PostBoostEvent {
+    BoostedOwnPost {
+        post_id: u64,
+        boost_percentage: u64,
+        boosted_until: SystemTime,
+    },
+}
+
+// * Credited to the referrer's canister
+#[derive(Clone, CandidType, Deserialize, Serialize, Debug, PartialEq, Eq)]
+pub enum ReferralCommissionEvent {
+    ShareOfRefereeBetWinnings {
+        referee_user_principal_id: Principal,
+        post_canister_id: Principal,
+        post_id: u64,
+        slot_id: u8,
+        room_id: u64,
+    },
 }
 
 pub const HOT_OR_NOT_BET_CREATOR_COMMISSION_PERCENTAGE: u64 = 10;
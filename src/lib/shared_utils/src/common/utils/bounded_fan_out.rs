@@ -0,0 +1,288 @@
+use std::{
+    cell::RefCell,
+    collections::VecDeque,
+    future::Future,
+    pin::Pin,
+    rc::Rc,
+    task::{Context, Poll, Waker},
+    time::Duration,
+};
+
+use ic_cdk_timers::TimerId;
+
+/// How far an orchestration path is allowed to fan out. Keeps the number of outgoing calls a
+/// single message has open under the subnet's limit, and stops one slow canister from holding
+/// up the rest of the batch.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FanOutLimits {
+    pub max_concurrent_calls: usize,
+    pub per_call_timeout: Duration,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CallTimedOut;
+
+/// Runs `calls` with at most `limits.max_concurrent_calls` of them in flight, giving up on any
+/// call that hasn't returned within `limits.per_call_timeout` of being started. Results come
+/// back in the same order as `calls`.
+///
+/// `ic_cdk::call` sends the call as soon as it is invoked, so wrap each call in an
+/// `async move` block for it to only go out once there is room for it.
+pub async fn fan_out_calls<F: Future>(
+    calls: impl IntoIterator<Item = F>,
+    limits: &FanOutLimits,
+) -> Vec<Result<F::Output, CallTimedOut>> {
+    let per_call_timeout = limits.per_call_timeout;
+
+    run_with_concurrency_limit(
+        calls
+            .into_iter()
+            .map(|call| with_timeout(call, per_call_timeout)),
+        limits.max_concurrent_calls,
+    )
+    .await
+}
+
+/// Runs `calls` with at most `max_concurrent_calls` of them in flight. Calls are only started
+/// once there is room for them. Results come back in the same order as `calls`.
+pub fn run_with_concurrency_limit<F: Future>(
+    calls: impl IntoIterator<Item = F>,
+    max_concurrent_calls: usize,
+) -> BoundedFanOut<F> {
+    let queued_calls: VecDeque<(usize, F)> = calls.into_iter().enumerate().collect();
+    let outputs = queued_calls.iter().map(|_| None).collect();
+
+    BoundedFanOut {
+        queued_calls,
+        in_flight_calls: Vec::new(),
+        outputs,
+        max_concurrent_calls: max_concurrent_calls.max(1),
+    }
+}
+
+pub struct BoundedFanOut<F: Future> {
+    queued_calls: VecDeque<(usize, F)>,
+    in_flight_calls: Vec<(usize, Pin<Box<F>>)>,
+    outputs: Vec<Option<F::Output>>,
+    max_concurrent_calls: usize,
+}
+
+impl<F: Future> Unpin for BoundedFanOut<F> {}
+
+impl<F: Future> Future for BoundedFanOut<F> {
+    type Output = Vec<F::Output>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = &mut *self;
+
+        loop {
+            while this.in_flight_calls.len() < this.max_concurrent_calls {
+                let Some((index, call)) = this.queued_calls.pop_front() else {
+                    break;
+                };
+                this.in_flight_calls.push((index, Box::pin(call)));
+            }
+
+            let in_flight_before_polling = this.in_flight_calls.len();
+            let outputs = &mut this.outputs;
+            this.in_flight_calls
+                .retain_mut(|(index, call)| match call.as_mut().poll(cx) {
+                    Poll::Ready(output) => {
+                        outputs[*index] = Some(output);
+                        false
+                    }
+                    Poll::Pending => true,
+                });
+
+            if this.in_flight_calls.is_empty() && this.queued_calls.is_empty() {
+                return Poll::Ready(
+                    this.outputs
+                        .drain(..)
+                        .map(|output| output.expect("every call has completed"))
+                        .collect(),
+                );
+            }
+
+            // * Only go round again when a call finished and freed up room for a queued one
+            if this.in_flight_calls.len() == in_flight_before_polling
+                || this.queued_calls.is_empty()
+            {
+                return Poll::Pending;
+            }
+        }
+    }
+}
+
+/// Resolves to `Err(CallTimedOut)` if `call` hasn't completed within `timeout` of first being
+/// polled. The underlying call isn't cancelled, its response is just ignored.
+pub fn with_timeout<F: Future>(call: F, timeout: Duration) -> Timeout<F> {
+    Timeout {
+        call: Box::pin(call),
+        timeout,
+        state: None,
+        timer_id: None,
+    }
+}
+
+#[derive(Default)]
+struct TimeoutState {
+    has_expired: bool,
+    waker: Option<Waker>,
+}
+
+pub struct Timeout<F: Future> {
+    call: Pin<Box<F>>,
+    timeout: Duration,
+    state: Option<Rc<RefCell<TimeoutState>>>,
+    timer_id: Option<TimerId>,
+}
+
+impl<F: Future> Future for Timeout<F> {
+    type Output = Result<F::Output, CallTimedOut>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = &mut *self;
+
+        if let Poll::Ready(output) = this.call.as_mut().poll(cx) {
+            if let Some(timer_id) = this.timer_id.take() {
+                ic_cdk_timers::clear_timer(timer_id);
+            }
+            return Poll::Ready(Ok(output));
+        }
+
+        // * The clock only starts once the call has actually been started
+        if this.state.is_none() {
+            let state = Rc::new(RefCell::new(TimeoutState::default()));
+            let timer_state = state.clone();
+            this.timer_id = Some(ic_cdk_timers::set_timer(this.timeout, move || {
+                let waker = {
+                    let mut timer_state = timer_state.borrow_mut();
+                    timer_state.has_expired = true;
+                    timer_state.waker.take()
+                };
+                // * Waking polls the call again right away, so drop the borrow first
+                if let Some(waker) = waker {
+                    waker.wake();
+                }
+            }));
+            this.state = Some(state);
+        }
+
+        let mut state = this
+            .state
+            .as_ref()
+            .expect("timeout state is set above")
+            .borrow_mut();
+        if state.has_expired {
+            this.timer_id = None;
+            return Poll::Ready(Err(CallTimedOut));
+        }
+        state.waker = Some(cx.waker().clone());
+
+        Poll::Pending
+    }
+}
+
+impl<F: Future> Drop for Timeout<F> {
+    fn drop(&mut self) {
+        if let Some(timer_id) = self.timer_id.take() {
+            ic_cdk_timers::clear_timer(timer_id);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{
+        cell::Cell,
+        task::{RawWaker, RawWakerVTable},
+    };
+
+    use super::*;
+
+    fn noop_waker() -> Waker {
+        fn clone(_: *const ()) -> RawWaker {
+            RawWaker::new(std::ptr::null(), &VTABLE)
+        }
+        fn noop(_: *const ()) {}
+        static VTABLE: RawWakerVTable = RawWakerVTable::new(clone, noop, noop, noop);
+
+        unsafe { Waker::from_raw(RawWaker::new(std::ptr::null(), &VTABLE)) }
+    }
+
+    fn block_on<F: Future>(future: F) -> F::Output {
+        let waker = noop_waker();
+        let mut cx = Context::from_waker(&waker);
+        let mut future = Box::pin(future);
+
+        loop {
+            if let Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+                return output;
+            }
+        }
+    }
+
+    /// Stands in for an inter-canister call that takes `polls_needed` polls to come back
+    struct MockCall {
+        value: u64,
+        polls_needed: u64,
+        in_flight: Rc<Cell<usize>>,
+        max_in_flight: Rc<Cell<usize>>,
+        is_started: bool,
+    }
+
+    impl Future for MockCall {
+        type Output = u64;
+
+        fn poll(mut self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<u64> {
+            if !self.is_started {
+                self.is_started = true;
+                self.in_flight.set(self.in_flight.get() + 1);
+                self.max_in_flight
+                    .set(self.max_in_flight.get().max(self.in_flight.get()));
+            }
+
+            if self.polls_needed > 0 {
+                self.polls_needed -= 1;
+                return Poll::Pending;
+            }
+
+            self.in_flight.set(self.in_flight.get() - 1);
+            Poll::Ready(self.value)
+        }
+    }
+
+    #[test]
+    fn test_run_with_concurrency_limit() {
+        let in_flight = Rc::new(Cell::new(0));
+        let max_in_flight = Rc::new(Cell::new(0));
+        let calls = (0..10).map(|value| MockCall {
+            value,
+            polls_needed: 10 - value,
+            in_flight: in_flight.clone(),
+            max_in_flight: max_in_flight.clone(),
+            is_started: false,
+        });
+
+        let outputs = block_on(run_with_concurrency_limit(calls, 3));
+
+        assert_eq!(outputs, (0..10).collect::<Vec<_>>());
+        assert_eq!(max_in_flight.get(), 3);
+        assert_eq!(in_flight.get(), 0);
+    }
+
+    #[test]
+    fn test_run_with_concurrency_limit_handles_empty_batches_and_zero_limit() {
+        let outputs = block_on(run_with_concurrency_limit(
+            Vec::<std::future::Ready<u64>>::new(),
+            3,
+        ));
+        assert!(outputs.is_empty());
+
+        let outputs = block_on(run_with_concurrency_limit(
+            (0..3).map(std::future::ready),
+            0,
+        ));
+        assert_eq!(outputs, vec![0, 1, 2]);
+    }
+}
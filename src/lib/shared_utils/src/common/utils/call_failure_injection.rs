@@ -0,0 +1,221 @@
+//! Only compiled with the `call_failure_injection` feature, so that integration tests can
+//! exercise the retry and rollback paths of outbound calls deterministically.
+
+use std::cell::RefCell;
+
+use candid::{CandidType, Deserialize};
+use ic_cdk::api::{
+    call::{CallResult, RejectionCode},
+    management_canister::main::raw_rand,
+};
+
+pub const INJECTED_CALL_FAILURE_MESSAGE: &str = "Call failed by call failure injection";
+
+/// What to do to the outbound calls a canister makes from the moment it's configured. Calls
+/// are numbered from 1 in the order they are made, only counting calls to `method_name`
+/// when it's set.
+#[derive(CandidType, Deserialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct CallFailureInjectionConfig {
+    pub fail_outbound_calls_numbered: Vec<u64>,
+    pub method_name: Option<String>,
+    // * Rounds the response to every counted call is held back for before the caller sees it
+    pub response_delay_in_rounds: u32,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct CallFailureInjectionStatus {
+    pub config: CallFailureInjectionConfig,
+    pub number_of_outbound_calls_counted: u64,
+    pub number_of_outbound_calls_failed: u64,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum OutboundCallFate {
+    Fail,
+    Send { response_delay_in_rounds: u32 },
+}
+
+#[derive(Default)]
+pub struct CallFailureInjector {
+    status: CallFailureInjectionStatus,
+}
+
+impl CallFailureInjector {
+    /// Replaces the config and starts counting calls from scratch
+    pub fn configure(&mut self, config: CallFailureInjectionConfig) {
+        self.status = CallFailureInjectionStatus {
+            config,
+            ..Default::default()
+        };
+    }
+
+    pub fn get_fate_of_outbound_call(&mut self, method_name: &str) -> OutboundCallFate {
+        let status = &mut self.status;
+
+        if status
+            .config
+            .method_name
+            .as_deref()
+            .is_some_and(|counted_method_name| counted_method_name != method_name)
+        {
+            return OutboundCallFate::Send {
+                response_delay_in_rounds: 0,
+            };
+        }
+
+        status.number_of_outbound_calls_counted += 1;
+        if status
+            .config
+            .fail_outbound_calls_numbered
+            .contains(&status.number_of_outbound_calls_counted)
+        {
+            status.number_of_outbound_calls_failed += 1;
+            return OutboundCallFate::Fail;
+        }
+
+        OutboundCallFate::Send {
+            response_delay_in_rounds: status.config.response_delay_in_rounds,
+        }
+    }
+
+    pub fn get_status(&self) -> CallFailureInjectionStatus {
+        self.status.clone()
+    }
+}
+
+thread_local! {
+    static CALL_FAILURE_INJECTOR: RefCell<CallFailureInjector> = RefCell::default();
+}
+
+pub fn configure_call_failure_injection(config: CallFailureInjectionConfig) {
+    CALL_FAILURE_INJECTOR.with(|call_failure_injector_ref_cell| {
+        call_failure_injector_ref_cell
+            .borrow_mut()
+            .configure(config)
+    });
+}
+
+pub fn get_call_failure_injection_status() -> CallFailureInjectionStatus {
+    CALL_FAILURE_INJECTOR
+        .with(|call_failure_injector_ref_cell| call_failure_injector_ref_cell.borrow().get_status())
+}
+
+/// Fails the call without sending it if it's one of the calls to fail. Otherwise sends it
+/// and holds its response back for as many rounds as configured.
+pub async fn send_with_failure_injection<O, F: std::future::Future<Output = CallResult<O>>>(
+    method_name: &str,
+    send_call: impl FnOnce() -> F,
+) -> CallResult<O> {
+    let fate = CALL_FAILURE_INJECTOR.with(|call_failure_injector_ref_cell| {
+        call_failure_injector_ref_cell
+            .borrow_mut()
+            .get_fate_of_outbound_call(method_name)
+    });
+
+    match fate {
+        OutboundCallFate::Fail => Err((
+            RejectionCode::SysTransient,
+            INJECTED_CALL_FAILURE_MESSAGE.to_string(),
+        )),
+        OutboundCallFate::Send {
+            response_delay_in_rounds,
+        } => {
+            let response = send_call().await;
+
+            // * a call to the management canister takes at least a round to come back
+            for _ in 0..response_delay_in_rounds {
+                let _ = raw_rand().await;
+            }
+
+            response
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn get_fates(
+        call_failure_injector: &mut CallFailureInjector,
+        method_names: &[&str],
+    ) -> Vec<OutboundCallFate> {
+        method_names
+            .iter()
+            .map(|method_name| call_failure_injector.get_fate_of_outbound_call(method_name))
+            .collect()
+    }
+
+    #[test]
+    fn test_nth_outbound_calls_fail() {
+        let mut call_failure_injector = CallFailureInjector::default();
+        call_failure_injector.configure(CallFailureInjectionConfig {
+            fail_outbound_calls_numbered: vec![2, 3],
+            method_name: None,
+            response_delay_in_rounds: 1,
+        });
+
+        assert_eq!(
+            get_fates(&mut call_failure_injector, &["a", "b", "a", "b"]),
+            vec![
+                OutboundCallFate::Send {
+                    response_delay_in_rounds: 1
+                },
+                OutboundCallFate::Fail,
+                OutboundCallFate::Fail,
+                OutboundCallFate::Send {
+                    response_delay_in_rounds: 1
+                },
+            ]
+        );
+        assert_eq!(
+            call_failure_injector
+                .get_status()
+                .number_of_outbound_calls_failed,
+            2
+        );
+    }
+
+    #[test]
+    fn test_only_calls_to_the_configured_method_are_counted() {
+        let mut call_failure_injector = CallFailureInjector::default();
+        call_failure_injector.configure(CallFailureInjectionConfig {
+            fail_outbound_calls_numbered: vec![2],
+            method_name: Some("receive_follow_status_from_follower".into()),
+            response_delay_in_rounds: 3,
+        });
+
+        assert_eq!(
+            get_fates(
+                &mut call_failure_injector,
+                &[
+                    "receive_follow_status_from_follower",
+                    "get_profile_details",
+                    "receive_follow_status_from_follower",
+                ]
+            ),
+            vec![
+                OutboundCallFate::Send {
+                    response_delay_in_rounds: 3
+                },
+                OutboundCallFate::Send {
+                    response_delay_in_rounds: 0
+                },
+                OutboundCallFate::Fail,
+            ]
+        );
+        assert_eq!(
+            call_failure_injector
+                .get_status()
+                .number_of_outbound_calls_counted,
+            2
+        );
+
+        // * reconfiguring starts counting over
+        call_failure_injector.configure(CallFailureInjectionConfig::default());
+        assert_eq!(
+            call_failure_injector.get_status(),
+            CallFailureInjectionStatus::default()
+        );
+    }
+}
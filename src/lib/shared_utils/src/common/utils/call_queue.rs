@@ -0,0 +1,316 @@
+use std::{
+    borrow::Cow,
+    time::{Duration, SystemTime},
+};
+
+use candid::{CandidType, Decode, Deserialize, Encode, Principal};
+use ic_stable_structures::{BoundedStorable, Memory, Storable};
+use serde::Serialize;
+
+use crate::{
+    common::{types::stable_queue::StableQueue, utils::inter_canister_call},
+    pagination::{self, Page},
+};
+
+// * Calls with larger arguments are sent once, without being queued for retries
+pub const MAX_QUEUED_CALL_ARGS_SIZE: usize = 8 * 1024;
+pub const MAX_QUEUED_CALL_METHOD_NAME_LENGTH: usize = 100;
+pub const MAX_QUEUED_CALL_ATTEMPTS: u32 = 10;
+pub const QUEUED_CALL_INITIAL_BACKOFF: Duration = Duration::from_secs(10);
+pub const QUEUED_CALL_MAXIMUM_BACKOFF: Duration = Duration::from_secs(60 * 60);
+// * A call still unanswered this long after it was sent, e.g. because the canister was
+// * upgraded while waiting on it, is sent again
+pub const QUEUED_CALL_IN_FLIGHT_LEASE: Duration = Duration::from_secs(10 * 60);
+// * Past this many, the oldest dead lettered calls are dropped
+pub const MAX_DEAD_LETTERED_CALLS: u64 = 1_000;
+
+/// A fire and forget call to another canister, along with how its attempts went so far
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug, PartialEq, Eq)]
+pub struct QueuedCall {
+    pub canister_id: Principal,
+    pub method_name: String,
+    // * Candid encoded
+    pub args: Vec<u8>,
+    pub queued_at: SystemTime,
+    pub number_of_failed_attempts: u32,
+    pub next_attempt_at: SystemTime,
+    pub last_error: Option<String>,
+}
+
+impl QueuedCall {
+    /// Returns `None` if the call is too large to be queued
+    pub fn new(
+        canister_id: Principal,
+        method_name: &str,
+        args: Vec<u8>,
+        current_time: &SystemTime,
+    ) -> Option<Self> {
+        if args.len() > MAX_QUEUED_CALL_ARGS_SIZE
+            || method_name.len() > MAX_QUEUED_CALL_METHOD_NAME_LENGTH
+        {
+            return None;
+        }
+
+        Some(Self {
+            canister_id,
+            method_name: method_name.to_string(),
+            args,
+            queued_at: *current_time,
+            number_of_failed_attempts: 0,
+            next_attempt_at: *current_time,
+            last_error: None,
+        })
+    }
+}
+
+impl Storable for QueuedCall {
+    fn to_bytes(&self) -> Cow<'_, [u8]> {
+        Cow::Owned(Encode!(self).unwrap())
+    }
+
+    fn from_bytes(bytes: Cow<[u8]>) -> Self {
+        Decode!(&bytes, Self).unwrap()
+    }
+}
+
+impl BoundedStorable for QueuedCall {
+    // * Room for the arguments, the method name and the last error on top of the fixed fields
+    const MAX_SIZE: u32 = MAX_QUEUED_CALL_ARGS_SIZE as u32 + 2 * 1_024;
+    const IS_FIXED_SIZE: bool = false;
+}
+
+/// Durable outbox of fire and forget calls to other canisters. Calls that fail are retried
+/// with exponential backoff, and set aside as dead letters for admins to look into once they
+/// run out of attempts. Pending and dead lettered calls each live in their own stable memory
+/// region.
+pub struct CallQueue<M: Memory> {
+    pending_calls: StableQueue<QueuedCall, M>,
+    dead_lettered_calls: StableQueue<QueuedCall, M>,
+}
+
+impl<M: Memory> CallQueue<M> {
+    pub fn init(pending_calls_memory: M, dead_lettered_calls_memory: M) -> Self {
+        Self {
+            pending_calls: StableQueue::init(pending_calls_memory),
+            dead_lettered_calls: StableQueue::init(dead_lettered_calls_memory),
+        }
+    }
+
+    pub fn len(&self) -> u64 {
+        self.pending_calls.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pending_calls.is_empty()
+    }
+
+    pub fn enqueue(&mut self, queued_call: QueuedCall) -> u64 {
+        self.pending_calls.push_back(queued_call)
+    }
+
+    /// Hands out up to `limit` calls that are due to be sent, along with the ids to report
+    /// back on them under. They stay queued until reported on, but aren't handed out again
+    /// before `QUEUED_CALL_IN_FLIGHT_LEASE` is up.
+    pub fn lease_due_calls(
+        &mut self,
+        current_time: &SystemTime,
+        limit: usize,
+    ) -> Vec<(u64, QueuedCall)> {
+        let due_calls: Vec<(u64, QueuedCall)> = self
+            .pending_calls
+            .iter()
+            .filter(|(_, queued_call)| queued_call.next_attempt_at <= *current_time)
+            .take(limit)
+            .collect();
+
+        due_calls
+            .into_iter()
+            .filter_map(|(id, mut queued_call)| {
+                queued_call.next_attempt_at = *current_time + QUEUED_CALL_IN_FLIGHT_LEASE;
+                let leased_id = self.pending_calls.requeue(id, queued_call.clone())?;
+                Some((leased_id, queued_call))
+            })
+            .collect()
+    }
+
+    pub fn record_success(&mut self, id: u64) {
+        self.pending_calls.ack(id);
+    }
+
+    /// Schedules the call to be retried after backing off, or dead letters it once it has
+    /// run out of attempts. Returns true if it was dead lettered.
+    pub fn record_failure(&mut self, id: u64, error: String, current_time: &SystemTime) -> bool {
+        let Some(mut queued_call) = self.pending_calls.get(id) else {
+            return false;
+        };
+        queued_call.number_of_failed_attempts += 1;
+        queued_call.last_error = Some(error);
+
+        if queued_call.number_of_failed_attempts >= MAX_QUEUED_CALL_ATTEMPTS {
+            self.pending_calls.ack(id);
+            if self.dead_lettered_calls.len() >= MAX_DEAD_LETTERED_CALLS {
+                if let Some((oldest_id, _)) = self.dead_lettered_calls.peek_front() {
+                    self.dead_lettered_calls.ack(oldest_id);
+                }
+            }
+            self.dead_lettered_calls.push_back(queued_call);
+
+            return true;
+        }
+
+        queued_call.next_attempt_at =
+            *current_time + get_backoff(queued_call.number_of_failed_attempts);
+        self.pending_calls.requeue(id, queued_call);
+
+        false
+    }
+
+    /// Oldest first
+    pub fn get_dead_lettered_calls(
+        &self,
+        from_inclusive_index: u64,
+        page_size: u64,
+    ) -> Page<QueuedCall> {
+        let index_page = pagination::get_page(
+            0..self.dead_lettered_calls.len() as usize,
+            from_inclusive_index,
+            page_size,
+        );
+
+        Page {
+            items: self
+                .dead_lettered_calls
+                .iter()
+                .skip(from_inclusive_index as usize)
+                .take(index_page.items.len())
+                .map(|(_, queued_call)| queued_call)
+                .collect(),
+            total_count: index_page.total_count,
+            next_from_inclusive_index: index_page.next_from_inclusive_index,
+        }
+    }
+}
+
+/// Doubles with every failed attempt, starting from `QUEUED_CALL_INITIAL_BACKOFF`
+pub fn get_backoff(number_of_failed_attempts: u32) -> Duration {
+    let doublings = number_of_failed_attempts.saturating_sub(1).min(31);
+
+    QUEUED_CALL_INITIAL_BACKOFF
+        .saturating_mul(1 << doublings)
+        .min(QUEUED_CALL_MAXIMUM_BACKOFF)
+}
+
+/// Sends a queued call and waits for it to be answered
+pub async fn send_queued_call(queued_call: &QueuedCall) -> Result<(), String> {
+    inter_canister_call::call_raw(
+        queued_call.canister_id,
+        &queued_call.method_name,
+        queued_call.args.clone(),
+        0,
+    )
+    .await
+    .map(|_| ())
+    .map_err(|(rejection_code, message)| format!("{:?}: {}", rejection_code, message))
+}
+
+#[cfg(test)]
+mod test {
+    use ic_stable_structures::VectorMemory;
+    use test_utils::setup::test_constants::get_mock_user_alice_canister_id;
+
+    use super::*;
+
+    fn new_call_queue() -> CallQueue<VectorMemory> {
+        CallQueue::init(VectorMemory::default(), VectorMemory::default())
+    }
+
+    fn get_queued_call(current_time: &SystemTime) -> QueuedCall {
+        QueuedCall::new(
+            get_mock_user_alice_canister_id(),
+            "receive_post_from_followee",
+            vec![0; 10],
+            current_time,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_oversized_calls_are_not_queued() {
+        let current_time = SystemTime::UNIX_EPOCH;
+        let canister_id = get_mock_user_alice_canister_id();
+
+        assert!(QueuedCall::new(
+            canister_id,
+            "method",
+            vec![0; MAX_QUEUED_CALL_ARGS_SIZE + 1],
+            &current_time
+        )
+        .is_none());
+        assert!(QueuedCall::new(
+            canister_id,
+            &"m".repeat(MAX_QUEUED_CALL_METHOD_NAME_LENGTH + 1),
+            vec![],
+            &current_time
+        )
+        .is_none());
+    }
+
+    #[test]
+    fn test_leased_calls_are_not_handed_out_again_until_the_lease_is_up() {
+        let mut call_queue = new_call_queue();
+        let current_time = SystemTime::UNIX_EPOCH;
+        call_queue.enqueue(get_queued_call(&current_time));
+
+        assert_eq!(call_queue.lease_due_calls(&current_time, 10).len(), 1);
+        assert!(call_queue.lease_due_calls(&current_time, 10).is_empty());
+        assert_eq!(call_queue.len(), 1);
+
+        let leased_calls =
+            call_queue.lease_due_calls(&(current_time + QUEUED_CALL_IN_FLIGHT_LEASE), 10);
+        assert_eq!(leased_calls.len(), 1);
+
+        call_queue.record_success(leased_calls[0].0);
+        assert!(call_queue.is_empty());
+    }
+
+    #[test]
+    fn test_failed_calls_back_off_and_end_up_dead_lettered() {
+        let mut call_queue = new_call_queue();
+        let mut current_time = SystemTime::UNIX_EPOCH;
+        call_queue.enqueue(get_queued_call(&current_time));
+
+        (1..MAX_QUEUED_CALL_ATTEMPTS).for_each(|attempt| {
+            let (id, _) = call_queue.lease_due_calls(&current_time, 10)[0].clone();
+            assert!(!call_queue.record_failure(id, "rejected".to_string(), &current_time));
+
+            // * not due again until it has backed off
+            let backoff = get_backoff(attempt);
+            assert!(call_queue
+                .lease_due_calls(&(current_time + backoff - Duration::from_secs(1)), 10)
+                .is_empty());
+            current_time += backoff;
+        });
+
+        let (id, _) = call_queue.lease_due_calls(&current_time, 10)[0].clone();
+        assert!(call_queue.record_failure(id, "rejected".to_string(), &current_time));
+        assert!(call_queue.is_empty());
+
+        let dead_lettered_calls = call_queue.get_dead_lettered_calls(0, 10);
+        assert_eq!(dead_lettered_calls.total_count, 1);
+        assert_eq!(
+            dead_lettered_calls.items[0].number_of_failed_attempts,
+            MAX_QUEUED_CALL_ATTEMPTS
+        );
+        assert_eq!(
+            dead_lettered_calls.items[0].last_error,
+            Some("rejected".to_string())
+        );
+    }
+
+    #[test]
+    fn test_get_backoff() {
+        assert_eq!(get_backoff(1), QUEUED_CALL_INITIAL_BACKOFF);
+        assert_eq!(get_backoff(3), QUEUED_CALL_INITIAL_BACKOFF * 4);
+        assert_eq!(get_backoff(u32::MAX), QUEUED_CALL_MAXIMUM_BACKOFF);
+    }
+}
@@ -0,0 +1,338 @@
+use std::collections::BTreeMap;
+
+use candid::{CandidType, Deserialize};
+use serde::Serialize;
+use unicode_normalization::UnicodeNormalization;
+
+/// Hashtags trend against others written in the same script, so that one locale posting a
+/// lot doesn't push every other locale's hashtags out of trending
+#[derive(
+    CandidType, Clone, Copy, Deserialize, Serialize, Debug, PartialEq, Eq, PartialOrd, Ord, Hash,
+)]
+pub enum HashtagLanguageBucket {
+    Latin,
+    Cyrillic,
+    Greek,
+    Arabic,
+    Hebrew,
+    Devanagari,
+    Bengali,
+    Tamil,
+    Telugu,
+    Thai,
+    Hangul,
+    Japanese,
+    Han,
+    Other,
+}
+
+/// Maps a hashtag onto the spelling it should trend under, e.g. a romanised form. Runs on the
+/// already case-folded and composed hashtag. Returns None to keep the hashtag as it is.
+pub trait HashtagTransliterator {
+    fn transliterate(&self, hashtag: &str) -> Option<String>;
+}
+
+pub struct NoTransliteration;
+
+impl HashtagTransliterator for NoTransliteration {
+    fn transliterate(&self, _hashtag: &str) -> Option<String> {
+        None
+    }
+}
+
+fn case_fold_and_compose(hashtag: &str) -> String {
+    hashtag
+        .trim()
+        .trim_start_matches('#')
+        .nfc()
+        .filter(|character| !character.is_whitespace() && !character.is_control())
+        .flat_map(char::to_lowercase)
+        // * Lowercasing can decompose some characters again
+        .nfc()
+        .collect()
+}
+
+/// Without the leading `#`, lowercased and in Unicode NFC, so that `#Café`, `café` and a
+/// `cafe` followed by a combining accent are all the same hashtag. None if nothing is left.
+pub fn normalize_hashtag(hashtag: &str) -> Option<String> {
+    normalize_hashtag_with_transliterator(hashtag, &NoTransliteration)
+}
+
+pub fn normalize_hashtag_with_transliterator(
+    hashtag: &str,
+    transliterator: &impl HashtagTransliterator,
+) -> Option<String> {
+    let normalized_hashtag = case_fold_and_compose(hashtag);
+    let normalized_hashtag = match transliterator.transliterate(&normalized_hashtag) {
+        Some(transliterated_hashtag) => case_fold_and_compose(&transliterated_hashtag),
+        None => normalized_hashtag,
+    };
+
+    (!normalized_hashtag.is_empty()).then_some(normalized_hashtag)
+}
+
+/// Normalizes each hashtag, dropping the empty ones and any that end up the same as an
+/// earlier one
+pub fn normalize_hashtags(hashtags: &[String]) -> Vec<String> {
+    let mut normalized_hashtags: Vec<String> = Vec::with_capacity(hashtags.len());
+
+    hashtags
+        .iter()
+        .filter_map(|hashtag| normalize_hashtag(hashtag))
+        .for_each(|normalized_hashtag| {
+            if !normalized_hashtags.contains(&normalized_hashtag) {
+                normalized_hashtags.push(normalized_hashtag);
+            }
+        });
+
+    normalized_hashtags
+}
+
+// * Keeps the table small enough to push to publishing canisters in one call
+pub const MAXIMUM_NUMBER_OF_HASHTAG_ALIASES: usize = 5_000;
+
+/// Hashtags moderators have merged into another one, e.g. misspelled or abusive variants.
+/// Key is the normalized hashtag merged away, value is the normalized hashtag it was merged
+/// into. Chains are collapsed as merges come in, so every alias points straight at a hashtag
+/// that isn't merged into anything itself.
+#[derive(CandidType, Deserialize, Serialize, Clone, Default, Debug, PartialEq, Eq)]
+pub struct HashtagAliases {
+    aliases: BTreeMap<String, String>,
+}
+
+impl HashtagAliases {
+    /// The hashtag a normalized hashtag is merged into, or the hashtag itself
+    pub fn resolve<'a>(&'a self, hashtag: &'a str) -> &'a str {
+        self.aliases
+            .get(hashtag)
+            .map(String::as_str)
+            .unwrap_or(hashtag)
+    }
+
+    /// Merges `hashtag` into `into_hashtag`, along with anything merged into `hashtag` before.
+    /// Returns the normalized pair recorded.
+    pub fn merge(&mut self, hashtag: &str, into_hashtag: &str) -> Result<(String, String), String> {
+        let (Some(alias), Some(into_hashtag)) =
+            (normalize_hashtag(hashtag), normalize_hashtag(into_hashtag))
+        else {
+            return Err("Hashtags can't be empty".to_string());
+        };
+        let canonical_hashtag = self.resolve(&into_hashtag).to_string();
+
+        if alias == canonical_hashtag {
+            return Err(format!(
+                "#{} is already merged into #{}, unmerge it first",
+                into_hashtag, alias
+            ));
+        }
+
+        if !self.aliases.contains_key(&alias)
+            && self.aliases.len() >= MAXIMUM_NUMBER_OF_HASHTAG_ALIASES
+        {
+            return Err("Too many hashtags merged already".to_string());
+        }
+
+        self.aliases
+            .values_mut()
+            .filter(|merged_into| **merged_into == alias)
+            .for_each(|merged_into| *merged_into = canonical_hashtag.clone());
+        self.aliases
+            .insert(alias.clone(), canonical_hashtag.clone());
+
+        Ok((alias, canonical_hashtag))
+    }
+
+    /// Returns false if the hashtag wasn't merged into anything
+    pub fn unmerge(&mut self, hashtag: &str) -> bool {
+        normalize_hashtag(hashtag)
+            .and_then(|alias| self.aliases.remove(&alias))
+            .is_some()
+    }
+
+    /// Resolves already normalized hashtags, dropping any that end up the same as an earlier
+    /// one
+    pub fn apply(&self, hashtags: &[String]) -> Vec<String> {
+        let mut resolved_hashtags: Vec<String> = Vec::with_capacity(hashtags.len());
+
+        hashtags
+            .iter()
+            .map(|hashtag| self.resolve(hashtag))
+            .for_each(|resolved_hashtag| {
+                if !resolved_hashtags
+                    .iter()
+                    .any(|hashtag| hashtag == resolved_hashtag)
+                {
+                    resolved_hashtags.push(resolved_hashtag.to_string());
+                }
+            });
+
+        resolved_hashtags
+    }
+
+    pub fn get_all(&self) -> Vec<(String, String)> {
+        self.aliases
+            .iter()
+            .map(|(alias, canonical_hashtag)| (alias.clone(), canonical_hashtag.clone()))
+            .collect()
+    }
+}
+
+fn get_script_bucket(character: char) -> Option<HashtagLanguageBucket> {
+    use HashtagLanguageBucket::*;
+
+    match character as u32 {
+        0x0041..=0x005A | 0x0061..=0x007A | 0x00C0..=0x024F | 0x1E00..=0x1EFF => Some(Latin),
+        0x0370..=0x03FF | 0x1F00..=0x1FFF => Some(Greek),
+        0x0400..=0x052F => Some(Cyrillic),
+        0x0590..=0x05FF => Some(Hebrew),
+        0x0600..=0x06FF | 0x0750..=0x077F => Some(Arabic),
+        0x0900..=0x097F => Some(Devanagari),
+        0x0980..=0x09FF => Some(Bengali),
+        0x0B80..=0x0BFF => Some(Tamil),
+        0x0C00..=0x0C7F => Some(Telugu),
+        0x0E00..=0x0E7F => Some(Thai),
+        0x1100..=0x11FF | 0x3130..=0x318F | 0xAC00..=0xD7AF => Some(Hangul),
+        0x3040..=0x30FF | 0x31F0..=0x31FF | 0xFF66..=0xFF9F => Some(Japanese),
+        0x3400..=0x4DBF | 0x4E00..=0x9FFF | 0xF900..=0xFAFF => Some(Han),
+        _ => None,
+    }
+}
+
+/// The script most of the hashtag's letters are written in. Digits and symbols don't count.
+pub fn get_language_bucket(hashtag: &str) -> HashtagLanguageBucket {
+    let mut letters_per_bucket: BTreeMap<HashtagLanguageBucket, usize> = BTreeMap::new();
+    hashtag
+        .chars()
+        .filter_map(get_script_bucket)
+        .for_each(|bucket| *letters_per_bucket.entry(bucket).or_default() += 1);
+
+    // * Japanese mixes kanji in with kana, so any kana at all makes it Japanese
+    if letters_per_bucket.contains_key(&HashtagLanguageBucket::Japanese) {
+        return HashtagLanguageBucket::Japanese;
+    }
+
+    letters_per_bucket
+        .into_iter()
+        .max_by(|(bucket_a, count_a), (bucket_b, count_b)| {
+            count_a.cmp(count_b).then(bucket_b.cmp(bucket_a))
+        })
+        .map(|(bucket, _)| bucket)
+        .unwrap_or(HashtagLanguageBucket::Other)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_normalize_hashtag() {
+        assert_eq!(normalize_hashtag("#Doggo"), Some("doggo".to_string()));
+        assert_eq!(
+            normalize_hashtag("  #hot or not "),
+            Some("hotornot".to_string())
+        );
+        assert_eq!(
+            normalize_hashtag("alice-tag-0"),
+            Some("alice-tag-0".to_string())
+        );
+        assert_eq!(normalize_hashtag("#"), None);
+        assert_eq!(normalize_hashtag("   "), None);
+
+        // * precomposed and decomposed é end up the same
+        assert_eq!(
+            normalize_hashtag("#Caf\u{e9}"),
+            normalize_hashtag("cafe\u{301}")
+        );
+        assert_eq!(normalize_hashtag("ПРИВЕТ"), Some("привет".to_string()));
+    }
+
+    #[test]
+    fn test_normalize_hashtag_with_transliterator() {
+        struct Romaniser;
+
+        impl HashtagTransliterator for Romaniser {
+            fn transliterate(&self, hashtag: &str) -> Option<String> {
+                (hashtag == "नमस्ते").then(|| "Namaste".to_string())
+            }
+        }
+
+        assert_eq!(
+            normalize_hashtag_with_transliterator("#नमस्ते", &Romaniser),
+            Some("namaste".to_string())
+        );
+        assert_eq!(
+            normalize_hashtag_with_transliterator("#Doggo", &Romaniser),
+            Some("doggo".to_string())
+        );
+    }
+
+    #[test]
+    fn test_normalize_hashtags() {
+        assert_eq!(
+            normalize_hashtags(&[
+                "#Fun".to_string(),
+                "fun".to_string(),
+                "#".to_string(),
+                "#Post".to_string(),
+            ]),
+            vec!["fun".to_string(), "post".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_hashtag_aliases() {
+        let mut hashtag_aliases = HashtagAliases::default();
+
+        assert_eq!(
+            hashtag_aliases.merge("#Dogo", "#Doggo"),
+            Ok(("dogo".to_string(), "doggo".to_string()))
+        );
+        // * merging the target on collapses the chain
+        assert_eq!(
+            hashtag_aliases.merge("doggo", "dog"),
+            Ok(("doggo".to_string(), "dog".to_string()))
+        );
+        assert_eq!(hashtag_aliases.resolve("dogo"), "dog");
+        // * merging into an alias lands on what the alias is merged into
+        assert_eq!(
+            hashtag_aliases.merge("d0g", "dogo"),
+            Ok(("d0g".to_string(), "dog".to_string()))
+        );
+        assert!(hashtag_aliases.merge("dog", "doggo").is_err());
+        assert!(hashtag_aliases.merge("#", "dog").is_err());
+
+        assert_eq!(
+            hashtag_aliases.apply(&["dogo".to_string(), "cat".to_string(), "dog".to_string(),]),
+            vec!["dog".to_string(), "cat".to_string()]
+        );
+
+        assert!(hashtag_aliases.unmerge("#D0g"));
+        assert!(!hashtag_aliases.unmerge("d0g"));
+        assert_eq!(hashtag_aliases.resolve("d0g"), "d0g");
+        assert_eq!(hashtag_aliases.get_all().len(), 2);
+    }
+
+    #[test]
+    fn test_get_language_bucket() {
+        assert_eq!(get_language_bucket("doggo"), HashtagLanguageBucket::Latin);
+        assert_eq!(
+            get_language_bucket("café2023"),
+            HashtagLanguageBucket::Latin
+        );
+        assert_eq!(
+            get_language_bucket("привет"),
+            HashtagLanguageBucket::Cyrillic
+        );
+        assert_eq!(
+            get_language_bucket("नमस्ते"),
+            HashtagLanguageBucket::Devanagari
+        );
+        assert_eq!(get_language_bucket("음악"), HashtagLanguageBucket::Hangul);
+        assert_eq!(get_language_bucket("音楽"), HashtagLanguageBucket::Han);
+        assert_eq!(
+            get_language_bucket("東京のラーメン"),
+            HashtagLanguageBucket::Japanese
+        );
+        assert_eq!(get_language_bucket("2023"), HashtagLanguageBucket::Other);
+    }
+}
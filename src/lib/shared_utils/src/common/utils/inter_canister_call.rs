@@ -0,0 +1,45 @@
+use candid::{
+    utils::{ArgumentDecoder, ArgumentEncoder},
+    Principal,
+};
+use ic_cdk::api::call::{self, CallResult};
+
+/// `ic_cdk::call`, with failures injectable from tests when built with the
+/// `call_failure_injection` feature. Use it for calls whose failure handling is worth
+/// testing, like the ones behind retries and rollbacks.
+pub async fn call<T: ArgumentEncoder, R: for<'a> ArgumentDecoder<'a>>(
+    canister_id: Principal,
+    method_name: &str,
+    args: T,
+) -> CallResult<R> {
+    send(method_name, || call::call(canister_id, method_name, args)).await
+}
+
+/// `ic_cdk::api::call::call_raw`, with failures injectable the same as for `call`
+pub async fn call_raw(
+    canister_id: Principal,
+    method_name: &str,
+    args: Vec<u8>,
+    payment: u64,
+) -> CallResult<Vec<u8>> {
+    send(method_name, || {
+        call::call_raw(canister_id, method_name, args, payment)
+    })
+    .await
+}
+
+#[cfg(feature = "call_failure_injection")]
+async fn send<O, F: std::future::Future<Output = CallResult<O>>>(
+    method_name: &str,
+    send_call: impl FnOnce() -> F,
+) -> CallResult<O> {
+    super::call_failure_injection::send_with_failure_injection(method_name, send_call).await
+}
+
+#[cfg(not(feature = "call_failure_injection"))]
+async fn send<O, F: std::future::Future<Output = CallResult<O>>>(
+    _method_name: &str,
+    send_call: impl FnOnce() -> F,
+) -> CallResult<O> {
+    send_call().await
+}
@@ -0,0 +1,137 @@
+use candid::{CandidType, Deserialize, Principal};
+
+use crate::common::types::known_principal::{KnownPrincipalMap, KnownPrincipalType};
+
+#[derive(CandidType, Deserialize, Debug, PartialEq, Eq)]
+pub enum KnownCallerError {
+    AnonymousCaller,
+    // * None of the allowed callers are in this canister's known principals yet
+    AllowedCallersNotConfigured,
+    CallerNotAllowed,
+}
+
+/// Guard for endpoints that only platform canisters are meant to call, e.g. score pushes,
+/// settlement callbacks and config pushes. Checks the caller against this canister's copy of
+/// the known principals and returns which of the allowed callers it is.
+pub fn authorize_known_caller(
+    caller: &Principal,
+    known_principal_ids: &KnownPrincipalMap,
+    allowed_callers: &[KnownPrincipalType],
+) -> Result<KnownPrincipalType, KnownCallerError> {
+    if *caller == Principal::anonymous() {
+        return Err(KnownCallerError::AnonymousCaller);
+    }
+
+    // * any instance of an allowed role is accepted, e.g. every post cache shard
+    let mut configured_allowed_callers = allowed_callers
+        .iter()
+        .flat_map(|known_principal_type| {
+            known_principal_ids
+                .get_all(known_principal_type)
+                .iter()
+                .map(move |principal_id| (known_principal_type, principal_id))
+        })
+        .peekable();
+
+    if configured_allowed_callers.peek().is_none() {
+        return Err(KnownCallerError::AllowedCallersNotConfigured);
+    }
+
+    configured_allowed_callers
+        .find(|(_known_principal_type, principal_id)| *principal_id == caller)
+        .map(|(known_principal_type, _principal_id)| *known_principal_type)
+        .ok_or(KnownCallerError::CallerNotAllowed)
+}
+
+#[cfg(test)]
+mod test {
+    use test_utils::setup::test_constants::{
+        get_global_super_admin_principal_id, get_mock_canister_id_data_backup,
+        get_mock_canister_id_user_index, get_mock_user_alice_principal_id,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_authorize_known_caller() {
+        let mut known_principal_ids = KnownPrincipalMap::default();
+
+        assert_eq!(
+            authorize_known_caller(
+                &get_mock_canister_id_user_index(),
+                &known_principal_ids,
+                &[KnownPrincipalType::CanisterIdUserIndex],
+            ),
+            Err(KnownCallerError::AllowedCallersNotConfigured)
+        );
+
+        known_principal_ids.insert(
+            KnownPrincipalType::CanisterIdUserIndex,
+            get_mock_canister_id_user_index(),
+        );
+        known_principal_ids.insert(
+            KnownPrincipalType::CanisterIdDataBackup,
+            get_mock_canister_id_data_backup(),
+        );
+        known_principal_ids.insert(
+            KnownPrincipalType::UserIdGlobalSuperAdmin,
+            get_global_super_admin_principal_id(),
+        );
+        let allowed_callers = [
+            KnownPrincipalType::CanisterIdUserIndex,
+            KnownPrincipalType::CanisterIdDataBackup,
+        ];
+
+        assert_eq!(
+            authorize_known_caller(
+                &get_mock_canister_id_data_backup(),
+                &known_principal_ids,
+                &allowed_callers,
+            ),
+            Ok(KnownPrincipalType::CanisterIdDataBackup)
+        );
+        assert_eq!(
+            authorize_known_caller(
+                &get_global_super_admin_principal_id(),
+                &known_principal_ids,
+                &allowed_callers,
+            ),
+            Err(KnownCallerError::CallerNotAllowed)
+        );
+        assert_eq!(
+            authorize_known_caller(
+                &get_mock_user_alice_principal_id(),
+                &known_principal_ids,
+                &allowed_callers,
+            ),
+            Err(KnownCallerError::CallerNotAllowed)
+        );
+
+        known_principal_ids.add_instance(
+            KnownPrincipalType::CanisterIdUserIndex,
+            get_mock_user_alice_principal_id(),
+        );
+        assert_eq!(
+            authorize_known_caller(
+                &get_mock_user_alice_principal_id(),
+                &known_principal_ids,
+                &allowed_callers,
+            ),
+            Ok(KnownPrincipalType::CanisterIdUserIndex)
+        );
+
+        // * even if a known principal were misconfigured as anonymous
+        known_principal_ids.insert(
+            KnownPrincipalType::CanisterIdUserIndex,
+            Principal::anonymous(),
+        );
+        assert_eq!(
+            authorize_known_caller(
+                &Principal::anonymous(),
+                &known_principal_ids,
+                &allowed_callers,
+            ),
+            Err(KnownCallerError::AnonymousCaller)
+        );
+    }
+}
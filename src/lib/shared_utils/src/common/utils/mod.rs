@@ -1,2 +1,11 @@
+pub mod bounded_fan_out;
+#[cfg(feature = "call_failure_injection")]
+pub mod call_failure_injection;
+pub mod call_queue;
+pub mod hashtag;
+pub mod inter_canister_call;
+pub mod known_caller;
+pub mod profanity;
+pub mod pseudonym;
 pub mod stable_memory_serializer_deserializer;
 pub mod system_time;
@@ -0,0 +1,62 @@
+// * Matched against normalized text, so spacing, casing and look-alike digits don't get around
+// * them. Kept to words that are never part of an innocent username.
+const BLOCKED_TERMS: [&str; 12] = [
+    "asshole",
+    "bitch",
+    "bollocks",
+    "cunt",
+    "dickhead",
+    "fuck",
+    "motherfucker",
+    "nigger",
+    "porn",
+    "shit",
+    "slut",
+    "whore",
+];
+
+/// Lowercased, with look-alike digits and symbols swapped for the letters they stand in for and
+/// everything that isn't a letter or digit dropped. `Hot_Or_N0t` and `hotornot` normalize to
+/// the same text.
+pub fn normalize_for_screening(text: &str) -> String {
+    text.chars()
+        .map(|character| match character {
+            '0' => 'o',
+            '1' | '!' => 'i',
+            '3' => 'e',
+            '4' | '@' => 'a',
+            '5' | '$' => 's',
+            '7' => 't',
+            _ => character,
+        })
+        .filter(|character| character.is_alphanumeric())
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+pub fn contains_profanity(text: &str) -> bool {
+    let normalized_text = normalize_for_screening(text);
+
+    BLOCKED_TERMS
+        .iter()
+        .any(|blocked_term| normalized_text.contains(blocked_term))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_normalize_for_screening() {
+        assert_eq!(normalize_for_screening("Hot_Or_N0t"), "hotornot");
+        assert_eq!(normalize_for_screening("  $ir @lice 7 "), "siralicet");
+    }
+
+    #[test]
+    fn test_contains_profanity() {
+        assert!(contains_profanity("sh1t_poster"));
+        assert!(contains_profanity("F.U.C.K"));
+        assert!(!contains_profanity("cool_alice_1234"));
+        assert!(!contains_profanity("Doggos and puppers"));
+    }
+}
@@ -0,0 +1,31 @@
+use candid::Principal;
+
+/// Stand-in for a principal that shouldn't show up in data leaving the canister. A principal
+/// always gets the same pseudonym for the same salt, so its activity can still be grouped, but
+/// the pseudonym can't be traced back to it without the salt.
+pub fn pseudonymize_principal(principal: &Principal, salt: &[u8]) -> Principal {
+    Principal::self_authenticating([salt, principal.as_slice()].concat())
+}
+
+#[cfg(test)]
+mod test {
+    use test_utils::setup::test_constants::{
+        get_mock_user_alice_principal_id, get_mock_user_bob_principal_id,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_pseudonymize_principal() {
+        let alice = get_mock_user_alice_principal_id();
+        let pseudonym = pseudonymize_principal(&alice, &[1; 32]);
+
+        assert_ne!(pseudonym, alice);
+        assert_eq!(pseudonymize_principal(&alice, &[1; 32]), pseudonym);
+        assert_ne!(pseudonymize_principal(&alice, &[2; 32]), pseudonym);
+        assert_ne!(
+            pseudonymize_principal(&get_mock_user_bob_principal_id(), &[1; 32]),
+            pseudonym
+        );
+    }
+}
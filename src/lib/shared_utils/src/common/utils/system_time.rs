@@ -1,13 +1,78 @@
 use ic_cdk::api;
-use std::time::{Duration, SystemTime, UNIX_EPOCH};
-
-pub type SystemTimeProvider = dyn Fn() -> SystemTime;
+use std::{
+    cell::Cell,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
 
 pub fn get_current_system_time_from_ic() -> SystemTime {
+    let nanos_since_epoch = api::time();
+
     UNIX_EPOCH
         .checked_add(Duration::new(
-            api::time() / 1_000_000_000,
-            (api::time() % 1_000_000_000) as u32,
+            nanos_since_epoch / 1_000_000_000,
+            (nanos_since_epoch % 1_000_000_000) as u32,
         ))
         .expect("Getting timestamp from ic_cdk failed")
 }
+
+/// Where betting and scoring code reads the clock from. Read it once at the start of an
+/// operation and pass the time on, so that every step of it agrees on which slot it is in.
+pub trait TimeProvider {
+    fn get_current_time(&self) -> SystemTime;
+}
+
+/// The time of the block the current message runs in. Only moves on across an await.
+pub struct IcTimeProvider;
+
+impl TimeProvider for IcTimeProvider {
+    fn get_current_time(&self) -> SystemTime {
+        get_current_system_time_from_ic()
+    }
+}
+
+/// A clock that only moves when told to, for reproducing slot boundaries in unit tests
+pub struct MockTimeProvider {
+    current_time: Cell<SystemTime>,
+}
+
+impl MockTimeProvider {
+    pub fn new(current_time: SystemTime) -> Self {
+        Self {
+            current_time: Cell::new(current_time),
+        }
+    }
+
+    pub fn set_current_time(&self, current_time: SystemTime) {
+        self.current_time.set(current_time);
+    }
+
+    pub fn advance_by(&self, duration: Duration) {
+        self.current_time.set(self.current_time.get() + duration);
+    }
+}
+
+impl TimeProvider for MockTimeProvider {
+    fn get_current_time(&self) -> SystemTime {
+        self.current_time.get()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_mock_time_provider() {
+        let time_provider = MockTimeProvider::new(UNIX_EPOCH);
+        assert_eq!(time_provider.get_current_time(), UNIX_EPOCH);
+
+        time_provider.advance_by(Duration::from_secs(60 * 60));
+        assert_eq!(
+            time_provider.get_current_time(),
+            UNIX_EPOCH + Duration::from_secs(60 * 60)
+        );
+
+        time_provider.set_current_time(UNIX_EPOCH);
+        assert_eq!(time_provider.get_current_time(), UNIX_EPOCH);
+    }
+}
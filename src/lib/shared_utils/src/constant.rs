@@ -1,14 +1,21 @@
+use std::time::Duration;
+
 use candid::Principal;
 
 use crate::common::types::known_principal::{KnownPrincipalMap, KnownPrincipalType};
 
 pub const INDIVIDUAL_USER_CANISTER_RECHARGE_AMOUNT: u128 = 1_000_000_000_000; // 1T Cycles
 pub const CYCLES_THRESHOLD_TO_INITIATE_RECHARGE: u128 = 500_000_000_000; // 0.5T Cycles
+                                                                         // * Below this, individual user canisters stop taking new bets and posts until topped up
+pub const CYCLES_THRESHOLD_TO_ENTER_LOW_CYCLES_MODE: u128 = 100_000_000_000; // 0.1T Cycles
+pub const CYCLE_TOP_UP_COOLDOWN: Duration = Duration::from_secs(60 * 60);
 
 pub const MAX_USERS_IN_FOLLOWER_FOLLOWING_LIST: u64 = 10000;
 pub const MAX_POSTS_IN_ONE_REQUEST: u64 = 100;
 pub const HOME_FEED_DIFFERENCE_TO_INITIATE_SYNCHRONISATION: u64 = 100;
 pub const HOT_OR_NOT_FEED_DIFFERENCE_TO_INITIATE_SYNCHRONISATION: u64 = 100;
+pub const MAX_TOKEN_FAUCET_AMOUNT: u64 = 10_000;
+pub const TOKEN_FAUCET_COOLDOWN: Duration = Duration::from_secs(60 * 60);
 // * Important Principal IDs
 
 pub fn get_global_super_admin_principal_id_v1(
@@ -24,3 +31,37 @@ pub fn get_global_super_admin_principal_id_v1(
             .expect("USER ID for global super admin not found"),
     }
 }
+
+/// The token faucet is for test and staging deployments, which opt in by building with
+/// `TOKEN_FAUCET_ENABLED=true`. Builds for mainnet never have it, whatever the flag says.
+pub fn is_token_faucet_enabled() -> bool {
+    is_token_faucet_enabled_for_build(
+        option_env!("DFX_NETWORK"),
+        option_env!("TOKEN_FAUCET_ENABLED"),
+    )
+}
+
+fn is_token_faucet_enabled_for_build(
+    dfx_network: Option<&str>,
+    token_faucet_enabled: Option<&str>,
+) -> bool {
+    dfx_network != Some("ic") && token_faucet_enabled == Some("true")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_is_token_faucet_enabled_for_build() {
+        assert!(!is_token_faucet_enabled_for_build(None, None));
+        assert!(!is_token_faucet_enabled_for_build(Some("local"), None));
+        assert!(!is_token_faucet_enabled_for_build(Some("local"), Some("1")));
+        assert!(is_token_faucet_enabled_for_build(None, Some("true")));
+        assert!(is_token_faucet_enabled_for_build(
+            Some("local"),
+            Some("true")
+        ));
+        assert!(!is_token_faucet_enabled_for_build(Some("ic"), Some("true")));
+    }
+}
@@ -1,5 +1,11 @@
+use candid::{CandidType, Deserialize};
+use serde::Serialize;
+
 use crate::constant::MAX_POSTS_IN_ONE_REQUEST;
 
+/// Largest page any list query hands back, well clear of the 2MB response limit
+pub const MAX_ITEMS_PER_PAGE: u64 = MAX_POSTS_IN_ONE_REQUEST;
+
 #[derive(PartialEq, Debug)]
 pub enum PaginationError {
     InvalidBoundsPassed,
@@ -33,6 +39,38 @@ pub fn get_pagination_bounds(
     Ok((from_inclusive_id, upper_bound_exclusive))
 }
 
+/// One page of a longer list. `next_from_inclusive_index` is where the following page starts,
+/// and is None on the last page.
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug, PartialEq, Eq)]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    pub total_count: u64,
+    pub next_from_inclusive_index: Option<u64>,
+}
+
+/// Page sizes above `MAX_ITEMS_PER_PAGE` are capped rather than rejected, and starting past
+/// the end of the list gives an empty last page
+pub fn get_page<I: ExactSizeIterator>(
+    items: I,
+    from_inclusive_index: u64,
+    page_size: u64,
+) -> Page<I::Item> {
+    let total_count = items.len() as u64;
+    let page_size = page_size.clamp(1, MAX_ITEMS_PER_PAGE);
+    let to_exclusive_index = from_inclusive_index
+        .saturating_add(page_size)
+        .min(total_count);
+
+    Page {
+        items: items
+            .skip(from_inclusive_index as usize)
+            .take(page_size as usize)
+            .collect(),
+        total_count,
+        next_from_inclusive_index: (to_exclusive_index < total_count).then_some(to_exclusive_index),
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -72,4 +110,41 @@ mod test {
             Err(PaginationError::ExceededMaxNumberOfItemsAllowedInOneRequest)
         );
     }
+
+    #[test]
+    fn test_get_page() {
+        assert_eq!(
+            get_page(0..25_u32, 10, 10),
+            Page {
+                items: (10..20).collect(),
+                total_count: 25,
+                next_from_inclusive_index: Some(20),
+            }
+        );
+
+        // last page
+        assert_eq!(
+            get_page(0..25_u32, 20, 10),
+            Page {
+                items: (20..25).collect(),
+                total_count: 25,
+                next_from_inclusive_index: None,
+            }
+        );
+
+        // starting past the end
+        assert_eq!(
+            get_page(0..25_u32, 30, 10),
+            Page {
+                items: vec![],
+                total_count: 25,
+                next_from_inclusive_index: None,
+            }
+        );
+
+        // page size is capped
+        let page = get_page(0..250_u32, 0, 1000);
+        assert_eq!(page.items.len() as u64, MAX_ITEMS_PER_PAGE);
+        assert_eq!(page.next_from_inclusive_index, Some(MAX_ITEMS_PER_PAGE));
+    }
 }
@@ -7,6 +7,8 @@ pub enum UpdateProfileSetUniqueUsernameError {
     SendingCanisterDoesNotMatchUserCanisterId,
     UserCanisterEntryDoesNotExist,
     UserIndexCrossCanisterCallFailed,
+    UsernameReserved,
+    UsernameContainsProfanity,
 }
 
 #[derive(CandidType, Debug, Deserialize)]
@@ -15,3 +17,20 @@ pub enum GetUserUtilityTokenTransactionHistoryError {
     ReachedEndOfItemsList,
     ExceededMaxNumberOfItemsAllowedInOneRequest,
 }
+
+crate::error_codes! {
+    UpdateProfileSetUniqueUsernameError {
+        NotAuthorized => (4601, Unauthorized, false),
+        UsernameAlreadyTaken => (4602, Conflict, false),
+        SendingCanisterDoesNotMatchUserCanisterId => (4603, Unauthorized, false),
+        UserCanisterEntryDoesNotExist => (4604, NotFound, false),
+        UserIndexCrossCanisterCallFailed => (4605, Unavailable, true),
+        UsernameReserved => (4606, Conflict, false),
+        UsernameContainsProfanity => (4607, InvalidInput, false),
+    }
+    GetUserUtilityTokenTransactionHistoryError {
+        InvalidBoundsPassed => (4701, InvalidInput, false),
+        ReachedEndOfItemsList => (4702, NotFound, false),
+        ExceededMaxNumberOfItemsAllowedInOneRequest => (4703, LimitExceeded, false),
+    }
+}
@@ -6,3 +6,20 @@ pub enum TopPostsFetchError {
     ReachedEndOfItemsList,
     ExceededMaxNumberOfItemsAllowedInOneRequest,
 }
+
+#[derive(CandidType, Deserialize, Debug, PartialEq, Eq)]
+pub enum PostDetailsHydrationError {
+    PostNotFoundInPublisherOrBackup,
+}
+
+// * Post cache enums are numbered 70 to 79
+crate::error_codes! {
+    TopPostsFetchError {
+        InvalidBoundsPassed => (7001, InvalidInput, false),
+        ReachedEndOfItemsList => (7002, NotFound, false),
+        ExceededMaxNumberOfItemsAllowedInOneRequest => (7003, LimitExceeded, false),
+    }
+    PostDetailsHydrationError {
+        PostNotFoundInPublisherOrBackup => (7101, NotFound, false),
+    }
+}
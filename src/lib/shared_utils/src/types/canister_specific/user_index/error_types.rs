@@ -5,4 +5,67 @@ pub enum SetUniqueUsernameError {
     UsernameAlreadyTaken,
     SendingCanisterDoesNotMatchUserCanisterId,
     UserCanisterEntryDoesNotExist,
+    UsernameReserved,
+    UsernameContainsProfanity,
+}
+
+#[derive(CandidType, Deserialize, Debug, PartialEq, Eq)]
+pub enum RegisterReferralCodeError {
+    SendingCanisterDoesNotMatchUserCanisterId,
+    UserCanisterEntryDoesNotExist,
+}
+
+#[derive(CandidType, Deserialize, Debug, PartialEq, Eq)]
+pub enum TournamentError {
+    Unauthorized,
+    TournamentAlreadyOpen,
+    NoOpenTournament,
+    TournamentNotFound,
+    TournamentStillRunning,
+    TournamentAlreadyClosed,
+    UserCanisterEntryDoesNotExist,
+    PostNotEligible,
+    PostAlreadyEnrolled,
+    TournamentBeingTallied,
+}
+
+#[derive(CandidType, Deserialize, Debug, PartialEq, Eq)]
+pub enum UserIndexShardError {
+    Unauthorized,
+    ShardAlreadyRegistered,
+    ShardNotFound,
+    CannotRegisterThisCanister,
+}
+
+// * User index enums are numbered 50 to 69
+crate::error_codes! {
+    SetUniqueUsernameError {
+        UsernameAlreadyTaken => (5001, Conflict, false),
+        SendingCanisterDoesNotMatchUserCanisterId => (5002, Unauthorized, false),
+        UserCanisterEntryDoesNotExist => (5003, NotFound, false),
+        UsernameReserved => (5004, Conflict, false),
+        UsernameContainsProfanity => (5005, InvalidInput, false),
+    }
+    RegisterReferralCodeError {
+        SendingCanisterDoesNotMatchUserCanisterId => (5101, Unauthorized, false),
+        UserCanisterEntryDoesNotExist => (5102, NotFound, false),
+    }
+    TournamentError {
+        Unauthorized => (5201, Unauthorized, false),
+        TournamentAlreadyOpen => (5202, Conflict, false),
+        NoOpenTournament => (5203, Conflict, false),
+        TournamentNotFound => (5204, NotFound, false),
+        TournamentStillRunning => (5205, Conflict, true),
+        TournamentAlreadyClosed => (5206, Conflict, false),
+        UserCanisterEntryDoesNotExist => (5207, NotFound, false),
+        PostNotEligible => (5208, Conflict, false),
+        PostAlreadyEnrolled => (5209, Conflict, false),
+        TournamentBeingTallied => (5210, Conflict, true),
+    }
+    UserIndexShardError {
+        Unauthorized => (5501, Unauthorized, false),
+        ShardAlreadyRegistered => (5502, Conflict, false),
+        ShardNotFound => (5503, NotFound, false),
+        CannotRegisterThisCanister => (5504, InvalidInput, false),
+    }
 }
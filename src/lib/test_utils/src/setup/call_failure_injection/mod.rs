@@ -0,0 +1,90 @@
+//! Programs the outbound calls of a canister under test to fail or answer late. The canister
+//! has to be built with the `call_failure_injection` feature for this to work, e.g.
+//! `cargo build --target wasm32-unknown-unknown --release -p individual_user_template --features call_failure_injection`
+
+use candid::Principal;
+use ic_test_state_machine_client::{StateMachine, WasmResult};
+pub use shared_utils::common::utils::call_failure_injection::{
+    CallFailureInjectionConfig, CallFailureInjectionStatus,
+};
+
+use crate::setup::test_constants::get_global_super_admin_principal_id;
+
+pub fn configure_call_failure_injection(
+    state_machine: &StateMachine,
+    canister_id: Principal,
+    config: CallFailureInjectionConfig,
+) {
+    state_machine
+        .update_call(
+            canister_id,
+            get_global_super_admin_principal_id(),
+            "configure_call_failure_injection",
+            candid::encode_one(config).unwrap(),
+        )
+        .expect("🛑 Failed to configure call failure injection");
+}
+
+/// Fails the outbound calls numbered in `call_numbers`, counting from 1 from now on. Only
+/// calls to `method_name` are counted when it's given.
+pub fn fail_nth_outbound_calls(
+    state_machine: &StateMachine,
+    canister_id: Principal,
+    call_numbers: &[u64],
+    method_name: Option<&str>,
+) {
+    configure_call_failure_injection(
+        state_machine,
+        canister_id,
+        CallFailureInjectionConfig {
+            fail_outbound_calls_numbered: call_numbers.to_vec(),
+            method_name: method_name.map(str::to_string),
+            response_delay_in_rounds: 0,
+        },
+    );
+}
+
+/// Holds back the response to every outbound call, or only to calls to `method_name` when
+/// it's given, for `rounds` rounds. Tick the state machine to let them through.
+pub fn delay_outbound_call_responses(
+    state_machine: &StateMachine,
+    canister_id: Principal,
+    rounds: u32,
+    method_name: Option<&str>,
+) {
+    configure_call_failure_injection(
+        state_machine,
+        canister_id,
+        CallFailureInjectionConfig {
+            fail_outbound_calls_numbered: vec![],
+            method_name: method_name.map(str::to_string),
+            response_delay_in_rounds: rounds,
+        },
+    );
+}
+
+pub fn clear_call_failure_injection(state_machine: &StateMachine, canister_id: Principal) {
+    configure_call_failure_injection(
+        state_machine,
+        canister_id,
+        CallFailureInjectionConfig::default(),
+    );
+}
+
+pub fn get_call_failure_injection_status(
+    state_machine: &StateMachine,
+    canister_id: Principal,
+) -> CallFailureInjectionStatus {
+    match state_machine
+        .query_call(
+            canister_id,
+            Principal::anonymous(),
+            "get_call_failure_injection_status",
+            candid::encode_one(()).unwrap(),
+        )
+        .expect("🛑 Failed to get call failure injection status")
+    {
+        WasmResult::Reply(payload) => candid::decode_one(&payload).unwrap(),
+        _ => panic!("\n🛑 get_call_failure_injection_status failed\n"),
+    }
+}
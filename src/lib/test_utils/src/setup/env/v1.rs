@@ -43,7 +43,7 @@ pub fn get_new_state_machine() -> StateMachine {
             gzip -d ic-test-state-machine.gz
             chmod +x ic-test-state-machine
         where $commit can be read from `.ic-commit` and $platform is 'x86_64-linux' for Linux and 'x86_64-darwin' for Intel/rosetta-enabled Darwin.
-        ", &path, &env::current_dir().map(|x| x.display().to_string()).unwrap_or_else(|_| "an unknown directory".to_string()));
+        ", path, env::current_dir().map(|x| x.display().to_string()).unwrap_or_else(|_| "an unknown directory".to_string()));
     }
 
     StateMachine::new(&path, false)
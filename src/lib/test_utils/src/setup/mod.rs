@@ -1,2 +1,5 @@
+pub mod call_failure_injection;
 pub mod env;
+pub mod snapshot;
+pub mod spoofed_callers;
 pub mod test_constants;
@@ -0,0 +1,131 @@
+use std::{fs, path::Path};
+
+use candid::Principal;
+use ic_test_state_machine_client::{CallError, StateMachine, WasmResult};
+use serde_bytes::ByteBuf;
+
+use crate::setup::test_constants::get_global_super_admin_principal_id;
+
+/// A query whose reply is recorded before an upgrade and compared after it. Replies are
+/// compared byte for byte, so only queries whose reply types are the same in both wasms fit.
+#[derive(Clone, Debug)]
+pub struct QueryToCompare {
+    pub method: String,
+    pub arg: Vec<u8>,
+    pub sender: Principal,
+}
+
+impl QueryToCompare {
+    pub fn new(method: &str, arg: Vec<u8>) -> Self {
+        Self {
+            method: method.to_string(),
+            arg,
+            sender: Principal::anonymous(),
+        }
+    }
+
+    pub fn with_sender(mut self, sender: Principal) -> Self {
+        self.sender = sender;
+        self
+    }
+}
+
+pub struct CanisterSnapshot {
+    pub canister_id: Principal,
+    pub stable_memory: Vec<u8>,
+    pub query_replies: Vec<(QueryToCompare, WasmResult)>,
+}
+
+impl CanisterSnapshot {
+    /// Persist the stable memory so that a failing upgrade can be reproduced later
+    pub fn save_stable_memory_to_file(&self, path: &Path) {
+        fs::write(path, &self.stable_memory)
+            .unwrap_or_else(|_| panic!("Failed to write snapshot to {}", path.display()));
+    }
+}
+
+/// Captures a canister's state after a scripted scenario has run against it.
+///
+/// The canister is first upgraded to the wasm it is already running so that
+/// `pre_upgrade` flushes heap state to stable memory; the stable memory is read
+/// only after that, and the query replies are recorded from the restored state.
+pub fn capture_canister_snapshot(
+    state_machine: &StateMachine,
+    canister_id: Principal,
+    currently_installed_wasm: Vec<u8>,
+    upgrade_arg: Vec<u8>,
+    queries_to_compare: &[QueryToCompare],
+) -> CanisterSnapshot {
+    state_machine
+        .upgrade_canister(
+            canister_id,
+            currently_installed_wasm,
+            upgrade_arg,
+            Some(get_global_super_admin_principal_id()),
+        )
+        .expect("🛑 Self upgrade to flush state to stable memory failed");
+
+    CanisterSnapshot {
+        canister_id,
+        stable_memory: state_machine.stable_memory(canister_id),
+        query_replies: queries_to_compare
+            .iter()
+            .map(|query| (query.clone(), run_query(state_machine, canister_id, query)))
+            .collect(),
+    }
+}
+
+/// Upgrades the snapshotted canister to `newer_wasm` and asserts that
+/// `post_upgrade` succeeds and every recorded query returns an identical reply.
+pub fn upgrade_and_assert_snapshot_is_preserved(
+    state_machine: &StateMachine,
+    snapshot: &CanisterSnapshot,
+    newer_wasm: Vec<u8>,
+    upgrade_arg: Vec<u8>,
+) {
+    let upgrade_result: Result<(), CallError> = state_machine.upgrade_canister(
+        snapshot.canister_id,
+        newer_wasm,
+        upgrade_arg,
+        Some(get_global_super_admin_principal_id()),
+    );
+
+    if let Err(error) = upgrade_result {
+        panic!(
+            "🛑 post_upgrade failed when replaying snapshot of {}: {:?}",
+            snapshot.canister_id, error
+        );
+    }
+
+    for (query, reply_before_upgrade) in snapshot.query_replies.iter() {
+        let reply_after_upgrade = run_query(state_machine, snapshot.canister_id, query);
+
+        assert_eq!(
+            &reply_after_upgrade, reply_before_upgrade,
+            "🛑 Reply to {} changed across the upgrade",
+            query.method
+        );
+    }
+}
+
+/// Overwrites a canister's stable memory with a snapshot saved to disk earlier
+pub fn restore_stable_memory_from_file(
+    state_machine: &StateMachine,
+    canister_id: Principal,
+    path: &Path,
+) {
+    let stable_memory = fs::read(path)
+        .unwrap_or_else(|_| panic!("Failed to read snapshot from {}", path.display()));
+
+    state_machine.set_stable_memory(canister_id, ByteBuf::from(stable_memory));
+}
+
+fn run_query(
+    state_machine: &StateMachine,
+    canister_id: Principal,
+    query: &QueryToCompare,
+) -> WasmResult {
+    state_machine
+        .query_call(canister_id, query.sender, &query.method, query.arg.clone())
+        .unwrap_or_else(|error| panic!("🛑 Query {} failed: {:?}", query.method, error))
+}
@@ -0,0 +1,117 @@
+use candid::Principal;
+use shared_utils::common::types::known_principal::{KnownPrincipalMap, KnownPrincipalType};
+
+use crate::setup::test_constants::{
+    get_global_super_admin_principal_id, get_mock_canister_id_configuration,
+    get_mock_canister_id_data_backup, get_mock_canister_id_post_cache, get_mock_canister_id_root,
+    get_mock_canister_id_sns, get_mock_canister_id_topic_cache, get_mock_canister_id_user_index,
+    get_mock_user_alice_canister_id, get_mock_user_alice_principal_id,
+    v2::{canister, name_of, user},
+};
+
+/// Every known principal type filled in with a mock id, as a fully configured canister
+/// would have them
+pub fn get_mock_known_principal_map() -> KnownPrincipalMap {
+    KnownPrincipalMap::from([
+        (
+            KnownPrincipalType::UserIdGlobalSuperAdmin,
+            get_global_super_admin_principal_id(),
+        ),
+        (
+            KnownPrincipalType::CanisterIdConfiguration,
+            get_mock_canister_id_configuration(),
+        ),
+        (
+            KnownPrincipalType::CanisterIdDataBackup,
+            get_mock_canister_id_data_backup(),
+        ),
+        (
+            KnownPrincipalType::CanisterIdPostCache,
+            get_mock_canister_id_post_cache(),
+        ),
+        (
+            KnownPrincipalType::CanisterIdProjectMemberIndex,
+            canister("project_member_index"),
+        ),
+        (
+            KnownPrincipalType::CanisterIdRootCanister,
+            get_mock_canister_id_root(),
+        ),
+        (
+            KnownPrincipalType::CanisterIdSNSController,
+            get_mock_canister_id_sns(),
+        ),
+        (
+            KnownPrincipalType::CanisterIdTokenLedger,
+            canister("token_ledger"),
+        ),
+        (
+            KnownPrincipalType::CanisterIdTopicCacheIndex,
+            get_mock_canister_id_topic_cache(),
+        ),
+        (KnownPrincipalType::CanisterIdTreasury, canister("treasury")),
+        (
+            KnownPrincipalType::CanisterIdUserIndex,
+            get_mock_canister_id_user_index(),
+        ),
+        (
+            KnownPrincipalType::CanisterIdWebSocketGateway,
+            canister("web_socket_gateway"),
+        ),
+    ])
+}
+
+/// Callers that pretend to be platform canisters without being one of `allowed_callers`:
+/// anonymous, users, individual user canisters, and every other known principal
+pub fn get_spoofed_callers(
+    known_principal_ids: &KnownPrincipalMap,
+    allowed_callers: &[KnownPrincipalType],
+) -> Vec<Principal> {
+    let allowed_principal_ids: Vec<Principal> = allowed_callers
+        .iter()
+        .flat_map(|known_principal_type| known_principal_ids.get_all(known_principal_type))
+        .copied()
+        .collect();
+
+    [
+        Principal::anonymous(),
+        Principal::management_canister(),
+        get_mock_user_alice_principal_id(),
+        get_mock_user_alice_canister_id(),
+        user("spoofer"),
+        canister("spoofer"),
+    ]
+    .into_iter()
+    .chain(known_principal_ids.values().copied())
+    .filter(|principal_id| !allowed_principal_ids.contains(principal_id))
+    .collect()
+}
+
+/// Runs an endpoint's access check with every spoofed caller and with every allowed one,
+/// naming the caller on failure. `is_accepted` should report whether the call went through.
+pub fn assert_only_allowed_callers_are_accepted(
+    known_principal_ids: &KnownPrincipalMap,
+    allowed_callers: &[KnownPrincipalType],
+    is_accepted: impl Fn(&Principal) -> bool,
+) {
+    get_spoofed_callers(known_principal_ids, allowed_callers)
+        .iter()
+        .for_each(|spoofed_caller| {
+            assert!(
+                !is_accepted(spoofed_caller),
+                "call from {} was accepted",
+                name_of(spoofed_caller)
+            );
+        });
+
+    allowed_callers
+        .iter()
+        .flat_map(|known_principal_type| known_principal_ids.get_all(known_principal_type))
+        .for_each(|allowed_caller| {
+            assert!(
+                is_accepted(allowed_caller),
+                "call from {} was turned away",
+                name_of(allowed_caller)
+            );
+        });
+}
@@ -4,6 +4,7 @@ use shared_utils::common::types::known_principal::KnownPrincipalType;
 use std::{fs::File, io::Read, path::PathBuf};
 
 pub mod v1;
+pub mod v2;
 
 pub fn get_global_super_admin_principal_id() -> Principal {
     Principal::self_authenticating([0])
@@ -111,6 +112,41 @@ pub fn get_post_cache_canister_wasm() -> Vec<u8> {
     bytes
 }
 
+/// The individual user canister wasm of the release currently deployed, built from `main` into
+/// `target/wasm32-unknown-unknown/previous_release` by
+/// `scripts/canisters/local_deploy/build_previous_release_individual_user_template.sh`.
+/// Upgrade tests install it to check that the current wasm reads state written by the
+/// previous release. None if it hasn't been built.
+pub fn get_previous_release_individual_user_template_wasm() -> Option<Vec<u8>> {
+    let mut file_path = PathBuf::from(
+        std::env::var("CARGO_MANIFEST_DIR")
+            .expect("Failed to read CARGO_MANIFEST_DIR env variable"),
+    );
+    file_path.push(
+        "../../../target/wasm32-unknown-unknown/previous_release/individual_user_template.wasm",
+    );
+
+    let mut file = File::open(&file_path).ok()?;
+    let mut bytes = Vec::new();
+    file.read_to_end(&mut bytes).expect("Failed to read file");
+    Some(bytes)
+}
+
+pub fn get_current_individual_user_template_wasm() -> Vec<u8> {
+    let mut file_path = PathBuf::from(
+        std::env::var("CARGO_MANIFEST_DIR")
+            .expect("Failed to read CARGO_MANIFEST_DIR env variable"),
+    );
+    file_path
+        .push("../../../target/wasm32-unknown-unknown/release/individual_user_template.wasm.gz");
+
+    let mut file = File::open(&file_path)
+        .unwrap_or_else(|_| panic!("Failed to open file: {}", file_path.to_str().unwrap()));
+    let mut bytes = Vec::new();
+    file.read_to_end(&mut bytes).expect("Failed to read file");
+    bytes
+}
+
 pub fn get_canister_wasm(canister_type: KnownPrincipalType) -> Vec<u8> {
     let mut bytes = Vec::new();
 
@@ -0,0 +1,152 @@
+use std::{collections::BTreeMap, sync::Mutex};
+
+use candid::Principal;
+use sha2::{Digest, Sha256};
+
+use super::{
+    get_global_super_admin_principal_id, get_mock_canister_id_configuration,
+    get_mock_canister_id_data_backup, get_mock_canister_id_post_cache, get_mock_canister_id_root,
+    get_mock_canister_id_sns, get_mock_canister_id_topic_cache, get_mock_canister_id_user_index,
+    get_mock_user_alice_canister_id, get_mock_user_alice_principal_id,
+    get_mock_user_bob_canister_id, get_mock_user_bob_principal_id,
+    get_mock_user_charlie_canister_id, get_mock_user_charlie_principal_id,
+    get_mock_user_dan_canister_id, get_mock_user_dan_principal_id,
+};
+
+// * Every principal handed out by the factories below, so that failures can name them
+static PRINCIPAL_NAMES: Mutex<BTreeMap<Principal, String>> = Mutex::new(BTreeMap::new());
+
+/// Self authenticating principal for the user with this label, e.g. `user("bettor-17")`.
+/// The same label always gives the same principal, in any test and in any order.
+pub fn user(label: &str) -> Principal {
+    let principal_id = Principal::self_authenticating(labeled_bytes("user", label));
+    register_name(principal_id, format!("user({})", label));
+    principal_id
+}
+
+/// Canister id for the canister with this label, e.g. `canister("post-3")`. Shaped like the
+/// ids the IC hands out, which keeps them clear of the fixed mock canister ids.
+pub fn canister(label: &str) -> Principal {
+    let mut canister_id_bytes = labeled_bytes("canister", label)[..8].to_vec();
+    canister_id_bytes.extend([0x01, 0x01]);
+
+    let canister_id = Principal::from_slice(&canister_id_bytes);
+    register_name(canister_id, format!("canister({})", label));
+    canister_id
+}
+
+/// `count` users labeled `<prefix>-0`, `<prefix>-1` and so on
+pub fn users(prefix: &str, count: usize) -> Vec<Principal> {
+    (0..count)
+        .map(|index| user(&format!("{}-{}", prefix, index)))
+        .collect()
+}
+
+/// `count` canisters labeled `<prefix>-0`, `<prefix>-1` and so on
+pub fn canisters(prefix: &str, count: usize) -> Vec<Principal> {
+    (0..count)
+        .map(|index| canister(&format!("{}-{}", prefix, index)))
+        .collect()
+}
+
+/// Human readable name for a principal, to use in assertion messages. Falls back to the
+/// principal's text for anything that didn't come from a factory or the v1 fixtures.
+pub fn name_of(principal_id: &Principal) -> String {
+    if let Some(name) = get_v1_fixture_name(principal_id) {
+        return name.to_string();
+    }
+
+    PRINCIPAL_NAMES
+        .lock()
+        .unwrap()
+        .get(principal_id)
+        .cloned()
+        .unwrap_or_else(|| principal_id.to_text())
+}
+
+pub fn names_of<'a>(principal_ids: impl IntoIterator<Item = &'a Principal>) -> Vec<String> {
+    principal_ids.into_iter().map(name_of).collect()
+}
+
+fn labeled_bytes(kind: &str, label: &str) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(b"hot-or-not-test-principal");
+    hasher.update([kind.len() as u8]);
+    hasher.update(kind);
+    hasher.update(label);
+    hasher.finalize().into()
+}
+
+fn register_name(principal_id: Principal, name: String) {
+    let mut principal_names = PRINCIPAL_NAMES.lock().unwrap();
+
+    if let Some(existing_name) = principal_names.get(&principal_id) {
+        assert_eq!(
+            *existing_name, name,
+            "{} and {} map to the same principal",
+            existing_name, name
+        );
+        return;
+    }
+
+    principal_names.insert(principal_id, name);
+}
+
+fn get_v1_fixture_name(principal_id: &Principal) -> Option<&'static str> {
+    [
+        (get_global_super_admin_principal_id(), "global_super_admin"),
+        (get_mock_user_alice_principal_id(), "alice"),
+        (get_mock_user_bob_principal_id(), "bob"),
+        (get_mock_user_charlie_principal_id(), "charlie"),
+        (get_mock_user_dan_principal_id(), "dan"),
+        (get_mock_canister_id_post_cache(), "canister(post_cache)"),
+        (get_mock_canister_id_root(), "canister(root)"),
+        (get_mock_canister_id_sns(), "canister(sns)"),
+        (get_mock_canister_id_topic_cache(), "canister(topic_cache)"),
+        (get_mock_canister_id_user_index(), "canister(user_index)"),
+        (
+            get_mock_canister_id_configuration(),
+            "canister(configuration)",
+        ),
+        (get_mock_canister_id_data_backup(), "canister(data_backup)"),
+        (get_mock_user_alice_canister_id(), "canister(alice)"),
+        (get_mock_user_bob_canister_id(), "canister(bob)"),
+        (get_mock_user_charlie_canister_id(), "canister(charlie)"),
+        (get_mock_user_dan_canister_id(), "canister(dan)"),
+    ]
+    .into_iter()
+    .find(|(fixture_principal_id, _name)| fixture_principal_id == principal_id)
+    .map(|(_fixture_principal_id, name)| name)
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::BTreeSet;
+
+    use super::*;
+
+    #[test]
+    fn test_labeled_principals() {
+        assert_eq!(user("bettor-17"), user("bettor-17"));
+        assert_ne!(user("bettor-17"), user("bettor-18"));
+        assert_ne!(user("post-3"), canister("post-3"));
+        assert_eq!(name_of(&user("bettor-17")), "user(bettor-17)");
+        assert_eq!(name_of(&canister("post-3")), "canister(post-3)");
+        assert_eq!(name_of(&get_mock_user_alice_principal_id()), "alice");
+        assert_eq!(
+            name_of(&Principal::anonymous()),
+            Principal::anonymous().to_text()
+        );
+
+        let all_principal_ids: BTreeSet<Principal> = users("bettor", 500)
+            .into_iter()
+            .chain(canisters("post", 500))
+            .collect();
+        assert_eq!(all_principal_ids.len(), 1000);
+        assert!(get_v1_fixture_name(&canister("alice")).is_none());
+        assert_eq!(
+            names_of(&users("bettor", 2)),
+            vec!["user(bettor-0)", "user(bettor-1)"]
+        );
+    }
+}